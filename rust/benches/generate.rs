@@ -0,0 +1,70 @@
+//! Ring generation on the default draperie config
+//!
+//! `direct_trig` traces the same rings with a `cos`/`sin` call per point,
+//! as generation did before the rings shared one table of angles; compare
+//! it with `layer` to see what the table saves.
+//!
+//! Run with `cargo bench --bench generate`.
+
+use std::f64::consts::PI;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use turtles::rose_engine::{RoseEngineLatheRun, RosettePattern};
+use turtles::{peak_alignment_phase, DraperieConfig, DraperieLayer, Point2D, RadialWave};
+
+fn direct_trig_rings(config: &DraperieConfig) -> Vec<Vec<Point2D>> {
+    let (frequency, revolutions) = config.ring_frequency();
+    let amplitude = config.effective_amplitude();
+    let resolution = config.resolution.get();
+    let n = config.num_rings;
+    (0..n)
+        .map(|i| {
+            let offset = (i as f64) - ((n as f64 - 1.0) / 2.0);
+            let wave = RadialWave::new(
+                config.base_radius + offset * config.radius_step,
+                amplitude,
+                RosettePattern::Sinusoidal { frequency },
+            )
+            .with_phase(peak_alignment_phase(frequency))
+            .with_wave_exponent(config.wave_exponent);
+            (0..=resolution * revolutions)
+                .map(|j| {
+                    let theta = 2.0 * PI * (j as f64) / (resolution as f64);
+                    let r = wave.radius_at(theta);
+                    Point2D::new(r * theta.cos(), r * theta.sin())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn draperie(c: &mut Criterion) {
+    let config = DraperieConfig::default();
+    let mut group = c.benchmark_group("draperie_default");
+
+    group.bench_function("direct_trig", |b| {
+        b.iter(|| direct_trig_rings(black_box(&config)))
+    });
+
+    let mut layer = DraperieLayer::new(config.clone()).unwrap();
+    group.bench_function("layer", |b| {
+        b.iter(|| {
+            layer.generate();
+            black_box(layer.lines().len())
+        })
+    });
+
+    let mut run = RoseEngineLatheRun::from_draperie_config(&config, 0.0, 0.0).unwrap();
+    group.bench_function("lathe_run", |b| {
+        b.iter(|| {
+            run.generate();
+            black_box(run.lines().len())
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, draperie);
+criterion_main!(benches);
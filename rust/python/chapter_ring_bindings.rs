@@ -0,0 +1,230 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use turtles::{
+    ChapterMotif as BaseChapterMotif,
+    ChapterRingConfig as BaseChapterRingConfig,
+    ChapterRingLayer as BaseChapterRingLayer,
+    ExportConfig as BaseExportConfig,
+    PatternLayer,
+};
+
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+/// Parse a motif name and its parameters
+///
+/// `motif` is "ticks", "wave" (`amplitude`, `frequency` waves per repeat),
+/// "rope" or "dots" (`diameter`).
+pub(crate) fn chapter_motif(
+    motif: &str,
+    amplitude: f64,
+    frequency: usize,
+    diameter: f64,
+) -> PyResult<BaseChapterMotif> {
+    match motif.to_lowercase().as_str() {
+        "ticks" => Ok(BaseChapterMotif::Ticks),
+        "wave" => Ok(BaseChapterMotif::Wave { amplitude, frequency }),
+        "rope" => Ok(BaseChapterMotif::Rope),
+        "dots" => Ok(BaseChapterMotif::Dots { diameter }),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(
+            "motif must be 'ticks', 'wave', 'rope' or 'dots'",
+        )),
+    }
+}
+
+/// Build a chapter ring configuration from Python arguments
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn chapter_ring_config(
+    r_inner: f64,
+    r_outer: f64,
+    minutes: usize,
+    major_every: usize,
+    major_extra_width: f64,
+    index_cutouts: Option<Vec<(u32, f64, f64)>>,
+    resolution: usize,
+    motif: &str,
+    amplitude: f64,
+    frequency: usize,
+    diameter: f64,
+) -> PyResult<BaseChapterRingConfig> {
+    Ok(BaseChapterRingConfig {
+        r_inner,
+        r_outer,
+        minutes,
+        motif: chapter_motif(motif, amplitude, frequency, diameter)?,
+        major_every,
+        major_extra_width,
+        index_cutouts: index_cutouts.unwrap_or_default(),
+        resolution: resolution.into(),
+    })
+}
+
+/// Python wrapper for ChapterRingLayer - railroad minute track with
+/// bolder major divisions, or a wave, rope or dot border motif, and
+/// cutouts for applied indices
+#[pyclass]
+pub struct ChapterRingLayer {
+    pub inner: BaseChapterRingLayer,
+}
+
+#[pymethods]
+impl ChapterRingLayer {
+    /// Create a new chapter ring centered on the dial
+    ///
+    /// # Arguments
+    /// * `r_inner` - Radius of the inner track circle
+    /// * `r_outer` - Radius of the outer track circle
+    /// * `minutes` - Number of divisions or motif repeats (default: 60)
+    /// * `major_every` - Every this many divisions is a major tick (default: 5)
+    /// * `major_extra_width` - Width of the major tick bars (default: 0.15)
+    /// * `index_cutouts` - List of (hour, width, height) applied-index cutouts
+    /// * `resolution` - Number of points per track circle (default: 720)
+    /// * `motif` - "ticks", "wave", "rope" or "dots" (default: "ticks")
+    /// * `amplitude` - Wave crest distance from the mid-radius (default: 0.5)
+    /// * `frequency` - Waves per repeat (default: 1)
+    /// * `diameter` - Dot diameter (default: 0.5)
+    #[new]
+    #[pyo3(signature = (r_inner, r_outer, minutes=60, major_every=5, major_extra_width=0.15, index_cutouts=None, resolution=720, motif="ticks", amplitude=0.5, frequency=1, diameter=0.5))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        r_inner: f64,
+        r_outer: f64,
+        minutes: usize,
+        major_every: usize,
+        major_extra_width: f64,
+        index_cutouts: Option<Vec<(u32, f64, f64)>>,
+        resolution: usize,
+        motif: &str,
+        amplitude: f64,
+        frequency: usize,
+        diameter: f64,
+    ) -> PyResult<Self> {
+        let config = chapter_ring_config(
+            r_inner,
+            r_outer,
+            minutes,
+            major_every,
+            major_extra_width,
+            index_cutouts,
+            resolution,
+            motif,
+            amplitude,
+            frequency,
+            diameter,
+        )?;
+        BaseChapterRingLayer::new(config)
+            .map(|inner| ChapterRingLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Generate the track circles, ticks or motif, and cutouts
+    fn generate(&mut self) {
+        self.inner.generate();
+    }
+
+    /// Export the chapter ring to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner
+            .to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get the inner track radius
+    #[getter]
+    fn r_inner(&self) -> f64 {
+        self.inner.config.r_inner
+    }
+
+    /// Get the outer track radius
+    #[getter]
+    fn r_outer(&self) -> f64 {
+        self.inner.config.r_outer
+    }
+
+    /// Get the motif name: "ticks", "wave", "rope" or "dots"
+    #[getter]
+    fn motif(&self) -> &'static str {
+        match self.inner.config.motif {
+            BaseChapterMotif::Ticks => "ticks",
+            BaseChapterMotif::Wave { .. } => "wave",
+            BaseChapterMotif::Rope => "rope",
+            BaseChapterMotif::Dots { .. } => "dots",
+        }
+    }
+
+    /// Get the ticks as (points, is_major) pairs, clockwise from 12 o'clock;
+    /// empty for other motifs
+    fn get_ticks(&self) -> Vec<(Vec<(f64, f64)>, bool)> {
+        self.inner
+            .ticks()
+            .into_iter()
+            .map(|(tick, major)| (tick.iter().map(|p| (p.x, p.y)).collect(), major))
+            .collect()
+    }
+
+    /// Get the index cutout rectangles as lists of four (x, y) corners
+    fn get_cutouts(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .cutouts()
+            .iter()
+            .map(|rect| rect.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Get the generated lines as a list of point lists
+    /// Each line is a list of (x, y) tuples
+    fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ChapterRingLayer(r_inner={}, r_outer={}, minutes={}, motif={}, cutouts={})",
+            self.inner.config.r_inner,
+            self.inner.config.r_outer,
+            self.inner.config.minutes,
+            self.motif(),
+            self.inner.config.index_cutouts.len()
+        )
+    }
+}
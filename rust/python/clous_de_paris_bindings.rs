@@ -1,9 +1,14 @@
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use turtles::{
     ClousDeParisConfig as BaseClousDeParisConfig,
     ClousDeParisLayer as BaseClousDeParisLayer,
+    ExportConfig as BaseExportConfig,
+    PatternLayer,
 };
 
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
 /// Python wrapper for ClousDeParisLayer - creates hobnail grid guilloché patterns
 /// using two perpendicular sets of parallel lines clipped to a circle
 #[pyclass]
@@ -20,19 +25,22 @@ impl ClousDeParisLayer {
     /// * `radius` - Radius of the circular clipping region in mm
     /// * `angle` - Rotation angle of the grid in radians (default π/4 = 45°)
     /// * `resolution` - Number of sample points per line
+    /// * `emit_cells` - Generate closed hobnail cell outlines instead of grooves
     #[new]
-    #[pyo3(signature = (spacing=1.0, radius=22.0, angle=std::f64::consts::FRAC_PI_4, resolution=200))]
+    #[pyo3(signature = (spacing=1.0, radius=22.0, angle=std::f64::consts::FRAC_PI_4, resolution=200, emit_cells=false))]
     pub fn new(
         spacing: f64,
         radius: f64,
         angle: f64,
         resolution: usize,
+        emit_cells: bool,
     ) -> PyResult<Self> {
         let config = BaseClousDeParisConfig {
             spacing,
             radius,
             angle,
-            resolution,
+            resolution: resolution.into(),
+            emit_cells,
         };
         BaseClousDeParisLayer::new(config)
             .map(|inner| ClousDeParisLayer { inner })
@@ -41,7 +49,7 @@ impl ClousDeParisLayer {
 
     /// Create a clous de Paris layer with a custom center point
     #[staticmethod]
-    #[pyo3(signature = (center_x, center_y, spacing=1.0, radius=22.0, angle=std::f64::consts::FRAC_PI_4, resolution=200))]
+    #[pyo3(signature = (center_x, center_y, spacing=1.0, radius=22.0, angle=std::f64::consts::FRAC_PI_4, resolution=200, emit_cells=false))]
     fn with_center(
         center_x: f64,
         center_y: f64,
@@ -49,12 +57,14 @@ impl ClousDeParisLayer {
         radius: f64,
         angle: f64,
         resolution: usize,
+        emit_cells: bool,
     ) -> PyResult<Self> {
         let config = BaseClousDeParisConfig {
             spacing,
             radius,
             angle,
-            resolution,
+            resolution: resolution.into(),
+            emit_cells,
         };
         BaseClousDeParisLayer::new_with_center(config, center_x, center_y)
             .map(|inner| ClousDeParisLayer { inner })
@@ -63,7 +73,7 @@ impl ClousDeParisLayer {
 
     /// Create a clous de Paris layer positioned at a given angle and distance from origin
     #[staticmethod]
-    #[pyo3(signature = (angle, distance, spacing=1.0, radius=22.0, grid_angle=std::f64::consts::FRAC_PI_4, resolution=200))]
+    #[pyo3(signature = (angle, distance, spacing=1.0, radius=22.0, grid_angle=std::f64::consts::FRAC_PI_4, resolution=200, emit_cells=false))]
     fn at_polar(
         angle: f64,
         distance: f64,
@@ -71,12 +81,14 @@ impl ClousDeParisLayer {
         radius: f64,
         grid_angle: f64,
         resolution: usize,
+        emit_cells: bool,
     ) -> PyResult<Self> {
         let config = BaseClousDeParisConfig {
             spacing,
             radius,
             angle: grid_angle,
-            resolution,
+            resolution: resolution.into(),
+            emit_cells,
         };
         BaseClousDeParisLayer::new_at_polar(config, angle, distance)
             .map(|inner| ClousDeParisLayer { inner })
@@ -90,7 +102,8 @@ impl ClousDeParisLayer {
     /// * `minute` - Minute position (0-59)
     /// * `distance` - Distance from center of watch face to the layer center
     #[staticmethod]
-    #[pyo3(signature = (hour, minute, distance, spacing=1.0, radius=22.0, angle=std::f64::consts::FRAC_PI_4, resolution=200))]
+    #[pyo3(signature = (hour, minute, distance, spacing=1.0, radius=22.0, angle=std::f64::consts::FRAC_PI_4, resolution=200, emit_cells=false))]
+    #[allow(clippy::too_many_arguments)]
     fn at_clock(
         hour: u32,
         minute: u32,
@@ -99,12 +112,14 @@ impl ClousDeParisLayer {
         radius: f64,
         angle: f64,
         resolution: usize,
+        emit_cells: bool,
     ) -> PyResult<Self> {
         let config = BaseClousDeParisConfig {
             spacing,
             radius,
             angle,
-            resolution,
+            resolution: resolution.into(),
+            emit_cells,
         };
         BaseClousDeParisLayer::new_at_clock(config, hour, minute, distance)
             .map(|inner| ClousDeParisLayer { inner })
@@ -116,10 +131,23 @@ impl ClousDeParisLayer {
         self.inner.generate();
     }
 
-    /// Export the pattern to SVG format
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
+    /// Export the pattern to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
         self.inner
-            .to_svg(filename)
+            .to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Export the pattern to DXF as LWPOLYLINE entities in mm
+    fn to_dxf(&self, filename: &str) -> PyResult<()> {
+        self.inner
+            .to_dxf(filename)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
@@ -132,6 +160,73 @@ impl ClousDeParisLayer {
             .collect()
     }
 
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Centers of the whole hobnail cells as a list of (x, y) tuples
+    fn cell_centers(&self) -> Vec<(f64, f64)> {
+        self.inner
+            .cell_centers()
+            .iter()
+            .map(|p| (p.x, p.y))
+            .collect()
+    }
+
+    /// Corner quads of the cells, in `cell_centers` order, as lists of
+    /// four (x, y) tuples
+    fn cell_polygons(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .cell_polygons()
+            .iter()
+            .map(|quad| quad.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Every hobnail cell as a closed list of (x, y) tuples: the whole
+    /// cells in `cell_polygons` order, then the edge cells clipped to the
+    /// circle
+    fn cells(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .cells()
+            .iter()
+            .map(|cell| cell.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Whether `generate` emits cell outlines instead of grooves
+    #[getter]
+    fn emit_cells(&self) -> bool {
+        self.inner.config.emit_cells
+    }
+
     /// Get the spacing between grooves
     #[getter]
     fn spacing(&self) -> f64 {
@@ -153,7 +248,7 @@ impl ClousDeParisLayer {
     /// Get the resolution
     #[getter]
     fn resolution(&self) -> usize {
-        self.inner.config.resolution
+        self.inner.config.resolution.get()
     }
 
     /// Get the center x coordinate
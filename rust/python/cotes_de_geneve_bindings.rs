@@ -0,0 +1,264 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use turtles::{
+    CotesDeGeneveConfig as BaseCotesDeGeneveConfig,
+    CotesDeGeneveLayer as BaseCotesDeGeneveLayer,
+    ExportConfig as BaseExportConfig,
+    PatternLayer,
+};
+
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+/// Build a CotesDeGeneveConfig from Python arguments
+#[allow(clippy::too_many_arguments)]
+pub fn cotes_de_geneve_config(
+    stripe_width: f64,
+    num_stripes: Option<usize>,
+    arc_sagitta: f64,
+    angle: f64,
+    radius: f64,
+    inner_radius: f64,
+    resolution: usize,
+    lines_per_stripe: usize,
+) -> BaseCotesDeGeneveConfig {
+    BaseCotesDeGeneveConfig {
+        stripe_width,
+        num_stripes_override: num_stripes,
+        arc_sagitta,
+        angle,
+        radius,
+        inner_radius,
+        resolution: resolution.into(),
+        lines_per_stripe,
+    }
+}
+
+/// Python wrapper for CotesDeGeneveLayer - Geneva stripes of gently arced
+/// parallel lines filling a circle or annulus
+#[pyclass]
+pub struct CotesDeGeneveLayer {
+    pub inner: BaseCotesDeGeneveLayer,
+}
+
+#[pymethods]
+impl CotesDeGeneveLayer {
+    /// Create a new Côtes de Genève layer centered at origin
+    ///
+    /// # Arguments
+    /// * `stripe_width` - Width of each stripe in mm
+    /// * `num_stripes` - Number of stripes, overriding `stripe_width` (None = from the width)
+    /// * `arc_sagitta` - How far each line bows over the full diameter in mm (0 = straight)
+    /// * `angle` - Direction the stripes run in radians
+    /// * `radius` - Radius of the circular clipping region in mm
+    /// * `inner_radius` - Radius of the bare center in mm (0 = full circle)
+    /// * `resolution` - Steps along each line
+    /// * `lines_per_stripe` - Lines in each stripe between its boundary lines
+    #[new]
+    #[pyo3(signature = (stripe_width=2.5, num_stripes=None, arc_sagitta=0.5, angle=0.0, radius=15.0, inner_radius=0.0, resolution=100, lines_per_stripe=6))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        stripe_width: f64,
+        num_stripes: Option<usize>,
+        arc_sagitta: f64,
+        angle: f64,
+        radius: f64,
+        inner_radius: f64,
+        resolution: usize,
+        lines_per_stripe: usize,
+    ) -> PyResult<Self> {
+        let config = cotes_de_geneve_config(stripe_width, num_stripes, arc_sagitta, angle, radius, inner_radius, resolution, lines_per_stripe);
+        BaseCotesDeGeneveLayer::new(config)
+            .map(|inner| CotesDeGeneveLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a Côtes de Genève layer with a custom center point
+    #[staticmethod]
+    #[pyo3(signature = (center_x, center_y, stripe_width=2.5, num_stripes=None, arc_sagitta=0.5, angle=0.0, radius=15.0, inner_radius=0.0, resolution=100, lines_per_stripe=6))]
+    #[allow(clippy::too_many_arguments)]
+    fn with_center(
+        center_x: f64,
+        center_y: f64,
+        stripe_width: f64,
+        num_stripes: Option<usize>,
+        arc_sagitta: f64,
+        angle: f64,
+        radius: f64,
+        inner_radius: f64,
+        resolution: usize,
+        lines_per_stripe: usize,
+    ) -> PyResult<Self> {
+        let config = cotes_de_geneve_config(stripe_width, num_stripes, arc_sagitta, angle, radius, inner_radius, resolution, lines_per_stripe);
+        BaseCotesDeGeneveLayer::new_with_center(config, center_x, center_y)
+            .map(|inner| CotesDeGeneveLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a Côtes de Genève layer positioned at a given angle and distance from origin
+    #[staticmethod]
+    #[pyo3(signature = (angle, distance, stripe_width=2.5, num_stripes=None, arc_sagitta=0.5, stripe_angle=0.0, radius=15.0, inner_radius=0.0, resolution=100, lines_per_stripe=6))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_polar(
+        angle: f64,
+        distance: f64,
+        stripe_width: f64,
+        num_stripes: Option<usize>,
+        arc_sagitta: f64,
+        stripe_angle: f64,
+        radius: f64,
+        inner_radius: f64,
+        resolution: usize,
+        lines_per_stripe: usize,
+    ) -> PyResult<Self> {
+        let config = cotes_de_geneve_config(stripe_width, num_stripes, arc_sagitta, stripe_angle, radius, inner_radius, resolution, lines_per_stripe);
+        BaseCotesDeGeneveLayer::new_at_polar(config, angle, distance)
+            .map(|inner| CotesDeGeneveLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a Côtes de Genève layer positioned at a clock position (like hour hand)
+    ///
+    /// # Arguments
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face to the layer center
+    #[staticmethod]
+    #[pyo3(signature = (hour, minute, distance, stripe_width=2.5, num_stripes=None, arc_sagitta=0.5, angle=0.0, radius=15.0, inner_radius=0.0, resolution=100, lines_per_stripe=6))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_clock(
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        stripe_width: f64,
+        num_stripes: Option<usize>,
+        arc_sagitta: f64,
+        angle: f64,
+        radius: f64,
+        inner_radius: f64,
+        resolution: usize,
+        lines_per_stripe: usize,
+    ) -> PyResult<Self> {
+        let config = cotes_de_geneve_config(stripe_width, num_stripes, arc_sagitta, angle, radius, inner_radius, resolution, lines_per_stripe);
+        BaseCotesDeGeneveLayer::new_at_clock(config, hour, minute, distance)
+            .map(|inner| CotesDeGeneveLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Generate the stripe lines
+    fn generate(&mut self) {
+        self.inner.generate();
+    }
+
+    /// Export the pattern to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner
+            .to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get all generated lines as list of list of (x, y) tuples
+    fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Number of stripes and their width in mm
+    fn stripe_layout(&self) -> (usize, f64) {
+        self.inner.config.stripe_layout()
+    }
+
+    /// Get the stripe width
+    #[getter]
+    fn stripe_width(&self) -> f64 {
+        self.inner.config.stripe_width
+    }
+
+    /// Get the line sagitta over the full diameter
+    #[getter]
+    fn arc_sagitta(&self) -> f64 {
+        self.inner.config.arc_sagitta
+    }
+
+    /// Get the stripe direction
+    #[getter]
+    fn angle(&self) -> f64 {
+        self.inner.config.angle
+    }
+
+    /// Get the radius
+    #[getter]
+    fn radius(&self) -> f64 {
+        self.inner.config.radius
+    }
+
+    /// Get the radius of the bare center
+    #[getter]
+    fn inner_radius(&self) -> f64 {
+        self.inner.config.inner_radius
+    }
+
+    /// Get the center x coordinate
+    #[getter]
+    fn center_x(&self) -> f64 {
+        self.inner.center_x
+    }
+
+    /// Get the center y coordinate
+    #[getter]
+    fn center_y(&self) -> f64 {
+        self.inner.center_y
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "CotesDeGeneveLayer(stripe_width={}, arc_sagitta={}, angle={}, radius={}, inner_radius={}, center=({}, {}))",
+            self.inner.config.stripe_width,
+            self.inner.config.arc_sagitta,
+            self.inner.config.angle,
+            self.inner.config.radius,
+            self.inner.config.inner_radius,
+            self.inner.center_x,
+            self.inner.center_y
+        )
+    }
+}
@@ -1,9 +1,14 @@
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use turtles::{
     CubeConfig as BaseCubeConfig,
     CubeLayer as BaseCubeLayer,
+    ExportConfig as BaseExportConfig,
+    PatternLayer,
 };
 
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
 /// Python wrapper for CubeLayer - creates tumbling-blocks guilloché patterns
 /// using parallel zigzag lines grouped in sets with phase-shifted gaps
 #[pyclass]
@@ -23,6 +28,7 @@ impl CubeLayer {
     /// * `cuts_per_group` - Number of zigzag lines per cutting group
     #[new]
     #[pyo3(signature = (spacing=0.5, radius=22.0, angle=0.0, resolution=200, cuts_per_group=8, gap_per_group=8, amplitude=0.0, leg_angle=30.0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         spacing: f64,
         radius: f64,
@@ -37,7 +43,7 @@ impl CubeLayer {
             spacing,
             radius,
             angle,
-            resolution,
+            resolution: resolution.into(),
             cuts_per_group,
             gap_per_group,
             amplitude,
@@ -51,6 +57,7 @@ impl CubeLayer {
     /// Create a cube layer with a custom center point
     #[staticmethod]
     #[pyo3(signature = (center_x, center_y, spacing=0.5, radius=22.0, angle=0.0, resolution=200, cuts_per_group=8, gap_per_group=8, amplitude=0.0, leg_angle=30.0))]
+    #[allow(clippy::too_many_arguments)]
     fn with_center(
         center_x: f64,
         center_y: f64,
@@ -67,7 +74,7 @@ impl CubeLayer {
             spacing,
             radius,
             angle,
-            resolution,
+            resolution: resolution.into(),
             cuts_per_group,
             gap_per_group,
             amplitude,
@@ -81,6 +88,7 @@ impl CubeLayer {
     /// Create a cube layer positioned at a given angle and distance from origin
     #[staticmethod]
     #[pyo3(signature = (angle, distance, spacing=0.5, radius=22.0, grid_angle=0.0, resolution=200, cuts_per_group=8, gap_per_group=8, amplitude=0.0, leg_angle=30.0))]
+    #[allow(clippy::too_many_arguments)]
     fn at_polar(
         angle: f64,
         distance: f64,
@@ -97,7 +105,7 @@ impl CubeLayer {
             spacing,
             radius,
             angle: grid_angle,
-            resolution,
+            resolution: resolution.into(),
             cuts_per_group,
             gap_per_group,
             amplitude,
@@ -116,6 +124,7 @@ impl CubeLayer {
     /// * `distance` - Distance from center of watch face to the layer center
     #[staticmethod]
     #[pyo3(signature = (hour, minute, distance, spacing=0.5, radius=22.0, angle=0.0, resolution=200, cuts_per_group=8, gap_per_group=8, amplitude=0.0, leg_angle=30.0))]
+    #[allow(clippy::too_many_arguments)]
     fn at_clock(
         hour: u32,
         minute: u32,
@@ -133,7 +142,7 @@ impl CubeLayer {
             spacing,
             radius,
             angle,
-            resolution,
+            resolution: resolution.into(),
             cuts_per_group,
             gap_per_group,
             amplitude,
@@ -149,10 +158,16 @@ impl CubeLayer {
         self.inner.generate();
     }
 
-    /// Export the pattern to SVG format
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
+    /// Export the pattern to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
         self.inner
-            .to_svg(filename)
+            .to_svg_with_config(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
@@ -165,6 +180,37 @@ impl CubeLayer {
             .collect()
     }
 
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
     /// Get the spacing between parallel lines
     #[getter]
     fn spacing(&self) -> f64 {
@@ -186,7 +232,7 @@ impl CubeLayer {
     /// Get the resolution
     #[getter]
     fn resolution(&self) -> usize {
-        self.inner.config.resolution
+        self.inner.config.resolution.get()
     }
 
     /// Get the number of zigzag lines per cutting group
@@ -1,9 +1,14 @@
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use turtles::{
     DiamantConfig as BaseDiamantConfig,
     DiamantLayer as BaseDiamantLayer,
+    ExportConfig as BaseExportConfig,
+    PatternLayer,
 };
 
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
 /// Python wrapper for DiamantLayer - creates diamond guilloché patterns
 /// using circles tangent to the center, rotated around the center
 #[pyclass]
@@ -19,13 +24,29 @@ impl DiamantLayer {
     /// * `num_circles` - Number of circles to draw (more = denser mesh)
     /// * `circle_radius` - Radius of each individual circle
     /// * `resolution` - Number of points per circle (default: 360)
+    /// * `num_rings` - Number of concentric rings of circles (default: 1)
+    /// * `ring_spacing` - Radial distance between neighbouring rings
+    /// * `scale_per_ring` - Circle radius factor from one ring to the next
+    /// * `stagger` - Turn every other ring by half a circle
     #[new]
-    #[pyo3(signature = (num_circles, circle_radius, resolution=360))]
-    fn new(num_circles: usize, circle_radius: f64, resolution: usize) -> PyResult<Self> {
+    #[pyo3(signature = (num_circles, circle_radius, resolution=360, num_rings=1, ring_spacing=0.0, scale_per_ring=1.0, stagger=false))]
+    fn new(
+        num_circles: usize,
+        circle_radius: f64,
+        resolution: usize,
+        num_rings: usize,
+        ring_spacing: f64,
+        scale_per_ring: f64,
+        stagger: bool,
+    ) -> PyResult<Self> {
         let config = BaseDiamantConfig {
             num_circles,
             circle_radius,
-            resolution,
+            resolution: resolution.into(),
+            num_rings,
+            ring_spacing,
+            scale_per_ring,
+            stagger,
         };
         BaseDiamantLayer::new(config)
             .map(|inner| DiamantLayer { inner })
@@ -34,18 +55,27 @@ impl DiamantLayer {
 
     /// Create a diamant layer with a custom center point
     #[staticmethod]
-    #[pyo3(signature = (num_circles, circle_radius, center_x, center_y, resolution=360))]
+    #[pyo3(signature = (num_circles, circle_radius, center_x, center_y, resolution=360, num_rings=1, ring_spacing=0.0, scale_per_ring=1.0, stagger=false))]
+    #[allow(clippy::too_many_arguments)]
     fn with_center(
         num_circles: usize,
         circle_radius: f64,
         center_x: f64,
         center_y: f64,
         resolution: usize,
+        num_rings: usize,
+        ring_spacing: f64,
+        scale_per_ring: f64,
+        stagger: bool,
     ) -> PyResult<Self> {
         let config = BaseDiamantConfig {
             num_circles,
             circle_radius,
-            resolution,
+            resolution: resolution.into(),
+            num_rings,
+            ring_spacing,
+            scale_per_ring,
+            stagger,
         };
         BaseDiamantLayer::new_with_center(config, center_x, center_y)
             .map(|inner| DiamantLayer { inner })
@@ -54,18 +84,27 @@ impl DiamantLayer {
 
     /// Create a diamant layer positioned at a given angle and distance from origin
     #[staticmethod]
-    #[pyo3(signature = (num_circles, circle_radius, angle, distance, resolution=360))]
+    #[pyo3(signature = (num_circles, circle_radius, angle, distance, resolution=360, num_rings=1, ring_spacing=0.0, scale_per_ring=1.0, stagger=false))]
+    #[allow(clippy::too_many_arguments)]
     fn at_polar(
         num_circles: usize,
         circle_radius: f64,
         angle: f64,
         distance: f64,
         resolution: usize,
+        num_rings: usize,
+        ring_spacing: f64,
+        scale_per_ring: f64,
+        stagger: bool,
     ) -> PyResult<Self> {
         let config = BaseDiamantConfig {
             num_circles,
             circle_radius,
-            resolution,
+            resolution: resolution.into(),
+            num_rings,
+            ring_spacing,
+            scale_per_ring,
+            stagger,
         };
         BaseDiamantLayer::new_at_polar(config, angle, distance)
             .map(|inner| DiamantLayer { inner })
@@ -82,7 +121,8 @@ impl DiamantLayer {
     /// * `distance` - Distance from center of watch face to the subdial center
     /// * `resolution` - Number of points per circle (default: 360)
     #[staticmethod]
-    #[pyo3(signature = (num_circles, circle_radius, hour, minute, distance, resolution=360))]
+    #[pyo3(signature = (num_circles, circle_radius, hour, minute, distance, resolution=360, num_rings=1, ring_spacing=0.0, scale_per_ring=1.0, stagger=false))]
+    #[allow(clippy::too_many_arguments)]
     fn at_clock(
         num_circles: usize,
         circle_radius: f64,
@@ -90,11 +130,19 @@ impl DiamantLayer {
         minute: u32,
         distance: f64,
         resolution: usize,
+        num_rings: usize,
+        ring_spacing: f64,
+        scale_per_ring: f64,
+        stagger: bool,
     ) -> PyResult<Self> {
         let config = BaseDiamantConfig {
             num_circles,
             circle_radius,
-            resolution,
+            resolution: resolution.into(),
+            num_rings,
+            ring_spacing,
+            scale_per_ring,
+            stagger,
         };
         BaseDiamantLayer::new_at_clock(config, hour, minute, distance)
             .map(|inner| DiamantLayer { inner })
@@ -116,10 +164,47 @@ impl DiamantLayer {
             .collect()
     }
 
-    /// Export the pattern to SVG format
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
         self.inner
-            .to_svg(filename)
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Export the pattern to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner
+            .to_svg_with_config(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
@@ -135,6 +220,30 @@ impl DiamantLayer {
         self.inner.config.circle_radius
     }
 
+    /// Get the number of concentric rings
+    #[getter]
+    fn num_rings(&self) -> usize {
+        self.inner.config.num_rings
+    }
+
+    /// Get the radial distance between neighbouring rings
+    #[getter]
+    fn ring_spacing(&self) -> f64 {
+        self.inner.config.ring_spacing
+    }
+
+    /// Get the circle radius factor from one ring to the next
+    #[getter]
+    fn scale_per_ring(&self) -> f64 {
+        self.inner.config.scale_per_ring
+    }
+
+    /// Whether every other ring is turned by half a circle
+    #[getter]
+    fn stagger(&self) -> bool {
+        self.inner.config.stagger
+    }
+
     /// Get the center x coordinate
     #[getter]
     fn center_x(&self) -> f64 {
@@ -1,9 +1,27 @@
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use turtles::{
     DraperieConfig as BaseDraperieConfig,
     DraperieLayer as BaseDraperieLayer,
+    ExportConfig as BaseExportConfig,
+    NonIntegerMode as BaseNonIntegerMode,
+    PatternLayer,
 };
 
+use crate::rose_engine_bindings::{checked_jitter, export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+use crate::progressive_bindings::{ProgressiveHandle, ProgressiveTarget};
+
+/// Parse a non-integer frequency mode name ("round" or "multi_revolution")
+fn parse_non_integer_mode(mode: &str) -> PyResult<BaseNonIntegerMode> {
+    match mode.to_lowercase().as_str() {
+        "round" => Ok(BaseNonIntegerMode::Round),
+        "multi_revolution" | "multi-revolution" => Ok(BaseNonIntegerMode::MultiRevolution),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(
+            "non_integer_mode must be 'round' or 'multi_revolution'",
+        )),
+    }
+}
+
 /// Python wrapper for DraperieLayer - creates flowing drapery guilloché patterns
 /// using concentric wavy rings with sinusoidal phase oscillation
 #[pyclass]
@@ -28,6 +46,7 @@ impl DraperieLayer {
     /// * `circular_phase` - Dome-shaped phase exponent; 0 disables (uses sin^e), 2.0 = rounded folds (default: 2.0)
     #[new]
     #[pyo3(signature = (num_rings=96, base_radius=22.0, radius_step=0.44, wave_frequency=12.0, phase_shift=None, phase_oscillations=2.5, resolution=1500, phase_exponent=3, wave_exponent=1, circular_phase=2.0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         num_rings: usize,
         base_radius: f64,
@@ -48,10 +67,13 @@ impl DraperieLayer {
             amplitude: None,
             phase_shift: phase_shift.unwrap_or(std::f64::consts::PI / 12.0),
             phase_oscillations,
-            resolution,
+            resolution: resolution.into(),
             phase_exponent,
             wave_exponent,
             circular_phase,
+            non_integer_mode: Default::default(),
+            dash_by_phase: None,
+            jitter: None,
         };
         BaseDraperieLayer::new(config)
             .map(|inner| DraperieLayer { inner })
@@ -61,6 +83,7 @@ impl DraperieLayer {
     /// Create a draperie layer with a custom center point
     #[staticmethod]
     #[pyo3(signature = (center_x, center_y, num_rings=96, base_radius=22.0, radius_step=0.44, wave_frequency=12.0, phase_shift=None, phase_oscillations=2.5, resolution=1500, phase_exponent=3, wave_exponent=1, circular_phase=2.0))]
+    #[allow(clippy::too_many_arguments)]
     fn with_center(
         center_x: f64,
         center_y: f64,
@@ -83,10 +106,13 @@ impl DraperieLayer {
             amplitude: None,
             phase_shift: phase_shift.unwrap_or(std::f64::consts::PI / 12.0),
             phase_oscillations,
-            resolution,
+            resolution: resolution.into(),
             phase_exponent,
             wave_exponent,
             circular_phase,
+            non_integer_mode: Default::default(),
+            dash_by_phase: None,
+            jitter: None,
         };
         BaseDraperieLayer::new_with_center(config, center_x, center_y)
             .map(|inner| DraperieLayer { inner })
@@ -96,6 +122,7 @@ impl DraperieLayer {
     /// Create a draperie layer positioned at a given angle and distance from origin
     #[staticmethod]
     #[pyo3(signature = (angle, distance, num_rings=96, base_radius=22.0, radius_step=0.44, wave_frequency=12.0, phase_shift=None, phase_oscillations=2.5, resolution=1500, phase_exponent=3, wave_exponent=1, circular_phase=2.0))]
+    #[allow(clippy::too_many_arguments)]
     fn at_polar(
         angle: f64,
         distance: f64,
@@ -118,10 +145,13 @@ impl DraperieLayer {
             amplitude: None,
             phase_shift: phase_shift.unwrap_or(std::f64::consts::PI / 12.0),
             phase_oscillations,
-            resolution,
+            resolution: resolution.into(),
             phase_exponent,
             wave_exponent,
             circular_phase,
+            non_integer_mode: Default::default(),
+            dash_by_phase: None,
+            jitter: None,
         };
         BaseDraperieLayer::new_at_polar(config, angle, distance)
             .map(|inner| DraperieLayer { inner })
@@ -136,6 +166,7 @@ impl DraperieLayer {
     /// * `distance` - Distance from center of watch face to the layer center
     #[staticmethod]
     #[pyo3(signature = (hour, minute, distance, num_rings=96, base_radius=22.0, radius_step=0.44, wave_frequency=12.0, phase_shift=None, phase_oscillations=2.5, resolution=1500, phase_exponent=3, wave_exponent=1, circular_phase=2.0))]
+    #[allow(clippy::too_many_arguments)]
     fn at_clock(
         hour: u32,
         minute: u32,
@@ -159,10 +190,13 @@ impl DraperieLayer {
             amplitude: None,
             phase_shift: phase_shift.unwrap_or(std::f64::consts::PI / 12.0),
             phase_oscillations,
-            resolution,
+            resolution: resolution.into(),
             phase_exponent,
             wave_exponent,
             circular_phase,
+            non_integer_mode: Default::default(),
+            dash_by_phase: None,
+            jitter: None,
         };
         BaseDraperieLayer::new_at_clock(config, hour, minute, distance)
             .map(|inner| DraperieLayer { inner })
@@ -174,13 +208,52 @@ impl DraperieLayer {
         self.inner.generate();
     }
 
+    /// Start a coarse-to-fine generation, e.g. `generate_progressive([0.1, 0.3, 1.0])`
+    ///
+    /// Returns a handle whose `advance()` computes the next level.
+    fn generate_progressive(slf: Py<Self>, levels: Vec<f32>) -> ProgressiveHandle {
+        ProgressiveHandle::new(ProgressiveTarget::Draperie(slf), &levels)
+    }
+
     /// Export the pattern to SVG format
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
+    ///
+    /// With `streaming`, the rings are traced straight from the config one
+    /// at a time instead of taken from `generate()`, keeping memory bounded.
+    /// Without it, a positive `tolerance` simplifies the lines to within
+    /// that distance first
+    #[pyo3(signature = (filename, streaming=false, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, streaming: bool, tolerance: f64) -> PyResult<()> {
+        let result = if streaming {
+            if tolerance > 0.0 {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "tolerance cannot be combined with streaming",
+                ));
+            }
+            self.inner.to_svg_streaming(filename)
+        } else {
+            let config = BaseExportConfig {
+                simplify_tolerance: tolerance,
+                ..Default::default()
+            };
+            self.inner.to_svg_with_config(filename, &config)
+        };
+        result.map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Export the pattern to DXF as LWPOLYLINE entities in mm
+    fn to_dxf(&self, filename: &str) -> PyResult<()> {
         self.inner
-            .to_svg(filename)
+            .to_dxf(filename)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
+    /// Export the pattern to SVG with gradient-shaded bands between the rings
+    fn to_svg_filled(&self, filename: &str, light_angle: f64) -> PyResult<()> {
+        self.inner
+            .to_svg_filled(filename, light_angle)
+            .map_err(export_err)
+    }
+
     /// Get all generated ring lines as list of list of (x, y) tuples
     fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
         self.inner
@@ -190,6 +263,91 @@ impl DraperieLayer {
             .collect()
     }
 
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Hash of the generated rings; equal hashes mean the drawing is unchanged
+    fn geometry_hash(&self) -> u64 {
+        self.inner.geometry_hash()
+    }
+
+    /// Set how a non-integer wave_frequency is closed: "round" (default) rounds it,
+    /// "multi_revolution" traces each ring over as many turns as it needs; call generate() again
+    fn set_non_integer_mode(&mut self, mode: &str) -> PyResult<()> {
+        self.inner.config.non_integer_mode = parse_non_integer_mode(mode)?;
+        Ok(())
+    }
+
+    /// Wobble each ring as if made by hand: RMS radial (mm) and angular
+    /// (radians) offsets drawn from `seed`, kept within a quarter of the
+    /// gap to the neighbouring rings; call generate() again
+    #[pyo3(signature = (radial_sigma, angular_sigma=0.0, seed=0))]
+    fn set_jitter(&mut self, radial_sigma: f64, angular_sigma: f64, seed: u64) -> PyResult<()> {
+        self.inner.config.jitter = Some(checked_jitter(radial_sigma, angular_sigma, seed)?);
+        Ok(())
+    }
+
+    /// Remove the jitter
+    fn clear_jitter(&mut self) {
+        self.inner.config.jitter = None;
+    }
+
+    /// The jitter as (radial_sigma, angular_sigma, seed), or None
+    #[getter]
+    fn jitter(&self) -> Option<(f64, f64, u64)> {
+        self.inner.config
+            .jitter
+            .map(|jitter| (jitter.radial_sigma, jitter.angular_sigma, jitter.seed))
+    }
+
+    /// Describe how wave_frequency was changed so the rings close, or None if unchanged
+    fn normalization_report(&self) -> Option<String> {
+        self.inner
+            .config
+            .normalization_report()
+            .map(|report| report.to_string())
+    }
+
+    /// Whether changing the named config field can change the rings
+    /// with the current settings
+    fn affects_geometry(&self, field: &str) -> bool {
+        self.inner.config.affects_geometry(field)
+    }
+
+    /// Get the wave amplitude that will be used (explicit or automatic)
+    #[getter]
+    fn effective_amplitude(&self) -> f64 {
+        self.inner.config.effective_amplitude()
+    }
+
     /// Get the number of rings in the pattern
     #[getter]
     fn num_rings(&self) -> usize {
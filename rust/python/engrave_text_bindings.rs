@@ -0,0 +1,178 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use turtles::{
+    ArcDirection as BaseArcDirection,
+    EngravedTextConfig as BaseEngravedTextConfig,
+    EngravedTextLayer as BaseEngravedTextLayer,
+    ExportConfig as BaseExportConfig,
+    Point2D,
+    TextPosition as BaseTextPosition,
+    PatternLayer,
+};
+
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+/// Parse an arc direction name ("clockwise" or "counterclockwise")
+fn parse_direction(direction: &str) -> PyResult<BaseArcDirection> {
+    match direction.to_lowercase().as_str() {
+        "clockwise" | "cw" => Ok(BaseArcDirection::Clockwise),
+        "counterclockwise" | "counter-clockwise" | "ccw" => Ok(BaseArcDirection::CounterClockwise),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(
+            "direction must be 'clockwise' or 'counterclockwise'",
+        )),
+    }
+}
+
+/// Build a text configuration from Python arguments
+///
+/// Text follows an arc when `radius` is given, otherwise it runs straight
+/// from `origin` at `angle`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn text_config(
+    text: &str,
+    height: f64,
+    radius: Option<f64>,
+    hour: u32,
+    minute: u32,
+    direction: &str,
+    origin: (f64, f64),
+    angle: f64,
+    spacing: f64,
+) -> PyResult<BaseEngravedTextConfig> {
+    let position = match radius {
+        Some(radius) => {
+            BaseTextPosition::arc_at_clock(hour, minute, radius, parse_direction(direction)?)
+        }
+        None => BaseTextPosition::Linear {
+            origin: Point2D::new(origin.0, origin.1),
+            angle,
+        },
+    };
+    Ok(BaseEngravedTextConfig::new(text, height, position).with_spacing(spacing))
+}
+
+/// Python wrapper for EngravedTextLayer - single-stroke engraved text
+/// for dial numerals and signatures
+#[pyclass]
+pub struct EngravedTextLayer {
+    pub inner: BaseEngravedTextLayer,
+}
+
+#[pymethods]
+impl EngravedTextLayer {
+    /// Create a new engraved text layer
+    ///
+    /// # Arguments
+    /// * `text` - Text to engrave (digits, letters, space and . , - + / : ')
+    /// * `height` - Capital height in mm (default: 3.0)
+    /// * `radius` - Follow an arc of this radius about the center (default: straight text)
+    /// * `hour` - Clock position the arc text is centered on (default: 12)
+    /// * `minute` - Minute of the clock position (default: 0)
+    /// * `direction` - "clockwise" (reads outside the arc) or "counterclockwise"
+    /// * `origin` - Baseline center of straight text (default: (0, 0))
+    /// * `angle` - Direction of straight text in radians (default: 0)
+    /// * `spacing` - Extra gap between glyphs in mm (default: 0)
+    #[new]
+    #[pyo3(signature = (text, height=3.0, radius=None, hour=12, minute=0, direction="clockwise", origin=(0.0, 0.0), angle=0.0, spacing=0.0))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        text: &str,
+        height: f64,
+        radius: Option<f64>,
+        hour: u32,
+        minute: u32,
+        direction: &str,
+        origin: (f64, f64),
+        angle: f64,
+        spacing: f64,
+    ) -> PyResult<Self> {
+        let config = text_config(text, height, radius, hour, minute, direction, origin, angle, spacing)?;
+        BaseEngravedTextLayer::new(config)
+            .map(|inner| EngravedTextLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Check whether a character is available in the engraving font
+    #[staticmethod]
+    fn supports(c: char) -> bool {
+        BaseEngravedTextLayer::supports(c)
+    }
+
+    /// Generate the text strokes
+    fn generate(&mut self) {
+        self.inner.generate();
+    }
+
+    /// Export the text to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner
+            .to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get the engraved text
+    #[getter]
+    fn text(&self) -> String {
+        self.inner.config.text.clone()
+    }
+
+    /// Get the capital height
+    #[getter]
+    fn height(&self) -> f64 {
+        self.inner.config.height
+    }
+
+    /// Get the generated strokes as a list of point lists
+    /// Each stroke is a list of (x, y) tuples
+    fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "EngravedTextLayer(text={:?}, height={})",
+            self.inner.config.text, self.inner.config.height
+        )
+    }
+}
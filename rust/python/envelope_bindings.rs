@@ -0,0 +1,71 @@
+use pyo3::prelude::*;
+use turtles::Envelope as BaseEnvelope;
+
+fn checked_envelope(inner: BaseEnvelope) -> PyResult<Envelope> {
+    inner
+        .validate()
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    Ok(Envelope { inner })
+}
+
+/// Python wrapper for Envelope, a weight in [0, 1] over dial angle and radius
+#[pyclass(from_py_object)]
+#[derive(Clone)]
+pub struct Envelope {
+    pub(crate) inner: BaseEnvelope,
+}
+
+#[pymethods]
+impl Envelope {
+    /// The same weight everywhere
+    #[staticmethod]
+    fn constant(value: f64) -> PyResult<Self> {
+        checked_envelope(BaseEnvelope::constant(value))
+    }
+
+    /// 1 from start_angle to end_angle (radians, wrapping through 0),
+    /// smoothstepped over feather radians inside each end
+    #[staticmethod]
+    #[pyo3(signature = (start_angle, end_angle, feather=0.0))]
+    fn angular_smoothstep(start_angle: f64, end_angle: f64, feather: f64) -> PyResult<Self> {
+        checked_envelope(BaseEnvelope::angular(start_angle, end_angle, feather))
+    }
+
+    /// 1 between radii r_start and r_end, smoothstepped over feather
+    /// inside each end
+    #[staticmethod]
+    #[pyo3(signature = (r_start, r_end, feather=0.0))]
+    fn radial_smoothstep(r_start: f64, r_end: f64, feather: f64) -> PyResult<Self> {
+        checked_envelope(BaseEnvelope::radial(r_start, r_end, feather))
+    }
+
+    /// The product of two envelopes
+    #[staticmethod]
+    fn product(a: Envelope, b: Envelope) -> Self {
+        Envelope {
+            inner: a.inner.product(b.inner),
+        }
+    }
+
+    /// Weights at evenly spaced angles over one revolution, interpolated
+    #[staticmethod]
+    fn table(samples: Vec<f64>) -> PyResult<Self> {
+        checked_envelope(BaseEnvelope::table(samples))
+    }
+
+    /// Weight at angle (radians) and radius, clamped to [0, 1]
+    #[pyo3(signature = (angle, radius=0.0))]
+    fn eval(&self, angle: f64, radius: f64) -> f64 {
+        self.inner.eval(angle, radius)
+    }
+
+    fn __mul__(&self, other: Envelope) -> Self {
+        Envelope {
+            inner: self.inner.clone().product(other.inner),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Envelope({:?})", self.inner)
+    }
+}
@@ -0,0 +1,91 @@
+use pyo3::prelude::*;
+use turtles::ExportConfig as BaseExportConfig;
+
+/// Python wrapper for ExportConfig - groove depth, base slab thickness and
+/// tool radius for the STL and STEP exports
+///
+/// Pass it as `config=` to an export instead of the separate `depth`,
+/// `base_thickness` and `tool_radius` keyword arguments.
+#[pyclass(from_py_object)]
+#[derive(Clone)]
+pub struct ExportConfig {
+    pub(crate) inner: BaseExportConfig,
+}
+
+#[pymethods]
+impl ExportConfig {
+    /// Create an export configuration
+    ///
+    /// # Arguments
+    /// * `depth` - Groove depth (default: 0.1)
+    /// * `base_thickness` - STL base slab thickness under the grooves, 0 for
+    ///   none (default: 2.0)
+    /// * `tool_radius` - Half-width of flat-bottomed grooves (0 for 90°
+    ///   V-grooves); rose engine exports, which cut with their bit, offset
+    ///   the tool path by it instead, outwards when positive (default: 0.0)
+    #[new]
+    #[pyo3(signature = (depth=0.1, base_thickness=2.0, tool_radius=0.0))]
+    fn new(depth: f64, base_thickness: f64, tool_radius: f64) -> Self {
+        ExportConfig {
+            inner: BaseExportConfig {
+                depth,
+                base_thickness,
+                tool_radius,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[getter]
+    fn depth(&self) -> f64 {
+        self.inner.depth
+    }
+
+    #[getter]
+    fn base_thickness(&self) -> f64 {
+        self.inner.base_thickness
+    }
+
+    #[getter]
+    fn tool_radius(&self) -> f64 {
+        self.inner.tool_radius
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ExportConfig(depth={}, base_thickness={}, tool_radius={})",
+            self.inner.depth,
+            self.inner.base_thickness,
+            self.inner.tool_radius
+        )
+    }
+}
+
+/// The export configuration from either `config` or the separate keyword
+/// arguments, raising ValueError when both are given
+///
+/// Arguments left out take `ExportConfig`'s defaults.
+pub(crate) fn export_config(
+    config: Option<ExportConfig>,
+    depth: Option<f64>,
+    base_thickness: Option<f64>,
+    tool_radius: Option<f64>,
+) -> PyResult<BaseExportConfig> {
+    match config {
+        Some(_) if depth.is_some() || base_thickness.is_some() || tool_radius.is_some() => {
+            Err(pyo3::exceptions::PyValueError::new_err(
+                "pass either config or depth/base_thickness/tool_radius, not both",
+            ))
+        }
+        Some(config) => Ok(config.inner),
+        None => {
+            let defaults = BaseExportConfig::default();
+            Ok(BaseExportConfig {
+                depth: depth.unwrap_or(defaults.depth),
+                base_thickness: base_thickness.unwrap_or(defaults.base_thickness),
+                tool_radius: tool_radius.unwrap_or(defaults.tool_radius),
+                ..defaults
+            })
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use turtles::{
+    ExportConfig as BaseExportConfig,
+    GrainDeRizConfig as BaseGrainDeRizConfig,
+    GrainDeRizLayer as BaseGrainDeRizLayer,
+    PatternLayer,
+};
+
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+/// Build a GrainDeRizConfig from Python arguments
+#[allow(clippy::too_many_arguments)]
+pub fn grain_de_riz_config(
+    num_rows: usize,
+    grains_per_row: usize,
+    grain_length: f64,
+    grain_width: f64,
+    base_radius: f64,
+    radius_step: f64,
+    resolution: usize,
+    stagger: bool,
+) -> BaseGrainDeRizConfig {
+    BaseGrainDeRizConfig {
+        num_rows,
+        grains_per_row,
+        grain_length,
+        grain_width,
+        base_radius,
+        radius_step,
+        resolution: resolution.into(),
+        stagger,
+    }
+}
+
+/// Python wrapper for GrainDeRizLayer - small closed ovals (rice grains)
+/// on concentric rows
+#[pyclass]
+pub struct GrainDeRizLayer {
+    pub inner: BaseGrainDeRizLayer,
+}
+
+#[pymethods]
+impl GrainDeRizLayer {
+    /// Create a new grain-de-riz layer centered at origin
+    ///
+    /// # Arguments
+    /// * `num_rows` - Number of concentric rows
+    /// * `grains_per_row` - Number of grains on every row
+    /// * `grain_length` - Length of each grain along its row in mm
+    /// * `grain_width` - Width of each grain across its row in mm
+    /// * `base_radius` - Radius of the innermost row in mm
+    /// * `radius_step` - Distance between neighbouring rows in mm
+    /// * `resolution` - Points around each grain
+    /// * `stagger` - Turn every other row by half a grain pitch
+    #[new]
+    #[pyo3(signature = (num_rows=12, grains_per_row=48, grain_length=1.2, grain_width=0.5, base_radius=8.0, radius_step=1.0, resolution=24, stagger=true))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        num_rows: usize,
+        grains_per_row: usize,
+        grain_length: f64,
+        grain_width: f64,
+        base_radius: f64,
+        radius_step: f64,
+        resolution: usize,
+        stagger: bool,
+    ) -> PyResult<Self> {
+        let config = grain_de_riz_config(num_rows, grains_per_row, grain_length, grain_width, base_radius, radius_step, resolution, stagger);
+        BaseGrainDeRizLayer::new(config)
+            .map(|inner| GrainDeRizLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a grain-de-riz layer with a custom center point
+    #[staticmethod]
+    #[pyo3(signature = (center_x, center_y, num_rows=12, grains_per_row=48, grain_length=1.2, grain_width=0.5, base_radius=8.0, radius_step=1.0, resolution=24, stagger=true))]
+    #[allow(clippy::too_many_arguments)]
+    fn with_center(
+        center_x: f64,
+        center_y: f64,
+        num_rows: usize,
+        grains_per_row: usize,
+        grain_length: f64,
+        grain_width: f64,
+        base_radius: f64,
+        radius_step: f64,
+        resolution: usize,
+        stagger: bool,
+    ) -> PyResult<Self> {
+        let config = grain_de_riz_config(num_rows, grains_per_row, grain_length, grain_width, base_radius, radius_step, resolution, stagger);
+        BaseGrainDeRizLayer::new_with_center(config, center_x, center_y)
+            .map(|inner| GrainDeRizLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a grain-de-riz layer positioned at a given angle and distance from origin
+    #[staticmethod]
+    #[pyo3(signature = (angle, distance, num_rows=12, grains_per_row=48, grain_length=1.2, grain_width=0.5, base_radius=8.0, radius_step=1.0, resolution=24, stagger=true))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_polar(
+        angle: f64,
+        distance: f64,
+        num_rows: usize,
+        grains_per_row: usize,
+        grain_length: f64,
+        grain_width: f64,
+        base_radius: f64,
+        radius_step: f64,
+        resolution: usize,
+        stagger: bool,
+    ) -> PyResult<Self> {
+        let config = grain_de_riz_config(num_rows, grains_per_row, grain_length, grain_width, base_radius, radius_step, resolution, stagger);
+        BaseGrainDeRizLayer::new_at_polar(config, angle, distance)
+            .map(|inner| GrainDeRizLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a grain-de-riz layer positioned at a clock position (like hour hand)
+    ///
+    /// # Arguments
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face to the layer center
+    #[staticmethod]
+    #[pyo3(signature = (hour, minute, distance, num_rows=12, grains_per_row=48, grain_length=1.2, grain_width=0.5, base_radius=8.0, radius_step=1.0, resolution=24, stagger=true))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_clock(
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        num_rows: usize,
+        grains_per_row: usize,
+        grain_length: f64,
+        grain_width: f64,
+        base_radius: f64,
+        radius_step: f64,
+        resolution: usize,
+        stagger: bool,
+    ) -> PyResult<Self> {
+        let config = grain_de_riz_config(num_rows, grains_per_row, grain_length, grain_width, base_radius, radius_step, resolution, stagger);
+        BaseGrainDeRizLayer::new_at_clock(config, hour, minute, distance)
+            .map(|inner| GrainDeRizLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Generate the grain-de-riz pattern
+    fn generate(&mut self) {
+        self.inner.generate();
+    }
+
+    /// Export the pattern to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner
+            .to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get all generated grain outlines as list of list of (x, y) tuples
+    fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Get the number of rows
+    #[getter]
+    fn num_rows(&self) -> usize {
+        self.inner.config.num_rows
+    }
+
+    /// Get the number of grains on every row
+    #[getter]
+    fn grains_per_row(&self) -> usize {
+        self.inner.config.grains_per_row
+    }
+
+    /// Get the grain length
+    #[getter]
+    fn grain_length(&self) -> f64 {
+        self.inner.config.grain_length
+    }
+
+    /// Get the grain width
+    #[getter]
+    fn grain_width(&self) -> f64 {
+        self.inner.config.grain_width
+    }
+
+    /// Get the center x coordinate
+    #[getter]
+    fn center_x(&self) -> f64 {
+        self.inner.center_x
+    }
+
+    /// Get the center y coordinate
+    #[getter]
+    fn center_y(&self) -> f64 {
+        self.inner.center_y
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GrainDeRizLayer(num_rows={}, grains_per_row={}, grain_length={}, grain_width={}, center=({}, {}))",
+            self.inner.config.num_rows,
+            self.inner.config.grains_per_row,
+            self.inner.config.grain_length,
+            self.inner.config.grain_width,
+            self.inner.center_x,
+            self.inner.center_y
+        )
+    }
+}
@@ -1,24 +1,32 @@
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::export_bindings::{export_config, ExportConfig};
+use crate::rose_engine_bindings::{checked_jitter, diff_summary_dict, export_err, point_lines, stats_dict, transformed_lines, PointTuples};
 use turtles::{
     DiamantConfig as BaseDiamantConfig,
     DiamantLayer as BaseDiamantLayer,
     GuillochePattern as BaseGuillochePattern,
     FlinqueConfig as BaseFlinqueConfig,
     FlinqueLayer as BaseFlinqueLayer,
-    LimaconConfig as BaseLimaconConfig,
     LimaconLayer as BaseLimaconLayer,
     PaonConfig as BasePaonConfig,
     PaonLayer as BasePaonLayer,
+    PaonOrigin as BasePaonOrigin,
     HorizontalSpirograph as BaseHorizontalSpirograph,
     VerticalSpirograph as BaseVerticalSpirograph,
     SphericalSpirograph as BaseSphericalSpirograph,
     ExportConfig as BaseExportConfig,
+    Point2D,
+    PatternLayer,
 };
 
 use crate::diamant_bindings::DiamantLayer;
-use crate::limacon_bindings::LimaconLayer;
+use crate::engrave_text_bindings::{text_config, EngravedTextLayer};
+use crate::limacon_bindings::{limacon_config, LimaconLayer};
 use crate::paon_bindings::PaonLayer;
 use crate::spirograph_bindings::{HorizontalSpirograph, VerticalSpirograph, SphericalSpirograph};
+use crate::watch_face_bindings::parse_radius_policy;
 
 /// Python wrapper for FlinqueLayer - a radial sunburst engine-turned pattern
 #[pyclass]
@@ -29,7 +37,8 @@ pub struct FlinqueLayer {
 #[pymethods]
 impl FlinqueLayer {
     #[new]
-    #[pyo3(signature = (radius, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05))]
+    #[pyo3(signature = (radius, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05, resolution=None, petal_phase_step=0.0, alternate=false))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         radius: f64,
         num_petals: usize,
@@ -37,6 +46,9 @@ impl FlinqueLayer {
         wave_amplitude: f64,
         wave_frequency: f64,
         inner_radius_ratio: f64,
+        resolution: Option<usize>,
+        petal_phase_step: f64,
+        alternate: bool,
     ) -> PyResult<Self> {
         let config = BaseFlinqueConfig {
             num_petals,
@@ -44,6 +56,10 @@ impl FlinqueLayer {
             wave_amplitude,
             wave_frequency,
             inner_radius_ratio,
+            resolution: resolution.map(Into::into),
+            petal_phase_step,
+            alternate,
+            jitter: None,
         };
         BaseFlinqueLayer::new(radius, config)
             .map(|inner| FlinqueLayer { inner })
@@ -52,7 +68,8 @@ impl FlinqueLayer {
 
     /// Create a flinqué layer with a custom center point
     #[staticmethod]
-    #[pyo3(signature = (radius, center_x, center_y, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05))]
+    #[pyo3(signature = (radius, center_x, center_y, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05, resolution=None, petal_phase_step=0.0, alternate=false))]
+    #[allow(clippy::too_many_arguments)]
     fn with_center(
         radius: f64,
         center_x: f64,
@@ -62,6 +79,9 @@ impl FlinqueLayer {
         wave_amplitude: f64,
         wave_frequency: f64,
         inner_radius_ratio: f64,
+        resolution: Option<usize>,
+        petal_phase_step: f64,
+        alternate: bool,
     ) -> PyResult<Self> {
         let config = BaseFlinqueConfig {
             num_petals,
@@ -69,6 +89,10 @@ impl FlinqueLayer {
             wave_amplitude,
             wave_frequency,
             inner_radius_ratio,
+            resolution: resolution.map(Into::into),
+            petal_phase_step,
+            alternate,
+            jitter: None,
         };
         BaseFlinqueLayer::new_with_center(radius, config, center_x, center_y)
             .map(|inner| FlinqueLayer { inner })
@@ -77,7 +101,8 @@ impl FlinqueLayer {
 
     /// Create a flinqué layer positioned at a given angle and distance from origin
     #[staticmethod]
-    #[pyo3(signature = (radius, angle, distance, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05))]
+    #[pyo3(signature = (radius, angle, distance, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05, resolution=None, petal_phase_step=0.0, alternate=false))]
+    #[allow(clippy::too_many_arguments)]
     fn at_polar(
         radius: f64,
         angle: f64,
@@ -87,6 +112,9 @@ impl FlinqueLayer {
         wave_amplitude: f64,
         wave_frequency: f64,
         inner_radius_ratio: f64,
+        resolution: Option<usize>,
+        petal_phase_step: f64,
+        alternate: bool,
     ) -> PyResult<Self> {
         let config = BaseFlinqueConfig {
             num_petals,
@@ -94,6 +122,10 @@ impl FlinqueLayer {
             wave_amplitude,
             wave_frequency,
             inner_radius_ratio,
+            resolution: resolution.map(Into::into),
+            petal_phase_step,
+            alternate,
+            jitter: None,
         };
         BaseFlinqueLayer::new_at_polar(radius, config, angle, distance)
             .map(|inner| FlinqueLayer { inner })
@@ -108,7 +140,8 @@ impl FlinqueLayer {
     /// * `minute` - Minute position (0-59)
     /// * `distance` - Distance from center of watch face to the subdial center
     #[staticmethod]
-    #[pyo3(signature = (radius, hour, minute, distance, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05))]
+    #[pyo3(signature = (radius, hour, minute, distance, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05, resolution=None, petal_phase_step=0.0, alternate=false))]
+    #[allow(clippy::too_many_arguments)]
     fn at_clock(
         radius: f64,
         hour: u32,
@@ -119,6 +152,9 @@ impl FlinqueLayer {
         wave_amplitude: f64,
         wave_frequency: f64,
         inner_radius_ratio: f64,
+        resolution: Option<usize>,
+        petal_phase_step: f64,
+        alternate: bool,
     ) -> PyResult<Self> {
         let config = BaseFlinqueConfig {
             num_petals,
@@ -126,6 +162,10 @@ impl FlinqueLayer {
             wave_amplitude,
             wave_frequency,
             inner_radius_ratio,
+            resolution: resolution.map(Into::into),
+            petal_phase_step,
+            alternate,
+            jitter: None,
         };
         BaseFlinqueLayer::new_at_clock(radius, config, hour, minute, distance)
             .map(|inner| FlinqueLayer { inner })
@@ -152,6 +192,27 @@ impl FlinqueLayer {
         self.inner.generate();
     }
 
+    /// Wobble each ring as if made by hand: RMS radial (mm) and angular
+    /// (radians) offsets drawn from `seed`; call generate() again
+    #[pyo3(signature = (radial_sigma, angular_sigma=0.0, seed=0))]
+    fn set_jitter(&mut self, radial_sigma: f64, angular_sigma: f64, seed: u64) -> PyResult<()> {
+        self.inner.config.jitter = Some(checked_jitter(radial_sigma, angular_sigma, seed)?);
+        Ok(())
+    }
+
+    /// Remove the jitter
+    fn clear_jitter(&mut self) {
+        self.inner.config.jitter = None;
+    }
+
+    /// The jitter as (radial_sigma, angular_sigma, seed), or None
+    #[getter]
+    fn jitter(&self) -> Option<(f64, f64, u64)> {
+        self.inner.config
+            .jitter
+            .map(|jitter| (jitter.radial_sigma, jitter.angular_sigma, jitter.seed))
+    }
+
     /// Get the generated pattern lines as a list of point lists
     /// Each line is a list of (x, y) tuples
     fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
@@ -162,6 +223,44 @@ impl FlinqueLayer {
             .collect()
     }
 
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Export the pattern to SVG with gradient-shaded bands between the rings
+    fn to_svg_filled(&self, filename: &str, light_angle: f64) -> PyResult<()> {
+        self.inner
+            .to_svg_filled(filename, light_angle)
+            .map_err(export_err)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "FlinqueLayer(radius={}, center=({}, {}), petals={})",
@@ -182,9 +281,9 @@ pub struct GuillochePattern {
 #[pymethods]
 impl GuillochePattern {
     #[new]
-    #[pyo3(signature = (radius))]
-    fn new(radius: f64) -> PyResult<Self> {
-        BaseGuillochePattern::new(radius)
+    #[pyo3(signature = (radius, radius_policy=None))]
+    fn new(radius: f64, radius_policy: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        BaseGuillochePattern::new_with_policy(radius, parse_radius_policy(radius_policy)?)
             .map(|inner| GuillochePattern { inner })
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
@@ -200,19 +299,20 @@ impl GuillochePattern {
         // Try to extract different spirograph types
         if let Ok(h_spiro) = spiro.extract::<PyRef<HorizontalSpirograph>>() {
             // We need to create a new instance since we can't clone or move the inner value
-            let new_spiro = BaseHorizontalSpirograph::new(
+            let new_spiro = BaseHorizontalSpirograph::new_with_policy(
                 h_spiro.inner.outer_radius,
                 h_spiro.inner.radius_ratio,
                 h_spiro.inner.point_distance,
                 h_spiro.inner.rotations,
                 h_spiro.inner.resolution,
+                h_spiro.inner.radius_policy,
             ).map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
             self.inner.add_horizontal_layer(new_spiro);
             return Ok(());
         }
 
         if let Ok(v_spiro) = spiro.extract::<PyRef<VerticalSpirograph>>() {
-            let new_spiro = BaseVerticalSpirograph::new(
+            let new_spiro = BaseVerticalSpirograph::new_with_policy(
                 v_spiro.inner.outer_radius,
                 v_spiro.inner.radius_ratio,
                 v_spiro.inner.point_distance,
@@ -220,19 +320,21 @@ impl GuillochePattern {
                 v_spiro.inner.resolution,
                 v_spiro.inner.wave_amplitude,
                 v_spiro.inner.wave_frequency,
+                v_spiro.inner.radius_policy,
             ).map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
             self.inner.add_vertical_layer(new_spiro);
             return Ok(());
         }
 
         if let Ok(s_spiro) = spiro.extract::<PyRef<SphericalSpirograph>>() {
-            let new_spiro = BaseSphericalSpirograph::new(
+            let new_spiro = BaseSphericalSpirograph::new_with_policy(
                 s_spiro.inner.outer_radius,
                 s_spiro.inner.radius_ratio,
                 s_spiro.inner.point_distance,
                 s_spiro.inner.rotations,
                 s_spiro.inner.resolution,
                 s_spiro.inner.dome_height,
+                s_spiro.inner.radius_policy,
             ).map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
             self.inner.add_spherical_layer(new_spiro);
             return Ok(());
@@ -257,6 +359,7 @@ impl GuillochePattern {
 
     /// Add a flinqué layer positioned at a given angle and distance from origin
     #[pyo3(signature = (radius, angle, distance, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05))]
+    #[allow(clippy::too_many_arguments)]
     fn add_flinque_at_polar(
         &mut self,
         radius: f64,
@@ -274,6 +377,10 @@ impl GuillochePattern {
             wave_amplitude,
             wave_frequency,
             inner_radius_ratio,
+            resolution: None,
+            petal_phase_step: 0.0,
+            alternate: false,
+            jitter: None,
         };
         self.inner.add_flinque_at_polar(radius, config, angle, distance)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
@@ -287,6 +394,7 @@ impl GuillochePattern {
     /// * `minute` - Minute position (0-59)
     /// * `distance` - Distance from center of watch face to the subdial center
     #[pyo3(signature = (radius, hour, minute, distance, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05))]
+    #[allow(clippy::too_many_arguments)]
     fn add_flinque_at_clock(
         &mut self,
         radius: f64,
@@ -305,6 +413,10 @@ impl GuillochePattern {
             wave_amplitude,
             wave_frequency,
             inner_radius_ratio,
+            resolution: None,
+            petal_phase_step: 0.0,
+            alternate: false,
+            jitter: None,
         };
         self.inner.add_flinque_at_clock(radius, config, hour, minute, distance)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
@@ -325,6 +437,7 @@ impl GuillochePattern {
     /// * `wave_frequency` - Vertical wave frequency (for vertical spirograph)
     /// * `dome_height` - Height of dome (for spherical spirograph)
     #[pyo3(signature = (spiro_type, outer_radius, radius_ratio, point_distance, rotations, resolution, angle, distance, wave_amplitude=1.0, wave_frequency=5.0, dome_height=5.0))]
+    #[allow(clippy::too_many_arguments)]
     fn add_layer_at_polar(
         &mut self,
         spiro_type: &str,
@@ -383,6 +496,7 @@ impl GuillochePattern {
     /// * `wave_frequency` - Vertical wave frequency (for vertical spirograph)
     /// * `dome_height` - Height of dome (for spherical spirograph)
     #[pyo3(signature = (spiro_type, outer_radius, radius_ratio, point_distance, rotations, resolution, hour, minute, distance, wave_amplitude=1.0, wave_frequency=5.0, dome_height=5.0))]
+    #[allow(clippy::too_many_arguments)]
     fn add_layer_at_clock(
         &mut self,
         spiro_type: &str,
@@ -450,7 +564,8 @@ impl GuillochePattern {
         let config = BaseDiamantConfig {
             num_circles,
             circle_radius,
-            resolution,
+            resolution: resolution.into(),
+            ..Default::default()
         };
         self.inner.add_diamant_at_polar(config, angle, distance)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
@@ -478,7 +593,8 @@ impl GuillochePattern {
         let config = BaseDiamantConfig {
             num_circles,
             circle_radius,
-            resolution,
+            resolution: resolution.into(),
+            ..Default::default()
         };
         self.inner.add_diamant_at_clock(config, hour, minute, distance)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
@@ -496,7 +612,8 @@ impl GuillochePattern {
     }
 
     /// Add a limacon layer positioned at a given angle and distance from origin
-    #[pyo3(signature = (num_curves, base_radius, amplitude, angle, distance, resolution=360))]
+    #[pyo3(signature = (num_curves, base_radius, amplitude, angle, distance, resolution=360, loop_mode="full"))]
+    #[allow(clippy::too_many_arguments)]
     fn add_limacon_at_polar(
         &mut self,
         num_curves: usize,
@@ -505,19 +622,16 @@ impl GuillochePattern {
         angle: f64,
         distance: f64,
         resolution: usize,
+        loop_mode: &str,
     ) -> PyResult<()> {
-        let config = BaseLimaconConfig {
-            num_curves,
-            base_radius,
-            amplitude,
-            resolution,
-        };
+        let config = limacon_config(num_curves, base_radius, amplitude, resolution, loop_mode)?;
         self.inner.add_limacon_at_polar(config, angle, distance)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
 
     /// Add a limacon layer positioned at a clock position (like hour hand)
-    #[pyo3(signature = (num_curves, base_radius, amplitude, hour, minute, distance, resolution=360))]
+    #[pyo3(signature = (num_curves, base_radius, amplitude, hour, minute, distance, resolution=360, loop_mode="full"))]
+    #[allow(clippy::too_many_arguments)]
     fn add_limacon_at_clock(
         &mut self,
         num_curves: usize,
@@ -527,17 +641,68 @@ impl GuillochePattern {
         minute: u32,
         distance: f64,
         resolution: usize,
+        loop_mode: &str,
     ) -> PyResult<()> {
-        let config = BaseLimaconConfig {
-            num_curves,
-            base_radius,
-            amplitude,
-            resolution,
-        };
+        let config = limacon_config(num_curves, base_radius, amplitude, resolution, loop_mode)?;
         self.inner.add_limacon_at_clock(config, hour, minute, distance)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
 
+    /// Add an engraved text layer
+    fn add_text_layer(&mut self, text: &EngravedTextLayer) {
+        self.inner.add_text_layer(text.inner.clone());
+    }
+
+    /// Add engraved single-stroke text, on an arc when `radius` is given
+    #[pyo3(signature = (text, height=3.0, radius=None, hour=12, minute=0, direction="clockwise", origin=(0.0, 0.0), angle=0.0, spacing=0.0))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_text(
+        &mut self,
+        text: &str,
+        height: f64,
+        radius: Option<f64>,
+        hour: u32,
+        minute: u32,
+        direction: &str,
+        origin: (f64, f64),
+        angle: f64,
+        spacing: f64,
+    ) -> PyResult<()> {
+        let config = text_config(text, height, radius, hour, minute, direction, origin, angle, spacing)?;
+        self.inner
+            .add_text(config)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add fixed polylines, as lists of (x, y) points, as a layer
+    fn add_imported_layer(&mut self, lines: Vec<Vec<(f64, f64)>>) {
+        self.inner.add_imported_layer(
+            lines
+                .into_iter()
+                .map(|line| line.into_iter().map(|(x, y)| Point2D::new(x, y)).collect())
+                .collect(),
+        );
+    }
+
+    /// Add arbitrary lines, as lists of (x, y) points, as a layer, such as
+    /// a layer's mirrored_x() or one copy from its replicated_radial()
+    fn add_raw_lines(&mut self, lines: Vec<Vec<(f64, f64)>>) {
+        self.inner.add_raw_lines(
+            lines
+                .into_iter()
+                .map(|line| line.into_iter().map(|(x, y)| Point2D::new(x, y)).collect())
+                .collect(),
+        );
+    }
+
+    /// Add the straight-line paths of an SVG file (or SVG text) as a layer;
+    /// returns a warning for each path skipped for having curves
+    fn add_imported_svg(&mut self, path_or_str: &str) -> PyResult<Vec<String>> {
+        self.inner
+            .add_imported_svg(path_or_str)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
     /// Add a paon (peacock pattern) layer to the pattern
     fn add_paon_layer(&mut self, paon: &PaonLayer) -> PyResult<()> {
         let new_layer = BasePaonLayer::new_with_center(
@@ -551,6 +716,7 @@ impl GuillochePattern {
 
     /// Add a paon layer positioned at a given angle and distance from origin
     #[pyo3(signature = (angle, distance, num_lines=500, radius=22.0, amplitude=0.035, wave_frequency=10.0, phase_rate=9.0, resolution=800, n_harmonics=3, fan_angle=4.0, vanishing_point=0.3))]
+    #[allow(clippy::too_many_arguments)]
     fn add_paon_at_polar(
         &mut self,
         angle: f64,
@@ -571,10 +737,13 @@ impl GuillochePattern {
             amplitude,
             wave_frequency,
             phase_rate,
-            resolution,
+            resolution: resolution.into(),
             n_harmonics,
             fan_angle,
             vanishing_point,
+            jitter: None,
+            origin: BasePaonOrigin::Bottom,
+            asymmetry: 0.0,
         };
         self.inner.add_paon_at_polar(config, angle, distance)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
@@ -582,6 +751,7 @@ impl GuillochePattern {
 
     /// Add a paon layer positioned at a clock position
     #[pyo3(signature = (hour, minute, distance, num_lines=500, radius=22.0, amplitude=0.035, wave_frequency=10.0, phase_rate=9.0, resolution=800, n_harmonics=3, fan_angle=4.0, vanishing_point=0.3))]
+    #[allow(clippy::too_many_arguments)]
     fn add_paon_at_clock(
         &mut self,
         hour: u32,
@@ -603,10 +773,13 @@ impl GuillochePattern {
             amplitude,
             wave_frequency,
             phase_rate,
-            resolution,
+            resolution: resolution.into(),
             n_harmonics,
             fan_angle,
             vanishing_point,
+            jitter: None,
+            origin: BasePaonOrigin::Bottom,
+            asymmetry: 0.0,
         };
         self.inner.add_paon_at_clock(config, hour, minute, distance)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
@@ -618,6 +791,29 @@ impl GuillochePattern {
         Ok(())
     }
 
+    /// Cut every layer at a circle of `radius` in the combined exports
+    fn clip_to_radius(&mut self, radius: f64) -> PyResult<()> {
+        self.inner.clip_to_radius(radius)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Stop clipping the combined exports
+    fn clear_clip(&mut self) {
+        self.inner.clear_clip();
+    }
+
+    /// Radius the combined exports are clipped to, or None
+    #[getter]
+    fn clip_radius(&self) -> Option<f64> {
+        self.inner.clip_radius()
+    }
+
+    /// Write each layer's configuration and geometry stats as data-*
+    /// attributes on its group in the combined SVG
+    fn set_svg_layer_data(&mut self, enabled: bool) {
+        self.inner.set_svg_layer_data(enabled);
+    }
+
     /// Export all layers to files
     #[pyo3(signature = (base_name, depth=0.1, base_thickness=2.0))]
     fn export_all(&self, base_name: &str, depth: f64, base_thickness: f64) -> PyResult<()> {
@@ -625,38 +821,91 @@ impl GuillochePattern {
             depth,
             base_thickness,
             tool_radius: 0.0,
+            ..Default::default()
         };
         self.inner.export_all(base_name, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
-    /// Export svg only
-    #[pyo3(signature = (filename))]
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
-        self.inner.export_combined_svg(filename)
+    /// Export svg only, simplifying the lines to within `tolerance` first
+    /// when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner.export_combined_svg_with_config(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
-    /// Export step only
-    #[pyo3(signature = (filename, depth=0.1))]
-    fn to_step(&self, filename: &str, depth: f64) -> PyResult<()> {
-        let config = BaseExportConfig {
-            depth,
-            base_thickness: 2.0,
-            tool_radius: 0.0,
-        };
+    /// Export every layer to HPGL for a pen plotter, in mm times `scale`;
+    /// layer `i` (in SVG order) is plotted with `pens[i]` (the last pen
+    /// repeats, pen 1 without any)
+    #[pyo3(signature = (filename, scale=1.0, pens=None))]
+    fn to_hpgl(&self, filename: &str, scale: f64, pens: Option<Vec<u32>>) -> PyResult<()> {
+        self.inner
+            .to_hpgl_with_pens(filename, scale, &pens.unwrap_or_default())
+            .map_err(export_err)
+    }
+
+    /// Rasterize the SVG export to an anti-aliased RGBA PNG file
+    #[pyo3(signature = (filename, width_px=1024, height_px=1024))]
+    fn to_png(&self, filename: &str, width_px: u32, height_px: u32) -> PyResult<()> {
+        self.inner.to_png(filename, width_px, height_px)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Rasterize the SVG export and return the PNG as bytes, e.g. for
+    /// `PIL.Image.open(io.BytesIO(...))`
+    #[pyo3(signature = (width_px=1024, height_px=1024))]
+    fn render_bytes<'py>(
+        &self,
+        py: Python<'py>,
+        width_px: u32,
+        height_px: u32,
+    ) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        let png = self
+            .inner
+            .render_raster(width_px, height_px)
+            .and_then(|image| image.to_png_bytes())
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(pyo3::types::PyBytes::new(py, &png))
+    }
+
+    /// Overlay this pattern on `other` in an SVG, coloring unchanged, moved,
+    /// added and removed lines; returns the counts as a dict
+    #[pyo3(signature = (other, filename, tolerance=0.01))]
+    fn diff_svg<'py>(
+        &self,
+        py: Python<'py>,
+        other: PyRef<'py, GuillochePattern>,
+        filename: &str,
+        tolerance: f64,
+    ) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+        diff_summary_dict(py, self.inner.diff_svg(&other.inner, filename, tolerance))
+    }
+
+    /// Export step only, with `depth` or an ExportConfig as `config`
+    #[pyo3(signature = (filename, depth=None, config=None))]
+    fn to_step(&self, filename: &str, depth: Option<f64>, config: Option<ExportConfig>) -> PyResult<()> {
+        let config = export_config(config, depth, None, None)?;
         self.inner.export_combined_step(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
-    /// Export stl only
-    #[pyo3(signature = (filename, depth=0.1, base_thickness=2.0))]
-    fn to_stl(&self, filename: &str, depth: f64, base_thickness: f64) -> PyResult<()> {
-        let config = BaseExportConfig {
-            depth,
-            base_thickness,
-            tool_radius: 0.0,
-        };
+    /// Export stl only, with `depth`, `base_thickness` and `tool_radius` or
+    /// an ExportConfig as `config`
+    #[pyo3(signature = (filename, depth=None, base_thickness=None, tool_radius=None, config=None))]
+    fn to_stl(
+        &self,
+        filename: &str,
+        depth: Option<f64>,
+        base_thickness: Option<f64>,
+        tool_radius: Option<f64>,
+        config: Option<ExportConfig>,
+    ) -> PyResult<()> {
+        let config = export_config(config, depth, base_thickness, tool_radius)?;
         self.inner.export_combined_stl(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
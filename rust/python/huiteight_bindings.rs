@@ -1,9 +1,14 @@
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use turtles::{
+    ExportConfig as BaseExportConfig,
     HuitEightConfig as BaseHuitEightConfig,
     HuitEightLayer as BaseHuitEightLayer,
+    PatternLayer,
 };
 
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
 /// Python wrapper for HuitEightLayer - creates figure-eight guilloché patterns
 /// using lemniscates of Bernoulli that pass through the centre, rotated
 /// around the centre
@@ -28,7 +33,7 @@ impl HuitEightLayer {
         let config = BaseHuitEightConfig {
             num_curves,
             scale,
-            resolution,
+            resolution: resolution.into(),
             num_clusters,
             cluster_spread,
         };
@@ -52,7 +57,7 @@ impl HuitEightLayer {
         let config = BaseHuitEightConfig {
             num_curves,
             scale,
-            resolution,
+            resolution: resolution.into(),
             num_clusters,
             cluster_spread,
         };
@@ -76,7 +81,7 @@ impl HuitEightLayer {
         let config = BaseHuitEightConfig {
             num_curves,
             scale,
-            resolution,
+            resolution: resolution.into(),
             num_clusters,
             cluster_spread,
         };
@@ -96,6 +101,7 @@ impl HuitEightLayer {
     /// * `resolution` - Number of points per curve (default: 360)
     #[staticmethod]
     #[pyo3(signature = (num_curves, scale, hour, minute, distance, resolution=360, num_clusters=0, cluster_spread=0.0))]
+    #[allow(clippy::too_many_arguments)]
     fn at_clock(
         num_curves: usize,
         scale: f64,
@@ -109,7 +115,7 @@ impl HuitEightLayer {
         let config = BaseHuitEightConfig {
             num_curves,
             scale,
-            resolution,
+            resolution: resolution.into(),
             num_clusters,
             cluster_spread,
         };
@@ -133,11 +139,60 @@ impl HuitEightLayer {
             .collect()
     }
 
-    /// Export the pattern to SVG format
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
         self.inner
-            .to_svg(filename)
-            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Export the pattern to SVG format
+    ///
+    /// With `streaming`, the curves are traced straight from the config one
+    /// at a time instead of taken from `generate()`, keeping memory bounded.
+    /// Without it, a positive `tolerance` simplifies the lines to within
+    /// that distance first
+    #[pyo3(signature = (filename, streaming=false, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, streaming: bool, tolerance: f64) -> PyResult<()> {
+        let result = if streaming {
+            if tolerance > 0.0 {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "tolerance cannot be combined with streaming",
+                ));
+            }
+            self.inner.to_svg_streaming(filename)
+        } else {
+            let config = BaseExportConfig {
+                simplify_tolerance: tolerance,
+                ..Default::default()
+            };
+            self.inner.to_svg_with_config(filename, &config)
+        };
+        result.map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
     /// Get the number of curves in the pattern
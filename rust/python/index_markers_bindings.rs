@@ -0,0 +1,202 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use turtles::{
+    ExportConfig as BaseExportConfig,
+    IndexConfig as BaseIndexConfig,
+    IndexMarkersLayer as BaseIndexMarkersLayer,
+    IndexShape as BaseIndexShape,
+    PatternLayer,
+};
+
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+/// A marker's strokes, each a list of (x, y) tuples
+type Strokes = Vec<Vec<(f64, f64)>>;
+
+/// Parse a marker shape name and its parameters
+///
+/// `shape` is "line" (`length`, `width`), "dot" (`radius`) or "roman"
+/// (`height`).
+pub(crate) fn index_shape(
+    shape: &str,
+    length: f64,
+    width: f64,
+    radius: f64,
+    height: f64,
+) -> PyResult<BaseIndexShape> {
+    match shape.to_lowercase().as_str() {
+        "line" => Ok(BaseIndexShape::Line { length, width }),
+        "dot" => Ok(BaseIndexShape::Dot { radius }),
+        "roman" => Ok(BaseIndexShape::RomanNumeralPlaceholder { height }),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(
+            "shape must be 'line', 'dot' or 'roman'",
+        )),
+    }
+}
+
+/// Build an index marker configuration from Python arguments
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn index_config(
+    inner_radius: f64,
+    outer_radius: f64,
+    shape: &str,
+    count: usize,
+    length: f64,
+    width: f64,
+    radius: f64,
+    height: f64,
+    exclude: Option<Vec<(u32, u32)>>,
+    resolution: usize,
+) -> PyResult<BaseIndexConfig> {
+    Ok(BaseIndexConfig {
+        shape: index_shape(shape, length, width, radius, height)?,
+        count,
+        inner_radius,
+        outer_radius,
+        exclude: exclude.unwrap_or_default(),
+        style: None,
+        resolution: resolution.into(),
+    })
+}
+
+/// Python wrapper for IndexMarkersLayer - hour or minute markers (batons,
+/// dots or Roman numerals) engraved as outlines
+#[pyclass]
+pub struct IndexMarkersLayer {
+    pub inner: BaseIndexMarkersLayer,
+}
+
+#[pymethods]
+impl IndexMarkersLayer {
+    /// Create index markers centered on the dial
+    ///
+    /// # Arguments
+    /// * `inner_radius` - Inner radius of the band holding the markers
+    /// * `outer_radius` - Outer radius of the band holding the markers
+    /// * `shape` - "line", "dot" or "roman" (default: "line")
+    /// * `count` - 12 for hours or 60 for minutes (default: 12)
+    /// * `length` - Baton length along the radius (default: 2.0)
+    /// * `width` - Baton width, 0 for a single stroke (default: 0.4)
+    /// * `radius` - Dot radius (default: 0.5)
+    /// * `height` - Numeral height (default: 2.0)
+    /// * `exclude` - List of (hour, minute) clock positions to leave empty
+    /// * `resolution` - Number of points around each dot (default: 32)
+    #[new]
+    #[pyo3(signature = (inner_radius, outer_radius, shape="line", count=12, length=2.0, width=0.4, radius=0.5, height=2.0, exclude=None, resolution=32))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        inner_radius: f64,
+        outer_radius: f64,
+        shape: &str,
+        count: usize,
+        length: f64,
+        width: f64,
+        radius: f64,
+        height: f64,
+        exclude: Option<Vec<(u32, u32)>>,
+        resolution: usize,
+    ) -> PyResult<Self> {
+        let config = index_config(inner_radius, outer_radius, shape, count, length, width, radius, height, exclude, resolution)?;
+        BaseIndexMarkersLayer::new(config)
+            .map(|inner| IndexMarkersLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Generate the marker outlines
+    fn generate(&mut self) {
+        self.inner.generate();
+    }
+
+    /// Export the markers to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner
+            .to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get the shape name: "line", "dot" or "roman"
+    #[getter]
+    fn shape(&self) -> &'static str {
+        match self.inner.config.shape {
+            BaseIndexShape::Line { .. } => "line",
+            BaseIndexShape::Dot { .. } => "dot",
+            BaseIndexShape::RomanNumeralPlaceholder { .. } => "roman",
+        }
+    }
+
+    /// Get the number of positions
+    #[getter]
+    fn count(&self) -> usize {
+        self.inner.config.count
+    }
+
+    /// Get the markers as (position, strokes) pairs, clockwise from
+    /// 12 o'clock; excluded positions are left out
+    fn get_markers(&self) -> Vec<(usize, Strokes)> {
+        self.inner
+            .markers()
+            .into_iter()
+            .map(|(i, strokes)| {
+                (i, strokes.iter().map(|s| s.iter().map(|p| (p.x, p.y)).collect()).collect())
+            })
+            .collect()
+    }
+
+    /// Get the generated lines as a list of point lists
+    /// Each line is a list of (x, y) tuples
+    fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "IndexMarkersLayer(inner_radius={}, outer_radius={}, shape={}, count={}, excluded={})",
+            self.inner.config.inner_radius,
+            self.inner.config.outer_radius,
+            self.shape(),
+            self.inner.config.count,
+            self.inner.config.exclude.len()
+        )
+    }
+}
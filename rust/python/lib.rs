@@ -1,27 +1,51 @@
 use pyo3::prelude::*;
 
+mod chapter_ring_bindings;
 mod diamant_bindings;
 mod draperie_bindings;
+mod engrave_text_bindings;
+mod envelope_bindings;
+mod export_bindings;
 mod clous_de_paris_bindings;
+mod cotes_de_geneve_bindings;
 mod cube_bindings;
+mod grain_de_riz_bindings;
 mod guilloche_bindings;
 mod huiteight_bindings;
+mod index_markers_bindings;
 mod limacon_bindings;
 mod paon_bindings;
+mod phyllotaxis_bindings;
+mod progressive_bindings;
 mod rose_engine_bindings;
+mod soleil_bindings;
 mod spirograph_bindings;
+mod tapisserie_bindings;
+mod vague_bindings;
 mod watch_face_bindings;
 
+pub use chapter_ring_bindings::ChapterRingLayer;
 pub use clous_de_paris_bindings::ClousDeParisLayer;
+pub use cotes_de_geneve_bindings::CotesDeGeneveLayer;
 pub use cube_bindings::CubeLayer;
 pub use diamant_bindings::DiamantLayer;
 pub use draperie_bindings::DraperieLayer;
+pub use engrave_text_bindings::EngravedTextLayer;
+pub use envelope_bindings::Envelope;
+pub use export_bindings::ExportConfig;
+pub use grain_de_riz_bindings::GrainDeRizLayer;
 pub use guilloche_bindings::{FlinqueLayer, GuillochePattern};
 pub use huiteight_bindings::HuitEightLayer;
+pub use index_markers_bindings::IndexMarkersLayer;
 pub use limacon_bindings::LimaconLayer;
 pub use paon_bindings::PaonLayer;
+pub use phyllotaxis_bindings::PhyllotaxisLayer;
+pub use progressive_bindings::ProgressiveHandle;
 pub use rose_engine_bindings::{CuttingBit, RoseEngineConfig, RoseEngineLathe, RoseEngineLatheRun, RosettePattern};
+pub use soleil_bindings::SoleilLayer;
 pub use spirograph_bindings::{HorizontalSpirograph, SphericalSpirograph, VerticalSpirograph};
+pub use tapisserie_bindings::TapisserieLayer;
+pub use vague_bindings::VagueLayer;
 pub use watch_face_bindings::WatchFace;
 
 #[pymodule]
@@ -55,9 +79,45 @@ fn turtles(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     // Cube (tumbling blocks) pattern layer
     m.add_class::<CubeLayer>().unwrap();
 
+    // Phyllotaxis (sunflower spiral) pattern layer
+    m.add_class::<PhyllotaxisLayer>().unwrap();
+
+    // Grain-de-riz (rice grain) pattern layer
+    m.add_class::<GrainDeRizLayer>().unwrap();
+
+    // Soleil (sunray) pattern layer
+    m.add_class::<SoleilLayer>().unwrap();
+
+    // Côtes de Genève (Geneva stripes) pattern layer
+    m.add_class::<CotesDeGeneveLayer>().unwrap();
+
+    // Tapisserie (tapestry, waffle) pattern layer
+    m.add_class::<TapisserieLayer>().unwrap();
+
+    // Vague (wave, flammes) pattern layer
+    m.add_class::<VagueLayer>().unwrap();
+
     // Limaçon pattern layer
     m.add_class::<LimaconLayer>().unwrap();
 
+    // Engraved single-stroke text layer
+    m.add_class::<EngravedTextLayer>().unwrap();
+
+    // Chapter ring (railroad minute track) layer
+    m.add_class::<ChapterRingLayer>().unwrap();
+
+    // Hour and minute index markers layer
+    m.add_class::<IndexMarkersLayer>().unwrap();
+
+    // Angle/radius weight for amplitude and opacity fades
+    m.add_class::<Envelope>().unwrap();
+
+    // Depth, base slab and tool radius for the 3D exports
+    m.add_class::<ExportConfig>().unwrap();
+
+    // Coarse-to-fine generation handle
+    m.add_class::<ProgressiveHandle>().unwrap();
+
     // Watch face
     m.add_class::<WatchFace>().unwrap();
 
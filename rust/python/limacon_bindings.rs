@@ -1,9 +1,41 @@
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use turtles::{
+    ExportConfig as BaseExportConfig,
     LimaconConfig as BaseLimaconConfig,
     LimaconLayer as BaseLimaconLayer,
+    LimaconLoopMode as BaseLimaconLoopMode,
+    PatternLayer,
 };
 
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+/// Parse a loop mode name ("full", "outer_only" or "inner_only")
+pub fn parse_loop_mode(loop_mode: &str) -> PyResult<BaseLimaconLoopMode> {
+    BaseLimaconLoopMode::from_name(&loop_mode.to_lowercase()).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(
+            "loop_mode must be 'full', 'outer_only' or 'inner_only'",
+        )
+    })
+}
+
+/// Build a LimaconConfig from Python arguments
+pub fn limacon_config(
+    num_curves: usize,
+    base_radius: f64,
+    amplitude: f64,
+    resolution: usize,
+    loop_mode: &str,
+) -> PyResult<BaseLimaconConfig> {
+    Ok(BaseLimaconConfig {
+        num_curves,
+        base_radius,
+        amplitude,
+        resolution: resolution.into(),
+        loop_mode: parse_loop_mode(loop_mode)?,
+    })
+}
+
 /// Python wrapper for LimaconLayer - creates limaçon guilloché patterns
 /// using the polar equation r = base_radius + amplitude * sin(θ + phase)
 /// This produces identical output to a rose engine with sinusoidal frequency=1
@@ -21,15 +53,11 @@ impl LimaconLayer {
     /// * `base_radius` - Base radius (distance from center when sin=0)
     /// * `amplitude` - Amplitude of sinusoidal modulation
     /// * `resolution` - Number of points per curve (default: 360)
+    /// * `loop_mode` - "full", "outer_only" (trim the inner loop) or "inner_only" (just the loop)
     #[new]
-    #[pyo3(signature = (num_curves, base_radius, amplitude, resolution=360))]
-    fn new(num_curves: usize, base_radius: f64, amplitude: f64, resolution: usize) -> PyResult<Self> {
-        let config = BaseLimaconConfig {
-            num_curves,
-            base_radius,
-            amplitude,
-            resolution,
-        };
+    #[pyo3(signature = (num_curves, base_radius, amplitude, resolution=360, loop_mode="full"))]
+    fn new(num_curves: usize, base_radius: f64, amplitude: f64, resolution: usize, loop_mode: &str) -> PyResult<Self> {
+        let config = limacon_config(num_curves, base_radius, amplitude, resolution, loop_mode)?;
         BaseLimaconLayer::new(config)
             .map(|inner| LimaconLayer { inner })
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
@@ -37,7 +65,7 @@ impl LimaconLayer {
 
     /// Create a limaçon layer with a custom center point
     #[staticmethod]
-    #[pyo3(signature = (num_curves, base_radius, amplitude, center_x, center_y, resolution=360))]
+    #[pyo3(signature = (num_curves, base_radius, amplitude, center_x, center_y, resolution=360, loop_mode="full"))]
     fn with_center(
         num_curves: usize,
         base_radius: f64,
@@ -45,13 +73,9 @@ impl LimaconLayer {
         center_x: f64,
         center_y: f64,
         resolution: usize,
+        loop_mode: &str,
     ) -> PyResult<Self> {
-        let config = BaseLimaconConfig {
-            num_curves,
-            base_radius,
-            amplitude,
-            resolution,
-        };
+        let config = limacon_config(num_curves, base_radius, amplitude, resolution, loop_mode)?;
         BaseLimaconLayer::new_with_center(config, center_x, center_y)
             .map(|inner| LimaconLayer { inner })
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
@@ -59,7 +83,7 @@ impl LimaconLayer {
 
     /// Create a limaçon layer positioned at a given angle and distance from origin
     #[staticmethod]
-    #[pyo3(signature = (num_curves, base_radius, amplitude, angle, distance, resolution=360))]
+    #[pyo3(signature = (num_curves, base_radius, amplitude, angle, distance, resolution=360, loop_mode="full"))]
     fn at_polar(
         num_curves: usize,
         base_radius: f64,
@@ -67,13 +91,9 @@ impl LimaconLayer {
         angle: f64,
         distance: f64,
         resolution: usize,
+        loop_mode: &str,
     ) -> PyResult<Self> {
-        let config = BaseLimaconConfig {
-            num_curves,
-            base_radius,
-            amplitude,
-            resolution,
-        };
+        let config = limacon_config(num_curves, base_radius, amplitude, resolution, loop_mode)?;
         BaseLimaconLayer::new_at_polar(config, angle, distance)
             .map(|inner| LimaconLayer { inner })
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
@@ -90,7 +110,8 @@ impl LimaconLayer {
     /// * `distance` - Distance from center of watch face to the subdial center
     /// * `resolution` - Number of points per curve (default: 360)
     #[staticmethod]
-    #[pyo3(signature = (num_curves, base_radius, amplitude, hour, minute, distance, resolution=360))]
+    #[pyo3(signature = (num_curves, base_radius, amplitude, hour, minute, distance, resolution=360, loop_mode="full"))]
+    #[allow(clippy::too_many_arguments)]
     fn at_clock(
         num_curves: usize,
         base_radius: f64,
@@ -99,27 +120,39 @@ impl LimaconLayer {
         minute: u32,
         distance: f64,
         resolution: usize,
+        loop_mode: &str,
     ) -> PyResult<Self> {
-        let config = BaseLimaconConfig {
-            num_curves,
-            base_radius,
-            amplitude,
-            resolution,
-        };
+        let config = limacon_config(num_curves, base_radius, amplitude, resolution, loop_mode)?;
         BaseLimaconLayer::new_at_clock(config, hour, minute, distance)
             .map(|inner| LimaconLayer { inner })
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
 
+    /// Create a layer of cardioids: limaçons whose amplitude equals their
+    /// base radius, with a cusp at the center
+    #[staticmethod]
+    #[pyo3(signature = (num_curves, radius, resolution=360))]
+    fn cardioid(num_curves: usize, radius: f64, resolution: usize) -> PyResult<Self> {
+        BaseLimaconLayer::new(BaseLimaconConfig::cardioid(num_curves, radius, resolution))
+            .map(|inner| LimaconLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
     /// Generate the limaçon pattern
     fn generate(&mut self) {
         self.inner.generate();
     }
 
-    /// Export the pattern to SVG format
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
+    /// Export the pattern to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
         self.inner
-            .to_svg(filename)
+            .to_svg_with_config(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
@@ -141,6 +174,12 @@ impl LimaconLayer {
         self.inner.config.amplitude
     }
 
+    /// Which part of each curve is drawn: "full", "outer_only" or "inner_only"
+    #[getter]
+    fn loop_mode(&self) -> &'static str {
+        self.inner.config.loop_mode.name()
+    }
+
     /// Get the center x coordinate
     #[getter]
     fn center_x(&self) -> f64 {
@@ -163,12 +202,44 @@ impl LimaconLayer {
             .collect()
     }
 
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
     fn __repr__(&self) -> String {
         format!(
-            "LimaconLayer(num_curves={}, base_radius={}, amplitude={}, center=({}, {}))",
+            "LimaconLayer(num_curves={}, base_radius={}, amplitude={}, loop_mode={}, center=({}, {}))",
             self.inner.config.num_curves,
             self.inner.config.base_radius,
             self.inner.config.amplitude,
+            self.inner.config.loop_mode.name(),
             self.inner.center_x,
             self.inner.center_y
         )
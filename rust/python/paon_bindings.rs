@@ -1,9 +1,40 @@
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use turtles::{
+    ExportConfig as BaseExportConfig,
     PaonConfig as BasePaonConfig,
     PaonLayer as BasePaonLayer,
+    PaonOrigin as BasePaonOrigin,
+    PatternLayer,
 };
 
+use crate::rose_engine_bindings::{checked_jitter, export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+use crate::progressive_bindings::{ProgressiveHandle, ProgressiveTarget};
+
+/// Parse a paon fan origin: "bottom", "top", "left", "right", or a turn
+/// from "bottom" in radians, clockwise on screen; None is "bottom"
+pub(crate) fn parse_paon_origin(origin: Option<&Bound<'_, PyAny>>) -> PyResult<BasePaonOrigin> {
+    let Some(origin) = origin else {
+        return Ok(BasePaonOrigin::Bottom);
+    };
+    let error = || {
+        pyo3::exceptions::PyValueError::new_err(
+            "origin must be 'bottom', 'top', 'left', 'right' or an angle in radians",
+        )
+    };
+    if let Ok(name) = origin.extract::<String>() {
+        return match name.to_lowercase().as_str() {
+            "bottom" => Ok(BasePaonOrigin::Bottom),
+            "top" => Ok(BasePaonOrigin::Top),
+            "left" => Ok(BasePaonOrigin::Left),
+            "right" => Ok(BasePaonOrigin::Right),
+            _ => Err(error()),
+        };
+    }
+    origin.extract::<f64>().map(BasePaonOrigin::Angle).map_err(|_| error())
+}
+
 /// Python wrapper for PaonLayer - creates peacock-feather guilloché patterns
 /// using a fan of lines emanating from 6 o'clock with zigzag oscillation
 #[pyclass]
@@ -26,6 +57,7 @@ impl PaonLayer {
     /// * `fan_angle` - Total angular spread in radians (~2.618 = 150°)
     #[new]
     #[pyo3(signature = (num_lines=500, radius=22.0, amplitude=0.035, wave_frequency=10.0, phase_rate=9.0, resolution=800, n_harmonics=3, fan_angle=4.0, vanishing_point=0.3))]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         num_lines: usize,
         radius: f64,
@@ -43,10 +75,13 @@ impl PaonLayer {
             amplitude,
             wave_frequency,
             phase_rate,
-            resolution,
+            resolution: resolution.into(),
             n_harmonics,
             fan_angle,
             vanishing_point,
+            jitter: None,
+            origin: BasePaonOrigin::Bottom,
+            asymmetry: 0.0,
         };
         BasePaonLayer::new(config)
             .map(|inner| PaonLayer { inner })
@@ -56,6 +91,7 @@ impl PaonLayer {
     /// Create a paon layer with a custom center point
     #[staticmethod]
     #[pyo3(signature = (center_x, center_y, num_lines=500, radius=22.0, amplitude=0.035, wave_frequency=10.0, phase_rate=9.0, resolution=800, n_harmonics=3, fan_angle=4.0, vanishing_point=0.3))]
+    #[allow(clippy::too_many_arguments)]
     fn with_center(
         center_x: f64,
         center_y: f64,
@@ -75,10 +111,13 @@ impl PaonLayer {
             amplitude,
             wave_frequency,
             phase_rate,
-            resolution,
+            resolution: resolution.into(),
             n_harmonics,
             fan_angle,
             vanishing_point,
+            jitter: None,
+            origin: BasePaonOrigin::Bottom,
+            asymmetry: 0.0,
         };
         BasePaonLayer::new_with_center(config, center_x, center_y)
             .map(|inner| PaonLayer { inner })
@@ -88,6 +127,7 @@ impl PaonLayer {
     /// Create a paon layer positioned at a given angle and distance from origin
     #[staticmethod]
     #[pyo3(signature = (angle, distance, num_lines=500, radius=22.0, amplitude=0.035, wave_frequency=10.0, phase_rate=9.0, resolution=800, n_harmonics=3, fan_angle=4.0, vanishing_point=0.3))]
+    #[allow(clippy::too_many_arguments)]
     fn at_polar(
         angle: f64,
         distance: f64,
@@ -107,10 +147,13 @@ impl PaonLayer {
             amplitude,
             wave_frequency,
             phase_rate,
-            resolution,
+            resolution: resolution.into(),
             n_harmonics,
             fan_angle,
             vanishing_point,
+            jitter: None,
+            origin: BasePaonOrigin::Bottom,
+            asymmetry: 0.0,
         };
         BasePaonLayer::new_at_polar(config, angle, distance)
             .map(|inner| PaonLayer { inner })
@@ -125,6 +168,7 @@ impl PaonLayer {
     /// * `distance` - Distance from center of watch face to the layer center
     #[staticmethod]
     #[pyo3(signature = (hour, minute, distance, num_lines=500, radius=22.0, amplitude=0.035, wave_frequency=10.0, phase_rate=9.0, resolution=800, n_harmonics=3, fan_angle=4.0, vanishing_point=0.3))]
+    #[allow(clippy::too_many_arguments)]
     fn at_clock(
         hour: u32,
         minute: u32,
@@ -145,10 +189,13 @@ impl PaonLayer {
             amplitude,
             wave_frequency,
             phase_rate,
-            resolution,
+            resolution: resolution.into(),
             n_harmonics,
             fan_angle,
             vanishing_point,
+            jitter: None,
+            origin: BasePaonOrigin::Bottom,
+            asymmetry: 0.0,
         };
         BasePaonLayer::new_at_clock(config, hour, minute, distance)
             .map(|inner| PaonLayer { inner })
@@ -160,10 +207,100 @@ impl PaonLayer {
         self.inner.generate();
     }
 
+    /// Wobble each line as if made by hand: RMS radial (mm) and angular
+    /// (radians) offsets drawn from `seed`; call generate() again
+    #[pyo3(signature = (radial_sigma, angular_sigma=0.0, seed=0))]
+    fn set_jitter(&mut self, radial_sigma: f64, angular_sigma: f64, seed: u64) -> PyResult<()> {
+        self.inner.config.jitter = Some(checked_jitter(radial_sigma, angular_sigma, seed)?);
+        Ok(())
+    }
+
+    /// Remove the jitter
+    fn clear_jitter(&mut self) {
+        self.inner.config.jitter = None;
+    }
+
+    /// The jitter as (radial_sigma, angular_sigma, seed), or None
+    #[getter]
+    fn jitter(&self) -> Option<(f64, f64, u64)> {
+        self.inner.config
+            .jitter
+            .map(|jitter| (jitter.radial_sigma, jitter.angular_sigma, jitter.seed))
+    }
+
+    /// Radiate the fan from `origin`: "bottom", "top", "left", "right", or
+    /// a turn from "bottom" in radians, clockwise on screen; call
+    /// generate() again
+    fn set_origin(&mut self, origin: &Bound<'_, PyAny>) -> PyResult<()> {
+        let mut config = self.inner.config.clone();
+        config.origin = parse_paon_origin(Some(origin))?;
+        config
+            .validate()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        self.inner.config = config;
+        Ok(())
+    }
+
+    /// The fan's turn from "bottom" in radians, clockwise on screen
+    #[getter]
+    fn origin_angle(&self) -> f64 {
+        self.inner.config.origin.angle()
+    }
+
+    /// Skew the fan's line spacing, between -1 and 1 exclusive; positive
+    /// values crowd the lines toward the right looking from the vanishing
+    /// point into the fan. Call generate() again
+    fn set_asymmetry(&mut self, asymmetry: f64) -> PyResult<()> {
+        let config = self.inner.config.clone().with_asymmetry(asymmetry);
+        config
+            .validate()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        self.inner.config = config;
+        Ok(())
+    }
+
+    /// The fan's line spacing skew
+    #[getter]
+    fn asymmetry(&self) -> f64 {
+        self.inner.config.asymmetry
+    }
+
+    /// Start a coarse-to-fine generation, e.g. `generate_progressive([0.1, 0.3, 1.0])`
+    ///
+    /// Returns a handle whose `advance()` computes the next level.
+    fn generate_progressive(slf: Py<Self>, levels: Vec<f32>) -> ProgressiveHandle {
+        ProgressiveHandle::new(ProgressiveTarget::Paon(slf), &levels)
+    }
+
     /// Export the pattern to SVG format
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
+    ///
+    /// With `streaming`, the lines are traced straight from the config one
+    /// at a time instead of taken from `generate()`, keeping memory bounded.
+    /// Without it, a positive `tolerance` simplifies the lines to within
+    /// that distance first
+    #[pyo3(signature = (filename, streaming=false, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, streaming: bool, tolerance: f64) -> PyResult<()> {
+        let result = if streaming {
+            if tolerance > 0.0 {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "tolerance cannot be combined with streaming",
+                ));
+            }
+            self.inner.to_svg_streaming(filename)
+        } else {
+            let config = BaseExportConfig {
+                simplify_tolerance: tolerance,
+                ..Default::default()
+            };
+            self.inner.to_svg_with_config(filename, &config)
+        };
+        result.map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Export the pattern to DXF as LWPOLYLINE entities in mm
+    fn to_dxf(&self, filename: &str) -> PyResult<()> {
         self.inner
-            .to_svg(filename)
+            .to_dxf(filename)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
@@ -176,6 +313,37 @@ impl PaonLayer {
             .collect()
     }
 
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
     /// Get the number of lines in the pattern
     #[getter]
     fn num_lines(&self) -> usize {
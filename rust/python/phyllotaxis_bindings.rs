@@ -0,0 +1,292 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use turtles::{
+    ExportConfig as BaseExportConfig,
+    PhylloEdge as BasePhylloEdge,
+    PhylloStyle as BasePhylloStyle,
+    PhyllotaxisConfig as BasePhyllotaxisConfig,
+    PhyllotaxisLayer as BasePhyllotaxisLayer,
+    PatternLayer,
+};
+
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+/// Build a PhyllotaxisConfig from Python arguments
+///
+/// `style` is "circle" (radius `size`), "tick" (length `size`) or
+/// "minilobe" (a `lobes`-lobed rosette of radius `size`); `edge` is
+/// "clip" or "drop".
+#[allow(clippy::too_many_arguments)]
+pub fn phyllotaxis_config(
+    num_points: usize,
+    scale_c: f64,
+    radius: f64,
+    style: &str,
+    size: f64,
+    lobes: usize,
+    inner_exclusion_radius: f64,
+    edge: &str,
+    resolution: usize,
+) -> PyResult<BasePhyllotaxisConfig> {
+    let point_style = match style.to_lowercase().as_str() {
+        "circle" => BasePhylloStyle::Circle { r: size },
+        "tick" => BasePhylloStyle::Tick { len: size },
+        "minilobe" | "mini_lobe" => BasePhylloStyle::MiniLobe { lobes, r: size },
+        _ => {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "style must be 'circle', 'tick' or 'minilobe'",
+            ))
+        }
+    };
+    let edge = match edge.to_lowercase().as_str() {
+        "clip" => BasePhylloEdge::Clip,
+        "drop" => BasePhylloEdge::Drop,
+        _ => {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "edge must be 'clip' or 'drop'",
+            ))
+        }
+    };
+    Ok(BasePhyllotaxisConfig {
+        num_points,
+        scale_c,
+        point_style,
+        inner_exclusion_radius,
+        radius,
+        edge,
+        resolution: resolution.into(),
+    })
+}
+
+/// Python wrapper for PhyllotaxisLayer - circles, ticks or tiny rosettes
+/// at the golden-angle (sunflower) spiral positions
+#[pyclass]
+pub struct PhyllotaxisLayer {
+    pub inner: BasePhyllotaxisLayer,
+}
+
+#[pymethods]
+impl PhyllotaxisLayer {
+    /// Create a new phyllotaxis layer centered at origin
+    ///
+    /// # Arguments
+    /// * `num_points` - Number of cells on the spiral
+    /// * `scale_c` - Spiral scale: cell n is scale_c·√n from the center
+    /// * `radius` - Radius of the circular clipping region in mm
+    /// * `style` - "circle", "tick" or "minilobe"
+    /// * `size` - Circle radius, tick length or rosette radius in mm
+    /// * `lobes` - Lobes of each "minilobe" rosette
+    /// * `inner_exclusion_radius` - No cells are centered closer than this
+    /// * `edge` - "clip" cells at the radius or "drop" those that cross it
+    /// * `resolution` - Points per circle or rosette loop
+    #[new]
+    #[pyo3(signature = (num_points=800, scale_c=0.75, radius=22.0, style="circle", size=0.25, lobes=6, inner_exclusion_radius=0.0, edge="clip", resolution=48))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        num_points: usize,
+        scale_c: f64,
+        radius: f64,
+        style: &str,
+        size: f64,
+        lobes: usize,
+        inner_exclusion_radius: f64,
+        edge: &str,
+        resolution: usize,
+    ) -> PyResult<Self> {
+        let config = phyllotaxis_config(num_points, scale_c, radius, style, size, lobes, inner_exclusion_radius, edge, resolution)?;
+        BasePhyllotaxisLayer::new(config)
+            .map(|inner| PhyllotaxisLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a phyllotaxis layer with a custom center point
+    #[staticmethod]
+    #[pyo3(signature = (center_x, center_y, num_points=800, scale_c=0.75, radius=22.0, style="circle", size=0.25, lobes=6, inner_exclusion_radius=0.0, edge="clip", resolution=48))]
+    #[allow(clippy::too_many_arguments)]
+    fn with_center(
+        center_x: f64,
+        center_y: f64,
+        num_points: usize,
+        scale_c: f64,
+        radius: f64,
+        style: &str,
+        size: f64,
+        lobes: usize,
+        inner_exclusion_radius: f64,
+        edge: &str,
+        resolution: usize,
+    ) -> PyResult<Self> {
+        let config = phyllotaxis_config(num_points, scale_c, radius, style, size, lobes, inner_exclusion_radius, edge, resolution)?;
+        BasePhyllotaxisLayer::new_with_center(config, center_x, center_y)
+            .map(|inner| PhyllotaxisLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a phyllotaxis layer positioned at a given angle and distance from origin
+    #[staticmethod]
+    #[pyo3(signature = (angle, distance, num_points=800, scale_c=0.75, radius=22.0, style="circle", size=0.25, lobes=6, inner_exclusion_radius=0.0, edge="clip", resolution=48))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_polar(
+        angle: f64,
+        distance: f64,
+        num_points: usize,
+        scale_c: f64,
+        radius: f64,
+        style: &str,
+        size: f64,
+        lobes: usize,
+        inner_exclusion_radius: f64,
+        edge: &str,
+        resolution: usize,
+    ) -> PyResult<Self> {
+        let config = phyllotaxis_config(num_points, scale_c, radius, style, size, lobes, inner_exclusion_radius, edge, resolution)?;
+        BasePhyllotaxisLayer::new_at_polar(config, angle, distance)
+            .map(|inner| PhyllotaxisLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a phyllotaxis layer positioned at a clock position (like hour hand)
+    ///
+    /// # Arguments
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face to the layer center
+    #[staticmethod]
+    #[pyo3(signature = (hour, minute, distance, num_points=800, scale_c=0.75, radius=22.0, style="circle", size=0.25, lobes=6, inner_exclusion_radius=0.0, edge="clip", resolution=48))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_clock(
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        num_points: usize,
+        scale_c: f64,
+        radius: f64,
+        style: &str,
+        size: f64,
+        lobes: usize,
+        inner_exclusion_radius: f64,
+        edge: &str,
+        resolution: usize,
+    ) -> PyResult<Self> {
+        let config = phyllotaxis_config(num_points, scale_c, radius, style, size, lobes, inner_exclusion_radius, edge, resolution)?;
+        BasePhyllotaxisLayer::new_at_clock(config, hour, minute, distance)
+            .map(|inner| PhyllotaxisLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Generate the phyllotaxis pattern
+    fn generate(&mut self) {
+        self.inner.generate();
+    }
+
+    /// Export the pattern to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner
+            .to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get all generated lines as list of list of (x, y) tuples
+    fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Indices of the cells drawn in the last generation
+    fn get_drawn_cells(&self) -> Vec<usize> {
+        self.inner.drawn_cells().to_vec()
+    }
+
+    /// Center (x, y) of cell `n`
+    fn cell_center(&self, n: usize) -> (f64, f64) {
+        let p = self.inner.cell_center(n);
+        (p.x, p.y)
+    }
+
+    /// Get the number of cells on the spiral
+    #[getter]
+    fn num_points(&self) -> usize {
+        self.inner.config.num_points
+    }
+
+    /// Get the spiral scale
+    #[getter]
+    fn scale_c(&self) -> f64 {
+        self.inner.config.scale_c
+    }
+
+    /// Get the radius
+    #[getter]
+    fn radius(&self) -> f64 {
+        self.inner.config.radius
+    }
+
+    /// Get the inner exclusion radius
+    #[getter]
+    fn inner_exclusion_radius(&self) -> f64 {
+        self.inner.config.inner_exclusion_radius
+    }
+
+    /// Get the center x coordinate
+    #[getter]
+    fn center_x(&self) -> f64 {
+        self.inner.center_x
+    }
+
+    /// Get the center y coordinate
+    #[getter]
+    fn center_y(&self) -> f64 {
+        self.inner.center_y
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PhyllotaxisLayer(num_points={}, scale_c={}, radius={}, center=({}, {}))",
+            self.inner.config.num_points,
+            self.inner.config.scale_c,
+            self.inner.config.radius,
+            self.inner.center_x,
+            self.inner.center_y
+        )
+    }
+}
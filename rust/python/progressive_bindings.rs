@@ -0,0 +1,63 @@
+use pyo3::prelude::*;
+use turtles::ProgressiveSchedule as BaseProgressiveSchedule;
+
+use crate::draperie_bindings::DraperieLayer;
+use crate::paon_bindings::PaonLayer;
+use crate::rose_engine_bindings::RoseEngineLatheRun;
+
+/// Pattern driven by a progressive handle
+pub(crate) enum ProgressiveTarget {
+    Draperie(Py<DraperieLayer>),
+    Paon(Py<PaonLayer>),
+    LatheRun(Py<RoseEngineLatheRun>),
+}
+
+/// Python handle for coarse-to-fine generation
+///
+/// Returned by `generate_progressive(levels)`. Each `advance()` computes
+/// the next detail level and replaces the pattern's lines; the last level
+/// is identical to a plain `generate()`.
+#[pyclass]
+pub struct ProgressiveHandle {
+    target: ProgressiveTarget,
+    schedule: BaseProgressiveSchedule,
+}
+
+impl ProgressiveHandle {
+    pub(crate) fn new(target: ProgressiveTarget, levels: &[f32]) -> Self {
+        ProgressiveHandle {
+            target,
+            schedule: BaseProgressiveSchedule::new(levels),
+        }
+    }
+}
+
+#[pymethods]
+impl ProgressiveHandle {
+    /// Compute the next level; returns False when all levels are done
+    fn advance(&mut self, py: Python<'_>) -> bool {
+        let schedule = &mut self.schedule;
+        match &self.target {
+            ProgressiveTarget::Draperie(layer) => schedule.advance(&mut layer.borrow_mut(py).inner),
+            ProgressiveTarget::Paon(layer) => schedule.advance(&mut layer.borrow_mut(py).inner),
+            ProgressiveTarget::LatheRun(run) => schedule.advance(&mut run.borrow_mut(py).inner),
+        }
+    }
+
+    /// Index of the last computed level, or None before the first advance
+    fn current_level(&self) -> Option<usize> {
+        self.schedule.current_level()
+    }
+
+    /// The sanitized level fractions (sorted, ending at 1.0)
+    #[getter]
+    fn levels(&self) -> Vec<f32> {
+        self.schedule.levels().to_vec()
+    }
+
+    /// True once the full-detail level has been computed
+    #[getter]
+    fn is_complete(&self) -> bool {
+        self.schedule.is_complete()
+    }
+}
@@ -1,4 +1,12 @@
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::envelope_bindings::Envelope;
+use crate::export_bindings::{export_config, ExportConfig};
+use crate::limacon_bindings::parse_loop_mode;
+use crate::paon_bindings::parse_paon_origin;
+use crate::progressive_bindings::{ProgressiveHandle, ProgressiveTarget};
+use crate::watch_face_bindings::{file_metadata, metadata_dict};
 use turtles::{
     RoseEngineLathe as BaseRoseEngineLathe,
     RoseEngineLatheRun as BaseRoseEngineLatheRun,
@@ -6,10 +14,134 @@ use turtles::{
     CuttingBit as BaseCuttingBit,
     RosettePattern as BaseRosettePattern,
     ExportConfig as BaseExportConfig,
+    RosetteMapping as BaseRosetteMapping,
+    OverlayOptions as BaseOverlayOptions,
+    BandSpec as BaseBandSpec,
+    PassOverride as BasePassOverride,
+    DiffSummary as BaseDiffSummary,
+    DepthPassOrder as BaseDepthPassOrder,
+    WearModel as BaseWearModel,
+    DashSpec as BaseDashSpec,
+    JitterConfig as BaseJitterConfig,
+    SurfaceMapping as BaseSurfaceMapping,
+    GcodeParams as BaseGcodeParams,
+    SvgColorMode as BaseSvgColorMode,
+    SvgStyle as BaseSvgStyle,
+    SpirographError,
+    GeometryStats,
+    Point2D,
 };
 
+/// Map an export error: invalid parameters (such as patterns that overflow
+/// the surface) are ValueErrors, everything else is an IOError
+pub(crate) fn export_err(e: SpirographError) -> PyErr {
+    match e {
+        SpirographError::InvalidParameter(_) => {
+            pyo3::exceptions::PyValueError::new_err(e.to_string())
+        }
+        _ => pyo3::exceptions::PyIOError::new_err(e.to_string()),
+    }
+}
+
+/// Wrap a rosette pattern, raising ValueError for zero counts or
+/// non-finite parameters
+fn checked_pattern(inner: BaseRosettePattern) -> PyResult<RosettePattern> {
+    inner
+        .validate()
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    Ok(RosettePattern { inner })
+}
+
+/// Build a jitter config, raising ValueError for negative or non-finite
+/// sigmas
+pub(crate) fn checked_jitter(
+    radial_sigma: f64,
+    angular_sigma: f64,
+    seed: u64,
+) -> PyResult<BaseJitterConfig> {
+    let jitter = BaseJitterConfig::new(radial_sigma, angular_sigma, seed);
+    jitter
+        .validate()
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    Ok(jitter)
+}
+
+/// Wrap a preset config, raising ValueError if its rosettes are invalid
+fn checked_config(inner: BaseRoseEngineConfig) -> PyResult<RoseEngineConfig> {
+    inner
+        .validate()
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    Ok(RoseEngineConfig { inner })
+}
+
+/// A polyline as (x, y) tuples
+pub(crate) type PointTuples = Vec<(f64, f64)>;
+
+/// Convert mapped 3D lines into lists of (x, y, z) tuples
+fn lines_3d_tuples(lines: Vec<Vec<turtles::Point3D>>) -> Vec<Vec<(f64, f64, f64)>> {
+    lines
+        .into_iter()
+        .map(|line| line.into_iter().map(|p| (p.x, p.y, p.z)).collect())
+        .collect()
+}
+
+/// Convert a diff result into a summary dict, mapping bad tolerances to
+/// ValueError and missing or unwritable output to IOError
+pub(crate) fn diff_summary_dict(
+    py: Python<'_>,
+    result: Result<BaseDiffSummary, SpirographError>,
+) -> PyResult<Bound<'_, PyDict>> {
+    let summary = result.map_err(|e| match e {
+        SpirographError::InvalidParameter(_) => {
+            pyo3::exceptions::PyValueError::new_err(e.to_string())
+        }
+        _ => pyo3::exceptions::PyIOError::new_err(e.to_string()),
+    })?;
+    let dict = PyDict::new(py);
+    dict.set_item("unchanged", summary.unchanged)?;
+    dict.set_item("moved", summary.moved)?;
+    dict.set_item("added", summary.added)?;
+    dict.set_item("removed", summary.removed)?;
+    Ok(dict)
+}
+
+/// Convert layer stats into a dict with min and max (x, y) corners,
+/// total_path_length, num_polylines and num_points, or None before the
+/// layer is generated
+pub(crate) fn stats_dict(
+    py: Python<'_>,
+    stats: Option<GeometryStats>,
+) -> PyResult<Option<Bound<'_, PyDict>>> {
+    let Some(stats) = stats else {
+        return Ok(None);
+    };
+    let dict = PyDict::new(py);
+    dict.set_item("min", (stats.min.x, stats.min.y))?;
+    dict.set_item("max", (stats.max.x, stats.max.y))?;
+    dict.set_item("total_path_length", stats.total_path_length)?;
+    dict.set_item("num_polylines", stats.num_polylines)?;
+    dict.set_item("num_points", stats.num_points)?;
+    Ok(Some(dict))
+}
+
+/// Lines as lists of (x, y) tuples
+pub(crate) fn point_lines(lines: Vec<Vec<Point2D>>) -> Vec<Vec<(f64, f64)>> {
+    lines
+        .into_iter()
+        .map(|line| line.into_iter().map(|p| (p.x, p.y)).collect())
+        .collect()
+}
+
+/// A layer transform's lines, raising as `export_err` does (an IOError
+/// before generate())
+pub(crate) fn transformed_lines(
+    lines: Result<Vec<Vec<Point2D>>, SpirographError>,
+) -> PyResult<Vec<Vec<(f64, f64)>>> {
+    lines.map(point_lines).map_err(export_err)
+}
+
 /// Python wrapper for RosettePattern
-#[pyclass]
+#[pyclass(from_py_object)]
 #[derive(Clone)]
 pub struct RosettePattern {
     pub(crate) inner: BaseRosettePattern,
@@ -28,74 +160,95 @@ impl RosettePattern {
     /// Create an elliptical pattern
     #[staticmethod]
     #[pyo3(signature = (eccentricity, rotation=0.0))]
-    fn elliptical(eccentricity: f64, rotation: f64) -> Self {
-        RosettePattern {
-            inner: BaseRosettePattern::Elliptical { eccentricity, rotation },
-        }
+    fn elliptical(eccentricity: f64, rotation: f64) -> PyResult<Self> {
+        checked_pattern(BaseRosettePattern::Elliptical { eccentricity, rotation })
     }
 
     /// Create a sinusoidal wave pattern
     #[staticmethod]
-    fn sinusoidal(frequency: f64) -> Self {
-        RosettePattern {
-            inner: BaseRosettePattern::Sinusoidal { frequency },
-        }
+    fn sinusoidal(frequency: f64) -> PyResult<Self> {
+        checked_pattern(BaseRosettePattern::Sinusoidal { frequency })
     }
 
     /// Create a multi-lobe rosette pattern
     #[staticmethod]
-    fn multi_lobe(lobes: usize) -> Self {
-        RosettePattern {
-            inner: BaseRosettePattern::MultiLobe { lobes },
-        }
+    fn multi_lobe(lobes: usize) -> PyResult<Self> {
+        checked_pattern(BaseRosettePattern::MultiLobe { lobes })
     }
 
     /// Create an epicycloid/rose curve pattern
     #[staticmethod]
-    fn epicycloid(petals: usize) -> Self {
-        RosettePattern {
-            inner: BaseRosettePattern::Epicycloid { petals },
-        }
+    fn epicycloid(petals: usize) -> PyResult<Self> {
+        checked_pattern(BaseRosettePattern::Epicycloid { petals })
     }
 
     /// Create a Huit-Eight (Figure-Eight) pattern
     #[staticmethod]
-    fn huit_eight(lobes: usize) -> Self {
-        RosettePattern {
-            inner: BaseRosettePattern::HuitEight { lobes },
-        }
+    fn huit_eight(lobes: usize) -> PyResult<Self> {
+        checked_pattern(BaseRosettePattern::HuitEight { lobes })
     }
 
     /// Create a Grain-de-Riz (Rice Grain) pattern
     #[staticmethod]
-    fn grain_de_riz(grain_size: f64, rows: usize) -> Self {
-        RosettePattern {
-            inner: BaseRosettePattern::GrainDeRiz { grain_size, rows },
-        }
+    fn grain_de_riz(grain_size: f64, rows: usize) -> PyResult<Self> {
+        checked_pattern(BaseRosettePattern::GrainDeRiz { grain_size, rows })
     }
 
     /// Create a Draperie (Drapery) pattern
     #[staticmethod]
     #[pyo3(signature = (frequency, wave_exponent=1))]
-    fn draperie(frequency: f64, wave_exponent: u32) -> Self {
-        RosettePattern {
-            inner: BaseRosettePattern::Draperie { frequency, wave_exponent },
-        }
+    fn draperie(frequency: f64, wave_exponent: u32) -> PyResult<Self> {
+        checked_pattern(BaseRosettePattern::Draperie { frequency, wave_exponent })
     }
 
     /// Create a Paon (Peacock) pattern
     #[staticmethod]
-    fn paon(frequency: f64) -> Self {
-        RosettePattern {
-            inner: BaseRosettePattern::Paon { frequency },
-        }
+    fn paon(frequency: f64) -> PyResult<Self> {
+        checked_pattern(BaseRosettePattern::Paon { frequency })
     }
 
     /// Create a Diamant (Diamond) pattern
     #[staticmethod]
-    fn diamant(divisions: usize) -> Self {
+    fn diamant(divisions: usize) -> PyResult<Self> {
+        checked_pattern(BaseRosettePattern::Diamant { divisions })
+    }
+
+    /// Create a custom pattern from a lookup table of displacements
+    /// sampled evenly around the circle
+    #[staticmethod]
+    fn custom(table: Vec<f64>) -> PyResult<Self> {
+        if table.is_empty() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "table must not be empty",
+            ));
+        }
+        Ok(RosettePattern {
+            inner: BaseRosettePattern::Custom {
+                samples: table.len(),
+                table,
+            },
+        })
+    }
+
+    /// Number of samples in a custom table (None for analytic patterns)
+    #[getter]
+    fn table_len(&self) -> Option<usize> {
+        self.inner.table_len()
+    }
+
+    /// Resample a custom table to `n` entries
+    fn resample_table(&self, n: usize) -> Self {
         RosettePattern {
-            inner: BaseRosettePattern::Diamant { divisions },
+            inner: self.inner.clone().resample_table(n),
+        }
+    }
+
+    /// Smooth a custom table, pinning corners sharper than
+    /// `preserve_corners_deg`
+    #[pyo3(signature = (window, preserve_corners_deg=30.0))]
+    fn smoothed(&self, window: usize, preserve_corners_deg: f64) -> Self {
+        RosettePattern {
+            inner: self.inner.clone().smoothed(window, preserve_corners_deg),
         }
     }
 
@@ -137,7 +290,7 @@ impl RosettePattern {
 }
 
 /// Python wrapper for CuttingBit
-#[pyclass]
+#[pyclass(from_py_object)]
 #[derive(Clone)]
 pub struct CuttingBit {
     pub(crate) inner: BaseCuttingBit,
@@ -187,6 +340,11 @@ impl CuttingBit {
         self.inner.depth
     }
 
+    /// Width of the cut at the surface with the tip `depth` mm below it
+    fn kerf_at_depth(&self, depth: f64) -> f64 {
+        self.inner.kerf_at_depth(depth)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "CuttingBit(width={}, depth={})",
@@ -220,7 +378,7 @@ impl RoseEngineConfig {
 
     /// Set the resolution (number of points)
     fn set_resolution(&mut self, resolution: usize) {
-        self.inner.resolution = resolution;
+        self.inner.resolution = resolution.into();
     }
 
     /// Add a secondary rosette for compound motion
@@ -233,36 +391,156 @@ impl RoseEngineConfig {
         self.inner.with_depth_modulation(amplitude, frequency);
     }
 
+    /// Cut into a cylinder about the Z axis (e.g. a case band)
+    ///
+    /// The spindle angle wraps around the cylinder and the pattern radius
+    /// becomes the height along it, from 0 to `height`.
+    fn set_cylinder(&mut self, radius: f64, height: f64) {
+        self.inner.with_surface(BaseSurfaceMapping::Cylinder { radius, height });
+    }
+
+    /// Cut into a cone about the Z axis with radius `r_bottom` at z = 0
+    fn set_cone(&mut self, r_top: f64, r_bottom: f64, height: f64) {
+        self.inner.with_surface(BaseSurfaceMapping::Cone { r_top, r_bottom, height });
+    }
+
+    /// Cut into a flat dial (the default)
+    fn set_flat(&mut self) {
+        self.inner.with_surface(BaseSurfaceMapping::Flat);
+    }
+
+    /// Weight the rosette displacement by an Envelope of spindle angle and base radius
+    fn set_amplitude_envelope(&mut self, envelope: Envelope) {
+        self.inner.with_amplitude_envelope(envelope.inner);
+    }
+
+    /// Remove the amplitude envelope
+    fn clear_amplitude_envelope(&mut self) {
+        self.inner.amplitude_envelope = None;
+    }
+
+    /// Cut each pass of a run as `dots_per_cycle` dots per revolution of the
+    /// rosette phase, each covering `duty` of its period (pointillé)
+    fn set_dash_by_phase(&mut self, dots_per_cycle: usize, duty: f64) -> PyResult<()> {
+        let dash = BaseDashSpec::new(dots_per_cycle, duty);
+        dash.validate()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        self.inner.with_dash_by_phase(dash);
+        Ok(())
+    }
+
+    /// Cut whole passes again
+    fn clear_dash_by_phase(&mut self) {
+        self.inner.dash_by_phase = None;
+    }
+
+    /// Wobble each pass's tool path as if made by hand: RMS radial (mm) and angular
+    /// (radians) offsets drawn from `seed`; call generate() again
+    #[pyo3(signature = (radial_sigma, angular_sigma=0.0, seed=0))]
+    fn set_jitter(&mut self, radial_sigma: f64, angular_sigma: f64, seed: u64) -> PyResult<()> {
+        self.inner.jitter = Some(checked_jitter(radial_sigma, angular_sigma, seed)?);
+        Ok(())
+    }
+
+    /// Remove the jitter
+    fn clear_jitter(&mut self) {
+        self.inner.jitter = None;
+    }
+
+    /// The jitter as (radial_sigma, angular_sigma, seed), or None
+    #[getter]
+    fn jitter(&self) -> Option<(f64, f64, u64)> {
+        self.inner
+            .jitter
+            .map(|jitter| (jitter.radial_sigma, jitter.angular_sigma, jitter.seed))
+    }
+
+    /// Lower a concentric run's ring amplitudes so no ring crosses the centre
+    fn set_clamp_amplitude(&mut self, clamp: bool) {
+        self.inner.clamp_amplitude = clamp;
+    }
+
+    #[getter]
+    fn clamp_amplitude(&self) -> bool {
+        self.inner.clamp_amplitude
+    }
+
+    /// Largest amplitude (mm) keeping the cut at or outside the centre (inf if unbounded)
+    fn max_safe_amplitude(&self) -> f64 {
+        self.inner.max_safe_amplitude()
+    }
+
+    /// Set the rosette phase offset (radians)
+    fn set_phase(&mut self, phase: f64) -> PyResult<()> {
+        if !phase.is_finite() {
+            return Err(pyo3::exceptions::PyValueError::new_err("phase must be finite"));
+        }
+        self.inner.phase = phase;
+        Ok(())
+    }
+
+    /// Set the spindle angle (radians) the cut starts at; it must stay below the end angle
+    fn set_start_angle(&mut self, angle: f64) -> PyResult<()> {
+        self.set_angle_range(angle, self.inner.end_angle)
+    }
+
+    /// Set the spindle angle (radians) the cut ends at; it must stay past the start angle
+    fn set_end_angle(&mut self, angle: f64) -> PyResult<()> {
+        self.set_angle_range(self.inner.start_angle, angle)
+    }
+
+    /// Set both spindle angles (radians) at once, for ranges that move past the current ones
+    fn set_angle_range(&mut self, start_angle: f64, end_angle: f64) -> PyResult<()> {
+        if !(start_angle.is_finite() && end_angle.is_finite() && end_angle > start_angle) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "end_angle must be finite and greater than start_angle",
+            ));
+        }
+        self.inner.start_angle = start_angle;
+        self.inner.end_angle = end_angle;
+        Ok(())
+    }
+
+    /// Repeat the sweep from start to end angle this many times as one continuous cut
+    fn set_revolutions(&mut self, revolutions: usize) -> PyResult<()> {
+        if revolutions == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("revolutions must be at least 1"));
+        }
+        self.inner.revolutions = revolutions;
+        Ok(())
+    }
+
+    /// Move the cutter in towards the centre by `feed` mm per spindle turn, cutting a spiral
+    fn set_radial_feed_per_rev(&mut self, feed: f64) -> PyResult<()> {
+        if !feed.is_finite() {
+            return Err(pyo3::exceptions::PyValueError::new_err("radial_feed_per_rev must be finite"));
+        }
+        self.inner.radial_feed_per_rev = feed;
+        Ok(())
+    }
+
     /// Classic multi-lobe pattern preset
     #[staticmethod]
-    fn classic_multi_lobe(base_radius: f64, lobes: usize, amplitude: f64) -> Self {
-        RoseEngineConfig {
-            inner: BaseRoseEngineConfig::classic_multi_lobe(base_radius, lobes, amplitude),
-        }
+    fn classic_multi_lobe(base_radius: f64, lobes: usize, amplitude: f64) -> PyResult<Self> {
+        checked_config(BaseRoseEngineConfig::classic_multi_lobe(base_radius, lobes, amplitude))
     }
 
     /// Sunburst pattern preset
     #[staticmethod]
-    fn sunburst(base_radius: f64, rays: usize, amplitude: f64) -> Self {
-        RoseEngineConfig {
-            inner: BaseRoseEngineConfig::sunburst(base_radius, rays, amplitude),
-        }
+    fn sunburst(base_radius: f64, rays: usize, amplitude: f64) -> PyResult<Self> {
+        checked_config(BaseRoseEngineConfig::sunburst(base_radius, rays, amplitude))
     }
 
     /// Wave pattern preset
     #[staticmethod]
-    fn wave(base_radius: f64, frequency: f64, amplitude: f64) -> Self {
-        RoseEngineConfig {
-            inner: BaseRoseEngineConfig::wave(base_radius, frequency, amplitude),
-        }
+    fn wave(base_radius: f64, frequency: f64, amplitude: f64) -> PyResult<Self> {
+        checked_config(BaseRoseEngineConfig::wave(base_radius, frequency, amplitude))
     }
 
     /// Rose curve pattern preset
     #[staticmethod]
-    fn rose_curve(base_radius: f64, petals: usize, amplitude: f64) -> Self {
-        RoseEngineConfig {
-            inner: BaseRoseEngineConfig::rose_curve(base_radius, petals, amplitude),
-        }
+    fn rose_curve(base_radius: f64, petals: usize, amplitude: f64) -> PyResult<Self> {
+        checked_config(BaseRoseEngineConfig::rose_curve(base_radius, petals, amplitude))
     }
 
     /// Compound pattern preset
@@ -273,48 +551,47 @@ impl RoseEngineConfig {
         primary_amplitude: f64,
         secondary_frequency: f64,
         secondary_amplitude: f64,
-    ) -> Self {
-        RoseEngineConfig {
-            inner: BaseRoseEngineConfig::compound(
-                base_radius,
-                primary_lobes,
-                primary_amplitude,
-                secondary_frequency,
-                secondary_amplitude,
-            ),
-        }
+    ) -> PyResult<Self> {
+        checked_config(BaseRoseEngineConfig::compound(
+            base_radius,
+            primary_lobes,
+            primary_amplitude,
+            secondary_frequency,
+            secondary_amplitude,
+        ))
     }
 
     /// Huit-Eight (Figure-Eight) pattern preset
     #[staticmethod]
-    fn huit_eight(base_radius: f64, amplitude: f64) -> Self {
-        RoseEngineConfig {
-            inner: BaseRoseEngineConfig::huit_eight(base_radius, amplitude),
-        }
+    fn huit_eight(base_radius: f64, amplitude: f64) -> PyResult<Self> {
+        checked_config(BaseRoseEngineConfig::huit_eight(base_radius, amplitude))
     }
 
     /// Grain-de-Riz (Rice Grain) pattern preset
     #[staticmethod]
-    fn grain_de_riz(base_radius: f64, grain_size: f64, amplitude: f64) -> Self {
-        RoseEngineConfig {
-            inner: BaseRoseEngineConfig::grain_de_riz(base_radius, grain_size, amplitude),
-        }
+    fn grain_de_riz(base_radius: f64, grain_size: f64, amplitude: f64) -> PyResult<Self> {
+        checked_config(BaseRoseEngineConfig::grain_de_riz(base_radius, grain_size, amplitude))
     }
 
     /// Draperie (Drapery) pattern preset
     #[staticmethod]
-    fn draperie(base_radius: f64, wave_frequency: f64, amplitude: f64) -> Self {
-        RoseEngineConfig {
-            inner: BaseRoseEngineConfig::draperie(base_radius, wave_frequency, amplitude),
-        }
+    fn draperie(base_radius: f64, wave_frequency: f64, amplitude: f64) -> PyResult<Self> {
+        checked_config(BaseRoseEngineConfig::draperie(base_radius, wave_frequency, amplitude))
     }
 
     /// Diamant (Diamond) pattern preset
     #[staticmethod]
-    fn diamant(base_radius: f64, divisions: usize, amplitude: f64) -> Self {
-        RoseEngineConfig {
-            inner: BaseRoseEngineConfig::diamant(base_radius, divisions, amplitude),
-        }
+    fn diamant(base_radius: f64, divisions: usize, amplitude: f64) -> PyResult<Self> {
+        checked_config(BaseRoseEngineConfig::diamant(base_radius, divisions, amplitude))
+    }
+
+    /// Default rosette cut only from start_deg to end_deg (degrees), e.g.
+    /// 180 to 360 for the top half of the dial as previewed
+    #[staticmethod]
+    fn partial_arc(base_radius: f64, amplitude: f64, start_deg: f64, end_deg: f64) -> PyResult<Self> {
+        let config = BaseRoseEngineConfig::partial_arc(base_radius, amplitude, start_deg, end_deg)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        checked_config(config)
     }
 
     #[getter]
@@ -322,6 +599,21 @@ impl RoseEngineConfig {
         self.inner.base_radius
     }
 
+    #[getter]
+    fn phase(&self) -> f64 {
+        self.inner.phase
+    }
+
+    #[getter]
+    fn start_angle(&self) -> f64 {
+        self.inner.start_angle
+    }
+
+    #[getter]
+    fn end_angle(&self) -> f64 {
+        self.inner.end_angle
+    }
+
     #[getter]
     fn amplitude(&self) -> f64 {
         self.inner.amplitude
@@ -329,7 +621,17 @@ impl RoseEngineConfig {
 
     #[getter]
     fn resolution(&self) -> usize {
-        self.inner.resolution
+        self.inner.resolution.get()
+    }
+
+    #[getter]
+    fn revolutions(&self) -> usize {
+        self.inner.revolutions
+    }
+
+    #[getter]
+    fn radial_feed_per_rev(&self) -> f64 {
+        self.inner.radial_feed_per_rev
     }
 
     fn __repr__(&self) -> String {
@@ -381,33 +683,169 @@ impl RoseEngineLathe {
         self.inner.generate();
     }
 
-    /// Export pattern as SVG
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
-        self.inner.to_svg(filename)
-            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    /// Generate, raising ValueError if the configuration is invalid
+    fn try_generate(&mut self) -> PyResult<()> {
+        self.inner
+            .try_generate()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
 
-    /// Export pattern as STL file
-    #[pyo3(signature = (filename, depth=0.1, base_thickness=2.0))]
-    fn to_stl(&self, filename: &str, depth: f64, base_thickness: f64) -> PyResult<()> {
+    /// Fit the generated tool path with arcs within `tolerance`, so
+    /// to_gcode cuts G2/G3 arcs and G1 lines where no arc fits
+    fn fit_arcs(&mut self, tolerance: f64) -> PyResult<()> {
+        self.inner
+            .fit_arcs(tolerance)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Fitted arcs as (center_x, center_y, radius, start_angle, end_angle)
+    /// tuples, angles in radians; empty until fit_arcs is called
+    #[getter]
+    fn arcs(&self) -> Vec<(f64, f64, f64, f64, f64)> {
+        self.inner
+            .tool_path()
+            .arcs
+            .iter()
+            .map(|arc| (arc.center.x, arc.center.y, arc.radius, arc.start_angle, arc.end_angle))
+            .collect()
+    }
+
+    /// Export pattern as SVG, simplifying the lines to within `tolerance`
+    /// first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
         let config = BaseExportConfig {
-            depth,
-            base_thickness,
-            tool_radius: 0.0,
+            simplify_tolerance: tolerance,
+            ..Default::default()
         };
+        self.inner.to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Export the tool path as SVG, grayed and widened by the depth map
+    fn to_svg_shaded(&self, filename: &str) -> PyResult<()> {
+        self.inner.to_svg_shaded(filename)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Export the tool path as G-code
+    ///
+    /// Rates are in mm/min; the tool steps down at most `depth_per_pass`
+    /// mm at a time and rapids at `safe_z` between cuts
+    #[pyo3(signature = (filename, feed_rate=300.0, plunge_rate=60.0, safe_z=2.0, depth_per_pass=0.1, spindle_speed=12000.0))]
+    fn to_gcode(&self, filename: &str, feed_rate: f64, plunge_rate: f64, safe_z: f64, depth_per_pass: f64, spindle_speed: f64) -> PyResult<()> {
+        let params = BaseGcodeParams::new(feed_rate, plunge_rate, safe_z, depth_per_pass, spindle_speed);
+        self.inner.to_gcode(filename, &params).map_err(export_err)
+    }
+
+    /// Export pattern as STL file
+    ///
+    /// Grooves are `depth` deep over a circular base slab `base_thickness`
+    /// thick (0 for no slab), with the path offset `tool_radius` outwards
+    /// (inwards when negative) for the cutter; all three may come from an
+    /// ExportConfig as `config`
+    #[pyo3(signature = (filename, depth=None, base_thickness=None, tool_radius=None, config=None))]
+    fn to_stl(
+        &self,
+        filename: &str,
+        depth: Option<f64>,
+        base_thickness: Option<f64>,
+        tool_radius: Option<f64>,
+        config: Option<ExportConfig>,
+    ) -> PyResult<()> {
+        let config = export_config(config, depth, base_thickness, tool_radius)?;
         self.inner.to_stl(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
     /// Export pattern as STEP file
+    #[pyo3(signature = (filename, depth=None, config=None))]
+    fn to_step(&self, filename: &str, depth: Option<f64>, config: Option<ExportConfig>) -> PyResult<()> {
+        let config = export_config(config, depth, None, None)?;
+        self.inner.to_step(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get the tool center path as a list of (x, y) tuples
+    fn get_tool_path(&self) -> Vec<(f64, f64)> {
+        self.inner.tool_path().center_line.iter().map(|p| (p.x, p.y)).collect()
+    }
+
+    /// Get the left and right edges cut by the bit as two lists of (x, y)
+    /// tuples; both are empty before generate()
+    fn get_cut_edges(&self) -> (PointTuples, PointTuples) {
+        let edges = &self.inner.tool_path().cut_edges;
+        let edge = |i: usize| -> PointTuples {
+            edges.get(i).map(|e| e.iter().map(|p| (p.x, p.y)).collect()).unwrap_or_default()
+        };
+        (edge(0), edge(1))
+    }
+
+    /// Get the cut width at each tool path point with depth modulation on;
+    /// empty otherwise
+    fn get_kerf(&self) -> Vec<f64> {
+        self.inner.tool_path().kerf.clone()
+    }
+
+    /// Get the rendered lines as list of list of (x, y) tuples
+    fn get_rendered_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .rendered_output()
+            .lines
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Get the tool path at `n_frames` rosette phases from `phase_start` to
+    /// `phase_end` (radians, both included), one list of (x, y) tuples per frame
+    #[pyo3(signature = (n_frames, phase_start=0.0, phase_end=std::f64::consts::TAU))]
+    fn frames_phase_sweep(&self, n_frames: usize, phase_start: f64, phase_end: f64) -> PyResult<Vec<PointTuples>> {
+        let frames = self.inner
+            .frames_phase_sweep(n_frames, (phase_start, phase_end))
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(frames
+            .iter()
+            .map(|frame| frame.iter().map(|p| (p.x, p.y)).collect())
+            .collect())
+    }
+
+    /// Get the rendered lines mapped onto the configured surface as (x, y, z) tuples
+    fn get_lines_3d(&self) -> Vec<Vec<(f64, f64, f64)>> {
+        lines_3d_tuples(self.inner.lines_3d())
+    }
+
+    /// Export the tool path cut `depth` into the configured surface as STL
     #[pyo3(signature = (filename, depth=0.1))]
-    fn to_step(&self, filename: &str, depth: f64) -> PyResult<()> {
+    fn to_stl_3d(&self, filename: &str, depth: f64) -> PyResult<()> {
         let config = BaseExportConfig {
             depth,
-            base_thickness: 2.0,
-            tool_radius: 0.0,
+            ..Default::default()
         };
-        self.inner.to_step(filename, &config)
+        self.inner.to_stl_3d(filename, &config).map_err(export_err)
+    }
+
+    /// Simulate the running engine at uniform spindle steps
+    ///
+    /// Returns `steps + 1` dicts with `spindle_angle`, `workpiece_rotation`
+    /// (radians), `tool_contact_point` (x, y) in the machine frame and
+    /// `cut_so_far_end_index` into the tool path.
+    fn simulate<'py>(&self, py: Python<'py>, steps: usize) -> PyResult<Bound<'py, PyList>> {
+        let frames = PyList::empty(py);
+        for frame in self.inner.simulate(steps) {
+            let f = PyDict::new(py);
+            f.set_item("spindle_angle", frame.spindle_angle)?;
+            f.set_item("workpiece_rotation", frame.workpiece_rotation)?;
+            f.set_item("tool_contact_point", (frame.tool_contact_point.x, frame.tool_contact_point.y))?;
+            f.set_item("cut_so_far_end_index", frame.cut_so_far_end_index)?;
+            frames.append(f)?;
+        }
+        Ok(frames)
+    }
+
+    /// Write one SVG per simulation frame (`frame_0000.svg`, ...) into `dir`
+    fn simulation_to_svg_frames(&self, dir: &str, steps: usize) -> PyResult<()> {
+        self.inner.simulation_to_svg_frames(dir, steps)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
@@ -436,6 +874,8 @@ impl RoseEngineLatheRun {
     /// * `bit` - Cutting bit configuration
     /// * `num_passes` - Number of rotational passes (typically 8-24)
     /// * `segments_per_pass` - Number of arc segments per pass (default 24, creates gaps)
+    /// * `segment_draw_ratio` - Drawn fraction of each segment, in (0, 1] (default 0.7)
+    /// * `segment_phase_offset` - Per-pass shift of segment starts, in segments (default 0.0)
     ///
     /// # Example
     /// ```python
@@ -450,7 +890,8 @@ impl RoseEngineLatheRun {
     /// run.to_svg("pattern.svg")
     /// ```
     #[new]
-    #[pyo3(signature = (config, bit, num_passes, segments_per_pass=24, radius_step=0.0, phase_shift=0.0, phase_oscillations=1.0, circular_phase=0.0, phase_exponent=1))]
+    #[pyo3(signature = (config, bit, num_passes, segments_per_pass=24, radius_step=0.0, phase_shift=0.0, phase_oscillations=1.0, circular_phase=0.0, phase_exponent=1, segment_draw_ratio=0.7, segment_phase_offset=0.0))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         config: PyRef<RoseEngineConfig>,
         bit: PyRef<CuttingBit>,
@@ -461,6 +902,8 @@ impl RoseEngineLatheRun {
         phase_oscillations: f64,
         circular_phase: f64,
         phase_exponent: u32,
+        segment_draw_ratio: f64,
+        segment_phase_offset: f64,
     ) -> PyResult<Self> {
         BaseRoseEngineLatheRun::new_with_segments(
             config.inner.clone(),
@@ -470,13 +913,14 @@ impl RoseEngineLatheRun {
             0.0,
             0.0,
         )
-        .map(|mut inner| {
+        .and_then(|mut inner| {
+            inner.set_segment_gaps(segment_draw_ratio, segment_phase_offset)?;
             inner.radius_step = radius_step;
             inner.phase_shift = phase_shift;
             inner.phase_oscillations = phase_oscillations;
             inner.circular_phase = circular_phase;
             inner.phase_exponent = phase_exponent;
-            RoseEngineLatheRun { inner }
+            Ok(RoseEngineLatheRun { inner })
         })
         .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
@@ -511,6 +955,7 @@ impl RoseEngineLatheRun {
     /// pattern, amplitude, phase alignment, and phase shape function.
     #[staticmethod]
     #[pyo3(signature = (num_rings=96, base_radius=22.0, radius_step=0.44, wave_frequency=12.0, phase_shift=None, phase_oscillations=2.5, resolution=1500, phase_exponent=3, wave_exponent=1, circular_phase=2.0, center_x=0.0, center_y=0.0))]
+    #[allow(clippy::too_many_arguments)]
     fn draperie(
         num_rings: usize,
         base_radius: f64,
@@ -548,9 +993,11 @@ impl RoseEngineLatheRun {
     /// output to the mathematical PaonLayer.
     ///
     /// This configures the rose engine lathe run in linear-pass mode with
-    /// fan lines emanating from 6 o'clock and zigzag oscillation.
+    /// fan lines emanating from 6 o'clock and zigzag oscillation; `origin`
+    /// and `asymmetry` turn and skew the fan as for PaonLayer.
     #[staticmethod]
-    #[pyo3(signature = (num_lines=500, radius=22.0, amplitude=0.035, wave_frequency=10.0, phase_rate=9.0, resolution=800, n_harmonics=3, fan_angle=4.0, vanishing_point=0.3, center_x=0.0, center_y=0.0))]
+    #[pyo3(signature = (num_lines=500, radius=22.0, amplitude=0.035, wave_frequency=10.0, phase_rate=9.0, resolution=800, n_harmonics=3, fan_angle=4.0, vanishing_point=0.3, center_x=0.0, center_y=0.0, origin=None, asymmetry=0.0))]
+    #[allow(clippy::too_many_arguments)]
     fn paon(
         num_lines: usize,
         radius: f64,
@@ -563,6 +1010,8 @@ impl RoseEngineLatheRun {
         vanishing_point: f64,
         center_x: f64,
         center_y: f64,
+        origin: Option<&Bound<'_, PyAny>>,
+        asymmetry: f64,
     ) -> PyResult<Self> {
         BaseRoseEngineLatheRun::new_paon(
             num_lines,
@@ -574,6 +1023,8 @@ impl RoseEngineLatheRun {
             n_harmonics,
             fan_angle,
             vanishing_point,
+            parse_paon_origin(origin)?,
+            asymmetry,
             center_x,
             center_y,
         )
@@ -588,20 +1039,31 @@ impl RoseEngineLatheRun {
     /// rosette, frequency 1) whose eccentricity equals the circle radius.
     /// Each pass traces a circle of radius `circle_radius` tangent to the
     /// centre.  Multiple passes at different angular positions create the
-    /// characteristic diamond mesh.
+    /// characteristic diamond mesh.  Further rings (`num_rings`) are cut
+    /// `ring_spacing` further out with circles `scale_per_ring` times as
+    /// large, every other one turned by half a circle when `stagger` is set.
     #[staticmethod]
-    #[pyo3(signature = (num_circles=72, circle_radius=20.0, resolution=360, center_x=0.0, center_y=0.0))]
+    #[pyo3(signature = (num_circles=72, circle_radius=20.0, resolution=360, center_x=0.0, center_y=0.0, num_rings=1, ring_spacing=0.0, scale_per_ring=1.0, stagger=false))]
+    #[allow(clippy::too_many_arguments)]
     fn diamant(
         num_circles: usize,
         circle_radius: f64,
         resolution: usize,
         center_x: f64,
         center_y: f64,
+        num_rings: usize,
+        ring_spacing: f64,
+        scale_per_ring: f64,
+        stagger: bool,
     ) -> PyResult<Self> {
         BaseRoseEngineLatheRun::new_diamant(
             num_circles,
             circle_radius,
             resolution,
+            num_rings,
+            ring_spacing,
+            scale_per_ring,
+            stagger,
             center_x,
             center_y,
         )
@@ -616,8 +1078,10 @@ impl RoseEngineLatheRun {
     /// (sinusoidal cam, frequency 1).  Each pass traces the polar curve
     /// r = base_radius + amplitude · sin(θ + phase).  Multiple passes at
     /// different phase offsets create the overlapping limaçon mesh.
+    /// `loop_mode` "outer_only" or "inner_only" cuts only the outer curves
+    /// or only the inner loops, as in LimaconLayer.
     #[staticmethod]
-    #[pyo3(signature = (num_curves=72, base_radius=20.0, amplitude=20.0, resolution=360, center_x=0.0, center_y=0.0))]
+    #[pyo3(signature = (num_curves=72, base_radius=20.0, amplitude=20.0, resolution=360, center_x=0.0, center_y=0.0, loop_mode="full"))]
     fn limacon(
         num_curves: usize,
         base_radius: f64,
@@ -625,12 +1089,14 @@ impl RoseEngineLatheRun {
         resolution: usize,
         center_x: f64,
         center_y: f64,
+        loop_mode: &str,
     ) -> PyResult<Self> {
         BaseRoseEngineLatheRun::new_limacon(
             num_curves,
             base_radius,
             amplitude,
             resolution,
+            parse_loop_mode(loop_mode)?,
             center_x,
             center_y,
         )
@@ -644,8 +1110,12 @@ impl RoseEngineLatheRun {
     /// Models a physical rose engine with a multi-lobe rosette (num_petals
     /// lobes) plus a secondary sinusoidal rosette for fine ripple.  The lathe
     /// makes concentric-ring passes from the inner to the outer radius.
+    /// `petal_phase_step` turns each ring that many radians past the one
+    /// inside it (pi / num_petals interlocks the chevrons in a herringbone)
+    /// and `alternate` flips the chevrons of every other ring.
     #[staticmethod]
-    #[pyo3(signature = (radius=10.0, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05, center_x=0.0, center_y=0.0))]
+    #[pyo3(signature = (radius=10.0, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05, center_x=0.0, center_y=0.0, petal_phase_step=0.0, alternate=false))]
+    #[allow(clippy::too_many_arguments)]
     fn flinque(
         radius: f64,
         num_petals: usize,
@@ -655,6 +1125,8 @@ impl RoseEngineLatheRun {
         inner_radius_ratio: f64,
         center_x: f64,
         center_y: f64,
+        petal_phase_step: f64,
+        alternate: bool,
     ) -> PyResult<Self> {
         BaseRoseEngineLatheRun::new_flinque(
             radius,
@@ -663,6 +1135,8 @@ impl RoseEngineLatheRun {
             wave_amplitude,
             wave_frequency,
             inner_radius_ratio,
+            petal_phase_step,
+            alternate,
             center_x,
             center_y,
         )
@@ -736,6 +1210,7 @@ impl RoseEngineLatheRun {
     /// illusion of 3D cubes.
     #[staticmethod]
     #[pyo3(signature = (spacing=0.5, radius=22.0, angle=0.0, resolution=200, cuts_per_group=8, gap_per_group=8, amplitude=0.0, leg_angle=30.0, center_x=0.0, center_y=0.0))]
+    #[allow(clippy::too_many_arguments)]
     fn cube(
         spacing: f64,
         radius: f64,
@@ -764,14 +1239,176 @@ impl RoseEngineLatheRun {
         .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
 
+    /// Create a rose engine grain-de-riz (rice grain) pattern that produces
+    /// identical output to the mathematical GrainDeRizLayer.
+    ///
+    /// Cuts one closed oval per grain on concentric rows, with every other
+    /// row turned by half a grain pitch when `stagger` is set.
+    #[staticmethod]
+    #[pyo3(signature = (num_rows=12, grains_per_row=48, grain_length=1.2, grain_width=0.5, base_radius=8.0, radius_step=1.0, resolution=24, stagger=true, center_x=0.0, center_y=0.0))]
+    #[allow(clippy::too_many_arguments)]
+    fn grain_de_riz(
+        num_rows: usize,
+        grains_per_row: usize,
+        grain_length: f64,
+        grain_width: f64,
+        base_radius: f64,
+        radius_step: f64,
+        resolution: usize,
+        stagger: bool,
+        center_x: f64,
+        center_y: f64,
+    ) -> PyResult<Self> {
+        BaseRoseEngineLatheRun::new_grain_de_riz(
+            num_rows,
+            grains_per_row,
+            grain_length,
+            grain_width,
+            base_radius,
+            radius_step,
+            resolution,
+            stagger,
+            center_x,
+            center_y,
+        )
+        .map(|inner| RoseEngineLatheRun { inner })
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a banded rose engine run with a different rosette in each
+    /// radial band.
+    ///
+    /// # Arguments
+    /// * `bands` - List of dicts with keys `r_inner`, `r_outer`, `rings` and
+    ///   `config` (a RoseEngineConfig), ordered from the center outward
+    /// * `bit` - Cutting bit configuration
+    ///
+    /// Rings are spaced evenly within each band and amplitudes are scaled
+    /// down where needed so every ring stays inside its band.
+    #[staticmethod]
+    #[pyo3(signature = (bands, bit, center_x=0.0, center_y=0.0))]
+    fn banded(
+        bands: Vec<Bound<'_, PyDict>>,
+        bit: PyRef<CuttingBit>,
+        center_x: f64,
+        center_y: f64,
+    ) -> PyResult<Self> {
+        fn field<'py>(band: &Bound<'py, PyDict>, i: usize, key: &str) -> PyResult<Bound<'py, PyAny>> {
+            band.get_item(key)?.ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!("band {} is missing '{}'", i, key))
+            })
+        }
+
+        let mut specs = Vec::with_capacity(bands.len());
+        for (i, band) in bands.iter().enumerate() {
+            let config = field(band, i, "config")?;
+            let config = config.cast::<RoseEngineConfig>()?.borrow();
+            specs.push(BaseBandSpec::new(
+                field(band, i, "r_inner")?.extract()?,
+                field(band, i, "r_outer")?.extract()?,
+                field(band, i, "rings")?.extract()?,
+                config.inner.clone(),
+            ));
+        }
+
+        BaseRoseEngineLatheRun::new_banded(specs, bit.inner.clone(), center_x, center_y)
+            .map(|inner| RoseEngineLatheRun { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
     /// Generate all passes of the rose engine pattern
     fn generate(&mut self) {
         self.inner.generate();
     }
 
-    /// Export combined pattern as SVG
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
-        self.inner.to_svg(filename)
+    /// Generate, raising ValueError if a concentric ring would cross the
+    /// centre or a pass cannot be cut; the message names the passes
+    fn try_generate(&mut self) -> PyResult<()> {
+        self.inner
+            .try_generate()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Start a coarse-to-fine generation, e.g. `generate_progressive([0.1, 0.3, 1.0])`
+    ///
+    /// Returns a handle whose `advance()` computes the next level.
+    fn generate_progressive(slf: Py<Self>, levels: Vec<f32>) -> ProgressiveHandle {
+        ProgressiveHandle::new(ProgressiveTarget::LatheRun(slf), &levels)
+    }
+
+    /// Overlay this run on `other` in an SVG, coloring unchanged, moved,
+    /// added and removed lines; returns the counts as a dict
+    #[pyo3(signature = (other, filename, tolerance=0.01))]
+    fn diff_svg<'py>(
+        &self,
+        py: Python<'py>,
+        other: PyRef<'py, RoseEngineLatheRun>,
+        filename: &str,
+        tolerance: f64,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        diff_summary_dict(py, self.inner.diff_svg(&other.inner, filename, tolerance))
+    }
+
+    /// Export combined pattern as SVG, simplifying the lines to within `tolerance`
+    /// first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner.to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Rasterize the SVG export to an anti-aliased RGBA PNG file
+    #[pyo3(signature = (filename, width_px=1024, height_px=1024))]
+    fn to_png(&self, filename: &str, width_px: u32, height_px: u32) -> PyResult<()> {
+        self.inner.to_png(filename, width_px, height_px)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Rasterize the SVG export and return the PNG as bytes, e.g. for
+    /// `PIL.Image.open(io.BytesIO(...))`
+    #[pyo3(signature = (width_px=1024, height_px=1024))]
+    fn render_bytes<'py>(
+        &self,
+        py: Python<'py>,
+        width_px: u32,
+        height_px: u32,
+    ) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        let png = self
+            .inner
+            .render_raster(width_px, height_px)
+            .and_then(|image| image.to_png_bytes())
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(pyo3::types::PyBytes::new(py, &png))
+    }
+
+    /// Export combined pattern as SVG with gradient-shaded bands between
+    /// the rings (concentric ring modes only)
+    fn to_svg_filled(&self, filename: &str, light_angle: f64) -> PyResult<()> {
+        self.inner.to_svg_filled(filename, light_angle)
+            .map_err(export_err)
+    }
+
+    /// Export combined pattern as SVG with a design-review overlay
+    #[pyo3(signature = (filename, mm_grid=None, radial_rays_every_deg=None, concentric_circles_every_mm=None, label_font_size=None))]
+    fn to_svg_with_overlay(
+        &self,
+        filename: &str,
+        mm_grid: Option<f64>,
+        radial_rays_every_deg: Option<f64>,
+        concentric_circles_every_mm: Option<f64>,
+        label_font_size: Option<f64>,
+    ) -> PyResult<()> {
+        let overlay = BaseOverlayOptions {
+            mm_grid,
+            radial_rays_every_deg,
+            concentric_circles_every_mm,
+            label_font_size,
+        };
+        self.inner.to_svg_with_overlay(filename, &overlay)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
@@ -791,6 +1428,424 @@ impl RoseEngineLatheRun {
             .collect()
     }
 
+    /// Export the segmented lines as STL grooves
+    ///
+    /// Grooves are `depth` deep over a circular base slab `base_thickness`
+    /// thick (0 for no slab), with the path offset `tool_radius` outwards
+    /// (inwards when negative) for the cutter; all three may come from an
+    /// ExportConfig as `config`
+    #[pyo3(signature = (filename, depth=None, base_thickness=None, tool_radius=None, config=None))]
+    fn to_stl(
+        &self,
+        filename: &str,
+        depth: Option<f64>,
+        base_thickness: Option<f64>,
+        tool_radius: Option<f64>,
+        config: Option<ExportConfig>,
+    ) -> PyResult<()> {
+        let config = export_config(config, depth, base_thickness, tool_radius)?;
+        self.inner.to_stl(filename, &config).map_err(export_err)
+    }
+
+    /// Export the segmented lines' points as a STEP file
+    #[pyo3(signature = (filename, depth=None, config=None))]
+    fn to_step(&self, filename: &str, depth: Option<f64>, config: Option<ExportConfig>) -> PyResult<()> {
+        let config = export_config(config, depth, None, None)?;
+        self.inner.to_step(filename, &config).map_err(export_err)
+    }
+
+    /// Get the generated lines mapped onto the configured surface as (x, y, z) tuples
+    fn get_lines_3d(&self) -> Vec<Vec<(f64, f64, f64)>> {
+        lines_3d_tuples(self.inner.lines_3d())
+    }
+
+    /// Export the generated lines cut `depth` into the configured surface as STL
+    #[pyo3(signature = (filename, depth=0.1))]
+    fn to_stl_3d(&self, filename: &str, depth: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            depth,
+            ..Default::default()
+        };
+        self.inner.to_stl_3d(filename, &config).map_err(export_err)
+    }
+
+    /// Hash of the generated lines; equal hashes mean the drawing is unchanged
+    fn geometry_hash(&self) -> u64 {
+        self.inner.geometry_hash()
+    }
+
+    /// Whether changing the named run field (e.g. "phase_exponent") can
+    /// change the lines with the current settings
+    fn affects_geometry(&self, field: &str) -> bool {
+        self.inner.affects_geometry(field)
+    }
+
+    /// Get the closed cells that can be pierced through, inset by half of
+    /// `min_web`, as lists of (x, y) tuples
+    #[pyo3(signature = (min_web, min_area=0.0))]
+    fn pierceable_cells(&self, min_web: f64, min_area: f64) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .pierceable_cells(min_web, min_area)
+            .iter()
+            .map(|cell| cell.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Export every pass as G-code, in cutting order
+    ///
+    /// Rates are in mm/min; the tool steps down at most `depth_per_pass`
+    /// mm at a time and rapids at `safe_z` between cuts
+    #[pyo3(signature = (filename, feed_rate=300.0, plunge_rate=60.0, safe_z=2.0, depth_per_pass=0.1, spindle_speed=12000.0))]
+    fn to_gcode(&self, filename: &str, feed_rate: f64, plunge_rate: f64, safe_z: f64, depth_per_pass: f64, spindle_speed: f64) -> PyResult<()> {
+        let params = BaseGcodeParams::new(feed_rate, plunge_rate, safe_z, depth_per_pass, spindle_speed);
+        self.inner.to_gcode(filename, &params).map_err(export_err)
+    }
+
+    /// Export the segmented lines to DXF as LWPOLYLINE entities in mm
+    fn to_dxf(&self, filename: &str) -> PyResult<()> {
+        self.inner
+            .to_dxf(filename)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Export the segmented lines to HPGL for a pen plotter, in mm times
+    /// `scale`; pass `k` is plotted with `pens[k]` (the last pen repeats,
+    /// pen 1 without any)
+    #[pyo3(signature = (filename, scale=1.0, pens=None))]
+    fn to_hpgl(&self, filename: &str, scale: f64, pens: Option<Vec<u32>>) -> PyResult<()> {
+        self.inner
+            .to_hpgl_with_pens(filename, scale, &pens.unwrap_or_default())
+            .map_err(export_err)
+    }
+
+    /// Export the pierceable cells to DXF as closed polylines
+    #[pyo3(signature = (filename, min_web, min_area=0.0))]
+    fn to_dxf_pierced(&self, filename: &str, min_web: f64, min_area: f64) -> PyResult<()> {
+        self.inner
+            .to_dxf_pierced(filename, min_web, min_area)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Set traceability metadata written into SVG and DXF exports
+    ///
+    /// Arguments as for `WatchFace.set_metadata`.
+    #[pyo3(signature = (design_id, params_hash="", license="", generator_version=None, extra=None))]
+    fn set_metadata(
+        &mut self,
+        design_id: &str,
+        params_hash: &str,
+        license: &str,
+        generator_version: Option<&str>,
+        extra: Option<Bound<'_, PyDict>>,
+    ) -> PyResult<()> {
+        let metadata = file_metadata(design_id, params_hash, license, generator_version, extra.as_ref())?;
+        self.inner.set_metadata(metadata);
+        Ok(())
+    }
+
+    /// Get the export metadata as a dict of its non-empty fields, or None
+    #[getter]
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.inner.metadata().map(|m| metadata_dict(py, m)).transpose()
+    }
+
+    /// Stop embedding metadata in exports
+    fn clear_metadata(&mut self) {
+        self.inner.clear_metadata();
+    }
+
+    /// Replace the computed settings of the first passes, one dict per pass
+    ///
+    /// Each dict may set `amplitude`, `phase`, `base_radius` and `rosette`
+    /// (a RosettePattern); omitted keys keep the pass's computed value.
+    /// Takes effect on the next generate().
+    fn set_pass_overrides(&mut self, overrides: Vec<Bound<'_, PyDict>>) -> PyResult<()> {
+        let mut passes = Vec::with_capacity(overrides.len());
+        for (i, dict) in overrides.iter().enumerate() {
+            let mut pass = BasePassOverride::default();
+            for (key, value) in dict.iter() {
+                let key: String = key.extract()?;
+                match key.as_str() {
+                    "amplitude" => pass.amplitude = Some(value.extract()?),
+                    "phase" => pass.phase = Some(value.extract()?),
+                    "base_radius" => pass.base_radius = Some(value.extract()?),
+                    "rosette" => pass.rosette = Some(value.cast::<RosettePattern>()?.borrow().inner.clone()),
+                    _ => {
+                        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                            "pass override {} has unknown key '{}'",
+                            i, key
+                        )))
+                    }
+                }
+            }
+            passes.push(pass);
+        }
+        self.inner
+            .set_pass_overrides(passes)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Use the computed settings for every pass again
+    fn clear_pass_overrides(&mut self) {
+        self.inner.clear_pass_overrides();
+    }
+
+    /// Repeat every line as n parallel traces `spacing` mm apart, centred on the original
+    ///
+    /// Takes effect on the next `generate()`.
+    fn with_parallel_traces(&mut self, n: usize, spacing: f64) -> PyResult<()> {
+        self.inner
+            .with_parallel_traces(n, spacing)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Fade SVG lines by an Envelope at each line's mean angle and radius
+    fn set_opacity_envelope(&mut self, envelope: Envelope) -> PyResult<()> {
+        self.inner
+            .set_opacity_envelope(envelope.inner)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Draw every SVG line fully opaque again
+    fn clear_opacity_envelope(&mut self) {
+        self.inner.clear_opacity_envelope();
+    }
+
+    /// Get the band index of each generated line, parallel to `get_lines()`
+    ///
+    /// Empty unless the run was created with `banded`.
+    fn get_band_indices(&self) -> Vec<usize> {
+        self.inner.line_band_indices().to_vec()
+    }
+
+    /// Get the pass index of each generated line, parallel to `get_lines()`
+    fn get_pass_indices(&self) -> Vec<usize> {
+        self.inner.line_pass_indices().to_vec()
+    }
+
+    /// Copy this run so every pass is cut in depth steps of at most `max_depth_per_pass`
+    ///
+    /// `order` is "depth_first" (each pass reaches full depth before the
+    /// next) or "pass_first" (all passes at one depth before stepping down).
+    #[pyo3(signature = (max_depth_per_pass, order="depth_first"))]
+    fn expand_depth_passes(&self, max_depth_per_pass: f64, order: &str) -> PyResult<Self> {
+        let order = match order.to_lowercase().as_str() {
+            "depth_first" => BaseDepthPassOrder::DepthFirst,
+            "pass_first" => BaseDepthPassOrder::PassFirst,
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "order must be 'depth_first' or 'pass_first'",
+                ))
+            }
+        };
+        self.inner
+            .expand_depth_passes(max_depth_per_pass, order)
+            .map(|inner| RoseEngineLatheRun { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Get the cut depth of each pass repeat, shallowest first
+    fn depth_steps(&self) -> Vec<f64> {
+        self.inner.depth_steps()
+    }
+
+    /// Get the cut depth of each generated line, parallel to `get_lines()`
+    fn get_line_depths(&self) -> Vec<f64> {
+        self.inner.line_depths().to_vec()
+    }
+
+    /// Total length of all generated lines in mm, counting depth repeats
+    fn cut_length(&self) -> f64 {
+        self.inner.cut_length()
+    }
+
+    /// Simulate the bit wearing with cut length, resharpening after
+    /// `resharpen_after_m` meters of cut if given
+    #[pyo3(signature = (width_growth_per_meter, depth_loss_per_meter=0.0, resharpen_after_m=None))]
+    fn set_wear_model(
+        &mut self,
+        width_growth_per_meter: f64,
+        depth_loss_per_meter: f64,
+        resharpen_after_m: Option<f64>,
+    ) -> PyResult<()> {
+        let mut model = BaseWearModel::new(width_growth_per_meter, depth_loss_per_meter);
+        model.resharpen_after_m = resharpen_after_m;
+        self.inner
+            .set_wear_model(model)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Cut with an unworn bit again
+    fn clear_wear_model(&mut self) {
+        self.inner.clear_wear_model();
+    }
+
+    /// Get the bit state at the start of every cut, in cutting order
+    ///
+    /// Returns dicts with `pass`, `lines` (start, end) into `get_lines()`,
+    /// `programmed_depth`, `cut_since_sharpening`, `width`, `depth` and
+    /// `resharpen_before`. Empty without a wear model.
+    fn get_pass_wear<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let cuts = PyList::empty(py);
+        for wear in self.inner.pass_wear() {
+            let w = PyDict::new(py);
+            w.set_item("pass", wear.pass)?;
+            w.set_item("lines", (wear.lines.start, wear.lines.end))?;
+            w.set_item("programmed_depth", wear.programmed_depth)?;
+            w.set_item("cut_since_sharpening", wear.cut_since_sharpening)?;
+            w.set_item("width", wear.width)?;
+            w.set_item("depth", wear.depth)?;
+            w.set_item("resharpen_before", wear.resharpen_before)?;
+            cuts.append(w)?;
+        }
+        Ok(cuts)
+    }
+
+    /// Indices into `get_pass_wear()` of the cuts preceded by a resharpening
+    fn resharpen_passes(&self) -> Vec<usize> {
+        self.inner.resharpen_passes()
+    }
+
+    /// Export combined pattern as SVG with strokes widened by bit wear
+    fn to_svg_worn(&self, filename: &str) -> PyResult<()> {
+        self.inner.to_svg_worn(filename).map_err(export_err)
+    }
+
+    /// Export combined pattern as SVG with strokes shaded by cut depth
+    fn to_svg_shaded(&self, filename: &str) -> PyResult<()> {
+        self.inner.to_svg_shaded(filename).map_err(export_err)
+    }
+
+    /// Export combined pattern as SVG with lines colored by pass or depth
+    ///
+    /// `colors` is a list of SVG colors such as "#ff0000": one color strokes
+    /// every line, several are cycled by pass index. With `by_depth`, lines
+    /// are shaded as in `to_svg_shaded` and `colors` must be left out.
+    #[pyo3(signature = (filename, colors=None, by_depth=false))]
+    fn to_svg_styled(&self, filename: &str, colors: Option<Vec<String>>, by_depth: bool) -> PyResult<()> {
+        let color_mode = match (colors, by_depth) {
+            (Some(_), true) => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "colors and by_depth cannot be combined",
+                ))
+            }
+            (None, true) => BaseSvgColorMode::ByDepth,
+            (None, false) => BaseSvgStyle::default().color_mode,
+            (Some(mut colors), false) if colors.len() == 1 => BaseSvgColorMode::Single(colors.remove(0)),
+            (Some(colors), false) => BaseSvgColorMode::PerPass(colors),
+        };
+        self.inner.to_svg_styled(filename, &BaseSvgStyle { color_mode }).map_err(export_err)
+    }
+
+    /// Get each generated line with its pass index, as (pass_index, points) pairs
+    fn get_segments_with_pass(&self) -> Vec<(usize, Vec<(f64, f64)>)> {
+        self.inner
+            .segments_with_pass()
+            .into_iter()
+            .map(|(pass, line)| (pass, line.iter().map(|p| (p.x, p.y)).collect()))
+            .collect()
+    }
+
+    /// Tag every generated line with `func(pass_index)` (an int >= 0)
+    ///
+    /// Call after `generate()`; regenerating clears the tags.
+    fn tag_passes(&mut self, func: Bound<'_, PyAny>) -> PyResult<()> {
+        let mut pass_tags = std::collections::HashMap::new();
+        for &pass in self.inner.line_pass_indices() {
+            if let std::collections::hash_map::Entry::Vacant(entry) = pass_tags.entry(pass) {
+                entry.insert(func.call1((pass,))?.extract::<u32>()?);
+            }
+        }
+        self.inner.tag_passes(|pass| pass_tags[&pass]);
+        Ok(())
+    }
+
+    /// Get the tag of each generated line, parallel to `get_lines()` (empty until tagged)
+    fn get_line_tags(&self) -> Vec<u32> {
+        self.inner.line_tags().to_vec()
+    }
+
+    /// Get the lines carrying `tag`
+    fn lines_for_tag(&self, tag: u32) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines_for_tag(tag)
+            .into_iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Export one SVG per tag into `directory` as `{basename}_tag{tag}.svg`
+    ///
+    /// All files share one viewBox so they overlay in registration.
+    /// Returns the written file paths in tag order.
+    #[pyo3(signature = (directory, basename))]
+    fn to_svg_by_tag(&self, directory: &str, basename: &str) -> PyResult<Vec<String>> {
+        self.inner
+            .to_svg_by_tag(std::path::Path::new(directory), basename)
+            .map(|paths| paths.iter().map(|p| p.to_string_lossy().into_owned()).collect())
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get the pattern built up pass by pass over `n_frames` frames
+    ///
+    /// Each frame is a list of lines (lists of (x, y) tuples); the last frame
+    /// is the whole pattern. Empty before generate().
+    fn get_frames(&self, n_frames: usize) -> Vec<Vec<PointTuples>> {
+        self.inner
+            .frames(n_frames)
+            .iter()
+            .map(|frame| frame.iter().map(|line| line.iter().map(|p| (p.x, p.y)).collect()).collect())
+            .collect()
+    }
+
+    /// Export `get_frames(n_frames)` to `directory` as frame_0000.svg, frame_0001.svg, ...
+    ///
+    /// All files share one viewBox so they play back in registration.
+    /// Returns the written file paths in frame order.
+    fn to_svg_frames(&self, directory: &str, n_frames: usize) -> PyResult<Vec<String>> {
+        self.inner
+            .to_svg_frames(std::path::Path::new(directory), n_frames)
+            .map(|paths| paths.iter().map(|p| p.to_string_lossy().into_owned()).collect())
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Build the cutting sequence for a physical rose engine controller
+    ///
+    /// # Arguments
+    /// * `mapping` - List of (RosettePattern, barrel_slot) pairs
+    ///
+    /// Returns a dict `{"passes": [...]}` with one entry per pass in
+    /// generation order. Phases are in degrees [0, 360), lengths in mm.
+    fn to_machine_job<'py>(&self, py: Python<'py>, mapping: Vec<(RosettePattern, usize)>) -> PyResult<Bound<'py, PyDict>> {
+        let mapping = mapping
+            .into_iter()
+            .fold(BaseRosetteMapping::new(), |m, (pattern, slot)| m.with_slot(pattern.inner, slot));
+        let job = self.inner
+            .to_machine_job(&mapping)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+
+        let passes = PyList::empty(py);
+        for (i, pass) in job.passes.iter().enumerate() {
+            let entry = PyDict::new(py);
+            entry.set_item("index", i)?;
+            entry.set_item("rosette_slot", pass.rosette_slot)?;
+            entry.set_item("amplitude", pass.amplitude)?;
+            entry.set_item("phase", pass.phase)?;
+            entry.set_item("base_radius", pass.base_radius)?;
+            entry.set_item("direction", pass.direction.as_str())?;
+            entry.set_item("depth", pass.depth)?;
+            entry.set_item("secondary_rosette_slot", pass.secondary_rosette_slot)?;
+            entry.set_item("secondary_amplitude", pass.secondary_amplitude)?;
+            entry.set_item("secondary_phase", pass.secondary_phase)?;
+            entry.set_item("resharpen_before", pass.resharpen_before)?;
+            passes.append(entry)?;
+        }
+
+        let job_dict = PyDict::new(py);
+        job_dict.set_item("passes", passes)?;
+        Ok(job_dict)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "RoseEngineLatheRun(center=({}, {}), passes={})",
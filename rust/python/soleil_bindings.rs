@@ -0,0 +1,244 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use turtles::{
+    ExportConfig as BaseExportConfig,
+    PatternLayer,
+    SoleilConfig as BaseSoleilConfig,
+    SoleilLayer as BaseSoleilLayer,
+};
+
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+/// Build a SoleilConfig from Python arguments
+pub fn soleil_config(
+    num_rays: usize,
+    inner_radius: f64,
+    outer_radius: f64,
+    curvature: f64,
+    taper: f64,
+    resolution: usize,
+) -> BaseSoleilConfig {
+    BaseSoleilConfig {
+        num_rays,
+        inner_radius,
+        outer_radius,
+        curvature,
+        taper,
+        resolution: resolution.into(),
+    }
+}
+
+/// Python wrapper for SoleilLayer - sunray lines or thin wedges running
+/// out from the center, straight or curved
+#[pyclass]
+pub struct SoleilLayer {
+    pub inner: BaseSoleilLayer,
+}
+
+#[pymethods]
+impl SoleilLayer {
+    /// Create a new soleil layer centered at origin
+    ///
+    /// # Arguments
+    /// * `num_rays` - Number of rays around the dial
+    /// * `inner_radius` - Radius the rays start from in mm
+    /// * `outer_radius` - Radius the rays end on in mm
+    /// * `curvature` - Ray curvature in 1/mm, positive bends counter-clockwise (0 = straight)
+    /// * `taper` - Ray width at the outer radius in mm (0 = single lines)
+    /// * `resolution` - Steps along each ray
+    #[new]
+    #[pyo3(signature = (num_rays=180, inner_radius=1.5, outer_radius=15.0, curvature=0.0, taper=0.0, resolution=32))]
+    pub fn new(
+        num_rays: usize,
+        inner_radius: f64,
+        outer_radius: f64,
+        curvature: f64,
+        taper: f64,
+        resolution: usize,
+    ) -> PyResult<Self> {
+        let config = soleil_config(num_rays, inner_radius, outer_radius, curvature, taper, resolution);
+        BaseSoleilLayer::new(config)
+            .map(|inner| SoleilLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a soleil layer with a custom center point
+    #[staticmethod]
+    #[pyo3(signature = (center_x, center_y, num_rays=180, inner_radius=1.5, outer_radius=15.0, curvature=0.0, taper=0.0, resolution=32))]
+    #[allow(clippy::too_many_arguments)]
+    fn with_center(
+        center_x: f64,
+        center_y: f64,
+        num_rays: usize,
+        inner_radius: f64,
+        outer_radius: f64,
+        curvature: f64,
+        taper: f64,
+        resolution: usize,
+    ) -> PyResult<Self> {
+        let config = soleil_config(num_rays, inner_radius, outer_radius, curvature, taper, resolution);
+        BaseSoleilLayer::new_with_center(config, center_x, center_y)
+            .map(|inner| SoleilLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a soleil layer positioned at a given angle and distance from origin
+    #[staticmethod]
+    #[pyo3(signature = (angle, distance, num_rays=180, inner_radius=1.5, outer_radius=15.0, curvature=0.0, taper=0.0, resolution=32))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_polar(
+        angle: f64,
+        distance: f64,
+        num_rays: usize,
+        inner_radius: f64,
+        outer_radius: f64,
+        curvature: f64,
+        taper: f64,
+        resolution: usize,
+    ) -> PyResult<Self> {
+        let config = soleil_config(num_rays, inner_radius, outer_radius, curvature, taper, resolution);
+        BaseSoleilLayer::new_at_polar(config, angle, distance)
+            .map(|inner| SoleilLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a soleil layer positioned at a clock position (like hour hand)
+    ///
+    /// # Arguments
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face to the layer center
+    #[staticmethod]
+    #[pyo3(signature = (hour, minute, distance, num_rays=180, inner_radius=1.5, outer_radius=15.0, curvature=0.0, taper=0.0, resolution=32))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_clock(
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        num_rays: usize,
+        inner_radius: f64,
+        outer_radius: f64,
+        curvature: f64,
+        taper: f64,
+        resolution: usize,
+    ) -> PyResult<Self> {
+        let config = soleil_config(num_rays, inner_radius, outer_radius, curvature, taper, resolution);
+        BaseSoleilLayer::new_at_clock(config, hour, minute, distance)
+            .map(|inner| SoleilLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Generate the soleil pattern
+    fn generate(&mut self) {
+        self.inner.generate();
+    }
+
+    /// Export the pattern to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner
+            .to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get all generated rays as list of list of (x, y) tuples; tapered
+    /// rays are closed wedges
+    fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Get the number of rays
+    #[getter]
+    fn num_rays(&self) -> usize {
+        self.inner.config.num_rays
+    }
+
+    /// Get the inner radius
+    #[getter]
+    fn inner_radius(&self) -> f64 {
+        self.inner.config.inner_radius
+    }
+
+    /// Get the outer radius
+    #[getter]
+    fn outer_radius(&self) -> f64 {
+        self.inner.config.outer_radius
+    }
+
+    /// Get the ray curvature
+    #[getter]
+    fn curvature(&self) -> f64 {
+        self.inner.config.curvature
+    }
+
+    /// Get the ray width at the outer radius
+    #[getter]
+    fn taper(&self) -> f64 {
+        self.inner.config.taper
+    }
+
+    /// Get the center x coordinate
+    #[getter]
+    fn center_x(&self) -> f64 {
+        self.inner.center_x
+    }
+
+    /// Get the center y coordinate
+    #[getter]
+    fn center_y(&self) -> f64 {
+        self.inner.center_y
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SoleilLayer(num_rays={}, inner_radius={}, outer_radius={}, curvature={}, taper={}, center=({}, {}))",
+            self.inner.config.num_rays,
+            self.inner.config.inner_radius,
+            self.inner.config.outer_radius,
+            self.inner.config.curvature,
+            self.inner.config.taper,
+            self.inner.center_x,
+            self.inner.center_y
+        )
+    }
+}
@@ -1,11 +1,28 @@
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use turtles::{
     HorizontalSpirograph as BaseHorizontalSpirograph,
     VerticalSpirograph as BaseVerticalSpirograph,
     SphericalSpirograph as BaseSphericalSpirograph,
     ExportConfig as BaseExportConfig,
+    ExportSurface as BaseExportSurface,
+    PatternLayer,
+    SpirographMode as BaseSpirographMode,
 };
 
+use crate::export_bindings::{export_config, ExportConfig};
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+use crate::watch_face_bindings::parse_radius_policy;
+
+/// Parse a spirograph mode name ("hypotrochoid" or "epitrochoid")
+fn parse_mode(mode: &str) -> PyResult<BaseSpirographMode> {
+    BaseSpirographMode::from_name(&mode.to_lowercase()).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(
+            "mode must be 'hypotrochoid' or 'epitrochoid'",
+        )
+    })
+}
+
 /// Python wrapper for HorizontalSpirograph
 #[pyclass]
 pub struct HorizontalSpirograph {
@@ -15,16 +32,20 @@ pub struct HorizontalSpirograph {
 #[pymethods]
 impl HorizontalSpirograph {
     #[new]
-    #[pyo3(signature = (outer_radius, radius_ratio, point_distance, rotations, resolution))]
+    #[pyo3(signature = (outer_radius, radius_ratio, point_distance, rotations, resolution, mode="hypotrochoid", radius_policy=None))]
     fn new(
         outer_radius: f64,
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
         resolution: usize,
+        mode: &str,
+        radius_policy: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Self> {
-        BaseHorizontalSpirograph::new(outer_radius, radius_ratio, point_distance, rotations, resolution)
-            .map(|inner| HorizontalSpirograph { inner })
+        let mode = parse_mode(mode)?;
+        let policy = parse_radius_policy(radius_policy)?;
+        BaseHorizontalSpirograph::new_with_policy(outer_radius, radius_ratio, point_distance, rotations, resolution, policy)
+            .map(|inner| HorizontalSpirograph { inner: inner.with_mode(mode) })
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
     
@@ -34,45 +55,101 @@ impl HorizontalSpirograph {
         Ok(())
     }
     
-    /// Export pattern as SVG
-    #[pyo3(signature = (filename))]
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
-        self.inner.to_svg(filename)
-            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    /// Bounds, engraved length and point counts of the generated curve as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Get the generated curve as a list of (x, y) tuples
+    fn get_points(&self) -> Vec<(f64, f64)> {
+        self.inner.points().iter().map(|p| (p.x, p.y)).collect()
     }
     
-    /// Export pattern as STEP file
-    #[pyo3(signature = (filename, depth=0.1))]
-    fn to_step(&self, filename: &str, depth: f64) -> PyResult<()> {
+    /// Export pattern as SVG, simplifying the curve to within `tolerance`
+    /// first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
         let config = BaseExportConfig {
-            depth,
-            base_thickness: 2.0,
-            tool_radius: 0.0,
+            simplify_tolerance: tolerance,
+            ..Default::default()
         };
+        self.inner.to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+    
+    /// Export pattern as STEP file
+    #[pyo3(signature = (filename, depth=None, config=None))]
+    fn to_step(&self, filename: &str, depth: Option<f64>, config: Option<ExportConfig>) -> PyResult<()> {
+        let config = export_config(config, depth, None, None)?;
         self.inner.to_step(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
     
     /// Export pattern as STL file
-    #[pyo3(signature = (filename, depth=0.1, base_thickness=2.0))]
-    fn to_stl(&self, filename: &str, depth: f64, base_thickness: f64) -> PyResult<()> {
-        let config = BaseExportConfig {
-            depth,
-            base_thickness,
-            tool_radius: 0.0,
-        };
+    ///
+    /// Grooves are `depth` deep over a circular base slab `base_thickness`
+    /// thick (0 for no slab), flat-bottomed `2 * tool_radius` wide when it
+    /// is positive; all three may come from an ExportConfig as `config`
+    #[pyo3(signature = (filename, depth=None, base_thickness=None, tool_radius=None, config=None))]
+    fn to_stl(
+        &self,
+        filename: &str,
+        depth: Option<f64>,
+        base_thickness: Option<f64>,
+        tool_radius: Option<f64>,
+        config: Option<ExportConfig>,
+    ) -> PyResult<()> {
+        let config = export_config(config, depth, base_thickness, tool_radius)?;
         self.inner.to_stl(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
     
+    /// "hypotrochoid" (rolling inside the fixed circle) or "epitrochoid" (outside)
+    #[getter]
+    fn mode(&self) -> &'static str {
+        self.inner.mode.name()
+    }
+
+    /// Fewest rotations after which the curve closes, from the radius ratio
+    fn closure_rotations(&self) -> usize {
+        self.inner.closure_rotations()
+    }
+
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!(
-            "HorizontalSpirograph(outer_radius={}, radius_ratio={}, point_distance={}, rotations={}, resolution={})",
+            "HorizontalSpirograph(outer_radius={}, radius_ratio={}, point_distance={}, rotations={}, resolution={}, mode={})",
             self.inner.outer_radius,
             self.inner.radius_ratio,
             self.inner.point_distance,
             self.inner.rotations,
-            self.inner.resolution
+            self.inner.resolution,
+            self.inner.mode.name()
         ))
     }
 }
@@ -86,7 +163,8 @@ pub struct VerticalSpirograph {
 #[pymethods]
 impl VerticalSpirograph {
     #[new]
-    #[pyo3(signature = (outer_radius, radius_ratio, point_distance, rotations, resolution, wave_amplitude=1.0, wave_frequency=5.0))]
+    #[pyo3(signature = (outer_radius, radius_ratio, point_distance, rotations, resolution, wave_amplitude=1.0, wave_frequency=5.0, mode="hypotrochoid", radius_policy=None))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         outer_radius: f64,
         radius_ratio: f64,
@@ -95,8 +173,12 @@ impl VerticalSpirograph {
         resolution: usize,
         wave_amplitude: f64,
         wave_frequency: f64,
+        mode: &str,
+        radius_policy: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Self> {
-        BaseVerticalSpirograph::new(
+        let mode = parse_mode(mode)?;
+        let policy = parse_radius_policy(radius_policy)?;
+        BaseVerticalSpirograph::new_with_policy(
             outer_radius,
             radius_ratio,
             point_distance,
@@ -104,8 +186,9 @@ impl VerticalSpirograph {
             resolution,
             wave_amplitude,
             wave_frequency,
+            policy,
         )
-        .map(|inner| VerticalSpirograph { inner })
+        .map(|inner| VerticalSpirograph { inner: inner.with_mode(mode) })
         .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
     
@@ -114,44 +197,95 @@ impl VerticalSpirograph {
         Ok(())
     }
     
-    #[pyo3(signature = (filename))]
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
-        self.inner.to_svg(filename)
-            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    /// Bounds, engraved length and point counts of the generated curve as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Get the generated curve as a list of (x, y) tuples
+    fn get_points(&self) -> Vec<(f64, f64)> {
+        self.inner.points().iter().map(|p| (p.x, p.y)).collect()
     }
     
-    #[pyo3(signature = (filename, depth=0.1))]
-    fn to_step(&self, filename: &str, depth: f64) -> PyResult<()> {
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
         let config = BaseExportConfig {
-            depth,
-            base_thickness: 2.0,
-            tool_radius: 0.0,
+            simplify_tolerance: tolerance,
+            ..Default::default()
         };
+        self.inner.to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+    
+    #[pyo3(signature = (filename, depth=None, config=None))]
+    fn to_step(&self, filename: &str, depth: Option<f64>, config: Option<ExportConfig>) -> PyResult<()> {
+        let config = export_config(config, depth, None, None)?;
         self.inner.to_step(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
     
-    #[pyo3(signature = (filename, depth=0.1, base_thickness=2.0))]
-    fn to_stl(&self, filename: &str, depth: f64, base_thickness: f64) -> PyResult<()> {
-        let config = BaseExportConfig {
-            depth,
-            base_thickness,
-            tool_radius: 0.0,
-        };
+    #[pyo3(signature = (filename, depth=None, base_thickness=None, tool_radius=None, config=None))]
+    fn to_stl(
+        &self,
+        filename: &str,
+        depth: Option<f64>,
+        base_thickness: Option<f64>,
+        tool_radius: Option<f64>,
+        config: Option<ExportConfig>,
+    ) -> PyResult<()> {
+        let config = export_config(config, depth, base_thickness, tool_radius)?;
         self.inner.to_stl(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
     
+    /// "hypotrochoid" (rolling inside the fixed circle) or "epitrochoid" (outside)
+    #[getter]
+    fn mode(&self) -> &'static str {
+        self.inner.mode.name()
+    }
+
+    /// Fewest rotations after which the curve closes, from the radius ratio
+    fn closure_rotations(&self) -> usize {
+        self.inner.closure_rotations()
+    }
+
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!(
-            "VerticalSpirograph(outer_radius={}, radius_ratio={}, point_distance={}, rotations={}, resolution={}, wave_amplitude={}, wave_frequency={})",
+            "VerticalSpirograph(outer_radius={}, radius_ratio={}, point_distance={}, rotations={}, resolution={}, wave_amplitude={}, wave_frequency={}, mode={})",
             self.inner.outer_radius,
             self.inner.radius_ratio,
             self.inner.point_distance,
             self.inner.rotations,
             self.inner.resolution,
             self.inner.wave_amplitude,
-            self.inner.wave_frequency
+            self.inner.wave_frequency,
+            self.inner.mode.name()
         ))
     }
 }
@@ -165,7 +299,8 @@ pub struct SphericalSpirograph {
 #[pymethods]
 impl SphericalSpirograph {
     #[new]
-    #[pyo3(signature = (outer_radius, radius_ratio, point_distance, rotations, resolution, dome_height=5.0))]
+    #[pyo3(signature = (outer_radius, radius_ratio, point_distance, rotations, resolution, dome_height=5.0, mode="hypotrochoid", radius_policy=None))]
+    #[allow(clippy::too_many_arguments)]
     fn new(
         outer_radius: f64,
         radius_ratio: f64,
@@ -173,16 +308,21 @@ impl SphericalSpirograph {
         rotations: usize,
         resolution: usize,
         dome_height: f64,
+        mode: &str,
+        radius_policy: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Self> {
-        BaseSphericalSpirograph::new(
+        let mode = parse_mode(mode)?;
+        let policy = parse_radius_policy(radius_policy)?;
+        BaseSphericalSpirograph::new_with_policy(
             outer_radius,
             radius_ratio,
             point_distance,
             rotations,
             resolution,
             dome_height,
+            policy,
         )
-        .map(|inner| SphericalSpirograph { inner })
+        .map(|inner| SphericalSpirograph { inner: inner.with_mode(mode) })
         .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
     
@@ -191,43 +331,115 @@ impl SphericalSpirograph {
         Ok(())
     }
     
-    #[pyo3(signature = (filename))]
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
-        self.inner.to_svg(filename)
-            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    /// Bounds, engraved length and point counts of the generated curve as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Get the generated curve projected onto the plane as a list of (x, y) tuples
+    fn get_points(&self) -> Vec<(f64, f64)> {
+        self.inner.points_2d().iter().map(|p| (p.x, p.y)).collect()
+    }
+
+    /// Get the generated curve on the dome as a list of (x, y, z) tuples
+    fn get_points_3d(&self) -> Vec<(f64, f64, f64)> {
+        self.inner.points_3d().iter().map(|p| (p.x, p.y, p.z)).collect()
     }
     
-    #[pyo3(signature = (filename, depth=0.1))]
-    fn to_step(&self, filename: &str, depth: f64) -> PyResult<()> {
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
         let config = BaseExportConfig {
-            depth,
-            base_thickness: 2.0,
-            tool_radius: 0.0,
+            simplify_tolerance: tolerance,
+            ..Default::default()
         };
+        self.inner.to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+    
+    #[pyo3(signature = (filename, depth=None, config=None))]
+    fn to_step(&self, filename: &str, depth: Option<f64>, config: Option<ExportConfig>) -> PyResult<()> {
+        let config = export_config(config, depth, None, None)?;
         self.inner.to_step(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
     
-    #[pyo3(signature = (filename, depth=0.1, base_thickness=2.0))]
-    fn to_stl(&self, filename: &str, depth: f64, base_thickness: f64) -> PyResult<()> {
+    /// Export pattern as STL file
+    ///
+    /// With `dome=True` the grooves are cut into a solid spherical cap of
+    /// `dome_segments` facets around, standing on a `base_thickness` skirt.
+    /// `depth`, `base_thickness` and `tool_radius` may come from an
+    /// ExportConfig as `config`
+    #[pyo3(signature = (filename, depth=None, base_thickness=None, dome=false, dome_segments=None, tool_radius=None, config=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn to_stl(
+        &self,
+        filename: &str,
+        depth: Option<f64>,
+        base_thickness: Option<f64>,
+        dome: bool,
+        dome_segments: Option<usize>,
+        tool_radius: Option<f64>,
+        config: Option<ExportConfig>,
+    ) -> PyResult<()> {
+        let surface = match (dome, dome_segments) {
+            (false, _) => BaseExportSurface::Flat,
+            (true, None) => BaseExportSurface::dome(),
+            (true, Some(segments)) => BaseExportSurface::Dome { segments },
+        };
         let config = BaseExportConfig {
-            depth,
-            base_thickness,
-            tool_radius: 0.0,
+            surface,
+            ..export_config(config, depth, base_thickness, tool_radius)?
         };
-        self.inner.to_stl(filename, &config)
-            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+        self.inner.to_stl(filename, &config).map_err(export_err)
     }
     
+    /// "hypotrochoid" (rolling inside the fixed circle) or "epitrochoid" (outside)
+    #[getter]
+    fn mode(&self) -> &'static str {
+        self.inner.mode.name()
+    }
+
+    /// Fewest rotations after which the curve closes, from the radius ratio
+    fn closure_rotations(&self) -> usize {
+        self.inner.closure_rotations()
+    }
+
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!(
-            "SphericalSpirograph(outer_radius={}, radius_ratio={}, point_distance={}, rotations={}, resolution={}, dome_height={})",
+            "SphericalSpirograph(outer_radius={}, radius_ratio={}, point_distance={}, rotations={}, resolution={}, dome_height={}, mode={})",
             self.inner.outer_radius,
             self.inner.radius_ratio,
             self.inner.point_distance,
             self.inner.rotations,
             self.inner.resolution,
-            self.inner.dome_height
+            self.inner.dome_height,
+            self.inner.mode.name()
         ))
     }
 }
@@ -0,0 +1,262 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use turtles::{
+    ExportConfig as BaseExportConfig,
+    PatternLayer,
+    TapisserieConfig as BaseTapisserieConfig,
+    TapisserieLayer as BaseTapisserieLayer,
+};
+
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+/// Build a TapisserieConfig from Python arguments
+pub fn tapisserie_config(
+    cell_size: f64,
+    channel_width: f64,
+    radius: f64,
+    rotation: f64,
+    resolution: usize,
+    truncate_corners: bool,
+) -> BaseTapisserieConfig {
+    BaseTapisserieConfig {
+        cell_size,
+        channel_width,
+        radius,
+        rotation,
+        resolution: resolution.into(),
+        truncate_corners,
+    }
+}
+
+/// Python wrapper for TapisserieLayer - a waffle grid of raised square (or
+/// octagonal) cells between straight channels, clipped to a circle
+#[pyclass]
+pub struct TapisserieLayer {
+    pub inner: BaseTapisserieLayer,
+}
+
+#[pymethods]
+impl TapisserieLayer {
+    /// Create a new tapisserie layer centered at origin
+    ///
+    /// # Arguments
+    /// * `cell_size` - Width of each raised cell in mm
+    /// * `channel_width` - Width of the channels between cells in mm
+    /// * `radius` - Radius of the circular clipping region in mm
+    /// * `rotation` - Rotation of the grid in radians
+    /// * `resolution` - Steps per full turn where cells follow the circle
+    /// * `truncate_corners` - Cut the cell corners off, leaving octagons
+    #[new]
+    #[pyo3(signature = (cell_size=1.0, channel_width=0.3, radius=15.0, rotation=0.0, resolution=360, truncate_corners=false))]
+    pub fn new(
+        cell_size: f64,
+        channel_width: f64,
+        radius: f64,
+        rotation: f64,
+        resolution: usize,
+        truncate_corners: bool,
+    ) -> PyResult<Self> {
+        let config = tapisserie_config(cell_size, channel_width, radius, rotation, resolution, truncate_corners);
+        BaseTapisserieLayer::new(config)
+            .map(|inner| TapisserieLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a tapisserie layer with a custom center point
+    #[staticmethod]
+    #[pyo3(signature = (center_x, center_y, cell_size=1.0, channel_width=0.3, radius=15.0, rotation=0.0, resolution=360, truncate_corners=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn with_center(
+        center_x: f64,
+        center_y: f64,
+        cell_size: f64,
+        channel_width: f64,
+        radius: f64,
+        rotation: f64,
+        resolution: usize,
+        truncate_corners: bool,
+    ) -> PyResult<Self> {
+        let config = tapisserie_config(cell_size, channel_width, radius, rotation, resolution, truncate_corners);
+        BaseTapisserieLayer::new_with_center(config, center_x, center_y)
+            .map(|inner| TapisserieLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a tapisserie layer positioned at a given angle and distance from origin
+    #[staticmethod]
+    #[pyo3(signature = (angle, distance, cell_size=1.0, channel_width=0.3, radius=15.0, rotation=0.0, resolution=360, truncate_corners=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_polar(
+        angle: f64,
+        distance: f64,
+        cell_size: f64,
+        channel_width: f64,
+        radius: f64,
+        rotation: f64,
+        resolution: usize,
+        truncate_corners: bool,
+    ) -> PyResult<Self> {
+        let config = tapisserie_config(cell_size, channel_width, radius, rotation, resolution, truncate_corners);
+        BaseTapisserieLayer::new_at_polar(config, angle, distance)
+            .map(|inner| TapisserieLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a tapisserie layer positioned at a clock position (like hour hand)
+    ///
+    /// # Arguments
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face to the layer center
+    #[staticmethod]
+    #[pyo3(signature = (hour, minute, distance, cell_size=1.0, channel_width=0.3, radius=15.0, rotation=0.0, resolution=360, truncate_corners=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_clock(
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        cell_size: f64,
+        channel_width: f64,
+        radius: f64,
+        rotation: f64,
+        resolution: usize,
+        truncate_corners: bool,
+    ) -> PyResult<Self> {
+        let config = tapisserie_config(cell_size, channel_width, radius, rotation, resolution, truncate_corners);
+        BaseTapisserieLayer::new_at_clock(config, hour, minute, distance)
+            .map(|inner| TapisserieLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Generate the cell outlines and channel centerlines
+    fn generate(&mut self) {
+        self.inner.generate();
+    }
+
+    /// Export the pattern to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner
+            .to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get all generated lines (cell outlines, then channel centerlines) as
+    /// list of list of (x, y) tuples
+    fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Get the closed cell outlines as list of list of (x, y) tuples
+    fn get_cell_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .cell_lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Get the channel centerlines as list of list of (x, y) tuples
+    fn get_channel_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .channel_lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Get the cell size
+    #[getter]
+    fn cell_size(&self) -> f64 {
+        self.inner.config.cell_size
+    }
+
+    /// Get the channel width
+    #[getter]
+    fn channel_width(&self) -> f64 {
+        self.inner.config.channel_width
+    }
+
+    /// Get the radius
+    #[getter]
+    fn radius(&self) -> f64 {
+        self.inner.config.radius
+    }
+
+    /// Get the grid rotation
+    #[getter]
+    fn rotation(&self) -> f64 {
+        self.inner.config.rotation
+    }
+
+    /// Whether the cells have their corners cut off
+    #[getter]
+    fn truncate_corners(&self) -> bool {
+        self.inner.config.truncate_corners
+    }
+
+    /// Get the center x coordinate
+    #[getter]
+    fn center_x(&self) -> f64 {
+        self.inner.center_x
+    }
+
+    /// Get the center y coordinate
+    #[getter]
+    fn center_y(&self) -> f64 {
+        self.inner.center_y
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "TapisserieLayer(cell_size={}, channel_width={}, radius={}, rotation={}, truncate_corners={}, center=({}, {}))",
+            self.inner.config.cell_size,
+            self.inner.config.channel_width,
+            self.inner.config.radius,
+            self.inner.config.rotation,
+            self.inner.config.truncate_corners,
+            self.inner.center_x,
+            self.inner.center_y
+        )
+    }
+}
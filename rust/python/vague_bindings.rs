@@ -0,0 +1,278 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use turtles::{
+    ExportConfig as BaseExportConfig,
+    PatternLayer,
+    VagueConfig as BaseVagueConfig,
+    VagueLayer as BaseVagueLayer,
+};
+
+use crate::rose_engine_bindings::{export_err, point_lines, stats_dict, transformed_lines, PointTuples};
+
+/// Build a VagueConfig from Python arguments
+#[allow(clippy::too_many_arguments)]
+pub fn vague_config(
+    num_lines: usize,
+    radius: f64,
+    wave_amplitude: f64,
+    wave_length: f64,
+    sweep_angle: f64,
+    group_size: usize,
+    group_phase_step: f64,
+    points_per_wave: usize,
+) -> BaseVagueConfig {
+    BaseVagueConfig {
+        num_lines,
+        radius,
+        wave_amplitude,
+        wave_length,
+        sweep_angle,
+        group_size,
+        group_phase_step,
+        points_per_wave,
+    }
+}
+
+/// Python wrapper for VagueLayer - parallel S-curves across a circle, laid
+/// in groups that share a phase so neighbouring groups interleave
+#[pyclass]
+pub struct VagueLayer {
+    pub inner: BaseVagueLayer,
+}
+
+#[pymethods]
+impl VagueLayer {
+    /// Create a new vague layer centered at origin
+    ///
+    /// # Arguments
+    /// * `num_lines` - Number of wave lines across the circle
+    /// * `radius` - Radius of the circular clipping region in mm
+    /// * `wave_amplitude` - Peak deviation of each wave from its track in mm
+    /// * `wave_length` - Length of one full wave in mm
+    /// * `sweep_angle` - Direction the waves travel in radians
+    /// * `group_size` - Consecutive lines sharing a phase
+    /// * `group_phase_step` - Phase shift between successive groups in radians
+    /// * `points_per_wave` - Points per wavelength
+    #[new]
+    #[pyo3(signature = (num_lines=40, radius=15.0, wave_amplitude=1.5, wave_length=10.0, sweep_angle=0.0, group_size=4, group_phase_step=std::f64::consts::FRAC_PI_2, points_per_wave=64))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        num_lines: usize,
+        radius: f64,
+        wave_amplitude: f64,
+        wave_length: f64,
+        sweep_angle: f64,
+        group_size: usize,
+        group_phase_step: f64,
+        points_per_wave: usize,
+    ) -> PyResult<Self> {
+        let config = vague_config(num_lines, radius, wave_amplitude, wave_length, sweep_angle, group_size, group_phase_step, points_per_wave);
+        BaseVagueLayer::new(config)
+            .map(|inner| VagueLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a vague layer with a custom center point
+    #[staticmethod]
+    #[pyo3(signature = (center_x, center_y, num_lines=40, radius=15.0, wave_amplitude=1.5, wave_length=10.0, sweep_angle=0.0, group_size=4, group_phase_step=std::f64::consts::FRAC_PI_2, points_per_wave=64))]
+    #[allow(clippy::too_many_arguments)]
+    fn with_center(
+        center_x: f64,
+        center_y: f64,
+        num_lines: usize,
+        radius: f64,
+        wave_amplitude: f64,
+        wave_length: f64,
+        sweep_angle: f64,
+        group_size: usize,
+        group_phase_step: f64,
+        points_per_wave: usize,
+    ) -> PyResult<Self> {
+        let config = vague_config(num_lines, radius, wave_amplitude, wave_length, sweep_angle, group_size, group_phase_step, points_per_wave);
+        BaseVagueLayer::new_with_center(config, center_x, center_y)
+            .map(|inner| VagueLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a vague layer positioned at a given angle and distance from origin
+    #[staticmethod]
+    #[pyo3(signature = (angle, distance, num_lines=40, radius=15.0, wave_amplitude=1.5, wave_length=10.0, sweep_angle=0.0, group_size=4, group_phase_step=std::f64::consts::FRAC_PI_2, points_per_wave=64))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_polar(
+        angle: f64,
+        distance: f64,
+        num_lines: usize,
+        radius: f64,
+        wave_amplitude: f64,
+        wave_length: f64,
+        sweep_angle: f64,
+        group_size: usize,
+        group_phase_step: f64,
+        points_per_wave: usize,
+    ) -> PyResult<Self> {
+        let config = vague_config(num_lines, radius, wave_amplitude, wave_length, sweep_angle, group_size, group_phase_step, points_per_wave);
+        BaseVagueLayer::new_at_polar(config, angle, distance)
+            .map(|inner| VagueLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a vague layer positioned at a clock position (like hour hand)
+    ///
+    /// # Arguments
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face to the layer center
+    #[staticmethod]
+    #[pyo3(signature = (hour, minute, distance, num_lines=40, radius=15.0, wave_amplitude=1.5, wave_length=10.0, sweep_angle=0.0, group_size=4, group_phase_step=std::f64::consts::FRAC_PI_2, points_per_wave=64))]
+    #[allow(clippy::too_many_arguments)]
+    fn at_clock(
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        num_lines: usize,
+        radius: f64,
+        wave_amplitude: f64,
+        wave_length: f64,
+        sweep_angle: f64,
+        group_size: usize,
+        group_phase_step: f64,
+        points_per_wave: usize,
+    ) -> PyResult<Self> {
+        let config = vague_config(num_lines, radius, wave_amplitude, wave_length, sweep_angle, group_size, group_phase_step, points_per_wave);
+        BaseVagueLayer::new_at_clock(config, hour, minute, distance)
+            .map(|inner| VagueLayer { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Generate the wave lines
+    fn generate(&mut self) {
+        self.inner.generate();
+    }
+
+    /// Export the pattern to SVG format, simplifying the lines to within
+    /// `tolerance` first when it is positive
+    #[pyo3(signature = (filename, tolerance=0.0))]
+    fn to_svg(&self, filename: &str, tolerance: f64) -> PyResult<()> {
+        let config = BaseExportConfig {
+            simplify_tolerance: tolerance,
+            ..Default::default()
+        };
+        self.inner
+            .to_svg_with_config(filename, &config)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Get all generated lines as list of list of (x, y) tuples
+    fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Get the phase group of each generated line, in the order of get_lines()
+    fn get_line_groups(&self) -> Vec<usize> {
+        self.inner.line_groups().to_vec()
+    }
+
+    /// Bounds, engraved length and point counts of the generated lines as
+    /// a dict, or None before generate()
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        stats_dict(py, self.inner.stats())
+    }
+
+    /// The generated lines mirrored across the vertical axis (x negated)
+    fn mirrored_x(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_x())
+    }
+
+    /// The generated lines mirrored across the horizontal axis (y negated)
+    fn mirrored_y(&self) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.mirrored_y())
+    }
+
+    /// The generated lines turned counterclockwise by `radians` about the
+    /// origin
+    fn rotated(&self, radians: f64) -> PyResult<Vec<Vec<(f64, f64)>>> {
+        transformed_lines(self.inner.rotated(radians))
+    }
+
+    /// `n` copies of the generated lines spaced evenly around a circle of
+    /// `radius`, each ready for GuillochePattern.add_raw_lines
+    fn replicated_radial(&self, n: usize, radius: f64) -> PyResult<Vec<Vec<PointTuples>>> {
+        self.inner
+            .replicated_radial(n, radius)
+            .map(|copies| copies.into_iter().map(point_lines).collect())
+            .map_err(export_err)
+    }
+
+    /// Get the number of lines
+    #[getter]
+    fn num_lines(&self) -> usize {
+        self.inner.config.num_lines
+    }
+
+    /// Get the radius
+    #[getter]
+    fn radius(&self) -> f64 {
+        self.inner.config.radius
+    }
+
+    /// Get the wave amplitude
+    #[getter]
+    fn wave_amplitude(&self) -> f64 {
+        self.inner.config.wave_amplitude
+    }
+
+    /// Get the wave length
+    #[getter]
+    fn wave_length(&self) -> f64 {
+        self.inner.config.wave_length
+    }
+
+    /// Get the sweep angle
+    #[getter]
+    fn sweep_angle(&self) -> f64 {
+        self.inner.config.sweep_angle
+    }
+
+    /// Get the number of lines sharing a phase
+    #[getter]
+    fn group_size(&self) -> usize {
+        self.inner.config.group_size
+    }
+
+    /// Get the phase shift between successive groups
+    #[getter]
+    fn group_phase_step(&self) -> f64 {
+        self.inner.config.group_phase_step
+    }
+
+    /// Get the center x coordinate
+    #[getter]
+    fn center_x(&self) -> f64 {
+        self.inner.center_x
+    }
+
+    /// Get the center y coordinate
+    #[getter]
+    fn center_y(&self) -> f64 {
+        self.inner.center_y
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VagueLayer(num_lines={}, radius={}, wave_amplitude={}, wave_length={}, sweep_angle={}, group_size={}, group_phase_step={}, center=({}, {}))",
+            self.inner.config.num_lines,
+            self.inner.config.radius,
+            self.inner.config.wave_amplitude,
+            self.inner.config.wave_length,
+            self.inner.config.sweep_angle,
+            self.inner.config.group_size,
+            self.inner.config.group_phase_step,
+            self.inner.center_x,
+            self.inner.center_y
+        )
+    }
+}
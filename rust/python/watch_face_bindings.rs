@@ -1,51 +1,491 @@
+use std::sync::{Arc, Mutex};
+
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use turtles::{
+    ApertureConfig as BaseApertureConfig,
+    ApertureShape as BaseApertureShape,
+    BezelConfig as BaseBezelConfig,
     ClousDeParisConfig as BaseClousDeParisConfig,
     ClousDeParisLayer as BaseClousDeParisLayer,
     CubeConfig as BaseCubeConfig,
     CubeLayer as BaseCubeLayer,
+    Density as BaseDensity,
+    DesignConstraints as BaseDesignConstraints,
+    DesignRecipe as BaseDesignRecipe,
     DiamantConfig as BaseDiamantConfig,
     DiamantLayer as BaseDiamantLayer,
     DraperieConfig as BaseDraperieConfig,
     DraperieLayer as BaseDraperieLayer,
+    DialConfig as BaseDialConfig,
+    DialShape as BaseDialShape,
+    Dither as BaseDither,
+    EditCommand as BaseEditCommand,
+    EditHistory as BaseEditHistory,
     ExportConfig as BaseExportConfig,
+    FileMetadata as BaseFileMetadata,
     FlinqueConfig as BaseFlinqueConfig,
     FlinqueLayer as BaseFlinqueLayer,
+    generate_random_design as base_generate_random_design,
+    HandConfig as BaseHandConfig,
+    HandStyle as BaseHandStyle,
+    HandsSet as BaseHandsSet,
+    HoleConfig as BaseHoleConfig,
     HorizontalSpirograph as BaseHorizontalSpirograph,
     HuitEightConfig as BaseHuitEightConfig,
     HuitEightLayer as BaseHuitEightLayer,
-    LimaconConfig as BaseLimaconConfig,
+    LayerKind as BaseLayerKind,
+    LayerSpec as BaseLayerSpec,
+    LayerStyle as BaseLayerStyle,
+    LayerValidationIssue as BaseLayerValidationIssue,
     LimaconLayer as BaseLimaconLayer,
+    OriginMode as BaseOriginMode,
+    OverlayOptions as BaseOverlayOptions,
     PaonConfig as BasePaonConfig,
     PaonLayer as BasePaonLayer,
+    Point2D,
+    ProjectSettings as BaseProjectSettings,
+    RadiusPolicy as BaseRadiusPolicy,
     SphericalSpirograph as BaseSphericalSpirograph,
+    Subdial as BaseSubdial,
+    guilloche::SpirographLayer as BaseSpirographLayer,
+    Unit as BaseUnit,
     VerticalSpirograph as BaseVerticalSpirograph,
     WatchFace as BaseWatchFace,
 };
 
+use crate::chapter_ring_bindings::{chapter_ring_config, ChapterRingLayer};
+use crate::index_markers_bindings::{index_config, IndexMarkersLayer};
 use crate::clous_de_paris_bindings::ClousDeParisLayer;
+use crate::cotes_de_geneve_bindings::{cotes_de_geneve_config, CotesDeGeneveLayer};
 use crate::cube_bindings::CubeLayer;
 use crate::diamant_bindings::DiamantLayer;
 use crate::draperie_bindings::DraperieLayer;
+use crate::engrave_text_bindings::{text_config, EngravedTextLayer};
+use crate::envelope_bindings::Envelope;
+use crate::grain_de_riz_bindings::{grain_de_riz_config, GrainDeRizLayer};
 use crate::guilloche_bindings::FlinqueLayer;
 use crate::huiteight_bindings::HuitEightLayer;
-use crate::limacon_bindings::LimaconLayer;
-use crate::paon_bindings::PaonLayer;
+use crate::limacon_bindings::{limacon_config, LimaconLayer};
+use crate::paon_bindings::{parse_paon_origin, PaonLayer};
+use crate::phyllotaxis_bindings::{phyllotaxis_config, PhyllotaxisLayer};
+use crate::export_bindings::{export_config, ExportConfig};
+use crate::rose_engine_bindings::export_err;
+use crate::soleil_bindings::{soleil_config, SoleilLayer};
 use crate::spirograph_bindings::{HorizontalSpirograph, SphericalSpirograph, VerticalSpirograph};
+use crate::tapisserie_bindings::{tapisserie_config, TapisserieLayer};
+use crate::vague_bindings::{vague_config, VagueLayer};
+
+/// Reference image as (href, width_mm, opacity, rotation_deg, offset)
+type ReferenceTuple = (String, f64, f64, f64, (f64, f64));
+
+/// Parse a unit name ("mm" or "in")
+pub(crate) fn parse_unit(unit: &str) -> PyResult<BaseUnit> {
+    match unit.to_lowercase().as_str() {
+        "mm" | "millimeter" | "millimeters" => Ok(BaseUnit::Millimeter),
+        "in" | "inch" | "inches" => Ok(BaseUnit::Inch),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(
+            "unit must be 'mm' or 'in'",
+        )),
+    }
+}
+
+/// Parse a radius policy: "watch" (26-44mm), "unrestricted" or a
+/// (min, max) range in mm; None is "watch"
+pub(crate) fn parse_radius_policy(policy: Option<&Bound<'_, PyAny>>) -> PyResult<BaseRadiusPolicy> {
+    let Some(policy) = policy else {
+        return Ok(BaseRadiusPolicy::Watch);
+    };
+    if let Ok(name) = policy.extract::<String>() {
+        return match name.to_lowercase().as_str() {
+            "watch" => Ok(BaseRadiusPolicy::Watch),
+            "unrestricted" => Ok(BaseRadiusPolicy::Unrestricted),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(
+                "radius_policy must be 'watch', 'unrestricted' or a (min, max) tuple",
+            )),
+        };
+    }
+    let (min, max) = policy.extract::<(f64, f64)>().map_err(|_| {
+        pyo3::exceptions::PyValueError::new_err(
+            "radius_policy must be 'watch', 'unrestricted' or a (min, max) tuple",
+        )
+    })?;
+    let policy = BaseRadiusPolicy::Custom { min, max };
+    policy
+        .validate()
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    Ok(policy)
+}
+
+/// Parse an export origin name ("center", "top_left" or "bottom_left")
+pub(crate) fn parse_origin(origin: &str) -> PyResult<BaseOriginMode> {
+    match origin.to_lowercase().as_str() {
+        "center" => Ok(BaseOriginMode::CenterOrigin),
+        "top_left" => Ok(BaseOriginMode::TopLeftYDown),
+        "bottom_left" => Ok(BaseOriginMode::BottomLeftYUp),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(
+            "origin must be 'center', 'top_left' or 'bottom_left'",
+        )),
+    }
+}
+
+/// Build metadata from the keyword arguments of `set_metadata`
+pub(crate) fn file_metadata(
+    design_id: &str,
+    params_hash: &str,
+    license: &str,
+    generator_version: Option<&str>,
+    extra: Option<&Bound<'_, PyDict>>,
+) -> PyResult<BaseFileMetadata> {
+    let mut metadata = BaseFileMetadata::new(design_id)
+        .with_params_hash(params_hash)
+        .with_license(license);
+    if let Some(version) = generator_version {
+        metadata.generator_version = version.to_string();
+    }
+    if let Some(extra) = extra {
+        for (key, value) in extra.iter() {
+            metadata.extra.push((key.extract()?, value.extract()?));
+        }
+    }
+    Ok(metadata)
+}
+
+/// Metadata entries as a dict, fixed fields first
+pub(crate) fn metadata_dict<'py>(py: Python<'py>, metadata: &BaseFileMetadata) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (key, value) in metadata.entries() {
+        dict.set_item(key, value)?;
+    }
+    Ok(dict)
+}
+
+/// A layer validation issue as a dict with kind, index, field and message
+fn issue_dict<'py>(py: Python<'py>, issue: &BaseLayerValidationIssue) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("kind", issue.kind.name())?;
+    dict.set_item("index", issue.index)?;
+    dict.set_item("field", &issue.field)?;
+    dict.set_item("message", &issue.message)?;
+    Ok(dict)
+}
+
+/// Parse a layer kind name as passed to post-process callbacks
+fn layer_kind(kind: &str) -> PyResult<BaseLayerKind> {
+    BaseLayerKind::from_name(kind).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!("unknown layer kind '{}'", kind))
+    })
+}
+
+/// Override the fields of the `name` hand given in `dict`
+fn apply_hand_config(name: &str, dict: &Bound<'_, PyDict>, config: &mut BaseHandConfig) -> PyResult<()> {
+    for (key, value) in dict.iter() {
+        let key: String = key.extract()?;
+        match key.as_str() {
+            "style" => {
+                let style: String = value.extract()?;
+                config.style = BaseHandStyle::from_name(&style).ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err(format!("unknown hand style '{}'", style))
+                })?;
+            }
+            "length_ratio" => config.length_ratio = value.extract()?,
+            "width" => config.width = value.extract()?,
+            "color" => config.color = value.extract()?,
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} hand has unknown key '{}'",
+                    name, key
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A copy of any layer object, to be added by an edit command
+fn layer_spec(layer: &Bound<'_, PyAny>) -> PyResult<BaseLayerSpec> {
+    if let Ok(spiro) = layer.extract::<PyRef<HorizontalSpirograph>>() {
+        return Ok(BaseLayerSpec::from(BaseSpirographLayer::Horizontal(spiro.inner.clone())));
+    }
+    if let Ok(spiro) = layer.extract::<PyRef<VerticalSpirograph>>() {
+        return Ok(BaseLayerSpec::from(BaseSpirographLayer::Vertical(spiro.inner.clone())));
+    }
+    if let Ok(spiro) = layer.extract::<PyRef<SphericalSpirograph>>() {
+        return Ok(BaseLayerSpec::from(BaseSpirographLayer::Spherical(spiro.inner.clone())));
+    }
+    if let Ok(flinque) = layer.extract::<PyRef<FlinqueLayer>>() {
+        return Ok(BaseLayerSpec::from(flinque.inner.clone()));
+    }
+    if let Ok(diamant) = layer.extract::<PyRef<DiamantLayer>>() {
+        return Ok(BaseLayerSpec::from(diamant.inner.clone()));
+    }
+    if let Ok(draperie) = layer.extract::<PyRef<DraperieLayer>>() {
+        return Ok(BaseLayerSpec::from(draperie.inner.clone()));
+    }
+    if let Ok(huiteight) = layer.extract::<PyRef<HuitEightLayer>>() {
+        return Ok(BaseLayerSpec::from(huiteight.inner.clone()));
+    }
+    if let Ok(limacon) = layer.extract::<PyRef<LimaconLayer>>() {
+        return Ok(BaseLayerSpec::from(limacon.inner.clone()));
+    }
+    if let Ok(paon) = layer.extract::<PyRef<PaonLayer>>() {
+        return Ok(BaseLayerSpec::from(paon.inner.clone()));
+    }
+    if let Ok(cdp) = layer.extract::<PyRef<ClousDeParisLayer>>() {
+        return Ok(BaseLayerSpec::from(cdp.inner.clone()));
+    }
+    if let Ok(cube) = layer.extract::<PyRef<CubeLayer>>() {
+        return Ok(BaseLayerSpec::from(cube.inner.clone()));
+    }
+    if let Ok(phyllotaxis) = layer.extract::<PyRef<PhyllotaxisLayer>>() {
+        return Ok(BaseLayerSpec::from(phyllotaxis.inner.clone()));
+    }
+    if let Ok(grain_de_riz) = layer.extract::<PyRef<GrainDeRizLayer>>() {
+        return Ok(BaseLayerSpec::from(grain_de_riz.inner.clone()));
+    }
+    if let Ok(soleil) = layer.extract::<PyRef<SoleilLayer>>() {
+        return Ok(BaseLayerSpec::from(soleil.inner.clone()));
+    }
+    if let Ok(cotes) = layer.extract::<PyRef<CotesDeGeneveLayer>>() {
+        return Ok(BaseLayerSpec::from(cotes.inner.clone()));
+    }
+    if let Ok(tapisserie) = layer.extract::<PyRef<TapisserieLayer>>() {
+        return Ok(BaseLayerSpec::from(tapisserie.inner.clone()));
+    }
+    if let Ok(vague) = layer.extract::<PyRef<VagueLayer>>() {
+        return Ok(BaseLayerSpec::from(vague.inner.clone()));
+    }
+    if let Ok(text) = layer.extract::<PyRef<EngravedTextLayer>>() {
+        return Ok(BaseLayerSpec::from(text.inner.clone()));
+    }
+    if let Ok(ring) = layer.extract::<PyRef<ChapterRingLayer>>() {
+        return Ok(BaseLayerSpec::from(ring.inner.clone()));
+    }
+    if let Ok(markers) = layer.extract::<PyRef<IndexMarkersLayer>>() {
+        return Ok(BaseLayerSpec::from(markers.inner.clone()));
+    }
+    Err(pyo3::exceptions::PyTypeError::new_err(
+        "Expected a spirograph or pattern layer",
+    ))
+}
+
+/// A keyword argument of `apply`, if given
+fn edit_arg<'py>(kwargs: Option<&Bound<'py, PyDict>>, key: &str) -> PyResult<Option<Bound<'py, PyAny>>> {
+    match kwargs {
+        Some(kwargs) => kwargs.get_item(key),
+        None => Ok(None),
+    }
+}
+
+/// A keyword argument `op` cannot do without
+fn required_edit_arg<'py>(kwargs: Option<&Bound<'py, PyDict>>, op: &str, key: &str) -> PyResult<Bound<'py, PyAny>> {
+    edit_arg(kwargs, key)?.ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!("'{}' needs a '{}' argument", op, key))
+    })
+}
+
+/// Build an edit command from the op name and keyword arguments of `apply`
+fn edit_command(op: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<BaseEditCommand> {
+    let enabled = match edit_arg(kwargs, "enabled")? {
+        Some(value) => value.extract::<bool>()?,
+        None => true,
+    };
+    let command = match op {
+        "add_layer" => BaseEditCommand::AddLayer(layer_spec(&required_edit_arg(kwargs, op, "layer")?)?),
+        "remove_layer" => BaseEditCommand::RemoveLayer {
+            kind: layer_kind(&required_edit_arg(kwargs, op, "kind")?.extract::<String>()?)?,
+            index: required_edit_arg(kwargs, op, "index")?.extract()?,
+        },
+        "move_layer" => BaseEditCommand::MoveLayer {
+            kind: layer_kind(&required_edit_arg(kwargs, op, "kind")?.extract::<String>()?)?,
+            from: required_edit_arg(kwargs, op, "index")?.extract()?,
+            to: required_edit_arg(kwargs, op, "to")?.extract()?,
+        },
+        "set_layer_center" => BaseEditCommand::SetLayerCenter {
+            kind: layer_kind(&required_edit_arg(kwargs, op, "kind")?.extract::<String>()?)?,
+            index: required_edit_arg(kwargs, op, "index")?.extract()?,
+            x: required_edit_arg(kwargs, op, "x")?.extract()?,
+            y: required_edit_arg(kwargs, op, "y")?.extract()?,
+        },
+        "set_dial" if !enabled => BaseEditCommand::SetDial(None),
+        "set_dial" => {
+            let mut config = BaseDialConfig::default();
+            if let Some(color) = edit_arg(kwargs, "fill_color")? {
+                config.fill_color = color.extract()?;
+            }
+            if let Some(color) = edit_arg(kwargs, "stroke_color")? {
+                config.stroke_color = color.extract()?;
+            }
+            if let Some(width) = edit_arg(kwargs, "stroke_width")? {
+                config.stroke_width = width.extract()?;
+            }
+            BaseEditCommand::SetDial(Some(config))
+        }
+        "set_bezel" if !enabled => BaseEditCommand::SetBezel(None),
+        "set_bezel" => {
+            let mut config = BaseBezelConfig::default();
+            if let Some(ratio) = edit_arg(kwargs, "radius_ratio")? {
+                config.radius_ratio = ratio.extract()?;
+            }
+            if let Some(color) = edit_arg(kwargs, "stroke_color")? {
+                config.stroke_color = color.extract()?;
+            }
+            if let Some(width) = edit_arg(kwargs, "stroke_width")? {
+                config.stroke_width = width.extract()?;
+            }
+            BaseEditCommand::SetBezel(Some(config))
+        }
+        "add_hole" => {
+            let mut config = BaseHoleConfig::default();
+            if let Some(x) = edit_arg(kwargs, "x")? {
+                config.center_x = x.extract()?;
+            }
+            if let Some(y) = edit_arg(kwargs, "y")? {
+                config.center_y = y.extract()?;
+            }
+            if let Some(radius) = edit_arg(kwargs, "radius")? {
+                config.radius = radius.extract()?;
+            }
+            if let Some(color) = edit_arg(kwargs, "fill_color")? {
+                config.fill_color = color.extract()?;
+            }
+            BaseEditCommand::AddHole(config)
+        }
+        _ => {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "unknown edit '{}'; expected add_layer, remove_layer, move_layer, set_layer_center, set_dial, set_bezel or add_hole",
+                op
+            )))
+        }
+    };
+    Ok(command)
+}
+
+/// Build a complication aperture from Python arguments; `shape` is "rect"
+/// (width, height, corner_radius, rotation) or "circle" (radius)
+#[allow(clippy::too_many_arguments)]
+fn aperture_config(
+    shape: &str,
+    width: f64,
+    height: f64,
+    corner_radius: f64,
+    rotation: f64,
+    radius: f64,
+    stroke_color: &str,
+    stroke_width: f64,
+) -> PyResult<BaseApertureConfig> {
+    let shape = match shape.to_lowercase().as_str() {
+        "rect" => BaseApertureShape::Rect { width, height, corner_radius, rotation },
+        "circle" => BaseApertureShape::Circle { radius },
+        _ => {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "aperture shape must be 'rect' or 'circle'",
+            ))
+        }
+    };
+    Ok(BaseApertureConfig {
+        shape,
+        stroke_color: stroke_color.to_string(),
+        stroke_width,
+        ..BaseApertureConfig::circle(radius)
+    })
+}
 
 /// Python wrapper for WatchFace
 #[pyclass]
 pub struct WatchFace {
     inner: BaseWatchFace,
+    /// Undo/redo stacks for edits made through apply()
+    history: BaseEditHistory,
+    /// First error raised by a Python post-process callback, re-raised by generate()
+    post_error: Arc<Mutex<Option<PyErr>>>,
 }
 
 #[pymethods]
 impl WatchFace {
     #[new]
-    #[pyo3(signature = (radius))]
-    fn new(radius: f64) -> PyResult<Self> {
-        BaseWatchFace::new(radius)
-            .map(|inner| WatchFace { inner })
+    #[pyo3(signature = (radius, unit="mm", radius_policy=None))]
+    fn new(radius: f64, unit: &str, radius_policy: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let settings = BaseProjectSettings::new(parse_unit(unit)?)
+            .with_radius_policy(parse_radius_policy(radius_policy)?);
+        BaseWatchFace::new_with_settings(radius, settings)
+            .map(Self::from_inner)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a face with an oval dial of semi-axes `rx` and `ry`; the
+    /// larger one is validated as the radius
+    #[staticmethod]
+    #[pyo3(signature = (rx, ry, unit="mm", radius_policy=None))]
+    fn oval(rx: f64, ry: f64, unit: &str, radius_policy: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let settings = BaseProjectSettings::new(parse_unit(unit)?)
+            .with_radius_policy(parse_radius_policy(radius_policy)?);
+        BaseWatchFace::new_with_shape(BaseDialShape::Ellipse { rx, ry }, settings)
+            .map(Self::from_inner)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Assemble a random design from `seed`; returns the face and its recipe
+    /// as JSON. `forbid` lists layer kind names that must not appear and
+    /// designs with a groove pitch under `min_groove_spacing` are re-rolled
+    #[staticmethod]
+    #[pyo3(signature = (seed, radius=None, density="medium", forbid=None, chapter_ring=false, min_groove_spacing=None))]
+    fn random_design(
+        seed: u64,
+        radius: Option<f64>,
+        density: &str,
+        forbid: Option<Vec<String>>,
+        chapter_ring: bool,
+        min_groove_spacing: Option<f64>,
+    ) -> PyResult<(Self, String)> {
+        let density = BaseDensity::from_name(&density.to_lowercase()).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(
+                "density must be 'sparse', 'medium' or 'dense'",
+            )
+        })?;
+        let forbidden = forbid
+            .unwrap_or_default()
+            .iter()
+            .map(|kind| layer_kind(kind))
+            .collect::<PyResult<Vec<_>>>()?;
+        let constraints = BaseDesignConstraints {
+            radius,
+            forbidden,
+            density,
+            chapter_ring,
+            min_groove_spacing,
+            ..Default::default()
+        };
+        let (inner, recipe) = base_generate_random_design(seed, &constraints)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let json = serde_json::to_string(&recipe)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok((Self::from_inner(inner), json))
+    }
+
+    /// Rebuild the face a `random_design` recipe (as JSON) describes
+    #[staticmethod]
+    fn from_recipe(recipe: &str) -> PyResult<Self> {
+        let recipe: BaseDesignRecipe = serde_json::from_str(recipe)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        recipe
+            .build()
+            .map(Self::from_inner)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Read a face saved with to_json(); call generate() before exporting
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        BaseWatchFace::from_json(json)
+            .map(Self::from_inner)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// The whole design as JSON, without generated lines or post-process callbacks
+    fn to_json(&self) -> PyResult<String> {
+        self.inner
+            .to_json()
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
 
@@ -54,6 +494,119 @@ impl WatchFace {
         self.inner.radius()
     }
 
+    /// Semi-axes of the dial outline as (rx, ry); equal for a round dial
+    #[getter]
+    fn semi_axes(&self) -> (f64, f64) {
+        self.inner.shape().semi_axes()
+    }
+
+    /// Design unit ("mm" or "in")
+    #[getter]
+    fn unit(&self) -> &'static str {
+        self.inner.unit().suffix()
+    }
+
+    /// Switch the design unit, rescaling all configs and generated lines
+    #[pyo3(signature = (unit))]
+    fn convert_units(&mut self, unit: &str) -> PyResult<()> {
+        self.inner.convert_units(parse_unit(unit)?);
+        Ok(())
+    }
+
+    /// Set the most points written to one SVG path; longer lines are split
+    #[pyo3(signature = (max_points))]
+    fn set_max_points_per_path(&mut self, max_points: usize) -> PyResult<()> {
+        self.inner
+            .set_max_points_per_path(max_points)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Write each layer's configuration and geometry stats as data-*
+    /// attributes on its SVG group
+    fn set_svg_layer_data(&mut self, enabled: bool) {
+        self.inner.set_svg_layer_data(enabled);
+    }
+
+    /// Set the space left around the content when sizing the SVG viewBox
+    #[pyo3(signature = (margin))]
+    fn set_svg_margin(&mut self, margin: f64) -> PyResult<()> {
+        self.inner
+            .set_svg_margin(margin)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Pin the SVG viewBox to (min_x, min_y, width, height); None fits the content
+    #[pyo3(signature = (viewbox=None))]
+    fn set_fixed_viewbox(&mut self, viewbox: Option<(f64, f64, f64, f64)>) -> PyResult<()> {
+        self.inner
+            .set_fixed_viewbox(viewbox)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Set a reference image (href written as given) drawn under the pattern
+    /// when exporting with include_reference; width and offset are in mm
+    #[pyo3(signature = (href, width_mm, opacity=0.5, rotation_deg=0.0, offset=(0.0, 0.0)))]
+    fn set_reference_image(
+        &mut self,
+        href: &str,
+        width_mm: f64,
+        opacity: f64,
+        rotation_deg: f64,
+        offset: (f64, f64),
+    ) -> PyResult<()> {
+        self.inner
+            .set_reference_image(href, width_mm, opacity, rotation_deg, offset)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Get the reference image as (href, width_mm, opacity, rotation_deg, offset), or None
+    #[getter]
+    fn reference_image(&self) -> Option<ReferenceTuple> {
+        self.inner.reference_image().map(|r| {
+            (
+                r.href.clone(),
+                r.width,
+                r.opacity,
+                r.rotation_deg,
+                r.offset,
+            )
+        })
+    }
+
+    /// Remove the reference image
+    fn clear_reference(&mut self) {
+        self.inner.clear_reference();
+    }
+
+    /// Set traceability metadata written into SVG, STEP and DXF exports
+    ///
+    /// generator_version defaults to this library's version; extra is a
+    /// dict of further string fields.
+    #[pyo3(signature = (design_id, params_hash="", license="", generator_version=None, extra=None))]
+    fn set_metadata(
+        &mut self,
+        design_id: &str,
+        params_hash: &str,
+        license: &str,
+        generator_version: Option<&str>,
+        extra: Option<Bound<'_, PyDict>>,
+    ) -> PyResult<()> {
+        let metadata = file_metadata(design_id, params_hash, license, generator_version, extra.as_ref())?;
+        self.inner.set_metadata(metadata);
+        Ok(())
+    }
+
+    /// Get the export metadata as a dict of its non-empty fields, or None
+    #[getter]
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.inner.metadata().map(|m| metadata_dict(py, m)).transpose()
+    }
+
+    /// Stop embedding metadata in exports
+    fn clear_metadata(&mut self) {
+        self.inner.clear_metadata();
+    }
+
     /// Add the inner dial circle with default styling
     fn add_inner(&mut self) {
         self.inner.add_inner();
@@ -81,15 +634,184 @@ impl WatchFace {
         self.inner.add_hole_at_clock(hour, minute, distance, hole_radius);
     }
 
+    /// Add a complication aperture (date window or subdial opening) centred at (x, y)
+    ///
+    /// Pattern lines stop at its edge, its outline is drawn with its own
+    /// stroke, and solid and DXF exports cut it through the dial.
+    #[pyo3(signature = (x, y, shape="rect", width=6.0, height=4.0, corner_radius=0.0, rotation=0.0, radius=5.0, stroke_color="#2c2c2c", stroke_width=0.2))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_aperture(
+        &mut self,
+        x: f64,
+        y: f64,
+        shape: &str,
+        width: f64,
+        height: f64,
+        corner_radius: f64,
+        rotation: f64,
+        radius: f64,
+        stroke_color: &str,
+        stroke_width: f64,
+    ) -> PyResult<()> {
+        let config = aperture_config(shape, width, height, corner_radius, rotation, radius, stroke_color, stroke_width)?;
+        self.inner
+            .add_aperture(BaseApertureConfig { center_x: x, center_y: y, ..config })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add a complication aperture centred at a clock position; see `add_aperture`
+    #[pyo3(signature = (hour, minute, distance, shape="rect", width=6.0, height=4.0, corner_radius=0.0, rotation=0.0, radius=5.0, stroke_color="#2c2c2c", stroke_width=0.2))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_aperture_at_clock(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        shape: &str,
+        width: f64,
+        height: f64,
+        corner_radius: f64,
+        rotation: f64,
+        radius: f64,
+        stroke_color: &str,
+        stroke_width: f64,
+    ) -> PyResult<()> {
+        let config = aperture_config(shape, width, height, corner_radius, rotation, radius, stroke_color, stroke_width)?;
+        self.inner
+            .add_aperture_at_clock(hour, minute, distance, config)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Remove every complication aperture
+    fn clear_apertures(&mut self) {
+        self.inner.clear_apertures();
+    }
+
+    /// Nest `face` as a subdial of `radius` centred at (x, y)
+    ///
+    /// Its layers are clipped to the subdial circle, which is ringed with
+    /// its own stroke; with `mask` the main pattern stops at the circle.
+    #[pyo3(signature = (x, y, radius, face, mask=true, stroke_color="#2c2c2c", stroke_width=0.2))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_subdial(
+        &mut self,
+        x: f64,
+        y: f64,
+        radius: f64,
+        face: &WatchFace,
+        mask: bool,
+        stroke_color: &str,
+        stroke_width: f64,
+    ) -> PyResult<()> {
+        self.inner
+            .add_subdial(BaseSubdial {
+                center_x: x,
+                center_y: y,
+                mask,
+                stroke_color: stroke_color.to_string(),
+                stroke_width,
+                ..BaseSubdial::new(radius, face.inner.clone())
+            })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Nest `face` as a subdial centred at a clock position; see `add_subdial`
+    #[pyo3(signature = (hour, minute, distance, radius, face, mask=true, stroke_color="#2c2c2c", stroke_width=0.2))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_subdial_at_clock(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        radius: f64,
+        face: &WatchFace,
+        mask: bool,
+        stroke_color: &str,
+        stroke_width: f64,
+    ) -> PyResult<()> {
+        let (x, y) = turtles::clock_to_cartesian(hour, minute, distance);
+        self.add_subdial(x, y, radius, face, mask, stroke_color, stroke_width)
+    }
+
+    /// Number of subdials
+    #[getter]
+    fn subdial_count(&self) -> usize {
+        self.inner.subdials().len()
+    }
+
+    /// Remove every subdial
+    fn clear_subdials(&mut self) {
+        self.inner.clear_subdials();
+    }
+
+    /// Draw hands set to hour:minute, with a seconds hand when `second` is given
+    ///
+    /// Each of `hour_hand`, `minute_hand` and `second_hand` is an optional
+    /// dict overriding the default hand's "style" ("baton", "dauphine" or
+    /// "breguet"), "length_ratio" of the face radius, "width" and "color".
+    /// Hands are drawn over everything in SVG and left out of STL and STEP
+    /// unless `set_hands_in_exports(True)`.
+    #[pyo3(signature = (hour, minute, second=None, hour_hand=None, minute_hand=None, second_hand=None))]
+    fn add_hands(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        second: Option<u32>,
+        hour_hand: Option<Bound<'_, PyDict>>,
+        minute_hand: Option<Bound<'_, PyDict>>,
+        second_hand: Option<Bound<'_, PyDict>>,
+    ) -> PyResult<()> {
+        let mut configs = BaseHandsSet::default();
+        for (name, dict, config) in [
+            ("hour", hour_hand, &mut configs.hour),
+            ("minute", minute_hand, &mut configs.minute),
+            ("second", second_hand, &mut configs.second),
+        ] {
+            if let Some(dict) = dict {
+                apply_hand_config(name, &dict, config)?;
+            }
+        }
+        self.inner
+            .add_hands(hour, minute, second, configs)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Remove the hands
+    fn clear_hands(&mut self) {
+        self.inner.clear_hands();
+    }
+
+    /// Cut the hands into STL and add them as a STEP part (off by default)
+    fn set_hands_in_exports(&mut self, enabled: bool) {
+        self.inner.set_hands_in_exports(enabled);
+    }
+
+    /// The closed outlines of each hand, hour then minute then second
+    ///
+    /// A Breguet hand has two outlines, the second the hole in its ring.
+    fn get_hand_outlines(&self) -> Vec<Vec<Vec<(f64, f64)>>> {
+        self.inner
+            .hand_outlines()
+            .into_iter()
+            .map(|outlines| {
+                outlines
+                    .into_iter()
+                    .map(|outline| outline.into_iter().map(|p| (p.x, p.y)).collect())
+                    .collect()
+            })
+            .collect()
+    }
+
     /// Add a spirograph layer (HorizontalSpirograph, VerticalSpirograph, or SphericalSpirograph)
     fn add_layer(&mut self, spiro: &Bound<'_, PyAny>) -> PyResult<()> {
         if let Ok(h_spiro) = spiro.extract::<PyRef<HorizontalSpirograph>>() {
-            let new_spiro = BaseHorizontalSpirograph::new(
+            let new_spiro = BaseHorizontalSpirograph::new_with_policy(
                 h_spiro.inner.outer_radius,
                 h_spiro.inner.radius_ratio,
                 h_spiro.inner.point_distance,
                 h_spiro.inner.rotations,
                 h_spiro.inner.resolution,
+                h_spiro.inner.radius_policy,
             )
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
             self.inner.add_horizontal_layer(new_spiro);
@@ -97,7 +819,7 @@ impl WatchFace {
         }
 
         if let Ok(v_spiro) = spiro.extract::<PyRef<VerticalSpirograph>>() {
-            let new_spiro = BaseVerticalSpirograph::new(
+            let new_spiro = BaseVerticalSpirograph::new_with_policy(
                 v_spiro.inner.outer_radius,
                 v_spiro.inner.radius_ratio,
                 v_spiro.inner.point_distance,
@@ -105,6 +827,7 @@ impl WatchFace {
                 v_spiro.inner.resolution,
                 v_spiro.inner.wave_amplitude,
                 v_spiro.inner.wave_frequency,
+                v_spiro.inner.radius_policy,
             )
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
             self.inner.add_vertical_layer(new_spiro);
@@ -112,13 +835,14 @@ impl WatchFace {
         }
 
         if let Ok(s_spiro) = spiro.extract::<PyRef<SphericalSpirograph>>() {
-            let new_spiro = BaseSphericalSpirograph::new(
+            let new_spiro = BaseSphericalSpirograph::new_with_policy(
                 s_spiro.inner.outer_radius,
                 s_spiro.inner.radius_ratio,
                 s_spiro.inner.point_distance,
                 s_spiro.inner.rotations,
                 s_spiro.inner.resolution,
                 s_spiro.inner.dome_height,
+                s_spiro.inner.radius_policy,
             )
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
             self.inner.add_spherical_layer(new_spiro);
@@ -146,6 +870,7 @@ impl WatchFace {
     /// * `wave_frequency` - Vertical wave frequency (for vertical spirograph)
     /// * `dome_height` - Height of dome (for spherical spirograph)
     #[pyo3(signature = (spiro_type, outer_radius, radius_ratio, point_distance, rotations, resolution, hour, minute, distance, wave_amplitude=1.0, wave_frequency=5.0, dome_height=5.0))]
+    #[allow(clippy::too_many_arguments)]
     fn add_layer_at_clock(
         &mut self,
         spiro_type: &str,
@@ -202,8 +927,10 @@ impl WatchFace {
         Ok(())
     }
 
-    /// Add a flinqué layer positioned at a clock position
-    #[pyo3(signature = (radius, hour, minute, distance, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05))]
+    /// Add a flinqué layer positioned at a clock position; `resolution` is the
+    /// points per ring (default 80 per petal)
+    #[pyo3(signature = (radius, hour, minute, distance, num_petals=12, num_waves=60, wave_amplitude=0.8, wave_frequency=20.0, inner_radius_ratio=0.05, resolution=None))]
+    #[allow(clippy::too_many_arguments)]
     fn add_flinque_at_clock(
         &mut self,
         radius: f64,
@@ -215,6 +942,7 @@ impl WatchFace {
         wave_amplitude: f64,
         wave_frequency: f64,
         inner_radius_ratio: f64,
+        resolution: Option<usize>,
     ) -> PyResult<()> {
         let config = BaseFlinqueConfig {
             num_petals,
@@ -222,6 +950,10 @@ impl WatchFace {
             wave_amplitude,
             wave_frequency,
             inner_radius_ratio,
+            resolution: resolution.map(Into::into),
+            petal_phase_step: 0.0,
+            alternate: false,
+            jitter: None,
         };
         self.inner
             .add_flinque_at_clock(radius, config, hour, minute, distance)
@@ -254,7 +986,8 @@ impl WatchFace {
         let config = BaseDiamantConfig {
             num_circles,
             circle_radius,
-            resolution,
+            resolution: resolution.into(),
+            ..Default::default()
         };
         self.inner
             .add_diamant_at_clock(config, hour, minute, distance)
@@ -275,6 +1008,7 @@ impl WatchFace {
 
     /// Add a draperie layer positioned at a clock position
     #[pyo3(signature = (hour, minute, distance, num_rings=96, base_radius=22.0, radius_step=0.44, wave_frequency=12.0, phase_shift=None, phase_oscillations=2.5, resolution=1500, phase_exponent=3, wave_exponent=1, circular_phase=2.0))]
+    #[allow(clippy::too_many_arguments)]
     fn add_draperie_at_clock(
         &mut self,
         hour: u32,
@@ -299,10 +1033,13 @@ impl WatchFace {
             amplitude: None,
             phase_shift: phase_shift.unwrap_or(std::f64::consts::PI / 12.0),
             phase_oscillations,
-            resolution,
+            resolution: resolution.into(),
             phase_exponent,
             wave_exponent,
             circular_phase,
+            non_integer_mode: Default::default(),
+            dash_by_phase: None,
+            jitter: None,
         };
         self.inner
             .add_draperie_at_clock(config, hour, minute, distance)
@@ -323,6 +1060,7 @@ impl WatchFace {
 
     /// Add a huit-eight layer positioned at a clock position
     #[pyo3(signature = (num_curves, scale, hour, minute, distance, resolution=360, num_clusters=0, cluster_spread=0.0))]
+    #[allow(clippy::too_many_arguments)]
     fn add_huiteight_at_clock(
         &mut self,
         num_curves: usize,
@@ -337,7 +1075,7 @@ impl WatchFace {
         let config = BaseHuitEightConfig {
             num_curves,
             scale,
-            resolution,
+            resolution: resolution.into(),
             num_clusters,
             cluster_spread,
         };
@@ -359,7 +1097,8 @@ impl WatchFace {
     }
 
     /// Add a limaçon layer positioned at a clock position
-    #[pyo3(signature = (num_curves, base_radius, amplitude, hour, minute, distance, resolution=360))]
+    #[pyo3(signature = (num_curves, base_radius, amplitude, hour, minute, distance, resolution=360, loop_mode="full"))]
+    #[allow(clippy::too_many_arguments)]
     fn add_limacon_at_clock(
         &mut self,
         num_curves: usize,
@@ -369,18 +1108,143 @@ impl WatchFace {
         minute: u32,
         distance: f64,
         resolution: usize,
+        loop_mode: &str,
     ) -> PyResult<()> {
-        let config = BaseLimaconConfig {
-            num_curves,
-            base_radius,
-            amplitude,
-            resolution,
-        };
+        let config = limacon_config(num_curves, base_radius, amplitude, resolution, loop_mode)?;
         self.inner
             .add_limacon_at_clock(config, hour, minute, distance)
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
 
+    /// Add an engraved text layer
+    fn add_text_layer(&mut self, text: &EngravedTextLayer) {
+        self.inner.add_text_layer(text.inner.clone());
+    }
+
+    /// Add engraved single-stroke text, on an arc when `radius` is given
+    #[pyo3(signature = (text, height=3.0, radius=None, hour=12, minute=0, direction="clockwise", origin=(0.0, 0.0), angle=0.0, spacing=0.0))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_text(
+        &mut self,
+        text: &str,
+        height: f64,
+        radius: Option<f64>,
+        hour: u32,
+        minute: u32,
+        direction: &str,
+        origin: (f64, f64),
+        angle: f64,
+        spacing: f64,
+    ) -> PyResult<()> {
+        let config = text_config(text, height, radius, hour, minute, direction, origin, angle, spacing)?;
+        self.inner
+            .add_text(config)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add a chapter ring layer
+    fn add_chapter_ring_layer(&mut self, ring: &ChapterRingLayer) {
+        self.inner.add_chapter_ring_layer(ring.inner.clone());
+    }
+
+    /// Add a chapter ring; `index_cutouts` is a list of (hour, width, height)
+    /// cutouts that are also cut through solid exports, and `motif` is
+    /// "ticks", "wave", "rope" or "dots" repeated `minutes` times
+    #[pyo3(signature = (r_inner, r_outer, minutes=60, major_every=5, major_extra_width=0.15, index_cutouts=None, resolution=720, motif="ticks", amplitude=0.5, frequency=1, diameter=0.5))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_chapter_ring(
+        &mut self,
+        r_inner: f64,
+        r_outer: f64,
+        minutes: usize,
+        major_every: usize,
+        major_extra_width: f64,
+        index_cutouts: Option<Vec<(u32, f64, f64)>>,
+        resolution: usize,
+        motif: &str,
+        amplitude: f64,
+        frequency: usize,
+        diameter: f64,
+    ) -> PyResult<()> {
+        let config = chapter_ring_config(
+            r_inner,
+            r_outer,
+            minutes,
+            major_every,
+            major_extra_width,
+            index_cutouts,
+            resolution,
+            motif,
+            amplitude,
+            frequency,
+            diameter,
+        )?;
+        self.inner
+            .add_chapter_ring(config)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add a railroad minute track of `ticks` ticks between two radii, with
+    /// every `major_every`-th tick drawn as a bolder bar
+    #[pyo3(signature = (inner_r, outer_r, ticks=60, major_every=5))]
+    fn add_minute_track(&mut self, inner_r: f64, outer_r: f64, ticks: usize, major_every: usize) -> PyResult<()> {
+        self.inner
+            .add_minute_track(inner_r, outer_r, ticks, major_every)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add an index markers layer
+    fn add_index_markers_layer(&mut self, markers: &IndexMarkersLayer) {
+        self.inner.add_index_markers_layer(markers.inner.clone());
+    }
+
+    /// Add hour or minute index markers; `shape` is "line", "dot" or
+    /// "roman", `exclude` is a list of (hour, minute) clock positions left
+    /// empty, and `stroke_color` (with `stroke_width` and `opacity`) styles
+    /// the layer's SVG stroke
+    #[pyo3(signature = (inner_radius, outer_radius, shape="line", count=12, length=2.0, width=0.4, radius=0.5, height=2.0, exclude=None, resolution=32, stroke_color=None, stroke_width=0.03, opacity=1.0))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_index_markers(
+        &mut self,
+        inner_radius: f64,
+        outer_radius: f64,
+        shape: &str,
+        count: usize,
+        length: f64,
+        width: f64,
+        radius: f64,
+        height: f64,
+        exclude: Option<Vec<(u32, u32)>>,
+        resolution: usize,
+        stroke_color: Option<&str>,
+        stroke_width: f64,
+        opacity: f64,
+    ) -> PyResult<()> {
+        let mut config = index_config(inner_radius, outer_radius, shape, count, length, width, radius, height, exclude, resolution)?;
+        config.style = stroke_color.map(|color| BaseLayerStyle::new(color, stroke_width, opacity));
+        self.inner
+            .add_index_markers(config)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add fixed polylines, as lists of (x, y) points, as a layer
+    fn add_imported_layer(&mut self, lines: Vec<Vec<(f64, f64)>>) {
+        self.inner.add_imported_layer(
+            lines
+                .into_iter()
+                .map(|line| line.into_iter().map(|(x, y)| Point2D::new(x, y)).collect())
+                .collect(),
+        );
+    }
+
+    /// Add the straight-line paths of an SVG file (or SVG text) as a layer;
+    /// returns a warning for each path skipped for having curves
+    fn add_imported_svg(&mut self, path_or_str: &str) -> PyResult<Vec<String>> {
+        self.inner
+            .add_imported_svg(path_or_str)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
     /// Add a paon (peacock pattern) layer
     fn add_paon_layer(&mut self, paon: &PaonLayer) -> PyResult<()> {
         let new_layer = BasePaonLayer::new_with_center(
@@ -394,7 +1258,11 @@ impl WatchFace {
     }
 
     /// Add a paon layer positioned at a clock position
-    #[pyo3(signature = (hour, minute, distance, num_lines=500, radius=22.0, amplitude=0.035, wave_frequency=10.0, phase_rate=9.0, resolution=800, n_harmonics=3, fan_angle=4.0, vanishing_point=0.3))]
+    ///
+    /// `origin` is the side the fan radiates from ("bottom", "top", "left",
+    /// "right" or an angle in radians) and `asymmetry` skews its spacing
+    #[pyo3(signature = (hour, minute, distance, num_lines=500, radius=22.0, amplitude=0.035, wave_frequency=10.0, phase_rate=9.0, resolution=800, n_harmonics=3, fan_angle=4.0, vanishing_point=0.3, origin=None, asymmetry=0.0))]
+    #[allow(clippy::too_many_arguments)]
     fn add_paon_at_clock(
         &mut self,
         hour: u32,
@@ -409,6 +1277,8 @@ impl WatchFace {
         n_harmonics: usize,
         fan_angle: f64,
         vanishing_point: f64,
+        origin: Option<&Bound<'_, PyAny>>,
+        asymmetry: f64,
     ) -> PyResult<()> {
         let config = BasePaonConfig {
             num_lines,
@@ -416,10 +1286,13 @@ impl WatchFace {
             amplitude,
             wave_frequency,
             phase_rate,
-            resolution,
+            resolution: resolution.into(),
             n_harmonics,
             fan_angle,
             vanishing_point,
+            jitter: None,
+            origin: parse_paon_origin(origin)?,
+            asymmetry,
         };
         self.inner
             .add_paon_at_clock(config, hour, minute, distance)
@@ -440,6 +1313,7 @@ impl WatchFace {
 
     /// Add a clous de Paris layer positioned at a clock position
     #[pyo3(signature = (hour, minute, distance, spacing=1.0, radius=22.0, angle=std::f64::consts::FRAC_PI_4, resolution=200))]
+    #[allow(clippy::too_many_arguments)]
     fn add_clous_de_paris_at_clock(
         &mut self,
         hour: u32,
@@ -454,7 +1328,8 @@ impl WatchFace {
             spacing,
             radius,
             angle,
-            resolution,
+            resolution: resolution.into(),
+            emit_cells: false,
         };
         self.inner
             .add_clous_de_paris_at_clock(config, hour, minute, distance)
@@ -475,6 +1350,7 @@ impl WatchFace {
 
     /// Add a cube layer positioned at a clock position
     #[pyo3(signature = (hour, minute, distance, spacing=0.5, radius=22.0, angle=0.0, resolution=200, cuts_per_group=8, gap_per_group=8, amplitude=0.0, leg_angle=30.0))]
+    #[allow(clippy::too_many_arguments)]
     fn add_cube_at_clock(
         &mut self,
         hour: u32,
@@ -493,7 +1369,7 @@ impl WatchFace {
             spacing,
             radius,
             angle,
-            resolution,
+            resolution: resolution.into(),
             cuts_per_group,
             gap_per_group,
             amplitude,
@@ -504,9 +1380,355 @@ impl WatchFace {
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
 
+    /// Add a phyllotaxis (sunflower spiral) pattern layer
+    fn add_phyllotaxis_layer(&mut self, phyllotaxis: &PhyllotaxisLayer) {
+        self.inner.add_phyllotaxis_layer(phyllotaxis.inner.clone());
+    }
+
+    /// Add a phyllotaxis layer positioned at a clock position
+    #[pyo3(signature = (hour, minute, distance, num_points=800, scale_c=0.75, radius=22.0, style="circle", size=0.25, lobes=6, inner_exclusion_radius=0.0, edge="clip", resolution=48))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_phyllotaxis_at_clock(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        num_points: usize,
+        scale_c: f64,
+        radius: f64,
+        style: &str,
+        size: f64,
+        lobes: usize,
+        inner_exclusion_radius: f64,
+        edge: &str,
+        resolution: usize,
+    ) -> PyResult<()> {
+        let config = phyllotaxis_config(num_points, scale_c, radius, style, size, lobes, inner_exclusion_radius, edge, resolution)?;
+        self.inner
+            .add_phyllotaxis_at_clock(config, hour, minute, distance)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add a grain-de-riz (rice grain) pattern layer
+    fn add_grain_de_riz_layer(&mut self, grain_de_riz: &GrainDeRizLayer) {
+        self.inner.add_grain_de_riz_layer(grain_de_riz.inner.clone());
+    }
+
+    /// Add a grain-de-riz layer positioned at a clock position
+    #[pyo3(signature = (hour, minute, distance, num_rows=12, grains_per_row=48, grain_length=1.2, grain_width=0.5, base_radius=8.0, radius_step=1.0, resolution=24, stagger=true))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_grain_de_riz_at_clock(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        num_rows: usize,
+        grains_per_row: usize,
+        grain_length: f64,
+        grain_width: f64,
+        base_radius: f64,
+        radius_step: f64,
+        resolution: usize,
+        stagger: bool,
+    ) -> PyResult<()> {
+        let config = grain_de_riz_config(num_rows, grains_per_row, grain_length, grain_width, base_radius, radius_step, resolution, stagger);
+        self.inner
+            .add_grain_de_riz_at_clock(config, hour, minute, distance)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add a soleil (sunray) pattern layer
+    fn add_soleil_layer(&mut self, soleil: &SoleilLayer) {
+        self.inner.add_soleil_layer(soleil.inner.clone());
+    }
+
+    /// Add a soleil layer positioned at a clock position
+    #[pyo3(signature = (hour, minute, distance, num_rays=180, inner_radius=1.5, outer_radius=15.0, curvature=0.0, taper=0.0, resolution=32))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_soleil_at_clock(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        num_rays: usize,
+        inner_radius: f64,
+        outer_radius: f64,
+        curvature: f64,
+        taper: f64,
+        resolution: usize,
+    ) -> PyResult<()> {
+        let config = soleil_config(num_rays, inner_radius, outer_radius, curvature, taper, resolution);
+        self.inner
+            .add_soleil_at_clock(config, hour, minute, distance)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add a Côtes de Genève (Geneva stripes) pattern layer
+    fn add_cotes_de_geneve_layer(&mut self, cotes: &CotesDeGeneveLayer) {
+        self.inner.add_cotes_de_geneve_layer(cotes.inner.clone());
+    }
+
+    /// Add a Côtes de Genève layer positioned at a clock position
+    #[pyo3(signature = (hour, minute, distance, stripe_width=2.5, num_stripes=None, arc_sagitta=0.5, angle=0.0, radius=15.0, inner_radius=0.0, resolution=100, lines_per_stripe=6))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_cotes_de_geneve_at_clock(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        stripe_width: f64,
+        num_stripes: Option<usize>,
+        arc_sagitta: f64,
+        angle: f64,
+        radius: f64,
+        inner_radius: f64,
+        resolution: usize,
+        lines_per_stripe: usize,
+    ) -> PyResult<()> {
+        let config = cotes_de_geneve_config(stripe_width, num_stripes, arc_sagitta, angle, radius, inner_radius, resolution, lines_per_stripe);
+        self.inner
+            .add_cotes_de_geneve_at_clock(config, hour, minute, distance)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add a tapisserie (tapestry, waffle) pattern layer
+    fn add_tapisserie_layer(&mut self, tapisserie: &TapisserieLayer) {
+        self.inner.add_tapisserie_layer(tapisserie.inner.clone());
+    }
+
+    /// Add a tapisserie layer positioned at a clock position
+    #[pyo3(signature = (hour, minute, distance, cell_size=1.0, channel_width=0.3, radius=15.0, rotation=0.0, resolution=360, truncate_corners=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_tapisserie_at_clock(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        cell_size: f64,
+        channel_width: f64,
+        radius: f64,
+        rotation: f64,
+        resolution: usize,
+        truncate_corners: bool,
+    ) -> PyResult<()> {
+        let config = tapisserie_config(cell_size, channel_width, radius, rotation, resolution, truncate_corners);
+        self.inner
+            .add_tapisserie_at_clock(config, hour, minute, distance)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add a vague (wave, flammes) pattern layer
+    fn add_vague_layer(&mut self, vague: &VagueLayer) {
+        self.inner.add_vague_layer(vague.inner.clone());
+    }
+
+    /// Add a vague layer positioned at a clock position
+    #[pyo3(signature = (hour, minute, distance, num_lines=40, radius=15.0, wave_amplitude=1.5, wave_length=10.0, sweep_angle=0.0, group_size=4, group_phase_step=std::f64::consts::FRAC_PI_2, points_per_wave=64))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_vague_at_clock(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        num_lines: usize,
+        radius: f64,
+        wave_amplitude: f64,
+        wave_length: f64,
+        sweep_angle: f64,
+        group_size: usize,
+        group_phase_step: f64,
+        points_per_wave: usize,
+    ) -> PyResult<()> {
+        let config = vague_config(num_lines, radius, wave_amplitude, wave_length, sweep_angle, group_size, group_phase_step, points_per_wave);
+        self.inner
+            .add_vague_at_clock(config, hour, minute, distance)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Register `func(lines, kind, index)` to edit each layer's lines after generation
+    ///
+    /// `lines` is a list of lists of (x, y) tuples, `kind` the layer kind
+    /// ("draperie", "spirograph", ...) and `index` the layer's index among
+    /// layers of that kind. Edit `lines` in place or return a new list;
+    /// exceptions are raised from generate().
+    fn add_post_process(&mut self, func: Py<PyAny>) {
+        let post_error = Arc::clone(&self.post_error);
+        self.inner.add_post_process(move |lines, kind, index| {
+            Python::attach(|py| {
+                if post_error.lock().unwrap().is_some() {
+                    return;
+                }
+                let result = (|| -> PyResult<Vec<Vec<(f64, f64)>>> {
+                    let arg = lines
+                        .iter()
+                        .map(|line| line.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>())
+                        .collect::<Vec<_>>()
+                        .into_pyobject(py)?;
+                    let returned = func.call1(py, (&arg, kind.name(), index))?;
+                    if returned.is_none(py) {
+                        arg.extract()
+                    } else {
+                        returned.extract(py)
+                    }
+                })();
+                match result {
+                    Ok(edited) => {
+                        *lines = edited
+                            .into_iter()
+                            .map(|line| line.into_iter().map(|(x, y)| Point2D::new(x, y)).collect())
+                            .collect();
+                    }
+                    Err(e) => *post_error.lock().unwrap() = Some(e),
+                }
+            })
+        });
+    }
+
+    /// Remove every post-process callback
+    fn clear_post_processes(&mut self) {
+        self.inner.clear_post_processes();
+    }
+
+    /// Fade one layer's SVG lines by an Envelope at each line's mean angle and radius
+    ///
+    /// kind is a layer kind name as passed to post-process callbacks
+    /// ("draperie", "paon", ...) and index counts layers of that kind.
+    fn set_opacity_envelope(&mut self, kind: &str, index: usize, envelope: Envelope) -> PyResult<()> {
+        let kind = layer_kind(kind)?;
+        self.inner
+            .set_opacity_envelope(kind, index, envelope.inner)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Draw every layer fully opaque again
+    fn clear_opacity_envelopes(&mut self) {
+        self.inner.clear_opacity_envelopes();
+    }
+
+    /// Stroke one layer's SVG lines with its own color, width and opacity
+    ///
+    /// kind and index name the layer as for set_opacity_envelope; the width
+    /// is in the design unit.
+    #[pyo3(signature = (kind, index, stroke_color, stroke_width, opacity=1.0))]
+    fn set_layer_style(&mut self, kind: &str, index: usize, stroke_color: &str, stroke_width: f64, opacity: f64) -> PyResult<()> {
+        let kind = layer_kind(kind)?;
+        self.inner
+            .set_layer_style(kind, index, BaseLayerStyle::new(stroke_color, stroke_width, opacity))
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Get a layer's (stroke_color, stroke_width, opacity), or None if it has no style
+    fn layer_style(&self, kind: &str, index: usize) -> PyResult<Option<(String, f64, f64)>> {
+        let kind = layer_kind(kind)?;
+        Ok(self
+            .inner
+            .layer_style(kind, index)
+            .map(|style| (style.stroke_color.clone(), style.stroke_width, style.opacity)))
+    }
+
+    /// Draw every layer with the default stroke again
+    fn clear_layer_styles(&mut self) {
+        self.inner.clear_layer_styles();
+    }
+
+    /// Apply an undoable edit; layers it adds or moves are regenerated by regenerate_stale()
+    ///
+    /// op is one of:
+    /// * "add_layer" - layer: any layer object, appended after its kind
+    /// * "remove_layer" - kind, index
+    /// * "move_layer" - kind, index, to
+    /// * "set_layer_center" - kind, index, x, y
+    /// * "set_dial" / "set_bezel" - enabled=True plus optional styling
+    ///   (fill_color, stroke_color, stroke_width, radius_ratio)
+    /// * "add_hole" - x, y, radius, fill_color (all optional)
+    ///
+    /// kind is a layer kind name as passed to post-process callbacks.
+    #[pyo3(signature = (op, **kwargs))]
+    fn apply(&mut self, op: &str, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
+        let command = edit_command(op, kwargs)?;
+        self.history
+            .apply(&mut self.inner, command)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Undo the last edit; returns False if there was nothing to undo
+    fn undo(&mut self) -> PyResult<bool> {
+        self.history
+            .undo(&mut self.inner)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Redo the last undone edit; returns False if there was nothing to redo
+    fn redo(&mut self) -> PyResult<bool> {
+        self.history
+            .redo(&mut self.inner)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Set how many edits can be undone (default 100)
+    fn set_undo_depth(&mut self, depth: usize) {
+        self.history.set_depth(depth);
+    }
+
+    /// Generate only the layers edits have marked stale; returns how many
+    fn regenerate_stale(&mut self) -> PyResult<usize> {
+        let count = self.inner.regenerate_stale();
+        self.take_post_error()?;
+        Ok(count)
+    }
+
+    /// Get every generated line across all layers as lists of (x, y) tuples
+    fn get_lines(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
     /// Generate all layers
-    fn generate(&mut self) {
+    fn generate(&mut self) -> PyResult<()> {
         self.inner.generate();
+        self.take_post_error()
+    }
+
+    /// Check every layer without generating; one dict (kind, index, field, message) per invalid layer
+    fn validate<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.inner
+            .validate()
+            .iter()
+            .map(|issue| issue_dict(py, issue))
+            .collect()
+    }
+
+    /// Generate all layers, raising ValueError listing every invalid layer
+    fn try_generate(&mut self) -> PyResult<()> {
+        self.inner
+            .try_generate()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        self.take_post_error()
+    }
+
+    /// Generate every valid layer; returns {"generated": count, "skipped": [issue dicts]}
+    fn generate_lenient<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let outcome = self.inner.generate_lenient();
+        self.take_post_error()?;
+        let skipped = outcome
+            .skipped
+            .iter()
+            .map(|issue| issue_dict(py, issue))
+            .collect::<PyResult<Vec<_>>>()?;
+        let dict = PyDict::new(py);
+        dict.set_item("generated", outcome.generated)?;
+        dict.set_item("skipped", skipped)?;
+        Ok(dict)
+    }
+
+    /// Generate all layers concurrently (releases the GIL; output identical to generate())
+    fn generate_parallel(&mut self, py: Python<'_>) -> PyResult<()> {
+        let inner = &mut self.inner;
+        py.detach(|| inner.generate_parallel());
+        self.take_post_error()
     }
 
     /// Get layer count
@@ -514,40 +1736,205 @@ impl WatchFace {
         self.inner.layer_count()
     }
 
-    /// Export to SVG
-    #[pyo3(signature = (filename))]
-    fn to_svg(&self, filename: &str) -> PyResult<()> {
+    /// Export to SVG, in the design unit unless `unit` is given; the reference
+    /// image is drawn only when `include_reference` is set. `origin` moves the
+    /// coordinates to "top_left" (y down) or "bottom_left" (y up) of the page.
+    /// A positive `tolerance` simplifies the pattern lines to within that
+    /// distance, in the export unit, first
+    #[pyo3(signature = (filename, unit=None, include_reference=false, origin=None, tolerance=0.0))]
+    fn to_svg(
+        &self,
+        filename: &str,
+        unit: Option<&str>,
+        include_reference: bool,
+        origin: Option<&str>,
+        tolerance: f64,
+    ) -> PyResult<()> {
+        let unit = self.export_unit(unit)?;
+        if origin.is_some() || tolerance > 0.0 {
+            if include_reference {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "origin and tolerance cannot be combined with include_reference",
+                ));
+            }
+            let config = BaseExportConfig {
+                unit,
+                origin: origin.map_or(Ok(BaseOriginMode::CenterOrigin), parse_origin)?,
+                simplify_tolerance: tolerance,
+                ..Default::default()
+            };
+            return self
+                .inner
+                .to_svg_with_config(filename, &config)
+                .map_err(export_err);
+        }
+        let result = if !include_reference {
+            self.inner.to_svg_in(filename, unit)
+        } else if unit == self.inner.unit() {
+            self.inner.to_svg_with_reference(filename, true)
+        } else {
+            let mut converted = self.inner.clone();
+            converted.convert_units(unit);
+            converted.to_svg_with_reference(filename, true)
+        };
+        result.map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Export to SVG with a design-review overlay (grid, angle rays, reference circles)
+    #[pyo3(signature = (filename, mm_grid=None, radial_rays_every_deg=None, concentric_circles_every_mm=None, label_font_size=None))]
+    fn to_svg_with_overlay(
+        &self,
+        filename: &str,
+        mm_grid: Option<f64>,
+        radial_rays_every_deg: Option<f64>,
+        concentric_circles_every_mm: Option<f64>,
+        label_font_size: Option<f64>,
+    ) -> PyResult<()> {
+        let overlay = BaseOverlayOptions {
+            mm_grid,
+            radial_rays_every_deg,
+            concentric_circles_every_mm,
+            label_font_size,
+        };
         self.inner
-            .to_svg(filename)
+            .to_svg_with_overlay(filename, &overlay)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
-    /// Export to STL
-    #[pyo3(signature = (filename, depth=0.1, base_thickness=2.0))]
-    fn to_stl(&self, filename: &str, depth: f64, base_thickness: f64) -> PyResult<()> {
+    /// Export to STL over a dial-sized base slab `base_thickness` thick (0 for
+    /// none); depth, thickness and tool radius are in the export unit, and
+    /// may come from an ExportConfig as `config` instead. `origin` is as for
+    /// `to_svg`
+    #[pyo3(signature = (filename, depth=None, base_thickness=None, unit=None, origin=None, tool_radius=None, config=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn to_stl(
+        &self,
+        filename: &str,
+        depth: Option<f64>,
+        base_thickness: Option<f64>,
+        unit: Option<&str>,
+        origin: Option<&str>,
+        tool_radius: Option<f64>,
+        config: Option<ExportConfig>,
+    ) -> PyResult<()> {
         let config = BaseExportConfig {
-            depth,
-            base_thickness,
-            tool_radius: 0.0,
+            unit: self.export_unit(unit)?,
+            origin: origin.map_or(Ok(BaseOriginMode::CenterOrigin), parse_origin)?,
+            ..export_config(config, depth, base_thickness, tool_radius)?
         };
         self.inner
             .to_stl(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
-    /// Export to STEP
-    #[pyo3(signature = (filename, depth=0.1))]
-    fn to_step(&self, filename: &str, depth: f64) -> PyResult<()> {
+    /// Export the dial edge, holes and chapter ring cutouts to DXF as closed
+    /// polylines in millimeters
+    fn to_dxf_profile(&self, filename: &str) -> PyResult<()> {
+        self.inner
+            .to_dxf_profile(filename)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Export every layer to HPGL for a pen plotter, in mm times `scale`
+    /// and clipped to the dial; layer `i` (in SVG order) is plotted with
+    /// `pens[i]` (the last pen repeats, pen 1 without any)
+    #[pyo3(signature = (filename, scale=1.0, pens=None))]
+    fn to_hpgl(&self, filename: &str, scale: f64, pens: Option<Vec<u32>>) -> PyResult<()> {
+        self.inner
+            .to_hpgl_with_pens(filename, scale, &pens.unwrap_or_default())
+            .map_err(export_err)
+    }
+
+    /// Get every opening through the dial (holes, then chapter ring
+    /// cutouts) as lists of (x, y) points
+    fn get_apertures(&self) -> Vec<Vec<(f64, f64)>> {
+        self.inner
+            .apertures()
+            .iter()
+            .map(|outline| outline.iter().map(|p| (p.x, p.y)).collect())
+            .collect()
+    }
+
+    /// Export to STEP; depth (or an ExportConfig as `config`) is in the
+    /// export unit and `origin` is as for `to_svg`
+    #[pyo3(signature = (filename, depth=None, unit=None, origin=None, config=None))]
+    fn to_step(
+        &self,
+        filename: &str,
+        depth: Option<f64>,
+        unit: Option<&str>,
+        origin: Option<&str>,
+        config: Option<ExportConfig>,
+    ) -> PyResult<()> {
         let config = BaseExportConfig {
-            depth,
-            base_thickness: 2.0,
-            tool_radius: 0.0,
+            unit: self.export_unit(unit)?,
+            origin: origin.map_or(Ok(BaseOriginMode::CenterOrigin), parse_origin)?,
+            ..export_config(config, depth, None, None)?
         };
         self.inner
             .to_step(filename, &config)
             .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
     }
 
+    /// Rasterize the SVG export to an anti-aliased RGBA PNG file
+    #[pyo3(signature = (filename, width_px=1024, height_px=1024))]
+    fn to_png(&self, filename: &str, width_px: u32, height_px: u32) -> PyResult<()> {
+        self.inner
+            .to_png(filename, width_px, height_px)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Rasterize the SVG export and return the PNG as bytes
+    #[pyo3(signature = (width_px=1024, height_px=1024))]
+    fn render_bytes<'py>(
+        &self,
+        py: Python<'py>,
+        width_px: u32,
+        height_px: u32,
+    ) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        let png = self
+            .inner
+            .render_raster(width_px, height_px)
+            .and_then(|image| image.to_png_bytes())
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(pyo3::types::PyBytes::new(py, &png))
+    }
+
+    /// Rasterize the pattern for a laser engraver and write a 1-bit PNG or PBM
+    ///
+    /// The format follows the extension (".pbm" for PBM, anything else PNG);
+    /// `dither` is "ordered" or "error_diffusion". Returns (width, height)
+    /// in pixels.
+    #[pyo3(signature = (filename, dpi, line_width_mm, dither="ordered"))]
+    fn to_engraver_bitmap(
+        &self,
+        filename: &str,
+        dpi: u32,
+        line_width_mm: f64,
+        dither: &str,
+    ) -> PyResult<(usize, usize)> {
+        let dither = match dither {
+            "ordered" => BaseDither::Ordered,
+            "error_diffusion" => BaseDither::ErrorDiffusion,
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "dither must be 'ordered' or 'error_diffusion'",
+                ))
+            }
+        };
+        let bitmap = self
+            .inner
+            .to_engraver_bitmap_with_dither(dpi, line_width_mm, dither)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let written = if filename.to_lowercase().ends_with(".pbm") {
+            bitmap.write_pbm(filename)
+        } else {
+            bitmap.write_png(filename)
+        };
+        written.map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+        Ok((bitmap.width, bitmap.height))
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "WatchFace(radius={}, layers={})",
@@ -556,3 +1943,27 @@ impl WatchFace {
         )
     }
 }
+
+impl WatchFace {
+    /// Wrap a face with empty edit history
+    fn from_inner(inner: BaseWatchFace) -> Self {
+        WatchFace {
+            inner,
+            history: BaseEditHistory::default(),
+            post_error: Arc::default(),
+        }
+    }
+
+    /// Raise the error a post-process callback hit during the last generation
+    fn take_post_error(&self) -> PyResult<()> {
+        match self.post_error.lock().unwrap().take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Requested export unit, defaulting to the design unit
+    fn export_unit(&self, unit: Option<&str>) -> PyResult<BaseUnit> {
+        unit.map_or(Ok(self.inner.unit()), parse_unit)
+    }
+}
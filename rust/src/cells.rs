@@ -0,0 +1,918 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::common::{dedupe_consecutive, polyline_closes, Point2D};
+
+/// Distance below which arrangement vertices are merged (1 nm, in mm)
+const VERTEX_EPSILON: f64 = 1e-6;
+
+/// Relative slack allowed on distance checks, for rounding in the offset
+const DISTANCE_SLACK: f64 = 1e-6;
+
+type Segment = (Point2D, Point2D);
+
+fn sub(a: Point2D, b: Point2D) -> Point2D {
+    Point2D::new(a.x - b.x, a.y - b.y)
+}
+
+fn cross(a: Point2D, b: Point2D) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot(a: Point2D, b: Point2D) -> f64 {
+    a.x * b.x + a.y * b.y
+}
+
+fn distance(a: Point2D, b: Point2D) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+/// Signed area of a closed polygon; positive when counter-clockwise in
+/// x-right, y-up axes
+pub fn polygon_area(polygon: &[Point2D]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| cross(polygon[i], polygon[(i + 1) % n]))
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Even-odd point-in-polygon test
+fn contains(polygon: &[Point2D], p: Point2D) -> bool {
+    let n = polygon.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Distance from `p` to the segment `(a, b)`
+fn segment_distance(p: Point2D, (a, b): Segment) -> f64 {
+    let ab = sub(b, a);
+    let len2 = dot(ab, ab);
+    if len2 == 0.0 {
+        return distance(p, a);
+    }
+    let t = (dot(sub(p, a), ab) / len2).clamp(0.0, 1.0);
+    distance(p, Point2D::new(a.x + ab.x * t, a.y + ab.y * t))
+}
+
+fn bounds(points: impl IntoIterator<Item = Point2D>) -> (f64, f64, f64, f64) {
+    points.into_iter().fold(
+        (
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        ),
+        |(x0, y0, x1, y1), p| (x0.min(p.x), y0.min(p.y), x1.max(p.x), y1.max(p.y)),
+    )
+}
+
+/// Uniform grid over segments for neighbourhood queries
+struct SegmentIndex {
+    cell: f64,
+    cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl SegmentIndex {
+    /// Index `segments`, with cells no finer than `min_cell`
+    fn new(segments: &[Segment], min_cell: f64) -> Self {
+        let (x0, y0, x1, y1) = bounds(segments.iter().flat_map(|&(a, b)| [a, b]));
+        let mean = segments.iter().map(|&(a, b)| distance(a, b)).sum::<f64>()
+            / segments.len().max(1) as f64;
+        // Cap the grid at 256 cells across so long segments stay cheap
+        let span = (x1 - x0).max(y1 - y0);
+        let cell = (2.0 * mean)
+            .max(span / 256.0)
+            .max(min_cell)
+            .max(VERTEX_EPSILON);
+
+        let mut index = SegmentIndex {
+            cell,
+            cells: HashMap::new(),
+        };
+        for (i, &(a, b)) in segments.iter().enumerate() {
+            for key in index.keys(bounds([a, b])) {
+                index.cells.entry(key).or_default().push(i);
+            }
+        }
+        index
+    }
+
+    fn keys(&self, (x0, y0, x1, y1): (f64, f64, f64, f64)) -> impl Iterator<Item = (i64, i64)> {
+        let cell = |v: f64| (v / self.cell).floor() as i64;
+        let (cx0, cy0, cx1, cy1) = (cell(x0), cell(y0), cell(x1), cell(y1));
+        (cx0..=cx1).flat_map(move |x| (cy0..=cy1).map(move |y| (x, y)))
+    }
+
+    /// Segments whose bounding boxes may overlap the box, each listed once
+    fn query(&self, bbox: (f64, f64, f64, f64)) -> Vec<usize> {
+        let mut found: Vec<usize> = self
+            .keys(bbox)
+            .filter_map(|key| self.cells.get(&key))
+            .flatten()
+            .copied()
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    /// Segments that may lie within `radius` of `p`
+    fn near(&self, p: Point2D, radius: f64) -> Vec<usize> {
+        self.query((p.x - radius, p.y - radius, p.x + radius, p.y + radius))
+    }
+}
+
+/// Parameters `(t, u)` along `a` and `b` at which the segments touch
+///
+/// Crossing segments touch once; collinear overlapping segments touch at
+/// every endpoint of one that lies on the other.
+fn touch_params(a: Segment, b: Segment) -> Vec<(f64, f64)> {
+    let r = sub(a.1, a.0);
+    let s = sub(b.1, b.0);
+    let (len_r, len_s) = (r.x.hypot(r.y), s.x.hypot(s.y));
+    if len_r == 0.0 || len_s == 0.0 {
+        return Vec::new();
+    }
+    let qp = sub(b.0, a.0);
+    let denom = cross(r, s);
+    let (tol_t, tol_u) = (VERTEX_EPSILON / len_r, VERTEX_EPSILON / len_s);
+    let on = |v: f64, tol: f64| (-tol..=1.0 + tol).contains(&v);
+
+    if denom.abs() <= 1e-12 * len_r * len_s {
+        // Parallel: only collinear overlaps touch
+        if (cross(qp, r) / len_r).abs() > VERTEX_EPSILON {
+            return Vec::new();
+        }
+        let mut params = Vec::new();
+        for (k, end) in [b.0, b.1].into_iter().enumerate() {
+            let t = dot(sub(end, a.0), r) / (len_r * len_r);
+            if on(t, tol_t) {
+                params.push((t.clamp(0.0, 1.0), k as f64));
+            }
+        }
+        for (k, end) in [a.0, a.1].into_iter().enumerate() {
+            let u = dot(sub(end, b.0), s) / (len_s * len_s);
+            if on(u, tol_u) {
+                params.push((k as f64, u.clamp(0.0, 1.0)));
+            }
+        }
+        return params;
+    }
+
+    let t = cross(qp, s) / denom;
+    let u = cross(qp, r) / denom;
+    if on(t, tol_t) && on(u, tol_u) {
+        vec![(t.clamp(0.0, 1.0), u.clamp(0.0, 1.0))]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Arrangement vertices, merged within `VERTEX_EPSILON`
+#[derive(Default)]
+struct VertexPool {
+    points: Vec<Point2D>,
+    buckets: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl VertexPool {
+    fn id(&mut self, p: Point2D) -> usize {
+        let key = (
+            (p.x / VERTEX_EPSILON).floor() as i64,
+            (p.y / VERTEX_EPSILON).floor() as i64,
+        );
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if let Some(ids) = self.buckets.get(&(key.0 + dx, key.1 + dy)) {
+                    if let Some(&id) = ids
+                        .iter()
+                        .find(|&&id| distance(self.points[id], p) <= VERTEX_EPSILON)
+                    {
+                        return id;
+                    }
+                }
+            }
+        }
+        self.points.push(p);
+        let id = self.points.len() - 1;
+        self.buckets.entry(key).or_default().push(id);
+        id
+    }
+}
+
+fn find(parent: &mut [usize], mut v: usize) -> usize {
+    while parent[v] != v {
+        parent[v] = parent[parent[v]];
+        v = parent[v];
+    }
+    v
+}
+
+/// Closed cells enclosed by a set of polylines
+///
+/// Builds the planar arrangement of the lines (splitting them wherever
+/// they cross or touch), discards dangling ends, and walks the bounded
+/// faces. Lines that return to their start (see `polyline_closes`) are
+/// closed first. Each cell is returned counter-clockwise (in x-right, y-up
+/// axes) without repeating its first point.
+///
+/// Faces that surround a separate island of lines would need a hole to
+/// cut, so they are left out; so are the open cells along an unbounded
+/// edge, such as a grid clipped to a circle that is not itself drawn.
+pub fn planar_cells(lines: &[Vec<Point2D>]) -> Vec<Vec<Point2D>> {
+    let mut segments: Vec<Segment> = Vec::new();
+    for line in lines {
+        segments.extend(line.windows(2).map(|w| (w[0], w[1])));
+        if polyline_closes(line) {
+            segments.push((line[line.len() - 1], line[0]));
+        }
+    }
+    segments.retain(|&(a, b)| distance(a, b) > VERTEX_EPSILON);
+    if segments.is_empty() {
+        return Vec::new();
+    }
+
+    // Split every segment where another touches it
+    let index = SegmentIndex::new(&segments, 0.0);
+    let mut splits: Vec<Vec<f64>> = vec![vec![0.0, 1.0]; segments.len()];
+    for (i, &segment) in segments.iter().enumerate() {
+        for j in index.query(bounds([segment.0, segment.1])) {
+            if j <= i {
+                continue;
+            }
+            for (t, u) in touch_params(segment, segments[j]) {
+                splits[i].push(t);
+                splits[j].push(u);
+            }
+        }
+    }
+
+    let mut pool = VertexPool::default();
+    let mut edges = HashSet::new();
+    for (&(a, b), params) in segments.iter().zip(&mut splits) {
+        params.sort_by(f64::total_cmp);
+        let ids: Vec<usize> = params
+            .iter()
+            .map(|&t| pool.id(Point2D::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)))
+            .collect();
+        for w in ids.windows(2) {
+            if w[0] != w[1] {
+                edges.insert((w[0].min(w[1]), w[0].max(w[1])));
+            }
+        }
+    }
+
+    let points = pool.points;
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); points.len()];
+    for &(a, b) in &edges {
+        adjacency[a].push(b);
+        adjacency[b].push(a);
+    }
+
+    // Dangling ends bound no cell
+    let mut dangling: Vec<usize> = (0..points.len())
+        .filter(|&v| adjacency[v].len() == 1)
+        .collect();
+    while let Some(v) = dangling.pop() {
+        if adjacency[v].len() != 1 {
+            continue;
+        }
+        let w = adjacency[v][0];
+        adjacency[v].clear();
+        adjacency[w].retain(|&x| x != v);
+        if adjacency[w].len() == 1 {
+            dangling.push(w);
+        }
+    }
+
+    // Connected components, to spot islands inside faces
+    let mut parent: Vec<usize> = (0..points.len()).collect();
+    for (v, neighbours) in adjacency.iter().enumerate() {
+        for &w in neighbours {
+            let (rv, rw) = (find(&mut parent, v), find(&mut parent, w));
+            parent[rv] = rw;
+        }
+    }
+
+    // Outgoing edges in counter-clockwise order around each vertex
+    let angle = |v: usize, w: usize| (points[w].y - points[v].y).atan2(points[w].x - points[v].x);
+    for (v, neighbours) in adjacency.iter_mut().enumerate() {
+        neighbours.sort_by(|&a, &b| angle(v, a).total_cmp(&angle(v, b)));
+    }
+    let mut slot = HashMap::new();
+    for (v, neighbours) in adjacency.iter().enumerate() {
+        for (k, &w) in neighbours.iter().enumerate() {
+            slot.insert((v, w), k);
+        }
+    }
+
+    // Walk each face keeping it on the left: at every vertex take the
+    // outgoing edge just clockwise of the one we arrived along
+    let mut visited = HashSet::new();
+    let mut faces = Vec::new();
+    let mut outlines = Vec::new();
+    for v in 0..points.len() {
+        for &w in &adjacency[v] {
+            if visited.contains(&(v, w)) {
+                continue;
+            }
+            let mut cycle = Vec::new();
+            let (mut a, mut b) = (v, w);
+            while visited.insert((a, b)) {
+                cycle.push(points[a]);
+                let around = &adjacency[b];
+                let k = slot[&(b, a)];
+                let next = around[(k + around.len() - 1) % around.len()];
+                (a, b) = (b, next);
+            }
+            let component = find(&mut parent, v);
+            if polygon_area(&cycle) > 0.0 {
+                faces.push((component, cycle));
+            } else {
+                outlines.push((component, cycle));
+            }
+        }
+    }
+
+    let inside = |outer: (f64, f64, f64, f64), inner: (f64, f64, f64, f64)| {
+        inner.0 >= outer.0 && inner.1 >= outer.1 && inner.2 <= outer.2 && inner.3 <= outer.3
+    };
+    let outline_bounds: Vec<_> = outlines
+        .iter()
+        .map(|(_, cycle)| bounds(cycle.iter().copied()))
+        .collect();
+    faces
+        .into_iter()
+        .filter(|(component, face)| {
+            let face_bounds = bounds(face.iter().copied());
+            !outlines
+                .iter()
+                .zip(&outline_bounds)
+                .any(|((other, outline), &outline_bounds)| {
+                    other != component
+                        && inside(face_bounds, outline_bounds)
+                        && contains(face, outline[0])
+                })
+        })
+        .map(|(_, face)| face)
+        .collect()
+}
+
+/// Offset a closed polygon inward by `distance`
+///
+/// Each edge is moved inward along its normal and neighbouring edges are
+/// re-joined at their intersection. Edges that flip direction have been
+/// swallowed by the offset and are dropped, repeatedly, which handles
+/// corners collapsing. The result is then checked: it must be a simple
+/// polygon whose vertices and edge midpoints all lie inside `polygon` at
+/// least `distance` from its boundary. Returns `None` when the polygon
+/// collapses or the check fails.
+///
+/// The input may wind either way and must not repeat its first point;
+/// the inset is returned counter-clockwise (x-right, y-up axes).
+pub fn inset_polygon(polygon: &[Point2D], distance: f64) -> Option<Vec<Point2D>> {
+    if !(distance.is_finite() && distance >= 0.0) {
+        return None;
+    }
+    let mut points = polygon.to_vec();
+    dedupe_consecutive(&mut points, VERTEX_EPSILON);
+    if points.len() > 1 && self::distance(points[0], points[points.len() - 1]) <= VERTEX_EPSILON {
+        points.pop();
+    }
+    if points.len() < 3 || polygon_area(&points) == 0.0 {
+        return None;
+    }
+    if polygon_area(&points) < 0.0 {
+        points.reverse();
+    }
+    if distance == 0.0 {
+        return Some(points);
+    }
+
+    // Offset lines as (point, unit direction)
+    let n = points.len();
+    let mut lines: Vec<(Point2D, Point2D)> = (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            let len = self::distance(a, b);
+            let dir = Point2D::new((b.x - a.x) / len, (b.y - a.y) / len);
+            // Left of a counter-clockwise edge is inside
+            (
+                Point2D::new(a.x - dir.y * distance, a.y + dir.x * distance),
+                dir,
+            )
+        })
+        .collect();
+
+    let join = |(o1, d1): (Point2D, Point2D), (o2, d2): (Point2D, Point2D)| {
+        let denom = cross(d1, d2);
+        if denom.abs() <= 1e-12 {
+            // Parallel neighbours continue straight on; opposed ones fold
+            return (dot(d1, d2) > 0.0).then_some(o2);
+        }
+        let t = cross(sub(o2, o1), d2) / denom;
+        Some(Point2D::new(o1.x + d1.x * t, o1.y + d1.y * t))
+    };
+
+    let inset = loop {
+        let m = lines.len();
+        if m < 3 {
+            return None;
+        }
+        let vertices = (0..m)
+            .map(|i| join(lines[(i + m - 1) % m], lines[i]))
+            .collect::<Option<Vec<Point2D>>>()?;
+        let flipped: Vec<bool> = (0..m)
+            .map(|i| dot(sub(vertices[(i + 1) % m], vertices[i]), lines[i].1) <= 0.0)
+            .collect();
+        if !flipped.contains(&true) {
+            break vertices;
+        }
+        if !flipped.contains(&false) {
+            return None;
+        }
+        lines = lines
+            .into_iter()
+            .zip(flipped)
+            .filter_map(|(line, flipped)| (!flipped).then_some(line))
+            .collect();
+    };
+
+    let m = inset.len();
+    if polygon_area(&inset) <= 0.0 || !contains(&points, inset[0]) {
+        return None;
+    }
+
+    // The inset must not cross itself
+    let inset_edges: Vec<Segment> = (0..m).map(|i| (inset[i], inset[(i + 1) % m])).collect();
+    let index = SegmentIndex::new(&inset_edges, 0.0);
+    for (i, &edge) in inset_edges.iter().enumerate() {
+        for j in index.query(bounds([edge.0, edge.1])) {
+            let adjacent = j == i || j == (i + 1) % m || i == (j + 1) % m;
+            if !adjacent && !touch_params(edge, inset_edges[j]).is_empty() {
+                return None;
+            }
+        }
+    }
+
+    // ...and must keep its distance from the original boundary
+    let edges: Vec<Segment> = (0..n).map(|i| (points[i], points[(i + 1) % n])).collect();
+    let index = SegmentIndex::new(&edges, distance);
+    let limit = distance * (1.0 - DISTANCE_SLACK);
+    let samples = inset_edges
+        .iter()
+        .flat_map(|&(a, b)| [a, Point2D::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)]);
+    for p in samples {
+        if index
+            .near(p, distance)
+            .into_iter()
+            .any(|j| segment_distance(p, edges[j]) < limit)
+        {
+            return None;
+        }
+    }
+
+    Some(inset)
+}
+
+/// Inset every closed cell larger than `min_area` by half the web width
+///
+/// Cells that collapse under the inset are dropped. As a final guard,
+/// where two insets still come closer than `min_web` the smaller is
+/// dropped, so every pair of returned contours is at least `min_web`
+/// apart. Negative `min_web` is treated as zero.
+pub(crate) fn pierceable_cells(
+    lines: &[Vec<Point2D>],
+    min_web: f64,
+    min_area: f64,
+) -> Vec<Vec<Point2D>> {
+    let min_web = min_web.max(0.0);
+    let insets: Vec<Vec<Point2D>> = planar_cells(lines)
+        .into_iter()
+        .filter(|cell| polygon_area(cell) > min_area)
+        .filter_map(|cell| inset_polygon(&cell, min_web / 2.0))
+        .collect();
+    if min_web == 0.0 {
+        return insets;
+    }
+
+    let mut owners = Vec::new();
+    let mut edges: Vec<Segment> = Vec::new();
+    for (k, inset) in insets.iter().enumerate() {
+        let m = inset.len();
+        for i in 0..m {
+            owners.push(k);
+            edges.push((inset[i], inset[(i + 1) % m]));
+        }
+    }
+    let index = SegmentIndex::new(&edges, min_web);
+    let limit = min_web * (1.0 - DISTANCE_SLACK);
+    let areas: Vec<f64> = insets.iter().map(|inset| polygon_area(inset)).collect();
+    let mut keep = vec![true; insets.len()];
+    for (k, inset) in insets.iter().enumerate() {
+        for &p in inset {
+            for j in index.near(p, min_web) {
+                let other = owners[j];
+                if other != k && keep[k] && keep[other] && segment_distance(p, edges[j]) < limit {
+                    let smaller = if areas[k] < areas[other] { k } else { other };
+                    keep[smaller] = false;
+                }
+            }
+        }
+    }
+
+    insets
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(inset, keep)| keep.then_some(inset))
+        .collect()
+}
+
+/// Triangulate a polygon with holes by ear clipping
+///
+/// Each hole is bridged into the outer boundary from its rightmost vertex
+/// to a vertex of the boundary visible along the +x ray, rightmost hole
+/// first, and the merged polygon is then clipped ear by ear. Holes must lie
+/// strictly inside `outer` and must not overlap each other. Inputs may wind
+/// either way and must not repeat their first point; triangles are returned
+/// counter-clockwise (x-right, y-up axes).
+pub(crate) fn triangulate_with_holes(
+    outer: &[Point2D],
+    holes: &[Vec<Point2D>],
+) -> Vec<[Point2D; 3]> {
+    let wound = |polygon: &[Point2D], ccw: bool| {
+        let mut points = polygon.to_vec();
+        if (polygon_area(&points) > 0.0) != ccw {
+            points.reverse();
+        }
+        points
+    };
+    let max_x = |polygon: &[Point2D]| polygon.iter().map(|p| p.x).fold(f64::MIN, f64::max);
+
+    let mut polygon = wound(outer, true);
+    let mut holes: Vec<Vec<Point2D>> = holes
+        .iter()
+        .filter(|hole| hole.len() >= 3)
+        .map(|hole| wound(hole, false))
+        .collect();
+    holes.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+    for hole in &holes {
+        bridge_hole(&mut polygon, hole);
+    }
+    ear_clip(&polygon)
+}
+
+/// Splice a clockwise `hole` into the counter-clockwise `polygon` along a
+/// bridge from the hole's rightmost vertex
+///
+/// The hole is left out if no boundary edge lies to its right.
+fn bridge_hole(polygon: &mut Vec<Point2D>, hole: &[Point2D]) {
+    let m = (0..hole.len())
+        .max_by(|&i, &j| hole[i].x.total_cmp(&hole[j].x))
+        .unwrap_or(0);
+    let start = hole[m];
+
+    // Nearest crossing of the +x ray, and the edge's rightmost end
+    let n = polygon.len();
+    let mut best: Option<(f64, usize)> = None;
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        if (a.y > start.y) == (b.y > start.y) {
+            continue;
+        }
+        let x = a.x + (start.y - a.y) / (b.y - a.y) * (b.x - a.x);
+        if x >= start.x && best.is_none_or(|(bx, _)| x < bx) {
+            best = Some((x, if a.x > b.x { i } else { (i + 1) % n }));
+        }
+    }
+    let Some((hit_x, mut target)) = best else {
+        return;
+    };
+
+    // A reflex vertex inside the triangle (start, hit, target) would block
+    // the bridge; take the one closest in angle to the ray instead
+    let hit = Point2D::new(hit_x, start.y);
+    let corner = polygon[target];
+    let mut best_angle = f64::INFINITY;
+    for i in 0..n {
+        let (prev, p, next) = (polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]);
+        if i == target || cross(sub(p, prev), sub(next, p)) > 0.0 {
+            continue;
+        }
+        if point_in_triangle(p, start, hit, corner) {
+            let offset = sub(p, start);
+            let angle = offset.y.atan2(offset.x).abs();
+            if angle < best_angle {
+                best_angle = angle;
+                target = i;
+            }
+        }
+    }
+
+    // An earlier bridge may have duplicated the target; take the copy
+    // whose corner opens toward the hole
+    let corner = polygon[target];
+    if let Some(copy) = (0..n).find(|&i| {
+        polygon[i] == corner
+            && wedge_contains(
+                polygon[(i + n - 1) % n],
+                corner,
+                polygon[(i + 1) % n],
+                start,
+            )
+    }) {
+        target = copy;
+    }
+
+    let mut merged = Vec::with_capacity(n + hole.len() + 2);
+    merged.extend_from_slice(&polygon[..=target]);
+    merged.extend(hole[m..].iter().chain(&hole[..=m]));
+    merged.extend_from_slice(&polygon[target..]);
+    *polygon = merged;
+}
+
+/// True if `target` is seen from vertex `p` of a counter-clockwise polygon
+/// through the polygon's interior angle there
+fn wedge_contains(prev: Point2D, p: Point2D, next: Point2D, target: Point2D) -> bool {
+    let d = sub(target, p);
+    let left_of_in = cross(sub(p, prev), d) > 0.0;
+    let left_of_out = cross(sub(next, p), d) > 0.0;
+    if cross(sub(p, prev), sub(next, p)) >= 0.0 {
+        left_of_in && left_of_out
+    } else {
+        left_of_in || left_of_out
+    }
+}
+
+/// True if `p` is inside or on the counter-clockwise or clockwise triangle
+fn point_in_triangle(p: Point2D, a: Point2D, b: Point2D, c: Point2D) -> bool {
+    let d1 = cross(sub(b, a), sub(p, a));
+    let d2 = cross(sub(c, b), sub(p, b));
+    let d3 = cross(sub(a, c), sub(p, c));
+    let negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(negative && positive)
+}
+
+/// Ear-clip a counter-clockwise polygon that may touch itself along bridges
+///
+/// An ear is blocked by any other vertex strictly inside it or on its new
+/// diagonal; vertices duplicated by a bridge never block. Flat vertices
+/// (where holes line up with a bridge) are kept, so the triangles share
+/// every boundary vertex and the surface has no T-junctions.
+fn ear_clip(polygon: &[Point2D]) -> Vec<[Point2D; 3]> {
+    let mut remaining: Vec<Point2D> = polygon.to_vec();
+    let mut triangles = Vec::with_capacity(remaining.len().saturating_sub(2));
+    let mut i = 0;
+    let mut misses = 0;
+    while remaining.len() > 3 {
+        let k = remaining.len();
+        i %= k;
+        let (a, b, c) = (
+            remaining[(i + k - 1) % k],
+            remaining[i],
+            remaining[(i + 1) % k],
+        );
+        let turn = cross(sub(b, a), sub(c, b));
+        let is_ear = turn > 0.0
+            && remaining
+                .iter()
+                .all(|&p| p == a || p == b || p == c || !blocks_ear(p, a, b, c));
+        // After a full fruitless pass (rounding on a bridge) clip anyway
+        // rather than loop
+        if is_ear || misses > k {
+            if turn > 0.0 {
+                triangles.push([a, b, c]);
+            }
+            remaining.remove(i);
+            misses = 0;
+        } else {
+            i += 1;
+            misses += 1;
+        }
+    }
+    if remaining.len() == 3
+        && cross(
+            sub(remaining[1], remaining[0]),
+            sub(remaining[2], remaining[1]),
+        ) > 0.0
+    {
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+    }
+    triangles
+}
+
+/// True if `p` is strictly inside the counter-clockwise triangle, or on the
+/// open diagonal from `a` to `c`
+fn blocks_ear(p: Point2D, a: Point2D, b: Point2D, c: Point2D) -> bool {
+    let inside = cross(sub(b, a), sub(p, a)) > 0.0
+        && cross(sub(c, b), sub(p, b)) > 0.0
+        && cross(sub(a, c), sub(p, c)) > 0.0;
+    let diagonal = sub(c, a);
+    let along = dot(sub(p, a), diagonal);
+    inside
+        || (cross(diagonal, sub(p, a)).abs() <= VERTEX_EPSILON * distance(a, c)
+            && along > 0.0
+            && along < dot(diagonal, diagonal))
+}
+
+/// Parts of `line` outside the convex `polygon`
+///
+/// `polygon` winds counter-clockwise (x-right, y-up axes) without
+/// repeating its first point. Returns `None` when no segment enters the
+/// polygon's interior, so untouched lines keep their closed flag; pieces
+/// that only touch the boundary are kept.
+pub(crate) fn cut_polyline_outside_convex(
+    line: &[Point2D],
+    polygon: &[Point2D],
+) -> Option<Vec<Vec<Point2D>>> {
+    let n = polygon.len();
+    if n < 3 || line.len() < 2 {
+        return None;
+    }
+    let lerp =
+        |p: Point2D, q: Point2D, t: f64| Point2D::new(p.x + (q.x - p.x) * t, p.y + (q.y - p.y) * t);
+
+    let mut pieces = Vec::new();
+    let mut current: Vec<Point2D> = Vec::new();
+    let mut entered = false;
+    for pair in line.windows(2) {
+        let (p, q) = (pair[0], pair[1]);
+        let d = sub(q, p);
+        // Clip the segment's parameter range to the inside of every edge
+        let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+        for i in 0..n {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            let inward = Point2D::new(a.y - b.y, b.x - a.x);
+            let num = dot(inward, sub(p, a));
+            let den = dot(inward, d);
+            if den == 0.0 {
+                if num <= 0.0 {
+                    t0 = 1.0;
+                    t1 = 0.0;
+                }
+            } else if den > 0.0 {
+                t0 = t0.max(-num / den);
+            } else {
+                t1 = t1.min(-num / den);
+            }
+        }
+
+        if t0 >= t1 {
+            if current.is_empty() {
+                current.push(p);
+            }
+            current.push(q);
+            continue;
+        }
+        entered = true;
+        if t0 > 0.0 {
+            if current.is_empty() {
+                current.push(p);
+            }
+            current.push(lerp(p, q, t0));
+        }
+        if current.len() >= 2 {
+            pieces.push(std::mem::take(&mut current));
+        }
+        current.clear();
+        if t1 < 1.0 {
+            current.push(lerp(p, q, t1));
+            current.push(q);
+        }
+    }
+    if current.len() >= 2 {
+        pieces.push(current);
+    }
+    entered.then_some(pieces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f64, y: f64, side: f64) -> Vec<Point2D> {
+        vec![
+            Point2D::new(x, y),
+            Point2D::new(x + side, y),
+            Point2D::new(x + side, y + side),
+            Point2D::new(x, y + side),
+        ]
+    }
+
+    #[test]
+    fn test_planar_cells_of_a_tic_tac_toe_grid() {
+        // Four lines crossing in a # give one closed cell; the open arms
+        // around it are dangling
+        let lines = vec![
+            vec![Point2D::new(-1.0, 1.0), Point2D::new(4.0, 1.0)],
+            vec![Point2D::new(-1.0, 2.0), Point2D::new(4.0, 2.0)],
+            vec![Point2D::new(1.0, -1.0), Point2D::new(1.0, 4.0)],
+            vec![Point2D::new(2.0, -1.0), Point2D::new(2.0, 4.0)],
+        ];
+        let cells = planar_cells(&lines);
+        assert_eq!(cells.len(), 1);
+        assert!((polygon_area(&cells[0]) - 1.0).abs() < 1e-12);
+
+        // A ring through the arm ends closes the eight outer cells; a
+        // separate ring inside the middle cell makes that cell an island
+        // holder, leaving the small ring's own cell
+        let mut ring = square(-1.0, -1.0, 5.0);
+        ring.push(ring[0]);
+        let mut island = square(1.4, 1.4, 0.2);
+        island.push(island[0]);
+        let mut closed = lines.clone();
+        closed.push(ring);
+        closed.push(island);
+        let cells = planar_cells(&closed);
+        assert_eq!(cells.len(), 9);
+        assert!(cells.iter().all(|cell| polygon_area(cell) > 0.0));
+        assert!(cells
+            .iter()
+            .all(|cell| !(contains(cell, Point2D::new(1.3, 1.5)))));
+    }
+
+    #[test]
+    fn test_inset_polygon_shrinks_and_collapses() {
+        let inset = inset_polygon(&square(0.0, 0.0, 2.0), 0.25).unwrap();
+        assert_eq!(inset.len(), 4);
+        assert!((polygon_area(&inset) - 1.5 * 1.5).abs() < 1e-12);
+
+        // Clockwise input gives the same counter-clockwise result
+        let mut clockwise = square(0.0, 0.0, 2.0);
+        clockwise.reverse();
+        let again = inset_polygon(&clockwise, 0.25).unwrap();
+        assert!((polygon_area(&again) - polygon_area(&inset)).abs() < 1e-12);
+
+        assert!(inset_polygon(&square(0.0, 0.0, 2.0), 1.0).is_none());
+
+        // A small chamfer is swallowed, leaving a plain square corner
+        let chamfered = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(4.0, 0.0),
+            Point2D::new(4.0, 3.9),
+            Point2D::new(3.9, 4.0),
+            Point2D::new(0.0, 4.0),
+        ];
+        let inset = inset_polygon(&chamfered, 0.5).unwrap();
+        assert_eq!(inset.len(), 4);
+        assert!((polygon_area(&inset) - 9.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_triangulate_with_holes_covers_the_remaining_area() {
+        let outer = square(0.0, 0.0, 10.0);
+        // One hole clockwise, one counter-clockwise, sharing a ray height
+        let mut left = square(1.0, 4.0, 2.0);
+        left.reverse();
+        let holes = vec![left, square(6.0, 4.0, 2.0), square(3.0, 7.5, 1.0)];
+        let triangles = triangulate_with_holes(&outer, &holes);
+        let areas: Vec<f64> = triangles.iter().map(|t| polygon_area(t)).collect();
+        assert!(areas.iter().all(|&a| a > 0.0));
+        assert!((areas.iter().sum::<f64>() - (100.0 - 4.0 - 4.0 - 1.0)).abs() < 1e-9);
+        // No triangle covers a hole
+        for t in &triangles {
+            let centroid = Point2D::new(
+                (t[0].x + t[1].x + t[2].x) / 3.0,
+                (t[0].y + t[1].y + t[2].y) / 3.0,
+            );
+            assert!(holes.iter().all(|hole| !contains(hole, centroid)));
+        }
+    }
+
+    #[test]
+    fn test_cut_polyline_outside_convex() {
+        let hole = square(1.0, -1.0, 2.0);
+        let line = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(4.0, 0.0),
+            Point2D::new(4.0, 2.0),
+        ];
+        let pieces = cut_polyline_outside_convex(&line, &hole).unwrap();
+        assert_eq!(
+            pieces,
+            vec![
+                vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 0.0)],
+                vec![
+                    Point2D::new(3.0, 0.0),
+                    Point2D::new(4.0, 0.0),
+                    Point2D::new(4.0, 2.0)
+                ],
+            ]
+        );
+        // Lines that miss the interior, or run along its edge, are untouched
+        let along = vec![Point2D::new(0.0, 1.0), Point2D::new(4.0, 1.0)];
+        assert!(cut_polyline_outside_convex(&along, &hole).is_none());
+        let inside = vec![Point2D::new(1.5, 0.0), Point2D::new(2.5, 0.0)];
+        assert_eq!(
+            cut_polyline_outside_convex(&inside, &hole),
+            Some(Vec::new())
+        );
+    }
+}
@@ -0,0 +1,664 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    debug_assert_finite, polyline_is_degenerate, scale_polylines, simplified_layer,
+    simplify_polylines, ExportConfig, PatternLayer, Point2D, PointsTotal, SpirographError,
+    StoredLayer,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+
+/// Decoration repeated around the band between the track circles
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ChapterMotif {
+    /// Railroad minute ticks, with bolder majors (the default)
+    Ticks,
+    /// One closed wave about the mid-radius, `amplitude` mm from it at the
+    /// crests, with `frequency` full waves per repeat
+    Wave { amplitude: f64, frequency: usize },
+    /// Two waves in antiphase spanning the whole band, crossing at the
+    /// mid-radius once per repeat like the strands of a twisted rope
+    Rope,
+    /// A closed circle `diameter` mm across on the mid-radius per repeat
+    Dots { diameter: f64 },
+}
+
+impl ChapterMotif {
+    /// Multiply the motif's lengths by `factor`
+    fn scale_lengths(&mut self, factor: f64) {
+        match self {
+            ChapterMotif::Wave { amplitude, .. } => *amplitude *= factor,
+            ChapterMotif::Dots { diameter } => *diameter *= factor,
+            ChapterMotif::Ticks | ChapterMotif::Rope => {}
+        }
+    }
+}
+
+/// Configuration for a chapter ring
+///
+/// The track is two concentric circles about the dial center with a motif
+/// repeated `minutes` times in the band between them. The default railroad
+/// motif draws a radial tick for every minute; every `major_every`-th tick
+/// (starting at 12 o'clock) is drawn as a closed bar `major_extra_width`
+/// wide instead of a single line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterRingConfig {
+    /// Radius of the inner track circle in mm
+    pub r_inner: f64,
+    /// Radius of the outer track circle in mm
+    pub r_outer: f64,
+    /// Number of divisions (motif repeats) around the dial
+    pub minutes: usize,
+    /// What is drawn between the track circles
+    pub motif: ChapterMotif,
+    /// Every this many divisions is a major (bolder) tick
+    pub major_every: usize,
+    /// Width of a major tick bar in mm; 0 draws it as a plain line
+    pub major_extra_width: f64,
+    /// Rectangular cutouts for applied indices as (hour, width, height):
+    /// centered on the track's mid-radius at the hour, `width` across the
+    /// radius and `height` along it
+    pub index_cutouts: Vec<(u32, f64, f64)>,
+    /// Number of points per track circle
+    pub resolution: PointsTotal,
+}
+
+impl Default for ChapterRingConfig {
+    fn default() -> Self {
+        ChapterRingConfig {
+            r_inner: 17.0,
+            r_outer: 19.0,
+            minutes: 60,
+            motif: ChapterMotif::Ticks,
+            major_every: 5,
+            major_extra_width: 0.15,
+            index_cutouts: Vec::new(),
+            resolution: PointsTotal(720),
+        }
+    }
+}
+
+impl ChapterRingConfig {
+    /// Create a 60-minute track between two radii
+    ///
+    /// # Arguments
+    /// * `r_inner` - Radius of the inner track circle
+    /// * `r_outer` - Radius of the outer track circle
+    pub fn new(r_inner: f64, r_outer: f64) -> Self {
+        ChapterRingConfig {
+            r_inner,
+            r_outer,
+            ..Default::default()
+        }
+    }
+
+    /// Draw `motif` in the band, `repeats` times around the dial
+    pub fn with_motif(mut self, motif: ChapterMotif, repeats: usize) -> Self {
+        self.motif = motif;
+        self.minutes = repeats;
+        self
+    }
+
+    /// Add a cutout for an applied index at `hour`
+    pub fn with_index_cutout(mut self, hour: u32, width: f64, height: f64) -> Self {
+        self.index_cutouts.push((hour, width, height));
+        self
+    }
+
+    /// Check the radii, divisions and cutouts
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if !(self.r_inner.is_finite() && self.r_inner > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "r_inner must be positive".to_string(),
+            ));
+        }
+        if !(self.r_outer.is_finite() && self.r_outer > self.r_inner) {
+            return Err(SpirographError::InvalidParameter(
+                "r_outer must be greater than r_inner".to_string(),
+            ));
+        }
+        if self.minutes == 0 || self.major_every == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "minutes and major_every must be at least 1".to_string(),
+            ));
+        }
+        if self.resolution.get() < 3 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 3".to_string(),
+            ));
+        }
+        match self.motif {
+            ChapterMotif::Wave {
+                amplitude,
+                frequency,
+            } => {
+                if !(amplitude.is_finite() && amplitude > 0.0) || frequency == 0 {
+                    return Err(SpirographError::InvalidParameter(
+                        "wave amplitude must be positive and frequency at least 1".to_string(),
+                    ));
+                }
+            }
+            ChapterMotif::Dots { diameter } => {
+                if !(diameter.is_finite() && diameter > 0.0) {
+                    return Err(SpirographError::InvalidParameter(
+                        "dot diameter must be positive".to_string(),
+                    ));
+                }
+            }
+            ChapterMotif::Ticks | ChapterMotif::Rope => {}
+        }
+        // A major bar must fit between its neighbours at the inner circle
+        let pitch = 2.0 * self.r_inner * (PI / self.minutes as f64).sin();
+        if self.motif == ChapterMotif::Ticks
+            && !(self.major_extra_width.is_finite()
+                && self.major_extra_width >= 0.0
+                && self.major_extra_width < pitch.min(self.r_inner))
+        {
+            return Err(SpirographError::InvalidParameter(format!(
+                "major_extra_width must be non-negative and below the tick pitch ({:.3})",
+                pitch
+            )));
+        }
+        let mid = self.mid_radius();
+        for &(hour, width, height) in &self.index_cutouts {
+            if !(width.is_finite() && width > 0.0 && height.is_finite() && height > 0.0) {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "index cutout at hour {} must have a positive width and height",
+                    hour
+                )));
+            }
+            if height / 2.0 >= mid || width / 2.0 >= mid {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "index cutout at hour {} reaches the dial center",
+                    hour
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Radius halfway across the track
+    pub fn mid_radius(&self) -> f64 {
+        (self.r_inner + self.r_outer) / 2.0
+    }
+
+    /// Angle of tick `index`, 0 at 12 o'clock and running clockwise on the dial
+    pub fn tick_angle(&self, index: usize) -> f64 {
+        -PI / 2.0 + 2.0 * PI * index as f64 / self.minutes as f64
+    }
+
+    /// True if tick `index` is a major division
+    pub fn is_major(&self, index: usize) -> bool {
+        index.is_multiple_of(self.major_every)
+    }
+}
+
+/// Chapter ring: track circles, minute ticks or a motif, and index cutouts
+///
+/// Always centered on the dial. Ticks run exactly from the inner to the
+/// outer circle, and every motif point is clamped radially into the band
+/// so no motif ever crosses either track circle. Cutout rectangles are drawn closed and are also exposed
+/// through [`cutouts`](Self::cutouts) so solid exports can pierce them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterRingLayer {
+    pub config: ChapterRingConfig,
+    #[serde(skip)]
+    lines: Vec<Vec<Point2D>>,
+}
+
+impl ChapterRingLayer {
+    /// Create a chapter ring layer, validating the configuration
+    pub fn new(config: ChapterRingConfig) -> Result<Self, SpirographError> {
+        config.validate()?;
+        Ok(ChapterRingLayer {
+            config,
+            lines: Vec::new(),
+        })
+    }
+
+    /// Generate the track: inner and outer circles, then every tick (or
+    /// the closed motif lines) in clockwise order from 12 o'clock, then the
+    /// closed cutout rectangles
+    pub fn generate(&mut self) {
+        let config = &self.config;
+        let mut lines = vec![circle(config.r_inner, config.resolution.get())];
+        lines.push(circle(config.r_outer, config.resolution.get()));
+        lines.extend(self.ticks().into_iter().map(|(tick, _)| tick));
+        lines.extend(self.motif_lines());
+        lines.extend(self.cutouts().into_iter().map(|mut rect| {
+            rect.push(rect[0]);
+            rect
+        }));
+        self.lines = lines;
+        debug_assert_finite("ChapterRingLayer", &self.lines);
+    }
+
+    /// Every tick with its major flag, clockwise from 12 o'clock; empty
+    /// unless the motif is [`ChapterMotif::Ticks`]
+    ///
+    /// A minor tick is a single radial segment. A major tick is a closed
+    /// bar whose long sides are offset half `major_extra_width` either side
+    /// of the radius and end where they meet the circles, so both kinds lie
+    /// exactly between the inner and outer circle.
+    pub fn ticks(&self) -> Vec<(Vec<Point2D>, bool)> {
+        let config = &self.config;
+        if config.motif != ChapterMotif::Ticks {
+            return Vec::new();
+        }
+        (0..config.minutes)
+            .map(|i| {
+                let (sin, cos) = config.tick_angle(i).sin_cos();
+                let major = config.is_major(i);
+                let half = config.major_extra_width / 2.0;
+                // Point at `along` the radius and `across` it (clockwise positive)
+                let at = |along: f64, across: f64| {
+                    Point2D::new(along * cos - across * sin, along * sin + across * cos)
+                };
+                let reach = |r: f64, across: f64| (r * r - across * across).sqrt();
+                let tick = if major && half > 0.0 {
+                    let (inner, outer) = (reach(config.r_inner, half), reach(config.r_outer, half));
+                    vec![
+                        at(inner, -half),
+                        at(outer, -half),
+                        at(outer, half),
+                        at(inner, half),
+                        at(inner, -half),
+                    ]
+                } else {
+                    vec![at(config.r_inner, 0.0), at(config.r_outer, 0.0)]
+                };
+                (tick, major)
+            })
+            .collect()
+    }
+
+    /// Closed motif lines, each point clamped radially into the band
+    ///
+    /// Waves and rope strands are traced from 12 o'clock with at least 16
+    /// points per wave; dots run clockwise from 12 o'clock. A wave whose
+    /// amplitude exceeds half the band, or a dot wider than it, is flattened
+    /// against the track circles rather than crossing them.
+    fn motif_lines(&self) -> Vec<Vec<Point2D>> {
+        let config = &self.config;
+        let (mid, half) = (config.mid_radius(), (config.r_outer - config.r_inner) / 2.0);
+        let confine = |p: Point2D| {
+            let r = p.x.hypot(p.y);
+            let clamped = r.clamp(config.r_inner, config.r_outer);
+            if r == clamped {
+                p
+            } else {
+                Point2D::new(p.x * clamped / r, p.y * clamped / r)
+            }
+        };
+        // Closed wave of `waves` crests about the mid-radius
+        let wave = |amplitude: f64, waves: usize| -> Vec<Point2D> {
+            let steps = config.resolution.get().max(16 * waves);
+            let mut line: Vec<Point2D> = (0..steps)
+                .map(|i| {
+                    let t = 2.0 * PI * i as f64 / steps as f64;
+                    let r = mid + amplitude * (waves as f64 * t).sin();
+                    let (sin, cos) = (config.tick_angle(0) + t).sin_cos();
+                    confine(Point2D::new(r * cos, r * sin))
+                })
+                .collect();
+            line.push(line[0]);
+            line
+        };
+        match config.motif {
+            ChapterMotif::Ticks => Vec::new(),
+            ChapterMotif::Wave {
+                amplitude,
+                frequency,
+            } => vec![wave(amplitude, config.minutes * frequency)],
+            ChapterMotif::Rope => vec![wave(half, config.minutes), wave(-half, config.minutes)],
+            ChapterMotif::Dots { diameter } => {
+                let steps = (config.resolution.get() / config.minutes).max(24);
+                (0..config.minutes)
+                    .map(|i| {
+                        let (sin, cos) = config.tick_angle(i).sin_cos();
+                        let center = Point2D::new(mid * cos, mid * sin);
+                        let mut dot: Vec<Point2D> = circle(diameter / 2.0, steps)[..steps]
+                            .iter()
+                            .map(|p| confine(Point2D::new(center.x + p.x, center.y + p.y)))
+                            .collect();
+                        dot.push(dot[0]);
+                        dot
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Outline of every index cutout, in configuration order
+    ///
+    /// Each is a rectangle centered on the mid-radius at its hour, listed
+    /// counter-clockwise (in x-right, y-up axes) without repeating the
+    /// first corner.
+    pub fn cutouts(&self) -> Vec<Vec<Point2D>> {
+        let mid = self.config.mid_radius();
+        self.config
+            .index_cutouts
+            .iter()
+            .map(|&(hour, width, height)| {
+                let angle = -PI / 2.0 + 2.0 * PI * (hour % 12) as f64 / 12.0;
+                let (sin, cos) = angle.sin_cos();
+                let (center_x, center_y) = (mid * cos, mid * sin);
+                [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+                    .into_iter()
+                    .map(|(u, v)| {
+                        let (along, across) = (u * height / 2.0, v * width / 2.0);
+                        Point2D::new(
+                            center_x + along * cos - across * sin,
+                            center_y + along * sin + across * cos,
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Get the generated lines: circles, ticks or motif, then closed cutouts
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.lines
+    }
+
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply every length (radii, widths, cutouts and lines) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.r_inner *= factor;
+        self.config.r_outer *= factor;
+        self.config.major_extra_width *= factor;
+        self.config.motif.scale_lengths(factor);
+        for (_, width, height) in &mut self.config.index_cutouts {
+            *width *= factor;
+            *height *= factor;
+        }
+        scale_polylines(&mut self.lines, factor);
+    }
+
+    /// Export the chapter ring to SVG format
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+
+        if self.lines.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.lines.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        for line in &layer.lines {
+            if polyline_is_degenerate(line) {
+                continue;
+            }
+            writer.add(writer.polyline_node(line, false, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+                    .set("stroke-linecap", "round")
+                    .set("stroke-linejoin", "round")
+            }));
+        }
+
+        writer.save(filename)
+    }
+}
+
+impl PatternLayer for ChapterRingLayer {
+    fn generate(&mut self) {
+        ChapterRingLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for ChapterRingLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        ChapterRingLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn stroke_width(&self) -> f64 {
+        0.05
+    }
+}
+
+impl LayerType for ChapterRingLayer {
+    const KIND: LayerKind = LayerKind::ChapterRing;
+}
+
+/// Closed circle about the origin, repeating its first point
+fn circle(radius: f64, resolution: usize) -> Vec<Point2D> {
+    (0..=resolution)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f64 / resolution as f64;
+            Point2D::new(radius * angle.cos(), radius * angle.sin())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::clock_to_cartesian;
+
+    fn radius(p: Point2D) -> f64 {
+        p.x.hypot(p.y)
+    }
+
+    #[test]
+    fn test_sixty_ticks_with_bold_fives_between_circles() {
+        let mut layer = ChapterRingLayer::new(ChapterRingConfig::new(17.0, 19.0)).unwrap();
+        layer.generate();
+        let ticks = layer.ticks();
+        assert_eq!(ticks.len(), 60);
+        assert_eq!(layer.lines().len(), 62);
+
+        for (i, (tick, major)) in ticks.iter().enumerate() {
+            assert_eq!(*major, i % 5 == 0, "tick {}", i);
+            // Major ticks are closed bars, minor ticks single segments
+            assert_eq!(tick.len(), if *major { 5 } else { 2 });
+            for &p in tick {
+                let r = radius(p);
+                assert!(
+                    (r - 17.0).abs() < 1e-9 || (r - 19.0).abs() < 1e-9,
+                    "tick {} point at r = {}",
+                    i,
+                    r
+                );
+            }
+        }
+
+        // Tick 0 is at 12 o'clock and a bar 0.15 wide
+        let (bar, _) = &ticks[0];
+        assert!(bar.iter().all(|p| p.y < 0.0));
+        let width = bar.iter().map(|p| p.x).fold(f64::MIN, f64::max)
+            - bar.iter().map(|p| p.x).fold(f64::MAX, f64::min);
+        assert!((width - 0.15).abs() < 1e-9);
+        // Tick 15 is at 3 o'clock, and tick 16 a minute past it
+        let (minor, _) = &ticks[16];
+        let angle = minor[0].y.atan2(minor[0].x);
+        assert!((angle - 2.0 * PI / 60.0).abs() < 1e-12);
+        let three = &ticks[15].0[..4];
+        assert!(three.iter().all(|p| p.x > 0.0));
+        assert!(three.iter().map(|p| p.y).sum::<f64>().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cutouts_centered_at_mid_radius_on_the_hour() {
+        let config = ChapterRingConfig::new(16.0, 20.0)
+            .with_index_cutout(12, 1.2, 2.5)
+            .with_index_cutout(3, 1.0, 2.0)
+            .with_index_cutout(8, 0.8, 3.0);
+        let mut layer = ChapterRingLayer::new(config.clone()).unwrap();
+        layer.generate();
+        let cutouts = layer.cutouts();
+        assert_eq!(cutouts.len(), 3);
+        assert_eq!(layer.lines().len(), 2 + 60 + 3);
+
+        for (rect, &(hour, width, height)) in cutouts.iter().zip(&config.index_cutouts) {
+            assert_eq!(rect.len(), 4);
+            let center = Point2D::new(
+                rect.iter().map(|p| p.x).sum::<f64>() / 4.0,
+                rect.iter().map(|p| p.y).sum::<f64>() / 4.0,
+            );
+            let (x, y) = clock_to_cartesian(hour, 0, 18.0);
+            assert!((center.x - x).abs() < 1e-9 && (center.y - y).abs() < 1e-9);
+            assert!((crate::cells::polygon_area(rect) - width * height).abs() < 1e-9);
+            // Height runs along the radius
+            let radial: Vec<f64> = rect.iter().map(|&p| radius(p)).collect();
+            let span = radial.iter().fold(f64::MIN, |a, &b| a.max(b))
+                - radial.iter().fold(f64::MAX, |a, &b| a.min(b));
+            assert!(span > height * 0.99);
+        }
+        // Drawn closed
+        let drawn = &layer.lines()[62];
+        assert_eq!(drawn.first(), drawn.last());
+    }
+
+    #[test]
+    fn test_motifs_are_closed_and_stay_in_the_band() {
+        let motifs = [
+            (
+                ChapterMotif::Wave {
+                    amplitude: 0.5,
+                    frequency: 2,
+                },
+                1,
+            ),
+            // Crests reach past both circles and are flattened onto them
+            (
+                ChapterMotif::Wave {
+                    amplitude: 3.0,
+                    frequency: 1,
+                },
+                1,
+            ),
+            (ChapterMotif::Rope, 2),
+            (ChapterMotif::Dots { diameter: 1.5 }, 36),
+            (ChapterMotif::Dots { diameter: 4.0 }, 36),
+        ];
+        for (motif, count) in motifs {
+            let config = ChapterRingConfig::new(17.0, 19.0).with_motif(motif, 36);
+            let mut layer = ChapterRingLayer::new(config).unwrap();
+            layer.generate();
+            assert!(layer.ticks().is_empty());
+            let lines = &layer.lines()[2..];
+            assert_eq!(lines.len(), count, "{:?}", motif);
+            for line in lines {
+                assert_eq!(line.first(), line.last(), "{:?} is not closed", motif);
+                for &p in line {
+                    let r = radius(p);
+                    assert!(
+                        (17.0 - 1e-9..=19.0 + 1e-9).contains(&r),
+                        "{:?} at r = {}",
+                        motif,
+                        r
+                    );
+                }
+            }
+            let reach = |f: fn(f64, f64) -> f64, init: f64| {
+                lines.iter().flatten().map(|&p| radius(p)).fold(init, f)
+            };
+            let (lo, hi) = (reach(f64::min, f64::MAX), reach(f64::max, f64::MIN));
+            match motif {
+                ChapterMotif::Wave { amplitude: 0.5, .. } => {
+                    assert!((lo - 17.5).abs() < 1e-3 && (hi - 18.5).abs() < 1e-3);
+                    // 72 crests, each at least 16 points apart
+                    assert!(lines[0].len() > 16 * 72);
+                }
+                ChapterMotif::Dots { diameter: 1.5 } => {
+                    assert!((lo - 17.25).abs() < 1e-9 && (hi - 18.75).abs() < 1e-9);
+                    // The first dot is centered at 12 o'clock
+                    let dot = &lines[0];
+                    let n = (dot.len() - 1) as f64;
+                    let cx = dot[..dot.len() - 1].iter().map(|p| p.x).sum::<f64>() / n;
+                    let cy = dot[..dot.len() - 1].iter().map(|p| p.y).sum::<f64>() / n;
+                    assert!(cx.abs() < 1e-9 && (cy + 18.0).abs() < 1e-9);
+                }
+                _ => assert!((lo - 17.0).abs() < 1e-9 && (hi - 19.0).abs() < 1e-9),
+            }
+        }
+    }
+
+    #[test]
+    fn test_chapter_ring_validation() {
+        let bad = |config: ChapterRingConfig| ChapterRingLayer::new(config).is_err();
+        assert!(bad(ChapterRingConfig::new(19.0, 17.0)));
+        assert!(bad(ChapterRingConfig::new(0.0, 17.0)));
+        assert!(bad(ChapterRingConfig {
+            minutes: 0,
+            ..ChapterRingConfig::default()
+        }));
+        assert!(bad(ChapterRingConfig {
+            major_extra_width: 2.0,
+            ..ChapterRingConfig::default()
+        }));
+        assert!(bad(
+            ChapterRingConfig::new(17.0, 19.0).with_index_cutout(3, 0.0, 1.0)
+        ));
+        assert!(bad(
+            ChapterRingConfig::new(17.0, 19.0).with_index_cutout(3, 1.0, 40.0)
+        ));
+        let wave = |amplitude, frequency| ChapterMotif::Wave {
+            amplitude,
+            frequency,
+        };
+        let motif = |motif| ChapterRingConfig::default().with_motif(motif, 24);
+        assert!(bad(motif(wave(0.0, 1))));
+        assert!(bad(motif(wave(0.5, 0))));
+        assert!(bad(motif(ChapterMotif::Dots { diameter: -1.0 })));
+        // Tick bar widths only matter for ticks
+        assert!(!bad(ChapterRingConfig {
+            major_extra_width: 2.0,
+            ..motif(ChapterMotif::Rope)
+        }));
+        assert!(!bad(ChapterRingConfig {
+            major_extra_width: 0.0,
+            ..ChapterRingConfig::default()
+        }));
+    }
+}
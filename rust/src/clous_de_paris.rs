@@ -1,6 +1,18 @@
 use std::f64::consts::PI;
 
-use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographError};
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clip_polyline_to_circle, clock_to_cartesian, debug_assert_finite, dedupe_polylines,
+    polar_to_cartesian, polyline_is_degenerate, scale_polylines, simplified_layer,
+    simplify_polylines, ExportConfig, PatternLayer, Point2D, PointsTotal, SpirographError,
+    StoredLayer, DEDUPE_EPSILON,
+};
+use crate::dxf::write_dxf_polylines;
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
 
 /// Configuration for the Clous de Paris (Hobnail) guilloché pattern
 ///
@@ -17,7 +29,7 @@ use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographE
 /// machine: the work moves back and forth under a V-shaped cutting tool, then is
 /// indexed (shifted) sideways for the next pass.  After one direction is complete,
 /// the work is rotated 90° and the process repeats.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClousDeParisConfig {
     /// Spacing between parallel grooves in mm (controls hobnail size)
     pub spacing: f64,
@@ -25,8 +37,12 @@ pub struct ClousDeParisConfig {
     pub radius: f64,
     /// Rotation angle of the grid in radians (default π/4 = 45° for classic diagonal)
     pub angle: f64,
-    /// Number of sample points per line for rendering
-    pub resolution: usize,
+    /// Number of sample points per line for rendering; also the points per
+    /// full turn of the arcs closing the partial cells
+    pub resolution: PointsTotal,
+    /// Generate the hobnail cells as closed outlines instead of the grooves
+    #[serde(default)]
+    pub emit_cells: bool,
 }
 
 impl Default for ClousDeParisConfig {
@@ -35,7 +51,8 @@ impl Default for ClousDeParisConfig {
             spacing: 1.0,
             radius: 22.0,
             angle: PI / 4.0,
-            resolution: 200,
+            resolution: PointsTotal(200),
+            emit_cells: false,
         }
     }
 }
@@ -55,10 +72,39 @@ impl ClousDeParisConfig {
     }
 
     /// Set the resolution (points per line)
-    pub fn with_resolution(mut self, resolution: usize) -> Self {
-        self.resolution = resolution;
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = resolution.into();
         self
     }
+
+    /// Set whether `generate` emits the cell outlines instead of the grooves
+    pub fn with_emit_cells(mut self, emit_cells: bool) -> Self {
+        self.emit_cells = emit_cells;
+        self
+    }
+
+    /// Check the groove spacing, radius and resolution
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.spacing <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "spacing must be positive".to_string(),
+            ));
+        }
+
+        if self.radius <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "radius must be positive".to_string(),
+            ));
+        }
+
+        if self.resolution.get() < 2 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 2".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 /// A Clous de Paris (Hobnail) pattern layer
@@ -68,11 +114,12 @@ impl ClousDeParisConfig {
 /// The pattern emerges from the intersection of V-shaped grooves cut
 /// in two orthogonal directions — each groove is a straight line, and
 /// the pyramidal facets form at the intersections.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClousDeParisLayer {
     pub config: ClousDeParisConfig,
     pub center_x: f64,
     pub center_y: f64,
+    #[serde(skip)]
     lines: Vec<Vec<Point2D>>,
 }
 
@@ -88,23 +135,7 @@ impl ClousDeParisLayer {
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        if config.spacing <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "spacing must be positive".to_string(),
-            ));
-        }
-
-        if config.radius <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "radius must be positive".to_string(),
-            ));
-        }
-
-        if config.resolution < 2 {
-            return Err(SpirographError::InvalidParameter(
-                "resolution must be at least 2".to_string(),
-            ));
-        }
+        config.validate()?;
 
         Ok(ClousDeParisLayer {
             config,
@@ -156,8 +187,16 @@ impl ClousDeParisLayer {
     ///
     /// so each line spans from `−√(r² − d²)` to `+√(r² − d²)` along its
     /// travel direction.
+    ///
+    /// With `config.emit_cells` the lines are the closed outlines from
+    /// `cells` instead.
     pub fn generate(&mut self) {
         self.lines.clear();
+        if self.config.emit_cells {
+            self.lines = self.cells();
+            debug_assert_finite("ClousDeParisLayer", &self.lines);
+            return;
+        }
 
         let r = self.config.radius;
         let s = self.config.spacing;
@@ -175,9 +214,10 @@ impl ClousDeParisLayer {
             for i in -n_lines..=n_lines {
                 let offset = (i as f64) * s;
 
-                // Analytic clip: line at perpendicular offset `offset` from centre
+                // Analytic clip: line at perpendicular offset `offset` from centre.
+                // A tangent line (disc == 0) has zero length and is skipped.
                 let disc = r * r - offset * offset;
-                if disc < 0.0 {
+                if disc <= 0.0 {
                     continue;
                 }
 
@@ -187,10 +227,10 @@ impl ClousDeParisLayer {
                 let ox = self.center_x + offset * (-sin_t);
                 let oy = self.center_y + offset * cos_t;
 
-                let mut line_points = Vec::with_capacity(self.config.resolution + 1);
+                let mut line_points = Vec::with_capacity(self.config.resolution.get() + 1);
 
-                for j in 0..=self.config.resolution {
-                    let frac = j as f64 / self.config.resolution as f64;
+                for j in 0..=self.config.resolution.get() {
+                    let frac = j as f64 / self.config.resolution.get() as f64;
                     let t = -t_half + 2.0 * t_half * frac;
 
                     let x = ox + t * cos_t;
@@ -204,6 +244,167 @@ impl ClousDeParisLayer {
                 }
             }
         }
+
+        dedupe_polylines(&mut self.lines, DEDUPE_EPSILON);
+        debug_assert_finite("ClousDeParisLayer", &self.lines);
+    }
+
+    /// Grid indices `(column, row)` of the whole hobnail cells, row by row
+    ///
+    /// Cell `(a, b)` spans `a..a + 1` spacings along the grid direction and
+    /// `b..b + 1` across it. A cell counts when its center lies within the
+    /// circle shrunk by half the cell's diagonal, which keeps all four
+    /// corners inside and drops the partial cells at the edge.
+    pub(crate) fn cell_grid(&self) -> Vec<(i64, i64)> {
+        let s = self.config.spacing;
+        let limit = self.config.radius - s * std::f64::consts::FRAC_1_SQRT_2;
+        if limit < 0.0 {
+            return Vec::new();
+        }
+
+        let n = (limit / s).ceil() as i64;
+        let mut cells = Vec::new();
+        for b in -n - 1..=n {
+            for a in -n - 1..=n {
+                let (u, v) = ((a as f64 + 0.5) * s, (b as f64 + 0.5) * s);
+                if u.hypot(v) <= limit {
+                    cells.push((a, b));
+                }
+            }
+        }
+        cells
+    }
+
+    /// Point at `u` spacings along the grid direction and `v` across it
+    fn grid_point(&self, u: f64, v: f64) -> Point2D {
+        let (sin_t, cos_t) = self.config.angle.sin_cos();
+        let s = self.config.spacing;
+        Point2D::new(
+            self.center_x + s * (u * cos_t - v * sin_t),
+            self.center_y + s * (u * sin_t + v * cos_t),
+        )
+    }
+
+    /// Centers of the whole hobnail cells formed by the two groove families
+    ///
+    /// Centers sit half a spacing off the grooves in both grid directions;
+    /// partial cells at the edge of the circle are left out. Computed from
+    /// the configuration, so the layer need not be generated.
+    pub fn cell_centers(&self) -> Vec<Point2D> {
+        self.cell_grid()
+            .into_iter()
+            .map(|(a, b)| self.grid_point(a as f64 + 0.5, b as f64 + 0.5))
+            .collect()
+    }
+
+    /// Corner quads of the cells from `cell_centers`, in the same order
+    ///
+    /// Corners run counter-clockwise in the grid's own axes; every edge
+    /// lies on a generated groove.
+    pub fn cell_polygons(&self) -> Vec<[Point2D; 4]> {
+        self.cell_grid()
+            .into_iter()
+            .map(|(a, b)| {
+                let (a, b) = (a as f64, b as f64);
+                [
+                    self.grid_point(a, b),
+                    self.grid_point(a + 1.0, b),
+                    self.grid_point(a + 1.0, b + 1.0),
+                    self.grid_point(a, b + 1.0),
+                ]
+            })
+            .collect()
+    }
+
+    /// Every hobnail cell as a closed polyline, its first point repeated
+    /// at the end
+    ///
+    /// The whole cells of `cell_polygons` come first, in the same order, as
+    /// 5-point quads. The cells the circle cuts through follow, clipped to
+    /// it: their groove edges end on the circle and are joined by arcs
+    /// sampled at `config.resolution` points per turn. Computed from the
+    /// configuration, so the layer need not be generated.
+    pub fn cells(&self) -> Vec<Vec<Point2D>> {
+        let whole = self.cell_grid();
+        let mut cells: Vec<Vec<Point2D>> = self
+            .cell_polygons()
+            .into_iter()
+            .map(|quad| {
+                let mut cell = quad.to_vec();
+                cell.push(quad[0]);
+                cell
+            })
+            .collect();
+
+        let s = self.config.spacing;
+        let r = self.config.radius;
+        let n = (r / s).ceil() as i64;
+        for b in -n - 1..=n {
+            for a in -n - 1..=n {
+                if whole
+                    .binary_search_by_key(&(b, a), |&(a, b)| (b, a))
+                    .is_ok()
+                {
+                    continue;
+                }
+                // Nearest point of the cell to the center, in grid units
+                let nearest = |lo: i64| (0.0f64).clamp(lo as f64, lo as f64 + 1.0);
+                if nearest(a).hypot(nearest(b)) * s >= r {
+                    continue;
+                }
+                let (a, b) = (a as f64, b as f64);
+                let quad = [
+                    self.grid_point(a, b),
+                    self.grid_point(a + 1.0, b),
+                    self.grid_point(a + 1.0, b + 1.0),
+                    self.grid_point(a, b + 1.0),
+                ];
+                cells.extend(self.clip_cell(&quad));
+            }
+        }
+        // Crossings at a corner repeat it; a cell only grazed by the
+        // circle is left with no area
+        dedupe_polylines(&mut cells, DEDUPE_EPSILON);
+        cells.retain(|cell| cell.len() >= 4);
+        cells
+    }
+
+    /// The counter-clockwise `quad` clipped to the circle as a closed
+    /// polyline, or `None` when nothing of it is inside
+    fn clip_cell(&self, quad: &[Point2D; 4]) -> Option<Vec<Point2D>> {
+        let center = Point2D::new(self.center_x, self.center_y);
+        let r = self.config.radius;
+        if quad
+            .iter()
+            .all(|p| (p.x - center.x).hypot(p.y - center.y) <= r)
+        {
+            let mut cell = quad.to_vec();
+            cell.push(quad[0]);
+            return Some(cell);
+        }
+        let pieces = clip_polyline_to_circle(quad, center, r, true);
+        if pieces.is_empty() {
+            return None;
+        }
+
+        // Each piece runs from where the outline enters the circle to where
+        // it leaves; the circle closes the gap to the next piece
+        let angle_of = |p: Point2D| (p.y - center.y).atan2(p.x - center.x);
+        let turn_step = 2.0 * PI / self.config.resolution.get() as f64;
+        let mut cell = Vec::new();
+        for (k, piece) in pieces.iter().enumerate() {
+            cell.extend_from_slice(piece);
+            let exit = angle_of(piece[piece.len() - 1]);
+            let entry = angle_of(pieces[(k + 1) % pieces.len()][0]);
+            let sweep = (entry - exit).rem_euclid(2.0 * PI);
+            let steps = (sweep / turn_step).ceil() as usize;
+            for j in 1..steps {
+                let (sin_a, cos_a) = (exit + sweep * j as f64 / steps as f64).sin_cos();
+                cell.push(Point2D::new(center.x + r * cos_a, center.y + r * sin_a));
+            }
+        }
+        cell.push(cell[0]);
+        Some(cell)
     }
 
     /// Get the generated lines
@@ -211,62 +412,143 @@ impl ClousDeParisLayer {
         &self.lines
     }
 
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply every length (config, center and generated lines) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.spacing *= factor;
+        self.config.radius *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.lines, factor);
+    }
+
     /// Export the pattern to SVG format
     pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        use svg::node::element::{path::Data, Path};
-        use svg::Document;
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export the pattern to DXF with one LWPOLYLINE per line, in mm
+    pub fn to_dxf(&self, filename: &str) -> Result<(), SpirographError> {
+        if self.lines.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        write_dxf_polylines(filename, &self.lines, false, None)
+    }
 
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
         if self.lines.is_empty() {
             return Err(SpirographError::ExportError(
                 "Pattern not generated. Call generate() first.".to_string(),
             ));
         }
 
-        // Find bounds
-        let mut min_x = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.lines.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
 
-        for line in &self.lines {
-            for point in line {
-                min_x = min_x.min(point.x);
-                max_x = max_x.max(point.x);
-                min_y = min_y.min(point.y);
-                max_y = max_y.max(point.y);
+        for line in &layer.lines {
+            if polyline_is_degenerate(line) {
+                continue;
             }
+
+            writer.add_polyline(line, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+            });
         }
 
-        let margin = 5.0;
-        let width = max_x - min_x + 2.0 * margin;
-        let height = max_y - min_y + 2.0 * margin;
+        writer.save(filename)
+    }
+}
 
-        let mut document = Document::new()
-            .set("width", format!("{}mm", width))
-            .set("height", format!("{}mm", height))
-            .set("viewBox", (min_x - margin, min_y - margin, width, height));
+impl PatternLayer for ClousDeParisLayer {
+    fn generate(&mut self) {
+        ClousDeParisLayer::generate(self)
+    }
 
-        for line in &self.lines {
-            if line.is_empty() {
-                continue;
-            }
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
 
-            let mut data = Data::new().move_to((line[0].x, line[0].y));
-            for point in line.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for ClousDeParisLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        ClousDeParisLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
 
-            let path = Path::new()
-                .set("d", data)
-                .set("fill", "none")
-                .set("stroke", "black")
-                .set("stroke-width", 0.05);
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
 
-            document = document.add(path);
+impl LayerType for ClousDeParisLayer {
+    const KIND: LayerKind = LayerKind::ClousDeParis;
+}
+
+impl Progressive for ClousDeParisLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
         }
 
-        svg::save(filename, &document)
-            .map_err(|e| SpirographError::ExportError(format!("Failed to save SVG: {}", e)))
+        // Grooves are straight, so two points per line lose nothing
+        let mut coarse = self.clone();
+        coarse.config.resolution =
+            PointsTotal(scale_count(self.config.resolution.get(), fraction, 2));
+        coarse.generate();
+        self.lines = coarse.lines;
+    }
+}
+
+impl PointCounted for ClousDeParisLayer {
+    fn point_count(&self) -> usize {
+        self.lines.iter().map(Vec::len).sum()
     }
 }
 
@@ -280,7 +562,7 @@ mod tests {
         assert!((config.spacing - 1.0).abs() < 1e-10);
         assert!((config.radius - 22.0).abs() < 1e-10);
         assert!((config.angle - PI / 4.0).abs() < 1e-10);
-        assert_eq!(config.resolution, 200);
+        assert_eq!(config.resolution, PointsTotal(200));
     }
 
     #[test]
@@ -329,7 +611,7 @@ mod tests {
 
         // low resolution
         let config = ClousDeParisConfig {
-            resolution: 1,
+            resolution: PointsTotal(1),
             ..Default::default()
         };
         assert!(ClousDeParisLayer::new(config).is_err());
@@ -341,7 +623,8 @@ mod tests {
             spacing: 2.0,
             radius: 10.0,
             angle: PI / 4.0,
-            resolution: 50,
+            resolution: PointsTotal(50),
+            emit_cells: false,
         };
         let mut layer = ClousDeParisLayer::new(config).unwrap();
         layer.generate();
@@ -361,7 +644,8 @@ mod tests {
             spacing: 2.0,
             radius: 10.0,
             angle: 0.0,
-            resolution: 100,
+            resolution: PointsTotal(100),
+            emit_cells: false,
         };
         let mut layer = ClousDeParisLayer::new(config).unwrap();
         layer.generate();
@@ -390,7 +674,8 @@ mod tests {
             spacing: 5.0,
             radius: 10.0,
             angle: 0.0,
-            resolution: 10,
+            resolution: PointsTotal(10),
+            emit_cells: false,
         };
         let mut layer = ClousDeParisLayer::new(config).unwrap();
         layer.generate();
@@ -410,7 +695,8 @@ mod tests {
             spacing: 3.0,
             radius: 10.0,
             angle: 0.0,
-            resolution: 50,
+            resolution: PointsTotal(50),
+            emit_cells: false,
         };
         let mut layer = ClousDeParisLayer::new(config).unwrap();
         layer.generate();
@@ -439,4 +725,176 @@ mod tests {
         // 3 o'clock → positive x
         assert!(layer.center_x > 0.0);
     }
+
+    /// Number of half-integer lattice points `((a + ½)s, (b + ½)s)` within `limit`
+    fn analytic_cell_count(spacing: f64, radius: f64) -> usize {
+        let limit = radius - spacing / 2f64.sqrt();
+        let columns = (limit / spacing + 0.5).floor() as i64;
+        (-columns..columns)
+            .map(|a| {
+                let u = (a as f64 + 0.5) * spacing;
+                let half_chord = (limit * limit - u * u).max(0.0).sqrt();
+                2 * (half_chord / spacing + 0.5).floor() as usize
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_clous_de_paris_cell_centers_match_analytic_count() {
+        for (spacing, radius, angle) in [(1.0, 22.0, PI / 4.0), (2.0, 10.0, 0.0), (0.7, 5.3, 0.3)] {
+            let config = ClousDeParisConfig {
+                spacing,
+                radius,
+                angle,
+                ..Default::default()
+            };
+            let layer = ClousDeParisLayer::new_with_center(config, 3.0, -2.0).unwrap();
+            let centers = layer.cell_centers();
+            assert_eq!(centers.len(), analytic_cell_count(spacing, radius));
+            assert_eq!(layer.cell_polygons().len(), centers.len());
+
+            // Whole cells only: every corner inside the circle
+            for quad in layer.cell_polygons() {
+                for corner in quad {
+                    assert!((corner.x - 3.0).hypot(corner.y + 2.0) <= radius + 1e-9);
+                }
+            }
+        }
+
+        // The four cells around the center of a 2 mm grid have corners
+        // 2√2 mm out; a 2.5 mm circle cuts them all off
+        let layer = ClousDeParisLayer::new(ClousDeParisConfig::new(2.0, 2.5)).unwrap();
+        assert!(layer.cell_centers().is_empty());
+        let layer = ClousDeParisLayer::new(ClousDeParisConfig::new(2.0, 2.9)).unwrap();
+        assert_eq!(layer.cell_centers().len(), 4);
+    }
+
+    #[test]
+    fn test_clous_de_paris_cell_edges_lie_on_grooves() {
+        let config = ClousDeParisConfig {
+            spacing: 1.5,
+            radius: 9.0,
+            angle: 0.4,
+            resolution: PointsTotal(10),
+            emit_cells: false,
+        };
+        let mut layer = ClousDeParisLayer::new_with_center(config, 1.0, 2.0).unwrap();
+        layer.generate();
+
+        // Distance from p to the infinite line through a groove, and whether
+        // p falls within the groove's extent
+        let on_groove = |p: Point2D, line: &[Point2D]| {
+            let (a, b) = (line[0], line[line.len() - 1]);
+            let (dx, dy) = (b.x - a.x, b.y - a.y);
+            let len = dx.hypot(dy);
+            let across = ((p.x - a.x) * dy - (p.y - a.y) * dx).abs() / len;
+            let along = ((p.x - a.x) * dx + (p.y - a.y) * dy) / len;
+            across < 1e-9 && (-1e-9..=len + 1e-9).contains(&along)
+        };
+        for quad in layer.cell_polygons() {
+            for k in 0..4 {
+                let (p, q) = (quad[k], quad[(k + 1) % 4]);
+                assert!(
+                    layer
+                        .lines()
+                        .iter()
+                        .any(|line| on_groove(p, line) && on_groove(q, line)),
+                    "edge {:?}-{:?} is not on a groove",
+                    p,
+                    q
+                );
+            }
+        }
+
+        let centers = layer.cell_centers();
+        for (center, quad) in centers.iter().zip(layer.cell_polygons()) {
+            let mean_x = quad.iter().map(|p| p.x).sum::<f64>() / 4.0;
+            let mean_y = quad.iter().map(|p| p.y).sum::<f64>() / 4.0;
+            assert!((mean_x - center.x).abs() < 1e-9 && (mean_y - center.y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_clous_de_paris_cells_cover_the_circle() {
+        let config = ClousDeParisConfig::new(2.0, 10.0).with_resolution(360);
+        let mut layer = ClousDeParisLayer::new_with_center(config, 1.0, -3.0).unwrap();
+        let cells = layer.cells();
+        let whole = analytic_cell_count(2.0, 10.0);
+        assert_eq!(layer.cell_centers().len(), whole);
+        assert!(cells.len() > whole);
+
+        for (cell, quad) in cells.iter().zip(layer.cell_polygons()) {
+            assert_eq!(cell.len(), 5);
+            assert_eq!(&cell[..4], &quad[..]);
+        }
+        for cell in &cells {
+            assert_eq!(cell[0], cell[cell.len() - 1]);
+            for p in cell {
+                assert!((p.x - 1.0).hypot(p.y + 3.0) <= 10.0 + 1e-6);
+            }
+        }
+
+        // Together the cells tile the disc, losing no more than the
+        // 360-gon inscribed in it
+        let area: f64 = cells
+            .iter()
+            .map(|cell| crate::cells::polygon_area(&cell[..cell.len() - 1]))
+            .sum();
+        assert!(cells
+            .iter()
+            .all(|cell| crate::cells::polygon_area(&cell[..cell.len() - 1]) > 0.0));
+        let inscribed = 180.0 * 100.0 * (2.0 * PI / 360.0).sin();
+        assert!(inscribed <= area && area <= PI * 100.0, "area {}", area);
+
+        layer.generate();
+        // Nine grooves each way; the two tangent ones are skipped
+        assert_eq!(layer.lines().len(), 2 * 9);
+        layer.config.emit_cells = true;
+        layer.generate();
+        assert_eq!(layer.lines(), &cells);
+    }
+
+    #[test]
+    fn test_clous_de_paris_svg_rejects_tangent_only_lines() {
+        // A radius far below the dedupe epsilon leaves every line a single point
+        let config = ClousDeParisConfig::new(1.0, 1e-10);
+        let mut layer = ClousDeParisLayer::new(config).unwrap();
+        layer.generate();
+        assert!(!layer.lines().is_empty());
+
+        let tmpfile = std::env::temp_dir().join("test_clous_tangent.svg");
+        let path = tmpfile.to_str().expect("temp dir path is valid UTF-8");
+        let err = layer.to_svg(path).unwrap_err();
+        assert!(err.to_string().contains("no drawable geometry"));
+        assert!(!tmpfile.exists());
+
+        let mut layer = ClousDeParisLayer::new(ClousDeParisConfig::new(2.0, 10.0)).unwrap();
+        layer.generate();
+        layer.to_svg(path).unwrap();
+        let svg = std::fs::read_to_string(&tmpfile).unwrap();
+        let _ = std::fs::remove_file(&tmpfile);
+        assert!(!svg.contains("inf") && !svg.contains("NaN"));
+    }
+
+    #[test]
+    fn test_clous_de_paris_dxf_export() {
+        let mut layer = ClousDeParisLayer::new(ClousDeParisConfig::new(2.0, 10.0)).unwrap();
+        let tmpfile = std::env::temp_dir().join("test_clous.dxf");
+        let path = tmpfile.to_str().expect("temp dir path is valid UTF-8");
+        assert!(layer.to_dxf(path).is_err());
+
+        layer.generate();
+        layer.to_dxf(path).unwrap();
+        let dxf = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let expected: Vec<Vec<Point2D>> = layer
+            .lines()
+            .iter()
+            .filter(|line| line.len() >= 2)
+            .cloned()
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(crate::dxf::read_dxf_polylines(&dxf), expected);
+    }
 }
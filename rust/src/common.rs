@@ -1,11 +1,24 @@
+use std::any::Any;
+use std::collections::HashMap;
 use std::f64::consts::PI;
 
+use serde::{Deserialize, Serialize};
+
+use crate::groove::GrooveProfile;
+use crate::guilloche::TaggedLayer;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::PointCounted;
+use crate::rose_engine::{Arc, PathSegment, RosettePattern};
+
+pub mod svg_writer;
+
 /// Common validation error for spirograph and flinque parameters
 #[derive(Debug, Clone, PartialEq)]
 pub enum SpirographError {
     InvalidRadius(String),
     InvalidParameter(String),
     ExportError(String),
+    UnsupportedForMachine(String),
 }
 
 impl std::fmt::Display for SpirographError {
@@ -14,6 +27,9 @@ impl std::fmt::Display for SpirographError {
             SpirographError::InvalidRadius(msg) => write!(f, "Invalid radius: {}", msg),
             SpirographError::InvalidParameter(msg) => write!(f, "Invalid parameter: {}", msg),
             SpirographError::ExportError(msg) => write!(f, "Export error: {}", msg),
+            SpirographError::UnsupportedForMachine(msg) => {
+                write!(f, "Unsupported for machine: {}", msg)
+            }
         }
     }
 }
@@ -22,16 +38,81 @@ impl std::error::Error for SpirographError {}
 
 /// Validates that a radius is within the required range for watch faces (26mm-44mm)
 pub fn validate_radius(radius: f64) -> Result<(), SpirographError> {
-    if radius < 26.0 || radius > 44.0 {
+    RadiusPolicy::Watch.check(radius)
+}
+
+/// Validates that a radius is within `min..=max` millimeters
+pub fn validate_radius_in(radius: f64, min: f64, max: f64) -> Result<(), SpirographError> {
+    if !(min..=max).contains(&radius) {
         Err(SpirographError::InvalidRadius(format!(
-            "Radius must be between 26mm and 44mm, got {}mm",
-            radius
+            "Radius must be between {}mm and {}mm, got {}mm",
+            min, max, radius
         )))
     } else {
         Ok(())
     }
 }
 
+/// Which radii a pattern, watch face or spirograph accepts
+///
+/// The plain constructors use `Watch`; pocket-watch dials and small
+/// jewelry pieces need `Unrestricted` or a `Custom` range.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum RadiusPolicy {
+    /// Wristwatch dials, 26mm to 44mm
+    #[default]
+    Watch,
+    /// Any positive, finite radius
+    Unrestricted,
+    /// Radii from `min` to `max` millimeters, both included
+    Custom { min: f64, max: f64 },
+}
+
+impl RadiusPolicy {
+    /// Check for a `Custom` range with `0 < min <= max`, both finite
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if let RadiusPolicy::Custom { min, max } = *self {
+            if !(min.is_finite() && max.is_finite() && min > 0.0 && min <= max) {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "radius range must satisfy 0 < min <= max, got {} to {}",
+                    min, max
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check `radius`, in millimeters, against this policy
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::RadiusPolicy;
+    ///
+    /// assert!(RadiusPolicy::Watch.check(60.0).is_err());
+    /// assert!(RadiusPolicy::Unrestricted.check(60.0).is_ok());
+    /// assert!(RadiusPolicy::Custom { min: 10.0, max: 20.0 }.check(15.0).is_ok());
+    /// ```
+    pub fn check(&self, radius: f64) -> Result<(), SpirographError> {
+        match *self {
+            RadiusPolicy::Watch => validate_radius_in(radius, 26.0, 44.0),
+            RadiusPolicy::Unrestricted => {
+                if radius.is_finite() && radius > 0.0 {
+                    Ok(())
+                } else {
+                    Err(SpirographError::InvalidRadius(format!(
+                        "Radius must be positive and finite, got {}mm",
+                        radius
+                    )))
+                }
+            }
+            RadiusPolicy::Custom { min, max } => {
+                self.validate()?;
+                validate_radius_in(radius, min, max)
+            }
+        }
+    }
+}
+
 /// Convert clock position (hour, minute) and distance from center to cartesian coordinates.
 ///
 /// # Arguments
@@ -59,52 +140,3286 @@ pub fn clock_to_cartesian(hour: u32, minute: u32, distance: f64) -> (f64, f64) {
     (x, y)
 }
 
-/// Convert polar coordinates (angle, distance) to cartesian (x, y)
-pub fn polar_to_cartesian(angle: f64, distance: f64) -> (f64, f64) {
-    (distance * angle.cos(), distance * angle.sin())
+/// Wrap an angle in radians to the range [0, 2π)
+pub fn normalize_angle(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(2.0 * PI);
+    // rem_euclid can round up to exactly 2π for tiny negative inputs
+    if wrapped >= 2.0 * PI {
+        0.0
+    } else {
+        wrapped
+    }
 }
 
-/// A 2D point
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Point2D {
-    pub x: f64,
-    pub y: f64,
+/// Sampling density per full turn of a curve's angle
+///
+/// A curve sweeping `n` turns at `PointsPerRev(k)` is cut into `n·k`
+/// steps and holds `n·k + 1` points. Spirographs (over `rotations`) and
+/// draperie rings (over several turns for non-integer frequencies) are
+/// sampled this way.
+///
+/// Every generator follows the same end-point rule: a closed curve holds
+/// its step count plus one points, the last repeating the first, and an
+/// open line holds exactly its step count plus one samples before any
+/// clipping or de-duplication.
+///
+/// # Example
+/// ```
+/// use turtles::{PointsPerRev, PointsTotal};
+///
+/// let per_rev = PointsPerRev::from(360);
+/// assert_eq!(per_rev.over(3), PointsTotal(1080));
+/// assert_eq!(usize::from(per_rev), 360);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PointsPerRev(pub usize);
+
+impl PointsPerRev {
+    /// The number of steps per turn
+    pub fn get(self) -> usize {
+        self.0
+    }
+
+    /// Total steps over `revolutions` turns
+    pub fn over(self, revolutions: usize) -> PointsTotal {
+        PointsTotal(self.0 * revolutions)
+    }
 }
 
-impl Point2D {
-    pub fn new(x: f64, y: f64) -> Self {
-        Point2D { x, y }
+impl From<usize> for PointsPerRev {
+    fn from(value: usize) -> Self {
+        PointsPerRev(value)
     }
 }
 
-/// A 3D point (for spherical spirographs)
+impl From<PointsPerRev> for usize {
+    fn from(value: PointsPerRev) -> Self {
+        value.0
+    }
+}
+
+impl std::fmt::Display for PointsPerRev {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} points per revolution", self.0)
+    }
+}
+
+/// Sampling density of one whole curve or line, whatever it sweeps
+///
+/// A closed curve at `PointsTotal(k)` holds `k + 1` points with the last
+/// repeating the first; an open line holds `k + 1` samples before
+/// clipping. Single-turn rings (diamant, limaçon, huit-eight, chapter
+/// ring, flinqué), paon and clous de Paris lines, cube strokes and rose
+/// engine tool paths are sampled this way. See [`PointsPerRev`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PointsTotal(pub usize);
+
+impl PointsTotal {
+    /// The number of steps along the curve
+    pub fn get(self) -> usize {
+        self.0
+    }
+}
+
+impl From<usize> for PointsTotal {
+    fn from(value: usize) -> Self {
+        PointsTotal(value)
+    }
+}
+
+impl From<PointsTotal> for usize {
+    fn from(value: PointsTotal) -> Self {
+        value.0
+    }
+}
+
+impl std::fmt::Display for PointsTotal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} points", self.0)
+    }
+}
+
+/// Locate the first non-finite point in a set of lines
+///
+/// Returns `(line_index, point_index)` of the first point with a NaN or
+/// infinite coordinate.
+pub fn first_non_finite(lines: &[Vec<Point2D>]) -> Option<(usize, usize)> {
+    lines
+        .iter()
+        .enumerate()
+        .find_map(|(i, line)| line.iter().position(|p| !p.is_finite()).map(|j| (i, j)))
+}
+
+/// Panic in debug builds if any generated point is non-finite
+///
+/// A NaN or infinity in generated geometry is always a bug (usually an
+/// unvalidated input); this names the layer and the first bad point.
+pub(crate) fn debug_assert_finite(layer: &str, lines: &[Vec<Point2D>]) {
+    if cfg!(debug_assertions) {
+        if let Some((i, j)) = first_non_finite(lines) {
+            panic!(
+                "{} generated a non-finite point at line {}, index {}: {:?}",
+                layer, i, j, lines[i][j]
+            );
+        }
+    }
+}
+
+/// Default distance below which consecutive points are merged (1e-9 mm)
+pub const DEDUPE_EPSILON: f64 = 1e-9;
+
+/// Drop points lying within `eps` of the previously kept point
+///
+/// Zero-length segments have no direction, so tangent and offset math
+/// would otherwise divide by zero and emit spikes.
+pub fn dedupe_consecutive(points: &mut Vec<Point2D>, eps: f64) {
+    points.dedup_by(|p, kept| (p.x - kept.x).hypot(p.y - kept.y) <= eps);
+}
+
+/// [`dedupe_consecutive`] applied lazily, yielding the points it keeps
+pub(crate) fn dedupe_consecutive_iter(
+    points: impl Iterator<Item = Point2D>,
+    eps: f64,
+) -> impl Iterator<Item = Point2D> {
+    let mut kept: Option<Point2D> = None;
+    points.filter(move |p| {
+        let dropped = kept.is_some_and(|k| (p.x - k.x).hypot(p.y - k.y) <= eps);
+        if !dropped {
+            kept = Some(*p);
+        }
+        !dropped
+    })
+}
+
+/// Apply [`dedupe_consecutive`] to every polyline
+pub(crate) fn dedupe_polylines(lines: &mut [Vec<Point2D>], eps: f64) {
+    for line in lines {
+        dedupe_consecutive(line, eps);
+    }
+}
+
+/// Ramer–Douglas–Peucker simplification of a polyline
+///
+/// Keeps the endpoints and every point needed to stay within `tolerance`
+/// of the original line, so nearly collinear runs collapse to a single
+/// segment. A closed line keeps its repeated first point. A `tolerance`
+/// of zero or less (or not finite) returns the points unchanged.
+///
+/// # Example
+/// ```
+/// use turtles::{simplify_polyline, Point2D};
+///
+/// let line: Vec<Point2D> = (0..1000).map(|i| Point2D::new(i as f64, 0.0)).collect();
+/// assert_eq!(simplify_polyline(&line, 0.01).len(), 2);
+/// ```
+pub fn simplify_polyline(points: &[Point2D], tolerance: f64) -> Vec<Point2D> {
+    if !(tolerance.is_finite() && tolerance > 0.0) || points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    let mut spans = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = spans.pop() {
+        let farthest = (start + 1..end)
+            .map(|i| {
+                (
+                    i,
+                    segment_distance(&points[i], &points[start], &points[end]),
+                )
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((i, _)) = farthest.filter(|&(_, d)| d > tolerance) {
+            keep[i] = true;
+            spans.push((start, i));
+            spans.push((i, end));
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(p, kept)| kept.then_some(*p))
+        .collect()
+}
+
+/// Apply [`simplify_polyline`] to every polyline
+pub(crate) fn simplify_polylines(lines: &mut [Vec<Point2D>], tolerance: f64) {
+    if tolerance > 0.0 {
+        for line in lines {
+            *line = simplify_polyline(line, tolerance);
+        }
+    }
+}
+
+/// Distance from `p` to the segment `a`-`b` (to `a` itself when `a == b`)
+fn segment_distance(p: &Point2D, a: &Point2D, b: &Point2D) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return (p.x - a.x).hypot(p.y - a.y);
+    }
+    let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / length_sq).clamp(0.0, 1.0);
+    (p.x - a.x - t * dx).hypot(p.y - a.y - t * dy)
+}
+
+/// Greedily fit a polyline with circular arcs, falling back to lines
+///
+/// From each point the longest run of following points that stays within
+/// `tolerance` of one circle, turning one way through less than a full
+/// turn, becomes an arc through its first, middle and last points. Where
+/// no three points fit an arc, or a straight segment covers at least as
+/// many, the longest run within `tolerance` of a segment becomes a line.
+/// Chord midpoints are held to the same tolerance, so the segments follow
+/// the polyline within it everywhere and join end to end.
+///
+/// # Example
+/// ```
+/// use turtles::{fit_arcs, PathSegment, Point2D};
+///
+/// let quarter: Vec<Point2D> = (0..=90)
+///     .map(|d| (d as f64).to_radians())
+///     .map(|a| Point2D::new(10.0 * a.cos(), 10.0 * a.sin()))
+///     .collect();
+/// let segments = fit_arcs(&quarter, 0.01);
+/// assert!(matches!(&segments[..], [PathSegment::Arc(arc)] if (arc.radius - 10.0).abs() < 1e-9));
+/// ```
+pub fn fit_arcs(points: &[Point2D], tolerance: f64) -> Vec<PathSegment> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    while start + 1 < points.len() {
+        let mut line_end = start + 1;
+        while line_end + 1 < points.len()
+            && points[start + 1..=line_end]
+                .iter()
+                .all(|p| segment_distance(p, &points[start], &points[line_end + 1]) <= tolerance)
+        {
+            line_end += 1;
+        }
+
+        let mut arc = None;
+        let mut arc_end = start + 1;
+        while arc_end + 1 < points.len() {
+            match fit_arc(&points[start..=arc_end + 1], tolerance) {
+                Some(fitted) => {
+                    arc = Some(fitted);
+                    arc_end += 1;
+                }
+                None => break,
+            }
+        }
+
+        match arc {
+            Some(arc) if arc_end > line_end => {
+                segments.push(PathSegment::Arc(arc));
+                start = arc_end;
+            }
+            _ => {
+                segments.push(PathSegment::Line {
+                    start: points[start],
+                    end: points[line_end],
+                });
+                start = line_end;
+            }
+        }
+    }
+    segments
+}
+
+/// The arc through the first, middle and last of `points`, if every point
+/// and chord midpoint lies within `tolerance` of it and the points turn
+/// one way through less than a full turn
+fn fit_arc(points: &[Point2D], tolerance: f64) -> Option<Arc> {
+    let (a, b, c) = (
+        points[0],
+        points[points.len() / 2],
+        points[points.len() - 1],
+    );
+    let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+    if d.abs() < 1e-12 {
+        return None;
+    }
+    let (a2, b2, c2) = (
+        a.x * a.x + a.y * a.y,
+        b.x * b.x + b.y * b.y,
+        c.x * c.x + c.y * c.y,
+    );
+    let center = Point2D::new(
+        (a2 * (b.y - c.y) + b2 * (c.y - a.y) + c2 * (a.y - b.y)) / d,
+        (a2 * (c.x - b.x) + b2 * (a.x - c.x) + c2 * (b.x - a.x)) / d,
+    );
+    let radius = (a.x - center.x).hypot(a.y - center.y);
+    let off_circle = |p: Point2D| ((p.x - center.x).hypot(p.y - center.y) - radius).abs();
+
+    let mut sweep = 0.0;
+    for pair in points.windows(2) {
+        let (p, q) = (pair[0], pair[1]);
+        let midpoint = Point2D::new((p.x + q.x) / 2.0, (p.y + q.y) / 2.0);
+        if off_circle(q) > tolerance || off_circle(midpoint) > tolerance {
+            return None;
+        }
+        let (u, v) = (
+            (p.x - center.x, p.y - center.y),
+            (q.x - center.x, q.y - center.y),
+        );
+        let turn = (u.0 * v.1 - u.1 * v.0).atan2(u.0 * v.0 + u.1 * v.1);
+        if turn * sweep < 0.0 {
+            return None;
+        }
+        sweep += turn;
+    }
+    if sweep.abs() >= 2.0 * PI {
+        return None;
+    }
+
+    let start_angle = (a.y - center.y).atan2(a.x - center.x);
+    Some(Arc {
+        center,
+        radius,
+        start_angle,
+        end_angle: start_angle + sweep,
+    })
+}
+
+/// A polyline offset `distance` to the right of its direction of travel
+///
+/// With y up, a loop traced counter-clockwise grows by `distance` and a
+/// negative distance offsets to the left. Each point moves along the
+/// perpendicular of its averaged tangent, lengthened to a miter so the
+/// offset stays `distance` from the segments on both sides of a corner,
+/// up to [`MITER_LIMIT`] times `distance`; a closed line (last point on
+/// the first) is mitered across the join.
+/// Where the offset folds back on itself inside a concave corner, the loop
+/// is cut out at the crossing, while crossings the line makes itself are
+/// kept.
+///
+/// # Example
+/// ```
+/// use turtles::{offset_polyline, Point2D};
+///
+/// let ring: Vec<Point2D> = (0..=360)
+///     .map(|d| (d as f64).to_radians())
+///     .map(|a| Point2D::new(10.0 * a.cos(), 10.0 * a.sin()))
+///     .collect();
+/// let wider = offset_polyline(&ring, 0.5);
+/// assert!(wider.iter().all(|p| (p.x.hypot(p.y) - 10.5).abs() < 1e-4));
+/// ```
+pub fn offset_polyline(points: &[Point2D], distance: f64) -> Vec<Point2D> {
+    trim_offset_loops(points, moved_points(points, |_| distance, true))
+}
+
+/// Longest move of an offset corner point, in multiples of the offset
+/// distance, as SVG's default `stroke-miterlimit`
+pub const MITER_LIMIT: f64 = 4.0;
+
+/// Each of `points` moved `distance(i)` to the right of the direction of
+/// travel, along the perpendicular of its averaged tangent, without
+/// trimming
+///
+/// Corners are not mitered: the points stay on the envelope of a round
+/// bit of radius `distance(i)`, as the rose engine's cut edges do.
+pub(crate) fn offset_points<F>(points: &[Point2D], distance: F) -> Vec<Point2D>
+where
+    F: Fn(usize) -> f64,
+{
+    moved_points(points, distance, false)
+}
+
+/// `offset_points`, lengthening each move by its miter factor if `mitered`
+fn moved_points<F>(points: &[Point2D], distance: F, mitered: bool) -> Vec<Point2D>
+where
+    F: Fn(usize) -> f64,
+{
+    let n = points.len();
+    let closed = n >= 3 && {
+        let (first, last) = (points[0], points[n - 1]);
+        (last.x - first.x).hypot(last.y - first.y) <= 1e-9
+    };
+    // Closed lines take the tangent across the join at both ends
+    let join = if closed {
+        polyline_vertex_join(&[points[n - 2], points[0], points[1]], 1)
+    } else {
+        None
+    };
+
+    (0..n)
+        .map(|i| {
+            let (angle, miter) = match join {
+                Some(join) if i == 0 || i == n - 1 => join,
+                _ => polyline_vertex_join(points, i).unwrap_or((0.0, 1.0)),
+            };
+            let perp_angle = angle + PI / 2.0;
+            let d = if mitered {
+                distance(i) * miter
+            } else {
+                distance(i)
+            };
+            Point2D::new(
+                points[i].x - d * perp_angle.cos(),
+                points[i].y - d * perp_angle.sin(),
+            )
+        })
+        .collect()
+}
+
+/// Cut the loops an offset line forms inside concave corners
+///
+/// Two crossing segments of `offset` close such a loop when the stretch of
+/// `original` between them turns through less than half a turn, too
+/// little for the original to cross itself there; the loop is replaced by
+/// the crossing point, taking the farthest crossing first.
+fn trim_offset_loops(original: &[Point2D], offset: Vec<Point2D>) -> Vec<Point2D> {
+    let n = offset.len();
+    if n < 4 {
+        return offset;
+    }
+
+    // Absolute turning of the original up to and including each point
+    let mut turning = vec![0.0; n];
+    for k in 1..n - 1 {
+        let (a, b, c) = (original[k - 1], original[k], original[k + 1]);
+        let (u, v) = ((b.x - a.x, b.y - a.y), (c.x - b.x, c.y - b.y));
+        let turn = (u.0 * v.1 - u.1 * v.0).atan2(u.0 * v.0 + u.1 * v.1);
+        turning[k] = turning[k - 1] + turn.abs();
+    }
+    turning[n - 1] = turning[n - 2];
+
+    // Grid of segments, in cells no smaller than the longest segment so
+    // each segment touches at most four
+    let cell = offset
+        .windows(2)
+        .map(|w| (w[1].x - w[0].x).hypot(w[1].y - w[0].y))
+        .fold(0.0, f64::max);
+    if !(cell.is_finite() && cell > 0.0) {
+        return offset;
+    }
+    let key = |p: Point2D| ((p.x / cell).floor() as i64, (p.y / cell).floor() as i64);
+    let cells = |k: usize| {
+        let (a, b) = (key(offset[k]), key(offset[k + 1]));
+        (a.0.min(b.0)..=a.0.max(b.0))
+            .flat_map(move |x| (a.1.min(b.1)..=a.1.max(b.1)).map(move |y| (x, y)))
+    };
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for k in 0..n - 1 {
+        for c in cells(k) {
+            grid.entry(c).or_default().push(k);
+        }
+    }
+
+    let mut trimmed = vec![offset[0]];
+    let mut i = 0;
+    while i + 1 < n {
+        let crossing = cells(i)
+            .filter_map(|c| grid.get(&c))
+            .flatten()
+            .filter(|&&j| j >= i + 2 && turning[j] - turning[i] < PI)
+            .filter_map(|&j| {
+                segment_crossing(offset[i], offset[i + 1], offset[j], offset[j + 1]).map(|p| (j, p))
+            })
+            .max_by_key(|&(j, _)| j);
+        match crossing {
+            Some((j, p)) => {
+                trimmed.push(p);
+                i = j;
+            }
+            None => {
+                trimmed.push(offset[i + 1]);
+                i += 1;
+            }
+        }
+    }
+    trimmed
+}
+
+/// Where segment a-b crosses segment c-d, if it does
+fn segment_crossing(a: Point2D, b: Point2D, c: Point2D, d: Point2D) -> Option<Point2D> {
+    let (r, s) = ((b.x - a.x, b.y - a.y), (d.x - c.x, d.y - c.y));
+    let denom = r.0 * s.1 - r.1 * s.0;
+    if denom == 0.0 {
+        return None;
+    }
+    let (qx, qy) = (c.x - a.x, c.y - a.y);
+    let t = (qx * s.1 - qy * s.0) / denom;
+    let u = (qx * r.1 - qy * r.0) / denom;
+    ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u))
+        .then(|| Point2D::new(a.x + t * r.0, a.y + t * r.1))
+}
+
+/// `layer` with its lines simplified to `tolerance` for export, or
+/// borrowed as-is when `tolerance` is zero
+pub(crate) fn simplified_layer<L: StoredLayer + Clone>(
+    layer: &L,
+    tolerance: f64,
+) -> std::borrow::Cow<'_, L> {
+    if tolerance > 0.0 {
+        let mut simplified = layer.clone();
+        simplified.simplify(tolerance);
+        std::borrow::Cow::Owned(simplified)
+    } else {
+        std::borrow::Cow::Borrowed(layer)
+    }
+}
+
+/// `f` applied to each of `0..n`, in order
+///
+/// With the `parallel` feature and `parallel` set, the calls are spread
+/// over the rayon thread pool; the results come back in the same order.
+#[cfg(feature = "parallel")]
+pub(crate) fn map_indices<T, F>(n: usize, parallel: bool, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync + Send,
+{
+    use rayon::prelude::*;
+
+    if parallel {
+        (0..n).into_par_iter().map(f).collect()
+    } else {
+        (0..n).map(f).collect()
+    }
+}
+
+/// `f` applied to each of `0..n`, in order
+///
+/// Always serial without the `parallel` feature.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map_indices<T, F>(n: usize, _parallel: bool, f: F) -> Vec<T>
+where
+    F: Fn(usize) -> T,
+{
+    (0..n).map(f).collect()
+}
+
+/// True if a polyline has fewer than two distinct points
+///
+/// Exporters skip (or reject) such lines: they have no length to draw.
+pub fn polyline_is_degenerate(points: &[Point2D]) -> bool {
+    match points.first() {
+        None => true,
+        Some(first) => points
+            .iter()
+            .all(|p| (p.x - first.x).hypot(p.y - first.y) <= DEDUPE_EPSILON),
+    }
+}
+
+/// Direction angle of a polyline at vertex `i` with its miter factor
+///
+/// Interior vertices average the unit directions of the incoming and
+/// outgoing segments. Zero-length segments are skipped in favour of the
+/// nearest non-degenerate neighbour, so repeated points borrow a real
+/// tangent. Returns `None` only if the whole polyline is a single point.
+///
+/// The factor is `1 / cos` of half the turn at the vertex, the stretch
+/// that keeps an offset point equally far from both segments, capped at
+/// [`MITER_LIMIT`]; ends and straight runs have a factor of 1.
+fn polyline_vertex_join(points: &[Point2D], i: usize) -> Option<(f64, f64)> {
+    let direction = |k: usize| {
+        let dx = points[k].x - points[k - 1].x;
+        let dy = points[k].y - points[k - 1].y;
+        let len = dx.hypot(dy);
+        (len > 0.0).then(|| (dx / len, dy / len))
+    };
+    let incoming = (1..=i).rev().find_map(direction);
+    let outgoing = (i + 1..points.len()).find_map(direction);
+    match (incoming, outgoing) {
+        (Some((ux1, uy1)), Some((ux2, uy2))) => {
+            let (mx, my) = ((ux1 + ux2) / 2.0, (uy1 + uy2) / 2.0);
+            let half_turn_cos = mx.hypot(my);
+            let miter = if half_turn_cos * MITER_LIMIT > 1.0 {
+                1.0 / half_turn_cos
+            } else {
+                MITER_LIMIT
+            };
+            Some((my.atan2(mx), miter))
+        }
+        (Some((ux, uy)), None) | (None, Some((ux, uy))) => Some((uy.atan2(ux), 1.0)),
+        (None, None) => None,
+    }
+}
+
+/// Default cap on the points in one SVG `<path>` element
+///
+/// Some importers truncate very long `d` attributes; longer polylines are
+/// split across several paths.
+pub const DEFAULT_MAX_POINTS_PER_PATH: usize = 10_000;
+
+/// Split a polyline into runs of at most `max_points` points
+///
+/// Consecutive runs share their boundary point, so drawn in order they
+/// trace the original line without a gap. `max_points` below 2 is treated
+/// as 2.
+pub fn split_polyline(points: &[Point2D], max_points: usize) -> Vec<&[Point2D]> {
+    let max_points = max_points.max(2);
+    if points.len() <= max_points {
+        return vec![points];
+    }
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start + 1 < points.len() {
+        let end = (start + max_points).min(points.len());
+        runs.push(&points[start..end]);
+        start = end - 1;
+    }
+    runs
+}
+
+/// `path` with an `opacity` attribute, when there is one
+pub(crate) fn with_opacity(
+    path: svg::node::element::Path,
+    opacity: Option<f64>,
+) -> svg::node::element::Path {
+    match opacity {
+        Some(opacity) => path.set("opacity", opacity),
+        None => path,
+    }
+}
+
+/// Axis-aligned bounds `(min_x, min_y, max_x, max_y)` of a set of polylines
+///
+/// Returns `None` when there are no points.
+pub fn polyline_bounds<'a, I>(lines: I) -> Option<(f64, f64, f64, f64)>
+where
+    I: IntoIterator<Item = &'a [Point2D]>,
+{
+    let mut bounds: Option<(f64, f64, f64, f64)> = None;
+    for point in lines.into_iter().flatten() {
+        bounds = Some(match bounds {
+            None => (point.x, point.y, point.x, point.y),
+            Some((min_x, min_y, max_x, max_y)) => (
+                min_x.min(point.x),
+                min_y.min(point.y),
+                max_x.max(point.x),
+                max_y.max(point.y),
+            ),
+        });
+    }
+    bounds
+}
+
+/// Bounds of the lines an exporter will actually draw
+///
+/// Degenerate lines are left out, matching the exporters that skip them.
+/// Errors when nothing drawable remains, so an exporter never sizes a
+/// document from infinite bounds.
+pub(crate) fn drawable_bounds<'a, I>(lines: I) -> Result<(f64, f64, f64, f64), SpirographError>
+where
+    I: IntoIterator<Item = &'a [Point2D]>,
+{
+    GeometryStats::from_polylines(lines)
+        .map(|stats| (stats.min.x, stats.min.y, stats.max.x, stats.max.y))
+        .ok_or_else(|| SpirographError::ExportError("no drawable geometry".to_string()))
+}
+
+/// Margin around the lines in the standalone SVG exports, in mm
+pub(crate) const SVG_MARGIN: f64 = 5.0;
+
+/// SVG viewBox `(x, y, width, height)` framing the drawable lines with
+/// [`SVG_MARGIN`] on every side
+pub(crate) fn svg_view_box<'a, I>(lines: I) -> Result<(f64, f64, f64, f64), SpirographError>
+where
+    I: IntoIterator<Item = &'a [Point2D]>,
+{
+    Ok(framed_view_box(drawable_bounds(lines)?))
+}
+
+/// `(min_x, min_y, max_x, max_y)` bounds grown by [`SVG_MARGIN`] into a
+/// viewBox `(x, y, width, height)`
+fn framed_view_box((min_x, min_y, max_x, max_y): (f64, f64, f64, f64)) -> (f64, f64, f64, f64) {
+    (
+        min_x - SVG_MARGIN,
+        min_y - SVG_MARGIN,
+        max_x - min_x + 2.0 * SVG_MARGIN,
+        max_y - min_y + 2.0 * SVG_MARGIN,
+    )
+}
+
+/// Bounds, length and size of the lines an exporter draws
+///
+/// Degenerate lines are left out, as in [`drawable_bounds`], which the SVG
+/// exporters size their documents from.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Point3D {
-    pub x: f64,
-    pub y: f64,
-    pub z: f64,
+pub struct GeometryStats {
+    /// Lower-left corner of the axis-aligned bounds
+    pub min: Point2D,
+    /// Upper-right corner of the axis-aligned bounds
+    pub max: Point2D,
+    /// Sum of every segment's length, i.e. the distance the tool engraves
+    pub total_path_length: f64,
+    /// Number of polylines
+    pub num_polylines: usize,
+    /// Number of points over all polylines
+    pub num_points: usize,
 }
 
-impl Point3D {
-    pub fn new(x: f64, y: f64, z: f64) -> Self {
-        Point3D { x, y, z }
+impl GeometryStats {
+    /// Stats of the drawable lines, or `None` when there are none
+    pub fn from_polylines<'a, I>(lines: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a [Point2D]>,
+    {
+        let mut stats: Option<GeometryStats> = None;
+        for line in lines
+            .into_iter()
+            .filter(|line| !polyline_is_degenerate(line))
+        {
+            let (min, max) = line.iter().fold((line[0], line[0]), |(min, max), p| {
+                (
+                    Point2D::new(min.x.min(p.x), min.y.min(p.y)),
+                    Point2D::new(max.x.max(p.x), max.y.max(p.y)),
+                )
+            });
+            let length: f64 = line
+                .windows(2)
+                .map(|pair| (pair[1].x - pair[0].x).hypot(pair[1].y - pair[0].y))
+                .sum();
+            let stats = stats.get_or_insert(GeometryStats {
+                min,
+                max,
+                total_path_length: 0.0,
+                num_polylines: 0,
+                num_points: 0,
+            });
+            stats.min = Point2D::new(stats.min.x.min(min.x), stats.min.y.min(min.y));
+            stats.max = Point2D::new(stats.max.x.max(max.x), stats.max.y.max(max.y));
+            stats.total_path_length += length;
+            stats.num_polylines += 1;
+            stats.num_points += line.len();
+        }
+        stats
+    }
+
+    /// Width and height of the bounds
+    pub fn size(&self) -> (f64, f64) {
+        (self.max.x - self.min.x, self.max.y - self.min.y)
     }
 }
 
-/// Configuration for export formats
-#[derive(Debug, Clone)]
-pub struct ExportConfig {
-    pub depth: f64,          // Groove/channel depth in mm
-    pub base_thickness: f64, // Base plate thickness in mm
-    pub tool_radius: f64,    // Tool radius compensation in mm
+/// A pattern layer with generated polylines
+///
+/// Implemented by every layer and spirograph, so layouts can generate,
+/// check and measure any of them the same way, including as trait objects
+/// (see [`GuillochePattern::layers`](crate::GuillochePattern::layers)).
+pub trait PatternLayer {
+    /// Generate the polylines from the current parameters
+    fn generate(&mut self);
+
+    /// Check the parameters without generating anything
+    fn validate(&self) -> Result<(), SpirographError> {
+        Ok(())
+    }
+
+    /// Generate after `validate`, failing with its error instead of
+    /// generating from invalid parameters
+    fn try_generate(&mut self) -> Result<(), SpirographError> {
+        self.validate()?;
+        self.generate();
+        Ok(())
+    }
+
+    /// The generated polylines, as the exporters draw them; empty or
+    /// degenerate before generating
+    fn polylines(&self) -> &[Vec<Point2D>];
+
+    /// Bounds, engraved length and counts of the drawable polylines, or
+    /// `None` before generating
+    fn stats(&self) -> Option<GeometryStats> {
+        GeometryStats::from_polylines(self.polylines().iter().map(Vec::as_slice))
+    }
+
+    /// Lower-left and upper-right corners of the drawable polylines, or
+    /// `None` before generating
+    fn bounds(&self) -> Option<(Point2D, Point2D)> {
+        self.stats().map(|stats| (stats.min, stats.max))
+    }
+
+    /// The generated polylines with x negated, mirroring them across the
+    /// vertical axis through the origin
+    ///
+    /// Fails with `ExportError` before generating, as do the other
+    /// transforms.
+    fn mirrored_x(&self) -> Result<Vec<Vec<Point2D>>, SpirographError> {
+        transform_generated(self, |p| Point2D::new(-p.x, p.y))
+    }
+
+    /// The generated polylines with y negated, mirroring them across the
+    /// horizontal axis through the origin
+    fn mirrored_y(&self) -> Result<Vec<Vec<Point2D>>, SpirographError> {
+        transform_generated(self, |p| Point2D::new(p.x, -p.y))
+    }
+
+    /// The generated polylines turned counterclockwise by `radians` about
+    /// the origin
+    fn rotated(&self, radians: f64) -> Result<Vec<Vec<Point2D>>, SpirographError> {
+        if !radians.is_finite() {
+            return Err(SpirographError::InvalidParameter(format!(
+                "Rotation must be finite, got {}",
+                radians
+            )));
+        }
+        let (sin, cos) = radians.sin_cos();
+        transform_generated(self, |p| {
+            Point2D::new(p.x * cos - p.y * sin, p.x * sin + p.y * cos)
+        })
+    }
+
+    /// `n` copies of the generated polylines spaced evenly around a circle
+    /// of `radius` about the origin
+    ///
+    /// Each copy is moved so the middle of its bounds sits on the circle,
+    /// the first at angle 0 (3 o'clock), and turned with its position so
+    /// the copies share an `n`-fold symmetry. With `radius` 0 and a
+    /// layer centered on the origin this is the layer rotated `n` times.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{HorizontalSpirograph, PatternLayer};
+    ///
+    /// let mut spiro = HorizontalSpirograph::new(30.0, 0.75, 0.6, 3, 90).unwrap();
+    /// spiro.generate();
+    /// let copies = spiro.replicated_radial(3, 20.0).unwrap();
+    /// assert_eq!(copies.len(), 3);
+    /// ```
+    fn replicated_radial(
+        &self,
+        n: usize,
+        radius: f64,
+    ) -> Result<Vec<Vec<Vec<Point2D>>>, SpirographError> {
+        if n == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "Radial replication needs at least one copy".to_string(),
+            ));
+        }
+        if !(radius.is_finite() && radius >= 0.0) {
+            return Err(SpirographError::InvalidParameter(format!(
+                "Replication radius must be non-negative and finite, got {}",
+                radius
+            )));
+        }
+        let lines = generated_polylines(self)?;
+        let (min_x, min_y, max_x, max_y) = polyline_bounds(lines.iter().map(Vec::as_slice))
+            .expect("generated polylines have points");
+        let middle = Point2D::new((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+        Ok((0..n)
+            .map(|k| {
+                let (sin, cos) = (2.0 * PI * k as f64 / n as f64).sin_cos();
+                let place = |p: Point2D| {
+                    let (x, y) = (p.x - middle.x + radius, p.y - middle.y);
+                    Point2D::new(x * cos - y * sin, x * sin + y * cos)
+                };
+                lines
+                    .iter()
+                    .map(|line| line.iter().map(|&p| place(p)).collect())
+                    .collect()
+            })
+            .collect())
+    }
 }
 
-impl Default for ExportConfig {
-    fn default() -> Self {
-        ExportConfig {
-            depth: 0.1,
-            base_thickness: 2.0,
-            tool_radius: 0.0,
+/// A layer's generated polylines, or `ExportError` before generating
+fn generated_polylines<L: PatternLayer + ?Sized>(
+    layer: &L,
+) -> Result<&[Vec<Point2D>], SpirographError> {
+    let lines = layer.polylines();
+    if lines.iter().all(Vec::is_empty) {
+        return Err(SpirographError::ExportError(
+            "Pattern not generated. Call generate() first.".to_string(),
+        ));
+    }
+    Ok(lines)
+}
+
+/// A layer's generated polylines with `f` applied to every point
+fn transform_generated<L: PatternLayer + ?Sized>(
+    layer: &L,
+    f: impl Fn(Point2D) -> Point2D,
+) -> Result<Vec<Vec<Point2D>>, SpirographError> {
+    Ok(generated_polylines(layer)?
+        .iter()
+        .map(|line| line.iter().map(|&p| f(p)).collect())
+        .collect())
+}
+
+/// A layer as a `GuillochePattern` stores it
+///
+/// Lets the pattern's generic passes (generation, validation,
+/// post-processing, scaling, point budgets, drawing, SVG metadata and
+/// centering) walk every kind of layer in one loop. The kind, cloning and
+/// serialization come from the layer's
+/// [`LayerType`](crate::guilloche::LayerType) impl.
+pub(crate) trait StoredLayer:
+    PatternLayer + TaggedLayer + std::fmt::Debug + Send + Sync + Any
+{
+    /// Run the post-processing callbacks over the generated lines
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize);
+
+    /// Drop the generated lines
+    fn clear(&mut self);
+
+    /// Multiply every length of the layer by `factor`
+    fn scale_lengths(&mut self, factor: f64);
+
+    /// Simplify the generated lines with [`simplify_polyline`]
+    fn simplify(&mut self, tolerance: f64);
+
+    /// `validate` for lengths in units of `mm_per_unit` millimeters
+    fn validate_scaled(&self, _mm_per_unit: f64) -> Result<(), SpirographError> {
+        self.validate()
+    }
+
+    /// The configuration in `Debug` style, or empty for layers without one
+    fn config_summary(&self) -> String {
+        String::new()
+    }
+
+    /// The layer's center, for kinds placed by one
+    fn center(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    /// Move the center; takes effect on the next `generate()`, and does
+    /// nothing for kinds without one
+    fn set_center(&mut self, _x: f64, _y: f64) {}
+
+    /// The layer as a budgeted generation can thin it, or `None` for
+    /// kinds kept at full detail
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        None
+    }
+
+    /// Stroke width of the layer's lines in the combined SVG
+    fn stroke_width(&self) -> f64 {
+        0.03
+    }
+}
+
+/// Fast hash of polyline geometry for change detection
+///
+/// Coordinates are rounded to a nanometre grid before hashing, so
+/// floating-point noise far below anything a cutter can resolve does not
+/// register as a change. Equal hashes mean a preview can be reused; the
+/// hash is not stable across releases and should not be persisted.
+pub fn geometry_hash<'a, I>(lines: I) -> u64
+where
+    I: IntoIterator<Item = &'a [Point2D]>,
+{
+    use std::hash::{Hash, Hasher};
+
+    let quantize = |v: f64| (v * 1e6).round() as i64;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for line in lines {
+        line.len().hash(&mut hasher);
+        for point in line {
+            quantize(point.x).hash(&mut hasher);
+            quantize(point.y).hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Small seeded PRNG (SplitMix64) for reproducible random choices
+///
+/// The stream depends only on the seed, so a seed recorded with a design
+/// reproduces it exactly. Not suitable for anything security related.
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        SplitMix64(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1), from the top 53 bits
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in [lo, hi)
+    pub(crate) fn range(&mut self, lo: f64, hi: f64) -> f64 {
+        lo + (hi - lo) * self.next_f64()
+    }
+
+    /// Uniform index below `n`, which must be positive
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// True if a sampled curve returns to its starting point
+///
+/// Generated curves repeat their start as the last point, but thinned or
+/// post-processed lines may stop a step short of it. The curve is treated
+/// as closed when the gap from
+/// the last point back to the first is no longer than 1.5× the longest
+/// sampled step; anything larger would draw a visible chord when closed.
+pub fn polyline_closes(points: &[Point2D]) -> bool {
+    if points.len() < 3 {
+        return false;
+    }
+    let dist = |a: &Point2D, b: &Point2D| (b.x - a.x).hypot(b.y - a.y);
+    let max_step = points
+        .windows(2)
+        .map(|w| dist(&w[0], &w[1]))
+        .fold(0.0, f64::max);
+    dist(&points[points.len() - 1], &points[0]) <= 1.5 * max_step
+}
+
+/// Parameters t ∈ [0, 1] where segment a→b crosses a circle, ascending
+pub(crate) fn segment_circle_crossings(
+    a: Point2D,
+    b: Point2D,
+    center: Point2D,
+    radius: f64,
+) -> Vec<f64> {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+    let fx = a.x - center.x;
+    let fy = a.y - center.y;
+    let qa = dx * dx + dy * dy;
+    if qa < 1e-24 {
+        return Vec::new();
+    }
+    let qb = 2.0 * (fx * dx + fy * dy);
+    let qc = fx * fx + fy * fy - radius * radius;
+    let disc = qb * qb - 4.0 * qa * qc;
+    if disc < 0.0 {
+        return Vec::new();
+    }
+    let sq = disc.sqrt();
+    [(-qb - sq) / (2.0 * qa), (-qb + sq) / (2.0 * qa)]
+        .into_iter()
+        .filter(|t| (0.0..=1.0).contains(t))
+        .collect()
+}
+
+/// Clip a polyline to a circle, splitting it where it crosses the boundary
+///
+/// Returns the pieces inside the circle; crossing points are computed
+/// exactly on the circle. When `closed` is true the segment from the last
+/// point back to the first is included and a piece running through the
+/// start is joined into one.
+pub fn clip_polyline_to_circle(
+    points: &[Point2D],
+    center: Point2D,
+    radius: f64,
+    closed: bool,
+) -> Vec<Vec<Point2D>> {
+    if points.is_empty() {
+        return Vec::new();
+    }
+    let inside = |p: &Point2D| (p.x - center.x).hypot(p.y - center.y) <= radius;
+    let lerp =
+        |a: Point2D, b: Point2D, t: f64| Point2D::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t);
+
+    let mut pieces: Vec<Vec<Point2D>> = Vec::new();
+    let mut current: Vec<Point2D> = Vec::new();
+    if inside(&points[0]) {
+        current.push(points[0]);
+    }
+
+    let segment_count = if closed {
+        points.len()
+    } else {
+        points.len() - 1
+    };
+    for i in 0..segment_count {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        match (inside(&a), inside(&b)) {
+            (true, true) => current.push(b),
+            (true, false) => {
+                if let Some(&t) = segment_circle_crossings(a, b, center, radius).last() {
+                    current.push(lerp(a, b, t));
+                }
+                pieces.push(std::mem::take(&mut current));
+            }
+            (false, true) => {
+                if let Some(&t) = segment_circle_crossings(a, b, center, radius).first() {
+                    current.push(lerp(a, b, t));
+                }
+                current.push(b);
+            }
+            (false, false) => {
+                let ts = segment_circle_crossings(a, b, center, radius);
+                if ts.len() == 2 {
+                    pieces.push(vec![lerp(a, b, ts[0]), lerp(a, b, ts[1])]);
+                }
+            }
+        }
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+
+    // A closed curve that starts inside has its first piece cut in two at
+    // the start point; rejoin them.
+    if closed && inside(&points[0]) && pieces.len() > 1 {
+        let last = pieces.pop().unwrap_or_default();
+        let first = std::mem::take(&mut pieces[0]);
+        pieces[0] = last;
+        pieces[0].extend(first.into_iter().skip(1));
+    }
+
+    pieces.retain(|piece| piece.len() >= 2);
+    pieces
+}
+
+/// Outline of a dial, centered on the origin
+///
+/// Patterns are clipped to it in the watch face exports, and the dial,
+/// bezel and base slab follow it. The watch face radius is the larger
+/// semi-axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DialShape {
+    /// A round dial
+    Circle { radius: f64 },
+    /// An oval dial with semi-axes `rx` along x and `ry` along y
+    Ellipse { rx: f64, ry: f64 },
+}
+
+impl DialShape {
+    /// Semi-axes along x and y
+    pub fn semi_axes(&self) -> (f64, f64) {
+        match *self {
+            DialShape::Circle { radius } => (radius, radius),
+            DialShape::Ellipse { rx, ry } => (rx, ry),
+        }
+    }
+
+    /// The larger semi-axis, i.e. the farthest the outline reaches
+    pub fn max_semi_axis(&self) -> f64 {
+        let (rx, ry) = self.semi_axes();
+        rx.max(ry)
+    }
+
+    /// Check that the semi-axes are positive and finite
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        let (rx, ry) = self.semi_axes();
+        if !(rx.is_finite() && ry.is_finite() && rx > 0.0 && ry > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "dial semi-axes must be positive and finite".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// True if `p` lies on or inside the outline
+    pub fn contains(&self, p: Point2D) -> bool {
+        let q = self.unstretch(p);
+        q.x.hypot(q.y) <= 1.0
+    }
+
+    /// The pieces of a polyline inside the outline
+    ///
+    /// As [`clip_polyline_to_circle`]: crossings are computed exactly on
+    /// the outline and a closed curve running through its start is joined.
+    pub fn clip_polyline(&self, points: &[Point2D], closed: bool) -> Vec<Vec<Point2D>> {
+        match *self {
+            DialShape::Circle { radius } => {
+                clip_polyline_to_circle(points, Point2D::new(0.0, 0.0), radius, closed)
+            }
+            DialShape::Ellipse { .. } => {
+                // Clip the unit circle in the ellipse's stretched frame;
+                // the stretch maps segments to segments, keeping crossings
+                let unit: Vec<Point2D> = points.iter().map(|&p| self.unstretch(p)).collect();
+                clip_polyline_to_circle(&unit, Point2D::new(0.0, 0.0), 1.0, closed)
+                    .into_iter()
+                    .map(|piece| piece.into_iter().map(|p| self.stretch(p)).collect())
+                    .collect()
+            }
+        }
+    }
+
+    /// The outline shrunk about its center so that everything within
+    /// `distance` of the result stays inside this one
+    pub(crate) fn inset(&self, distance: f64) -> DialShape {
+        let (rx, ry) = self.semi_axes();
+        let mut shape = *self;
+        shape.scale_lengths((1.0 - distance / rx.min(ry)).max(0.0));
+        shape
+    }
+
+    /// `segments` points on the outline, counter-clockwise from the +x
+    /// axis, not repeating the first
+    pub fn outline(&self, segments: usize) -> Vec<Point2D> {
+        let (rx, ry) = self.semi_axes();
+        (0..segments)
+            .map(|i| {
+                let angle = 2.0 * PI * i as f64 / segments as f64;
+                Point2D::new(rx * angle.cos(), ry * angle.sin())
+            })
+            .collect()
+    }
+
+    /// Multiply both semi-axes by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        match self {
+            DialShape::Circle { radius } => *radius *= factor,
+            DialShape::Ellipse { rx, ry } => {
+                *rx *= factor;
+                *ry *= factor;
+            }
+        }
+    }
+
+    /// The outline grown by `factor` as an SVG circle or ellipse element
+    pub(crate) fn svg_element(&self, factor: f64) -> svg::node::element::Element {
+        use svg::Node;
+
+        let mut element = match *self {
+            DialShape::Circle { radius } => {
+                let mut circle = svg::node::element::Element::new("circle");
+                circle.assign("r", radius * factor);
+                circle
+            }
+            DialShape::Ellipse { rx, ry } => {
+                let mut ellipse = svg::node::element::Element::new("ellipse");
+                ellipse.assign("rx", rx * factor);
+                ellipse.assign("ry", ry * factor);
+                ellipse
+            }
+        };
+        element.assign("cx", 0);
+        element.assign("cy", 0);
+        element
+    }
+
+    /// Base slab following the outline, with apertures, for STL exports
+    ///
+    /// See [`pierced_slab_triangles`]; the slab grows with the same
+    /// aspect ratio to cover any of `points` past the outline.
+    pub(crate) fn slab_triangles(
+        &self,
+        points: impl IntoIterator<Item = Point2D>,
+        z_top: f64,
+        thickness: f64,
+        apertures: &[Vec<Point2D>],
+    ) -> Vec<stl_io::Triangle> {
+        let origin = Point2D::new(0.0, 0.0);
+        match *self {
+            DialShape::Circle { radius } => {
+                pierced_slab_triangles(origin, points, radius, z_top, thickness, apertures)
+            }
+            DialShape::Ellipse { rx, ry } => {
+                // Build a unit disc in the stretched frame and stretch it back
+                let apertures: Vec<Vec<Point2D>> = apertures
+                    .iter()
+                    .map(|aperture| aperture.iter().map(|&p| self.unstretch(p)).collect())
+                    .collect();
+                let points = points.into_iter().map(|p| self.unstretch(p));
+                let mut triangles =
+                    pierced_slab_triangles(origin, points, 1.0, z_top, thickness, &apertures);
+                for triangle in &mut triangles {
+                    for vertex in &mut triangle.vertices {
+                        *vertex = stl_io::Vertex::new([
+                            vertex[0] * rx as f32,
+                            vertex[1] * ry as f32,
+                            vertex[2],
+                        ]);
+                    }
+                    // Normals transform by the inverse stretch
+                    let [nx, ny, nz] = [
+                        triangle.normal[0] / rx as f32,
+                        triangle.normal[1] / ry as f32,
+                        triangle.normal[2],
+                    ];
+                    let length = (nx * nx + ny * ny + nz * nz).sqrt();
+                    triangle.normal = stl_io::Normal::new([nx / length, ny / length, nz / length]);
+                }
+                triangles
+            }
+        }
+    }
+
+    /// Map a point into the frame where the outline is the unit circle
+    fn unstretch(&self, p: Point2D) -> Point2D {
+        let (rx, ry) = self.semi_axes();
+        Point2D::new(p.x / rx, p.y / ry)
+    }
+
+    /// Inverse of `unstretch`
+    fn stretch(&self, p: Point2D) -> Point2D {
+        let (rx, ry) = self.semi_axes();
+        Point2D::new(p.x * rx, p.y * ry)
+    }
+}
+
+/// Convert polar coordinates (angle, distance) to cartesian (x, y)
+pub fn polar_to_cartesian(angle: f64, distance: f64) -> (f64, f64) {
+    (distance * angle.cos(), distance * angle.sin())
+}
+
+/// A 2D point
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Point2D {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point2D {
+    pub fn new(x: f64, y: f64) -> Self {
+        Point2D { x, y }
+    }
+
+    /// True if both coordinates are finite (not NaN or infinite)
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+}
+
+/// A 3D point (for spherical spirographs)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Point3D {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Point3D { x, y, z }
+    }
+
+    /// True if all coordinates are finite (not NaN or infinite)
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+}
+
+/// Phase that puts a crest of `sin(frequency · (θ + phase))` at 12 o'clock
+///
+/// 12 o'clock is θ = −π/2 in screen coordinates, so the phase solves
+/// `frequency · (−π/2 + phase) = π/2`. Used with `RadialWave::with_phase`
+/// and `RoseEngineConfig::phase`, which both add the phase to θ.
+///
+/// # Example
+/// ```
+/// use std::f64::consts::PI;
+/// use turtles::peak_alignment_phase;
+///
+/// let phase = peak_alignment_phase(11.5);
+/// assert!(((11.5 * (-PI / 2.0 + phase)).sin() - 1.0).abs() < 1e-12);
+/// ```
+pub fn peak_alignment_phase(frequency: f64) -> f64 {
+    PI / 2.0 + PI / (2.0 * frequency)
+}
+
+/// Cosines and sines of the angles θⱼ = 2π·j / resolution, one turn of
+/// uniformly spaced samples
+///
+/// Rings traced at the same resolution all visit the same angles; sharing
+/// one table between them saves a `cos` and a `sin` per point. Entries are
+/// computed exactly as the direct formula would, so sample `j < resolution`
+/// matches `θⱼ.cos()` bit for bit; later turns reuse the first turn's
+/// entries and so agree to within rounding.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TrigTable {
+    cos_sin: Vec<(f64, f64)>,
+}
+
+impl TrigTable {
+    /// Table for `resolution` samples per turn (at least 1)
+    pub(crate) fn new(resolution: usize) -> Self {
+        let resolution = resolution.max(1);
+        TrigTable {
+            cos_sin: (0..resolution)
+                .map(|j| {
+                    let theta = 2.0 * PI * (j as f64) / (resolution as f64);
+                    (theta.cos(), theta.sin())
+                })
+                .collect(),
+        }
+    }
+
+    /// Samples per turn
+    pub(crate) fn resolution(&self) -> usize {
+        self.cos_sin.len()
+    }
+
+    /// `(cos θⱼ, sin θⱼ)`, for sample `j` of any turn
+    pub(crate) fn cos_sin(&self, j: usize) -> (f64, f64) {
+        self.cos_sin[j % self.cos_sin.len()]
+    }
+}
+
+/// A radial wave: `radius = base_radius + amplitude · shape(pattern(θ + phase))`
+///
+/// This is the ring equation shared by the rose engine tool path and the
+/// mathematical layers that mirror it (draperie, flinqué), so both sides
+/// trace their rings through the same arithmetic.
+///
+/// `shape` raises the rosette displacement to `wave_exponent` while keeping
+/// its sign: 1 leaves the pattern unchanged, higher values soften the
+/// crests (`|d|^e · sign(d)`). The phase is used as given; callers that
+/// want wrapped phases normalize before building the wave.
+///
+/// # Example
+/// ```
+/// use turtles::{Point2D, RadialWave, RosettePattern};
+///
+/// let wave = RadialWave::new(20.0, 1.0, RosettePattern::Sinusoidal { frequency: 4.0 });
+/// assert!((wave.radius_at(std::f64::consts::PI / 8.0) - 21.0).abs() < 1e-12);
+///
+/// let ring = wave.sample_ring(360, Point2D::new(0.0, 0.0));
+/// assert_eq!(ring.len(), 361);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialWave {
+    /// Radius the wave oscillates about
+    pub base_radius: f64,
+    /// Peak radial displacement
+    pub amplitude: f64,
+    /// Rosette providing the displacement shape
+    pub pattern: RosettePattern,
+    /// Angular offset in radians added to θ before evaluating the pattern
+    pub phase: f64,
+    /// Exponent applied to the displacement (1 = unchanged)
+    pub wave_exponent: u32,
+}
+
+impl RadialWave {
+    /// Create a wave with zero phase and `wave_exponent` 1
+    pub fn new(base_radius: f64, amplitude: f64, pattern: RosettePattern) -> Self {
+        RadialWave {
+            base_radius,
+            amplitude,
+            pattern,
+            phase: 0.0,
+            wave_exponent: 1,
+        }
+    }
+
+    /// Set the phase offset in radians
+    pub fn with_phase(mut self, phase: f64) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    /// Set the wave exponent
+    pub fn with_wave_exponent(mut self, wave_exponent: u32) -> Self {
+        self.wave_exponent = wave_exponent;
+        self
+    }
+
+    /// Shaped displacement at angle `theta`, before scaling by amplitude
+    pub fn displacement_at(&self, theta: f64) -> f64 {
+        let d = self.pattern.displacement(theta + self.phase);
+        if self.wave_exponent == 1 {
+            d
+        } else {
+            d.abs().powi(self.wave_exponent as i32) * d.signum()
+        }
+    }
+
+    /// Radius at angle `theta` in radians
+    ///
+    /// A zero amplitude gives exactly `base_radius`, whatever the pattern
+    /// and phase, so flat rings are perfect circles.
+    pub fn radius_at(&self, theta: f64) -> f64 {
+        if self.amplitude == 0.0 {
+            return self.base_radius;
+        }
+        self.base_radius + self.amplitude * self.displacement_at(theta)
+    }
+
+    /// Sample a closed ring of `resolution + 1` points around `center`
+    ///
+    /// Point `j` lies at θ = 2π·j / resolution, so the last point repeats
+    /// the first.
+    pub fn sample_ring(&self, resolution: usize, center: Point2D) -> Vec<Point2D> {
+        Self::sample_sum(std::slice::from_ref(self), resolution, center)
+    }
+
+    /// Sample `revolutions` turns of the wave, `resolution` points per turn
+    ///
+    /// Point `j` lies at θ = 2π·j / resolution as in `sample_ring`, which
+    /// this matches for one revolution.
+    pub fn sample_revolutions(
+        &self,
+        resolution: usize,
+        revolutions: usize,
+        center: Point2D,
+    ) -> Vec<Point2D> {
+        self.sample_revolutions_with(&TrigTable::new(resolution), revolutions, center)
+    }
+
+    /// `sample_revolutions` at the table's resolution, reusing its angles
+    pub(crate) fn sample_revolutions_with(
+        &self,
+        table: &TrigTable,
+        revolutions: usize,
+        center: Point2D,
+    ) -> Vec<Point2D> {
+        let resolution = table.resolution();
+        (0..=resolution * revolutions)
+            .map(|j| {
+                let theta = 2.0 * PI * (j as f64) / (resolution as f64);
+                let r = self.radius_at(theta);
+                let (cos, sin) = table.cos_sin(j);
+                Point2D::new(center.x + r * cos, center.y + r * sin)
+            })
+            .collect()
+    }
+
+    /// Sample a closed ring whose radius is the sum of several waves
+    ///
+    /// Used for compound motion and ripple add-ons; give the extra waves a
+    /// zero `base_radius` so only the first wave sets the ring size.
+    pub fn sample_sum(waves: &[RadialWave], resolution: usize, center: Point2D) -> Vec<Point2D> {
+        Self::sample_sum_with(waves, &TrigTable::new(resolution), center)
+    }
+
+    /// `sample_sum` at the table's resolution, reusing its angles
+    pub(crate) fn sample_sum_with(
+        waves: &[RadialWave],
+        table: &TrigTable,
+        center: Point2D,
+    ) -> Vec<Point2D> {
+        let resolution = table.resolution();
+        (0..=resolution)
+            .map(|j| {
+                let theta = 2.0 * PI * (j as f64) / (resolution as f64);
+                let r: f64 = waves.iter().map(|w| w.radius_at(theta)).sum();
+                let (cos, sin) = table.cos_sin(j);
+                Point2D::new(center.x + r * cos, center.y + r * sin)
+            })
+            .collect()
+    }
+}
+
+/// Dotted ("pointillé") cutting of a rosette pass by its phase
+///
+/// The groove is kept where `fract(φ · dots_per_cycle / 2π) < duty`, with
+/// φ the rosette's angular phase (spindle angle plus the pass's phase), so
+/// every revolution holds `dots_per_cycle` dots locked to the wave rather
+/// than spaced by path length.
+///
+/// # Example
+/// ```
+/// use turtles::{DashSpec, Point2D};
+///
+/// let ring: Vec<Point2D> = (0..=360)
+///     .map(|j| {
+///         let t = (j as f64).to_radians();
+///         Point2D::new(10.0 * t.cos(), 10.0 * t.sin())
+///     })
+///     .collect();
+/// let dots = DashSpec::new(12, 0.5).split(&ring, Point2D::new(0.0, 0.0), 0.0);
+/// assert_eq!(dots.len(), 12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DashSpec {
+    /// Number of dots per revolution of the rosette phase
+    pub dots_per_cycle: usize,
+    /// Fraction of each dot period that is cut, in (0, 1]
+    pub duty: f64,
+}
+
+impl DashSpec {
+    /// Create a dash spec
+    pub fn new(dots_per_cycle: usize, duty: f64) -> Self {
+        DashSpec {
+            dots_per_cycle,
+            duty,
+        }
+    }
+
+    /// Check for at least one dot per cycle and a duty in (0, 1]
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.dots_per_cycle == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "dots_per_cycle must be at least 1".to_string(),
+            ));
+        }
+        if !(self.duty > 0.0 && self.duty <= 1.0) {
+            return Err(SpirographError::InvalidParameter(
+                "duty must be within (0, 1]".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Split a pass into its kept dots
+    ///
+    /// The phase at each point is its polar angle about `center` plus
+    /// `phase`, unwrapped along the line. Dot ends are interpolated to the
+    /// exact phase threshold. A closed line whose start falls inside a dot
+    /// has that dot rejoined across the start.
+    pub fn split(&self, line: &[Point2D], center: Point2D, phase: f64) -> Vec<Vec<Point2D>> {
+        if line.len() < 2 {
+            return Vec::new();
+        }
+        let cycles = self.dots_per_cycle as f64 / (2.0 * PI);
+        let kept = |u: f64| u - u.floor() < self.duty;
+        let lerp = |a: Point2D, b: Point2D, t: f64| {
+            Point2D::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+        };
+
+        // Dot-period coordinate of each point, unwrapped along the line
+        let angle = |p: &Point2D| (p.y - center.y).atan2(p.x - center.x);
+        let mut u = Vec::with_capacity(line.len());
+        let mut theta = angle(&line[0]);
+        u.push((theta + phase) * cycles);
+        for w in line.windows(2) {
+            let next = angle(&w[1]);
+            theta += normalize_angle(next - theta + PI) - PI;
+            u.push((theta + phase) * cycles);
+        }
+
+        let mut dots: Vec<Vec<Point2D>> = Vec::new();
+        let mut current: Vec<Point2D> = Vec::new();
+        for i in 0..line.len() - 1 {
+            let (a, b) = (line[i], line[i + 1]);
+            let (u0, u1) = (u[i], u[i + 1]);
+            let (lo, hi) = (u0.min(u1), u0.max(u1));
+
+            // Threshold crossings inside the segment, in order along it
+            let mut ts = vec![0.0];
+            let mut n = lo.floor();
+            while n <= hi {
+                for edge in [n, n + self.duty] {
+                    if edge > lo && edge < hi {
+                        ts.push((edge - u0) / (u1 - u0));
+                    }
+                }
+                n += 1.0;
+            }
+            ts.push(1.0);
+            ts.sort_by(f64::total_cmp);
+
+            for span in ts.windows(2) {
+                let (t0, t1) = (span[0], span[1]);
+                if t1 <= t0 {
+                    continue;
+                }
+                if kept(u0 + (u1 - u0) * (t0 + t1) / 2.0) {
+                    if current.is_empty() {
+                        current.push(lerp(a, b, t0));
+                    }
+                    current.push(lerp(a, b, t1));
+                } else if !current.is_empty() {
+                    dots.push(std::mem::take(&mut current));
+                }
+            }
+        }
+        let runs_to_end = !current.is_empty();
+        if runs_to_end {
+            dots.push(current);
+        }
+
+        let first = line[0];
+        let last = line[line.len() - 1];
+        let closed = (last.x - first.x).hypot(last.y - first.y) <= DEDUPE_EPSILON;
+        if closed && runs_to_end && dots.len() > 1 && dots[0][0] == first {
+            let tail = dots.pop().unwrap_or_default();
+            let head = std::mem::replace(&mut dots[0], tail);
+            dots[0].extend(head.into_iter().skip(1));
+        }
+        dots
+    }
+}
+
+/// Seeded hand-made imperfection for generated lines
+///
+/// Each line is displaced about its layer's center by a smooth, low
+/// frequency wobble: a radial offset of `radial_sigma` mm and a turn of
+/// `angular_sigma` radians, both root-mean-square over the line. The
+/// wobble is a sum of a few harmonics over the whole line with Gaussian
+/// weights drawn from `seed` and the line's index, so the same seed gives
+/// the same lines on every run, and a closed line stays closed.
+///
+/// # Example
+/// ```
+/// use turtles::{JitterConfig, Point2D};
+///
+/// let jitter = JitterConfig::new(0.05, 0.002, 7);
+/// let ring: Vec<Point2D> = (0..=360)
+///     .map(|i| {
+///         let theta = (i as f64).to_radians();
+///         Point2D::new(10.0 * theta.cos(), 10.0 * theta.sin())
+///     })
+///     .collect();
+/// let mut wobbly = ring.clone();
+/// jitter.apply(0, &mut wobbly, Point2D::new(0.0, 0.0));
+/// assert_ne!(wobbly, ring);
+/// // Still closed
+/// let (first, last) = (wobbly[0], wobbly[360]);
+/// assert!((first.x - last.x).hypot(first.y - last.y) < 1e-9);
+///
+/// let mut again = ring.clone();
+/// jitter.apply(0, &mut again, Point2D::new(0.0, 0.0));
+/// assert_eq!(again, wobbly);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct JitterConfig {
+    /// RMS radial offset (mm)
+    pub radial_sigma: f64,
+    /// RMS angular offset (radians)
+    pub angular_sigma: f64,
+    /// Seed of the offsets
+    pub seed: u64,
+}
+
+/// Harmonics summed for each jitter wobble
+const JITTER_HARMONICS: usize = 4;
+
+impl JitterConfig {
+    /// Create a jitter config
+    pub fn new(radial_sigma: f64, angular_sigma: f64, seed: u64) -> Self {
+        JitterConfig {
+            radial_sigma,
+            angular_sigma,
+            seed,
+        }
+    }
+
+    /// Check that both sigmas are finite and non-negative
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        for (name, sigma) in [
+            ("radial_sigma", self.radial_sigma),
+            ("angular_sigma", self.angular_sigma),
+        ] {
+            if !(sigma.is_finite() && sigma >= 0.0) {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "jitter {} must be non-negative",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The wobble of line `index`
+    pub(crate) fn line(&self, index: usize) -> LineJitter {
+        // Hash the index so neighbouring lines draw unrelated streams
+        let mut rng = SplitMix64::new(SplitMix64::new(index as u64).next_u64() ^ self.seed);
+        LineJitter {
+            radial: Wobble::draw(&mut rng, self.radial_sigma),
+            angular: Wobble::draw(&mut rng, self.angular_sigma),
+        }
+    }
+
+    /// Displace the points of line `index` about `center`
+    ///
+    /// The wobble runs once over the line, from its first point to its
+    /// last, whatever its length.
+    pub fn apply(&self, index: usize, points: &mut [Point2D], center: Point2D) {
+        let line = self.line(index);
+        let last = points.len().saturating_sub(1).max(1) as f64;
+        for (i, point) in points.iter_mut().enumerate() {
+            *point = line.displace(i as f64 / last, *point, center);
+        }
+    }
+}
+
+/// Radial and angular wobble of one line, over a parameter in [0, 1]
+pub(crate) struct LineJitter {
+    radial: Wobble,
+    angular: Wobble,
+}
+
+impl LineJitter {
+    /// Radial (mm) and angular (radians) offsets at `t`
+    pub(crate) fn offsets(&self, t: f64) -> (f64, f64) {
+        (self.radial.at(t), self.angular.at(t))
+    }
+
+    /// `point` moved out from `center` and turned about it by the offsets
+    /// at `t`; the radius never goes below 0
+    pub(crate) fn displace(&self, t: f64, point: Point2D, center: Point2D) -> Point2D {
+        let (dr, dtheta) = self.offsets(t);
+        let (dx, dy) = (point.x - center.x, point.y - center.y);
+        let r = dx.hypot(dy);
+        let theta = dy.atan2(dx) + dtheta;
+        let r = (r + dr).max(0.0);
+        Point2D::new(center.x + r * theta.cos(), center.y + r * theta.sin())
+    }
+}
+
+/// A periodic sum of harmonics with unit-RMS Gaussian weights, scaled
+/// to `sigma`
+struct Wobble {
+    /// Amplitude and phase of harmonic `k + 1`
+    harmonics: [(f64, f64); JITTER_HARMONICS],
+}
+
+impl Wobble {
+    fn draw(rng: &mut SplitMix64, sigma: f64) -> Self {
+        let mut harmonics = [(0.0, 0.0); JITTER_HARMONICS];
+        for (k, harmonic) in harmonics.iter_mut().enumerate() {
+            // Box-Muller; weights fall off as 1/k for a low-frequency wobble
+            let u = 1.0 - rng.next_f64();
+            let gauss = (-2.0 * u.ln()).sqrt() * (2.0 * PI * rng.next_f64()).cos();
+            *harmonic = (gauss / (k + 1) as f64, rng.range(0.0, 2.0 * PI));
+        }
+        // A sine of amplitude a has RMS a/√2
+        let rms = (harmonics.iter().map(|(a, _)| a * a).sum::<f64>() / 2.0).sqrt();
+        let scale = if rms > 0.0 { sigma / rms } else { 0.0 };
+        for harmonic in &mut harmonics {
+            harmonic.0 *= scale;
+        }
+        Wobble { harmonics }
+    }
+
+    fn at(&self, t: f64) -> f64 {
+        self.harmonics
+            .iter()
+            .enumerate()
+            .map(|(k, (a, phase))| a * (2.0 * PI * (k + 1) as f64 * t + phase).sin())
+            .sum()
+    }
+}
+
+/// A weight in [0, 1] that varies smoothly with dial angle and radius
+///
+/// Used to fade something over part of the dial: a rosette's amplitude
+/// (`RoseEngineConfig::amplitude_envelope`) or a layer's SVG opacity.
+/// Angles are in radians, measured like the generators: 0 along +x and
+/// increasing toward +y, so in SVG coordinates 12 o'clock is 3π/2 and
+/// 6 o'clock is π/2. Radii are in the design unit. `eval` clamps the
+/// result to [0, 1].
+///
+/// # Example
+/// ```
+/// use std::f64::consts::PI;
+/// use turtles::Envelope;
+///
+/// // The upper half of the dial, feathered over 0.2 rad at each end,
+/// // and only between 10 and 18 mm
+/// let envelope = Envelope::angular(0.0, PI, 0.2) * Envelope::radial(10.0, 18.0, 1.0);
+/// assert_eq!(envelope.eval(PI / 2.0, 14.0), 1.0);
+/// assert_eq!(envelope.eval(-PI / 2.0, 14.0), 0.0);
+/// assert_eq!(envelope.eval(PI / 2.0, 20.0), 0.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Envelope {
+    /// The same weight everywhere
+    Constant(f64),
+    /// 1 from `start_angle` to `end_angle` in the direction of increasing
+    /// angle (wrapping through 0), 0 outside, with a smoothstep over
+    /// `feather` radians inside each end
+    AngularSmoothstep {
+        start_angle: f64,
+        end_angle: f64,
+        feather: f64,
+    },
+    /// 1 between radii `r_start` and `r_end`, 0 outside, with a smoothstep
+    /// over `feather` inside each end
+    RadialSmoothstep {
+        r_start: f64,
+        r_end: f64,
+        feather: f64,
+    },
+    /// The product of two envelopes
+    Product(Box<Envelope>, Box<Envelope>),
+    /// Weights at evenly spaced angles from 0 over one revolution,
+    /// interpolated linearly and wrapping from the last back to the first
+    Table { samples: Vec<f64> },
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Envelope::Constant(1.0)
+    }
+}
+
+impl Envelope {
+    /// An envelope of `value` everywhere
+    pub fn constant(value: f64) -> Self {
+        Envelope::Constant(value)
+    }
+
+    /// An angular window; see [`Envelope::AngularSmoothstep`]
+    pub fn angular(start_angle: f64, end_angle: f64, feather: f64) -> Self {
+        Envelope::AngularSmoothstep {
+            start_angle,
+            end_angle,
+            feather,
+        }
+    }
+
+    /// A radial band; see [`Envelope::RadialSmoothstep`]
+    pub fn radial(r_start: f64, r_end: f64, feather: f64) -> Self {
+        Envelope::RadialSmoothstep {
+            r_start,
+            r_end,
+            feather,
+        }
+    }
+
+    /// A table of weights around the revolution; see [`Envelope::Table`]
+    pub fn table(samples: Vec<f64>) -> Self {
+        Envelope::Table { samples }
+    }
+
+    /// This envelope multiplied by `other`
+    pub fn product(self, other: Envelope) -> Self {
+        Envelope::Product(Box::new(self), Box::new(other))
+    }
+
+    /// Check that every value, angle and radius is finite, feathers are
+    /// non-negative, radial bands are not inverted and tables are not empty
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        let invalid = |message: &str| Err(SpirographError::InvalidParameter(message.to_string()));
+        match self {
+            Envelope::Constant(value) if !value.is_finite() => {
+                invalid("envelope constant must be finite")
+            }
+            Envelope::AngularSmoothstep {
+                start_angle,
+                end_angle,
+                feather,
+            } => {
+                if !(start_angle.is_finite() && end_angle.is_finite()) {
+                    invalid("envelope angles must be finite")
+                } else if !(feather.is_finite() && *feather >= 0.0) {
+                    invalid("envelope feather must be non-negative")
+                } else {
+                    Ok(())
+                }
+            }
+            Envelope::RadialSmoothstep {
+                r_start,
+                r_end,
+                feather,
+            } => {
+                if !(r_start.is_finite() && r_end.is_finite() && r_start <= r_end) {
+                    invalid("envelope radii must be finite with r_start <= r_end")
+                } else if !(feather.is_finite() && *feather >= 0.0) {
+                    invalid("envelope feather must be non-negative")
+                } else {
+                    Ok(())
+                }
+            }
+            Envelope::Product(a, b) => a.validate().and_then(|_| b.validate()),
+            Envelope::Table { samples } if samples.is_empty() => {
+                invalid("envelope table must have at least one sample")
+            }
+            Envelope::Table { samples } if samples.iter().any(|s| !s.is_finite()) => {
+                invalid("envelope table samples must be finite")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Weight at `angle` (radians) and `radius`, clamped to [0, 1]
+    pub fn eval(&self, angle: f64, radius: f64) -> f64 {
+        self.raw(angle, radius).clamp(0.0, 1.0)
+    }
+
+    /// Weight at a polyline's mean angle and mean radius about `center`
+    ///
+    /// The mean angle is the direction of the summed unit vectors, so it
+    /// is arbitrary for a line that goes all the way round; give such
+    /// lines radial envelopes.
+    pub fn eval_polyline(&self, points: &[Point2D], center: Point2D) -> f64 {
+        if points.is_empty() {
+            return self.eval(0.0, 0.0);
+        }
+        let (mut sin_sum, mut cos_sum, mut r_sum) = (0.0, 0.0, 0.0);
+        for p in points {
+            let (dx, dy) = (p.x - center.x, p.y - center.y);
+            let angle = dy.atan2(dx);
+            sin_sum += angle.sin();
+            cos_sum += angle.cos();
+            r_sum += dx.hypot(dy);
+        }
+        self.eval(sin_sum.atan2(cos_sum), r_sum / points.len() as f64)
+    }
+
+    fn raw(&self, angle: f64, radius: f64) -> f64 {
+        match self {
+            Envelope::Constant(value) => *value,
+            Envelope::AngularSmoothstep {
+                start_angle,
+                end_angle,
+                feather,
+            } => {
+                let span = if end_angle - start_angle >= 2.0 * PI {
+                    2.0 * PI
+                } else {
+                    normalize_angle(end_angle - start_angle)
+                };
+                let offset = normalize_angle(angle - start_angle);
+                if offset > span {
+                    0.0
+                } else {
+                    feathered(offset, *feather) * feathered(span - offset, *feather)
+                }
+            }
+            Envelope::RadialSmoothstep {
+                r_start,
+                r_end,
+                feather,
+            } => {
+                if radius < *r_start || radius > *r_end {
+                    0.0
+                } else {
+                    feathered(radius - r_start, *feather) * feathered(r_end - radius, *feather)
+                }
+            }
+            Envelope::Product(a, b) => a.eval(angle, radius) * b.eval(angle, radius),
+            Envelope::Table { samples } => {
+                if samples.is_empty() {
+                    return 1.0;
+                }
+                let n = samples.len();
+                let position = normalize_angle(angle) / (2.0 * PI) * n as f64;
+                let i = (position.floor() as usize).min(n - 1);
+                let t = position - i as f64;
+                samples[i] * (1.0 - t) + samples[(i + 1) % n] * t
+            }
+        }
+    }
+
+    /// Multiply every radius by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        match self {
+            Envelope::RadialSmoothstep {
+                r_start,
+                r_end,
+                feather,
+            } => {
+                *r_start *= factor;
+                *r_end *= factor;
+                *feather *= factor;
+            }
+            Envelope::Product(a, b) => {
+                a.scale_lengths(factor);
+                b.scale_lengths(factor);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl std::ops::Mul for Envelope {
+    type Output = Envelope;
+
+    fn mul(self, other: Envelope) -> Envelope {
+        self.product(other)
+    }
+}
+
+/// Smoothstep from 0 at `distance` 0 to 1 at `feather`; a zero feather
+/// is a hard edge
+fn feathered(distance: f64, feather: f64) -> f64 {
+    if feather <= 0.0 {
+        return 1.0;
+    }
+    let t = (distance / feather).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Unit of length for design dimensions and exported files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Unit {
+    #[default]
+    Millimeter,
+    Inch,
+}
+
+impl Unit {
+    /// Millimeters per inch
+    pub const MM_PER_INCH: f64 = 25.4;
+
+    /// Length of one unit in millimeters
+    pub fn mm_per_unit(self) -> f64 {
+        match self {
+            Unit::Millimeter => 1.0,
+            Unit::Inch => Self::MM_PER_INCH,
+        }
+    }
+
+    /// Factor converting a length in this unit to `to`
+    ///
+    /// Exactly 1.0 when the units match, so same-unit exports are untouched.
+    pub fn factor_to(self, to: Unit) -> f64 {
+        match (self, to) {
+            (Unit::Millimeter, Unit::Inch) => 1.0 / Self::MM_PER_INCH,
+            (Unit::Inch, Unit::Millimeter) => Self::MM_PER_INCH,
+            _ => 1.0,
+        }
+    }
+
+    /// Suffix used for SVG width/height attributes
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Unit::Millimeter => "mm",
+            Unit::Inch => "in",
+        }
+    }
+}
+
+/// Project-wide settings for a design
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectSettings {
+    /// Unit all design dimensions are expressed in
+    pub unit: Unit,
+    /// Radii the dial may have, checked in millimeters
+    #[serde(default)]
+    pub radius_policy: RadiusPolicy,
+}
+
+impl ProjectSettings {
+    /// Settings for a design in the given unit
+    pub fn new(unit: Unit) -> Self {
+        ProjectSettings {
+            unit,
+            radius_policy: RadiusPolicy::default(),
+        }
+    }
+
+    /// Accept dial radii by `policy` instead of the wristwatch range
+    pub fn with_radius_policy(mut self, policy: RadiusPolicy) -> Self {
+        self.radius_policy = policy;
+        self
+    }
+}
+
+/// Multiply every point of every polyline by `factor`
+pub(crate) fn scale_polylines(lines: &mut [Vec<Point2D>], factor: f64) {
+    for p in lines.iter_mut().flatten() {
+        p.x *= factor;
+        p.y *= factor;
+    }
+}
+
+/// Configuration for export formats
+///
+/// Lengths are in `unit`. Project-level exports (`WatchFace`) convert their
+/// geometry from the design unit to `unit`; single layers have no design
+/// unit and are written as-is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportConfig {
+    pub depth: f64,          // Groove/channel depth
+    pub base_thickness: f64, // STL base slab thickness under the grooves (0 = none)
+    pub tool_radius: f64,    // Flat STL groove half-width, or rose engine STL path offset
+    pub unit: Unit,          // Unit of the exported file
+    pub origin: OriginMode,  // Where (0, 0) lands in the exported file
+    #[serde(default)]
+    pub simplify_tolerance: f64, // Max deviation when simplifying SVG paths (0 = off)
+    #[serde(default)]
+    pub surface: ExportSurface, // Surface STL grooves are cut into
+    #[serde(default = "default_svg_precision")]
+    pub svg_precision: usize, // Decimal places of SVG coordinates
+}
+
+fn default_svg_precision() -> usize {
+    svg_writer::DEFAULT_SVG_PRECISION
+}
+
+/// Surface a 3D STL export cuts its grooves into
+///
+/// Only `SphericalSpirograph` builds a dome; the other exports are flat
+/// whatever is set here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExportSurface {
+    /// Grooves hang straight down from the curve into a flat slab
+    #[default]
+    Flat,
+    /// A solid spherical cap with `segments` facets around its axis, the
+    /// grooves following its normal
+    Dome { segments: usize },
+}
+
+impl ExportSurface {
+    /// Facets around a dome unless set otherwise
+    pub const DEFAULT_DOME_SEGMENTS: usize = 128;
+
+    /// A dome with the default facet count
+    pub fn dome() -> Self {
+        ExportSurface::Dome {
+            segments: Self::DEFAULT_DOME_SEGMENTS,
+        }
+    }
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        ExportConfig {
+            depth: 0.1,
+            base_thickness: 2.0,
+            tool_radius: 0.0,
+            unit: Unit::Millimeter,
+            origin: OriginMode::CenterOrigin,
+            simplify_tolerance: 0.0,
+            surface: ExportSurface::Flat,
+            svg_precision: svg_writer::DEFAULT_SVG_PRECISION,
+        }
+    }
+}
+
+impl ExportConfig {
+    /// Cross-section of STL grooves cut without a known bit
+    ///
+    /// Rose engine exports cut with their bit's profile instead and use
+    /// `tool_radius` to offset the tool path (see `RoseEngineLathe::to_stl`).
+    ///
+    /// A positive `tool_radius` cuts flat-bottomed grooves twice as wide;
+    /// otherwise grooves are 90° V-grooves, `2 * depth` wide.
+    pub(crate) fn groove_profile(&self) -> Result<GrooveProfile, SpirographError> {
+        if self.tool_radius > 0.0 {
+            GrooveProfile::flat(2.0 * self.tool_radius, self.depth)
+        } else {
+            GrooveProfile::v_shaped(90.0, 2.0 * self.depth, self.depth)
+        }
+    }
+}
+
+/// Coordinate origin of an exported file
+///
+/// Applied as a final translation (and, for y-up modes, a flip of y) when
+/// the file is written; stored geometry never moves. Modes are described
+/// as the drawing appears in the SVG preview, where generated y grows
+/// downward. The extent is the SVG page (the viewBox) or, for formats
+/// without a page, the bounds of the written geometry.
+///
+/// SVG user space is always y-down, so the y-up modes show the preview
+/// mirrored; they are meant for tools that read SVG y as up.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum OriginMode {
+    /// Coordinates as generated, with the dial center at (0, 0)
+    #[default]
+    CenterOrigin,
+    /// (0, 0) at the top-left of the extent, y growing downward
+    TopLeftYDown,
+    /// (0, 0) at the bottom-left of the extent, y growing upward
+    BottomLeftYUp,
+    /// The generated (0, 0) moved to `offset`, with y growing downward or upward
+    Custom { offset: (f64, f64), y_down: bool },
+}
+
+impl OriginMode {
+    /// Check that a custom offset is finite
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        match self {
+            OriginMode::Custom { offset, .. }
+                if !(offset.0.is_finite() && offset.1.is_finite()) =>
+            {
+                Err(SpirographError::InvalidParameter(
+                    "origin offset must be finite".to_string(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Affine transform `[a, b, c, d, e, f]` for an extent of `bounds`
+    ///
+    /// Maps `(x, y)` to `(a·x + c·y + e, b·x + d·y + f)`, as in an SVG
+    /// `matrix()`. `bounds` is `(min_x, min_y, max_x, max_y)`.
+    pub fn affine(self, bounds: (f64, f64, f64, f64)) -> [f64; 6] {
+        let (min_x, min_y, _, max_y) = bounds;
+        match self {
+            OriginMode::CenterOrigin => [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+            OriginMode::TopLeftYDown => [1.0, 0.0, 0.0, 1.0, -min_x, -min_y],
+            OriginMode::BottomLeftYUp => [1.0, 0.0, 0.0, -1.0, -min_x, max_y],
+            OriginMode::Custom {
+                offset: (x, y),
+                y_down,
+            } => [1.0, 0.0, 0.0, if y_down { 1.0 } else { -1.0 }, x, y],
+        }
+    }
+
+    /// Map `p` into the exported frame for an extent of `bounds`
+    pub fn apply(self, bounds: (f64, f64, f64, f64), p: Point2D) -> Point2D {
+        let [a, b, c, d, e, f] = self.affine(bounds);
+        Point2D::new(a * p.x + c * p.y + e, b * p.x + d * p.y + f)
+    }
+
+    /// Move `document` into this frame
+    ///
+    /// Everything drawn is wrapped in one group carrying the transform, and
+    /// the viewBox is moved to match, so the page size and stroke widths
+    /// are unchanged. Title, description and metadata stay at the root.
+    pub(crate) fn apply_svg(self, mut document: svg::Document) -> svg::Document {
+        use svg::node::element::Group;
+
+        if self == OriginMode::CenterOrigin {
+            return document;
+        }
+        let view_box: Vec<f64> = document
+            .get_attributes()
+            .get("viewBox")
+            .map(|v| v.split([' ', ',']).filter_map(|n| n.parse().ok()).collect())
+            .unwrap_or_default();
+        let &[x, y, width, height] = view_box.as_slice() else {
+            return document;
+        };
+
+        let bounds = (x, y, x + width, y + height);
+        let m = self.affine(bounds);
+        let top_left = self.apply(bounds, Point2D::new(x, y));
+        let bottom_right = self.apply(bounds, Point2D::new(x + width, y + height));
+
+        let children = std::mem::take(document.get_children_mut());
+        let mut group = Group::new().set(
+            "transform",
+            format!(
+                "matrix({} {} {} {} {} {})",
+                m[0], m[1], m[2], m[3], m[4], m[5]
+            ),
+        );
+        for child in children {
+            if matches!(child.get_name(), "title" | "desc" | "metadata") {
+                document = document.add(child);
+            } else {
+                group = group.add(child);
+            }
+        }
+        document.add(group).set(
+            "viewBox",
+            (
+                top_left.x.min(bottom_right.x),
+                top_left.y.min(bottom_right.y),
+                width,
+                height,
+            ),
+        )
+    }
+
+    /// Move `triangles` into this frame, bounded by their own XY extent
+    ///
+    /// A y flip mirrors the mesh, so each facet's winding and normal are
+    /// flipped with it to keep the facets facing outward.
+    pub(crate) fn apply_stl(self, triangles: &mut [stl_io::Triangle]) {
+        if self == OriginMode::CenterOrigin {
+            return;
+        }
+        let vertices = || triangles.iter().flat_map(|t| t.vertices.iter());
+        let bounds = vertices().fold(
+            (
+                f64::INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NEG_INFINITY,
+            ),
+            |(min_x, min_y, max_x, max_y), v| {
+                let (x, y) = (v[0] as f64, v[1] as f64);
+                (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+            },
+        );
+        if !bounds.0.is_finite() {
+            return;
+        }
+        let flip = self.affine(bounds)[3] < 0.0;
+        for triangle in triangles.iter_mut() {
+            for v in triangle.vertices.iter_mut() {
+                let p = self.apply(bounds, Point2D::new(v[0] as f64, v[1] as f64));
+                *v = stl_io::Vertex::new([p.x as f32, p.y as f32, v[2]]);
+            }
+            if flip {
+                let n = triangle.normal;
+                triangle.vertices.swap(1, 2);
+                triangle.normal = stl_io::Normal::new([n[0], -n[1], n[2]]);
+            }
+        }
+    }
+
+    /// Map `points` into this frame, bounded by their own extent
+    pub(crate) fn apply_points(self, points: &[Point2D]) -> Vec<Point2D> {
+        match polyline_bounds(std::iter::once(points)) {
+            Some(bounds) if self != OriginMode::CenterOrigin => {
+                points.iter().map(|&p| self.apply(bounds, p)).collect()
+            }
+            _ => points.to_vec(),
+        }
+    }
+}
+
+/// Sides used for the circular base slab of STL exports
+const SLAB_SIDES: usize = 128;
+
+/// Closed circular slab under a pattern, for STL exports
+///
+/// The slab is centred on `center` with a radius reaching the farthest
+/// point and at least `min_radius`. Its rim vertices lie on that circle,
+/// so a dial slab ends exactly at the dial edge (the chords between them
+/// cut in by under 0.03% of the radius). It spans `z_top - thickness ..= z_top` and is returned
+/// as a closed prism with outward normals. A zero `thickness` gives no
+/// triangles, leaving the grooves as they were.
+pub(crate) fn base_slab_triangles(
+    center: Point2D,
+    points: impl IntoIterator<Item = Point2D>,
+    min_radius: f64,
+    z_top: f64,
+    thickness: f64,
+) -> Vec<stl_io::Triangle> {
+    pierced_slab_triangles(center, points, min_radius, z_top, thickness, &[])
+}
+
+/// Circular base slab with apertures cut through it, for STL exports
+///
+/// As [`base_slab_triangles`], with each of `apertures` (closed polygons,
+/// either winding, not repeating their first point) cut through the full
+/// thickness and lined with inward-facing walls. Apertures must not overlap;
+/// any not strictly inside the slab rim are left out. Without apertures
+/// the slab is the same triangle fan as `base_slab_triangles`.
+pub(crate) fn pierced_slab_triangles(
+    center: Point2D,
+    points: impl IntoIterator<Item = Point2D>,
+    min_radius: f64,
+    z_top: f64,
+    thickness: f64,
+    apertures: &[Vec<Point2D>],
+) -> Vec<stl_io::Triangle> {
+    use stl_io::{Normal, Triangle, Vertex};
+
+    if thickness <= 0.0 {
+        return Vec::new();
+    }
+    let radius = points
+        .into_iter()
+        .map(|p| (p.x - center.x).hypot(p.y - center.y))
+        .fold(min_radius.max(0.0), f64::max);
+    let z_bottom = z_top - thickness;
+    let rim: Vec<(f64, f64)> = (0..SLAB_SIDES)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f64 / SLAB_SIDES as f64;
+            (
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect();
+    let vertex = |(x, y): (f64, f64), z: f64| Vertex::new([x as f32, y as f32, z as f32]);
+    let hub = (center.x, center.y);
+
+    // Apertures must clear the rim's chords
+    let inside = radius * (PI / SLAB_SIDES as f64).cos();
+    let apertures: Vec<Vec<Point2D>> = apertures
+        .iter()
+        .filter(|aperture| {
+            aperture.len() >= 3
+                && aperture
+                    .iter()
+                    .all(|p| (p.x - center.x).hypot(p.y - center.y) < inside)
+        })
+        .map(|aperture| {
+            let mut points = aperture.clone();
+            if crate::cells::polygon_area(&points) < 0.0 {
+                points.reverse();
+            }
+            points
+        })
+        .collect();
+
+    // Side wall, facing away from the center
+    let side_wall = |i: usize| {
+        let (a, b) = (rim[i], rim[(i + 1) % SLAB_SIDES]);
+        let mid = PI * (2 * i + 1) as f64 / SLAB_SIDES as f64;
+        let normal = Normal::new([mid.cos() as f32, mid.sin() as f32, 0.0]);
+        [
+            Triangle {
+                normal,
+                vertices: [vertex(a, z_bottom), vertex(b, z_bottom), vertex(b, z_top)],
+            },
+            Triangle {
+                normal,
+                vertices: [vertex(a, z_bottom), vertex(b, z_top), vertex(a, z_top)],
+            },
+        ]
+    };
+
+    let mut triangles = Vec::with_capacity(4 * SLAB_SIDES);
+    if apertures.is_empty() {
+        for i in 0..SLAB_SIDES {
+            let (a, b) = (rim[i], rim[(i + 1) % SLAB_SIDES]);
+            triangles.push(Triangle {
+                normal: Normal::new([0.0, 0.0, 1.0]),
+                vertices: [vertex(hub, z_top), vertex(a, z_top), vertex(b, z_top)],
+            });
+            triangles.push(Triangle {
+                normal: Normal::new([0.0, 0.0, -1.0]),
+                vertices: [
+                    vertex(hub, z_bottom),
+                    vertex(b, z_bottom),
+                    vertex(a, z_bottom),
+                ],
+            });
+            triangles.extend(side_wall(i));
+        }
+    } else {
+        let outline: Vec<Point2D> = rim.iter().map(|&(x, y)| Point2D::new(x, y)).collect();
+        for [a, b, c] in crate::cells::triangulate_with_holes(&outline, &apertures) {
+            let (a, b, c) = ((a.x, a.y), (b.x, b.y), (c.x, c.y));
+            triangles.push(Triangle {
+                normal: Normal::new([0.0, 0.0, 1.0]),
+                vertices: [vertex(a, z_top), vertex(b, z_top), vertex(c, z_top)],
+            });
+            triangles.push(Triangle {
+                normal: Normal::new([0.0, 0.0, -1.0]),
+                vertices: [
+                    vertex(a, z_bottom),
+                    vertex(c, z_bottom),
+                    vertex(b, z_bottom),
+                ],
+            });
+        }
+    }
+    if !apertures.is_empty() {
+        (0..SLAB_SIDES).for_each(|i| triangles.extend(side_wall(i)));
+    }
+    // Aperture walls, facing into the opening
+    for aperture in &apertures {
+        let n = aperture.len();
+        for i in 0..n {
+            let (p, q) = (aperture[i], aperture[(i + 1) % n]);
+            let length = (q.x - p.x).hypot(q.y - p.y);
+            if length == 0.0 {
+                continue;
+            }
+            let normal = Normal::new([
+                ((p.y - q.y) / length) as f32,
+                ((q.x - p.x) / length) as f32,
+                0.0,
+            ]);
+            let (a, b) = ((p.x, p.y), (q.x, q.y));
+            triangles.push(Triangle {
+                normal,
+                vertices: [vertex(b, z_bottom), vertex(a, z_bottom), vertex(a, z_top)],
+            });
+            triangles.push(Triangle {
+                normal,
+                vertices: [vertex(b, z_bottom), vertex(a, z_top), vertex(b, z_top)],
+            });
+        }
+    }
+    triangles
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_angle() {
+        assert_eq!(normalize_angle(0.0), 0.0);
+        assert!((normalize_angle(-PI / 2.0) - 1.5 * PI).abs() < 1e-12);
+        assert!((normalize_angle(700.0) - 700.0_f64.rem_euclid(2.0 * PI)).abs() < 1e-12);
+        assert_eq!(normalize_angle(-1e-18), 0.0);
+        for a in [-100.0, -1.0, 0.5, 7.0, 1e6] {
+            let n = normalize_angle(a);
+            assert!((0.0..2.0 * PI).contains(&n));
+        }
+    }
+
+    #[test]
+    fn test_first_non_finite() {
+        let good = vec![vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 1.0)]];
+        assert_eq!(first_non_finite(&good), None);
+
+        let bad = vec![
+            vec![Point2D::new(0.0, 0.0)],
+            vec![Point2D::new(1.0, 1.0), Point2D::new(f64::NAN, 0.0)],
+        ];
+        assert_eq!(first_non_finite(&bad), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_radial_wave_exponent_keeps_sign() {
+        let sine = RosettePattern::Sinusoidal { frequency: 1.0 };
+        let linear = RadialWave::new(10.0, 2.0, sine.clone());
+        let cubed = RadialWave::new(10.0, 2.0, sine).with_wave_exponent(3);
+
+        // Crests and troughs are unchanged by the exponent
+        for theta in [PI / 2.0, 1.5 * PI] {
+            assert!((linear.radius_at(theta) - cubed.radius_at(theta)).abs() < 1e-12);
+        }
+
+        // sin(π/6) = 0.5 → 0.125; sin(7π/6) = −0.5 → −0.125
+        assert!((linear.displacement_at(PI / 6.0) - 0.5).abs() < 1e-12);
+        assert!((cubed.displacement_at(PI / 6.0) - 0.125).abs() < 1e-12);
+        assert!((cubed.displacement_at(7.0 * PI / 6.0) + 0.125).abs() < 1e-12);
+        assert!((cubed.radius_at(7.0 * PI / 6.0) - 9.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_radial_wave_phase_shifts_pattern_backwards() {
+        // phase is added to θ, so a positive phase moves the crest to
+        // smaller angles: sin(4(θ + φ)) peaks at θ = π/8 − φ.
+        let phase = 0.1;
+        let wave = RadialWave::new(20.0, 1.0, RosettePattern::Sinusoidal { frequency: 4.0 })
+            .with_phase(phase);
+        assert!((wave.radius_at(PI / 8.0 - phase) - 21.0).abs() < 1e-12);
+
+        // The phase is used as given, not wrapped
+        let wrapped = wave.clone().with_phase(phase + 2.0 * PI);
+        assert_ne!(wrapped.phase, wave.phase);
+        assert!((wrapped.radius_at(0.3) - wave.radius_at(0.3)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trig_table_matches_direct_trig() {
+        let table = TrigTable::new(1500);
+        assert_eq!(table.resolution(), 1500);
+        for j in 0..=3 * 1500 {
+            let theta = 2.0 * PI * (j as f64) / 1500.0;
+            let (cos, sin) = table.cos_sin(j);
+            if j < 1500 {
+                assert_eq!((cos, sin), (theta.cos(), theta.sin()));
+            }
+            assert!((cos - theta.cos()).abs() < 1e-12);
+            assert!((sin - theta.sin()).abs() < 1e-12);
+        }
+
+        // Several turns through the table match the direct formula
+        let wave = RadialWave::new(8.0, 0.4, RosettePattern::Sinusoidal { frequency: 2.5 });
+        let center = Point2D::new(0.5, 0.25);
+        let ring = wave.sample_revolutions(90, 2, center);
+        for (j, p) in ring.iter().enumerate() {
+            let theta = 2.0 * PI * (j as f64) / 90.0;
+            let r = wave.radius_at(theta);
+            assert!((p.x - (center.x + r * theta.cos())).abs() < 1e-12);
+            assert!((p.y - (center.y + r * theta.sin())).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_radial_wave_sample_ring() {
+        let wave = RadialWave::new(5.0, 0.5, RosettePattern::MultiLobe { lobes: 6 });
+        let center = Point2D::new(1.0, -2.0);
+        let ring = wave.sample_ring(120, center);
+
+        assert_eq!(ring.len(), 121);
+        assert!((ring[0].x - ring[120].x).abs() < 1e-12);
+        assert!((ring[0].y - ring[120].y).abs() < 1e-12);
+        for (j, p) in ring.iter().enumerate() {
+            let theta = 2.0 * PI * (j as f64) / 120.0;
+            let r = (p.x - center.x).hypot(p.y - center.y);
+            assert!((r - wave.radius_at(theta)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_radial_wave_sum_adds_radii() {
+        let base = RadialWave::new(8.0, 1.0, RosettePattern::Sinusoidal { frequency: 3.0 });
+        let ripple = RadialWave::new(0.0, 0.1, RosettePattern::Sinusoidal { frequency: 30.0 });
+        let center = Point2D::new(0.0, 0.0);
+        let ring = RadialWave::sample_sum(&[base.clone(), ripple.clone()], 90, center);
+
+        for (j, p) in ring.iter().enumerate() {
+            let theta = 2.0 * PI * (j as f64) / 90.0;
+            let expected = base.radius_at(theta) + ripple.radius_at(theta);
+            assert!((p.x.hypot(p.y) - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_polyline_closes() {
+        let circle: Vec<Point2D> = (0..36)
+            .map(|i| {
+                let a = 2.0 * PI * (i as f64) / 36.0;
+                Point2D::new(a.cos(), a.sin())
+            })
+            .collect();
+        assert!(polyline_closes(&circle));
+        assert!(!polyline_closes(&circle[..30]));
+    }
+
+    #[test]
+    fn test_polyline_bounds() {
+        let a = vec![Point2D::new(1.0, -2.0), Point2D::new(3.0, 4.0)];
+        let b = vec![Point2D::new(-5.0, 0.5)];
+        assert_eq!(
+            polyline_bounds([a.as_slice(), b.as_slice()]),
+            Some((-5.0, -2.0, 3.0, 4.0))
+        );
+        assert_eq!(polyline_bounds(std::iter::empty()), None);
+    }
+
+    #[test]
+    fn test_drawable_bounds_skips_degenerate_lines() {
+        let line = vec![Point2D::new(1.0, -2.0), Point2D::new(3.0, 4.0)];
+        let dot = vec![Point2D::new(-5.0, 0.5)];
+        assert_eq!(
+            drawable_bounds([line.as_slice(), dot.as_slice()]).unwrap(),
+            (1.0, -2.0, 3.0, 4.0)
+        );
+        assert!(drawable_bounds([dot.as_slice(), &[]]).is_err());
+    }
+
+    #[test]
+    fn test_geometry_stats_of_drawable_lines() {
+        let square = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(2.0, 0.0),
+            Point2D::new(2.0, 2.0),
+            Point2D::new(0.0, 2.0),
+            Point2D::new(0.0, 0.0),
+        ];
+        let line = vec![Point2D::new(-1.0, 1.0), Point2D::new(-1.0, 4.0)];
+        let dot = vec![Point2D::new(9.0, 9.0)];
+        let stats =
+            GeometryStats::from_polylines([square.as_slice(), line.as_slice(), dot.as_slice()])
+                .unwrap();
+        assert_eq!(stats.min, Point2D::new(-1.0, 0.0));
+        assert_eq!(stats.max, Point2D::new(2.0, 4.0));
+        assert_eq!(stats.size(), (3.0, 4.0));
+        assert_eq!(stats.total_path_length, 11.0);
+        assert_eq!(stats.num_polylines, 2);
+        assert_eq!(stats.num_points, 7);
+
+        let (min_x, min_y, max_x, max_y) =
+            drawable_bounds([square.as_slice(), line.as_slice()]).unwrap();
+        assert_eq!((min_x, min_y, max_x, max_y), (-1.0, 0.0, 2.0, 4.0));
+        assert_eq!(GeometryStats::from_polylines([dot.as_slice()]), None);
+    }
+
+    /// Every `<g>` in `svg` in document order, as its nesting depth (0 for
+    /// a child of the root) and its attributes
+    pub(crate) fn svg_groups(
+        svg: &str,
+    ) -> Vec<(usize, std::collections::BTreeMap<String, String>)> {
+        use svg::node::element::tag::Type;
+        use svg::parser::Event;
+
+        let mut depth = 0usize;
+        let mut groups = Vec::new();
+        for event in svg::read(svg).unwrap() {
+            if let Event::Tag(name, kind, attributes) = event {
+                match kind {
+                    Type::Start if name == "g" => groups.push((
+                        depth - 1,
+                        attributes
+                            .iter()
+                            .map(|(key, value)| (key.clone(), value.to_string()))
+                            .collect(),
+                    )),
+                    _ => {}
+                }
+                match kind {
+                    Type::Start => depth += 1,
+                    Type::End => depth -= 1,
+                    Type::Empty => {}
+                }
+            }
+        }
+        groups
+    }
+
+    /// Check that every triangle's winding agrees with its normal and that
+    /// the mesh is closed: each edge is used once in each direction
+    pub(crate) fn assert_closed_outward(slab: &[stl_io::Triangle]) {
+        let key = |v: &stl_io::Vertex| (v[0].to_bits(), v[1].to_bits(), v[2].to_bits());
+        let mut edges = std::collections::HashMap::new();
+        for triangle in slab {
+            let [a, b, c] = triangle.vertices;
+            // Winding agrees with the stored normal
+            let (u, v) = (
+                [b[0] - a[0], b[1] - a[1], b[2] - a[2]],
+                [c[0] - a[0], c[1] - a[1], c[2] - a[2]],
+            );
+            let n = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+            let normal = triangle.normal;
+            assert!(n[0] * normal[0] + n[1] * normal[1] + n[2] * normal[2] > 0.0);
+            for (p, q) in [(a, b), (b, c), (c, a)] {
+                *edges.entry((key(&p), key(&q))).or_insert(0) += 1;
+            }
+        }
+        for (&(p, q), &count) in &edges {
+            assert_eq!(count, 1);
+            assert_eq!(edges.get(&(q, p)), Some(&1));
+        }
+    }
+
+    #[test]
+    fn test_base_slab_is_a_closed_outward_prism() {
+        let center = Point2D::new(1.0, -2.0);
+        let points = [Point2D::new(6.0, -2.0), Point2D::new(1.0, 1.0)];
+        let slab = base_slab_triangles(center, points, 3.0, -0.1, 2.0);
+        assert!(base_slab_triangles(center, points, 3.0, -0.1, 0.0).is_empty());
+
+        assert_closed_outward(&slab);
+        for vertex in slab.iter().flat_map(|t| t.vertices) {
+            assert!(vertex[2] == -0.1 || vertex[2] == -2.1_f32);
+        }
+
+        // The rim runs through the farthest point's circle
+        for vertex in slab.iter().flat_map(|t| t.vertices) {
+            let r = (vertex[0] as f64 - center.x).hypot(vertex[1] as f64 - center.y);
+            assert!(r < 1e-5 || (r - 5.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_pierced_slab_is_closed_around_its_apertures() {
+        let square = |x: f64, y: f64, side: f64| {
+            vec![
+                Point2D::new(x, y),
+                Point2D::new(x + side, y),
+                Point2D::new(x + side, y + side),
+                Point2D::new(x, y + side),
+            ]
+        };
+        // Level squares either side of the center line up on a bridge ray
+        let mut apertures = vec![square(-6.0, -1.0, 2.0), square(4.0, -1.0, 2.0)];
+        apertures.push(
+            (0..16)
+                .rev()
+                .map(|i| {
+                    let angle = 2.0 * PI * i as f64 / 16.0;
+                    Point2D::new(0.8 * angle.cos(), 3.0 + 0.8 * angle.sin())
+                })
+                .collect(),
+        );
+        // Past the rim, so left out
+        apertures.push(square(9.5, 0.0, 1.0));
+        let center = Point2D::new(0.0, 0.0);
+        let slab = pierced_slab_triangles(center, [], 10.0, 0.0, 1.5, &apertures);
+        assert_closed_outward(&slab);
+
+        // The top face covers the disc less the three openings
+        let top: f64 = slab
+            .iter()
+            .filter(|t| t.normal[2] > 0.5)
+            .map(|t| {
+                let [a, b, c] = t.vertices.map(|v| Point2D::new(v[0] as f64, v[1] as f64));
+                ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)) / 2.0
+            })
+            .sum();
+        let rim = 64.0 * 100.0 * (2.0 * PI / 128.0).sin();
+        let openings: f64 = apertures[..3]
+            .iter()
+            .map(|a| crate::cells::polygon_area(a).abs())
+            .sum();
+        assert!((top - (rim - openings)).abs() < 1e-3, "{}", top);
+
+        // Without apertures it is the plain slab
+        let plain = base_slab_triangles(center, [], 10.0, 0.0, 1.5);
+        assert_eq!(
+            pierced_slab_triangles(center, [], 10.0, 0.0, 1.5, &[]),
+            plain
+        );
+    }
+
+    #[test]
+    fn test_geometry_hash_ignores_noise_but_not_structure() {
+        let line = vec![Point2D::new(1.0, 2.0), Point2D::new(3.0, 4.0)];
+        let noisy = vec![
+            Point2D::new(1.0 + 1e-12, 2.0),
+            Point2D::new(3.0, 4.0 - 1e-12),
+        ];
+        let hash = |lines: &[Vec<Point2D>]| geometry_hash(lines.iter().map(Vec::as_slice));
+        let whole = hash(std::slice::from_ref(&line));
+        assert_eq!(whole, hash(&[noisy]));
+
+        // Same points split into two strokes is a different drawing
+        let split = [line[..1].to_vec(), line[1..].to_vec()];
+        assert_ne!(whole, hash(&split));
+        assert_ne!(whole, hash(&[vec![Point2D::new(1.0, 2.0)]]));
+    }
+
+    #[test]
+    fn test_split_polyline_shares_boundaries() {
+        let ring: Vec<Point2D> = (0..25_000)
+            .map(|i| {
+                let t = 2.0 * PI * i as f64 / 25_000.0;
+                Point2D::new(10.0 * t.cos(), 10.0 * t.sin())
+            })
+            .collect();
+        let runs = split_polyline(&ring, 10_000);
+        assert_eq!(runs.len(), 3);
+        assert!(runs.iter().all(|run| run.len() <= 10_000));
+        for pair in runs.windows(2) {
+            assert_eq!(pair[0].last(), pair[1].first());
+        }
+
+        let mut joined = runs[0].to_vec();
+        for run in &runs[1..] {
+            joined.extend_from_slice(&run[1..]);
+        }
+        assert_eq!(joined, ring);
+
+        assert_eq!(split_polyline(&ring, 30_000).len(), 1);
+        assert_eq!(split_polyline(&ring[..3], 0).len(), 2);
+    }
+
+    #[test]
+    fn test_simplify_polyline_collapses_a_straight_line() {
+        let line: Vec<Point2D> = (0..1000)
+            .map(|i| Point2D::new(i as f64 * 0.01, 0.5 * i as f64 * 0.01))
+            .collect();
+        let simplified = simplify_polyline(&line, 0.01);
+        assert_eq!(simplified, vec![line[0], line[999]]);
+
+        // Zero tolerance leaves the line alone
+        assert_eq!(simplify_polyline(&line, 0.0), line);
+    }
+
+    #[test]
+    fn test_offset_polyline_circle_and_concave_corner() {
+        let circle: Vec<Point2D> = (0..=720)
+            .map(|i| {
+                let angle = 2.0 * PI * i as f64 / 720.0;
+                Point2D::new(10.0 * angle.cos(), 10.0 * angle.sin())
+            })
+            .collect();
+        // Mitered vertices sit d / cos(half a step) off the center, a few
+        // microns off the true circle
+        for (distance, radius) in [(0.5, 10.5), (-0.5, 9.5)] {
+            let offset = offset_polyline(&circle, distance);
+            assert_eq!(offset.len(), circle.len());
+            assert!(offset
+                .iter()
+                .all(|p| (p.x.hypot(p.y) - radius).abs() < 1e-5));
+        }
+
+        // Any two non-adjacent segments that cross
+        let crossings = |line: &[Point2D]| {
+            (0..line.len() - 1)
+                .flat_map(|i| (i + 2..line.len() - 1).map(move |j| (i, j)))
+                .filter(|&(i, j)| {
+                    segment_crossing(line[i], line[i + 1], line[j], line[j + 1]).is_some()
+                })
+                .count()
+        };
+
+        // A right-angled V, finely sampled, run left to right: its inside
+        // is on the left, so negative offsets fold back at the apex
+        let v: Vec<Point2D> = (-20..=20)
+            .map(|i| Point2D::new(i as f64 * 0.25, (i as f64 * 0.25).abs()))
+            .collect();
+        let raw = offset_points(&v, |_| -1.0);
+        assert!(crossings(&raw) > 0);
+        let inside = offset_polyline(&v, -1.0);
+        assert_eq!(crossings(&inside), 0);
+        assert!(inside.len() < v.len());
+        // The arms stay a unit from the V and meet on its axis
+        let apex = inside.iter().min_by(|a, b| a.y.total_cmp(&b.y)).unwrap();
+        assert!(apex.x.abs() < 1e-9 && (apex.y - 2f64.sqrt()).abs() < 1e-9);
+        let outside = offset_polyline(&v, 1.0);
+        assert_eq!(outside.len(), v.len());
+        assert_eq!(crossings(&outside), 0);
+
+        // A figure eight keeps the crossing it makes itself
+        let eight: Vec<Point2D> = (0..=400)
+            .map(|i| {
+                let t = 2.0 * PI * i as f64 / 400.0;
+                Point2D::new(10.0 * t.sin(), 5.0 * (2.0 * t).sin())
+            })
+            .collect();
+        let offset = offset_polyline(&eight, 0.3);
+        assert_eq!(offset.len(), eight.len());
+        assert!(crossings(&offset) > 0);
+    }
+
+    #[test]
+    fn test_offset_polyline_miters_corners() {
+        // A right-angled left turn: the outside corner sits a full
+        // distance from both legs rather than 0.707 of it
+        let corner = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(10.0, 0.0),
+            Point2D::new(10.0, 10.0),
+        ];
+        let outside = offset_polyline(&corner, 1.0);
+        assert!((outside[1].x - 11.0).abs() < 1e-12 && (outside[1].y + 1.0).abs() < 1e-12);
+        assert!((outside[0].y + 1.0).abs() < 1e-12 && (outside[2].x - 11.0).abs() < 1e-12);
+        let inside = offset_polyline(&corner, -1.0);
+        assert!((inside[1].x - 9.0).abs() < 1e-12 && (inside[1].y - 1.0).abs() < 1e-12);
+
+        // A closed square is mitered across its join too
+        let square = [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)]
+            .map(|(x, y)| Point2D::new(x, y));
+        let grown = offset_polyline(&square, 0.5);
+        for (p, q) in grown.iter().zip(square.iter()) {
+            assert!((p.x - (q.x - 2.0) * 1.25 - 2.0).abs() < 1e-12);
+            assert!((p.y - (q.y - 2.0) * 1.25 - 2.0).abs() < 1e-12);
+        }
+
+        // A near hairpin is held to the miter limit
+        let hairpin = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(10.0, 0.0),
+            Point2D::new(0.0, 0.1),
+        ];
+        let offset = offset_polyline(&hairpin, 1.0);
+        let moved = (offset[1].x - 10.0).hypot(offset[1].y);
+        assert!((moved - MITER_LIMIT).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fit_arcs_keeps_straight_runs_as_lines() {
+        // A stadium: two straight sides joined by half circles
+        let mut stadium: Vec<Point2D> = (0..=20).map(|i| Point2D::new(i as f64, -5.0)).collect();
+        stadium.extend((1..=60).map(|i| {
+            let angle = -PI / 2.0 + PI * i as f64 / 60.0;
+            Point2D::new(20.0 + 5.0 * angle.cos(), 5.0 * angle.sin())
+        }));
+        stadium.extend((1..=20).map(|i| Point2D::new(20.0 - i as f64, 5.0)));
+        let segments = fit_arcs(&stadium, 0.005);
+        assert!(matches!(
+            &segments[..],
+            [
+                PathSegment::Line { .. },
+                PathSegment::Arc(arc),
+                PathSegment::Line { .. },
+            ] if (arc.radius - 5.0).abs() < 1e-9 && (arc.end_angle - arc.start_angle - PI).abs() < 1e-9
+        ));
+        assert!(fit_arcs(&stadium[..1], 0.01).is_empty());
+    }
+
+    #[test]
+    fn test_simplify_polyline_keeps_sine_extrema() {
+        let tolerance = 0.01;
+        let wave: Vec<Point2D> = (0..=2000)
+            .map(|i| {
+                let x = 4.0 * PI * i as f64 / 2000.0;
+                Point2D::new(x, x.sin())
+            })
+            .collect();
+        let simplified = simplify_polyline(&wave, tolerance);
+        assert!(simplified.len() < wave.len() / 10);
+        assert_eq!(simplified.first(), wave.first());
+        assert_eq!(simplified.last(), wave.last());
+
+        // Height of the simplified line at x, by linear interpolation
+        let height = |x: f64| {
+            let i = simplified
+                .partition_point(|p| p.x < x)
+                .clamp(1, simplified.len() - 1);
+            let (a, b) = (simplified[i - 1], simplified[i]);
+            a.y + (b.y - a.y) * (x - a.x) / (b.x - a.x)
+        };
+        for k in 0..4 {
+            let x = PI / 2.0 + k as f64 * PI;
+            assert!((height(x) - x.sin()).abs() <= tolerance);
+        }
+
+        // Every original point stays within tolerance of the simplified line
+        let off_line = |p: &Point2D| {
+            simplified
+                .windows(2)
+                .map(|s| {
+                    let (dx, dy) = (s[1].x - s[0].x, s[1].y - s[0].y);
+                    let t = ((p.x - s[0].x) * dx + (p.y - s[0].y) * dy) / (dx * dx + dy * dy);
+                    let t = t.clamp(0.0, 1.0);
+                    (p.x - s[0].x - t * dx).hypot(p.y - s[0].y - t * dy)
+                })
+                .fold(f64::INFINITY, f64::min)
+        };
+        assert!(wave.iter().all(|p| off_line(p) <= tolerance));
+    }
+
+    #[test]
+    fn test_dedupe_consecutive() {
+        let mut line = vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(0.0, 0.0),
+            Point2D::new(1.0, 0.0),
+            Point2D::new(1.0 + 1e-12, 0.0),
+            Point2D::new(2.0, 0.0),
+            Point2D::new(0.0, 0.0),
+        ];
+        dedupe_consecutive(&mut line, DEDUPE_EPSILON);
+        // Only consecutive repeats go; the return to the start is kept
+        assert_eq!(
+            line,
+            vec![
+                Point2D::new(0.0, 0.0),
+                Point2D::new(1.0, 0.0),
+                Point2D::new(2.0, 0.0),
+                Point2D::new(0.0, 0.0),
+            ]
+        );
+
+        assert!(polyline_is_degenerate(&[]));
+        assert!(polyline_is_degenerate(&[Point2D::new(1.0, 1.0); 4]));
+        assert!(!polyline_is_degenerate(&line));
+    }
+
+    #[test]
+    fn test_tangent_borrows_across_zero_length_segments() {
+        let p = |x, y| Point2D::new(x, y);
+        // Repeated point in the middle of a straight line along +x
+        let line = [p(0.0, 0.0), p(1.0, 0.0), p(1.0, 0.0), p(2.0, 0.0)];
+        for i in 0..line.len() {
+            assert_eq!(polyline_vertex_join(&line, i), Some((0.0, 1.0)));
+        }
+        // Repeated endpoints borrow the nearest real segment
+        let line = [p(0.0, 0.0), p(0.0, 0.0), p(0.0, 1.0), p(0.0, 1.0)];
+        assert_eq!(polyline_vertex_join(&line, 0), Some((PI / 2.0, 1.0)));
+        assert_eq!(polyline_vertex_join(&line, 3), Some((PI / 2.0, 1.0)));
+        assert_eq!(polyline_vertex_join(&[p(1.0, 1.0); 3], 1), None);
+    }
+
+    #[test]
+    fn test_clip_polyline_to_circle() {
+        // Horizontal line through a unit circle is cut at x = ±1
+        let line = vec![Point2D::new(-2.0, 0.0), Point2D::new(2.0, 0.0)];
+        let pieces = clip_polyline_to_circle(&line, Point2D::new(0.0, 0.0), 1.0, false);
+        assert_eq!(pieces.len(), 1);
+        assert!((pieces[0][0].x + 1.0).abs() < 1e-12);
+        assert!((pieces[0][1].x - 1.0).abs() < 1e-12);
+
+        // A square of radius √2 around the origin, clipped to r = 1.2, leaves
+        // four corner-free pieces, the one through the start rejoined.
+        let square = vec![
+            Point2D::new(1.0, 0.0),
+            Point2D::new(1.0, 1.0),
+            Point2D::new(-1.0, 1.0),
+            Point2D::new(-1.0, -1.0),
+            Point2D::new(1.0, -1.0),
+        ];
+        let pieces = clip_polyline_to_circle(&square, Point2D::new(0.0, 0.0), 1.2, true);
+        assert_eq!(pieces.len(), 4);
+        for piece in &pieces {
+            for p in piece {
+                assert!(p.x.hypot(p.y) <= 1.2 + 1e-12);
+            }
+        }
+        assert!(pieces
+            .iter()
+            .any(|piece| piece.contains(&Point2D::new(1.0, 0.0))));
+
+        // Entirely inside: a single closed piece ending back at the start
+        let pieces = clip_polyline_to_circle(&square, Point2D::new(0.0, 0.0), 2.0, true);
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].len(), 6);
+        assert_eq!(pieces[0][0], pieces[0][5]);
+    }
+
+    #[test]
+    fn test_ellipse_dial_shape_clips_and_slabs_to_its_outline() {
+        let shape = DialShape::Ellipse { rx: 4.0, ry: 2.0 };
+        assert!(shape.validate().is_ok());
+        assert!(DialShape::Ellipse { rx: 4.0, ry: 0.0 }.validate().is_err());
+        assert_eq!(shape.max_semi_axis(), 4.0);
+        assert!(shape.contains(Point2D::new(3.9, 0.0)));
+        assert!(!shape.contains(Point2D::new(0.0, 2.1)));
+
+        // Lines along each axis are cut at that axis' semi-axis
+        let across = vec![Point2D::new(-5.0, 0.0), Point2D::new(5.0, 0.0)];
+        let pieces = shape.clip_polyline(&across, false);
+        assert_eq!(pieces.len(), 1);
+        assert!((pieces[0][0].x + 4.0).abs() < 1e-12);
+        assert!((pieces[0][1].x - 4.0).abs() < 1e-12);
+        let up = vec![Point2D::new(0.0, -5.0), Point2D::new(0.0, 5.0)];
+        let pieces = shape.clip_polyline(&up, false);
+        assert!((pieces[0][0].y + 2.0).abs() < 1e-12);
+        assert!((pieces[0][1].y - 2.0).abs() < 1e-12);
+
+        // A circle is the same as clipping to it directly
+        let circle = DialShape::Circle { radius: 3.0 };
+        let origin = Point2D::new(0.0, 0.0);
+        assert_eq!(
+            circle.clip_polyline(&across, false),
+            clip_polyline_to_circle(&across, origin, 3.0, false)
+        );
+
+        // The slab stays closed and its rim runs along the outline
+        let slab = shape.slab_triangles([], 0.0, 1.0, &[]);
+        assert_closed_outward(&slab);
+        for vertex in slab.iter().flat_map(|t| t.vertices) {
+            let (x, y) = (vertex[0] as f64 / 4.0, vertex[1] as f64 / 2.0);
+            let r = x.hypot(y);
+            assert!(r < 1e-5 || (r - 1.0).abs() < 1e-5);
+        }
+        assert!(shape
+            .outline(16)
+            .iter()
+            .all(|p| ((p.x / 4.0).hypot(p.y / 2.0) - 1.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_envelope_smoothstep_endpoints_and_product() {
+        // Angular window from 1 to 2 rad feathered over 0.25 rad: exact 0
+        // and 1 at the ends of each ramp, 0 outside
+        let angular = Envelope::angular(1.0, 2.0, 0.25);
+        assert_eq!(angular.eval(1.0, 0.0), 0.0);
+        assert_eq!(angular.eval(1.25, 0.0), 1.0);
+        assert_eq!(angular.eval(1.5, 0.0), 1.0);
+        assert_eq!(angular.eval(1.75, 0.0), 1.0);
+        assert_eq!(angular.eval(2.0, 0.0), 0.0);
+        assert_eq!(angular.eval(0.5, 0.0), 0.0);
+        assert!((angular.eval(1.125, 0.0) - 0.5).abs() < 1e-12);
+        // Angles wrap, and so does a window running through 0
+        assert_eq!(angular.eval(1.5 + 4.0 * PI, 0.0), 1.0);
+        let wrapped = Envelope::angular(1.5 * PI, 0.5 * PI, 0.0);
+        assert_eq!(wrapped.eval(0.0, 0.0), 1.0);
+        assert_eq!(wrapped.eval(PI, 0.0), 0.0);
+
+        let radial = Envelope::radial(10.0, 20.0, 2.0);
+        assert_eq!(radial.eval(0.0, 10.0), 0.0);
+        assert_eq!(radial.eval(0.0, 12.0), 1.0);
+        assert_eq!(radial.eval(0.0, 18.0), 1.0);
+        assert_eq!(radial.eval(0.0, 20.0), 0.0);
+        assert_eq!(radial.eval(0.0, 25.0), 0.0);
+
+        // Products multiply; constants are clamped
+        let product = angular.clone() * radial.clone();
+        for (angle, radius) in [(1.1, 11.0), (1.2, 19.5), (1.9, 15.0), (3.0, 15.0)] {
+            let expected = angular.eval(angle, radius) * radial.eval(angle, radius);
+            assert_eq!(product.eval(angle, radius), expected);
+        }
+        assert_eq!(
+            Envelope::constant(0.5)
+                .product(Envelope::constant(0.5))
+                .eval(0.0, 0.0),
+            0.25
+        );
+        assert_eq!(Envelope::constant(3.0).eval(0.0, 0.0), 1.0);
+        assert_eq!(Envelope::default().eval(2.0, 7.0), 1.0);
+
+        // Tables interpolate around the revolution
+        let table = Envelope::table(vec![0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(table.eval(PI / 2.0, 0.0), 1.0);
+        assert!((table.eval(PI / 4.0, 0.0) - 0.5).abs() < 1e-12);
+        assert!((table.eval(-PI / 4.0, 0.0) - 0.5).abs() < 1e-12);
+
+        assert!(Envelope::angular(0.0, 1.0, -0.1).validate().is_err());
+        assert!(Envelope::radial(2.0, 1.0, 0.0).validate().is_err());
+        assert!(Envelope::table(Vec::new()).validate().is_err());
+        assert!((Envelope::constant(1.0) * Envelope::constant(f64::NAN))
+            .validate()
+            .is_err());
+        assert!(product.validate().is_ok());
+
+        let json = serde_json::to_string(&product).unwrap();
+        assert_eq!(serde_json::from_str::<Envelope>(&json).unwrap(), product);
+    }
+
+    #[test]
+    fn test_origin_mode_maps_extent_corners() {
+        let bounds = (-10.0, -4.0, 30.0, 6.0);
+        let corner = |mode: OriginMode, x, y| {
+            let p = mode.apply(bounds, Point2D::new(x, y));
+            (p.x, p.y)
+        };
+        assert_eq!(corner(OriginMode::CenterOrigin, 3.0, 2.0), (3.0, 2.0));
+        assert_eq!(corner(OriginMode::TopLeftYDown, -10.0, -4.0), (0.0, 0.0));
+        assert_eq!(corner(OriginMode::TopLeftYDown, 30.0, 6.0), (40.0, 10.0));
+        assert_eq!(corner(OriginMode::BottomLeftYUp, -10.0, 6.0), (0.0, 0.0));
+        assert_eq!(corner(OriginMode::BottomLeftYUp, 30.0, -4.0), (40.0, 10.0));
+        let custom = OriginMode::Custom {
+            offset: (1.0, 2.0),
+            y_down: false,
+        };
+        assert_eq!(corner(custom, 3.0, 2.0), (4.0, 0.0));
+
+        assert!(custom.validate().is_ok());
+        assert!(OriginMode::Custom {
+            offset: (f64::NAN, 0.0),
+            y_down: true
         }
+        .validate()
+        .is_err());
+        assert_eq!(ExportConfig::default().origin, OriginMode::CenterOrigin);
     }
 }
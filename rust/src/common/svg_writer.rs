@@ -0,0 +1,379 @@
+//! Standalone SVG documents with coordinates rounded to a fixed precision
+//!
+//! The `svg` crate writes path data as `f32` and other numbers at full
+//! `f64` precision. Engraving needs far less, so every exporter builds its
+//! document through an [`SvgWriter`], which writes path data, circles and
+//! the viewBox with at most [`precision`](SvgWriter::precision) decimals.
+//! Polylines longer than
+//! [`max_points_per_path`](SvgWriter::max_points_per_path) are split across
+//! several paths.
+//!
+//! # Example
+//! ```
+//! use turtles::{Point2D, SvgWriter};
+//!
+//! let mut writer = SvgWriter::new((0.0, 0.0, 10.0, 10.0), 1.0).with_precision(2);
+//! let line = [Point2D::new(0.0, 0.0), Point2D::new(1.0 / 3.0, 2.0 / 3.0)];
+//! writer.add_polyline(&line, |path| path.set("stroke", "black"));
+//! writer.add_circle(Point2D::new(5.0, 5.0), 1.23456, |circle| circle.set("fill", "none"));
+//!
+//! let svg = writer.into_document().to_string();
+//! assert!(svg.contains("d=\"M0,0 L0.33,0.67\""));
+//! assert!(svg.contains("r=\"1.23\""));
+//! ```
+
+use std::path::Path as FilePath;
+
+use svg::node::element::{Circle, Group, Path};
+use svg::{Document, Node};
+
+use super::{
+    drawable_bounds, framed_view_box, polyline_is_degenerate, split_polyline, OriginMode, Point2D,
+    SpirographError, DEFAULT_MAX_POINTS_PER_PATH,
+};
+
+/// Decimal places of SVG coordinates unless set otherwise, 0.1µm in mm
+pub const DEFAULT_SVG_PRECISION: usize = 4;
+
+/// An SVG document being written, with its viewBox, page unit, the
+/// precision its coordinates are rounded to and the most points one path
+/// holds
+pub struct SvgWriter {
+    document: Document,
+    view_box: (f64, f64, f64, f64),
+    unit_suffix: String,
+    precision: usize,
+    max_points_per_path: usize,
+    origin: OriginMode,
+}
+
+impl SvgWriter {
+    /// An empty document in mm showing `bounds` `(min_x, min_y, max_x,
+    /// max_y)` with `margin` on every side
+    pub fn new(bounds: (f64, f64, f64, f64), margin: f64) -> Self {
+        let (min_x, min_y, max_x, max_y) = bounds;
+        Self::with_view_box(
+            (
+                min_x - margin,
+                min_y - margin,
+                max_x - min_x + 2.0 * margin,
+                max_y - min_y + 2.0 * margin,
+            ),
+            "mm",
+        )
+    }
+
+    /// An empty document in mm framing the drawable lines with
+    /// [`SVG_MARGIN`](super::SVG_MARGIN) on every side
+    pub(crate) fn framing<'a, I>(lines: I) -> Result<Self, SpirographError>
+    where
+        I: IntoIterator<Item = &'a [Point2D]>,
+    {
+        Ok(Self::with_view_box(
+            framed_view_box(drawable_bounds(lines)?),
+            "mm",
+        ))
+    }
+
+    /// An empty document showing `view_box` `(x, y, width, height)`, its
+    /// page size in the unit with `unit_suffix` ("mm", "in")
+    pub(crate) fn with_view_box(view_box: (f64, f64, f64, f64), unit_suffix: &str) -> Self {
+        let mut writer = SvgWriter {
+            document: Document::new(),
+            view_box,
+            unit_suffix: unit_suffix.to_string(),
+            precision: DEFAULT_SVG_PRECISION,
+            max_points_per_path: DEFAULT_MAX_POINTS_PER_PATH,
+            origin: OriginMode::CenterOrigin,
+        };
+        writer.set_page();
+        writer
+    }
+
+    /// Round coordinates to `decimals` places
+    ///
+    /// Set it before drawing: nodes already added keep their rounding.
+    pub fn with_precision(mut self, decimals: usize) -> Self {
+        self.precision = decimals;
+        self.set_page();
+        self
+    }
+
+    /// Split polylines into paths of at most `max_points` points (see
+    /// [`split_polyline`](super::split_polyline))
+    ///
+    /// Set it before drawing: nodes already added keep their splitting.
+    pub fn with_max_points_per_path(mut self, max_points: usize) -> Self {
+        self.max_points_per_path = max_points;
+        self
+    }
+
+    /// Move everything drawn into `origin`'s frame when the document is finished
+    pub fn with_origin(mut self, origin: OriginMode) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Decimal places coordinates are rounded to
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Most points written to one `<path>`
+    pub fn max_points_per_path(&self) -> usize {
+        self.max_points_per_path
+    }
+
+    /// `value` rounded to the precision, without trailing zeros
+    pub fn number(&self, value: f64) -> String {
+        let mut text = format!("{:.*}", self.precision, value);
+        if text.contains('.') {
+            text.truncate(text.trim_end_matches('0').trim_end_matches('.').len());
+        }
+        if text == "-0" {
+            text.remove(0);
+        }
+        text
+    }
+
+    /// Path data through `points`, closed with `z` if `closed`
+    pub fn path_data(&self, points: &[Point2D], closed: bool) -> String {
+        let mut data = String::with_capacity(points.len() * 2 * (self.precision + 5));
+        for (i, point) in points.iter().enumerate() {
+            if i > 0 {
+                data.push(' ');
+            }
+            data.push(if i == 0 { 'M' } else { 'L' });
+            data.push_str(&self.number(point.x));
+            data.push(',');
+            data.push_str(&self.number(point.y));
+        }
+        if closed && !points.is_empty() {
+            data.push_str(" z");
+        }
+        data
+    }
+
+    /// Path data tracing each outline as a closed subpath
+    pub(crate) fn outlines_data(&self, outlines: &[Vec<Point2D>]) -> String {
+        outlines
+            .iter()
+            .filter(|outline| !outline.is_empty())
+            .map(|outline| self.path_data(outline, true))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// An open `<path>` through `points`, styled by `style`
+    pub fn polyline(&self, points: &[Point2D], style: impl FnOnce(Path) -> Path) -> Path {
+        style(Path::new()).set("d", self.path_data(points, false))
+    }
+
+    /// A `<circle>` of `radius` about `center`, styled by `style`
+    pub fn circle(
+        &self,
+        center: Point2D,
+        radius: f64,
+        style: impl FnOnce(Circle) -> Circle,
+    ) -> Circle {
+        style(Circle::new())
+            .set("cx", self.number(center.x))
+            .set("cy", self.number(center.y))
+            .set("r", self.number(radius))
+    }
+
+    /// SVG node for a polyline, split per [`split_polyline`]
+    ///
+    /// Returns a single `<path>`, or a `<g>` of consecutive paths when the
+    /// line exceeds [`max_points_per_path`](Self::max_points_per_path), so
+    /// it stays one logical line. `style`
+    /// applies the shared stroke attributes to every path. A closed line
+    /// that is split ends with an explicit segment back to its start
+    /// instead of `z`.
+    pub(crate) fn polyline_node(
+        &self,
+        points: &[Point2D],
+        closed: bool,
+        style: impl Fn(Path) -> Path,
+    ) -> Box<dyn Node> {
+        let runs = split_polyline(points, self.max_points_per_path);
+        if runs.len() == 1 {
+            return Box::new(style(Path::new()).set("d", self.path_data(runs[0], closed)));
+        }
+        let last = runs.len() - 1;
+        let mut group = Group::new();
+        for (i, run) in runs.into_iter().enumerate() {
+            let mut data = self.path_data(run, false);
+            if closed && i == last {
+                data.push_str(&format!(
+                    " L{},{}",
+                    self.number(points[0].x),
+                    self.number(points[0].y)
+                ));
+            }
+            group = group.add(style(Path::new()).set("d", data));
+        }
+        Box::new(group)
+    }
+
+    /// Add an open polyline through `points`, styled by `style`, split
+    /// into several paths when it exceeds
+    /// [`max_points_per_path`](Self::max_points_per_path)
+    pub fn add_polyline(&mut self, points: &[Point2D], style: impl Fn(Path) -> Path) {
+        let node = self.polyline_node(points, false, style);
+        self.add(node);
+    }
+
+    /// Add a circle of `radius` about `center`, styled by `style`
+    pub fn add_circle(
+        &mut self,
+        center: Point2D,
+        radius: f64,
+        style: impl FnOnce(Circle) -> Circle,
+    ) {
+        let circle = self.circle(center, radius, style);
+        self.add(circle);
+    }
+
+    /// Add any node, such as a group built with this writer's paths
+    pub fn add<T: Into<Box<dyn Node>>>(&mut self, node: T) {
+        self.document.append(node);
+    }
+
+    /// Apply `f` to the document, for attributes on the root element
+    pub(crate) fn map_document(mut self, f: impl FnOnce(Document) -> Document) -> Self {
+        self.document = f(self.document);
+        self
+    }
+
+    /// The finished document, moved into the origin's frame
+    pub fn into_document(self) -> Document {
+        self.origin.apply_svg(self.document)
+    }
+
+    /// Write the finished document to `filename`
+    pub fn save(self, filename: impl AsRef<FilePath>) -> Result<(), SpirographError> {
+        let filename = filename.as_ref();
+        svg::save(filename, &self.into_document()).map_err(|e| {
+            SpirographError::ExportError(format!(
+                "Failed to save SVG file '{}': {}",
+                filename.display(),
+                e
+            ))
+        })
+    }
+
+    /// Set the page size and viewBox at the current precision
+    fn set_page(&mut self) {
+        let (x, y, width, height) = self.view_box;
+        let view_box = [x, y, width, height]
+            .map(|value| self.number(value))
+            .join(" ");
+        let width = format!("{}{}", self.number(width), self.unit_suffix);
+        let height = format!("{}{}", self.number(height), self.unit_suffix);
+        let document = std::mem::replace(&mut self.document, Document::new());
+        self.document = document
+            .set("width", width)
+            .set("height", height)
+            .set("viewBox", view_box);
+    }
+}
+
+/// Write a standalone SVG one line at a time, for patterns too large to
+/// hold in memory
+///
+/// `lines` is called twice: once to frame the document as
+/// [`SvgWriter::framing`] does, then again to write each drawable line
+/// through `node` as it is produced. The file matches the one a framing
+/// writer splitting paths at `max_points_per_path`, with a node per
+/// drawable line, saves.
+pub(crate) fn write_svg_streaming<I>(
+    filename: &str,
+    max_points_per_path: usize,
+    lines: impl Fn() -> I,
+    node: impl Fn(&SvgWriter, &[Point2D]) -> Box<dyn Node>,
+) -> Result<(), SpirographError>
+where
+    I: Iterator<Item = Vec<Point2D>>,
+{
+    use std::io::Write;
+
+    let bounds = lines()
+        .filter_map(|line| drawable_bounds([line.as_slice()]).ok())
+        .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))
+        .ok_or_else(|| SpirographError::ExportError("no drawable geometry".to_string()))?;
+    let writer = SvgWriter::with_view_box(framed_view_box(bounds), "mm")
+        .with_max_points_per_path(max_points_per_path);
+    // A childless document closes its tag with "/>"
+    let canvas = writer.document.to_string();
+    let open_tag = canvas.strip_suffix("/>").unwrap_or(&canvas);
+
+    let save_err = |e: std::io::Error| {
+        SpirographError::ExportError(format!("Failed to save SVG file '{}': {}", filename, e))
+    };
+    let mut out = std::io::BufWriter::new(std::fs::File::create(filename).map_err(save_err)?);
+    write!(out, "{}>", open_tag).map_err(save_err)?;
+    for line in lines().filter(|line| !polyline_is_degenerate(line)) {
+        write!(out, "\n{}", node(&writer, &line)).map_err(save_err)?;
+    }
+    write!(out, "\n</svg>").map_err(save_err)?;
+    out.flush().map_err(save_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_are_rounded_without_trailing_zeros() {
+        let writer = SvgWriter::new((0.0, 0.0, 1.0, 1.0), 0.0);
+        assert_eq!(writer.number(1.0 / 3.0), "0.3333");
+        assert_eq!(writer.number(2.5), "2.5");
+        assert_eq!(writer.number(-7.00000049), "-7");
+        assert_eq!(writer.number(-0.00001), "0");
+        assert_eq!(writer.with_precision(0).number(12.6), "13");
+
+        let writer = SvgWriter::new((-1.0 / 3.0, 0.0, 1.0, 1.0), 0.0).with_precision(2);
+        let svg = writer.into_document().to_string();
+        assert!(svg.contains("viewBox=\"-0.33 0 1.33 1\""));
+        assert!(svg.contains("width=\"1.33mm\""));
+    }
+
+    #[test]
+    fn test_split_closed_polyline_returns_to_its_start() {
+        let writer = SvgWriter::new((0.0, 0.0, 1.0, 1.0), 0.0);
+        let square = [
+            Point2D::new(0.0, 0.0),
+            Point2D::new(1.0, 0.0),
+            Point2D::new(1.0, 1.0),
+            Point2D::new(0.0, 1.0),
+        ];
+        let whole = writer.polyline_node(&square, true, |path| path).to_string();
+        assert!(whole.contains("d=\"M0,0 L1,0 L1,1 L0,1 z\""));
+
+        let writer = writer.with_max_points_per_path(3);
+        let split = writer.polyline_node(&square, true, |path| path).to_string();
+        assert!(split.starts_with("<g>"));
+        assert!(split.contains("d=\"M0,0 L1,0 L1,1\""));
+        assert!(split.contains("d=\"M1,1 L0,1 L0,0\""));
+    }
+
+    #[test]
+    fn test_add_polyline_splits_at_the_writer_limit() {
+        let line: Vec<Point2D> = (0..25).map(|i| Point2D::new(i as f64, 0.0)).collect();
+
+        let mut writer = SvgWriter::new((0.0, 0.0, 24.0, 1.0), 0.0);
+        assert_eq!(writer.max_points_per_path(), DEFAULT_MAX_POINTS_PER_PATH);
+        writer.add_polyline(&line, |path| path);
+        assert_eq!(
+            writer.into_document().to_string().matches("<path").count(),
+            1
+        );
+
+        let mut writer = SvgWriter::new((0.0, 0.0, 24.0, 1.0), 0.0).with_max_points_per_path(10);
+        writer.add_polyline(&line, |path| path);
+        let svg = writer.into_document().to_string();
+        assert_eq!(svg.matches("<path").count(), 3);
+        assert!(svg.contains("d=\"M9,0 L10,0"));
+    }
+}
@@ -0,0 +1,616 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_polylines, polar_to_cartesian,
+    polyline_is_degenerate, scale_polylines, simplified_layer, simplify_polylines, ExportConfig,
+    PatternLayer, Point2D, PointsTotal, SpirographError, StoredLayer, DEDUPE_EPSILON,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
+
+/// Configuration for the Côtes de Genève (Geneva stripes) finish
+///
+/// The disc is covered by parallel stripes running along `angle`. Every
+/// line is a circular arc whose chord across the full diameter bows out
+/// by `arc_sagitta`, like the sweep of the grinding wheel, and all arcs
+/// share that radius so neighbouring lines stay evenly spaced. Each stripe
+/// is edged by a boundary line and holds a group of `lines_per_stripe`
+/// lines across its middle half, leaving a clear gap on either side of
+/// the boundary.
+///
+/// Lines are clipped to the circle of `radius` analytically, and to the
+/// annulus outside `inner_radius` when that is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CotesDeGeneveConfig {
+    /// Width of each stripe in mm
+    pub stripe_width: f64,
+    /// Number of stripes across the disc; when set, `stripe_width` is
+    /// ignored and the stripes divide the covered span evenly
+    pub num_stripes_override: Option<usize>,
+    /// How far each line bows out over a chord of the full diameter, in
+    /// mm; positive bows towards the left of `angle`, 0 draws straight
+    /// lines
+    pub arc_sagitta: f64,
+    /// Direction the stripes run in radians, counter-clockwise from +x
+    pub angle: f64,
+    /// Radius of the circular clipping region in mm
+    pub radius: f64,
+    /// Radius of the bare center in mm, 0 to fill the whole circle
+    pub inner_radius: f64,
+    /// Number of steps along each line
+    pub resolution: PointsTotal,
+    /// Number of lines in each stripe, between its boundary lines
+    pub lines_per_stripe: usize,
+}
+
+impl Default for CotesDeGeneveConfig {
+    fn default() -> Self {
+        CotesDeGeneveConfig {
+            stripe_width: 2.5,
+            num_stripes_override: None,
+            arc_sagitta: 0.5,
+            angle: 0.0,
+            radius: 15.0,
+            inner_radius: 0.0,
+            resolution: PointsTotal(100),
+            lines_per_stripe: 6,
+        }
+    }
+}
+
+impl CotesDeGeneveConfig {
+    /// Create a new Côtes de Genève configuration
+    ///
+    /// # Arguments
+    /// * `stripe_width` - Width of each stripe in mm
+    /// * `radius` - Radius of the circular clipping region in mm
+    pub fn new(stripe_width: f64, radius: f64) -> Self {
+        CotesDeGeneveConfig {
+            stripe_width,
+            radius,
+            ..Default::default()
+        }
+    }
+
+    /// Set the number of stripes, overriding the stripe width
+    pub fn with_num_stripes(mut self, num_stripes: usize) -> Self {
+        self.num_stripes_override = Some(num_stripes);
+        self
+    }
+
+    /// Set how far the lines bow over the full diameter, in mm
+    pub fn with_arc_sagitta(mut self, arc_sagitta: f64) -> Self {
+        self.arc_sagitta = arc_sagitta;
+        self
+    }
+
+    /// Set the direction the stripes run in radians
+    pub fn with_angle(mut self, angle: f64) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    /// Leave a bare center of `inner_radius` mm, filling an annulus
+    pub fn with_inner_radius(mut self, inner_radius: f64) -> Self {
+        self.inner_radius = inner_radius;
+        self
+    }
+
+    /// Set the number of lines in each stripe
+    pub fn with_lines_per_stripe(mut self, lines_per_stripe: usize) -> Self {
+        self.lines_per_stripe = lines_per_stripe;
+        self
+    }
+
+    /// Set the resolution (steps along each line)
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = resolution.into();
+        self
+    }
+
+    /// Check the stripe width and count, the radii, the sagitta and the
+    /// resolution
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if !(self.stripe_width.is_finite() && self.stripe_width > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "stripe_width must be positive".to_string(),
+            ));
+        }
+        if self.num_stripes_override == Some(0) {
+            return Err(SpirographError::InvalidParameter(
+                "num_stripes_override must be at least 1".to_string(),
+            ));
+        }
+        if !(self.radius.is_finite() && self.radius > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "radius must be positive".to_string(),
+            ));
+        }
+        if !(self.inner_radius.is_finite()
+            && self.inner_radius >= 0.0
+            && self.inner_radius < self.radius)
+        {
+            return Err(SpirographError::InvalidParameter(
+                "inner_radius must be non-negative and less than radius".to_string(),
+            ));
+        }
+        if !(self.arc_sagitta.is_finite() && self.arc_sagitta.abs() <= self.radius) {
+            return Err(SpirographError::InvalidParameter(
+                "arc_sagitta must be no more than radius".to_string(),
+            ));
+        }
+        if self.resolution.get() < 1 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Number of stripes and their width in mm
+    ///
+    /// The stripes span the diameter plus the sagitta, so the bowed lines
+    /// still reach every part of the disc.
+    pub fn stripe_layout(&self) -> (usize, f64) {
+        let span = 2.0 * self.radius + self.arc_sagitta.abs();
+        match self.num_stripes_override {
+            Some(n) => (n, span / n as f64),
+            None => (
+                (span / self.stripe_width).ceil().max(1.0) as usize,
+                self.stripe_width,
+            ),
+        }
+    }
+
+    /// Offsets of every line across the stripes, each with whether it is
+    /// a stripe boundary, in order
+    ///
+    /// An offset is where the line crosses the axis through the center
+    /// perpendicular to `angle`, measured to the left of `angle`. The
+    /// stripes are centered on half the sagitta.
+    pub fn line_offsets(&self) -> Vec<(f64, bool)> {
+        let (n, width) = self.stripe_layout();
+        let start = self.arc_sagitta / 2.0 - width * n as f64 / 2.0;
+        let per_stripe = self.lines_per_stripe;
+        let mut offsets = Vec::with_capacity(n * (per_stripe + 1) + 1);
+        for k in 0..=n {
+            let edge = start + width * k as f64;
+            offsets.push((edge, true));
+            if k == n {
+                break;
+            }
+            for j in 0..per_stripe {
+                let across = 0.25 + 0.5 * (j as f64 + 0.5) / per_stripe as f64;
+                offsets.push((edge + width * across, false));
+            }
+        }
+        offsets
+    }
+
+    /// Half-widths along the stripe direction where the line at `offset`
+    /// runs inside the circle of `clip_radius`, or `None` when it misses
+    ///
+    /// Each line is the top of a circle of the shared arc radius, so it
+    /// meets any circle about the center symmetrically.
+    fn half_span(&self, offset: f64, clip_radius: f64) -> Option<f64> {
+        // Work with the bow pointing to +offset
+        let v = offset * self.arc_sagitta.signum();
+        if v.abs() >= clip_radius {
+            return None;
+        }
+        let s = self.arc_sagitta.abs();
+        if s == 0.0 {
+            return Some((clip_radius * clip_radius - v * v).sqrt());
+        }
+        // Arc radius rho with center c below the apex v; points are
+        // (u, c + sqrt(rho² - u²)), which is clip_radius out where
+        // sqrt(rho² - u²) = (R² - c² - rho²) / 2c
+        let r = self.radius;
+        let rho = (r * r + s * s) / (2.0 * s);
+        let c = v - rho;
+        let h = ((clip_radius * clip_radius - c * c - rho * rho) / (2.0 * c)).clamp(0.0, rho);
+        Some((rho * rho - h * h).sqrt())
+    }
+
+    /// Point at `u` along the stripe direction on the line at `offset`,
+    /// about `center`
+    fn line_point(&self, offset: f64, u: f64, center: Point2D) -> Point2D {
+        let s = self.arc_sagitta.abs();
+        let v = if s == 0.0 {
+            offset
+        } else {
+            let rho = (self.radius * self.radius + s * s) / (2.0 * s);
+            offset + self.arc_sagitta.signum() * ((rho * rho - u * u).max(0.0).sqrt() - rho)
+        };
+        let (sin_t, cos_t) = self.angle.sin_cos();
+        Point2D::new(
+            center.x + u * cos_t - v * sin_t,
+            center.y + u * sin_t + v * cos_t,
+        )
+    }
+
+    /// Every stripe line about `center`, clipped to the circle or annulus
+    ///
+    /// A line crossing the bare center comes out as two pieces, each with
+    /// `resolution` steps. Lines that miss the disc are left out.
+    pub(crate) fn stripe_lines(&self, center: Point2D) -> Vec<Vec<Point2D>> {
+        let steps = self.resolution.get();
+        let sample = |offset: f64, from: f64, to: f64| -> Vec<Point2D> {
+            (0..=steps)
+                .map(|k| {
+                    let u = from + (to - from) * (k as f64) / (steps as f64);
+                    self.line_point(offset, u, center)
+                })
+                .collect()
+        };
+
+        let mut lines = Vec::new();
+        for (offset, _) in self.line_offsets() {
+            let Some(outer) = self.half_span(offset, self.radius) else {
+                continue;
+            };
+            if outer <= 0.0 {
+                continue;
+            }
+            match self
+                .half_span(offset, self.inner_radius)
+                .filter(|&inner| self.inner_radius > 0.0 && inner > 0.0)
+            {
+                Some(inner) => {
+                    lines.push(sample(offset, -outer, -inner));
+                    lines.push(sample(offset, inner, outer));
+                }
+                None => lines.push(sample(offset, -outer, outer)),
+            }
+        }
+        lines
+    }
+}
+
+/// A Côtes de Genève (Geneva stripes) pattern layer
+///
+/// Generates the gently arced stripe lines across a circle or annulus
+/// about the layer's center; see `CotesDeGeneveConfig` for the layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CotesDeGeneveLayer {
+    pub config: CotesDeGeneveConfig,
+    pub center_x: f64,
+    pub center_y: f64,
+    #[serde(skip)]
+    lines: Vec<Vec<Point2D>>,
+}
+
+impl CotesDeGeneveLayer {
+    /// Create a new Côtes de Genève layer centered at origin
+    pub fn new(config: CotesDeGeneveConfig) -> Result<Self, SpirographError> {
+        Self::new_with_center(config, 0.0, 0.0)
+    }
+
+    /// Create a new Côtes de Genève layer with a custom center point
+    pub fn new_with_center(
+        config: CotesDeGeneveConfig,
+        center_x: f64,
+        center_y: f64,
+    ) -> Result<Self, SpirographError> {
+        config.validate()?;
+
+        Ok(CotesDeGeneveLayer {
+            config,
+            center_x,
+            center_y,
+            lines: Vec::new(),
+        })
+    }
+
+    /// Create a Côtes de Genève layer positioned at a given angle and
+    /// distance from origin
+    pub fn new_at_polar(
+        config: CotesDeGeneveConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (center_x, center_y) = polar_to_cartesian(angle, distance);
+        Self::new_with_center(config, center_x, center_y)
+    }
+
+    /// Create a Côtes de Genève layer positioned at a clock position
+    ///
+    /// # Arguments
+    /// * `config` - Côtes de Genève configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn new_at_clock(
+        config: CotesDeGeneveConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (center_x, center_y) = clock_to_cartesian(hour, minute, distance);
+        Self::new_with_center(config, center_x, center_y)
+    }
+
+    /// Generate the stripe lines
+    pub fn generate(&mut self) {
+        self.lines = self
+            .config
+            .stripe_lines(Point2D::new(self.center_x, self.center_y));
+        dedupe_polylines(&mut self.lines, DEDUPE_EPSILON);
+        debug_assert_finite("CotesDeGeneveLayer", &self.lines);
+    }
+
+    /// Get the generated lines
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.lines
+    }
+
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply every length (config, center and generated lines) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.stripe_width *= factor;
+        self.config.arc_sagitta *= factor;
+        self.config.radius *= factor;
+        self.config.inner_radius *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.lines, factor);
+    }
+
+    /// Export the pattern to SVG format
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+
+        if self.lines.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.lines.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        for line in &layer.lines {
+            if polyline_is_degenerate(line) {
+                continue;
+            }
+
+            writer.add_polyline(line, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+            });
+        }
+
+        writer.save(filename)
+    }
+}
+
+impl PatternLayer for CotesDeGeneveLayer {
+    fn generate(&mut self) {
+        CotesDeGeneveLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for CotesDeGeneveLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        CotesDeGeneveLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for CotesDeGeneveLayer {
+    const KIND: LayerKind = LayerKind::CotesDeGeneve;
+}
+
+impl Progressive for CotesDeGeneveLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
+        }
+
+        // The stripes are the finish; only the sampling along each line is reduced
+        let mut coarse = self.clone();
+        coarse.config.resolution =
+            PointsTotal(scale_count(self.config.resolution.get(), fraction, 4));
+        coarse.generate();
+        self.lines = coarse.lines;
+    }
+}
+
+impl PointCounted for CotesDeGeneveLayer {
+    fn point_count(&self) -> usize {
+        self.lines.iter().map(Vec::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cotes_de_geneve_invalid_params() {
+        assert!(CotesDeGeneveLayer::new(CotesDeGeneveConfig::default()).is_ok());
+        assert!(CotesDeGeneveLayer::new(CotesDeGeneveConfig::new(0.0, 10.0)).is_err());
+        assert!(CotesDeGeneveLayer::new(CotesDeGeneveConfig::new(2.0, -1.0)).is_err());
+        let no_stripes = CotesDeGeneveConfig::new(2.0, 10.0).with_num_stripes(0);
+        assert!(CotesDeGeneveLayer::new(no_stripes).is_err());
+        let filled_hole = CotesDeGeneveConfig::new(2.0, 10.0).with_inner_radius(10.0);
+        assert!(CotesDeGeneveLayer::new(filled_hole).is_err());
+        let too_bowed = CotesDeGeneveConfig::new(2.0, 10.0).with_arc_sagitta(-10.5);
+        assert!(CotesDeGeneveLayer::new(too_bowed).is_err());
+
+        // Edited after construction, the config is caught by try_generate
+        let mut layer = CotesDeGeneveLayer::new(CotesDeGeneveConfig::new(2.0, 10.0)).unwrap();
+        layer.config.inner_radius = 12.0;
+        assert!(layer.try_generate().is_err());
+        assert!(layer.lines().is_empty());
+    }
+
+    #[test]
+    fn test_stripe_layout_covers_diameter_and_sagitta() {
+        let config = CotesDeGeneveConfig::new(3.0, 10.0).with_arc_sagitta(1.0);
+        assert_eq!(config.stripe_layout(), (7, 3.0));
+        let config = config.with_num_stripes(5).with_lines_per_stripe(3);
+        assert_eq!(config.stripe_layout(), (5, 4.2));
+
+        let offsets = config.line_offsets();
+        assert_eq!(offsets.len(), 5 * 4 + 1);
+        let boundaries: Vec<f64> = offsets.iter().filter(|o| o.1).map(|o| o.0).collect();
+        assert_eq!(boundaries.len(), 6);
+        assert!((boundaries[0] + 10.0).abs() < 1e-12 && (boundaries[5] - 11.0).abs() < 1e-12);
+
+        // Each group sits in the middle half of its stripe, so the gap at a
+        // boundary is wider than the spacing inside the group
+        let first_group: Vec<f64> = offsets[1..4].iter().map(|o| o.0).collect();
+        let spacing = first_group[1] - first_group[0];
+        assert!(first_group[0] - boundaries[0] > spacing);
+        assert!(boundaries[1] - first_group[2] > spacing);
+    }
+
+    #[test]
+    fn test_lines_are_arcs_clipped_to_the_circle() {
+        for sagitta in [0.0, 0.8, -0.8] {
+            let config = CotesDeGeneveConfig::new(2.0, 10.0)
+                .with_arc_sagitta(sagitta)
+                .with_angle(0.6)
+                .with_resolution(40);
+            let mut layer = CotesDeGeneveLayer::new_with_center(config.clone(), 2.0, -1.0).unwrap();
+            layer.generate();
+            assert!(!layer.lines().is_empty());
+
+            let (sin_t, cos_t) = 0.6f64.sin_cos();
+            let local = |p: Point2D| {
+                let (dx, dy) = (p.x - 2.0, p.y + 1.0);
+                (dx * cos_t + dy * sin_t, -dx * sin_t + dy * cos_t)
+            };
+            for line in layer.lines() {
+                // Both ends on the circle, nothing outside it
+                for end in [line[0], line[line.len() - 1]] {
+                    assert!(((end.x - 2.0).hypot(end.y + 1.0) - 10.0).abs() < 1e-9);
+                }
+                for p in line {
+                    assert!((p.x - 2.0).hypot(p.y + 1.0) <= 10.0 + 1e-9);
+                }
+                // Every line bows by the same arc: the drop from the apex
+                // grows with the distance along the stripe
+                let apex = local(line[line.len() / 2]);
+                for &p in line {
+                    let (u, v) = local(p);
+                    let s = sagitta.abs();
+                    let drop = if s == 0.0 {
+                        0.0
+                    } else {
+                        let rho = (100.0 + s * s) / (2.0 * s);
+                        rho - (rho * rho - u * u).sqrt()
+                    };
+                    assert!((apex.1 - sagitta.signum() * drop - v).abs() < 1e-9);
+                }
+            }
+        }
+
+        // A full-diameter chord bows by exactly the sagitta
+        let config = CotesDeGeneveConfig::new(2.0, 10.0).with_arc_sagitta(0.8);
+        let p = config.line_point(0.0, 10.0, Point2D::new(0.0, 0.0));
+        assert!((p.y + 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_annulus_splits_lines_through_the_center() {
+        let config = CotesDeGeneveConfig::new(2.0, 10.0)
+            .with_inner_radius(4.0)
+            .with_lines_per_stripe(2)
+            .with_resolution(20);
+        let mut layer = CotesDeGeneveLayer::new(config.clone()).unwrap();
+        layer.generate();
+
+        let crossing = config
+            .line_offsets()
+            .iter()
+            .filter(|(offset, _)| config.half_span(*offset, 4.0).is_some_and(|h| h > 0.0))
+            .count();
+        assert!(crossing > 0);
+        let inside = config
+            .line_offsets()
+            .iter()
+            .filter(|(offset, _)| config.half_span(*offset, 10.0).is_some_and(|h| h > 0.0))
+            .count();
+        assert_eq!(layer.lines().len(), inside + crossing);
+        for p in layer.lines().iter().flatten() {
+            let r = p.x.hypot(p.y);
+            assert!((4.0 - 1e-9..=10.0 + 1e-9).contains(&r));
+        }
+    }
+
+    #[test]
+    fn test_cotes_de_geneve_composes_into_pattern() {
+        use crate::guilloche::GuillochePattern;
+
+        let mut pattern = GuillochePattern::new(30.0).unwrap();
+        pattern
+            .add_cotes_de_geneve_at_clock(CotesDeGeneveConfig::new(3.0, 20.0), 12, 0, 0.0)
+            .unwrap();
+        pattern.generate();
+        assert_eq!(pattern.kind_count(LayerKind::CotesDeGeneve), 1);
+        assert!(!pattern.cotes_de_geneve_lines()[0].is_empty());
+        assert_eq!(
+            LayerKind::from_name("cotes_de_geneve"),
+            Some(LayerKind::CotesDeGeneve)
+        );
+    }
+}
@@ -1,4 +1,13 @@
-use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographError};
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_polylines, polar_to_cartesian,
+    polyline_is_degenerate, scale_polylines, simplified_layer, simplify_polylines, ExportConfig,
+    PatternLayer, Point2D, PointsTotal, SpirographError, StoredLayer, DEDUPE_EPSILON,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
 
 /// Configuration for the Cube (tumbling blocks) guilloché pattern
 ///
@@ -21,7 +30,7 @@ use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographE
 /// | `gap_per_group`   | Number of line-spacings of empty gap between groups |
 /// | `amplitude`       | Half peak-to-trough zigzag height (0 = auto so diamonds close) |
 /// | `leg_angle`       | Angle of each zigzag leg from horizontal in degrees |
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CubeConfig {
     /// Spacing between adjacent zigzag lines in mm
     pub spacing: f64,
@@ -30,7 +39,7 @@ pub struct CubeConfig {
     /// Base rotation angle of the pattern in radians
     pub angle: f64,
     /// Number of sample points per line (kept for API compatibility)
-    pub resolution: usize,
+    pub resolution: PointsTotal,
     /// Number of zigzag lines per cutting group
     pub cuts_per_group: usize,
     /// Number of line-spacings of empty gap between groups
@@ -47,7 +56,7 @@ impl Default for CubeConfig {
             spacing: 0.5,
             radius: 22.0,
             angle: 0.0,
-            resolution: 200,
+            resolution: PointsTotal(200),
             cuts_per_group: 8,
             gap_per_group: 8,
             amplitude: 0.0,
@@ -71,10 +80,51 @@ impl CubeConfig {
     }
 
     /// Set the resolution (points per line)
-    pub fn with_resolution(mut self, resolution: usize) -> Self {
-        self.resolution = resolution;
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = resolution.into();
         self
     }
+
+    /// Check the spacing, radius, resolution and cut grouping
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.spacing <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "spacing must be positive".to_string(),
+            ));
+        }
+        if self.radius <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "radius must be positive".to_string(),
+            ));
+        }
+        if self.resolution.get() < 2 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 2".to_string(),
+            ));
+        }
+        if self.cuts_per_group < 1 {
+            return Err(SpirographError::InvalidParameter(
+                "cuts_per_group must be at least 1".to_string(),
+            ));
+        }
+        if self.gap_per_group < 1 {
+            return Err(SpirographError::InvalidParameter(
+                "gap_per_group must be at least 1".to_string(),
+            ));
+        }
+        if self.leg_angle <= 0.0 || self.leg_angle >= 90.0 {
+            return Err(SpirographError::InvalidParameter(
+                "leg_angle must be between 0 and 90 degrees (exclusive)".to_string(),
+            ));
+        }
+        if self.amplitude < 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "amplitude must be non-negative (0 = auto)".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 /// A Cube (tumbling blocks) pattern layer
@@ -84,11 +134,12 @@ impl CubeConfig {
 /// groups are phase-shifted by half a zigzag period, creating interlocking
 /// diamond-shaped uncut regions that produce the optical illusion of
 /// three-dimensional cubes.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CubeLayer {
     pub config: CubeConfig,
     pub center_x: f64,
     pub center_y: f64,
+    #[serde(skip)]
     lines: Vec<Vec<Point2D>>,
 }
 
@@ -159,41 +210,8 @@ impl CubeLayer {
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        if config.spacing <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "spacing must be positive".to_string(),
-            ));
-        }
-        if config.radius <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "radius must be positive".to_string(),
-            ));
-        }
-        if config.resolution < 2 {
-            return Err(SpirographError::InvalidParameter(
-                "resolution must be at least 2".to_string(),
-            ));
-        }
-        if config.cuts_per_group < 1 {
-            return Err(SpirographError::InvalidParameter(
-                "cuts_per_group must be at least 1".to_string(),
-            ));
-        }
-        if config.gap_per_group < 1 {
-            return Err(SpirographError::InvalidParameter(
-                "gap_per_group must be at least 1".to_string(),
-            ));
-        }
-        if config.leg_angle <= 0.0 || config.leg_angle >= 90.0 {
-            return Err(SpirographError::InvalidParameter(
-                "leg_angle must be between 0 and 90 degrees (exclusive)".to_string(),
-            ));
-        }
-        if config.amplitude < 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "amplitude must be non-negative (0 = auto)".to_string(),
-            ));
-        }
+        config.validate()?;
+
         Ok(CubeLayer {
             config,
             center_x,
@@ -353,6 +371,9 @@ impl CubeLayer {
                 }
             }
         }
+
+        dedupe_polylines(&mut self.lines, DEDUPE_EPSILON);
+        debug_assert_finite("CubeLayer", &self.lines);
     }
 
     /// Get the generated lines
@@ -360,10 +381,36 @@ impl CubeLayer {
         &self.lines
     }
 
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply every length (config, center and generated lines) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.spacing *= factor;
+        self.config.radius *= factor;
+        self.config.amplitude *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.lines, factor);
+    }
+
     /// Export the pattern to SVG format
     pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        use svg::node::element::{path::Data, Path};
-        use svg::Document;
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
 
         if self.lines.is_empty() {
             return Err(SpirographError::ExportError(
@@ -371,52 +418,73 @@ impl CubeLayer {
             ));
         }
 
-        // Find bounds
-        let mut min_x = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.lines.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
 
-        for line in &self.lines {
-            for point in line {
-                min_x = min_x.min(point.x);
-                max_x = max_x.max(point.x);
-                min_y = min_y.min(point.y);
-                max_y = max_y.max(point.y);
+        for line in &layer.lines {
+            if polyline_is_degenerate(line) {
+                continue;
             }
+
+            writer.add_polyline(line, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+            });
         }
 
-        let margin = 5.0;
-        let width = max_x - min_x + 2.0 * margin;
-        let height = max_y - min_y + 2.0 * margin;
+        writer.save(filename)
+    }
+}
 
-        let mut document = Document::new()
-            .set("width", format!("{}mm", width))
-            .set("height", format!("{}mm", height))
-            .set("viewBox", (min_x - margin, min_y - margin, width, height));
+impl PatternLayer for CubeLayer {
+    fn generate(&mut self) {
+        CubeLayer::generate(self)
+    }
 
-        for line in &self.lines {
-            if line.is_empty() {
-                continue;
-            }
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
 
-            let mut data = Data::new().move_to((line[0].x, line[0].y));
-            for point in line.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
 
-            let path = Path::new()
-                .set("d", data)
-                .set("fill", "none")
-                .set("stroke", "black")
-                .set("stroke-width", 0.05);
+impl StoredLayer for CubeLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
 
-            document = document.add(path);
-        }
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        CubeLayer::scale_lengths(self, factor);
+    }
 
-        svg::save(filename, &document)
-            .map_err(|e| SpirographError::ExportError(format!("Failed to save SVG: {}", e)))
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
     }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+}
+
+impl LayerType for CubeLayer {
+    const KIND: LayerKind = LayerKind::Cube;
 }
 
 #[cfg(test)]
@@ -429,7 +497,7 @@ mod tests {
         assert!((config.spacing - 0.5).abs() < 1e-10);
         assert!((config.radius - 22.0).abs() < 1e-10);
         assert!((config.angle - 0.0).abs() < 1e-10);
-        assert_eq!(config.resolution, 200);
+        assert_eq!(config.resolution, PointsTotal(200));
         assert_eq!(config.cuts_per_group, 8);
         assert_eq!(config.gap_per_group, 8);
         assert!((config.amplitude - 0.0).abs() < 1e-10);
@@ -475,7 +543,7 @@ mod tests {
 
         // low resolution
         let config = CubeConfig {
-            resolution: 1,
+            resolution: PointsTotal(1),
             ..Default::default()
         };
         assert!(CubeLayer::new(config).is_err());
@@ -522,7 +590,7 @@ mod tests {
             spacing: 1.0,
             radius: 10.0,
             angle: 0.0,
-            resolution: 100,
+            resolution: PointsTotal(100),
             ..Default::default()
         };
         let mut layer = CubeLayer::new(config).unwrap();
@@ -539,7 +607,7 @@ mod tests {
             spacing: 3.0,
             radius: 10.0,
             angle: 0.0,
-            resolution: 200,
+            resolution: PointsTotal(200),
             ..Default::default()
         };
         let mut layer = CubeLayer::new(config).unwrap();
@@ -569,7 +637,7 @@ mod tests {
             spacing: 2.0,
             radius: 20.0,
             angle: 0.0,
-            resolution: 50,
+            resolution: PointsTotal(50),
             cuts_per_group: 4,
             gap_per_group: 4,
             ..Default::default()
@@ -629,14 +697,14 @@ mod tests {
             spacing: 1.0,
             radius: 10.0,
             angle: 0.0,
-            resolution: 20,
+            resolution: PointsTotal(20),
             ..Default::default()
         };
         let config_30 = CubeConfig {
             spacing: 1.0,
             radius: 10.0,
             angle: PI / 6.0,
-            resolution: 20,
+            resolution: PointsTotal(20),
             ..Default::default()
         };
 
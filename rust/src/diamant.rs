@@ -1,20 +1,55 @@
 use std::f64::consts::PI;
 
-use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographError};
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_polylines, polar_to_cartesian,
+    polyline_is_degenerate, scale_polylines, simplified_layer, simplify_polylines, ExportConfig,
+    PatternLayer, Point2D, PointsTotal, SpirographError, StoredLayer, DEDUPE_EPSILON,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
 
 /// Configuration for the Diamant (Diamond) guilloché pattern
 ///
 /// The diamant pattern is formed by drawing equally-sized circles that are
 /// tangent to the center point, rotated around the center at different angles.
 /// The overlapping circles create the characteristic diamond/mesh appearance.
-#[derive(Debug, Clone)]
+///
+/// Further rings repeat the figure outwards: ring `k` has `num_circles`
+/// circles of radius `circle_radius * scale_per_ring^k` whose centers lie
+/// `circle_radius + k * ring_spacing` from the pattern center. With one
+/// ring only the first, tangent to the center, is drawn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiamantConfig {
     /// Number of circles to draw (more = denser mesh)
     pub num_circles: usize,
     /// Radius of each individual circle
     pub circle_radius: f64,
     /// Resolution - number of points per circle
-    pub resolution: usize,
+    pub resolution: PointsTotal,
+    /// Number of concentric rings of circles
+    #[serde(default = "one_ring")]
+    pub num_rings: usize,
+    /// Radial distance between the circle centers of neighbouring rings
+    #[serde(default)]
+    pub ring_spacing: f64,
+    /// Factor applied to the circle radius from one ring to the next
+    #[serde(default = "unscaled")]
+    pub scale_per_ring: f64,
+    /// Turn every other ring by half the angle between its circles
+    #[serde(default)]
+    pub stagger: bool,
+}
+
+fn one_ring() -> usize {
+    1
+}
+
+fn unscaled() -> f64 {
+    1.0
 }
 
 impl Default for DiamantConfig {
@@ -22,7 +57,11 @@ impl Default for DiamantConfig {
         DiamantConfig {
             num_circles: 72,
             circle_radius: 20.0,
-            resolution: 360,
+            resolution: PointsTotal(360),
+            num_rings: 1,
+            ring_spacing: 0.0,
+            scale_per_ring: 1.0,
+            stagger: false,
         }
     }
 }
@@ -37,15 +76,124 @@ impl DiamantConfig {
         DiamantConfig {
             num_circles,
             circle_radius,
-            resolution: 360,
+            ..Default::default()
         }
     }
 
     /// Set the resolution (points per circle)
-    pub fn with_resolution(mut self, resolution: usize) -> Self {
-        self.resolution = resolution;
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = resolution.into();
+        self
+    }
+
+    /// Draw `num_rings` rings, `ring_spacing` apart, each with circles
+    /// `scale_per_ring` times the size of the ring inside it
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{DiamantConfig, DiamantLayer};
+    ///
+    /// let config = DiamantConfig::new(24, 6.0).with_rings(3, 5.0, 0.8).with_stagger(true);
+    /// let mut diamant = DiamantLayer::new(config).unwrap();
+    /// diamant.generate();
+    /// assert_eq!(diamant.circles().len(), 72);
+    /// ```
+    pub fn with_rings(mut self, num_rings: usize, ring_spacing: f64, scale_per_ring: f64) -> Self {
+        self.num_rings = num_rings;
+        self.ring_spacing = ring_spacing;
+        self.scale_per_ring = scale_per_ring;
         self
     }
+
+    /// Set whether every other ring is turned by half a circle
+    pub fn with_stagger(mut self, stagger: bool) -> Self {
+        self.stagger = stagger;
+        self
+    }
+
+    /// Check the circle count, radius, rings and resolution
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.circle_radius <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "circle_radius must be positive".to_string(),
+            ));
+        }
+
+        if self.num_circles == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "num_circles must be at least 1".to_string(),
+            ));
+        }
+
+        if self.num_rings == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "num_rings must be at least 1".to_string(),
+            ));
+        }
+
+        if !(self.ring_spacing.is_finite() && self.ring_spacing >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "ring_spacing must be non-negative and finite".to_string(),
+            ));
+        }
+
+        if !(self.scale_per_ring.is_finite() && self.scale_per_ring > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "scale_per_ring must be positive and finite".to_string(),
+            ));
+        }
+
+        if self.resolution.get() < 10 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 10".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Every circle about `center`, ring by ring from the one tangent to
+    /// the center and counter-clockwise around each ring from the +x axis
+    ///
+    /// Each circle is sampled at `resolution` steps from its +x point and
+    /// closed on it. Shared by `DiamantLayer` and the rose-engine
+    /// equivalent.
+    pub(crate) fn circles(&self, center: Point2D) -> Vec<Vec<Point2D>> {
+        let angle_step = 2.0 * PI / (self.num_circles as f64);
+        let res = self.resolution.get();
+
+        let mut circles = Vec::with_capacity(self.num_rings * self.num_circles);
+        for ring in 0..self.num_rings {
+            let r = self.circle_radius * self.scale_per_ring.powi(ring as i32);
+            // The first ring's circles are tangent to the center
+            let distance = self.circle_radius + ring as f64 * self.ring_spacing;
+            let offset = if self.stagger && ring % 2 == 1 {
+                angle_step / 2.0
+            } else {
+                0.0
+            };
+
+            for i in 0..self.num_circles {
+                // Angle for this circle's center position
+                let rotation_angle = (i as f64) * angle_step + offset;
+                let circle_center_x = center.x + distance * rotation_angle.cos();
+                let circle_center_y = center.y + distance * rotation_angle.sin();
+
+                let mut circle_points = Vec::with_capacity(res + 1);
+                for j in 0..=res {
+                    let t = (j as f64) / (res as f64);
+                    let angle = 2.0 * PI * t;
+
+                    let x = circle_center_x + r * angle.cos();
+                    let y = circle_center_y + r * angle.sin();
+
+                    circle_points.push(Point2D::new(x, y));
+                }
+                circles.push(circle_points);
+            }
+        }
+        circles
+    }
 }
 
 /// A Diamant pattern layer that creates the diamond guilloché effect
@@ -53,11 +201,12 @@ impl DiamantConfig {
 /// This pattern is created by drawing circles that are tangent to the center
 /// and rotated at equal angular intervals. The overlapping circles create
 /// diamond-shaped intersection patterns that radiate from the center.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiamantLayer {
     pub config: DiamantConfig,
     pub center_x: f64,
     pub center_y: f64,
+    #[serde(skip)]
     circles: Vec<Vec<Point2D>>,
 }
 
@@ -73,23 +222,7 @@ impl DiamantLayer {
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        if config.circle_radius <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "circle_radius must be positive".to_string(),
-            ));
-        }
-
-        if config.num_circles == 0 {
-            return Err(SpirographError::InvalidParameter(
-                "num_circles must be at least 1".to_string(),
-            ));
-        }
-
-        if config.resolution < 10 {
-            return Err(SpirographError::InvalidParameter(
-                "resolution must be at least 10".to_string(),
-            ));
-        }
+        config.validate()?;
 
         Ok(DiamantLayer {
             config,
@@ -128,39 +261,17 @@ impl DiamantLayer {
 
     /// Generate the diamant pattern
     ///
-    /// Each circle is positioned so that it is tangent to the center point.
-    /// The center of each circle is at distance `circle_radius` from the origin,
-    /// at an angle determined by dividing the full rotation among all circles.
+    /// Each circle of the first ring is positioned so that it is tangent to
+    /// the center point: its center is at distance `circle_radius` from the
+    /// layer center, at an angle determined by dividing the full rotation
+    /// among all circles. Further rings follow `DiamantConfig`'s layout.
     pub fn generate(&mut self) {
-        self.circles.clear();
-
-        let angle_step = 2.0 * PI / (self.config.num_circles as f64);
-        let r = self.config.circle_radius;
-
-        for i in 0..self.config.num_circles {
-            // Angle for this circle's center position
-            let rotation_angle = (i as f64) * angle_step;
-
-            // Position the center of this circle at distance r from origin
-            // This makes the circle tangent to the origin
-            let circle_center_x = self.center_x + r * rotation_angle.cos();
-            let circle_center_y = self.center_y + r * rotation_angle.sin();
-
-            // Generate points along this circle
-            let mut circle_points = Vec::with_capacity(self.config.resolution + 1);
-
-            for j in 0..=self.config.resolution {
-                let t = (j as f64) / (self.config.resolution as f64);
-                let angle = 2.0 * PI * t;
+        self.circles = self
+            .config
+            .circles(Point2D::new(self.center_x, self.center_y));
 
-                let x = circle_center_x + r * angle.cos();
-                let y = circle_center_y + r * angle.sin();
-
-                circle_points.push(Point2D::new(x, y));
-            }
-
-            self.circles.push(circle_points);
-        }
+        dedupe_polylines(&mut self.circles, DEDUPE_EPSILON);
+        debug_assert_finite("DiamantLayer", &self.circles);
     }
 
     /// Get the generated circles as a vector of point vectors
@@ -173,10 +284,35 @@ impl DiamantLayer {
         &self.circles
     }
 
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.circles
+    }
+
+    /// Multiply every length (config, center and generated circles) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.circle_radius *= factor;
+        self.config.ring_spacing *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.circles, factor);
+    }
+
     /// Export the pattern to SVG format
     pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        use svg::node::element::{path::Data, Path};
-        use svg::Document;
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
 
         if self.circles.is_empty() {
             return Err(SpirographError::ExportError(
@@ -184,66 +320,113 @@ impl DiamantLayer {
             ));
         }
 
-        // Find bounds
-        let mut min_x = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
-
-        for circle in &self.circles {
-            for point in circle {
-                min_x = min_x.min(point.x);
-                max_x = max_x.max(point.x);
-                min_y = min_y.min(point.y);
-                max_y = max_y.max(point.y);
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.circles.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        // Draw each circle
+        for circle in &layer.circles {
+            if polyline_is_degenerate(circle) {
+                continue;
             }
+
+            writer.add_polyline(circle, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+            });
         }
 
-        let margin = 5.0;
-        let width = max_x - min_x + 2.0 * margin;
-        let height = max_y - min_y + 2.0 * margin;
+        writer.save(filename)
+    }
+}
 
-        let mut document = Document::new()
-            .set("width", format!("{}mm", width))
-            .set("height", format!("{}mm", height))
-            .set("viewBox", (min_x - margin, min_y - margin, width, height));
+impl PatternLayer for DiamantLayer {
+    fn generate(&mut self) {
+        DiamantLayer::generate(self)
+    }
 
-        // Draw each circle
-        for circle in &self.circles {
-            if circle.is_empty() {
-                continue;
-            }
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
 
-            let mut data = Data::new().move_to((circle[0].x, circle[0].y));
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
 
-            for point in circle.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
+impl StoredLayer for DiamantLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
 
-            let path = Path::new()
-                .set("d", data)
-                .set("fill", "none")
-                .set("stroke", "black")
-                .set("stroke-width", 0.05);
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
 
-            document = document.add(path);
+    fn scale_lengths(&mut self, factor: f64) {
+        DiamantLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for DiamantLayer {
+    const KIND: LayerKind = LayerKind::Diamant;
+}
+
+impl Progressive for DiamantLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
         }
 
-        svg::save(filename, &document)
-            .map_err(|e| SpirographError::ExportError(format!("Failed to save SVG: {}", e)))
+        // The circle count defines the rosette; only sampling is reduced
+        let mut coarse = self.clone();
+        coarse.config.resolution =
+            PointsTotal(scale_count(self.config.resolution.get(), fraction, 10));
+        coarse.generate();
+        self.circles = coarse.circles;
+    }
+}
+
+impl PointCounted for DiamantLayer {
+    fn point_count(&self) -> usize {
+        self.circles.iter().map(Vec::len).sum()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::drawable_bounds;
 
     #[test]
     fn test_diamant_config_default() {
         let config = DiamantConfig::default();
         assert_eq!(config.num_circles, 72);
         assert_eq!(config.circle_radius, 20.0);
-        assert_eq!(config.resolution, 360);
+        assert_eq!(config.resolution, PointsTotal(360));
     }
 
     #[test]
@@ -277,6 +460,25 @@ mod tests {
         assert_eq!(layer.circles()[0].len(), 37); // resolution + 1 for closed circle
     }
 
+    #[test]
+    fn test_diamant_layer_stats() {
+        let config = DiamantConfig::new(12, 10.0).with_resolution(36);
+        let mut layer = DiamantLayer::new(config).unwrap();
+        assert_eq!(layer.stats(), None);
+        layer.generate();
+
+        let stats = layer.stats().unwrap();
+        let (min_x, min_y, max_x, max_y) =
+            drawable_bounds(layer.lines().iter().map(Vec::as_slice)).unwrap();
+        assert_eq!(stats.min, Point2D::new(min_x, min_y));
+        assert_eq!(stats.max, Point2D::new(max_x, max_y));
+        assert_eq!(stats.num_polylines, 12);
+        assert_eq!(stats.num_points, 12 * 37);
+        // Twelve 36-gons inscribed in circles of radius 10
+        let perimeter = 36.0 * 2.0 * 10.0 * (PI / 36.0).sin();
+        assert!((stats.total_path_length - 12.0 * perimeter).abs() < 1e-9);
+    }
+
     #[test]
     fn test_diamant_circles_tangent_to_center() {
         let config = DiamantConfig::new(4, 10.0).with_resolution(360);
@@ -299,6 +501,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_diamant_single_ring_is_the_classic_layout() {
+        let config = DiamantConfig::new(8, 10.0).with_resolution(36);
+        let mut layer = DiamantLayer::new_with_center(config, 1.5, -2.0).unwrap();
+        layer.generate();
+
+        // Circles of radius 10 with centers 10 from the layer center, to the
+        // bit: the formula before rings were added
+        for (i, circle) in layer.circles().iter().enumerate() {
+            let rotation = (i as f64) * (2.0 * PI / 8.0);
+            let (cx, cy) = (1.5 + 10.0 * rotation.cos(), -2.0 + 10.0 * rotation.sin());
+            for (j, point) in circle.iter().enumerate() {
+                let angle = 2.0 * PI * ((j as f64) / 36.0);
+                assert_eq!(
+                    *point,
+                    Point2D::new(cx + 10.0 * angle.cos(), cy + 10.0 * angle.sin())
+                );
+            }
+        }
+
+        // Saved configurations from before rings load as one ring
+        let json = r#"{"num_circles":8,"circle_radius":10.0,"resolution":36}"#;
+        let old: DiamantConfig = serde_json::from_str(json).unwrap();
+        assert_eq!((old.num_rings, old.scale_per_ring), (1, 1.0));
+        assert_eq!((old.ring_spacing, old.stagger), (0.0, false));
+    }
+
+    #[test]
+    fn test_diamant_rings_shrink_spread_and_stagger() {
+        let config = DiamantConfig::new(6, 8.0)
+            .with_resolution(60)
+            .with_rings(3, 5.0, 0.5)
+            .with_stagger(true);
+        let mut layer = DiamantLayer::new(config).unwrap();
+        layer.generate();
+        assert_eq!(layer.circles().len(), 18);
+
+        let step = 2.0 * PI / 6.0;
+        for (k, circle) in layer.circles().iter().enumerate() {
+            let (ring, i) = (k / 6, k % 6);
+            let n = (circle.len() - 1) as f64;
+            let center = circle[..circle.len() - 1]
+                .iter()
+                .fold(Point2D::new(0.0, 0.0), |c, p| {
+                    Point2D::new(c.x + p.x / n, c.y + p.y / n)
+                });
+            let radius = (circle[0].x - center.x).hypot(circle[0].y - center.y);
+            assert!((radius - 8.0 * 0.5_f64.powi(ring as i32)).abs() < 1e-9);
+            assert!((center.x.hypot(center.y) - (8.0 + 5.0 * ring as f64)).abs() < 1e-9);
+            let offset = if ring == 1 { step / 2.0 } else { 0.0 };
+            let angle = (i as f64) * step + offset;
+            assert!((center.y.atan2(center.x) - angle).sin().abs() < 1e-9);
+        }
+
+        for bad in [
+            DiamantConfig::new(6, 8.0).with_rings(0, 5.0, 0.5),
+            DiamantConfig::new(6, 8.0).with_rings(2, -1.0, 0.5),
+            DiamantConfig::new(6, 8.0).with_rings(2, 5.0, 0.0),
+            DiamantConfig::new(6, 8.0).with_rings(2, 5.0, f64::NAN),
+        ] {
+            assert!(DiamantLayer::new(bad).is_err());
+        }
+    }
+
+    #[test]
+    fn test_diamant_rings_match_rose_engine() {
+        use crate::rose_engine::RoseEngineLatheRun;
+
+        let config = DiamantConfig::new(9, 6.0)
+            .with_resolution(90)
+            .with_rings(3, 4.0, 0.75)
+            .with_stagger(true);
+        let mut diamant = DiamantLayer::new_with_center(config, 2.0, 1.0).unwrap();
+        diamant.generate();
+
+        let mut rose_run =
+            RoseEngineLatheRun::new_diamant(9, 6.0, 90, 3, 4.0, 0.75, true, 2.0, 1.0).unwrap();
+        rose_run.generate();
+        assert_eq!(rose_run.num_passes, 27);
+        assert_eq!(diamant.lines(), rose_run.lines());
+        assert!(RoseEngineLatheRun::new_diamant(9, 6.0, 90, 0, 4.0, 0.75, true, 0.0, 0.0).is_err());
+    }
+
     #[test]
     fn test_diamant_matches_rose_engine() {
         use crate::rose_engine::RoseEngineLatheRun;
@@ -313,9 +598,18 @@ mod tests {
         diamant.generate();
 
         // Create equivalent rose engine diamant
-        let mut rose_run =
-            RoseEngineLatheRun::new_diamant(num_circles, circle_radius, resolution, 0.0, 0.0)
-                .unwrap();
+        let mut rose_run = RoseEngineLatheRun::new_diamant(
+            num_circles,
+            circle_radius,
+            resolution,
+            1,
+            0.0,
+            1.0,
+            false,
+            0.0,
+            0.0,
+        )
+        .unwrap();
         rose_run.generate();
 
         let diamant_lines = diamant.lines();
@@ -0,0 +1,256 @@
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{polyline_bounds, polyline_is_degenerate, Point2D, SpirographError};
+
+/// Counts of how lines changed between two generations
+///
+/// `added` lines exist only in the current generation and `removed` lines
+/// only in the baseline it is compared against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffSummary {
+    pub unchanged: usize,
+    pub moved: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+/// How a current line relates to the baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineChange {
+    Unchanged,
+    Moved,
+    Added,
+}
+
+/// Per-line classification of a diff
+struct LineDiff {
+    current: Vec<LineChange>,
+    removed: Vec<usize>,
+    summary: DiffSummary,
+}
+
+/// Largest distance between two lines
+///
+/// Lines with the same point count are compared point by point; otherwise
+/// the symmetric Hausdorff distance is used.
+fn line_deviation(a: &[Point2D], b: &[Point2D]) -> f64 {
+    if a.len() == b.len() {
+        return a
+            .iter()
+            .zip(b)
+            .map(|(p, q)| (p.x - q.x).hypot(p.y - q.y))
+            .fold(0.0, f64::max);
+    }
+    let directed = |from: &[Point2D], to: &[Point2D]| {
+        from.iter()
+            .map(|p| {
+                to.iter()
+                    .map(|q| (p.x - q.x).hypot(p.y - q.y))
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .fold(0.0, f64::max)
+    };
+    directed(a, b).max(directed(b, a))
+}
+
+/// Distance between the endpoints of two lines, used to pick match candidates
+fn endpoint_distance(a: &[Point2D], b: &[Point2D]) -> f64 {
+    match (a.first(), a.last(), b.first(), b.last()) {
+        (Some(a0), Some(a1), Some(b0), Some(b1)) => (a0.x - b0.x)
+            .hypot(a0.y - b0.y)
+            .max((a1.x - b1.x).hypot(a1.y - b1.y)),
+        _ => f64::INFINITY,
+    }
+}
+
+/// Classify every line of `current` against `baseline`
+///
+/// When both sets have the same structure (line count and per-line point
+/// counts) lines are matched by index. Otherwise each current line is paired
+/// with the unmatched baseline line whose endpoints are nearest. A pair
+/// within `tolerance` is unchanged, within 10× `tolerance` moved, and
+/// anything further apart counts as one added and one removed line.
+fn diff_lines(current: &[&[Point2D]], baseline: &[&[Point2D]], tolerance: f64) -> LineDiff {
+    let same_structure = current.len() == baseline.len()
+        && current
+            .iter()
+            .zip(baseline)
+            .all(|(a, b)| a.len() == b.len());
+
+    let mut matched = vec![false; baseline.len()];
+    let mut changes = Vec::with_capacity(current.len());
+    for (i, line) in current.iter().enumerate() {
+        let candidate = if same_structure {
+            Some(i)
+        } else {
+            (0..baseline.len())
+                .filter(|&j| !matched[j])
+                .map(|j| (j, endpoint_distance(line, baseline[j])))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(j, _)| j)
+        };
+
+        let change = match candidate {
+            Some(j) => {
+                let deviation = line_deviation(line, baseline[j]);
+                if deviation <= 10.0 * tolerance {
+                    matched[j] = true;
+                }
+                if deviation <= tolerance {
+                    LineChange::Unchanged
+                } else if deviation <= 10.0 * tolerance {
+                    LineChange::Moved
+                } else {
+                    LineChange::Added
+                }
+            }
+            None => LineChange::Added,
+        };
+        changes.push(change);
+    }
+
+    let removed: Vec<usize> = (0..baseline.len()).filter(|&j| !matched[j]).collect();
+    let count = |kind| changes.iter().filter(|&&c| c == kind).count();
+    let summary = DiffSummary {
+        unchanged: count(LineChange::Unchanged),
+        moved: count(LineChange::Moved),
+        added: count(LineChange::Added),
+        removed: removed.len(),
+    };
+
+    LineDiff {
+        current: changes,
+        removed,
+        summary,
+    }
+}
+
+/// Write an SVG overlaying `current` on `baseline` and summarize the changes
+///
+/// Unchanged lines are light gray, moved lines orange, added lines blue and
+/// removed lines red.
+pub(crate) fn write_diff_svg(
+    current: &[&[Point2D]],
+    baseline: &[&[Point2D]],
+    filename: &str,
+    tolerance: f64,
+) -> Result<DiffSummary, SpirographError> {
+    use svg::node::element::Group;
+
+    if !(tolerance.is_finite() && tolerance > 0.0) {
+        return Err(SpirographError::InvalidParameter(
+            "tolerance must be positive and finite".to_string(),
+        ));
+    }
+
+    let diff = diff_lines(current, baseline, tolerance);
+
+    let bounds = polyline_bounds(current.iter().chain(baseline).copied()).ok_or_else(|| {
+        SpirographError::ExportError("Nothing to compare: both line sets are empty".to_string())
+    })?;
+    let mut writer = SvgWriter::new(bounds, 5.0);
+
+    let layer =
+        |writer: &SvgWriter, id: &str, color: &str, lines: &mut dyn Iterator<Item = &[Point2D]>| {
+            let mut group = Group::new().set("id", id);
+            for line in lines {
+                if polyline_is_degenerate(line) {
+                    continue;
+                }
+                group = group.add(writer.polyline_node(line, false, |path| {
+                    path.set("fill", "none")
+                        .set("stroke", color)
+                        .set("stroke-width", 0.05)
+                }));
+            }
+            group
+        };
+    let current_of = |kind: LineChange| {
+        current
+            .iter()
+            .zip(&diff.current)
+            .filter(move |(_, &c)| c == kind)
+            .map(|(line, _)| *line)
+    };
+
+    // Changes are drawn last so they sit above the unchanged lines
+    let groups = [
+        layer(
+            &writer,
+            "unchanged",
+            "#c8c8c8",
+            &mut current_of(LineChange::Unchanged),
+        ),
+        layer(
+            &writer,
+            "removed",
+            "#d62728",
+            &mut diff.removed.iter().map(|&j| baseline[j]),
+        ),
+        layer(
+            &writer,
+            "added",
+            "#1f5fd6",
+            &mut current_of(LineChange::Added),
+        ),
+        layer(
+            &writer,
+            "moved",
+            "#ff8c00",
+            &mut current_of(LineChange::Moved),
+        ),
+    ];
+    for group in groups {
+        writer.add(group);
+    }
+
+    writer.save(filename)?;
+    Ok(diff.summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring(radius: f64, n: usize) -> Vec<Point2D> {
+        (0..n)
+            .map(|i| {
+                let t = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+                Point2D::new(radius * t.cos(), radius * t.sin())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_lines_index_and_fallback() {
+        let a = [ring(10.0, 100), ring(11.0, 100), ring(12.0, 100)];
+        let b = [ring(10.0, 100), ring(11.05, 100), ring(14.0, 100)];
+        fn refs(set: &[Vec<Point2D>]) -> Vec<&[Point2D]> {
+            set.iter().map(Vec::as_slice).collect()
+        }
+
+        let summary = diff_lines(&refs(&a), &refs(&b), 0.01).summary;
+        assert_eq!(
+            summary,
+            DiffSummary {
+                unchanged: 1,
+                moved: 1,
+                added: 1,
+                removed: 1,
+            }
+        );
+
+        // Different structure falls back to nearest-endpoint matching
+        let c = [ring(12.0, 50), ring(10.0, 100)];
+        let summary = diff_lines(&refs(&c), &refs(&a), 0.01).summary;
+        // The resampled ring deviates by a chord's sag, so it is re-cut
+        assert_eq!(
+            summary,
+            DiffSummary {
+                unchanged: 1,
+                moved: 0,
+                added: 1,
+                removed: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Helpers that keep the documentation examples short
+//!
+//! Not part of the supported API. Examples prefer the in-memory exports
+//! (`to_svg_string`, `to_stl_bytes`); exporters that only write files use
+//! [`temp_path`], which cleans up after itself.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::common::Point2D;
+
+/// A file path in a fresh temporary directory, removed with the directory
+/// when dropped
+#[derive(Debug)]
+pub struct TempPath {
+    dir: PathBuf,
+    path: PathBuf,
+}
+
+impl TempPath {
+    /// The path as a `&str`, as the exporters take it
+    pub fn as_str(&self) -> &str {
+        self.path.to_str().expect("temporary paths are UTF-8")
+    }
+
+    /// The path itself
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// A path named `name` in a new directory under the system temp dir
+///
+/// The directory is unique to this process and call, so examples running
+/// in parallel never share files, and it is removed when the returned
+/// value is dropped.
+pub fn temp_path(name: &str) -> TempPath {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "turtles-doctest-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&dir).expect("temp dir is writable");
+    let path = dir.join(name);
+    TempPath { dir, path }
+}
+
+/// Largest distance of any point of `lines` from `center`
+pub fn max_radius(lines: &[Vec<Point2D>], center: (f64, f64)) -> f64 {
+    lines
+        .iter()
+        .flatten()
+        .map(|p| (p.x - center.0).hypot(p.y - center.1))
+        .fold(0.0, f64::max)
+}
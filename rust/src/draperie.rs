@@ -1,6 +1,71 @@
 use std::f64::consts::PI;
 
-use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographError};
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::{write_svg_streaming, SvgWriter, DEFAULT_SVG_PRECISION};
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_polylines, geometry_hash, map_indices,
+    peak_alignment_phase, polar_to_cartesian, polyline_is_degenerate, scale_polylines,
+    simplified_layer, simplify_polylines, DashSpec, ExportConfig, JitterConfig, PatternLayer,
+    Point2D, PointsPerRev, RadialWave, SpirographError, StoredLayer, TrigTable, DEDUPE_EPSILON,
+    DEFAULT_MAX_POINTS_PER_PATH,
+};
+use crate::dxf::write_dxf_polylines;
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive, ProgressiveHandle};
+use crate::rose_engine::RosettePattern;
+use crate::shading::write_shaded_rings_svg;
+
+/// Largest share of the gap to its nearer neighbour a jittered ring may
+/// move, so two neighbours together close at most half their gap
+const JITTER_GAP_FRACTION: f64 = 0.25;
+
+/// Most revolutions a ring may take to close in
+/// `NonIntegerMode::MultiRevolution`
+pub const MAX_CLOSING_REVOLUTIONS: usize = 16;
+
+/// How far a frequency may be from p/q and still count as p/q
+const FREQUENCY_EPSILON: f64 = 1e-9;
+
+/// How rings are closed when `wave_frequency` is not a whole number
+///
+/// A ring traced once around with a non-integer frequency ends at a
+/// different radius than it started, leaving a radial step at the seam.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NonIntegerMode {
+    /// Round the frequency to the nearest whole number (at least 1), so
+    /// each ring closes after one revolution
+    #[default]
+    Round,
+    /// Keep a frequency p/q and trace each ring over q revolutions, which
+    /// closes it on itself. A frequency that is no fraction with
+    /// q ≤ `MAX_CLOSING_REVOLUTIONS` uses the closest one.
+    MultiRevolution,
+}
+
+/// How a draperie wave frequency was changed so its rings close
+///
+/// See `DraperieConfig::normalization_report`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyNormalization {
+    /// `wave_frequency` as configured
+    pub requested: f64,
+    /// Frequency the rings are drawn with
+    pub used: f64,
+    /// Revolutions each ring takes to close
+    pub revolutions: usize,
+}
+
+impl std::fmt::Display for FrequencyNormalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "wave_frequency {} does not close a ring; drawn as {} over {} revolution(s)",
+            self.requested, self.used, self.revolutions
+        )
+    }
+}
 
 /// Configuration for the Draperie (Drapery) guilloché pattern
 ///
@@ -12,7 +77,7 @@ use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographE
 /// where `φ_i = phase_shift * sin(2π * phase_oscillations * i / N)`.
 ///
 /// The amplitude is automatically clamped so adjacent rings never cross.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DraperieConfig {
     /// Number of concentric rings
     pub num_rings: usize,
@@ -28,13 +93,16 @@ pub struct DraperieConfig {
     pub amplitude: Option<f64>,
     /// Peak angular oscillation amplitude (radians).
     /// The phase of each ring swings by ±phase_shift from its neutral position.
+    /// This is an amplitude, not an angle, so it is validated to lie in [0, π]
+    /// rather than wrapped.
     pub phase_shift: f64,
     /// Number of full sinusoidal phase cycles across the ring stack.
     /// Each cycle produces 2 visible direction changes, so `phase_oscillations = 2`
     /// produces 4 visible folds from centre to edge.
     pub phase_oscillations: f64,
-    /// Resolution — number of points per ring
-    pub resolution: usize,
+    /// Resolution — points per revolution of each ring; a ring that
+    /// closes over several turns holds that many times more
+    pub resolution: PointsPerRev,
     /// Exponent applied to the sinusoidal phase envelope.
     /// Only used when `circular_phase` is 0 (dome mode disabled).
     /// Use 3 (default) for smooth folds; use 1 for sharp angular folds.
@@ -51,6 +119,16 @@ pub struct DraperieConfig {
     /// values produce even more "squared-off" flat-top domes.
     /// When 0.0, falls back to `sin^e` mode using `phase_exponent`.
     pub circular_phase: f64,
+    /// How a non-integer `wave_frequency` is closed (default: round it)
+    pub non_integer_mode: NonIntegerMode,
+    /// Cut each ring as dots locked to its wave phase, as a rose engine run
+    /// with `RoseEngineConfig::dash_by_phase` does
+    pub dash_by_phase: Option<DashSpec>,
+    /// Seeded hand-made wobble of each ring; see
+    /// [`generate`](DraperieLayer::generate) for how it is kept from
+    /// crossing the neighbouring rings
+    #[serde(default)]
+    pub jitter: Option<JitterConfig>,
 }
 
 impl Default for DraperieConfig {
@@ -63,10 +141,13 @@ impl Default for DraperieConfig {
             amplitude: None,        // auto-computed
             phase_shift: PI / 12.0, // 15°
             phase_oscillations: 2.5,
-            resolution: 1500,
+            resolution: PointsPerRev(1500),
             phase_exponent: 3,
             wave_exponent: 1,
             circular_phase: 2.0,
+            non_integer_mode: NonIntegerMode::Round,
+            dash_by_phase: None,
+            jitter: None,
         }
     }
 }
@@ -85,12 +166,148 @@ impl DraperieConfig {
         }
     }
 
-    /// Set the resolution (points per ring)
-    pub fn with_resolution(mut self, resolution: usize) -> Self {
-        self.resolution = resolution;
+    /// Set the resolution (points per revolution)
+    pub fn with_resolution(mut self, resolution: impl Into<PointsPerRev>) -> Self {
+        self.resolution = resolution.into();
+        self
+    }
+
+    /// Set how a non-integer wave frequency is closed
+    pub fn with_non_integer_mode(mut self, non_integer_mode: NonIntegerMode) -> Self {
+        self.non_integer_mode = non_integer_mode;
+        self
+    }
+
+    /// Cut each ring as dots locked to its wave phase
+    pub fn with_dash_by_phase(mut self, dash: DashSpec) -> Self {
+        self.dash_by_phase = Some(dash);
+        self
+    }
+
+    /// Wobble each ring as if turned by hand
+    pub fn with_jitter(mut self, jitter: JitterConfig) -> Self {
+        self.jitter = Some(jitter);
         self
     }
 
+    /// Wave frequency the rings are drawn with, and the revolutions each
+    /// ring takes to close
+    ///
+    /// Whole-number frequencies are returned unchanged with one
+    /// revolution; others are closed as `non_integer_mode` says.
+    /// `resolution` stays the number of points per revolution.
+    pub fn ring_frequency(&self) -> (f64, usize) {
+        let frequency = self.wave_frequency;
+        let rounded = frequency.round().max(1.0);
+        match self.non_integer_mode {
+            NonIntegerMode::Round => (rounded, 1),
+            NonIntegerMode::MultiRevolution => {
+                // Smallest denominator within the tolerance, else the
+                // closest fraction
+                let mut best = (rounded, 1);
+                let mut best_error = (frequency - rounded).abs();
+                for q in 1..=MAX_CLOSING_REVOLUTIONS {
+                    let p = (frequency * q as f64).round();
+                    if p < 1.0 {
+                        continue;
+                    }
+                    let error = (frequency - p / q as f64).abs();
+                    if error <= FREQUENCY_EPSILON {
+                        return (p / q as f64, q);
+                    }
+                    if error < best_error {
+                        best = (p / q as f64, q);
+                        best_error = error;
+                    }
+                }
+                best
+            }
+        }
+    }
+
+    /// How `wave_frequency` is changed so the rings close, or `None` when
+    /// it is drawn as configured
+    pub fn normalization_report(&self) -> Option<FrequencyNormalization> {
+        let (used, revolutions) = self.ring_frequency();
+        (used != self.wave_frequency).then_some(FrequencyNormalization {
+            requested: self.wave_frequency,
+            used,
+            revolutions,
+        })
+    }
+
+    /// Check the ring stack, the wave and the amplitude
+    ///
+    /// An explicit amplitude of 0 is valid and draws plain concentric
+    /// circles. Every ring's base radius must be positive, so the stack
+    /// never collapses onto the centre.
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.num_rings == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "num_rings must be at least 1".to_string(),
+            ));
+        }
+
+        if self.radius_step <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "radius_step must be positive".to_string(),
+            ));
+        }
+
+        if self.base_radius <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "base_radius must be positive".to_string(),
+            ));
+        }
+
+        if self.resolution.get() < 10 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 10".to_string(),
+            ));
+        }
+
+        if !(0.0..=PI).contains(&self.phase_shift) {
+            return Err(SpirographError::InvalidParameter(
+                "phase_shift must be within [0, π]".to_string(),
+            ));
+        }
+
+        if !(self.wave_frequency.is_finite() && self.wave_frequency > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "wave_frequency must be positive".to_string(),
+            ));
+        }
+
+        if let Some(amplitude) = self.amplitude {
+            if !(amplitude.is_finite() && amplitude >= 0.0) {
+                return Err(SpirographError::InvalidParameter(
+                    "amplitude must be non-negative".to_string(),
+                ));
+            }
+        }
+
+        if self.innermost_radius() <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "innermost ring radius must be positive".to_string(),
+            ));
+        }
+
+        if let Some(ref dash) = self.dash_by_phase {
+            dash.validate()?;
+        }
+
+        if let Some(ref jitter) = self.jitter {
+            jitter.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Base radius of the innermost ring
+    pub fn innermost_radius(&self) -> f64 {
+        self.base_radius - ((self.num_rings as f64 - 1.0) / 2.0) * self.radius_step
+    }
+
     /// Compute the maximum safe amplitude so that adjacent rings never cross
     /// and the innermost ring does not pass through the centre.
     pub fn safe_amplitude(&self) -> f64 {
@@ -107,7 +324,7 @@ impl DraperieConfig {
             max_diff = max_diff.max((v2 - v1).abs());
         }
         let max_adj_dphi = self.phase_shift * max_diff;
-        let sin_term = (self.wave_frequency * max_adj_dphi / 2.0).sin().abs();
+        let sin_term = (self.ring_frequency().0 * max_adj_dphi / 2.0).sin().abs();
         let max_amp_phase = if sin_term > 1e-12 {
             self.radius_step / (2.0 * sin_term)
         } else {
@@ -115,8 +332,7 @@ impl DraperieConfig {
         };
 
         // Constraint 2: innermost ring must not reach r = 0.
-        let innermost_base =
-            self.base_radius - ((self.num_rings as f64 - 1.0) / 2.0) * self.radius_step;
+        let innermost_base = self.innermost_radius();
         let max_amp_centre = if innermost_base > 0.0 {
             innermost_base * 0.9
         } else {
@@ -128,6 +344,44 @@ impl DraperieConfig {
         0.6 * max_amplitude
     }
 
+    /// Amplitude `generate()` will actually use: the explicit `amplitude`,
+    /// or `safe_amplitude()` when it is `None`
+    pub fn effective_amplitude(&self) -> f64 {
+        self.amplitude.unwrap_or_else(|| self.safe_amplitude())
+    }
+
+    /// Whether changing `field` can change the generated rings
+    ///
+    /// Lets an editor skip regeneration for edits that are provably inert
+    /// with the current settings:
+    ///
+    /// * `phase_exponent` only shapes the envelope when `circular_phase` is 0.
+    /// * `phase_oscillations`, `circular_phase` and `phase_exponent` do
+    ///   nothing when `phase_shift` is 0.
+    /// * The wave and phase fields do nothing when the effective amplitude
+    ///   is 0.
+    /// * With a single ring, `radius_step` and `phase_oscillations` only
+    ///   matter through `safe_amplitude()`, so they are inert once
+    ///   `amplitude` is set explicitly. With `amplitude = None` they stay
+    ///   live, as do `radius_step` and `wave_frequency` in general.
+    /// * `non_integer_mode` only matters for a non-integer `wave_frequency`.
+    ///
+    /// Unknown field names report `true`.
+    pub fn affects_geometry(&self, field: &str) -> bool {
+        let flat = self.effective_amplitude() == 0.0;
+        let no_phase = self.phase_shift == 0.0;
+        let single_fixed = self.num_rings == 1 && self.amplitude.is_some();
+        match field {
+            "wave_frequency" | "wave_exponent" | "phase_shift" => !flat,
+            "non_integer_mode" => !flat && self.wave_frequency.fract() != 0.0,
+            "phase_oscillations" => !(flat || no_phase || single_fixed),
+            "circular_phase" => !(flat || no_phase),
+            "phase_exponent" => !(flat || no_phase || self.circular_phase > 0.0),
+            "radius_step" => !single_fixed,
+            _ => true,
+        }
+    }
+
     /// Evaluate the phase-shape function at parameter `t`.
     ///
     /// * **dome mode** (`circular_phase > 0`):
@@ -155,16 +409,37 @@ impl DraperieConfig {
 /// This pattern is created by drawing concentric sinusoidal rings whose phase
 /// oscillates back and forth across the stack, producing visually flowing
 /// wave-folds that radiate from the centre — a hallmark of fine guilloché.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DraperieLayer {
     pub config: DraperieConfig,
     pub center_x: f64,
     pub center_y: f64,
+    #[serde(skip)]
     rings: Vec<Vec<Point2D>>,
+    max_points_per_path: usize,
 }
 
 impl DraperieLayer {
     /// Create a new draperie layer centred at origin
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{polyline_closes, DraperieConfig, DraperieLayer};
+    ///
+    /// let mut layer = DraperieLayer::new(DraperieConfig::new(24, 15.0)).unwrap();
+    /// layer.generate();
+    ///
+    /// // One closed ring per configured ring, inside the band's outer edge
+    /// assert_eq!(layer.rings().len(), 24);
+    /// assert!(layer.rings().iter().all(|ring| polyline_closes(ring)));
+    /// let config = &layer.config;
+    /// let outer = config.base_radius + 11.5 * config.radius_step + config.effective_amplitude();
+    /// # use turtles::doctest_support::max_radius;
+    /// assert!(max_radius(layer.rings(), (0.0, 0.0)) <= outer + 1e-9);
+    ///
+    /// let svg = layer.to_svg_string().unwrap();
+    /// assert_eq!(svg.matches("<path").count(), 24);
+    /// ```
     pub fn new(config: DraperieConfig) -> Result<Self, SpirographError> {
         Self::new_with_center(config, 0.0, 0.0)
     }
@@ -175,35 +450,14 @@ impl DraperieLayer {
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        if config.num_rings == 0 {
-            return Err(SpirographError::InvalidParameter(
-                "num_rings must be at least 1".to_string(),
-            ));
-        }
-
-        if config.radius_step <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "radius_step must be positive".to_string(),
-            ));
-        }
-
-        if config.base_radius <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "base_radius must be positive".to_string(),
-            ));
-        }
-
-        if config.resolution < 10 {
-            return Err(SpirographError::InvalidParameter(
-                "resolution must be at least 10".to_string(),
-            ));
-        }
+        config.validate()?;
 
         Ok(DraperieLayer {
             config,
             center_x,
             center_y,
             rings: Vec::new(),
+            max_points_per_path: DEFAULT_MAX_POINTS_PER_PATH,
         })
     }
 
@@ -239,22 +493,65 @@ impl DraperieLayer {
     /// Produces `num_rings` concentric wavy rings with a sinusoidal phase
     /// envelope. The amplitude is automatically clamped to prevent overlap
     /// if not explicitly set.
+    ///
+    /// With [`jitter`](DraperieConfig::jitter), each ring's radius at every
+    /// sample angle moves by its radial offset plus the change the angular
+    /// offset makes to the wave there, clamped to a quarter of the gap to
+    /// the nearer neighbouring ring (or to the centre). Neighbours thus
+    /// never close more than half their gap, and the rings still never
+    /// cross at the sampled angles.
     pub fn generate(&mut self) {
-        self.rings.clear();
+        self.generate_rings(false);
+    }
 
-        let amplitude = self
-            .config
-            .amplitude
-            .unwrap_or_else(|| self.config.safe_amplitude());
+    /// Generate as [`generate`](Self::generate), one ring per thread task
+    ///
+    /// The rings are identical to the serial ones, in the same order.
+    #[cfg(feature = "parallel")]
+    pub fn generate_parallel(&mut self) {
+        self.generate_rings(true);
+    }
+
+    /// Generate every ring, optionally in parallel
+    fn generate_rings(&mut self, parallel: bool) {
+        let rings = map_indices(self.config.num_rings, parallel, self.ring_tracer());
+        self.rings = rings.into_iter().flatten().collect();
+
+        debug_assert_finite("DraperieLayer", &self.rings);
+    }
+
+    /// Rings computed lazily from the config, one at a time
+    ///
+    /// Yields the same rings, point for point, as `generate` followed by
+    /// `rings`, but holds only the ring being traced (or its dashes) in
+    /// memory. The generated rings are neither used nor changed.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{DraperieConfig, DraperieLayer, Point2D};
+    ///
+    /// let mut layer = DraperieLayer::new(DraperieConfig::new(24, 15.0)).unwrap();
+    /// let lazy: Vec<Vec<Point2D>> = layer.iter_rings().map(Iterator::collect).collect();
+    /// layer.generate();
+    /// assert_eq!(&lazy, layer.rings());
+    /// ```
+    pub fn iter_rings(&self) -> impl Iterator<Item = impl Iterator<Item = Point2D> + '_> + '_ {
+        let trace = self.ring_tracer();
+        (0..self.config.num_rings)
+            .flat_map(trace)
+            .map(Vec::into_iter)
+    }
+
+    /// The pieces of ring `i` as generated: the whole ring, or its dashes
+    fn ring_tracer(&self) -> impl Fn(usize) -> Vec<Vec<Point2D>> + Sync + Send + '_ {
+        let amplitude = self.config.effective_amplitude();
 
         let n = self.config.num_rings;
 
-        // Phase offset so that wave peaks align with 12 o'clock (θ = −π/2 in
-        // screen coordinates).  We need sin(f*(−π/2 + base_phase)) = 1,
-        // i.e. base_phase = π/2 + π/(2f).
-        let base_phase = PI / 2.0 + PI / (2.0 * self.config.wave_frequency);
+        let (frequency, revolutions) = self.config.ring_frequency();
+        let base_phase = peak_alignment_phase(frequency);
 
-        for i in 0..n {
+        let ring_wave = move |i: usize| {
             // Ring base radius — centred around config.base_radius
             let offset = (i as f64) - ((n as f64 - 1.0) / 2.0);
             let ring_base_radius = self.config.base_radius + offset * self.config.radius_step;
@@ -264,27 +561,72 @@ impl DraperieLayer {
             let phase_t = 2.0 * PI * self.config.phase_oscillations * (i as f64) / (n as f64);
             let ring_phase = self.config.phase_shift * self.config.phase_shape_fn(phase_t);
 
+            RadialWave::new(
+                ring_base_radius,
+                amplitude,
+                RosettePattern::Sinusoidal { frequency },
+            )
+            .with_phase(base_phase + ring_phase)
+            .with_wave_exponent(self.config.wave_exponent)
+        };
+
+        // Every ring visits the same angles
+        let table = TrigTable::new(self.config.resolution.get());
+
+        move |i| {
             // Trace the ring
-            let mut ring_points = Vec::with_capacity(self.config.resolution + 1);
-            for j in 0..=self.config.resolution {
-                let t = (j as f64) / (self.config.resolution as f64);
-                let theta = 2.0 * PI * t;
-
-                let wave_sin =
-                    (self.config.wave_frequency * (theta + base_phase + ring_phase)).sin();
-                let wave_val =
-                    wave_sin.abs().powi(self.config.wave_exponent as i32) * wave_sin.signum();
-                let r = ring_base_radius + amplitude * wave_val;
-
-                let x = self.center_x + r * theta.cos();
-                let y = self.center_y + r * theta.sin();
-                ring_points.push(Point2D::new(x, y));
+            let wave = ring_wave(i);
+            let center = Point2D::new(self.center_x, self.center_y);
+            let resolution = self.config.resolution.get();
+            let mut ring = match self.config.jitter {
+                Some(jitter) => {
+                    let inner = i.checked_sub(1).map(ring_wave);
+                    let outer = (i + 1 < n).then(|| ring_wave(i + 1));
+                    let line = jitter.line(i);
+                    let samples = resolution * revolutions;
+                    (0..=samples)
+                        .map(|j| {
+                            let theta = 2.0 * PI * (j as f64) / (resolution as f64);
+                            let r = wave.radius_at(theta);
+                            // The innermost ring keeps clear of the centre
+                            let inner_gap = inner.as_ref().map_or(r, |w| r - w.radius_at(theta));
+                            let outer_gap = outer
+                                .as_ref()
+                                .map_or(f64::INFINITY, |w| w.radius_at(theta) - r);
+                            let limit = JITTER_GAP_FRACTION * inner_gap.min(outer_gap).max(0.0);
+                            let (dr, dtheta) = line.offsets(j as f64 / samples as f64);
+                            let shift =
+                                (wave.radius_at(theta + dtheta) - r + dr).clamp(-limit, limit);
+                            let r = r + shift;
+                            let (cos, sin) = table.cos_sin(j);
+                            Point2D::new(center.x + r * cos, center.y + r * sin)
+                        })
+                        .collect()
+                }
+                None => wave.sample_revolutions_with(&table, revolutions, center),
+            };
+            if revolutions > 1 {
+                // A p/q wave repeats after q turns only up to rounding
+                let first = ring[0];
+                *ring.last_mut().unwrap() = first;
             }
-
-            self.rings.push(ring_points);
+            let mut pieces = match self.config.dash_by_phase {
+                Some(dash) => dash.split(&ring, center, wave.phase),
+                None => vec![ring],
+            };
+            dedupe_polylines(&mut pieces, DEDUPE_EPSILON);
+            pieces
         }
     }
 
+    /// Start a coarse-to-fine generation at the given detail fractions
+    ///
+    /// Coarse levels use fewer rings (spread over the same band) and fewer
+    /// points per ring; see `ProgressiveHandle`.
+    pub fn generate_progressive(&mut self, levels: &[f32]) -> ProgressiveHandle<'_, Self> {
+        ProgressiveHandle::new(self, levels)
+    }
+
     /// Get the generated rings
     pub fn rings(&self) -> &Vec<Vec<Point2D>> {
         &self.rings
@@ -295,62 +637,232 @@ impl DraperieLayer {
         &self.rings
     }
 
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.rings
+    }
+
+    /// Hash of the generated rings, for skipping redundant redraws
+    ///
+    /// See `geometry_hash`.
+    pub fn geometry_hash(&self) -> u64 {
+        geometry_hash(self.rings.iter().map(Vec::as_slice))
+    }
+
+    /// Set the most points written to one SVG `<path>`
+    ///
+    /// Longer rings are split into consecutive paths grouped in a `<g>`.
+    pub fn set_max_points_per_path(&mut self, max_points: usize) -> Result<(), SpirographError> {
+        if max_points < 2 {
+            return Err(SpirographError::InvalidParameter(
+                "max_points_per_path must be at least 2".to_string(),
+            ));
+        }
+        self.max_points_per_path = max_points;
+        Ok(())
+    }
+
+    /// Multiply every length (config, center and generated rings) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.radius_step *= factor;
+        self.config.base_radius *= factor;
+        if let Some(a) = self.config.amplitude.as_mut() {
+            *a *= factor;
+        }
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.rings, factor);
+    }
+
     /// Export the pattern to SVG format
     pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        use svg::node::element::{path::Data, Path};
-        use svg::Document;
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
 
+    /// Export the pattern to DXF with one LWPOLYLINE per ring, in mm
+    pub fn to_dxf(&self, filename: &str) -> Result<(), SpirographError> {
         if self.rings.is_empty() {
             return Err(SpirographError::ExportError(
                 "Pattern not generated. Call generate() first.".to_string(),
             ));
         }
+        write_dxf_polylines(filename, &self.rings, false, None)
+    }
 
-        // Find bounds
-        let mut min_x = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+        simplified_layer(self, config.simplify_tolerance)
+            .svg_writer(config.svg_precision)?
+            .with_origin(config.origin)
+            .save(filename)
+    }
 
-        for ring in &self.rings {
-            for point in ring {
-                min_x = min_x.min(point.x);
-                max_x = max_x.max(point.x);
-                min_y = min_y.min(point.y);
-                max_y = max_y.max(point.y);
-            }
-        }
+    /// The SVG export as a string, without writing a file
+    pub fn to_svg_string(&self) -> Result<String, SpirographError> {
+        Ok(self
+            .svg_writer(DEFAULT_SVG_PRECISION)?
+            .into_document()
+            .to_string())
+    }
 
-        let margin = 5.0;
-        let width = max_x - min_x + 2.0 * margin;
-        let height = max_y - min_y + 2.0 * margin;
+    /// Build the SVG document with one path per ring
+    fn svg_writer(&self, precision: usize) -> Result<SvgWriter, SpirographError> {
+        if self.rings.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
 
-        let mut document = Document::new()
-            .set("width", format!("{}mm", width))
-            .set("height", format!("{}mm", height))
-            .set("viewBox", (min_x - margin, min_y - margin, width, height));
+        let mut writer = SvgWriter::framing(self.rings.iter().map(Vec::as_slice))?
+            .with_precision(precision)
+            .with_max_points_per_path(self.max_points_per_path);
 
         for ring in &self.rings {
-            if ring.is_empty() {
+            if polyline_is_degenerate(ring) {
                 continue;
             }
 
-            let mut data = Data::new().move_to((ring[0].x, ring[0].y));
-            for point in ring.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
+            let node = self.ring_svg_node(&writer, ring);
+            writer.add(node);
+        }
+
+        Ok(writer)
+    }
+
+    /// Export to SVG straight from [`iter_rings`](Self::iter_rings),
+    /// without generating
+    ///
+    /// Writes the same file as `generate` then `to_svg`, but traces each
+    /// ring twice (once to size the document) rather than holding them all,
+    /// so memory stays bounded by the largest ring.
+    pub fn to_svg_streaming(&self, filename: &str) -> Result<(), SpirographError> {
+        write_svg_streaming(
+            filename,
+            self.max_points_per_path,
+            || self.iter_rings().map(Iterator::collect),
+            |writer, ring| self.ring_svg_node(writer, ring),
+        )
+    }
 
-            let path = Path::new()
-                .set("d", data)
-                .set("fill", "none")
+    /// SVG node stroking one ring
+    fn ring_svg_node(&self, writer: &SvgWriter, ring: &[Point2D]) -> Box<dyn svg::Node> {
+        writer.polyline_node(ring, false, |path| {
+            path.set("fill", "none")
                 .set("stroke", "black")
-                .set("stroke-width", 0.05);
+                .set("stroke-width", 0.05)
+        })
+    }
 
-            document = document.add(path);
+    /// Export the pattern to SVG with shaded bands between the rings
+    ///
+    /// Each band between adjacent rings is filled with a light gradient
+    /// turned by the band's lean, and the rings are stroked on top as in
+    /// `to_svg`. Uses the generated rings as they are.
+    ///
+    /// # Arguments
+    /// * `filename` - Output SVG file path
+    /// * `light_angle` - Direction the light comes from (radians)
+    pub fn to_svg_filled(&self, filename: &str, light_angle: f64) -> Result<(), SpirographError> {
+        if self.config.dash_by_phase.is_some() {
+            return Err(SpirographError::ExportError(
+                "Shaded export needs whole rings; clear dash_by_phase".to_string(),
+            ));
+        }
+        write_shaded_rings_svg(
+            filename,
+            &self.rings,
+            Point2D::new(self.center_x, self.center_y),
+            light_angle,
+            self.max_points_per_path,
+        )
+    }
+}
+
+impl PatternLayer for DraperieLayer {
+    fn generate(&mut self) {
+        DraperieLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for DraperieLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        DraperieLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for DraperieLayer {
+    const KIND: LayerKind = LayerKind::Draperie;
+}
+
+impl Progressive for DraperieLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
+        }
+
+        let mut coarse = self.clone();
+        let config = &mut coarse.config;
+        config.num_rings = scale_count(self.config.num_rings, fraction, 2);
+        config.resolution = PointsPerRev(scale_count(self.config.resolution.get(), fraction, 10));
+        // Keep the band width and wave depth of the full pattern
+        if config.num_rings > 1 {
+            config.radius_step = self.config.radius_step * (self.config.num_rings - 1) as f64
+                / (config.num_rings - 1) as f64;
         }
+        config.amplitude = Some(self.config.effective_amplitude());
+        coarse.generate();
+        self.rings = coarse.rings;
+    }
+}
 
-        svg::save(filename, &document)
-            .map_err(|e| SpirographError::ExportError(format!("Failed to save SVG: {}", e)))
+impl PointCounted for DraperieLayer {
+    fn point_count(&self) -> usize {
+        self.rings.iter().map(Vec::len).sum()
     }
 }
 
@@ -358,6 +870,19 @@ impl DraperieLayer {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_generation_matches_serial() {
+        let mut config = DraperieConfig::new(80, 25.0);
+        config.dash_by_phase = Some(DashSpec::new(3, 0.6));
+        let mut serial = DraperieLayer::new(config.clone()).unwrap();
+        serial.generate();
+        let mut parallel = DraperieLayer::new(config).unwrap();
+        parallel.generate_parallel();
+        assert!(parallel.rings().len() > 80);
+        assert_eq!(parallel.rings(), serial.rings());
+    }
+
     #[test]
     fn test_draperie_config_default() {
         let config = DraperieConfig::default();
@@ -443,6 +968,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_jitter_is_seeded_and_keeps_rings_apart() {
+        use crate::common::polyline_closes;
+
+        let rings = |seed: u64| {
+            let config = DraperieConfig::new(40, 15.0)
+                .with_resolution(600)
+                .with_jitter(JitterConfig::new(1.0, 0.05, seed));
+            let mut layer = DraperieLayer::new(config).unwrap();
+            layer.generate();
+            layer.rings().clone()
+        };
+        let plain = {
+            let mut layer =
+                DraperieLayer::new(DraperieConfig::new(40, 15.0).with_resolution(600)).unwrap();
+            layer.generate();
+            layer.rings().clone()
+        };
+        let jittered = rings(3);
+        assert_eq!(jittered, rings(3));
+        assert_ne!(jittered, rings(4));
+        assert_ne!(jittered, plain);
+
+        // A sigma far above the ring gap is clamped: every point stays on
+        // its angle, ordered between its neighbours, and rings stay closed
+        let radius = |p: &Point2D| p.x.hypot(p.y);
+        for (ring, plain_ring) in jittered.iter().zip(&plain) {
+            assert_eq!(ring.len(), plain_ring.len());
+            assert!(polyline_closes(ring));
+            for (p, q) in ring.iter().zip(plain_ring) {
+                let turn = p.y.atan2(p.x) - q.y.atan2(q.x);
+                assert!(turn.sin().abs() < 1e-9);
+            }
+        }
+        for pair in jittered.windows(2) {
+            for (inner, outer) in pair[0].iter().zip(&pair[1]) {
+                assert!(radius(outer) > radius(inner));
+            }
+        }
+    }
+
     #[test]
     fn test_safe_amplitude_not_zero() {
         let config = DraperieConfig::default();
@@ -450,6 +1016,105 @@ mod tests {
         assert!(amp > 0.0, "Safe amplitude should be positive, got {}", amp);
     }
 
+    /// Apply `edit` to `field` and check that `affects_geometry` predicted
+    /// whether the rings changed; returns whether they did
+    fn edit_changes_rings(
+        config: &DraperieConfig,
+        field: &str,
+        edit: impl Fn(&mut DraperieConfig),
+    ) -> bool {
+        let hash = |config: &DraperieConfig| {
+            let mut layer = DraperieLayer::new(config.clone()).unwrap();
+            layer.generate();
+            layer.geometry_hash()
+        };
+        let mut edited = config.clone();
+        edit(&mut edited);
+        let changed = hash(config) != hash(&edited);
+        assert_eq!(config.affects_geometry(field), changed, "{}", field);
+        changed
+    }
+
+    #[test]
+    fn test_affects_geometry_rules() {
+        let base = DraperieConfig {
+            num_rings: 12,
+            base_radius: 10.0,
+            wave_frequency: 10.0,
+            resolution: PointsPerRev(240),
+            ..Default::default()
+        };
+
+        // phase_exponent only matters in sin-power mode
+        assert!(!edit_changes_rings(&base, "phase_exponent", |c| c
+            .phase_exponent =
+            1));
+        let sin_power = DraperieConfig {
+            circular_phase: 0.0,
+            ..base.clone()
+        };
+        assert!(edit_changes_rings(&sin_power, "phase_exponent", |c| c
+            .phase_exponent =
+            1));
+
+        // No phase shift: the envelope fields are inert
+        let no_phase = DraperieConfig {
+            phase_shift: 0.0,
+            ..sin_power.clone()
+        };
+        assert!(!edit_changes_rings(&no_phase, "phase_oscillations", |c| {
+            c.phase_oscillations = 1.0
+        }));
+        assert!(!edit_changes_rings(&no_phase, "circular_phase", |c| c
+            .circular_phase =
+            3.0));
+        assert!(!edit_changes_rings(&no_phase, "phase_exponent", |c| c
+            .phase_exponent =
+            1));
+
+        // Zero amplitude: plain circles whatever the wave settings
+        let flat = DraperieConfig {
+            amplitude: Some(0.0),
+            ..base.clone()
+        };
+        assert_eq!(flat.effective_amplitude(), 0.0);
+        assert!(!edit_changes_rings(&flat, "wave_frequency", |c| c
+            .wave_frequency =
+            7.0));
+        assert!(!edit_changes_rings(&flat, "wave_exponent", |c| c
+            .wave_exponent =
+            3));
+        assert!(!edit_changes_rings(&flat, "phase_shift", |c| c
+            .phase_shift =
+            0.5));
+
+        // A single ring with a fixed amplitude ignores the step and phase cycles
+        let single = DraperieConfig {
+            num_rings: 1,
+            amplitude: Some(0.3),
+            ..base.clone()
+        };
+        assert!(!edit_changes_rings(&single, "radius_step", |c| c
+            .radius_step =
+            0.6));
+        assert!(!edit_changes_rings(&single, "phase_oscillations", |c| c
+            .phase_oscillations =
+            1.0));
+
+        // ...but an automatic amplitude is recomputed from both
+        let auto = DraperieConfig {
+            amplitude: None,
+            ..single.clone()
+        };
+        assert_eq!(auto.effective_amplitude(), auto.safe_amplitude());
+        assert!(edit_changes_rings(&auto, "radius_step", |c| c
+            .radius_step =
+            0.6));
+        assert!(edit_changes_rings(&auto, "wave_frequency", |c| c
+            .wave_frequency =
+            8.0));
+    }
+
     #[test]
     fn test_draperie_matches_rose_engine() {
         use crate::rose_engine::RoseEngineLatheRun;
@@ -461,7 +1126,7 @@ mod tests {
         let wave_frequency = 12.0;
         let phase_shift = PI / 12.0;
         let phase_oscillations = 2.5;
-        let resolution = 1500;
+        let resolution = PointsPerRev(1500);
         let phase_exponent = 3_u32;
         let wave_exponent = 1_u32;
         let circular_phase = 2.0_f64;
@@ -479,6 +1144,9 @@ mod tests {
             phase_exponent,
             wave_exponent,
             circular_phase,
+            non_integer_mode: NonIntegerMode::Round,
+            dash_by_phase: None,
+            jitter: None,
         };
         let mut math_layer = DraperieLayer::new(config).unwrap();
         math_layer.generate();
@@ -501,10 +1169,81 @@ mod tests {
         .unwrap();
         rose_run.generate();
 
-        // Both should have the same number of rings/lines
-        let math_lines = math_layer.lines();
-        let rose_lines = rose_run.lines();
+        assert_same_rings(math_layer.lines(), rose_run.lines());
+
+        // A non-integer frequency is closed the same way by both, rounded
+        // by default and over two turns on request
+        let drifting = DraperieConfig {
+            num_rings: 24,
+            wave_frequency: 11.5,
+            resolution: PointsPerRev(360),
+            ..DraperieConfig::default()
+        };
+        for mode in [NonIntegerMode::Round, NonIntegerMode::MultiRevolution] {
+            let config = drifting.clone().with_non_integer_mode(mode);
+            let mut math_layer = DraperieLayer::new(config.clone()).unwrap();
+            math_layer.generate();
+            let mut rose_run = RoseEngineLatheRun::from_draperie_config(&config, 0.0, 0.0).unwrap();
+            rose_run.generate();
+            assert_same_rings(math_layer.lines(), rose_run.lines());
+        }
+    }
+
+    #[test]
+    fn test_dashed_draperie_matches_rose_engine() {
+        use crate::rose_engine::RoseEngineLatheRun;
+
+        let config = DraperieConfig {
+            num_rings: 12,
+            resolution: PointsPerRev(720),
+            ..DraperieConfig::default()
+        }
+        .with_dash_by_phase(DashSpec::new(36, 0.6));
+        let mut math_layer = DraperieLayer::new(config.clone()).unwrap();
+        math_layer.generate();
+        assert_eq!(math_layer.lines().len(), 12 * 36);
+
+        let mut rose_run = RoseEngineLatheRun::from_draperie_config(&config, 0.0, 0.0).unwrap();
+        rose_run.generate();
+        assert_same_rings(math_layer.lines(), rose_run.lines());
 
+        let path = std::env::temp_dir().join("test_dashed_draperie_filled.svg");
+        assert!(math_layer
+            .to_svg_filled(path.to_str().unwrap(), 0.5)
+            .is_err());
+        assert!(rose_run.to_svg_filled(path.to_str().unwrap(), 0.5).is_err());
+    }
+
+    #[test]
+    fn test_iter_rings_and_streaming_svg_match_generate() {
+        let configs = [
+            DraperieConfig::new(24, 15.0),
+            DraperieConfig {
+                wave_frequency: 4.5,
+                non_integer_mode: NonIntegerMode::MultiRevolution,
+                ..DraperieConfig::new(8, 12.0)
+            },
+            DraperieConfig::new(12, 15.0).with_dash_by_phase(DashSpec::new(9, 0.6)),
+        ];
+        for (k, config) in configs.into_iter().enumerate() {
+            let mut layer = DraperieLayer::new_with_center(config, 1.5, -2.0).unwrap();
+            layer.set_max_points_per_path(100).unwrap();
+            let lazy: Vec<Vec<Point2D>> = layer.iter_rings().map(Iterator::collect).collect();
+            assert!(layer.rings().is_empty());
+
+            let streamed = std::env::temp_dir().join(format!("test_draperie_stream_{}.svg", k));
+            layer.to_svg_streaming(streamed.to_str().unwrap()).unwrap();
+            let streamed_svg = std::fs::read_to_string(&streamed).unwrap();
+            let _ = std::fs::remove_file(&streamed);
+
+            layer.generate();
+            assert_eq!(&lazy, layer.rings());
+            assert_eq!(streamed_svg, layer.to_svg_string().unwrap());
+        }
+    }
+
+    /// Assert a draperie layer and its rose engine twin drew the same rings
+    fn assert_same_rings(math_lines: &[Vec<Point2D>], rose_lines: &[Vec<Point2D>]) {
         assert_eq!(
             math_lines.len(),
             rose_lines.len(),
@@ -538,4 +1277,240 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_non_integer_frequency_closes_over_two_turns() {
+        let config = DraperieConfig {
+            num_rings: 12,
+            wave_frequency: 11.5,
+            resolution: PointsPerRev(360),
+            ..DraperieConfig::default()
+        };
+        assert_eq!(config.ring_frequency(), (12.0, 1));
+        let report = config.normalization_report().unwrap();
+        assert_eq!((report.requested, report.used), (11.5, 12.0));
+        assert!(config.affects_geometry("non_integer_mode"));
+
+        let config = config.with_non_integer_mode(NonIntegerMode::MultiRevolution);
+        assert_eq!(config.ring_frequency(), (11.5, 2));
+        assert_eq!(config.normalization_report(), None);
+        let mut layer = DraperieLayer::new(config.clone()).unwrap();
+        layer.generate();
+        let radius = |p: &Point2D| p.x.hypot(p.y);
+        for ring in layer.rings() {
+            assert_eq!(ring.len(), 2 * 360 + 1);
+            assert_eq!(ring.first(), ring.last());
+            // The seam is no steeper than any other step of the ring
+            let steps: Vec<f64> = ring
+                .windows(2)
+                .map(|w| (radius(&w[1]) - radius(&w[0])).abs())
+                .collect();
+            let interior = steps[..steps.len() - 1].iter().cloned().fold(0.0, f64::max);
+            assert!(steps[steps.len() - 1] <= interior + 1e-9);
+        }
+
+        // Without the snap the second turn already ends where the first began
+        let mut run =
+            crate::rose_engine::RoseEngineLatheRun::from_draperie_config(&config, 0.0, 0.0)
+                .unwrap();
+        run.generate();
+        for ring in run.lines() {
+            let seam = (radius(&ring[ring.len() - 1]) - radius(&ring[0])).abs();
+            assert!(seam < 1e-9, "seam step {}", seam);
+        }
+
+        // A frequency that is no simple fraction uses the closest one
+        let irrational = DraperieConfig {
+            wave_frequency: 12.0 + std::f64::consts::SQRT_2 / 10.0,
+            ..config
+        };
+        let (used, revolutions) = irrational.ring_frequency();
+        assert!(revolutions <= MAX_CLOSING_REVOLUTIONS);
+        assert!((used * revolutions as f64).fract() == 0.0);
+        assert!(irrational.normalization_report().is_some());
+    }
+
+    #[test]
+    fn test_draperie_dxf_export() {
+        let mut layer = DraperieLayer::new(DraperieConfig::new(12, 15.0)).unwrap();
+        let tmpfile = std::env::temp_dir().join("test_draperie.dxf");
+        let path = tmpfile.to_str().expect("temp dir path is valid UTF-8");
+        assert!(layer.to_dxf(path).is_err());
+
+        layer.generate();
+        layer.to_dxf(path).unwrap();
+        let dxf = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(dxf.matches("\nLWPOLYLINE\n").count(), 12);
+        assert_eq!(crate::dxf::read_dxf_polylines(&dxf), *layer.lines());
+    }
+
+    #[test]
+    fn test_draperie_svg_splits_long_rings() {
+        let config = DraperieConfig {
+            num_rings: 1,
+            resolution: PointsPerRev(25_000),
+            ..Default::default()
+        };
+        let mut layer = DraperieLayer::new(config).unwrap();
+        layer.generate();
+        assert!(layer.set_max_points_per_path(1).is_err());
+
+        let dir = std::env::temp_dir();
+        let split_file = dir.join("turtles_draperie_split.svg");
+        let whole_file = dir.join("turtles_draperie_whole.svg");
+        layer.to_svg(split_file.to_str().unwrap()).unwrap();
+        layer.set_max_points_per_path(100_000).unwrap();
+        layer.to_svg(whole_file.to_str().unwrap()).unwrap();
+
+        let split = std::fs::read_to_string(&split_file).unwrap();
+        let whole = std::fs::read_to_string(&whole_file).unwrap();
+        std::fs::remove_file(&split_file).ok();
+        std::fs::remove_file(&whole_file).ok();
+
+        assert_eq!(split.matches("<path").count(), 3);
+        assert_eq!(split.matches("<g").count(), 1);
+        assert_eq!(whole.matches("<path").count(), 1);
+        assert!(!whole.contains("<g"));
+
+        let view_box = |svg: &str| {
+            let start = svg.find("viewBox=\"").unwrap() + 9;
+            let end = start + svg[start..].find('"').unwrap();
+            svg[start..end].to_string()
+        };
+        assert_eq!(view_box(&split), view_box(&whole));
+    }
+
+    #[test]
+    fn test_draperie_svg_precision_rounds_and_shrinks_the_file() {
+        let mut layer = DraperieLayer::new(DraperieConfig::new(20, 25.0)).unwrap();
+        layer.generate();
+
+        let dir = std::env::temp_dir();
+        let export = |precision: usize, name: &str| {
+            let file = dir.join(name);
+            let config = ExportConfig {
+                svg_precision: precision,
+                ..Default::default()
+            };
+            layer
+                .to_svg_with_config(file.to_str().unwrap(), &config)
+                .unwrap();
+            let svg = std::fs::read_to_string(&file).unwrap();
+            std::fs::remove_file(&file).ok();
+            svg
+        };
+        let full = export(15, "turtles_draperie_precision_15.svg");
+        let default = export(DEFAULT_SVG_PRECISION, "turtles_draperie_precision_4.svg");
+        let coarse = export(2, "turtles_draperie_precision_2.svg");
+
+        assert!(default.len() * 2 < full.len());
+        assert_eq!(
+            default.matches("<path").count(),
+            full.matches("<path").count()
+        );
+
+        let decimals = |svg: &str| {
+            svg.split("d=\"")
+                .skip(1)
+                .flat_map(|rest| rest[..rest.find('"').unwrap()].split([' ', ',', 'M', 'L']))
+                .filter_map(|number| number.split_once('.'))
+                .map(|(_, fraction)| fraction.len())
+                .max()
+                .unwrap_or(0)
+        };
+        assert_eq!(decimals(&coarse), 2);
+        assert_eq!(decimals(&default), DEFAULT_SVG_PRECISION);
+        assert!(decimals(&full) > DEFAULT_SVG_PRECISION);
+    }
+
+    #[test]
+    fn test_draperie_svg_filled_draws_one_band_per_ring_pair() {
+        let config = DraperieConfig {
+            num_rings: 12,
+            resolution: PointsPerRev(180),
+            ..Default::default()
+        };
+        let mut layer = DraperieLayer::new(config).unwrap();
+        layer.generate();
+        let rings_before = layer.rings().clone();
+
+        let file = std::env::temp_dir().join("turtles_draperie_filled.svg");
+        layer
+            .to_svg_filled(file.to_str().unwrap(), -PI / 4.0)
+            .unwrap();
+        let svg = std::fs::read_to_string(&file).unwrap();
+        std::fs::remove_file(&file).ok();
+
+        assert_eq!(svg.matches("class=\"band\"").count(), 12 - 1);
+        assert_eq!(svg.matches("<defs>").count(), 1);
+        for k in 0..crate::shading::SHADE_GRADIENTS {
+            let id = crate::shading::shade_gradient_id(k);
+            assert_eq!(svg.matches(&format!("id=\"{}\"", id)).count(), 1);
+        }
+        // Bands sit beneath the stroked rings
+        assert!(svg.find("class=\"band\"").unwrap() < svg.find("stroke=\"black\"").unwrap());
+        assert_eq!(layer.rings(), &rings_before);
+
+        assert!(layer
+            .to_svg_filled(file.to_str().unwrap(), f64::NAN)
+            .is_err());
+    }
+
+    #[test]
+    fn test_zero_amplitude_rings_are_circles() {
+        use crate::rose_engine::RoseEngineLatheRun;
+
+        let config = DraperieConfig {
+            num_rings: 24,
+            amplitude: Some(0.0),
+            resolution: PointsPerRev(360),
+            ..DraperieConfig::default()
+        };
+        let mut layer = DraperieLayer::new_with_center(config.clone(), 1.5, -2.0).unwrap();
+        layer.generate();
+        assert_eq!(layer.rings().len(), 24);
+        for (i, ring) in layer.rings().iter().enumerate() {
+            let expected = config.innermost_radius() + i as f64 * config.radius_step;
+            for p in ring {
+                let r = (p.x - 1.5).hypot(p.y + 2.0);
+                assert!((r - expected).abs() < 1e-12, "ring {} radius {}", i, r);
+            }
+        }
+
+        // The rose engine twin cuts the same circles
+        let mut run = RoseEngineLatheRun::from_draperie_config(&config, 1.5, -2.0).unwrap();
+        run.generate();
+        assert_eq!(run.lines().len(), 24);
+        for (a, b) in layer.rings().iter().zip(run.lines()) {
+            for (p, q) in a.iter().zip(b) {
+                assert!((p.x - q.x).abs() < 1e-10 && (p.y - q.y).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_negative_amplitude_and_collapsed_stack() {
+        let negative = DraperieConfig {
+            amplitude: Some(-0.1),
+            ..DraperieConfig::default()
+        };
+        assert!(DraperieLayer::new(negative).is_err());
+
+        // 11 rings 1mm apart about 5mm puts the innermost ring at the centre
+        let collapsed = DraperieConfig {
+            num_rings: 11,
+            radius_step: 1.0,
+            base_radius: 5.0,
+            amplitude: Some(0.0),
+            ..DraperieConfig::default()
+        };
+        assert_eq!(collapsed.innermost_radius(), 0.0);
+        assert!(DraperieLayer::new(collapsed.clone()).is_err());
+        assert!(
+            crate::rose_engine::RoseEngineLatheRun::from_draperie_config(&collapsed, 0.0, 0.0)
+                .is_err()
+        );
+    }
 }
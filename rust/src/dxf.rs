@@ -0,0 +1,124 @@
+use std::fmt::Write;
+
+use crate::common::{Point2D, SpirographError};
+use crate::metadata::FileMetadata;
+
+/// Write polylines to an ASCII DXF file as LWPOLYLINE entities
+///
+/// The file has a minimal HEADER (version and millimetre units) and an
+/// ENTITIES section on layer 0. Coordinates are written as generated, with
+/// no axis flip, matching the STL exporters. `closed` sets the closed flag
+/// on every polyline, so closed contours should not repeat their first
+/// point.
+///
+/// With `metadata`, each entry is written as a leading comment and the
+/// design id as the `$PROJECTNAME` header variable.
+pub(crate) fn write_dxf_polylines(
+    filename: &str,
+    lines: &[Vec<Point2D>],
+    closed: bool,
+    metadata: Option<&FileMetadata>,
+) -> Result<(), SpirographError> {
+    let mut dxf = String::new();
+    if let Some(metadata) = metadata {
+        dxf.push_str(&metadata.dxf_comments());
+    }
+    // Writing to a String cannot fail
+    let _ = write!(
+        dxf,
+        "0\nSECTION\n2\nHEADER\n9\n$ACADVER\n1\nAC1015\n9\n$INSUNITS\n70\n4\n"
+    );
+    if let Some(metadata) = metadata.filter(|m| !m.design_id.is_empty()) {
+        let _ = write!(
+            dxf,
+            "9\n$PROJECTNAME\n1\n{}\n",
+            metadata.design_id.replace(|c: char| c.is_control(), " ")
+        );
+    }
+    dxf.push_str("0\nENDSEC\n0\nSECTION\n2\nENTITIES\n");
+    for line in lines.iter().filter(|line| line.len() >= 2) {
+        let _ = write!(
+            dxf,
+            "0\nLWPOLYLINE\n100\nAcDbEntity\n8\n0\n100\nAcDbPolyline\n90\n{}\n70\n{}\n",
+            line.len(),
+            u8::from(closed)
+        );
+        for point in line {
+            let _ = write!(dxf, "10\n{}\n20\n{}\n", point.x, point.y);
+        }
+    }
+    dxf.push_str("0\nENDSEC\n0\nEOF\n");
+
+    std::fs::write(filename, dxf).map_err(|e| {
+        SpirographError::ExportError(format!("Failed to write DXF file '{}': {}", filename, e))
+    })
+}
+
+/// Read back the LWPOLYLINE entities written by `write_dxf_polylines`
+///
+/// Panics if a polyline's vertex count (group code 90) does not match the
+/// vertices that follow it.
+#[cfg(test)]
+pub(crate) fn read_dxf_polylines(dxf: &str) -> Vec<Vec<Point2D>> {
+    let lines: Vec<&str> = dxf.lines().collect();
+    let mut polylines: Vec<(usize, Vec<Point2D>)> = Vec::new();
+    let mut in_polyline = false;
+    for pair in lines.chunks(2) {
+        let (code, value) = (pair[0].trim(), pair.get(1).copied().unwrap_or(""));
+        match code {
+            "0" => {
+                in_polyline = value == "LWPOLYLINE";
+                if in_polyline {
+                    polylines.push((0, Vec::new()));
+                }
+            }
+            "90" if in_polyline => polylines.last_mut().unwrap().0 = value.parse().unwrap(),
+            "10" if in_polyline => {
+                let x = value.parse().unwrap();
+                polylines.last_mut().unwrap().1.push(Point2D::new(x, 0.0));
+            }
+            "20" if in_polyline => {
+                polylines.last_mut().unwrap().1.last_mut().unwrap().y = value.parse().unwrap();
+            }
+            _ => {}
+        }
+    }
+    polylines
+        .into_iter()
+        .map(|(count, points)| {
+            assert_eq!(count, points.len());
+            points
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dxf_polylines_round_trip() {
+        let lines = vec![
+            vec![Point2D::new(0.0, 0.0), Point2D::new(1.5, -2.25)],
+            vec![Point2D::new(3.0, 3.0)],
+            vec![
+                Point2D::new(-1.0, 0.125),
+                Point2D::new(0.0, 1.0),
+                Point2D::new(1.0, 0.125),
+            ],
+        ];
+        let path = std::env::temp_dir().join("test_dxf_round_trip.dxf");
+        let path = path.to_str().unwrap();
+        write_dxf_polylines(path, &lines, false, None).unwrap();
+        let dxf = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        assert!(dxf.contains("$INSUNITS\n70\n4\n"));
+        assert!(dxf.ends_with("0\nEOF\n"));
+        // The single-point line is skipped
+        assert_eq!(
+            read_dxf_polylines(&dxf),
+            vec![lines[0].clone(), lines[2].clone()]
+        );
+    }
+}
@@ -0,0 +1,605 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_polylines, polyline_is_degenerate,
+    scale_polylines, simplified_layer, simplify_polylines, ExportConfig, PatternLayer, Point2D,
+    SpirographError, StoredLayer, DEDUPE_EPSILON,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+
+/// Cap height of the built-in font in font units
+const FONT_CAP_HEIGHT: f64 = 21.0;
+
+/// Fixed cell width of the built-in font in font units (the digit width)
+const FONT_CELL_WIDTH: f64 = 20.0;
+
+/// A glyph of the built-in single-stroke font: advance width and strokes
+///
+/// Coordinates are Hershey font units with the baseline at y = 0 and
+/// capitals 21 units tall, y pointing up.
+struct Glyph {
+    width: u8,
+    strokes: &'static [&'static [(i8, i8)]],
+}
+
+/// Look up a glyph of the built-in Hershey simplex subset
+///
+/// Covers digits, A–Z and `space . , - + / : '`; lowercase letters use
+/// the capitals.
+#[rustfmt::skip]
+fn glyph(c: char) -> Option<Glyph> {
+    let (width, strokes): (u8, &'static [&'static [(i8, i8)]]) = match c.to_ascii_uppercase() {
+        ' ' => (16, &[]),
+        '0' => (20, &[&[
+            (9, 21), (6, 20), (4, 17), (3, 12), (3, 9), (4, 4), (6, 1), (9, 0), (11, 0),
+            (14, 1), (16, 4), (17, 9), (17, 12), (16, 17), (14, 20), (11, 21), (9, 21),
+        ]]),
+        '1' => (20, &[&[(6, 17), (8, 18), (11, 21), (11, 0)]]),
+        '2' => (20, &[&[
+            (4, 16), (4, 17), (5, 19), (6, 20), (8, 21), (12, 21), (14, 20), (15, 19),
+            (16, 17), (16, 15), (15, 13), (13, 10), (3, 0), (17, 0),
+        ]]),
+        '3' => (20, &[&[
+            (5, 21), (16, 21), (10, 13), (13, 13), (15, 12), (16, 11), (17, 8), (17, 6),
+            (16, 3), (14, 1), (11, 0), (8, 0), (5, 1), (4, 2), (3, 4),
+        ]]),
+        '4' => (20, &[&[(13, 21), (3, 7), (18, 7)], &[(13, 21), (13, 0)]]),
+        '5' => (20, &[&[
+            (15, 21), (5, 21), (4, 12), (5, 13), (8, 14), (11, 14), (14, 13), (16, 11),
+            (17, 8), (17, 6), (16, 3), (14, 1), (11, 0), (8, 0), (5, 1), (4, 2), (3, 4),
+        ]]),
+        '6' => (20, &[&[
+            (16, 18), (15, 20), (12, 21), (10, 21), (7, 20), (5, 17), (4, 12), (4, 7),
+            (5, 3), (7, 1), (10, 0), (11, 0), (14, 1), (16, 3), (17, 6), (17, 7), (16, 10),
+            (14, 12), (11, 13), (10, 13), (7, 12), (5, 10), (4, 7),
+        ]]),
+        '7' => (20, &[&[(17, 21), (7, 0)], &[(3, 21), (17, 21)]]),
+        '8' => (20, &[&[
+            (8, 21), (5, 20), (4, 18), (4, 16), (5, 14), (7, 13), (11, 12), (14, 11),
+            (16, 9), (17, 7), (17, 4), (16, 2), (15, 1), (12, 0), (8, 0), (5, 1), (4, 2),
+            (3, 4), (3, 7), (4, 9), (6, 11), (9, 12), (13, 13), (15, 14), (16, 16),
+            (16, 18), (15, 20), (12, 21), (8, 21),
+        ]]),
+        '9' => (20, &[&[
+            (16, 14), (15, 11), (13, 9), (10, 8), (9, 8), (6, 9), (4, 11), (3, 14), (3, 15),
+            (4, 18), (6, 20), (9, 21), (10, 21), (13, 20), (15, 18), (16, 14), (16, 9),
+            (15, 4), (13, 1), (10, 0), (8, 0), (5, 1), (4, 3),
+        ]]),
+        'A' => (18, &[&[(9, 21), (1, 0)], &[(9, 21), (17, 0)], &[(4, 7), (14, 7)]]),
+        'B' => (21, &[
+            &[(4, 21), (4, 0)],
+            &[(4, 21), (13, 21), (16, 20), (17, 19), (18, 17), (18, 15), (17, 13), (16, 12),
+              (13, 11)],
+            &[(4, 11), (13, 11), (16, 10), (17, 9), (18, 7), (18, 4), (17, 2), (16, 1),
+              (13, 0), (4, 0)],
+        ]),
+        'C' => (21, &[&[
+            (18, 16), (17, 18), (15, 20), (13, 21), (9, 21), (7, 20), (5, 18), (4, 16),
+            (3, 13), (3, 8), (4, 5), (5, 3), (7, 1), (9, 0), (13, 0), (15, 1), (17, 3),
+            (18, 5),
+        ]]),
+        'D' => (21, &[
+            &[(4, 21), (4, 0)],
+            &[(4, 21), (11, 21), (14, 20), (16, 18), (17, 16), (18, 13), (18, 8), (17, 5),
+              (16, 3), (14, 1), (11, 0), (4, 0)],
+        ]),
+        'E' => (19, &[
+            &[(4, 21), (4, 0)], &[(4, 21), (17, 21)], &[(4, 11), (12, 11)], &[(4, 0), (17, 0)],
+        ]),
+        'F' => (18, &[&[(4, 21), (4, 0)], &[(4, 21), (17, 21)], &[(4, 11), (12, 11)]]),
+        'G' => (21, &[
+            &[(18, 16), (17, 18), (15, 20), (13, 21), (9, 21), (7, 20), (5, 18), (4, 16),
+              (3, 13), (3, 8), (4, 5), (5, 3), (7, 1), (9, 0), (13, 0), (15, 1), (17, 3),
+              (18, 5), (18, 8)],
+            &[(13, 8), (18, 8)],
+        ]),
+        'H' => (22, &[&[(4, 21), (4, 0)], &[(18, 21), (18, 0)], &[(4, 11), (18, 11)]]),
+        'I' => (8, &[&[(4, 21), (4, 0)]]),
+        'J' => (16, &[&[
+            (12, 21), (12, 5), (11, 2), (10, 1), (8, 0), (6, 0), (4, 1), (3, 2), (2, 5),
+            (2, 7),
+        ]]),
+        'K' => (21, &[&[(4, 21), (4, 0)], &[(18, 21), (4, 7)], &[(9, 12), (18, 0)]]),
+        'L' => (17, &[&[(4, 21), (4, 0)], &[(4, 0), (16, 0)]]),
+        'M' => (24, &[
+            &[(4, 21), (4, 0)], &[(4, 21), (12, 0)], &[(20, 21), (12, 0)], &[(20, 21), (20, 0)],
+        ]),
+        'N' => (22, &[&[(4, 21), (4, 0)], &[(4, 21), (18, 0)], &[(18, 21), (18, 0)]]),
+        'O' => (22, &[&[
+            (9, 21), (7, 20), (5, 18), (4, 16), (3, 13), (3, 8), (4, 5), (5, 3), (7, 1),
+            (9, 0), (13, 0), (15, 1), (17, 3), (18, 5), (19, 8), (19, 13), (18, 16),
+            (17, 18), (15, 20), (13, 21), (9, 21),
+        ]]),
+        'P' => (21, &[
+            &[(4, 21), (4, 0)],
+            &[(4, 21), (13, 21), (16, 20), (17, 19), (18, 17), (18, 14), (17, 12), (16, 11),
+              (13, 10), (4, 10)],
+        ]),
+        'Q' => (22, &[
+            &[(9, 21), (7, 20), (5, 18), (4, 16), (3, 13), (3, 8), (4, 5), (5, 3), (7, 1),
+              (9, 0), (13, 0), (15, 1), (17, 3), (18, 5), (19, 8), (19, 13), (18, 16),
+              (17, 18), (15, 20), (13, 21), (9, 21)],
+            &[(12, 4), (18, -2)],
+        ]),
+        'R' => (21, &[
+            &[(4, 21), (4, 0)],
+            &[(4, 21), (13, 21), (16, 20), (17, 19), (18, 17), (18, 15), (17, 13), (16, 12),
+              (13, 11), (4, 11)],
+            &[(11, 11), (18, 0)],
+        ]),
+        'S' => (20, &[&[
+            (17, 18), (15, 20), (12, 21), (8, 21), (5, 20), (3, 18), (3, 16), (4, 14),
+            (5, 13), (7, 12), (13, 10), (15, 9), (16, 8), (17, 6), (17, 3), (15, 1),
+            (12, 0), (8, 0), (5, 1), (3, 3),
+        ]]),
+        'T' => (16, &[&[(8, 21), (8, 0)], &[(1, 21), (15, 21)]]),
+        'U' => (22, &[&[
+            (4, 21), (4, 6), (5, 3), (7, 1), (10, 0), (12, 0), (15, 1), (17, 3), (18, 6),
+            (18, 21),
+        ]]),
+        'V' => (18, &[&[(1, 21), (9, 0)], &[(17, 21), (9, 0)]]),
+        'W' => (24, &[
+            &[(2, 21), (7, 0)], &[(12, 21), (7, 0)], &[(12, 21), (17, 0)], &[(22, 21), (17, 0)],
+        ]),
+        'X' => (20, &[&[(3, 21), (17, 0)], &[(17, 21), (3, 0)]]),
+        'Y' => (18, &[&[(1, 21), (9, 11), (9, 0)], &[(17, 21), (9, 11)]]),
+        'Z' => (20, &[&[(17, 21), (3, 0)], &[(3, 21), (17, 21)], &[(3, 0), (17, 0)]]),
+        '.' => (10, &[&[(5, 2), (4, 1), (5, 0), (6, 1), (5, 2)]]),
+        ',' => (10, &[&[(6, 1), (5, 0), (4, 1), (5, 2), (6, 1), (6, -1), (5, -3), (4, -4)]]),
+        '-' => (26, &[&[(4, 9), (22, 9)]]),
+        '+' => (26, &[&[(13, 18), (13, 0)], &[(4, 9), (22, 9)]]),
+        '/' => (22, &[&[(20, 25), (2, -7)]]),
+        ':' => (10, &[
+            &[(5, 14), (4, 13), (5, 12), (6, 13), (5, 14)],
+            &[(5, 2), (4, 1), (5, 0), (6, 1), (5, 2)],
+        ]),
+        '\'' => (10, &[&[(5, 21), (5, 14)]]),
+        _ => return None,
+    };
+    Some(Glyph { width, strokes })
+}
+
+/// Reading direction of text set along an arc
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ArcDirection {
+    /// Reads clockwise with glyph tops facing outward (the top of a dial)
+    #[default]
+    Clockwise,
+    /// Reads counter-clockwise with glyph tops facing the center (the
+    /// bottom of a dial)
+    CounterClockwise,
+}
+
+/// Where engraved text is placed on the dial
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TextPosition {
+    /// Straight text whose baseline is centered on `origin`, running at
+    /// `angle` radians (0 reads left to right)
+    Linear { origin: Point2D, angle: f64 },
+    /// Text whose baseline follows a circle of `radius` about the origin,
+    /// centered at `center_angle` radians (12 o'clock is -π/2, screen
+    /// coordinates). Each glyph is rotated so its baseline is tangent to
+    /// the arc.
+    Arc {
+        radius: f64,
+        center_angle: f64,
+        direction: ArcDirection,
+    },
+}
+
+impl TextPosition {
+    /// Arc placement centered on a clock position
+    pub fn arc_at_clock(hour: u32, minute: u32, radius: f64, direction: ArcDirection) -> Self {
+        let (x, y) = clock_to_cartesian(hour, minute, 1.0);
+        TextPosition::Arc {
+            radius,
+            center_angle: y.atan2(x),
+            direction,
+        }
+    }
+}
+
+/// Configuration for engraved single-stroke text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngravedTextConfig {
+    /// Text to engrave; see [`EngravedTextLayer::supports`] for the
+    /// available characters
+    pub text: String,
+    /// Capital height in mm
+    pub height: f64,
+    /// Straight or arc placement
+    pub position: TextPosition,
+    /// Extra gap between fixed-width glyph cells in mm
+    pub spacing: f64,
+}
+
+impl Default for EngravedTextConfig {
+    fn default() -> Self {
+        EngravedTextConfig {
+            text: "12".to_string(),
+            height: 3.0,
+            position: TextPosition::Linear {
+                origin: Point2D::new(0.0, 0.0),
+                angle: 0.0,
+            },
+            spacing: 0.0,
+        }
+    }
+}
+
+impl EngravedTextConfig {
+    /// Create a text configuration
+    ///
+    /// # Arguments
+    /// * `text` - Text to engrave
+    /// * `height` - Capital height in mm
+    /// * `position` - Straight or arc placement
+    pub fn new(text: &str, height: f64, position: TextPosition) -> Self {
+        EngravedTextConfig {
+            text: text.to_string(),
+            height,
+            position,
+            ..Default::default()
+        }
+    }
+
+    /// Set the extra gap between glyph cells
+    pub fn with_spacing(mut self, spacing: f64) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Check the text, height, spacing and arc radius
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.text.trim().is_empty() {
+            return Err(SpirographError::InvalidParameter(
+                "text must not be empty".to_string(),
+            ));
+        }
+        if let Some(c) = self.text.chars().find(|&c| !EngravedTextLayer::supports(c)) {
+            return Err(SpirographError::InvalidParameter(format!(
+                "character {:?} is not in the engraving font",
+                c
+            )));
+        }
+        if !(self.height.is_finite() && self.height > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "height must be positive".to_string(),
+            ));
+        }
+        if !(self.spacing.is_finite() && self.spacing >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "spacing must be non-negative".to_string(),
+            ));
+        }
+        if let TextPosition::Arc { radius, .. } = self.position {
+            if !(radius.is_finite() && radius > 0.0) {
+                return Err(SpirographError::InvalidParameter(
+                    "arc radius must be positive".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Engraved text as cuttable single-stroke polylines
+///
+/// Uses a built-in subset of the public-domain Hershey simplex font with
+/// fixed-width cells, so numerals line up on a dial. Every glyph stroke
+/// becomes one open polyline, which exports to SVG and toolpaths like any
+/// other layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngravedTextLayer {
+    pub config: EngravedTextConfig,
+    #[serde(skip)]
+    lines: Vec<Vec<Point2D>>,
+}
+
+impl EngravedTextLayer {
+    /// Create a text layer, checking that every character is in the font
+    pub fn new(config: EngravedTextConfig) -> Result<Self, SpirographError> {
+        config.validate()?;
+        Ok(EngravedTextLayer {
+            config,
+            lines: Vec::new(),
+        })
+    }
+
+    /// True if `c` can be engraved with the built-in font
+    pub fn supports(c: char) -> bool {
+        glyph(c).is_some()
+    }
+
+    /// Lay out the glyph strokes
+    ///
+    /// Glyphs sit in fixed-width cells centered on the position. On an arc
+    /// each cell center is placed along the circle at its arc length from
+    /// the text center and the glyph is rotated rigidly so its baseline is
+    /// tangent there.
+    pub fn generate(&mut self) {
+        self.lines.clear();
+
+        let scale = self.config.height / FONT_CAP_HEIGHT;
+        let cell = FONT_CELL_WIDTH * scale;
+        let advance = cell + self.config.spacing;
+        let count = self.config.text.chars().count();
+        let total = count as f64 * cell + count.saturating_sub(1) as f64 * self.config.spacing;
+
+        for (i, c) in self.config.text.chars().enumerate() {
+            let Some(glyph) = glyph(c) else { continue };
+            let cell_center = -total / 2.0 + cell / 2.0 + i as f64 * advance;
+
+            // Baseline origin of this glyph, reading direction and up direction
+            let (origin, along, up) = match self.config.position {
+                TextPosition::Linear { origin, angle } => {
+                    let (sin, cos) = angle.sin_cos();
+                    (
+                        Point2D::new(origin.x + cell_center * cos, origin.y + cell_center * sin),
+                        (cos, sin),
+                        (sin, -cos),
+                    )
+                }
+                TextPosition::Arc {
+                    radius,
+                    center_angle,
+                    direction,
+                } => {
+                    let sign = match direction {
+                        ArcDirection::Clockwise => 1.0,
+                        ArcDirection::CounterClockwise => -1.0,
+                    };
+                    let theta = center_angle + sign * cell_center / radius;
+                    let (sin, cos) = theta.sin_cos();
+                    (
+                        Point2D::new(radius * cos, radius * sin),
+                        (-sign * sin, sign * cos),
+                        (sign * cos, sign * sin),
+                    )
+                }
+            };
+
+            let half_width = glyph.width as f64 / 2.0;
+            for stroke in glyph.strokes {
+                let line = stroke
+                    .iter()
+                    .map(|&(x, y)| {
+                        let u = (x as f64 - half_width) * scale;
+                        let v = y as f64 * scale;
+                        Point2D::new(
+                            origin.x + u * along.0 + v * up.0,
+                            origin.y + u * along.1 + v * up.1,
+                        )
+                    })
+                    .collect();
+                self.lines.push(line);
+            }
+        }
+
+        dedupe_polylines(&mut self.lines, DEDUPE_EPSILON);
+        debug_assert_finite("EngravedTextLayer", &self.lines);
+    }
+
+    /// Get the generated strokes, one polyline per glyph stroke
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.lines
+    }
+
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply every length (height, spacing, placement and strokes) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.height *= factor;
+        self.config.spacing *= factor;
+        match &mut self.config.position {
+            TextPosition::Linear { origin, .. } => {
+                origin.x *= factor;
+                origin.y *= factor;
+            }
+            TextPosition::Arc { radius, .. } => *radius *= factor,
+        }
+        scale_polylines(&mut self.lines, factor);
+    }
+
+    /// Export the strokes to SVG format
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+
+        if self.lines.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.lines.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        for line in &layer.lines {
+            if polyline_is_degenerate(line) {
+                continue;
+            }
+            writer.add(writer.polyline_node(line, false, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+                    .set("stroke-linecap", "round")
+                    .set("stroke-linejoin", "round")
+            }));
+        }
+
+        writer.save(filename)
+    }
+}
+
+impl PatternLayer for EngravedTextLayer {
+    fn generate(&mut self) {
+        EngravedTextLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for EngravedTextLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        EngravedTextLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn stroke_width(&self) -> f64 {
+        0.08
+    }
+}
+
+impl LayerType for EngravedTextLayer {
+    const KIND: LayerKind = LayerKind::Text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stroke_count(text: &str) -> usize {
+        text.chars().map(|c| glyph(c).unwrap().strokes.len()).sum()
+    }
+
+    #[test]
+    fn test_font_coverage() {
+        for c in ('0'..='9').chain('A'..='Z').chain('a'..='z') {
+            assert!(EngravedTextLayer::supports(c), "missing {:?}", c);
+        }
+        assert!(!EngravedTextLayer::supports('é'));
+        // Strokes are drawn within the cap height, bar descenders and slashes
+        for c in ('0'..='9').chain('A'..='Z') {
+            let g = glyph(c).unwrap();
+            assert!(g
+                .strokes
+                .iter()
+                .flat_map(|s| s.iter())
+                .all(|&(x, y)| x >= 0 && x <= g.width as i8 && (-2..=21).contains(&y)));
+        }
+    }
+
+    #[test]
+    fn test_stroke_counts_match_font() {
+        for text in ["12", "XII", "A4", "GENEVE 1-2"] {
+            let config = EngravedTextConfig::new(
+                text,
+                3.0,
+                TextPosition::arc_at_clock(12, 0, 15.0, ArcDirection::Clockwise),
+            );
+            let mut layer = EngravedTextLayer::new(config).unwrap();
+            layer.generate();
+            assert_eq!(layer.lines().len(), stroke_count(text), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_arc_text_at_twelve_straddles_top() {
+        let radius = 15.0;
+        let height = 3.0;
+        let config = EngravedTextConfig::new(
+            "12",
+            height,
+            TextPosition::arc_at_clock(12, 0, radius, ArcDirection::Clockwise),
+        );
+        let mut layer = EngravedTextLayer::new(config).unwrap();
+        layer.generate();
+
+        let (min_x, min_y, max_x, max_y) =
+            crate::common::polyline_bounds(layer.lines().iter().map(Vec::as_slice)).unwrap();
+        // Centered left-right on the 12 o'clock axis
+        assert!(min_x < 0.0 && max_x > 0.0);
+        assert!((min_x + max_x).abs() < 0.5);
+        // Baseline on the arc, tops toward the rim (negative y is up)
+        assert!(max_y <= -radius + 0.5);
+        assert!(min_y >= -radius - height - 0.5);
+    }
+
+    #[test]
+    fn test_arc_glyph_baselines_are_tangent() {
+        let radius = 12.0;
+        let config = EngravedTextConfig::new(
+            "EE",
+            2.0,
+            TextPosition::arc_at_clock(3, 0, radius, ArcDirection::CounterClockwise),
+        );
+        let mut layer = EngravedTextLayer::new(config).unwrap();
+        layer.generate();
+
+        // The bottom bar of each E lies on the baseline, which touches the
+        // arc: its line passes exactly `radius` from the center
+        for bar in [&layer.lines()[3], &layer.lines()[7]] {
+            let (a, b) = (bar[0], bar[1]);
+            let cross = (b.x - a.x) * a.y - (b.y - a.y) * a.x;
+            let distance = cross.abs() / (b.x - a.x).hypot(b.y - a.y);
+            assert!((distance - radius).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_linear_text_and_validation() {
+        let config = EngravedTextConfig::new(
+            "10",
+            2.1,
+            TextPosition::Linear {
+                origin: Point2D::new(5.0, 5.0),
+                angle: 0.0,
+            },
+        )
+        .with_spacing(0.5);
+        let mut layer = EngravedTextLayer::new(config).unwrap();
+        layer.generate();
+        let (min_x, min_y, max_x, max_y) =
+            crate::common::polyline_bounds(layer.lines().iter().map(Vec::as_slice)).unwrap();
+        assert!((max_y - 5.0).abs() < 1e-9);
+        assert!((min_y - 2.9).abs() < 1e-9);
+        assert!(min_x < 5.0 && max_x > 5.0);
+
+        let arc = TextPosition::arc_at_clock(12, 0, 10.0, ArcDirection::Clockwise);
+        let bad = |config: EngravedTextConfig| EngravedTextLayer::new(config).is_err();
+        assert!(bad(EngravedTextConfig::new("", 2.0, arc)));
+        assert!(bad(EngravedTextConfig::new("12€", 2.0, arc)));
+        assert!(bad(EngravedTextConfig::new("12", 0.0, arc)));
+        let flat = TextPosition::arc_at_clock(12, 0, 0.0, ArcDirection::Clockwise);
+        assert!(bad(EngravedTextConfig::new("12", 2.0, flat)));
+    }
+}
@@ -1,9 +1,18 @@
-use std::f64::consts::PI;
-
-use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographError};
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_polylines, polar_to_cartesian, scale_polylines,
+    simplify_polylines, JitterConfig, PatternLayer, Point2D, PointsTotal, RadialWave,
+    SpirographError, StoredLayer, TrigTable, DEDUPE_EPSILON, DEFAULT_MAX_POINTS_PER_PATH,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
+use crate::rose_engine::RosettePattern;
+use crate::shading::write_shaded_rings_svg;
 
 /// Configuration for radial sunburst flinqué pattern (engine-turned guilloche)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlinqueConfig {
     /// Number of radial "petals" or segments (typically 8-16)
     pub num_petals: usize,
@@ -15,6 +24,16 @@ pub struct FlinqueConfig {
     pub wave_frequency: f64,
     /// Inner radius where pattern starts (as fraction of outer radius)
     pub inner_radius_ratio: f64,
+    /// Points around each ring; `None` traces 80 per petal
+    pub resolution: Option<PointsTotal>,
+    /// Radians added to the petal phase of each ring over the one inside
+    /// it; `PI / num_petals` turns every ring half a petal for a herringbone
+    pub petal_phase_step: f64,
+    /// Flip the chevrons of every other ring to point inward
+    pub alternate: bool,
+    /// Seeded hand-made wobble of each ring
+    #[serde(default)]
+    pub jitter: Option<JitterConfig>,
 }
 
 impl Default for FlinqueConfig {
@@ -25,17 +44,146 @@ impl Default for FlinqueConfig {
             wave_amplitude: 0.8,
             wave_frequency: 20.0,
             inner_radius_ratio: 0.05,
+            resolution: None,
+            petal_phase_step: 0.0,
+            alternate: false,
+            jitter: None,
         }
     }
 }
 
+impl FlinqueConfig {
+    /// Set the points traced around each ring
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = Some(resolution.into());
+        self
+    }
+
+    /// Turn each ring `petal_phase_step` radians further than the one
+    /// inside it, flipping every other ring's chevrons if `alternate`
+    pub fn with_ring_offsets(mut self, petal_phase_step: f64, alternate: bool) -> Self {
+        self.petal_phase_step = petal_phase_step;
+        self.alternate = alternate;
+        self
+    }
+
+    /// Wobble each ring as if turned by hand
+    pub fn with_jitter(mut self, jitter: JitterConfig) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    /// Check the petal count, wave amplitude, phase step, resolution and
+    /// jitter
+    ///
+    /// A wave amplitude of 0 is valid and draws plain concentric circles.
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.num_petals == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "num_petals must be at least 1".to_string(),
+            ));
+        }
+
+        if !self.petal_phase_step.is_finite() {
+            return Err(SpirographError::InvalidParameter(
+                "petal_phase_step must be finite".to_string(),
+            ));
+        }
+
+        if !(self.wave_amplitude.is_finite() && self.wave_amplitude >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "wave_amplitude must be non-negative".to_string(),
+            ));
+        }
+
+        if self.resolution.is_some_and(|r| r.get() < 10) {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 10".to_string(),
+            ));
+        }
+
+        if let Some(ref jitter) = self.jitter {
+            jitter.validate()?;
+        }
+
+        Ok(())
+    }
+
+    /// Index and base radius of the rings traced inside `outer_r`,
+    /// innermost first
+    ///
+    /// Rings sit at the centres of `num_waves` equal steps from the inner
+    /// radius outward. Rings too close to the centre are skipped: those
+    /// whose trough would sit within a tenth of the amplitude of it, and
+    /// any at or below radius 0, which would be degenerate even with zero
+    /// amplitude. Skipped rings keep their index, so they do not shift the
+    /// phase of the rings outside them.
+    pub(crate) fn ring_radii(&self, outer_r: f64) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let inner_r = outer_r * self.inner_radius_ratio;
+        let min_radius = self.wave_amplitude * 0.1;
+        (0..self.num_waves)
+            .map(move |ring_idx| {
+                let t = (ring_idx as f64 + 0.5) / self.num_waves as f64;
+                (ring_idx, inner_r + (outer_r - inner_r) * t)
+            })
+            .filter(move |&(_, base_r)| base_r > 0.0 && base_r >= min_radius)
+    }
+
+    /// Points traced per ring: the configured resolution, or 80 per
+    /// petal for smooth arcs
+    pub(crate) fn points_per_ring(&self) -> usize {
+        self.resolution
+            .map_or(self.num_petals * 80, PointsTotal::get)
+    }
+
+    /// Waves whose sum traces ring `ring_idx` at base radius `base_r`
+    ///
+    /// The chevron is `wave_amplitude · |sin(num_petals·θ/2)|`: smooth
+    /// rounded peaks and sharp V troughs, one petal per half-period. It is
+    /// the multi-lobe rosette (`2|sin| − 1`) at half amplitude, lifted by
+    /// half the amplitude so the troughs sit on `base_r`. A fine ripple at
+    /// `wave_frequency` times the petal rate adds texture.
+    ///
+    /// Both waves are turned by `ring_idx · petal_phase_step`. With
+    /// `alternate`, odd rings take the chevron upside down, with sharp
+    /// peaks at `base_r + wave_amplitude` and rounded troughs on `base_r`.
+    pub(crate) fn ring_waves(&self, ring_idx: usize, base_r: f64) -> [RadialWave; 2] {
+        let half = 0.5 * self.wave_amplitude;
+        let phase = ring_idx as f64 * self.petal_phase_step;
+        let sign = if self.alternate && ring_idx % 2 == 1 {
+            -1.0
+        } else {
+            1.0
+        };
+        [
+            RadialWave::new(
+                base_r + half,
+                sign * half,
+                RosettePattern::MultiLobe {
+                    lobes: self.num_petals,
+                },
+            )
+            .with_phase(phase),
+            RadialWave::new(
+                0.0,
+                0.05 * self.wave_amplitude,
+                RosettePattern::Sinusoidal {
+                    frequency: self.num_petals as f64 / 2.0 * self.wave_frequency,
+                },
+            )
+            .with_phase(phase),
+        ]
+    }
+}
+
 /// A flinqué (engine-turned) layer with configurable center point
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlinqueLayer {
     pub config: FlinqueConfig,
     pub radius: f64,
     pub center_x: f64,
     pub center_y: f64,
+    #[serde(skip)]
     lines: Vec<Vec<Point2D>>, // Each wave line is a series of points
 }
 
@@ -52,21 +200,27 @@ impl FlinqueLayer {
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
+        let layer = FlinqueLayer {
+            config,
+            radius,
+            center_x,
+            center_y,
+            lines: Vec::new(),
+        };
+        layer.validate()?;
+        Ok(layer)
+    }
+
+    /// Check the radius and configuration
+    pub fn validate(&self) -> Result<(), SpirographError> {
         // For flinque layers, we don't validate against watch radius constraints
         // since they may be subdials or smaller elements
-        if radius <= 0.0 {
+        if self.radius <= 0.0 {
             return Err(SpirographError::InvalidParameter(
                 "radius must be positive".to_string(),
             ));
         }
-
-        Ok(FlinqueLayer {
-            config,
-            radius,
-            center_x,
-            center_y,
-            lines: Vec::new(),
-        })
+        self.config.validate()
     }
 
     /// Create a flinqué layer positioned at a given angle and distance from origin
@@ -105,71 +259,137 @@ impl FlinqueLayer {
     /// num_petals = number of chevron peaks per ring
     /// num_waves = number of concentric rings
     pub fn generate(&mut self) {
-        let inner_r = self.radius * self.config.inner_radius_ratio;
-        let outer_r = self.radius;
+        self.generate_with_ring_points(self.config.points_per_ring());
+    }
 
+    /// Generate with `points_per_ring` samples around each ring
+    fn generate_with_ring_points(&mut self, points_per_ring: usize) {
         self.lines.clear();
 
         // The wave amplitude is constant - same angular chevrons at all radii
-        let wave_amplitude = self.config.wave_amplitude;
-
-        // Calculate minimum radius to avoid self-intersection
-        // The trough of a ring is at base_r, peak is at base_r + wave_amplitude
-        // We need the trough to stay positive and rings not to cross themselves
-        // A small fraction of amplitude is sufficient as the minimum
-        let min_radius = wave_amplitude * 0.1;
-
-        // Generate concentric rings (num_waves controls how many rings)
-        for ring_idx in 0..self.config.num_waves {
-            // Position along the radius (0 = inner, 1 = outer)
-            let t = (ring_idx as f64 + 0.5) / self.config.num_waves as f64;
-            let base_r = inner_r + (outer_r - inner_r) * t;
-
-            // Skip rings that are too close to center (would self-intersect)
-            if base_r < min_radius {
-                continue;
+        let center = Point2D::new(self.center_x, self.center_y);
+        let table = TrigTable::new(points_per_ring);
+        for (ring_idx, base_r) in self.config.ring_radii(self.radius) {
+            // More points for smoother arcs, sweeping the full 360 degrees
+            let mut ring = RadialWave::sample_sum_with(
+                &self.config.ring_waves(ring_idx, base_r),
+                &table,
+                center,
+            );
+            if let Some(jitter) = self.config.jitter {
+                jitter.apply(ring_idx, &mut ring, center);
             }
+            self.lines.push(ring);
+        }
+
+        dedupe_polylines(&mut self.lines, DEDUPE_EPSILON);
+        debug_assert_finite("FlinqueLayer", &self.lines);
+    }
+
+    /// Get the generated lines
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.lines
+    }
+
+    /// Export the pattern to SVG with shaded bands between the rings
+    ///
+    /// Each band between adjacent rings is filled with a light gradient
+    /// turned by the band's lean, and the rings are stroked on top. Uses
+    /// the generated lines as they are.
+    ///
+    /// # Arguments
+    /// * `filename` - Output SVG file path
+    /// * `light_angle` - Direction the light comes from (radians)
+    pub fn to_svg_filled(&self, filename: &str, light_angle: f64) -> Result<(), SpirographError> {
+        write_shaded_rings_svg(
+            filename,
+            &self.lines,
+            Point2D::new(self.center_x, self.center_y),
+            light_angle,
+            DEFAULT_MAX_POINTS_PER_PATH,
+        )
+    }
 
-            let mut line_points = Vec::new();
-            // More points for smoother arcs
-            let points_per_ring = self.config.num_petals * 80;
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
 
-            // Sweep full 360 degrees
-            for i in 0..=points_per_ring {
-                let angle = 2.0 * PI * (i as f64) / (points_per_ring as f64);
+    /// Multiply every length (wave amplitude, radius, center and generated lines) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.wave_amplitude *= factor;
+        self.radius *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.lines, factor);
+    }
+}
 
-                // Chevron wave: creates num_petals peaks around the circle
-                // Divide by 2 because |sin| has period π, so |sin(x/2)| gives correct count
-                let petal_phase = angle * self.config.num_petals as f64 / 2.0;
+impl PatternLayer for FlinqueLayer {
+    fn generate(&mut self) {
+        FlinqueLayer::generate(self)
+    }
 
-                // Use |sin| wave: smooth rounded peaks at max, sharp V troughs at zero
-                // sin goes from -1 to 1, abs(sin) goes from 0 to 1
-                // This gives: sharp troughs (at 0, pi, 2pi...) and smooth peaks (at pi/2, 3pi/2...)
-                let wave = petal_phase.sin().abs();
+    fn validate(&self) -> Result<(), SpirographError> {
+        FlinqueLayer::validate(self)
+    }
 
-                // Constant amplitude - same chevron depth at all radii
-                let chevron = wave_amplitude * wave;
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
 
-                // Optional fine ripple for texture
-                let ripple =
-                    0.05 * wave_amplitude * (petal_phase * self.config.wave_frequency).sin();
+impl StoredLayer for FlinqueLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
 
-                // Radius varies to create the wavy chevron effect
-                let r_mod = base_r + chevron + ripple;
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
 
-                let x = r_mod * angle.cos() + self.center_x;
-                let y = r_mod * angle.sin() + self.center_y;
+    fn scale_lengths(&mut self, factor: f64) {
+        FlinqueLayer::scale_lengths(self, factor);
+    }
 
-                line_points.push(Point2D::new(x, y));
-            }
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
 
-            self.lines.push(line_points);
-        }
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
     }
 
-    /// Get the generated lines
-    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
-        &self.lines
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for FlinqueLayer {
+    const KIND: LayerKind = LayerKind::Flinque;
+}
+
+impl Progressive for FlinqueLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        // Ring count sets the engraving pitch; only sampling is reduced,
+        // keeping enough points per petal to show the chevrons
+        let full = self.config.points_per_ring();
+        let points = scale_count(full, fraction, 8 * self.config.num_petals);
+        self.generate_with_ring_points(points);
+    }
+}
+
+impl PointCounted for FlinqueLayer {
+    fn point_count(&self) -> usize {
+        self.lines.iter().map(Vec::len).sum()
     }
 }
 
@@ -214,6 +434,10 @@ mod tests {
             wave_amplitude: 0.5,
             wave_frequency: 10.0,
             inner_radius_ratio: 0.1,
+            resolution: None,
+            petal_phase_step: 0.0,
+            alternate: false,
+            jitter: None,
         };
         let mut layer = FlinqueLayer::new(10.0, config).unwrap();
         layer.generate();
@@ -240,62 +464,164 @@ mod tests {
         let wave_frequency = 10.0;
         let inner_radius_ratio = 0.1;
 
-        // Create mathematical FlinqueLayer
-        let config = FlinqueConfig {
-            num_petals,
-            num_waves,
-            wave_amplitude,
-            wave_frequency,
-            inner_radius_ratio,
-        };
-        let mut flinque = FlinqueLayer::new(radius, config).unwrap();
-        flinque.generate();
+        // Aligned chevrons, then a half-petal herringbone with every other
+        // ring flipped
+        let half_petal = std::f64::consts::PI / num_petals as f64;
+        for (petal_phase_step, alternate) in [(0.0, false), (half_petal, true)] {
+            // Create mathematical FlinqueLayer
+            let config = FlinqueConfig {
+                num_petals,
+                num_waves,
+                wave_amplitude,
+                wave_frequency,
+                inner_radius_ratio,
+                resolution: None,
+                petal_phase_step,
+                alternate,
+                jitter: None,
+            };
+            let mut flinque = FlinqueLayer::new(radius, config).unwrap();
+            flinque.generate();
+
+            // Create equivalent rose engine flinque
+            let mut rose_run = RoseEngineLatheRun::new_flinque(
+                radius,
+                num_petals,
+                num_waves,
+                wave_amplitude,
+                wave_frequency,
+                inner_radius_ratio,
+                petal_phase_step,
+                alternate,
+                0.0,
+                0.0,
+            )
+            .unwrap();
+            rose_run.generate();
+
+            let flinque_lines = flinque.lines();
+            let rose_lines = rose_run.lines();
 
-        // Create equivalent rose engine flinque
-        let mut rose_run = RoseEngineLatheRun::new_flinque(
-            radius,
-            num_petals,
-            num_waves,
-            wave_amplitude,
-            wave_frequency,
-            inner_radius_ratio,
-            0.0,
-            0.0,
-        )
-        .unwrap();
-        rose_run.generate();
-
-        let flinque_lines = flinque.lines();
-        let rose_lines = rose_run.lines();
-
-        assert_eq!(
-            flinque_lines.len(),
-            rose_lines.len(),
-            "FlinqueLayer and RoseEngineLatheRun should have same number of rings"
-        );
-
-        for (i, (f_ring, r_ring)) in flinque_lines.iter().zip(rose_lines.iter()).enumerate() {
             assert_eq!(
-                f_ring.len(),
-                r_ring.len(),
-                "Ring {} should have same number of points",
-                i
+                flinque_lines.len(),
+                rose_lines.len(),
+                "FlinqueLayer and RoseEngineLatheRun should have same number of rings"
             );
 
-            for (j, (f_pt, r_pt)) in f_ring.iter().zip(r_ring.iter()).enumerate() {
-                let dist = ((f_pt.x - r_pt.x).powi(2) + (f_pt.y - r_pt.y).powi(2)).sqrt();
-                assert!(
-                    dist < 1e-10,
-                    "Point {},{} differs: flinque=({}, {}), rose=({}, {}), dist={}",
-                    i,
-                    j,
-                    f_pt.x,
-                    f_pt.y,
-                    r_pt.x,
-                    r_pt.y,
-                    dist
+            for (i, (f_ring, r_ring)) in flinque_lines.iter().zip(rose_lines.iter()).enumerate() {
+                assert_eq!(
+                    f_ring.len(),
+                    r_ring.len(),
+                    "Ring {} should have same number of points",
+                    i
                 );
+
+                for (j, (f_pt, r_pt)) in f_ring.iter().zip(r_ring.iter()).enumerate() {
+                    let dist = ((f_pt.x - r_pt.x).powi(2) + (f_pt.y - r_pt.y).powi(2)).sqrt();
+                    assert!(
+                        dist < 1e-10,
+                        "Point {},{} differs: flinque=({}, {}), rose=({}, {}), dist={}",
+                        i,
+                        j,
+                        f_pt.x,
+                        f_pt.y,
+                        r_pt.x,
+                        r_pt.y,
+                        dist
+                    );
+                }
             }
         }
     }
+
+    #[test]
+    fn test_herringbone_turns_and_flips_alternate_rings() {
+        let base = FlinqueConfig {
+            num_petals: 8,
+            num_waves: 4,
+            wave_amplitude: 1.0,
+            wave_frequency: 0.0,
+            inner_radius_ratio: 0.5,
+            resolution: Some(PointsTotal(64)),
+            ..FlinqueConfig::default()
+        };
+        let rings = |config: FlinqueConfig| {
+            let mut layer = FlinqueLayer::new(16.0, config).unwrap();
+            layer.generate();
+            layer.lines().clone()
+        };
+        // Radius of `ring` at sample `j` (8 samples per petal)
+        let r = |lines: &Vec<Vec<Point2D>>, ring: usize, j: usize| {
+            lines[ring][j].x.hypot(lines[ring][j].y)
+        };
+
+        let aligned = rings(base.clone());
+        // Without a ripple, every ring has a sharp trough at θ = 0
+        for ring in 0..4 {
+            let base_r = 8.0 + 8.0 * (ring as f64 + 0.5) / 4.0;
+            assert!((r(&aligned, ring, 0) - base_r).abs() < 1e-12);
+        }
+
+        // Half a petal is 4 samples: the second ring's trough moves back
+        // by 4 samples, and the third ring's by a whole petal
+        let half_petal = std::f64::consts::PI / 8.0;
+        let turned = rings(base.clone().with_ring_offsets(half_petal, false));
+        for j in 0..64 {
+            assert!((r(&turned, 0, j) - r(&aligned, 0, j)).abs() < 1e-9);
+            assert!((r(&turned, 1, (j + 4) % 64) - r(&aligned, 1, j)).abs() < 1e-9);
+            assert!((r(&turned, 2, j) - r(&aligned, 2, j)).abs() < 1e-9);
+        }
+
+        // Flipped rings keep their band but swap peaks and troughs
+        let flipped = rings(base.clone().with_ring_offsets(0.0, true));
+        assert_eq!(flipped[0], aligned[0]);
+        let base_r = 8.0 + 8.0 * 1.5 / 4.0;
+        assert!((r(&flipped, 1, 0) - (base_r + 1.0)).abs() < 1e-12);
+        assert!((r(&flipped, 1, 4) - base_r).abs() < 1e-12);
+
+        assert!(FlinqueLayer::new(16.0, base.with_ring_offsets(f64::NAN, false)).is_err());
+    }
+
+    #[test]
+    fn test_zero_amplitude_flinque_matches_rose_engine() {
+        use crate::rose_engine::RoseEngineLatheRun;
+
+        let config = FlinqueConfig {
+            num_petals: 6,
+            num_waves: 8,
+            wave_amplitude: 0.0,
+            wave_frequency: 10.0,
+            inner_radius_ratio: 0.0,
+            resolution: None,
+            petal_phase_step: 0.0,
+            alternate: false,
+            jitter: None,
+        };
+        let mut flinque = FlinqueLayer::new(10.0, config).unwrap();
+        flinque.generate();
+        // No ring is skipped, and each is a perfect circle
+        assert_eq!(flinque.lines().len(), 8);
+        for (i, ring) in flinque.lines().iter().enumerate() {
+            let expected = 10.0 * (i as f64 + 0.5) / 8.0;
+            assert!(ring
+                .iter()
+                .all(|p| (p.x.hypot(p.y) - expected).abs() < 1e-12));
+        }
+
+        let mut run =
+            RoseEngineLatheRun::new_flinque(10.0, 6, 8, 0.0, 10.0, 0.0, 0.0, false, 0.0, 0.0)
+                .unwrap();
+        run.generate();
+        assert_eq!(run.lines(), flinque.lines());
+
+        let negative = FlinqueConfig {
+            wave_amplitude: -0.5,
+            ..FlinqueConfig::default()
+        };
+        assert!(FlinqueLayer::new(10.0, negative).is_err());
+        assert!(
+            RoseEngineLatheRun::new_flinque(10.0, 6, 8, -0.5, 10.0, 0.0, 0.0, false, 0.0, 0.0)
+                .is_err()
+        );
+    }
 }
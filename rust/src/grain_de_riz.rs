@@ -0,0 +1,522 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_polylines, polar_to_cartesian,
+    polyline_is_degenerate, scale_polylines, simplified_layer, simplify_polylines, ExportConfig,
+    PatternLayer, Point2D, PointsTotal, SpirographError, StoredLayer, DEDUPE_EPSILON,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
+
+/// Configuration for the grain-de-riz (rice grain) guilloché pattern
+///
+/// Small closed ovals are set end to end on concentric rows, each with its
+/// long axis along the row. With `stagger`, every other row is turned by
+/// half a grain pitch so the grains sit over the gaps of their neighbours,
+/// like grains of rice laid in a bowl.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrainDeRizConfig {
+    /// Number of concentric rows of grains
+    pub num_rows: usize,
+    /// Number of grains on every row
+    pub grains_per_row: usize,
+    /// Length of each grain along its row in mm
+    pub grain_length: f64,
+    /// Width of each grain across its row in mm
+    pub grain_width: f64,
+    /// Radius of the innermost row in mm
+    pub base_radius: f64,
+    /// Distance between neighbouring rows in mm
+    pub radius_step: f64,
+    /// Number of points around each grain
+    pub resolution: PointsTotal,
+    /// Turn every other row by half a grain pitch
+    pub stagger: bool,
+}
+
+impl Default for GrainDeRizConfig {
+    fn default() -> Self {
+        GrainDeRizConfig {
+            num_rows: 12,
+            grains_per_row: 48,
+            grain_length: 1.2,
+            grain_width: 0.5,
+            base_radius: 8.0,
+            radius_step: 1.0,
+            resolution: PointsTotal(24),
+            stagger: true,
+        }
+    }
+}
+
+impl GrainDeRizConfig {
+    /// Create a new grain-de-riz configuration
+    ///
+    /// # Arguments
+    /// * `num_rows` - Number of concentric rows
+    /// * `grains_per_row` - Number of grains on every row
+    pub fn new(num_rows: usize, grains_per_row: usize) -> Self {
+        GrainDeRizConfig {
+            num_rows,
+            grains_per_row,
+            ..Default::default()
+        }
+    }
+
+    /// Set the grain size in mm
+    pub fn with_grain_size(mut self, length: f64, width: f64) -> Self {
+        self.grain_length = length;
+        self.grain_width = width;
+        self
+    }
+
+    /// Set the innermost row radius and the row spacing in mm
+    pub fn with_rows(mut self, base_radius: f64, radius_step: f64) -> Self {
+        self.base_radius = base_radius;
+        self.radius_step = radius_step;
+        self
+    }
+
+    /// Set the resolution (points per grain)
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = resolution.into();
+        self
+    }
+
+    /// Set whether every other row is turned by half a grain pitch
+    pub fn with_stagger(mut self, stagger: bool) -> Self {
+        self.stagger = stagger;
+        self
+    }
+
+    /// Check the counts, the grain size, the row radii and the resolution
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.num_rows == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "num_rows must be at least 1".to_string(),
+            ));
+        }
+        if self.grains_per_row == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "grains_per_row must be at least 1".to_string(),
+            ));
+        }
+        if !(self.grain_length.is_finite() && self.grain_length > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "grain_length must be positive".to_string(),
+            ));
+        }
+        if !(self.grain_width.is_finite() && self.grain_width > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "grain_width must be positive".to_string(),
+            ));
+        }
+        if !self.radius_step.is_finite() {
+            return Err(SpirographError::InvalidParameter(
+                "radius_step must be finite".to_string(),
+            ));
+        }
+        let innermost = self.row_radius(0).min(self.row_radius(self.num_rows - 1));
+        if !(innermost.is_finite() && innermost > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "every row radius must be positive".to_string(),
+            ));
+        }
+        if self.resolution.get() < 8 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 8".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Radius of row `row`, counted from `base_radius`
+    pub fn row_radius(&self, row: usize) -> f64 {
+        self.base_radius + (row as f64) * self.radius_step
+    }
+
+    /// Angle of the middle of grain `index` on row `row`, counter-clockwise
+    /// from the +x axis
+    pub fn grain_angle(&self, row: usize, index: usize) -> f64 {
+        let pitch = 2.0 * PI / (self.grains_per_row as f64);
+        let offset = if self.stagger && row % 2 == 1 {
+            0.5
+        } else {
+            0.0
+        };
+        ((index as f64) + offset) * pitch
+    }
+
+    /// Every grain outline about `center`, row by row from `base_radius`
+    /// and counter-clockwise along each row from the +x axis
+    ///
+    /// Each grain is an ellipse sampled at `resolution` steps and closed
+    /// on its first point, starting from its forward (counter-clockwise)
+    /// tip. Shared by `GrainDeRizLayer` and the rose-engine equivalent.
+    pub(crate) fn grains(&self, center: Point2D) -> Vec<Vec<Point2D>> {
+        let res = self.resolution.get();
+        let half_length = self.grain_length / 2.0;
+        let half_width = self.grain_width / 2.0;
+
+        let mut grains = Vec::with_capacity(self.num_rows * self.grains_per_row);
+        for row in 0..self.num_rows {
+            let r = self.row_radius(row);
+            for index in 0..self.grains_per_row {
+                let angle = self.grain_angle(row, index);
+                let (sin_a, cos_a) = angle.sin_cos();
+                let mid_x = center.x + r * cos_a;
+                let mid_y = center.y + r * sin_a;
+
+                let mut grain = Vec::with_capacity(res + 1);
+                for j in 0..=res {
+                    let t = 2.0 * PI * ((j % res) as f64) / (res as f64);
+                    // Along the row (tangent) and across it (radial)
+                    let along = half_length * t.cos();
+                    let across = half_width * t.sin();
+                    grain.push(Point2D::new(
+                        mid_x - along * sin_a + across * cos_a,
+                        mid_y + along * cos_a + across * sin_a,
+                    ));
+                }
+                grains.push(grain);
+            }
+        }
+        grains
+    }
+}
+
+/// A grain-de-riz pattern layer
+///
+/// Generates one closed oval per grain on concentric rows around the
+/// layer's center; see `GrainDeRizConfig` for the layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrainDeRizLayer {
+    pub config: GrainDeRizConfig,
+    pub center_x: f64,
+    pub center_y: f64,
+    #[serde(skip)]
+    grains: Vec<Vec<Point2D>>,
+}
+
+impl GrainDeRizLayer {
+    /// Create a new grain-de-riz layer centered at origin
+    pub fn new(config: GrainDeRizConfig) -> Result<Self, SpirographError> {
+        Self::new_with_center(config, 0.0, 0.0)
+    }
+
+    /// Create a new grain-de-riz layer with a custom center point
+    pub fn new_with_center(
+        config: GrainDeRizConfig,
+        center_x: f64,
+        center_y: f64,
+    ) -> Result<Self, SpirographError> {
+        config.validate()?;
+
+        Ok(GrainDeRizLayer {
+            config,
+            center_x,
+            center_y,
+            grains: Vec::new(),
+        })
+    }
+
+    /// Create a grain-de-riz layer positioned at a given angle and distance from origin
+    pub fn new_at_polar(
+        config: GrainDeRizConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (center_x, center_y) = polar_to_cartesian(angle, distance);
+        Self::new_with_center(config, center_x, center_y)
+    }
+
+    /// Create a grain-de-riz layer positioned at a clock position (like hour hand)
+    ///
+    /// # Arguments
+    /// * `config` - Grain-de-riz configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn new_at_clock(
+        config: GrainDeRizConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (center_x, center_y) = clock_to_cartesian(hour, minute, distance);
+        Self::new_with_center(config, center_x, center_y)
+    }
+
+    /// Generate the grain-de-riz pattern
+    pub fn generate(&mut self) {
+        self.grains = self
+            .config
+            .grains(Point2D::new(self.center_x, self.center_y));
+        dedupe_polylines(&mut self.grains, DEDUPE_EPSILON);
+        debug_assert_finite("GrainDeRizLayer", &self.grains);
+    }
+
+    /// Get the generated grain outlines, one closed line per grain
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.grains
+    }
+
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.grains
+    }
+
+    /// Multiply every length (config, center and generated grains) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.grain_length *= factor;
+        self.config.grain_width *= factor;
+        self.config.base_radius *= factor;
+        self.config.radius_step *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.grains, factor);
+    }
+
+    /// Export the pattern to SVG format
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+
+        if self.grains.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.grains.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        for grain in &layer.grains {
+            if polyline_is_degenerate(grain) {
+                continue;
+            }
+
+            writer.add_polyline(grain, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+            });
+        }
+
+        writer.save(filename)
+    }
+}
+
+impl PatternLayer for GrainDeRizLayer {
+    fn generate(&mut self) {
+        GrainDeRizLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for GrainDeRizLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        GrainDeRizLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for GrainDeRizLayer {
+    const KIND: LayerKind = LayerKind::GrainDeRiz;
+}
+
+impl Progressive for GrainDeRizLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
+        }
+
+        // The rows and grains define the texture; only the ovals are sampled coarser
+        let mut coarse = self.clone();
+        coarse.config.resolution =
+            PointsTotal(scale_count(self.config.resolution.get(), fraction, 8));
+        coarse.generate();
+        self.grains = coarse.grains;
+    }
+}
+
+impl PointCounted for GrainDeRizLayer {
+    fn point_count(&self) -> usize {
+        self.grains.iter().map(Vec::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grain_de_riz_invalid_params() {
+        assert!(GrainDeRizLayer::new(GrainDeRizConfig::default()).is_ok());
+        assert!(GrainDeRizLayer::new(GrainDeRizConfig::new(0, 48)).is_err());
+        assert!(GrainDeRizLayer::new(GrainDeRizConfig::new(12, 0)).is_err());
+        let bad_size = GrainDeRizConfig::default().with_grain_size(1.0, 0.0);
+        assert!(GrainDeRizLayer::new(bad_size).is_err());
+        let bad_resolution = GrainDeRizConfig::default().with_resolution(4);
+        assert!(GrainDeRizLayer::new(bad_resolution).is_err());
+        // Rows stepping inwards must stay outside the center
+        let crossing = GrainDeRizConfig::new(10, 24).with_rows(5.0, -1.0);
+        assert!(GrainDeRizLayer::new(crossing).is_err());
+        assert!(GrainDeRizLayer::new(GrainDeRizConfig::new(5, 24).with_rows(5.0, -1.0)).is_ok());
+    }
+
+    #[test]
+    fn test_grains_are_closed_ovals_on_rows() {
+        let config = GrainDeRizConfig::new(3, 16)
+            .with_grain_size(2.0, 0.8)
+            .with_rows(10.0, 1.5)
+            .with_resolution(32);
+        let mut layer = GrainDeRizLayer::new_with_center(config.clone(), 3.0, -2.0).unwrap();
+        layer.generate();
+        assert_eq!(layer.lines().len(), 3 * 16);
+
+        for (k, grain) in layer.lines().iter().enumerate() {
+            let (row, index) = (k / 16, k % 16);
+            assert_eq!(grain.len(), 33);
+            assert_eq!(grain[0], grain[32]);
+
+            // Tips lie on the row's tangent, half a length either side
+            let angle = config.grain_angle(row, index);
+            let r = config.row_radius(row);
+            let mid = Point2D::new(3.0 + r * angle.cos(), -2.0 + r * angle.sin());
+            let tip = grain[0];
+            assert!(((tip.x - mid.x).hypot(tip.y - mid.y) - 1.0).abs() < 1e-9);
+            let radial = (tip.x - mid.x) * angle.cos() + (tip.y - mid.y) * angle.sin();
+            assert!(radial.abs() < 1e-9);
+
+            // Every point stays on the ellipse about the grain's middle
+            for p in grain {
+                let dx = p.x - mid.x;
+                let dy = p.y - mid.y;
+                let along = -dx * angle.sin() + dy * angle.cos();
+                let across = dx * angle.cos() + dy * angle.sin();
+                let e = (along / 1.0).powi(2) + (across / 0.4).powi(2);
+                assert!((e - 1.0).abs() < 1e-9, "grain {} off its ellipse", k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stagger_offsets_odd_rows_by_half_a_grain() {
+        let staggered = GrainDeRizConfig::new(2, 12);
+        let aligned = staggered.clone().with_stagger(false);
+        let pitch = 2.0 * PI / 12.0;
+        assert_eq!(staggered.grain_angle(0, 3), 3.0 * pitch);
+        assert!((staggered.grain_angle(1, 3) - 3.5 * pitch).abs() < 1e-12);
+        assert_eq!(aligned.grain_angle(1, 3), 3.0 * pitch);
+    }
+
+    #[test]
+    fn test_grain_de_riz_at_clock() {
+        let config = GrainDeRizConfig::new(2, 12);
+        let layer = GrainDeRizLayer::new_at_clock(config.clone(), 3, 0, 10.0).unwrap();
+        assert!((layer.center_x - 10.0).abs() < 1e-9);
+        assert!(layer.center_y.abs() < 1e-9);
+        let layer = GrainDeRizLayer::new_at_polar(config, PI / 2.0, 5.0).unwrap();
+        assert!(layer.center_x.abs() < 1e-9);
+        assert!((layer.center_y - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_grain_de_riz_composes_into_pattern() {
+        use crate::guilloche::GuillochePattern;
+        use crate::post::LayerKind;
+
+        let mut pattern = GuillochePattern::new(30.0).unwrap();
+        pattern
+            .add_grain_de_riz_at_clock(GrainDeRizConfig::new(3, 24), 12, 0, 0.0)
+            .unwrap();
+        pattern.generate();
+        assert_eq!(pattern.kind_count(LayerKind::GrainDeRiz), 1);
+        assert_eq!(pattern.grain_de_riz_lines()[0].len(), 3 * 24);
+        assert_eq!(
+            LayerKind::from_name("grain_de_riz"),
+            Some(LayerKind::GrainDeRiz)
+        );
+    }
+
+    #[test]
+    fn test_grain_de_riz_matches_rose_engine() {
+        use crate::rose_engine::RoseEngineLatheRun;
+
+        let config = GrainDeRizConfig::new(4, 20)
+            .with_grain_size(1.5, 0.6)
+            .with_rows(6.0, 1.2)
+            .with_resolution(20);
+        let mut layer = GrainDeRizLayer::new_with_center(config, 1.0, 2.0).unwrap();
+        layer.generate();
+
+        let mut run =
+            RoseEngineLatheRun::new_grain_de_riz(4, 20, 1.5, 0.6, 6.0, 1.2, 20, true, 1.0, 2.0)
+                .unwrap();
+        run.generate();
+
+        let grain_lines = layer.lines();
+        let rose_lines = run.lines();
+        assert_eq!(grain_lines.len(), rose_lines.len());
+        for (i, (g, r)) in grain_lines.iter().zip(rose_lines).enumerate() {
+            assert_eq!(g.len(), r.len(), "grain {} point count", i);
+            for (j, (gp, rp)) in g.iter().zip(r).enumerate() {
+                let dist = (gp.x - rp.x).hypot(gp.y - rp.y);
+                assert!(dist < 1e-10, "grain {} point {} differs by {}", i, j, dist);
+            }
+        }
+    }
+}
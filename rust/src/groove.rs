@@ -0,0 +1,350 @@
+use stl_io::{Normal, Triangle, Vertex};
+
+use crate::common::{Point2D, Point3D, SpirographError, DEDUPE_EPSILON};
+
+/// Longest side offset at a sharp turn, in half-widths of the groove
+const MITRE_LIMIT: f64 = 4.0;
+
+type Vec3 = [f64; 3];
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(a: Vec3, s: f64) -> Vec3 {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Unit vector along `a`, or `a` itself when it has no length
+fn normalize(a: Vec3) -> Vec3 {
+    let length = dot(a, a).sqrt();
+    if length > 0.0 {
+        scale(a, 1.0 / length)
+    } else {
+        a
+    }
+}
+
+/// Triangle `a, b, c` with its normal taken from the winding, which is
+/// counter-clockwise seen from outside
+pub(crate) fn facet(a: Vec3, b: Vec3, c: Vec3) -> Triangle {
+    let n = normalize(cross(sub(b, a), sub(c, a)));
+    let vertex = |p: Vec3| Vertex::new([p[0] as f32, p[1] as f32, p[2] as f32]);
+    Triangle {
+        normal: Normal::new([n[0] as f32, n[1] as f32, n[2] as f32]),
+        vertices: [vertex(a), vertex(b), vertex(c)],
+    }
+}
+
+/// Cross-section of an STL groove
+///
+/// Points are (offset across the path, height above the surface), running
+/// from the surface at one edge down across the floor and back up to the
+/// surface at the other, counter-clockwise with the offset to the right and
+/// the height up. The surface closes the outline.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct GrooveProfile {
+    points: Vec<(f64, f64)>,
+    /// End cap triangles, as counter-clockwise indices into `points`
+    caps: Vec<[usize; 3]>,
+}
+
+impl GrooveProfile {
+    /// Groove left by a tool plunged `depth` below the surface
+    ///
+    /// `tip_height(u)` is the height of the tool's edge above its tip at
+    /// offset `u` from its axis, sampled at `samples` evenly spaced offsets
+    /// across `width`. Where the tool's edge is still below the surface its
+    /// sides run straight up, and where it rises out of the surface the
+    /// groove narrows to the crossing.
+    pub(crate) fn from_tip_heights(
+        width: f64,
+        depth: f64,
+        samples: usize,
+        tip_height: impl Fn(f64) -> f64,
+    ) -> Result<Self, SpirographError> {
+        if !(width.is_finite() && width > 0.0 && depth.is_finite() && depth > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "STL grooves need a positive width and depth".to_string(),
+            ));
+        }
+        let floor: Vec<(f64, f64)> = (0..samples.max(2))
+            .map(|i| {
+                let u = width * (i as f64 / (samples.max(2) - 1) as f64 - 0.5);
+                (u, tip_height(u) - depth)
+            })
+            .collect();
+        let crossing =
+            |(u0, z0): (f64, f64), (u1, z1): (f64, f64)| (u0 + (u1 - u0) * z0 / (z0 - z1), 0.0);
+
+        let mut points = Vec::with_capacity(floor.len() + 2);
+        for (i, &(u, z)) in floor.iter().enumerate() {
+            let below = z < 0.0;
+            match i.checked_sub(1).map(|j| floor[j]) {
+                None if below => points.push((u, 0.0)),
+                Some(previous) if below != (previous.1 < 0.0) => {
+                    points.push(crossing(previous, (u, z)))
+                }
+                _ => {}
+            }
+            if below {
+                points.push((u, z));
+            }
+        }
+        if let Some(&(u, _)) = floor.last().filter(|(_, z)| *z < 0.0) {
+            points.push((u, 0.0));
+        }
+        if points.len() < 3 {
+            return Err(SpirographError::InvalidParameter(
+                "STL groove profile does not reach below the surface".to_string(),
+            ));
+        }
+
+        let outline: Vec<Point2D> = points.iter().map(|&(u, z)| Point2D::new(u, z)).collect();
+        let index = |p: Point2D| outline.iter().position(|&q| q == p);
+        let caps = crate::cells::triangulate_with_holes(&outline, &[])
+            .into_iter()
+            .filter_map(|[a, b, c]| Some([index(a)?, index(b)?, index(c)?]))
+            .collect();
+        Ok(GrooveProfile { points, caps })
+    }
+
+    /// Flat-bottomed groove `width` wide
+    pub(crate) fn flat(width: f64, depth: f64) -> Result<Self, SpirographError> {
+        Self::from_tip_heights(width, depth, 2, |_| 0.0)
+    }
+
+    /// V-groove of a bit with an included angle of `angle` degrees, at most
+    /// `width` wide
+    pub(crate) fn v_shaped(angle: f64, width: f64, depth: f64) -> Result<Self, SpirographError> {
+        let slope = 1.0 / (angle.to_radians() / 2.0).tan();
+        Self::from_tip_heights(width, depth, 3, |u| u.abs() * slope)
+    }
+
+    /// Width of the groove at the surface
+    pub(crate) fn width(&self) -> f64 {
+        let offsets = || self.points.iter().map(|&(u, _)| u);
+        offsets().fold(f64::NEG_INFINITY, f64::max) - offsets().fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// Closed solid of `profile` swept along `points`, for STL exports
+///
+/// The profile hangs below each point, `up` giving the surface normal
+/// there, and its sides follow the path's turns with mitred joints. A
+/// `closed` path, or one that ends on its first point, wraps around; an
+/// open one is capped at both ends. Every facet faces outward, so each
+/// groove is a watertight shell however the path turns or crosses itself.
+/// Points within `DEDUPE_EPSILON` of the one before are skipped; a path
+/// with fewer than two distinct points gives no triangles.
+pub(crate) fn groove_solid_triangles(
+    points: &[Point3D],
+    closed: bool,
+    profile: &GrooveProfile,
+    up: impl Fn(Point3D) -> Point3D,
+) -> Vec<Triangle> {
+    let near =
+        |a: &Point3D, b: &Point3D| (a.x - b.x).hypot(a.y - b.y).hypot(a.z - b.z) <= DEDUPE_EPSILON;
+    let mut path: Vec<Point3D> = Vec::with_capacity(points.len());
+    for p in points {
+        if !path.last().is_some_and(|last| near(last, p)) {
+            path.push(*p);
+        }
+    }
+    let mut closed = closed;
+    if path.len() > 2 && near(&path[0], &path[path.len() - 1]) {
+        path.pop();
+        closed = true;
+    }
+    if path.len() < 2 {
+        return Vec::new();
+    }
+    let closed = closed && path.len() > 2;
+    let n = path.len();
+    let segments = if closed { n } else { n - 1 };
+    let vec3 = |p: Point3D| [p.x, p.y, p.z];
+
+    let directions: Vec<Vec3> = (0..segments)
+        .map(|i| normalize(sub(vec3(path[(i + 1) % n]), vec3(path[i]))))
+        .collect();
+    let rings: Vec<Vec<Vec3>> = (0..n)
+        .map(|k| {
+            let up = normalize(vec3(up(path[k])));
+            let side = |t: Vec3| normalize(cross(up, t));
+            let incoming = match k {
+                0 if closed => Some(directions[n - 1]),
+                0 => None,
+                _ => Some(directions[k - 1]),
+            };
+            let across = match (incoming.map(side), directions.get(k).copied().map(side)) {
+                (Some(a), Some(b)) => {
+                    // Mitre, shortened at turns too sharp for the limit
+                    let mitre = add(a, b);
+                    let d = 1.0 + dot(a, b);
+                    if d >= 2.0 / (MITRE_LIMIT * MITRE_LIMIT) {
+                        scale(mitre, 1.0 / d)
+                    } else if dot(mitre, mitre) > 1e-18 {
+                        scale(normalize(mitre), MITRE_LIMIT)
+                    } else {
+                        a
+                    }
+                }
+                (Some(a), None) | (None, Some(a)) => a,
+                (None, None) => unreachable!("paths have at least two points"),
+            };
+            let p = vec3(path[k]);
+            profile
+                .points
+                .iter()
+                .map(|&(u, z)| add(add(p, scale(across, u)), scale(up, z)))
+                .collect()
+        })
+        .collect();
+
+    let m = profile.points.len();
+    let mut triangles = Vec::with_capacity(2 * m * segments + 2 * profile.caps.len());
+    for k in 0..segments {
+        let (a, b) = (&rings[k], &rings[(k + 1) % n]);
+        for j in 0..m {
+            let next = (j + 1) % m;
+            triangles.push(facet(a[j], a[next], b[next]));
+            triangles.push(facet(a[j], b[next], b[j]));
+        }
+    }
+    if !closed {
+        let (first, last) = (&rings[0], &rings[n - 1]);
+        for &[a, b, c] in &profile.caps {
+            triangles.push(facet(first[a], first[c], first[b]));
+            triangles.push(facet(last[a], last[b], last[c]));
+        }
+    }
+    triangles
+}
+
+/// Groove solids along flat lines at z = 0, one closed shell per line
+pub(crate) fn flat_groove_triangles<'a>(
+    lines: impl IntoIterator<Item = (&'a [Point2D], bool)>,
+    profile: &GrooveProfile,
+) -> Vec<Triangle> {
+    lines
+        .into_iter()
+        .flat_map(|(line, closed)| {
+            let points: Vec<Point3D> = line.iter().map(|p| Point3D::new(p.x, p.y, 0.0)).collect();
+            groove_solid_triangles(&points, closed, profile, |_| Point3D::new(0.0, 0.0, 1.0))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::common::tests::assert_closed_outward;
+
+    /// Signed volume enclosed by a closed mesh; positive when its facets
+    /// face outward
+    pub(crate) fn enclosed_volume(triangles: &[Triangle]) -> f64 {
+        triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.vertices.map(|v| [v[0] as f64, v[1] as f64, v[2] as f64]);
+                dot(a, cross(b, c)) / 6.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_profiles_follow_the_tool() {
+        let flat = GrooveProfile::flat(0.4, 0.1).unwrap();
+        assert_eq!(
+            flat.points,
+            vec![(-0.2, 0.0), (-0.2, -0.1), (0.2, -0.1), (0.2, 0.0)]
+        );
+        assert_eq!(flat.caps.len(), 2);
+
+        // A 90° V cut 0.1 deep is 0.2 wide, however wide the bit
+        let v = GrooveProfile::v_shaped(90.0, 1.0, 0.1).unwrap();
+        assert_eq!(v.points.len(), 3);
+        assert!((v.width() - 0.2).abs() < 1e-12);
+        assert!((v.points[1].1 + 0.1).abs() < 1e-12);
+
+        // Cut deeper than the bit is long, its sides run straight up
+        let deep = GrooveProfile::v_shaped(90.0, 0.2, 0.3).unwrap();
+        assert_eq!(deep.points.len(), 5);
+        assert!((deep.width() - 0.2).abs() < 1e-12);
+        assert_eq!(deep.caps.len(), 3);
+
+        assert!(GrooveProfile::flat(0.0, 0.1).is_err());
+        assert!(GrooveProfile::flat(0.4, 0.0).is_err());
+        assert!(GrooveProfile::from_tip_heights(0.4, 0.1, 5, |_| 1.0).is_err());
+    }
+
+    #[test]
+    fn test_groove_solids_are_closed_and_outward() {
+        let square = [
+            Point2D::new(0.0, 0.0),
+            Point2D::new(4.0, 0.0),
+            Point2D::new(4.0, 4.0),
+            Point2D::new(0.0, 4.0),
+        ];
+        let zigzag = [
+            Point2D::new(0.0, 0.0),
+            Point2D::new(2.0, 0.0),
+            Point2D::new(2.0, 0.0),
+            Point2D::new(0.5, 0.2),
+            Point2D::new(3.0, 1.0),
+        ];
+        let round =
+            GrooveProfile::from_tip_heights(0.4, 0.2, 9, |u| 0.2 - (0.04 - u * u).max(0.0).sqrt())
+                .unwrap();
+        for profile in [
+            GrooveProfile::flat(0.4, 0.1).unwrap(),
+            GrooveProfile::v_shaped(60.0, 0.4, 0.1).unwrap(),
+            round,
+        ] {
+            // Open, closed, and closed by repeating the first point
+            let mut ring = square.to_vec();
+            ring.push(square[0]);
+            for (line, closed) in [
+                (&zigzag[..], false),
+                (&square[..], true),
+                (&ring[..], false),
+            ] {
+                let triangles = flat_groove_triangles([(line, closed)], &profile);
+                assert!(!triangles.is_empty());
+                assert_closed_outward(&triangles);
+                assert!(enclosed_volume(&triangles) > 0.0);
+                // Nothing stands above the surface
+                assert!(triangles
+                    .iter()
+                    .flat_map(|t| t.vertices.iter())
+                    .all(|v| v[2] <= 0.0));
+            }
+        }
+
+        // A straight flat groove is a box: length × width × depth
+        let profile = GrooveProfile::flat(0.5, 0.2).unwrap();
+        let line = [Point2D::new(0.0, 0.0), Point2D::new(3.0, 4.0)];
+        let triangles = flat_groove_triangles([(&line[..], false)], &profile);
+        assert_eq!(triangles.len(), 2 * 4 + 2 * 2);
+        assert!((enclosed_volume(&triangles) - 5.0 * 0.5 * 0.2).abs() < 1e-5);
+
+        let dot = [Point2D::new(1.0, 1.0), Point2D::new(1.0, 1.0)];
+        assert!(flat_groove_triangles([(&dot[..], false)], &profile).is_empty());
+    }
+}
@@ -1,16 +1,51 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use svg::node::element::{Group, Path};
+
+use crate::cells::cut_polyline_outside_convex;
+use crate::chapter_ring::{ChapterRingConfig, ChapterRingLayer};
 use crate::clous_de_paris::{ClousDeParisConfig, ClousDeParisLayer};
-use crate::common::{validate_radius, ExportConfig, Point2D, SpirographError};
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    polyline_bounds, polyline_closes, polyline_is_degenerate, simplify_polyline, DialShape,
+    ExportConfig, GeometryStats, PatternLayer, Point2D, PointsPerRev, RadiusPolicy,
+    SpirographError, StoredLayer, Unit,
+};
+use crate::cotes_de_geneve::{CotesDeGeneveConfig, CotesDeGeneveLayer};
 use crate::cube::{CubeConfig, CubeLayer};
 use crate::diamant::{DiamantConfig, DiamantLayer};
+use crate::diff::{write_diff_svg, DiffSummary};
 use crate::draperie::{DraperieConfig, DraperieLayer};
+use crate::engrave_text::{EngravedTextConfig, EngravedTextLayer};
 use crate::flinque::{FlinqueConfig, FlinqueLayer};
+use crate::grain_de_riz::{GrainDeRizConfig, GrainDeRizLayer};
+use crate::groove::flat_groove_triangles;
+use crate::hpgl::{pens_by_layer, write_hpgl};
 use crate::huiteight::{HuitEightConfig, HuitEightLayer};
+use crate::import::{svg_import, ImportedLayer};
+use crate::index_markers::{IndexConfig, IndexMarkersLayer};
 use crate::limacon::LimaconLayer;
+use crate::metadata::{step_header, FileMetadata};
 use crate::paon::{PaonConfig, PaonLayer};
+use crate::phyllotaxis::{PhyllotaxisConfig, PhyllotaxisLayer};
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{fit_point_budget, scale_count, PointCounted, Progressive};
+use crate::raster::{render_preview, svg_color_rgb, PreviewStroke, RgbaImage};
+use crate::soleil::{SoleilConfig, SoleilLayer};
 use crate::spirograph::{HorizontalSpirograph, SphericalSpirograph, VerticalSpirograph};
+use crate::step::{write_step_assembly, StepPart};
+use crate::tapisserie::{TapisserieConfig, TapisserieLayer};
+use crate::vague::{VagueConfig, VagueLayer};
+
+/// Lines of every layer of one kind, by layer index
+pub(crate) type KindLines<'a> = Vec<&'a [Vec<Point2D>]>;
 
 /// Enum to hold different types of spirograph patterns
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SpirographLayer {
     Horizontal(HorizontalSpirograph),
     Vertical(VerticalSpirograph),
@@ -41,6 +76,195 @@ impl SpirographLayer {
             SpirographLayer::Spherical(s) => s.points_2d().clone(),
         }
     }
+
+    /// Check this layer's parameters, with lengths in units of
+    /// `mm_per_unit` millimeters
+    pub(crate) fn validate_scaled(&self, mm_per_unit: f64) -> Result<(), SpirographError> {
+        match self {
+            SpirographLayer::Horizontal(s) => s.validate_scaled(mm_per_unit),
+            SpirographLayer::Vertical(s) => s.validate_scaled(mm_per_unit),
+            SpirographLayer::Spherical(s) => s.validate_scaled(mm_per_unit),
+        }
+    }
+
+    /// Center point of this layer
+    pub fn center(&self) -> (f64, f64) {
+        match self {
+            SpirographLayer::Horizontal(s) => (s.center_x, s.center_y),
+            SpirographLayer::Vertical(s) => (s.center_x, s.center_y),
+            SpirographLayer::Spherical(s) => (s.center_x, s.center_y),
+        }
+    }
+
+    /// Move this layer's center; takes effect on the next `generate()`
+    pub fn set_center(&mut self, x: f64, y: f64) {
+        match self {
+            SpirographLayer::Horizontal(s) => (s.center_x, s.center_y) = (x, y),
+            SpirographLayer::Vertical(s) => (s.center_x, s.center_y) = (x, y),
+            SpirographLayer::Spherical(s) => (s.center_x, s.center_y) = (x, y),
+        }
+    }
+
+    /// Mutable access to the generated 2D points, for post-processing
+    pub(crate) fn points_mut(&mut self) -> &mut Vec<Point2D> {
+        match self {
+            SpirographLayer::Horizontal(s) => s.points_mut(),
+            SpirographLayer::Vertical(s) => s.points_mut(),
+            SpirographLayer::Spherical(s) => s.points_mut(),
+        }
+    }
+
+    /// Drop this layer's generated points
+    pub(crate) fn clear(&mut self) {
+        match self {
+            SpirographLayer::Horizontal(s) => s.clear(),
+            SpirographLayer::Vertical(s) => s.clear(),
+            SpirographLayer::Spherical(s) => s.clear(),
+        }
+    }
+
+    /// Multiply every length of this layer by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        match self {
+            SpirographLayer::Horizontal(s) => s.scale_lengths(factor),
+            SpirographLayer::Vertical(s) => s.scale_lengths(factor),
+            SpirographLayer::Spherical(s) => s.scale_lengths(factor),
+        }
+    }
+
+    /// Bounds, engraved length and point count of the generated points
+    pub fn stats(&self) -> Option<GeometryStats> {
+        match self {
+            SpirographLayer::Horizontal(s) => s.stats(),
+            SpirographLayer::Vertical(s) => s.stats(),
+            SpirographLayer::Spherical(s) => s.stats(),
+        }
+    }
+
+    /// The parameters that shape this layer, in `Debug` style
+    pub fn config_summary(&self) -> String {
+        match self {
+            SpirographLayer::Horizontal(s) => format!(
+                "Horizontal {{ outer_radius: {:?}, radius_ratio: {:?}, point_distance: {:?}, rotations: {}, resolution: {:?} }}",
+                s.outer_radius, s.radius_ratio, s.point_distance, s.rotations, s.resolution
+            ),
+            SpirographLayer::Vertical(s) => format!(
+                "Vertical {{ outer_radius: {:?}, radius_ratio: {:?}, point_distance: {:?}, rotations: {}, resolution: {:?}, wave_amplitude: {:?}, wave_frequency: {:?} }}",
+                s.outer_radius,
+                s.radius_ratio,
+                s.point_distance,
+                s.rotations,
+                s.resolution,
+                s.wave_amplitude,
+                s.wave_frequency
+            ),
+            SpirographLayer::Spherical(s) => format!(
+                "Spherical {{ outer_radius: {:?}, radius_ratio: {:?}, point_distance: {:?}, rotations: {}, resolution: {:?}, dome_height: {:?} }}",
+                s.outer_radius, s.radius_ratio, s.point_distance, s.rotations, s.resolution, s.dome_height
+            ),
+        }
+    }
+}
+
+impl PatternLayer for SpirographLayer {
+    fn generate(&mut self) {
+        SpirographLayer::generate(self);
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.validate_scaled(1.0)
+    }
+
+    /// The curve as one polyline, projected onto the plane
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        match self {
+            SpirographLayer::Horizontal(s) => s.polylines(),
+            SpirographLayer::Vertical(s) => s.polylines(),
+            SpirographLayer::Spherical(s) => s.polylines(),
+        }
+    }
+}
+
+impl StoredLayer for SpirographLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply_single(self.points_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        SpirographLayer::clear(self);
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        SpirographLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        let points = self.points_mut();
+        *points = simplify_polyline(points, tolerance);
+    }
+
+    fn validate_scaled(&self, mm_per_unit: f64) -> Result<(), SpirographError> {
+        SpirographLayer::validate_scaled(self, mm_per_unit)
+    }
+
+    fn config_summary(&self) -> String {
+        SpirographLayer::config_summary(self)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some(SpirographLayer::center(self))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        SpirographLayer::set_center(self, x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for SpirographLayer {
+    const KIND: LayerKind = LayerKind::Spirograph;
+}
+
+impl Progressive for SpirographLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        // Fewer points per revolution; the rotation count sets the figure.
+        // The coarse points are kept but the configured resolution is not.
+        let scale =
+            |resolution: PointsPerRev| PointsPerRev(scale_count(resolution.get(), fraction, 16));
+        match self {
+            SpirographLayer::Horizontal(s) => {
+                let full = s.resolution;
+                s.resolution = scale(full);
+                s.generate();
+                s.resolution = full;
+            }
+            SpirographLayer::Vertical(s) => {
+                let full = s.resolution;
+                s.resolution = scale(full);
+                s.generate();
+                s.resolution = full;
+            }
+            SpirographLayer::Spherical(s) => {
+                let full = s.resolution;
+                s.resolution = scale(full);
+                s.generate();
+                s.resolution = full;
+            }
+        }
+    }
+}
+
+impl PointCounted for SpirographLayer {
+    fn point_count(&self) -> usize {
+        match self {
+            SpirographLayer::Horizontal(s) => s.points().len(),
+            SpirographLayer::Vertical(s) => s.points().len(),
+            SpirographLayer::Spherical(s) => s.points_2d().len(),
+        }
+    }
 }
 
 /// Enum to hold all layer types including flinqué
@@ -60,61 +284,562 @@ impl GuillocheLayer {
     }
 }
 
-/// GuillochePattern - Combines multiple spirograph and flinqué patterns for complex guilloche effects
+/// A layer whose parameters fail validation
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerValidationIssue {
+    /// Kind of the layer
+    pub kind: LayerKind,
+    /// Position among layers of that kind, as used by post-processing
+    pub index: usize,
+    /// Parameter the message names first, e.g. `base_radius`
+    pub field: String,
+    /// Why the layer is invalid
+    pub message: String,
+}
+
+impl LayerValidationIssue {
+    fn new(kind: LayerKind, index: usize, error: &SpirographError) -> Self {
+        let (field, message) = match error {
+            // Only a spirograph's outer radius is checked against the dial range
+            SpirographError::InvalidRadius(message) => ("outer_radius".to_string(), message),
+            SpirographError::InvalidParameter(message)
+            | SpirographError::ExportError(message)
+            | SpirographError::UnsupportedForMachine(message) => {
+                let field = message
+                    .split_whitespace()
+                    .next()
+                    .filter(|word| {
+                        word.chars()
+                            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+                    })
+                    .unwrap_or_default();
+                (field.to_string(), message)
+            }
+        };
+        LayerValidationIssue {
+            kind,
+            index,
+            field,
+            message: message.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for LayerValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} layer {}: {}",
+            self.kind.name(),
+            self.index,
+            self.message
+        )
+    }
+}
+
+/// Result of `GuillochePattern::generate_lenient`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GenerateOutcome {
+    /// Number of layers that were generated
+    pub generated: usize,
+    /// Layers left empty because their parameters are invalid
+    pub skipped: Vec<LayerValidationIssue>,
+}
+
+impl GenerateOutcome {
+    /// True if no layer was skipped
+    pub fn is_clean(&self) -> bool {
+        self.skipped.is_empty()
+    }
+}
+
+/// Spirograph line colors, cycling by layer - subtle dark tones that
+/// simulate engraved metal; varying shades create depth and visual interest
+pub(crate) const SPIROGRAPH_COLORS: [&str; 6] = [
+    "#1a1a1a", // Deep black for primary pattern
+    "#2d2d2d", // Dark gray
+    "#3a3a3a", // Medium-dark gray
+    "#454545", // Medium gray
+    "#505050", // Lighter gray
+    "#5a5a5a", // Light gray for subtle background patterns
+];
+
+/// Spirograph stroke widths in mm, cycling with the colors - thinner lines
+/// for a more delicate guilloche appearance
+pub(crate) const SPIROGRAPH_STROKE_WIDTHS: [f64; 6] = [0.04, 0.035, 0.03, 0.03, 0.025, 0.025];
+
+/// The pieces of `points` inside `clip`, each flagged if closed
+///
+/// Without a clip, or with the whole curve inside it, the curve is kept
+/// as it is; otherwise every piece is open.
+pub(crate) fn clip_pieces(
+    clip: Option<DialShape>,
+    points: Vec<Point2D>,
+    closed: bool,
+) -> Vec<(Vec<Point2D>, bool)> {
+    match clip {
+        Some(shape) if !points.iter().all(|&p| shape.contains(p)) => shape
+            .clip_polyline(&points, closed)
+            .into_iter()
+            .map(|piece| (piece, false))
+            .collect(),
+        _ => vec![(points, closed)],
+    }
+}
+
+/// The parts of each polyline, flagged if closed, outside every convex
+/// outline in `outlines`
+///
+/// A closed polyline cut by an outline becomes open pieces.
+fn cut_outside_convex(
+    mut polylines: Vec<(Vec<Point2D>, bool)>,
+    outlines: &[Vec<Point2D>],
+) -> Vec<(Vec<Point2D>, bool)> {
+    for outline in outlines {
+        polylines = polylines
+            .into_iter()
+            .flat_map(|(points, closed)| {
+                let mut open = points.clone();
+                if closed {
+                    open.push(points[0]);
+                }
+                match cut_polyline_outside_convex(&open, outline) {
+                    Some(pieces) => pieces.into_iter().map(|piece| (piece, false)).collect(),
+                    None => vec![(points, closed)],
+                }
+            })
+            .collect();
+    }
+    polylines
+}
+
+/// A type of layer a `GuillochePattern` stores
+///
+/// Implemented next to each layer type's `StoredLayer` impl. `KIND` tags
+/// the layer in storage and in JSON, and [`LAYER_READERS`] reads it back,
+/// so a new type of layer needs this impl, a reader and its [`LayerKind`].
+pub(crate) trait LayerType: StoredLayer + Clone + Serialize + DeserializeOwned {
+    /// Kind of every layer of this type
+    const KIND: LayerKind;
+}
+
+/// What a stored layer gets from its [`LayerType`] impl
+pub(crate) trait TaggedLayer {
+    /// Kind of the layer
+    fn kind(&self) -> LayerKind;
+
+    /// A boxed copy of the layer
+    fn clone_box(&self) -> Box<dyn StoredLayer>;
+
+    /// The layer as `Serialize` writes it, without its kind
+    fn to_json_value(&self) -> Result<serde_json::Value, serde_json::Error>;
+}
+
+impl<L: LayerType> TaggedLayer for L {
+    fn kind(&self) -> LayerKind {
+        L::KIND
+    }
+
+    fn clone_box(&self) -> Box<dyn StoredLayer> {
+        Box::new(self.clone())
+    }
+
+    fn to_json_value(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+impl Clone for Box<dyn StoredLayer> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Reads layers of one kind back from JSON
+struct LayerReader {
+    kind: LayerKind,
+    read: fn(serde_json::Value) -> Result<Box<dyn StoredLayer>, serde_json::Error>,
+}
+
+impl LayerReader {
+    /// The reader for layers of type `L`
+    const fn of<L: LayerType>() -> Self {
+        LayerReader {
+            kind: L::KIND,
+            read: read_layer::<L>,
+        }
+    }
+}
+
+/// A layer of type `L` from the JSON `to_json_value` wrote
+fn read_layer<L: LayerType>(
+    value: serde_json::Value,
+) -> Result<Box<dyn StoredLayer>, serde_json::Error> {
+    Ok(Box::new(serde_json::from_value::<L>(value)?))
+}
+
+/// A reader for every type of layer a pattern stores
+const LAYER_READERS: &[LayerReader] = &[
+    LayerReader::of::<SpirographLayer>(),
+    LayerReader::of::<FlinqueLayer>(),
+    LayerReader::of::<DiamantLayer>(),
+    LayerReader::of::<DraperieLayer>(),
+    LayerReader::of::<HuitEightLayer>(),
+    LayerReader::of::<LimaconLayer>(),
+    LayerReader::of::<PaonLayer>(),
+    LayerReader::of::<ClousDeParisLayer>(),
+    LayerReader::of::<CubeLayer>(),
+    LayerReader::of::<PhyllotaxisLayer>(),
+    LayerReader::of::<GrainDeRizLayer>(),
+    LayerReader::of::<SoleilLayer>(),
+    LayerReader::of::<CotesDeGeneveLayer>(),
+    LayerReader::of::<TapisserieLayer>(),
+    LayerReader::of::<VagueLayer>(),
+    LayerReader::of::<EngravedTextLayer>(),
+    LayerReader::of::<ChapterRingLayer>(),
+    LayerReader::of::<IndexMarkersLayer>(),
+    LayerReader::of::<ImportedLayer>(),
+];
+
+/// A layer to add to a pattern, of any kind
+///
+/// Made from a layer with `From`, e.g. `LayerSpec::from(diamant)`.
 #[derive(Debug, Clone)]
+pub struct LayerSpec(pub(crate) Box<dyn StoredLayer>);
+
+impl LayerSpec {
+    /// Kind of layer this spec adds
+    pub fn kind(&self) -> LayerKind {
+        self.0.kind()
+    }
+}
+
+impl<L: LayerType> From<L> for LayerSpec {
+    fn from(layer: L) -> Self {
+        LayerSpec(Box::new(layer))
+    }
+}
+
+/// How the indices of one kind of layer change when a layer is inserted
+/// or removed
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum IndexShift {
+    Inserted(usize),
+    Removed(usize),
+}
+
+impl IndexShift {
+    /// New index of the layer that was at `index`, or `None` if it was
+    /// the one removed
+    pub(crate) fn apply(self, index: usize) -> Option<usize> {
+        match self {
+            IndexShift::Inserted(at) if index >= at => Some(index + 1),
+            IndexShift::Removed(at) if index == at => None,
+            IndexShift::Removed(at) if index > at => Some(index - 1),
+            _ => Some(index),
+        }
+    }
+}
+
+/// GuillochePattern - Combines multiple spirograph and flinqué patterns for complex guilloche effects
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GuillochePattern {
     pub radius: f64,
-    spirograph_layers: Vec<SpirographLayer>,
-    flinque_layers: Vec<FlinqueLayer>,
-    diamant_layers: Vec<DiamantLayer>,
-    draperie_layers: Vec<DraperieLayer>,
-    huiteight_layers: Vec<HuitEightLayer>,
-    limacon_layers: Vec<LimaconLayer>,
-    paon_layers: Vec<PaonLayer>,
-    clous_de_paris_layers: Vec<ClousDeParisLayer>,
-    cube_layers: Vec<CubeLayer>,
+    /// Every layer, grouped by kind in `LayerKind` order
+    #[serde(flatten, with = "layer_lists")]
+    layers: Vec<Box<dyn StoredLayer>>,
+    #[serde(skip)]
+    post_processes: PostProcessors,
+    #[serde(skip)]
+    stale_layers: Vec<(LayerKind, usize)>,
+    clip_radius: Option<f64>,
+    svg_layer_data: bool,
+}
+
+/// Serde for `GuillochePattern::layers`, as a `<kind>_layers` list per
+/// kind with layers, named by [`LayerKind::name`]
+mod layer_lists {
+    use std::fmt;
+
+    use serde::de::{DeserializeSeed, Error as _, IgnoredAny, MapAccess, SeqAccess, Visitor};
+    use serde::ser::{Error as _, SerializeMap};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::LAYER_READERS;
+    use crate::common::StoredLayer;
+    use crate::post::LayerKind;
+
+    pub(super) fn serialize<S: Serializer>(
+        layers: &[Box<dyn StoredLayer>],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        for group in layers.chunk_by(|a, b| a.kind() == b.kind()) {
+            let values = group
+                .iter()
+                .map(|layer| layer.to_json_value())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(S::Error::custom)?;
+            map.serialize_entry(&format!("{}_layers", group[0].kind().name()), &values)?;
+        }
+        map.end()
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Box<dyn StoredLayer>>, D::Error> {
+        deserializer.deserialize_map(Lists)
+    }
+
+    /// Reads every `<kind>_layers` entry, ignoring other keys
+    struct Lists;
+
+    impl<'de> Visitor<'de> for Lists {
+        type Value = Vec<Box<dyn StoredLayer>>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("lists of layers by kind")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut layers = Vec::new();
+            while let Some(key) = map.next_key::<String>()? {
+                match key.strip_suffix("_layers").and_then(LayerKind::from_name) {
+                    Some(kind) => layers.extend(map.next_value_seed(KindList(kind))?),
+                    None => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+            }
+            layers.sort_by_key(|layer| layer.kind());
+            Ok(layers)
+        }
+    }
+
+    /// Reads one list of layers of a kind
+    struct KindList(LayerKind);
+
+    impl<'de> DeserializeSeed<'de> for KindList {
+        type Value = Vec<Box<dyn StoredLayer>>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for KindList {
+        type Value = Vec<Box<dyn StoredLayer>>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a list of {} layers", self.0.name())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut layers = Vec::new();
+            while let Some(layer) = seq.next_element_seed(KindLayer(self.0))? {
+                layers.push(layer);
+            }
+            Ok(layers)
+        }
+    }
+
+    /// Reads one layer of a kind through its reader
+    struct KindLayer(LayerKind);
+
+    impl<'de> DeserializeSeed<'de> for KindLayer {
+        type Value = Box<dyn StoredLayer>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            let reader = LAYER_READERS
+                .iter()
+                .find(|reader| reader.kind == self.0)
+                .ok_or_else(|| {
+                    D::Error::custom(format!("{} layers are not pattern layers", self.0.name()))
+                })?;
+            let value = serde_json::Value::deserialize(deserializer)?;
+            (reader.read)(value).map_err(D::Error::custom)
+        }
+    }
 }
 
 impl GuillochePattern {
     /// Create a new guilloche pattern with the specified radius
     pub fn new(radius: f64) -> Result<Self, SpirographError> {
-        validate_radius(radius)?;
+        Self::new_with_policy(radius, RadiusPolicy::Watch)
+    }
+
+    /// Create a new guilloche pattern whose radius is checked by `policy`
+    /// instead of the 26-44mm wristwatch range
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{GuillochePattern, RadiusPolicy};
+    ///
+    /// assert!(GuillochePattern::new(60.0).is_err());
+    /// assert!(GuillochePattern::new_with_policy(60.0, RadiusPolicy::Unrestricted).is_ok());
+    /// ```
+    pub fn new_with_policy(radius: f64, policy: RadiusPolicy) -> Result<Self, SpirographError> {
+        policy.check(radius)?;
+        Ok(Self::new_unchecked(radius))
+    }
 
-        Ok(GuillochePattern {
+    /// Create an empty pattern without validating the radius
+    ///
+    /// For callers that validate in another unit (see `WatchFace::new_with_settings`).
+    pub(crate) fn new_unchecked(radius: f64) -> Self {
+        GuillochePattern {
             radius,
-            spirograph_layers: Vec::new(),
-            flinque_layers: Vec::new(),
-            diamant_layers: Vec::new(),
-            draperie_layers: Vec::new(),
-            huiteight_layers: Vec::new(),
-            limacon_layers: Vec::new(),
-            paon_layers: Vec::new(),
-            clous_de_paris_layers: Vec::new(),
-            cube_layers: Vec::new(),
-        })
+            layers: Vec::new(),
+            post_processes: PostProcessors::default(),
+            stale_layers: Vec::new(),
+            clip_radius: None,
+            svg_layer_data: false,
+        }
+    }
+
+    /// Multiply the radius and every layer's lengths by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.radius *= factor;
+        if let Some(radius) = &mut self.clip_radius {
+            *radius *= factor;
+        }
+        for (_, _, layer) in self.stored_layers_mut() {
+            layer.scale_lengths(factor);
+        }
+    }
+
+    /// Simplify every layer's generated lines to `tolerance`
+    pub(crate) fn simplify_lines(&mut self, tolerance: f64) {
+        for (_, _, layer) in self.stored_layers_mut() {
+            layer.simplify(tolerance);
+        }
+    }
+
+    /// Cut every layer at a circle of `radius` about the origin in the
+    /// combined exports
+    ///
+    /// Lines are split where they cross the circle, with the crossing
+    /// points computed exactly on it, and the parts outside are dropped;
+    /// a line leaving and re-entering becomes several pieces. The SVG,
+    /// STL and STEP exports all use the clipped geometry.
+    pub fn clip_to_radius(&mut self, radius: f64) -> Result<(), SpirographError> {
+        if !(radius.is_finite() && radius > 0.0) {
+            return Err(SpirographError::InvalidRadius(format!(
+                "Clip radius must be positive and finite, got {}",
+                radius
+            )));
+        }
+        self.clip_radius = Some(radius);
+        Ok(())
+    }
+
+    /// Stop clipping the combined exports
+    pub fn clear_clip(&mut self) {
+        self.clip_radius = None;
+    }
+
+    /// Radius the combined exports are clipped to, if any
+    pub fn clip_radius(&self) -> Option<f64> {
+        self.clip_radius
+    }
+
+    /// Write each layer's configuration and geometry as `data-` attributes
+    /// on its SVG group
+    ///
+    /// Adds `data-config` (the layer's parameters in `Debug` style),
+    /// `data-center` ("x,y", for layers placed by a center) and, once
+    /// generated, `data-lines`, `data-points` and `data-length`.
+    pub fn set_svg_layer_data(&mut self, enabled: bool) {
+        self.svg_layer_data = enabled;
+    }
+
+    /// Whether SVG layer groups carry `data-` attributes
+    pub fn svg_layer_data(&self) -> bool {
+        self.svg_layer_data
+    }
+
+    /// The SVG `<g>` for layer `index` of `kind`
+    ///
+    /// Its id is the kind's name and the index (`flinque-0`) and its class
+    /// `turtles-layer` plus the kind's name, so post-processing tools can
+    /// find layers by either. See
+    /// [`set_svg_layer_data`](Self::set_svg_layer_data) for the optional
+    /// `data-` attributes.
+    pub(crate) fn svg_layer_group(&self, kind: LayerKind, index: usize) -> Group {
+        let mut group = Group::new()
+            .set("id", format!("{}-{}", kind.name(), index))
+            .set("class", format!("turtles-layer {}", kind.name()));
+        if !self.svg_layer_data {
+            return group;
+        }
+        let (config, stats) = self.layer_summary(kind, index);
+        if !config.is_empty() {
+            // The svg crate does not escape quotes in attribute values
+            group = group.set("data-config", config.replace('"', "'"));
+        }
+        if let Ok(Some((x, y))) = self.layer_center(kind, index) {
+            group = group.set("data-center", format!("{},{}", x, y));
+        }
+        if let Some(stats) = stats {
+            group = group
+                .set("data-lines", stats.num_polylines)
+                .set("data-points", stats.num_points)
+                .set("data-length", format!("{:.3}", stats.total_path_length));
+        }
+        group
+    }
+
+    /// Layer `index` of `kind`'s configuration in `Debug` style (empty for
+    /// imported layers) and its geometry stats
+    fn layer_summary(&self, kind: LayerKind, index: usize) -> (String, Option<GeometryStats>) {
+        match self.stored_layer(kind, index) {
+            Some(layer) => (layer.config_summary(), layer.stats()),
+            None => (String::new(), None),
+        }
+    }
+
+    /// The clip circle as a dial outline
+    fn clip_shape(&self) -> Option<DialShape> {
+        self.clip_radius.map(|radius| DialShape::Circle { radius })
+    }
+
+    /// `lines` cut to the clip circle, or as they are without one
+    fn clipped_lines<'a>(&self, lines: &'a [Vec<Point2D>]) -> Cow<'a, [Vec<Point2D>]> {
+        match self.clip_shape() {
+            Some(shape) => Cow::Owned(
+                lines
+                    .iter()
+                    .flat_map(|line| shape.clip_polyline(line, false))
+                    .collect(),
+            ),
+            None => Cow::Borrowed(lines),
+        }
     }
 
     /// Add a horizontal spirograph layer centered at origin
     pub fn add_horizontal_layer(&mut self, spiro: HorizontalSpirograph) {
-        self.spirograph_layers
-            .push(SpirographLayer::Horizontal(spiro));
+        self.push_layer(SpirographLayer::Horizontal(spiro));
     }
 
     /// Add a vertical spirograph layer centered at origin
     pub fn add_vertical_layer(&mut self, spiro: VerticalSpirograph) {
-        self.spirograph_layers
-            .push(SpirographLayer::Vertical(spiro));
+        self.push_layer(SpirographLayer::Vertical(spiro));
     }
 
     /// Add a spherical spirograph layer centered at origin
     pub fn add_spherical_layer(&mut self, spiro: SphericalSpirograph) {
-        self.spirograph_layers
-            .push(SpirographLayer::Spherical(spiro));
+        self.push_layer(SpirographLayer::Spherical(spiro));
     }
 
     /// Add a flinqué (engine-turned) layer
     pub fn add_flinque_layer(&mut self, flinque: FlinqueLayer) {
-        self.flinque_layers.push(flinque);
+        self.push_layer(flinque);
     }
 
     /// Add a flinqué layer positioned at a given angle and distance from center
@@ -127,7 +852,7 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let flinque = FlinqueLayer::new_at_polar(radius, config, angle, distance)?;
-        self.flinque_layers.push(flinque);
+        self.push_layer(flinque);
         Ok(())
     }
 
@@ -148,13 +873,13 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let flinque = FlinqueLayer::new_at_clock(radius, config, hour, minute, distance)?;
-        self.flinque_layers.push(flinque);
+        self.push_layer(flinque);
         Ok(())
     }
 
     /// Add a diamant (diamond pattern) layer
     pub fn add_diamant_layer(&mut self, diamant: DiamantLayer) {
-        self.diamant_layers.push(diamant);
+        self.push_layer(diamant);
     }
 
     /// Add a diamant layer positioned at a given angle and distance from center
@@ -166,7 +891,7 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let diamant = DiamantLayer::new_at_polar(config, angle, distance)?;
-        self.diamant_layers.push(diamant);
+        self.push_layer(diamant);
         Ok(())
     }
 
@@ -185,18 +910,18 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let diamant = DiamantLayer::new_at_clock(config, hour, minute, distance)?;
-        self.diamant_layers.push(diamant);
+        self.push_layer(diamant);
         Ok(())
     }
 
     /// Add a draperie (drapery pattern) layer
     pub fn add_draperie_layer(&mut self, draperie: DraperieLayer) {
-        self.draperie_layers.push(draperie);
+        self.push_layer(draperie);
     }
 
     /// Add a huit-eight (figure-eight) pattern layer
     pub fn add_huiteight_layer(&mut self, huiteight: HuitEightLayer) {
-        self.huiteight_layers.push(huiteight);
+        self.push_layer(huiteight);
     }
 
     /// Add a huit-eight layer positioned at a given angle and distance from center
@@ -207,7 +932,7 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let huiteight = HuitEightLayer::new_at_polar(config, angle, distance)?;
-        self.huiteight_layers.push(huiteight);
+        self.push_layer(huiteight);
         Ok(())
     }
 
@@ -226,7 +951,7 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let huiteight = HuitEightLayer::new_at_clock(config, hour, minute, distance)?;
-        self.huiteight_layers.push(huiteight);
+        self.push_layer(huiteight);
         Ok(())
     }
 
@@ -238,7 +963,7 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let draperie = DraperieLayer::new_at_polar(config, angle, distance)?;
-        self.draperie_layers.push(draperie);
+        self.push_layer(draperie);
         Ok(())
     }
 
@@ -251,13 +976,13 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let draperie = DraperieLayer::new_at_clock(config, hour, minute, distance)?;
-        self.draperie_layers.push(draperie);
+        self.push_layer(draperie);
         Ok(())
     }
 
     /// Add a limaçon pattern layer
     pub fn add_limacon_layer(&mut self, limacon: LimaconLayer) {
-        self.limacon_layers.push(limacon);
+        self.push_layer(limacon);
     }
 
     /// Add a limaçon layer positioned at a given angle and distance from center
@@ -269,7 +994,7 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let limacon = LimaconLayer::new_at_polar(config, angle, distance)?;
-        self.limacon_layers.push(limacon);
+        self.push_layer(limacon);
         Ok(())
     }
 
@@ -288,13 +1013,13 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let limacon = LimaconLayer::new_at_clock(config, hour, minute, distance)?;
-        self.limacon_layers.push(limacon);
+        self.push_layer(limacon);
         Ok(())
     }
 
     /// Add a paon (peacock) pattern layer
     pub fn add_paon_layer(&mut self, paon: PaonLayer) {
-        self.paon_layers.push(paon);
+        self.push_layer(paon);
     }
 
     /// Add a paon layer positioned at a given angle and distance from center
@@ -305,7 +1030,7 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let paon = PaonLayer::new_at_polar(config, angle, distance)?;
-        self.paon_layers.push(paon);
+        self.push_layer(paon);
         Ok(())
     }
 
@@ -324,13 +1049,13 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let paon = PaonLayer::new_at_clock(config, hour, minute, distance)?;
-        self.paon_layers.push(paon);
+        self.push_layer(paon);
         Ok(())
     }
 
     /// Add a clous de Paris (hobnail) pattern layer
     pub fn add_clous_de_paris_layer(&mut self, cdp: ClousDeParisLayer) {
-        self.clous_de_paris_layers.push(cdp);
+        self.push_layer(cdp);
     }
 
     /// Add a clous de Paris layer positioned at a given angle and distance from center
@@ -341,7 +1066,7 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let cdp = ClousDeParisLayer::new_at_polar(config, angle, distance)?;
-        self.clous_de_paris_layers.push(cdp);
+        self.push_layer(cdp);
         Ok(())
     }
 
@@ -360,13 +1085,13 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let cdp = ClousDeParisLayer::new_at_clock(config, hour, minute, distance)?;
-        self.clous_de_paris_layers.push(cdp);
+        self.push_layer(cdp);
         Ok(())
     }
 
     /// Add a cube (tumbling blocks) pattern layer
     pub fn add_cube_layer(&mut self, cube: CubeLayer) {
-        self.cube_layers.push(cube);
+        self.push_layer(cube);
     }
 
     /// Add a cube layer positioned at a given angle and distance from center
@@ -377,7 +1102,7 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let cube = CubeLayer::new_at_polar(config, angle, distance)?;
-        self.cube_layers.push(cube);
+        self.push_layer(cube);
         Ok(())
     }
 
@@ -396,103 +1121,895 @@ impl GuillochePattern {
         distance: f64,
     ) -> Result<(), SpirographError> {
         let cube = CubeLayer::new_at_clock(config, hour, minute, distance)?;
-        self.cube_layers.push(cube);
+        self.push_layer(cube);
         Ok(())
     }
 
-    /// Generate all layers
-    pub fn generate(&mut self) {
-        for layer in &mut self.spirograph_layers {
-            layer.generate();
-        }
-        for layer in &mut self.flinque_layers {
-            layer.generate();
-        }
-        for layer in &mut self.diamant_layers {
-            layer.generate();
-        }
-        for layer in &mut self.draperie_layers {
-            layer.generate();
-        }
-        for layer in &mut self.huiteight_layers {
-            layer.generate();
-        }
-        for layer in &mut self.limacon_layers {
-            layer.generate();
-        }
-        for layer in &mut self.paon_layers {
-            layer.generate();
-        }
-        for layer in &mut self.clous_de_paris_layers {
-            layer.generate();
-        }
-        for layer in &mut self.cube_layers {
-            layer.generate();
-        }
-    }
-
-    /// Get total layer count (spirographs + flinqué + diamant + limaçon)
-    pub fn layer_count(&self) -> usize {
-        self.spirograph_layers.len()
-            + self.flinque_layers.len()
-            + self.diamant_layers.len()
-            + self.draperie_layers.len()
-            + self.huiteight_layers.len()
-            + self.limacon_layers.len()
-            + self.paon_layers.len()
-            + self.clous_de_paris_layers.len()
-            + self.cube_layers.len()
+    /// Add a phyllotaxis (sunflower) pattern layer
+    pub fn add_phyllotaxis_layer(&mut self, phyllotaxis: PhyllotaxisLayer) {
+        self.push_layer(phyllotaxis);
     }
 
-    /// Get all spirograph layer points (for rendering)
-    pub fn spirograph_points(&self) -> Vec<Vec<Point2D>> {
-        self.spirograph_layers
-            .iter()
-            .map(|layer| layer.points_2d())
-            .collect()
+    /// Add a phyllotaxis layer positioned at a given angle and distance from center
+    pub fn add_phyllotaxis_at_polar(
+        &mut self,
+        config: PhyllotaxisConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let phyllotaxis = PhyllotaxisLayer::new_at_polar(config, angle, distance)?;
+        self.push_layer(phyllotaxis);
+        Ok(())
     }
 
-    /// Get all flinqué layer lines (for rendering)
+    /// Add a phyllotaxis layer positioned at a clock position
+    ///
+    /// # Arguments
+    /// * `config` - Phyllotaxis configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn add_phyllotaxis_at_clock(
+        &mut self,
+        config: PhyllotaxisConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let phyllotaxis = PhyllotaxisLayer::new_at_clock(config, hour, minute, distance)?;
+        self.push_layer(phyllotaxis);
+        Ok(())
+    }
+
+    /// Add a grain-de-riz (rice grain) pattern layer
+    pub fn add_grain_de_riz_layer(&mut self, grain_de_riz: GrainDeRizLayer) {
+        self.push_layer(grain_de_riz);
+    }
+
+    /// Add a grain-de-riz layer positioned at a given angle and distance from center
+    pub fn add_grain_de_riz_at_polar(
+        &mut self,
+        config: GrainDeRizConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let grain_de_riz = GrainDeRizLayer::new_at_polar(config, angle, distance)?;
+        self.push_layer(grain_de_riz);
+        Ok(())
+    }
+
+    /// Add a grain-de-riz layer positioned at a clock position
+    ///
+    /// # Arguments
+    /// * `config` - Grain-de-riz configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn add_grain_de_riz_at_clock(
+        &mut self,
+        config: GrainDeRizConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let grain_de_riz = GrainDeRizLayer::new_at_clock(config, hour, minute, distance)?;
+        self.push_layer(grain_de_riz);
+        Ok(())
+    }
+
+    /// Add a soleil (sunray) pattern layer
+    pub fn add_soleil_layer(&mut self, soleil: SoleilLayer) {
+        self.push_layer(soleil);
+    }
+
+    /// Add a soleil layer positioned at a given angle and distance from center
+    pub fn add_soleil_at_polar(
+        &mut self,
+        config: SoleilConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let soleil = SoleilLayer::new_at_polar(config, angle, distance)?;
+        self.push_layer(soleil);
+        Ok(())
+    }
+
+    /// Add a soleil layer positioned at a clock position
+    ///
+    /// # Arguments
+    /// * `config` - Soleil configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn add_soleil_at_clock(
+        &mut self,
+        config: SoleilConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let soleil = SoleilLayer::new_at_clock(config, hour, minute, distance)?;
+        self.push_layer(soleil);
+        Ok(())
+    }
+
+    /// Add a Côtes de Genève (Geneva stripes) pattern layer
+    pub fn add_cotes_de_geneve_layer(&mut self, cotes: CotesDeGeneveLayer) {
+        self.push_layer(cotes);
+    }
+
+    /// Add a Côtes de Genève layer positioned at a given angle and distance
+    /// from center
+    pub fn add_cotes_de_geneve_at_polar(
+        &mut self,
+        config: CotesDeGeneveConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let cotes = CotesDeGeneveLayer::new_at_polar(config, angle, distance)?;
+        self.push_layer(cotes);
+        Ok(())
+    }
+
+    /// Add a Côtes de Genève layer positioned at a clock position
+    ///
+    /// # Arguments
+    /// * `config` - Côtes de Genève configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn add_cotes_de_geneve_at_clock(
+        &mut self,
+        config: CotesDeGeneveConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let cotes = CotesDeGeneveLayer::new_at_clock(config, hour, minute, distance)?;
+        self.push_layer(cotes);
+        Ok(())
+    }
+
+    /// Add a tapisserie (tapestry, waffle) pattern layer
+    pub fn add_tapisserie_layer(&mut self, tapisserie: TapisserieLayer) {
+        self.push_layer(tapisserie);
+    }
+
+    /// Add a tapisserie layer positioned at a given angle and distance
+    /// from center
+    pub fn add_tapisserie_at_polar(
+        &mut self,
+        config: TapisserieConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let tapisserie = TapisserieLayer::new_at_polar(config, angle, distance)?;
+        self.push_layer(tapisserie);
+        Ok(())
+    }
+
+    /// Add a tapisserie layer positioned at a clock position
+    ///
+    /// # Arguments
+    /// * `config` - Tapisserie configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn add_tapisserie_at_clock(
+        &mut self,
+        config: TapisserieConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let tapisserie = TapisserieLayer::new_at_clock(config, hour, minute, distance)?;
+        self.push_layer(tapisserie);
+        Ok(())
+    }
+
+    /// Add a vague (wave, flammes) pattern layer
+    pub fn add_vague_layer(&mut self, vague: VagueLayer) {
+        self.push_layer(vague);
+    }
+
+    /// Add a vague layer positioned at a given angle and distance from
+    /// center
+    pub fn add_vague_at_polar(
+        &mut self,
+        config: VagueConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let vague = VagueLayer::new_at_polar(config, angle, distance)?;
+        self.push_layer(vague);
+        Ok(())
+    }
+
+    /// Add a vague layer positioned at a clock position
+    ///
+    /// # Arguments
+    /// * `config` - Vague configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn add_vague_at_clock(
+        &mut self,
+        config: VagueConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        let vague = VagueLayer::new_at_clock(config, hour, minute, distance)?;
+        self.push_layer(vague);
+        Ok(())
+    }
+
+    /// Add an engraved text layer
+    pub fn add_text_layer(&mut self, text: EngravedTextLayer) {
+        self.push_layer(text);
+    }
+
+    /// Add engraved text from a configuration
+    pub fn add_text(&mut self, config: EngravedTextConfig) -> Result<(), SpirographError> {
+        self.push_layer(EngravedTextLayer::new(config)?);
+        Ok(())
+    }
+
+    /// Add a chapter ring layer
+    pub fn add_chapter_ring_layer(&mut self, ring: ChapterRingLayer) {
+        self.push_layer(ring);
+    }
+
+    /// Add a chapter ring from a configuration
+    pub fn add_chapter_ring(&mut self, config: ChapterRingConfig) -> Result<(), SpirographError> {
+        self.push_layer(ChapterRingLayer::new(config)?);
+        Ok(())
+    }
+
+    /// Add an index markers layer
+    pub fn add_index_markers_layer(&mut self, markers: IndexMarkersLayer) {
+        self.push_layer(markers);
+    }
+
+    /// Add index markers from a configuration
+    ///
+    /// The configuration's style only applies to watch face exports.
+    pub fn add_index_markers(&mut self, config: IndexConfig) -> Result<(), SpirographError> {
+        self.push_layer(IndexMarkersLayer::new(config)?);
+        Ok(())
+    }
+
+    /// Add fixed polylines, such as ones read by
+    /// [`svg_polylines`](crate::import::svg_polylines), as a layer
+    ///
+    /// They are drawn and exported like engraved text strokes, and are in
+    /// the pattern's units.
+    pub fn add_imported_layer(&mut self, lines: Vec<Vec<Point2D>>) {
+        self.push_layer(ImportedLayer::new(lines));
+    }
+
+    /// Add arbitrary polylines as a layer, such as a layer's transformed
+    /// lines from [`PatternLayer::mirrored_x`] or one copy from
+    /// [`PatternLayer::replicated_radial`]
+    ///
+    /// The lines are kept as given and stored like
+    /// [`add_imported_layer`](Self::add_imported_layer)'s.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{GuillochePattern, HorizontalSpirograph, PatternLayer};
+    ///
+    /// let mut spiro = HorizontalSpirograph::new_with_center(30.0, 0.75, 0.6, 3, 90, 15.0, 0.0).unwrap();
+    /// spiro.generate();
+    /// let mut pattern = GuillochePattern::new(38.0).unwrap();
+    /// pattern.add_raw_lines(spiro.mirrored_x().unwrap());
+    /// pattern.add_horizontal_layer(spiro);
+    /// ```
+    pub fn add_raw_lines(&mut self, lines: Vec<Vec<Point2D>>) {
+        self.add_imported_layer(lines);
+    }
+
+    /// Add the straight-line paths of an SVG file (or SVG text) as a layer,
+    /// returning the warnings for the paths that were skipped
+    ///
+    /// See [`svg_import`](crate::import::svg_import).
+    pub fn add_imported_svg(&mut self, path_or_str: &str) -> Result<Vec<String>, SpirographError> {
+        let import = svg_import(path_or_str)?;
+        self.add_imported_layer(import.lines);
+        Ok(import.warnings)
+    }
+
+    /// Register a callback that edits each layer's lines after generation
+    ///
+    /// Callbacks run in registration order at the end of every
+    /// `generate()` (and `generate_parallel()`), once per layer, with the
+    /// layer's kind and its index among layers of that kind. Regenerating
+    /// starts from fresh lines, so edits are re-applied rather than
+    /// stacked. Lines are in the design unit. Spirograph layers are a single
+    /// line; if a callback splits one, the pieces are joined back end to
+    /// end, and a spherical spirograph's 3D points are left as generated.
+    /// See [`crate::post`] for ready-made callbacks.
+    pub fn add_post_process<F>(&mut self, f: F)
+    where
+        F: Fn(&mut Vec<Vec<Point2D>>, LayerKind, usize) + Send + Sync + 'static,
+    {
+        self.post_processes.push(Arc::new(f));
+    }
+
+    /// Remove every post-processing callback
+    ///
+    /// Lines already generated keep their edits until the next `generate()`.
+    pub fn clear_post_processes(&mut self) {
+        self.post_processes.clear();
+    }
+
+    /// Number of registered post-processing callbacks
+    pub fn post_process_count(&self) -> usize {
+        self.post_processes.len()
+    }
+
+    /// Run the post-processing callbacks over every generated layer
+    fn apply_post_processes(&mut self) {
+        if self.post_processes.is_empty() {
+            return;
+        }
+        let post = self.post_processes.clone();
+        for (kind, index, layer) in self.stored_layers_mut() {
+            layer.post_process(&post, kind, index);
+        }
+    }
+
+    /// Generate all layers
+    pub fn generate(&mut self) {
+        self.generate_layers();
+        self.apply_post_processes();
+        self.stale_layers.clear();
+    }
+
+    /// Generate every layer without post-processing
+    fn generate_layers(&mut self) {
+        for (_, _, layer) in self.stored_layers_mut() {
+            layer.generate();
+        }
+    }
+
+    /// Check every layer's parameters without generating anything
+    ///
+    /// Layers are normally checked when they are constructed, but their
+    /// public fields can be changed afterwards. An empty result means
+    /// `generate()` will only see valid layers.
+    pub fn validate(&self) -> Vec<LayerValidationIssue> {
+        self.validate_scaled(1.0)
+    }
+
+    /// `validate` for lengths in units of `mm_per_unit` millimeters
+    pub(crate) fn validate_scaled(&self, mm_per_unit: f64) -> Vec<LayerValidationIssue> {
+        self.stored_layers()
+            .into_iter()
+            .filter_map(|(kind, index, layer)| {
+                let error = layer.validate_scaled(mm_per_unit).err()?;
+                Some(LayerValidationIssue::new(kind, index, &error))
+            })
+            .collect()
+    }
+
+    /// Generate all layers, failing without generating anything if any
+    /// layer is invalid
+    ///
+    /// The error lists every issue `validate()` reports.
+    pub fn try_generate(&mut self) -> Result<(), SpirographError> {
+        self.try_generate_scaled(1.0)
+    }
+
+    /// `try_generate` for lengths in units of `mm_per_unit` millimeters
+    pub(crate) fn try_generate_scaled(&mut self, mm_per_unit: f64) -> Result<(), SpirographError> {
+        let issues = self.validate_scaled(mm_per_unit);
+        if !issues.is_empty() {
+            let messages: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+            return Err(SpirographError::InvalidParameter(messages.join("; ")));
+        }
+        self.generate();
+        Ok(())
+    }
+
+    /// Generate every valid layer, skipping and reporting the invalid ones
+    ///
+    /// Skipped layers are left without geometry, so exports show the rest
+    /// of the design. Post-processing runs as in `generate()`.
+    pub fn generate_lenient(&mut self) -> GenerateOutcome {
+        self.generate_lenient_scaled(1.0)
+    }
+
+    /// `generate_lenient` for lengths in units of `mm_per_unit` millimeters
+    pub(crate) fn generate_lenient_scaled(&mut self, mm_per_unit: f64) -> GenerateOutcome {
+        let skipped = self.validate_scaled(mm_per_unit);
+        let skip = |kind: LayerKind, index: usize| {
+            skipped
+                .iter()
+                .any(|issue| issue.kind == kind && issue.index == index)
+        };
+        for (kind, index, layer) in self.stored_layers_mut() {
+            if skip(kind, index) {
+                layer.clear();
+            } else {
+                layer.generate();
+            }
+        }
+        self.apply_post_processes();
+        self.stale_layers.clear();
+
+        GenerateOutcome {
+            generated: self.layer_count() - skipped.len(),
+            skipped,
+        }
+    }
+
+    /// Generate all layers concurrently on scoped threads
+    ///
+    /// Each layer is generated independently on its own thread, in place, so
+    /// the stored order of layers and their lines is exactly the same as
+    /// after `generate()` and the output is identical point-for-point.
+    pub fn generate_parallel(&mut self) {
+        let layers = self.stored_layers_mut();
+        std::thread::scope(|scope| {
+            for (_, _, layer) in layers {
+                scope.spawn(move || layer.generate());
+            }
+        });
+        self.apply_post_processes();
+        self.stale_layers.clear();
+    }
+
+    /// Thin the generated layers so the pattern holds at most about `budget`
+    /// points
+    ///
+    /// Layers keep their parameters: sampling density (and, for draperie
+    /// and paon, line counts) is reduced in proportion to each layer's
+    /// share of the full point count. Cube, text, chapter ring and imported
+    /// layers stay at full detail. Call after `generate()`.
+    pub(crate) fn reduce_to_point_budget(&mut self, budget: usize) {
+        // Start from unprocessed lines so the edits are applied once, after thinning
+        if !self.post_processes.is_empty() {
+            self.generate_layers();
+        }
+
+        let mut fixed = 0;
+        let mut thinned: Vec<&mut dyn PointCounted> = Vec::new();
+        for layer in &mut self.layers {
+            let points: usize = layer.polylines().iter().map(Vec::len).sum();
+            match layer.point_counted_mut() {
+                Some(layer) => thinned.push(layer),
+                None => fixed += points,
+            }
+        }
+        fit_point_budget(&mut thinned, budget.saturating_sub(fixed));
+        self.apply_post_processes();
+    }
+
+    /// Get total layer count over every kind
+    pub fn layer_count(&self) -> usize {
+        self.stored_layers().len()
+    }
+
+    /// Number of layers of `kind`
+    pub fn kind_count(&self, kind: LayerKind) -> usize {
+        self.kind_range(kind).len()
+    }
+
+    /// Every layer as a trait object with its kind and its index among
+    /// layers of that kind, spirographs first, then in storage order
+    ///
+    /// Lets callers generate, check and measure layers of any kind in one
+    /// loop; the typed `*_lines()` accessors remain for kind-specific work.
+    pub fn layers(&self) -> Vec<(LayerKind, usize, &dyn PatternLayer)> {
+        self.stored_layers()
+            .into_iter()
+            .map(|(kind, index, layer)| (kind, index, layer as &dyn PatternLayer))
+            .collect()
+    }
+
+    /// The polylines of every layer of `kind`, by index
+    ///
+    /// Spirographs give their curve as one polyline, projected onto the
+    /// plane. Empty for lathe passes.
+    pub fn kind_lines(&self, kind: LayerKind) -> Vec<&[Vec<Point2D>]> {
+        self.stored_layers()
+            .into_iter()
+            .filter(|&(k, _, _)| k == kind)
+            .map(|(_, _, layer)| layer.polylines())
+            .collect()
+    }
+
+    /// Lines of every layer but the spirographs, grouped by kind in
+    /// storage order; kinds without layers are left out
+    pub(crate) fn line_sets_by_kind(&self) -> Vec<(LayerKind, KindLines<'_>)> {
+        self.layer_line_sets()
+            .into_iter()
+            .map(|(kind, lines, _)| (kind, lines))
+            .collect()
+    }
+
+    /// Every layer with its kind and index, in storage order
+    fn stored_layers(&self) -> Vec<(LayerKind, usize, &dyn StoredLayer)> {
+        self.layer_tags()
+            .into_iter()
+            .zip(&self.layers)
+            .map(|((kind, index), layer)| (kind, index, layer.as_ref()))
+            .collect()
+    }
+
+    /// `stored_layers` with mutable access
+    fn stored_layers_mut(&mut self) -> Vec<(LayerKind, usize, &mut dyn StoredLayer)> {
+        self.layer_tags()
+            .into_iter()
+            .zip(&mut self.layers)
+            .map(|((kind, index), layer)| (kind, index, layer.as_mut()))
+            .collect()
+    }
+
+    /// Kind of each stored layer and its index among layers of that kind
+    fn layer_tags(&self) -> Vec<(LayerKind, usize)> {
+        let mut tags: Vec<(LayerKind, usize)> = Vec::with_capacity(self.layers.len());
+        for layer in &self.layers {
+            let kind = layer.kind();
+            let index = match tags.last() {
+                Some(&(last, index)) if last == kind => index + 1,
+                _ => 0,
+            };
+            tags.push((kind, index));
+        }
+        tags
+    }
+
+    /// Positions in storage of the layers of `kind`
+    fn kind_range(&self, kind: LayerKind) -> std::ops::Range<usize> {
+        let start = self.layers.partition_point(|layer| layer.kind() < kind);
+        let end = self.layers.partition_point(|layer| layer.kind() <= kind);
+        start..end
+    }
+
+    /// Every layer of type `L`, by index
+    fn typed_layers<L: LayerType>(&self) -> impl Iterator<Item = &L> {
+        self.layers[self.kind_range(L::KIND)]
+            .iter()
+            .filter_map(|layer| (layer.as_ref() as &dyn Any).downcast_ref())
+    }
+
+    /// Layer `index` of `kind`, if there is one
+    fn stored_layer(&self, kind: LayerKind, index: usize) -> Option<&dyn StoredLayer> {
+        let range = self.kind_range(kind);
+        (index < range.len()).then(|| self.layers[range.start + index].as_ref())
+    }
+
+    /// Layer `index` of `kind` with mutable access, if there is one
+    fn stored_layer_mut(&mut self, kind: LayerKind, index: usize) -> Option<&mut dyn StoredLayer> {
+        let range = self.kind_range(kind);
+        if index < range.len() {
+            Some(self.layers[range.start + index].as_mut())
+        } else {
+            None
+        }
+    }
+
+    /// Append a layer after the others of its kind
+    fn push_layer<L: LayerType>(&mut self, layer: L) {
+        self.insert_layer(Box::new(layer), usize::MAX);
+    }
+
+    /// Layers changed by edits since the last full generation, as
+    /// (kind, index)
+    ///
+    /// Their stored geometry is out of date until `regenerate_stale()`.
+    pub fn stale_layers(&self) -> &[(LayerKind, usize)] {
+        &self.stale_layers
+    }
+
+    /// Generate only the stale layers, running the post-processing
+    /// callbacks on each; returns how many were generated
+    ///
+    /// Every other layer keeps its geometry, so the result matches a full
+    /// `generate()` without redoing unaffected layers.
+    pub fn regenerate_stale(&mut self) -> usize {
+        let stale = std::mem::take(&mut self.stale_layers);
+        for &(kind, index) in &stale {
+            self.generate_layer(kind, index);
+        }
+        stale.len()
+    }
+
+    /// Generate and post-process layer `index` of `kind`
+    fn generate_layer(&mut self, kind: LayerKind, index: usize) {
+        let post = self.post_processes.clone();
+        if let Some(layer) = self.stored_layer_mut(kind, index) {
+            layer.generate();
+            layer.post_process(&post, kind, index);
+        }
+    }
+
+    /// Error unless `index` names a layer of `kind`
+    fn check_layer(&self, kind: LayerKind, index: usize) -> Result<(), SpirographError> {
+        if index < self.kind_count(kind) {
+            Ok(())
+        } else {
+            Err(SpirographError::InvalidParameter(format!(
+                "no {} layer at index {}",
+                kind.name(),
+                index
+            )))
+        }
+    }
+
+    /// Insert a layer at `index` among layers of its kind (clamped to the
+    /// end); returns its kind and index
+    pub(crate) fn insert_layer(
+        &mut self,
+        layer: Box<dyn StoredLayer>,
+        index: usize,
+    ) -> (LayerKind, usize) {
+        let kind = layer.kind();
+        let range = self.kind_range(kind);
+        let index = index.min(range.len());
+        self.layers.insert(range.start + index, layer);
+        self.shift_stale(kind, IndexShift::Inserted(index));
+        (kind, index)
+    }
+
+    /// Take layer `index` of `kind` out of the pattern
+    ///
+    /// Also returns whether it was stale, so putting it back with
+    /// `insert_layer` can restore that.
+    pub(crate) fn remove_layer(
+        &mut self,
+        kind: LayerKind,
+        index: usize,
+    ) -> Result<(Box<dyn StoredLayer>, bool), SpirographError> {
+        self.check_layer(kind, index)?;
+        let layer = self.layers.remove(self.kind_range(kind).start + index);
+        let was_stale = self.stale_layers.contains(&(kind, index));
+        self.shift_stale(kind, IndexShift::Removed(index));
+        Ok((layer, was_stale))
+    }
+
+    /// Move layer `from` of `kind` to position `to`, shifting the layers in
+    /// between
+    pub(crate) fn move_layer(
+        &mut self,
+        kind: LayerKind,
+        from: usize,
+        to: usize,
+    ) -> Result<(), SpirographError> {
+        self.check_layer(kind, from)?;
+        self.check_layer(kind, to)?;
+        let (layer, was_stale) = self.remove_layer(kind, from)?;
+        self.insert_layer(layer, to);
+        if was_stale {
+            self.mark_stale(kind, to);
+        }
+        Ok(())
+    }
+
+    /// Center of layer `index` of `kind`, if that kind of layer has one
+    pub(crate) fn layer_center(
+        &self,
+        kind: LayerKind,
+        index: usize,
+    ) -> Result<Option<(f64, f64)>, SpirographError> {
+        self.check_layer(kind, index)?;
+        Ok(self
+            .stored_layer(kind, index)
+            .and_then(|layer| layer.center()))
+    }
+
+    /// Move the center of layer `index` of `kind` and mark it stale
+    pub(crate) fn set_layer_center(
+        &mut self,
+        kind: LayerKind,
+        index: usize,
+        x: f64,
+        y: f64,
+    ) -> Result<(), SpirographError> {
+        if self.layer_center(kind, index)?.is_none() {
+            return Err(SpirographError::InvalidParameter(format!(
+                "{} layers have no center",
+                kind.name()
+            )));
+        }
+        if let Some(layer) = self.stored_layer_mut(kind, index) {
+            layer.set_center(x, y);
+        }
+        self.mark_stale(kind, index);
+        Ok(())
+    }
+
+    /// Record that layer `index` of `kind` needs regenerating
+    pub(crate) fn mark_stale(&mut self, kind: LayerKind, index: usize) {
+        if !self.stale_layers.contains(&(kind, index)) {
+            self.stale_layers.push((kind, index));
+        }
+    }
+
+    /// Follow the stale layers of `kind` through an insertion or removal
+    fn shift_stale(&mut self, kind: LayerKind, shift: IndexShift) {
+        self.stale_layers = std::mem::take(&mut self.stale_layers)
+            .into_iter()
+            .filter_map(|(k, i)| {
+                if k == kind {
+                    shift.apply(i).map(|i| (k, i))
+                } else {
+                    Some((k, i))
+                }
+            })
+            .collect();
+    }
+
+    /// Get all spirograph layer points (for rendering)
+    pub fn spirograph_points(&self) -> Vec<Vec<Point2D>> {
+        self.typed_layers::<SpirographLayer>()
+            .map(|layer| layer.points_2d())
+            .collect()
+    }
+
+    /// Every generated line across all layers, spirographs first
+    pub(crate) fn all_lines(&self) -> Vec<Vec<Point2D>> {
+        self.stored_layers()
+            .into_iter()
+            .flat_map(|(_, _, layer)| layer.polylines().iter().cloned())
+            .collect()
+    }
+
+    /// Render this pattern's lines over `other`'s and report what changed
+    ///
+    /// See [`RoseEngineLatheRun::diff_svg`](crate::rose_engine::RoseEngineLatheRun::diff_svg)
+    /// for the colors and matching rules.
+    pub fn diff_svg(
+        &self,
+        other: &Self,
+        filename: &str,
+        tolerance: f64,
+    ) -> Result<DiffSummary, SpirographError> {
+        let (current, baseline) = (self.all_lines(), other.all_lines());
+        let current: Vec<&[Point2D]> = current.iter().map(Vec::as_slice).collect();
+        let baseline: Vec<&[Point2D]> = baseline.iter().map(Vec::as_slice).collect();
+        write_diff_svg(&current, &baseline, filename, tolerance)
+    }
+
+    /// Get all flinqué layer lines (for rendering)
     pub fn flinque_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.flinque_layers.iter().map(|f| f.lines()).collect()
+        self.typed_layers::<FlinqueLayer>()
+            .map(|f| f.lines())
+            .collect()
+    }
+
+    /// Get all diamant layer lines (for rendering)
+    pub fn diamant_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<DiamantLayer>()
+            .map(|d| d.lines())
+            .collect()
+    }
+
+    /// Get all draperie layer lines (for rendering)
+    pub fn draperie_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<DraperieLayer>()
+            .map(|d| d.lines())
+            .collect()
+    }
+
+    /// Get all huit-eight layer lines (for rendering)
+    pub fn huiteight_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<HuitEightLayer>()
+            .map(|h| h.lines())
+            .collect()
+    }
+
+    /// Get all limaçon layer lines (for rendering)
+    pub fn limacon_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<LimaconLayer>()
+            .map(|l| l.lines())
+            .collect()
+    }
+
+    /// Get all paon layer lines (for rendering)
+    pub fn paon_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<PaonLayer>()
+            .map(|p| p.lines())
+            .collect()
+    }
+
+    /// Get all clous de Paris layer lines (for rendering)
+    pub fn clous_de_paris_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<ClousDeParisLayer>()
+            .map(|c| c.lines())
+            .collect()
+    }
+
+    /// The `index`-th clous de Paris layer, if there is one
+    pub(crate) fn clous_de_paris_layer(&self, index: usize) -> Option<&ClousDeParisLayer> {
+        self.typed_layers().nth(index)
+    }
+
+    /// Get all cube layer lines (for rendering)
+    pub fn cube_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<CubeLayer>()
+            .map(|c| c.lines())
+            .collect()
+    }
+
+    /// Get all phyllotaxis layer lines (for rendering)
+    pub fn phyllotaxis_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<PhyllotaxisLayer>()
+            .map(|p| p.lines())
+            .collect()
+    }
+
+    /// Get all grain-de-riz layer lines (for rendering)
+    pub fn grain_de_riz_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<GrainDeRizLayer>()
+            .map(|g| g.lines())
+            .collect()
+    }
+
+    /// Get all soleil layer lines (for rendering)
+    pub fn soleil_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<SoleilLayer>()
+            .map(|s| s.lines())
+            .collect()
     }
 
-    /// Get all diamant layer lines (for rendering)
-    pub fn diamant_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.diamant_layers.iter().map(|d| d.lines()).collect()
+    /// Get all Côtes de Genève layer lines (for rendering)
+    pub fn cotes_de_geneve_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<CotesDeGeneveLayer>()
+            .map(|c| c.lines())
+            .collect()
     }
 
-    /// Get all draperie layer lines (for rendering)
-    pub fn draperie_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.draperie_layers.iter().map(|d| d.lines()).collect()
+    /// Get all tapisserie layer lines (for rendering)
+    pub fn tapisserie_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<TapisserieLayer>()
+            .map(|t| t.lines())
+            .collect()
     }
 
-    /// Get all huit-eight layer lines (for rendering)
-    pub fn huiteight_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.huiteight_layers.iter().map(|h| h.lines()).collect()
+    /// Get all vague layer lines (for rendering)
+    pub fn vague_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<VagueLayer>()
+            .map(|v| v.lines())
+            .collect()
     }
 
-    /// Get all limaçon layer lines (for rendering)
-    pub fn limacon_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.limacon_layers.iter().map(|l| l.lines()).collect()
+    /// Get all engraved text strokes (for rendering)
+    pub fn text_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<EngravedTextLayer>()
+            .map(|t| t.lines())
+            .collect()
     }
 
-    /// Get all paon layer lines (for rendering)
-    pub fn paon_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.paon_layers.iter().map(|p| p.lines()).collect()
+    /// Get all chapter ring lines (for rendering)
+    pub fn chapter_ring_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<ChapterRingLayer>()
+            .map(|c| c.lines())
+            .collect()
     }
 
-    /// Get all clous de Paris layer lines (for rendering)
-    pub fn clous_de_paris_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.clous_de_paris_layers
-            .iter()
-            .map(|c| c.lines())
+    /// Get all index marker lines (for rendering)
+    pub fn index_markers_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<IndexMarkersLayer>()
+            .map(|m| m.lines())
             .collect()
     }
 
-    /// Get all cube layer lines (for rendering)
-    pub fn cube_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.cube_layers.iter().map(|c| c.lines()).collect()
+    /// Get all imported layer lines (for rendering)
+    pub fn imported_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
+        self.typed_layers::<ImportedLayer>()
+            .map(|i| i.lines())
+            .collect()
+    }
+
+    /// Outlines to cut through solid exports: every chapter ring's index
+    /// cutouts, counter-clockwise without repeating the first point
+    pub fn apertures(&self) -> Vec<Vec<Point2D>> {
+        self.typed_layers::<ChapterRingLayer>()
+            .flat_map(|c| c.cutouts())
+            .collect()
     }
 
     /// Export all layers to separate files with the given base name
@@ -501,23 +2018,14 @@ impl GuillochePattern {
         base_name: &str,
         config: &ExportConfig,
     ) -> Result<(), SpirographError> {
-        if self.spirograph_layers.is_empty()
-            && self.flinque_layers.is_empty()
-            && self.diamant_layers.is_empty()
-            && self.draperie_layers.is_empty()
-            && self.huiteight_layers.is_empty()
-            && self.limacon_layers.is_empty()
-            && self.paon_layers.is_empty()
-            && self.clous_de_paris_layers.is_empty()
-            && self.cube_layers.is_empty()
-        {
+        if self.layer_count() == 0 {
             return Err(SpirographError::ExportError(
                 "No layers to export. Add layers first.".to_string(),
             ));
         }
 
         // Export combined SVG
-        self.export_combined_svg(&format!("{}.svg", base_name))?;
+        self.export_combined_svg_with_config(&format!("{}.svg", base_name), config)?;
 
         // Export combined STL
         self.export_combined_stl(&format!("{}.stl", base_name), config)?;
@@ -529,251 +2037,379 @@ impl GuillochePattern {
     }
 
     /// Export combined SVG with all layers
+    ///
+    /// Lines are cut to the [`clip_radius`](Self::clip_radius) when one is set.
+    /// Each layer's paths are grouped in a `<g>` with an id like `flinque-0`
+    /// and class `turtles-layer flinque`; the dial, bezel and center hole
+    /// are in groups with ids `dial`, `bezel` and `holes`.
     pub fn export_combined_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        use ::svg::node::element::path::Data;
-        use ::svg::node::element::{Circle, Path};
-        use ::svg::Document;
+        self.export_combined_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export combined SVG with all layers, `config.origin` applied and
+    /// lines simplified to `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; the pattern is written in its own units.
+    pub fn export_combined_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        if config.simplify_tolerance > 0.0 {
+            let mut simplified = self.clone();
+            simplified.simplify_lines(config.simplify_tolerance);
+            let config = ExportConfig {
+                simplify_tolerance: 0.0,
+                ..config.clone()
+            };
+            return simplified.export_combined_svg_with_config(filename, &config);
+        }
 
         let size = self.radius * 2.5;
-        let mut document = Document::new()
-            .set("viewBox", (-size, -size, size * 2.0, size * 2.0))
-            .set("width", format!("{}mm", size * 2.0))
-            .set("height", format!("{}mm", size * 2.0));
+        let mut writer = SvgWriter::new((-size, -size, size, size), 0.0)
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+        let center = Point2D::new(0.0, 0.0);
 
         // Watch dial circle
-        let dial_circle = Circle::new()
-            .set("cx", 0)
-            .set("cy", 0)
-            .set("r", self.radius)
-            .set("fill", "#fafaf5") // Slightly lighter center
-            .set("stroke", "#2c2c2c")
-            .set("stroke-width", 0.3);
-
-        document = document.add(dial_circle);
-
-        // Guilloche line colors - subtle dark tones that simulate engraved metal
-        // Using varying shades creates depth and visual interest
-        let colors = [
-            "#1a1a1a", // Deep black for primary pattern
-            "#2d2d2d", // Dark gray
-            "#3a3a3a", // Medium-dark gray
-            "#454545", // Medium gray
-            "#505050", // Lighter gray
-            "#5a5a5a", // Light gray for subtle background patterns
-        ];
-
-        // Stroke widths - thinner lines for more delicate guilloche appearance
-        let stroke_widths = [0.04, 0.035, 0.03, 0.03, 0.025, 0.025];
+        let dial_circle = writer.circle(center, self.radius, |circle| {
+            circle
+                .set("fill", "#fafaf5") // Slightly lighter center
+                .set("stroke", "#2c2c2c")
+                .set("stroke-width", 0.3)
+        });
+
+        writer.add(Group::new().set("id", "dial").add(dial_circle));
 
         // Render spirograph layers
-        for (i, layer) in self.spirograph_layers.iter().enumerate() {
+        for (i, layer) in self.typed_layers::<SpirographLayer>().enumerate() {
+            let mut group = self.svg_layer_group(LayerKind::Spirograph, i);
             let points = layer.points_2d();
-            if points.is_empty() {
+            if polyline_is_degenerate(&points) {
+                writer.add(group);
                 continue;
             }
+            // Only close curves that return to their start; closing an open
+            // curve draws a chord across the dial
+            let closed = polyline_closes(&points);
 
-            let mut data = Data::new().move_to((points[0].x, points[0].y));
-            for point in points.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
-            data = data.close();
-
-            let color = colors[i % colors.len()];
-            let stroke_width = stroke_widths[i % stroke_widths.len()];
-            let path = Path::new()
-                .set("fill", "none")
-                .set("stroke", color)
-                .set("stroke-width", stroke_width)
-                .set("stroke-linecap", "round")
-                .set("stroke-linejoin", "round")
-                .set("d", data);
-
-            document = document.add(path);
-        }
-
-        // Render flinqué layers
-        for flinque_layer in &self.flinque_layers {
-            for wave_points in flinque_layer.lines() {
-                if wave_points.is_empty() {
-                    continue;
-                }
-
-                let mut data = Data::new().move_to((wave_points[0].x, wave_points[0].y));
-                for point in wave_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
-
+            let color = SPIROGRAPH_COLORS[i % SPIROGRAPH_COLORS.len()];
+            let stroke_width = SPIROGRAPH_STROKE_WIDTHS[i % SPIROGRAPH_STROKE_WIDTHS.len()];
+            for (piece, closed) in clip_pieces(self.clip_shape(), points, closed) {
                 let path = Path::new()
                     .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
+                    .set("stroke", color)
+                    .set("stroke-width", stroke_width)
                     .set("stroke-linecap", "round")
                     .set("stroke-linejoin", "round")
-                    .set("d", data);
+                    .set("d", writer.path_data(&piece, closed));
 
-                document = document.add(path);
+                group = group.add(path);
             }
+            writer.add(group);
         }
 
-        // Render diamant layers
-        for diamant_layer in &self.diamant_layers {
-            for circle_points in diamant_layer.lines() {
-                if circle_points.is_empty() {
-                    continue;
-                }
-
-                let mut data = Data::new().move_to((circle_points[0].x, circle_points[0].y));
-                for point in circle_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
+        // Render every other layer kind, in drawing order
+        for (kind, layers, stroke_width) in self.layer_line_sets() {
+            for (index, lines) in layers.into_iter().enumerate() {
+                let mut group = self.svg_layer_group(kind, index);
+                for line_points in self.clipped_lines(lines).iter() {
+                    if polyline_is_degenerate(line_points) {
+                        continue;
+                    }
+
+                    let path = writer.polyline_node(line_points, false, |path| {
+                        path.set("fill", "none")
+                            .set("stroke", "#1a1a1a")
+                            .set("stroke-width", stroke_width)
+                            .set("stroke-linecap", "round")
+                            .set("stroke-linejoin", "round")
+                    });
+
+                    group = group.add(path);
                 }
-
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
-
-                document = document.add(path);
+                writer.add(group);
             }
         }
 
-        // Render huit-eight layers
-        for huiteight_layer in &self.huiteight_layers {
-            for curve_points in huiteight_layer.lines() {
-                if curve_points.is_empty() {
-                    continue;
-                }
+        // Add outer bezel ring
+        let bezel = writer.circle(center, self.radius * 1.05, |circle| {
+            circle
+                .set("fill", "none")
+                .set("stroke", "#1a1a1a")
+                .set("stroke-width", 0.8)
+        });
 
-                let mut data = Data::new().move_to((curve_points[0].x, curve_points[0].y));
-                for point in curve_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
+        writer.add(Group::new().set("id", "bezel").add(bezel));
 
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+        // Add center pinhole for watch hands
+        let center_hole = writer.circle(center, 0.8, |circle| circle.set("fill", "#1a1a1a"));
 
-                document = document.add(path);
-            }
-        }
+        writer.add(Group::new().set("id", "holes").add(center_hole));
 
-        // Render draperie layers
-        for draperie_layer in &self.draperie_layers {
-            for ring_points in draperie_layer.lines() {
-                if ring_points.is_empty() {
-                    continue;
-                }
+        writer.save(filename)
+    }
 
-                let mut data = Data::new().move_to((ring_points[0].x, ring_points[0].y));
-                for point in ring_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
+    /// Export every layer to HPGL for a pen plotter, all with pen 1
+    ///
+    /// See [`to_hpgl_with_pens`](Self::to_hpgl_with_pens).
+    pub fn to_hpgl(&self, filename: &str, scale: f64) -> Result<(), SpirographError> {
+        self.to_hpgl_with_pens(filename, scale, &[])
+    }
 
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+    /// Export every layer to HPGL, plotting layer `i` with `pens[i]`
+    ///
+    /// Layers are numbered in combined SVG order: spirographs, then each
+    /// other kind in turn. Layers past the end of `pens` use its last pen,
+    /// or pen 1 when it is empty. Lines are cut to the
+    /// [`clip_radius`](Self::clip_radius) when one is set, and lengths
+    /// are taken as mm and multiplied by `scale`; see
+    /// [`hpgl_commands`](crate::hpgl::hpgl_commands).
+    pub fn to_hpgl_with_pens(
+        &self,
+        filename: &str,
+        scale: f64,
+        pens: &[u32],
+    ) -> Result<(), SpirographError> {
+        let (lines, line_pens) = pens_by_layer(self.plot_layers(self.clip_shape()), pens);
+        write_hpgl(filename, &lines, &line_pens, scale)
+    }
 
-                document = document.add(path);
+    /// Lines of every layer in combined SVG order, cut to `clip`
+    pub(crate) fn plot_layers(&self, clip: Option<DialShape>) -> Vec<Vec<Vec<Point2D>>> {
+        let spirographs = self.typed_layers::<SpirographLayer>().map(|layer| {
+            let points = layer.points_2d();
+            if polyline_is_degenerate(&points) {
+                return Vec::new();
             }
-        }
-
-        // Render paon layers
-        for paon_layer in &self.paon_layers {
-            for line_points in paon_layer.lines() {
-                if line_points.is_empty() {
-                    continue;
-                }
+            let closed = polyline_closes(&points);
+            clip_pieces(clip, points, closed)
+                .into_iter()
+                .map(|(piece, _)| piece)
+                .collect()
+        });
+        let others = self
+            .layer_line_sets()
+            .into_iter()
+            .flat_map(|(_, layers, _)| layers)
+            .map(|lines| match clip {
+                Some(shape) => lines
+                    .iter()
+                    .flat_map(|line| shape.clip_polyline(line, false))
+                    .collect(),
+                None => lines.to_vec(),
+            });
+        spirographs.chain(others).collect()
+    }
 
-                let mut data = Data::new().move_to((line_points[0].x, line_points[0].y));
-                for point in line_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
+    /// Rasterize the combined SVG to an RGBA preview
+    ///
+    /// Draws the same lines as `export_combined_svg`, with its colors and
+    /// stroke widths, over the same view fitted into `width_px × height_px`
+    /// on a white background. The dial and center hole are outlined and
+    /// dotted rather than filled.
+    pub fn render_raster(
+        &self,
+        width_px: u32,
+        height_px: u32,
+    ) -> Result<RgbaImage, SpirographError> {
+        let size = self.radius * 2.5;
+        let view_box = (-size, -size, size * 2.0, size * 2.0);
+        render_preview(self.preview_strokes(), view_box, width_px, height_px)
+    }
 
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+    /// Write the [`render_raster`](Self::render_raster) preview to a PNG file
+    pub fn to_png(
+        &self,
+        filename: &str,
+        width_px: u32,
+        height_px: u32,
+    ) -> Result<(), SpirographError> {
+        self.render_raster(width_px, height_px)?.write_png(filename)
+    }
 
-                document = document.add(path);
+    /// Lines of every non-spirograph layer grouped by kind, with the
+    /// combined SVG stroke width, in drawing order
+    fn layer_line_sets(&self) -> Vec<(LayerKind, KindLines<'_>, f64)> {
+        let mut sets: Vec<(LayerKind, KindLines<'_>, f64)> = Vec::new();
+        for (kind, _, layer) in self.stored_layers() {
+            if kind == LayerKind::Spirograph {
+                continue;
+            }
+            match sets.last_mut() {
+                Some((last, lines, _)) if *last == kind => lines.push(layer.polylines()),
+                _ => sets.push((kind, vec![layer.polylines()], layer.stroke_width())),
             }
         }
+        sets
+    }
 
-        // Add outer bezel ring
-        let bezel = Circle::new()
-            .set("cx", 0)
-            .set("cy", 0)
-            .set("r", self.radius * 1.05)
-            .set("fill", "none")
-            .set("stroke", "#1a1a1a")
-            .set("stroke-width", 0.8);
-
-        document = document.add(bezel);
+    /// Every stroke of the combined SVG, in drawing order
+    fn preview_strokes(&self) -> Vec<PreviewStroke<'_>> {
+        let outline = |radius: f64, width: f64, color: &str| PreviewStroke {
+            points: Cow::Owned(DialShape::Circle { radius }.outline(360)),
+            closed: true,
+            width,
+            color: svg_color_rgb(color),
+            opacity: 1.0,
+        };
+        let mut strokes = vec![outline(self.radius, 0.3, "#2c2c2c")];
+
+        for (i, layer) in self.typed_layers::<SpirographLayer>().enumerate() {
+            let points = layer.points_2d();
+            if polyline_is_degenerate(&points) {
+                continue;
+            }
+            let closed = polyline_closes(&points);
+            let color = svg_color_rgb(SPIROGRAPH_COLORS[i % SPIROGRAPH_COLORS.len()]);
+            let width = SPIROGRAPH_STROKE_WIDTHS[i % SPIROGRAPH_STROKE_WIDTHS.len()];
+            for (piece, closed) in clip_pieces(self.clip_shape(), points, closed) {
+                strokes.push(PreviewStroke {
+                    points: Cow::Owned(piece),
+                    closed,
+                    width,
+                    color,
+                    opacity: 1.0,
+                });
+            }
+        }
 
-        // Add center pinhole for watch hands
-        let center_hole = Circle::new()
-            .set("cx", 0)
-            .set("cy", 0)
-            .set("r", 0.8)
-            .set("fill", "#1a1a1a");
+        for (_, layers, width) in self.layer_line_sets() {
+            for lines in layers {
+                let pieces: Vec<Cow<'_, [Point2D]>> = match self.clipped_lines(lines) {
+                    Cow::Borrowed(lines) => lines.iter().map(|l| Cow::Borrowed(&l[..])).collect(),
+                    Cow::Owned(lines) => lines.into_iter().map(Cow::Owned).collect(),
+                };
+                strokes.extend(
+                    pieces
+                        .into_iter()
+                        .filter(|points| !polyline_is_degenerate(points))
+                        .map(|points| PreviewStroke {
+                            points,
+                            closed: false,
+                            width,
+                            color: svg_color_rgb("#1a1a1a"),
+                            opacity: 1.0,
+                        }),
+                );
+            }
+        }
 
-        document = document.add(center_hole);
+        strokes.push(outline(self.radius * 1.05, 0.8, "#1a1a1a"));
+        strokes.push(PreviewStroke {
+            points: Cow::Owned(vec![Point2D::new(0.0, 0.0)]),
+            closed: false,
+            width: 1.6,
+            color: svg_color_rgb("#1a1a1a"),
+            opacity: 1.0,
+        });
+        strokes
+    }
 
-        ::svg::save(filename, &document)
-            .map_err(|e| SpirographError::ExportError(format!("SVG export failed: {}", e)))
+    /// Polylines for the 3D exports, each flagged if closed
+    ///
+    /// Covers the spirograph curves, the engraved text strokes, the
+    /// chapter rings, the index markers and the imported polylines. With a
+    /// clip shape the curves are cut exactly at its outline; see
+    /// [`clip_pieces`].
+    pub(crate) fn export_polylines(&self, clip: Option<DialShape>) -> Vec<(Vec<Point2D>, bool)> {
+        let spirographs = self.typed_layers::<SpirographLayer>().map(|layer| {
+            let points = layer.points_2d();
+            let closed = polyline_closes(&points);
+            (points, closed)
+        });
+        let strokes = self
+            .stored_layers()
+            .into_iter()
+            .filter(|(kind, _, _)| {
+                matches!(
+                    kind,
+                    LayerKind::Text
+                        | LayerKind::ChapterRing
+                        | LayerKind::IndexMarkers
+                        | LayerKind::Imported
+                )
+            })
+            .flat_map(|(_, _, layer)| layer.polylines().iter())
+            .map(|stroke| (stroke.clone(), false));
+
+        let mut polylines = Vec::new();
+        for (points, closed) in spirographs.chain(strokes) {
+            if polyline_is_degenerate(&points) {
+                continue;
+            }
+            polylines.extend(clip_pieces(clip, points, closed));
+        }
+        polylines
     }
 
     /// Export combined STL with all layers
+    ///
+    /// Every line becomes a closed groove solid `config.depth` deep (see
+    /// `ExportConfig::tool_radius`) over the base slab. Lines are cut to
+    /// the [`clip_radius`](Self::clip_radius) when one is set.
     pub fn export_combined_stl(
         &self,
         filename: &str,
         config: &ExportConfig,
     ) -> Result<(), SpirographError> {
-        use stl_io::{Normal, Triangle, Vertex};
+        self.write_combined_stl(filename, config, self.clip_shape(), &[], &[], Vec::new())
+    }
 
-        let mut all_triangles = Vec::new();
+    /// Write the combined STL, optionally clipping to a dial outline
+    ///
+    /// The base slab follows the outline, or a circle of the pattern's
+    /// radius without one. The [`apertures`](Self::apertures) and any
+    /// `extra_apertures` (convex outlines, counter-clockwise) are cut
+    /// through it, and the grooves are cut back to their edges. Grooves
+    /// stop half their width inside the outline, so their sides do too.
+    ///
+    /// The grooves are also cut back to the convex `masks`, which leave the
+    /// slab whole. `extra_polylines`, each flagged if closed, are cut as
+    /// given, less the apertures.
+    pub(crate) fn write_combined_stl(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+        clip: Option<DialShape>,
+        extra_apertures: &[Vec<Point2D>],
+        masks: &[Vec<Point2D>],
+        extra_polylines: Vec<(Vec<Point2D>, bool)>,
+    ) -> Result<(), SpirographError> {
         let depth = config.depth;
-
-        for layer in &self.spirograph_layers {
-            let points = layer.points_2d();
-            if points.is_empty() {
-                continue;
-            }
-
-            let num_points = points.len();
-            for i in 0..num_points {
-                let p1 = points[i];
-                let p2 = points[(i + 1) % num_points];
-
-                let v1_top = Vertex::new([p1.x as f32, p1.y as f32, 0.0]);
-                let v2_top = Vertex::new([p2.x as f32, p2.y as f32, 0.0]);
-                let v1_bottom = Vertex::new([p1.x as f32, p1.y as f32, -depth as f32]);
-                let v2_bottom = Vertex::new([p2.x as f32, p2.y as f32, -depth as f32]);
-
-                let normal = Normal::new([0.0, 0.0, 1.0]);
-
-                all_triangles.push(Triangle {
-                    normal,
-                    vertices: [v1_top, v2_top, v1_bottom],
-                });
-                all_triangles.push(Triangle {
-                    normal,
-                    vertices: [v2_top, v2_bottom, v1_bottom],
-                });
-            }
-        }
+        let profile = config.groove_profile()?;
+        let mut apertures = self.apertures();
+        apertures.extend_from_slice(extra_apertures);
+        let mut polylines = cut_outside_convex(
+            self.export_polylines(clip.map(|shape| shape.inset(profile.width() / 2.0))),
+            masks,
+        );
+        polylines.extend(extra_polylines);
+        let polylines = cut_outside_convex(polylines, &apertures);
+
+        let mut all_triangles = flat_groove_triangles(
+            polylines
+                .iter()
+                .map(|(points, closed)| (points.as_slice(), *closed)),
+            &profile,
+        );
+
+        // Base slab under the dial, covering anything drawn past its edge
+        let outline = clip.unwrap_or(DialShape::Circle {
+            radius: self.radius,
+        });
+        all_triangles.extend(
+            outline.slab_triangles(
+                polylines
+                    .iter()
+                    .flat_map(|(points, _)| points.iter().copied()),
+                -depth,
+                config.base_thickness,
+                &apertures,
+            ),
+        );
+        config.origin.validate()?;
+        config.origin.apply_stl(&mut all_triangles);
 
         let mut file = std::fs::File::create(filename)
             .map_err(|e| SpirographError::ExportError(format!("Failed to create file: {}", e)))?;
@@ -782,44 +2418,123 @@ impl GuillochePattern {
     }
 
     /// Export combined STEP with all layers
+    ///
+    /// Lines are cut to the [`clip_radius`](Self::clip_radius) when one is set.
+    /// See [`write_combined_step`](Self::write_combined_step) for the layout.
     pub fn export_combined_step(
         &self,
         filename: &str,
-        _config: &ExportConfig,
+        config: &ExportConfig,
     ) -> Result<(), SpirographError> {
-        let mut content = String::new();
-
-        let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+        self.write_combined_step(
+            filename,
+            config,
+            self.clip_shape(),
+            None,
+            "guilloche",
+            Unit::default(),
+        )
+    }
 
-        content.push_str("ISO-10303-21;\n");
-        content.push_str("HEADER;\n");
-        content.push_str("FILE_DESCRIPTION(('Guilloche Pattern - Multiple Layers'),'2;1');\n");
-        content.push_str(&format!(
-            "FILE_NAME('guilloche.stp','{}',(''),(''),'','','');\n",
-            timestamp
-        ));
-        content.push_str("FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));\n");
-        content.push_str("ENDSEC;\n");
-        content.push_str("DATA;\n");
+    /// Write the combined STEP, optionally clipping to a dial outline and
+    /// filling the header from `metadata`
+    ///
+    /// The file is an assembly named `name` with a wireframe part per
+    /// layer, named like the SVG layer groups (`flinque-0`), and a `dial`
+    /// part with the clip outline, or the pattern's circle without one, on
+    /// its plane. Layers left without lines after clipping are left out.
+    /// Lengths are declared in `unit`; the coordinates are not scaled.
+    pub(crate) fn write_combined_step(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+        clip: Option<DialShape>,
+        metadata: Option<&FileMetadata>,
+        name: &str,
+        unit: Unit,
+    ) -> Result<(), SpirographError> {
+        let dial = clip.unwrap_or(DialShape::Circle {
+            radius: self.radius,
+        });
+        let parts = self.step_layer_parts(clip);
+        write_step_parts(filename, config, dial, parts, metadata, name, unit)
+    }
 
-        let mut point_id = 1;
-        for layer in &self.spirograph_layers {
+    /// A STEP part per layer, named like the SVG layer groups, with its
+    /// lines cut to `clip`
+    pub(crate) fn step_layer_parts(&self, clip: Option<DialShape>) -> Vec<StepPart> {
+        let mut parts = Vec::new();
+        for (index, layer) in self.typed_layers::<SpirographLayer>().enumerate() {
             let points = layer.points_2d();
-            for point in points {
-                content.push_str(&format!(
-                    "#{}=CARTESIAN_POINT('',({}.,{}.,0.));\n",
-                    point_id, point.x, point.y
-                ));
-                point_id += 1;
+            let closed = polyline_closes(&points);
+            parts.push(StepPart {
+                name: format!("{}-{}", LayerKind::Spirograph.name(), index),
+                lines: clip_pieces(clip, points, closed),
+            });
+        }
+        for (kind, layers, _) in self.layer_line_sets() {
+            for (index, lines) in layers.into_iter().enumerate() {
+                parts.push(StepPart {
+                    name: format!("{}-{}", kind.name(), index),
+                    lines: lines
+                        .iter()
+                        .flat_map(|line| clip_pieces(clip, line.clone(), false))
+                        .collect(),
+                });
             }
         }
+        parts
+    }
+}
 
-        content.push_str("ENDSEC;\n");
-        content.push_str("END-ISO-10303-21;\n");
+/// Write `parts` as a STEP assembly named `name` with a `dial` part for
+/// the `dial` outline, placed by `config.origin`
+///
+/// Parts left without lines are left out. Lengths are declared in `unit`;
+/// the coordinates are not scaled.
+pub(crate) fn write_step_parts(
+    filename: &str,
+    config: &ExportConfig,
+    dial: DialShape,
+    mut parts: Vec<StepPart>,
+    metadata: Option<&FileMetadata>,
+    name: &str,
+    unit: Unit,
+) -> Result<(), SpirographError> {
+    config.origin.validate()?;
+    for part in &mut parts {
+        part.lines
+            .retain(|(points, _)| !polyline_is_degenerate(points));
+    }
 
-        std::fs::write(filename, content)
-            .map_err(|e| SpirographError::ExportError(format!("Failed to write STEP file: {}", e)))
+    // The extent covers the dial as well as the lines
+    let (rx, ry) = dial.semi_axes();
+    let dial_box = [Point2D::new(-rx, -ry), Point2D::new(rx, ry)];
+    let bounds = polyline_bounds(
+        std::iter::once(&dial_box[..]).chain(
+            parts
+                .iter()
+                .flat_map(|part| part.lines.iter().map(|(points, _)| points.as_slice())),
+        ),
+    )
+    .unwrap_or_default();
+    for part in &mut parts {
+        for (points, _) in &mut part.lines {
+            for point in points.iter_mut() {
+                *point = config.origin.apply(bounds, *point);
+            }
+        }
     }
+    let center = config.origin.apply(bounds, Point2D::new(0.0, 0.0));
+
+    let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+    let header = step_header(
+        metadata,
+        "Guilloche Pattern - Multiple Layers",
+        "guilloche.stp",
+        &timestamp,
+    );
+    write_step_assembly(filename, &header, name, unit, Some((center, dial)), &parts)
 }
 
 #[cfg(test)]
@@ -835,6 +2550,140 @@ mod tests {
         assert!(pattern_bad.is_err());
     }
 
+    #[test]
+    fn test_layer_transforms_mirror_rotate_and_replicate() {
+        use std::f64::consts::PI;
+
+        let mut spiro =
+            HorizontalSpirograph::new_with_center(30.0, 0.75, 0.6, 3, 90, 15.0, 4.0).unwrap();
+        assert!(matches!(
+            spiro.mirrored_x(),
+            Err(SpirographError::ExportError(_))
+        ));
+        spiro.generate();
+        let original = spiro.polylines().to_vec();
+        let close = |a: &[Vec<Point2D>], b: &[Vec<Point2D>], tolerance: f64| {
+            a.len() == b.len()
+                && a.iter().zip(b).all(|(a, b)| {
+                    a.len() == b.len()
+                        && a.iter().zip(b).all(|(p, q)| {
+                            (p.x - q.x).abs() < tolerance && (p.y - q.y).abs() < tolerance
+                        })
+                })
+        };
+
+        // Mirroring twice, either way, is the identity
+        let twice_x = ImportedLayer::new(spiro.mirrored_x().unwrap())
+            .mirrored_x()
+            .unwrap();
+        let twice_y = ImportedLayer::new(spiro.mirrored_y().unwrap())
+            .mirrored_y()
+            .unwrap();
+        assert!(close(&twice_x, &original, 1e-12));
+        assert!(close(&twice_y, &original, 1e-12));
+        let mirrored = spiro.mirrored_x().unwrap();
+        assert_eq!(mirrored[0][0].x, -original[0][0].x);
+
+        // A half turn is both mirrors
+        let turned = spiro.rotated(PI).unwrap();
+        let both = ImportedLayer::new(mirrored).mirrored_y().unwrap();
+        assert!(close(&turned, &both, 1e-9));
+        assert!(spiro.rotated(f64::NAN).is_err());
+
+        // The first copy's bounds are centered at angle 0 on the circle, and
+        // the others are it turned by thirds
+        let copies = spiro.replicated_radial(3, 20.0).unwrap();
+        assert_eq!(copies.len(), 3);
+        let (min_x, min_y, max_x, max_y) =
+            polyline_bounds(copies[0].iter().map(Vec::as_slice)).unwrap();
+        assert!(((min_x + max_x) / 2.0 - 20.0).abs() < 1e-9);
+        assert!(((min_y + max_y) / 2.0).abs() < 1e-9);
+        for (k, copy) in copies.iter().enumerate() {
+            let turned = ImportedLayer::new(copies[0].clone())
+                .rotated(2.0 * PI * k as f64 / 3.0)
+                .unwrap();
+            assert!(close(copy, &turned, 1e-9));
+        }
+        assert!(spiro.replicated_radial(0, 20.0).is_err());
+        assert!(spiro.replicated_radial(3, -1.0).is_err());
+
+        let mut pattern = GuillochePattern::new(38.0).unwrap();
+        for copy in copies {
+            pattern.add_raw_lines(copy);
+        }
+        assert_eq!(pattern.kind_count(LayerKind::Imported), 3);
+        pattern.generate();
+        assert_eq!(pattern.layers()[0].2.polylines().len(), 1);
+    }
+
+    #[test]
+    fn test_radius_policy_admits_pocket_watch_dials() {
+        assert!(GuillochePattern::new(60.0).is_err());
+        assert!(GuillochePattern::new_with_policy(60.0, RadiusPolicy::Watch).is_err());
+        let mut pattern =
+            GuillochePattern::new_with_policy(60.0, RadiusPolicy::Unrestricted).unwrap();
+        assert_eq!(pattern.radius, 60.0);
+
+        let jewelry = RadiusPolicy::Custom {
+            min: 10.0,
+            max: 20.0,
+        };
+        assert!(GuillochePattern::new_with_policy(15.0, jewelry).is_ok());
+        assert!(GuillochePattern::new_with_policy(25.0, jewelry).is_err());
+        let backwards = RadiusPolicy::Custom {
+            min: 20.0,
+            max: 10.0,
+        };
+        assert!(matches!(
+            GuillochePattern::new_with_policy(15.0, backwards),
+            Err(SpirographError::InvalidParameter(_))
+        ));
+
+        // A spirograph built under the same policy validates in the pattern
+        assert!(HorizontalSpirograph::new(55.0, 0.75, 0.6, 3, 120).is_err());
+        let spiro = HorizontalSpirograph::new_with_policy(
+            55.0,
+            0.75,
+            0.6,
+            3,
+            120,
+            RadiusPolicy::Unrestricted,
+        )
+        .unwrap();
+        pattern.add_horizontal_layer(spiro);
+        assert!(pattern.validate().is_empty());
+        pattern.try_generate().unwrap();
+    }
+
+    #[test]
+    fn test_combined_stl_base_slab_covers_dial() {
+        let mut pattern = GuillochePattern::new(38.0).unwrap();
+        pattern.add_horizontal_layer(HorizontalSpirograph::new(38.0, 0.75, 0.6, 3, 120).unwrap());
+        pattern.generate();
+        let path = std::env::temp_dir().join("test_guilloche_base_slab.stl");
+        let config = ExportConfig {
+            depth: 0.1,
+            base_thickness: 1.0,
+            ..ExportConfig::default()
+        };
+        pattern
+            .export_combined_stl(path.to_str().unwrap(), &config)
+            .unwrap();
+        let mut file = std::fs::File::open(&path).unwrap();
+        let mesh = stl_io::read_stl(&mut file).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let z = mesh.vertices.iter().map(|v| v[2] as f64);
+        let extent = z.clone().fold(f64::NEG_INFINITY, f64::max) - z.fold(f64::INFINITY, f64::min);
+        assert!((extent - 1.1).abs() < 1e-6);
+        let widest = mesh
+            .vertices
+            .iter()
+            .map(|v| (v[0] as f64).hypot(v[1] as f64))
+            .fold(0.0, f64::max);
+        assert!(widest >= 38.0);
+    }
+
     #[test]
     fn test_add_layers() {
         let mut pattern = GuillochePattern::new(38.0).unwrap();
@@ -848,6 +2697,77 @@ mod tests {
         assert_eq!(pattern.layer_count(), 2);
     }
 
+    #[test]
+    fn test_layers_view_covers_every_kind_in_storage_order() {
+        let mut pattern = GuillochePattern::new(38.0).unwrap();
+        pattern.add_flinque_layer(FlinqueLayer::new(10.0, FlinqueConfig::default()).unwrap());
+        pattern.add_horizontal_layer(HorizontalSpirograph::new(38.0, 0.75, 0.6, 10, 90).unwrap());
+        pattern.add_imported_layer(vec![vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 2.0)]]);
+        pattern.add_flinque_layer(FlinqueLayer::new(5.0, FlinqueConfig::default()).unwrap());
+
+        let kinds: Vec<(LayerKind, usize)> = pattern
+            .layers()
+            .iter()
+            .map(|&(kind, index, _)| (kind, index))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (LayerKind::Spirograph, 0),
+                (LayerKind::Flinque, 0),
+                (LayerKind::Flinque, 1),
+                (LayerKind::Imported, 0),
+            ]
+        );
+        assert_eq!(pattern.layers().len(), pattern.layer_count());
+        assert!(pattern
+            .layers()
+            .iter()
+            .all(|(_, _, l)| l.validate().is_ok()));
+
+        pattern.generate();
+        for (kind, index, layer) in pattern.layers() {
+            let (min, max) = layer.bounds().unwrap();
+            assert!(min.x <= max.x && min.y <= max.y, "{:?} {}", kind, index);
+        }
+        let flinque = pattern.kind_lines(LayerKind::Flinque);
+        assert_eq!(flinque.len(), 2);
+        assert_eq!(flinque[1], pattern.flinque_lines()[1].as_slice());
+        assert!(pattern.kind_lines(LayerKind::LathePass).is_empty());
+    }
+
+    #[test]
+    fn test_json_keeps_a_list_per_kind() {
+        let mut pattern = GuillochePattern::new(38.0).unwrap();
+        pattern.add_imported_layer(vec![vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 2.0)]]);
+        pattern.add_flinque_layer(FlinqueLayer::new(10.0, FlinqueConfig::default()).unwrap());
+        pattern.add_flinque_layer(FlinqueLayer::new(5.0, FlinqueConfig::default()).unwrap());
+        pattern.add_horizontal_layer(HorizontalSpirograph::new(38.0, 0.75, 0.6, 10, 90).unwrap());
+
+        let value = serde_json::to_value(&pattern).unwrap();
+        assert_eq!(value["flinque_layers"].as_array().unwrap().len(), 2);
+        assert_eq!(value["imported_layers"].as_array().unwrap().len(), 1);
+        assert!(value.get("diamant_layers").is_none());
+
+        // The value's keys are sorted, putting `spirograph_layers` last;
+        // layers come back grouped in kind order all the same
+        let copy: GuillochePattern = serde_json::from_value(value).unwrap();
+        let kinds: Vec<LayerKind> = copy.layers().iter().map(|&(kind, _, _)| kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                LayerKind::Spirograph,
+                LayerKind::Flinque,
+                LayerKind::Flinque,
+                LayerKind::Imported,
+            ]
+        );
+        assert_eq!(
+            copy.typed_layers::<FlinqueLayer>().nth(1).unwrap().radius,
+            5.0
+        );
+    }
+
     #[test]
     fn test_generate_pattern() {
         let mut pattern = GuillochePattern::new(38.0).unwrap();
@@ -860,4 +2780,454 @@ mod tests {
         // Verify points were generated
         assert_eq!(pattern.layer_count(), 1);
     }
+
+    #[test]
+    fn test_open_spirograph_svg_has_no_closing_chord() {
+        // radius_ratio 0.77 → the rolling circle turns (1 − ρ)/ρ ≈ 0.3 extra
+        // times per revolution, so one revolution is a 1.3-rotation
+        // hypotrochoid that does not return to its start.
+        let mut pattern = GuillochePattern::new(38.0).unwrap();
+        pattern.add_horizontal_layer(HorizontalSpirograph::new(38.0, 0.77, 5.0, 1, 360).unwrap());
+        pattern.generate();
+        assert!(!polyline_closes(&pattern.spirograph_points()[0]));
+
+        let path = std::env::temp_dir().join("test_guilloche_open_spirograph.svg");
+        pattern.export_combined_svg(path.to_str().unwrap()).unwrap();
+        let out = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(out.contains("<path"));
+        // No path data closes (the bezel group's id has the only z)
+        let data: Vec<&str> = out.split(" d=\"").skip(1).collect();
+        assert!(!data.is_empty());
+        assert!(data
+            .iter()
+            .all(|d| !d[..d.find('"').unwrap()].contains(['z', 'Z'])));
+    }
+
+    #[test]
+    fn test_combined_svg_groups_each_layer() {
+        use crate::common::tests::svg_groups;
+
+        let mut pattern = GuillochePattern::new(38.0).unwrap();
+        pattern.add_horizontal_layer(HorizontalSpirograph::new(38.0, 0.75, 5.0, 3, 120).unwrap());
+        for radius in [20.0, 30.0] {
+            let flinque = FlinqueLayer::new(radius, FlinqueConfig::default()).unwrap();
+            pattern.add_flinque_layer(flinque);
+        }
+        pattern
+            .add_chapter_ring(ChapterRingConfig::new(32.0, 34.0))
+            .unwrap();
+        pattern.generate();
+
+        let path = std::env::temp_dir().join("test_guilloche_svg_groups.svg");
+        let file = path.to_str().unwrap();
+        pattern.export_combined_svg(file).unwrap();
+        let plain = std::fs::read_to_string(&path).unwrap();
+        pattern.set_svg_layer_data(true);
+        pattern.export_combined_svg(file).unwrap();
+        let annotated = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let groups = svg_groups(&plain);
+        let ids: Vec<&str> = groups.iter().map(|(_, g)| g["id"].as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "dial",
+                "spirograph-0",
+                "flinque-0",
+                "flinque-1",
+                "chapter_ring-0",
+                "bezel",
+                "holes"
+            ]
+        );
+        assert!(groups.iter().all(|(depth, _)| *depth == 0));
+        assert_eq!(groups[3].1["class"], "turtles-layer flinque");
+        assert!(groups.iter().all(|(_, g)| !g.contains_key("data-config")));
+        // Every layer's paths are inside its group
+        let ring_paths = plain.split("id=\"chapter_ring-0\"").nth(1).unwrap();
+        let ring_paths = &ring_paths[..ring_paths.find("</g>").unwrap()];
+        assert_eq!(ring_paths.matches("<path").count(), 2 + 60);
+
+        // The data attributes change nothing drawn
+        let paths = |svg: &str| -> Vec<String> {
+            svg.split("<path")
+                .skip(1)
+                .map(|p| p[..p.find("/>").unwrap()].to_string())
+                .collect()
+        };
+        assert_eq!(paths(&plain), paths(&annotated));
+        let groups = svg_groups(&annotated);
+        let flinque = &groups[3].1;
+        assert!(flinque["data-config"].starts_with("FlinqueConfig {"));
+        assert_eq!(flinque["data-center"], "0,0");
+        let stats = pattern
+            .typed_layers::<FlinqueLayer>()
+            .nth(1)
+            .unwrap()
+            .stats()
+            .unwrap();
+        assert_eq!(flinque["data-lines"], stats.num_polylines.to_string());
+        assert_eq!(flinque["data-points"], stats.num_points.to_string());
+        assert!(groups[1].1["data-config"].starts_with("Horizontal { outer_radius: 38.0"));
+        assert!(!groups[4].1.contains_key("data-center"));
+        assert!(!groups[0].1.contains_key("data-config"));
+    }
+
+    #[test]
+    fn test_combined_svg_simplify_tolerance() {
+        let mut pattern = GuillochePattern::new(38.0).unwrap();
+        pattern.add_horizontal_layer(HorizontalSpirograph::new(38.0, 0.75, 5.0, 3, 360).unwrap());
+        let flinque = FlinqueLayer::new(30.0, FlinqueConfig::default()).unwrap();
+        pattern.add_flinque_layer(flinque);
+        pattern.generate();
+
+        let path = std::env::temp_dir().join("test_guilloche_simplified.svg");
+        let file = path.to_str().unwrap();
+        let export = |tolerance: f64| {
+            let config = ExportConfig {
+                simplify_tolerance: tolerance,
+                ..ExportConfig::default()
+            };
+            pattern
+                .export_combined_svg_with_config(file, &config)
+                .unwrap();
+            std::fs::read_to_string(&path).unwrap()
+        };
+        pattern.export_combined_svg(file).unwrap();
+        let plain = std::fs::read_to_string(&path).unwrap();
+        let unsimplified = export(0.0);
+        let simplified = export(0.05);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(unsimplified, plain);
+        assert!(simplified.len() < plain.len());
+        assert_eq!(
+            simplified.matches("<path").count(),
+            plain.matches("<path").count()
+        );
+    }
+
+    #[test]
+    fn test_hpgl_pens_by_layer_and_clip() {
+        let mut pattern = GuillochePattern::new(30.0).unwrap();
+        let segment = |y: f64| vec![vec![Point2D::new(-20.0, y), Point2D::new(1.0, y)]];
+        pattern.add_imported_layer(segment(0.0));
+        pattern.add_imported_layer(segment(0.5));
+        pattern.add_imported_layer(segment(-0.5));
+        pattern.clip_to_radius(5.0).unwrap();
+        pattern.generate();
+
+        let path = std::env::temp_dir().join("test_guilloche.hpgl");
+        let file = path.to_str().unwrap();
+        pattern.to_hpgl_with_pens(file, 2.0, &[2, 5]).unwrap();
+        let hpgl = std::fs::read_to_string(&path).unwrap();
+        pattern.to_hpgl(file, 1.0).unwrap();
+        let single = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        // Cut at x = -5 (or just inside it off the axis), scaled by 2 and
+        // 40 units/mm
+        assert!(hpgl.starts_with("IN;PA;SP2;PU-400,0;PD80,0;SP5;PU-"));
+        assert!(hpgl.ends_with(",-40;PD80,-40;PU;SP0;\n"));
+        assert_eq!(hpgl.matches("SP").count(), 3);
+        assert!(single.starts_with("IN;PA;SP1;PU-200,0;PD40,0;PU-"));
+    }
+
+    #[test]
+    fn test_closed_spirograph_svg_is_closed() {
+        // radius_ratio 0.75 → 1/3 extra turn per revolution, closed after 3
+        let mut pattern = GuillochePattern::new(38.0).unwrap();
+        pattern.add_horizontal_layer(HorizontalSpirograph::new(38.0, 0.75, 5.0, 3, 360).unwrap());
+        pattern.generate();
+        assert!(polyline_closes(&pattern.spirograph_points()[0]));
+
+        let path = std::env::temp_dir().join("test_guilloche_closed_spirograph.svg");
+        pattern.export_combined_svg(path.to_str().unwrap()).unwrap();
+        let out = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(out.contains('z'));
+    }
+
+    #[test]
+    fn test_clip_to_radius_cuts_every_export_at_the_circle() {
+        use crate::limacon::LimaconConfig;
+
+        let mut pattern = GuillochePattern::new(30.0).unwrap();
+        // Zigzag in and out of the circle: it leaves three times and
+        // comes back twice
+        let zigzag: Vec<Point2D> = (0..6)
+            .map(|i| {
+                let r = if i % 2 == 0 { 24.0 } else { 36.0 };
+                let angle = i as f64 * 0.3;
+                Point2D::new(r * angle.cos(), r * angle.sin())
+            })
+            .collect();
+        pattern.add_imported_layer(vec![zigzag]);
+        pattern.add_horizontal_layer(HorizontalSpirograph::new(40.0, 0.75, 25.0, 3, 120).unwrap());
+        pattern.add_limacon_layer(LimaconLayer::new(LimaconConfig::new(6, 30.0, 12.0)).unwrap());
+        pattern.generate();
+        let reach = |p: &Point2D| p.x.hypot(p.y);
+        assert!(pattern
+            .all_lines()
+            .iter()
+            .flatten()
+            .any(|p| reach(p) > 36.0));
+
+        assert!(pattern.clip_to_radius(-1.0).is_err());
+        pattern.clip_to_radius(30.0).unwrap();
+        assert_eq!(pattern.clip_radius(), Some(30.0));
+
+        let pieces = pattern.clipped_lines(
+            pattern
+                .typed_layers::<ImportedLayer>()
+                .next()
+                .unwrap()
+                .lines(),
+        );
+        assert_eq!(pieces.len(), 3);
+        for piece in pieces.iter() {
+            assert!(piece.iter().all(|p| reach(p) <= 30.0 + 1e-9));
+        }
+        assert!((reach(&pieces[1][0]) - 30.0).abs() < 1e-9);
+        assert!((reach(pieces[1].last().unwrap()) - 30.0).abs() < 1e-9);
+
+        let dir = std::env::temp_dir();
+        let svg_path = dir.join("test_guilloche_clip_to_radius.svg");
+        let stl_path = dir.join("test_guilloche_clip_to_radius.stl");
+        let step_path = dir.join("test_guilloche_clip_to_radius.stp");
+        let config = ExportConfig::default();
+        pattern
+            .export_combined_svg(svg_path.to_str().unwrap())
+            .unwrap();
+        pattern
+            .export_combined_stl(stl_path.to_str().unwrap(), &config)
+            .unwrap();
+        pattern
+            .export_combined_step(step_path.to_str().unwrap(), &config)
+            .unwrap();
+        let svg = std::fs::read_to_string(&svg_path).unwrap();
+        let mut file = std::fs::File::open(&stl_path).unwrap();
+        let mesh = stl_io::read_stl(&mut file).unwrap();
+        let step = std::fs::read_to_string(&step_path).unwrap();
+        for path in [&svg_path, &stl_path, &step_path] {
+            let _ = std::fs::remove_file(path);
+        }
+
+        let svg_points: Vec<Point2D> = svg
+            .split(" d=\"")
+            .skip(1)
+            .flat_map(|rest| {
+                let data = &rest[..rest.find('"').unwrap()];
+                let numbers: Vec<f64> = data
+                    .split(|c: char| c.is_ascii_alphabetic() || c == ',' || c == ' ')
+                    .filter_map(|n| n.parse().ok())
+                    .collect();
+                numbers
+                    .chunks(2)
+                    .map(|xy| Point2D::new(xy[0], xy[1]))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert!(!svg_points.is_empty());
+        // SVG coordinates are rounded to 4 decimals
+        assert!(svg_points.iter().all(|p| reach(p) <= 30.0 + 1e-4));
+        assert!(mesh
+            .vertices
+            .iter()
+            .all(|v| (v[0] as f64).hypot(v[1] as f64) <= 30.0 + 1e-4));
+        let step_points: Vec<Point2D> = step
+            .lines()
+            .filter_map(|line| line.split_once("CARTESIAN_POINT('',("))
+            .map(|(_, rest)| {
+                let xy: Vec<f64> = rest
+                    .split(',')
+                    .take(2)
+                    .map(|n| n.parse().unwrap())
+                    .collect();
+                Point2D::new(xy[0], xy[1])
+            })
+            .collect();
+        assert!(!step_points.is_empty());
+        assert!(step_points.iter().all(|p| reach(p) <= 30.0 + 1e-6));
+
+        pattern.clear_clip();
+        assert_eq!(
+            pattern
+                .clipped_lines(
+                    pattern
+                        .typed_layers::<ImportedLayer>()
+                        .next()
+                        .unwrap()
+                        .lines()
+                )
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_render_raster_draws_the_combined_svg_view() {
+        let mut pattern = GuillochePattern::new(38.0).unwrap();
+        pattern.add_horizontal_layer(HorizontalSpirograph::new(38.0, 0.75, 20.0, 3, 360).unwrap());
+        pattern.generate();
+
+        let image = pattern.render_raster(300, 200).unwrap();
+        assert_eq!((image.width, image.height), (300, 200));
+        let dark = image.data().chunks(4).filter(|px| px[0] < 128).count();
+        assert!(dark > 0);
+        // The square view is centred, leaving white bands on either side
+        assert_eq!(image.pixel(10, 100), Some([255; 4]));
+        assert!(pattern.render_raster(0, 200).is_err());
+
+        let path = std::env::temp_dir().join("test_guilloche_preview.png");
+        pattern.to_png(path.to_str().unwrap(), 300, 200).unwrap();
+        let png = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(png, image.to_png_bytes().unwrap());
+    }
+
+    #[test]
+    fn test_every_layer_samples_resolution_plus_one_points() {
+        use crate::common::PointsTotal;
+        use crate::limacon::LimaconConfig;
+
+        let steps = 48;
+        let closed = |name: &str, lines: &[Vec<Point2D>]| {
+            for line in lines {
+                assert_eq!(line.len(), steps + 1, "{}", name);
+                let (first, last) = (line[0], line[steps]);
+                assert!(
+                    (first.x - last.x).hypot(first.y - last.y) < 1e-9,
+                    "{}",
+                    name
+                );
+            }
+        };
+
+        let mut diamant =
+            DiamantLayer::new(DiamantConfig::new(6, 10.0).with_resolution(steps)).unwrap();
+        diamant.generate();
+        closed("diamant", diamant.lines());
+        let mut limacon =
+            LimaconLayer::new(LimaconConfig::new(6, 10.0, 4.0).with_resolution(steps)).unwrap();
+        limacon.generate();
+        closed("limacon", limacon.lines());
+        let mut huiteight =
+            HuitEightLayer::new(HuitEightConfig::new(6, 10.0).with_resolution(steps)).unwrap();
+        huiteight.generate();
+        closed("huiteight", huiteight.lines());
+        let mut draperie =
+            DraperieLayer::new(DraperieConfig::new(6, 10.0).with_resolution(steps)).unwrap();
+        draperie.generate();
+        closed("draperie", draperie.lines());
+        let mut flinque =
+            FlinqueLayer::new(10.0, FlinqueConfig::default().with_resolution(steps)).unwrap();
+        flinque.generate();
+        closed("flinque", flinque.lines());
+        let mut ring = ChapterRingLayer::new(ChapterRingConfig {
+            resolution: PointsTotal(steps),
+            ..ChapterRingConfig::default()
+        })
+        .unwrap();
+        ring.generate();
+        closed("chapter ring", &ring.lines()[..2]);
+
+        // Open lines: the spirograph's 2 turns, a paon line left unclipped
+        let mut spiro = HorizontalSpirograph::new(38.0, 0.77, 5.0, 2, 24).unwrap();
+        assert_eq!(spiro.generate().len(), 2 * 24 + 1);
+        let mut paon = PaonLayer::new(PaonConfig {
+            amplitude: 0.0,
+            fan_angle: 0.0,
+            ..PaonConfig::new(1, 10.0).with_resolution(steps)
+        })
+        .unwrap();
+        paon.generate();
+        assert_eq!(paon.lines()[0].len(), steps + 1);
+    }
+
+    #[test]
+    fn test_post_process_nudges_selected_rings_once() {
+        use crate::post::radial_nudge;
+
+        let mut plain = GuillochePattern::new(38.0).unwrap();
+        plain.add_draperie_layer(DraperieLayer::new(DraperieConfig::default()).unwrap());
+        let mut nudged = plain.clone();
+        nudged.add_post_process(radial_nudge(
+            |kind, _, line| kind == LayerKind::Draperie && line % 7 == 0,
+            0.05,
+        ));
+        plain.generate();
+        nudged.generate();
+
+        let before = plain
+            .typed_layers::<DraperieLayer>()
+            .next()
+            .unwrap()
+            .rings();
+        let after = nudged
+            .typed_layers::<DraperieLayer>()
+            .next()
+            .unwrap()
+            .rings()
+            .clone();
+        assert_eq!(before.len(), after.len());
+        for (i, (a, b)) in before.iter().zip(&after).enumerate() {
+            assert_eq!(a != b, i % 7 == 0, "ring {}", i);
+        }
+        let (p, q) = (before[0][0], after[0][0]);
+        assert!((q.x.hypot(q.y) - p.x.hypot(p.y) - 0.05).abs() < 1e-9);
+
+        // Regenerating re-applies the edit to fresh lines instead of stacking it
+        nudged.generate();
+        assert_eq!(
+            nudged
+                .typed_layers::<DraperieLayer>()
+                .next()
+                .unwrap()
+                .rings(),
+            &after
+        );
+        nudged.generate_parallel();
+        assert_eq!(
+            nudged
+                .typed_layers::<DraperieLayer>()
+                .next()
+                .unwrap()
+                .rings(),
+            &after
+        );
+    }
+
+    #[test]
+    fn test_imported_svg_layer_exports_and_regenerates() {
+        let mut pattern = GuillochePattern::new(30.0).unwrap();
+        let warnings = pattern
+            .add_imported_svg(
+                r#"<svg><path d="M0 0 L10 0 L10 10"/><path d="M0 0 Q5 5 10 0"/></svg>"#,
+            )
+            .unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(pattern.kind_count(LayerKind::Imported), 1);
+
+        pattern.add_post_process(|lines, kind, _| {
+            if kind == LayerKind::Imported {
+                lines[0][0].x += 1.0;
+            }
+        });
+        pattern.generate();
+        pattern.generate();
+        assert_eq!(pattern.imported_lines()[0][0][0], Point2D::new(1.0, 0.0));
+
+        let file = std::env::temp_dir().join("test_imported_layer_combined.svg");
+        pattern.export_combined_svg(file.to_str().unwrap()).unwrap();
+        let lines = crate::import::svg_polylines(file.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&file).ok();
+        assert_eq!(lines, pattern.all_lines());
+
+        assert_eq!(pattern.export_polylines(None).len(), 1);
+    }
 }
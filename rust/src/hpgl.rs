@@ -0,0 +1,151 @@
+use std::fmt::Write;
+
+use crate::common::{first_non_finite, Point2D, SpirographError};
+
+/// HPGL plotter units per millimetre
+pub const HPGL_UNITS_PER_MM: f64 = 40.0;
+
+/// HPGL commands plotting `lines`, with lengths in mm multiplied by `scale`
+///
+/// The program starts with `IN;` and `PA;` (absolute coordinates), selects
+/// each line's pen with `SP` when it changes, travels to the line's start
+/// with `PU` and draws the rest with a single `PD`, and ends with
+/// `PU;SP0;`. Coordinates are rounded to whole plotter units (40 per mm)
+/// and written as generated, with no axis flip, matching the DXF export.
+///
+/// `pens[i]` is the pen for line `i`; lines past the end of `pens` use its
+/// last entry, or pen 1 when it is empty. Lines with fewer than two points
+/// are skipped.
+///
+/// # Example
+/// ```
+/// use turtles::hpgl::hpgl_commands;
+/// use turtles::Point2D;
+///
+/// let line = vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 0.0), Point2D::new(1.0, 0.5)];
+/// let hpgl = hpgl_commands(&[line], &[], 1.0).unwrap();
+/// assert_eq!(hpgl, "IN;PA;SP1;PU0,0;PD40,0,40,20;PU;SP0;\n");
+/// ```
+pub fn hpgl_commands(
+    lines: &[Vec<Point2D>],
+    pens: &[u32],
+    scale: f64,
+) -> Result<String, SpirographError> {
+    if !(scale.is_finite() && scale > 0.0) {
+        return Err(SpirographError::InvalidParameter(
+            "HPGL scale must be positive and finite".to_string(),
+        ));
+    }
+    if let Some(pen) = pens.iter().find(|&&pen| pen == 0) {
+        return Err(SpirographError::InvalidParameter(format!(
+            "HPGL pen {} is not a pen; pens are numbered from 1",
+            pen
+        )));
+    }
+    if let Some((i, j)) = first_non_finite(lines) {
+        return Err(SpirographError::ExportError(format!(
+            "line {} point {} is not finite",
+            i, j
+        )));
+    }
+
+    let units = |value: f64| (value * scale * HPGL_UNITS_PER_MM).round() as i64;
+    let mut hpgl = String::from("IN;PA;");
+    let mut current_pen = None;
+    for (i, line) in lines.iter().enumerate() {
+        if line.len() < 2 {
+            continue;
+        }
+        let pen = pen_for(pens, i);
+        // Writing to a String cannot fail
+        if current_pen != Some(pen) {
+            let _ = write!(hpgl, "SP{};", pen);
+            current_pen = Some(pen);
+        }
+        let _ = write!(hpgl, "PU{},{};PD", units(line[0].x), units(line[0].y));
+        for (k, point) in line[1..].iter().enumerate() {
+            let separator = if k == 0 { "" } else { "," };
+            let _ = write!(hpgl, "{}{},{}", separator, units(point.x), units(point.y));
+        }
+        hpgl.push(';');
+    }
+    hpgl.push_str("PU;SP0;\n");
+    Ok(hpgl)
+}
+
+/// Write [`hpgl_commands`] for `lines` to an HPGL file
+pub fn write_hpgl(
+    filename: &str,
+    lines: &[Vec<Point2D>],
+    pens: &[u32],
+    scale: f64,
+) -> Result<(), SpirographError> {
+    let hpgl = hpgl_commands(lines, pens, scale)?;
+    std::fs::write(filename, hpgl).map_err(|e| {
+        SpirographError::ExportError(format!("Failed to write HPGL file '{}': {}", filename, e))
+    })
+}
+
+/// Entry `index` of `pens`, its last entry past the end, or pen 1 when empty
+pub(crate) fn pen_for(pens: &[u32], index: usize) -> u32 {
+    pens.get(index).or(pens.last()).copied().unwrap_or(1)
+}
+
+/// Lines of each layer flattened for [`write_hpgl`], with every line
+/// given its layer's pen from `layer_pens` (see [`hpgl_commands`] for how
+/// short pen lists are extended)
+pub(crate) fn pens_by_layer(
+    layers: Vec<Vec<Vec<Point2D>>>,
+    layer_pens: &[u32],
+) -> (Vec<Vec<Point2D>>, Vec<u32>) {
+    let mut lines = Vec::new();
+    let mut pens = Vec::new();
+    for (i, layer) in layers.into_iter().enumerate() {
+        let pen = pen_for(layer_pens, i);
+        pens.extend(std::iter::repeat_n(pen, layer.len()));
+        lines.extend(layer);
+    }
+    (lines, pens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_segment_command_sequence() {
+        let lines = vec![
+            vec![Point2D::new(0.0, 0.0), Point2D::new(2.5, 0.0)],
+            Vec::new(),
+            vec![Point2D::new(1.0, 1.0)],
+            vec![Point2D::new(-1.0, 0.5), Point2D::new(-1.0, -0.01249)],
+        ];
+        let hpgl = hpgl_commands(&lines, &[], 1.0).unwrap();
+        assert_eq!(hpgl, "IN;PA;SP1;PU0,0;PD100,0;PU-40,20;PD-40,0;PU;SP0;\n");
+
+        // Pens change only between lines that differ, and the scale applies
+        // before converting to plotter units
+        let hpgl = hpgl_commands(&lines, &[2, 2, 2, 3], 0.5).unwrap();
+        assert_eq!(
+            hpgl,
+            "IN;PA;SP2;PU0,0;PD50,0;SP3;PU-20,10;PD-20,0;PU;SP0;\n"
+        );
+        // The last pen carries on past the end of the list
+        assert!(hpgl_commands(&lines, &[4], 1.0)
+            .unwrap()
+            .starts_with("IN;PA;SP4;PU0,0;PD100,0;PU-40,20"));
+    }
+
+    #[test]
+    fn test_hpgl_rejects_bad_input() {
+        let line = vec![vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 0.0)]];
+        assert!(hpgl_commands(&line, &[], 0.0).is_err());
+        assert!(hpgl_commands(&line, &[], f64::NAN).is_err());
+        assert!(hpgl_commands(&line, &[0], 1.0).is_err());
+        let bad = vec![vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(f64::INFINITY, 0.0),
+        ]];
+        assert!(hpgl_commands(&bad, &[], 1.0).is_err());
+    }
+}
@@ -1,6 +1,17 @@
 use std::f64::consts::PI;
 
-use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographError};
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::{write_svg_streaming, SvgWriter};
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_consecutive, map_indices, polar_to_cartesian,
+    polyline_is_degenerate, scale_polylines, simplified_layer, simplify_polylines, ExportConfig,
+    PatternLayer, Point2D, PointsTotal, SpirographError, StoredLayer, DEDUPE_EPSILON,
+    DEFAULT_MAX_POINTS_PER_PATH,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
 
 /// Configuration for the Huit-Eight (Figure-Eight) guilloché pattern
 ///
@@ -22,14 +33,14 @@ use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographE
 ///
 /// Each lemniscate is rotated by 2π·i/N around the origin so that N curves
 /// tile the full circle.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HuitEightConfig {
     /// Number of figure-eight curves to draw (more = denser mesh)
     pub num_curves: usize,
     /// Scale (half-width) of each figure-eight
     pub scale: f64,
     /// Resolution – number of points per curve
-    pub resolution: usize,
+    pub resolution: PointsTotal,
     /// Number of clusters to group curves into (0 = uniform distribution)
     ///
     /// When non-zero, the `num_curves` are divided among `num_clusters`
@@ -51,7 +62,7 @@ impl Default for HuitEightConfig {
         HuitEightConfig {
             num_curves: 72,
             scale: 20.0,
-            resolution: 360,
+            resolution: PointsTotal(360),
             num_clusters: 0,
             cluster_spread: 0.0,
         }
@@ -68,15 +79,15 @@ impl HuitEightConfig {
         HuitEightConfig {
             num_curves,
             scale,
-            resolution: 360,
+            resolution: PointsTotal(360),
             num_clusters: 0,
             cluster_spread: 0.0,
         }
     }
 
     /// Set the resolution (points per curve)
-    pub fn with_resolution(mut self, resolution: usize) -> Self {
-        self.resolution = resolution;
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = resolution.into();
         self
     }
 
@@ -90,6 +101,29 @@ impl HuitEightConfig {
         self.cluster_spread = spread;
         self
     }
+
+    /// Check the curve count, scale and resolution
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.scale <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "scale must be positive".to_string(),
+            ));
+        }
+
+        if self.num_curves == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "num_curves must be at least 1".to_string(),
+            ));
+        }
+
+        if self.resolution.get() < 10 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 10".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 /// A Huit-Eight (Figure-Eight) pattern layer
@@ -98,11 +132,12 @@ impl HuitEightConfig {
 /// (figure-eights) that pass through the centre and are rotated at equal
 /// angular intervals.  The overlapping curves create a dense woven mesh
 /// pattern reminiscent of traditional engine-turned watch dials.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HuitEightLayer {
     pub config: HuitEightConfig,
     pub center_x: f64,
     pub center_y: f64,
+    #[serde(skip)]
     curves: Vec<Vec<Point2D>>,
 }
 
@@ -118,23 +153,7 @@ impl HuitEightLayer {
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        if config.scale <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "scale must be positive".to_string(),
-            ));
-        }
-
-        if config.num_curves == 0 {
-            return Err(SpirographError::InvalidParameter(
-                "num_curves must be at least 1".to_string(),
-            ));
-        }
-
-        if config.resolution < 10 {
-            return Err(SpirographError::InvalidParameter(
-                "resolution must be at least 10".to_string(),
-            ));
-        }
+        config.validate()?;
 
         Ok(HuitEightLayer {
             config,
@@ -182,8 +201,37 @@ impl HuitEightLayer {
     ///
     /// rotated by the per-curve rotation angle.
     pub fn generate(&mut self) {
-        self.curves.clear();
+        self.generate_curves(false);
+    }
+
+    /// Generate as [`generate`](Self::generate), one curve per thread task
+    ///
+    /// The curves are identical to the serial ones, in the same order.
+    #[cfg(feature = "parallel")]
+    pub fn generate_parallel(&mut self) {
+        self.generate_curves(true);
+    }
+
+    /// Generate every curve, optionally in parallel
+    fn generate_curves(&mut self, parallel: bool) {
+        self.curves = map_indices(self.config.num_curves, parallel, self.curve_tracer());
 
+        debug_assert_finite("HuitEightLayer", &self.curves);
+    }
+
+    /// Curves computed lazily from the config, one at a time
+    ///
+    /// Yields the same curves, point for point, as `generate` followed by
+    /// `curves`, but holds only the curve being traced in memory. The
+    /// generated curves are neither used nor changed.
+    pub fn iter_curves(&self) -> impl Iterator<Item = impl Iterator<Item = Point2D> + '_> + '_ {
+        (0..self.config.num_curves)
+            .map(self.curve_tracer())
+            .map(Vec::into_iter)
+    }
+
+    /// Curve `i`, rotated into place with repeated points removed
+    fn curve_tracer(&self) -> impl Fn(usize) -> Vec<Point2D> + Sync + Send + '_ {
         let a = self.config.scale;
         let n = self.config.num_curves;
 
@@ -219,14 +267,17 @@ impl HuitEightLayer {
             (0..n).map(|i| (i as f64) * angle_step).collect()
         };
 
-        for rotation in &rotations {
+        // Every curve has a rotation, clustered or not
+        debug_assert_eq!(rotations.len(), n);
+        move |i| {
+            let rotation = rotations[i];
             let cos_rot = rotation.cos();
             let sin_rot = rotation.sin();
 
-            let mut curve_points = Vec::with_capacity(self.config.resolution + 1);
+            let mut curve_points = Vec::with_capacity(self.config.resolution.get() + 1);
 
-            for j in 0..=self.config.resolution {
-                let t = (j as f64) / (self.config.resolution as f64);
+            for j in 0..=self.config.resolution.get() {
+                let t = (j as f64) / (self.config.resolution.get() as f64);
                 let angle = 2.0 * PI * t;
 
                 // Lemniscate of Bernoulli parametric form
@@ -244,7 +295,8 @@ impl HuitEightLayer {
                 curve_points.push(Point2D::new(x, y));
             }
 
-            self.curves.push(curve_points);
+            dedupe_consecutive(&mut curve_points, DEDUPE_EPSILON);
+            curve_points
         }
     }
 
@@ -258,62 +310,152 @@ impl HuitEightLayer {
         &self.curves
     }
 
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.curves
+    }
+
+    /// Multiply every length (config, center and generated curves) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.scale *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.curves, factor);
+    }
+
     /// Export the pattern to SVG format
     pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        use svg::node::element::{path::Data, Path};
-        use svg::Document;
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
 
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
         if self.curves.is_empty() {
             return Err(SpirographError::ExportError(
                 "Pattern not generated. Call generate() first.".to_string(),
             ));
         }
 
-        // Find bounds
-        let mut min_x = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
-
-        for curve in &self.curves {
-            for point in curve {
-                min_x = min_x.min(point.x);
-                max_x = max_x.max(point.x);
-                min_y = min_y.min(point.y);
-                max_y = max_y.max(point.y);
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.curves.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        for curve in &layer.curves {
+            if polyline_is_degenerate(curve) {
+                continue;
             }
+
+            let node = curve_svg_node(&writer, curve);
+            writer.add(node);
         }
 
-        let margin = 5.0;
-        let width = max_x - min_x + 2.0 * margin;
-        let height = max_y - min_y + 2.0 * margin;
+        writer.save(filename)
+    }
 
-        let mut document = Document::new()
-            .set("width", format!("{}mm", width))
-            .set("height", format!("{}mm", height))
-            .set("viewBox", (min_x - margin, min_y - margin, width, height));
+    /// Export to SVG straight from [`iter_curves`](Self::iter_curves),
+    /// without generating
+    ///
+    /// Writes the same file as `generate` then `to_svg`, but traces each
+    /// curve twice (once to size the document) rather than holding them all.
+    pub fn to_svg_streaming(&self, filename: &str) -> Result<(), SpirographError> {
+        write_svg_streaming(
+            filename,
+            DEFAULT_MAX_POINTS_PER_PATH,
+            || self.iter_curves().map(Iterator::collect),
+            curve_svg_node,
+        )
+    }
+}
 
-        for curve in &self.curves {
-            if curve.is_empty() {
-                continue;
-            }
+/// SVG node stroking one curve
+fn curve_svg_node(writer: &SvgWriter, curve: &[Point2D]) -> Box<dyn svg::Node> {
+    writer.polyline_node(curve, false, |path| {
+        path.set("fill", "none")
+            .set("stroke", "black")
+            .set("stroke-width", 0.05)
+    })
+}
 
-            let mut data = Data::new().move_to((curve[0].x, curve[0].y));
-            for point in curve.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
+impl PatternLayer for HuitEightLayer {
+    fn generate(&mut self) {
+        HuitEightLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for HuitEightLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        HuitEightLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
 
-            let path = Path::new()
-                .set("d", data)
-                .set("fill", "none")
-                .set("stroke", "black")
-                .set("stroke-width", 0.05);
+impl LayerType for HuitEightLayer {
+    const KIND: LayerKind = LayerKind::HuitEight;
+}
 
-            document = document.add(path);
+impl Progressive for HuitEightLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
         }
 
-        svg::save(filename, &document)
-            .map_err(|e| SpirographError::ExportError(format!("Failed to save SVG: {}", e)))
+        // The curve count defines the pattern; only sampling is reduced
+        let mut coarse = self.clone();
+        coarse.config.resolution =
+            PointsTotal(scale_count(self.config.resolution.get(), fraction, 10));
+        coarse.generate();
+        self.curves = coarse.curves;
+    }
+}
+
+impl PointCounted for HuitEightLayer {
+    fn point_count(&self) -> usize {
+        self.curves.iter().map(Vec::len).sum()
     }
 }
 
@@ -321,12 +463,25 @@ impl HuitEightLayer {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_generation_matches_serial() {
+        let mut config = HuitEightConfig::new(48, 15.0);
+        config.num_clusters = 5;
+        let mut serial = HuitEightLayer::new(config.clone()).unwrap();
+        serial.generate();
+        let mut parallel = HuitEightLayer::new(config).unwrap();
+        parallel.generate_parallel();
+        assert_eq!(parallel.curves().len(), 48);
+        assert_eq!(parallel.curves(), serial.curves());
+    }
+
     #[test]
     fn test_huiteight_config_default() {
         let config = HuitEightConfig::default();
         assert_eq!(config.num_curves, 72);
         assert_eq!(config.scale, 20.0);
-        assert_eq!(config.resolution, 360);
+        assert_eq!(config.resolution, PointsTotal(360));
     }
 
     #[test]
@@ -481,4 +636,31 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_iter_curves_and_streaming_svg_match_generate() {
+        let mut config = HuitEightConfig::new(23, 15.0);
+        config.num_clusters = 4;
+        let mut layer = HuitEightLayer::new_with_center(config, 2.0, 1.0).unwrap();
+        let lazy: Vec<Vec<Point2D>> = layer.iter_curves().map(Iterator::collect).collect();
+        assert!(layer.curves().is_empty());
+
+        let dir = std::env::temp_dir();
+        let (streamed, saved) = (
+            dir.join("test_huiteight_stream.svg"),
+            dir.join("test_huiteight_saved.svg"),
+        );
+        layer.to_svg_streaming(streamed.to_str().unwrap()).unwrap();
+        layer.generate();
+        layer.to_svg(saved.to_str().unwrap()).unwrap();
+        let streamed_svg = std::fs::read_to_string(&streamed).unwrap();
+        let saved_svg = std::fs::read_to_string(&saved).unwrap();
+        for path in [&streamed, &saved] {
+            let _ = std::fs::remove_file(path);
+        }
+
+        assert_eq!(&lazy, layer.curves());
+        assert_eq!(lazy.len(), 23);
+        assert_eq!(streamed_svg, saved_svg);
+    }
 }
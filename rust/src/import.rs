@@ -0,0 +1,496 @@
+//! Polylines read back from SVG files
+//!
+//! [`svg_polylines`] turns the straight-line `<path>` elements of an SVG
+//! (such as this crate's own exports) into polylines, with the `transform`
+//! attributes of each path and its ancestors applied. Paths holding curves
+//! or arcs are skipped and reported rather than approximated; see
+//! [`svg_import`] for the report. [`ImportedLayer`] keeps the result as a
+//! layer of a [`GuillochePattern`](crate::GuillochePattern).
+
+use serde::{Deserialize, Serialize};
+use svg::node::element::path::{Command, Data, Position};
+use svg::node::element::tag::Type;
+use svg::node::Attributes;
+use svg::parser::Event;
+
+use crate::common::{
+    scale_polylines, simplify_polylines, PatternLayer, Point2D, SpirographError, StoredLayer,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+
+/// Elements whose content is referenced rather than drawn
+const UNRENDERED: [&str; 6] = ["defs", "clipPath", "mask", "marker", "pattern", "symbol"];
+
+/// Polylines read from an SVG, with the paths that were left out
+#[derive(Debug, Clone, Default)]
+pub struct SvgImport {
+    /// One polyline per straight-line subpath, in document order
+    pub lines: Vec<Vec<Point2D>>,
+    /// Why each skipped path was skipped
+    pub warnings: Vec<String>,
+}
+
+/// Straight-line polylines of an SVG file, or of SVG text
+///
+/// `path_or_str` is read as SVG text when it starts with `<`, and as a file
+/// name otherwise. Paths with curves or arcs are skipped; use
+/// [`svg_import`] to see which.
+///
+/// # Example
+/// ```
+/// let svg = r#"<svg><g transform="translate(10 0)">
+///     <path d="M0,0 L1,0 l0,1"/>
+/// </g></svg>"#;
+/// let lines = turtles::import::svg_polylines(svg).unwrap();
+/// assert_eq!(lines.len(), 1);
+/// assert_eq!((lines[0][2].x, lines[0][2].y), (11.0, 1.0));
+/// ```
+pub fn svg_polylines(path_or_str: &str) -> Result<Vec<Vec<Point2D>>, SpirographError> {
+    Ok(svg_import(path_or_str)?.lines)
+}
+
+/// [`svg_polylines`], also listing the paths that were skipped
+///
+/// Fails only if the file cannot be read, the XML is malformed or a
+/// `transform` attribute cannot be parsed. A path whose data uses curves
+/// or arcs, or cannot be parsed, is skipped with a warning naming it by
+/// `id`, or by its position among the document's paths.
+pub fn svg_import(path_or_str: &str) -> Result<SvgImport, SpirographError> {
+    let content = if path_or_str.trim_start().starts_with('<') {
+        path_or_str.to_string()
+    } else {
+        std::fs::read_to_string(path_or_str).map_err(|e| {
+            SpirographError::InvalidParameter(format!(
+                "cannot read SVG file {}: {}",
+                path_or_str, e
+            ))
+        })?
+    };
+
+    let mut import = SvgImport::default();
+    // Transform in effect inside each open element, and whether it is drawn
+    let mut stack: Vec<(Affine, bool)> = Vec::new();
+    let mut path_count = 0;
+    for event in svg::read(&content)
+        .map_err(|e| SpirographError::InvalidParameter(format!("malformed SVG: {}", e)))?
+    {
+        let (name, kind, attributes) = match event {
+            Event::Tag(name, kind, attributes) => (name, kind, attributes),
+            Event::Error(e) => {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "malformed SVG: {}",
+                    e
+                )))
+            }
+            _ => continue,
+        };
+        if kind == Type::End {
+            stack.pop();
+            continue;
+        }
+
+        let (parent, drawn) = stack.last().copied().unwrap_or((Affine::IDENTITY, true));
+        let transform = match attributes.get("transform") {
+            Some(value) => parent.then(parse_transform(value)?),
+            None => parent,
+        };
+        let drawn = drawn && !UNRENDERED.contains(&name);
+        if name == "path" {
+            path_count += 1;
+            if drawn {
+                import_path(&attributes, path_count, transform, &mut import);
+            }
+        }
+        if kind == Type::Start {
+            stack.push((transform, drawn));
+        }
+    }
+    Ok(import)
+}
+
+/// Add the subpaths of one `<path>` to `import`, or a warning if it has to
+/// be skipped
+fn import_path(attributes: &Attributes, number: usize, transform: Affine, import: &mut SvgImport) {
+    let name = match attributes.get("id") {
+        Some(id) => format!("path \"{}\"", &**id),
+        None => format!("path {}", number),
+    };
+    let Some(d) = attributes.get("d") else {
+        return;
+    };
+    let data = match Data::parse(d) {
+        Ok(data) => data,
+        Err(e) => {
+            import
+                .warnings
+                .push(format!("{} skipped: unreadable path data ({})", name, e));
+            return;
+        }
+    };
+    match subpaths(&data) {
+        Ok(lines) => import.lines.extend(lines.into_iter().map(|line| {
+            line.into_iter()
+                .map(|(x, y)| transform.apply(x, y))
+                .collect()
+        })),
+        Err(segment) => import.warnings.push(format!(
+            "{} skipped: {} segments are not supported",
+            name, segment
+        )),
+    }
+}
+
+/// Untransformed polylines of path data made only of lines, or the name of
+/// the first other kind of segment
+///
+/// Each moveto starts a polyline, closepath returns to the subpath's start,
+/// and polylines of fewer than two points are dropped.
+fn subpaths(data: &Data) -> Result<Vec<Vec<(f64, f64)>>, &'static str> {
+    let mut lines = Vec::new();
+    let mut line: Vec<(f64, f64)> = Vec::new();
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+
+    let mut finish = |line: &mut Vec<(f64, f64)>| {
+        if line.len() >= 2 {
+            lines.push(std::mem::take(line));
+        } else {
+            line.clear();
+        }
+    };
+    // Move to `to` with a line, starting from `current` if nothing is drawn yet
+    let line_to = |line: &mut Vec<(f64, f64)>, current: &mut (f64, f64), to: (f64, f64)| {
+        if line.is_empty() {
+            line.push(*current);
+        }
+        line.push(to);
+        *current = to;
+    };
+    let target = |position: &Position, current: (f64, f64), x: f64, y: f64| match position {
+        Position::Absolute => (x, y),
+        Position::Relative => (current.0 + x, current.1 + y),
+    };
+
+    for command in data.iter() {
+        match command {
+            Command::Move(position, parameters) => {
+                finish(&mut line);
+                for (i, pair) in parameters.chunks_exact(2).enumerate() {
+                    let to = target(position, current, pair[0] as f64, pair[1] as f64);
+                    if i == 0 {
+                        // Only the first pair moves; the rest are implicit lines
+                        current = to;
+                        start = to;
+                    } else {
+                        line_to(&mut line, &mut current, to);
+                    }
+                }
+            }
+            Command::Line(position, parameters) => {
+                for pair in parameters.chunks_exact(2) {
+                    let to = target(position, current, pair[0] as f64, pair[1] as f64);
+                    line_to(&mut line, &mut current, to);
+                }
+            }
+            Command::HorizontalLine(position, parameters) => {
+                for &x in parameters.iter() {
+                    let to = match position {
+                        Position::Absolute => (x as f64, current.1),
+                        Position::Relative => (current.0 + x as f64, current.1),
+                    };
+                    line_to(&mut line, &mut current, to);
+                }
+            }
+            Command::VerticalLine(position, parameters) => {
+                for &y in parameters.iter() {
+                    let to = match position {
+                        Position::Absolute => (current.0, y as f64),
+                        Position::Relative => (current.0, current.1 + y as f64),
+                    };
+                    line_to(&mut line, &mut current, to);
+                }
+            }
+            Command::Close => {
+                if !line.is_empty() && line.last() != Some(&start) {
+                    line.push(start);
+                }
+                current = start;
+                finish(&mut line);
+            }
+            Command::QuadraticCurve(..) | Command::SmoothQuadraticCurve(..) => {
+                return Err("quadratic curve")
+            }
+            Command::CubicCurve(..) | Command::SmoothCubicCurve(..) => return Err("cubic curve"),
+            Command::EllipticalArc(..) => return Err("arc"),
+        }
+    }
+    finish(&mut line);
+    Ok(lines)
+}
+
+/// 2D affine map in SVG `matrix(a b c d e f)` order:
+/// x' = a x + c y + e, y' = b x + d y + f
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Affine([f64; 6]);
+
+impl Affine {
+    const IDENTITY: Affine = Affine([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    fn translate(tx: f64, ty: f64) -> Self {
+        Affine([1.0, 0.0, 0.0, 1.0, tx, ty])
+    }
+
+    /// Rotation by `degrees` about the origin
+    fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Affine([cos, sin, -sin, cos, 0.0, 0.0])
+    }
+
+    /// This map applied after `inner`
+    fn then(self, inner: Affine) -> Self {
+        let [a, b, c, d, e, f] = self.0;
+        let [a2, b2, c2, d2, e2, f2] = inner.0;
+        Affine([
+            a * a2 + c * b2,
+            b * a2 + d * b2,
+            a * c2 + c * d2,
+            b * c2 + d * d2,
+            a * e2 + c * f2 + e,
+            b * e2 + d * f2 + f,
+        ])
+    }
+
+    fn apply(self, x: f64, y: f64) -> Point2D {
+        let [a, b, c, d, e, f] = self.0;
+        Point2D::new(a * x + c * y + e, b * x + d * y + f)
+    }
+}
+
+/// The map of an SVG `transform` attribute: a list of `translate`,
+/// `rotate`, `scale`, `matrix`, `skewX` and `skewY`, applied right to left
+fn parse_transform(value: &str) -> Result<Affine, SpirographError> {
+    let invalid =
+        || SpirographError::InvalidParameter(format!("unsupported SVG transform \"{}\"", value));
+
+    let mut transform = Affine::IDENTITY;
+    for item in value.split(')') {
+        let item = item.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if item.trim().is_empty() {
+            continue;
+        }
+        let (name, arguments) = item.split_once('(').ok_or_else(invalid)?;
+        let arguments = arguments
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|_| invalid())?;
+        let step = match (name.trim(), arguments.as_slice()) {
+            ("translate", &[tx]) => Affine::translate(tx, 0.0),
+            ("translate", &[tx, ty]) => Affine::translate(tx, ty),
+            ("rotate", &[angle]) => Affine::rotate(angle),
+            ("rotate", &[angle, cx, cy]) => Affine::translate(cx, cy)
+                .then(Affine::rotate(angle))
+                .then(Affine::translate(-cx, -cy)),
+            ("scale", &[s]) => Affine([s, 0.0, 0.0, s, 0.0, 0.0]),
+            ("scale", &[sx, sy]) => Affine([sx, 0.0, 0.0, sy, 0.0, 0.0]),
+            ("matrix", &[a, b, c, d, e, f]) => Affine([a, b, c, d, e, f]),
+            ("skewX", &[angle]) => Affine([1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0]),
+            ("skewY", &[angle]) => Affine([1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0]),
+            _ => return Err(invalid()),
+        };
+        transform = transform.then(step);
+    }
+    Ok(transform)
+}
+
+/// A layer of fixed polylines, such as ones read by [`svg_polylines`]
+///
+/// Generating restores the stored polylines, so post-processing callbacks
+/// see them fresh each time like any generated layer's lines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedLayer {
+    source: Vec<Vec<Point2D>>,
+    #[serde(skip)]
+    lines: Vec<Vec<Point2D>>,
+}
+
+impl ImportedLayer {
+    /// A layer drawing `lines`, ready to export without generating
+    pub fn new(lines: Vec<Vec<Point2D>>) -> Self {
+        ImportedLayer {
+            source: lines.clone(),
+            lines,
+        }
+    }
+
+    /// Restore the stored polylines
+    pub fn generate(&mut self) {
+        self.lines = self.source.clone();
+    }
+
+    /// Get the lines
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.lines
+    }
+
+    /// Mutable access to the lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply the stored and current lines by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        scale_polylines(&mut self.source, factor);
+        scale_polylines(&mut self.lines, factor);
+    }
+}
+
+impl PatternLayer for ImportedLayer {
+    fn generate(&mut self) {
+        ImportedLayer::generate(self)
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for ImportedLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        ImportedLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+}
+
+impl LayerType for ImportedLayer {
+    const KIND: LayerKind = LayerKind::Imported;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClousDeParisConfig, ClousDeParisLayer};
+
+    fn assert_close(p: Point2D, x: f64, y: f64) {
+        assert!(
+            (p.x - x).abs() < 1e-9 && (p.y - y).abs() < 1e-9,
+            "({}, {}) != ({}, {})",
+            p.x,
+            p.y,
+            x,
+            y
+        );
+    }
+
+    #[test]
+    fn test_clous_de_paris_svg_round_trip() {
+        let mut layer = ClousDeParisLayer::new(ClousDeParisConfig::new(1.5, 12.0)).unwrap();
+        layer.generate();
+        let file = std::env::temp_dir().join("test_import_clous_round_trip.svg");
+        layer.to_svg(file.to_str().unwrap()).unwrap();
+
+        let import = svg_import(file.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&file).ok();
+        assert!(import.warnings.is_empty());
+
+        // Degenerate lines are left out of the export
+        let original: Vec<&Vec<Point2D>> = layer
+            .lines()
+            .iter()
+            .filter(|line| !crate::common::polyline_is_degenerate(line))
+            .collect();
+        assert_eq!(import.lines.len(), original.len());
+        for (imported, original) in import.lines.iter().zip(original) {
+            assert_eq!(imported.len(), original.len());
+            for (a, b) in imported.iter().zip(original) {
+                // The exporter rounds coordinates to 4 decimals and the
+                // importer reads single-precision numbers
+                let tolerance = 0.5e-4 + 1e-6 * b.x.abs().max(b.y.abs()).max(1.0);
+                assert!((a.x - b.x).abs() <= tolerance && (a.y - b.y).abs() <= tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nested_transforms_apply_innermost_first() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg">
+            <g transform="translate(10, 20)">
+                <g transform="rotate(90) scale(2)">
+                    <path transform="matrix(1 0 0 1 1 0)" d="M0 0 L1 0"/>
+                </g>
+            </g>
+            <path d="M0 0 L0 1"/>
+        </svg>"#;
+        let lines = svg_polylines(svg).unwrap();
+        assert_eq!(lines.len(), 2);
+        // (0,0) -> (1,0) -> (2,0) -> (0,2) -> (10,22)
+        assert_close(lines[0][0], 10.0, 22.0);
+        assert_close(lines[0][1], 10.0, 24.0);
+        // The groups closed before the second path
+        assert_close(lines[1][1], 0.0, 1.0);
+    }
+
+    #[test]
+    fn test_rotate_about_point_and_skew() {
+        let rotate = parse_transform("rotate(180 5 5)").unwrap();
+        assert_close(rotate.apply(0.0, 0.0), 10.0, 10.0);
+        let skew = parse_transform("skewX(45)").unwrap();
+        assert_close(skew.apply(0.0, 1.0), 1.0, 1.0);
+        assert!(parse_transform("perspective(3)").is_err());
+        assert!(parse_transform("translate(1 2 3)").is_err());
+    }
+
+    #[test]
+    fn test_relative_commands_and_closepath() {
+        let svg = r#"<svg><path d="m1 1 h2 v2 H1 z M5 5 l1 0 m1 1 l1 1"/></svg>"#;
+        let lines = svg_polylines(svg).unwrap();
+        assert_eq!(lines.len(), 3);
+        let square: Vec<(f64, f64)> = lines[0].iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(
+            square,
+            vec![(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0), (1.0, 1.0)]
+        );
+        // A relative moveto after a line starts from the line's end
+        assert_close(lines[2][0], 7.0, 6.0);
+        assert_close(lines[2][1], 8.0, 7.0);
+    }
+
+    #[test]
+    fn test_curves_are_skipped_with_warnings() {
+        let svg = r#"<svg>
+            <path d="M0 0 L1 1"/>
+            <path id="swoosh" d="M0 0 C1 1 2 1 3 0"/>
+            <path d="M0 0 A1 1 0 0 1 2 0"/>
+            <defs><path d="M0 0 L5 5"/></defs>
+        </svg>"#;
+        let import = svg_import(svg).unwrap();
+        assert_eq!(import.lines.len(), 1);
+        assert_eq!(import.warnings.len(), 2);
+        assert!(import.warnings[0].contains("\"swoosh\""));
+        assert!(import.warnings[0].contains("cubic curve"));
+        assert!(import.warnings[1].starts_with("path 3"));
+        assert!(import.warnings[1].contains("arc"));
+    }
+
+    #[test]
+    fn test_unreadable_input_is_an_error() {
+        assert!(svg_polylines("/no/such/file.svg").is_err());
+        assert!(
+            svg_polylines(r#"<svg><g transform="wobble(1)"><path d="M0 0 L1 1"/></g></svg>"#)
+                .is_err()
+        );
+    }
+}
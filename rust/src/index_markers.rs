@@ -0,0 +1,562 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    debug_assert_finite, polyline_is_degenerate, scale_polylines, simplified_layer,
+    simplify_polylines, ExportConfig, PatternLayer, Point2D, PointsTotal, SpirographError,
+    StoredLayer,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::watch_face::LayerStyle;
+
+/// Outline drawn at every index position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum IndexShape {
+    /// Radial baton `length` mm along the radius, drawn as a closed bar
+    /// `width` mm across it, or as a single stroke when `width` is 0
+    Line { length: f64, width: f64 },
+    /// Closed circle of `radius` mm
+    Dot { radius: f64 },
+    /// Single-stroke Roman numeral `height` mm tall, reading upright with
+    /// its top towards the dial edge; 4 o'clock is the dial-maker's IIII
+    RomanNumeralPlaceholder { height: f64 },
+}
+
+impl IndexShape {
+    /// Extent of the shape along the radius
+    fn radial_extent(&self) -> f64 {
+        match *self {
+            IndexShape::Line { length, .. } => length,
+            IndexShape::Dot { radius } => 2.0 * radius,
+            IndexShape::RomanNumeralPlaceholder { height } => height,
+        }
+    }
+
+    /// Multiply the shape's lengths by `factor`
+    fn scale_lengths(&mut self, factor: f64) {
+        match self {
+            IndexShape::Line { length, width } => {
+                *length *= factor;
+                *width *= factor;
+            }
+            IndexShape::Dot { radius } => *radius *= factor,
+            IndexShape::RomanNumeralPlaceholder { height } => *height *= factor,
+        }
+    }
+}
+
+/// Configuration for a set of hour or minute index markers
+///
+/// Markers sit at `count` evenly spaced positions clockwise from
+/// 12 o'clock, each centered halfway between `inner_radius` and
+/// `outer_radius` and no longer along the radius than the band between
+/// them. Positions listed in `exclude` are left empty, for instance where
+/// a date window or subdial covers them. They are clock positions as for
+/// `add_hole_at_clock`, read like the hour hand: with 60 markers the mark
+/// for minute 16 is at (3, 12).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexConfig {
+    /// Outline of every marker
+    pub shape: IndexShape,
+    /// Number of positions: 12 for hours or 60 for minutes
+    pub count: usize,
+    /// Inner radius of the band holding the markers in mm
+    pub inner_radius: f64,
+    /// Outer radius of the band holding the markers in mm
+    pub outer_radius: f64,
+    /// Clock positions (hour, minute) to leave without a marker
+    pub exclude: Vec<(u32, u32)>,
+    /// Stroke for the markers in watch face SVG exports; `None` keeps the
+    /// face's default stroke
+    pub style: Option<LayerStyle>,
+    /// Number of points around each dot
+    pub resolution: PointsTotal,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        IndexConfig {
+            shape: IndexShape::Line {
+                length: 2.0,
+                width: 0.4,
+            },
+            count: 12,
+            inner_radius: 15.0,
+            outer_radius: 18.0,
+            exclude: Vec::new(),
+            style: None,
+            resolution: PointsTotal(32),
+        }
+    }
+}
+
+impl IndexConfig {
+    /// Create twelve hour markers of `shape` between two radii
+    ///
+    /// # Arguments
+    /// * `shape` - Outline of every marker
+    /// * `inner_radius` - Inner radius of the band holding the markers
+    /// * `outer_radius` - Outer radius of the band holding the markers
+    pub fn new(shape: IndexShape, inner_radius: f64, outer_radius: f64) -> Self {
+        IndexConfig {
+            shape,
+            inner_radius,
+            outer_radius,
+            ..Default::default()
+        }
+    }
+
+    /// Use `count` positions (12 or 60)
+    pub fn with_count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Leave the position at `hour`:`minute` without a marker
+    pub fn excluding(mut self, hour: u32, minute: u32) -> Self {
+        self.exclude.push((hour, minute));
+        self
+    }
+
+    /// Stroke the markers with `style` in watch face SVG exports
+    pub fn with_style(mut self, style: LayerStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Check the count, radii, shape, exclusions and style
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.count != 12 && self.count != 60 {
+            return Err(SpirographError::InvalidParameter(format!(
+                "index count must be 12 or 60, got {}",
+                self.count
+            )));
+        }
+        if !(self.inner_radius.is_finite() && self.inner_radius > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "inner_radius must be positive".to_string(),
+            ));
+        }
+        if !(self.outer_radius.is_finite() && self.outer_radius > self.inner_radius) {
+            return Err(SpirographError::InvalidParameter(
+                "outer_radius must be greater than inner_radius".to_string(),
+            ));
+        }
+        if self.resolution.get() < 8 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 8".to_string(),
+            ));
+        }
+        let band = self.outer_radius - self.inner_radius;
+        let extent = self.shape.radial_extent();
+        if !(extent.is_finite() && extent > 0.0 && extent <= band) {
+            return Err(SpirographError::InvalidParameter(format!(
+                "index markers must have a positive size no longer than the band ({:.3})",
+                band
+            )));
+        }
+        // Neighbouring markers must not touch at the inner radius
+        let pitch = 2.0 * self.inner_radius * (PI / self.count as f64).sin();
+        match self.shape {
+            IndexShape::Line { width, .. } => {
+                if !(width.is_finite() && width >= 0.0 && width < pitch) {
+                    return Err(SpirographError::InvalidParameter(format!(
+                        "index width must be non-negative and below the marker pitch ({:.3})",
+                        pitch
+                    )));
+                }
+            }
+            IndexShape::Dot { radius } => {
+                if 2.0 * radius >= pitch {
+                    return Err(SpirographError::InvalidParameter(format!(
+                        "index dots must be narrower than the marker pitch ({:.3})",
+                        pitch
+                    )));
+                }
+            }
+            IndexShape::RomanNumeralPlaceholder { .. } => {
+                if self.count != 12 {
+                    return Err(SpirographError::InvalidParameter(
+                        "Roman numeral indices need a count of 12".to_string(),
+                    ));
+                }
+            }
+        }
+        for &(hour, minute) in &self.exclude {
+            let dial_minutes = (hour % 12) as usize * 60 + minute as usize;
+            if minute >= 60 || !(dial_minutes * self.count).is_multiple_of(720) {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "excluded position {}:{:02} is not one of the {} index positions",
+                    hour, minute, self.count
+                )));
+            }
+        }
+        if let Some(style) = &self.style {
+            style.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Radius halfway across the band, where every marker is centered
+    pub fn mid_radius(&self) -> f64 {
+        (self.inner_radius + self.outer_radius) / 2.0
+    }
+
+    /// Angle of position `index`, 0 at 12 o'clock and running clockwise on the dial
+    pub fn position_angle(&self, index: usize) -> f64 {
+        -PI / 2.0 + 2.0 * PI * index as f64 / self.count as f64
+    }
+
+    /// True if position `index` is listed in `exclude`
+    pub fn is_excluded(&self, index: usize) -> bool {
+        let dial_minutes = index * 720 / self.count;
+        self.exclude
+            .iter()
+            .any(|&(hour, minute)| (hour % 12) as usize * 60 + minute as usize == dial_minutes)
+    }
+}
+
+/// Hour or minute index markers generated as engraved outlines
+///
+/// Always centered on the dial. Every marker is made of polylines, so the
+/// markers are cut into STL and STEP exports like any other stroke rather
+/// than only being drawn in SVG.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexMarkersLayer {
+    pub config: IndexConfig,
+    #[serde(skip)]
+    lines: Vec<Vec<Point2D>>,
+}
+
+impl IndexMarkersLayer {
+    /// Create an index markers layer, validating the configuration
+    pub fn new(config: IndexConfig) -> Result<Self, SpirographError> {
+        config.validate()?;
+        Ok(IndexMarkersLayer {
+            config,
+            lines: Vec::new(),
+        })
+    }
+
+    /// Generate the strokes of every marker not excluded, clockwise from
+    /// 12 o'clock
+    pub fn generate(&mut self) {
+        self.lines = self.markers().into_iter().flat_map(|(_, m)| m).collect();
+        debug_assert_finite("IndexMarkersLayer", &self.lines);
+    }
+
+    /// The strokes of every marker drawn, with its position index
+    ///
+    /// Bars and dots are single closed outlines; a numeral is one stroke
+    /// per straight line of its letters.
+    pub fn markers(&self) -> Vec<(usize, Vec<Vec<Point2D>>)> {
+        let config = &self.config;
+        let mid = config.mid_radius();
+        (0..config.count)
+            .filter(|&i| !config.is_excluded(i))
+            .map(|i| {
+                let (sin, cos) = config.position_angle(i).sin_cos();
+                // Point at `along` the radius from the mid-radius and `across`
+                // it (clockwise positive)
+                let at = |along: f64, across: f64| {
+                    let r = mid + along;
+                    Point2D::new(r * cos - across * sin, r * sin + across * cos)
+                };
+                let strokes = match config.shape {
+                    IndexShape::Line { length, width } => {
+                        let (l, w) = (length / 2.0, width / 2.0);
+                        if w > 0.0 {
+                            vec![vec![at(-l, -w), at(l, -w), at(l, w), at(-l, w), at(-l, -w)]]
+                        } else {
+                            vec![vec![at(-l, 0.0), at(l, 0.0)]]
+                        }
+                    }
+                    IndexShape::Dot { radius } => {
+                        let steps = config.resolution.get();
+                        vec![(0..=steps)
+                            .map(|k| {
+                                let t = 2.0 * PI * (k % steps) as f64 / steps as f64;
+                                at(radius * t.sin(), radius * t.cos())
+                            })
+                            .collect()]
+                    }
+                    IndexShape::RomanNumeralPlaceholder { height } => {
+                        let hour = if i == 0 { 12 } else { i };
+                        roman_strokes(hour, height)
+                            .into_iter()
+                            .map(|stroke| stroke.into_iter().map(|(x, y)| at(y, x)).collect())
+                            .collect()
+                    }
+                };
+                (i, strokes)
+            })
+            .collect()
+    }
+
+    /// Get the generated strokes of every marker, clockwise from 12 o'clock
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.lines
+    }
+
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply every length (radii, shape and lines) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.inner_radius *= factor;
+        self.config.outer_radius *= factor;
+        self.config.shape.scale_lengths(factor);
+        scale_polylines(&mut self.lines, factor);
+    }
+
+    /// Export the index markers to SVG format
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+
+        if self.lines.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.lines.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        for line in &layer.lines {
+            if polyline_is_degenerate(line) {
+                continue;
+            }
+            writer.add(writer.polyline_node(line, false, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+                    .set("stroke-linecap", "round")
+                    .set("stroke-linejoin", "round")
+            }));
+        }
+
+        writer.save(filename)
+    }
+}
+
+impl PatternLayer for IndexMarkersLayer {
+    fn generate(&mut self) {
+        IndexMarkersLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for IndexMarkersLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        IndexMarkersLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn stroke_width(&self) -> f64 {
+        0.05
+    }
+}
+
+impl LayerType for IndexMarkersLayer {
+    const KIND: LayerKind = LayerKind::IndexMarkers;
+}
+
+/// Strokes of the Roman numeral for `hour` (1 to 12) as (x, y) points, `x`
+/// across the numeral and centered on 0, `y` from `-height / 2` at the
+/// foot to `height / 2` at the top
+fn roman_strokes(hour: usize, height: f64) -> Vec<Vec<(f64, f64)>> {
+    const LETTERS: [&str; 12] = [
+        "I", "II", "III", "IIII", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII",
+    ];
+    let letters = LETTERS[(hour - 1) % 12];
+    let (wide, gap) = (0.5 * height, 0.15 * height);
+    let width = |letter: char| if letter == 'I' { 0.0 } else { wide };
+    let total =
+        letters.chars().map(width).sum::<f64>() + gap * (letters.chars().count() - 1) as f64;
+    let (foot, top) = (-height / 2.0, height / 2.0);
+
+    let mut x = -total / 2.0;
+    let mut strokes = Vec::new();
+    for letter in letters.chars() {
+        match letter {
+            'I' => strokes.push(vec![(x, foot), (x, top)]),
+            'V' => strokes.push(vec![(x, top), (x + wide / 2.0, foot), (x + wide, top)]),
+            _ => {
+                strokes.push(vec![(x, foot), (x + wide, top)]);
+                strokes.push(vec![(x, top), (x + wide, foot)]);
+            }
+        }
+        x += width(letter) + gap;
+    }
+    strokes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::clock_to_cartesian;
+
+    fn radius(p: Point2D) -> f64 {
+        p.x.hypot(p.y)
+    }
+
+    #[test]
+    fn test_line_markers_sit_on_the_hours_within_the_band() {
+        let shape = IndexShape::Line {
+            length: 2.0,
+            width: 0.5,
+        };
+        let mut layer = IndexMarkersLayer::new(IndexConfig::new(shape, 15.0, 18.0)).unwrap();
+        layer.generate();
+        assert_eq!(layer.lines().len(), 12);
+
+        for (i, bar) in layer.lines().iter().enumerate() {
+            assert_eq!(bar.len(), 5);
+            assert_eq!(bar.first(), bar.last());
+            let n = 4.0;
+            let center = Point2D::new(
+                bar[..4].iter().map(|p| p.x).sum::<f64>() / n,
+                bar[..4].iter().map(|p| p.y).sum::<f64>() / n,
+            );
+            let hour = if i == 0 { 12 } else { i as u32 };
+            let (x, y) = clock_to_cartesian(hour, 0, 16.5);
+            assert!((center.x - x).abs() < 1e-9 && (center.y - y).abs() < 1e-9);
+            assert!((crate::cells::polygon_area(&bar[..4]).abs() - 1.0).abs() < 1e-9);
+            // The ends lie 1 mm either side of the mid-radius
+            let along: Vec<f64> = bar[..4].iter().map(|&p| radius(p)).collect();
+            assert!(along.iter().all(|&r| r > 15.4 && r < 17.6));
+        }
+
+        // A zero width gives single radial strokes
+        let shape = IndexShape::Line {
+            length: 3.0,
+            width: 0.0,
+        };
+        let mut layer = IndexMarkersLayer::new(IndexConfig::new(shape, 15.0, 18.0)).unwrap();
+        layer.generate();
+        for stroke in layer.lines() {
+            assert_eq!(stroke.len(), 2);
+            assert!((radius(stroke[0]) - 15.0).abs() < 1e-9);
+            assert!((radius(stroke[1]) - 18.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_excluded_positions_are_skipped() {
+        let config = IndexConfig::new(IndexShape::Dot { radius: 0.3 }, 16.0, 18.0)
+            .with_count(60)
+            .excluding(3, 0)
+            .excluding(6, 36);
+        let mut layer = IndexMarkersLayer::new(config).unwrap();
+        layer.generate();
+        let positions: Vec<usize> = layer.markers().iter().map(|(i, _)| *i).collect();
+        assert_eq!(positions.len(), 58);
+        assert!(!positions.contains(&15) && !positions.contains(&33));
+        assert_eq!(layer.lines().len(), 58);
+
+        for dot in layer.lines() {
+            assert_eq!(dot.len(), 33);
+            assert_eq!(dot.first(), dot.last());
+            assert!(dot
+                .iter()
+                .all(|&p| (16.7 - 1e-9..=17.3 + 1e-9).contains(&radius(p))));
+        }
+        // Hour 15 is the same position as 3 o'clock
+        let config = IndexConfig::default().excluding(15, 0);
+        assert!(config.is_excluded(3));
+    }
+
+    #[test]
+    fn test_roman_numerals_read_outwards() {
+        let shape = IndexShape::RomanNumeralPlaceholder { height: 2.0 };
+        let mut layer = IndexMarkersLayer::new(IndexConfig::new(shape, 14.0, 17.0)).unwrap();
+        layer.generate();
+        let markers = layer.markers();
+        let strokes: Vec<usize> = markers.iter().map(|(_, m)| m.len()).collect();
+        // XII, I, II, III, IIII, V, VI, VII, VIII, IX, X, XI
+        assert_eq!(strokes, vec![4, 1, 2, 3, 4, 1, 2, 3, 4, 3, 2, 3]);
+        // Every point lies within 1 mm of the mid-radius along the radius
+        for (i, marker) in &markers {
+            let (sin, cos) = layer.config.position_angle(*i).sin_cos();
+            for &p in marker.iter().flatten() {
+                let along = p.x * cos + p.y * sin;
+                assert!((14.5 - 1e-9..=16.5 + 1e-9).contains(&along));
+            }
+        }
+        // At 12 o'clock (y up the screen is negative) the single I of XII
+        // runs from the foot at r = 14.5 to the top at r = 16.5
+        let stroke = &markers[0].1[2];
+        assert!((stroke[0].y + 14.5).abs() < 1e-9 && (stroke[1].y + 16.5).abs() < 1e-9);
+        assert!(stroke[0].x > 0.0);
+    }
+
+    #[test]
+    fn test_index_validation() {
+        let bad = |config: IndexConfig| IndexMarkersLayer::new(config).is_err();
+        let line = |length, width| IndexShape::Line { length, width };
+        assert!(bad(IndexConfig::default().with_count(24)));
+        assert!(bad(IndexConfig::new(line(2.0, 0.4), 18.0, 15.0)));
+        assert!(bad(IndexConfig::new(line(4.0, 0.4), 15.0, 18.0)));
+        assert!(bad(IndexConfig::new(line(2.0, -0.1), 15.0, 18.0)));
+        assert!(bad(
+            IndexConfig::new(line(2.0, 2.0), 15.0, 18.0).with_count(60)
+        ));
+        assert!(bad(IndexConfig::new(
+            IndexShape::Dot { radius: 0.0 },
+            15.0,
+            18.0
+        )));
+        assert!(bad(IndexConfig::new(
+            IndexShape::RomanNumeralPlaceholder { height: 2.0 },
+            15.0,
+            18.0
+        )
+        .with_count(60)));
+        assert!(bad(IndexConfig::default().excluding(3, 30)));
+        assert!(bad(IndexConfig::default().excluding(3, 60)));
+        assert!(!bad(IndexConfig::default().with_count(60).excluding(3, 36)));
+        assert!(bad(
+            IndexConfig::default().with_style(LayerStyle::new("#000", -1.0, 1.0))
+        ));
+    }
+}
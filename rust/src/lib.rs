@@ -1,49 +1,220 @@
+// Planar cell extraction and inset contours for openworked dials
+pub mod cells;
+// Chapter ring (minute track or border motif) generation
+pub mod chapter_ring;
 // Common types shared across modules
 pub mod common;
 // Diamant (diamond) pattern generation
 pub mod diamant;
+// Line-set comparison for previewing changes between generations
+pub mod diff;
+// Helpers for the documentation examples
+#[doc(hidden)]
+pub mod doctest_support;
+// DXF polyline export
+mod dxf;
+// Single-stroke engraved text and numerals
+pub mod engrave_text;
 // Draperie (drapery) pattern generation
 pub mod draperie;
 // Flinque (engine-turned) pattern generation
 pub mod flinque;
+// Grain-de-riz (rice grain) pattern generation
+pub mod grain_de_riz;
 // Spirograph and guilloche pattern generation modules
 pub mod guilloche;
+// Closed groove solids swept along tool paths for STL export
+mod groove;
+// HPGL export for pen plotters
+pub mod hpgl;
 // Huit-Eight (Figure-Eight) pattern generation
 pub mod huiteight;
+// Hour and minute index markers
+pub mod index_markers;
+// Straight-line polylines read back from SVG files
+pub mod import;
 // Limaçon pattern generation
 pub mod limacon;
+// Traceability metadata embedded in exported files
+pub mod metadata;
 // Clous de Paris (Hobnail) pattern generation
 pub mod clous_de_paris;
+// Côtes de Genève (Geneva stripes) pattern generation
+pub mod cotes_de_geneve;
 // Cube (tumbling blocks) pattern generation
 pub mod cube;
+// Design-review SVG overlay (grid, rays, reference circles)
+pub mod overlay;
 // Paon (Peacock) pattern generation
 pub mod paon;
+// Phyllotaxis (sunflower spiral) pattern generation
+pub mod phyllotaxis;
+// Post-generation line edits (nudges, dropped lines)
+pub mod post;
+// Coarse-to-fine progressive generation for interactive previews
+pub mod progressive;
+// Seeded random watch face designs
+pub mod random_design;
+// 1-bit raster export for laser engravers
+pub mod raster;
+// Gradient-filled bands between concentric rings for shaded SVG previews
+mod shading;
+// Soleil (sunray) pattern generation
+pub mod soleil;
 pub mod spirograph;
+// STEP wireframe assembly export
+mod step;
+// Tapisserie (tapestry, waffle) pattern generation
+pub mod tapisserie;
+// Vague (wave, flammes) pattern generation
+pub mod vague;
 // Rose engine lathe module
 pub mod rose_engine;
 // Watch face wrapper
 pub mod watch_face;
 
 // Re-export main types for convenience
+pub use cells::{inset_polygon, planar_cells, polygon_area};
+pub use chapter_ring::{ChapterMotif, ChapterRingConfig, ChapterRingLayer};
 pub use clous_de_paris::{ClousDeParisConfig, ClousDeParisLayer};
+pub use common::svg_writer::{SvgWriter, DEFAULT_SVG_PRECISION};
 pub use common::{
-    clock_to_cartesian, polar_to_cartesian, validate_radius, ExportConfig, Point2D, Point3D,
-    SpirographError,
+    clip_polyline_to_circle, clock_to_cartesian, dedupe_consecutive, first_non_finite, fit_arcs,
+    geometry_hash, normalize_angle, offset_polyline, peak_alignment_phase, polar_to_cartesian,
+    polyline_bounds, polyline_closes, polyline_is_degenerate, simplify_polyline, split_polyline,
+    validate_radius, validate_radius_in, DashSpec, DialShape, Envelope, ExportConfig,
+    ExportSurface, GeometryStats, JitterConfig, OriginMode, PatternLayer, Point2D, Point3D,
+    PointsPerRev, PointsTotal, ProjectSettings, RadialWave, RadiusPolicy, SpirographError, Unit,
+    DEDUPE_EPSILON, DEFAULT_MAX_POINTS_PER_PATH, MITER_LIMIT,
 };
+pub use cotes_de_geneve::{CotesDeGeneveConfig, CotesDeGeneveLayer};
 pub use cube::{CubeConfig, CubeLayer};
 pub use diamant::{DiamantConfig, DiamantLayer};
-pub use draperie::{DraperieConfig, DraperieLayer};
+pub use diff::DiffSummary;
+pub use draperie::{
+    DraperieConfig, DraperieLayer, FrequencyNormalization, NonIntegerMode, MAX_CLOSING_REVOLUTIONS,
+};
+pub use engrave_text::{ArcDirection, EngravedTextConfig, EngravedTextLayer, TextPosition};
 pub use flinque::{FlinqueConfig, FlinqueLayer};
-pub use guilloche::GuillochePattern;
+pub use grain_de_riz::{GrainDeRizConfig, GrainDeRizLayer};
+pub use guilloche::{GenerateOutcome, GuillochePattern, LayerSpec, LayerValidationIssue};
 pub use huiteight::{HuitEightConfig, HuitEightLayer};
-pub use limacon::{LimaconConfig, LimaconLayer};
-pub use paon::{paon_wave_fn, PaonConfig, PaonLayer};
+pub use import::{svg_import, svg_polylines, ImportedLayer, SvgImport};
+pub use index_markers::{IndexConfig, IndexMarkersLayer, IndexShape};
+pub use limacon::{LimaconConfig, LimaconLayer, LimaconLoopMode};
+pub use metadata::FileMetadata;
+pub use overlay::OverlayOptions;
+pub use paon::{paon_wave_fn, PaonConfig, PaonLayer, PaonOrigin};
+pub use phyllotaxis::{PhylloEdge, PhylloStyle, PhyllotaxisConfig, PhyllotaxisLayer};
+pub use post::LayerKind;
+pub use progressive::{Progressive, ProgressiveHandle, ProgressiveSchedule};
+pub use random_design::{
+    generate_random_design, Density, DesignConstraints, DesignRecipe, MotifRecipe,
+};
+pub use raster::{Bitmap1, Dither, RgbaImage};
 pub use rose_engine::{
-    Arc, BitShape, CuttingBit, RenderedOutput, RoseEngineConfig, RoseEngineLathe,
-    RoseEngineLatheRun, RosettePattern, ToolPathOutput,
+    Arc, BandSpec, BitShape, CuttingBit, DepthPassOrder, GcodeParams, MachineJob, MachinePass,
+    PassOverride, PassWear, PathSegment, RenderedOutput, RoseEngineConfig, RoseEngineLathe,
+    RoseEngineLatheRun, RosetteMapping, RosettePattern, SimFrame, SpindleDirection, SurfaceMapping,
+    SvgColorMode, SvgStyle, ToolPathOutput, WearModel,
+};
+pub use soleil::{SoleilConfig, SoleilLayer};
+pub use spirograph::{
+    closure_rotations, HorizontalSpirograph, SphericalSpirograph, SpirographMode,
+    VerticalSpirograph,
+};
+pub use tapisserie::{TapisserieConfig, TapisserieLayer};
+pub use vague::{VagueConfig, VagueLayer};
+pub use watch_face::edit::{EditCommand, EditHistory, UndoToken};
+pub use watch_face::hands::{HandConfig, HandStyle, HandsSet};
+pub use watch_face::{
+    ApertureConfig, ApertureShape, BezelConfig, CellDecoration, DialConfig, HoleConfig, LayerStyle,
+    LodFile, LodSpec, ReferenceImage, Subdial, WatchFace,
+};
+
+// Compile-time guarantee that patterns can be generated and exported from
+// multiple threads. Adding a non-Send/Sync field to any of these types is a
+// build error rather than a silent downstream breakage.
+const fn assert_send_sync<T: Send + Sync>() {}
+const _: () = {
+    assert_send_sync::<WatchFace>();
+    assert_send_sync::<EditHistory>();
+    assert_send_sync::<DesignRecipe>();
+    assert_send_sync::<DesignConstraints>();
+    assert_send_sync::<GuillochePattern>();
+    assert_send_sync::<guilloche::SpirographLayer>();
+    assert_send_sync::<guilloche::GuillocheLayer>();
+    assert_send_sync::<HorizontalSpirograph>();
+    assert_send_sync::<VerticalSpirograph>();
+    assert_send_sync::<SphericalSpirograph>();
+    assert_send_sync::<FlinqueLayer>();
+    assert_send_sync::<FlinqueConfig>();
+    assert_send_sync::<DiamantLayer>();
+    assert_send_sync::<DiamantConfig>();
+    assert_send_sync::<DraperieLayer>();
+    assert_send_sync::<DraperieConfig>();
+    assert_send_sync::<EngravedTextLayer>();
+    assert_send_sync::<EngravedTextConfig>();
+    assert_send_sync::<HuitEightLayer>();
+    assert_send_sync::<HuitEightConfig>();
+    assert_send_sync::<LimaconLayer>();
+    assert_send_sync::<LimaconConfig>();
+    assert_send_sync::<PaonLayer>();
+    assert_send_sync::<PaonConfig>();
+    assert_send_sync::<ClousDeParisLayer>();
+    assert_send_sync::<ClousDeParisConfig>();
+    assert_send_sync::<CubeLayer>();
+    assert_send_sync::<CubeConfig>();
+    assert_send_sync::<PhyllotaxisLayer>();
+    assert_send_sync::<PhyllotaxisConfig>();
+    assert_send_sync::<GrainDeRizLayer>();
+    assert_send_sync::<GrainDeRizConfig>();
+    assert_send_sync::<SoleilLayer>();
+    assert_send_sync::<SoleilConfig>();
+    assert_send_sync::<CotesDeGeneveLayer>();
+    assert_send_sync::<CotesDeGeneveConfig>();
+    assert_send_sync::<TapisserieLayer>();
+    assert_send_sync::<TapisserieConfig>();
+    assert_send_sync::<VagueLayer>();
+    assert_send_sync::<VagueConfig>();
+    assert_send_sync::<ChapterRingLayer>();
+    assert_send_sync::<ChapterRingConfig>();
+    assert_send_sync::<ChapterMotif>();
+    assert_send_sync::<IndexMarkersLayer>();
+    assert_send_sync::<IndexConfig>();
+    assert_send_sync::<IndexShape>();
+    assert_send_sync::<ImportedLayer>();
+    assert_send_sync::<RoseEngineLatheRun>();
+    assert_send_sync::<BandSpec>();
+    assert_send_sync::<PassOverride>();
+    assert_send_sync::<RoseEngineLathe>();
+    assert_send_sync::<RoseEngineConfig>();
+    assert_send_sync::<RosettePattern>();
+    assert_send_sync::<CuttingBit>();
+    assert_send_sync::<WearModel>();
+    assert_send_sync::<PassWear>();
+    assert_send_sync::<ExportConfig>();
+    assert_send_sync::<DialShape>();
+    assert_send_sync::<Unit>();
+    assert_send_sync::<OriginMode>();
+    assert_send_sync::<ProjectSettings>();
+    assert_send_sync::<RadialWave>();
+    assert_send_sync::<Envelope>();
+    assert_send_sync::<DashSpec>();
+    assert_send_sync::<OverlayOptions>();
+    assert_send_sync::<ProgressiveSchedule>();
+    assert_send_sync::<RosetteMapping>();
+    assert_send_sync::<MachineJob>();
+    assert_send_sync::<GcodeParams>();
+    assert_send_sync::<SimFrame>();
+    assert_send_sync::<DialConfig>();
+    assert_send_sync::<BezelConfig>();
+    assert_send_sync::<HoleConfig>();
+    assert_send_sync::<ApertureConfig>();
+    assert_send_sync::<CellDecoration>();
+    assert_send_sync::<LayerStyle>();
+    assert_send_sync::<DiffSummary>();
 };
-pub use spirograph::{HorizontalSpirograph, SphericalSpirograph, VerticalSpirograph};
-pub use watch_face::{BezelConfig, DialConfig, HoleConfig, WatchFace};
 
 /**********************************/
 // #[cfg(test)]
@@ -1,6 +1,57 @@
 use std::f64::consts::PI;
 
-use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographError};
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_polylines, polar_to_cartesian,
+    polyline_is_degenerate, scale_polylines, simplified_layer, simplify_polylines, ExportConfig,
+    PatternLayer, Point2D, PointsTotal, SpirographError, StoredLayer, DEDUPE_EPSILON,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
+
+/// Which part of each limaçon is drawn
+///
+/// When the amplitude exceeds the base radius, ρ(θ) goes negative over an
+/// arc and the curve folds through the center into an inner loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LimaconLoopMode {
+    /// The whole curve, inner loop included
+    #[default]
+    Full,
+    /// Only the outer curve, where ρ ≥ 0; the inner loop is trimmed
+    OuterOnly,
+    /// Only the inner loop, where ρ < 0
+    InnerOnly,
+}
+
+impl LimaconLoopMode {
+    /// Lower-case name, as used by the Python bindings
+    pub fn name(self) -> &'static str {
+        match self {
+            LimaconLoopMode::Full => "full",
+            LimaconLoopMode::OuterOnly => "outer_only",
+            LimaconLoopMode::InnerOnly => "inner_only",
+        }
+    }
+
+    /// The mode whose `name()` is `name`
+    pub fn from_name(name: &str) -> Option<LimaconLoopMode> {
+        [
+            LimaconLoopMode::Full,
+            LimaconLoopMode::OuterOnly,
+            LimaconLoopMode::InnerOnly,
+        ]
+        .into_iter()
+        .find(|mode| mode.name() == name)
+    }
+}
+
+/// ρ values within this of zero count as on the outer curve, so a cardioid's
+/// cusp does not split it
+const LOOP_EPSILON: f64 = 1e-9;
 
 /// Configuration for the Limaçon guilloché pattern
 ///
@@ -9,7 +60,7 @@ use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographE
 /// same output as a rose engine with a sinusoidal rosette of frequency 1.
 ///
 /// The limaçon equation in polar form is: r = base_radius + amplitude * sin(θ + phase)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LimaconConfig {
     /// Number of limaçon curves to draw (more = denser mesh)
     pub num_curves: usize,
@@ -18,7 +69,10 @@ pub struct LimaconConfig {
     /// Amplitude of the sinusoidal modulation
     pub amplitude: f64,
     /// Resolution - number of points per curve
-    pub resolution: usize,
+    pub resolution: PointsTotal,
+    /// Which part of each curve is drawn
+    #[serde(default)]
+    pub loop_mode: LimaconLoopMode,
 }
 
 impl Default for LimaconConfig {
@@ -27,7 +81,8 @@ impl Default for LimaconConfig {
             num_curves: 72,
             base_radius: 20.0,
             amplitude: 20.0,
-            resolution: 360,
+            resolution: PointsTotal(360),
+            loop_mode: LimaconLoopMode::Full,
         }
     }
 }
@@ -44,15 +99,147 @@ impl LimaconConfig {
             num_curves,
             base_radius,
             amplitude,
-            resolution: 360,
+            resolution: PointsTotal(360),
+            loop_mode: LimaconLoopMode::Full,
         }
     }
 
+    /// Cardioid preset: a limaçon whose amplitude equals its base radius,
+    /// so every curve has a cusp at the center and no inner loop
+    pub fn cardioid(num_curves: usize, radius: f64, resolution: impl Into<PointsTotal>) -> Self {
+        LimaconConfig::new(num_curves, radius, radius).with_resolution(resolution)
+    }
+
     /// Set the resolution (points per curve)
-    pub fn with_resolution(mut self, resolution: usize) -> Self {
-        self.resolution = resolution;
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = resolution.into();
+        self
+    }
+
+    /// Draw only part of each curve; see [`LimaconLoopMode`]
+    pub fn with_loop_mode(mut self, loop_mode: LimaconLoopMode) -> Self {
+        self.loop_mode = loop_mode;
         self
     }
+
+    /// Whether the curves fold through the center into an inner loop
+    pub fn has_inner_loop(&self) -> bool {
+        self.amplitude.abs() > self.base_radius
+    }
+
+    /// Check the curve count, base radius and resolution, and that an
+    /// inner-loop-only config has a loop to draw
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.base_radius <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "base_radius must be positive".to_string(),
+            ));
+        }
+
+        if self.num_curves == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "num_curves must be at least 1".to_string(),
+            ));
+        }
+
+        if self.resolution.get() < 10 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 10".to_string(),
+            ));
+        }
+
+        if self.loop_mode == LimaconLoopMode::InnerOnly && !self.has_inner_loop() {
+            return Err(SpirographError::InvalidParameter(
+                "inner_only needs an amplitude larger than base_radius".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Trace every curve of `config` about (`center_x`, `center_y`)
+///
+/// With a trimmed loop mode a curve is split where ρ changes sign, each
+/// piece ending on the center, so one curve may give several lines (or
+/// none). Shared with the rose engine's limaçon mode.
+pub(crate) fn limacon_curves(
+    config: &LimaconConfig,
+    center_x: f64,
+    center_y: f64,
+) -> Vec<Vec<Point2D>> {
+    let phase_step = 2.0 * PI / (config.num_curves as f64);
+    let resolution = config.resolution.get();
+    let mut curves = Vec::with_capacity(config.num_curves);
+
+    for i in 0..config.num_curves {
+        // Phase offset for this curve (equivalent to rotating the rose engine)
+        let phase = (i as f64) * phase_step;
+
+        // Sample ρ(θ) = base_radius + amplitude * sin(θ + phase) and convert to Cartesian
+        let samples: Vec<(f64, Point2D)> = (0..=resolution)
+            .map(|j| {
+                let theta = 2.0 * PI * (j as f64) / (resolution as f64);
+                let r = config.base_radius + config.amplitude * (theta + phase).sin();
+                (
+                    r,
+                    Point2D::new(center_x + r * theta.cos(), center_y + r * theta.sin()),
+                )
+            })
+            .collect();
+
+        let keep: fn(f64) -> bool = match config.loop_mode {
+            LimaconLoopMode::Full => {
+                curves.push(samples.into_iter().map(|(_, p)| p).collect());
+                continue;
+            }
+            LimaconLoopMode::OuterOnly => |r| r >= -LOOP_EPSILON,
+            LimaconLoopMode::InnerOnly => |r| r < -LOOP_EPSILON,
+        };
+        curves.extend(split_at_sign_changes(
+            &samples,
+            keep,
+            Point2D::new(center_x, center_y),
+        ));
+    }
+
+    curves
+}
+
+/// The runs of a closed curve's samples whose ρ passes `keep`
+///
+/// ρ crosses zero at the center, so each run that starts or ends at a
+/// crossing is extended to `center`. A run through the curve's start and
+/// end is joined into one line.
+fn split_at_sign_changes(
+    samples: &[(f64, Point2D)],
+    keep: fn(f64) -> bool,
+    center: Point2D,
+) -> Vec<Vec<Point2D>> {
+    let mut pieces: Vec<Vec<Point2D>> = Vec::new();
+    let mut current: Vec<Point2D> = Vec::new();
+    for (j, &(r, point)) in samples.iter().enumerate() {
+        if keep(r) {
+            if current.is_empty() && j > 0 {
+                current.push(center);
+            }
+            current.push(point);
+        } else if !current.is_empty() {
+            current.push(center);
+            pieces.push(std::mem::take(&mut current));
+        }
+    }
+    let wraps = !current.is_empty() && !pieces.is_empty() && keep(samples[0].0);
+    if wraps {
+        // The first and last samples are the same point
+        let first = pieces.remove(0);
+        current.extend(first.into_iter().skip(1));
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces.retain(|piece| piece.len() >= 2);
+    pieces
 }
 
 /// A Limaçon pattern layer that creates polar-coordinate guilloché effects
@@ -60,11 +247,12 @@ impl LimaconConfig {
 /// This pattern is created by drawing limaçon curves (r = a + b*sin(θ)) that are
 /// rotated at equal angular intervals. The overlapping curves create intersection
 /// patterns similar to rose engine output with a sinusoidal rosette.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LimaconLayer {
     pub config: LimaconConfig,
     pub center_x: f64,
     pub center_y: f64,
+    #[serde(skip)]
     curves: Vec<Vec<Point2D>>,
 }
 
@@ -80,23 +268,7 @@ impl LimaconLayer {
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        if config.base_radius <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "base_radius must be positive".to_string(),
-            ));
-        }
-
-        if config.num_curves == 0 {
-            return Err(SpirographError::InvalidParameter(
-                "num_curves must be at least 1".to_string(),
-            ));
-        }
-
-        if config.resolution < 10 {
-            return Err(SpirographError::InvalidParameter(
-                "resolution must be at least 10".to_string(),
-            ));
-        }
+        config.validate()?;
 
         Ok(LimaconLayer {
             config,
@@ -139,33 +311,10 @@ impl LimaconLayer {
     /// where phase is rotated for each curve to distribute them around the center.
     /// This produces identical output to a rose engine with sinusoidal frequency=1.
     pub fn generate(&mut self) {
-        self.curves.clear();
-
-        let phase_step = 2.0 * PI / (self.config.num_curves as f64);
-
-        for i in 0..self.config.num_curves {
-            // Phase offset for this curve (equivalent to rotating the rose engine)
-            let phase = (i as f64) * phase_step;
+        self.curves = limacon_curves(&self.config, self.center_x, self.center_y);
 
-            // Generate points along this limaçon curve
-            let mut curve_points = Vec::with_capacity(self.config.resolution + 1);
-
-            for j in 0..=self.config.resolution {
-                let t = (j as f64) / (self.config.resolution as f64);
-                let theta = 2.0 * PI * t;
-
-                // Limaçon in polar coordinates: r = base_radius + amplitude * sin(θ + phase)
-                let r = self.config.base_radius + self.config.amplitude * (theta + phase).sin();
-
-                // Convert to Cartesian
-                let x = self.center_x + r * theta.cos();
-                let y = self.center_y + r * theta.sin();
-
-                curve_points.push(Point2D::new(x, y));
-            }
-
-            self.curves.push(curve_points);
-        }
+        dedupe_polylines(&mut self.curves, DEDUPE_EPSILON);
+        debug_assert_finite("LimaconLayer", &self.curves);
     }
 
     /// Get the generated curves as a vector of point vectors
@@ -178,10 +327,35 @@ impl LimaconLayer {
         &self.curves
     }
 
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.curves
+    }
+
+    /// Multiply every length (config, center and generated curves) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.base_radius *= factor;
+        self.config.amplitude *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.curves, factor);
+    }
+
     /// Export the pattern to SVG format
     pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        use svg::node::element::{path::Data, Path};
-        use svg::Document;
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
 
         if self.curves.is_empty() {
             return Err(SpirographError::ExportError(
@@ -189,53 +363,99 @@ impl LimaconLayer {
             ));
         }
 
-        // Find bounds
-        let mut min_x = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
-
-        for curve in &self.curves {
-            for point in curve {
-                min_x = min_x.min(point.x);
-                max_x = max_x.max(point.x);
-                min_y = min_y.min(point.y);
-                max_y = max_y.max(point.y);
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.curves.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        // Draw each curve
+        for curve in &layer.curves {
+            if polyline_is_degenerate(curve) {
+                continue;
             }
+
+            writer.add_polyline(curve, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+            });
         }
 
-        let margin = 5.0;
-        let width = max_x - min_x + 2.0 * margin;
-        let height = max_y - min_y + 2.0 * margin;
+        writer.save(filename)
+    }
+}
 
-        let mut document = Document::new()
-            .set("width", format!("{}mm", width))
-            .set("height", format!("{}mm", height))
-            .set("viewBox", (min_x - margin, min_y - margin, width, height));
+impl PatternLayer for LimaconLayer {
+    fn generate(&mut self) {
+        LimaconLayer::generate(self)
+    }
 
-        // Draw each curve
-        for curve in &self.curves {
-            if curve.is_empty() {
-                continue;
-            }
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
 
-            let mut data = Data::new().move_to((curve[0].x, curve[0].y));
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
 
-            for point in curve.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
+impl StoredLayer for LimaconLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        LimaconLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
 
-            let path = Path::new()
-                .set("d", data)
-                .set("fill", "none")
-                .set("stroke", "black")
-                .set("stroke-width", 0.05);
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
 
-            document = document.add(path);
+impl LayerType for LimaconLayer {
+    const KIND: LayerKind = LayerKind::Limacon;
+}
+
+impl Progressive for LimaconLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
         }
 
-        svg::save(filename, &document)
-            .map_err(|e| SpirographError::ExportError(format!("Failed to save SVG: {}", e)))
+        // The curve count defines the pattern; only sampling is reduced
+        let mut coarse = self.clone();
+        coarse.config.resolution =
+            PointsTotal(scale_count(self.config.resolution.get(), fraction, 10));
+        coarse.generate();
+        self.curves = coarse.curves;
+    }
+}
+
+impl PointCounted for LimaconLayer {
+    fn point_count(&self) -> usize {
+        self.curves.iter().map(Vec::len).sum()
     }
 }
 
@@ -249,7 +469,7 @@ mod tests {
         assert_eq!(config.num_curves, 72);
         assert_eq!(config.base_radius, 20.0);
         assert_eq!(config.amplitude, 20.0);
-        assert_eq!(config.resolution, 360);
+        assert_eq!(config.resolution, PointsTotal(360));
     }
 
     #[test]
@@ -328,7 +548,7 @@ mod tests {
         // Create equivalent RoseEngineLatheRun with sinusoidal frequency=1
         let mut rose_config = RoseEngineConfig::new(base_radius, amplitude);
         rose_config.rosette = RosettePattern::Sinusoidal { frequency: 1.0 };
-        rose_config.resolution = resolution;
+        rose_config.resolution = resolution.into();
 
         let bit = CuttingBit::v_shaped(30.0, 0.02);
         let mut rose_run = RoseEngineLatheRun::new_with_segments(
@@ -379,4 +599,96 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_outer_only_stays_outside_inner_loop() {
+        // One curve, phase 0: ρ(θ) = 6 + 10 sin θ, with an inner loop
+        let (a, b) = (6.0, 10.0);
+        let config = LimaconConfig::new(1, a, b)
+            .with_resolution(720)
+            .with_loop_mode(LimaconLoopMode::OuterOnly);
+        let mut layer = LimaconLayer::new(config).unwrap();
+        layer.generate();
+
+        // The negative arc wraps neither end, so the outer curve is one line
+        // from the center round to the center
+        assert_eq!(layer.lines().len(), 1);
+        let outer = &layer.lines()[0];
+        assert_eq!(outer.first(), Some(&Point2D::new(0.0, 0.0)));
+        assert_eq!(outer.last(), Some(&Point2D::new(0.0, 0.0)));
+
+        // In direction ψ the inner loop reaches out to b sin ψ - a
+        for p in outer {
+            let r = p.x.hypot(p.y);
+            let psi = p.y.atan2(p.x);
+            let loop_r = (b * psi.sin() - a).max(0.0);
+            assert!(
+                r >= loop_r - 1e-9,
+                "({}, {}) lies inside the loop",
+                p.x,
+                p.y
+            );
+        }
+
+        let mut inner = LimaconLayer::new(
+            LimaconConfig::new(1, a, b)
+                .with_resolution(720)
+                .with_loop_mode(LimaconLoopMode::InnerOnly),
+        )
+        .unwrap();
+        inner.generate();
+        assert_eq!(inner.lines().len(), 1);
+        assert!(inner.lines()[0]
+            .iter()
+            .all(|p| p.x.hypot(p.y) <= b - a + 1e-9));
+    }
+
+    #[test]
+    fn test_cardioid_preset() {
+        let config = LimaconConfig::cardioid(8, 12.0, 360);
+        assert_eq!(config.base_radius, config.amplitude);
+        assert!(!config.has_inner_loop());
+
+        // No inner loop to draw, and trimming leaves each cardioid whole
+        assert!(config
+            .clone()
+            .with_loop_mode(LimaconLoopMode::InnerOnly)
+            .validate()
+            .is_err());
+        let mut full = LimaconLayer::new(config.clone()).unwrap();
+        let mut outer =
+            LimaconLayer::new(config.with_loop_mode(LimaconLoopMode::OuterOnly)).unwrap();
+        full.generate();
+        outer.generate();
+        assert_eq!(outer.lines(), full.lines());
+    }
+
+    #[test]
+    fn test_trimmed_limacon_matches_rose_engine() {
+        use crate::rose_engine::RoseEngineLatheRun;
+
+        for mode in [
+            LimaconLoopMode::Full,
+            LimaconLoopMode::OuterOnly,
+            LimaconLoopMode::InnerOnly,
+        ] {
+            let config = LimaconConfig::new(12, 8.0, 14.0)
+                .with_resolution(360)
+                .with_loop_mode(mode);
+            let mut limacon = LimaconLayer::new_with_center(config, 1.0, -2.0).unwrap();
+            limacon.generate();
+
+            let mut run =
+                RoseEngineLatheRun::new_limacon(12, 8.0, 14.0, 360, mode, 1.0, -2.0).unwrap();
+            run.generate();
+
+            assert_eq!(run.lines().len(), limacon.lines().len(), "{:?}", mode);
+            for (rose, lim) in run.lines().iter().zip(limacon.lines()) {
+                assert_eq!(rose.len(), lim.len());
+                for (p, q) in rose.iter().zip(lim) {
+                    assert!((p.x - q.x).hypot(p.y - q.y) < 1e-10, "{:?}", mode);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+use svg::node::element::Element;
+use svg::node::Text;
+use svg::{Document, Node};
+
+/// Traceability fields embedded in exported files
+///
+/// Stored on a `WatchFace` or `RoseEngineLatheRun` with `set_metadata`,
+/// then written into every SVG (a `<metadata>` RDF block plus `data-`
+/// attributes on the root), STEP (FILE_DESCRIPTION and FILE_NAME) and DXF
+/// (comments and `$PROJECTNAME`) export. Empty fields are left out.
+///
+/// `params_hash` is free-form; a run or draperie layer's
+/// `geometry_hash()` formatted as hex is one option.
+///
+/// # Example
+/// ```
+/// use turtles::{FileMetadata, WatchFace};
+///
+/// let mut face = WatchFace::new(38.0).unwrap();
+/// face.set_metadata(
+///     FileMetadata::new("DIAL-0042")
+///         .with_params_hash("9f3c2a")
+///         .with_license("CC-BY-4.0")
+///         .with_extra("customer", "Atelier Nord"),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct FileMetadata {
+    /// Design identifier, e.g. a drawing or order number
+    pub design_id: String,
+    /// Fingerprint of the parameters the design was generated from
+    pub params_hash: String,
+    /// Generator name and version; `new` fills in this crate's
+    pub generator_version: String,
+    /// License or rights statement
+    pub license: String,
+    /// Further key/value pairs, written after the fixed fields
+    pub extra: Vec<(String, String)>,
+}
+
+impl FileMetadata {
+    /// Metadata for `design_id`, generated by this version of the crate
+    pub fn new(design_id: &str) -> Self {
+        FileMetadata {
+            design_id: design_id.to_string(),
+            generator_version: format!("turtles {}", env!("CARGO_PKG_VERSION")),
+            ..Default::default()
+        }
+    }
+
+    /// Set the parameter fingerprint
+    pub fn with_params_hash(mut self, params_hash: &str) -> Self {
+        self.params_hash = params_hash.to_string();
+        self
+    }
+
+    /// Set the license string
+    pub fn with_license(mut self, license: &str) -> Self {
+        self.license = license.to_string();
+        self
+    }
+
+    /// Append an extra key/value pair
+    pub fn with_extra(mut self, key: &str, value: &str) -> Self {
+        self.extra.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Every non-empty field as (key, value), fixed fields first
+    pub fn entries(&self) -> Vec<(&str, &str)> {
+        let fixed = [
+            ("design_id", self.design_id.as_str()),
+            ("params_hash", self.params_hash.as_str()),
+            ("generator_version", self.generator_version.as_str()),
+            ("license", self.license.as_str()),
+        ];
+        fixed
+            .into_iter()
+            .chain(self.extra.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+            .filter(|(_, value)| !value.is_empty())
+            .collect()
+    }
+
+    /// Add the metadata to an SVG document's root and as its first child
+    ///
+    /// Dublin Core carries the design id (`dc:identifier`), generator
+    /// (`dc:creator`) and license (`dc:rights`); the parameter hash and
+    /// extras go in `dc:description`, one `key: value` per line. Every
+    /// entry is also a `data-` attribute on the root (keys lower-cased,
+    /// other characters as `-`).
+    pub(crate) fn apply_svg(&self, mut document: Document) -> Document {
+        document = document
+            .set("xmlns:rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#")
+            .set("xmlns:dc", "http://purl.org/dc/elements/1.1/")
+            .set("xmlns:cc", "http://creativecommons.org/ns#");
+        for (key, value) in self
+            .entries()
+            .into_iter()
+            .filter(|(key, _)| !key.is_empty())
+        {
+            // The svg crate does not escape quotes in attribute values
+            document = document.set(
+                format!("data-{}", attribute_key(key)),
+                value.replace('"', "'"),
+            );
+        }
+
+        let mut work = Element::new("cc:Work");
+        work.assign("rdf:about", "");
+        let mut dc = |name: &str, value: &str| {
+            let mut element = Element::new(format!("dc:{}", name));
+            element.append(Text::new(value));
+            work.append(element);
+        };
+        for (name, value) in [
+            ("identifier", &self.design_id),
+            ("creator", &self.generator_version),
+            ("rights", &self.license),
+        ] {
+            if !value.is_empty() {
+                dc(name, value);
+            }
+        }
+        let description: Vec<String> = self
+            .entries()
+            .into_iter()
+            .filter(|(key, _)| !matches!(*key, "design_id" | "generator_version" | "license"))
+            .map(|(key, value)| format!("{}: {}", key, value))
+            .collect();
+        if !description.is_empty() {
+            dc("description", &description.join("\n"));
+        }
+
+        let mut rdf = Element::new("rdf:RDF");
+        rdf.append(work);
+        let mut metadata = Element::new("metadata");
+        metadata.assign("id", "file-metadata");
+        metadata.append(rdf);
+        document.add(metadata)
+    }
+
+    /// STEP FILE_DESCRIPTION and FILE_NAME header lines
+    ///
+    /// The description list gains a `key: value` string per entry. In
+    /// FILE_NAME the design id is the author, the generator is the
+    /// preprocessor and originating system, and the license is the
+    /// authorization.
+    pub(crate) fn step_header(
+        &self,
+        description: &str,
+        file_name: &str,
+        timestamp: &str,
+    ) -> String {
+        let descriptions: Vec<String> = std::iter::once(step_string(description))
+            .chain(
+                self.entries()
+                    .into_iter()
+                    .map(|(key, value)| step_string(&format!("{}: {}", key, value))),
+            )
+            .collect();
+        format!(
+            "FILE_DESCRIPTION(({}),'2;1');\nFILE_NAME({},{},({}),(''),{},{},{});\n",
+            descriptions.join(","),
+            step_string(file_name),
+            step_string(timestamp),
+            step_string(&self.design_id),
+            step_string(&self.generator_version),
+            step_string(&self.generator_version),
+            step_string(&self.license),
+        )
+    }
+
+    /// DXF comment (group 999) lines, one per entry
+    pub(crate) fn dxf_comments(&self) -> String {
+        self.entries()
+            .into_iter()
+            .map(|(key, value)| format!("999\n{}: {}\n", key, single_line(value)))
+            .collect()
+    }
+}
+
+/// STEP header lines, with metadata when given and the historical empty
+/// author fields otherwise
+pub(crate) fn step_header(
+    metadata: Option<&FileMetadata>,
+    description: &str,
+    file_name: &str,
+    timestamp: &str,
+) -> String {
+    match metadata {
+        Some(metadata) => metadata.step_header(description, file_name, timestamp),
+        None => format!(
+            "FILE_DESCRIPTION(('{}'),'2;1');\nFILE_NAME('{}','{}',(''),(''),'','','');\n",
+            description, file_name, timestamp
+        ),
+    }
+}
+
+/// Quote a STEP string: apostrophes and backslashes doubled, non-ASCII
+/// characters as `\X2\` UTF-16 hex
+pub(crate) fn step_string(value: &str) -> String {
+    let mut out = String::from("'");
+    for c in single_line(value).chars() {
+        match c {
+            '\'' => out.push_str("''"),
+            '\\' => out.push_str("\\\\"),
+            ' '..='~' => out.push(c),
+            _ => {
+                out.push_str("\\X2\\");
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("{:04X}", unit));
+                }
+                out.push_str("\\X0\\");
+            }
+        }
+    }
+    out.push('\'');
+    out
+}
+
+/// Replace line breaks and other control characters with spaces
+fn single_line(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+/// Lower-case `key`, with anything but ASCII letters and digits as `-`
+fn attribute_key(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_strings_are_escaped() {
+        assert_eq!(step_string("it's"), "'it''s'");
+        assert_eq!(step_string("a\\b"), "'a\\\\b'");
+        assert_eq!(step_string("café\n"), "'caf\\X2\\00E9\\X0\\ '");
+
+        let meta = FileMetadata::new("D-1").with_license("MIT");
+        let header = meta.step_header("Pattern", "p.stp", "2026-01-01T00:00:00");
+        assert!(header.contains("FILE_DESCRIPTION(('Pattern','design_id: D-1',"));
+        assert!(header.contains("('D-1'),('')"));
+        assert!(header.ends_with(",'MIT');\n"));
+        assert_eq!(attribute_key("Batch No."), "batch-no-");
+    }
+}
@@ -0,0 +1,205 @@
+use svg::node::element::{Circle, Group, Line, Text};
+
+/// Stroke color used for all overlay geometry
+const OVERLAY_COLOR: &str = "#c8c8c8";
+/// Text color used for overlay labels
+const OVERLAY_LABEL_COLOR: &str = "#808080";
+/// Default label font size in mm when none is configured
+const DEFAULT_LABEL_FONT_SIZE: f64 = 2.0;
+
+/// Design-review overlay drawn beneath an exported SVG pattern
+///
+/// Every element is optional; an all-`None` overlay draws nothing. The
+/// overlay only ever appears in SVG output and is never part of STL or
+/// STEP exports.
+#[derive(Debug, Clone, Default)]
+pub struct OverlayOptions {
+    /// Pitch of a square millimeter grid across the bounding box
+    pub mm_grid: Option<f64>,
+    /// Angular spacing in degrees of labeled radial rays from the pattern center
+    pub radial_rays_every_deg: Option<f64>,
+    /// Radial spacing in mm of labeled concentric reference circles
+    pub concentric_circles_every_mm: Option<f64>,
+    /// Label font size in mm (defaults to 2.0)
+    pub label_font_size: Option<f64>,
+}
+
+impl OverlayOptions {
+    /// Create an empty overlay
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the millimeter grid pitch
+    pub fn with_mm_grid(mut self, pitch: f64) -> Self {
+        self.mm_grid = Some(pitch);
+        self
+    }
+
+    /// Set the angular spacing of radial rays in degrees
+    pub fn with_radial_rays(mut self, every_deg: f64) -> Self {
+        self.radial_rays_every_deg = Some(every_deg);
+        self
+    }
+
+    /// Set the radial spacing of concentric reference circles in mm
+    pub fn with_concentric_circles(mut self, every_mm: f64) -> Self {
+        self.concentric_circles_every_mm = Some(every_mm);
+        self
+    }
+
+    /// Set the label font size in mm
+    pub fn with_label_font_size(mut self, size: f64) -> Self {
+        self.label_font_size = Some(size);
+        self
+    }
+
+    /// Build the `<g id="overlay">` group
+    ///
+    /// # Arguments
+    /// * `bounds` - (min_x, min_y, max_x, max_y) of the area the grid covers
+    /// * `center` - Pattern center for rays and circles
+    /// * `extent` - Outer radius of the design; rays and circles stop here
+    ///   and labels are placed just outside it
+    pub(crate) fn to_group(
+        &self,
+        bounds: (f64, f64, f64, f64),
+        center: (f64, f64),
+        extent: f64,
+    ) -> Group {
+        let (min_x, min_y, max_x, max_y) = bounds;
+        let (cx, cy) = center;
+        let font_size = self.label_font_size.unwrap_or(DEFAULT_LABEL_FONT_SIZE);
+        let label_radius = extent + font_size;
+
+        let mut group = Group::new()
+            .set("id", "overlay")
+            .set("fill", "none")
+            .set("stroke", OVERLAY_COLOR)
+            .set("stroke-width", 0.05);
+
+        // Square grid aligned to multiples of the pitch so the origin falls on a line
+        if let Some(pitch) = self.mm_grid.filter(|p| *p > 0.0) {
+            for k in (min_x / pitch).ceil() as i64..=(max_x / pitch).floor() as i64 {
+                let x = k as f64 * pitch;
+                group = group.add(
+                    Line::new()
+                        .set("class", "overlay-grid")
+                        .set("x1", x)
+                        .set("y1", min_y)
+                        .set("x2", x)
+                        .set("y2", max_y),
+                );
+            }
+            for k in (min_y / pitch).ceil() as i64..=(max_y / pitch).floor() as i64 {
+                let y = k as f64 * pitch;
+                group = group.add(
+                    Line::new()
+                        .set("class", "overlay-grid")
+                        .set("x1", min_x)
+                        .set("y1", y)
+                        .set("x2", max_x)
+                        .set("y2", y),
+                );
+            }
+        }
+
+        // Concentric reference circles, labeled to the right of the design
+        if let Some(step) = self.concentric_circles_every_mm.filter(|s| *s > 0.0) {
+            let count = (extent / step).floor() as usize;
+            for k in 1..=count {
+                let r = k as f64 * step;
+                group = group.add(
+                    Circle::new()
+                        .set("class", "overlay-circle")
+                        .set("cx", cx)
+                        .set("cy", cy)
+                        .set("r", r),
+                );
+                group = group.add(
+                    Text::new(format!("{} mm", r))
+                        .set("class", "overlay-label")
+                        .set("x", cx + label_radius)
+                        .set("y", cy - r)
+                        .set("font-size", font_size)
+                        .set("fill", OVERLAY_LABEL_COLOR)
+                        .set("stroke", "none")
+                        .set("dominant-baseline", "middle"),
+                );
+            }
+        }
+
+        // Radial rays from the center, labeled just outside the design
+        if let Some(every) = self.radial_rays_every_deg.filter(|d| *d > 0.0) {
+            let count = (360.0 / every).ceil() as usize;
+            for k in 0..count {
+                let deg = k as f64 * every;
+                if deg >= 360.0 {
+                    break;
+                }
+                let (sin, cos) = deg.to_radians().sin_cos();
+                group = group.add(
+                    Line::new()
+                        .set("class", "overlay-ray")
+                        .set("x1", cx)
+                        .set("y1", cy)
+                        .set("x2", cx + extent * cos)
+                        .set("y2", cy + extent * sin),
+                );
+                group = group.add(
+                    Text::new(format!("{}°", deg))
+                        .set("class", "overlay-label")
+                        .set("x", cx + label_radius * cos)
+                        .set("y", cy + label_radius * sin)
+                        .set("font-size", font_size)
+                        .set("fill", OVERLAY_LABEL_COLOR)
+                        .set("stroke", "none")
+                        .set("text-anchor", "middle")
+                        .set("dominant-baseline", "middle"),
+                );
+            }
+        }
+
+        group
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_empty_by_default() {
+        let group = OverlayOptions::new().to_group((-10.0, -10.0, 10.0, 10.0), (0.0, 0.0), 8.0);
+        let out = group.to_string();
+        assert!(out.contains("id=\"overlay\""));
+        assert!(!out.contains("<line"));
+        assert!(!out.contains("<circle"));
+    }
+
+    #[test]
+    fn test_overlay_grid_line_count() {
+        let opts = OverlayOptions::new().with_mm_grid(5.0);
+        let out = opts
+            .to_group((-20.0, -10.0, 20.0, 10.0), (0.0, 0.0), 8.0)
+            .to_string();
+        // x: -20..=20 step 5 → 9 lines, y: -10..=10 step 5 → 5 lines
+        assert_eq!(out.matches("class=\"overlay-grid\"").count(), 14);
+    }
+
+    #[test]
+    fn test_overlay_rays_and_circles() {
+        let opts = OverlayOptions::new()
+            .with_radial_rays(30.0)
+            .with_concentric_circles(5.0)
+            .with_label_font_size(1.5);
+        let out = opts
+            .to_group((-30.0, -30.0, 30.0, 30.0), (0.0, 0.0), 20.0)
+            .to_string();
+        assert_eq!(out.matches("class=\"overlay-ray\"").count(), 12);
+        assert_eq!(out.matches("class=\"overlay-circle\"").count(), 4);
+        assert_eq!(out.matches("class=\"overlay-label\"").count(), 16);
+        assert!(out.contains("90°"));
+        assert!(out.contains("15 mm"));
+    }
+}
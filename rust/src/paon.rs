@@ -1,6 +1,18 @@
 use std::f64::consts::PI;
 
-use crate::common::{clock_to_cartesian, polar_to_cartesian, Point2D, SpirographError};
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::{write_svg_streaming, SvgWriter, DEFAULT_SVG_PRECISION};
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_consecutive, dedupe_polylines, map_indices,
+    polar_to_cartesian, polyline_is_degenerate, scale_polylines, simplified_layer,
+    simplify_polylines, ExportConfig, JitterConfig, PatternLayer, Point2D, PointsTotal,
+    SpirographError, StoredLayer, DEDUPE_EPSILON, DEFAULT_MAX_POINTS_PER_PATH,
+};
+use crate::dxf::write_dxf_polylines;
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive, ProgressiveHandle};
 
 /// Compute the paon waveform value at angle `theta`.
 ///
@@ -31,6 +43,40 @@ pub fn paon_wave_fn(theta: f64, n_harmonics: usize) -> f64 {
     }
 }
 
+/// Side of the circle the paon fan radiates from
+///
+/// Directions are as rendered, with SVG's y axis pointing down. The whole
+/// construction (vanishing point, line angles and phase) turns about the
+/// layer centre; `Angle(a)` turns the `Bottom` fan by `a` radians,
+/// clockwise on screen, so `Angle(PI / 2.0)` is `Left`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum PaonOrigin {
+    /// Vanishing point below the circle; arches open upward from 6 o'clock
+    #[default]
+    Bottom,
+    /// Vanishing point above the circle, radiating from 12 o'clock
+    Top,
+    /// Vanishing point left of the circle, radiating from 9 o'clock
+    Left,
+    /// Vanishing point right of the circle, radiating from 3 o'clock
+    Right,
+    /// The `Bottom` fan turned by this many radians
+    Angle(f64),
+}
+
+impl PaonOrigin {
+    /// Turn from `Bottom` in radians, clockwise on screen
+    pub fn angle(self) -> f64 {
+        match self {
+            PaonOrigin::Bottom => 0.0,
+            PaonOrigin::Left => PI / 2.0,
+            PaonOrigin::Top => PI,
+            PaonOrigin::Right => -PI / 2.0,
+            PaonOrigin::Angle(angle) => angle,
+        }
+    }
+}
+
 /// Configuration for the Paon (Peacock) guilloché pattern
 ///
 /// The paon pattern uses **parallel horizontal lines** stacked from the
@@ -47,7 +93,7 @@ pub fn paon_wave_fn(theta: f64, n_harmonics: usize) -> f64 {
 /// appear as nested arches when clipped to the circle.
 ///
 /// Lines are clipped to a circle of the given `radius`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaonConfig {
     /// Number of horizontal passes (more = denser, finer pattern)
     pub num_lines: usize,
@@ -63,7 +109,7 @@ pub struct PaonConfig {
     /// Each bump of the |sin| phase envelope creates one arch column.
     pub phase_rate: f64,
     /// Number of sample points per line
-    pub resolution: usize,
+    pub resolution: PointsTotal,
     /// Number of Fourier harmonics for the waveform shape.
     /// 0 = pure sine (smooth arches), 1+ = triangle-wave approximation
     /// (sharper, more pointed arch cusps). 3 is a good default for a
@@ -76,10 +122,23 @@ pub struct PaonConfig {
     /// Vanishing-point distance below the circle bottom, expressed as a
     /// fraction of the diameter.  Lines radiate from the vanishing point,
     /// so arches are narrow at the bottom and wide at the top.
-    /// 0 = VP at circle bottom (extreme fan), large values → nearly
-    /// parallel vertical lines.  Default 0.3 ≈ 20 % of diameter below
+    /// Must be positive: small values give an extreme fan, large values →
+    /// nearly parallel vertical lines.  Default 0.3 ≈ 20 % of diameter below
     /// the bottom edge.
     pub vanishing_point: f64,
+    /// Seeded hand-made wobble of each line about the layer centre,
+    /// applied before the line is clipped to the circle
+    #[serde(default)]
+    pub jitter: Option<JitterConfig>,
+    /// Side of the circle the fan radiates from
+    #[serde(default)]
+    pub origin: PaonOrigin,
+    /// Skew of the fan's line spacing, between -1 and 1 exclusive.
+    /// Positive values crowd the lines toward the side that is on the
+    /// right looking from the vanishing point into the fan, negative
+    /// values toward the left; 0 spaces them evenly by angle.
+    #[serde(default)]
+    pub asymmetry: f64,
 }
 
 impl Default for PaonConfig {
@@ -90,10 +149,13 @@ impl Default for PaonConfig {
             amplitude: 0.035,
             wave_frequency: 10.0,
             phase_rate: 9.0,
-            resolution: 800,
+            resolution: PointsTotal(800),
             n_harmonics: 3,
             fan_angle: 4.0,
             vanishing_point: 0.3,
+            jitter: None,
+            origin: PaonOrigin::Bottom,
+            asymmetry: 0.0,
         }
     }
 }
@@ -113,10 +175,116 @@ impl PaonConfig {
     }
 
     /// Set the resolution (points per line)
-    pub fn with_resolution(mut self, resolution: usize) -> Self {
-        self.resolution = resolution;
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = resolution.into();
+        self
+    }
+
+    /// Wobble each line as if engraved by hand
+    pub fn with_jitter(mut self, jitter: JitterConfig) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    /// Radiate the fan from `origin`
+    pub fn with_origin(mut self, origin: PaonOrigin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Skew the fan's line spacing; see [`asymmetry`](Self::asymmetry)
+    pub fn with_asymmetry(mut self, asymmetry: f64) -> Self {
+        self.asymmetry = asymmetry;
         self
     }
+
+    /// Position of fan line `frac` (0 to 1 by index) across the fan's
+    /// angle range, skewed by `asymmetry`
+    pub(crate) fn fan_fraction(&self, frac: f64) -> f64 {
+        frac + self.asymmetry * frac * (1.0 - frac)
+    }
+
+    /// Sine and cosine of the origin's turn, applied to every point about
+    /// the layer centre
+    pub(crate) fn origin_rotation(&self) -> (f64, f64) {
+        self.origin.angle().sin_cos()
+    }
+
+    /// Check the parameters that have hard physical constraints
+    ///
+    /// A vanishing point at or inside the circle, a negative fan or phase
+    /// rate, or a non-positive wave frequency make the fan geometry
+    /// degenerate (NaN or runaway phase values). An asymmetry of 1 or more
+    /// either way would fold the fan back on itself.
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.num_lines == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "num_lines must be at least 1".to_string(),
+            ));
+        }
+
+        if self.radius <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "radius must be positive".to_string(),
+            ));
+        }
+
+        if self.resolution.get() < 10 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 10".to_string(),
+            ));
+        }
+
+        if self.amplitude < 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "amplitude must be non-negative".to_string(),
+            ));
+        }
+
+        if self.vanishing_point.is_nan() || self.vanishing_point <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "vanishing_point must be positive".to_string(),
+            ));
+        }
+
+        if self.fan_angle.is_nan() || self.fan_angle < 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "fan_angle must be non-negative".to_string(),
+            ));
+        }
+
+        if self.phase_rate.is_nan() || self.phase_rate < 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "phase_rate must be non-negative".to_string(),
+            ));
+        }
+
+        if self.wave_frequency.is_nan() || self.wave_frequency <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "wave_frequency must be positive".to_string(),
+            ));
+        }
+
+        if let PaonOrigin::Angle(angle) = self.origin {
+            if !angle.is_finite() {
+                return Err(SpirographError::InvalidParameter(
+                    "origin angle must be finite".to_string(),
+                ));
+            }
+        }
+
+        if self.asymmetry.is_nan() || self.asymmetry.abs() >= 1.0 {
+            return Err(SpirographError::InvalidParameter(
+                "asymmetry must be between -1 and 1".to_string(),
+            ));
+        }
+
+        if let Some(ref jitter) = self.jitter {
+            jitter.validate()?;
+        }
+
+        Ok(())
+    }
 }
 
 /// A Paon (Peacock) pattern layer that creates the arch/peacock-feather guilloché effect
@@ -124,16 +292,37 @@ impl PaonConfig {
 /// Parallel horizontal lines oscillate vertically with progressive phase
 /// offsets.  The interference between neighbours creates moiré-style arch
 /// bands that resemble peacock feathers.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaonLayer {
     pub config: PaonConfig,
     pub center_x: f64,
     pub center_y: f64,
+    #[serde(skip)]
     lines: Vec<Vec<Point2D>>,
 }
 
 impl PaonLayer {
     /// Create a new paon layer centred at origin
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{PaonConfig, PaonLayer};
+    ///
+    /// let config = PaonConfig {
+    ///     num_lines: 60,
+    ///     ..PaonConfig::default()
+    /// };
+    /// let mut layer = PaonLayer::new(config).unwrap();
+    /// layer.generate();
+    ///
+    /// // Every line is clipped to the dial
+    /// assert!(!layer.lines().is_empty());
+    /// # use turtles::doctest_support::max_radius;
+    /// assert!(max_radius(layer.lines(), (0.0, 0.0)) <= 22.0 + 1e-9);
+    ///
+    /// let svg = layer.to_svg_string().unwrap();
+    /// assert_eq!(svg.matches("<path").count(), layer.lines().len());
+    /// ```
     pub fn new(config: PaonConfig) -> Result<Self, SpirographError> {
         Self::new_with_center(config, 0.0, 0.0)
     }
@@ -144,29 +333,7 @@ impl PaonLayer {
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        if config.num_lines == 0 {
-            return Err(SpirographError::InvalidParameter(
-                "num_lines must be at least 1".to_string(),
-            ));
-        }
-
-        if config.radius <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "radius must be positive".to_string(),
-            ));
-        }
-
-        if config.resolution < 10 {
-            return Err(SpirographError::InvalidParameter(
-                "resolution must be at least 10".to_string(),
-            ));
-        }
-
-        if config.amplitude < 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "amplitude must be non-negative".to_string(),
-            ));
-        }
+        config.validate()?;
 
         Ok(PaonLayer {
             config,
@@ -209,9 +376,11 @@ impl PaonLayer {
     /// mathematical coordinates.  Because SVG renders y downward, the VP
     /// appears at the **bottom** of the rendered image — lines converge
     /// at the bottom and fan out toward the top, matching the classic
-    /// peacock-feather guilloché reference.
+    /// peacock-feather guilloché reference. `config.origin` turns the whole
+    /// construction about the layer centre to radiate from another side.
     ///
-    /// Lines are uniformly spaced by **angle** from the VP.  The angle
+    /// Lines are uniformly spaced by **angle** from the VP, unless
+    /// `config.asymmetry` skews the spacing toward one side.  The angle
     /// range is computed to cover the entire circle: the critical
     /// y-level where the circle edge subtends the maximum angle from the
     /// VP is found analytically, guaranteeing every point inside the
@@ -234,8 +403,44 @@ impl PaonLayer {
     /// that neighbouring lines **never cross** — the visual pattern is a
     /// pure moiré density illusion.
     pub fn generate(&mut self) {
-        self.lines.clear();
+        self.generate_lines(false);
+    }
+
+    /// Generate as [`generate`](Self::generate), one line per thread task
+    ///
+    /// The lines are identical to the serial ones, in the same order.
+    #[cfg(feature = "parallel")]
+    pub fn generate_parallel(&mut self) {
+        self.generate_lines(true);
+    }
+
+    /// Generate every fan line, optionally in parallel
+    fn generate_lines(&mut self, parallel: bool) {
+        let lines = map_indices(self.config.num_lines, parallel, self.line_tracer());
+        self.lines = lines.into_iter().filter(|line| line.len() >= 2).collect();
 
+        dedupe_polylines(&mut self.lines, DEDUPE_EPSILON);
+        debug_assert_finite("PaonLayer", &self.lines);
+    }
+
+    /// Lines computed lazily from the config, one at a time
+    ///
+    /// Yields the same lines, point for point, as `generate` followed by
+    /// `lines`, but holds only the line being traced in memory. The
+    /// generated lines are neither used nor changed.
+    pub fn iter_lines(&self) -> impl Iterator<Item = impl Iterator<Item = Point2D> + '_> + '_ {
+        (0..self.config.num_lines)
+            .map(self.line_tracer())
+            .filter(|line| line.len() >= 2)
+            .map(|mut line| {
+                dedupe_consecutive(&mut line, DEDUPE_EPSILON);
+                line.into_iter()
+            })
+    }
+
+    /// Fan line `i` clipped to the circle, before short lines are dropped
+    /// and repeated points removed
+    fn line_tracer(&self) -> impl Fn(usize) -> Vec<Point2D> + Sync + Send + '_ {
         let r = self.config.radius;
         let n = self.config.num_lines;
         let nh = self.config.n_harmonics;
@@ -253,15 +458,18 @@ impl PaonLayer {
         // Reference distance: VP to nearest circle edge (math top = SVG bottom).
         let dist_near = y_vp - r; // = vanishing_point * diameter
 
-        for i in 0..n {
+        // The fan is traced from the bottom and turned to its origin
+        let (sin_o, cos_o) = self.config.origin_rotation();
+
+        move |i| {
             let frac = if n > 1 {
                 i as f64 / (n - 1) as f64
             } else {
                 0.5
             };
 
-            // Uniform angular spacing from the VP
-            let angle = -angle_max + 2.0 * angle_max * frac;
+            // Angular spacing from the VP, uniform unless skewed
+            let angle = -angle_max + 2.0 * angle_max * self.config.fan_fraction(frac);
             let tan_a = angle.tan();
 
             // Negative |sin| phase offset → arches open UPWARD (M-shape)
@@ -270,10 +478,11 @@ impl PaonLayer {
                 * self.config.fan_angle
                 * (PI * self.config.phase_rate * frac).sin().abs();
 
-            let mut line_points = Vec::with_capacity(self.config.resolution + 1);
+            let jitter = self.config.jitter.map(|jitter| jitter.line(i));
+            let mut line_points = Vec::with_capacity(self.config.resolution.get() + 1);
 
-            for j in 0..=self.config.resolution {
-                let t_frac = j as f64 / self.config.resolution as f64;
+            for j in 0..=self.config.resolution.get() {
+                let t_frac = j as f64 / self.config.resolution.get() as f64;
 
                 // y sweeps from −r (SVG top, far from VP) to +r (SVG bottom, near VP)
                 let y = -r + diameter * t_frac;
@@ -292,82 +501,210 @@ impl PaonLayer {
 
                 let offset = self.config.amplitude * paon_wave_fn(theta, nh);
 
-                // Horizontal oscillation
-                let x = x_base + offset;
+                // Horizontal oscillation, wobbled about the centre
+                let point = Point2D::new(x_base + offset, y);
+                let Point2D { x, y } = match jitter {
+                    Some(ref jitter) => jitter.displace(t_frac, point, Point2D::new(0.0, 0.0)),
+                    None => point,
+                };
 
-                // Clip to circle
+                // Clip to circle, then turn to the origin
                 if x * x + y * y <= r * r {
-                    line_points.push(Point2D::new(self.center_x + x, self.center_y + y));
+                    line_points.push(Point2D::new(
+                        self.center_x + x * cos_o - y * sin_o,
+                        self.center_y + x * sin_o + y * cos_o,
+                    ));
                 }
             }
 
-            if line_points.len() >= 2 {
-                self.lines.push(line_points);
-            }
+            line_points
         }
     }
 
+    /// Start a coarse-to-fine generation at the given detail fractions
+    ///
+    /// Coarse levels use fewer fan lines and fewer points per line; see
+    /// `ProgressiveHandle`.
+    pub fn generate_progressive(&mut self, levels: &[f32]) -> ProgressiveHandle<'_, Self> {
+        ProgressiveHandle::new(self, levels)
+    }
+
     /// Get the generated lines
     pub fn lines(&self) -> &Vec<Vec<Point2D>> {
         &self.lines
     }
 
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply every length (config, center and generated lines) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.radius *= factor;
+        self.config.amplitude *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.lines, factor);
+    }
+
     /// Export the pattern to SVG format
     pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        use svg::node::element::{path::Data, Path};
-        use svg::Document;
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
 
+    /// Export the pattern to DXF with one LWPOLYLINE per line, in mm
+    pub fn to_dxf(&self, filename: &str) -> Result<(), SpirographError> {
         if self.lines.is_empty() {
             return Err(SpirographError::ExportError(
                 "Pattern not generated. Call generate() first.".to_string(),
             ));
         }
+        write_dxf_polylines(filename, &self.lines, false, None)
+    }
 
-        // Find bounds
-        let mut min_x = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+        simplified_layer(self, config.simplify_tolerance)
+            .svg_writer(config.svg_precision)?
+            .with_origin(config.origin)
+            .save(filename)
+    }
 
-        for line in &self.lines {
-            for point in line {
-                min_x = min_x.min(point.x);
-                max_x = max_x.max(point.x);
-                min_y = min_y.min(point.y);
-                max_y = max_y.max(point.y);
-            }
-        }
+    /// The SVG export as a string, without writing a file
+    pub fn to_svg_string(&self) -> Result<String, SpirographError> {
+        Ok(self
+            .svg_writer(DEFAULT_SVG_PRECISION)?
+            .into_document()
+            .to_string())
+    }
 
-        let margin = 5.0;
-        let width = max_x - min_x + 2.0 * margin;
-        let height = max_y - min_y + 2.0 * margin;
+    /// Build the SVG document with one path per line
+    fn svg_writer(&self, precision: usize) -> Result<SvgWriter, SpirographError> {
+        if self.lines.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
 
-        let mut document = Document::new()
-            .set("width", format!("{}mm", width))
-            .set("height", format!("{}mm", height))
-            .set("viewBox", (min_x - margin, min_y - margin, width, height));
+        let mut writer =
+            SvgWriter::framing(self.lines.iter().map(Vec::as_slice))?.with_precision(precision);
 
         for line in &self.lines {
-            if line.is_empty() {
+            if polyline_is_degenerate(line) {
                 continue;
             }
 
-            let mut data = Data::new().move_to((line[0].x, line[0].y));
-            for point in line.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
+            let node = line_svg_node(&writer, line);
+            writer.add(node);
+        }
+
+        Ok(writer)
+    }
+
+    /// Export to SVG straight from [`iter_lines`](Self::iter_lines),
+    /// without generating
+    ///
+    /// Writes the same file as `generate` then `to_svg`, but traces each
+    /// line twice (once to size the document) rather than holding them all.
+    pub fn to_svg_streaming(&self, filename: &str) -> Result<(), SpirographError> {
+        write_svg_streaming(
+            filename,
+            DEFAULT_MAX_POINTS_PER_PATH,
+            || self.iter_lines().map(Iterator::collect),
+            line_svg_node,
+        )
+    }
+}
+
+/// SVG node stroking one fan line
+fn line_svg_node(writer: &SvgWriter, line: &[Point2D]) -> Box<dyn svg::Node> {
+    writer.polyline_node(line, false, |path| {
+        path.set("fill", "none")
+            .set("stroke", "black")
+            .set("stroke-width", 0.05)
+    })
+}
+
+impl PatternLayer for PaonLayer {
+    fn generate(&mut self) {
+        PaonLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for PaonLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
 
-            let path = Path::new()
-                .set("d", data)
-                .set("fill", "none")
-                .set("stroke", "black")
-                .set("stroke-width", 0.05);
+    fn scale_lengths(&mut self, factor: f64) {
+        PaonLayer::scale_lengths(self, factor);
+    }
 
-            document = document.add(path);
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for PaonLayer {
+    const KIND: LayerKind = LayerKind::Paon;
+}
+
+impl Progressive for PaonLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
         }
 
-        svg::save(filename, &document)
-            .map_err(|e| SpirographError::ExportError(format!("Failed to save SVG: {}", e)))
+        let mut coarse = self.clone();
+        coarse.config.num_lines = scale_count(self.config.num_lines, fraction, 1);
+        coarse.config.resolution =
+            PointsTotal(scale_count(self.config.resolution.get(), fraction, 10));
+        coarse.generate();
+        self.lines = coarse.lines;
+    }
+}
+
+impl PointCounted for PaonLayer {
+    fn point_count(&self) -> usize {
+        self.lines.iter().map(Vec::len).sum()
     }
 }
 
@@ -375,6 +712,18 @@ impl PaonLayer {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_generation_matches_serial() {
+        let config = PaonConfig::new(500, 25.0).with_resolution(800);
+        let mut serial = PaonLayer::new(config.clone()).unwrap();
+        serial.generate();
+        let mut parallel = PaonLayer::new(config).unwrap();
+        parallel.generate_parallel();
+        assert_eq!(parallel.lines().len(), serial.lines().len());
+        assert_eq!(parallel.lines(), serial.lines());
+    }
+
     #[test]
     fn test_paon_config_default() {
         let config = PaonConfig::default();
@@ -427,7 +776,7 @@ mod tests {
 
         // low resolution
         let config = PaonConfig {
-            resolution: 5,
+            resolution: PointsTotal(5),
             ..Default::default()
         };
         assert!(PaonLayer::new(config).is_err());
@@ -440,6 +789,64 @@ mod tests {
         assert!(PaonLayer::new(config).is_err());
     }
 
+    #[test]
+    fn test_paon_physical_constraints() {
+        let invalid = [
+            PaonConfig {
+                vanishing_point: 0.0,
+                ..Default::default()
+            },
+            PaonConfig {
+                vanishing_point: -0.2,
+                ..Default::default()
+            },
+            PaonConfig {
+                fan_angle: -3.0,
+                ..Default::default()
+            },
+            PaonConfig {
+                phase_rate: -1.0,
+                ..Default::default()
+            },
+            PaonConfig {
+                wave_frequency: 0.0,
+                ..Default::default()
+            },
+            PaonConfig {
+                wave_frequency: f64::NAN,
+                ..Default::default()
+            },
+        ];
+
+        for config in invalid {
+            assert!(PaonLayer::new(config.clone()).is_err(), "{:?}", config);
+            assert!(crate::rose_engine::RoseEngineLatheRun::new_paon(
+                config.num_lines,
+                config.radius,
+                config.amplitude,
+                config.wave_frequency,
+                config.phase_rate,
+                config.resolution,
+                config.n_harmonics,
+                config.fan_angle,
+                config.vanishing_point,
+                config.origin,
+                config.asymmetry,
+                0.0,
+                0.0,
+            )
+            .is_err());
+        }
+
+        // Zero fan and phase rate are degenerate but valid
+        let config = PaonConfig {
+            fan_angle: 0.0,
+            phase_rate: 0.0,
+            ..Default::default()
+        };
+        assert!(PaonLayer::new(config).is_ok());
+    }
+
     #[test]
     fn test_paon_generate() {
         let config = PaonConfig {
@@ -448,10 +855,13 @@ mod tests {
             amplitude: 0.5,
             wave_frequency: 6.0,
             phase_rate: 4.0,
-            resolution: 200,
+            resolution: PointsTotal(200),
             n_harmonics: 0,
             fan_angle: 1.4,
             vanishing_point: 0.3,
+            jitter: None,
+            origin: PaonOrigin::Bottom,
+            asymmetry: 0.0,
         };
         let mut layer = PaonLayer::new(config).unwrap();
         layer.generate();
@@ -474,10 +884,13 @@ mod tests {
             amplitude: 0.5,
             wave_frequency: 6.0,
             phase_rate: 4.0,
-            resolution: 200,
+            resolution: PointsTotal(200),
             n_harmonics: 0,
             fan_angle: 1.4,
             vanishing_point: 0.3,
+            jitter: None,
+            origin: PaonOrigin::Bottom,
+            asymmetry: 0.0,
         };
         let mut layer = PaonLayer::new(config).unwrap();
         layer.generate();
@@ -522,10 +935,13 @@ mod tests {
             amplitude: 0.5,
             wave_frequency: 4.0,
             phase_rate: 3.0,
-            resolution: 100,
+            resolution: PointsTotal(100),
             n_harmonics: 0,
             fan_angle: 1.4,
             vanishing_point: 0.3,
+            jitter: None,
+            origin: PaonOrigin::Bottom,
+            asymmetry: 0.0,
         };
         let mut layer = PaonLayer::new(config).unwrap();
         layer.generate();
@@ -537,6 +953,42 @@ mod tests {
         let _ = std::fs::remove_file(&tmpfile);
     }
 
+    #[test]
+    fn test_paon_dxf_export() {
+        let mut layer = PaonLayer::new(PaonConfig::new(30, 15.0)).unwrap();
+        layer.generate();
+
+        let tmpfile = std::env::temp_dir().join("test_paon.dxf");
+        let path = tmpfile.to_str().expect("temp dir path is valid UTF-8");
+        layer.to_dxf(path).unwrap();
+        let dxf = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+        assert_eq!(crate::dxf::read_dxf_polylines(&dxf), *layer.lines());
+    }
+
+    #[test]
+    fn test_iter_lines_and_streaming_svg_match_generate() {
+        // Without waves the outermost lines only touch the circle, leaving
+        // them too few points to keep
+        let config = PaonConfig {
+            amplitude: 0.0,
+            ..PaonConfig::new(61, 15.0)
+        };
+        let mut layer = PaonLayer::new(config).unwrap();
+        let lazy: Vec<Vec<Point2D>> = layer.iter_lines().map(Iterator::collect).collect();
+        assert!(layer.lines().is_empty());
+
+        let streamed = std::env::temp_dir().join("test_paon_stream.svg");
+        layer.to_svg_streaming(streamed.to_str().unwrap()).unwrap();
+        let streamed_svg = std::fs::read_to_string(&streamed).unwrap();
+        let _ = std::fs::remove_file(&streamed);
+
+        layer.generate();
+        assert_eq!(&lazy, layer.lines());
+        assert_eq!(lazy.len(), 59);
+        assert_eq!(streamed_svg, layer.to_svg_string().unwrap());
+    }
+
     #[test]
     fn test_paon_wave_fn() {
         // n_harmonics=0 should be pure sine
@@ -571,78 +1023,143 @@ mod tests {
         let amplitude = 0.5;
         let wave_frequency = 8.0;
         let phase_rate = 5.0;
-        let resolution = 400;
+        let resolution = PointsTotal(400);
         let n_harmonics: usize = 3;
         let fan_angle: f64 = 1.4;
 
-        // Mathematical PaonLayer
-        let config = PaonConfig {
-            num_lines,
-            radius,
-            amplitude,
-            wave_frequency,
-            phase_rate,
-            resolution,
-            n_harmonics,
-            fan_angle,
-            vanishing_point: 0.3,
-        };
-        let mut math_layer = PaonLayer::new(config).unwrap();
-        math_layer.generate();
-
-        // Rose engine PaonLayer
-        let mut rose_run = RoseEngineLatheRun::new_paon(
-            num_lines,
-            radius,
-            amplitude,
-            wave_frequency,
-            phase_rate,
-            resolution,
-            n_harmonics,
-            fan_angle,
-            0.3,
-            0.0,
-            0.0,
-        )
-        .unwrap();
-        rose_run.generate();
-
-        let math_lines = math_layer.lines();
-        let rose_lines = rose_run.lines();
-
-        assert_eq!(
-            math_lines.len(),
-            rose_lines.len(),
-            "PaonLayer and RoseEngineLatheRun should have same number of lines: math={}, rose={}",
-            math_lines.len(),
-            rose_lines.len(),
-        );
+        for (origin, asymmetry) in [
+            (PaonOrigin::Bottom, 0.0),
+            (PaonOrigin::Left, 0.4),
+            (PaonOrigin::Angle(1.0), -0.3),
+        ] {
+            // Mathematical PaonLayer
+            let config = PaonConfig {
+                num_lines,
+                radius,
+                amplitude,
+                wave_frequency,
+                phase_rate,
+                resolution,
+                n_harmonics,
+                fan_angle,
+                vanishing_point: 0.3,
+                jitter: None,
+                origin,
+                asymmetry,
+            };
+            let mut math_layer = PaonLayer::new(config).unwrap();
+            math_layer.generate();
+
+            // Rose engine PaonLayer
+            let mut rose_run = RoseEngineLatheRun::new_paon(
+                num_lines,
+                radius,
+                amplitude,
+                wave_frequency,
+                phase_rate,
+                resolution,
+                n_harmonics,
+                fan_angle,
+                0.3,
+                origin,
+                asymmetry,
+                0.0,
+                0.0,
+            )
+            .unwrap();
+            rose_run.generate();
+
+            let math_lines = math_layer.lines();
+            let rose_lines = rose_run.lines();
 
-        for (i, (math_line, rose_line)) in math_lines.iter().zip(rose_lines.iter()).enumerate() {
             assert_eq!(
-                math_line.len(),
-                rose_line.len(),
-                "Line {} should have same number of points: math={}, rose={}",
-                i,
-                math_line.len(),
-                rose_line.len(),
+                math_lines.len(),
+                rose_lines.len(),
+                "PaonLayer and RoseEngineLatheRun should have same number of lines: math={}, rose={}",
+                math_lines.len(),
+                rose_lines.len(),
             );
 
-            for (j, (math_pt, rose_pt)) in math_line.iter().zip(rose_line.iter()).enumerate() {
-                let dist =
-                    ((math_pt.x - rose_pt.x).powi(2) + (math_pt.y - rose_pt.y).powi(2)).sqrt();
-                assert!(
-                    dist < 1e-10,
-                    "Point {},{} differs: math=({}, {}), rose=({}, {}), dist={}",
+            for (i, (math_line, rose_line)) in math_lines.iter().zip(rose_lines.iter()).enumerate()
+            {
+                assert_eq!(
+                    math_line.len(),
+                    rose_line.len(),
+                    "Line {} should have same number of points: math={}, rose={}",
                     i,
-                    j,
-                    math_pt.x,
-                    math_pt.y,
-                    rose_pt.x,
-                    rose_pt.y,
-                    dist
+                    math_line.len(),
+                    rose_line.len(),
                 );
+
+                for (j, (math_pt, rose_pt)) in math_line.iter().zip(rose_line.iter()).enumerate() {
+                    let dist =
+                        ((math_pt.x - rose_pt.x).powi(2) + (math_pt.y - rose_pt.y).powi(2)).sqrt();
+                    assert!(
+                        dist < 1e-10,
+                        "Point {},{} differs: math=({}, {}), rose=({}, {}), dist={}",
+                        i,
+                        j,
+                        math_pt.x,
+                        math_pt.y,
+                        rose_pt.x,
+                        rose_pt.y,
+                        dist
+                    );
+                }
             }
         }
     }
+
+    #[test]
+    fn test_paon_origin_turns_the_fan() {
+        let config = PaonConfig::new(80, 20.0).with_resolution(300);
+        let mut bottom = PaonLayer::new_with_center(config.clone(), 3.0, -2.0).unwrap();
+        bottom.generate();
+        let turned = |origin: PaonOrigin| {
+            let mut layer =
+                PaonLayer::new_with_center(config.clone().with_origin(origin), 3.0, -2.0).unwrap();
+            layer.generate();
+            layer.lines().clone()
+        };
+
+        // A quarter turn clockwise on screen about the centre: (x, y) -> (-y, x)
+        let quarter = turned(PaonOrigin::Angle(PI / 2.0));
+        assert_eq!(quarter.len(), bottom.lines().len());
+        for (line, original) in quarter.iter().zip(bottom.lines()) {
+            assert_eq!(line.len(), original.len());
+            for (p, q) in line.iter().zip(original) {
+                assert!((p.x - (3.0 - (q.y + 2.0))).abs() < 1e-9);
+                assert!((p.y - (-2.0 + (q.x - 3.0))).abs() < 1e-9);
+            }
+        }
+        assert_eq!(turned(PaonOrigin::Left), quarter);
+
+        // Top is a half turn: (x, y) -> (-x, -y) about the centre
+        for (line, original) in turned(PaonOrigin::Top).iter().zip(bottom.lines()) {
+            for (p, q) in line.iter().zip(original) {
+                assert!((p.x - 3.0 + (q.x - 3.0)).abs() < 1e-9);
+                assert!((p.y + 2.0 + (q.y + 2.0)).abs() < 1e-9);
+            }
+        }
+
+        // Skewing crowds the lines to one side, so the middle line leans
+        // the other way; from the bottom, positive asymmetry crowds the right
+        let middle_x = |asymmetry: f64| {
+            let mut layer = PaonLayer::new(config.clone().with_asymmetry(asymmetry)).unwrap();
+            layer.generate();
+            let line = &layer.lines()[layer.lines().len() / 2];
+            line.iter().map(|p| p.x).sum::<f64>() / line.len() as f64
+        };
+        assert!(middle_x(0.5) > 1.0);
+        assert!(middle_x(-0.5) < -1.0);
+        assert!(middle_x(0.0).abs() < 0.5);
+
+        for invalid in [
+            config.clone().with_asymmetry(1.0),
+            config.clone().with_asymmetry(f64::NAN),
+            config.clone().with_origin(PaonOrigin::Angle(f64::INFINITY)),
+        ] {
+            assert!(PaonLayer::new(invalid).is_err());
+        }
+    }
 }
@@ -0,0 +1,635 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clip_polyline_to_circle, clock_to_cartesian, debug_assert_finite, polar_to_cartesian,
+    polyline_is_degenerate, scale_polylines, simplified_layer, simplify_polylines, ExportConfig,
+    PatternLayer, Point2D, PointsTotal, SpirographError, StoredLayer,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
+use crate::rose_engine::RosettePattern;
+
+/// The golden angle, π(3 − √5) radians (about 137.507764°)
+pub const GOLDEN_ANGLE: f64 = PI * (3.0 - 2.236_067_977_499_79);
+
+/// Shape drawn at each phyllotaxis cell
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PhylloStyle {
+    /// Closed circle of radius `r`
+    Circle { r: f64 },
+    /// Radial tick of length `len`, centered on the cell
+    Tick { len: f64 },
+    /// Tiny closed rosette of `lobes` crests, traced like a rose engine
+    /// ring with `RosettePattern::MultiLobe`: its radius swings between
+    /// `r / 2` at the troughs and `r` at the crests, and the first crest
+    /// points away from the layer center
+    MiniLobe { lobes: usize, r: f64 },
+}
+
+impl PhylloStyle {
+    /// Farthest any of the cell's geometry reaches from its center
+    fn extent(&self) -> f64 {
+        match *self {
+            PhylloStyle::Circle { r } | PhylloStyle::MiniLobe { r, .. } => r,
+            PhylloStyle::Tick { len } => len / 2.0,
+        }
+    }
+}
+
+/// What happens to cells whose geometry crosses the clip circle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PhylloEdge {
+    /// Cut the geometry exactly at the circle
+    #[default]
+    Clip,
+    /// Leave out every cell that does not fit inside the circle
+    Drop,
+}
+
+/// Configuration for the phyllotaxis (sunflower) pattern
+///
+/// Cell `n` (counting from 0) sits on Vogel's spiral at
+/// r = `scale_c`·√n, θ = n·[`GOLDEN_ANGLE`], which packs cells evenly
+/// with the Fibonacci spirals of a sunflower head. Each cell is drawn in
+/// `point_style`.
+///
+/// ### Parameters
+///
+/// | field                    | meaning |
+/// |--------------------------|---------|
+/// | `num_points`             | Number of cells on the spiral |
+/// | `scale_c`                | Spiral scale c; cell spacing is about c·√π/2 |
+/// | `point_style`            | Shape drawn at each cell |
+/// | `inner_exclusion_radius` | Cells whose centers are closer than this are left out |
+/// | `radius`                 | Radius of the circular clipping region |
+/// | `edge`                   | Whether cells crossing the circle are clipped or dropped |
+/// | `resolution`             | Points per circle or rosette loop |
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhyllotaxisConfig {
+    /// Number of cells on the spiral
+    pub num_points: usize,
+    /// Spiral scale c in mm: cell n is c·√n from the center
+    pub scale_c: f64,
+    /// Shape drawn at each cell
+    pub point_style: PhylloStyle,
+    /// Cells centered closer than this to the center are left out, in mm
+    pub inner_exclusion_radius: f64,
+    /// Radius of the circular clipping region in mm
+    pub radius: f64,
+    /// Whether cells crossing the clip circle are clipped or dropped
+    pub edge: PhylloEdge,
+    /// Points per circle or rosette loop
+    pub resolution: PointsTotal,
+}
+
+impl Default for PhyllotaxisConfig {
+    fn default() -> Self {
+        PhyllotaxisConfig {
+            num_points: 800,
+            scale_c: 0.75,
+            point_style: PhylloStyle::Circle { r: 0.25 },
+            inner_exclusion_radius: 0.0,
+            radius: 22.0,
+            edge: PhylloEdge::Clip,
+            resolution: PointsTotal(48),
+        }
+    }
+}
+
+impl PhyllotaxisConfig {
+    /// Create a new phyllotaxis configuration
+    ///
+    /// # Arguments
+    /// * `num_points` - Number of cells on the spiral
+    /// * `scale_c` - Spiral scale: cell n is `scale_c`·√n from the center
+    /// * `radius` - Radius of the circular clipping region in mm
+    pub fn new(num_points: usize, scale_c: f64, radius: f64) -> Self {
+        PhyllotaxisConfig {
+            num_points,
+            scale_c,
+            radius,
+            ..Default::default()
+        }
+    }
+
+    /// Set the shape drawn at each cell
+    pub fn with_point_style(mut self, point_style: PhylloStyle) -> Self {
+        self.point_style = point_style;
+        self
+    }
+
+    /// Set the radius inside which no cells are drawn
+    pub fn with_inner_exclusion_radius(mut self, radius: f64) -> Self {
+        self.inner_exclusion_radius = radius;
+        self
+    }
+
+    /// Set whether cells crossing the clip circle are clipped or dropped
+    pub fn with_edge(mut self, edge: PhylloEdge) -> Self {
+        self.edge = edge;
+        self
+    }
+
+    /// Set the resolution (points per loop)
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = resolution.into();
+        self
+    }
+
+    /// Check the counts, radii and cell style
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        let invalid = |msg: &str| Err(SpirographError::InvalidParameter(msg.to_string()));
+        if self.num_points == 0 {
+            return invalid("num_points must be at least 1");
+        }
+        if !(self.scale_c.is_finite() && self.scale_c > 0.0) {
+            return invalid("scale_c must be positive");
+        }
+        if !(self.radius.is_finite() && self.radius > 0.0) {
+            return invalid("radius must be positive");
+        }
+        if !(self.inner_exclusion_radius >= 0.0 && self.inner_exclusion_radius < self.radius) {
+            return invalid("inner_exclusion_radius must be non-negative and less than radius");
+        }
+        if self.resolution.get() < 3 {
+            return invalid("resolution must be at least 3");
+        }
+        match self.point_style {
+            PhylloStyle::Circle { r } => {
+                if !(r.is_finite() && r > 0.0) {
+                    return invalid("circle radius must be positive");
+                }
+            }
+            PhylloStyle::Tick { len } => {
+                if !(len.is_finite() && len > 0.0) {
+                    return invalid("tick length must be positive");
+                }
+            }
+            PhylloStyle::MiniLobe { lobes, r } => {
+                RosettePattern::MultiLobe { lobes }.validate()?;
+                if !(r.is_finite() && r > 0.0) {
+                    return invalid("rosette radius must be positive");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A phyllotaxis (sunflower) pattern layer
+///
+/// Draws a circle, tick or tiny rosette at each golden-angle spiral
+/// position, clipped to (or dropped at) the configured radius about the
+/// layer center.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhyllotaxisLayer {
+    pub config: PhyllotaxisConfig,
+    pub center_x: f64,
+    pub center_y: f64,
+    #[serde(skip)]
+    lines: Vec<Vec<Point2D>>,
+    #[serde(skip)]
+    drawn_cells: Vec<usize>,
+}
+
+impl PhyllotaxisLayer {
+    /// Create a new phyllotaxis layer centred at origin
+    pub fn new(config: PhyllotaxisConfig) -> Result<Self, SpirographError> {
+        Self::new_with_center(config, 0.0, 0.0)
+    }
+
+    /// Create a new phyllotaxis layer with a custom centre point
+    pub fn new_with_center(
+        config: PhyllotaxisConfig,
+        center_x: f64,
+        center_y: f64,
+    ) -> Result<Self, SpirographError> {
+        config.validate()?;
+
+        Ok(PhyllotaxisLayer {
+            config,
+            center_x,
+            center_y,
+            lines: Vec::new(),
+            drawn_cells: Vec::new(),
+        })
+    }
+
+    /// Create a phyllotaxis layer positioned at a given angle and distance
+    /// from origin
+    pub fn new_at_polar(
+        config: PhyllotaxisConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (cx, cy) = polar_to_cartesian(angle, distance);
+        Self::new_with_center(config, cx, cy)
+    }
+
+    /// Create a phyllotaxis layer positioned at a clock position
+    ///
+    /// # Arguments
+    /// * `config` - Phyllotaxis configuration
+    /// * `hour`   - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from centre of watch face
+    pub fn new_at_clock(
+        config: PhyllotaxisConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (cx, cy) = clock_to_cartesian(hour, minute, distance);
+        Self::new_with_center(config, cx, cy)
+    }
+
+    /// Polar position (angle, distance) of cell `n` about the layer center
+    ///
+    /// The angle is n·[`GOLDEN_ANGLE`] and is not reduced to one turn.
+    pub fn cell_polar(&self, n: usize) -> (f64, f64) {
+        (
+            n as f64 * GOLDEN_ANGLE,
+            self.config.scale_c * (n as f64).sqrt(),
+        )
+    }
+
+    /// Center of cell `n`
+    pub fn cell_center(&self, n: usize) -> Point2D {
+        let (angle, distance) = self.cell_polar(n);
+        let (x, y) = polar_to_cartesian(angle, distance);
+        Point2D::new(self.center_x + x, self.center_y + y)
+    }
+
+    /// Generate the pattern
+    ///
+    /// Cells inside `inner_exclusion_radius` are skipped. A cell whose
+    /// geometry reaches past `radius` is cut at the circle, or left out
+    /// entirely with [`PhylloEdge::Drop`].
+    pub fn generate(&mut self) {
+        self.lines.clear();
+        self.drawn_cells.clear();
+
+        let center = Point2D::new(self.center_x, self.center_y);
+        let radius = self.config.radius;
+        let extent = self.config.point_style.extent();
+
+        for n in 0..self.config.num_points {
+            let (angle, distance) = self.cell_polar(n);
+            if distance < self.config.inner_exclusion_radius {
+                continue;
+            }
+            // Spiral distance only grows, so no later cell can reach the circle
+            if distance - extent > radius {
+                break;
+            }
+
+            let (shape, closed) = self.cell_shape(angle, distance);
+            let pieces = if distance + extent <= radius {
+                vec![shape]
+            } else if self.config.edge == PhylloEdge::Drop {
+                continue;
+            } else if closed {
+                // The loop repeats its start; the clipper closes it itself
+                clip_polyline_to_circle(&shape[..shape.len() - 1], center, radius, true)
+            } else {
+                clip_polyline_to_circle(&shape, center, radius, false)
+            };
+
+            if !pieces.is_empty() {
+                self.drawn_cells.push(n);
+                self.lines.extend(pieces);
+            }
+        }
+
+        debug_assert_finite("PhyllotaxisLayer", &self.lines);
+    }
+
+    /// Points of the cell at polar (`angle`, `distance`), and whether they
+    /// form a closed loop repeating its first point
+    fn cell_shape(&self, angle: f64, distance: f64) -> (Vec<Point2D>, bool) {
+        let (cx, cy) = polar_to_cartesian(angle, distance);
+        let (cx, cy) = (self.center_x + cx, self.center_y + cy);
+        let steps = self.config.resolution.get();
+        let loop_points = |radius_at: &dyn Fn(f64) -> f64| {
+            (0..=steps)
+                .map(|j| {
+                    let t = 2.0 * PI * (j % steps) as f64 / steps as f64;
+                    let r = radius_at(t);
+                    Point2D::new(cx + r * (angle + t).cos(), cy + r * (angle + t).sin())
+                })
+                .collect()
+        };
+
+        match self.config.point_style {
+            PhylloStyle::Circle { r } => (loop_points(&|_| r), true),
+            PhylloStyle::Tick { len } => {
+                let (dx, dy) = (angle.cos() * len / 2.0, angle.sin() * len / 2.0);
+                (
+                    vec![
+                        Point2D::new(cx - dx, cy - dy),
+                        Point2D::new(cx + dx, cy + dy),
+                    ],
+                    false,
+                )
+            }
+            PhylloStyle::MiniLobe { lobes, r } => {
+                let rosette = RosettePattern::MultiLobe { lobes };
+                // Shift by half a lobe so a crest rather than a trough faces outward
+                let offset = PI / lobes as f64;
+                let shape = |t: f64| r * (0.75 + 0.25 * rosette.displacement(t + offset));
+                (loop_points(&shape), true)
+            }
+        }
+    }
+
+    /// Get the lines
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.lines
+    }
+
+    /// Indices of the cells that drew anything in the last generation
+    pub fn drawn_cells(&self) -> &[usize] {
+        &self.drawn_cells
+    }
+
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply every length (config, center and generated lines) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.scale_c *= factor;
+        self.config.radius *= factor;
+        self.config.inner_exclusion_radius *= factor;
+        self.config.point_style = match self.config.point_style {
+            PhylloStyle::Circle { r } => PhylloStyle::Circle { r: r * factor },
+            PhylloStyle::Tick { len } => PhylloStyle::Tick { len: len * factor },
+            PhylloStyle::MiniLobe { lobes, r } => PhylloStyle::MiniLobe {
+                lobes,
+                r: r * factor,
+            },
+        };
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.lines, factor);
+    }
+
+    /// Export the pattern to SVG format
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+
+        if self.lines.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.lines.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        for line in &layer.lines {
+            if polyline_is_degenerate(line) {
+                continue;
+            }
+
+            writer.add_polyline(line, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+            });
+        }
+
+        writer.save(filename)
+    }
+}
+
+impl PatternLayer for PhyllotaxisLayer {
+    fn generate(&mut self) {
+        PhyllotaxisLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for PhyllotaxisLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        PhyllotaxisLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for PhyllotaxisLayer {
+    const KIND: LayerKind = LayerKind::Phyllotaxis;
+}
+
+impl Progressive for PhyllotaxisLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
+        }
+
+        // The cells define the pattern; only the loops are sampled coarser
+        let mut coarse = self.clone();
+        coarse.config.resolution =
+            PointsTotal(scale_count(self.config.resolution.get(), fraction, 8));
+        coarse.generate();
+        self.lines = coarse.lines;
+        self.drawn_cells = coarse.drawn_cells;
+    }
+}
+
+impl PointCounted for PhyllotaxisLayer {
+    fn point_count(&self) -> usize {
+        self.lines.iter().map(Vec::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn max_distance(lines: &[Vec<Point2D>], center: Point2D) -> f64 {
+        lines
+            .iter()
+            .flatten()
+            .map(|p| (p.x - center.x).hypot(p.y - center.y))
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_cell_positions_follow_vogel_spiral() {
+        let config = PhyllotaxisConfig::new(500, 0.8, 20.0);
+        let layer = PhyllotaxisLayer::new_with_center(config, 3.0, -2.0).unwrap();
+        assert!((GOLDEN_ANGLE.to_degrees() - 137.507_764).abs() < 1e-6);
+        for n in [0, 1, 2, 13, 144, 499] {
+            let p = layer.cell_center(n);
+            let (dx, dy) = (p.x - 3.0, p.y + 2.0);
+            assert!((dx.hypot(dy) - 0.8 * (n as f64).sqrt()).abs() < 1e-9);
+            if n > 0 {
+                let expected = (n as f64 * GOLDEN_ANGLE).rem_euclid(2.0 * PI);
+                let angle = dy.atan2(dx).rem_euclid(2.0 * PI);
+                let diff = (angle - expected).abs();
+                assert!(diff.min(2.0 * PI - diff) < 1e-9, "cell {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dropped_cell_count_matches_spiral_radius() {
+        // Cells fit while c·√n + r ≤ R: n ≤ ((10 - 0.5) / 1)² = 90.25
+        let config = PhyllotaxisConfig::new(1000, 1.0, 10.0)
+            .with_point_style(PhylloStyle::Circle { r: 0.5 })
+            .with_edge(PhylloEdge::Drop);
+        let mut layer = PhyllotaxisLayer::new(config).unwrap();
+        layer.generate();
+        assert_eq!(layer.drawn_cells().len(), 91);
+        assert_eq!(layer.lines().len(), 91);
+
+        // With an exclusion zone, cells with c·√n < 3 (n < 9) are skipped too
+        layer.config.inner_exclusion_radius = 3.0;
+        layer.generate();
+        assert_eq!(layer.drawn_cells().len(), 91 - 9);
+        assert_eq!(layer.drawn_cells()[0], 9);
+
+        // num_points caps the count before the radius does
+        layer.config.num_points = 40;
+        layer.generate();
+        assert_eq!(layer.drawn_cells().len(), 40 - 9);
+    }
+
+    #[test]
+    fn test_no_geometry_beyond_clip_radius() {
+        let styles = [
+            PhylloStyle::Circle { r: 0.6 },
+            PhylloStyle::Tick { len: 1.0 },
+            PhylloStyle::MiniLobe { lobes: 5, r: 0.7 },
+        ];
+        for style in styles {
+            for edge in [PhylloEdge::Clip, PhylloEdge::Drop] {
+                let config = PhyllotaxisConfig::new(600, 0.7, 12.0)
+                    .with_point_style(style)
+                    .with_edge(edge);
+                let mut layer = PhyllotaxisLayer::new_with_center(config, 1.0, 2.0).unwrap();
+                layer.generate();
+                assert!(!layer.lines().is_empty());
+                let reach = max_distance(layer.lines(), Point2D::new(1.0, 2.0));
+                assert!(reach <= 12.0 + 1e-9, "{:?} {:?}: {}", style, edge, reach);
+            }
+        }
+    }
+
+    #[test]
+    fn test_clipping_keeps_more_than_dropping() {
+        let config = PhyllotaxisConfig::new(600, 0.7, 12.0);
+        let mut clipped = PhyllotaxisLayer::new(config.clone()).unwrap();
+        let mut dropped = PhyllotaxisLayer::new(config.with_edge(PhylloEdge::Drop)).unwrap();
+        clipped.generate();
+        dropped.generate();
+        assert!(clipped.drawn_cells().len() > dropped.drawn_cells().len());
+        // Whole cells are closed loops repeating their first point
+        let loop_ = &dropped.lines()[0];
+        assert_eq!(loop_.len(), 48 + 1);
+        assert_eq!(loop_[0], loop_[48]);
+    }
+
+    #[test]
+    fn test_mini_lobe_swings_between_half_and_full_radius() {
+        let config = PhyllotaxisConfig::new(10, 2.0, 20.0)
+            .with_point_style(PhylloStyle::MiniLobe { lobes: 6, r: 1.0 })
+            .with_resolution(600);
+        let mut layer = PhyllotaxisLayer::new(config).unwrap();
+        layer.generate();
+        let center = layer.cell_center(4);
+        let cell = &layer.lines()[4];
+        let radii: Vec<f64> = cell
+            .iter()
+            .map(|p| (p.x - center.x).hypot(p.y - center.y))
+            .collect();
+        let max = radii.iter().cloned().fold(0.0, f64::max);
+        let min = radii.iter().cloned().fold(f64::INFINITY, f64::min);
+        assert!((max - 1.0).abs() < 1e-3 && (min - 0.5).abs() < 1e-3);
+        // The first point is a crest, pointing away from the layer center
+        assert!((radii[0] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_phyllotaxis_invalid_params() {
+        let base = PhyllotaxisConfig::default();
+        assert!(PhyllotaxisLayer::new(base.clone()).is_ok());
+        let invalid = [
+            PhyllotaxisConfig {
+                num_points: 0,
+                ..base.clone()
+            },
+            PhyllotaxisConfig {
+                scale_c: 0.0,
+                ..base.clone()
+            },
+            PhyllotaxisConfig {
+                inner_exclusion_radius: 22.0,
+                ..base.clone()
+            },
+            base.clone()
+                .with_point_style(PhylloStyle::Tick { len: 0.0 }),
+            base.clone()
+                .with_point_style(PhylloStyle::MiniLobe { lobes: 0, r: 1.0 }),
+            base.clone().with_resolution(2),
+        ];
+        for config in invalid {
+            assert!(PhyllotaxisLayer::new(config).is_err());
+        }
+    }
+}
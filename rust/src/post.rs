@@ -0,0 +1,392 @@
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{offset_polyline, segment_circle_crossings, Point2D};
+
+/// Kind of layer a post-processing callback is running on
+///
+/// Kinds order as declared, which is the order a `GuillochePattern`
+/// stores its layers in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LayerKind {
+    Spirograph,
+    Flinque,
+    Diamant,
+    Draperie,
+    HuitEight,
+    Limacon,
+    Paon,
+    ClousDeParis,
+    Cube,
+    Phyllotaxis,
+    GrainDeRiz,
+    Soleil,
+    CotesDeGeneve,
+    Tapisserie,
+    Vague,
+    Text,
+    ChapterRing,
+    IndexMarkers,
+    /// Polylines added as they are, such as ones read from an SVG
+    Imported,
+    /// One pass of a `RoseEngineLatheRun`; the index is the pass index
+    LathePass,
+}
+
+impl LayerKind {
+    /// Lower-case name, as used by the Python bindings
+    pub fn name(self) -> &'static str {
+        match self {
+            LayerKind::Spirograph => "spirograph",
+            LayerKind::Flinque => "flinque",
+            LayerKind::Diamant => "diamant",
+            LayerKind::Draperie => "draperie",
+            LayerKind::HuitEight => "huiteight",
+            LayerKind::Limacon => "limacon",
+            LayerKind::Paon => "paon",
+            LayerKind::ClousDeParis => "clous_de_paris",
+            LayerKind::Cube => "cube",
+            LayerKind::Phyllotaxis => "phyllotaxis",
+            LayerKind::GrainDeRiz => "grain_de_riz",
+            LayerKind::Soleil => "soleil",
+            LayerKind::CotesDeGeneve => "cotes_de_geneve",
+            LayerKind::Tapisserie => "tapisserie",
+            LayerKind::Vague => "vague",
+            LayerKind::Text => "text",
+            LayerKind::ChapterRing => "chapter_ring",
+            LayerKind::IndexMarkers => "index_markers",
+            LayerKind::Imported => "imported",
+            LayerKind::LathePass => "lathe_pass",
+        }
+    }
+
+    /// The kind whose `name()` is `name`
+    pub fn from_name(name: &str) -> Option<LayerKind> {
+        [
+            LayerKind::Spirograph,
+            LayerKind::Flinque,
+            LayerKind::Diamant,
+            LayerKind::Draperie,
+            LayerKind::HuitEight,
+            LayerKind::Limacon,
+            LayerKind::Paon,
+            LayerKind::ClousDeParis,
+            LayerKind::Cube,
+            LayerKind::Phyllotaxis,
+            LayerKind::GrainDeRiz,
+            LayerKind::Soleil,
+            LayerKind::CotesDeGeneve,
+            LayerKind::Tapisserie,
+            LayerKind::Vague,
+            LayerKind::Text,
+            LayerKind::ChapterRing,
+            LayerKind::IndexMarkers,
+            LayerKind::Imported,
+            LayerKind::LathePass,
+        ]
+        .into_iter()
+        .find(|kind| kind.name() == name)
+    }
+}
+
+/// A post-processing callback: edits one layer's lines in place, given the
+/// layer's kind and its index among layers of that kind (or its pass index)
+pub type PostProcessFn = dyn Fn(&mut Vec<Vec<Point2D>>, LayerKind, usize) + Send + Sync;
+
+/// Callbacks run in registration order after every generation
+#[derive(Clone, Default)]
+pub(crate) struct PostProcessors(Vec<Arc<PostProcessFn>>);
+
+impl PostProcessors {
+    pub(crate) fn push(&mut self, f: Arc<PostProcessFn>) {
+        self.0.push(f);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Run every callback, in order, on one layer's lines
+    pub(crate) fn apply(&self, lines: &mut Vec<Vec<Point2D>>, kind: LayerKind, index: usize) {
+        for f in &self.0 {
+            f(lines, kind, index);
+        }
+    }
+
+    /// Run every callback on a layer stored as a single polyline
+    ///
+    /// Lines the callbacks split it into are joined back end to end.
+    pub(crate) fn apply_single(&self, points: &mut Vec<Point2D>, kind: LayerKind, index: usize) {
+        if self.is_empty() {
+            return;
+        }
+        let mut lines = vec![std::mem::take(points)];
+        self.apply(&mut lines, kind, index);
+        *points = lines.into_iter().flatten().collect();
+    }
+}
+
+impl fmt::Debug for PostProcessors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PostProcessors({})", self.0.len())
+    }
+}
+
+/// Move the selected lines radially by `delta` (outward when positive)
+///
+/// `selector(kind, index, line)` picks lines by layer kind, layer (or pass)
+/// index and line index within the layer. Radii are measured from the
+/// origin, the dial centre; a point at the origin stays put.
+///
+/// # Example
+/// ```
+/// use turtles::post::{radial_nudge, LayerKind};
+/// use turtles::{DraperieConfig, DraperieLayer, GuillochePattern};
+///
+/// let mut pattern = GuillochePattern::new(38.0).unwrap();
+/// pattern.add_draperie_layer(DraperieLayer::new(DraperieConfig::default()).unwrap());
+/// // Every 7th draperie ring sits 0.05mm further out
+/// pattern.add_post_process(radial_nudge(
+///     |kind, _, line| kind == LayerKind::Draperie && line % 7 == 0,
+///     0.05,
+/// ));
+/// pattern.generate();
+/// ```
+pub fn radial_nudge<S>(
+    selector: S,
+    delta: f64,
+) -> impl Fn(&mut Vec<Vec<Point2D>>, LayerKind, usize) + Send + Sync + 'static
+where
+    S: Fn(LayerKind, usize, usize) -> bool + Send + Sync + 'static,
+{
+    move |lines, kind, index| {
+        for (i, line) in lines.iter_mut().enumerate() {
+            if !selector(kind, index, i) {
+                continue;
+            }
+            for point in line {
+                let r = point.x.hypot(point.y);
+                if r > 0.0 {
+                    let scale = (r + delta) / r;
+                    point.x *= scale;
+                    point.y *= scale;
+                }
+            }
+        }
+    }
+}
+
+/// Remove every line for which `predicate` returns true
+///
+/// # Example
+/// ```
+/// use turtles::post::drop_lines;
+///
+/// // Clear a band between 10mm and 12mm mean radius
+/// let clear_band = drop_lines(|line| {
+///     let mean = line.iter().map(|p| p.x.hypot(p.y)).sum::<f64>() / line.len() as f64;
+///     (10.0..12.0).contains(&mean)
+/// });
+/// # let _ = clear_band;
+/// ```
+pub fn drop_lines<P>(
+    predicate: P,
+) -> impl Fn(&mut Vec<Vec<Point2D>>, LayerKind, usize) + Send + Sync + 'static
+where
+    P: Fn(&[Point2D]) -> bool + Send + Sync + 'static,
+{
+    move |lines, _, _| lines.retain(|line| !predicate(line))
+}
+
+/// Keep only the parts of the selected layers' lines between two radii
+///
+/// `selector(kind, index)` picks layers by kind and layer (or pass) index.
+/// Lines are split exactly where they cross the circles of radius `r_min`
+/// and `r_max` about the origin, the dial centre; pieces outside the
+/// annulus are dropped. Pass `f64::INFINITY` as `r_max` to clear only a
+/// central disc.
+///
+/// # Example
+/// ```
+/// use turtles::post::{clip_annulus, LayerKind};
+/// use turtles::{ClousDeParisConfig, ClousDeParisLayer, GuillochePattern};
+///
+/// let mut pattern = GuillochePattern::new(38.0).unwrap();
+/// let hobnails = ClousDeParisConfig::new(1.0, 30.0);
+/// pattern.add_clous_de_paris_layer(ClousDeParisLayer::new(hobnails).unwrap());
+/// // Hobnails only on a band from 18mm outward
+/// pattern.add_post_process(clip_annulus(
+///     |kind, _| kind == LayerKind::ClousDeParis,
+///     18.0,
+///     f64::INFINITY,
+/// ));
+/// pattern.generate();
+/// ```
+pub fn clip_annulus<S>(
+    selector: S,
+    r_min: f64,
+    r_max: f64,
+) -> impl Fn(&mut Vec<Vec<Point2D>>, LayerKind, usize) + Send + Sync + 'static
+where
+    S: Fn(LayerKind, usize) -> bool + Send + Sync + 'static,
+{
+    move |lines, kind, index| {
+        if !selector(kind, index) {
+            return;
+        }
+        let origin = Point2D::new(0.0, 0.0);
+        let lerp = |a: Point2D, b: Point2D, t: f64| {
+            Point2D::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t)
+        };
+        let inside = |p: Point2D| (r_min..=r_max).contains(&p.x.hypot(p.y));
+
+        let mut pieces = Vec::new();
+        for line in lines.iter() {
+            let mut current: Vec<Point2D> = Vec::new();
+            for segment in line.windows(2) {
+                let (a, b) = (segment[0], segment[1]);
+                let mut ts = vec![0.0, 1.0];
+                ts.extend(segment_circle_crossings(a, b, origin, r_min));
+                if r_max.is_finite() {
+                    ts.extend(segment_circle_crossings(a, b, origin, r_max));
+                }
+                ts.sort_by(f64::total_cmp);
+                for span in ts.windows(2) {
+                    if span[1] - span[0] < 1e-12 {
+                        continue;
+                    }
+                    if inside(lerp(a, b, (span[0] + span[1]) / 2.0)) {
+                        if current.is_empty() {
+                            current.push(lerp(a, b, span[0]));
+                        }
+                        current.push(lerp(a, b, span[1]));
+                    } else if !current.is_empty() {
+                        pieces.push(std::mem::take(&mut current));
+                    }
+                }
+            }
+            if !current.is_empty() {
+                pieces.push(current);
+            }
+        }
+        *lines = pieces;
+    }
+}
+
+/// Repeat the selected layers' lines as `n` parallel traces `spacing` apart
+///
+/// `selector(kind, index)` picks layers by kind and layer (or pass) index.
+/// Each line is replaced by its `n` traces, made with [`offset_polyline`]
+/// and centred on the line, so an odd `n` keeps the line itself between
+/// its neighbours. An `n` of 0 or 1 leaves the lines alone.
+///
+/// # Example
+/// ```
+/// use turtles::post::{parallel_traces, LayerKind};
+/// use turtles::{DraperieConfig, DraperieLayer, GuillochePattern};
+///
+/// let mut pattern = GuillochePattern::new(38.0).unwrap();
+/// pattern.add_draperie_layer(DraperieLayer::new(DraperieConfig::default()).unwrap());
+/// // Widen every draperie ring into three traces 0.05mm apart
+/// pattern.add_post_process(parallel_traces(
+///     |kind, _| kind == LayerKind::Draperie,
+///     3,
+///     0.05,
+/// ));
+/// pattern.generate();
+/// ```
+pub fn parallel_traces<S>(
+    selector: S,
+    n: usize,
+    spacing: f64,
+) -> impl Fn(&mut Vec<Vec<Point2D>>, LayerKind, usize) + Send + Sync + 'static
+where
+    S: Fn(LayerKind, usize) -> bool + Send + Sync + 'static,
+{
+    move |lines, kind, index| {
+        if n < 2 || !selector(kind, index) {
+            return;
+        }
+        let middle = (n as f64 - 1.0) / 2.0;
+        *lines = lines
+            .iter()
+            .flat_map(|line| {
+                (0..n).map(move |k| {
+                    let distance = (k as f64 - middle) * spacing;
+                    if distance == 0.0 {
+                        line.clone()
+                    } else {
+                        offset_polyline(line, distance)
+                    }
+                })
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_radial_nudge_and_drop() {
+        let ring = |r: f64| vec![Point2D::new(r, 0.0), Point2D::new(0.0, r)];
+        let mut lines = vec![ring(1.0), ring(2.0), ring(3.0)];
+
+        let nudge = radial_nudge(|_, _, line| line == 1, 0.5);
+        nudge(&mut lines, LayerKind::Draperie, 0);
+        assert_eq!(lines, vec![ring(1.0), ring(2.5), ring(3.0)]);
+
+        let drop = drop_lines(|line| line[0].x > 2.0);
+        drop(&mut lines, LayerKind::Draperie, 0);
+        assert_eq!(lines, vec![ring(1.0)]);
+    }
+
+    #[test]
+    fn test_parallel_traces_centre_copies_on_each_line() {
+        let line = |y: f64| vec![Point2D::new(0.0, y), Point2D::new(4.0, y)];
+        let mut lines = vec![line(0.0), line(10.0)];
+        let traces = parallel_traces(|kind, _| kind == LayerKind::Paon, 3, 0.5);
+        traces(&mut lines, LayerKind::Draperie, 0);
+        assert_eq!(lines.len(), 2);
+
+        // Running in +x, positive offsets go right of travel (down, y up)
+        traces(&mut lines, LayerKind::Paon, 0);
+        let heights: Vec<f64> = lines.iter().map(|l| l[0].y).collect();
+        assert_eq!(heights, vec![0.5, 0.0, -0.5, 10.5, 10.0, 9.5]);
+        assert!(lines
+            .iter()
+            .all(|l| l[0].x.abs() < 1e-12 && (l[1].x - 4.0).abs() < 1e-12 && l[0].y == l[1].y));
+
+        parallel_traces(|_, _| true, 1, 0.5)(&mut lines, LayerKind::Paon, 0);
+        assert_eq!(lines.len(), 6);
+    }
+
+    #[test]
+    fn test_clip_annulus_splits_at_both_radii() {
+        let mut lines = vec![vec![Point2D::new(-3.0, 0.0), Point2D::new(3.0, 0.0)]];
+        let clip = clip_annulus(|kind, _| kind == LayerKind::ClousDeParis, 1.0, 2.0);
+        let before = lines.clone();
+        clip(&mut lines, LayerKind::Draperie, 0);
+        assert_eq!(lines, before);
+
+        clip(&mut lines, LayerKind::ClousDeParis, 0);
+        assert_eq!(
+            lines,
+            vec![
+                vec![Point2D::new(-2.0, 0.0), Point2D::new(-1.0, 0.0)],
+                vec![Point2D::new(1.0, 0.0), Point2D::new(2.0, 0.0)],
+            ]
+        );
+    }
+}
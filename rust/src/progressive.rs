@@ -0,0 +1,317 @@
+/// A pattern that can be generated at a fraction of its full detail
+///
+/// Implementors scale their resolution and line/ring/pass counts by the
+/// fraction and replace their stored lines with the coarse result. A
+/// fraction of 1.0 must produce exactly the output of a plain `generate()`.
+pub trait Progressive {
+    /// Generate at `fraction` (0 < fraction ≤ 1) of full detail
+    fn generate_at_fraction(&mut self, fraction: f32);
+}
+
+/// Scale a count by `fraction`, keeping at least `min` (or `count` if smaller)
+pub(crate) fn scale_count(count: usize, fraction: f32, min: usize) -> usize {
+    let scaled = (count as f64 * fraction as f64).ceil() as usize;
+    scaled.clamp(min.min(count), count)
+}
+
+/// A progressive pattern that can count the points it currently holds
+///
+/// Lets budgeted generation weigh heterogeneous layers against each other.
+pub(crate) trait PointCounted: Progressive {
+    /// Total points across the generated lines
+    fn point_count(&self) -> usize;
+}
+
+/// Coarsest fraction tried when fitting a budget; every layer's floors
+/// apply well before this
+const MIN_BUDGET_FRACTION: f32 = 1e-3;
+
+/// Reduce already-generated layers so their points fit `budget`
+///
+/// The budget is shared in proportion to each layer's full point count.
+/// Layers whose share is below what their minimum detail produces are
+/// pinned at that minimum and the rest is re-shared among the others, so
+/// the total can exceed a budget smaller than the sum of the floors. Each
+/// remaining layer is regenerated at the largest fraction that fits its
+/// share. Nothing changes when the layers already fit.
+pub(crate) fn fit_point_budget(layers: &mut [&mut dyn PointCounted], budget: usize) {
+    let full: Vec<usize> = layers.iter().map(|layer| layer.point_count()).collect();
+    if full.iter().sum::<usize>() <= budget {
+        return;
+    }
+
+    let floors: Vec<usize> = layers
+        .iter_mut()
+        .map(|layer| {
+            layer.generate_at_fraction(MIN_BUDGET_FRACTION);
+            layer.point_count()
+        })
+        .collect();
+
+    let mut pinned = vec![false; layers.len()];
+    let shares = loop {
+        let free_full: usize = (0..full.len())
+            .filter(|&i| !pinned[i])
+            .map(|i| full[i])
+            .sum();
+        let pinned_floor: usize = (0..full.len())
+            .filter(|&i| pinned[i])
+            .map(|i| floors[i])
+            .sum();
+        let free_budget = budget.saturating_sub(pinned_floor) as f64;
+        let shares: Vec<f64> = full
+            .iter()
+            .map(|&count| free_budget * count as f64 / free_full.max(1) as f64)
+            .collect();
+        let newly_pinned: Vec<usize> = (0..full.len())
+            .filter(|&i| !pinned[i] && shares[i] < floors[i] as f64)
+            .collect();
+        if newly_pinned.is_empty() {
+            break shares;
+        }
+        newly_pinned.into_iter().for_each(|i| pinned[i] = true);
+    };
+
+    for (i, layer) in layers.iter_mut().enumerate() {
+        if pinned[i] {
+            continue;
+        }
+        // Largest fraction whose output fits the share; the floor always does
+        let (mut fits, mut too_big) = (MIN_BUDGET_FRACTION, 1.0f32);
+        for _ in 0..14 {
+            let mid = 0.5 * (fits + too_big);
+            layer.generate_at_fraction(mid);
+            if layer.point_count() as f64 <= shares[i] {
+                fits = mid;
+            } else {
+                too_big = mid;
+            }
+        }
+        layer.generate_at_fraction(fits);
+    }
+}
+
+/// The sequence of detail levels a progressive generation steps through
+///
+/// Levels are clamped to (0, 1], sorted ascending and de-duplicated, and a
+/// final level of 1.0 is appended if missing, so the last step always
+/// reproduces a plain `generate()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressiveSchedule {
+    levels: Vec<f32>,
+    current: Option<usize>,
+}
+
+impl ProgressiveSchedule {
+    /// Build a schedule from requested level fractions
+    pub fn new(levels: &[f32]) -> Self {
+        let mut levels: Vec<f32> = levels
+            .iter()
+            .filter(|f| f.is_finite() && **f > 0.0)
+            .map(|f| f.min(1.0))
+            .collect();
+        levels.sort_by(|a, b| a.total_cmp(b));
+        levels.dedup();
+        if levels.last() != Some(&1.0) {
+            levels.push(1.0);
+        }
+        ProgressiveSchedule {
+            levels,
+            current: None,
+        }
+    }
+
+    /// The sanitized level fractions
+    pub fn levels(&self) -> &[f32] {
+        &self.levels
+    }
+
+    /// Index of the last computed level, or `None` before the first advance
+    pub fn current_level(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// True once the full-detail level has been computed
+    pub fn is_complete(&self) -> bool {
+        self.current == Some(self.levels.len() - 1)
+    }
+
+    /// Compute the next level on `target`; returns false when already complete
+    pub fn advance<T: Progressive + ?Sized>(&mut self, target: &mut T) -> bool {
+        let next = self.current.map_or(0, |c| c + 1);
+        match self.levels.get(next) {
+            Some(&fraction) => {
+                target.generate_at_fraction(fraction);
+                self.current = Some(next);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Handle driving a coarse-to-fine generation of a pattern
+///
+/// Nothing is computed until the first `advance()`. Each call computes the
+/// next level and replaces the pattern's stored lines; the final level is
+/// bit-identical to a plain `generate()`.
+///
+/// # Example
+/// ```
+/// use turtles::{DraperieConfig, DraperieLayer};
+///
+/// let mut layer = DraperieLayer::new(DraperieConfig::default()).unwrap();
+/// let mut handle = layer.generate_progressive(&[0.1, 0.3, 1.0]);
+/// while handle.advance() {
+///     let preview = handle.target().lines();
+///     assert!(!preview.is_empty());
+/// }
+/// assert_eq!(handle.current_level(), Some(2));
+/// ```
+pub struct ProgressiveHandle<'a, T: Progressive> {
+    target: &'a mut T,
+    schedule: ProgressiveSchedule,
+}
+
+impl<'a, T: Progressive> ProgressiveHandle<'a, T> {
+    /// Create a handle over `target` with the given level fractions
+    pub fn new(target: &'a mut T, levels: &[f32]) -> Self {
+        ProgressiveHandle {
+            target,
+            schedule: ProgressiveSchedule::new(levels),
+        }
+    }
+
+    /// Compute the next level; returns false when all levels are done
+    pub fn advance(&mut self) -> bool {
+        self.schedule.advance(self.target)
+    }
+
+    /// Index of the last computed level, or `None` before the first advance
+    pub fn current_level(&self) -> Option<usize> {
+        self.schedule.current_level()
+    }
+
+    /// Fraction of full detail of the last computed level
+    pub fn current_fraction(&self) -> Option<f32> {
+        self.schedule
+            .current_level()
+            .map(|i| self.schedule.levels()[i])
+    }
+
+    /// The sanitized level fractions
+    pub fn levels(&self) -> &[f32] {
+        self.schedule.levels()
+    }
+
+    /// True once the full-detail level has been computed
+    pub fn is_complete(&self) -> bool {
+        self.schedule.is_complete()
+    }
+
+    /// The pattern being generated, for reading the current level's lines
+    pub fn target(&self) -> &T {
+        self.target
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Point2D;
+    use crate::draperie::{DraperieConfig, DraperieLayer};
+    use crate::paon::{PaonConfig, PaonLayer};
+    use crate::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLatheRun};
+
+    fn point_count(lines: &[Vec<Point2D>]) -> usize {
+        lines.iter().map(|l| l.len()).sum()
+    }
+
+    /// Step through levels, checking point counts grow and the final level
+    /// matches plain generation exactly.
+    fn check_progressive<T, F>(mut progressive: T, mut plain: T, lines: F)
+    where
+        T: Progressive,
+        F: Fn(&T) -> Vec<Vec<Point2D>>,
+    {
+        let mut counts = Vec::new();
+        {
+            let mut handle = ProgressiveHandle::new(&mut progressive, &[0.1, 0.3, 1.0]);
+            assert_eq!(handle.current_level(), None);
+            while handle.advance() {
+                counts.push(point_count(&lines(handle.target())));
+            }
+            assert!(handle.is_complete());
+            assert_eq!(handle.current_fraction(), Some(1.0));
+        }
+
+        assert_eq!(counts.len(), 3);
+        assert!(
+            counts.windows(2).all(|w| w[0] < w[1]),
+            "point counts not increasing: {:?}",
+            counts
+        );
+
+        plain.generate_at_fraction(1.0);
+        assert_eq!(lines(&progressive), lines(&plain));
+    }
+
+    #[test]
+    fn test_schedule_sanitizes_levels() {
+        let schedule = ProgressiveSchedule::new(&[0.5, 0.1, 0.5, -1.0, 2.0]);
+        assert_eq!(schedule.levels(), &[0.1, 0.5, 1.0]);
+        assert_eq!(ProgressiveSchedule::new(&[]).levels(), &[1.0]);
+        assert_eq!(ProgressiveSchedule::new(&[0.2]).levels(), &[0.2, 1.0]);
+    }
+
+    #[test]
+    fn test_draperie_progressive() {
+        let layer = || DraperieLayer::new(DraperieConfig::default()).unwrap();
+        check_progressive(layer(), layer(), |l| l.lines().clone());
+
+        // Final level equals a plain generate()
+        let mut progressive = layer();
+        let mut handle = progressive.generate_progressive(&[0.25]);
+        while handle.advance() {}
+        let mut plain = layer();
+        plain.generate();
+        assert_eq!(progressive.lines(), plain.lines());
+    }
+
+    #[test]
+    fn test_paon_progressive() {
+        let layer = || PaonLayer::new(PaonConfig::default()).unwrap();
+        check_progressive(layer(), layer(), |l| l.lines().clone());
+
+        let mut progressive = layer();
+        let mut handle = progressive.generate_progressive(&[0.1, 0.3, 1.0]);
+        while handle.advance() {}
+        let mut plain = layer();
+        plain.generate();
+        assert_eq!(progressive.lines(), plain.lines());
+    }
+
+    #[test]
+    fn test_lathe_run_progressive() {
+        let run = || {
+            let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+            RoseEngineLatheRun::new(config, CuttingBit::default(), 24).unwrap()
+        };
+        check_progressive(run(), run(), |r| r.lines().clone());
+
+        let draperie = || {
+            RoseEngineLatheRun::new_draperie(
+                40, 22.0, 0.44, 12.0, 0.2, 2.5, 600, 3, 1, 2.0, 0.0, 0.0,
+            )
+            .unwrap()
+        };
+        check_progressive(draperie(), draperie(), |r| r.lines().clone());
+
+        let mut progressive = run();
+        let mut handle = progressive.generate_progressive(&[0.1, 0.3, 1.0]);
+        while handle.advance() {}
+        let mut plain = run();
+        plain.generate();
+        assert_eq!(progressive.lines(), plain.lines());
+    }
+}
@@ -0,0 +1,633 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::chapter_ring::ChapterRingConfig;
+use crate::clous_de_paris::{ClousDeParisConfig, ClousDeParisLayer};
+use crate::common::{validate_radius, SpirographError, SplitMix64};
+use crate::draperie::{DraperieConfig, DraperieLayer};
+use crate::flinque::{FlinqueConfig, FlinqueLayer};
+use crate::huiteight::{HuitEightConfig, HuitEightLayer};
+use crate::limacon::{LimaconConfig, LimaconLayer};
+use crate::post::{clip_annulus, LayerKind};
+use crate::watch_face::WatchFace;
+
+/// Families a random design may pick for its center motif
+const CENTER_FAMILIES: [LayerKind; 4] = [
+    LayerKind::Draperie,
+    LayerKind::Flinque,
+    LayerKind::Limacon,
+    LayerKind::HuitEight,
+];
+
+/// Families a random design may pick for its outer band
+const BAND_FAMILIES: [LayerKind; 2] = [LayerKind::ClousDeParis, LayerKind::Flinque];
+
+/// Fold counts shared by every layer of a random design
+///
+/// Each is a multiple of 4 but not of 8, so every family can put a crest
+/// at 12 o'clock: draperie waves and huit-eight clusters use the full
+/// count, flinqué petals half of it (which must be 2 mod 4) and limaçon
+/// curve counts a multiple of it.
+const SYMMETRY_ORDERS: [usize; 2] = [12, 20];
+
+/// Groove density of a random design
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Density {
+    Sparse,
+    #[default]
+    Medium,
+    Dense,
+}
+
+impl Density {
+    /// Nominal distance between neighbouring grooves in mm, before the
+    /// per-design variation
+    pub fn groove_pitch(self) -> f64 {
+        match self {
+            Density::Sparse => 0.6,
+            Density::Medium => 0.42,
+            Density::Dense => 0.3,
+        }
+    }
+
+    /// Lower-case name, as used by the Python bindings
+    pub fn name(self) -> &'static str {
+        match self {
+            Density::Sparse => "sparse",
+            Density::Medium => "medium",
+            Density::Dense => "dense",
+        }
+    }
+
+    /// The density whose `name()` is `name`
+    pub fn from_name(name: &str) -> Option<Density> {
+        [Density::Sparse, Density::Medium, Density::Dense]
+            .into_iter()
+            .find(|density| density.name() == name)
+    }
+}
+
+/// Limits on what `generate_random_design` may produce
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesignConstraints {
+    /// Dial radius in mm; `None` draws one from 30-42mm
+    pub radius: Option<f64>,
+    /// Pattern families that must not appear
+    pub forbidden: Vec<LayerKind>,
+    /// Groove density
+    pub density: Density,
+    /// Whether to add a minute-track chapter ring inside the dial edge
+    pub chapter_ring: bool,
+    /// Smallest groove pitch in mm the tooling can cut; designs below it
+    /// are re-rolled
+    pub min_groove_spacing: Option<f64>,
+    /// Draws tried before giving up on the constraints
+    pub max_attempts: usize,
+}
+
+impl Default for DesignConstraints {
+    fn default() -> Self {
+        DesignConstraints {
+            radius: None,
+            forbidden: Vec::new(),
+            density: Density::Medium,
+            chapter_ring: false,
+            min_groove_spacing: None,
+            max_attempts: 20,
+        }
+    }
+}
+
+impl DesignConstraints {
+    /// Check the pinned radius, spacing and attempt count
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if let Some(radius) = self.radius {
+            validate_radius(radius)?;
+        }
+        if let Some(spacing) = self.min_groove_spacing {
+            if !(spacing.is_finite() && spacing > 0.0) {
+                return Err(SpirographError::InvalidParameter(
+                    "min_groove_spacing must be positive".to_string(),
+                ));
+            }
+        }
+        if self.max_attempts == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "max_attempts must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Families from `families` that are not forbidden
+    fn allowed(&self, families: &[LayerKind]) -> Vec<LayerKind> {
+        families
+            .iter()
+            .copied()
+            .filter(|kind| !self.forbidden.contains(kind))
+            .collect()
+    }
+}
+
+/// One pattern layer of a random design, with every parameter it was
+/// drawn with; lengths are in mm
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "family", rename_all = "snake_case")]
+pub enum MotifRecipe {
+    Draperie {
+        num_rings: usize,
+        radius_step: f64,
+        base_radius: f64,
+        amplitude: f64,
+        wave_frequency: f64,
+        phase_shift: f64,
+        phase_oscillations: f64,
+    },
+    Flinque {
+        radius: f64,
+        inner_radius_ratio: f64,
+        num_petals: usize,
+        num_waves: usize,
+        wave_amplitude: f64,
+        wave_frequency: f64,
+    },
+    Limacon {
+        num_curves: usize,
+        base_radius: f64,
+        amplitude: f64,
+    },
+    HuitEight {
+        num_curves: usize,
+        scale: f64,
+        num_clusters: usize,
+    },
+    /// Hobnails between `inner_radius` and `radius`
+    ClousDeParis {
+        spacing: f64,
+        radius: f64,
+        inner_radius: f64,
+        angle: f64,
+    },
+}
+
+impl MotifRecipe {
+    /// Layer kind the motif is added as
+    pub fn kind(&self) -> LayerKind {
+        match self {
+            MotifRecipe::Draperie { .. } => LayerKind::Draperie,
+            MotifRecipe::Flinque { .. } => LayerKind::Flinque,
+            MotifRecipe::Limacon { .. } => LayerKind::Limacon,
+            MotifRecipe::HuitEight { .. } => LayerKind::HuitEight,
+            MotifRecipe::ClousDeParis { .. } => LayerKind::ClousDeParis,
+        }
+    }
+
+    /// Nominal distance between neighbouring grooves in mm
+    ///
+    /// Ring steps for the concentric families and the hobnail spacing are
+    /// exact. The limaçon uses the mean radial gap between neighbouring
+    /// curves and the huit-eight the gap between neighbouring lobe tips
+    /// within a cluster; both families cross themselves at the centre by
+    /// design.
+    pub fn groove_pitch(&self) -> f64 {
+        match *self {
+            MotifRecipe::Draperie { radius_step, .. } => radius_step,
+            MotifRecipe::Flinque {
+                radius,
+                inner_radius_ratio,
+                num_waves,
+                ..
+            } => radius * (1.0 - inner_radius_ratio) / num_waves as f64,
+            MotifRecipe::Limacon {
+                num_curves,
+                amplitude,
+                ..
+            } => 4.0 / PI * amplitude * (PI / num_curves as f64).sin(),
+            MotifRecipe::HuitEight {
+                num_curves,
+                scale,
+                num_clusters,
+            } if num_clusters > 0 => {
+                let per_cluster = num_curves / num_clusters;
+                scale * (PI / num_clusters as f64) / per_cluster.saturating_sub(1).max(1) as f64
+            }
+            MotifRecipe::HuitEight {
+                num_curves, scale, ..
+            } => scale * 2.0 * PI / num_curves.max(1) as f64,
+            MotifRecipe::ClousDeParis { spacing, .. } => spacing,
+        }
+    }
+
+    /// Add the motif to `face`
+    fn add_to(&self, face: &mut WatchFace) -> Result<(), SpirographError> {
+        match *self {
+            MotifRecipe::Draperie {
+                num_rings,
+                radius_step,
+                base_radius,
+                amplitude,
+                wave_frequency,
+                phase_shift,
+                phase_oscillations,
+            } => {
+                let config = DraperieConfig {
+                    radius_step,
+                    wave_frequency,
+                    amplitude: Some(amplitude),
+                    phase_shift,
+                    phase_oscillations,
+                    ..DraperieConfig::new(num_rings, base_radius)
+                };
+                face.add_draperie_layer(DraperieLayer::new(config)?);
+            }
+            MotifRecipe::Flinque {
+                radius,
+                inner_radius_ratio,
+                num_petals,
+                num_waves,
+                wave_amplitude,
+                wave_frequency,
+            } => {
+                let config = FlinqueConfig {
+                    num_petals,
+                    num_waves,
+                    wave_amplitude,
+                    wave_frequency,
+                    inner_radius_ratio,
+                    resolution: None,
+                    petal_phase_step: 0.0,
+                    alternate: false,
+                    jitter: None,
+                };
+                face.add_flinque_layer(FlinqueLayer::new(radius, config)?);
+            }
+            MotifRecipe::Limacon {
+                num_curves,
+                base_radius,
+                amplitude,
+            } => {
+                let config = LimaconConfig::new(num_curves, base_radius, amplitude);
+                face.add_limacon_layer(LimaconLayer::new(config)?);
+            }
+            MotifRecipe::HuitEight {
+                num_curves,
+                scale,
+                num_clusters,
+            } => {
+                let config =
+                    HuitEightConfig::new(num_curves, scale).with_clusters(num_clusters, 0.0);
+                face.add_huiteight_layer(HuitEightLayer::new(config)?);
+            }
+            MotifRecipe::ClousDeParis {
+                spacing,
+                radius,
+                inner_radius,
+                angle,
+            } => {
+                let config = ClousDeParisConfig {
+                    angle,
+                    ..ClousDeParisConfig::new(spacing, radius)
+                };
+                let index = face.guilloche.kind_count(LayerKind::ClousDeParis);
+                face.add_clous_de_paris_layer(ClousDeParisLayer::new(config)?);
+                face.add_post_process(clip_annulus(
+                    move |kind, i| kind == LayerKind::ClousDeParis && i == index,
+                    inner_radius,
+                    f64::INFINITY,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Every choice behind a random design
+///
+/// Serializes to a plain record that can be saved, edited and rebuilt
+/// with `build()`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DesignRecipe {
+    /// Seed the design was drawn from
+    pub seed: u64,
+    /// Dial radius in mm
+    pub radius: f64,
+    /// Requested groove density
+    pub density: Density,
+    /// Fold count shared by the layers (see the crest alignment notes on
+    /// `generate_random_design`)
+    pub symmetry: usize,
+    /// Motif filling the centre of the dial
+    pub center: MotifRecipe,
+    /// Motif on the band around the centre
+    pub band: MotifRecipe,
+    /// Chapter ring track as (r_inner, r_outer) in mm
+    pub chapter_ring: Option<(f64, f64)>,
+}
+
+impl DesignRecipe {
+    /// Assemble the watch face the recipe describes
+    ///
+    /// The face has a dial, a center hole and the recipe's layers; call
+    /// `generate()` on it as usual.
+    pub fn build(&self) -> Result<WatchFace, SpirographError> {
+        let mut face = WatchFace::new(self.radius)?;
+        face.add_inner();
+        face.add_center_hole();
+        self.center.add_to(&mut face)?;
+        self.band.add_to(&mut face)?;
+        if let Some((r_inner, r_outer)) = self.chapter_ring {
+            face.add_chapter_ring(ChapterRingConfig::new(r_inner, r_outer))?;
+        }
+        Ok(face)
+    }
+
+    /// Smallest nominal groove pitch over every layer, in mm
+    ///
+    /// Chapter ring ticks count by their spacing along the inner track.
+    pub fn min_groove_spacing(&self) -> f64 {
+        let ticks = self.chapter_ring.map_or(f64::INFINITY, |(r_inner, _)| {
+            2.0 * r_inner * (PI / ChapterRingConfig::default().minutes as f64).sin()
+        });
+        self.center
+            .groove_pitch()
+            .min(self.band.groove_pitch())
+            .min(ticks)
+    }
+}
+
+/// Round a drawn value to 3 decimals so recipes stay readable
+fn round3(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+/// Draw a center motif of `family` filling radius `extent`
+fn draw_center(
+    rng: &mut SplitMix64,
+    family: LayerKind,
+    extent: f64,
+    pitch: f64,
+    symmetry: usize,
+) -> MotifRecipe {
+    match family {
+        LayerKind::Draperie => {
+            // Rings from 15% of the extent out, leaving room for the waves
+            let amplitude_cap = 0.04 * extent;
+            let (inner, outer) = (0.15 * extent, extent - amplitude_cap);
+            let num_rings = ((outer - inner) / pitch) as usize + 1;
+            let radius_step = (outer - inner) / (num_rings - 1).max(1) as f64;
+            let phase_shift = round3(rng.range(PI / 24.0, PI / 8.0));
+            let phase_oscillations = (rng.range(1.5, 3.5) * 2.0).round() / 2.0;
+            let config = DraperieConfig {
+                radius_step,
+                wave_frequency: symmetry as f64,
+                phase_shift,
+                phase_oscillations,
+                ..DraperieConfig::new(num_rings, (inner + outer) / 2.0)
+            };
+            MotifRecipe::Draperie {
+                num_rings,
+                radius_step,
+                base_radius: config.base_radius,
+                amplitude: round3(config.safe_amplitude().min(amplitude_cap)),
+                wave_frequency: config.wave_frequency,
+                phase_shift,
+                phase_oscillations,
+            }
+        }
+        LayerKind::Flinque => {
+            let inner_radius_ratio = round3(rng.range(0.03, 0.08));
+            let wave_amplitude = round3(pitch * rng.range(1.5, 3.0));
+            let radius = extent - wave_amplitude;
+            MotifRecipe::Flinque {
+                radius,
+                inner_radius_ratio,
+                num_petals: symmetry / 2,
+                num_waves: ((radius * (1.0 - inner_radius_ratio) / pitch).round() as usize).max(1),
+                wave_amplitude,
+                wave_frequency: rng.range(8.0, 24.0).round(),
+            }
+        }
+        LayerKind::Limacon => {
+            // base + amplitude reaches the extent
+            let ratio = round3(rng.range(0.7, 1.0));
+            let base_radius = extent / (1.0 + ratio);
+            let amplitude = base_radius * ratio;
+            let multiple = ((4.0 * amplitude / pitch / symmetry as f64).round() as usize).max(1);
+            MotifRecipe::Limacon {
+                num_curves: multiple * symmetry,
+                base_radius,
+                amplitude,
+            }
+        }
+        _ => {
+            // Huit-eight: auto cluster spread is π / clusters
+            let per_cluster =
+                ((extent * PI / (symmetry as f64 * pitch)).round() as usize + 1).max(2);
+            MotifRecipe::HuitEight {
+                num_curves: per_cluster * symmetry,
+                scale: extent,
+                num_clusters: symmetry,
+            }
+        }
+    }
+}
+
+/// Draw an outer band of `family` between radii `inner` and `outer`
+fn draw_band(
+    rng: &mut SplitMix64,
+    family: LayerKind,
+    (inner, outer): (f64, f64),
+    pitch: f64,
+    symmetry: usize,
+) -> MotifRecipe {
+    match family {
+        LayerKind::Flinque => {
+            let wave_amplitude = round3(pitch * rng.range(1.5, 3.0));
+            let radius = outer - wave_amplitude;
+            MotifRecipe::Flinque {
+                radius,
+                inner_radius_ratio: inner / radius,
+                num_petals: symmetry / 2,
+                num_waves: (((radius - inner) / pitch).round() as usize).max(1),
+                wave_amplitude,
+                wave_frequency: rng.range(8.0, 24.0).round(),
+            }
+        }
+        _ => MotifRecipe::ClousDeParis {
+            spacing: round3(pitch * rng.range(2.5, 4.0)),
+            radius: outer,
+            inner_radius: inner,
+            angle: PI / 4.0,
+        },
+    }
+}
+
+/// Draw one candidate recipe from `rng`
+fn draw_recipe(
+    rng: &mut SplitMix64,
+    seed: u64,
+    constraints: &DesignConstraints,
+    centers: &[LayerKind],
+    bands: &[LayerKind],
+) -> DesignRecipe {
+    let radius = constraints
+        .radius
+        .unwrap_or_else(|| (rng.range(30.0, 42.0) * 2.0).round() / 2.0);
+    let symmetry = SYMMETRY_ORDERS[rng.below(SYMMETRY_ORDERS.len())];
+    let pitch = constraints.density.groove_pitch() * rng.range(0.85, 1.2);
+    let gap = (4.0 * pitch).max(0.03 * radius);
+
+    let chapter_ring = constraints.chapter_ring.then(|| {
+        let r_outer = 0.94 * radius;
+        (round3(r_outer - radius * rng.range(0.06, 0.09)), r_outer)
+    });
+    let pattern_outer = chapter_ring.map_or(0.94 * radius, |(r_inner, _)| r_inner - gap);
+    let center_extent = pattern_outer * rng.range(0.45, 0.6);
+
+    let center_family = centers[rng.below(centers.len())];
+    let band_family = bands[rng.below(bands.len())];
+    DesignRecipe {
+        seed,
+        radius,
+        density: constraints.density,
+        symmetry,
+        center: draw_center(rng, center_family, center_extent, pitch, symmetry),
+        band: draw_band(
+            rng,
+            band_family,
+            (center_extent + gap, pattern_outer),
+            pitch,
+            symmetry,
+        ),
+        chapter_ring,
+    }
+}
+
+/// Assemble a plausible random watch face from `seed`
+///
+/// A center motif (draperie, flinqué, limaçon or huit-eight) is surrounded
+/// by a band of clous de Paris or flinqué, optionally inside a chapter
+/// ring. Groove counts follow from the dial radius and the density, so a
+/// larger dial gets more grooves at the same pitch. Every layer repeats
+/// with the recipe's `symmetry`, chosen so each family puts a crest at 12
+/// o'clock and the crests of the center and band line up.
+///
+/// Everything is drawn from a PRNG seeded with `seed`, so the same seed and
+/// constraints always give the same recipe and geometry. A draw that fails
+/// layer validation or `min_groove_spacing` is re-rolled from the same
+/// stream, up to `max_attempts` draws.
+///
+/// # Example
+/// ```
+/// use turtles::{generate_random_design, DesignConstraints};
+///
+/// let constraints = DesignConstraints {
+///     radius: Some(38.0),
+///     chapter_ring: true,
+///     ..DesignConstraints::default()
+/// };
+/// let (mut face, recipe) = generate_random_design(7, &constraints).unwrap();
+/// assert_eq!(recipe.radius, 38.0);
+/// face.generate();
+/// ```
+pub fn generate_random_design(
+    seed: u64,
+    constraints: &DesignConstraints,
+) -> Result<(WatchFace, DesignRecipe), SpirographError> {
+    constraints.validate()?;
+    let centers = constraints.allowed(&CENTER_FAMILIES);
+    let bands = constraints.allowed(&BAND_FAMILIES);
+    if centers.is_empty() || bands.is_empty() {
+        return Err(SpirographError::InvalidParameter(
+            "forbidden families leave no center motif or outer band".to_string(),
+        ));
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    for _ in 0..constraints.max_attempts {
+        let recipe = draw_recipe(&mut rng, seed, constraints, &centers, &bands);
+        if constraints
+            .min_groove_spacing
+            .is_some_and(|spacing| recipe.min_groove_spacing() < spacing)
+        {
+            continue;
+        }
+        let face = recipe.build()?;
+        if face.validate().is_empty() {
+            return Ok((face, recipe));
+        }
+    }
+    Err(SpirographError::InvalidParameter(format!(
+        "no design met the constraints in {} attempts",
+        constraints.max_attempts
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::geometry_hash;
+
+    fn geometry(face: &mut WatchFace) -> u64 {
+        face.generate();
+        let lines = face.lines();
+        geometry_hash(lines.iter().map(Vec::as_slice))
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_design() {
+        let constraints = DesignConstraints::default();
+        let (mut a, recipe_a) = generate_random_design(42, &constraints).unwrap();
+        let (mut b, recipe_b) = generate_random_design(42, &constraints).unwrap();
+        assert_eq!(recipe_a, recipe_b);
+        assert_eq!(geometry(&mut a), geometry(&mut b));
+
+        // A saved recipe rebuilds the same face
+        let json = serde_json::to_string(&recipe_a).unwrap();
+        let restored: DesignRecipe = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, recipe_a);
+        assert_eq!(geometry(&mut restored.build().unwrap()), geometry(&mut a));
+
+        let (mut c, recipe_c) = generate_random_design(43, &constraints).unwrap();
+        assert_ne!(recipe_c, recipe_a);
+        assert_ne!(geometry(&mut c), geometry(&mut a));
+    }
+
+    #[test]
+    fn test_constraints_hold_across_seeds() {
+        let constraints = DesignConstraints {
+            radius: Some(36.0),
+            forbidden: vec![LayerKind::Draperie, LayerKind::ClousDeParis],
+            density: Density::Dense,
+            chapter_ring: true,
+            min_groove_spacing: Some(0.3),
+            max_attempts: 50,
+        };
+        let mut centers = std::collections::HashSet::new();
+        for seed in 0..50 {
+            let (face, recipe) = generate_random_design(seed, &constraints).unwrap();
+            assert_eq!(recipe.radius, 36.0);
+            assert_eq!(face.radius(), 36.0);
+            assert_eq!(recipe.density, Density::Dense);
+            for motif in [&recipe.center, &recipe.band] {
+                assert!(!constraints.forbidden.contains(&motif.kind()));
+            }
+            assert!(recipe.min_groove_spacing() >= 0.3);
+            let (r_inner, r_outer) = recipe.chapter_ring.unwrap();
+            assert!(r_outer <= 36.0 && r_inner < r_outer);
+            assert_eq!(face.guilloche.kind_count(LayerKind::ChapterRing), 1);
+            assert!(face.validate().is_empty());
+            centers.insert(recipe.center.kind().name());
+        }
+        // Every allowed center family turns up
+        assert_eq!(centers.len(), 3);
+
+        let impossible = DesignConstraints {
+            forbidden: vec![LayerKind::ClousDeParis, LayerKind::Flinque],
+            ..DesignConstraints::default()
+        };
+        assert!(generate_random_design(0, &impossible).is_err());
+    }
+}
@@ -0,0 +1,688 @@
+use std::borrow::Cow;
+
+use crate::common::{Point2D, SpirographError};
+
+/// Millimetres per inch, for converting dpi to pixels per millimetre
+const MM_PER_INCH: f64 = 25.4;
+
+/// How anti-aliased stroke coverage is reduced to 1-bit pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dither {
+    /// 8×8 Bayer threshold matrix: a fixed, tiling pattern, so repeated
+    /// grooves stay identical wherever they fall on the pixel grid
+    #[default]
+    Ordered,
+    /// Floyd–Steinberg error diffusion: partial edge coverage is carried to
+    /// neighbouring pixels, keeping stroke weight without a fixed pattern
+    ErrorDiffusion,
+}
+
+/// A packed 1-bit raster for laser engravers
+///
+/// Rows run top to bottom in the same orientation as the SVG exports (y
+/// down). Each row is `stride()` bytes with the leftmost pixel in the most
+/// significant bit; a set bit means "burn".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bitmap1 {
+    /// Width in pixels
+    pub width: usize,
+    /// Height in pixels
+    pub height: usize,
+    /// Resolution in dots per inch
+    pub dpi: u32,
+    /// Design position, in mm, of the top-left corner of pixel (0, 0)
+    pub origin_mm: (f64, f64),
+    data: Vec<u8>,
+}
+
+impl Bitmap1 {
+    /// Blank bitmap of `width × height` pixels
+    pub fn new(width: usize, height: usize, dpi: u32, origin_mm: (f64, f64)) -> Self {
+        Bitmap1 {
+            width,
+            height,
+            dpi,
+            origin_mm,
+            data: vec![0; width.div_ceil(8) * height],
+        }
+    }
+
+    /// Bytes per row
+    pub fn stride(&self) -> usize {
+        self.width.div_ceil(8)
+    }
+
+    /// Packed rows, `stride()` bytes each
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Whether pixel (x, y) is set; out-of-range pixels are clear
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width
+            && y < self.height
+            && self.data[y * self.stride() + x / 8] & (0x80 >> (x % 8)) != 0
+    }
+
+    /// Set or clear pixel (x, y)
+    pub fn set(&mut self, x: usize, y: usize, on: bool) {
+        let index = y * self.stride() + x / 8;
+        let mask = 0x80 >> (x % 8);
+        if on {
+            self.data[index] |= mask;
+        } else {
+            self.data[index] &= !mask;
+        }
+    }
+
+    /// Number of set pixels
+    pub fn count_set(&self) -> usize {
+        let stride = self.stride();
+        let tail = self.width % 8;
+        self.data
+            .chunks(stride.max(1))
+            .map(|row| {
+                let full: u32 = row.iter().map(|b| b.count_ones()).sum();
+                // Padding bits past the last column are never set, but mask
+                // them anyway so callers writing through `set` can't skew it
+                match (tail, row.last()) {
+                    (0, _) | (_, None) => full,
+                    (t, Some(&last)) => full - (last & (0xFF >> t)).count_ones(),
+                }
+            })
+            .sum::<u32>() as usize
+    }
+
+    /// Write a binary PBM (P4) file; set pixels are black
+    pub fn write_pbm(&self, path: &str) -> Result<(), SpirographError> {
+        let mut out = format!("P4\n{} {}\n", self.width, self.height).into_bytes();
+        out.extend_from_slice(&self.data);
+        write_file(path, "PBM", &out)
+    }
+
+    /// Write a 1-bit greyscale PNG file; set pixels are black
+    ///
+    /// The image data is stored uncompressed, so the file is about the
+    /// size of the packed buffer. The pixel density is recorded in a pHYs
+    /// chunk so engraver software picks up the physical size.
+    pub fn write_png(&self, path: &str) -> Result<(), SpirographError> {
+        // Bit depth 1, greyscale
+        let ihdr = png_header(self.width, self.height, 1, 0)?;
+
+        let pixels_per_metre = (f64::from(self.dpi) * 1000.0 / MM_PER_INCH).round() as u32;
+        let mut phys = Vec::with_capacity(9);
+        phys.extend_from_slice(&pixels_per_metre.to_be_bytes());
+        phys.extend_from_slice(&pixels_per_metre.to_be_bytes());
+        phys.push(1); // unit: metre
+
+        // Greyscale 0 is black, so burn bits are inverted; each row is
+        // prefixed with filter type 0
+        let stride = self.stride();
+        let mut raw = Vec::with_capacity((stride + 1) * self.height);
+        for row in self.data.chunks(stride) {
+            raw.push(0);
+            raw.extend(row.iter().map(|b| !b));
+        }
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &ihdr);
+        png_chunk(&mut png, b"pHYs", &phys);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut png, b"IEND", &[]);
+        write_file(path, "PNG", &png)
+    }
+}
+
+/// An 8-bit RGBA image for previews
+///
+/// Rows run top to bottom in the same orientation as the SVG exports (y
+/// down), four bytes per pixel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RgbaImage {
+    /// Width in pixels
+    pub width: usize,
+    /// Height in pixels
+    pub height: usize,
+    data: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Opaque white image of `width × height` pixels
+    pub fn new(width: usize, height: usize) -> Self {
+        RgbaImage {
+            width,
+            height,
+            data: vec![0xFF; width * height * 4],
+        }
+    }
+
+    /// RGBA bytes, row by row
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The RGBA value of pixel (x, y), if it is in range
+    pub fn pixel(&self, x: usize, y: usize) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = 4 * (y * self.width + x);
+        Some([
+            self.data[i],
+            self.data[i + 1],
+            self.data[i + 2],
+            self.data[i + 3],
+        ])
+    }
+
+    /// Paint `color` over pixel `index` (row-major) with coverage `alpha`
+    fn blend(&mut self, index: usize, color: [u8; 3], alpha: f32) {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let pixel = &mut self.data[4 * index..4 * index + 3];
+        for (channel, &value) in pixel.iter_mut().zip(&color) {
+            let mixed = f32::from(*channel) * (1.0 - alpha) + f32::from(value) * alpha;
+            *channel = mixed.round() as u8;
+        }
+    }
+
+    /// Encode as an 8-bit RGBA PNG
+    ///
+    /// As with [`Bitmap1::write_png`] the image data is stored
+    /// uncompressed.
+    pub fn to_png_bytes(&self) -> Result<Vec<u8>, SpirographError> {
+        // Bit depth 8, truecolor with alpha
+        let ihdr = png_header(self.width, self.height, 8, 6)?;
+        let stride = 4 * self.width;
+        let mut raw = Vec::with_capacity((stride + 1) * self.height);
+        for row in self.data.chunks(stride) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &ihdr);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut png, b"IEND", &[]);
+        Ok(png)
+    }
+
+    /// Write an 8-bit RGBA PNG file
+    pub fn write_png(&self, path: &str) -> Result<(), SpirographError> {
+        write_file(path, "PNG", &self.to_png_bytes()?)
+    }
+}
+
+/// IHDR data for a `width × height` image with deflate, adaptive
+/// filtering and no interlace
+fn png_header(
+    width: usize,
+    height: usize,
+    bit_depth: u8,
+    color_type: u8,
+) -> Result<Vec<u8>, SpirographError> {
+    let (width, height) = match (u32::try_from(width), u32::try_from(height)) {
+        (Ok(w), Ok(h)) if w > 0 && h > 0 => (w, h),
+        _ => {
+            return Err(SpirographError::ExportError(
+                "PNG dimensions must be between 1 and 2^32 - 1 pixels".to_string(),
+            ))
+        }
+    };
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[bit_depth, color_type, 0, 0, 0]);
+    Ok(ihdr)
+}
+
+fn write_file(path: &str, kind: &str, bytes: &[u8]) -> Result<(), SpirographError> {
+    std::fs::write(path, bytes).map_err(|e| {
+        SpirographError::ExportError(format!("Failed to write {} file '{}': {}", kind, path, e))
+    })
+}
+
+/// Append a PNG chunk: length, type, data and CRC of type + data
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(kind.iter().chain(data));
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// CRC-32 (ISO-HDLC), as used by PNG
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Wrap `data` in a zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xFFFF;
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_BLOCK * 5 + 11);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    out
+}
+
+/// Rasterize polylines as round-ended strokes into a 1-bit bitmap
+///
+/// Coordinates are in mm. The bitmap covers `bounds` (min x, min y, max x,
+/// max y). Each pixel's coverage is how far its centre lies inside the
+/// stroke, with a one-pixel linear ramp at the edge; overlapping strokes
+/// take the maximum, so joints and crossings never darken twice. Strokes
+/// are stamped segment by segment over each segment's own bounding box, so
+/// the cost grows with the inked area rather than the image size.
+pub(crate) fn rasterize_strokes<'a, I>(
+    lines: I,
+    bounds: (f64, f64, f64, f64),
+    dpi: u32,
+    line_width_mm: f64,
+    dither: Dither,
+) -> Result<Bitmap1, SpirographError>
+where
+    I: IntoIterator<Item = &'a [Point2D]>,
+{
+    if dpi == 0 {
+        return Err(SpirographError::InvalidParameter(
+            "dpi must be at least 1".to_string(),
+        ));
+    }
+    if !(line_width_mm.is_finite() && line_width_mm > 0.0) {
+        return Err(SpirographError::InvalidParameter(
+            "line_width_mm must be positive".to_string(),
+        ));
+    }
+
+    let px_per_mm = f64::from(dpi) / MM_PER_INCH;
+    let (min_x, min_y, max_x, max_y) = bounds;
+    let width = ((max_x - min_x) * px_per_mm).ceil().max(1.0) as usize;
+    let height = ((max_y - min_y) * px_per_mm).ceil().max(1.0) as usize;
+    let half = 0.5 * line_width_mm * px_per_mm;
+    let reach = half + 0.5;
+
+    let mut coverage = vec![0.0_f32; width * height];
+    let to_px = |p: &Point2D| ((p.x - min_x) * px_per_mm, (p.y - min_y) * px_per_mm);
+    for line in lines {
+        let points: Vec<(f64, f64)> = line.iter().map(to_px).collect();
+        if let [dot] = points[..] {
+            stamp_segment(&mut coverage, width, height, dot, dot, half, reach);
+        }
+        for pair in points.windows(2) {
+            stamp_segment(&mut coverage, width, height, pair[0], pair[1], half, reach);
+        }
+    }
+
+    let mut bitmap = Bitmap1::new(width, height, dpi, (min_x, min_y));
+    match dither {
+        Dither::Ordered => {
+            for y in 0..height {
+                for x in 0..width {
+                    let threshold = (f32::from(BAYER_8[y % 8][x % 8]) + 0.5) / 64.0;
+                    if coverage[y * width + x] > threshold {
+                        bitmap.set(x, y, true);
+                    }
+                }
+            }
+        }
+        Dither::ErrorDiffusion => {
+            for y in 0..height {
+                for x in 0..width {
+                    let value = coverage[y * width + x];
+                    let on = value >= 0.5;
+                    if on {
+                        bitmap.set(x, y, true);
+                    }
+                    let error = value - if on { 1.0 } else { 0.0 };
+                    let mut spread = |dx: isize, dy: usize, weight: f32| {
+                        let nx = x as isize + dx;
+                        let ny = y + dy;
+                        if nx >= 0 && (nx as usize) < width && ny < height {
+                            coverage[ny * width + nx as usize] += error * weight;
+                        }
+                    };
+                    spread(1, 0, 7.0 / 16.0);
+                    spread(-1, 1, 3.0 / 16.0);
+                    spread(0, 1, 5.0 / 16.0);
+                    spread(1, 1, 1.0 / 16.0);
+                }
+            }
+        }
+    }
+    Ok(bitmap)
+}
+
+/// A polyline as an SVG export strokes it, for [`render_preview`]
+///
+/// The width is in design units; a single point is drawn as a dot the
+/// stroke's width across.
+pub(crate) struct PreviewStroke<'a> {
+    pub points: Cow<'a, [Point2D]>,
+    pub closed: bool,
+    pub width: f64,
+    pub color: [u8; 3],
+    pub opacity: f64,
+}
+
+/// Render strokes in order over a white background
+///
+/// `view_box` (x, y, width, height) is fitted into the image and centred,
+/// like an SVG viewBox with the default `xMidYMid meet`. Coverage is
+/// anti-aliased as in [`rasterize_strokes`], and each stroke is blended
+/// over what is already drawn with its coverage times its opacity.
+pub(crate) fn render_preview<'a, I>(
+    strokes: I,
+    view_box: (f64, f64, f64, f64),
+    width_px: u32,
+    height_px: u32,
+) -> Result<RgbaImage, SpirographError>
+where
+    I: IntoIterator<Item = PreviewStroke<'a>>,
+{
+    if width_px == 0 || height_px == 0 {
+        return Err(SpirographError::InvalidParameter(
+            "preview width and height must be at least 1 pixel".to_string(),
+        ));
+    }
+    let (view_x, view_y, view_width, view_height) = view_box;
+    if !(view_width > 0.0 && view_height > 0.0) {
+        return Err(SpirographError::ExportError(
+            "Nothing to render: the view is empty".to_string(),
+        ));
+    }
+
+    let (width, height) = (width_px as usize, height_px as usize);
+    let scale = (width as f64 / view_width).min(height as f64 / view_height);
+    let offset_x = (width as f64 - view_width * scale) / 2.0;
+    let offset_y = (height as f64 - view_height * scale) / 2.0;
+    let to_px = |p: &Point2D| {
+        (
+            (p.x - view_x) * scale + offset_x,
+            (p.y - view_y) * scale + offset_y,
+        )
+    };
+
+    let mut image = RgbaImage::new(width, height);
+    let mut coverage = vec![0.0_f32; width * height];
+    for stroke in strokes {
+        if stroke.points.is_empty() || stroke.opacity <= 0.0 {
+            continue;
+        }
+        let half = 0.5 * stroke.width * scale;
+        let reach = half + 0.5;
+        let points: Vec<(f64, f64)> = stroke.points.iter().map(to_px).collect();
+        let mut segments: Vec<((f64, f64), (f64, f64))> =
+            points.windows(2).map(|pair| (pair[0], pair[1])).collect();
+        match points[..] {
+            [dot] => segments.push((dot, dot)),
+            [first, .., last] if stroke.closed => segments.push((last, first)),
+            _ => {}
+        }
+        for &(a, b) in &segments {
+            stamp_segment(&mut coverage, width, height, a, b, half, reach);
+        }
+
+        // Blend and clear only the stroke's own pixels
+        let (min_x, min_y, max_x, max_y) = points.iter().fold(
+            (
+                f64::INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NEG_INFINITY,
+            ),
+            |(x0, y0, x1, y1), &(x, y)| (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+        );
+        let x0 = (min_x - reach).floor().max(0.0) as usize;
+        let y0 = (min_y - reach).floor().max(0.0) as usize;
+        let x1 = ((max_x + reach).ceil().max(0.0) as usize).min(width);
+        let y1 = ((max_y + reach).ceil().max(0.0) as usize).min(height);
+        let opacity = stroke.opacity.min(1.0) as f32;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let index = y * width + x;
+                let cover = std::mem::take(&mut coverage[index]);
+                if cover > 0.0 {
+                    image.blend(index, stroke.color, cover * opacity);
+                }
+            }
+        }
+    }
+    Ok(image)
+}
+
+/// RGB of an SVG color: `#rgb`, `#rrggbb`, `black` or `white`
+///
+/// Anything else is drawn in the default stroke color, `#1a1a1a`.
+pub(crate) fn svg_color_rgb(color: &str) -> [u8; 3] {
+    const DEFAULT: [u8; 3] = [0x1a, 0x1a, 0x1a];
+    let color = color.trim();
+    if color.eq_ignore_ascii_case("black") {
+        return [0, 0, 0];
+    }
+    if color.eq_ignore_ascii_case("white") {
+        return [0xFF, 0xFF, 0xFF];
+    }
+    let Some(hex) = color.strip_prefix('#').filter(|h| h.is_ascii()) else {
+        return DEFAULT;
+    };
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+    let rgb = match hex.len() {
+        3 => hex
+            .chars()
+            .map(|c| channel(&c.to_string()).map(|v| v * 17))
+            .collect::<Option<Vec<u8>>>(),
+        6 => (0..3)
+            .map(|i| channel(&hex[2 * i..2 * i + 2]))
+            .collect::<Option<Vec<u8>>>(),
+        _ => None,
+    };
+    match rgb {
+        Some(rgb) => [rgb[0], rgb[1], rgb[2]],
+        None => DEFAULT,
+    }
+}
+
+/// 8×8 Bayer index matrix
+const BAYER_8: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// Raise coverage around segment `a`–`b` (pixel units) to the stroke's own
+fn stamp_segment(
+    coverage: &mut [f32],
+    width: usize,
+    height: usize,
+    a: (f64, f64),
+    b: (f64, f64),
+    half: f64,
+    reach: f64,
+) {
+    let x0 = (a.0.min(b.0) - reach).floor().max(0.0);
+    let y0 = (a.1.min(b.1) - reach).floor().max(0.0);
+    let x1 = (a.0.max(b.0) + reach).ceil().min(width as f64);
+    let y1 = (a.1.max(b.1) + reach).ceil().min(height as f64);
+    if !(x0 < x1 && y0 < y1) {
+        return;
+    }
+
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dy * dy;
+    for y in y0 as usize..y1 as usize {
+        let py = y as f64 + 0.5;
+        for x in x0 as usize..x1 as usize {
+            let px = x as f64 + 0.5;
+            let t = if len_sq > 0.0 {
+                (((px - a.0) * dx + (py - a.1) * dy) / len_sq).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let distance = (px - a.0 - t * dx).hypot(py - a.1 - t * dy);
+            let cover = (half + 0.5 - distance).clamp(0.0, 1.0) as f32;
+            let cell = &mut coverage[y * width + x];
+            if cover > *cell {
+                *cell = cover;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Set pixels in column `x`
+    fn column_run(bitmap: &Bitmap1, x: usize) -> usize {
+        (0..bitmap.height).filter(|&y| bitmap.get(x, y)).count()
+    }
+
+    #[test]
+    fn test_straight_groove_width_and_dpi_scaling() {
+        // A 20 mm groove 0.5 mm wide across a 20 × 4 mm field
+        let groove = [Point2D::new(0.0, 2.0), Point2D::new(20.0, 2.0)];
+        let bounds = (0.0, 0.0, 20.0, 4.0);
+        for dither in [Dither::Ordered, Dither::ErrorDiffusion] {
+            let mut counts = Vec::new();
+            for dpi in [254, 508, 1016] {
+                let bitmap = rasterize_strokes([&groove[..]], bounds, dpi, 0.5, dither).unwrap();
+                let expected = 0.5 * f64::from(dpi) / MM_PER_INCH;
+                for x in [bitmap.width / 4, bitmap.width / 2, 3 * bitmap.width / 4] {
+                    let run = column_run(&bitmap, x) as f64;
+                    assert!(
+                        (run - expected).abs() <= 1.0,
+                        "{:?} run {} at {} dpi",
+                        dither,
+                        run,
+                        dpi
+                    );
+                }
+                counts.push(bitmap.count_set() as f64 / f64::from(dpi));
+            }
+            // The stroke's width and length in pixels both grow with dpi, so
+            // set pixels per dot of resolution grow linearly too
+            for pair in counts.windows(2) {
+                assert!(
+                    (pair[1] / pair[0] - 2.0).abs() < 0.05,
+                    "{:?} {:?}",
+                    dither,
+                    counts
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_preview_blends_strokes_in_the_fitted_view() {
+        // A 10 × 5 view in a 40 × 40 image: 4 px per unit, with 10 px
+        // bands above and below
+        let line = [Point2D::new(0.0, 2.5), Point2D::new(10.0, 2.5)];
+        let stroke = |opacity| PreviewStroke {
+            points: Cow::Borrowed(&line[..]),
+            closed: false,
+            width: 1.0,
+            color: [0, 0, 0],
+            opacity,
+        };
+        let view = (0.0, 0.0, 10.0, 5.0);
+        let image = render_preview([stroke(1.0)], view, 40, 40).unwrap();
+        assert_eq!((image.width, image.height), (40, 40));
+        assert_eq!(image.data().len(), 40 * 40 * 4);
+        // The line runs along y = 20 px, 4 px wide
+        assert_eq!(image.pixel(20, 19), Some([0, 0, 0, 255]));
+        assert_eq!(image.pixel(20, 21), Some([0, 0, 0, 255]));
+        assert_eq!(image.pixel(20, 16), Some([255; 4]));
+        assert_eq!(image.pixel(40, 0), None);
+
+        let faded = render_preview([stroke(0.5)], view, 40, 40).unwrap();
+        assert_eq!(faded.pixel(20, 19), Some([128, 128, 128, 255]));
+
+        // A lone point is a dot as wide as the stroke
+        let dot = PreviewStroke {
+            points: Cow::Owned(vec![Point2D::new(5.0, 0.5)]),
+            closed: false,
+            width: 1.0,
+            color: [255, 0, 0],
+            opacity: 1.0,
+        };
+        let image = render_preview([dot], view, 40, 40).unwrap();
+        assert_eq!(image.pixel(20, 12), Some([255, 0, 0, 255]));
+
+        assert!(render_preview([stroke(1.0)], view, 0, 40).is_err());
+        assert!(render_preview([stroke(1.0)], (0.0, 0.0, 0.0, 5.0), 40, 40).is_err());
+
+        let png = image.to_png_bytes().unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        // 8-bit RGBA
+        assert_eq!(&png[24..26], &[8, 6]);
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn test_svg_color_rgb() {
+        assert_eq!(svg_color_rgb("#fff"), [255, 255, 255]);
+        assert_eq!(svg_color_rgb("#1A2b3c"), [0x1a, 0x2b, 0x3c]);
+        assert_eq!(svg_color_rgb(" black "), [0, 0, 0]);
+        assert_eq!(svg_color_rgb("tomato"), [0x1a, 0x1a, 0x1a]);
+        assert_eq!(svg_color_rgb("#12345g"), [0x1a, 0x1a, 0x1a]);
+    }
+
+    #[test]
+    fn test_pbm_and_png_encoding() {
+        let mut bitmap = Bitmap1::new(10, 3, 300, (0.0, 0.0));
+        bitmap.set(0, 0, true);
+        bitmap.set(9, 2, true);
+        assert_eq!(bitmap.count_set(), 2);
+        assert!(bitmap.get(9, 2) && !bitmap.get(8, 2) && !bitmap.get(10, 2));
+
+        let path = std::env::temp_dir().join("test_raster.pbm");
+        let path = path.to_str().unwrap();
+        bitmap.write_pbm(path).unwrap();
+        let pbm = std::fs::read(path).unwrap();
+        let _ = std::fs::remove_file(path);
+        assert_eq!(&pbm[..9], b"P4\n10 3\n\x80");
+        assert_eq!(pbm.len(), 8 + 6);
+
+        let path = std::env::temp_dir().join("test_raster.png");
+        let path = path.to_str().unwrap();
+        bitmap.write_png(path).unwrap();
+        let png = std::fs::read(path).unwrap();
+        let _ = std::fs::remove_file(path);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+}
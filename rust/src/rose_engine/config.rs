@@ -1,7 +1,26 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{
+    normalize_angle, DashSpec, Envelope, JitterConfig, PointsTotal, RadialWave, SpirographError,
+    DEDUPE_EPSILON,
+};
 use crate::rose_engine::rosette::RosettePattern;
+use crate::rose_engine::surface::SurfaceMapping;
+
+/// Wrap `phase` to [0, 2π), unless `rosette` has a non-integer frequency
+/// and so does not repeat after one turn
+fn wrap_phase(phase: f64, rosette: &RosettePattern) -> f64 {
+    if rosette.has_fractional_frequency() {
+        phase
+    } else {
+        normalize_angle(phase)
+    }
+}
 
 /// Configuration for the rose engine lathe
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoseEngineConfig {
     /// Primary rosette pattern
     pub rosette: RosettePattern,
@@ -12,7 +31,7 @@ pub struct RoseEngineConfig {
     /// Base radius (distance from center to nominal cutting path) in mm
     pub base_radius: f64,
 
-    /// Phase offset for the rosette pattern in radians
+    /// Phase offset for the rosette pattern in radians (wrapped to [0, 2π) when used)
     pub phase: f64,
 
     /// Start angle for spindle rotation in radians
@@ -22,7 +41,7 @@ pub struct RoseEngineConfig {
     pub end_angle: f64,
 
     /// Number of points to generate along the path
-    pub resolution: usize,
+    pub resolution: PointsTotal,
 
     /// Optional second rosette for compound motion
     pub secondary_rosette: Option<RosettePattern>,
@@ -30,7 +49,7 @@ pub struct RoseEngineConfig {
     /// Amplitude of secondary rosette if present
     pub secondary_amplitude: f64,
 
-    /// Phase offset for secondary rosette
+    /// Phase offset for secondary rosette (wrapped to [0, 2π) when used)
     pub secondary_phase: f64,
 
     /// Depth modulation - if true, vary cut depth with angle
@@ -41,6 +60,45 @@ pub struct RoseEngineConfig {
 
     /// Depth modulation frequency (cycles per revolution)
     pub depth_modulation_frequency: f64,
+
+    /// Consecutive tool-path points closer than this (mm) are merged after generation
+    pub dedupe_epsilon: f64,
+
+    /// Surface the pattern is cut into (flat dial, cylinder or cone)
+    pub surface: SurfaceMapping,
+
+    /// Optional weight on both rosettes' displacement, evaluated at the
+    /// spindle angle and `base_radius`
+    pub amplitude_envelope: Option<Envelope>,
+
+    /// Optional pointillé cutting: a `RoseEngineLatheRun` keeps only the
+    /// parts of each pass that fall in a dot of the rosette phase
+    pub dash_by_phase: Option<DashSpec>,
+
+    /// In a `RoseEngineLatheRun`'s concentric ring mode, lower each pass's
+    /// amplitude to its [`max_safe_amplitude`](Self::max_safe_amplitude)
+    /// instead of letting the inner rings cross the centre
+    pub clamp_amplitude: bool,
+
+    /// Optional hand-made wobble of the tool path, over the spindle turn
+    /// from `start_angle` to `end_angle`. Every pass of a run takes the
+    /// same wobble, so passes that do not cross still do not.
+    #[serde(default)]
+    pub jitter: Option<JitterConfig>,
+
+    /// Number of times the spindle sweeps `start_angle` to `end_angle`,
+    /// as one continuous cut; each sweep takes `resolution` steps
+    #[serde(default = "default_revolutions")]
+    pub revolutions: usize,
+
+    /// How far (mm) the cutter moves in towards the centre per full turn
+    /// of the spindle, turning the cut into a spiral
+    #[serde(default)]
+    pub radial_feed_per_rev: f64,
+}
+
+fn default_revolutions() -> usize {
+    1
 }
 
 impl RoseEngineConfig {
@@ -65,13 +123,21 @@ impl RoseEngineConfig {
             phase: 0.0,
             start_angle: 0.0,
             end_angle: std::f64::consts::PI * 2.0,
-            resolution: 1000,
+            resolution: PointsTotal(1000),
             secondary_rosette: None,
             secondary_amplitude: 0.0,
             secondary_phase: 0.0,
             depth_modulation: false,
             depth_modulation_amplitude: 0.0,
             depth_modulation_frequency: 1.0,
+            dedupe_epsilon: DEDUPE_EPSILON,
+            surface: SurfaceMapping::Flat,
+            amplitude_envelope: None,
+            dash_by_phase: None,
+            clamp_amplitude: false,
+            jitter: None,
+            revolutions: 1,
+            radial_feed_per_rev: 0.0,
         }
     }
 
@@ -107,6 +173,105 @@ impl RoseEngineConfig {
         self.depth_modulation_frequency = frequency;
     }
 
+    /// Cut the pattern into a cylinder or cone instead of a flat dial
+    ///
+    /// See [`SurfaceMapping`] for how the pattern wraps onto the surface.
+    pub fn with_surface(&mut self, surface: SurfaceMapping) {
+        self.surface = surface;
+    }
+
+    /// Set the distance below which consecutive tool-path points are merged
+    pub fn with_dedupe_epsilon(&mut self, epsilon: f64) {
+        self.dedupe_epsilon = epsilon;
+    }
+
+    /// Fade the rosette displacement around (and, across a run's rings,
+    /// along) the dial
+    ///
+    /// # Example
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use turtles::rose_engine::RoseEngineConfig;
+    /// use turtles::Envelope;
+    ///
+    /// // Full amplitude at 12 o'clock, none at 6 o'clock
+    /// let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// config.with_amplitude_envelope(Envelope::angular(PI, 2.0 * PI, PI / 2.0));
+    /// assert_eq!(config.radius_at_angle(PI / 2.0), 20.0);
+    /// ```
+    pub fn with_amplitude_envelope(&mut self, envelope: Envelope) {
+        self.amplitude_envelope = Some(envelope);
+    }
+
+    /// Cut each pass of a run as dots locked to the rosette phase
+    pub fn with_dash_by_phase(&mut self, dash: DashSpec) {
+        self.dash_by_phase = Some(dash);
+    }
+
+    /// Wobble the tool path as if turned by hand
+    pub fn with_jitter(&mut self, jitter: JitterConfig) {
+        self.jitter = Some(jitter);
+    }
+
+    /// Cut `revolutions` sweeps as one spiral, moving in by
+    /// `feed_per_rev` mm per full turn
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::RoseEngineConfig;
+    ///
+    /// let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 0.0);
+    /// config.with_spiral(5, 1.0);
+    /// assert!((config.spiral_radius_at_angle(config.sweep_end_angle()) - 15.0).abs() < 1e-9);
+    /// ```
+    pub fn with_spiral(&mut self, revolutions: usize, feed_per_rev: f64) {
+        self.revolutions = revolutions;
+        self.radial_feed_per_rev = feed_per_rev;
+    }
+
+    /// Check the amplitudes, the rosettes, the dashes, the jitter, the
+    /// spiral and the surface
+    ///
+    /// Presets build their config without checking it; lathes and runs
+    /// call this when they are created. A zero amplitude is valid and cuts
+    /// plain circles.
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if !(self.amplitude.is_finite() && self.amplitude >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "amplitude must be non-negative".to_string(),
+            ));
+        }
+        if !self.secondary_amplitude.is_finite() {
+            return Err(SpirographError::InvalidParameter(
+                "secondary_amplitude must be finite".to_string(),
+            ));
+        }
+        if let Some(ref envelope) = self.amplitude_envelope {
+            envelope.validate()?;
+        }
+        if let Some(ref dash) = self.dash_by_phase {
+            dash.validate()?;
+        }
+        if let Some(ref jitter) = self.jitter {
+            jitter.validate()?;
+        }
+        if self.revolutions == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "revolutions must be at least 1".to_string(),
+            ));
+        }
+        if !self.radial_feed_per_rev.is_finite() {
+            return Err(SpirographError::InvalidParameter(
+                "radial_feed_per_rev must be finite".to_string(),
+            ));
+        }
+        self.rosette.validate()?;
+        if let Some(ref secondary) = self.secondary_rosette {
+            secondary.validate()?;
+        }
+        self.surface.validate()
+    }
+
     /// Calculate the radial position at a given angle
     ///
     /// # Arguments
@@ -115,17 +280,144 @@ impl RoseEngineConfig {
     /// # Returns
     /// Radius at the given angle
     pub fn radius_at_angle(&self, angle: f64) -> f64 {
-        let primary_displacement = self.rosette.displacement(angle + self.phase);
-        let mut total_displacement = self.amplitude * primary_displacement;
+        // Phases are wrapped to [0, 2π) so huge or negative inputs behave
+        // exactly like their in-range equivalents (see `wrap_phase`).
+        // A zero amplitude contributes nothing, even where the rosette or
+        // phase would not be finite
+        let mut total_displacement = 0.0;
+        if self.amplitude != 0.0 {
+            let phase = wrap_phase(self.phase, &self.rosette);
+            total_displacement += self.amplitude * self.rosette.displacement(angle + phase);
+        }
 
         if let Some(ref secondary) = self.secondary_rosette {
-            let secondary_displacement = secondary.displacement(angle + self.secondary_phase);
-            total_displacement += self.secondary_amplitude * secondary_displacement;
+            if self.secondary_amplitude != 0.0 {
+                let secondary_phase = wrap_phase(self.secondary_phase, secondary);
+                let secondary_displacement = secondary.displacement(angle + secondary_phase);
+                total_displacement += self.secondary_amplitude * secondary_displacement;
+            }
+        }
+
+        if let Some(ref envelope) = self.amplitude_envelope {
+            total_displacement *= envelope.eval(angle, self.base_radius);
         }
 
         self.base_radius + total_displacement
     }
 
+    /// Where the spindle stops after all `revolutions` sweeps; the same as
+    /// `end_angle` for a single sweep
+    pub fn sweep_end_angle(&self) -> f64 {
+        self.start_angle + (self.end_angle - self.start_angle) * self.revolutions as f64
+    }
+
+    /// Steps a lathe takes from `start_angle` to `sweep_end_angle`
+    pub fn sample_steps(&self) -> usize {
+        self.resolution.get() * self.revolutions
+    }
+
+    /// Steps per turn when the sweep covers a whole number of turns in the
+    /// same whole number of steps each, so every turn visits the same angles
+    pub(crate) fn steps_per_turn(&self) -> Option<usize> {
+        let turns = (self.sweep_end_angle() - self.start_angle) / (2.0 * PI);
+        let steps = self.sample_steps();
+        (turns >= 1.0 && turns.fract() == 0.0 && steps.is_multiple_of(turns as usize))
+            .then(|| steps / turns as usize)
+    }
+
+    /// How far the radial feed has moved the cutter in by `angle`
+    pub fn radial_feed_at(&self, angle: f64) -> f64 {
+        if self.radial_feed_per_rev == 0.0 {
+            return 0.0;
+        }
+        self.radial_feed_per_rev * (angle - self.start_angle).abs() / (2.0 * PI)
+    }
+
+    /// Radius of the cut at `angle`, with the rosette motion and the
+    /// radial feed of a spiral
+    pub fn spiral_radius_at_angle(&self, angle: f64) -> f64 {
+        self.radius_at_angle(angle) - self.radial_feed_at(angle)
+    }
+
+    /// The spindle angles a lathe samples, from `start_angle` to
+    /// `sweep_end_angle`
+    fn sample_angles(&self) -> impl Iterator<Item = f64> + '_ {
+        let n = self.sample_steps();
+        let step = (self.sweep_end_angle() - self.start_angle) / (n as f64);
+        (0..=n).map(move |i| self.start_angle + (i as f64) * step)
+    }
+
+    /// Smallest radius of the tool path, over the angles a lathe samples
+    ///
+    /// Negative when the cut crosses the centre and comes back out on the
+    /// opposite side, folding the pass over itself.
+    pub fn min_radius(&self) -> f64 {
+        self.sample_angles()
+            .map(|angle| self.spiral_radius_at_angle(angle))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Largest primary `amplitude` that keeps the tool path at or outside
+    /// the centre, over the angles a lathe samples
+    ///
+    /// Infinite when the primary rosette never pulls the cutter inwards,
+    /// and zero when the base radius and secondary rosette alone already
+    /// reach the centre.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::RoseEngineConfig;
+    ///
+    /// // A 12-lobe rosette swings the full amplitude either side of the base
+    /// let config = RoseEngineConfig::classic_multi_lobe(5.0, 12, 1.0);
+    /// assert!((config.max_safe_amplitude() - 5.0).abs() < 1e-9);
+    /// ```
+    pub fn max_safe_amplitude(&self) -> f64 {
+        // The radius is linear in the primary amplitude:
+        // r(θ) = rest(θ) + amplitude · pull(θ)
+        let mut unit = self.clone();
+        unit.amplitude = 1.0;
+        unit.secondary_amplitude = 0.0;
+        let mut rest = self.clone();
+        rest.amplitude = 0.0;
+        self.sample_angles()
+            .filter_map(|angle| {
+                let pull = unit.radius_at_angle(angle) - self.base_radius;
+                (pull < 0.0).then(|| rest.spiral_radius_at_angle(angle) / -pull)
+            })
+            .fold(f64::INFINITY, f64::min)
+            .max(0.0)
+    }
+
+    /// The rosette motion as radial waves, with phases wrapped as in
+    /// `radius_at_angle`
+    ///
+    /// The first wave is the primary rosette about `base_radius`; a
+    /// secondary rosette adds a second wave with zero base radius. Summing
+    /// `radius_at` over the waves gives `radius_at_angle`, unless an
+    /// amplitude envelope is set.
+    pub fn radial_waves(&self) -> Vec<RadialWave> {
+        let wrap = |phase: f64, rosette: &RosettePattern| {
+            if rosette.has_fractional_frequency() {
+                phase
+            } else {
+                normalize_angle(phase)
+            }
+        };
+        let mut waves =
+            vec![
+                RadialWave::new(self.base_radius, self.amplitude, self.rosette.clone())
+                    .with_phase(wrap(self.phase, &self.rosette)),
+            ];
+        if let Some(ref secondary) = self.secondary_rosette {
+            waves.push(
+                RadialWave::new(0.0, self.secondary_amplitude, secondary.clone())
+                    .with_phase(wrap(self.secondary_phase, secondary)),
+            );
+        }
+        waves
+    }
+
     /// Calculate the depth at a given angle (if depth modulation is enabled)
     ///
     /// # Arguments
@@ -164,7 +456,7 @@ impl RoseEngineConfig {
     pub fn sunburst(base_radius: f64, rays: usize, amplitude: f64) -> Self {
         let mut config = RoseEngineConfig::new(base_radius, amplitude);
         config.rosette = RosettePattern::MultiLobe { lobes: rays };
-        config.resolution = 2000; // Higher resolution for crisp rays
+        config.resolution = PointsTotal(2000); // Higher resolution for crisp rays
         config
     }
 
@@ -208,7 +500,7 @@ impl RoseEngineConfig {
     pub fn huit_eight(base_radius: f64, amplitude: f64) -> Self {
         let mut config = RoseEngineConfig::new(base_radius, amplitude);
         config.rosette = RosettePattern::HuitEight { lobes: 8 };
-        config.resolution = 1500; // Higher resolution for smooth curves
+        config.resolution = PointsTotal(1500); // Higher resolution for smooth curves
         config
     }
 
@@ -220,7 +512,7 @@ impl RoseEngineConfig {
             grain_size,
             rows: 12,
         };
-        config.resolution = 2000; // Very high resolution for small grains
+        config.resolution = PointsTotal(2000); // Very high resolution for small grains
         config
     }
 
@@ -235,7 +527,7 @@ impl RoseEngineConfig {
             frequency: wave_frequency,
             wave_exponent: 1,
         };
-        config.resolution = 1500;
+        config.resolution = PointsTotal(1500);
         config
     }
 
@@ -244,9 +536,45 @@ impl RoseEngineConfig {
     pub fn diamant(base_radius: f64, divisions: usize, amplitude: f64) -> Self {
         let mut config = RoseEngineConfig::new(base_radius, amplitude);
         config.rosette = RosettePattern::Diamant { divisions };
-        config.resolution = 2000; // High resolution for crisp diamonds
+        config.resolution = PointsTotal(2000); // High resolution for crisp diamonds
         config
     }
+
+    /// Partial arc preset: the default rosette cut from `start_deg` to
+    /// `end_deg` only
+    ///
+    /// Angles are spindle angles in degrees, counter-clockwise from +x in
+    /// the generated frame, where y grows downward in the SVG preview. The
+    /// arc keeps a full turn's point spacing. Fails unless `end_deg` is
+    /// past `start_deg` and both are finite.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::RoseEngineConfig;
+    ///
+    /// // Decorate only the top half of the dial as previewed
+    /// let config = RoseEngineConfig::partial_arc(20.0, 1.0, 180.0, 360.0).unwrap();
+    /// assert_eq!(config.resolution.get(), 500);
+    /// ```
+    pub fn partial_arc(
+        base_radius: f64,
+        amplitude: f64,
+        start_deg: f64,
+        end_deg: f64,
+    ) -> Result<Self, SpirographError> {
+        if !(start_deg.is_finite() && end_deg.is_finite() && end_deg > start_deg) {
+            return Err(SpirographError::InvalidParameter(
+                "end angle must be finite and past the start angle".to_string(),
+            ));
+        }
+        let mut config = RoseEngineConfig::new(base_radius, amplitude);
+        config.start_angle = start_deg.to_radians();
+        config.end_angle = end_deg.to_radians();
+        let turns = (end_deg - start_deg) / 360.0;
+        config.resolution =
+            PointsTotal(((config.resolution.get() as f64 * turns).ceil() as usize).max(1));
+        Ok(config)
+    }
 }
 
 #[cfg(test)]
@@ -259,7 +587,7 @@ mod tests {
         let config = RoseEngineConfig::new(20.0, 2.0);
         assert_eq!(config.base_radius, 20.0);
         assert_eq!(config.amplitude, 2.0);
-        assert_eq!(config.resolution, 1000);
+        assert_eq!(config.resolution, PointsTotal(1000));
     }
 
     #[test]
@@ -276,8 +604,8 @@ mod tests {
         let r_half = config.radius_at_angle(PI);
 
         // Should be within reasonable range
-        assert!(r0 >= 18.0 && r0 <= 22.0);
-        assert!(r_half >= 18.0 && r_half <= 22.0);
+        assert!((18.0..=22.0).contains(&r0));
+        assert!((18.0..=22.0).contains(&r_half));
     }
 
     #[test]
@@ -318,6 +646,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_preset_partial_arc() {
+        let config = RoseEngineConfig::partial_arc(20.0, 1.0, 30.0, 120.0).unwrap();
+        assert!((config.start_angle - PI / 6.0).abs() < 1e-12);
+        assert!((config.end_angle - 2.0 * PI / 3.0).abs() < 1e-12);
+        assert_eq!(config.resolution, PointsTotal(250));
+        assert!(config.validate().is_ok());
+
+        assert!(RoseEngineConfig::partial_arc(20.0, 1.0, 120.0, 30.0).is_err());
+        assert!(RoseEngineConfig::partial_arc(20.0, 1.0, 90.0, 90.0).is_err());
+        assert!(RoseEngineConfig::partial_arc(20.0, 1.0, 0.0, f64::NAN).is_err());
+    }
+
     #[test]
     fn test_preset_compound() {
         let config = RoseEngineConfig::compound(20.0, 8, 2.0, 3.0, 1.0);
@@ -333,7 +674,7 @@ mod tests {
 
         assert_eq!(config.base_radius, 20.0);
         assert_eq!(config.amplitude, 2.0);
-        assert_eq!(config.resolution, 1500);
+        assert_eq!(config.resolution, PointsTotal(1500));
 
         // Verify it's a Draperie pattern with correct frequency
         match config.rosette {
@@ -347,4 +688,76 @@ mod tests {
             _ => panic!("Should be Draperie pattern"),
         }
     }
+
+    #[test]
+    fn test_phase_wraparound_equivalence() {
+        use crate::rose_engine::{CuttingBit, RoseEngineLathe};
+
+        let lines_for = |phase: f64| {
+            let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 7, 1.5);
+            config.phase = phase;
+            let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+            lathe.generate();
+            lathe.rendered_output().lines[0].clone()
+        };
+
+        let base = lines_for(0.7);
+        for phase in [0.7 + 2.0 * PI, 0.7 - 2.0 * PI, 0.7 + 100.0 * 2.0 * PI] {
+            let wrapped = lines_for(phase);
+            assert_eq!(base.len(), wrapped.len());
+            for (a, b) in base.iter().zip(wrapped.iter()) {
+                assert!((a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_radial_waves_match_radius_at_angle() {
+        let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.5);
+        config.phase = -0.7;
+        config.with_secondary_rosette(RosettePattern::Sinusoidal { frequency: 40.0 }, 0.2);
+        config.secondary_phase = 9.0;
+
+        let waves = config.radial_waves();
+        assert_eq!(waves.len(), 2);
+        assert!((0.0..2.0 * PI).contains(&waves[0].phase));
+        assert!((0.0..2.0 * PI).contains(&waves[1].phase));
+
+        for i in 0..100 {
+            let angle = (i as f64) * 0.0731;
+            let sum: f64 = waves.iter().map(|w| w.radius_at(angle)).sum();
+            assert!((sum - config.radius_at_angle(angle)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_amplitude_envelope_scales_displacement() {
+        use crate::rose_engine::{CuttingBit, RoseEngineLathe};
+
+        let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.5);
+        config.with_secondary_rosette(RosettePattern::Sinusoidal { frequency: 40.0 }, 0.2);
+        let plain = config.clone();
+        // Full amplitude over the upper half, none over the lower half
+        config.with_amplitude_envelope(Envelope::angular(PI, 2.0 * PI, 0.3));
+        config.validate().unwrap();
+
+        for i in 0..200 {
+            let angle = (i as f64) * 0.0731;
+            let weight = Envelope::angular(PI, 2.0 * PI, 0.3).eval(angle, 20.0);
+            let expected = 20.0 + weight * (plain.radius_at_angle(angle) - 20.0);
+            assert!((config.radius_at_angle(angle) - expected).abs() < 1e-12);
+        }
+        assert_eq!(config.radius_at_angle(PI / 2.0), 20.0);
+
+        // The lathe's tool path follows the enveloped radius
+        let mut lathe = RoseEngineLathe::new(config.clone(), CuttingBit::default()).unwrap();
+        lathe.generate();
+        for p in &lathe.tool_path().center_line {
+            let angle = p.y.atan2(p.x);
+            assert!((p.x.hypot(p.y) - config.radius_at_angle(angle)).abs() < 1e-9);
+        }
+
+        config.with_amplitude_envelope(Envelope::radial(5.0, 1.0, 0.0));
+        assert!(config.validate().is_err());
+    }
 }
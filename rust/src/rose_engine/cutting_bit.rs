@@ -1,8 +1,11 @@
-use crate::common::Point2D;
+use crate::common::{Point2D, SpirographError};
+use crate::groove::GrooveProfile;
 use std::f64::consts::PI;
 
+use serde::{Deserialize, Serialize};
+
 /// Shape of the cutting bit
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BitShape {
     /// V-shaped bit with specified angle (in degrees)
     VShaped {
@@ -29,8 +32,11 @@ pub enum BitShape {
     },
 }
 
+/// Offsets sampled across curved bits for STL groove profiles
+const GROOVE_PROFILE_SAMPLES: usize = 25;
+
 /// Configuration for the cutting bit/tool
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CuttingBit {
     /// Shape of the bit
     pub shape: BitShape,
@@ -222,8 +228,80 @@ impl CuttingBit {
         profile[profile.len() - 1].y
     }
 
+    /// Cross-section of the groove the bit leaves cut `depth` deep, for
+    /// STL exports
+    ///
+    /// A V-bit leaves a V-groove and a round bit a semicircular one when cut
+    /// to its radius; a bit cut deeper than its own depth leaves straight
+    /// walls above it.
+    pub(crate) fn groove_profile(&self, depth: f64) -> Result<GrooveProfile, SpirographError> {
+        let half_width = self.width / 2.0;
+        let samples = GROOVE_PROFILE_SAMPLES;
+        match &self.shape {
+            BitShape::VShaped { angle } => GrooveProfile::v_shaped(*angle, self.width, depth),
+            BitShape::Flat => GrooveProfile::flat(self.width, depth),
+            BitShape::Round => GrooveProfile::from_tip_heights(self.width, depth, samples, |u| {
+                half_width - (half_width * half_width - u * u).max(0.0).sqrt()
+            }),
+            BitShape::Elliptical { aspect_ratio } => {
+                let b = half_width / aspect_ratio;
+                GrooveProfile::from_tip_heights(self.width, depth, samples, |u| {
+                    b * (1.0 - (1.0 - (u / half_width).powi(2)).max(0.0).sqrt())
+                })
+            }
+            BitShape::Custom { profile } => {
+                GrooveProfile::from_tip_heights(self.width, depth, samples, |u| {
+                    self.interpolate_profile(profile, u / self.width + 0.5) * self.width
+                })
+            }
+        }
+    }
+
+    /// Width of the cut at the surface with the tip `depth` below it
+    ///
+    /// A V-bit cuts `2·depth·tan(angle/2)` wide and a round or elliptical
+    /// bit the chord of its profile at that depth, each capped at the bit's
+    /// width once the cut is deeper than the cutting edge; a flat bit always
+    /// cuts its full width. Custom bits use the surface width of their
+    /// [`groove_profile`](Self::groove_profile), which every bit's STL
+    /// groove matches. A depth of zero or less cuts nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::CuttingBit;
+    ///
+    /// let bit = CuttingBit::v_shaped(90.0, 1.0);
+    /// assert!((bit.kerf_at_depth(0.1) - 0.2).abs() < 1e-12);
+    /// assert_eq!(bit.kerf_at_depth(2.0), 1.0);
+    /// ```
+    pub fn kerf_at_depth(&self, depth: f64) -> f64 {
+        if depth.is_nan() || depth <= 0.0 {
+            return 0.0;
+        }
+        let half_width = self.width / 2.0;
+        // Chord of an ellipse with semi-axes (half_width, b) cut d below its tip
+        let chord = |b: f64| {
+            let rise = (b - depth.min(b)) / b;
+            2.0 * half_width * (1.0 - rise * rise).max(0.0).sqrt()
+        };
+        match &self.shape {
+            BitShape::VShaped { angle } => {
+                (2.0 * depth * (angle.to_radians() / 2.0).tan()).min(self.width)
+            }
+            BitShape::Flat => self.width,
+            BitShape::Round => chord(half_width),
+            BitShape::Elliptical { aspect_ratio } => chord(half_width / aspect_ratio),
+            BitShape::Custom { .. } => self
+                .groove_profile(depth)
+                .map_or(0.0, |profile| profile.width()),
+        }
+    }
+
     /// Calculate the footprint of the bit at a given position and angle
     ///
+    /// The edges sit half the bit's [`kerf_at_depth`](Self::kerf_at_depth)
+    /// its own `depth` either side of the center.
+    ///
     /// # Arguments
     /// * `center` - Center position of the bit
     /// * `angle` - Angle of the tool path at this point (radians)
@@ -240,14 +318,14 @@ impl CuttingBit {
         let mut left_edge = Vec::new();
         let mut right_edge = Vec::new();
 
-        let half_width = self.width / 2.0;
+        let half_width = self.kerf_at_depth(self.depth) / 2.0;
 
         // Perpendicular to the tool path
         let perp_angle = angle + PI / 2.0;
         let perp_x = perp_angle.cos();
         let perp_y = perp_angle.sin();
 
-        // For a simple approximation, create edges offset by half_width
+        // For a simple approximation, create edges offset by half the kerf
         // perpendicular to the path direction
         left_edge.push(Point2D::new(
             center.x - perp_x * half_width,
@@ -269,6 +347,72 @@ impl Default for CuttingBit {
     }
 }
 
+/// How a bit dulls with the distance it has cut
+///
+/// Wear grows linearly with cut length: the groove widens by
+/// `width_growth_per_meter` and shallows by `depth_loss_per_meter` for
+/// every meter cut since the bit was last sharpened.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WearModel {
+    /// Groove width gained per meter of cut, in mm
+    pub width_growth_per_meter: f64,
+    /// Groove depth lost per meter of cut, in mm
+    pub depth_loss_per_meter: f64,
+    /// Cut length in meters after which the bit is resharpened, restoring
+    /// its nominal size before the next pass
+    pub resharpen_after_m: Option<f64>,
+}
+
+impl WearModel {
+    /// Create a wear model that is never resharpened
+    pub fn new(width_growth_per_meter: f64, depth_loss_per_meter: f64) -> Self {
+        WearModel {
+            width_growth_per_meter,
+            depth_loss_per_meter,
+            resharpen_after_m: None,
+        }
+    }
+
+    /// Resharpen once `meters` have been cut since the last sharpening
+    pub fn with_resharpen_after(mut self, meters: f64) -> Self {
+        self.resharpen_after_m = Some(meters);
+        self
+    }
+
+    /// Check that the rates are non-negative and the interval positive
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if !(self.width_growth_per_meter.is_finite() && self.width_growth_per_meter >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "width_growth_per_meter must be non-negative".to_string(),
+            ));
+        }
+        if !(self.depth_loss_per_meter.is_finite() && self.depth_loss_per_meter >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "depth_loss_per_meter must be non-negative".to_string(),
+            ));
+        }
+        if let Some(meters) = self.resharpen_after_m {
+            if !(meters.is_finite() && meters > 0.0) {
+                return Err(SpirographError::InvalidParameter(
+                    "resharpen_after_m must be positive".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Effective (width, depth) after cutting `length_mm` since sharpening
+    ///
+    /// The depth never drops below zero.
+    pub fn worn(&self, width: f64, depth: f64, length_mm: f64) -> (f64, f64) {
+        let meters = length_mm / 1000.0;
+        (
+            width + self.width_growth_per_meter * meters,
+            (depth - self.depth_loss_per_meter * meters).max(0.0),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -335,6 +479,43 @@ mod tests {
         assert!(profile[profile.len() - 1].y < 0.01);
     }
 
+    #[test]
+    fn test_kerf_at_depth_follows_the_shape() {
+        // A 30° V-bit cut 0.1 mm deep is far narrower than its 1 mm width
+        let v = CuttingBit::v_shaped(30.0, 1.0);
+        let shallow = v.kerf_at_depth(0.1);
+        assert!((shallow - 0.2 * 15f64.to_radians().tan()).abs() < 1e-12);
+        assert!(shallow < v.width / 10.0);
+        assert!((v.kerf_at_depth(v.depth) - v.width).abs() < 1e-12);
+        assert_eq!(v.kerf_at_depth(10.0), v.width);
+
+        let flat = CuttingBit::flat(1.0, 0.5);
+        assert_eq!(flat.kerf_at_depth(0.1), 1.0);
+
+        // A 2 mm ball 0.2 mm deep cuts the chord 2·√(0.2·1.8)
+        let round = CuttingBit::round(2.0);
+        assert!((round.kerf_at_depth(0.2) - 2.0 * 0.36f64.sqrt()).abs() < 1e-12);
+        assert_eq!(round.kerf_at_depth(1.5), 2.0);
+
+        // Flatter ellipses cut wider at the same depth
+        let elliptical = CuttingBit::elliptical(2.0, 4.0);
+        assert!(elliptical.kerf_at_depth(0.1) > round.kerf_at_depth(0.1));
+        assert_eq!(elliptical.kerf_at_depth(elliptical.depth), 2.0);
+
+        for bit in [&v, &flat, &round, &elliptical] {
+            assert_eq!(bit.kerf_at_depth(0.0), 0.0);
+        }
+
+        // STL grooves are as wide as the kerf
+        for (bit, depth) in [(&v, 0.1), (&flat, 0.1), (&v, 5.0)] {
+            let groove = bit.groove_profile(depth).unwrap().width();
+            assert!((groove - bit.kerf_at_depth(depth)).abs() < 1e-12);
+        }
+        let custom = CuttingBit::custom(vec![Point2D::new(0.0, 0.0), Point2D::new(1.0, 0.5)], 1.0);
+        let groove = custom.groove_profile(0.2).unwrap().width();
+        assert_eq!(custom.kerf_at_depth(0.2), groove);
+    }
+
     #[test]
     fn test_default_bit() {
         let bit = CuttingBit::default();
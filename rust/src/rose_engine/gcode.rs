@@ -0,0 +1,334 @@
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+use crate::common::{polyline_is_degenerate, Point2D, SpirographError};
+use crate::rose_engine::config::RoseEngineConfig;
+use crate::rose_engine::lathe::Arc;
+
+/// Machining parameters for G-code export
+///
+/// Rates are in mm/min and heights and depths in mm, with Z = 0 at the
+/// work surface. Each line is cut in steps of at most `depth_per_pass`
+/// until it reaches its full depth.
+///
+/// # Example
+/// ```
+/// use turtles::rose_engine::GcodeParams;
+///
+/// let params = GcodeParams::default().with_depth_per_pass(0.05);
+/// assert!(params.validate().is_ok());
+/// assert!(GcodeParams::new(300.0, 60.0, -1.0, 0.1, 12000.0).validate().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GcodeParams {
+    /// Feed rate along the cut in mm/min
+    pub feed_rate: f64,
+    /// Feed rate when plunging into the work in mm/min
+    pub plunge_rate: f64,
+    /// Height above the surface for rapid moves between cuts
+    pub safe_z: f64,
+    /// Deepest step the tool may take into the work in one pass
+    pub depth_per_pass: f64,
+    /// Spindle speed in rpm
+    pub spindle_speed: f64,
+}
+
+impl GcodeParams {
+    /// Create machining parameters
+    pub fn new(
+        feed_rate: f64,
+        plunge_rate: f64,
+        safe_z: f64,
+        depth_per_pass: f64,
+        spindle_speed: f64,
+    ) -> Self {
+        GcodeParams {
+            feed_rate,
+            plunge_rate,
+            safe_z,
+            depth_per_pass,
+            spindle_speed,
+        }
+    }
+
+    /// Set the depth of each step
+    pub fn with_depth_per_pass(mut self, depth_per_pass: f64) -> Self {
+        self.depth_per_pass = depth_per_pass;
+        self
+    }
+
+    /// Check that every rate, height and step is positive
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        for (name, value) in [
+            ("feed_rate", self.feed_rate),
+            ("plunge_rate", self.plunge_rate),
+            ("safe_z", self.safe_z),
+            ("depth_per_pass", self.depth_per_pass),
+            ("spindle_speed", self.spindle_speed),
+        ] {
+            if !(value.is_finite() && value > 0.0) {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "{} must be positive",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for GcodeParams {
+    fn default() -> Self {
+        GcodeParams::new(300.0, 60.0, 2.0, 0.1, 12000.0)
+    }
+}
+
+/// Cut depth at each point of `points`, following `config.depth_at_angle`
+///
+/// Angles are measured about `center` and unwrapped along the line,
+/// starting within a turn of `config.start_angle`, so they match the
+/// angles the lathe generated the points at.
+pub(crate) fn modulated_depths(
+    points: &[Point2D],
+    center: Point2D,
+    config: &RoseEngineConfig,
+    base_depth: f64,
+) -> Vec<f64> {
+    if !config.depth_modulation {
+        return vec![base_depth; points.len()];
+    }
+    let mut angle: Option<f64> = None;
+    points
+        .iter()
+        .map(|p| {
+            let polar = (p.y - center.y).atan2(p.x - center.x);
+            let unwrapped = match angle {
+                None => config.start_angle + (polar - config.start_angle).rem_euclid(2.0 * PI),
+                Some(previous) => previous + (polar - previous + PI).rem_euclid(2.0 * PI) - PI,
+            };
+            angle = Some(unwrapped);
+            config.depth_at_angle(unwrapped, base_depth)
+        })
+        .collect()
+}
+
+/// Builds a G-code program from polylines and arcs
+///
+/// Cuts are made in the order they are added. The tool retracts to
+/// `safe_z` only when the next cut starts away from where the last one
+/// ended, so a closed ring is stepped down without lifting.
+pub(crate) struct GcodeProgram<'a> {
+    params: &'a GcodeParams,
+    gcode: String,
+    /// Where the tool is in the work, or None when retracted
+    tool_down: Option<(Point2D, f64)>,
+}
+
+impl<'a> GcodeProgram<'a> {
+    /// Start a program: millimetres, absolute XY plane moves, spindle on
+    pub(crate) fn new(params: &'a GcodeParams) -> Result<Self, SpirographError> {
+        params.validate()?;
+        let mut gcode = String::new();
+        // Writing to a String cannot fail
+        let _ = write!(
+            gcode,
+            "G21\nG90\nG17\nG0 Z{}\nM3 S{}\n",
+            fmt(params.safe_z),
+            fmt(params.spindle_speed)
+        );
+        Ok(GcodeProgram {
+            params,
+            gcode,
+            tool_down: None,
+        })
+    }
+
+    /// Cut a polyline with a depth per point, stepping down to full depth
+    ///
+    /// Lines with fewer than two distinct points are skipped.
+    pub(crate) fn polyline(&mut self, points: &[Point2D], depths: &[f64]) {
+        if polyline_is_degenerate(points) {
+            return;
+        }
+        let deepest = depths.iter().fold(0.0, |a: f64, &b| a.max(b));
+        for limit in self.step_limits(deepest) {
+            self.move_to(points[0], depths[0].min(limit));
+            for (p, depth) in points.iter().zip(depths).skip(1) {
+                self.cut_to(*p, depth.min(limit));
+            }
+        }
+    }
+
+    /// Cut an arc as G2/G3 moves, with depths at its start and end
+    pub(crate) fn arc(&mut self, arc: &Arc, depths: (f64, f64)) {
+        let point = |angle: f64| {
+            Point2D::new(
+                arc.center.x + arc.radius * angle.cos(),
+                arc.center.y + arc.radius * angle.sin(),
+            )
+        };
+        let (start, end) = (point(arc.start_angle), point(arc.end_angle));
+        let command = if arc.end_angle >= arc.start_angle {
+            "G3"
+        } else {
+            "G2"
+        };
+        for limit in self.step_limits(depths.0.max(depths.1)) {
+            let z = -depths.1.min(limit);
+            self.move_to(start, depths.0.min(limit));
+            let _ = writeln!(
+                self.gcode,
+                "{} X{} Y{} Z{} I{} J{} F{}",
+                command,
+                fmt(end.x),
+                fmt(end.y),
+                fmt(z),
+                fmt(arc.center.x - start.x),
+                fmt(arc.center.y - start.y),
+                fmt(self.params.feed_rate)
+            );
+            self.tool_down = Some((end, z));
+        }
+    }
+
+    /// Retract, stop the spindle and end the program
+    pub(crate) fn finish(mut self) -> String {
+        let _ = write!(self.gcode, "G0 Z{}\nM5\nM30\n", fmt(self.params.safe_z));
+        self.gcode
+    }
+
+    /// Depth limit of each step down to `depth`, shallowest first
+    fn step_limits(&self, depth: f64) -> Vec<f64> {
+        let step = self.params.depth_per_pass;
+        // Tolerate rounding so 0.3 at 0.1 per pass is three steps
+        let count = ((depth / step - 1e-9).ceil() as usize).max(1);
+        (1..=count)
+            .map(|k| if k == count { depth } else { k as f64 * step })
+            .collect()
+    }
+
+    /// Bring the tool to `p` at `depth`, retracting first if it is elsewhere
+    fn move_to(&mut self, p: Point2D, depth: f64) {
+        let z = -depth;
+        match self.tool_down {
+            Some((at, at_z)) if (at.x - p.x).hypot(at.y - p.y) <= 1e-9 => {
+                if at_z != z {
+                    let _ = writeln!(
+                        self.gcode,
+                        "G1 Z{} F{}",
+                        fmt(z),
+                        fmt(self.params.plunge_rate)
+                    );
+                }
+            }
+            down => {
+                if down.is_some() {
+                    let _ = writeln!(self.gcode, "G0 Z{}", fmt(self.params.safe_z));
+                }
+                let _ = write!(
+                    self.gcode,
+                    "G0 X{} Y{}\nG1 Z{} F{}\n",
+                    fmt(p.x),
+                    fmt(p.y),
+                    fmt(z),
+                    fmt(self.params.plunge_rate)
+                );
+            }
+        }
+        self.tool_down = Some((p, z));
+    }
+
+    /// Feed in a straight line to `p` at `depth`
+    fn cut_to(&mut self, p: Point2D, depth: f64) {
+        let z = -depth;
+        let _ = writeln!(
+            self.gcode,
+            "G1 X{} Y{} Z{} F{}",
+            fmt(p.x),
+            fmt(p.y),
+            fmt(z),
+            fmt(self.params.feed_rate)
+        );
+        self.tool_down = Some((p, z));
+    }
+}
+
+/// Format a coordinate to 4 decimals, without a trailing "-0"
+fn fmt(value: f64) -> String {
+    let s = format!("{:.4}", value);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    match s {
+        "-0" | "" => "0".to_string(),
+        _ => s.to_string(),
+    }
+}
+
+/// Write a finished program to `filename`
+pub(crate) fn write_gcode(filename: &str, gcode: &str) -> Result<(), SpirographError> {
+    std::fs::write(filename, gcode).map_err(|e| {
+        SpirographError::ExportError(format!("Failed to write G-code file '{}': {}", filename, e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt_trims_zeros() {
+        assert_eq!(fmt(2.0), "2");
+        assert_eq!(fmt(-0.15), "-0.15");
+        assert_eq!(fmt(-0.00001), "0");
+        assert_eq!(fmt(12000.0), "12000");
+    }
+
+    #[test]
+    fn test_program_steps_down_and_retracts_between_lines() {
+        let params = GcodeParams::default();
+        let mut program = GcodeProgram::new(&params).unwrap();
+        let ring = vec![
+            Point2D::new(1.0, 0.0),
+            Point2D::new(0.0, 1.0),
+            Point2D::new(-1.0, 0.0),
+            Point2D::new(1.0, 0.0),
+        ];
+        program.polyline(&ring, &[0.25; 4]);
+        let open = vec![Point2D::new(5.0, 5.0), Point2D::new(6.0, 5.0)];
+        program.polyline(&open, &[0.1, 0.1]);
+        program.polyline(&[Point2D::new(9.0, 9.0)], &[0.1]);
+        let gcode = program.finish();
+
+        assert!(gcode.starts_with("G21\nG90\nG17\nG0 Z2\nM3 S12000\n"));
+        assert!(gcode.ends_with("G0 Z2\nM5\nM30\n"));
+        // The ring is cut at 0.1, 0.2 and 0.25 without lifting
+        let plunges: Vec<&str> = gcode.lines().filter(|l| l.starts_with("G1 Z")).collect();
+        assert_eq!(
+            plunges,
+            vec![
+                "G1 Z-0.1 F60",
+                "G1 Z-0.2 F60",
+                "G1 Z-0.25 F60",
+                "G1 Z-0.1 F60"
+            ]
+        );
+        // One rapid to each line; the degenerate line is skipped
+        let rapids: Vec<&str> = gcode.lines().filter(|l| l.starts_with("G0 X")).collect();
+        assert_eq!(rapids, vec!["G0 X1 Y0", "G0 X5 Y5"]);
+        assert_eq!(gcode.matches("G0 Z2").count(), 3);
+        assert!(gcode.contains("G1 X6 Y5 Z-0.1 F300"));
+    }
+
+    #[test]
+    fn test_params_validation() {
+        assert!(GcodeParams::default().validate().is_ok());
+        assert!(GcodeParams::default()
+            .with_depth_per_pass(0.0)
+            .validate()
+            .is_err());
+        let params = GcodeParams {
+            feed_rate: f64::NAN,
+            ..Default::default()
+        };
+        assert!(GcodeProgram::new(&params).is_err());
+    }
+}
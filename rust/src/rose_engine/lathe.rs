@@ -1,7 +1,41 @@
-use crate::common::{ExportConfig, Point2D, SpirographError};
+use crate::common::svg_writer::{SvgWriter, DEFAULT_SVG_PRECISION};
+use crate::common::{
+    debug_assert_finite, dedupe_consecutive_iter, offset_points, polyline_is_degenerate,
+    simplify_polylines, ExportConfig, Point2D, Point3D, SpirographError, TrigTable,
+};
 use crate::rose_engine::config::RoseEngineConfig;
 use crate::rose_engine::cutting_bit::CuttingBit;
-use std::f64::consts::PI;
+use crate::rose_engine::gcode::{modulated_depths, write_gcode, GcodeParams, GcodeProgram};
+use crate::rose_engine::solid::{groove_triangles, write_step, write_stl};
+use crate::rose_engine::surface::{check_fits_surface, write_surface_stl};
+use crate::shading::depth_shaded_paths;
+use std::borrow::Cow;
+
+/// Stroke width of the tool path in the SVG export, in mm; shaded exports
+/// scale it by the cut depth over the bit's depth
+const TOOL_PATH_STROKE_WIDTH: f64 = 0.1;
+
+/// Shading of a cut `depth` deep with a bit cutting `bit_depth`, in [0, 1]
+///
+/// Falls from 1 at the surface to 0 at twice the bit's depth, so a cut at
+/// the bit's own depth is 0.5. Zero-depth bits shade everything 0.5.
+pub(crate) fn depth_shading(depth: f64, bit_depth: f64) -> f64 {
+    if bit_depth > 0.0 {
+        1.0 - (depth / (bit_depth * 2.0)).min(1.0)
+    } else {
+        0.5
+    }
+}
+
+/// Stroke width of a cut `depth` deep, scaled from `full_width` at the
+/// bit's depth
+pub(crate) fn depth_stroke_width(full_width: f64, depth: f64, bit_depth: f64) -> f64 {
+    if bit_depth > 0.0 {
+        full_width * depth / bit_depth
+    } else {
+        full_width
+    }
+}
 
 /// Arc segment for tool path representation
 #[derive(Debug, Clone)]
@@ -16,6 +50,15 @@ pub struct Arc {
     pub end_angle: f64,
 }
 
+/// One piece of a tool path fitted with [`fit_arcs`](crate::fit_arcs)
+#[derive(Debug, Clone)]
+pub enum PathSegment {
+    /// Straight cut from `start` to `end`
+    Line { start: Point2D, end: Point2D },
+    /// Circular cut
+    Arc(Arc),
+}
+
 /// Output structure for tool paths and cut geometry
 #[derive(Debug, Clone)]
 pub struct ToolPathOutput {
@@ -23,8 +66,14 @@ pub struct ToolPathOutput {
     pub center_line: Vec<Point2D>,
     /// Actual cut edges (considering bit shape) - left and right edges
     pub cut_edges: Vec<Vec<Point2D>>,
+    /// Cut width at the surface at each center line point, with depth
+    /// modulation on; empty otherwise, when the kerf is constant
+    pub kerf: Vec<f64>,
     /// Optional arc segments for CNC machining
     pub arcs: Vec<Arc>,
+    /// The center line as arcs and lines, in order, once
+    /// [`RoseEngineLathe::fit_arcs`] has run; `arcs` holds its arcs
+    pub segments: Vec<PathSegment>,
 }
 
 /// Output structure for rendered visualization
@@ -90,23 +139,7 @@ impl RoseEngineLathe {
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        if config.base_radius <= 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "base_radius must be positive".to_string(),
-            ));
-        }
-
-        if config.amplitude < 0.0 {
-            return Err(SpirographError::InvalidParameter(
-                "amplitude must be non-negative".to_string(),
-            ));
-        }
-
-        if config.resolution < 10 {
-            return Err(SpirographError::InvalidParameter(
-                "resolution must be at least 10".to_string(),
-            ));
-        }
+        Self::validate_config(&config)?;
 
         Ok(RoseEngineLathe {
             config,
@@ -117,7 +150,9 @@ impl RoseEngineLathe {
             cut_geometry: ToolPathOutput {
                 center_line: Vec::new(),
                 cut_edges: Vec::new(),
+                kerf: Vec::new(),
                 arcs: Vec::new(),
+                segments: Vec::new(),
             },
             rendered: RenderedOutput {
                 lines: Vec::new(),
@@ -128,99 +163,191 @@ impl RoseEngineLathe {
         })
     }
 
+    /// Check a configuration as `new_with_center` does
+    fn validate_config(config: &RoseEngineConfig) -> Result<(), SpirographError> {
+        if config.base_radius <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "base_radius must be positive".to_string(),
+            ));
+        }
+
+        if config.amplitude < 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "amplitude must be non-negative".to_string(),
+            ));
+        }
+
+        if config.resolution.get() < 10 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 10".to_string(),
+            ));
+        }
+
+        config.validate()
+    }
+
+    /// Check the configuration again, then generate
+    ///
+    /// `config` is public and may have changed since construction; this
+    /// fails with the constructor's error instead of generating from an
+    /// invalid configuration.
+    pub fn try_generate(&mut self) -> Result<(), SpirographError> {
+        Self::validate_config(&self.config)?;
+        self.generate();
+        Ok(())
+    }
+
     /// Generate the rose engine pattern
     /// This creates the tool path, cut geometry, and rendered output
     pub fn generate(&mut self) {
-        self.generate_tool_path();
+        self.generate_with_table(None);
+    }
+
+    /// Generate as [`generate`](Self::generate), taking the spindle angles'
+    /// cosines and sines from `table` when it has as many steps per turn
+    /// as this lathe samples
+    ///
+    /// Runs share one table between passes that visit the same angles.
+    pub(crate) fn generate_with_table(&mut self, table: Option<&TrigTable>) {
+        self.generate_tool_path(table);
         self.generate_cut_geometry();
         self.generate_rendered_output();
         self.generated = true;
+
+        debug_assert_finite("RoseEngineLathe", &self.rendered.lines);
     }
 
-    /// Generate the tool path (center line that the cutting bit follows)
-    fn generate_tool_path(&mut self) {
-        self.tool_path.clear();
+    /// Swap in a differently sized bit, recutting the edges and depth map
+    /// of a generated pattern
+    pub(crate) fn set_cutting_bit(&mut self, cutting_bit: CuttingBit) {
+        self.cutting_bit = cutting_bit;
+        if self.generated {
+            self.generate_cut_geometry();
+            self.generate_rendered_output();
+        }
+    }
 
-        let angle_step =
-            (self.config.end_angle - self.config.start_angle) / (self.config.resolution as f64);
+    /// Generate the tool path (center line that the cutting bit follows)
+    fn generate_tool_path(&mut self, table: Option<&TrigTable>) {
+        self.tool_path =
+            dedupe_consecutive_iter(self.sample_path(table), self.config.dedupe_epsilon).collect();
+    }
 
-        for i in 0..=self.config.resolution {
-            let angle = self.config.start_angle + (i as f64) * angle_step;
-            let radius = self.config.radius_at_angle(angle);
+    /// The tool path computed lazily from the config, point by point
+    ///
+    /// Yields the same points as the center line `generate` stores in
+    /// [`tool_path`](Self::tool_path), without generating or allocating
+    /// the path (a sweep of whole turns holds one turn's angles).
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLathe};
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+    /// let lazy: Vec<_> = lathe.iter_tool_path().collect();
+    /// lathe.generate();
+    /// assert_eq!(lazy, lathe.tool_path().center_line);
+    /// ```
+    pub fn iter_tool_path(&self) -> impl Iterator<Item = Point2D> + '_ {
+        dedupe_consecutive_iter(self.sample_path(None), self.config.dedupe_epsilon)
+    }
 
-            let x = self.center_x + radius * angle.cos();
-            let y = self.center_y + radius * angle.sin();
+    /// The tool path at every sampled angle, before repeated points are
+    /// dropped; index `i` is at the angle of `depth_map[i]`
+    ///
+    /// A [`jitter`](RoseEngineConfig::jitter) moves each point by its
+    /// wobble over the spindle turn. With several
+    /// [`revolutions`](RoseEngineConfig::revolutions) the sweeps run on as
+    /// one line, drawn in by the radial feed.
+    ///
+    /// A sweep of whole turns takes its cosines and sines from a table of
+    /// one turn, `table` if it fits, turned through `start_angle`.
+    fn sample_path<'a>(
+        &'a self,
+        table: Option<&'a TrigTable>,
+    ) -> impl Iterator<Item = Point2D> + 'a {
+        let resolution = self.config.sample_steps();
+        let angle_step =
+            (self.config.sweep_end_angle() - self.config.start_angle) / (resolution as f64);
+        let waves = self.config.radial_waves();
+        let jitter = self.config.jitter.map(|jitter| jitter.line(0));
+        let center = Point2D::new(self.center_x, self.center_y);
+        let table = self.config.steps_per_turn().map(|n| match table {
+            Some(table) if table.resolution() == n => Cow::Borrowed(table),
+            _ => Cow::Owned(TrigTable::new(n)),
+        });
+        let start = self.config.start_angle;
+        let (cos_start, sin_start) = (start.cos(), start.sin());
+
+        (0..=resolution).map(move |i| {
+            let angle = start + (i as f64) * angle_step;
+            let radius = match self.config.amplitude_envelope {
+                Some(_) => self.config.radius_at_angle(angle),
+                None => waves.iter().map(|w| w.radius_at(angle)).sum::<f64>(),
+            } - self.config.radial_feed_at(angle);
+
+            let (cos, sin) = match table {
+                Some(ref table) if start == 0.0 => table.cos_sin(i),
+                Some(ref table) => {
+                    let (cos, sin) = table.cos_sin(i);
+                    (
+                        cos_start * cos - sin_start * sin,
+                        sin_start * cos + cos_start * sin,
+                    )
+                }
+                None => (angle.cos(), angle.sin()),
+            };
+            let x = self.center_x + radius * cos;
+            let y = self.center_y + radius * sin;
 
-            self.tool_path.push(Point2D::new(x, y));
-        }
+            match jitter {
+                Some(ref line) => {
+                    line.displace(i as f64 / resolution as f64, Point2D::new(x, y), center)
+                }
+                None => Point2D::new(x, y),
+            }
+        })
     }
 
     /// Generate cut geometry considering the bit shape
+    ///
+    /// The edges sit half the bit's kerf at the programmed depth either
+    /// side of the path, so a V-bit cut shallower than its own depth
+    /// leaves a narrower groove. With depth modulation the kerf follows
+    /// the depth at each point.
     fn generate_cut_geometry(&mut self) {
         self.cut_geometry.center_line = self.tool_path.clone();
         self.cut_geometry.cut_edges.clear();
+        self.cut_geometry.kerf.clear();
         self.cut_geometry.arcs.clear();
+        self.cut_geometry.segments.clear();
 
         if self.tool_path.len() < 2 {
             return;
         }
 
-        let half_width = self.cutting_bit.width / 2.0;
-        let mut left_edge = Vec::new();
-        let mut right_edge = Vec::new();
-
-        // Calculate edges offset by bit width perpendicular to path
-        for i in 0..self.tool_path.len() {
-            let angle = if i == 0 {
-                // Use angle to next point
-                let dx = self.tool_path[i + 1].x - self.tool_path[i].x;
-                let dy = self.tool_path[i + 1].y - self.tool_path[i].y;
-                dy.atan2(dx)
-            } else if i == self.tool_path.len() - 1 {
-                // Use angle from previous point
-                let dx = self.tool_path[i].x - self.tool_path[i - 1].x;
-                let dy = self.tool_path[i].y - self.tool_path[i - 1].y;
-                dy.atan2(dx)
-            } else {
-                // Use average of unit vectors to handle angle wraparound correctly
-                let dx1 = self.tool_path[i].x - self.tool_path[i - 1].x;
-                let dy1 = self.tool_path[i].y - self.tool_path[i - 1].y;
-                let dx2 = self.tool_path[i + 1].x - self.tool_path[i].x;
-                let dy2 = self.tool_path[i + 1].y - self.tool_path[i].y;
-
-                // Normalize to unit vectors
-                let len1 = (dx1 * dx1 + dy1 * dy1).sqrt();
-                let len2 = (dx2 * dx2 + dy2 * dy2).sqrt();
-
-                if len1 > 0.0 && len2 > 0.0 {
-                    let ux1 = dx1 / len1;
-                    let uy1 = dy1 / len1;
-                    let ux2 = dx2 / len2;
-                    let uy2 = dy2 / len2;
-
-                    // Average unit vectors
-                    let avg_ux = (ux1 + ux2) / 2.0;
-                    let avg_uy = (uy1 + uy2) / 2.0;
-                    avg_uy.atan2(avg_ux)
-                } else {
-                    dy1.atan2(dx1)
-                }
-            };
-
-            let perp_angle = angle + PI / 2.0;
-            let offset_x = half_width * perp_angle.cos();
-            let offset_y = half_width * perp_angle.sin();
-
-            left_edge.push(Point2D::new(
-                self.tool_path[i].x - offset_x,
-                self.tool_path[i].y - offset_y,
-            ));
-
-            right_edge.push(Point2D::new(
-                self.tool_path[i].x + offset_x,
-                self.tool_path[i].y + offset_y,
-            ));
+        if self.config.depth_modulation {
+            let center = Point2D::new(self.center_x, self.center_y);
+            let depths = modulated_depths(
+                &self.tool_path,
+                center,
+                &self.config,
+                self.cutting_bit.depth,
+            );
+            self.cut_geometry.kerf = depths
+                .iter()
+                .map(|&depth| self.cutting_bit.kerf_at_depth(depth))
+                .collect();
         }
+        let programmed_kerf = self.cutting_bit.kerf_at_depth(self.cutting_bit.depth);
+        let kerf = &self.cut_geometry.kerf;
+        let half_width = |i: usize| kerf.get(i).copied().unwrap_or(programmed_kerf) / 2.0;
+
+        // Edges sit half the kerf either side of the path, along the
+        // perpendicular of each point's averaged tangent
+        let left_edge = offset_points(&self.tool_path, half_width);
+        let right_edge = offset_points(&self.tool_path, |i| -half_width(i));
 
         self.cut_geometry.cut_edges.push(left_edge);
         self.cut_geometry.cut_edges.push(right_edge);
@@ -242,22 +369,17 @@ impl RoseEngineLathe {
 
         // Calculate depth and shading if depth modulation is enabled
         if self.config.depth_modulation {
+            let steps = self.config.sample_steps();
             let angle_step =
-                (self.config.end_angle - self.config.start_angle) / (self.config.resolution as f64);
+                (self.config.sweep_end_angle() - self.config.start_angle) / (steps as f64);
 
-            for i in 0..=self.config.resolution {
+            for i in 0..=steps {
                 let angle = self.config.start_angle + (i as f64) * angle_step;
                 let depth = self.config.depth_at_angle(angle, self.cutting_bit.depth);
                 self.rendered.depth_map.push(depth);
-
-                // Simple shading based on depth (deeper = darker)
-                // Avoid division by zero when depth is 0
-                let shading = if self.cutting_bit.depth > 0.0 {
-                    1.0 - (depth / (self.cutting_bit.depth * 2.0)).min(1.0)
-                } else {
-                    0.5 // Default shading for zero-depth bits
-                };
-                self.rendered.shading.push(shading);
+                self.rendered
+                    .shading
+                    .push(depth_shading(depth, self.cutting_bit.depth));
             }
         }
     }
@@ -267,154 +389,452 @@ impl RoseEngineLathe {
         &self.cut_geometry
     }
 
+    /// Fit the generated center line with arcs within `tolerance`
+    ///
+    /// Fills the tool path's `segments` with [`fit_arcs`](crate::fit_arcs)
+    /// of the center line and its `arcs` with the arcs among them, so
+    /// G-code export cuts G2/G3 arcs and falls back to G1 lines where the
+    /// curvature changes too fast. Generating again clears the fit.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLathe, RosettePattern};
+    ///
+    /// let mut config = RoseEngineConfig::new(20.0, 0.0);
+    /// config.rosette = RosettePattern::Circular;
+    /// let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+    /// lathe.generate();
+    /// lathe.fit_arcs(0.01).unwrap();
+    /// assert!(lathe.tool_path().arcs.len() <= 8);
+    /// ```
+    pub fn fit_arcs(&mut self, tolerance: f64) -> Result<(), SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        if !(tolerance.is_finite() && tolerance > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "tolerance must be positive".to_string(),
+            ));
+        }
+        let segments = crate::common::fit_arcs(&self.tool_path, tolerance);
+        self.cut_geometry.arcs = segments
+            .iter()
+            .filter_map(|segment| match segment {
+                PathSegment::Arc(arc) => Some(arc.clone()),
+                PathSegment::Line { .. } => None,
+            })
+            .collect();
+        self.cut_geometry.segments = segments;
+        Ok(())
+    }
+
     /// Get the rendered output
     pub fn rendered_output(&self) -> &RenderedOutput {
         &self.rendered
     }
 
+    /// The tool path at `n_frames` rosette phases evenly spaced from
+    /// `phase_range.0` to `phase_range.1` (radians), both included
+    ///
+    /// Each frame is the center line `iter_tool_path` yields with
+    /// `config.phase` set to that frame's phase; a single frame uses the
+    /// start phase. Works without generating.
+    ///
+    /// # Example
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLathe};
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+    /// let frames = lathe.frames_phase_sweep(8, (0.0, PI / 6.0)).unwrap();
+    /// assert_eq!(frames.len(), 8);
+    /// ```
+    pub fn frames_phase_sweep(
+        &self,
+        n_frames: usize,
+        phase_range: (f64, f64),
+    ) -> Result<Vec<Vec<Point2D>>, SpirographError> {
+        let (start, end) = phase_range;
+        if !(start.is_finite() && end.is_finite()) {
+            return Err(SpirographError::InvalidParameter(
+                "phase range must be finite".to_string(),
+            ));
+        }
+        let mut lathe = self.clone();
+        Ok((0..n_frames)
+            .map(|k| {
+                let t = if n_frames > 1 {
+                    k as f64 / (n_frames - 1) as f64
+                } else {
+                    0.0
+                };
+                lathe.config.phase = start + (end - start) * t;
+                lathe.iter_tool_path().collect()
+            })
+            .collect())
+    }
+
+    /// Rendered lines mapped onto `config.surface`
+    ///
+    /// On a flat surface this is the rendered output at z = 0; `to_svg` and
+    /// `rendered_output` always show the flat pattern.
+    pub fn lines_3d(&self) -> Vec<Vec<Point3D>> {
+        self.config.surface.map_lines(
+            &self.rendered.lines,
+            Point2D::new(self.center_x, self.center_y),
+        )
+    }
+
+    /// Export the tool path cut into `config.surface` to STL
+    ///
+    /// Grooves run `config.depth` into the surface along its normal, with
+    /// the cutting bit's cross-section. Fails if the pattern runs past the
+    /// end of a cylinder or cone.
+    ///
+    /// # Arguments
+    /// * `filename` - Output STL file path
+    /// * `config` - Export configuration (depth)
+    pub fn to_stl_3d(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+
+        let center = Point2D::new(self.center_x, self.center_y);
+        let lines = self
+            .config
+            .surface
+            .map_lines(std::slice::from_ref(&self.tool_path), center);
+        check_fits_surface(&lines, &self.config.surface)?;
+        write_surface_stl(
+            filename,
+            &lines,
+            None,
+            &self.config.surface,
+            &self.cutting_bit,
+            config,
+        )
+    }
+
     /// Export to SVG format
     ///
     /// # Arguments
     /// * `filename` - Output SVG file path
     pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+        let mut lathe = Cow::Borrowed(self);
+        if config.simplify_tolerance > 0.0 {
+            simplify_polylines(
+                &mut lathe.to_mut().rendered.lines,
+                config.simplify_tolerance,
+            );
+        }
+        lathe
+            .svg_writer(config.svg_precision)?
+            .with_origin(config.origin)
+            .save(filename)
+    }
+
+    /// The SVG export as a string, without writing a file
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLathe};
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+    /// lathe.generate();
+    ///
+    /// // The tool path and both cut edges
+    /// let svg = lathe.to_svg_string().unwrap();
+    /// assert_eq!(svg.matches("<path").count(), 3);
+    /// ```
+    pub fn to_svg_string(&self) -> Result<String, SpirographError> {
+        Ok(self
+            .svg_writer(DEFAULT_SVG_PRECISION)?
+            .into_document()
+            .to_string())
+    }
+
+    /// Export to SVG with the tool path shaded by the depth map
+    ///
+    /// The tool path is drawn as one short segment per sampled angle of
+    /// the depth map, stroked from light gray (shading 0) to black
+    /// (shading 1) with a width that scales with the cut depth. Runs of
+    /// segments that look the same share a path. Without depth modulation
+    /// every segment is the bit's depth, so the path is a single gray.
+    ///
+    /// # Arguments
+    /// * `filename` - Output SVG file path
+    pub fn to_svg_shaded(&self, filename: &str) -> Result<(), SpirographError> {
+        self.shaded_svg_writer()?.save(filename)
+    }
+
+    /// The shaded SVG export as a string, without writing a file
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLathe};
+    ///
+    /// let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// config.with_depth_modulation(0.5, 6.0);
+    /// let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+    /// lathe.generate();
+    ///
+    /// // Deeper and shallower stretches of the cut stroke differently
+    /// let svg = lathe.to_svg_shaded_string().unwrap();
+    /// assert!(svg.matches("<path").count() > 1);
+    /// ```
+    pub fn to_svg_shaded_string(&self) -> Result<String, SpirographError> {
+        Ok(self.shaded_svg_writer()?.into_document().to_string())
+    }
+
+    /// Build the shaded SVG document: the tool path split by depth
+    fn shaded_svg_writer(&self) -> Result<SvgWriter, SpirographError> {
         if !self.generated {
             return Err(SpirographError::ExportError(
                 "Pattern not generated. Call generate() first.".to_string(),
             ));
         }
 
-        use svg::node::element::{path::Data, Path};
-        use svg::Document;
-
-        // Find bounds
-        let mut min_x = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
-
-        for line in &self.rendered.lines {
-            for point in line {
-                min_x = min_x.min(point.x);
-                max_x = max_x.max(point.x);
-                min_y = min_y.min(point.y);
-                max_y = max_y.max(point.y);
-            }
+        let points: Vec<Point2D> = self.sample_path(None).collect();
+        let bit_depth = self.cutting_bit.depth;
+        let (depths, shading) = if self.rendered.depth_map.is_empty() {
+            (
+                vec![bit_depth; points.len()],
+                vec![depth_shading(bit_depth, bit_depth); points.len()],
+            )
+        } else {
+            (
+                self.rendered.depth_map.clone(),
+                self.rendered.shading.clone(),
+            )
+        };
+        let widths: Vec<f64> = depths
+            .iter()
+            .map(|&depth| depth_stroke_width(TOOL_PATH_STROKE_WIDTH, depth, bit_depth))
+            .collect();
+
+        let mut writer = SvgWriter::framing(self.rendered.lines.iter().map(Vec::as_slice))?;
+        for path in depth_shaded_paths(&writer, &points, &shading, &widths) {
+            writer.add(path);
         }
+        Ok(writer)
+    }
 
-        let margin = 5.0;
-        let width = max_x - min_x + 2.0 * margin;
-        let height = max_y - min_y + 2.0 * margin;
+    /// Build the SVG document: the tool path over the cut edges
+    fn svg_writer(&self, precision: usize) -> Result<SvgWriter, SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
 
-        let mut document = Document::new()
-            .set("width", format!("{}mm", width))
-            .set("height", format!("{}mm", height))
-            .set("viewBox", (min_x - margin, min_y - margin, width, height));
+        let mut writer = SvgWriter::framing(self.rendered.lines.iter().map(Vec::as_slice))?
+            .with_precision(precision);
 
         // Add each line
         for (idx, line) in self.rendered.lines.iter().enumerate() {
-            if line.is_empty() {
+            if polyline_is_degenerate(line) {
                 continue;
             }
 
-            let mut data = Data::new().move_to((line[0].x, line[0].y));
-
-            for point in line.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
-
-            let stroke_width = if idx == 0 { 0.1 } else { 0.05 };
-            let path = Path::new()
-                .set("d", data)
-                .set("fill", "none")
-                .set("stroke", "black")
-                .set("stroke-width", stroke_width);
-
-            document = document.add(path);
+            let stroke_width = if idx == 0 {
+                TOOL_PATH_STROKE_WIDTH
+            } else {
+                0.05
+            };
+            writer.add_polyline(line, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", stroke_width)
+            });
         }
 
-        svg::save(filename, &document).map_err(|e| {
-            SpirographError::ExportError(format!("Failed to save SVG file '{}': {}", filename, e))
-        })
+        Ok(writer)
     }
 
     /// Export to STL format
     ///
+    /// The tool path becomes a closed groove solid with the cutting bit's
+    /// cross-section, cut `config.depth` deep, over a base slab. A nonzero
+    /// `config.tool_radius` first offsets the path by that much along its
+    /// normal, outwards when positive, to compensate for the cutter.
+    ///
     /// # Arguments
     /// * `filename` - Output STL file path
     /// * `config` - Export configuration (depth, base thickness, etc.)
     pub fn to_stl(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
+        write_stl(filename, &self.stl_triangles(config)?)
+    }
+
+    /// The binary STL export as bytes, without writing a file
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLathe};
+    /// use turtles::ExportConfig;
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+    /// lathe.generate();
+    ///
+    /// // An 80-byte header, a triangle count, then 50 bytes per triangle
+    /// let stl = lathe.to_stl_bytes(&ExportConfig::default()).unwrap();
+    /// let count = u32::from_le_bytes(stl[80..84].try_into().unwrap()) as usize;
+    /// assert!(count > 0);
+    /// assert_eq!(stl.len(), 84 + 50 * count);
+    /// ```
+    pub fn to_stl_bytes(&self, config: &ExportConfig) -> Result<Vec<u8>, SpirographError> {
+        let triangles = self.stl_triangles(config)?;
+        let mut bytes = Vec::new();
+        stl_io::write_stl(&mut bytes, triangles.iter())
+            .map_err(|e| SpirographError::ExportError(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Groove and base slab triangles for the STL exports
+    fn stl_triangles(
+        &self,
+        config: &ExportConfig,
+    ) -> Result<Vec<stl_io::Triangle>, SpirographError> {
         if !self.generated {
             return Err(SpirographError::ExportError(
                 "Pattern not generated. Call generate() first.".to_string(),
             ));
         }
 
-        // For STL export, we need to create triangular mesh
-        use stl_io::{Normal, Triangle, Vertex};
-
-        let mut triangles = Vec::new();
-        let depth = config.depth;
-        let num_points = self.tool_path.len();
-
-        // For each line segment in the path, create a rectangular groove
-        for i in 0..num_points {
-            if i >= num_points - 1 {
-                break;
-            }
-
-            let p1 = self.tool_path[i];
-            let p2 = self.tool_path[i + 1];
+        if polyline_is_degenerate(&self.tool_path) {
+            return Err(SpirographError::ExportError(
+                "Tool path has fewer than 2 distinct points".to_string(),
+            ));
+        }
 
-            // Create vertices for the groove
-            let v1_top = Vertex::new([p1.x as f32, p1.y as f32, 0.0]);
-            let v2_top = Vertex::new([p2.x as f32, p2.y as f32, 0.0]);
-            let v1_bottom = Vertex::new([p1.x as f32, p1.y as f32, -depth as f32]);
-            let v2_bottom = Vertex::new([p2.x as f32, p2.y as f32, -depth as f32]);
+        groove_triangles(
+            std::slice::from_ref(&self.tool_path),
+            Point2D::new(self.center_x, self.center_y),
+            &self.cutting_bit.groove_profile(config.depth)?,
+            config,
+        )
+    }
 
-            // Create triangles for the groove sides
-            let normal = Normal::new([0.0, 0.0, 1.0]);
+    /// Export the tool path to G-code
+    ///
+    /// The center line is cut at the bit's depth, in steps of
+    /// `params.depth_per_pass`; with `config.depth_modulation` the Z
+    /// coordinate follows `config.depth_at_angle` along the path. When the
+    /// tool path carries arcs they are cut as G2/G3 moves instead of the
+    /// center line, and after [`fit_arcs`](Self::fit_arcs) the fitted
+    /// arcs and lines are cut in order.
+    ///
+    /// # Arguments
+    /// * `filename` - Output G-code file path
+    /// * `params` - Feeds, speeds, safe height and step depth
+    pub fn to_gcode(&self, filename: &str, params: &GcodeParams) -> Result<(), SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        if polyline_is_degenerate(&self.tool_path) {
+            return Err(SpirographError::ExportError(
+                "Tool path has fewer than 2 distinct points".to_string(),
+            ));
+        }
 
-            // Two triangles per segment
-            triangles.push(Triangle {
-                normal,
-                vertices: [v1_top, v2_top, v1_bottom],
-            });
-            triangles.push(Triangle {
-                normal,
-                vertices: [v2_top, v2_bottom, v1_bottom],
+        let center = Point2D::new(self.center_x, self.center_y);
+        let depth = self.cutting_bit.depth;
+        let mut program = GcodeProgram::new(params)?;
+        let cut_line = |program: &mut GcodeProgram, points: &[Point2D]| {
+            let depths = modulated_depths(points, center, &self.config, depth);
+            program.polyline(points, &depths);
+        };
+        let cut_arc = |program: &mut GcodeProgram, arc: &Arc| {
+            let ends = [arc.start_angle, arc.end_angle].map(|angle| {
+                Point2D::new(
+                    arc.center.x + arc.radius * angle.cos(),
+                    arc.center.y + arc.radius * angle.sin(),
+                )
             });
+            let depths = modulated_depths(&ends, center, &self.config, depth);
+            program.arc(arc, (depths[0], depths[1]));
+        };
+        if !self.cut_geometry.segments.is_empty() {
+            // Runs of lines are cut as one polyline between the arcs
+            let mut run: Vec<Point2D> = Vec::new();
+            for segment in &self.cut_geometry.segments {
+                match segment {
+                    PathSegment::Line { start, end } => {
+                        if run.last() != Some(start) {
+                            cut_line(&mut program, &run);
+                            run = vec![*start];
+                        }
+                        run.push(*end);
+                    }
+                    PathSegment::Arc(arc) => {
+                        cut_line(&mut program, &run);
+                        run.clear();
+                        cut_arc(&mut program, arc);
+                    }
+                }
+            }
+            cut_line(&mut program, &run);
+        } else if !self.cut_geometry.arcs.is_empty() {
+            for arc in &self.cut_geometry.arcs {
+                cut_arc(&mut program, arc);
+            }
+        } else {
+            cut_line(&mut program, &self.tool_path);
         }
-
-        let mut file = std::fs::File::create(filename)
-            .map_err(|e| SpirographError::ExportError(e.to_string()))?;
-        stl_io::write_stl(&mut file, triangles.iter())
-            .map_err(|e| SpirographError::ExportError(e.to_string()))
+        write_gcode(filename, &program.finish())
     }
 
-    /// Export to STEP format
+    /// Export the tool path's points to STEP format
     ///
     /// # Arguments
     /// * `filename` - Output STEP file path
-    /// * `config` - Export configuration
-    pub fn to_step(&self, _filename: &str, _config: &ExportConfig) -> Result<(), SpirographError> {
+    /// * `config` - Export configuration (origin)
+    pub fn to_step(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
         if !self.generated {
             return Err(SpirographError::ExportError(
                 "Pattern not generated. Call generate() first.".to_string(),
             ));
         }
 
-        // STEP export would require a STEP library
-        // This is a placeholder for now
-        Err(SpirographError::ExportError(
-            "STEP export not yet implemented".to_string(),
-        ))
+        write_step(
+            filename,
+            std::slice::from_ref(&self.tool_path),
+            config,
+            None,
+            "Rose Engine Pattern",
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::tests::assert_closed_outward;
+    use crate::common::{JitterConfig, PointsTotal};
     use crate::rose_engine::rosette::RosettePattern;
+    use crate::rose_engine::surface::SurfaceMapping;
+    use std::f64::consts::PI;
 
     #[test]
     fn test_rose_engine_creation() {
@@ -429,8 +849,15 @@ mod tests {
         let mut config = RoseEngineConfig::new(20.0, 2.0);
         config.base_radius = -1.0;
         let bit = CuttingBit::v_shaped(60.0, 1.0);
-        let lathe = RoseEngineLathe::new(config, bit);
+        let lathe = RoseEngineLathe::new(config, bit.clone());
         assert!(lathe.is_err());
+
+        // Zero-count rosettes are rejected, including as the secondary
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 0, 1.0);
+        assert!(RoseEngineLathe::new(config, bit.clone()).is_err());
+        let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 8, 1.0);
+        config.with_secondary_rosette(RosettePattern::Diamant { divisions: 0 }, 0.5);
+        assert!(RoseEngineLathe::new(config, bit).is_err());
     }
 
     #[test]
@@ -445,6 +872,57 @@ mod tests {
         assert!(!lathe.cut_geometry.center_line.is_empty());
     }
 
+    #[test]
+    fn test_phase_sweep_over_one_lobe_returns_to_the_start() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+        let frames = lathe.frames_phase_sweep(5, (0.0, 2.0 * PI / 12.0)).unwrap();
+        assert_eq!(frames.len(), 5);
+        assert_eq!(frames[0], lathe.iter_tool_path().collect::<Vec<_>>());
+        assert!(frames[2]
+            .iter()
+            .zip(&frames[0])
+            .any(|(a, b)| (a.x - b.x).hypot(a.y - b.y) > 0.1));
+        assert_eq!(frames[4].len(), frames[0].len());
+        assert!(frames[4]
+            .iter()
+            .zip(&frames[0])
+            .all(|(a, b)| (a.x - b.x).hypot(a.y - b.y) < 1e-9));
+
+        assert_eq!(lathe.frames_phase_sweep(1, (0.3, 1.0)).unwrap().len(), 1);
+        assert!(lathe.frames_phase_sweep(3, (0.0, f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn test_jitter_wobbles_the_tool_path_reproducibly() {
+        let path = |jitter: Option<JitterConfig>| {
+            let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+            config.jitter = jitter;
+            let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+            lathe.generate();
+            lathe.tool_path.clone()
+        };
+        let plain = path(None);
+        let jittered = path(Some(JitterConfig::new(0.1, 0.01, 11)));
+        assert_eq!(jittered, path(Some(JitterConfig::new(0.1, 0.01, 11))));
+        assert_ne!(jittered, path(Some(JitterConfig::new(0.1, 0.01, 12))));
+
+        // A small wobble about the plain path that still closes
+        assert_eq!(jittered.len(), plain.len());
+        let moved = jittered
+            .iter()
+            .zip(&plain)
+            .map(|(p, q)| (p.x - q.x).hypot(p.y - q.y))
+            .fold(0.0, f64::max);
+        assert!(moved > 0.01 && moved < 1.5, "{}", moved);
+        let (first, last) = (jittered[0], jittered[jittered.len() - 1]);
+        assert!((first.x - last.x).hypot(first.y - last.y) < 1e-9);
+
+        let mut config = RoseEngineConfig::new(20.0, 2.0);
+        config.with_jitter(JitterConfig::new(-0.1, 0.0, 0));
+        assert!(RoseEngineLathe::new(config, CuttingBit::default()).is_err());
+    }
+
     #[test]
     fn test_tool_path_output() {
         let mut config = RoseEngineConfig::new(20.0, 2.0);
@@ -459,6 +937,143 @@ mod tests {
         assert!(!output.cut_edges.is_empty());
     }
 
+    #[test]
+    fn test_gcode_steps_down_along_the_tool_path() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut lathe = RoseEngineLathe::new(config, CuttingBit::flat(0.5, 0.25)).unwrap();
+        let path = std::env::temp_dir().join("test_lathe.nc");
+        let path = path.to_str().unwrap();
+        let params = GcodeParams::default();
+        assert!(lathe.to_gcode(path, &params).is_err());
+
+        lathe.generate();
+        lathe.to_gcode(path, &params).unwrap();
+        let gcode = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        // A closed ring: one rapid in, then 0.1, 0.2 and 0.25 without lifting
+        assert_eq!(gcode.matches("G0 X").count(), 1);
+        assert_eq!(gcode.matches("G0 Z2\n").count(), 2);
+        let cuts = gcode.lines().filter(|l| l.starts_with("G1 X")).count();
+        assert_eq!(cuts, 3 * (lathe.tool_path.len() - 1));
+        assert!(gcode.contains("G1 Z-0.2 F60\n"));
+        assert!(gcode.ends_with("G0 Z2\nM5\nM30\n"));
+    }
+
+    #[test]
+    fn test_gcode_follows_depth_modulation() {
+        let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        config.with_depth_modulation(0.5, 3.0);
+        let mut lathe = RoseEngineLathe::new(config, CuttingBit::flat(0.5, 0.2)).unwrap();
+        lathe.generate();
+        assert_eq!(lathe.tool_path.len(), lathe.rendered.depth_map.len());
+
+        let path = std::env::temp_dir().join("test_lathe_modulated.nc");
+        let path = path.to_str().unwrap();
+        let params = GcodeParams::default().with_depth_per_pass(1.0);
+        lathe.to_gcode(path, &params).unwrap();
+        let gcode = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let z: Vec<f64> = gcode
+            .lines()
+            .filter(|l| l.starts_with("G1 X"))
+            .map(|l| l.split(" Z").nth(1).unwrap().split(' ').next().unwrap())
+            .map(|z| z.parse().unwrap())
+            .collect();
+        assert_eq!(z.len(), lathe.tool_path.len() - 1);
+        for (z, depth) in z.iter().zip(&lathe.rendered.depth_map[1..]) {
+            assert!((z + depth).abs() <= 1e-4);
+        }
+        assert!(z.iter().any(|&z| z < -0.25) && z.iter().any(|&z| z > -0.15));
+    }
+
+    #[test]
+    fn test_gcode_emits_arcs_when_present() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut lathe = RoseEngineLathe::new(config, CuttingBit::flat(0.5, 0.2)).unwrap();
+        lathe.generate();
+        lathe.cut_geometry.arcs = vec![
+            Arc {
+                center: Point2D::new(0.0, 0.0),
+                radius: 20.0,
+                start_angle: 0.0,
+                end_angle: PI,
+            },
+            Arc {
+                center: Point2D::new(0.0, 0.0),
+                radius: 20.0,
+                start_angle: PI / 2.0,
+                end_angle: 0.0,
+            },
+        ];
+
+        let path = std::env::temp_dir().join("test_lathe_arcs.nc");
+        let path = path.to_str().unwrap();
+        lathe.to_gcode(path, &GcodeParams::default()).unwrap();
+        let gcode = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        assert!(!gcode.contains("G1 X"));
+        assert_eq!(gcode.matches("G3 X-20 Y0 ").count(), 2);
+        assert!(gcode.contains("G3 X-20 Y0 Z-0.2 I-20 J0 F300\n"));
+        assert!(gcode.contains("G0 X0 Y20\nG1 Z-0.1 F60\nG2 X20 Y0 Z-0.1 I0 J-20 F300\n"));
+    }
+
+    #[test]
+    fn test_fit_arcs_on_circle_and_lobes() {
+        let mut config = RoseEngineConfig::new(20.0, 0.0);
+        config.rosette = RosettePattern::Circular;
+        let mut lathe = RoseEngineLathe::new(config, CuttingBit::flat(0.5, 0.2)).unwrap();
+        assert!(lathe.fit_arcs(0.01).is_err());
+        lathe.generate();
+        assert!(lathe.fit_arcs(0.0).is_err());
+        lathe.fit_arcs(0.01).unwrap();
+        let arcs = &lathe.tool_path().arcs;
+        assert!(!arcs.is_empty() && arcs.len() <= 8);
+        assert!(arcs.iter().all(|arc| (arc.radius - 20.0).abs() <= 0.01));
+        let sweep: f64 = arcs.iter().map(|arc| arc.end_angle - arc.start_angle).sum();
+        assert!(sweep.abs() > 1.99 * PI && sweep.abs() <= 2.0 * PI);
+
+        // Lobes fit with many arcs that join end to end along the path
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut lathe = RoseEngineLathe::new(config, CuttingBit::flat(0.5, 0.2)).unwrap();
+        lathe.generate();
+        lathe.fit_arcs(0.01).unwrap();
+        let output = lathe.tool_path();
+        let ends = |segment: &PathSegment| match segment {
+            PathSegment::Line { start, end } => (*start, *end),
+            PathSegment::Arc(arc) => {
+                let at = |angle: f64| {
+                    Point2D::new(
+                        arc.center.x + arc.radius * angle.cos(),
+                        arc.center.y + arc.radius * angle.sin(),
+                    )
+                };
+                (at(arc.start_angle), at(arc.end_angle))
+            }
+        };
+        let distance = |a: Point2D, b: Point2D| (a.x - b.x).hypot(a.y - b.y);
+        assert!(output.arcs.len() >= 24 && output.arcs.len() < output.center_line.len() / 4);
+        assert_eq!(
+            distance(ends(&output.segments[0]).0, output.center_line[0]),
+            0.0
+        );
+        for pair in output.segments.windows(2) {
+            assert!(distance(ends(&pair[0]).1, ends(&pair[1]).0) < 1e-9);
+        }
+
+        let path = std::env::temp_dir().join("test_lathe_fit_arcs.nc");
+        let path = path.to_str().unwrap();
+        lathe.to_gcode(path, &GcodeParams::default()).unwrap();
+        let gcode = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+        assert!(gcode.contains("G2 X") || gcode.contains("G3 X"));
+
+        lathe.generate();
+        assert!(lathe.tool_path().segments.is_empty() && lathe.tool_path().arcs.is_empty());
+    }
+
     #[test]
     fn test_rendered_output() {
         let config = RoseEngineConfig::new(20.0, 2.0);
@@ -471,6 +1086,48 @@ mod tests {
         assert!(!rendered.lines.is_empty());
     }
 
+    #[test]
+    fn test_svg_shaded_grays_follow_depth_map() {
+        let stroke_colors = |svg: &str| {
+            let mut colors: Vec<String> = svg
+                .split("stroke=\"")
+                .skip(1)
+                .map(|rest| rest[..rest.find('"').unwrap()].to_string())
+                .collect();
+            colors.sort();
+            colors.dedup();
+            colors
+        };
+
+        let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        config.with_depth_modulation(0.5, 6.0);
+        let mut lathe = RoseEngineLathe::new(config, CuttingBit::v_shaped(60.0, 1.0)).unwrap();
+        assert!(lathe.to_svg_shaded_string().is_err());
+        lathe.generate();
+        let colors = stroke_colors(&lathe.to_svg_shaded_string().unwrap());
+        assert!(colors.len() > 2, "{:?}", colors);
+        assert!(colors.iter().all(|c| c.starts_with('#') && c.len() == 7));
+
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut flat = RoseEngineLathe::new(config, CuttingBit::v_shaped(60.0, 1.0)).unwrap();
+        flat.generate();
+        let svg = flat.to_svg_shaded_string().unwrap();
+        assert_eq!(svg.matches("<path").count(), 1);
+        assert_eq!(stroke_colors(&svg), vec!["#6a6a6a".to_string()]);
+    }
+
+    #[test]
+    fn test_try_generate_rechecks_config() {
+        let mut lathe =
+            RoseEngineLathe::new(RoseEngineConfig::new(20.0, 2.0), CuttingBit::default()).unwrap();
+        lathe.config.base_radius = -1.0;
+        assert!(lathe.try_generate().is_err());
+        assert!(lathe.to_svg_shaded_string().is_err());
+        lathe.config.base_radius = 20.0;
+        lathe.try_generate().unwrap();
+        assert!(!lathe.rendered_output().lines.is_empty());
+    }
+
     #[test]
     fn test_svg_export_without_generate() {
         let config = RoseEngineConfig::new(20.0, 2.0);
@@ -495,4 +1152,386 @@ mod tests {
         assert_eq!(lathe.center_x, 10.0);
         assert_eq!(lathe.center_y, 5.0);
     }
+
+    #[test]
+    fn test_constant_custom_rosette_cuts_clean_circle() {
+        let mut config = RoseEngineConfig::new(20.0, 2.0);
+        config.rosette = RosettePattern::Custom {
+            table: vec![0.5; 8],
+            samples: 8,
+        };
+        config.resolution = PointsTotal(720);
+        let bit = CuttingBit::v_shaped(60.0, 1.0);
+        let mut lathe = RoseEngineLathe::new(config, bit).unwrap();
+        lathe.generate();
+
+        // Constant displacement: a 21 mm circle with edges ±0.5 mm away.
+        // Interior points are exact; the open ends use one chord each.
+        let output = lathe.tool_path();
+        for (edge, expected) in output.cut_edges.iter().zip([21.5, 20.5]) {
+            for (i, p) in edge.iter().enumerate() {
+                let tolerance = if i == 0 || i == edge.len() - 1 {
+                    1e-4
+                } else {
+                    1e-9
+                };
+                assert!((p.x.hypot(p.y) - expected).abs() < tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cut_edges_follow_the_bit_kerf() {
+        let config = RoseEngineConfig::wave(12.0, 8.0, 0.0);
+        let edge_offsets = |bit: CuttingBit, config: RoseEngineConfig| {
+            let mut lathe = RoseEngineLathe::new(config, bit).unwrap();
+            lathe.generate();
+            let output = lathe.tool_path().clone();
+            let offsets: Vec<f64> = output.cut_edges[0]
+                .iter()
+                .zip(&output.center_line)
+                .map(|(e, c)| (e.x - c.x).hypot(e.y - c.y))
+                .collect();
+            (offsets, output.kerf)
+        };
+
+        // A 30° V-bit plunged 0.1 mm cuts far narrower than a flat bit
+        // of the same width
+        let mut v_bit = CuttingBit::v_shaped(30.0, 1.0);
+        v_bit.depth = 0.1;
+        let kerf = v_bit.kerf_at_depth(0.1);
+        let (v_offsets, v_kerf) = edge_offsets(v_bit.clone(), config.clone());
+        let (flat_offsets, _) = edge_offsets(CuttingBit::flat(1.0, 0.1), config.clone());
+        assert!(v_kerf.is_empty());
+        assert!(v_offsets.iter().all(|&o| (o - kerf / 2.0).abs() < 1e-9));
+        assert!(flat_offsets.iter().all(|&o| (o - 0.5).abs() < 1e-9));
+        assert!(kerf < 0.1);
+
+        // With depth modulation the kerf follows the depth point by point
+        let mut modulated = config;
+        modulated.with_depth_modulation(0.5, 4.0);
+        let (offsets, kerf) = edge_offsets(v_bit, modulated);
+        assert_eq!(kerf.len(), offsets.len());
+        for (offset, kerf) in offsets.iter().zip(&kerf) {
+            assert!((offset - kerf / 2.0).abs() < 1e-9);
+        }
+        let widest = kerf.iter().cloned().fold(0.0, f64::max);
+        let narrowest = kerf.iter().cloned().fold(f64::INFINITY, f64::min);
+        assert!(widest > 2.0 * narrowest);
+    }
+
+    #[test]
+    fn test_zero_amplitude_cuts_circle_whatever_the_phase() {
+        // Zero amplitude: the rosette is never sampled, so even a phase that
+        // is not finite leaves a clean circle
+        let mut config = RoseEngineConfig::wave(12.0, 8.0, 0.0);
+        config.phase = f64::INFINITY;
+        config.with_depth_modulation(0.0, 4.0);
+        let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+        lathe.generate();
+        assert!(lathe
+            .tool_path()
+            .center_line
+            .iter()
+            .all(|p| (p.x.hypot(p.y) - 12.0).abs() < 1e-12));
+        assert!(lathe.rendered_output().shading.iter().all(|&s| s == 0.5));
+
+        let mut negative = RoseEngineConfig::wave(12.0, 8.0, 0.0);
+        negative.amplitude = -1.0;
+        assert!(negative.validate().is_err());
+        assert!(
+            crate::rose_engine::RoseEngineLatheRun::new(negative, CuttingBit::default(), 4)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_collapsed_tool_path_is_deduped_before_export() {
+        // Half the table pulls the radius to zero, parking the tool on the
+        // center for a stretch of repeated points
+        let mut config = RoseEngineConfig::new(10.0, 10.0);
+        config.rosette = RosettePattern::Custom {
+            table: [vec![0.0; 4], vec![-1.0; 4]].concat(),
+            samples: 8,
+        };
+        config.resolution = PointsTotal(360);
+        let bit = CuttingBit::v_shaped(60.0, 1.0);
+        let mut lathe = RoseEngineLathe::new(config, bit).unwrap();
+        lathe.generate();
+
+        let center_line = &lathe.tool_path().center_line;
+        assert!(center_line.len() < 361);
+        assert!(center_line.windows(2).all(|w| w[0] != w[1]));
+
+        // Every edge point sits exactly half the bit width from the path
+        for edge in &lathe.tool_path().cut_edges {
+            for (e, c) in edge.iter().zip(center_line) {
+                assert!(((e.x - c.x).hypot(e.y - c.y) - 0.5).abs() < 1e-9);
+            }
+        }
+
+        let path = std::env::temp_dir().join("test_lathe_deduped.svg");
+        lathe.to_svg(path.to_str().unwrap()).unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(!svg.contains("NaN"));
+    }
+
+    #[test]
+    fn test_iter_tool_path_matches_generate() {
+        // Deduped like the stored path when the tool parks on the center
+        let mut config = RoseEngineConfig::new(10.0, 10.0);
+        config.rosette = RosettePattern::Custom {
+            table: [vec![0.0; 4], vec![-1.0; 4]].concat(),
+            samples: 8,
+        };
+        let mut enveloped = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        enveloped.with_amplitude_envelope(crate::common::Envelope::angular(0.0, PI, PI / 4.0));
+        for config in [config, enveloped] {
+            let mut lathe =
+                RoseEngineLathe::new_with_center(config, CuttingBit::default(), 3.0, -1.0).unwrap();
+            let lazy: Vec<Point2D> = lathe.iter_tool_path().collect();
+            lathe.generate();
+            assert_eq!(lazy, lathe.tool_path().center_line);
+        }
+    }
+
+    #[test]
+    fn test_cylinder_mapping_wraps_pattern_around_band() {
+        let mut config = RoseEngineConfig::new(3.0, 0.5);
+        config.rosette = RosettePattern::MultiLobe { lobes: 12 };
+        config.resolution = PointsTotal(720);
+        config.with_surface(SurfaceMapping::Cylinder {
+            radius: 20.0,
+            height: 5.0,
+        });
+        let bit = CuttingBit::v_shaped(60.0, 0.2);
+        let mut lathe = RoseEngineLathe::new_with_center(config, bit, 5.0, -3.0).unwrap();
+        lathe.generate();
+
+        let lines = lathe.lines_3d();
+        assert_eq!(lines.len(), lathe.rendered_output().lines.len());
+        for p in lines.iter().flatten() {
+            assert!((p.x.hypot(p.y) - 20.0).abs() < 1e-9);
+        }
+
+        // The rosette's ±amplitude swing becomes the axial extent
+        let z: Vec<f64> = lines[0].iter().map(|p| p.z).collect();
+        let min_z = z.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max_z = z.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        assert!((min_z - 2.5).abs() < 1e-9, "min z {}", min_z);
+        assert!((max_z - 3.5).abs() < 1e-9, "max z {}", max_z);
+
+        // The flat rendering is untouched
+        let first = lathe.rendered_output().lines[0][0];
+        assert!((first.x - 5.0 - 2.5).abs() < 1e-9 && (first.y + 3.0).abs() < 1e-9);
+
+        let path = std::env::temp_dir().join("test_lathe_cylinder.stl");
+        let path = path.to_str().unwrap();
+        lathe.to_stl_3d(path, &ExportConfig::default()).unwrap();
+        let size = std::fs::metadata(path).unwrap().len();
+        let _ = std::fs::remove_file(path);
+        // A closed V-groove ring, three faces per segment
+        assert_eq!(size, 84 + 50 * 6 * (lathe.tool_path.len() as u64 - 1));
+    }
+
+    #[test]
+    fn test_stl_base_slab_sets_z_extent() {
+        let mut lathe =
+            RoseEngineLathe::new(RoseEngineConfig::new(10.0, 0.5), CuttingBit::default()).unwrap();
+        lathe.generate();
+        let path = std::env::temp_dir().join("test_lathe_base_slab.stl");
+        let z_extent = |base_thickness: f64| {
+            let config = ExportConfig {
+                depth: 0.1,
+                base_thickness,
+                ..ExportConfig::default()
+            };
+            lathe.to_stl(path.to_str().unwrap(), &config).unwrap();
+            let mut file = std::fs::File::open(&path).unwrap();
+            let mesh = stl_io::read_stl(&mut file).unwrap();
+            let z = mesh.vertices.iter().map(|v| v[2] as f64);
+            z.clone().fold(f64::NEG_INFINITY, f64::max) - z.fold(f64::INFINITY, f64::min)
+        };
+        assert!((z_extent(2.0) - 2.1).abs() < 1e-6);
+        assert!((z_extent(0.0) - 0.1).abs() < 1e-6);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_partial_arc_tool_path_spans_its_range() {
+        let config = RoseEngineConfig::partial_arc(20.0, 1.0, 180.0, 360.0).unwrap();
+        let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+        lathe.generate();
+
+        // The top half as previewed, from 9 o'clock round to 3 o'clock
+        let path = &lathe.tool_path;
+        assert_eq!(path.len(), 501);
+        assert!(path.iter().all(|p| p.y <= 1e-9));
+        let (last, arc) = path.split_last().unwrap();
+        let angles: Vec<f64> = arc
+            .iter()
+            .map(|p| p.y.atan2(p.x).rem_euclid(2.0 * PI))
+            .collect();
+        assert!((angles[0] - PI).abs() < 1e-9);
+        assert!(angles.windows(2).all(|pair| pair[1] > pair[0]));
+        assert!(last.x > 0.0 && last.y.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_whole_turn_sweeps_match_direct_trig() {
+        // Tabled angles, turned through a start angle, over one turn and two
+        for (start, turns) in [(0.0, 1.0), (1.0, 1.0), (-0.5, 2.0)] {
+            let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+            config.start_angle = start;
+            config.end_angle = start + 2.0 * PI * turns;
+            let lathe = RoseEngineLathe::new(config.clone(), CuttingBit::default()).unwrap();
+            let step = 2.0 * PI * turns / config.resolution.get() as f64;
+            for (i, p) in lathe.sample_path(None).enumerate() {
+                let angle = start + (i as f64) * step;
+                let r = config.radius_at_angle(angle);
+                assert!((p.x - r * angle.cos()).abs() < 1e-12);
+                assert!((p.y - r * angle.sin()).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_spiral_feeds_inward_over_revolutions() {
+        let mut config = RoseEngineConfig::new(20.0, 0.0);
+        config.resolution = PointsTotal(360);
+        config.with_spiral(5, 1.0);
+        config.with_depth_modulation(0.5, 3.0);
+        let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+        lathe.generate();
+
+        // One continuous line, 5 mm further in where it stops
+        let path = &lathe.tool_path;
+        assert_eq!(path.len(), 5 * 360 + 1);
+        let radii: Vec<f64> = path.iter().map(|p| p.x.hypot(p.y)).collect();
+        assert!((radii[0] - 20.0).abs() < 1e-9);
+        assert!((radii[radii.len() - 1] - 15.0).abs() < 1e-9);
+        assert!(radii.windows(2).all(|pair| pair[1] < pair[0]));
+
+        // The depth map and edges follow the spiral point for point
+        let depths = &lathe.rendered_output().depth_map;
+        assert_eq!(depths.len(), path.len());
+        assert_eq!(lathe.tool_path().kerf.len(), path.len());
+        assert_eq!(lathe.tool_path().cut_edges[0].len(), path.len());
+        let center = Point2D::new(0.0, 0.0);
+        let followed = modulated_depths(path, center, &lathe.config, lathe.cutting_bit.depth);
+        assert!(followed
+            .iter()
+            .zip(depths)
+            .all(|(a, b)| (a - b).abs() < 1e-9));
+
+        let mut config = RoseEngineConfig::new(20.0, 0.0);
+        config.revolutions = 0;
+        assert!(RoseEngineLathe::new(config, CuttingBit::default()).is_err());
+    }
+
+    #[test]
+    fn test_stl_is_closed_with_the_bit_profile() {
+        let config = ExportConfig {
+            depth: 0.2,
+            base_thickness: 1.0,
+            ..ExportConfig::default()
+        };
+        for (bit, width) in [
+            // Cut to its radius, a ball leaves a semicircle as wide as itself
+            (CuttingBit::round(0.4), 0.4),
+            // A 90° V cut 0.2 deep is 0.4 wide, narrower than the bit
+            (CuttingBit::v_shaped(90.0, 1.0), 0.4),
+        ] {
+            let mut lathe = RoseEngineLathe::new(RoseEngineConfig::new(10.0, 0.5), bit).unwrap();
+            lathe.generate();
+            let triangles = lathe.stl_triangles(&config).unwrap();
+            assert_closed_outward(&triangles);
+
+            // At the lobe tips the groove's top face reaches half the cut
+            // width past the path
+            let outer = triangles
+                .iter()
+                .flat_map(|t| t.vertices.iter())
+                .filter(|v| v[2] == 0.0)
+                .map(|v| (v[0] as f64).hypot(v[1] as f64))
+                .fold(0.0, f64::max);
+            let path_outer = lathe
+                .tool_path
+                .iter()
+                .map(|p| p.x.hypot(p.y))
+                .fold(0.0, f64::max);
+            assert!((outer - path_outer - width / 2.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_stl_tool_radius_offsets_the_path() {
+        let mut lathe =
+            RoseEngineLathe::new(RoseEngineConfig::new(10.0, 0.5), CuttingBit::round(0.4)).unwrap();
+        lathe.generate();
+        let top_radii = |tool_radius: f64| {
+            let config = ExportConfig {
+                depth: 0.2,
+                base_thickness: 1.0,
+                tool_radius,
+                ..ExportConfig::default()
+            };
+            let triangles = lathe.stl_triangles(&config).unwrap();
+            assert_closed_outward(&triangles);
+            let radii = triangles
+                .iter()
+                .flat_map(|t| t.vertices.iter())
+                .filter(|v| v[2] == 0.0)
+                .map(|v| (v[0] as f64).hypot(v[1] as f64));
+            (
+                radii.clone().fold(f64::INFINITY, f64::min),
+                radii.fold(0.0, f64::max),
+            )
+        };
+
+        // The whole groove moves out, or in, by the tool radius; inside
+        // the valleys the mitred corners follow the offset path's sharper
+        // curvature, so only roughly there
+        let (inner, outer) = top_radii(0.0);
+        for tool_radius in [0.3, -0.3] {
+            let (offset_inner, offset_outer) = top_radii(tool_radius);
+            assert!((offset_outer - outer - tool_radius).abs() < 1e-4);
+            assert!((offset_inner - inner - tool_radius).abs() < 0.05);
+        }
+
+        let bad = ExportConfig {
+            tool_radius: f64::NAN,
+            ..ExportConfig::default()
+        };
+        assert!(matches!(
+            lathe.stl_triangles(&bad),
+            Err(SpirographError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_surface_validation_and_overflow() {
+        let mut config = RoseEngineConfig::new(3.0, 0.5);
+        config.surface = SurfaceMapping::Cylinder {
+            radius: 0.0,
+            height: 5.0,
+        };
+        assert!(RoseEngineLathe::new(config.clone(), CuttingBit::default()).is_err());
+
+        // A 20 mm pattern radius cannot fit on a 5 mm band
+        config.base_radius = 20.0;
+        config.surface = SurfaceMapping::Cone {
+            r_top: 18.0,
+            r_bottom: 20.0,
+            height: 5.0,
+        };
+        let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+        lathe.generate();
+        let path = std::env::temp_dir().join("test_lathe_cone_overflow.stl");
+        let err = lathe
+            .to_stl_3d(path.to_str().unwrap(), &ExportConfig::default())
+            .unwrap_err();
+        assert!(matches!(err, SpirographError::InvalidParameter(_)));
+    }
 }
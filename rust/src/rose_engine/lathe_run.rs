@@ -1,14 +1,60 @@
+use crate::cells::pierceable_cells;
 use crate::clous_de_paris::ClousDeParisConfig;
-use crate::common::{Point2D, SpirographError};
+use crate::common::svg_writer::{SvgWriter, DEFAULT_SVG_PRECISION};
+use crate::common::{
+    debug_assert_finite, dedupe_polylines, drawable_bounds, geometry_hash, map_indices,
+    peak_alignment_phase, polyline_closes, polyline_is_degenerate, simplify_polylines,
+    svg_view_box, with_opacity, Envelope, ExportConfig, Point2D, Point3D, PointsPerRev,
+    PointsTotal, RadialWave, SpirographError, TrigTable, DEFAULT_MAX_POINTS_PER_PATH, SVG_MARGIN,
+};
 use crate::cube::CubeConfig;
 use crate::diamant::DiamantConfig;
-use crate::draperie::DraperieConfig;
+use crate::diff::{write_diff_svg, DiffSummary};
+use crate::draperie::{DraperieConfig, NonIntegerMode};
+use crate::dxf::write_dxf_polylines;
 use crate::flinque::FlinqueConfig;
+use crate::grain_de_riz::GrainDeRizConfig;
+use crate::hpgl::{pen_for, write_hpgl};
 use crate::huiteight::HuitEightConfig;
-use crate::limacon::LimaconConfig;
-use crate::paon::{paon_wave_fn, PaonConfig};
-use crate::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLathe, RosettePattern};
+use crate::limacon::{limacon_curves, LimaconConfig, LimaconLoopMode};
+use crate::metadata::FileMetadata;
+use crate::overlay::OverlayOptions;
+use crate::paon::{paon_wave_fn, PaonConfig, PaonOrigin};
+use crate::post::{parallel_traces, LayerKind, PostProcessors};
+use crate::progressive::{scale_count, Progressive, ProgressiveHandle};
+use crate::raster::{render_preview, PreviewStroke, RgbaImage};
+use crate::rose_engine::gcode::{modulated_depths, write_gcode, GcodeParams, GcodeProgram};
+use crate::rose_engine::lathe::{depth_shading, depth_stroke_width};
+use crate::rose_engine::solid::{groove_triangles, write_step, write_stl};
+use crate::rose_engine::surface::{check_fits_surface, write_surface_stl};
+use crate::rose_engine::{
+    CuttingBit, RoseEngineConfig, RoseEngineLathe, RosettePattern, WearModel,
+};
+use crate::shading::{depth_shaded_paths, shaded_bands};
+use std::borrow::Cow;
 use std::f64::consts::PI;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Stroke width of every line in the SVG export and preview, in mm
+const SVG_STROKE_WIDTH: f64 = 0.05;
+
+/// How the lines of the SVG export are stroked
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LineStroke<'a> {
+    /// Black at the plain stroke width
+    Plain,
+    /// Plain width, colored from the palette by pass index
+    Palette(&'a [String]),
+    /// Widened by the bit's worn width
+    Worn,
+    /// Gray and width following each pass's depth map
+    DepthShaded,
+}
+
+/// Rounding allowed below radius 0 before a clamped ring counts as
+/// crossing the centre, in mm
+const RADIUS_TOLERANCE: f64 = 1e-9;
 
 /// Find t ∈ [0,1] where the segment (x1,y1)→(x2,y2) crosses circle x²+y²=r².
 fn seg_circle_t(x1: f64, y1: f64, x2: f64, y2: f64, r: f64) -> Option<f64> {
@@ -63,6 +109,229 @@ fn seg_circle_t_both(x1: f64, y1: f64, x2: f64, y2: f64, r: f64) -> Vec<f64> {
     out
 }
 
+/// A radial band of a banded lathe run
+///
+/// The band is cut as a concentric stack of `rings` rings whose base radii
+/// are spaced evenly between `r_inner` and `r_outer`. Every ring uses
+/// `config` (rosette, phase, resolution, …) with its base radius replaced.
+#[derive(Debug, Clone)]
+pub struct BandSpec {
+    /// Inner radius of the band in mm
+    pub r_inner: f64,
+    /// Outer radius of the band in mm
+    pub r_outer: f64,
+    /// Number of rings cut within the band
+    pub rings: usize,
+    /// Rose engine configuration shared by all rings of the band
+    pub config: RoseEngineConfig,
+}
+
+impl BandSpec {
+    /// Create a new band specification
+    pub fn new(r_inner: f64, r_outer: f64, rings: usize, config: RoseEngineConfig) -> Self {
+        BandSpec {
+            r_inner,
+            r_outer,
+            rings,
+            config,
+        }
+    }
+
+    /// Base radius of ring `k`, spaced evenly with a half-step from each edge
+    pub fn ring_radius(&self, k: usize) -> f64 {
+        let step = (self.r_outer - self.r_inner) / (self.rings as f64);
+        self.r_inner + (k as f64 + 0.5) * step
+    }
+
+    /// Scale factor applied to the configured amplitudes so every ring stays
+    /// inside the band.
+    ///
+    /// Rings in a band share their phase, so they never cross each other;
+    /// the only constraint is containment. Each ring sits half a ring step
+    /// from the nearest band edge, so the peak radial excursion
+    /// (`amplitude · max|d| + secondary_amplitude · max|d₂|`) is limited to
+    /// 60 % of that half step — the same headroom `DraperieConfig::safe_amplitude`
+    /// leaves.
+    pub fn amplitude_scale(&self) -> f64 {
+        let half_step = (self.r_outer - self.r_inner) / (2.0 * self.rings as f64);
+        let limit = 0.6 * half_step;
+
+        let samples = self.config.resolution.get().max(360);
+        let peak = |pattern: &RosettePattern| {
+            (0..samples)
+                .map(|i| {
+                    pattern
+                        .displacement(2.0 * PI * (i as f64) / (samples as f64))
+                        .abs()
+                })
+                .fold(0.0, f64::max)
+        };
+
+        let mut excursion = self.config.amplitude.abs() * peak(&self.config.rosette);
+        if let Some(ref secondary) = self.config.secondary_rosette {
+            excursion += self.config.secondary_amplitude.abs() * peak(secondary);
+        }
+
+        if excursion > limit {
+            limit / excursion
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Settings that replace a lathe run's own for one pass
+///
+/// A run computes each pass's phase, base radius and amplitude from its
+/// mode (phase rotation, concentric rings or bands); every field that is
+/// set here replaces the computed value for its pass. `phase` is absolute,
+/// so it also replaces the per-pass rotation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PassOverride {
+    /// Rosette amplitude in mm
+    pub amplitude: Option<f64>,
+    /// Rosette phase in radians
+    pub phase: Option<f64>,
+    /// Base radius in mm
+    pub base_radius: Option<f64>,
+    /// Rosette pattern
+    pub rosette: Option<RosettePattern>,
+}
+
+impl PassOverride {
+    /// Check that every set field is usable in a pass configuration
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if let Some(amplitude) = self.amplitude {
+            if !(amplitude.is_finite() && amplitude >= 0.0) {
+                return Err(SpirographError::InvalidParameter(
+                    "amplitude must be non-negative".to_string(),
+                ));
+            }
+        }
+        if let Some(phase) = self.phase {
+            if !phase.is_finite() {
+                return Err(SpirographError::InvalidParameter(
+                    "phase must be finite".to_string(),
+                ));
+            }
+        }
+        if let Some(base_radius) = self.base_radius {
+            if !(base_radius.is_finite() && base_radius > 0.0) {
+                return Err(SpirographError::InvalidParameter(
+                    "base_radius must be positive".to_string(),
+                ));
+            }
+        }
+        if let Some(ref rosette) = self.rosette {
+            rosette.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Replace the set fields of `config`
+    fn apply(&self, config: &mut RoseEngineConfig) {
+        if let Some(amplitude) = self.amplitude {
+            config.amplitude = amplitude;
+        }
+        if let Some(phase) = self.phase {
+            config.phase = phase;
+        }
+        if let Some(base_radius) = self.base_radius {
+            config.base_radius = base_radius;
+        }
+        if let Some(ref rosette) = self.rosette {
+            config.rosette = rosette.clone();
+        }
+    }
+}
+
+/// Order in which the depth steps of a deep cut are machined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DepthPassOrder {
+    /// Cut every depth step of one pass before moving to the next pass
+    #[default]
+    DepthFirst,
+    /// Cut every pass at one depth before stepping down to the next
+    PassFirst,
+}
+
+/// State of a worn bit at the start of one cut
+///
+/// A cut is a run of consecutive lines sharing a pass index and depth, so
+/// each depth step of an expanded pass is its own cut.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PassWear {
+    /// Pass index of the cut's lines (see `line_passes()`)
+    pub pass: usize,
+    /// The cut's lines as a range of `lines()`
+    pub lines: std::ops::Range<usize>,
+    /// Programmed cut depth in mm
+    pub programmed_depth: f64,
+    /// Length cut since the bit was last sharpened, in mm
+    pub cut_since_sharpening: f64,
+    /// Effective groove width in mm
+    pub width: f64,
+    /// Effective cut depth in mm
+    pub depth: f64,
+    /// Whether the bit is resharpened before this cut
+    pub resharpen_before: bool,
+}
+
+/// How the lines of a styled SVG export are colored
+#[derive(Debug, Clone, PartialEq)]
+pub enum SvgColorMode {
+    /// Every line in one SVG color
+    Single(String),
+    /// Line colors taken from the palette by pass index, wrapping around
+    PerPass(Vec<String>),
+    /// Gray and width following each pass's depth map, as in `to_svg_shaded`
+    ByDepth,
+}
+
+/// Stroke options for [`RoseEngineLatheRun::to_svg_styled`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgStyle {
+    pub color_mode: SvgColorMode,
+}
+
+impl SvgStyle {
+    /// Style with lines colored by pass from `palette`
+    pub fn per_pass(palette: &[&str]) -> Self {
+        SvgStyle {
+            color_mode: SvgColorMode::PerPass(palette.iter().map(|c| c.to_string()).collect()),
+        }
+    }
+
+    /// Check for a non-empty palette without blank colors
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        let colors: &[String] = match &self.color_mode {
+            SvgColorMode::Single(color) => std::slice::from_ref(color),
+            SvgColorMode::PerPass(palette) => palette,
+            SvgColorMode::ByDepth => &[],
+        };
+        if matches!(self.color_mode, SvgColorMode::PerPass(_)) && colors.is_empty() {
+            return Err(SpirographError::InvalidParameter(
+                "per-pass palette must not be empty".to_string(),
+            ));
+        }
+        if colors.iter().any(|c| c.trim().is_empty()) {
+            return Err(SpirographError::InvalidParameter(
+                "stroke colors must not be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for SvgStyle {
+    /// Black lines, as in `to_svg`
+    fn default() -> Self {
+        SvgStyle {
+            color_mode: SvgColorMode::Single("black".to_string()),
+        }
+    }
+}
+
 /// A multi-pass rose engine lathe run that creates complex guilloché patterns
 /// by making multiple overlapping cuts at different rotations.
 ///
@@ -79,6 +348,13 @@ pub struct RoseEngineLatheRun {
     pub num_passes: usize,
     /// Number of segments per pass (creates gaps for classical guilloché appearance)
     pub segments_per_pass: usize,
+    /// Fraction of each segment's share of the path that is drawn, in (0, 1];
+    /// the rest is the gap. Default 0.7; barleycorn uses short dashes.
+    pub segment_draw_ratio: f64,
+    /// Shift of the segment starts per pass, as a fraction of one segment
+    /// and gap. Pass `i` starts `i * segment_phase_offset` of the way along,
+    /// so a non-zero offset staggers the gaps between passes. Default 0.0.
+    pub segment_phase_offset: f64,
     /// Radius step for concentric ring mode.
     /// When non-zero, each pass changes the base_radius by this amount
     /// instead of rotating the phase. Used for draperie and similar patterns
@@ -116,11 +392,6 @@ pub struct RoseEngineLatheRun {
     /// the mathematical `DiamantLayer` point-for-point.
     circular_diamant: Option<DiamantConfig>,
 
-    /// Optional limacon configuration.
-    /// When set, `generate()` produces limaçon polar curves, matching
-    /// the mathematical `LimaconLayer` point-for-point.
-    polar_limacon: Option<LimaconConfig>,
-
     /// Optional flinque (engine-turned) configuration.
     /// When set, `generate()` produces concentric chevron rings, matching
     /// the mathematical `FlinqueLayer` point-for-point.
@@ -143,9 +414,57 @@ pub struct RoseEngineLatheRun {
     /// `CubeLayer` point-for-point.
     grid_cube: Option<CubeConfig>,
 
+    /// Optional grain-de-riz (rice grain) configuration.
+    /// When set, `generate()` produces one closed oval per grain on
+    /// concentric rows, matching the mathematical `GrainDeRizLayer`
+    /// point-for-point.
+    ring_grain_de_riz: Option<GrainDeRizConfig>,
+
+    /// Optional trimmed limaçon configuration.
+    /// When set, `generate()` produces the outer curves or inner loops of
+    /// the limaçons, matching the mathematical `LimaconLayer`
+    /// point-for-point. Full limaçons use the standard lathe passes.
+    trimmed_limacon: Option<LimaconConfig>,
+
+    /// Optional radial bands.
+    /// When set, each pass is one ring of one band; bands are cut from the
+    /// innermost outward.
+    bands: Option<Vec<BandSpec>>,
+
+    /// `BandSpec::amplitude_scale` of each band, computed once when the
+    /// bands are set
+    band_amplitude_scales: Vec<f64>,
+
+    /// Per-pass replacements for the computed pass settings, by pass
+    /// index; passes past the end use their computed settings.
+    pass_overrides: Vec<PassOverride>,
+
+    /// Optional depth-pass expansion: (max depth per pass, order).
+    /// When set, `generate()` repeats every pass at stepped depths down to
+    /// the cutting bit's full depth.
+    depth_passes: Option<(f64, DepthPassOrder)>,
+
+    /// Callbacks applied to each pass's lines after generation
+    post_processes: PostProcessors,
+
+    /// Traceability fields embedded in SVG and DXF exports
+    metadata: Option<FileMetadata>,
+
+    /// Opacity of each SVG line, evaluated at its mean angle and radius
+    opacity_envelope: Option<Envelope>,
+
+    /// How the bit wears with cut length, if wear is simulated
+    wear_model: Option<WearModel>,
+
     // Generated data
     passes: Vec<RoseEngineLathe>,
     segmented_lines: Vec<Vec<Point2D>>,
+    line_bands: Vec<usize>,
+    line_passes: Vec<usize>,
+    line_tags: Vec<u32>,
+    line_depths: Vec<f64>,
+    pass_wear: Vec<PassWear>,
+    max_points_per_path: usize,
     generated: bool,
 }
 
@@ -167,7 +486,14 @@ impl RoseEngineLatheRun {
     /// let bit = CuttingBit::v_shaped(30.0, 0.5);
     /// let mut run = RoseEngineLatheRun::new(config, bit, 12).unwrap();
     /// run.generate();
-    /// run.to_svg("guilloche_pattern.svg").unwrap();
+    ///
+    /// // 24 arcs per pass, all within the rosette's reach
+    /// assert_eq!(run.lines().len(), 12 * 24);
+    /// # use turtles::doctest_support::max_radius;
+    /// assert!(max_radius(run.lines(), (0.0, 0.0)) <= 22.0 + 1e-9);
+    ///
+    /// let svg = run.to_svg_string().unwrap();
+    /// assert_eq!(svg.matches("<path").count(), 12 * 24);
     /// ```
     pub fn new(
         config: RoseEngineConfig,
@@ -213,11 +539,15 @@ impl RoseEngineLatheRun {
             ));
         }
 
+        config.validate()?;
+
         Ok(RoseEngineLatheRun {
             base_config: config,
             cutting_bit,
             num_passes,
             segments_per_pass,
+            segment_draw_ratio: 0.7,
+            segment_phase_offset: 0.0,
             radius_step: 0.0,
             phase_shift: 0.0,
             phase_oscillations: 1.0,
@@ -227,13 +557,28 @@ impl RoseEngineLatheRun {
             center_y,
             linear_paon: None,
             circular_diamant: None,
-            polar_limacon: None,
             concentric_flinque: None,
             circular_huiteight: None,
             grid_clous_de_paris: None,
             grid_cube: None,
+            ring_grain_de_riz: None,
+            trimmed_limacon: None,
+            bands: None,
+            band_amplitude_scales: Vec::new(),
+            pass_overrides: Vec::new(),
+            depth_passes: None,
+            post_processes: PostProcessors::default(),
+            metadata: None,
+            opacity_envelope: None,
+            wear_model: None,
             passes: Vec::new(),
             segmented_lines: Vec::new(),
+            line_bands: Vec::new(),
+            line_passes: Vec::new(),
+            line_tags: Vec::new(),
+            line_depths: Vec::new(),
+            pass_wear: Vec::new(),
+            max_points_per_path: DEFAULT_MAX_POINTS_PER_PATH,
             generated: false,
         })
     }
@@ -276,6 +621,7 @@ impl RoseEngineLatheRun {
     /// * `circular_phase` - Dome-shaped phase exponent (0 = disabled, 2.0 = rounded folds)
     /// * `center_x` - X coordinate of center
     /// * `center_y` - Y coordinate of center
+    #[allow(clippy::too_many_arguments)]
     pub fn new_draperie(
         num_rings: usize,
         base_radius: f64,
@@ -283,15 +629,14 @@ impl RoseEngineLatheRun {
         wave_frequency: f64,
         phase_shift: f64,
         phase_oscillations: f64,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         phase_exponent: u32,
         wave_exponent: u32,
         circular_phase: f64,
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        // Compute safe amplitude using the same logic as DraperieConfig
-        let draperie_config = DraperieConfig {
+        let config = DraperieConfig {
             num_rings,
             base_radius,
             radius_step,
@@ -299,30 +644,54 @@ impl RoseEngineLatheRun {
             amplitude: None,
             phase_shift,
             phase_oscillations,
-            resolution,
+            resolution: resolution.into(),
             phase_exponent,
             wave_exponent,
             circular_phase,
+            non_integer_mode: NonIntegerMode::default(),
+            dash_by_phase: None,
+            jitter: None,
         };
-        let amplitude = draperie_config.safe_amplitude();
+        Self::from_draperie_config(&config, center_x, center_y)
+    }
 
-        // Set up the rose engine config with base_phase for 12 o'clock alignment
-        let base_phase = PI / 2.0 + PI / (2.0 * wave_frequency);
-        let mut re_config = RoseEngineConfig::new(base_radius, amplitude);
+    /// Create a rose engine draperie pattern from a `DraperieConfig`
+    ///
+    /// Matches `DraperieLayer::new_with_center(config.clone(), center_x,
+    /// center_y)` point-for-point, including an explicit `amplitude` (0
+    /// gives plain concentric circles); `new_draperie` is this with the
+    /// automatic amplitude. A `jitter` is carried over, but the run's rings
+    /// all take the same wobble (see [`RoseEngineConfig::jitter`]), so they
+    /// only match the layer's without one.
+    pub fn from_draperie_config(
+        config: &DraperieConfig,
+        center_x: f64,
+        center_y: f64,
+    ) -> Result<Self, SpirographError> {
+        config.validate()?;
+
+        // Peaks at 12 o'clock; a non-integer frequency is closed the same
+        // way as in the layer, over as many turns as it needs
+        let (frequency, revolutions) = config.ring_frequency();
+        let mut re_config = RoseEngineConfig::new(config.base_radius, config.effective_amplitude());
         re_config.rosette = RosettePattern::Draperie {
-            frequency: wave_frequency,
-            wave_exponent,
+            frequency,
+            wave_exponent: config.wave_exponent,
         };
-        re_config.resolution = resolution;
-        re_config.phase = base_phase;
+        re_config.resolution = config.resolution.over(revolutions);
+        re_config.end_angle = re_config.start_angle + 2.0 * PI * revolutions as f64;
+        re_config.phase = peak_alignment_phase(frequency);
+        re_config.dash_by_phase = config.dash_by_phase;
+        re_config.jitter = config.jitter;
 
         let bit = CuttingBit::v_shaped(30.0, 0.02);
-        let mut run = Self::new_with_segments(re_config, bit, num_rings, 1, center_x, center_y)?;
-        run.radius_step = radius_step;
-        run.phase_shift = phase_shift;
-        run.phase_oscillations = phase_oscillations;
-        run.circular_phase = circular_phase;
-        run.phase_exponent = phase_exponent;
+        let mut run =
+            Self::new_with_segments(re_config, bit, config.num_rings, 1, center_x, center_y)?;
+        run.radius_step = config.radius_step;
+        run.phase_shift = config.phase_shift;
+        run.phase_oscillations = config.phase_oscillations;
+        run.circular_phase = config.circular_phase;
+        run.phase_exponent = config.phase_exponent;
         Ok(run)
     }
 
@@ -344,18 +713,23 @@ impl RoseEngineLatheRun {
     /// * `n_harmonics` - Fourier harmonics for triangle-wave sharpness (0=sine)
     /// * `fan_angle` - Total angular spread of the fan in radians
     /// * `vanishing_point` - VP distance below circle bottom (fraction of diameter)
+    /// * `origin` - Side of the circle the fan radiates from
+    /// * `asymmetry` - Skew of the line spacing toward one side (-1 to 1)
     /// * `center_x` - X coordinate of center
     /// * `center_y` - Y coordinate of center
+    #[allow(clippy::too_many_arguments)]
     pub fn new_paon(
         num_lines: usize,
         radius: f64,
         amplitude: f64,
         wave_frequency: f64,
         phase_rate: f64,
-        resolution: usize,
+        resolution: impl Into<PointsTotal>,
         n_harmonics: usize,
         fan_angle: f64,
         vanishing_point: f64,
+        origin: PaonOrigin,
+        asymmetry: f64,
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
@@ -365,11 +739,15 @@ impl RoseEngineLatheRun {
             amplitude,
             wave_frequency,
             phase_rate,
-            resolution,
+            resolution: resolution.into(),
             n_harmonics,
             fan_angle,
             vanishing_point,
+            jitter: None,
+            origin,
+            asymmetry,
         };
+        paon_config.validate()?;
 
         // Set up a dummy rose engine config (the linear_paon path will bypass it)
         let re_config = RoseEngineConfig::new(radius, amplitude);
@@ -410,23 +788,41 @@ impl RoseEngineLatheRun {
     /// differs.  For 1-to-1 point matching with `DiamantLayer`, this
     /// constructor uses the Cartesian parameterisation directly.
     ///
+    /// Further rings are cut the same way with the work-piece shifted
+    /// outwards by `ring_spacing` each time and a smaller circle, as the
+    /// layer lays them out (see `DiamantConfig`).
+    ///
     /// # Arguments
-    /// * `num_circles` – Number of circles (= number of lathe passes)
-    /// * `circle_radius` – Radius of each individual circle
+    /// * `num_circles` – Number of circles per ring
+    /// * `circle_radius` – Radius of each circle of the first ring
     /// * `resolution` – Number of points per circle
+    /// * `num_rings` – Number of concentric rings (1 for the classic figure)
+    /// * `ring_spacing` – Radial distance between neighbouring rings
+    /// * `scale_per_ring` – Circle radius factor from one ring to the next
+    /// * `stagger` – Turn every other ring by half a circle
     /// * `center_x` / `center_y` – Pattern centre
+    #[allow(clippy::too_many_arguments)]
     pub fn new_diamant(
         num_circles: usize,
         circle_radius: f64,
-        resolution: usize,
+        resolution: impl Into<PointsTotal>,
+        num_rings: usize,
+        ring_spacing: f64,
+        scale_per_ring: f64,
+        stagger: bool,
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
         let diamant_config = DiamantConfig {
             num_circles,
             circle_radius,
-            resolution,
+            resolution: resolution.into(),
+            num_rings,
+            ring_spacing,
+            scale_per_ring,
+            stagger,
         };
+        diamant_config.validate()?;
 
         // The equivalent rose engine setup:
         //   rosette = Sinusoidal { frequency: 1 }
@@ -436,7 +832,8 @@ impl RoseEngineLatheRun {
         // constraint, but the actual generation bypasses the lathe path.
         let re_config = RoseEngineConfig::new(circle_radius, circle_radius);
         let bit = CuttingBit::v_shaped(30.0, 0.02);
-        let mut run = Self::new_with_segments(re_config, bit, num_circles, 1, center_x, center_y)?;
+        let passes = num_circles * num_rings;
+        let mut run = Self::new_with_segments(re_config, bit, passes, 1, center_x, center_y)?;
         run.circular_diamant = Some(diamant_config);
         Ok(run)
     }
@@ -460,28 +857,50 @@ impl RoseEngineLatheRun {
     /// `RosettePattern::Sinusoidal { frequency: 1.0 }`.  The output matches
     /// `LimaconLayer` point-for-point.
     ///
+    /// ### Trimmed loops
+    ///
+    /// When the amplitude exceeds the base radius the tool crosses the
+    /// centre and cuts an inner loop.  On the engine the cutter is lifted
+    /// while ρ < 0 (`OuterOnly`) or kept down only then (`InnerOnly`); the
+    /// cuts are split where ρ changes sign, each ending on the centre, as
+    /// in `LimaconLayer`.
+    ///
     /// # Arguments
     /// * `num_curves` – Number of curves (= number of lathe passes)
     /// * `base_radius` – Base radius (limaçon *a* parameter)
     /// * `amplitude` – Sinusoidal amplitude (limaçon *b* parameter)
     /// * `resolution` – Number of points per curve
+    /// * `loop_mode` – Which part of each curve is cut
     /// * `center_x` / `center_y` – Pattern centre
     pub fn new_limacon(
         num_curves: usize,
         base_radius: f64,
         amplitude: f64,
-        resolution: usize,
+        resolution: impl Into<PointsTotal>,
+        loop_mode: LimaconLoopMode,
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
+        let resolution = resolution.into();
         let mut re_config = RoseEngineConfig::new(base_radius, amplitude);
         re_config.rosette = RosettePattern::Sinusoidal { frequency: 1.0 };
         re_config.resolution = resolution;
 
         let bit = CuttingBit::v_shaped(30.0, 0.02);
-        let run = Self::new_with_segments(re_config, bit, num_curves, 1, center_x, center_y)?;
-        // No special fields needed – the standard phase-rotation generate()
-        // with Sinusoidal{freq=1} already produces exact limaçon curves.
+        let mut run = Self::new_with_segments(re_config, bit, num_curves, 1, center_x, center_y)?;
+        // Full curves need no special fields – the standard phase-rotation
+        // generate() with Sinusoidal{freq=1} already produces exact limaçons.
+        if loop_mode != LimaconLoopMode::Full {
+            let limacon_config = LimaconConfig {
+                num_curves,
+                base_radius,
+                amplitude,
+                resolution,
+                loop_mode,
+            };
+            limacon_config.validate()?;
+            run.trimmed_limacon = Some(limacon_config);
+        }
         Ok(run)
     }
 
@@ -518,6 +937,14 @@ impl RoseEngineLatheRun {
     ///
     ///   ripple = 0.05 · A · sin(n · wave_frequency · θ / 2)
     ///
+    /// ### Ring offsets
+    ///
+    /// Between passes the rosette can be indexed round by
+    /// `petal_phase_step` per ring, as with the phasing dial of a real
+    /// engine, so that a step of π / n interlocks the chevrons in a
+    /// herringbone. With `alternate` every other pass follows the rosette
+    /// inverted, pointing its chevrons inward.
+    ///
     /// ### Why exact 1-to-1 matching requires direct computation
     ///
     /// The mathematical `FlinqueLayer` spaces its concentric rings uniformly
@@ -535,7 +962,11 @@ impl RoseEngineLatheRun {
     /// * `wave_amplitude` – Chevron amplitude (depth of the V peaks)
     /// * `wave_frequency` – Fine ripple frequency multiplier
     /// * `inner_radius_ratio` – Inner radius as fraction of outer radius
+    /// * `petal_phase_step` – Radians each ring's rosette is turned past
+    ///   the one inside it (0 keeps the chevrons aligned)
+    /// * `alternate` – Flip the chevrons of every other ring
     /// * `center_x` / `center_y` – Pattern centre
+    #[allow(clippy::too_many_arguments)]
     pub fn new_flinque(
         radius: f64,
         num_petals: usize,
@@ -543,6 +974,8 @@ impl RoseEngineLatheRun {
         wave_amplitude: f64,
         wave_frequency: f64,
         inner_radius_ratio: f64,
+        petal_phase_step: f64,
+        alternate: bool,
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
@@ -552,7 +985,12 @@ impl RoseEngineLatheRun {
             wave_amplitude,
             wave_frequency,
             inner_radius_ratio,
+            resolution: None,
+            petal_phase_step,
+            alternate,
+            jitter: None,
         };
+        flinque_config.validate()?;
 
         // The equivalent rose engine setup:
         //   primary rosette  = MultiLobe { lobes: num_petals }
@@ -607,7 +1045,7 @@ impl RoseEngineLatheRun {
     pub fn new_huiteight(
         num_curves: usize,
         scale: f64,
-        resolution: usize,
+        resolution: impl Into<PointsTotal>,
         center_x: f64,
         center_y: f64,
         num_clusters: usize,
@@ -616,7 +1054,7 @@ impl RoseEngineLatheRun {
         let he_config = HuitEightConfig {
             num_curves,
             scale,
-            resolution,
+            resolution: resolution.into(),
             num_clusters,
             cluster_spread,
         };
@@ -652,7 +1090,7 @@ impl RoseEngineLatheRun {
         spacing: f64,
         radius: f64,
         angle: f64,
-        resolution: usize,
+        resolution: impl Into<PointsTotal>,
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
@@ -660,7 +1098,8 @@ impl RoseEngineLatheRun {
             spacing,
             radius,
             angle,
-            resolution,
+            resolution: resolution.into(),
+            emit_cells: false,
         };
 
         let re_config = RoseEngineConfig::new(radius, 0.0);
@@ -688,11 +1127,12 @@ impl RoseEngineLatheRun {
     /// * `resolution`      - Points per line
     /// * `cuts_per_group`  - Number of zigzag lines per cutting group
     /// * `center_x` / `center_y` - Pattern centre
+    #[allow(clippy::too_many_arguments)]
     pub fn new_cube(
         spacing: f64,
         radius: f64,
         angle: f64,
-        resolution: usize,
+        resolution: impl Into<PointsTotal>,
         cuts_per_group: usize,
         gap_per_group: usize,
         amplitude: f64,
@@ -704,7 +1144,7 @@ impl RoseEngineLatheRun {
             spacing,
             radius,
             angle,
-            resolution,
+            resolution: resolution.into(),
             cuts_per_group,
             gap_per_group,
             amplitude,
@@ -718,556 +1158,3364 @@ impl RoseEngineLatheRun {
         Ok(run)
     }
 
-    /// Evaluate the phase-shape function at parameter `t`.
+    /// Create a rose engine grain-de-riz (rice grain) pattern that produces
+    /// identical output to the mathematical `GrainDeRizLayer`.
     ///
-    /// * **dome mode** (`circular_phase > 0`):
-    ///   Uses `sgn(sin(t)) · [1 − (1 − |sin(t)|)^n]` where `n = circular_phase`.
+    /// ## Physical model
     ///
-    /// * **sin-power mode** (`circular_phase == 0`):
-    ///   Uses `|sin(t)|^e · sign(sin(t))` where `e = phase_exponent`.
-    fn phase_shape_fn(&self, t: f64) -> f64 {
-        if self.circular_phase > 0.0 {
-            let s = t.sin();
-            let a = s.abs();
-            let dome = 1.0 - (1.0 - a).powf(self.circular_phase);
-            dome * s.signum()
-        } else {
-            let s = t.sin();
-            s.abs().powi(self.phase_exponent as i32) * s.signum()
-        }
+    /// On a rose engine each grain is a short oval cut with the rosette
+    /// rubber lifted between grains, one row per setting of the slide.
+    /// `RosettePattern::GrainDeRiz` only approximates the grains as a
+    /// ripple on a continuous ring, so for point-for-point matching with
+    /// `GrainDeRizLayer` this constructor stores the `GrainDeRizConfig`
+    /// and traces the same closed ovals directly.
+    ///
+    /// # Arguments
+    /// * `num_rows` - Number of concentric rows (= number of passes)
+    /// * `grains_per_row` - Number of grains on every row
+    /// * `grain_length` / `grain_width` - Grain size in mm
+    /// * `base_radius` - Radius of the innermost row in mm
+    /// * `radius_step` - Distance between neighbouring rows in mm
+    /// * `resolution` - Points around each grain
+    /// * `stagger` - Turn every other row by half a grain pitch
+    /// * `center_x` / `center_y` - Pattern centre
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_grain_de_riz(
+        num_rows: usize,
+        grains_per_row: usize,
+        grain_length: f64,
+        grain_width: f64,
+        base_radius: f64,
+        radius_step: f64,
+        resolution: impl Into<PointsTotal>,
+        stagger: bool,
+        center_x: f64,
+        center_y: f64,
+    ) -> Result<Self, SpirographError> {
+        let grain_config = GrainDeRizConfig {
+            num_rows,
+            grains_per_row,
+            grain_length,
+            grain_width,
+            base_radius,
+            radius_step,
+            resolution: resolution.into(),
+            stagger,
+        };
+        grain_config.validate()?;
+
+        let re_config = RoseEngineConfig::new(base_radius, grain_width / 2.0);
+        let bit = CuttingBit::v_shaped(30.0, 0.02);
+        let mut run = Self::new_with_segments(re_config, bit, num_rows, 1, center_x, center_y)?;
+        run.ring_grain_de_riz = Some(grain_config);
+        Ok(run)
     }
 
-    /// Generate all passes of the rose engine pattern
+    /// Create a banded rose engine run that cuts a different rosette in
+    /// each radial band, the way an operator works through a dial in one
+    /// setup.
     ///
-    /// This creates multiple lathe passes, each rotated by an equal angular increment.
-    /// Each pass is segmented into multiple arcs with gaps to create the characteristic
-    /// guilloché mesh appearance.
+    /// Each band is a concentric stack of rings spaced evenly within the
+    /// band. Amplitudes are scaled down where needed so every ring stays
+    /// strictly inside its band (see `BandSpec::amplitude_scale`).
     ///
-    /// For patterns like diamant (sinusoidal with frequency=1), rotating the phase
-    /// rotates the entire circle around the center, creating the overlapping circles
-    /// pattern. For multi-lobe patterns, rotating the phase rotates the pattern itself.
-    pub fn generate(&mut self) {
-        self.passes.clear();
-        self.segmented_lines.clear();
-
-        // ── Diamant mode: concentric circles tangent to centre ────────
-        if let Some(ref diamant_cfg) = self.circular_diamant {
-            let r = diamant_cfg.circle_radius;
-            let n = diamant_cfg.num_circles;
-            let res = diamant_cfg.resolution;
-            let angle_step = 2.0 * PI / (n as f64);
-
-            for i in 0..n {
-                let rotation_angle = (i as f64) * angle_step;
-                let circle_cx = self.center_x + r * rotation_angle.cos();
-                let circle_cy = self.center_y + r * rotation_angle.sin();
+    /// # Arguments
+    /// * `bands` - Bands ordered from the center outward; must not overlap
+    /// * `cutting_bit` - Cutting bit configuration
+    /// * `center_x` / `center_y` - Pattern centre
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{BandSpec, CuttingBit, RoseEngineConfig, RoseEngineLatheRun};
+    ///
+    /// let bands = vec![
+    ///     BandSpec::new(8.0, 14.0, 6, RoseEngineConfig::classic_multi_lobe(11.0, 24, 0.3)),
+    ///     BandSpec::new(14.0, 19.0, 5, RoseEngineConfig::wave(16.5, 8.0, 0.3)),
+    /// ];
+    /// let mut run = RoseEngineLatheRun::new_banded(bands, CuttingBit::default(), 0.0, 0.0).unwrap();
+    /// run.generate();
+    /// assert_eq!(run.lines().len(), 11);
+    /// ```
+    pub fn new_banded(
+        bands: Vec<BandSpec>,
+        cutting_bit: CuttingBit,
+        center_x: f64,
+        center_y: f64,
+    ) -> Result<Self, SpirographError> {
+        if bands.is_empty() {
+            return Err(SpirographError::InvalidParameter(
+                "at least one band is required".to_string(),
+            ));
+        }
 
-                let mut circle_points = Vec::with_capacity(res + 1);
-                for j in 0..=res {
-                    let t = (j as f64) / (res as f64);
-                    let theta = 2.0 * PI * t;
-                    circle_points.push(Point2D::new(
-                        circle_cx + r * theta.cos(),
-                        circle_cy + r * theta.sin(),
-                    ));
-                }
-                self.segmented_lines.push(circle_points);
+        for (i, band) in bands.iter().enumerate() {
+            if band.r_inner < 0.0 || band.r_outer <= band.r_inner {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "band {} must have 0 <= r_inner < r_outer, got {}..{}",
+                    i, band.r_inner, band.r_outer
+                )));
+            }
+            if band.rings == 0 {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "band {} must have at least 1 ring",
+                    i
+                )));
+            }
+            if band.config.resolution.get() < 10 {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "band {} resolution must be at least 10",
+                    i
+                )));
+            }
+            if i > 0 && band.r_inner < bands[i - 1].r_outer {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "bands must be ordered and non-overlapping: band {} starts at {} inside band {} ending at {}",
+                    i,
+                    band.r_inner,
+                    i - 1,
+                    bands[i - 1].r_outer
+                )));
             }
-
-            self.generated = true;
-            return;
         }
 
-        // ── Huit-eight mode: lemniscate (figure-eight) curves ─────────
-        if let Some(ref he_cfg) = self.circular_huiteight {
-            let n = he_cfg.num_curves;
-            let a = he_cfg.scale;
-            let res = he_cfg.resolution;
-
-            // Build rotation angles (matches HuitEightLayer::generate exactly)
-            let rotations: Vec<f64> = if he_cfg.num_clusters > 0 && he_cfg.num_clusters < n {
-                let nc = he_cfg.num_clusters;
-                let curves_per_cluster = n / nc;
-                let remainder = n % nc;
-                let sector = 2.0 * PI / (nc as f64);
-                let spread = if he_cfg.cluster_spread > 0.0 {
-                    he_cfg.cluster_spread
-                } else {
-                    sector * 0.5
-                };
+        let total_rings = bands.iter().map(|b| b.rings).sum();
+        let mut base_config = bands[0].config.clone();
+        base_config.base_radius = bands[0].ring_radius(0);
 
-                let mut rots = Vec::with_capacity(n);
-                for k in 0..nc {
-                    let cluster_center = (k as f64) * sector;
-                    let count = curves_per_cluster + if k < remainder { 1 } else { 0 };
-                    for c in 0..count {
-                        let t = if count > 1 {
-                            (c as f64) / ((count - 1) as f64) - 0.5
-                        } else {
-                            0.0
-                        };
-                        rots.push(cluster_center + t * spread);
-                    }
-                }
-                rots
-            } else {
-                let angle_step = 2.0 * PI / (n as f64);
-                (0..n).map(|i| (i as f64) * angle_step).collect()
-            };
+        let mut run =
+            Self::new_with_segments(base_config, cutting_bit, total_rings, 1, center_x, center_y)?;
+        run.band_amplitude_scales = bands.iter().map(BandSpec::amplitude_scale).collect();
+        run.bands = Some(bands);
+        Ok(run)
+    }
 
-            for rot in &rotations {
-                let cos_rot = rot.cos();
-                let sin_rot = rot.sin();
+    /// Get the bands of a banded run (empty for other modes)
+    pub fn bands(&self) -> &[BandSpec] {
+        self.bands.as_deref().unwrap_or(&[])
+    }
 
-                let mut pts = Vec::with_capacity(res + 1);
-                for j in 0..=res {
-                    let t = 2.0 * PI * (j as f64) / (res as f64);
-                    let sin_t = t.sin();
-                    let cos_t = t.cos();
-                    let denom = 1.0 + sin_t * sin_t;
-                    let lx = a * cos_t / denom;
-                    let ly = a * sin_t * cos_t / denom;
+    /// Band index of each generated line, parallel to `lines()`.
+    ///
+    /// Empty unless the run was created with `new_banded`.
+    pub fn line_band_indices(&self) -> &[usize] {
+        &self.line_bands
+    }
 
-                    // Rotate and translate
-                    pts.push(Point2D::new(
-                        self.center_x + lx * cos_rot - ly * sin_rot,
-                        self.center_y + lx * sin_rot + ly * cos_rot,
-                    ));
-                }
-                self.segmented_lines.push(pts);
-            }
+    /// Set the drawn fraction of each segment and the per-pass stagger
+    /// of segment starts
+    ///
+    /// `draw_ratio` must be in (0, 1] and `phase_offset` finite; see
+    /// [`segment_draw_ratio`](Self::segment_draw_ratio) and
+    /// [`segment_phase_offset`](Self::segment_phase_offset).
+    pub fn set_segment_gaps(
+        &mut self,
+        draw_ratio: f64,
+        phase_offset: f64,
+    ) -> Result<(), SpirographError> {
+        if !(draw_ratio > 0.0 && draw_ratio <= 1.0) {
+            return Err(SpirographError::InvalidParameter(
+                "segment_draw_ratio must be in (0, 1]".to_string(),
+            ));
+        }
+        if !phase_offset.is_finite() {
+            return Err(SpirographError::InvalidParameter(
+                "segment_phase_offset must be finite".to_string(),
+            ));
+        }
+        self.segment_draw_ratio = draw_ratio;
+        self.segment_phase_offset = phase_offset;
+        Ok(())
+    }
 
-            self.generated = true;
-            return;
+    /// Set the most points written to one SVG `<path>`
+    ///
+    /// Longer lines are split into consecutive paths grouped in a `<g>`.
+    pub fn set_max_points_per_path(&mut self, max_points: usize) -> Result<(), SpirographError> {
+        if max_points < 2 {
+            return Err(SpirographError::InvalidParameter(
+                "max_points_per_path must be at least 2".to_string(),
+            ));
         }
+        self.max_points_per_path = max_points;
+        Ok(())
+    }
 
-        // ── Flinqué mode: concentric chevron rings ────────────────────
-        if let Some(ref flinque_cfg) = self.concentric_flinque {
-            let outer_r = self.base_config.base_radius; // stored in new_flinque
-            let inner_r = outer_r * flinque_cfg.inner_radius_ratio;
-            let wave_amplitude = flinque_cfg.wave_amplitude;
-            let min_radius = wave_amplitude * 0.1;
-            let num_petals = flinque_cfg.num_petals;
-            let wave_frequency = flinque_cfg.wave_frequency;
-
-            for ring_idx in 0..flinque_cfg.num_waves {
-                let t = (ring_idx as f64 + 0.5) / flinque_cfg.num_waves as f64;
-                let base_r = inner_r + (outer_r - inner_r) * t;
-
-                if base_r < min_radius {
-                    continue;
+    /// Replace the computed settings of the first passes, one override per
+    /// pass in pass order
+    ///
+    /// Passes without an override keep their computed settings. The
+    /// direct-geometry modes (paon, diamant, flinque, ...) have no lathe
+    /// passes and ignore overrides. Takes effect on the next `generate()`.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, PassOverride, RoseEngineConfig, RoseEngineLatheRun};
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let mut run = RoseEngineLatheRun::new(config, CuttingBit::flat(0.5, 0.1), 4).unwrap();
+    /// // Fade the amplitude out over the passes
+    /// let ramp = (0..4)
+    ///     .map(|i| PassOverride {
+    ///         amplitude: Some(1.0 - 0.25 * i as f64),
+    ///         ..Default::default()
+    ///     })
+    ///     .collect();
+    /// run.set_pass_overrides(ramp).unwrap();
+    /// assert_eq!(run.pass_overrides()[3].amplitude, Some(0.25));
+    /// run.generate();
+    /// assert_eq!(run.passes()[3].config.amplitude, 0.25);
+    /// ```
+    pub fn set_pass_overrides(
+        &mut self,
+        overrides: Vec<PassOverride>,
+    ) -> Result<(), SpirographError> {
+        if overrides.len() > self.num_passes {
+            return Err(SpirographError::InvalidParameter(format!(
+                "{} pass overrides given for {} passes",
+                overrides.len(),
+                self.num_passes
+            )));
+        }
+        for (i, pass) in overrides.iter().enumerate() {
+            pass.validate().map_err(|e| match e {
+                SpirographError::InvalidParameter(message) => {
+                    SpirographError::InvalidParameter(format!("pass override {}: {}", i, message))
                 }
+                e => e,
+            })?;
+        }
+        self.pass_overrides = overrides;
+        Ok(())
+    }
 
-                let points_per_ring = num_petals * 80;
-                let mut line_points = Vec::with_capacity(points_per_ring + 1);
-
-                for i in 0..=points_per_ring {
-                    let angle = 2.0 * PI * (i as f64) / (points_per_ring as f64);
-                    let petal_phase = angle * num_petals as f64 / 2.0;
+    /// The overrides set by `set_pass_overrides`, by pass index
+    pub fn pass_overrides(&self) -> &[PassOverride] {
+        &self.pass_overrides
+    }
 
-                    // Primary: multi-lobe |sin| chevron
-                    let wave = petal_phase.sin().abs();
-                    let chevron = wave_amplitude * wave;
+    /// Use the computed settings for every pass again
+    pub fn clear_pass_overrides(&mut self) {
+        self.pass_overrides.clear();
+    }
 
-                    // Secondary: fine sinusoidal ripple
-                    let ripple = 0.05 * wave_amplitude * (petal_phase * wave_frequency).sin();
+    /// A copy of this run that cuts every pass in several depth steps
+    ///
+    /// A groove deeper than `max_depth_per_pass` cannot be cut in one go,
+    /// so each pass is repeated `ceil(depth / max_depth_per_pass)` times
+    /// with identical XY geometry and stepped depths, e.g. 0.15, 0.30 and
+    /// 0.40 for a 0.4 mm bit at 0.15 mm per pass. The depth of every line
+    /// is reported by `line_depths()`. If this run has been generated the
+    /// copy is generated too.
+    ///
+    /// # Arguments
+    /// * `max_depth_per_pass` - Deepest cut a single pass may take in mm
+    /// * `order` - Whether each pass reaches full depth before the next starts
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, DepthPassOrder, RoseEngineConfig, RoseEngineLatheRun};
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let bit = CuttingBit::flat(0.5, 0.4);
+    /// let run = RoseEngineLatheRun::new(config, bit, 12).unwrap();
+    /// let deep = run.expand_depth_passes(0.15, DepthPassOrder::DepthFirst).unwrap();
+    /// assert_eq!(deep.depth_steps(), vec![0.15, 0.3, 0.4]);
+    /// ```
+    pub fn expand_depth_passes(
+        &self,
+        max_depth_per_pass: f64,
+        order: DepthPassOrder,
+    ) -> Result<RoseEngineLatheRun, SpirographError> {
+        if !(max_depth_per_pass.is_finite() && max_depth_per_pass > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "max_depth_per_pass must be positive".to_string(),
+            ));
+        }
+        let mut run = self.clone();
+        run.depth_passes = Some((max_depth_per_pass, order));
+        if run.generated {
+            run.generate();
+        }
+        Ok(run)
+    }
+
+    /// Cut depth of each repeat of a pass, shallowest first
+    ///
+    /// A single entry (the bit's depth) unless `expand_depth_passes` was used.
+    pub fn depth_steps(&self) -> Vec<f64> {
+        let total = self.cutting_bit.depth;
+        match self.depth_passes {
+            Some((max_step, _)) if total > max_step => {
+                // Tolerate rounding so 0.3 at 0.15 per pass is two steps
+                let count = (total / max_step - 1e-9).ceil() as usize;
+                (1..=count)
+                    .map(|k| {
+                        if k == count {
+                            total
+                        } else {
+                            k as f64 * max_step
+                        }
+                    })
+                    .collect()
+            }
+            _ => vec![total],
+        }
+    }
+
+    /// Order of the depth steps set by `expand_depth_passes`
+    pub fn depth_pass_order(&self) -> DepthPassOrder {
+        self.depth_passes
+            .map(|(_, order)| order)
+            .unwrap_or_default()
+    }
+
+    /// Cut depth of each generated line, parallel to `lines()`.
+    pub fn line_depths(&self) -> &[f64] {
+        &self.line_depths
+    }
+
+    /// Total length of all generated lines in mm
+    ///
+    /// Depth-pass repeats are counted once per repeat, so this is the
+    /// distance the tool travels while cutting.
+    pub fn cut_length(&self) -> f64 {
+        self.segmented_lines
+            .iter()
+            .flat_map(|line| line.windows(2))
+            .map(|w| (w[1].x - w[0].x).hypot(w[1].y - w[0].y))
+            .sum()
+    }
+
+    /// Generated lines mapped onto `base_config.surface`, parallel to `lines()`
+    ///
+    /// The pattern is measured about the run's center. On a flat surface
+    /// this is `lines()` at z = 0; the SVG exports always show the flat
+    /// pattern.
+    pub fn lines_3d(&self) -> Vec<Vec<Point3D>> {
+        self.base_config.surface.map_lines(
+            &self.segmented_lines,
+            Point2D::new(self.center_x, self.center_y),
+        )
+    }
+
+    /// Export the segmented lines to STL as grooves of `config.depth`
+    ///
+    /// Every line is cut to the same depth with the cutting bit's
+    /// cross-section, over a base slab about the run's center, and offset
+    /// by `config.tool_radius`, as in `RoseEngineLathe::to_stl`.
+    pub fn to_stl(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        let center = Point2D::new(self.center_x, self.center_y);
+        write_stl(
+            filename,
+            &groove_triangles(
+                &self.segmented_lines,
+                center,
+                &self.cutting_bit.groove_profile(config.depth)?,
+                config,
+            )?,
+        )
+    }
+
+    /// Export the points of the segmented lines to STEP
+    ///
+    /// Metadata set with `set_metadata` fills the header.
+    pub fn to_step(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        write_step(
+            filename,
+            &self.segmented_lines,
+            config,
+            self.metadata.as_ref(),
+            "Rose Engine Pattern - Multiple Passes",
+        )
+    }
+
+    /// Export the generated lines cut into `base_config.surface` to STL
+    ///
+    /// Grooves run `config.depth` into the surface along its normal, with
+    /// the cutting bit's cross-section. Fails if the pattern runs past the
+    /// end of a cylinder or cone. With a wear
+    /// model set, each cut's grooves lose the same share of `config.depth`
+    /// as the worn bit loses of its programmed depth.
+    pub fn to_stl_3d(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        let lines = self.lines_3d();
+        check_fits_surface(&lines, &self.base_config.surface)?;
+        let worn_depths = (!self.pass_wear.is_empty()).then(|| {
+            let mut depths = vec![config.depth; lines.len()];
+            for wear in &self.pass_wear {
+                if wear.programmed_depth > 0.0 {
+                    depths[wear.lines.clone()]
+                        .fill(config.depth * wear.depth / wear.programmed_depth);
+                }
+            }
+            depths
+        });
+        write_surface_stl(
+            filename,
+            &lines,
+            worn_depths.as_deref(),
+            &self.base_config.surface,
+            &self.cutting_bit,
+            config,
+        )
+    }
+
+    /// Closed cells of the pattern that can be pierced through for an
+    /// openworked dial
+    ///
+    /// Finds the closed faces enclosed by the generated lines (see
+    /// `planar_cells`), keeps those with area above `min_area`, and insets
+    /// each by `min_web / 2` so neighbouring openings are separated by at
+    /// least `min_web` of material. Cells that collapse under the inset are
+    /// dropped. The inset contours are returned counter-clockwise, ready to
+    /// cut.
+    pub fn pierceable_cells(&self, min_web: f64, min_area: f64) -> Vec<Vec<Point2D>> {
+        pierceable_cells(&self.segmented_lines, min_web, min_area)
+    }
+
+    /// Export the segmented lines to DXF with one LWPOLYLINE each, in mm
+    ///
+    /// Polylines are written open; closed rings already repeat their first
+    /// point. Metadata set with `set_metadata` is included as for
+    /// `to_dxf_pierced`.
+    pub fn to_dxf(&self, filename: &str) -> Result<(), SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        write_dxf_polylines(
+            filename,
+            &self.segmented_lines,
+            false,
+            self.metadata.as_ref(),
+        )
+    }
+
+    /// Export the segmented lines to HPGL for a pen plotter, all with pen 1
+    ///
+    /// See [`to_hpgl_with_pens`](Self::to_hpgl_with_pens).
+    pub fn to_hpgl(&self, filename: &str, scale: f64) -> Result<(), SpirographError> {
+        self.to_hpgl_with_pens(filename, scale, &[])
+    }
+
+    /// Export the segmented lines to HPGL, plotting pass `k` with `pens[k]`
+    ///
+    /// Passes are numbered as in `line_pass_indices()`; passes past the
+    /// end of `pens` use its last pen, or pen 1 when it is empty. Lengths
+    /// are in mm multiplied by `scale`; see [`hpgl_commands`](crate::hpgl::hpgl_commands).
+    pub fn to_hpgl_with_pens(
+        &self,
+        filename: &str,
+        scale: f64,
+        pens: &[u32],
+    ) -> Result<(), SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        let line_pens: Vec<u32> = self.line_passes.iter().map(|&k| pen_for(pens, k)).collect();
+        write_hpgl(filename, &self.segmented_lines, &line_pens, scale)
+    }
+
+    /// Export every pass to G-code, in cutting order
+    ///
+    /// Each segmented line is cut down to its `line_depths()` entry in
+    /// steps of `params.depth_per_pass`; with `base_config.depth_modulation`
+    /// the Z coordinate follows `depth_at_angle` about the run's center.
+    /// Lines already repeated by `expand_depth_passes` are each stepped
+    /// down from the surface again, so pick one of the two step settings.
+    pub fn to_gcode(&self, filename: &str, params: &GcodeParams) -> Result<(), SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        let center = Point2D::new(self.center_x, self.center_y);
+        let mut program = GcodeProgram::new(params)?;
+        for (line, &depth) in self.segmented_lines.iter().zip(&self.line_depths) {
+            let depths = modulated_depths(line, center, &self.base_config, depth);
+            program.polyline(line, &depths);
+        }
+        write_gcode(filename, &program.finish())
+    }
+
+    /// Export the pierceable cells to DXF as closed polylines
+    ///
+    /// See `pierceable_cells`. Fails if no cell survives the inset.
+    pub fn to_dxf_pierced(
+        &self,
+        filename: &str,
+        min_web: f64,
+        min_area: f64,
+    ) -> Result<(), SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        let cells = self.pierceable_cells(min_web, min_area);
+        if cells.is_empty() {
+            return Err(SpirographError::ExportError(format!(
+                "No closed cells larger than {} remain after a {} web inset",
+                min_area, min_web
+            )));
+        }
+        write_dxf_polylines(filename, &cells, true, self.metadata.as_ref())
+    }
+
+    /// Register a callback that edits each pass's lines after generation
+    ///
+    /// Callbacks run in registration order at the end of every
+    /// `generate()`, once per pass, with `LayerKind::LathePass` and the pass
+    /// index (see `line_pass_indices()`). They run after deduplication and
+    /// before depth-pass expansion, so every depth repeats the edited lines.
+    /// Regenerating starts from fresh lines, so edits are re-applied rather
+    /// than stacked. See [`crate::post`] for ready-made callbacks.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::post::drop_lines;
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLatheRun};
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 12).unwrap();
+    /// run.add_post_process(drop_lines(|line| line.len() < 3));
+    /// run.generate();
+    /// ```
+    pub fn add_post_process<F>(&mut self, f: F)
+    where
+        F: Fn(&mut Vec<Vec<Point2D>>, LayerKind, usize) + Send + Sync + 'static,
+    {
+        self.post_processes.push(Arc::new(f));
+    }
+
+    /// Remove every post-processing callback
+    pub fn clear_post_processes(&mut self) {
+        self.post_processes.clear();
+    }
+
+    /// Cut every line as `n` parallel traces `spacing` apart
+    ///
+    /// Registers [`parallel_traces`](crate::post::parallel_traces)
+    /// for every pass, after the callbacks already added; an odd `n` keeps
+    /// each line between its traces. `clear_post_processes` removes it.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLatheRun};
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 4).unwrap();
+    /// run.generate();
+    /// let single = run.lines().len();
+    /// run.with_parallel_traces(3, 0.05).unwrap();
+    /// run.generate();
+    /// assert_eq!(run.lines().len(), 3 * single);
+    /// ```
+    pub fn with_parallel_traces(&mut self, n: usize, spacing: f64) -> Result<(), SpirographError> {
+        if n == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "n must be at least 1".to_string(),
+            ));
+        }
+        if !(spacing.is_finite() && spacing > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "spacing must be positive".to_string(),
+            ));
+        }
+        self.add_post_process(parallel_traces(|_, _| true, n, spacing));
+        Ok(())
+    }
+
+    /// Set the traceability metadata written into SVG and DXF exports
+    pub fn set_metadata(&mut self, metadata: FileMetadata) {
+        self.metadata = Some(metadata);
+    }
+
+    /// Get the export metadata, if set
+    pub fn metadata(&self) -> Option<&FileMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Stop embedding metadata in exports
+    pub fn clear_metadata(&mut self) {
+        self.metadata = None;
+    }
+
+    /// Fade the SVG export's lines with `envelope`
+    ///
+    /// Each line's opacity is the envelope at the line's mean angle and
+    /// mean radius about the run's center.
+    pub fn set_opacity_envelope(&mut self, envelope: Envelope) -> Result<(), SpirographError> {
+        envelope.validate()?;
+        self.opacity_envelope = Some(envelope);
+        Ok(())
+    }
+
+    /// Draw every SVG line fully opaque again
+    pub fn clear_opacity_envelope(&mut self) {
+        self.opacity_envelope = None;
+    }
+
+    /// Simulate the bit wearing as it cuts, in cutting order
+    ///
+    /// The worn widths and depths are reported by `pass_wear()`, drawn by
+    /// `to_svg_worn`, and used by the passes' depth maps and `to_stl_3d`.
+    pub fn set_wear_model(&mut self, model: WearModel) -> Result<(), SpirographError> {
+        model.validate()?;
+        self.wear_model = Some(model);
+        if self.generated {
+            self.generate();
+        }
+        Ok(())
+    }
+
+    /// Cut with an unworn bit again
+    pub fn clear_wear_model(&mut self) {
+        self.wear_model = None;
+        if self.generated {
+            self.generate();
+        }
+    }
+
+    /// The wear model set by `set_wear_model`
+    pub fn wear_model(&self) -> Option<WearModel> {
+        self.wear_model
+    }
+
+    /// Bit state at the start of every cut, in cutting order
+    ///
+    /// Empty unless a wear model is set and the run is generated.
+    pub fn pass_wear(&self) -> &[PassWear] {
+        &self.pass_wear
+    }
+
+    /// Indices into `pass_wear()` of the cuts preceded by a resharpening
+    pub fn resharpen_passes(&self) -> Vec<usize> {
+        self.pass_wear
+            .iter()
+            .enumerate()
+            .filter(|(_, wear)| wear.resharpen_before)
+            .map(|(k, _)| k)
+            .collect()
+    }
+
+    /// Pass index of each generated line, parallel to `lines()`.
+    ///
+    /// Segments of one lathe pass share its index. In the direct-geometry
+    /// modes (paon, diamant, flinqué, huit-eight, clous de Paris, cube)
+    /// every line is its own pass.
+    pub fn line_pass_indices(&self) -> &[usize] {
+        &self.line_passes
+    }
+
+    /// Each generated line with its pass index, in `lines()` order
+    pub fn segments_with_pass(&self) -> Vec<(usize, &[Point2D])> {
+        self.line_passes
+            .iter()
+            .zip(&self.segmented_lines)
+            .map(|(&pass, line)| (pass, line.as_slice()))
+            .collect()
+    }
+
+    /// Tag every generated line with `f(pass index)`
+    ///
+    /// Tags group lines for separate exports, e.g. even and odd passes for
+    /// a two-tone finish. Call after `generate()`; regenerating clears them.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLatheRun};
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 12).unwrap();
+    /// run.generate();
+    /// run.tag_passes(|pass| (pass % 2) as u32);
+    /// assert_eq!(run.lines_for_tag(0).len() + run.lines_for_tag(1).len(), run.lines().len());
+    /// ```
+    pub fn tag_passes(&mut self, f: impl Fn(usize) -> u32) {
+        self.line_tags = self.line_passes.iter().map(|&pass| f(pass)).collect();
+    }
+
+    /// Tag of each generated line, parallel to `lines()` (empty until tagged)
+    pub fn line_tags(&self) -> &[u32] {
+        &self.line_tags
+    }
+
+    /// Distinct tags in ascending order
+    pub fn tags(&self) -> Vec<u32> {
+        let mut tags = self.line_tags.clone();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    /// Lines carrying `tag`, in generation order
+    pub fn lines_for_tag(&self, tag: u32) -> Vec<&Vec<Point2D>> {
+        self.segmented_lines
+            .iter()
+            .zip(&self.line_tags)
+            .filter(|(_, &t)| t == tag)
+            .map(|(line, _)| line)
+            .collect()
+    }
+
+    /// Map a pass index to its (band index, ring index within the band)
+    fn band_ring(bands: &[BandSpec], mut i: usize) -> (usize, usize) {
+        for (b, band) in bands.iter().enumerate() {
+            if i < band.rings {
+                return (b, i);
+            }
+            i -= band.rings;
+        }
+        let last = bands.len() - 1;
+        (last, bands[last].rings - 1)
+    }
+
+    /// Evaluate the phase-shape function at parameter `t`.
+    ///
+    /// * **dome mode** (`circular_phase > 0`):
+    ///   Uses `sgn(sin(t)) · [1 − (1 − |sin(t)|)^n]` where `n = circular_phase`.
+    ///
+    /// * **sin-power mode** (`circular_phase == 0`):
+    ///   Uses `|sin(t)|^e · sign(sin(t))` where `e = phase_exponent`.
+    fn phase_shape_fn(&self, t: f64) -> f64 {
+        if self.circular_phase > 0.0 {
+            let s = t.sin();
+            let a = s.abs();
+            let dome = 1.0 - (1.0 - a).powf(self.circular_phase);
+            dome * s.signum()
+        } else {
+            let s = t.sin();
+            s.abs().powi(self.phase_exponent as i32) * s.signum()
+        }
+    }
+
+    /// Name of the special (non-rosette) generation mode, if one is set.
+    ///
+    /// These modes bypass the per-pass lathe configuration entirely and
+    /// compute their geometry directly.
+    pub(crate) fn special_mode_name(&self) -> Option<&'static str> {
+        if self.circular_diamant.is_some() {
+            Some("diamant")
+        } else if self.circular_huiteight.is_some() {
+            Some("huit-eight")
+        } else if self.concentric_flinque.is_some() {
+            Some("flinque")
+        } else if self.linear_paon.is_some() {
+            Some("paon")
+        } else if self.grid_clous_de_paris.is_some() {
+            Some("clous de Paris")
+        } else if self.grid_cube.is_some() {
+            Some("cube")
+        } else if self.ring_grain_de_riz.is_some() {
+            Some("grain de riz")
+        } else if self.trimmed_limacon.is_some() {
+            Some("trimmed limaçon")
+        } else {
+            None
+        }
+    }
+
+    /// Build the lathe configuration used for pass `i`.
+    ///
+    /// In banded mode each pass is one ring of one band. In concentric ring
+    /// mode (`radius_step != 0`) the base radius steps across the ring stack
+    /// and the phase oscillates; otherwise the phase is rotated by an equal
+    /// increment per pass. The pass's override, if any, is applied last.
+    pub(crate) fn pass_config(&self, i: usize) -> RoseEngineConfig {
+        if let Some(ref bands) = self.bands {
+            // Banded mode: ring k of band b, amplitude kept inside the band
+            let (b, k) = Self::band_ring(bands, i);
+            let band = &bands[b];
+            let scale = self.band_amplitude_scales[b];
+            let mut pass_config = band.config.clone();
+            pass_config.base_radius = band.ring_radius(k);
+            pass_config.amplitude *= scale;
+            pass_config.secondary_amplitude *= scale;
+            self.apply_pass_override(i, &mut pass_config);
+            return pass_config;
+        }
+
+        let mut pass_config = self.base_config.clone();
+
+        if self.radius_step != 0.0 {
+            // Concentric ring mode: vary base_radius and optionally oscillate phase.
+            // Rings are centred around the original base_radius.
+            let offset = (i as f64) - ((self.num_passes - 1) as f64) / 2.0;
+            pass_config.base_radius = self.base_config.base_radius + offset * self.radius_step;
+            // Sinusoidal phase oscillation: peaks sway back and forth across
+            // the ring stack, creating the classic draperie fold effect.
+            // Uses the configurable phase shape function (dome or sin^e).
+            let phase_t =
+                2.0 * PI * self.phase_oscillations * (i as f64) / (self.num_passes as f64);
+            pass_config.phase =
+                self.base_config.phase + self.phase_shift * self.phase_shape_fn(phase_t);
+            self.apply_pass_override(i, &mut pass_config);
+            if pass_config.clamp_amplitude {
+                pass_config.amplitude = pass_config.amplitude.min(pass_config.max_safe_amplitude());
+            }
+        } else {
+            // Phase-rotation mode (default): rotate the pattern for each pass.
+            let rotation_step = 2.0 * PI / (self.num_passes as f64);
+            let rotation = (i as f64) * rotation_step;
+            pass_config.phase = self.base_config.phase + rotation;
+            self.apply_pass_override(i, &mut pass_config);
+        }
+
+        pass_config
+    }
+
+    /// Replace the computed settings of pass `i` with its override
+    fn apply_pass_override(&self, i: usize, config: &mut RoseEngineConfig) {
+        if let Some(pass) = self.pass_overrides.get(i) {
+            pass.apply(config);
+        }
+    }
+
+    /// Generate all passes of the rose engine pattern
+    ///
+    /// This creates multiple lathe passes, each rotated by an equal angular increment.
+    /// Each pass is segmented into multiple arcs with gaps to create the characteristic
+    /// guilloché mesh appearance.
+    ///
+    /// For patterns like diamant (sinusoidal with frequency=1), rotating the phase
+    /// rotates the entire circle around the center, creating the overlapping circles
+    /// pattern. For multi-lobe patterns, rotating the phase rotates the pattern itself.
+    pub fn generate(&mut self) {
+        self.generate_with(false);
+    }
+
+    /// Check that no concentric ring crosses the centre and that every
+    /// pass can be cut, then generate
+    ///
+    /// In concentric ring mode (`radius_step != 0`) a pass whose amplitude
+    /// exceeds its base radius dips below radius 0 and folds over itself.
+    /// This reports every such pass and the largest amplitude that keeps
+    /// all of them clear, unless the config's `clamp_amplitude` already
+    /// lowers their amplitudes. A pass whose own configuration is invalid,
+    /// such as a ring stepped to a base radius of 0, fails with the index
+    /// of the first such pass. [`generate`](Self::generate) draws folded
+    /// passes as they are and leaves invalid passes out.
+    pub fn try_generate(&mut self) -> Result<(), SpirographError> {
+        self.validate_ring_radii()?;
+        self.validate_passes()?;
+        self.generate();
+        Ok(())
+    }
+
+    /// Fail on the first pass whose lathe cannot be built, naming it
+    fn validate_passes(&self) -> Result<(), SpirographError> {
+        if self.special_mode_name().is_some() {
+            return Ok(());
+        }
+        for i in 0..self.num_passes {
+            RoseEngineLathe::new_with_center(
+                self.pass_config(i),
+                self.cutting_bit.clone(),
+                self.center_x,
+                self.center_y,
+            )
+            .map_err(|e| match e {
+                SpirographError::InvalidParameter(message) => SpirographError::InvalidParameter(
+                    format!("pass {} of {}: {}", i, self.num_passes, message),
+                ),
+                e => e,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Fail if any concentric ring pass would cut below radius 0
+    fn validate_ring_radii(&self) -> Result<(), SpirographError> {
+        if self.radius_step == 0.0 || self.bands.is_some() || self.special_mode_name().is_some() {
+            return Ok(());
+        }
+        let configs: Vec<RoseEngineConfig> =
+            (0..self.num_passes).map(|i| self.pass_config(i)).collect();
+        let negative: Vec<usize> = configs
+            .iter()
+            .enumerate()
+            .filter(|(_, config)| config.min_radius() < -RADIUS_TOLERANCE)
+            .map(|(i, _)| i)
+            .collect();
+        let Some(&first) = negative.first() else {
+            return Ok(());
+        };
+        let safe = configs
+            .iter()
+            .map(|config| config.max_safe_amplitude())
+            .fold(f64::INFINITY, f64::min);
+        Err(SpirographError::InvalidParameter(format!(
+            "passes {:?} of {} would cut below radius 0 (pass {} reaches {:.3} mm); \
+             keep amplitude at or below {:.3} mm, or set clamp_amplitude",
+            negative,
+            self.num_passes,
+            first,
+            configs[first].min_radius(),
+            safe
+        )))
+    }
+
+    /// Generate as [`generate`](Self::generate), with the passes' lathes
+    /// built in parallel
+    ///
+    /// The lines, their order and everything derived from them are
+    /// identical to the serial ones.
+    #[cfg(feature = "parallel")]
+    pub fn generate_parallel(&mut self) {
+        self.generate_with(true);
+    }
+
+    /// Generate, optionally building the passes in parallel
+    fn generate_with(&mut self, parallel: bool) {
+        self.generate_lines(parallel);
+        if self.line_passes.len() != self.segmented_lines.len() {
+            // Direct-geometry modes: each line is its own pass
+            self.line_passes = (0..self.segmented_lines.len()).collect();
+        }
+        dedupe_polylines(&mut self.segmented_lines, self.base_config.dedupe_epsilon);
+        self.apply_post_processes();
+        self.line_depths = vec![self.cutting_bit.depth; self.segmented_lines.len()];
+        if let Some((_, order)) = self.depth_passes {
+            self.repeat_at_depths(order);
+        }
+        self.simulate_wear();
+        debug_assert_finite("RoseEngineLatheRun", &self.segmented_lines);
+    }
+
+    /// Run the post-processing callbacks over each pass's lines
+    ///
+    /// Lines keep their band when a pass's line count is unchanged;
+    /// otherwise every line of the pass takes the band of its first line.
+    fn apply_post_processes(&mut self) {
+        if self.post_processes.is_empty() {
+            return;
+        }
+        let has_bands = !self.line_bands.is_empty();
+        let mut remaining = std::mem::take(&mut self.segmented_lines).into_iter();
+        let mut lines = Vec::new();
+        let mut passes = Vec::new();
+        let mut bands = Vec::new();
+        let mut start = 0;
+        while start < self.line_passes.len() {
+            let pass = self.line_passes[start];
+            let end = self.line_passes[start..]
+                .iter()
+                .position(|&p| p != pass)
+                .map_or(self.line_passes.len(), |n| start + n);
+            let mut group: Vec<Vec<Point2D>> = remaining.by_ref().take(end - start).collect();
+            self.post_processes
+                .apply(&mut group, LayerKind::LathePass, pass);
+            let unchanged = group.len() == end - start;
+            for i in 0..group.len() {
+                passes.push(pass);
+                if has_bands {
+                    bands.push(self.line_bands[if unchanged { start + i } else { start }]);
+                }
+            }
+            lines.extend(group);
+            start = end;
+        }
+        self.segmented_lines = lines;
+        self.line_passes = passes;
+        self.line_bands = bands;
+    }
+
+    /// Repeat the generated lines of every pass once per depth step
+    ///
+    /// Runs after deduplication so the repeats, which are identical in XY,
+    /// survive.
+    fn repeat_at_depths(&mut self, order: DepthPassOrder) {
+        let steps = self.depth_steps();
+
+        // Consecutive lines sharing a pass index form one pass
+        let mut groups: Vec<std::ops::Range<usize>> = Vec::new();
+        for (i, &pass) in self.line_passes.iter().enumerate() {
+            match groups.last_mut() {
+                Some(group) if self.line_passes[group.start] == pass => group.end = i + 1,
+                _ => groups.push(i..i + 1),
+            }
+        }
+        let mut schedule: Vec<(std::ops::Range<usize>, f64)> =
+            Vec::with_capacity(groups.len() * steps.len());
+        match order {
+            DepthPassOrder::DepthFirst => {
+                for group in &groups {
+                    schedule.extend(steps.iter().map(|&depth| (group.clone(), depth)));
+                }
+            }
+            DepthPassOrder::PassFirst => {
+                for &depth in &steps {
+                    schedule.extend(groups.iter().map(|group| (group.clone(), depth)));
+                }
+            }
+        }
+
+        let has_bands = !self.line_bands.is_empty();
+        let mut lines = Vec::with_capacity(self.segmented_lines.len() * steps.len());
+        let mut passes = Vec::with_capacity(lines.capacity());
+        let mut bands = Vec::new();
+        let mut depths = Vec::with_capacity(lines.capacity());
+        for (group, depth) in schedule {
+            for i in group {
+                lines.push(self.segmented_lines[i].clone());
+                passes.push(self.line_passes[i]);
+                if has_bands {
+                    bands.push(self.line_bands[i]);
+                }
+                depths.push(depth);
+            }
+        }
+        self.segmented_lines = lines;
+        self.line_passes = passes;
+        self.line_bands = bands;
+        self.line_depths = depths;
+    }
+
+    /// Fill `pass_wear` by walking the cuts in order, and cut each lathe
+    /// pass's depth map with the bit as worn at its last cut
+    ///
+    /// The bit is resharpened before a cut once the length cut since the
+    /// last sharpening reaches `resharpen_after_m`.
+    fn simulate_wear(&mut self) {
+        self.pass_wear.clear();
+        let Some(model) = self.wear_model else {
+            return;
+        };
+
+        let count = self.segmented_lines.len();
+        let mut since = 0.0;
+        let mut start = 0;
+        while start < count {
+            let key = (self.line_passes[start], self.line_depths[start]);
+            let end = (start..count)
+                .find(|&i| (self.line_passes[i], self.line_depths[i]) != key)
+                .unwrap_or(count);
+            let resharpen_before = start > 0
+                && model
+                    .resharpen_after_m
+                    .is_some_and(|meters| since >= meters * 1000.0);
+            if resharpen_before {
+                since = 0.0;
+            }
+            let (width, depth) = model.worn(self.cutting_bit.width, key.1, since);
+            self.pass_wear.push(PassWear {
+                pass: key.0,
+                lines: start..end,
+                programmed_depth: key.1,
+                cut_since_sharpening: since,
+                width,
+                depth,
+                resharpen_before,
+            });
+            since += self.segmented_lines[start..end]
+                .iter()
+                .flat_map(|line| line.windows(2))
+                .map(|w| (w[1].x - w[0].x).hypot(w[1].y - w[0].y))
+                .sum::<f64>();
+            start = end;
+        }
+
+        for wear in &self.pass_wear {
+            if let Some(lathe) = self.passes.get_mut(wear.pass) {
+                lathe.set_cutting_bit(CuttingBit {
+                    width: wear.width,
+                    depth: wear.depth,
+                    ..self.cutting_bit.clone()
+                });
+            }
+        }
+    }
+
+    /// Fill `passes` and `segmented_lines` for the active generation mode
+    fn generate_lines(&mut self, parallel: bool) {
+        self.passes.clear();
+        self.segmented_lines.clear();
+        self.line_bands.clear();
+        self.line_passes.clear();
+        self.line_tags.clear();
+
+        // ── Diamant mode: rings of circles, the first tangent to centre ─
+        if let Some(ref diamant_cfg) = self.circular_diamant {
+            let center = Point2D::new(self.center_x, self.center_y);
+            self.segmented_lines = diamant_cfg.circles(center);
+            self.generated = true;
+            return;
+        }
+
+        // ── Huit-eight mode: lemniscate (figure-eight) curves ─────────
+        if let Some(ref he_cfg) = self.circular_huiteight {
+            let n = he_cfg.num_curves;
+            let a = he_cfg.scale;
+            let res = he_cfg.resolution.get();
+
+            // Build rotation angles (matches HuitEightLayer::generate exactly)
+            let rotations: Vec<f64> = if he_cfg.num_clusters > 0 && he_cfg.num_clusters < n {
+                let nc = he_cfg.num_clusters;
+                let curves_per_cluster = n / nc;
+                let remainder = n % nc;
+                let sector = 2.0 * PI / (nc as f64);
+                let spread = if he_cfg.cluster_spread > 0.0 {
+                    he_cfg.cluster_spread
+                } else {
+                    sector * 0.5
+                };
+
+                let mut rots = Vec::with_capacity(n);
+                for k in 0..nc {
+                    let cluster_center = (k as f64) * sector;
+                    let count = curves_per_cluster + if k < remainder { 1 } else { 0 };
+                    for c in 0..count {
+                        let t = if count > 1 {
+                            (c as f64) / ((count - 1) as f64) - 0.5
+                        } else {
+                            0.0
+                        };
+                        rots.push(cluster_center + t * spread);
+                    }
+                }
+                rots
+            } else {
+                let angle_step = 2.0 * PI / (n as f64);
+                (0..n).map(|i| (i as f64) * angle_step).collect()
+            };
+
+            for rot in &rotations {
+                let cos_rot = rot.cos();
+                let sin_rot = rot.sin();
+
+                let mut pts = Vec::with_capacity(res + 1);
+                for j in 0..=res {
+                    let t = 2.0 * PI * (j as f64) / (res as f64);
+                    let sin_t = t.sin();
+                    let cos_t = t.cos();
+                    let denom = 1.0 + sin_t * sin_t;
+                    let lx = a * cos_t / denom;
+                    let ly = a * sin_t * cos_t / denom;
+
+                    // Rotate and translate
+                    pts.push(Point2D::new(
+                        self.center_x + lx * cos_rot - ly * sin_rot,
+                        self.center_y + lx * sin_rot + ly * cos_rot,
+                    ));
+                }
+                self.segmented_lines.push(pts);
+            }
+
+            self.generated = true;
+            return;
+        }
+
+        // ── Flinqué mode: concentric chevron rings ────────────────────
+        if let Some(ref flinque_cfg) = self.concentric_flinque {
+            let outer_r = self.base_config.base_radius; // stored in new_flinque
+            let table = TrigTable::new(flinque_cfg.points_per_ring());
+
+            for (ring_idx, base_r) in flinque_cfg.ring_radii(outer_r) {
+                // Primary: multi-lobe |sin| chevron; secondary: fine ripple
+                self.segmented_lines.push(RadialWave::sample_sum_with(
+                    &flinque_cfg.ring_waves(ring_idx, base_r),
+                    &table,
+                    Point2D::new(self.center_x, self.center_y),
+                ));
+            }
+
+            self.generated = true;
+            return;
+        }
+
+        // Linear paon mode: radiating lines from vanishing point
+        if let Some(ref paon_cfg) = self.linear_paon {
+            let r = paon_cfg.radius;
+            let n = paon_cfg.num_lines;
+            let nh = paon_cfg.n_harmonics;
+            let diameter = 2.0 * r;
+
+            // VP above circle top in math coords (= below circle in SVG)
+            let y_vp = r + paon_cfg.vanishing_point * diameter;
+            let y_crit = (r * r / y_vp).min(r);
+            let angle_max = ((r * r - y_crit * y_crit).sqrt() / (y_vp - y_crit)).atan();
+            let dist_near = y_vp - r;
+            let (sin_o, cos_o) = paon_cfg.origin_rotation();
+
+            for i in 0..n {
+                let frac = if n > 1 {
+                    i as f64 / (n - 1) as f64
+                } else {
+                    0.5
+                };
+
+                let angle = -angle_max + 2.0 * angle_max * paon_cfg.fan_fraction(frac);
+                let tan_a = angle.tan();
+
+                // Negative |sin| phase offset → arches open UPWARD (M-shape)
+                let line_phase =
+                    -2.0 * PI * paon_cfg.fan_angle * (PI * paon_cfg.phase_rate * frac).sin().abs();
+
+                let mut line_points = Vec::with_capacity(paon_cfg.resolution.get() + 1);
+
+                for j in 0..=paon_cfg.resolution.get() {
+                    let t_frac = j as f64 / paon_cfg.resolution.get() as f64;
+
+                    let y = -r + diameter * t_frac;
+                    let x_base = (y_vp - y) * tan_a;
+                    let dist = y_vp - y;
+
+                    let theta =
+                        2.0 * PI * paon_cfg.wave_frequency * (dist / dist_near).ln() + line_phase;
+                    let offset = paon_cfg.amplitude * paon_wave_fn(theta, nh);
+
+                    let x = x_base + offset;
+
+                    if x * x + y * y <= r * r {
+                        line_points.push(Point2D::new(
+                            self.center_x + x * cos_o - y * sin_o,
+                            self.center_y + x * sin_o + y * cos_o,
+                        ));
+                    }
+                }
+
+                if line_points.len() >= 2 {
+                    self.segmented_lines.push(line_points);
+                }
+            }
+
+            self.generated = true;
+            return;
+        }
+
+        // ── Clous de Paris mode: two orthogonal sets of parallel lines ─
+        if let Some(ref cdp_cfg) = self.grid_clous_de_paris {
+            let r = cdp_cfg.radius;
+            let s = cdp_cfg.spacing;
+            let grid_angle = cdp_cfg.angle;
+            let res = cdp_cfg.resolution.get();
+
+            for dir in 0..2 {
+                let theta = grid_angle + (dir as f64) * PI / 2.0;
+                let cos_t = theta.cos();
+                let sin_t = theta.sin();
+
+                let n_lines = (r / s).ceil() as i32;
+
+                for i in -n_lines..=n_lines {
+                    let offset = (i as f64) * s;
+
+                    // Tangent lines (disc == 0) have zero length
+                    let disc = r * r - offset * offset;
+                    if disc <= 0.0 {
+                        continue;
+                    }
+
+                    let t_half = disc.sqrt();
+                    let ox = self.center_x + offset * (-sin_t);
+                    let oy = self.center_y + offset * cos_t;
+
+                    let mut line_points = Vec::with_capacity(res + 1);
+
+                    for j in 0..=res {
+                        let frac = j as f64 / res as f64;
+                        let t = -t_half + 2.0 * t_half * frac;
+
+                        let x = ox + t * cos_t;
+                        let y = oy + t * sin_t;
+                        line_points.push(Point2D::new(x, y));
+                    }
+
+                    if line_points.len() >= 2 {
+                        self.segmented_lines.push(line_points);
+                    }
+                }
+            }
+
+            self.generated = true;
+            return;
+        }
+
+        // ── Grain-de-riz mode: closed ovals on concentric rows ─────────
+        if let Some(ref grain_cfg) = self.ring_grain_de_riz {
+            let center = Point2D::new(self.center_x, self.center_y);
+            self.segmented_lines = grain_cfg.grains(center);
+            self.generated = true;
+            return;
+        }
+
+        // ── Trimmed limaçon mode: outer curves or inner loops only ──────
+        if let Some(ref limacon_cfg) = self.trimmed_limacon {
+            self.segmented_lines = limacon_curves(limacon_cfg, self.center_x, self.center_y);
+            self.generated = true;
+            return;
+        }
+
+        // ── Cube mode: parallel zigzag lines with grouping ──────────────
+        if let Some(ref cube_cfg) = self.grid_cube {
+            let r = cube_cfg.radius;
+            let s = cube_cfg.spacing;
+            let cuts = cube_cfg.cuts_per_group;
+            let gap = cube_cfg.gap_per_group;
+            let base_angle = cube_cfg.angle;
+
+            let amplitude = if cube_cfg.amplitude > 0.0 {
+                cube_cfg.amplitude
+            } else {
+                ((gap as f64) + 1.0) * s / 2.0
+            };
+            let leg_rad = cube_cfg.leg_angle.to_radians();
+            let period = 4.0 * amplitude / leg_rad.tan();
+            let half_period = period / 2.0;
+            let group_cycle = (cuts as f64 + gap as f64) * s;
+
+            let cos_a = base_angle.cos();
+            let sin_a = base_angle.sin();
+            let r_sq = r * r;
+
+            let n_groups = (r / group_cycle).ceil() as i32 + 2;
+
+            for g in -n_groups..=n_groups {
+                let group_base = (g as f64) * group_cycle;
+                let phase = if g.rem_euclid(2) == 0 { 0.0 } else { 0.5 };
+
+                for i in 0..(cuts as i32) {
+                    let baseline = group_base + (i as f64) * s;
+
+                    if baseline - amplitude > r || baseline + amplitude < -r {
+                        continue;
+                    }
+
+                    let x_extent = r + period;
+                    let phase_offset = phase * period;
+
+                    let k_start = ((-x_extent + phase_offset) / half_period).floor() as i32;
+                    let k_end = ((x_extent + phase_offset) / half_period).ceil() as i32;
+
+                    let cap = (k_end - k_start + 1).max(0) as usize;
+                    let mut vertices: Vec<(f64, f64)> = Vec::with_capacity(cap);
+                    for k in k_start..=k_end {
+                        let x = (k as f64) * half_period - phase_offset;
+                        let sign = if k.rem_euclid(2) == 0 { 1.0 } else { -1.0 };
+                        let y = baseline + amplitude * sign;
+                        vertices.push((x, y));
+                    }
+
+                    // Clip to circle and collect segments
+                    let mut current_segment: Vec<Point2D> = Vec::new();
+
+                    for v_idx in 0..vertices.len() {
+                        let (x, y) = vertices[v_idx];
+                        let inside = x * x + y * y <= r_sq;
+
+                        if v_idx > 0 {
+                            let (px, py) = vertices[v_idx - 1];
+                            let prev_inside = px * px + py * py <= r_sq;
+
+                            if prev_inside && !inside {
+                                if let Some(t) = seg_circle_t(px, py, x, y, r) {
+                                    let ix = px + t * (x - px);
+                                    let iy = py + t * (y - py);
+                                    let rx = self.center_x + ix * cos_a - iy * sin_a;
+                                    let ry = self.center_y + ix * sin_a + iy * cos_a;
+                                    current_segment.push(Point2D::new(rx, ry));
+                                }
+                                if current_segment.len() >= 2 {
+                                    self.segmented_lines
+                                        .push(std::mem::take(&mut current_segment));
+                                }
+                                current_segment.clear();
+                            } else if !prev_inside && inside {
+                                if let Some(t) = seg_circle_t(px, py, x, y, r) {
+                                    let ix = px + t * (x - px);
+                                    let iy = py + t * (y - py);
+                                    let rx = self.center_x + ix * cos_a - iy * sin_a;
+                                    let ry = self.center_y + ix * sin_a + iy * cos_a;
+                                    current_segment.push(Point2D::new(rx, ry));
+                                }
+                            } else if !prev_inside && !inside {
+                                let both = seg_circle_t_both(px, py, x, y, r);
+                                if both.len() == 2 {
+                                    let t1 = both[0];
+                                    let t2 = both[1];
+                                    let ix1 = px + t1 * (x - px);
+                                    let iy1 = py + t1 * (y - py);
+                                    let ix2 = px + t2 * (x - px);
+                                    let iy2 = py + t2 * (y - py);
+                                    let rx1 = self.center_x + ix1 * cos_a - iy1 * sin_a;
+                                    let ry1 = self.center_y + ix1 * sin_a + iy1 * cos_a;
+                                    let rx2 = self.center_x + ix2 * cos_a - iy2 * sin_a;
+                                    let ry2 = self.center_y + ix2 * sin_a + iy2 * cos_a;
+                                    self.segmented_lines
+                                        .push(vec![Point2D::new(rx1, ry1), Point2D::new(rx2, ry2)]);
+                                }
+                            }
+                        }
 
-                    let r_mod = base_r + chevron + ripple;
-                    line_points.push(Point2D::new(
-                        r_mod * angle.cos() + self.center_x,
-                        r_mod * angle.sin() + self.center_y,
-                    ));
-                }
+                        if inside {
+                            let rx = self.center_x + x * cos_a - y * sin_a;
+                            let ry = self.center_y + x * sin_a + y * cos_a;
+                            current_segment.push(Point2D::new(rx, ry));
+                        }
+                    }
 
-                self.segmented_lines.push(line_points);
+                    if current_segment.len() >= 2 {
+                        self.segmented_lines.push(current_segment);
+                    }
+                }
             }
 
             self.generated = true;
             return;
         }
 
-        // Linear paon mode: radiating lines from vanishing point
-        if let Some(ref paon_cfg) = self.linear_paon {
-            let r = paon_cfg.radius;
-            let n = paon_cfg.num_lines;
-            let nh = paon_cfg.n_harmonics;
-            let diameter = 2.0 * r;
+        // Passes are independent, so their lathes can be generated together;
+        // passes sweeping whole turns share the angles' cosines and sines
+        let table = self.base_config.steps_per_turn().map(TrigTable::new);
+        let lathes = map_indices(self.num_passes, parallel, |i| {
+            let mut lathe = RoseEngineLathe::new_with_center(
+                self.pass_config(i),
+                self.cutting_bit.clone(),
+                self.center_x,
+                self.center_y,
+            )
+            .ok()?;
+            lathe.generate_with_table(table.as_ref());
+            Some(lathe)
+        });
 
-            // VP above circle top in math coords (= below circle in SVG)
-            let y_vp = r + paon_cfg.vanishing_point * diameter;
-            let y_crit = (r * r / y_vp).min(r);
-            let angle_max = ((r * r - y_crit * y_crit).sqrt() / (y_vp - y_crit)).atan();
-            let dist_near = y_vp - r;
+        for (i, lathe) in lathes.into_iter().enumerate() {
+            if let Some(lathe) = lathe {
+                let (dash, phase) = (lathe.config.dash_by_phase, lathe.config.phase);
 
-            for i in 0..n {
-                let frac = if n > 1 {
-                    i as f64 / (n - 1) as f64
+                // Get the complete circular path from this pass
+                let rendered = lathe.rendered_output();
+                if !rendered.lines.is_empty() && !rendered.lines[0].is_empty() {
+                    let complete_path = &rendered.lines[0];
+
+                    // Segment this path into multiple arcs with gaps
+                    let first = self.segmented_lines.len();
+                    self.segment_path(complete_path, i);
+
+                    if let Some(dash) = dash {
+                        let center = Point2D::new(self.center_x, self.center_y);
+                        let dots: Vec<Vec<Point2D>> = self
+                            .segmented_lines
+                            .drain(first..)
+                            .flat_map(|line| dash.split(&line, center, phase))
+                            .collect();
+                        self.segmented_lines.extend(dots);
+                    }
+                }
+
+                self.passes.push(lathe);
+            }
+
+            self.line_passes.resize(self.segmented_lines.len(), i);
+            if let Some(ref bands) = self.bands {
+                let (b, _) = Self::band_ring(bands, i);
+                self.line_bands.resize(self.segmented_lines.len(), b);
+            }
+        }
+
+        self.generated = true;
+    }
+
+    /// Start a coarse-to-fine generation at the given detail fractions
+    ///
+    /// Coarse levels use fewer points per pass and, outside banded mode,
+    /// fewer passes (concentric stacks keep their overall width); the
+    /// direct-geometry modes scale their own line counts and resolutions.
+    /// See `ProgressiveHandle`.
+    pub fn generate_progressive(&mut self, levels: &[f32]) -> ProgressiveHandle<'_, Self> {
+        ProgressiveHandle::new(self, levels)
+    }
+
+    /// A copy of this run with counts and resolutions scaled by `fraction`
+    fn scaled(&self, fraction: f32) -> Self {
+        let mut coarse = self.clone();
+        coarse.base_config.resolution =
+            PointsTotal(scale_count(self.base_config.resolution.get(), fraction, 10));
+
+        if self.bands.is_none() {
+            coarse.num_passes = scale_count(self.num_passes, fraction, 1);
+            if self.radius_step != 0.0 && coarse.num_passes > 1 {
+                coarse.radius_step = self.radius_step * (self.num_passes - 1) as f64
+                    / (coarse.num_passes - 1) as f64;
+            }
+            // Each coarse pass takes the override of the nearest full pass
+            if !self.pass_overrides.is_empty() && coarse.num_passes > 1 {
+                let stretch = (self.num_passes - 1) as f64 / (coarse.num_passes - 1) as f64;
+                coarse.pass_overrides = (0..coarse.num_passes)
+                    .map_while(|j| {
+                        self.pass_overrides
+                            .get((j as f64 * stretch).round() as usize)
+                    })
+                    .cloned()
+                    .collect();
+            } else {
+                coarse.pass_overrides.truncate(coarse.num_passes);
+            }
+        }
+
+        if let Some(ref mut cfg) = coarse.linear_paon {
+            cfg.num_lines = scale_count(cfg.num_lines, fraction, 1);
+            cfg.resolution = PointsTotal(scale_count(cfg.resolution.get(), fraction, 10));
+        }
+        if let Some(ref mut cfg) = coarse.circular_diamant {
+            cfg.num_circles = scale_count(cfg.num_circles, fraction, 1);
+            cfg.resolution = PointsTotal(scale_count(cfg.resolution.get(), fraction, 10));
+        }
+        if let Some(ref mut cfg) = coarse.concentric_flinque {
+            cfg.num_waves = scale_count(cfg.num_waves, fraction, 1);
+        }
+        if let Some(ref mut cfg) = coarse.circular_huiteight {
+            cfg.resolution = PointsTotal(scale_count(cfg.resolution.get(), fraction, 10));
+        }
+        if let Some(ref mut cfg) = coarse.grid_clous_de_paris {
+            cfg.resolution = PointsTotal(scale_count(cfg.resolution.get(), fraction, 10));
+        }
+        if let Some(ref mut cfg) = coarse.grid_cube {
+            cfg.resolution = PointsTotal(scale_count(cfg.resolution.get(), fraction, 10));
+        }
+        if let Some(ref mut cfg) = coarse.ring_grain_de_riz {
+            cfg.resolution = PointsTotal(scale_count(cfg.resolution.get(), fraction, 8));
+        }
+        if let Some(ref mut cfg) = coarse.trimmed_limacon {
+            cfg.resolution = PointsTotal(scale_count(cfg.resolution.get(), fraction, 10));
+        }
+
+        coarse
+    }
+
+    /// Segment a complete circular path into multiple arcs with gaps
+    ///
+    /// Segment starts are shifted by `pass_index * segment_phase_offset`
+    /// cycles; a segment running past the end of the path wraps to its start.
+    fn segment_path(&mut self, path: &[Point2D], pass_index: usize) {
+        if path.is_empty() || self.segments_per_pass == 0 {
+            return;
+        }
+
+        // Special case: segments_per_pass=1 means draw the complete path without gaps
+        if self.segments_per_pass == 1 {
+            self.segmented_lines.push(path.to_vec());
+            return;
+        }
+
+        let total_points = path.len();
+
+        // Each segment takes up a fraction of the circle, drawn for
+        // `segment_draw_ratio` of it and then a gap
+        let points_per_cycle = total_points / self.segments_per_pass;
+        let draw_ratio = self.segment_draw_ratio.clamp(0.0, 1.0);
+        let draw_points = (points_per_cycle as f64 * draw_ratio) as usize;
+
+        // A closed path repeats its first point at the end; skip it on wrapping
+        let period = if polyline_closes(path) {
+            total_points - 1
+        } else {
+            total_points
+        };
+        let shift = if points_per_cycle == 0 {
+            0
+        } else {
+            let cycles = (pass_index as f64 * self.segment_phase_offset).rem_euclid(1.0);
+            ((cycles * points_per_cycle as f64).round() as usize) % points_per_cycle
+        };
+
+        for seg_idx in 0..self.segments_per_pass {
+            let start_idx = shift + seg_idx * points_per_cycle;
+            let end_idx = start_idx + draw_points;
+
+            if start_idx < total_points && end_idx > start_idx {
+                let segment: Vec<Point2D> = if end_idx <= total_points {
+                    path[start_idx..end_idx].to_vec()
                 } else {
-                    0.5
+                    (start_idx..end_idx).map(|k| path[k % period]).collect()
                 };
+                self.segmented_lines.push(segment);
+            }
+        }
+    }
 
-                let angle = -angle_max + 2.0 * angle_max * frac;
-                let tan_a = angle.tan();
+    /// Export combined pattern to SVG format
+    ///
+    /// # Arguments
+    /// * `filename` - Output SVG file path
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.svg_writer(None, None, None, LineStroke::Plain, DEFAULT_SVG_PRECISION)?
+            .save(filename)
+    }
 
-                // Negative |sin| phase offset → arches open UPWARD (M-shape)
-                let line_phase =
-                    -2.0 * PI * paon_cfg.fan_angle * (PI * paon_cfg.phase_rate * frac).sin().abs();
+    /// The combined pattern's SVG export as a string, without writing a file
+    pub fn to_svg_string(&self) -> Result<String, SpirographError> {
+        Ok(self
+            .svg_writer(None, None, None, LineStroke::Plain, DEFAULT_SVG_PRECISION)?
+            .into_document()
+            .to_string())
+    }
 
-                let mut line_points = Vec::with_capacity(paon_cfg.resolution + 1);
+    /// Export combined pattern to SVG with `config.origin` applied and
+    /// lines simplified to `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; the run is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+        let mut run = Cow::Borrowed(self);
+        if config.simplify_tolerance > 0.0 {
+            simplify_polylines(&mut run.to_mut().segmented_lines, config.simplify_tolerance);
+        }
+        run.svg_writer(None, None, None, LineStroke::Plain, config.svg_precision)?
+            .with_origin(config.origin)
+            .save(filename)
+    }
 
-                for j in 0..=paon_cfg.resolution {
-                    let t_frac = j as f64 / paon_cfg.resolution as f64;
+    /// Export combined pattern to SVG format with a design-review overlay
+    ///
+    /// The overlay is drawn beneath the pattern; rays and reference circles
+    /// are centered on the run's center and labeled just outside the pattern.
+    ///
+    /// # Arguments
+    /// * `filename` - Output SVG file path
+    /// * `overlay` - Grid, ray and reference-circle options
+    pub fn to_svg_with_overlay(
+        &self,
+        filename: &str,
+        overlay: &OverlayOptions,
+    ) -> Result<(), SpirographError> {
+        self.svg_writer(
+            Some(overlay),
+            None,
+            None,
+            LineStroke::Plain,
+            DEFAULT_SVG_PRECISION,
+        )?
+        .save(filename)
+    }
 
-                    let y = -r + diameter * t_frac;
-                    let x_base = (y_vp - y) * tan_a;
-                    let dist = y_vp - y;
+    /// Export combined pattern to SVG with shaded bands between the rings
+    ///
+    /// Only for the concentric ring modes (draperie-style `radius_step`,
+    /// flinqué and bands) with unsegmented passes. Each band between
+    /// adjacent full-depth rings is filled with a light gradient turned by
+    /// the band's lean, and the lines are stroked on top as in `to_svg`.
+    /// Uses the generated lines as they are.
+    ///
+    /// # Arguments
+    /// * `filename` - Output SVG file path
+    /// * `light_angle` - Direction the light comes from (radians)
+    pub fn to_svg_filled(&self, filename: &str, light_angle: f64) -> Result<(), SpirographError> {
+        self.svg_writer(
+            None,
+            None,
+            Some(light_angle),
+            LineStroke::Plain,
+            DEFAULT_SVG_PRECISION,
+        )?
+        .save(filename)
+    }
 
-                    let theta =
-                        2.0 * PI * paon_cfg.wave_frequency * (dist / dist_near).ln() + line_phase;
-                    let offset = paon_cfg.amplitude * paon_wave_fn(theta, nh);
+    /// Export combined pattern to SVG with strokes widened by bit wear
+    ///
+    /// Each cut's stroke width is scaled by its worn width over the bit's
+    /// nominal width, so the lines thicken as the bit dulls and snap back
+    /// after each resharpening. Without a wear model this matches `to_svg`.
+    ///
+    /// # Arguments
+    /// * `filename` - Output SVG file path
+    pub fn to_svg_worn(&self, filename: &str) -> Result<(), SpirographError> {
+        self.svg_writer(None, None, None, LineStroke::Worn, DEFAULT_SVG_PRECISION)?
+            .save(filename)
+    }
 
-                    let x = x_base + offset;
+    /// Export combined pattern to SVG with strokes shaded by cut depth
+    ///
+    /// Each line is split into short segments at the angles its pass
+    /// samples depth, stroked from light gray for shallow cuts to black for
+    /// cuts twice the bit's depth, with widths scaled by depth over the
+    /// bit's depth. Passes without depth modulation stay a single gray.
+    ///
+    /// # Arguments
+    /// * `filename` - Output SVG file path
+    pub fn to_svg_shaded(&self, filename: &str) -> Result<(), SpirographError> {
+        self.svg_writer(
+            None,
+            None,
+            None,
+            LineStroke::DepthShaded,
+            DEFAULT_SVG_PRECISION,
+        )?
+        .save(filename)
+    }
 
-                    if x * x + y * y <= r * r {
-                        line_points.push(Point2D::new(self.center_x + x, self.center_y + y));
-                    }
-                }
+    /// Export combined pattern to SVG with lines colored by `style`
+    ///
+    /// `SvgColorMode::PerPass` cycles its palette by `line_pass_indices()`,
+    /// so overlapping passes can be told apart; `ByDepth` matches
+    /// `to_svg_shaded`.
+    ///
+    /// # Arguments
+    /// * `filename` - Output SVG file path
+    /// * `style` - Line coloring
+    pub fn to_svg_styled(&self, filename: &str, style: &SvgStyle) -> Result<(), SpirographError> {
+        style.validate()?;
+        let stroke = match &style.color_mode {
+            SvgColorMode::Single(color) => LineStroke::Palette(std::slice::from_ref(color)),
+            SvgColorMode::PerPass(palette) => LineStroke::Palette(palette),
+            SvgColorMode::ByDepth => LineStroke::DepthShaded,
+        };
+        self.svg_writer(None, None, None, stroke, DEFAULT_SVG_PRECISION)?
+            .save(filename)
+    }
 
-                if line_points.len() >= 2 {
-                    self.segmented_lines.push(line_points);
-                }
-            }
+    /// Whether the generated lines are whole concentric rings
+    fn has_concentric_rings(&self) -> bool {
+        let dashed = match self.bands {
+            Some(ref bands) => bands.iter().any(|b| b.config.dash_by_phase.is_some()),
+            None => self.base_config.dash_by_phase.is_some(),
+        };
+        if dashed {
+            return false;
+        }
+        if self.concentric_flinque.is_some() {
+            return true;
+        }
+        let ring_mode =
+            self.bands.is_some() || (self.radius_step != 0.0 && self.special_mode_name().is_none());
+        ring_mode && self.segments_per_pass == 1
+    }
 
-            self.generated = true;
-            return;
+    /// Export one SVG per tag into `dir`, named `{basename}_tag{tag}.svg`
+    ///
+    /// Every file shares the viewBox of the full pattern, so the files
+    /// overlay in registration. Returns the written paths in tag order.
+    pub fn to_svg_by_tag(
+        &self,
+        dir: &Path,
+        basename: &str,
+    ) -> Result<Vec<PathBuf>, SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        if self.line_tags.len() != self.segmented_lines.len() {
+            return Err(SpirographError::ExportError(
+                "Passes not tagged. Call tag_passes() first.".to_string(),
+            ));
         }
 
-        // ── Clous de Paris mode: two orthogonal sets of parallel lines ─
-        if let Some(ref cdp_cfg) = self.grid_clous_de_paris {
-            let r = cdp_cfg.radius;
-            let s = cdp_cfg.spacing;
-            let grid_angle = cdp_cfg.angle;
-            let res = cdp_cfg.resolution;
+        let mut written = Vec::new();
+        for tag in self.tags() {
+            let writer = self.svg_writer(
+                None,
+                Some(&|i| self.line_tags[i] == tag),
+                None,
+                LineStroke::Plain,
+                DEFAULT_SVG_PRECISION,
+            )?;
+            let path = dir.join(format!("{}_tag{}.svg", basename, tag));
+            writer.save(&path)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
 
-            for dir in 0..2 {
-                let theta = grid_angle + (dir as f64) * PI / 2.0;
-                let cos_t = theta.cos();
-                let sin_t = theta.sin();
+    /// Number of lines-by-pass groups frames are built from: one past the
+    /// highest pass index, or 0 before generating
+    fn frame_pass_count(&self) -> usize {
+        self.line_passes.iter().max().map_or(0, |&pass| pass + 1)
+    }
 
-                let n_lines = (r / s).ceil() as i32;
+    /// Passes shown in frame `k` of `n_frames`
+    fn frame_passes(&self, k: usize, n_frames: usize) -> usize {
+        (k + 1) * self.frame_pass_count() / n_frames
+    }
 
-                for i in -n_lines..=n_lines {
-                    let offset = (i as f64) * s;
+    /// The pattern built up pass by pass over `n_frames` frames
+    ///
+    /// Frame `k` holds the lines of passes below ⌊(k + 1)·P / n_frames⌋,
+    /// in generation order, where P counts the passes of
+    /// `line_pass_indices()`, so the last frame is the whole pattern. The
+    /// direct-geometry modes (paon, diamant, flinqué, ...) number every
+    /// line as its own pass and so appear line by line. Empty before
+    /// `generate()`.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLatheRun};
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 4).unwrap();
+    /// run.generate();
+    /// let frames = run.frames(4);
+    /// assert_eq!(frames.len(), 4);
+    /// assert_eq!(frames[3], *run.lines());
+    /// ```
+    pub fn frames(&self, n_frames: usize) -> Vec<Vec<Vec<Point2D>>> {
+        if !self.generated {
+            return Vec::new();
+        }
+        (0..n_frames)
+            .map(|k| {
+                let passes = self.frame_passes(k, n_frames);
+                self.segmented_lines
+                    .iter()
+                    .zip(&self.line_passes)
+                    .filter(|(_, &pass)| pass < passes)
+                    .map(|(line, _)| line.clone())
+                    .collect()
+            })
+            .collect()
+    }
 
-                    let disc = r * r - offset * offset;
-                    if disc < 0.0 {
-                        continue;
+    /// Export the frames of `frames(n_frames)` into `dir` as
+    /// `frame_0000.svg`, `frame_0001.svg`, ...
+    ///
+    /// Every frame shares the viewBox of the full pattern, so the files
+    /// play back in registration. Returns the written paths in frame order.
+    pub fn to_svg_frames(
+        &self,
+        dir: &Path,
+        n_frames: usize,
+    ) -> Result<Vec<PathBuf>, SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+
+        let mut written = Vec::with_capacity(n_frames);
+        for k in 0..n_frames {
+            let passes = self.frame_passes(k, n_frames);
+            let writer = self.svg_writer(
+                None,
+                Some(&|i| self.line_passes[i] < passes),
+                None,
+                LineStroke::Plain,
+                DEFAULT_SVG_PRECISION,
+            )?;
+            let path = dir.join(format!("frame_{:04}.svg", k));
+            writer.save(&path)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    /// Build the SVG writer, optionally with an overlay beneath the pattern
+    ///
+    /// With `keep`, only the lines whose index it accepts are drawn; bounds
+    /// always cover the whole pattern. With `light_angle`, shaded bands
+    /// between the rings are drawn beneath the lines. `stroke` picks plain,
+    /// palette-colored, worn or depth-shaded strokes. Coordinates are
+    /// rounded to `precision` decimals.
+    fn svg_writer(
+        &self,
+        overlay: Option<&OverlayOptions>,
+        keep: Option<&dyn Fn(usize) -> bool>,
+        light_angle: Option<f64>,
+        stroke: LineStroke<'_>,
+        precision: usize,
+    ) -> Result<SvgWriter, SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+
+        // Use segmented lines instead of complete passes
+        let all_lines = &self.segmented_lines;
+
+        let (min_x, min_y, max_x, max_y) = drawable_bounds(all_lines.iter().map(Vec::as_slice))?;
+
+        let margin = SVG_MARGIN;
+        let mut writer = SvgWriter::new((min_x, min_y, max_x, max_y), margin)
+            .with_precision(precision)
+            .with_max_points_per_path(self.max_points_per_path);
+        if let Some(metadata) = &self.metadata {
+            writer = writer.map_document(|document| metadata.apply_svg(document));
+        }
+
+        if let Some(overlay) = overlay {
+            let extent = all_lines
+                .iter()
+                .flatten()
+                .map(|p| (p.x - self.center_x).hypot(p.y - self.center_y))
+                .fold(0.0, f64::max);
+            writer.add(overlay.to_group(
+                (
+                    min_x - margin,
+                    min_y - margin,
+                    max_x + margin,
+                    max_y + margin,
+                ),
+                (self.center_x, self.center_y),
+                extent,
+            ));
+        }
+
+        if let Some(light_angle) = light_angle {
+            if !self.has_concentric_rings() {
+                return Err(SpirographError::ExportError(
+                    "Shaded export needs whole concentric rings (draperie, flinque or bands \
+                     mode with one segment per pass)"
+                        .to_string(),
+                ));
+            }
+            // Depth-pass repeats share their XY; shade between full-depth rings
+            let rings: Vec<&[Point2D]> = all_lines
+                .iter()
+                .zip(&self.line_depths)
+                .filter(|(_, &depth)| depth == self.cutting_bit.depth)
+                .map(|(line, _)| line.as_slice())
+                .collect();
+            let (defs, bands) = shaded_bands(
+                &writer,
+                &rings,
+                Point2D::new(self.center_x, self.center_y),
+                light_angle,
+            )?;
+            writer.add(defs);
+            writer.add(bands);
+        }
+
+        let mut stroke_widths = vec![SVG_STROKE_WIDTH; all_lines.len()];
+        if stroke == LineStroke::Worn && self.cutting_bit.width > 0.0 {
+            for wear in &self.pass_wear {
+                stroke_widths[wear.lines.clone()]
+                    .fill(SVG_STROKE_WIDTH * wear.width / self.cutting_bit.width);
+            }
+        }
+
+        // Add each segmented line
+        for (i, line) in all_lines.iter().enumerate() {
+            if polyline_is_degenerate(line) || keep.is_some_and(|keep| !keep(i)) {
+                continue;
+            }
+
+            let center = Point2D::new(self.center_x, self.center_y);
+            let opacity = self
+                .opacity_envelope
+                .as_ref()
+                .map(|e| e.eval_polyline(line, center));
+            if stroke == LineStroke::DepthShaded {
+                let config = self
+                    .passes
+                    .get(self.line_passes[i])
+                    .map_or(&self.base_config, |pass| &pass.config);
+                let bit_depth = self.cutting_bit.depth;
+                let depths = modulated_depths(line, center, config, self.line_depths[i]);
+                let shading: Vec<f64> = depths
+                    .iter()
+                    .map(|&depth| depth_shading(depth, bit_depth))
+                    .collect();
+                let widths: Vec<f64> = depths
+                    .iter()
+                    .map(|&depth| depth_stroke_width(SVG_STROKE_WIDTH, depth, bit_depth))
+                    .collect();
+                for path in depth_shaded_paths(&writer, line, &shading, &widths) {
+                    writer.add(with_opacity(path, opacity));
+                }
+                continue;
+            }
+            writer.add(writer.polyline_node(line, false, |path| {
+                let color = match stroke {
+                    LineStroke::Palette(palette) => {
+                        palette[self.line_passes[i] % palette.len()].as_str()
                     }
+                    _ => "black",
+                };
+                let path = path
+                    .set("fill", "none")
+                    .set("stroke", color)
+                    .set("stroke-width", stroke_widths[i]);
+                with_opacity(path, opacity)
+            }));
+        }
+
+        Ok(writer)
+    }
+
+    /// Rasterize the SVG export to an RGBA preview
+    ///
+    /// Draws the same segmented lines as `to_svg`, in black with its stroke
+    /// width and opacity envelope, over the same view fitted into
+    /// `width_px × height_px` on a white background.
+    pub fn render_raster(
+        &self,
+        width_px: u32,
+        height_px: u32,
+    ) -> Result<RgbaImage, SpirographError> {
+        if !self.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        let lines = &self.segmented_lines;
+        let view_box = svg_view_box(lines.iter().map(Vec::as_slice))?;
+        let center = Point2D::new(self.center_x, self.center_y);
+        let strokes = lines
+            .iter()
+            .filter(|line| !polyline_is_degenerate(line))
+            .map(|line| PreviewStroke {
+                points: Cow::Borrowed(line.as_slice()),
+                closed: false,
+                width: SVG_STROKE_WIDTH,
+                color: [0, 0, 0],
+                opacity: self
+                    .opacity_envelope
+                    .as_ref()
+                    .map_or(1.0, |e| e.eval_polyline(line, center)),
+            });
+        render_preview(strokes, view_box, width_px, height_px)
+    }
+
+    /// Write the [`render_raster`](Self::render_raster) preview to a PNG file
+    pub fn to_png(
+        &self,
+        filename: &str,
+        width_px: u32,
+        height_px: u32,
+    ) -> Result<(), SpirographError> {
+        self.render_raster(width_px, height_px)?.write_png(filename)
+    }
+
+    /// Render this run's lines over `other`'s and report what changed
+    ///
+    /// Lines matching within `tolerance` are drawn light gray, lines that
+    /// moved by up to 10× `tolerance` orange, lines only in `self` blue
+    /// (added) and lines only in `other` red (removed). Runs with the same
+    /// structure are matched line by line; otherwise by nearest endpoints.
+    pub fn diff_svg(
+        &self,
+        other: &Self,
+        filename: &str,
+        tolerance: f64,
+    ) -> Result<DiffSummary, SpirographError> {
+        if !self.generated || !other.generated {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        let current: Vec<&[Point2D]> = self.segmented_lines.iter().map(Vec::as_slice).collect();
+        let baseline: Vec<&[Point2D]> = other.segmented_lines.iter().map(Vec::as_slice).collect();
+        write_diff_svg(&current, &baseline, filename, tolerance)
+    }
 
-                    let t_half = disc.sqrt();
-                    let ox = self.center_x + offset * (-sin_t);
-                    let oy = self.center_y + offset * cos_t;
+    /// Get the number of passes
+    pub fn num_passes(&self) -> usize {
+        self.num_passes
+    }
 
-                    let mut line_points = Vec::with_capacity(res + 1);
+    /// Get reference to individual passes
+    pub fn passes(&self) -> &[RoseEngineLathe] {
+        &self.passes
+    }
 
-                    for j in 0..=res {
-                        let frac = j as f64 / res as f64;
-                        let t = -t_half + 2.0 * t_half * frac;
+    /// Get reference to the segmented lines (the generated pattern curves)
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.segmented_lines
+    }
 
-                        let x = ox + t * cos_t;
-                        let y = oy + t * sin_t;
-                        line_points.push(Point2D::new(x, y));
-                    }
+    /// Hash of the generated lines, for skipping redundant redraws
+    ///
+    /// See `geometry_hash`.
+    pub fn geometry_hash(&self) -> u64 {
+        geometry_hash(self.segmented_lines.iter().map(Vec::as_slice))
+    }
 
-                    if line_points.len() >= 2 {
-                        self.segmented_lines.push(line_points);
-                    }
+    /// Whether changing the run field `field` can change the generated lines
+    ///
+    /// Lets an editor skip regeneration for edits that are provably inert
+    /// with the current settings:
+    ///
+    /// * The direct-geometry modes (paon, diamant, flinque, ...) only read
+    ///   the center from the run; every other pass field is inert.
+    /// * Banded runs take radii and phases from their bands, so
+    ///   `radius_step` and the phase envelope fields are inert.
+    /// * In phase-rotation mode (`radius_step == 0`) the phase envelope
+    ///   fields are inert.
+    /// * `phase_oscillations`, `circular_phase` and `phase_exponent` do
+    ///   nothing when `phase_shift` is 0, and `phase_exponent` only matters
+    ///   when `circular_phase` is 0.
+    /// * A single concentric ring sits at the base radius with the phase
+    ///   envelope at its start, so `phase_oscillations` is inert, and so is
+    ///   `radius_step` when that start is zero.
+    /// * `max_points_per_path` only affects how SVG paths are split.
+    ///
+    /// Unknown field names, including `base_config` fields, report `true`.
+    pub fn affects_geometry(&self, field: &str) -> bool {
+        let envelope = matches!(
+            field,
+            "phase_shift" | "phase_oscillations" | "circular_phase" | "phase_exponent"
+        );
+        match field {
+            "max_points_per_path" => false,
+            "center_x" | "center_y" => true,
+            "num_passes" | "segments_per_pass" | "pass_overrides" => {
+                self.special_mode_name().is_none()
+            }
+            _ if envelope || field == "radius_step" => {
+                if self.special_mode_name().is_some() || self.bands.is_some() {
+                    return false;
+                }
+                if self.radius_step == 0.0 && envelope {
+                    return false;
+                }
+                // Phase offset of a lone ring, at the start of the envelope
+                let lone_ring = self.num_passes == 1;
+                let start_offset = self.phase_shift * self.phase_shape_fn(0.0);
+                match field {
+                    "radius_step" => !lone_ring || start_offset != 0.0,
+                    "phase_oscillations" => !lone_ring && self.phase_shift != 0.0,
+                    "circular_phase" => self.phase_shift != 0.0,
+                    "phase_exponent" => self.phase_shift != 0.0 && self.circular_phase <= 0.0,
+                    _ => true,
                 }
             }
+            _ => true,
+        }
+    }
+}
 
-            self.generated = true;
+impl Progressive for RoseEngineLatheRun {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
             return;
         }
 
-        // ── Cube mode: parallel zigzag lines with grouping ──────────────
-        if let Some(ref cube_cfg) = self.grid_cube {
-            let r = cube_cfg.radius;
-            let s = cube_cfg.spacing;
-            let cuts = cube_cfg.cuts_per_group;
-            let gap = cube_cfg.gap_per_group;
-            let base_angle = cube_cfg.angle;
-
-            let amplitude = if cube_cfg.amplitude > 0.0 {
-                cube_cfg.amplitude
-            } else {
-                ((gap as f64) + 1.0) * s / 2.0
-            };
-            let leg_rad = cube_cfg.leg_angle.to_radians();
-            let period = 4.0 * amplitude / leg_rad.tan();
-            let half_period = period / 2.0;
-            let group_cycle = (cuts as f64 + gap as f64) * s;
+        let mut coarse = self.scaled(fraction);
+        coarse.generate();
+        self.passes = coarse.passes;
+        self.segmented_lines = coarse.segmented_lines;
+        self.line_bands = coarse.line_bands;
+        self.line_passes = coarse.line_passes;
+        self.line_tags = coarse.line_tags;
+        self.line_depths = coarse.line_depths;
+        self.pass_wear = coarse.pass_wear;
+        self.generated = coarse.generated;
+    }
+}
 
-            let cos_a = base_angle.cos();
-            let sin_a = base_angle.sin();
-            let r_sq = r * r;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rose_engine::SurfaceMapping;
+
+    fn three_bands() -> Vec<BandSpec> {
+        let mut wave = RoseEngineConfig::wave(0.0, 8.0, 2.0);
+        wave.resolution = PointsTotal(720);
+        vec![
+            BandSpec::new(
+                4.0,
+                9.0,
+                4,
+                RoseEngineConfig::classic_multi_lobe(0.0, 12, 1.5),
+            ),
+            BandSpec::new(9.0, 15.0, 6, wave),
+            BandSpec::new(15.0, 20.0, 3, RoseEngineConfig::sunburst(0.0, 24, 0.2)),
+        ]
+    }
 
-            let n_groups = (r / group_cycle).ceil() as i32 + 2;
+    #[test]
+    fn test_banded_rings_stay_inside_bands() {
+        let bands = three_bands();
+        let mut run =
+            RoseEngineLatheRun::new_banded(bands.clone(), CuttingBit::default(), 0.0, 0.0).unwrap();
+        run.generate();
+
+        let total: usize = bands.iter().map(|b| b.rings).sum();
+        assert_eq!(run.lines().len(), total);
+        assert_eq!(run.line_band_indices().len(), total);
+
+        for (line, &b) in run.lines().iter().zip(run.line_band_indices()) {
+            let band = &bands[b];
+            for p in line {
+                let r = (p.x * p.x + p.y * p.y).sqrt();
+                assert!(
+                    r > band.r_inner && r < band.r_outer,
+                    "radius {} escapes band {} ({}..{})",
+                    r,
+                    b,
+                    band.r_inner,
+                    band.r_outer
+                );
+            }
+        }
 
-            for g in -n_groups..=n_groups {
-                let group_base = (g as f64) * group_cycle;
-                let phase = if g.rem_euclid(2) == 0 { 0.0 } else { 0.5 };
+        // Bands are cut innermost first
+        let expected: Vec<usize> = bands
+            .iter()
+            .enumerate()
+            .flat_map(|(b, band)| std::iter::repeat_n(b, band.rings))
+            .collect();
+        assert_eq!(run.line_band_indices(), expected.as_slice());
+    }
 
-                for i in 0..(cuts as i32) {
-                    let baseline = group_base + (i as f64) * s;
+    #[test]
+    fn test_banded_amplitude_clamped_only_when_needed() {
+        let bands = three_bands();
+        // 1.5 mm of 12-lobe motion cannot fit 4 rings in a 5 mm band
+        assert!(bands[0].amplitude_scale() < 1.0);
+        // 0.2 mm of sunburst fits comfortably in 5/3 mm ring spacing
+        assert_eq!(bands[2].amplitude_scale(), 1.0);
+
+        let run = RoseEngineLatheRun::new_banded(bands, CuttingBit::default(), 0.0, 0.0).unwrap();
+        let last = run.pass_config(run.num_passes - 1);
+        assert_eq!(last.amplitude, 0.2);
+        assert!((last.base_radius - (15.0 + 2.5 * 5.0 / 3.0)).abs() < 1e-12);
+    }
 
-                    if baseline - amplitude > r || baseline + amplitude < -r {
-                        continue;
-                    }
+    #[test]
+    fn test_banded_validation() {
+        let bit = CuttingBit::default();
+        let config = RoseEngineConfig::new(0.0, 0.5);
+
+        assert!(RoseEngineLatheRun::new_banded(Vec::new(), bit.clone(), 0.0, 0.0).is_err());
+
+        let overlapping = vec![
+            BandSpec::new(5.0, 10.0, 2, config.clone()),
+            BandSpec::new(9.0, 12.0, 2, config.clone()),
+        ];
+        assert!(RoseEngineLatheRun::new_banded(overlapping, bit.clone(), 0.0, 0.0).is_err());
+
+        let unordered = vec![
+            BandSpec::new(10.0, 12.0, 2, config.clone()),
+            BandSpec::new(5.0, 10.0, 2, config.clone()),
+        ];
+        assert!(RoseEngineLatheRun::new_banded(unordered, bit.clone(), 0.0, 0.0).is_err());
+
+        let empty_band = vec![BandSpec::new(5.0, 5.0, 2, config.clone())];
+        assert!(RoseEngineLatheRun::new_banded(empty_band, bit.clone(), 0.0, 0.0).is_err());
+
+        let no_rings = vec![BandSpec::new(5.0, 8.0, 0, config.clone())];
+        assert!(RoseEngineLatheRun::new_banded(no_rings, bit.clone(), 0.0, 0.0).is_err());
+
+        // Touching bands are allowed
+        let touching = vec![
+            BandSpec::new(5.0, 10.0, 2, config.clone()),
+            BandSpec::new(10.0, 12.0, 2, config),
+        ];
+        assert!(RoseEngineLatheRun::new_banded(touching, bit, 0.0, 0.0).is_ok());
+    }
 
-                    let x_extent = r + period;
-                    let phase_offset = phase * period;
+    #[test]
+    fn test_dxf_export_round_trips_segmented_lines() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 6).unwrap();
+        let path = std::env::temp_dir().join("test_lathe_run.dxf");
+        let path = path.to_str().unwrap();
+        assert!(run.to_dxf(path).is_err());
+
+        run.generate();
+        run.to_dxf(path).unwrap();
+        let dxf = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        assert!(dxf.contains("$INSUNITS\n70\n4\n"));
+        assert!(!dxf.contains("\n70\n1\n"));
+        let expected: Vec<Vec<Point2D>> = run
+            .lines()
+            .iter()
+            .filter(|line| line.len() >= 2)
+            .cloned()
+            .collect();
+        assert_eq!(dxf.matches("\nLWPOLYLINE\n").count(), expected.len());
+        assert_eq!(crate::dxf::read_dxf_polylines(&dxf), expected);
+    }
 
-                    let k_start = ((-x_extent + phase_offset) / half_period).floor() as i32;
-                    let k_end = ((x_extent + phase_offset) / half_period).ceil() as i32;
+    #[test]
+    fn test_hpgl_export_pens_by_pass() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 3).unwrap();
+        let path = std::env::temp_dir().join("test_lathe_run.hpgl");
+        let path = path.to_str().unwrap();
+        assert!(run.to_hpgl(path, 1.0).is_err());
+
+        run.generate();
+        run.to_hpgl_with_pens(path, 1.0, &[3, 7]).unwrap();
+        let hpgl = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        // Passes 1 and 2 share the last pen, so it is selected once
+        assert_eq!(hpgl.matches("SP3;").count(), 1);
+        assert_eq!(hpgl.matches("SP7;").count(), 1);
+        assert!(hpgl.find("SP3;").unwrap() < hpgl.find("SP7;").unwrap());
+        assert_eq!(hpgl.matches("PD").count(), run.lines().len());
+        assert!(hpgl.ends_with("PU;SP0;\n"));
+        let first = run.lines()[0][0];
+        assert!(hpgl.contains(&format!(
+            "PU{},{};",
+            (first.x * 40.0).round(),
+            (first.y * 40.0).round()
+        )));
+    }
 
-                    let cap = (k_end - k_start + 1).max(0) as usize;
-                    let mut vertices: Vec<(f64, f64)> = Vec::with_capacity(cap);
-                    for k in k_start..=k_end {
-                        let x = (k as f64) * half_period - phase_offset;
-                        let sign = if k.rem_euclid(2) == 0 { 1.0 } else { -1.0 };
-                        let y = baseline + amplitude * sign;
-                        vertices.push((x, y));
-                    }
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_generation_matches_serial() {
+        let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        config.resolution = PointsTotal(1500);
+        let mut serial = RoseEngineLatheRun::new(config, CuttingBit::default(), 96).unwrap();
+        serial.set_segment_gaps(0.5, 0.25).unwrap();
+        let mut parallel = serial.clone();
+        serial.generate();
+        parallel.generate_parallel();
+        assert_eq!(parallel.lines().len(), 96 * 24);
+        assert_eq!(parallel.lines(), serial.lines());
+        assert_eq!(parallel.line_passes, serial.line_passes);
+        assert_eq!(parallel.passes.len(), serial.passes.len());
+
+        // Banded runs keep each line's band
+        let mut serial =
+            RoseEngineLatheRun::new_banded(three_bands(), CuttingBit::default(), 0.0, 0.0).unwrap();
+        let mut parallel = serial.clone();
+        serial.generate();
+        parallel.generate_parallel();
+        assert_eq!(parallel.lines(), serial.lines());
+        assert_eq!(parallel.line_bands, serial.line_bands);
+    }
 
-                    // Clip to circle and collect segments
-                    let mut current_segment: Vec<Point2D> = Vec::new();
+    #[test]
+    fn test_stl_and_step_cover_every_segmented_line() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let export = ExportConfig::default();
+        let path = std::env::temp_dir().join("test_lathe_run_grooves.stl");
+        let path = path.to_str().unwrap();
+
+        // Triangles beyond the shared base slab: each line is a closed
+        // V-groove of three faces per segment, capped at both ends
+        let groove_triangles = |segments_per_pass: usize| {
+            let mut run = RoseEngineLatheRun::new_with_segments(
+                config.clone(),
+                CuttingBit::default(),
+                4,
+                segments_per_pass,
+                0.0,
+                0.0,
+            )
+            .unwrap();
+            assert!(run.to_stl(path, &export).is_err());
+            run.generate();
+            run.to_stl(path, &export).unwrap();
+            let mut file = std::fs::File::open(path).unwrap();
+            let mesh = stl_io::read_stl(&mut file).unwrap();
+            let _ = std::fs::remove_file(path);
+
+            let segments: usize = run.lines().iter().map(|line| line.len() - 1).sum();
+            let caps = 2 * run.lines().len();
+            (run.lines().len(), mesh.faces.len() - 6 * segments - caps)
+        };
+        let (lines_8, slab_8) = groove_triangles(8);
+        let (lines_16, slab_16) = groove_triangles(16);
+        assert_eq!((lines_8, lines_16), (4 * 8, 4 * 16));
+        assert_eq!(slab_8, slab_16);
+
+        let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 3).unwrap();
+        run.generate();
+        let step = std::env::temp_dir().join("test_lathe_run.stp");
+        let step = step.to_str().unwrap();
+        run.to_step(step, &export).unwrap();
+        let content = std::fs::read_to_string(step).unwrap();
+        let _ = std::fs::remove_file(step);
+        let points: usize = run.lines().iter().map(Vec::len).sum();
+        assert_eq!(content.matches("CARTESIAN_POINT").count(), points);
+    }
 
-                    for v_idx in 0..vertices.len() {
-                        let (x, y) = vertices[v_idx];
-                        let inside = x * x + y * y <= r_sq;
+    #[test]
+    fn test_render_raster_preview() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 3).unwrap();
+        assert!(run.render_raster(64, 64).is_err());
+        run.generate();
+
+        let image = run.render_raster(128, 96).unwrap();
+        assert_eq!((image.width, image.height), (128, 96));
+        assert!(image.data().chunks(4).any(|px| px[0] < 128));
+        // The margin around the lines stays white
+        assert_eq!(image.pixel(64, 0), Some([255; 4]));
+
+        let path = std::env::temp_dir().join("test_lathe_run_preview.png");
+        run.to_png(path.to_str().unwrap(), 128, 96).unwrap();
+        let png = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(png, image.to_png_bytes().unwrap());
+    }
 
-                        if v_idx > 0 {
-                            let (px, py) = vertices[v_idx - 1];
-                            let prev_inside = px * px + py * py <= r_sq;
+    #[test]
+    fn test_segment_draw_ratio_and_phase_offset() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut run =
+            RoseEngineLatheRun::new_with_segments(config, CuttingBit::default(), 6, 8, 0.0, 0.0)
+                .unwrap();
+        assert!(run.set_segment_gaps(0.0, 0.0).is_err());
+        assert!(run.set_segment_gaps(1.5, 0.0).is_err());
+        assert!(run.set_segment_gaps(0.5, f64::NAN).is_err());
+        run.set_segment_gaps(0.25, 0.5).unwrap();
+        run.generate();
+        assert_eq!(run.lines().len(), 6 * 8);
+
+        // A closed ring of 161 points: 20 per cycle, 5 of them drawn
+        let mut ring: Vec<Point2D> = (0..160)
+            .map(|i| {
+                let angle = 2.0 * PI * i as f64 / 160.0;
+                Point2D::new(angle.cos(), angle.sin())
+            })
+            .collect();
+        ring.push(ring[0]);
+        let starts = |run: &mut RoseEngineLatheRun, pass_index: usize| {
+            run.segmented_lines.clear();
+            run.segment_path(&ring, pass_index);
+            assert_eq!(run.segmented_lines.len(), 8);
+            assert!(run.segmented_lines.iter().all(|line| line.len() == 5));
+            run.segmented_lines[0][0]
+        };
+        assert_eq!(starts(&mut run, 0), ring[0]);
+        // Pass 1 starts half a cycle along
+        assert_eq!(starts(&mut run, 1), ring[10]);
+
+        // Near a whole cycle, the last segment wraps past the repeated point
+        run.set_segment_gaps(0.25, 0.9).unwrap();
+        assert_eq!(starts(&mut run, 1), ring[18]);
+        assert_eq!(
+            run.segmented_lines[7],
+            vec![ring[158], ring[159], ring[160], ring[1], ring[2]]
+        );
+    }
 
-                            if prev_inside && !inside {
-                                if let Some(t) = seg_circle_t(px, py, x, y, r) {
-                                    let ix = px + t * (x - px);
-                                    let iy = py + t * (y - py);
-                                    let rx = self.center_x + ix * cos_a - iy * sin_a;
-                                    let ry = self.center_y + ix * sin_a + iy * cos_a;
-                                    current_segment.push(Point2D::new(rx, ry));
-                                }
-                                if current_segment.len() >= 2 {
-                                    self.segmented_lines
-                                        .push(std::mem::take(&mut current_segment));
-                                }
-                                current_segment.clear();
-                            } else if !prev_inside && inside {
-                                if let Some(t) = seg_circle_t(px, py, x, y, r) {
-                                    let ix = px + t * (x - px);
-                                    let iy = py + t * (y - py);
-                                    let rx = self.center_x + ix * cos_a - iy * sin_a;
-                                    let ry = self.center_y + ix * sin_a + iy * cos_a;
-                                    current_segment.push(Point2D::new(rx, ry));
-                                }
-                            } else if !prev_inside && !inside {
-                                let both = seg_circle_t_both(px, py, x, y, r);
-                                if both.len() == 2 {
-                                    let t1 = both[0];
-                                    let t2 = both[1];
-                                    let ix1 = px + t1 * (x - px);
-                                    let iy1 = py + t1 * (y - py);
-                                    let ix2 = px + t2 * (x - px);
-                                    let iy2 = py + t2 * (y - py);
-                                    let rx1 = self.center_x + ix1 * cos_a - iy1 * sin_a;
-                                    let ry1 = self.center_y + ix1 * sin_a + iy1 * cos_a;
-                                    let rx2 = self.center_x + ix2 * cos_a - iy2 * sin_a;
-                                    let ry2 = self.center_y + ix2 * sin_a + iy2 * cos_a;
-                                    self.segmented_lines
-                                        .push(vec![Point2D::new(rx1, ry1), Point2D::new(rx2, ry2)]);
-                                }
-                            }
-                        }
+    #[test]
+    fn test_gcode_cuts_every_segment_at_its_depth() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let bit = CuttingBit::flat(0.5, 0.3);
+        let run = RoseEngineLatheRun::new(config, bit, 4).unwrap();
+        let mut run = run
+            .expand_depth_passes(0.15, DepthPassOrder::DepthFirst)
+            .unwrap();
+        let path = std::env::temp_dir().join("test_lathe_run.nc");
+        let path = path.to_str().unwrap();
+        let params = GcodeParams::default().with_depth_per_pass(1.0);
+        assert!(run.to_gcode(path, &params).is_err());
+
+        run.generate();
+        run.to_gcode(path, &params).unwrap();
+        let gcode = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let drawable: Vec<(&Vec<Point2D>, f64)> = run
+            .lines()
+            .iter()
+            .zip(run.line_depths().iter().copied())
+            .filter(|(line, _)| !polyline_is_degenerate(line))
+            .collect();
+        let cuts = gcode.lines().filter(|l| l.starts_with("G1 X")).count();
+        assert_eq!(
+            cuts,
+            drawable
+                .iter()
+                .map(|(line, _)| line.len() - 1)
+                .sum::<usize>()
+        );
+        assert!(gcode.contains(" Z-0.15 F300\n") && gcode.contains(" Z-0.3 F300\n"));
+        // Every segment starts with a rapid from the safe height
+        assert_eq!(gcode.matches("G0 X").count(), drawable.len());
+    }
 
-                        if inside {
-                            let rx = self.center_x + x * cos_a - y * sin_a;
-                            let ry = self.center_y + x * sin_a + y * cos_a;
-                            current_segment.push(Point2D::new(rx, ry));
-                        }
-                    }
+    #[test]
+    fn test_svg_by_tag_splits_even_and_odd_passes() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 12).unwrap();
+        run.generate();
+        assert_eq!(run.line_pass_indices().len(), run.lines().len());
+        // 24 segments per pass
+        assert_eq!(run.line_pass_indices()[24], 1);
+
+        let dir = std::env::temp_dir().join("test_lathe_run_by_tag");
+        let _ = std::fs::create_dir_all(&dir);
+        assert!(run.to_svg_by_tag(&dir, "dial").is_err());
+
+        run.tag_passes(|pass| (pass % 2) as u32);
+        assert_eq!(run.tags(), vec![0, 1]);
+        let paths = run.to_svg_by_tag(&dir, "dial").unwrap();
+        assert_eq!(paths.len(), 2);
+        assert!(paths[1].ends_with("dial_tag1.svg"));
+
+        let files: Vec<String> = paths
+            .iter()
+            .map(|p| std::fs::read_to_string(p).unwrap())
+            .collect();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let path_count = |svg: &str| svg.matches("<path").count();
+        assert_eq!(
+            path_count(&files[0]) + path_count(&files[1]),
+            run.lines().len()
+        );
+        assert_eq!(path_count(&files[0]), run.lines_for_tag(0).len());
+
+        let view_box = |svg: &str| {
+            let start = svg.find("viewBox=\"").unwrap();
+            let end = svg[start + 9..].find('"').unwrap();
+            svg[start..start + 9 + end].to_string()
+        };
+        assert_eq!(view_box(&files[0]), view_box(&files[1]));
 
-                    if current_segment.len() >= 2 {
-                        self.segmented_lines.push(current_segment);
-                    }
-                }
-            }
+        // Regenerating clears the tags
+        run.generate();
+        assert!(run.line_tags().is_empty());
+    }
 
-            self.generated = true;
-            return;
+    #[test]
+    fn test_frames_accumulate_pass_by_pass() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 6).unwrap();
+        assert!(run.frames(6).is_empty());
+        run.generate();
+
+        let frames = run.frames(6);
+        assert_eq!(frames.len(), 6);
+        for pair in frames.windows(2) {
+            assert!(pair[0].len() < pair[1].len());
+            assert_eq!(pair[0][..], pair[1][..pair[0].len()]);
         }
+        assert_eq!(frames[5], *run.lines());
+        // Fewer frames than passes still end on the whole pattern
+        assert_eq!(run.frames(4)[3], *run.lines());
+
+        // Paon numbers each line as its own pass
+        let mut run = RoseEngineLatheRun::new_paon(
+            24,
+            15.0,
+            0.5,
+            10.0,
+            2.0,
+            200,
+            3,
+            PI / 2.0,
+            0.3,
+            PaonOrigin::Bottom,
+            0.0,
+            0.0,
+            0.0,
+        )
+        .unwrap();
+        run.generate();
+        let frames = run.frames(run.lines().len());
+        assert!(frames.iter().enumerate().all(|(k, f)| f.len() == k + 1));
+
+        let dir = std::env::temp_dir().join("test_lathe_run_frames");
+        let _ = std::fs::create_dir_all(&dir);
+        let paths = run.to_svg_frames(&dir, 3).unwrap();
+        let path_counts: Vec<usize> = paths
+            .iter()
+            .map(|p| std::fs::read_to_string(p).unwrap().matches("<path").count())
+            .collect();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(paths[2].ends_with("frame_0002.svg"));
+        assert!(path_counts[0] < path_counts[1] && path_counts[1] < path_counts[2]);
+    }
 
-        let rotation_step = 2.0 * PI / (self.num_passes as f64);
+    #[test]
+    fn test_svg_styled_colors_each_pass() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 3).unwrap();
+        run.generate();
+        let segments = run.segments_with_pass();
+        assert_eq!(segments.len(), run.lines().len());
+        assert_eq!(segments.last().unwrap().0, 2);
+
+        let path = std::env::temp_dir().join("test_lathe_run_styled.svg");
+        let path = path.to_str().unwrap();
+        let style = SvgStyle::per_pass(&["#ff0000", "#00ff00", "#0000ff"]);
+        run.to_svg_styled(path, &style).unwrap();
+        let svg = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let mut strokes: Vec<&str> = svg
+            .split("stroke=\"")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect();
+        strokes.sort_unstable();
+        strokes.dedup();
+        assert_eq!(strokes, vec!["#0000ff", "#00ff00", "#ff0000"]);
+
+        let empty = SvgStyle {
+            color_mode: SvgColorMode::PerPass(Vec::new()),
+        };
+        assert!(run.to_svg_styled(path, &empty).is_err());
+    }
 
-        for i in 0..self.num_passes {
-            let mut pass_config = self.base_config.clone();
-
-            if self.radius_step != 0.0 {
-                // Concentric ring mode: vary base_radius and optionally oscillate phase.
-                // Rings are centred around the original base_radius.
-                let offset = (i as f64) - ((self.num_passes - 1) as f64) / 2.0;
-                pass_config.base_radius = self.base_config.base_radius + offset * self.radius_step;
-                // Sinusoidal phase oscillation: peaks sway back and forth across
-                // the ring stack, creating the classic draperie fold effect.
-                // Uses the configurable phase shape function (dome or sin^e).
-                let phase_t =
-                    2.0 * PI * self.phase_oscillations * (i as f64) / (self.num_passes as f64);
-                pass_config.phase =
-                    self.base_config.phase + self.phase_shift * self.phase_shape_fn(phase_t);
-            } else {
-                // Phase-rotation mode (default): rotate the pattern for each pass.
-                let rotation = (i as f64) * rotation_step;
-                pass_config.phase = self.base_config.phase + rotation;
+    #[test]
+    fn test_diff_svg_reports_moved_and_added_rings() {
+        let draperie = |phase_shift: f64| {
+            let mut run = RoseEngineLatheRun::new_draperie(
+                10,
+                20.0,
+                0.5,
+                12.0,
+                phase_shift,
+                1.0,
+                720,
+                1,
+                1,
+                0.0,
+                0.0,
+                0.0,
+            )
+            .unwrap();
+            run.generate();
+            run
+        };
+        let file = std::env::temp_dir().join("test_lathe_run_diff.svg");
+        let file = file.to_str().unwrap();
+
+        // A phase tweak nudges every ring without changing the structure
+        let base = draperie(0.1);
+        let summary = draperie(0.12).diff_svg(&base, file, 0.02).unwrap();
+        assert_eq!(
+            summary,
+            DiffSummary {
+                unchanged: 0,
+                moved: 10,
+                added: 0,
+                removed: 0,
+            }
+        );
+        let svg = std::fs::read_to_string(file).unwrap();
+        assert_eq!(svg.matches("#ff8c00").count(), 10);
+
+        // Two more passes on the same ring pitch add the two outermost rings
+        let rings = |num_passes: usize| {
+            let mut config = RoseEngineConfig::new(20.0, 0.2);
+            config.rosette = RosettePattern::Draperie {
+                frequency: 12.0,
+                wave_exponent: 1,
+            };
+            let mut run = RoseEngineLatheRun::new_with_segments(
+                config,
+                CuttingBit::default(),
+                num_passes,
+                1,
+                0.0,
+                0.0,
+            )
+            .unwrap();
+            run.radius_step = 0.5;
+            run.generate();
+            run
+        };
+        let summary = rings(12).diff_svg(&rings(10), file, 0.02).unwrap();
+        assert_eq!(
+            summary,
+            DiffSummary {
+                unchanged: 10,
+                moved: 0,
+                added: 2,
+                removed: 0,
             }
+        );
+        let _ = std::fs::remove_file(file);
 
-            // Create and generate the lathe for this pass
-            if let Ok(mut lathe) = RoseEngineLathe::new_with_center(
-                pass_config,
-                self.cutting_bit.clone(),
-                self.center_x,
-                self.center_y,
-            ) {
-                lathe.generate();
+        assert!(base.diff_svg(&base, file, 0.0).is_err());
+    }
 
-                // Get the complete circular path from this pass
-                let rendered = lathe.rendered_output();
-                if !rendered.lines.is_empty() && !rendered.lines[0].is_empty() {
-                    let complete_path = &rendered.lines[0];
+    #[test]
+    fn test_svg_filled_shades_concentric_modes_only() {
+        let file = std::env::temp_dir().join("test_lathe_run_filled.svg");
+        let file = file.to_str().unwrap();
+
+        let mut draperie = RoseEngineLatheRun::new_draperie(
+            10, 20.0, 0.5, 12.0, 0.1, 1.0, 720, 1, 1, 0.0, 0.0, 0.0,
+        )
+        .unwrap();
+        assert!(draperie.to_svg_filled(file, 0.0).is_err());
+        draperie.generate();
+        draperie.to_svg_filled(file, 0.0).unwrap();
+        let svg = std::fs::read_to_string(file).unwrap();
+        assert_eq!(svg.matches("class=\"band\"").count(), 10 - 1);
+
+        let mut flinque =
+            RoseEngineLatheRun::new_flinque(20.0, 12, 8, 0.8, 20.0, 0.05, 0.0, false, 0.0, 0.0)
+                .unwrap();
+        flinque.generate();
+        flinque.to_svg_filled(file, PI).unwrap();
+        let svg = std::fs::read_to_string(file).unwrap();
+        assert_eq!(
+            svg.matches("class=\"band\"").count(),
+            flinque.lines().len() - 1
+        );
+        std::fs::remove_file(file).ok();
+
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut rosette = RoseEngineLatheRun::new(config, CuttingBit::default(), 12).unwrap();
+        rosette.generate();
+        assert!(rosette.to_svg_filled(file, 0.0).is_err());
+    }
 
-                    // Segment this path into multiple arcs with gaps
-                    self.segment_path(complete_path);
-                }
+    #[test]
+    fn test_svg_shaded_carries_each_pass_depth() {
+        let file = std::env::temp_dir().join("test_lathe_run_shaded.svg");
+        let file = file.to_str().unwrap();
+        let stroke_colors = |svg: &str| {
+            let mut colors: Vec<String> = svg
+                .split("stroke=\"")
+                .skip(1)
+                .map(|rest| rest[..rest.find('"').unwrap()].to_string())
+                .collect();
+            colors.sort();
+            colors.dedup();
+            colors
+        };
 
-                self.passes.push(lathe);
+        let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        config.with_depth_modulation(0.3, 4.0);
+        let bit = CuttingBit::v_shaped(60.0, 0.5);
+        let mut run =
+            RoseEngineLatheRun::new_with_segments(config, bit.clone(), 3, 2, 0.0, 0.0).unwrap();
+        assert!(run.to_svg_shaded(file).is_err());
+        run.generate();
+        run.to_svg_shaded(file).unwrap();
+        let svg = std::fs::read_to_string(file).unwrap();
+        assert!(stroke_colors(&svg).len() > 2);
+
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut flat = RoseEngineLatheRun::new_with_segments(config, bit, 3, 2, 0.0, 0.0).unwrap();
+        flat.generate();
+        flat.to_svg_shaded(file).unwrap();
+        let svg = std::fs::read_to_string(file).unwrap();
+        std::fs::remove_file(file).ok();
+        assert_eq!(stroke_colors(&svg).len(), 1);
+        assert_eq!(svg.matches("<path").count(), flat.lines().len());
+    }
+
+    #[test]
+    fn test_depth_passes_repeat_each_pass_at_stepped_depths() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let bit = CuttingBit::flat(0.5, 0.4);
+        let mut run = RoseEngineLatheRun::new_with_segments(config, bit, 4, 3, 0.0, 0.0).unwrap();
+        run.generate();
+        let single = run.lines().clone();
+
+        let deep = run
+            .expand_depth_passes(0.15, DepthPassOrder::DepthFirst)
+            .unwrap();
+        assert_eq!(deep.depth_steps(), vec![0.15, 0.30, 0.40]);
+        assert_eq!(deep.lines().len(), 3 * single.len());
+        assert_eq!(deep.line_depths().len(), deep.lines().len());
+        assert!((deep.cut_length() - 3.0 * run.cut_length()).abs() < 1e-9);
+
+        // Depth-first: the three segments of pass 0 at each depth, then pass 1
+        let expected_z = [0.15, 0.15, 0.15, 0.30, 0.30, 0.30, 0.40, 0.40, 0.40];
+        assert_eq!(&deep.line_depths()[..9], &expected_z);
+        assert_eq!(
+            &deep.line_pass_indices()[..10],
+            &[0, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+        );
+        let bits = |line: &Vec<Point2D>| -> Vec<(u64, u64)> {
+            line.iter()
+                .map(|p| (p.x.to_bits(), p.y.to_bits()))
+                .collect()
+        };
+        for repeat in deep.lines()[..9].chunks(3) {
+            for (a, b) in repeat.iter().zip(&single[..3]) {
+                assert_eq!(bits(a), bits(b));
             }
         }
 
-        self.generated = true;
+        // Pass-first: every pass at 0.15 before stepping down
+        let layered = run
+            .expand_depth_passes(0.15, DepthPassOrder::PassFirst)
+            .unwrap();
+        let n = single.len();
+        assert_eq!(&layered.lines()[..n], &single[..]);
+        assert!(layered.line_depths()[..n].iter().all(|&z| z == 0.15));
+        assert!(layered.line_depths()[2 * n..].iter().all(|&z| z == 0.40));
+
+        // Shallow cuts and regeneration keep the expansion settings
+        let mut shallow = run
+            .expand_depth_passes(0.5, DepthPassOrder::DepthFirst)
+            .unwrap();
+        assert_eq!(shallow.depth_steps(), vec![0.4]);
+        shallow.generate();
+        assert_eq!(shallow.lines().len(), n);
+        assert!(run
+            .expand_depth_passes(0.0, DepthPassOrder::DepthFirst)
+            .is_err());
     }
 
-    /// Segment a complete circular path into multiple arcs with gaps
-    fn segment_path(&mut self, path: &[Point2D]) {
-        if path.is_empty() || self.segments_per_pass == 0 {
-            return;
-        }
+    #[test]
+    fn test_post_process_runs_per_pass_before_depth_expansion() {
+        use crate::post::radial_nudge;
 
-        // Special case: segments_per_pass=1 means draw the complete path without gaps
-        if self.segments_per_pass == 1 {
-            self.segmented_lines.push(path.to_vec());
-            return;
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let bit = CuttingBit::flat(0.5, 0.4);
+        let mut run = RoseEngineLatheRun::new_with_segments(config, bit, 4, 3, 0.0, 0.0).unwrap();
+        run.generate();
+        let plain = run.lines().clone();
+
+        run.add_post_process(|lines, kind, pass| {
+            if kind == LayerKind::LathePass && pass == 1 {
+                lines.clear();
+            }
+        });
+        run.add_post_process(radial_nudge(|_, pass, _| pass == 2, 0.1));
+        run.generate();
+        assert_eq!(run.lines().len(), 9);
+        assert_eq!(run.line_pass_indices(), &[0, 0, 0, 2, 2, 2, 3, 3, 3]);
+        assert_eq!(&run.lines()[..3], &plain[..3]);
+        assert_eq!(&run.lines()[6..], &plain[9..]);
+        let (p, q) = (plain[6][0], run.lines()[3][0]);
+        assert!((q.x.hypot(q.y) - p.x.hypot(p.y) - 0.1).abs() < 1e-9);
+
+        // Every depth step repeats the edited passes
+        let deep = run
+            .expand_depth_passes(0.15, DepthPassOrder::DepthFirst)
+            .unwrap();
+        assert_eq!(deep.lines().len(), 27);
+        assert_eq!(&deep.lines()[9..12], &run.lines()[3..6]);
+
+        run.clear_post_processes();
+        run.generate();
+        assert_eq!(run.lines(), &plain);
+    }
+
+    #[test]
+    fn test_concentric_rings_stack_up_a_cylinder() {
+        let mut config = RoseEngineConfig::new(2.0, 0.0);
+        config.resolution = PointsTotal(360);
+        config.with_surface(SurfaceMapping::Cylinder {
+            radius: 18.0,
+            height: 3.5,
+        });
+        let mut run =
+            RoseEngineLatheRun::new_with_segments(config, CuttingBit::default(), 6, 1, 0.0, 0.0)
+                .unwrap();
+        run.radius_step = 0.5;
+        run.generate();
+
+        // Rings are centred on the base radius, so they span z = 0.75..3.25
+        let rings = run.lines_3d();
+        assert_eq!(rings.len(), 6);
+        for (i, ring) in rings.iter().enumerate() {
+            let z = 0.75 + 0.5 * i as f64;
+            for p in ring {
+                assert!((p.x.hypot(p.y) - 18.0).abs() < 1e-9);
+                assert!((p.z - z).abs() < 1e-9);
+            }
         }
 
-        let total_points = path.len();
+        let path = std::env::temp_dir().join("test_lathe_run_cylinder.stl");
+        let path = path.to_str().unwrap();
+        run.to_stl_3d(path, &ExportConfig::default()).unwrap();
+        let _ = std::fs::remove_file(path);
 
-        // Calculate points per segment
-        // Each segment takes up a fraction of the circle with a gap
-        // For visual effect: 70% drawing, 30% gap
-        let draw_ratio = 0.7;
-        let points_per_cycle = total_points / self.segments_per_pass;
-        let draw_points = (points_per_cycle as f64 * draw_ratio) as usize;
+        // Two more rings would run off the top of the band
+        run.num_passes = 8;
+        run.generate();
+        assert!(run.to_stl_3d(path, &ExportConfig::default()).is_err());
+    }
 
-        for seg_idx in 0..self.segments_per_pass {
-            let start_idx = seg_idx * points_per_cycle;
-            let end_idx = (start_idx + draw_points).min(total_points);
+    /// Apply `edit` to `field` and check that `affects_geometry` predicted
+    /// whether the lines changed; returns whether they did
+    fn edit_changes_lines(
+        run: &RoseEngineLatheRun,
+        field: &str,
+        edit: impl Fn(&mut RoseEngineLatheRun),
+    ) -> bool {
+        let hash = |run: &RoseEngineLatheRun| {
+            let mut run = run.clone();
+            run.generate();
+            run.geometry_hash()
+        };
+        let mut edited = run.clone();
+        edit(&mut edited);
+        let changed = hash(run) != hash(&edited);
+        assert_eq!(run.affects_geometry(field), changed, "{}", field);
+        changed
+    }
 
-            if start_idx < total_points && end_idx > start_idx {
-                let segment: Vec<Point2D> = path[start_idx..end_idx].to_vec();
-                if !segment.is_empty() {
-                    self.segmented_lines.push(segment);
-                }
+    #[test]
+    fn test_affects_geometry_rules() {
+        let mut config = RoseEngineConfig::wave(10.0, 8.0, 0.3);
+        config.resolution = PointsTotal(240);
+        let mut concentric =
+            RoseEngineLatheRun::new_with_segments(config, CuttingBit::default(), 8, 4, 0.0, 0.0)
+                .unwrap();
+        concentric.radius_step = 0.5;
+        concentric.phase_shift = 0.3;
+        concentric.phase_oscillations = 1.5;
+        concentric.circular_phase = 2.0;
+
+        assert!(edit_changes_lines(&concentric, "segments_per_pass", |r| {
+            r.segments_per_pass = 1
+        }));
+        assert!(!edit_changes_lines(
+            &concentric,
+            "max_points_per_path",
+            |r| { r.set_max_points_per_path(500).unwrap() }
+        ));
+
+        // phase_exponent only matters in sin-power mode
+        assert!(!edit_changes_lines(&concentric, "phase_exponent", |r| r
+            .phase_exponent =
+            3));
+        let mut sin_power = concentric.clone();
+        sin_power.circular_phase = 0.0;
+        assert!(edit_changes_lines(&sin_power, "phase_exponent", |r| r
+            .phase_exponent =
+            3));
+
+        // No phase shift: the envelope fields are inert
+        let mut no_phase = sin_power.clone();
+        no_phase.phase_shift = 0.0;
+        assert!(!edit_changes_lines(&no_phase, "phase_oscillations", |r| {
+            r.phase_oscillations = 4.0
+        }));
+        assert!(!edit_changes_lines(&no_phase, "circular_phase", |r| r
+            .circular_phase =
+            2.0));
+        assert!(!edit_changes_lines(&no_phase, "phase_exponent", |r| r
+            .phase_exponent =
+            3));
+
+        // Phase-rotation mode ignores the envelope entirely
+        let mut rotation = concentric.clone();
+        rotation.radius_step = 0.0;
+        assert!(!edit_changes_lines(&rotation, "phase_shift", |r| r
+            .phase_shift =
+            0.6));
+        assert!(!edit_changes_lines(&rotation, "phase_oscillations", |r| {
+            r.phase_oscillations = 4.0
+        }));
+
+        // A lone dome-mode ring starts the envelope at zero offset
+        let mut lone = concentric.clone();
+        lone.num_passes = 1;
+        assert!(!edit_changes_lines(&lone, "radius_step", |r| r
+            .radius_step =
+            0.0));
+        assert!(!edit_changes_lines(&lone, "phase_oscillations", |r| r
+            .phase_oscillations =
+            4.0));
+        // ...but sin^0 starts at the full phase shift
+        lone.circular_phase = 0.0;
+        lone.phase_exponent = 0;
+        assert!(edit_changes_lines(&lone, "radius_step", |r| r
+            .radius_step =
+            0.0));
+
+        // Banded runs take radii and phases from their bands
+        let banded =
+            RoseEngineLatheRun::new_banded(three_bands(), CuttingBit::default(), 0.0, 0.0).unwrap();
+        assert!(!edit_changes_lines(&banded, "radius_step", |r| r
+            .radius_step =
+            0.5));
+        assert!(!edit_changes_lines(&banded, "phase_shift", |r| r
+            .phase_shift =
+            0.3));
+
+        // Direct-geometry modes only read the center
+        let diamant =
+            RoseEngineLatheRun::new_diamant(12, 5.0, 120, 1, 0.0, 1.0, false, 0.0, 0.0).unwrap();
+        assert!(!edit_changes_lines(&diamant, "num_passes", |r| r
+            .num_passes =
+            3));
+        assert!(!edit_changes_lines(&diamant, "segments_per_pass", |r| r
+            .segments_per_pass =
+            1));
+        assert!(!edit_changes_lines(&diamant, "radius_step", |r| r
+            .radius_step =
+            0.5));
+        assert!(edit_changes_lines(&diamant, "center_x", |r| r.center_x = 1.0));
+    }
+
+    #[test]
+    fn test_pierced_clous_de_paris_cells_keep_their_web() {
+        let (spacing, min_web) = (2.0, 0.3);
+        let mut run =
+            RoseEngineLatheRun::new_clous_de_paris(spacing, 9.0, PI / 4.0, 20, 0.0, 0.0).unwrap();
+        run.generate();
+
+        let diamonds = crate::cells::planar_cells(run.lines());
+        let cells = run.pierceable_cells(min_web, 1.0);
+        assert!(!cells.is_empty());
+        assert_eq!(cells.len(), diamonds.len());
+
+        let edges = |poly: &[Point2D]| -> Vec<(Point2D, Point2D)> {
+            (0..poly.len())
+                .map(|i| (poly[i], poly[(i + 1) % poly.len()]))
+                .collect()
+        };
+        let dist = |p: Point2D, (a, b): (Point2D, Point2D)| {
+            let (dx, dy) = (b.x - a.x, b.y - a.y);
+            let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+            (p.x - a.x - dx * t).hypot(p.y - a.y - dy * t)
+        };
+        let inside = |poly: &[Point2D], p: Point2D| {
+            edges(poly).iter().fold(false, |inside, &(a, b)| {
+                let crosses = (a.y > p.y) != (b.y > p.y)
+                    && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                inside != crosses
+            })
+        };
+
+        for cell in &cells {
+            // Strictly inside its source diamond, half a web from the grooves
+            let source = diamonds
+                .iter()
+                .find(|diamond| inside(diamond, cell[0]))
+                .unwrap();
+            for &p in cell {
+                assert!(inside(source, p));
+                let clearance = edges(source)
+                    .into_iter()
+                    .map(|edge| dist(p, edge))
+                    .fold(f64::INFINITY, f64::min);
+                assert!(clearance >= min_web / 2.0 - 1e-9);
             }
+            let side = spacing - min_web;
+            assert!((crate::cells::polygon_area(cell) - side * side).abs() < 1e-9);
         }
-    }
 
-    /// Export combined pattern to SVG format
-    ///
-    /// # Arguments
-    /// * `filename` - Output SVG file path
-    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        if !self.generated {
-            return Err(SpirographError::ExportError(
-                "Pattern not generated. Call generate() first.".to_string(),
-            ));
+        // Every pair of openings is separated by at least the web
+        for (i, a) in cells.iter().enumerate() {
+            for b in &cells[i + 1..] {
+                for &p in a {
+                    for edge in edges(b) {
+                        assert!(dist(p, edge) >= min_web - 1e-9);
+                    }
+                }
+            }
         }
 
-        use svg::node::element::{path::Data, Path};
-        use svg::Document;
+        // A web wider than the cells leaves nothing to pierce
+        assert!(run.pierceable_cells(spacing, 0.0).is_empty());
+
+        let path = std::env::temp_dir().join("test_lathe_run_pierced.dxf");
+        let path = path.to_str().unwrap();
+        run.to_dxf_pierced(path, min_web, 1.0).unwrap();
+        let dxf = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+        assert_eq!(dxf.matches("\nLWPOLYLINE\n").count(), cells.len());
+        assert!(dxf.ends_with("0\nEOF\n"));
+        assert!(!dxf.contains("999\n"));
+        assert!(run.to_dxf_pierced(path, spacing, 0.0).is_err());
+
+        run.set_metadata(FileMetadata::new("CDP-7").with_license("MIT"));
+        run.to_dxf_pierced(path, min_web, 1.0).unwrap();
+        let dxf = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+        assert!(dxf.starts_with("999\ndesign_id: CDP-7\n999\ngenerator_version: turtles "));
+        assert!(dxf.contains("999\nlicense: MIT\n0\nSECTION\n"));
+        assert!(dxf.contains("$PROJECTNAME\n1\nCDP-7\n"));
+        assert_eq!(dxf.matches("\nLWPOLYLINE\n").count(), cells.len());
+    }
 
-        // Use segmented lines instead of complete passes
-        let all_lines = &self.segmented_lines;
+    #[test]
+    fn test_wear_grows_width_and_resharpens() {
+        // Ten circles of r = 10 at 360 points: each cut is a regular polygon
+        let perimeter = 360.0 * 20.0 * (PI / 360.0).sin();
+        let mut run =
+            RoseEngineLatheRun::new_diamant(10, 10.0, 360, 1, 0.0, 1.0, false, 0.0, 0.0).unwrap();
+        run.generate();
+        let nominal = run.cutting_bit.width;
+
+        run.set_wear_model(WearModel::new(0.01, 0.0)).unwrap();
+        let wear = run.pass_wear();
+        assert_eq!(wear.len(), 10);
+        assert_eq!(wear[0].width, nominal);
+        let expected = nominal + 0.01 * 9.0 * perimeter / 1000.0;
+        assert!((wear[9].width - expected).abs() < 1e-12);
+        assert!(run.resharpen_passes().is_empty());
+
+        // 3 circles (188 mm) stay under 0.2 m, the 4th pushes past it
+        run.set_wear_model(WearModel::new(0.01, 0.0).with_resharpen_after(0.2))
+            .unwrap();
+        assert_eq!(run.resharpen_passes(), vec![4, 8]);
+        let wear = run.pass_wear();
+        assert_eq!(wear[4].width, nominal);
+        assert_eq!(wear[4].cut_since_sharpening, 0.0);
+        let expected = nominal + 0.01 * perimeter / 1000.0;
+        assert!((wear[9].width - expected).abs() < 1e-12);
+
+        run.clear_wear_model();
+        assert!(run.pass_wear().is_empty());
+        assert!(WearModel::new(-0.01, 0.0).validate().is_err());
+        assert!(WearModel::new(0.01, 0.0)
+            .with_resharpen_after(0.0)
+            .validate()
+            .is_err());
+    }
 
-        // Find bounds
-        let mut min_x = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
-
-        for line in all_lines {
-            for point in line {
-                min_x = min_x.min(point.x);
-                max_x = max_x.max(point.x);
-                min_y = min_y.min(point.y);
-                max_y = max_y.max(point.y);
-            }
+    #[test]
+    fn test_wear_reaches_depth_map_svg_and_stl() {
+        let mut config = RoseEngineConfig::classic_multi_lobe(10.0, 6, 0.5);
+        config.depth_modulation = true;
+        let bit = CuttingBit::v_shaped(30.0, 0.4);
+        let mut run = RoseEngineLatheRun::new_with_segments(config, bit, 4, 1, 0.0, 0.0).unwrap();
+        run.generate();
+        run.set_wear_model(WearModel::new(2.0, 1.0)).unwrap();
+
+        let wear = run.pass_wear().to_vec();
+        assert_eq!(wear.len(), 4);
+        assert!(wear[3].depth < wear[0].depth);
+        let last = run.passes().last().unwrap();
+        assert_eq!(last.cutting_bit.width, wear[3].width);
+        let peak = last
+            .rendered_output()
+            .depth_map
+            .iter()
+            .fold(0.0, |a: f64, &b| a.max(b));
+        assert!(peak <= wear[3].depth * 2.0 + 1e-12 && peak > 0.0);
+
+        let path = std::env::temp_dir().join("test_lathe_run_worn.svg");
+        let path = path.to_str().unwrap();
+        run.to_svg_worn(path).unwrap();
+        let svg = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+        let widths: Vec<f64> = svg
+            .split("stroke-width=\"")
+            .skip(1)
+            .map(|rest| rest[..rest.find('"').unwrap()].parse().unwrap())
+            .collect();
+        assert_eq!(widths.len(), 4);
+        for (w, pass) in widths.iter().zip(&wear) {
+            assert!((w - 0.05 * pass.width / run.cutting_bit.width).abs() < 1e-9);
         }
 
-        let margin = 5.0;
-        let width = max_x - min_x + 2.0 * margin;
-        let height = max_y - min_y + 2.0 * margin;
-
-        let mut document = Document::new()
-            .set("width", format!("{}mm", width))
-            .set("height", format!("{}mm", height))
-            .set("viewBox", (min_x - margin, min_y - margin, width, height));
+        let path = std::env::temp_dir().join("test_lathe_run_worn.stl");
+        let path = path.to_str().unwrap();
+        run.to_stl_3d(path, &ExportConfig::default()).unwrap();
+        let mut file = std::fs::File::open(path).unwrap();
+        let mesh = stl_io::read_stl(&mut file).unwrap();
+        let _ = std::fs::remove_file(path);
+        let lowest = mesh.vertices.iter().fold(0.0, |a: f32, v| a.min(v[2]));
+        let expected = ExportConfig::default().depth * wear[0].depth / wear[0].programmed_depth;
+        assert!((lowest as f64 + expected).abs() < 1e-5);
+    }
 
-        // Add each segmented line
-        for line in all_lines.iter() {
-            if line.is_empty() {
-                continue;
+    #[test]
+    fn test_dash_by_phase_cuts_dots_per_revolution() {
+        use crate::common::{normalize_angle, DashSpec};
+
+        let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        config.resolution = PointsTotal(1440);
+        config.with_dash_by_phase(DashSpec::new(24, 0.4));
+        let mut run =
+            RoseEngineLatheRun::new_with_segments(config, CuttingBit::default(), 3, 1, 0.0, 0.0)
+                .unwrap();
+        run.generate();
+
+        let sample = 2.0 * PI / 1440.0;
+        let span = 0.4 * 2.0 * PI / 24.0;
+        for pass in 0..3 {
+            let dots: Vec<&Vec<Point2D>> = run
+                .lines()
+                .iter()
+                .zip(run.line_pass_indices())
+                .filter(|(_, &p)| p == pass)
+                .map(|(line, _)| line)
+                .collect();
+            assert_eq!(dots.len(), 24, "pass {}", pass);
+            for dot in dots {
+                let swept: f64 = dot
+                    .windows(2)
+                    .map(|w| normalize_angle(w[1].y.atan2(w[1].x) - w[0].y.atan2(w[0].x) + PI) - PI)
+                    .sum();
+                assert!((swept - span).abs() < sample, "span {} vs {}", swept, span);
             }
+        }
 
-            let mut data = Data::new().move_to((line[0].x, line[0].y));
+        // Dots start on the exact phase threshold, not the nearest sample
+        let phase = run.pass_config(1).phase;
+        let start = run.lines()[24][0];
+        let u = (start.y.atan2(start.x) + phase) * 24.0 / (2.0 * PI);
+        assert!((u - u.round()).abs() < 1e-9);
 
-            for point in line.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
+        let mut bad = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        bad.with_dash_by_phase(DashSpec::new(24, 0.0));
+        assert!(RoseEngineLatheRun::new(bad, CuttingBit::default(), 3).is_err());
+    }
 
-            let path = Path::new()
-                .set("d", data)
-                .set("fill", "none")
-                .set("stroke", "black")
-                .set("stroke-width", 0.05);
+    #[test]
+    fn test_large_amplitude_is_reported_or_clamped() {
+        // Rings at radii 2..6 around (10, 5); a 3 mm swing takes the
+        // innermost through the centre
+        let mut config = RoseEngineConfig::classic_multi_lobe(4.0, 12, 3.0);
+        config.resolution = PointsTotal(720);
+        config.with_dedupe_epsilon(0.0);
+        let make = |config: RoseEngineConfig| {
+            let mut run = RoseEngineLatheRun::new_with_segments(
+                config,
+                CuttingBit::default(),
+                5,
+                1,
+                10.0,
+                5.0,
+            )
+            .unwrap();
+            run.radius_step = 1.0;
+            run
+        };
 
-            document = document.add(path);
+        let mut run = make(config.clone());
+        let err = run.try_generate().unwrap_err().to_string();
+        assert!(err.contains("passes [0] of 5"), "{}", err);
+        assert!(err.contains("clamp_amplitude"), "{}", err);
+        assert!(run.lines().is_empty());
+        assert!((run.pass_config(0).max_safe_amplitude() - 2.0).abs() < 1e-9);
+
+        config.clamp_amplitude = true;
+        let mut run = make(config);
+        run.try_generate().unwrap();
+        let amplitudes: Vec<f64> = run.passes().iter().map(|p| p.config.amplitude).collect();
+        assert!((amplitudes[0] - 2.0).abs() < 1e-9);
+        assert_eq!(&amplitudes[1..], &[3.0; 4]);
+        for pass in run.passes() {
+            // Each point lies on its own spindle angle's side of the centre
+            let path = &pass.tool_path().center_line;
+            let step = 2.0 * PI / 720.0;
+            assert_eq!(path.len(), 721);
+            for (j, p) in path.iter().enumerate() {
+                let angle = j as f64 * step;
+                let r = (p.x - 10.0) * angle.cos() + (p.y - 5.0) * angle.sin();
+                assert!(r >= -1e-9, "radius {} at sample {}", r, j);
+            }
         }
-
-        svg::save(filename, &document).map_err(|e| {
-            SpirographError::ExportError(format!("Failed to save SVG file '{}': {}", filename, e))
-        })
     }
 
-    /// Get the number of passes
-    pub fn num_passes(&self) -> usize {
-        self.num_passes
+    #[test]
+    fn test_try_generate_names_pass_stepped_to_zero_radius() {
+        // Rings at radii 0..8; clamping flattens the innermost to a point
+        let mut config = RoseEngineConfig::classic_multi_lobe(4.0, 12, 1.0);
+        config.clamp_amplitude = true;
+        let mut run =
+            RoseEngineLatheRun::new_with_segments(config, CuttingBit::default(), 5, 1, 0.0, 0.0)
+                .unwrap();
+        run.radius_step = 2.0;
+        assert_eq!(run.pass_config(0).base_radius, 0.0);
+
+        let err = run.try_generate().unwrap_err();
+        assert_eq!(
+            err,
+            SpirographError::InvalidParameter(
+                "pass 0 of 5: base_radius must be positive".to_string()
+            )
+        );
+        assert!(run.lines().is_empty());
+
+        // The lenient generate leaves the pass out
+        run.generate();
+        assert_eq!(run.passes().len(), 4);
     }
 
-    /// Get reference to individual passes
-    pub fn passes(&self) -> &[RoseEngineLathe] {
-        &self.passes
+    #[test]
+    fn test_pass_overrides_ramp_amplitude_across_rings() {
+        // Six concentric rings 2 mm apart; the amplitude ramps up outward
+        let mut config = RoseEngineConfig::classic_multi_lobe(15.0, 12, 0.5);
+        config.resolution = PointsTotal(720);
+        let mut run =
+            RoseEngineLatheRun::new_with_segments(config, CuttingBit::default(), 6, 1, 0.0, 0.0)
+                .unwrap();
+        run.radius_step = 2.0;
+        let ramp: Vec<PassOverride> = (0..6)
+            .map(|i| PassOverride {
+                amplitude: Some(0.1 + 0.1 * i as f64),
+                ..Default::default()
+            })
+            .collect();
+        run.set_pass_overrides(ramp).unwrap();
+        run.generate();
+
+        let deviation = |pass: usize| {
+            let radii: Vec<f64> = run
+                .lines()
+                .iter()
+                .zip(run.line_pass_indices())
+                .filter(|(_, &p)| p == pass)
+                .flat_map(|(line, _)| line.iter().map(|p| p.x.hypot(p.y)))
+                .collect();
+            let max = radii.iter().cloned().fold(f64::MIN, f64::max);
+            let min = radii.iter().cloned().fold(f64::MAX, f64::min);
+            max - min
+        };
+        for pass in 0..5 {
+            assert!(
+                deviation(pass + 1) > deviation(pass) + 0.1,
+                "pass {} deviates {} vs {}",
+                pass + 1,
+                deviation(pass + 1),
+                deviation(pass)
+            );
+        }
+        // Rings still step outward from their computed radii
+        assert!((run.passes()[5].config.base_radius - 20.0).abs() < 1e-12);
+
+        // Overrides only reach the passes they cover
+        let mut partial = run.clone();
+        partial
+            .set_pass_overrides(vec![PassOverride {
+                rosette: Some(RosettePattern::MultiLobe { lobes: 6 }),
+                phase: Some(0.5),
+                ..Default::default()
+            }])
+            .unwrap();
+        assert_eq!(partial.pass_config(0).phase, 0.5);
+        assert_eq!(
+            partial.pass_config(0).rosette,
+            RosettePattern::MultiLobe { lobes: 6 }
+        );
+        let mut computed = run.clone();
+        computed.clear_pass_overrides();
+        assert_eq!(partial.pass_config(1).phase, computed.pass_config(1).phase);
+        assert_eq!(partial.pass_config(1).amplitude, 0.5);
     }
 
-    /// Get reference to the segmented lines (the generated pattern curves)
-    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
-        &self.segmented_lines
+    #[test]
+    fn test_pass_overrides_are_validated_and_skip_special_modes() {
+        let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+        let mut run = RoseEngineLatheRun::new(config, CuttingBit::default(), 3).unwrap();
+        let amplitude = |a: f64| PassOverride {
+            amplitude: Some(a),
+            ..Default::default()
+        };
+        assert!(run.set_pass_overrides(vec![amplitude(1.0); 4]).is_err());
+        assert!(run.set_pass_overrides(vec![amplitude(-1.0)]).is_err());
+        let radius = PassOverride {
+            base_radius: Some(0.0),
+            ..Default::default()
+        };
+        assert!(run.set_pass_overrides(vec![radius]).is_err());
+        assert!(run.pass_overrides().is_empty());
+        assert!(run.set_pass_overrides(vec![amplitude(2.0); 3]).is_ok());
+        assert!(run.affects_geometry("pass_overrides"));
+
+        let mut diamant =
+            RoseEngineLatheRun::new_diamant(12, 10.0, 90, 1, 0.0, 1.0, false, 0.0, 0.0).unwrap();
+        diamant.generate();
+        let plain = diamant.lines().clone();
+        diamant.set_pass_overrides(vec![amplitude(3.0)]).unwrap();
+        diamant.generate();
+        assert_eq!(diamant.lines(), &plain);
+        assert!(!diamant.affects_geometry("pass_overrides"));
     }
 }
@@ -0,0 +1,459 @@
+use serde::ser::SerializeSeq;
+use serde::{Serialize, Serializer};
+
+use crate::common::SpirographError;
+use crate::rose_engine::{DepthPassOrder, RoseEngineLatheRun, RosettePattern};
+
+/// Direction the spindle turns while a pass is being cut
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum SpindleDirection {
+    /// Increasing angle (end_angle >= start_angle)
+    #[serde(rename = "ccw")]
+    CounterClockwise,
+    /// Decreasing angle (end_angle < start_angle)
+    #[serde(rename = "cw")]
+    Clockwise,
+}
+
+impl SpindleDirection {
+    /// Short identifier used in the job format ("ccw" or "cw")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpindleDirection::CounterClockwise => "ccw",
+            SpindleDirection::Clockwise => "cw",
+        }
+    }
+}
+
+/// Mapping from rosette patterns to slots on a physical rosette barrel
+///
+/// A physical rose engine carries a stack of rosettes (the barrel); the
+/// controller selects one by its index. Each pattern used by a run must be
+/// mapped to the slot holding the matching rosette.
+///
+/// # Example
+/// ```
+/// use turtles::rose_engine::{RosetteMapping, RosettePattern};
+///
+/// let mapping = RosetteMapping::new()
+///     .with_slot(RosettePattern::MultiLobe { lobes: 12 }, 0)
+///     .with_slot(RosettePattern::Sinusoidal { frequency: 1.0 }, 3);
+/// assert_eq!(mapping.slot_for(&RosettePattern::Sinusoidal { frequency: 1.0 }), Some(3));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RosetteMapping {
+    slots: Vec<(RosettePattern, usize)>,
+}
+
+impl RosetteMapping {
+    /// Create an empty mapping
+    pub fn new() -> Self {
+        RosetteMapping { slots: Vec::new() }
+    }
+
+    /// Map a rosette pattern to a barrel slot, replacing any earlier mapping
+    pub fn with_slot(mut self, pattern: RosettePattern, slot: usize) -> Self {
+        self.slots.retain(|(p, _)| *p != pattern);
+        self.slots.push((pattern, slot));
+        self
+    }
+
+    /// Look up the barrel slot for a pattern
+    pub fn slot_for(&self, pattern: &RosettePattern) -> Option<usize> {
+        self.slots
+            .iter()
+            .find(|(p, _)| p == pattern)
+            .map(|(_, slot)| *slot)
+    }
+
+    /// Resolve the slot for a pattern, erroring if it cannot be cut on a barrel
+    fn resolve(&self, pattern: &RosettePattern) -> Result<usize, SpirographError> {
+        if let RosettePattern::Custom { .. } = pattern {
+            return Err(SpirographError::UnsupportedForMachine(
+                "custom rosette tables have no physical barrel rosette".to_string(),
+            ));
+        }
+        self.slot_for(pattern).ok_or_else(|| {
+            SpirographError::InvalidParameter(format!(
+                "no barrel slot mapped for rosette {:?}",
+                pattern
+            ))
+        })
+    }
+}
+
+/// A single pass in a machine job
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MachinePass {
+    /// Barrel slot of the primary rosette
+    pub rosette_slot: usize,
+    /// Rubber engagement on the primary rosette in mm
+    pub amplitude: f64,
+    /// Spindle index offset in degrees, normalized to [0, 360)
+    pub phase: f64,
+    /// Nominal cutting radius in mm
+    pub base_radius: f64,
+    /// Spindle direction for this pass
+    pub direction: SpindleDirection,
+    /// Cut depth in mm
+    pub depth: f64,
+    /// Barrel slot of the secondary rosette, if compound motion is used
+    pub secondary_rosette_slot: Option<usize>,
+    /// Rubber engagement on the secondary rosette in mm
+    pub secondary_amplitude: f64,
+    /// Secondary rosette phase in degrees, normalized to [0, 360)
+    pub secondary_phase: f64,
+    /// Whether the bit is resharpened before this pass
+    pub resharpen_before: bool,
+}
+
+impl MachinePass {
+    /// Name of the first numeric field that is NaN or infinite, if any
+    fn first_non_finite_field(&self) -> Option<&'static str> {
+        [
+            ("amplitude", self.amplitude),
+            ("phase", self.phase),
+            ("base_radius", self.base_radius),
+            ("depth", self.depth),
+            ("secondary_amplitude", self.secondary_amplitude),
+            ("secondary_phase", self.secondary_phase),
+        ]
+        .into_iter()
+        .find(|(_, value)| !value.is_finite())
+        .map(|(name, _)| name)
+    }
+}
+
+/// A cutting sequence for a physical rose engine controller
+///
+/// Passes are listed in cutting order. A run expanded with
+/// `RoseEngineLatheRun::expand_depth_passes` lists one pass per depth step.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MachineJob {
+    #[serde(serialize_with = "serialize_indexed_passes")]
+    pub passes: Vec<MachinePass>,
+}
+
+/// A pass together with its position in the cutting order
+#[derive(Serialize)]
+struct IndexedPass<'a> {
+    index: usize,
+    #[serde(flatten)]
+    pass: &'a MachinePass,
+}
+
+/// Write each pass with an `index` field giving its cutting order
+fn serialize_indexed_passes<S: Serializer>(
+    passes: &[MachinePass],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(Some(passes.len()))?;
+    for (index, pass) in passes.iter().enumerate() {
+        seq.serialize_element(&IndexedPass { index, pass })?;
+    }
+    seq.end()
+}
+
+/// Convert radians to degrees normalized to [0, 360)
+fn normalized_degrees(radians: f64) -> f64 {
+    let degrees = radians.to_degrees().rem_euclid(360.0);
+    // rem_euclid can round up to exactly 360.0 for tiny negative inputs
+    if degrees >= 360.0 {
+        0.0
+    } else {
+        degrees
+    }
+}
+
+impl MachineJob {
+    /// Serialize the job to a JSON string
+    ///
+    /// The format is `{"passes": [{...}, ...]}` with one object per pass,
+    /// each carrying an `index` field giving its cutting order. NaN and
+    /// infinite values are written as `null`.
+    pub fn to_json_string(&self) -> Result<String, SpirographError> {
+        serde_json::to_string(self)
+            .map_err(|e| SpirographError::ExportError(format!("JSON export failed: {}", e)))
+    }
+}
+
+impl RoseEngineLatheRun {
+    /// Build the cutting sequence for a physical rose engine controller
+    ///
+    /// Only rosette-driven runs can be expressed as a machine job. Runs using
+    /// one of the direct-geometry modes (diamant, huit-eight, flinque, paon,
+    /// clous de Paris, cube) and custom rosette tables return
+    /// `SpirographError::UnsupportedForMachine`. A pass with a NaN or
+    /// infinite value returns `SpirographError::InvalidParameter`.
+    ///
+    /// A generated run with a wear model marks the passes preceded by a
+    /// resharpening (see `RoseEngineLatheRun::pass_wear`).
+    ///
+    /// # Arguments
+    /// * `mapping` - Barrel slot for each rosette pattern used by the run
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{
+    ///     CuttingBit, RoseEngineConfig, RoseEngineLatheRun, RosetteMapping, RosettePattern,
+    /// };
+    ///
+    /// let mut config = RoseEngineConfig::new(20.0, 1.0);
+    /// config.rosette = RosettePattern::MultiLobe { lobes: 12 };
+    /// let run = RoseEngineLatheRun::new(config, CuttingBit::default(), 12).unwrap();
+    ///
+    /// let mapping = RosetteMapping::new().with_slot(RosettePattern::MultiLobe { lobes: 12 }, 0);
+    /// let job = run.to_machine_job(&mapping).unwrap();
+    /// assert_eq!(job.passes.len(), 12);
+    /// ```
+    pub fn to_machine_job(&self, mapping: &RosetteMapping) -> Result<MachineJob, SpirographError> {
+        if let Some(mode) = self.special_mode_name() {
+            return Err(SpirographError::UnsupportedForMachine(format!(
+                "{} mode is generated directly and has no rosette-driven passes",
+                mode
+            )));
+        }
+
+        let mut passes = Vec::with_capacity(self.num_passes);
+        for i in 0..self.num_passes {
+            let config = self.pass_config(i);
+
+            let rosette_slot = mapping.resolve(&config.rosette)?;
+            let secondary_rosette_slot = match &config.secondary_rosette {
+                Some(pattern) => Some(mapping.resolve(pattern)?),
+                None => None,
+            };
+
+            let direction = if config.end_angle >= config.start_angle {
+                SpindleDirection::CounterClockwise
+            } else {
+                SpindleDirection::Clockwise
+            };
+
+            passes.push(MachinePass {
+                rosette_slot,
+                amplitude: config.amplitude,
+                phase: normalized_degrees(config.phase),
+                base_radius: config.base_radius,
+                direction,
+                depth: self.cutting_bit.depth,
+                secondary_rosette_slot,
+                secondary_amplitude: config.secondary_amplitude,
+                secondary_phase: normalized_degrees(config.secondary_phase),
+                resharpen_before: false,
+            });
+        }
+
+        let steps = self.depth_steps();
+        let at_depth = |(i, pass): (usize, &MachinePass), depth: f64| MachinePass {
+            depth,
+            resharpen_before: self
+                .pass_wear()
+                .iter()
+                .any(|w| w.resharpen_before && w.pass == i && w.programmed_depth == depth),
+            ..pass.clone()
+        };
+        let passes: Vec<MachinePass> = match self.depth_pass_order() {
+            DepthPassOrder::DepthFirst => passes
+                .iter()
+                .enumerate()
+                .flat_map(|pass| steps.iter().map(move |&depth| at_depth(pass, depth)))
+                .collect(),
+            DepthPassOrder::PassFirst => steps
+                .iter()
+                .flat_map(|&depth| {
+                    passes
+                        .iter()
+                        .enumerate()
+                        .map(move |pass| at_depth(pass, depth))
+                })
+                .collect(),
+        };
+
+        for (i, pass) in passes.iter().enumerate() {
+            if let Some(field) = pass.first_non_finite_field() {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "machine pass {} has a non-finite {}",
+                    i, field
+                )));
+            }
+        }
+
+        Ok(MachineJob { passes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rose_engine::{CuttingBit, RoseEngineConfig, WearModel};
+
+    fn multi_lobe_run(num_passes: usize) -> RoseEngineLatheRun {
+        let mut config = RoseEngineConfig::new(20.0, 1.5);
+        config.rosette = RosettePattern::MultiLobe { lobes: 12 };
+        RoseEngineLatheRun::new(config, CuttingBit::v_shaped(30.0, 0.5), num_passes).unwrap()
+    }
+
+    #[test]
+    fn test_multi_lobe_job_phases() {
+        let run = multi_lobe_run(12);
+        let mapping = RosetteMapping::new().with_slot(RosettePattern::MultiLobe { lobes: 12 }, 2);
+        let job = run.to_machine_job(&mapping).unwrap();
+
+        assert_eq!(job.passes.len(), 12);
+        for (i, pass) in job.passes.iter().enumerate() {
+            assert_eq!(pass.rosette_slot, 2);
+            assert_eq!(pass.amplitude, 1.5);
+            assert_eq!(pass.direction, SpindleDirection::CounterClockwise);
+            assert!(
+                (pass.phase - 30.0 * i as f64).abs() < 1e-9,
+                "pass {} phase {}",
+                i,
+                pass.phase
+            );
+            assert!(pass.secondary_rosette_slot.is_none());
+        }
+    }
+
+    #[test]
+    fn test_phase_normalized_to_degrees() {
+        let mut run = multi_lobe_run(4);
+        run.base_config.phase = -std::f64::consts::PI / 2.0;
+        let mapping = RosetteMapping::new().with_slot(RosettePattern::MultiLobe { lobes: 12 }, 0);
+        let job = run.to_machine_job(&mapping).unwrap();
+
+        let phases: Vec<f64> = job.passes.iter().map(|p| p.phase).collect();
+        let expected = [270.0, 0.0, 90.0, 180.0];
+        for (got, want) in phases.iter().zip(expected.iter()) {
+            assert!((got - want).abs() < 1e-9, "got {}, want {}", got, want);
+            assert!((0.0..360.0).contains(got));
+        }
+    }
+
+    #[test]
+    fn test_diamant_mode_unsupported() {
+        let run =
+            RoseEngineLatheRun::new_diamant(12, 10.0, 360, 1, 0.0, 1.0, false, 0.0, 0.0).unwrap();
+        let err = run.to_machine_job(&RosetteMapping::new()).unwrap_err();
+        match err {
+            SpirographError::UnsupportedForMachine(msg) => assert!(msg.contains("diamant")),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unmapped_and_custom_rosettes() {
+        let run = multi_lobe_run(3);
+        let err = run.to_machine_job(&RosetteMapping::new()).unwrap_err();
+        assert!(matches!(err, SpirographError::InvalidParameter(_)));
+
+        let mut config = RoseEngineConfig::new(20.0, 1.0);
+        config.rosette = RosettePattern::from_function(|a| a.sin(), 64);
+        let run = RoseEngineLatheRun::new(config, CuttingBit::default(), 3).unwrap();
+        let err = run.to_machine_job(&RosetteMapping::new()).unwrap_err();
+        assert!(matches!(err, SpirographError::UnsupportedForMachine(_)));
+    }
+
+    #[test]
+    fn test_job_json() {
+        let run = multi_lobe_run(2);
+        let mapping = RosetteMapping::new().with_slot(RosettePattern::MultiLobe { lobes: 12 }, 1);
+        let json = run
+            .to_machine_job(&mapping)
+            .unwrap()
+            .to_json_string()
+            .unwrap();
+
+        assert!(json.starts_with("{\"passes\":[{\"index\":0,\"rosette_slot\":1,"));
+        assert!(json.contains("\"phase\":180"));
+        assert!(json.contains("\"direction\":\"ccw\""));
+        assert!(json.contains("\"secondary_rosette_slot\":null"));
+        assert!(json.ends_with("}]}"));
+    }
+
+    #[test]
+    fn test_job_json_with_non_finite_value_is_valid() {
+        let mapping = RosetteMapping::new().with_slot(RosettePattern::MultiLobe { lobes: 12 }, 0);
+        let mut job = multi_lobe_run(1).to_machine_job(&mapping).unwrap();
+        job.passes[0].depth = f64::NAN;
+
+        let json = job.to_json_string().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["passes"][0]["depth"].is_null());
+        assert_eq!(value["passes"][0]["index"], 0);
+    }
+
+    #[test]
+    fn test_non_finite_pass_rejected() {
+        let mapping = RosetteMapping::new().with_slot(RosettePattern::MultiLobe { lobes: 12 }, 0);
+        let mut run = multi_lobe_run(2);
+        run.base_config.amplitude = f64::NAN;
+        match run.to_machine_job(&mapping).unwrap_err() {
+            SpirographError::InvalidParameter(msg) => assert!(msg.contains("amplitude")),
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let mut run = multi_lobe_run(2);
+        run.base_config.base_radius = f64::INFINITY;
+        assert!(matches!(
+            run.to_machine_job(&mapping),
+            Err(SpirographError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_depth_passes_in_job() {
+        let mut run = multi_lobe_run(2);
+        run.cutting_bit = CuttingBit::flat(0.5, 0.4);
+        let mapping = RosetteMapping::new().with_slot(RosettePattern::MultiLobe { lobes: 12 }, 0);
+
+        let job = run
+            .expand_depth_passes(0.15, DepthPassOrder::DepthFirst)
+            .unwrap()
+            .to_machine_job(&mapping)
+            .unwrap();
+        let steps: Vec<(f64, f64)> = job.passes.iter().map(|p| (p.phase, p.depth)).collect();
+        assert_eq!(
+            steps,
+            vec![
+                (0.0, 0.15),
+                (0.0, 0.30),
+                (0.0, 0.40),
+                (180.0, 0.15),
+                (180.0, 0.30),
+                (180.0, 0.40),
+            ]
+        );
+
+        let job = run
+            .expand_depth_passes(0.15, DepthPassOrder::PassFirst)
+            .unwrap()
+            .to_machine_job(&mapping)
+            .unwrap();
+        let depths: Vec<f64> = job.passes.iter().map(|p| p.depth).collect();
+        assert_eq!(depths, vec![0.15, 0.15, 0.30, 0.30, 0.40, 0.40]);
+    }
+
+    #[test]
+    fn test_resharpen_marked_in_job() {
+        let mut run = multi_lobe_run(6)
+            .expand_depth_passes(0.5, DepthPassOrder::PassFirst)
+            .unwrap();
+        run.generate();
+        let ring = run.cut_length() / 12.0;
+        run.set_wear_model(WearModel::new(0.1, 0.0).with_resharpen_after(2.5 * ring / 1000.0))
+            .unwrap();
+        assert_eq!(run.resharpen_passes(), vec![3, 6, 9]);
+
+        let mapping = RosetteMapping::new().with_slot(RosettePattern::MultiLobe { lobes: 12 }, 0);
+        let job = run.to_machine_job(&mapping).unwrap();
+        let marked: Vec<usize> = (0..job.passes.len())
+            .filter(|&k| job.passes[k].resharpen_before)
+            .collect();
+        assert_eq!(marked, vec![3, 6, 9]);
+        assert!(job
+            .to_json_string()
+            .unwrap()
+            .contains("\"resharpen_before\":true"));
+    }
+}
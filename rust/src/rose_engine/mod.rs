@@ -15,6 +15,10 @@
 //! - **Cutting Bit**: Defines the tool shape and size (V-shaped, flat, round, etc.)
 //! - **Configuration**: Parameters for the rose engine (amplitude, base radius, phase, etc.)
 //! - **Lathe**: Main implementation that generates tool paths and cut geometry
+//! - **Machine Job**: Cutting sequence export for a physical rose engine controller
+//! - **G-code**: Tool path export for CNC machines
+//! - **Simulation**: Frame-by-frame machine state for animating a running engine
+//! - **Surface Mapping**: Wrapping patterns onto cylindrical and conical case parts
 //!
 //! # Example
 //!
@@ -33,12 +37,20 @@
 //! let mut lathe = RoseEngineLathe::new(config, bit).unwrap();
 //! lathe.generate();
 //!
+//! // The tool path closes on itself and stays within base radius ± amplitude
+//! let path = &lathe.tool_path().center_line;
+//! assert!(turtles::polyline_closes(path));
+//! # use turtles::doctest_support::max_radius;
+//! assert!(max_radius(std::slice::from_ref(path), (0.0, 0.0)) <= 22.0 + 1e-9);
+//!
 //! // Export to SVG
-//! lathe.to_svg("rose_pattern.svg").unwrap();
+//! let svg = lathe.to_svg_string().unwrap();
+//! assert!(svg.starts_with("<svg"));
 //!
 //! // Export to STL for 3D printing/CNC
 //! let export_config = ExportConfig::default();
-//! lathe.to_stl("rose_pattern.stl", &export_config).unwrap();
+//! let stl = lathe.to_stl_bytes(&export_config).unwrap();
+//! assert!(stl.len() > 84);
 //! ```
 //!
 //! # Preset Patterns
@@ -46,33 +58,48 @@
 //! The module includes several preset configurations for common patterns:
 //!
 //! ```
+//! use turtles::polyline_closes;
 //! use turtles::rose_engine::{RoseEngineLathe, RoseEngineConfig, CuttingBit};
 //!
 //! // Classic multi-lobe pattern
 //! let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 2.0);
 //! let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
 //! lathe.generate();
+//! assert!(polyline_closes(&lathe.tool_path().center_line));
 //!
 //! // Sunburst pattern
 //! let config = RoseEngineConfig::sunburst(20.0, 24, 1.5);
 //! let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
 //! lathe.generate();
+//! assert!(polyline_closes(&lathe.tool_path().center_line));
 //!
 //! // Wave pattern
 //! let config = RoseEngineConfig::wave(20.0, 8.0, 2.0);
 //! let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
 //! lathe.generate();
+//! assert!(polyline_closes(&lathe.tool_path().center_line));
 //! ```
 
 pub mod config;
 pub mod cutting_bit;
+pub mod gcode;
 pub mod lathe;
 pub mod lathe_run;
+pub mod machine;
 pub mod rosette;
+pub mod simulation;
+mod solid;
+pub mod surface;
 
 // Re-export main types for convenience
 pub use config::RoseEngineConfig;
-pub use cutting_bit::{BitShape, CuttingBit};
-pub use lathe::{Arc, RenderedOutput, RoseEngineLathe, ToolPathOutput};
-pub use lathe_run::RoseEngineLatheRun;
+pub use cutting_bit::{BitShape, CuttingBit, WearModel};
+pub use gcode::GcodeParams;
+pub use lathe::{Arc, PathSegment, RenderedOutput, RoseEngineLathe, ToolPathOutput};
+pub use lathe_run::{
+    BandSpec, DepthPassOrder, PassOverride, PassWear, RoseEngineLatheRun, SvgColorMode, SvgStyle,
+};
+pub use machine::{MachineJob, MachinePass, RosetteMapping, SpindleDirection};
 pub use rosette::RosettePattern;
+pub use simulation::SimFrame;
+pub use surface::SurfaceMapping;
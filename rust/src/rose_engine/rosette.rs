@@ -1,7 +1,11 @@
 use std::f64::consts::PI;
 
+use serde::{Deserialize, Serialize};
+
+use crate::common::SpirographError;
+
 /// Rosette pattern type - defines how the radius modulates with angle
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RosettePattern {
     /// Simple circular pattern (no modulation)
     Circular,
@@ -21,6 +25,11 @@ pub enum RosettePattern {
     },
 
     /// Multi-lobe rosette (classic rose engine pattern)
+    ///
+    /// `2|sin(lobes·θ/2)| − 1` has period 2π/lobes whatever the parity of
+    /// `lobes`, so a revolution has exactly `lobes` rounded crests (at
+    /// odd multiples of π/lobes) between sharp troughs (at multiples of
+    /// 2π/lobes, including θ = 0 = 2π).
     MultiLobe {
         /// Number of lobes/petals
         lobes: usize,
@@ -191,6 +200,85 @@ impl RosettePattern {
         }
     }
 
+    /// True for a sinusoidal, draperie or paon frequency that is not a
+    /// whole number
+    ///
+    /// Such a wave does not repeat after one turn, so its phase must not be
+    /// wrapped by 2π.
+    pub(crate) fn has_fractional_frequency(&self) -> bool {
+        match self {
+            RosettePattern::Sinusoidal { frequency }
+            | RosettePattern::Draperie { frequency, .. }
+            | RosettePattern::Paon { frequency } => frequency.fract() != 0.0,
+            _ => false,
+        }
+    }
+
+    /// Check the pattern's parameters
+    ///
+    /// Zero lobe, petal, row and division counts would give a constant or
+    /// degenerate displacement, and non-finite frequencies poison every
+    /// sample, so both are rejected.
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        let invalid = |message: &str| Err(SpirographError::InvalidParameter(message.to_string()));
+        match self {
+            RosettePattern::Circular => Ok(()),
+            RosettePattern::Elliptical {
+                eccentricity,
+                rotation,
+            } => {
+                if !(eccentricity.is_finite() && *eccentricity > 0.0 && rotation.is_finite()) {
+                    return invalid("ellipse eccentricity must be positive and rotation finite");
+                }
+                Ok(())
+            }
+            RosettePattern::Sinusoidal { frequency }
+            | RosettePattern::Draperie { frequency, .. }
+            | RosettePattern::Paon { frequency } => {
+                if !frequency.is_finite() {
+                    return invalid("rosette frequency must be finite");
+                }
+                Ok(())
+            }
+            RosettePattern::MultiLobe { lobes } | RosettePattern::HuitEight { lobes } => {
+                if *lobes == 0 {
+                    return invalid("lobes must be at least 1");
+                }
+                Ok(())
+            }
+            RosettePattern::Epicycloid { petals } => {
+                if *petals == 0 {
+                    return invalid("petals must be at least 1");
+                }
+                Ok(())
+            }
+            RosettePattern::GrainDeRiz { grain_size, rows } => {
+                if *rows == 0 {
+                    return invalid("rows must be at least 1");
+                }
+                if !(grain_size.is_finite() && *grain_size != 0.0) {
+                    return invalid("grain size must be finite and non-zero");
+                }
+                Ok(())
+            }
+            RosettePattern::Diamant { divisions } => {
+                if *divisions == 0 {
+                    return invalid("divisions must be at least 1");
+                }
+                Ok(())
+            }
+            RosettePattern::Custom { table, samples } => {
+                if *samples == 0 || table.len() != *samples {
+                    return invalid("custom table must hold `samples` entries, at least one");
+                }
+                if table.iter().any(|v| !v.is_finite()) {
+                    return invalid("custom table values must be finite");
+                }
+                Ok(())
+            }
+        }
+    }
+
     /// Create a custom rosette pattern from a function
     ///
     /// # Arguments
@@ -219,6 +307,87 @@ impl RosettePattern {
 
         RosettePattern::Custom { table, samples }
     }
+
+    /// Number of samples in a custom lookup table, or `None` for the
+    /// analytic patterns
+    pub fn table_len(&self) -> Option<usize> {
+        match self {
+            RosettePattern::Custom { samples, .. } => Some(*samples),
+            _ => None,
+        }
+    }
+
+    /// Resample a custom table to `new_samples` entries by linear
+    /// interpolation around the circle
+    ///
+    /// Normalizing tables to a standard size first makes `smoothed` windows
+    /// cover the same angular span whatever the source resolution. Analytic
+    /// patterns are returned unchanged.
+    pub fn resample_table(self, new_samples: usize) -> RosettePattern {
+        match self {
+            RosettePattern::Custom { .. } => {
+                RosettePattern::from_function(|angle| self.displacement(angle), new_samples.max(1))
+            }
+            other => other,
+        }
+    }
+
+    /// Smooth a custom table with a circular moving average of `window`
+    /// samples, keeping sharp features intact
+    ///
+    /// The turn angle at each sample is measured on the table drawn with
+    /// one unit per sample. Samples turning by more than
+    /// `preserve_corners_deg` are pinned to their original value and no
+    /// averaging window reaches past them, so ratchet drops and chevron tips
+    /// keep their position. Pass 180 or more to disable corner preservation.
+    /// Even windows round up to the next odd width; a window of 0 or 1 and
+    /// analytic patterns are returned unchanged.
+    pub fn smoothed(self, window: usize, preserve_corners_deg: f64) -> RosettePattern {
+        let RosettePattern::Custom { table, samples } = self else {
+            return self;
+        };
+        let n = table.len();
+        let half = (window / 2).min(n.saturating_sub(1) / 2);
+        if half == 0 {
+            return RosettePattern::Custom { table, samples };
+        }
+
+        let pinned: Vec<bool> = (0..n)
+            .map(|i| {
+                let prev = table[(i + n - 1) % n];
+                let next = table[(i + 1) % n];
+                let turn = ((next - table[i]).atan() - (table[i] - prev).atan()).abs();
+                turn.to_degrees() > preserve_corners_deg
+            })
+            .collect();
+
+        let smoothed = (0..n)
+            .map(|i| {
+                if pinned[i] {
+                    return table[i];
+                }
+                let mut sum = table[i];
+                let mut count = 1;
+                for step in [1, n - 1] {
+                    let mut j = i;
+                    for _ in 0..half {
+                        j = (j + step) % n;
+                        sum += table[j];
+                        count += 1;
+                        if pinned[j] {
+                            break;
+                        }
+                    }
+                }
+                sum / count as f64
+            })
+            .collect();
+
+        RosettePattern::Custom {
+            table: smoothed,
+            samples,
+        }
+    }
 }
 
 impl Default for RosettePattern {
@@ -258,6 +427,56 @@ mod tests {
         assert!(d_half > 0.5);
     }
 
+    /// Local maxima of the displacement over one revolution, wrapping at 2π
+    fn count_maxima(pattern: &RosettePattern) -> usize {
+        let n = 7200;
+        let samples: Vec<f64> = (0..n)
+            .map(|i| pattern.displacement(i as f64 * 2.0 * PI / n as f64))
+            .collect();
+        (0..n)
+            .filter(|&i| {
+                let here = samples[i];
+                here > samples[(i + n - 1) % n] && here >= samples[(i + 1) % n]
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_multi_lobe_has_one_crest_per_lobe() {
+        for lobes in [5, 6, 7, 12] {
+            let pattern = RosettePattern::MultiLobe { lobes };
+            assert_eq!(count_maxima(&pattern), lobes, "lobes = {}", lobes);
+            // Seamless at the end of the revolution for either parity
+            assert!((pattern.displacement(2.0 * PI) - pattern.displacement(0.0)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_counts() {
+        assert!(RosettePattern::MultiLobe { lobes: 0 }.validate().is_err());
+        assert!(RosettePattern::Epicycloid { petals: 0 }.validate().is_err());
+        assert!(RosettePattern::HuitEight { lobes: 0 }.validate().is_err());
+        assert!(RosettePattern::Diamant { divisions: 0 }.validate().is_err());
+        assert!(RosettePattern::GrainDeRiz {
+            grain_size: 0.5,
+            rows: 0
+        }
+        .validate()
+        .is_err());
+        assert!(RosettePattern::Sinusoidal {
+            frequency: f64::NAN
+        }
+        .validate()
+        .is_err());
+        assert!(RosettePattern::from_function(|a| a.sin(), 0)
+            .validate()
+            .is_err());
+
+        assert!(RosettePattern::default().validate().is_ok());
+        assert!(RosettePattern::Circular.validate().is_ok());
+        assert!(RosettePattern::Diamant { divisions: 3 }.validate().is_ok());
+    }
+
     #[test]
     fn test_epicycloid_pattern() {
         let pattern = RosettePattern::Epicycloid { petals: 5 };
@@ -274,6 +493,89 @@ mod tests {
         assert!((d_half - 1.0).abs() < 0.1);
     }
 
+    fn table(pattern: &RosettePattern) -> &[f64] {
+        match pattern {
+            RosettePattern::Custom { table, .. } => table,
+            _ => panic!("expected a custom pattern"),
+        }
+    }
+
+    #[test]
+    fn test_resample_table() {
+        let pattern = RosettePattern::from_function(|angle| angle.sin(), 100);
+        assert_eq!(pattern.table_len(), Some(100));
+        assert_eq!(RosettePattern::Circular.table_len(), None);
+
+        let resampled = pattern.clone().resample_table(400);
+        assert_eq!(resampled.table_len(), Some(400));
+        assert_eq!(table(&resampled).len(), 400);
+        for i in 0..50 {
+            let angle = i as f64 * 2.0 * PI / 50.0;
+            assert!((resampled.displacement(angle) - pattern.displacement(angle)).abs() < 1e-3);
+        }
+        assert_eq!(
+            RosettePattern::Circular.resample_table(10),
+            RosettePattern::Circular
+        );
+    }
+
+    #[test]
+    fn test_smoothing_reduces_noise() {
+        // Deterministic uniform noise in [-0.5, 0.5)
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let noise: Vec<f64> = (0..4096)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+            })
+            .collect();
+        let rms = |t: &[f64]| (t.iter().map(|v| v * v).sum::<f64>() / t.len() as f64).sqrt();
+
+        let pattern = RosettePattern::Custom {
+            samples: noise.len(),
+            table: noise,
+        };
+        let before = rms(table(&pattern));
+        let smoothed = pattern.smoothed(9, 180.0);
+        let after = rms(table(&smoothed));
+
+        // Averaging 9 independent samples divides the RMS by 3
+        let ratio = after / before;
+        assert!((ratio - 1.0 / 3.0).abs() < 0.03, "ratio {}", ratio);
+    }
+
+    #[test]
+    fn test_smoothing_preserves_square_wave_edges() {
+        let samples = 200;
+        let square: Vec<f64> = (0..samples)
+            .map(|i| if (i / 25) % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let edges = |t: &[f64]| -> Vec<usize> {
+            (0..t.len())
+                .filter(|&i| (t[i] >= 0.0) != (t[(i + 1) % t.len()] >= 0.0))
+                .collect()
+        };
+        let pattern = RosettePattern::Custom {
+            table: square.clone(),
+            samples,
+        };
+
+        let kept = pattern.clone().smoothed(11, 30.0);
+        let original_edges = edges(&square);
+        let kept_edges = edges(table(&kept));
+        assert_eq!(kept_edges.len(), original_edges.len());
+        for (a, b) in original_edges.iter().zip(&kept_edges) {
+            assert!(a.abs_diff(*b) <= 1);
+        }
+        assert!(table(&kept).iter().all(|v| v.abs() > 0.99));
+
+        // Without corner preservation the edges are ramped
+        let blurred = pattern.smoothed(11, 180.0);
+        assert!(table(&blurred).iter().any(|v| v.abs() < 0.5));
+    }
+
     #[test]
     fn test_draperie_pattern_range() {
         // Verify displacement values stay within [-1.0, 1.0] for various angles
@@ -287,7 +589,7 @@ mod tests {
             let angle = (i as f64) * 2.0 * PI / 100.0;
             let displacement = pattern.displacement(angle);
             assert!(
-                displacement >= -1.0 && displacement <= 1.0,
+                (-1.0..=1.0).contains(&displacement),
                 "Displacement {} at angle {} is out of range [-1.0, 1.0]",
                 displacement,
                 angle
@@ -386,4 +688,16 @@ mod tests {
             _ => panic!("Default should be MultiLobe with 12 lobes"),
         }
     }
+
+    #[test]
+    fn test_custom_table_serializes_as_numbers() {
+        let pattern = RosettePattern::from_function(|a| a.sin(), 4);
+        let json = serde_json::to_value(&pattern).unwrap();
+        let table = json["Custom"]["table"].as_array().unwrap();
+        assert_eq!(table.len(), 4);
+        assert!(table.iter().all(|v| v.is_f64()));
+
+        let restored: RosettePattern = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, pattern);
+    }
 }
@@ -0,0 +1,364 @@
+use crate::common::{Point2D, SpirographError};
+use crate::rose_engine::RoseEngineLathe;
+use std::f64::consts::PI;
+
+/// Machine-frame angle of the cutter, measured from the spindle axis.
+///
+/// The cutter sits on the +x side of the workpiece and never moves; the
+/// rosette only pushes the headstock in and out along that line.
+const TOOL_ANGLE: f64 = 0.0;
+
+/// Machine state at one instant of a running-engine simulation
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimFrame {
+    /// Angle the spindle has turned since the start of the cut, in radians
+    pub spindle_angle: f64,
+    /// Rotation (radians, counter-clockwise) that maps workpiece-frame
+    /// points into the machine frame about the lathe center
+    pub workpiece_rotation: f64,
+    /// Point where the cutter touches the workpiece, in the machine frame
+    pub tool_contact_point: Point2D,
+    /// Exclusive end index into the tool path center line of the portion
+    /// already cut
+    pub cut_so_far_end_index: usize,
+}
+
+impl SimFrame {
+    /// Transform a workpiece-frame point into the machine frame
+    pub fn to_machine_frame(&self, point: Point2D, center: Point2D) -> Point2D {
+        let (sin, cos) = self.workpiece_rotation.sin_cos();
+        let dx = point.x - center.x;
+        let dy = point.y - center.y;
+        Point2D::new(
+            center.x + dx * cos - dy * sin,
+            center.y + dx * sin + dy * cos,
+        )
+    }
+}
+
+impl RoseEngineLathe {
+    /// Simulate the running engine at uniform spindle steps
+    ///
+    /// The generated tool path is fixed in the workpiece; while the spindle
+    /// turns, the workpiece rotates under a cutter that stays at a fixed
+    /// machine angle and the rosette moves the work in and out beneath it.
+    /// Frame `k` of `steps + 1` is taken at
+    /// `start_angle + k · (sweep_end_angle − start_angle) / steps`, so the first
+    /// frame has nothing cut and the last frame has the whole path cut.
+    ///
+    /// Returns an empty vector if the pattern has not been generated or
+    /// `steps` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::rose_engine::{CuttingBit, RoseEngineConfig, RoseEngineLathe};
+    ///
+    /// let config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.0);
+    /// let mut lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+    /// lathe.generate();
+    ///
+    /// let frames = lathe.simulate(36);
+    /// assert_eq!(frames.len(), 37);
+    /// assert_eq!(frames[36].cut_so_far_end_index, lathe.tool_path().center_line.len());
+    /// ```
+    pub fn simulate(&self, steps: usize) -> Vec<SimFrame> {
+        let path_len = self.tool_path().center_line.len();
+        if path_len == 0 || steps == 0 {
+            return Vec::new();
+        }
+
+        let sweep = self.config.sweep_end_angle() - self.config.start_angle;
+        let resolution = self.config.sample_steps() as f64;
+
+        (0..=steps)
+            .map(|k| {
+                let fraction = (k as f64) / (steps as f64);
+                let spindle_angle = fraction * sweep;
+                let angle = self.config.start_angle + spindle_angle;
+
+                // Tool path point i sits at start_angle + i·sweep/resolution;
+                // the small epsilon keeps exact multiples from rounding down.
+                let last_cut = (fraction * resolution + 1e-9).floor() as usize;
+
+                let radius = self.config.spiral_radius_at_angle(angle);
+                SimFrame {
+                    spindle_angle,
+                    workpiece_rotation: TOOL_ANGLE - angle,
+                    tool_contact_point: Point2D::new(
+                        self.center_x + radius * TOOL_ANGLE.cos(),
+                        self.center_y + radius * TOOL_ANGLE.sin(),
+                    ),
+                    cut_so_far_end_index: (last_cut + 1).min(path_len),
+                }
+            })
+            .collect()
+    }
+
+    /// Write one SVG per simulation frame into `dir`
+    ///
+    /// Each frame (`frame_0000.svg`, `frame_0001.svg`, …) shows the
+    /// workpiece blank and the portion cut so far, both rotated into the
+    /// machine frame, the rosette beside it turning with the spindle, and
+    /// markers for the cutter and the rosette rubber. The directory is
+    /// created if needed.
+    ///
+    /// # Arguments
+    /// * `dir` - Output directory
+    /// * `steps` - Number of spindle steps (writes `steps + 1` frames)
+    pub fn simulation_to_svg_frames(&self, dir: &str, steps: usize) -> Result<(), SpirographError> {
+        use svg::node::element::{path::Data, Circle, Group, Path};
+        use svg::Document;
+
+        let center_line = &self.tool_path().center_line;
+        if center_line.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+        if steps == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "steps must be at least 1".to_string(),
+            ));
+        }
+
+        std::fs::create_dir_all(dir).map_err(|e| {
+            SpirographError::ExportError(format!("Failed to create directory '{}': {}", dir, e))
+        })?;
+
+        let (cx, cy) = (self.center_x, self.center_y);
+
+        // Workpiece blank slightly larger than the furthest cut
+        let max_radius = center_line
+            .iter()
+            .map(|p| ((p.x - cx).powi(2) + (p.y - cy).powi(2)).sqrt())
+            .fold(0.0, f64::max);
+        let blank_radius = max_radius * 1.1 + 1.0;
+
+        // Rosette drawn to the right of the workpiece at half its size,
+        // sharing the spindle rotation; the rubber is on the same machine
+        // line as the cutter.
+        let rosette_base = blank_radius * 0.5;
+        let rosette_cx = cx + blank_radius * 2.0 + rosette_base;
+        let rosette_samples = self.config.resolution.get().max(360);
+        let rosette_profile: Vec<Point2D> = (0..=rosette_samples)
+            .map(|i| {
+                let phi = 2.0 * PI * (i as f64) / (rosette_samples as f64);
+                let r = rosette_base + self.config.radius_at_angle(phi) - self.config.base_radius;
+                Point2D::new(rosette_cx + r * phi.cos(), cy + r * phi.sin())
+            })
+            .collect();
+
+        let margin = 5.0;
+        let min_x = cx - blank_radius - margin;
+        let min_y = cy - blank_radius - margin;
+        let width = rosette_cx + rosette_base * 1.5 + margin - min_x;
+        let height = 2.0 * (blank_radius + margin);
+        let marker_radius = (blank_radius * 0.02).max(0.2);
+
+        let polyline = |points: &[Point2D]| {
+            let mut data = Data::new().move_to((points[0].x, points[0].y));
+            for p in points.iter().skip(1) {
+                data = data.line_to((p.x, p.y));
+            }
+            data
+        };
+        let rosette_data = polyline(&rosette_profile);
+
+        for (k, frame) in self.simulate(steps).iter().enumerate() {
+            let degrees = frame.workpiece_rotation.to_degrees();
+
+            let mut workpiece = Group::new()
+                .set("id", "workpiece")
+                .set("transform", format!("rotate({} {} {})", degrees, cx, cy))
+                .add(
+                    Circle::new()
+                        .set("cx", cx)
+                        .set("cy", cy)
+                        .set("r", blank_radius)
+                        .set("fill", "#eeeeee")
+                        .set("stroke", "black")
+                        .set("stroke-width", 0.1),
+                );
+            if frame.cut_so_far_end_index >= 2 {
+                workpiece = workpiece.add(
+                    Path::new()
+                        .set("d", polyline(&center_line[..frame.cut_so_far_end_index]))
+                        .set("fill", "none")
+                        .set("stroke", "black")
+                        .set("stroke-width", 0.05),
+                );
+            }
+
+            let rosette = Group::new()
+                .set("id", "rosette")
+                .set(
+                    "transform",
+                    format!("rotate({} {} {})", degrees, rosette_cx, cy),
+                )
+                .add(
+                    Path::new()
+                        .set("d", rosette_data.clone())
+                        .set("fill", "#dddddd")
+                        .set("stroke", "black")
+                        .set("stroke-width", 0.1),
+                );
+
+            let contact = frame.tool_contact_point;
+            let angle = self.config.start_angle + frame.spindle_angle;
+            let rubber_r =
+                rosette_base + self.config.radius_at_angle(angle) - self.config.base_radius;
+
+            let document = Document::new()
+                .set("width", format!("{}mm", width))
+                .set("height", format!("{}mm", height))
+                .set("viewBox", (min_x, min_y, width, height))
+                .add(workpiece)
+                .add(rosette)
+                .add(
+                    Circle::new()
+                        .set("id", "tool")
+                        .set("cx", contact.x)
+                        .set("cy", contact.y)
+                        .set("r", marker_radius)
+                        .set("fill", "red"),
+                )
+                .add(
+                    Circle::new()
+                        .set("id", "rubber")
+                        .set("cx", rosette_cx + rubber_r * TOOL_ANGLE.cos())
+                        .set("cy", cy + rubber_r * TOOL_ANGLE.sin())
+                        .set("r", marker_radius)
+                        .set("fill", "blue"),
+                );
+
+            let path = std::path::Path::new(dir).join(format!("frame_{:04}.svg", k));
+            svg::save(&path, &document).map_err(|e| {
+                SpirographError::ExportError(format!(
+                    "Failed to save SVG file '{}': {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PointsTotal;
+    use crate::rose_engine::{CuttingBit, RoseEngineConfig};
+
+    fn lathe() -> RoseEngineLathe {
+        let mut config = RoseEngineConfig::classic_multi_lobe(20.0, 12, 1.5);
+        config.resolution = PointsTotal(360);
+        let mut lathe =
+            RoseEngineLathe::new_with_center(config, CuttingBit::default(), 3.0, -2.0).unwrap();
+        lathe.generate();
+        lathe
+    }
+
+    #[test]
+    fn test_tool_stays_at_fixed_machine_angle() {
+        let lathe = lathe();
+        let path = &lathe.tool_path().center_line;
+        let frames = lathe.simulate(72);
+        assert_eq!(frames.len(), 73);
+
+        // Every 5° the spindle reaches the angle of tool path point 5k; the
+        // cutter must sit on the +x machine line at that point's radius.
+        for (k, frame) in frames.iter().enumerate() {
+            let cut = path[5 * k];
+            let radius = (cut.x - lathe.center_x).hypot(cut.y - lathe.center_y);
+            let tool = frame.tool_contact_point;
+            assert!(
+                (tool.x - (lathe.center_x + radius)).abs() < 1e-9
+                    && (tool.y - lathe.center_y).abs() < 1e-9,
+                "frame {} has the tool at {:?}, expected radius {}",
+                k,
+                tool,
+                radius
+            );
+        }
+
+        // The 12-lobe rosette at 1.5 mm engagement swings the cut between
+        // 18.5 mm at a cusp (0°) and 21.5 mm on the crest of a lobe (15°)
+        assert!((frames[0].tool_contact_point.x - lathe.center_x - 18.5).abs() < 1e-9);
+        assert!((frames[3].tool_contact_point.x - lathe.center_x - 21.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cut_is_stationary_in_workpiece_frame() {
+        let lathe = lathe();
+        let center = Point2D::new(lathe.center_x, lathe.center_y);
+        let path = &lathe.tool_path().center_line;
+
+        // 36 steps divides the 360-point resolution, so the last cut point
+        // of every frame is exactly the point under the cutter.
+        let frames = lathe.simulate(36);
+        assert_eq!(frames[0].cut_so_far_end_index, 1);
+        assert_eq!(frames[36].cut_so_far_end_index, path.len());
+
+        for frame in &frames {
+            let newest = path[frame.cut_so_far_end_index - 1];
+            let in_machine = frame.to_machine_frame(newest, center);
+            let tool = frame.tool_contact_point;
+            assert!(
+                (in_machine.x - tool.x).hypot(in_machine.y - tool.y) < 1e-9,
+                "newest cut point {:?} is not under the tool {:?}",
+                in_machine,
+                frame.tool_contact_point
+            );
+        }
+
+        assert!(frames
+            .windows(2)
+            .all(|w| w[0].cut_so_far_end_index <= w[1].cut_so_far_end_index));
+    }
+
+    #[test]
+    fn test_simulate_requires_generate() {
+        let config = RoseEngineConfig::new(20.0, 1.0);
+        let lathe = RoseEngineLathe::new(config, CuttingBit::default()).unwrap();
+        assert!(lathe.simulate(10).is_empty());
+        assert!(lathe
+            .simulation_to_svg_frames(std::env::temp_dir().to_str().unwrap(), 10)
+            .is_err());
+    }
+
+    #[test]
+    fn test_svg_frames_written() {
+        let lathe = lathe();
+        let dir = std::env::temp_dir().join("turtles_sim_frames_test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        lathe
+            .simulation_to_svg_frames(dir.to_str().unwrap(), 4)
+            .unwrap();
+
+        let mut names: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "frame_0000.svg",
+                "frame_0001.svg",
+                "frame_0002.svg",
+                "frame_0003.svg",
+                "frame_0004.svg"
+            ]
+        );
+
+        let last = std::fs::read_to_string(dir.join("frame_0004.svg")).unwrap();
+        assert!(last.contains("id=\"workpiece\""));
+        assert!(last.contains("id=\"rosette\""));
+        assert!(last.contains("id=\"tool\""));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
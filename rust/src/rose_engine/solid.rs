@@ -0,0 +1,162 @@
+use stl_io::Triangle;
+
+use crate::common::{
+    base_slab_triangles, polyline_bounds, polyline_is_degenerate, ExportConfig, Point2D,
+    SpirographError,
+};
+use crate::groove::{flat_groove_triangles, GrooveProfile};
+use crate::metadata::{step_header, FileMetadata};
+
+/// Groove and base slab triangles for the flat STL exports
+///
+/// Every line with at least two distinct points becomes a closed groove
+/// solid of `profile`, over a slab about `center` wide enough to cover
+/// them all with its top at the groove floor. The result is moved to
+/// `config.origin`.
+///
+/// A nonzero `config.tool_radius` compensates for the cutter: each line
+/// is first moved that far along its normal, away from `center` when
+/// positive and towards it when negative.
+pub(crate) fn groove_triangles(
+    lines: &[Vec<Point2D>],
+    center: Point2D,
+    profile: &GrooveProfile,
+    config: &ExportConfig,
+) -> Result<Vec<Triangle>, SpirographError> {
+    if !config.tool_radius.is_finite() {
+        return Err(SpirographError::InvalidParameter(format!(
+            "tool_radius must be finite, got {}",
+            config.tool_radius
+        )));
+    }
+    let offset: Vec<Vec<Point2D>>;
+    let lines = if config.tool_radius != 0.0 {
+        offset = lines
+            .iter()
+            .map(|line| offset_line(line, center, config.tool_radius))
+            .collect();
+        &offset
+    } else {
+        lines
+    };
+    let lines: Vec<&[Point2D]> = lines
+        .iter()
+        .map(Vec::as_slice)
+        .filter(|line| !polyline_is_degenerate(line))
+        .collect();
+    if lines.is_empty() {
+        return Err(SpirographError::ExportError(
+            "No segments to export".to_string(),
+        ));
+    }
+
+    let mut triangles = flat_groove_triangles(lines.iter().map(|&line| (line, false)), profile);
+
+    // Base slab under the grooves
+    triangles.extend(base_slab_triangles(
+        center,
+        lines.iter().flat_map(|line| line.iter().copied()),
+        0.0,
+        -config.depth,
+        config.base_thickness,
+    ));
+    config.origin.validate()?;
+    config.origin.apply_stl(&mut triangles);
+    Ok(triangles)
+}
+
+/// `line` moved `distance` along its normal at every point, the normal
+/// taken on the side away from `center`
+///
+/// The normal at a point is square to the chord between its neighbours;
+/// a closed line wraps around, so its seam moves as one point.
+fn offset_line(line: &[Point2D], center: Point2D, distance: f64) -> Vec<Point2D> {
+    let n = line.len();
+    let closed = n > 2 && line[0] == line[n - 1];
+    (0..n)
+        .map(|i| {
+            let prev = if i > 0 {
+                line[i - 1]
+            } else if closed {
+                line[n - 2]
+            } else {
+                line[0]
+            };
+            let next = if i + 1 < n {
+                line[i + 1]
+            } else if closed {
+                line[1]
+            } else {
+                line[i]
+            };
+            let p = line[i];
+            let (tx, ty) = (next.x - prev.x, next.y - prev.y);
+            let length = tx.hypot(ty);
+            if length == 0.0 {
+                return p;
+            }
+            let (mut nx, mut ny) = (ty / length, -tx / length);
+            if nx * (p.x - center.x) + ny * (p.y - center.y) < 0.0 {
+                (nx, ny) = (-nx, -ny);
+            }
+            Point2D::new(p.x + distance * nx, p.y + distance * ny)
+        })
+        .collect()
+}
+
+/// Write triangles to a binary STL file
+pub(crate) fn write_stl(filename: &str, triangles: &[Triangle]) -> Result<(), SpirographError> {
+    let mut file = std::fs::File::create(filename).map_err(|e| {
+        SpirographError::ExportError(format!("Failed to create STL file '{}': {}", filename, e))
+    })?;
+    stl_io::write_stl(&mut file, triangles.iter())
+        .map_err(|e| SpirographError::ExportError(e.to_string()))
+}
+
+/// Write the points of every line to a STEP file, moved to `config.origin`
+///
+/// The header carries `description` and, when given, the metadata.
+pub(crate) fn write_step(
+    filename: &str,
+    lines: &[Vec<Point2D>],
+    config: &ExportConfig,
+    metadata: Option<&FileMetadata>,
+    description: &str,
+) -> Result<(), SpirographError> {
+    config.origin.validate()?;
+    let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
+
+    let mut content = String::new();
+    content.push_str("ISO-10303-21;\n");
+    content.push_str("HEADER;\n");
+    content.push_str(&step_header(
+        metadata,
+        description,
+        "rose_engine.stp",
+        &timestamp,
+    ));
+    content.push_str("FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));\n");
+    content.push_str("ENDSEC;\n");
+    content.push_str("DATA;\n");
+
+    let bounds = polyline_bounds(lines.iter().map(Vec::as_slice)).unwrap_or_default();
+    let points = lines
+        .iter()
+        .flatten()
+        .map(|&p| config.origin.apply(bounds, p));
+    for (i, point) in points.enumerate() {
+        content.push_str(&format!(
+            "#{}=CARTESIAN_POINT('',({}.,{}.,0.));\n",
+            i + 1,
+            point.x,
+            point.y
+        ));
+    }
+
+    content.push_str("ENDSEC;\n");
+    content.push_str("END-ISO-10303-21;\n");
+
+    std::fs::write(filename, content).map_err(|e| {
+        SpirographError::ExportError(format!("Failed to write STEP file '{}': {}", filename, e))
+    })
+}
@@ -0,0 +1,225 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::{ExportConfig, Point2D, Point3D, SpirographError};
+use crate::groove::groove_solid_triangles;
+use crate::rose_engine::cutting_bit::CuttingBit;
+use crate::rose_engine::solid::write_stl;
+
+/// Surface a rose engine pattern is cut into
+///
+/// On the curved surfaces the spindle angle wraps around the Z axis and the
+/// pattern's radial coordinate (distance from the lathe center) becomes the
+/// height along the axis, so a concentric ring stack turns into rings
+/// stacked up a case band. The surface starts at z = 0, so base radii and
+/// ring steps are chosen in terms of the band height.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum SurfaceMapping {
+    /// A flat dial in the XY plane
+    #[default]
+    Flat,
+    /// A cylinder about the Z axis spanning z ∈ [0, height]
+    Cylinder { radius: f64, height: f64 },
+    /// A cone about the Z axis with radius `r_bottom` at z = 0 and `r_top`
+    /// at z = height
+    Cone {
+        r_top: f64,
+        r_bottom: f64,
+        height: f64,
+    },
+}
+
+impl SurfaceMapping {
+    /// Check the surface dimensions
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        let positive = |v: f64| v.is_finite() && v > 0.0;
+        match *self {
+            SurfaceMapping::Flat => Ok(()),
+            SurfaceMapping::Cylinder { radius, height } => {
+                if !positive(radius) {
+                    return Err(SpirographError::InvalidParameter(
+                        "cylinder radius must be positive".to_string(),
+                    ));
+                }
+                if !positive(height) {
+                    return Err(SpirographError::InvalidParameter(
+                        "cylinder height must be positive".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            SurfaceMapping::Cone {
+                r_top,
+                r_bottom,
+                height,
+            } => {
+                let non_negative = |v: f64| v.is_finite() && v >= 0.0;
+                if !(non_negative(r_top) && non_negative(r_bottom)) || r_top + r_bottom == 0.0 {
+                    return Err(SpirographError::InvalidParameter(
+                        "cone radii must be non-negative and not both zero".to_string(),
+                    ));
+                }
+                if !positive(height) {
+                    return Err(SpirographError::InvalidParameter(
+                        "cone height must be positive".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Axial length of a curved surface (`None` when flat)
+    pub fn height(&self) -> Option<f64> {
+        match *self {
+            SurfaceMapping::Flat => None,
+            SurfaceMapping::Cylinder { height, .. } | SurfaceMapping::Cone { height, .. } => {
+                Some(height)
+            }
+        }
+    }
+
+    /// Surface radius at height `z`, and its change per unit of height
+    fn radius_at(&self, z: f64) -> (f64, f64) {
+        match *self {
+            SurfaceMapping::Flat => (0.0, 0.0),
+            SurfaceMapping::Cylinder { radius, .. } => (radius, 0.0),
+            SurfaceMapping::Cone {
+                r_top,
+                r_bottom,
+                height,
+            } => {
+                let slope = (r_top - r_bottom) / height;
+                (r_bottom + slope * z, slope)
+            }
+        }
+    }
+
+    /// Map a flat pattern point, measured about the lathe `center`, onto the
+    /// surface
+    ///
+    /// Flat surfaces keep the point where it is, at z = 0.
+    pub fn map_point(&self, p: Point2D, center: Point2D) -> Point3D {
+        if let SurfaceMapping::Flat = self {
+            return Point3D::new(p.x, p.y, 0.0);
+        }
+        let dx = p.x - center.x;
+        let dy = p.y - center.y;
+        let theta = dy.atan2(dx);
+        let z = dx.hypot(dy);
+        let (radius, _) = self.radius_at(z);
+        Point3D::new(radius * theta.cos(), radius * theta.sin(), z)
+    }
+
+    /// Map whole polylines onto the surface
+    pub fn map_lines(&self, lines: &[Vec<Point2D>], center: Point2D) -> Vec<Vec<Point3D>> {
+        lines
+            .iter()
+            .map(|line| line.iter().map(|&p| self.map_point(p, center)).collect())
+            .collect()
+    }
+
+    /// Unit outward surface normal at a mapped point
+    fn normal_at(&self, p: Point3D) -> Point3D {
+        if let SurfaceMapping::Flat = self {
+            return Point3D::new(0.0, 0.0, 1.0);
+        }
+        let theta = p.y.atan2(p.x);
+        let (_, slope) = self.radius_at(p.z);
+        let length = (1.0 + slope * slope).sqrt();
+        Point3D::new(theta.cos() / length, theta.sin() / length, -slope / length)
+    }
+}
+
+/// Write mapped polylines to STL as grooves cut `config.depth` into the
+/// surface, moved to `config.origin`
+///
+/// Each line becomes a closed groove solid with the bit's cross-section,
+/// hanging from the surface along its inward normal. With `line_depths`,
+/// each line's groove runs its own depth instead.
+pub(crate) fn write_surface_stl(
+    filename: &str,
+    lines: &[Vec<Point3D>],
+    line_depths: Option<&[f64]>,
+    surface: &SurfaceMapping,
+    bit: &CuttingBit,
+    config: &ExportConfig,
+) -> Result<(), SpirographError> {
+    let mut triangles = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let depth = line_depths.map_or(config.depth, |depths| depths[i]);
+        if depth <= 0.0 {
+            continue;
+        }
+        let profile = bit.groove_profile(depth)?;
+        triangles.extend(groove_solid_triangles(line, false, &profile, |p| {
+            surface.normal_at(p)
+        }));
+    }
+
+    if triangles.is_empty() {
+        return Err(SpirographError::ExportError(
+            "No segments to export".to_string(),
+        ));
+    }
+    config.origin.validate()?;
+    config.origin.apply_stl(&mut triangles);
+    write_stl(filename, &triangles)
+}
+
+/// Reject mapped lines that run off the end of a curved surface
+pub(crate) fn check_fits_surface(
+    lines: &[Vec<Point3D>],
+    surface: &SurfaceMapping,
+) -> Result<(), SpirographError> {
+    if let Some(height) = surface.height() {
+        let top = lines
+            .iter()
+            .flatten()
+            .map(|p| p.z)
+            .fold(f64::NEG_INFINITY, f64::max);
+        if top > height {
+            return Err(SpirographError::InvalidParameter(format!(
+                "pattern reaches z = {:.3} but the surface is only {} high; \
+                 reduce the base radius or ring count",
+                top, height
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cone_normal_is_perpendicular_to_slant() {
+        let cone = SurfaceMapping::Cone {
+            r_top: 10.0,
+            r_bottom: 12.0,
+            height: 4.0,
+        };
+        let p = cone.map_point(Point2D::new(0.0, 2.0), Point2D::new(0.0, 0.0));
+        assert!((p.x.hypot(p.y) - 11.0).abs() < 1e-12);
+
+        // Slant direction in the plane of the point: (dr, dz) = (-2, 4)
+        let n = cone.normal_at(p);
+        let radial = n.x * p.x / 11.0 + n.y * p.y / 11.0;
+        assert!((radial * -2.0 + n.z * 4.0).abs() < 1e-12);
+        assert!(radial > 0.0);
+
+        assert!(SurfaceMapping::Cone {
+            r_top: 0.0,
+            r_bottom: 0.0,
+            height: 1.0
+        }
+        .validate()
+        .is_err());
+        assert!(SurfaceMapping::Cylinder {
+            radius: 20.0,
+            height: -1.0
+        }
+        .validate()
+        .is_err());
+    }
+}
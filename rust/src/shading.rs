@@ -0,0 +1,292 @@
+use std::f64::consts::PI;
+
+use svg::node::element::{Definitions, Group, LinearGradient, Path, Stop};
+
+use crate::cells::polygon_area;
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{polyline_is_degenerate, Point2D, SpirographError};
+
+/// Number of precomputed band gradients, spanning leans of -90° to +90°
+pub(crate) const SHADE_GRADIENTS: usize = 7;
+
+/// Tone of a band's side facing away from the light
+const SHADOW_TONE: &str = "#8c8c8c";
+
+/// Tone of a band's side facing the light
+const LIT_TONE: &str = "#f2f2f2";
+
+/// Id of the `k`th band gradient
+pub(crate) fn shade_gradient_id(k: usize) -> String {
+    format!("band-shade-{}", k)
+}
+
+/// Mean lean of the band between `outer` and `inner`, in [-1, 1]
+///
+/// At matching samples the step from the inner to the outer ring is split
+/// into its radial and tangential parts about `center`; the lean is the
+/// tangential share, positive counter-clockwise. Concentric circles have no
+/// lean, while a ring-to-ring phase shift tilts the band's grooves sideways.
+fn band_lean(outer: &[Point2D], inner: &[Point2D], center: Point2D) -> f64 {
+    let n = outer.len().min(inner.len());
+    let mut sum = 0.0;
+    let mut count = 0;
+    for j in 0..n {
+        let o = outer[j * outer.len() / n];
+        let i = inner[j * inner.len() / n];
+        let (dx, dy) = (o.x - i.x, o.y - i.y);
+        let step = dx.hypot(dy);
+        let (mx, my) = ((o.x + i.x) / 2.0 - center.x, (o.y + i.y) / 2.0 - center.y);
+        let r = mx.hypot(my);
+        if step < 1e-12 || r < 1e-12 {
+            continue;
+        }
+        sum += (dx * -my + dy * mx) / (r * step);
+        count += 1;
+    }
+    if count == 0 {
+        0.0
+    } else {
+        (sum / count as f64).clamp(-1.0, 1.0)
+    }
+}
+
+/// Index of the gradient for a band with the given lean
+///
+/// A lean of `sin α` turns the light `α` away from `light_angle`; gradient
+/// `k` is turned by `(k / (SHADE_GRADIENTS - 1) - 1/2) · π`.
+fn shade_index(lean: f64) -> usize {
+    let turn = lean.asin() / PI + 0.5;
+    ((turn * (SHADE_GRADIENTS - 1) as f64).round() as usize).min(SHADE_GRADIENTS - 1)
+}
+
+/// The precomputed band gradients, dark away from the light
+fn shade_gradients(light_angle: f64) -> Definitions {
+    let mut defs = Definitions::new();
+    for k in 0..SHADE_GRADIENTS {
+        let angle = light_angle + (k as f64 / (SHADE_GRADIENTS - 1) as f64 - 0.5) * PI;
+        let (dx, dy) = (0.5 * angle.cos(), 0.5 * angle.sin());
+        defs = defs.add(
+            LinearGradient::new()
+                .set("id", shade_gradient_id(k))
+                .set("x1", 0.5 - dx)
+                .set("y1", 0.5 - dy)
+                .set("x2", 0.5 + dx)
+                .set("y2", 0.5 + dy)
+                .add(
+                    Stop::new()
+                        .set("offset", 0.0)
+                        .set("stop-color", SHADOW_TONE),
+                )
+                .add(Stop::new().set("offset", 1.0).set("stop-color", LIT_TONE)),
+        );
+    }
+    defs
+}
+
+/// Closed band path: `outer` forward, then `inner` wound the other way
+///
+/// The inner ring is reversed only when both rings wind the same way, so
+/// under the nonzero fill rule the inner disc is always a hole.
+fn band_path(writer: &SvgWriter, outer: &[Point2D], inner: &[Point2D], gradient: usize) -> Path {
+    let same_winding = polygon_area(outer).signum() == polygon_area(inner).signum();
+    let inner_points: Vec<Point2D> = if same_winding {
+        inner.iter().rev().copied().collect()
+    } else {
+        inner.to_vec()
+    };
+    let data = writer.outlines_data(&[outer.to_vec(), inner_points]);
+
+    Path::new()
+        .set("class", "band")
+        .set("fill", format!("url(#{})", shade_gradient_id(gradient)))
+        .set("fill-rule", "nonzero")
+        .set("stroke", "none")
+        .set("d", data)
+}
+
+/// Gray level of a depth-shaded stroke with shading 0; shading 1 is black
+const DEPTH_LIGHT_LEVEL: f64 = 211.0;
+
+/// Stroke widths are rounded to this before neighbouring segments are
+/// compared, in mm
+const DEPTH_WIDTH_STEP: f64 = 1e-3;
+
+/// Stroke color for a depth shading in [0, 1], from light gray to black
+pub(crate) fn depth_shade_color(shading: f64) -> String {
+    let level = (DEPTH_LIGHT_LEVEL * (1.0 - shading.clamp(0.0, 1.0))).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", level, level, level)
+}
+
+/// `line` stroked segment by segment, each segment colored and sized from
+/// the mean of its end points' `shading` and `widths`
+///
+/// Consecutive segments with the same color and width (to 0.001 mm) are
+/// joined into one path, so a line of constant depth stays a single path.
+pub(crate) fn depth_shaded_paths(
+    writer: &SvgWriter,
+    line: &[Point2D],
+    shading: &[f64],
+    widths: &[f64],
+) -> Vec<Path> {
+    let n = line.len().min(shading.len()).min(widths.len());
+    let mut paths = Vec::new();
+    let mut run: Option<(String, f64, Vec<Point2D>)> = None;
+    for i in 1..n {
+        let color = depth_shade_color((shading[i - 1] + shading[i]) / 2.0);
+        let width =
+            ((widths[i - 1] + widths[i]) / 2.0 / DEPTH_WIDTH_STEP).round() * DEPTH_WIDTH_STEP;
+        let (a, b) = (line[i - 1], line[i]);
+        run = Some(match run.take() {
+            Some((c, w, mut points)) if c == color && w == width => {
+                points.push(b);
+                (c, w, points)
+            }
+            previous => {
+                paths.extend(previous.map(|(c, w, points)| depth_path(writer, &points, &c, w)));
+                (color, width, vec![a, b])
+            }
+        });
+    }
+    paths.extend(run.map(|(c, w, points)| depth_path(writer, &points, &c, w)));
+    paths
+}
+
+/// One run of depth-shaded segments
+fn depth_path(writer: &SvgWriter, points: &[Point2D], color: &str, width: f64) -> Path {
+    Path::new()
+        .set("d", writer.path_data(points, false))
+        .set("fill", "none")
+        .set("stroke", color)
+        .set("stroke-width", width)
+}
+
+/// Gradient definitions and one filled band per adjacent ring pair
+///
+/// Rings are paired in order, so `rings.len() - 1` bands are drawn (rings
+/// too short to enclose an area are skipped). Each band is filled with the
+/// gradient matching its lean about `center`; `light_angle` is in radians
+/// in the pattern's coordinates.
+pub(crate) fn shaded_bands(
+    writer: &SvgWriter,
+    rings: &[&[Point2D]],
+    center: Point2D,
+    light_angle: f64,
+) -> Result<(Definitions, Group), SpirographError> {
+    if !light_angle.is_finite() {
+        return Err(SpirographError::InvalidParameter(
+            "light_angle must be finite".to_string(),
+        ));
+    }
+
+    let rings: Vec<&[Point2D]> = rings
+        .iter()
+        .copied()
+        .filter(|ring| !polyline_is_degenerate(ring))
+        .collect();
+    let mut group = Group::new().set("id", "bands");
+    for pair in rings.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (outer, inner) = if polygon_area(a).abs() >= polygon_area(b).abs() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        let lean = band_lean(outer, inner, center);
+        group = group.add(band_path(writer, outer, inner, shade_index(lean)));
+    }
+    Ok((shade_gradients(light_angle), group))
+}
+
+/// Write concentric rings to SVG over gradient-filled bands
+///
+/// The bands come from [`shaded_bands`]; the rings are stroked on top as in
+/// the plain line-art export.
+pub(crate) fn write_shaded_rings_svg(
+    filename: &str,
+    rings: &[Vec<Point2D>],
+    center: Point2D,
+    light_angle: f64,
+    max_points_per_path: usize,
+) -> Result<(), SpirographError> {
+    if rings.is_empty() {
+        return Err(SpirographError::ExportError(
+            "Pattern not generated. Call generate() first.".to_string(),
+        ));
+    }
+
+    let ring_slices: Vec<&[Point2D]> = rings.iter().map(Vec::as_slice).collect();
+    let mut writer = SvgWriter::framing(ring_slices.iter().copied())?
+        .with_max_points_per_path(max_points_per_path);
+    let (defs, bands) = shaded_bands(&writer, &ring_slices, center, light_angle)?;
+    writer.add(defs);
+    writer.add(bands);
+
+    for ring in rings {
+        if polyline_is_degenerate(ring) {
+            continue;
+        }
+        let node = writer.polyline_node(ring, false, |path| {
+            path.set("fill", "none")
+                .set("stroke", "black")
+                .set("stroke-width", 0.05)
+        });
+        writer.add(node);
+    }
+
+    writer.save(filename)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circle(r: f64, phase: f64, n: usize) -> Vec<Point2D> {
+        (0..=n)
+            .map(|i| {
+                let t = 2.0 * PI * i as f64 / n as f64;
+                Point2D::new(r * (t + phase).cos(), r * (t + phase).sin())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_band_lean_follows_phase_shift() {
+        let center = Point2D::new(0.0, 0.0);
+        assert!(band_lean(&circle(2.0, 0.0, 90), &circle(1.0, 0.0, 90), center).abs() < 1e-9);
+        assert!(band_lean(&circle(2.0, 0.3, 90), &circle(1.0, 0.0, 90), center) > 0.0);
+        assert!(band_lean(&circle(2.0, -0.3, 90), &circle(1.0, 0.0, 90), center) < 0.0);
+        assert_eq!(shade_index(0.0), SHADE_GRADIENTS / 2);
+        assert_eq!(shade_index(1.0), SHADE_GRADIENTS - 1);
+        assert_eq!(shade_index(-1.0), 0);
+    }
+
+    #[test]
+    fn test_band_path_winds_inner_ring_against_outer() {
+        let outer = circle(2.0, 0.0, 12);
+        let writer = SvgWriter::new((-2.0, -2.0, 2.0, 2.0), 0.0);
+        for inner in [
+            circle(1.0, 0.0, 12),
+            circle(1.0, 0.0, 12).into_iter().rev().collect(),
+        ] {
+            let d = band_path(&writer, &outer, &inner, 0)
+                .get_attributes()
+                .get("d")
+                .unwrap()
+                .to_string();
+            let areas: Vec<f64> = d
+                .split('M')
+                .filter(|sub| !sub.trim().is_empty())
+                .map(|sub| {
+                    let points: Vec<Point2D> = sub
+                        .split(['L', 'z'])
+                        .filter_map(|xy| xy.trim().split_once(','))
+                        .map(|(x, y)| Point2D::new(x.parse().unwrap(), y.parse().unwrap()))
+                        .collect();
+                    polygon_area(&points)
+                })
+                .collect();
+            assert_eq!(areas.len(), 2);
+            assert!(areas[0] * areas[1] < 0.0);
+        }
+    }
+}
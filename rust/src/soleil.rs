@@ -0,0 +1,576 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_polylines, polar_to_cartesian,
+    polyline_is_degenerate, scale_polylines, simplified_layer, simplify_polylines, ExportConfig,
+    PatternLayer, Point2D, PointsTotal, SpirographError, StoredLayer, DEDUPE_EPSILON,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
+
+/// Configuration for the soleil (sunray) dial finish
+///
+/// Rays run outwards from `inner_radius` to `outer_radius`, evenly spaced
+/// around the center. Each ray leaves its inner end pointing straight out
+/// and bends along a circular arc of the given `curvature`; every ray ends
+/// exactly on `outer_radius`. With a `taper`, rays are drawn as thin closed
+/// wedges that widen from a point at the inner end.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoleilConfig {
+    /// Number of rays around the dial
+    pub num_rays: usize,
+    /// Radius the rays start from in mm (0 starts them at the center)
+    pub inner_radius: f64,
+    /// Radius the rays end on in mm
+    pub outer_radius: f64,
+    /// Curvature of each ray in 1/mm, the inverse of its arc radius;
+    /// positive bends counter-clockwise, 0 draws straight rays
+    pub curvature: f64,
+    /// Width of each ray at `outer_radius` in mm; 0 draws a single line,
+    /// anything more a closed wedge
+    pub taper: f64,
+    /// Number of steps along each ray (and along each side of a wedge)
+    pub resolution: PointsTotal,
+}
+
+impl Default for SoleilConfig {
+    fn default() -> Self {
+        SoleilConfig {
+            num_rays: 180,
+            inner_radius: 1.5,
+            outer_radius: 15.0,
+            curvature: 0.0,
+            taper: 0.0,
+            resolution: PointsTotal(32),
+        }
+    }
+}
+
+impl SoleilConfig {
+    /// Create a new soleil configuration
+    ///
+    /// # Arguments
+    /// * `num_rays` - Number of rays around the dial
+    /// * `inner_radius` - Radius the rays start from in mm
+    /// * `outer_radius` - Radius the rays end on in mm
+    pub fn new(num_rays: usize, inner_radius: f64, outer_radius: f64) -> Self {
+        SoleilConfig {
+            num_rays,
+            inner_radius,
+            outer_radius,
+            ..Default::default()
+        }
+    }
+
+    /// Set the ray curvature in 1/mm (positive bends counter-clockwise)
+    pub fn with_curvature(mut self, curvature: f64) -> Self {
+        self.curvature = curvature;
+        self
+    }
+
+    /// Set the ray width at the outer radius in mm (0 for single lines)
+    pub fn with_taper(mut self, taper: f64) -> Self {
+        self.taper = taper;
+        self
+    }
+
+    /// Set the resolution (steps along each ray)
+    pub fn with_resolution(mut self, resolution: impl Into<PointsTotal>) -> Self {
+        self.resolution = resolution.into();
+        self
+    }
+
+    /// Check the ray count, the radii, the curvature, the taper and the
+    /// resolution
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.num_rays == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "num_rays must be at least 1".to_string(),
+            ));
+        }
+        if !(self.inner_radius.is_finite() && self.inner_radius >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "inner_radius must be non-negative".to_string(),
+            ));
+        }
+        if !(self.outer_radius.is_finite() && self.outer_radius > self.inner_radius) {
+            return Err(SpirographError::InvalidParameter(
+                "outer_radius must be greater than inner_radius".to_string(),
+            ));
+        }
+        if !self.curvature.is_finite() {
+            return Err(SpirographError::InvalidParameter(
+                "curvature must be finite".to_string(),
+            ));
+        }
+        if self.ray_sweep().is_none() {
+            return Err(SpirographError::InvalidParameter(
+                "curvature is too tight for the rays to reach outer_radius".to_string(),
+            ));
+        }
+        if !(self.taper.is_finite() && self.taper >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "taper must be non-negative".to_string(),
+            ));
+        }
+        if self.resolution.get() < 1 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Angle of the inner end of ray `index`, counter-clockwise from the
+    /// +x axis
+    pub fn ray_angle(&self, index: usize) -> f64 {
+        2.0 * PI * (index as f64) / (self.num_rays as f64)
+    }
+
+    /// Angle a curved ray turns through about its arc's center on the way
+    /// to `outer_radius`, 0 for straight rays, or `None` when the arc
+    /// never gets that far out or the radii are out of order
+    fn ray_sweep(&self) -> Option<f64> {
+        let (r0, r1) = (self.inner_radius, self.outer_radius);
+        if !(r0 >= 0.0 && r1 > r0) {
+            return None;
+        }
+        if self.curvature == 0.0 {
+            return Some(0.0);
+        }
+        // The arc's center sits rho to the side of the inner end, d from the
+        // dial center; after turning phi the arc is
+        // sqrt(d² + rho² - 2·d·rho·cos(phi + gamma)) out, growing until
+        // phi + gamma reaches a half turn
+        let rho = 1.0 / self.curvature.abs();
+        let d = r0.hypot(rho);
+        let cos_end = (d * d + rho * rho - r1 * r1) / (2.0 * d * rho);
+        if cos_end < -1.0 {
+            return None;
+        }
+        let gamma = (r0 / d).asin();
+        Some(cos_end.min(1.0).acos() - gamma)
+    }
+
+    /// Points along the centerline of ray `index` about `center`, from the
+    /// inner radius to exactly the outer radius
+    fn ray_centerline(&self, index: usize, center: Point2D, sweep: f64) -> Vec<Point2D> {
+        let steps = self.resolution.get();
+        let (sin_a, cos_a) = self.ray_angle(index).sin_cos();
+        let (r0, r1) = (self.inner_radius, self.outer_radius);
+
+        let mut points: Vec<Point2D> = (0..=steps)
+            .map(|k| {
+                let t = (k as f64) / (steps as f64);
+                // Along the ray (radial) and to its left (counter-clockwise)
+                let (along, side) = if self.curvature == 0.0 {
+                    (r0 + t * (r1 - r0), 0.0)
+                } else {
+                    let rho = 1.0 / self.curvature;
+                    let phi = t * sweep;
+                    (r0 + rho.abs() * phi.sin(), rho * (1.0 - phi.cos()))
+                };
+                Point2D::new(along * cos_a - side * sin_a, along * sin_a + side * cos_a)
+            })
+            .collect();
+        // Put the outer end on the circle exactly, whatever the rounding
+        let end = &mut points[steps];
+        let scale = r1 / end.x.hypot(end.y);
+        *end = Point2D::new(end.x * scale, end.y * scale);
+
+        points
+            .into_iter()
+            .map(|p| Point2D::new(center.x + p.x, center.y + p.y))
+            .collect()
+    }
+
+    /// Every ray about `center`, counter-clockwise from the +x axis
+    ///
+    /// Each ray is an open line from the inner to the outer radius, or,
+    /// with a taper, a closed wedge starting and ending on its inner point.
+    /// Empty when the radii or curvature leave no ray to draw.
+    pub(crate) fn rays(&self, center: Point2D) -> Vec<Vec<Point2D>> {
+        let Some(sweep) = self.ray_sweep() else {
+            return Vec::new();
+        };
+        (0..self.num_rays)
+            .map(|index| {
+                let line = self.ray_centerline(index, center, sweep);
+                if self.taper > 0.0 {
+                    self.wedge(&line, center)
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    /// A closed wedge about the centerline `line`, `taper` wide at its
+    /// outer end and narrowing to a point at its inner end
+    ///
+    /// Each side point is the centerline point turned about `center`, so
+    /// both outer corners stay on the outer radius.
+    fn wedge(&self, line: &[Point2D], center: Point2D) -> Vec<Point2D> {
+        let steps = line.len() - 1;
+        let side = |k: usize, sign: f64| {
+            let p = line[k];
+            let (dx, dy) = (p.x - center.x, p.y - center.y);
+            let r = dx.hypot(dy);
+            if k == 0 || r == 0.0 {
+                return p;
+            }
+            let half_width = 0.5 * self.taper * (k as f64) / (steps as f64);
+            let (sin_t, cos_t) = (sign * half_width / r).sin_cos();
+            Point2D::new(
+                center.x + dx * cos_t - dy * sin_t,
+                center.y + dx * sin_t + dy * cos_t,
+            )
+        };
+        (0..=steps)
+            .map(|k| side(k, 1.0))
+            .chain((0..=steps).rev().map(|k| side(k, -1.0)))
+            .collect()
+    }
+}
+
+/// A soleil (sunray) pattern layer
+///
+/// Generates one line or wedge per ray around the layer's center; see
+/// `SoleilConfig` for the layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoleilLayer {
+    pub config: SoleilConfig,
+    pub center_x: f64,
+    pub center_y: f64,
+    #[serde(skip)]
+    rays: Vec<Vec<Point2D>>,
+}
+
+impl SoleilLayer {
+    /// Create a new soleil layer centered at origin
+    pub fn new(config: SoleilConfig) -> Result<Self, SpirographError> {
+        Self::new_with_center(config, 0.0, 0.0)
+    }
+
+    /// Create a new soleil layer with a custom center point
+    pub fn new_with_center(
+        config: SoleilConfig,
+        center_x: f64,
+        center_y: f64,
+    ) -> Result<Self, SpirographError> {
+        config.validate()?;
+
+        Ok(SoleilLayer {
+            config,
+            center_x,
+            center_y,
+            rays: Vec::new(),
+        })
+    }
+
+    /// Create a soleil layer positioned at a given angle and distance from origin
+    pub fn new_at_polar(
+        config: SoleilConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (center_x, center_y) = polar_to_cartesian(angle, distance);
+        Self::new_with_center(config, center_x, center_y)
+    }
+
+    /// Create a soleil layer positioned at a clock position (like hour hand)
+    ///
+    /// # Arguments
+    /// * `config` - Soleil configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn new_at_clock(
+        config: SoleilConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (center_x, center_y) = clock_to_cartesian(hour, minute, distance);
+        Self::new_with_center(config, center_x, center_y)
+    }
+
+    /// Generate the soleil pattern
+    pub fn generate(&mut self) {
+        self.rays = self.config.rays(Point2D::new(self.center_x, self.center_y));
+        dedupe_polylines(&mut self.rays, DEDUPE_EPSILON);
+        debug_assert_finite("SoleilLayer", &self.rays);
+    }
+
+    /// Get the generated rays, one line or closed wedge per ray
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.rays
+    }
+
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.rays
+    }
+
+    /// Multiply every length (config, center and generated rays) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.inner_radius *= factor;
+        self.config.outer_radius *= factor;
+        self.config.curvature /= factor;
+        self.config.taper *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.rays, factor);
+    }
+
+    /// Export the pattern to SVG format
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+
+        if self.rays.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.rays.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        for ray in &layer.rays {
+            if polyline_is_degenerate(ray) {
+                continue;
+            }
+
+            writer.add_polyline(ray, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+            });
+        }
+
+        writer.save(filename)
+    }
+}
+
+impl PatternLayer for SoleilLayer {
+    fn generate(&mut self) {
+        SoleilLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for SoleilLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        SoleilLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for SoleilLayer {
+    const KIND: LayerKind = LayerKind::Soleil;
+}
+
+impl Progressive for SoleilLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
+        }
+
+        // The ray count is the finish; only the sampling along each ray is reduced
+        let mut coarse = self.clone();
+        coarse.config.resolution =
+            PointsTotal(scale_count(self.config.resolution.get(), fraction, 4));
+        coarse.generate();
+        self.rays = coarse.rays;
+    }
+}
+
+impl PointCounted for SoleilLayer {
+    fn point_count(&self) -> usize {
+        self.rays.iter().map(Vec::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn radius(p: Point2D) -> f64 {
+        p.x.hypot(p.y)
+    }
+
+    #[test]
+    fn test_soleil_invalid_params() {
+        assert!(SoleilLayer::new(SoleilConfig::default()).is_ok());
+        assert!(SoleilLayer::new(SoleilConfig::new(0, 1.0, 10.0)).is_err());
+        assert!(SoleilLayer::new(SoleilConfig::new(60, 10.0, 10.0)).is_err());
+        assert!(SoleilLayer::new(SoleilConfig::new(60, 12.0, 10.0)).is_err());
+        assert!(SoleilLayer::new(SoleilConfig::new(60, -1.0, 10.0)).is_err());
+        let negative_taper = SoleilConfig::new(60, 1.0, 10.0).with_taper(-0.1);
+        assert!(SoleilLayer::new(negative_taper).is_err());
+        // An arc of radius 2 from radius 1 never gets past 1 + 2·2 ≈ 5.1
+        let too_tight = SoleilConfig::new(60, 1.0, 10.0).with_curvature(0.5);
+        assert!(SoleilLayer::new(too_tight).is_err());
+        let gentle = SoleilConfig::new(60, 1.0, 10.0).with_curvature(-0.05);
+        assert!(SoleilLayer::new(gentle).is_ok());
+    }
+
+    #[test]
+    fn test_straight_rays_run_from_inner_to_outer_radius() {
+        let config = SoleilConfig::new(24, 2.0, 12.0).with_resolution(8);
+        let mut layer = SoleilLayer::new(config.clone()).unwrap();
+        layer.generate();
+        assert_eq!(layer.lines().len(), 24);
+
+        for (i, ray) in layer.lines().iter().enumerate() {
+            assert_eq!(ray.len(), 9);
+            assert!((radius(ray[0]) - 2.0).abs() < 1e-12);
+            assert!((radius(ray[8]) - 12.0).abs() < 1e-12);
+            let angle = config.ray_angle(i);
+            for p in ray {
+                let across = -p.x * angle.sin() + p.y * angle.cos();
+                assert!(across.abs() < 1e-9, "ray {} is not straight", i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_curved_rays_follow_an_arc_and_stop_at_outer_radius() {
+        for curvature in [0.08, -0.08] {
+            let config = SoleilConfig::new(12, 3.0, 14.0)
+                .with_curvature(curvature)
+                .with_resolution(40);
+            let mut layer = SoleilLayer::new_with_center(config.clone(), 1.0, -2.0).unwrap();
+            layer.generate();
+
+            let center = Point2D::new(1.0, -2.0);
+            let rho = 1.0 / curvature;
+            for (i, ray) in layer.lines().iter().enumerate() {
+                let offset = |p: Point2D| Point2D::new(p.x - center.x, p.y - center.y);
+                assert!((radius(offset(ray[0])) - 3.0).abs() < 1e-12);
+                assert!((radius(offset(ray[40])) - 14.0).abs() < 1e-12);
+
+                // Every point is rho from the arc's center, beside the inner end
+                let angle = config.ray_angle(i);
+                let arc_center = Point2D::new(
+                    3.0 * angle.cos() - rho * angle.sin(),
+                    3.0 * angle.sin() + rho * angle.cos(),
+                );
+                for &p in ray {
+                    let p = offset(p);
+                    let d = (p.x - arc_center.x).hypot(p.y - arc_center.y);
+                    assert!((d - rho.abs()).abs() < 1e-9, "ray {} leaves its arc", i);
+                }
+                // Radii only grow along the ray, so it stops at the first crossing
+                for pair in ray.windows(2) {
+                    assert!(radius(offset(pair[1])) > radius(offset(pair[0])));
+                }
+                // Positive curvature bends counter-clockwise
+                let end = offset(ray[40]);
+                let turn = (end.y * angle.cos() - end.x * angle.sin()) * curvature.signum();
+                assert!(turn > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tapered_rays_are_closed_wedges() {
+        let config = SoleilConfig::new(8, 0.0, 10.0)
+            .with_taper(0.4)
+            .with_resolution(10);
+        let mut layer = SoleilLayer::new(config).unwrap();
+        layer.generate();
+        assert_eq!(layer.lines().len(), 8);
+
+        for wedge in layer.lines() {
+            assert_eq!(wedge.first(), wedge.last());
+            // Both outer corners are on the circle, taper apart along it
+            let corners: Vec<&Point2D> = wedge
+                .iter()
+                .filter(|p| (radius(**p) - 10.0).abs() < 1e-9)
+                .collect();
+            assert_eq!(corners.len(), 2);
+            let gap = (corners[0].x - corners[1].x).hypot(corners[0].y - corners[1].y);
+            assert!((gap - 0.4).abs() < 1e-3);
+            assert!(wedge.iter().all(|p| radius(*p) <= 10.0 + 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_no_rays_when_inner_radius_reaches_outer() {
+        let mut layer = SoleilLayer::new(SoleilConfig::new(12, 2.0, 10.0)).unwrap();
+        layer.config.inner_radius = 10.0;
+        layer.generate();
+        assert!(layer.lines().is_empty());
+        layer.config.inner_radius = 11.0;
+        layer.generate();
+        assert!(layer.lines().is_empty());
+    }
+
+    #[test]
+    fn test_soleil_composes_into_pattern() {
+        use crate::guilloche::GuillochePattern;
+
+        let mut pattern = GuillochePattern::new(30.0).unwrap();
+        pattern
+            .add_soleil_at_clock(SoleilConfig::new(36, 1.0, 20.0), 12, 0, 0.0)
+            .unwrap();
+        pattern.generate();
+        assert_eq!(pattern.kind_count(LayerKind::Soleil), 1);
+        assert_eq!(pattern.soleil_lines()[0].len(), 36);
+        assert_eq!(LayerKind::from_name("soleil"), Some(LayerKind::Soleil));
+    }
+}
@@ -1,31 +1,192 @@
 use std::f64::consts::PI;
 
+use serde::{Deserialize, Serialize};
+
 // Re-export common types for backward compatibility
+use crate::common::svg_writer::DEFAULT_SVG_PRECISION;
+use crate::common::{
+    base_slab_triangles, debug_assert_finite, dedupe_consecutive, polyline_closes,
+    polyline_is_degenerate, simplify_polyline, ExportSurface, PatternLayer, PointsPerRev,
+    RadiusPolicy, DEDUPE_EPSILON,
+};
 pub use crate::common::{
     clock_to_cartesian, validate_radius, ExportConfig, Point2D, Point3D, SpirographError,
 };
 
+/// Fewest points per revolution that still trace a recognizable curve
+const MIN_RESOLUTION: usize = 8;
+
+/// Check the sampling parameters shared by every spirograph constructor
+fn validate_sampling(rotations: usize, resolution: PointsPerRev) -> Result<(), SpirographError> {
+    if rotations == 0 {
+        return Err(SpirographError::InvalidParameter(
+            "rotations must be at least 1".to_string(),
+        ));
+    }
+    if resolution.get() < MIN_RESOLUTION {
+        return Err(SpirographError::InvalidParameter(format!(
+            "resolution must be at least {} points per revolution",
+            MIN_RESOLUTION
+        )));
+    }
+    Ok(())
+}
+
+/// Points traced over `rotations` turns: every step plus the end point,
+/// or none when there are no steps
+fn sample_count(rotations: usize, resolution: PointsPerRev) -> usize {
+    match resolution.over(rotations).get() {
+        0 => 0,
+        steps => steps + 1,
+    }
+}
+
+/// Check there is something to export, telling a missing `generate()` call
+/// apart from a generation that produced no points
+fn check_generated(generated: bool, is_empty: bool) -> Result<(), SpirographError> {
+    if !generated {
+        Err(SpirographError::ExportError(
+            "generate() was never called; call it before exporting".to_string(),
+        ))
+    } else if is_empty {
+        Err(SpirographError::ExportError(
+            "generate() produced no points; check rotations and resolution".to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Which side of the fixed circle the rolling circle runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SpirographMode {
+    /// Rolling inside the fixed circle; the pen stays within R - r + d
+    #[default]
+    Hypotrochoid,
+    /// Rolling outside the fixed circle; the pen reaches R + r + d
+    Epitrochoid,
+}
+
+impl SpirographMode {
+    /// Lower-case name, as used by the Python bindings
+    pub fn name(self) -> &'static str {
+        match self {
+            SpirographMode::Hypotrochoid => "hypotrochoid",
+            SpirographMode::Epitrochoid => "epitrochoid",
+        }
+    }
+
+    /// The mode whose `name()` is `name`
+    pub fn from_name(name: &str) -> Option<SpirographMode> {
+        [SpirographMode::Hypotrochoid, SpirographMode::Epitrochoid]
+            .into_iter()
+            .find(|mode| mode.name() == name)
+    }
+
+    /// Pen position at angle `t` of the rolling circle's center, for a
+    /// fixed circle of radius `outer_r`, a rolling circle of radius
+    /// `inner_r` and a pen `d` from its center
+    fn point(self, outer_r: f64, inner_r: f64, d: f64, t: f64) -> (f64, f64) {
+        match self {
+            SpirographMode::Hypotrochoid => {
+                let k = (outer_r - inner_r) / inner_r;
+                (
+                    (outer_r - inner_r) * t.cos() + d * (k * t).cos(),
+                    (outer_r - inner_r) * t.sin() - d * (k * t).sin(),
+                )
+            }
+            SpirographMode::Epitrochoid => {
+                let k = (outer_r + inner_r) / inner_r;
+                (
+                    (outer_r + inner_r) * t.cos() - d * (k * t).cos(),
+                    (outer_r + inner_r) * t.sin() - d * (k * t).sin(),
+                )
+            }
+        }
+    }
+}
+
+/// Largest denominator tried when reading a radius ratio as a fraction
+const MAX_RATIO_DENOMINATOR: u64 = 1000;
+
+/// How close a fraction must come to the radius ratio to stand for it
+const RATIO_TOLERANCE: f64 = 1e-9;
+
+/// Fewest rotations after which a curve with this `radius_ratio` closes
+///
+/// The ratio is read as the fraction p/q in lowest terms (the closest
+/// continued-fraction convergent with q up to 1000); the curve closes
+/// after p turns, in either mode. Ratios no such fraction matches
+/// closely only come near closing, after the returned number of turns.
+pub fn closure_rotations(radius_ratio: f64) -> usize {
+    // Convergents h/k of the continued fraction of radius_ratio
+    let (mut h_prev, mut h) = (0u64, 1u64);
+    let (mut k_prev, mut k) = (1u64, 0u64);
+    let mut x = radius_ratio;
+    loop {
+        let a = x.floor();
+        let (h_next, k_next) = (a as u64 * h + h_prev, a as u64 * k + k_prev);
+        if k_next > MAX_RATIO_DENOMINATOR {
+            break;
+        }
+        (h_prev, h, k_prev, k) = (h, h_next, k, k_next);
+        let fraction = x - a;
+        if (h as f64 / k as f64 - radius_ratio).abs() < RATIO_TOLERANCE
+            || fraction < RATIO_TOLERANCE
+        {
+            break;
+        }
+        x = 1.0 / fraction;
+    }
+    (h as usize).max(1)
+}
+
 /// Horizontal Spirograph - Traditional hypotrochoid/epitrochoid patterns
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HorizontalSpirograph {
-    pub outer_radius: f64,   // R - outer circle radius (26-44mm)
-    pub radius_ratio: f64,   // r/R - inner circle radius ratio
-    pub point_distance: f64, // d - drawing point distance
-    pub rotations: usize,    // Number of rotations/revolutions
-    pub resolution: usize,   // Points per revolution
-    pub center_x: f64,       // X coordinate of center point
-    pub center_y: f64,       // Y coordinate of center point
-    points: Vec<Point2D>,    // Generated points
+    pub outer_radius: f64,        // R - outer circle radius (see radius_policy)
+    pub radius_ratio: f64,        // r/R - inner circle radius ratio
+    pub point_distance: f64,      // d - drawing point distance
+    pub rotations: usize,         // Number of rotations/revolutions
+    pub resolution: PointsPerRev, // Points per revolution
+    pub center_x: f64,            // X coordinate of center point
+    pub center_y: f64,            // Y coordinate of center point
+    #[serde(default)]
+    pub mode: SpirographMode, // Rolling inside or outside the fixed circle
+    #[serde(default)]
+    pub radius_policy: RadiusPolicy, // Radii outer_radius may take (default 26-44mm)
+    #[serde(skip)]
+    points: Vec<Point2D>, // Generated points
+    #[serde(skip)]
+    generated: bool,
 }
 
 impl HorizontalSpirograph {
     /// Create a new horizontal spirograph centered at origin
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{polyline_closes, HorizontalSpirograph};
+    ///
+    /// // r/R = 3/10 closes after three turns
+    /// let mut spiro = HorizontalSpirograph::new(40.0, 0.3, 8.0, 3, 360).unwrap();
+    /// spiro.generate();
+    /// assert_eq!(spiro.points().len(), 3 * 360 + 1);
+    /// assert!(polyline_closes(spiro.points()));
+    ///
+    /// // The pen never reaches past R - r + d
+    /// # use turtles::doctest_support::max_radius;
+    /// assert!(max_radius(std::slice::from_ref(spiro.points()), (0.0, 0.0)) <= 28.0 + 8.0 + 1e-9);
+    ///
+    /// let svg = spiro.to_svg_string().unwrap();
+    /// assert_eq!(svg.matches("<path").count(), 1);
+    /// ```
     pub fn new(
         outer_radius: f64,
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
     ) -> Result<Self, SpirographError> {
         Self::new_with_center(
             outer_radius,
@@ -44,34 +205,109 @@ impl HorizontalSpirograph {
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        validate_radius(outer_radius)?;
+        Self::build(
+            outer_radius,
+            radius_ratio,
+            point_distance,
+            rotations,
+            resolution,
+            center_x,
+            center_y,
+            RadiusPolicy::Watch,
+        )
+    }
 
-        if radius_ratio <= 0.0 || radius_ratio >= 1.0 {
+    /// Create a new horizontal spirograph centered at origin whose outer
+    /// radius is checked by `policy` instead of the 26-44mm wristwatch range
+    pub fn new_with_policy(
+        outer_radius: f64,
+        radius_ratio: f64,
+        point_distance: f64,
+        rotations: usize,
+        resolution: impl Into<PointsPerRev>,
+        policy: RadiusPolicy,
+    ) -> Result<Self, SpirographError> {
+        Self::build(
+            outer_radius,
+            radius_ratio,
+            point_distance,
+            rotations,
+            resolution,
+            0.0,
+            0.0,
+            policy,
+        )
+    }
+
+    /// Build and validate a spirograph
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        outer_radius: f64,
+        radius_ratio: f64,
+        point_distance: f64,
+        rotations: usize,
+        resolution: impl Into<PointsPerRev>,
+        center_x: f64,
+        center_y: f64,
+        radius_policy: RadiusPolicy,
+    ) -> Result<Self, SpirographError> {
+        let spirograph = HorizontalSpirograph {
+            outer_radius,
+            radius_ratio,
+            point_distance,
+            rotations,
+            resolution: resolution.into(),
+            center_x,
+            center_y,
+            mode: SpirographMode::Hypotrochoid,
+            radius_policy,
+            points: Vec::new(),
+            generated: false,
+        };
+        spirograph.validate()?;
+        Ok(spirograph)
+    }
+
+    /// Roll the circle on the given side of the fixed circle
+    pub fn with_mode(mut self, mode: SpirographMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Fewest rotations after which the curve closes; see
+    /// [`closure_rotations`]
+    pub fn closure_rotations(&self) -> usize {
+        closure_rotations(self.radius_ratio)
+    }
+
+    /// Check the outer radius, radius ratio and sampling parameters
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        self.validate_scaled(1.0)
+    }
+
+    /// `validate` for lengths in units of `mm_per_unit` millimeters
+    pub(crate) fn validate_scaled(&self, mm_per_unit: f64) -> Result<(), SpirographError> {
+        self.radius_policy.check(self.outer_radius * mm_per_unit)?;
+
+        if self.radius_ratio <= 0.0 || self.radius_ratio >= 1.0 {
             return Err(SpirographError::InvalidParameter(
                 "radius_ratio must be between 0 and 1".to_string(),
             ));
         }
 
-        if point_distance < 0.0 {
+        validate_sampling(self.rotations, self.resolution)?;
+
+        if self.point_distance < 0.0 {
             return Err(SpirographError::InvalidParameter(
                 "point_distance must be positive".to_string(),
             ));
         }
 
-        Ok(HorizontalSpirograph {
-            outer_radius,
-            radius_ratio,
-            point_distance,
-            rotations,
-            resolution,
-            center_x,
-            center_y,
-            points: Vec::new(),
-        })
+        Ok(())
     }
 
     /// Create a spirograph positioned at a given angle and distance from origin
@@ -81,7 +317,7 @@ impl HorizontalSpirograph {
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         angle: f64,
         distance: f64,
     ) -> Result<Self, SpirographError> {
@@ -109,12 +345,13 @@ impl HorizontalSpirograph {
     /// * `hour` - Hour position (1-12, where 12 is at top)
     /// * `minute` - Minute position (0-59)
     /// * `distance` - Distance from center of watch face
+    #[allow(clippy::too_many_arguments)]
     pub fn new_at_clock(
         outer_radius: f64,
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         hour: u32,
         minute: u32,
         distance: f64,
@@ -137,24 +374,24 @@ impl HorizontalSpirograph {
         let outer_r = self.outer_radius;
         let d = self.point_distance;
 
-        let total_points = self.rotations * self.resolution;
+        let total_points = sample_count(self.rotations, self.resolution);
         self.points.clear();
         self.points.reserve(total_points);
 
         for i in 0..total_points {
-            let t = 2.0 * PI * (i as f64) / (self.resolution as f64);
+            let t = 2.0 * PI * (i as f64) / (self.resolution.get() as f64);
 
-            // Hypotrochoid formula
-            let x = (outer_r - inner_radius) * t.cos()
-                + d * (((outer_r - inner_radius) / inner_radius) * t).cos();
-            let y = (outer_r - inner_radius) * t.sin()
-                - d * (((outer_r - inner_radius) / inner_radius) * t).sin();
+            let (x, y) = self.mode.point(outer_r, inner_radius, d, t);
 
             // Apply center offset
             self.points
                 .push(Point2D::new(x + self.center_x, y + self.center_y));
         }
 
+        dedupe_consecutive(&mut self.points, DEDUPE_EPSILON);
+        self.generated = true;
+        debug_assert_finite("HorizontalSpirograph", std::slice::from_ref(&self.points));
+
         &self.points
     }
 
@@ -163,56 +400,110 @@ impl HorizontalSpirograph {
         &self.points
     }
 
+    /// Mutable access to the generated points, for post-processing
+    pub(crate) fn points_mut(&mut self) -> &mut Vec<Point2D> {
+        &mut self.points
+    }
+
+    /// Drop the generated points, as if `generate()` had never run
+    pub(crate) fn clear(&mut self) {
+        self.points.clear();
+        self.generated = false;
+    }
+
+    /// Multiply every length (radii, center and generated points) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.outer_radius *= factor;
+        self.point_distance *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        for p in &mut self.points {
+            p.x *= factor;
+            p.y *= factor;
+        }
+    }
+
     /// Export pattern as SVG
     pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        if self.points.is_empty() {
-            return Err(SpirographError::ExportError(
-                "No points generated. Call generate() first.".to_string(),
-            ));
-        }
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export pattern as SVG with `config.origin` applied and the curve
+    /// simplified to `config.simplify_tolerance`
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+        check_generated(self.generated, self.points.is_empty())?;
 
-        svg_export::export_svg(filename, &self.points, self.outer_radius)
+        let points = simplify_polyline(&self.points, config.simplify_tolerance);
+        svg_export::export_svg(filename, &points, self.outer_radius, config)
+            .map_err(|e| SpirographError::ExportError(format!("SVG export failed: {}", e)))
+    }
+
+    /// The SVG export as a string, without writing a file
+    pub fn to_svg_string(&self) -> Result<String, SpirographError> {
+        check_generated(self.generated, self.points.is_empty())?;
+        svg_export::svg_writer(&self.points, self.outer_radius, DEFAULT_SVG_PRECISION)
+            .map(|writer| writer.into_document().to_string())
             .map_err(|e| SpirographError::ExportError(format!("SVG export failed: {}", e)))
     }
 
     /// Export pattern as STL with depth
     pub fn to_stl(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
-        if self.points.is_empty() {
-            return Err(SpirographError::ExportError(
-                "No points generated. Call generate() first.".to_string(),
-            ));
-        }
+        check_generated(self.generated, self.points.is_empty())?;
 
-        stl::export_stl(filename, &self.points, config)
+        let center = Point2D::new(self.center_x, self.center_y);
+        stl::export_stl(filename, &self.points, center, config)
             .map_err(|e| SpirographError::ExportError(format!("STL export failed: {}", e)))
     }
 
     /// Export pattern as STEP (placeholder - requires full STEP implementation)
     pub fn to_step(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
-        if self.points.is_empty() {
-            return Err(SpirographError::ExportError(
-                "No points generated. Call generate() first.".to_string(),
-            ));
-        }
+        check_generated(self.generated, self.points.is_empty())?;
 
         step::export_step(filename, &self.points, config)
             .map_err(|e| SpirographError::ExportError(format!("STEP export failed: {}", e)))
     }
 }
 
+impl PatternLayer for HorizontalSpirograph {
+    fn generate(&mut self) {
+        HorizontalSpirograph::generate(self);
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        HorizontalSpirograph::validate(self)
+    }
+
+    /// The curve as one polyline
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        std::slice::from_ref(self.points())
+    }
+}
+
 /// Vertical Spirograph - Spirograph patterns with vertical wave modulation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerticalSpirograph {
     pub outer_radius: f64,
     pub radius_ratio: f64,
     pub point_distance: f64,
     pub rotations: usize,
-    pub resolution: usize,
+    pub resolution: PointsPerRev,
     pub wave_amplitude: f64, // Vertical wave amplitude
     pub wave_frequency: f64, // Vertical wave frequency
     pub center_x: f64,       // X coordinate of center point
     pub center_y: f64,       // Y coordinate of center point
+    #[serde(default)]
+    pub mode: SpirographMode, // Rolling inside or outside the fixed circle
+    #[serde(default)]
+    pub radius_policy: RadiusPolicy, // Radii outer_radius may take (default 26-44mm)
+    #[serde(skip)]
     points: Vec<Point2D>,
+    #[serde(skip)]
+    generated: bool,
 }
 
 impl VerticalSpirograph {
@@ -222,7 +513,7 @@ impl VerticalSpirograph {
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         wave_amplitude: f64,
         wave_frequency: f64,
     ) -> Result<Self, SpirographError> {
@@ -240,26 +531,46 @@ impl VerticalSpirograph {
     }
 
     /// Create a new vertical spirograph with a custom center point
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_center(
         outer_radius: f64,
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         wave_amplitude: f64,
         wave_frequency: f64,
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        validate_radius(outer_radius)?;
-
-        if radius_ratio <= 0.0 || radius_ratio >= 1.0 {
-            return Err(SpirographError::InvalidParameter(
-                "radius_ratio must be between 0 and 1".to_string(),
-            ));
-        }
+        Self::build(
+            outer_radius,
+            radius_ratio,
+            point_distance,
+            rotations,
+            resolution,
+            wave_amplitude,
+            wave_frequency,
+            center_x,
+            center_y,
+            RadiusPolicy::Watch,
+        )
+    }
 
-        Ok(VerticalSpirograph {
+    /// Create a new vertical spirograph centered at origin whose outer
+    /// radius is checked by `policy` instead of the 26-44mm wristwatch range
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_policy(
+        outer_radius: f64,
+        radius_ratio: f64,
+        point_distance: f64,
+        rotations: usize,
+        resolution: impl Into<PointsPerRev>,
+        wave_amplitude: f64,
+        wave_frequency: f64,
+        policy: RadiusPolicy,
+    ) -> Result<Self, SpirographError> {
+        Self::build(
             outer_radius,
             radius_ratio,
             point_distance,
@@ -267,19 +578,85 @@ impl VerticalSpirograph {
             resolution,
             wave_amplitude,
             wave_frequency,
+            0.0,
+            0.0,
+            policy,
+        )
+    }
+
+    /// Build and validate a spirograph
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        outer_radius: f64,
+        radius_ratio: f64,
+        point_distance: f64,
+        rotations: usize,
+        resolution: impl Into<PointsPerRev>,
+        wave_amplitude: f64,
+        wave_frequency: f64,
+        center_x: f64,
+        center_y: f64,
+        radius_policy: RadiusPolicy,
+    ) -> Result<Self, SpirographError> {
+        let spirograph = VerticalSpirograph {
+            outer_radius,
+            radius_ratio,
+            point_distance,
+            rotations,
+            resolution: resolution.into(),
+            wave_amplitude,
+            wave_frequency,
             center_x,
             center_y,
+            mode: SpirographMode::Hypotrochoid,
+            radius_policy,
             points: Vec::new(),
-        })
+            generated: false,
+        };
+        spirograph.validate()?;
+        Ok(spirograph)
+    }
+
+    /// Roll the circle on the given side of the fixed circle
+    pub fn with_mode(mut self, mode: SpirographMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Fewest rotations after which the curve closes; see
+    /// [`closure_rotations`]
+    pub fn closure_rotations(&self) -> usize {
+        closure_rotations(self.radius_ratio)
+    }
+
+    /// Check the outer radius, radius ratio and sampling parameters
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        self.validate_scaled(1.0)
+    }
+
+    /// `validate` for lengths in units of `mm_per_unit` millimeters
+    pub(crate) fn validate_scaled(&self, mm_per_unit: f64) -> Result<(), SpirographError> {
+        self.radius_policy.check(self.outer_radius * mm_per_unit)?;
+
+        if self.radius_ratio <= 0.0 || self.radius_ratio >= 1.0 {
+            return Err(SpirographError::InvalidParameter(
+                "radius_ratio must be between 0 and 1".to_string(),
+            ));
+        }
+
+        validate_sampling(self.rotations, self.resolution)?;
+
+        Ok(())
     }
 
     /// Create a spirograph positioned at a given angle and distance from origin
+    #[allow(clippy::too_many_arguments)]
     pub fn new_at_polar(
         outer_radius: f64,
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         wave_amplitude: f64,
         wave_frequency: f64,
         angle: f64,
@@ -306,12 +683,13 @@ impl VerticalSpirograph {
     /// * `hour` - Hour position (1-12, where 12 is at top)
     /// * `minute` - Minute position (0-59)
     /// * `distance` - Distance from center of watch face
+    #[allow(clippy::too_many_arguments)]
     pub fn new_at_clock(
         outer_radius: f64,
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         wave_amplitude: f64,
         wave_frequency: f64,
         hour: u32,
@@ -338,18 +716,14 @@ impl VerticalSpirograph {
         let outer_r = self.outer_radius;
         let d = self.point_distance;
 
-        let total_points = self.rotations * self.resolution;
+        let total_points = sample_count(self.rotations, self.resolution);
         self.points.clear();
         self.points.reserve(total_points);
 
         for i in 0..total_points {
-            let t = 2.0 * PI * (i as f64) / (self.resolution as f64);
+            let t = 2.0 * PI * (i as f64) / (self.resolution.get() as f64);
 
-            // Base hypotrochoid
-            let base_x = (outer_r - inner_radius) * t.cos()
-                + d * (((outer_r - inner_radius) / inner_radius) * t).cos();
-            let base_y = (outer_r - inner_radius) * t.sin()
-                - d * (((outer_r - inner_radius) / inner_radius) * t).sin();
+            let (base_x, base_y) = self.mode.point(outer_r, inner_radius, d, t);
 
             // Add vertical wave modulation
             let wave = self.wave_amplitude * (self.wave_frequency * t).sin();
@@ -359,6 +733,10 @@ impl VerticalSpirograph {
             self.points.push(Point2D::new(x, y));
         }
 
+        dedupe_consecutive(&mut self.points, DEDUPE_EPSILON);
+        self.generated = true;
+        debug_assert_finite("VerticalSpirograph", std::slice::from_ref(&self.points));
+
         &self.points
     }
 
@@ -366,63 +744,134 @@ impl VerticalSpirograph {
         &self.points
     }
 
-    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        if self.points.is_empty() {
-            return Err(SpirographError::ExportError(
-                "No points generated. Call generate() first.".to_string(),
-            ));
+    /// Mutable access to the generated points, for post-processing
+    pub(crate) fn points_mut(&mut self) -> &mut Vec<Point2D> {
+        &mut self.points
+    }
+
+    /// Drop the generated points, as if `generate()` had never run
+    pub(crate) fn clear(&mut self) {
+        self.points.clear();
+        self.generated = false;
+    }
+
+    /// Multiply every length (radii, center and generated points) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.outer_radius *= factor;
+        self.point_distance *= factor;
+        self.wave_amplitude *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        for p in &mut self.points {
+            p.x *= factor;
+            p.y *= factor;
         }
+    }
 
-        svg_export::export_svg(filename, &self.points, self.outer_radius)
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export pattern as SVG with `config.origin` applied and the curve
+    /// simplified to `config.simplify_tolerance`
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+        check_generated(self.generated, self.points.is_empty())?;
+
+        let points = simplify_polyline(&self.points, config.simplify_tolerance);
+        svg_export::export_svg(filename, &points, self.outer_radius, config)
+            .map_err(|e| SpirographError::ExportError(format!("SVG export failed: {}", e)))
+    }
+
+    /// The SVG export as a string, without writing a file
+    pub fn to_svg_string(&self) -> Result<String, SpirographError> {
+        check_generated(self.generated, self.points.is_empty())?;
+        svg_export::svg_writer(&self.points, self.outer_radius, DEFAULT_SVG_PRECISION)
+            .map(|writer| writer.into_document().to_string())
             .map_err(|e| SpirographError::ExportError(format!("SVG export failed: {}", e)))
     }
 
     pub fn to_stl(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
-        if self.points.is_empty() {
-            return Err(SpirographError::ExportError(
-                "No points generated. Call generate() first.".to_string(),
-            ));
-        }
+        check_generated(self.generated, self.points.is_empty())?;
 
-        stl::export_stl(filename, &self.points, config)
+        let center = Point2D::new(self.center_x, self.center_y);
+        stl::export_stl(filename, &self.points, center, config)
             .map_err(|e| SpirographError::ExportError(format!("STL export failed: {}", e)))
     }
 
     pub fn to_step(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
-        if self.points.is_empty() {
-            return Err(SpirographError::ExportError(
-                "No points generated. Call generate() first.".to_string(),
-            ));
-        }
+        check_generated(self.generated, self.points.is_empty())?;
 
         step::export_step(filename, &self.points, config)
             .map_err(|e| SpirographError::ExportError(format!("STEP export failed: {}", e)))
     }
 }
 
+impl PatternLayer for VerticalSpirograph {
+    fn generate(&mut self) {
+        VerticalSpirograph::generate(self);
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        VerticalSpirograph::validate(self)
+    }
+
+    /// The curve as one polyline
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        std::slice::from_ref(self.points())
+    }
+}
+
 /// Spherical Spirograph - 3D spirograph patterns projected onto a spherical surface
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SphericalSpirograph {
     pub outer_radius: f64,
     pub radius_ratio: f64,
     pub point_distance: f64,
     pub rotations: usize,
-    pub resolution: usize,
-    pub dome_height: f64,    // Height of the dome
-    pub center_x: f64,       // X coordinate of center point
-    pub center_y: f64,       // Y coordinate of center point
+    pub resolution: PointsPerRev,
+    pub dome_height: f64, // Height of the dome
+    pub center_x: f64,    // X coordinate of center point
+    pub center_y: f64,    // Y coordinate of center point
+    #[serde(default)]
+    pub mode: SpirographMode, // Rolling inside or outside the fixed circle
+    #[serde(default)]
+    pub radius_policy: RadiusPolicy, // Radii outer_radius may take (default 26-44mm)
+    #[serde(skip)]
     points_2d: Vec<Point2D>, // 2D projection
+    #[serde(skip)]
     points_3d: Vec<Point3D>, // 3D points on sphere
+    #[serde(skip)]
+    generated: bool,
 }
 
 impl SphericalSpirograph {
     /// Create a new spherical spirograph centered at origin
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::SphericalSpirograph;
+    ///
+    /// let mut spiro = SphericalSpirograph::new(40.0, 0.3, 8.0, 3, 360, 5.0).unwrap();
+    /// spiro.generate();
+    ///
+    /// // Every point lies on the dome, between its rim and its crown
+    /// assert_eq!(spiro.points_3d().len(), spiro.points_2d().len());
+    /// assert!(spiro.points_3d().iter().all(|p| p.z >= 0.0 && p.z <= 5.0));
+    ///
+    /// let svg = spiro.to_svg_string().unwrap();
+    /// assert!(svg.contains("viewBox"));
+    /// ```
     pub fn new(
         outer_radius: f64,
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         dome_height: f64,
     ) -> Result<Self, SpirographError> {
         Self::new_with_center(
@@ -438,45 +887,126 @@ impl SphericalSpirograph {
     }
 
     /// Create a new spherical spirograph with a custom center point
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_center(
         outer_radius: f64,
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         dome_height: f64,
         center_x: f64,
         center_y: f64,
     ) -> Result<Self, SpirographError> {
-        validate_radius(outer_radius)?;
-
-        if radius_ratio <= 0.0 || radius_ratio >= 1.0 {
-            return Err(SpirographError::InvalidParameter(
-                "radius_ratio must be between 0 and 1".to_string(),
-            ));
-        }
+        Self::build(
+            outer_radius,
+            radius_ratio,
+            point_distance,
+            rotations,
+            resolution,
+            dome_height,
+            center_x,
+            center_y,
+            RadiusPolicy::Watch,
+        )
+    }
 
-        Ok(SphericalSpirograph {
+    /// Create a new spherical spirograph centered at origin whose outer
+    /// radius is checked by `policy` instead of the 26-44mm wristwatch range
+    pub fn new_with_policy(
+        outer_radius: f64,
+        radius_ratio: f64,
+        point_distance: f64,
+        rotations: usize,
+        resolution: impl Into<PointsPerRev>,
+        dome_height: f64,
+        policy: RadiusPolicy,
+    ) -> Result<Self, SpirographError> {
+        Self::build(
             outer_radius,
             radius_ratio,
             point_distance,
             rotations,
             resolution,
             dome_height,
+            0.0,
+            0.0,
+            policy,
+        )
+    }
+
+    /// Build and validate a spirograph
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        outer_radius: f64,
+        radius_ratio: f64,
+        point_distance: f64,
+        rotations: usize,
+        resolution: impl Into<PointsPerRev>,
+        dome_height: f64,
+        center_x: f64,
+        center_y: f64,
+        radius_policy: RadiusPolicy,
+    ) -> Result<Self, SpirographError> {
+        let spirograph = SphericalSpirograph {
+            outer_radius,
+            radius_ratio,
+            point_distance,
+            rotations,
+            resolution: resolution.into(),
+            dome_height,
             center_x,
             center_y,
+            mode: SpirographMode::Hypotrochoid,
+            radius_policy,
             points_2d: Vec::new(),
             points_3d: Vec::new(),
-        })
+            generated: false,
+        };
+        spirograph.validate()?;
+        Ok(spirograph)
+    }
+
+    /// Roll the circle on the given side of the fixed circle
+    pub fn with_mode(mut self, mode: SpirographMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Fewest rotations after which the curve closes; see
+    /// [`closure_rotations`]
+    pub fn closure_rotations(&self) -> usize {
+        closure_rotations(self.radius_ratio)
+    }
+
+    /// Check the outer radius, radius ratio and sampling parameters
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        self.validate_scaled(1.0)
+    }
+
+    /// `validate` for lengths in units of `mm_per_unit` millimeters
+    pub(crate) fn validate_scaled(&self, mm_per_unit: f64) -> Result<(), SpirographError> {
+        self.radius_policy.check(self.outer_radius * mm_per_unit)?;
+
+        if self.radius_ratio <= 0.0 || self.radius_ratio >= 1.0 {
+            return Err(SpirographError::InvalidParameter(
+                "radius_ratio must be between 0 and 1".to_string(),
+            ));
+        }
+
+        validate_sampling(self.rotations, self.resolution)?;
+
+        Ok(())
     }
 
     /// Create a spirograph positioned at a given angle and distance from origin
+    #[allow(clippy::too_many_arguments)]
     pub fn new_at_polar(
         outer_radius: f64,
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         dome_height: f64,
         angle: f64,
         distance: f64,
@@ -501,12 +1031,13 @@ impl SphericalSpirograph {
     /// * `hour` - Hour position (1-12, where 12 is at top)
     /// * `minute` - Minute position (0-59)
     /// * `distance` - Distance from center of watch face
+    #[allow(clippy::too_many_arguments)]
     pub fn new_at_clock(
         outer_radius: f64,
         radius_ratio: f64,
         point_distance: f64,
         rotations: usize,
-        resolution: usize,
+        resolution: impl Into<PointsPerRev>,
         dome_height: f64,
         hour: u32,
         minute: u32,
@@ -528,30 +1059,43 @@ impl SphericalSpirograph {
     /// Minimum distance to prevent division by zero in spherical projection
     const MIN_RADIUS: f64 = 0.0001;
 
+    /// Radius of the dome's base circle; an epitrochoid reaches past R, so
+    /// its dome spans the whole curve
+    fn dome_span(&self) -> f64 {
+        match self.mode {
+            SpirographMode::Hypotrochoid => self.outer_radius,
+            SpirographMode::Epitrochoid => {
+                self.outer_radius * (1.0 + self.radius_ratio) + self.point_distance
+            }
+        }
+    }
+
+    /// Radius of the sphere whose cap rises `dome_height` over a base of
+    /// radius `dome_span()`
+    fn sphere_radius(&self) -> f64 {
+        let span = self.dome_span();
+        (span * span + self.dome_height * self.dome_height) / (2.0 * self.dome_height)
+    }
+
     /// Generate the spherical spirograph pattern
     pub fn generate(&mut self) -> &Vec<Point3D> {
         let inner_radius = self.outer_radius * self.radius_ratio;
         let outer_r = self.outer_radius;
         let d = self.point_distance;
 
-        let total_points = self.rotations * self.resolution;
+        let total_points = sample_count(self.rotations, self.resolution);
         self.points_2d.clear();
         self.points_3d.clear();
         self.points_2d.reserve(total_points);
         self.points_3d.reserve(total_points);
 
-        // Calculate sphere radius for dome projection
-        let sphere_radius =
-            (outer_r * outer_r + self.dome_height * self.dome_height) / (2.0 * self.dome_height);
+        let sphere_radius = self.sphere_radius();
 
         for i in 0..total_points {
-            let t = 2.0 * PI * (i as f64) / (self.resolution as f64);
+            let t = 2.0 * PI * (i as f64) / (self.resolution.get() as f64);
 
-            // Base hypotrochoid in 2D
-            let x_2d = (outer_r - inner_radius) * t.cos()
-                + d * (((outer_r - inner_radius) / inner_radius) * t).cos();
-            let y_2d = (outer_r - inner_radius) * t.sin()
-                - d * (((outer_r - inner_radius) / inner_radius) * t).sin();
+            // Base curve in 2D
+            let (x_2d, y_2d) = self.mode.point(outer_r, inner_radius, d, t);
 
             // Apply center offset for 2D
             self.points_2d
@@ -571,6 +1115,14 @@ impl SphericalSpirograph {
             self.points_3d.push(Point3D::new(x_3d, y_3d, z));
         }
 
+        self.generated = true;
+        debug_assert_finite("SphericalSpirograph", std::slice::from_ref(&self.points_2d));
+        debug_assert!(
+            self.points_3d.iter().all(|p| p.is_finite()),
+            "SphericalSpirograph generated a non-finite 3D point at index {:?}",
+            self.points_3d.iter().position(|p| !p.is_finite())
+        );
+
         &self.points_3d
     }
 
@@ -578,165 +1130,354 @@ impl SphericalSpirograph {
         &self.points_2d
     }
 
+    /// Mutable access to the 2D projection, for post-processing
+    ///
+    /// The 3D points are left as generated.
+    pub(crate) fn points_mut(&mut self) -> &mut Vec<Point2D> {
+        &mut self.points_2d
+    }
+
+    /// Drop the generated points, as if `generate()` had never run
+    pub(crate) fn clear(&mut self) {
+        self.points_2d.clear();
+        self.points_3d.clear();
+        self.generated = false;
+    }
+
     pub fn points_3d(&self) -> &Vec<Point3D> {
         &self.points_3d
     }
 
-    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        if self.points_2d.is_empty() {
-            return Err(SpirographError::ExportError(
-                "No points generated. Call generate() first.".to_string(),
-            ));
+    /// Multiply every length (radii, center and generated points) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.outer_radius *= factor;
+        self.point_distance *= factor;
+        self.dome_height *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        for p in &mut self.points_2d {
+            p.x *= factor;
+            p.y *= factor;
+        }
+        for p in &mut self.points_3d {
+            p.x *= factor;
+            p.y *= factor;
+            p.z *= factor;
         }
+    }
 
-        svg_export::export_svg(filename, &self.points_2d, self.outer_radius)
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export pattern as SVG with `config.origin` applied and the curve
+    /// simplified to `config.simplify_tolerance`
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+        check_generated(self.generated, self.points_2d.is_empty())?;
+
+        let points = simplify_polyline(&self.points_2d, config.simplify_tolerance);
+        svg_export::export_svg(filename, &points, self.outer_radius, config)
             .map_err(|e| SpirographError::ExportError(format!("SVG export failed: {}", e)))
     }
 
+    /// The SVG export of the flat projection as a string, without writing a
+    /// file
+    pub fn to_svg_string(&self) -> Result<String, SpirographError> {
+        check_generated(self.generated, self.points_2d.is_empty())?;
+        svg_export::svg_writer(&self.points_2d, self.outer_radius, DEFAULT_SVG_PRECISION)
+            .map(|writer| writer.into_document().to_string())
+            .map_err(|e| SpirographError::ExportError(format!("SVG export failed: {}", e)))
+    }
+
+    /// Export the curve as STL grooves
+    ///
+    /// With `config.surface` set to `ExportSurface::Dome`, the grooves are
+    /// cut along the sphere's normal into a solid cap rising `dome_height`
+    /// over the dome's base circle, on a skirt `base_thickness` deep.
+    /// Otherwise they hang straight down from the curve over a flat slab.
     pub fn to_stl(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
-        if self.points_3d.is_empty() {
-            return Err(SpirographError::ExportError(
-                "No points generated. Call generate() first.".to_string(),
-            ));
+        check_generated(self.generated, self.points_3d.is_empty())?;
+
+        let center = Point2D::new(self.center_x, self.center_y);
+        match config.surface {
+            ExportSurface::Flat => stl::export_stl_3d(filename, &self.points_3d, center, config),
+            ExportSurface::Dome { segments } => {
+                if segments < 3 {
+                    return Err(SpirographError::InvalidParameter(
+                        "dome segments must be at least 3".to_string(),
+                    ));
+                }
+                let dome = stl::Dome {
+                    center,
+                    span: self.dome_span(),
+                    height: self.dome_height,
+                    segments,
+                };
+                stl::export_stl_dome(filename, &self.points_3d, &dome, config)
+            }
         }
-
-        stl::export_stl_3d(filename, &self.points_3d, config)
-            .map_err(|e| SpirographError::ExportError(format!("STL export failed: {}", e)))
+        .map_err(|e| SpirographError::ExportError(format!("STL export failed: {}", e)))
     }
 
     pub fn to_step(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
-        if self.points_3d.is_empty() {
-            return Err(SpirographError::ExportError(
-                "No points generated. Call generate() first.".to_string(),
-            ));
-        }
+        check_generated(self.generated, self.points_3d.is_empty())?;
 
         step::export_step_3d(filename, &self.points_3d, config)
             .map_err(|e| SpirographError::ExportError(format!("STEP export failed: {}", e)))
     }
 }
 
+impl PatternLayer for SphericalSpirograph {
+    fn generate(&mut self) {
+        SphericalSpirograph::generate(self);
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        SphericalSpirograph::validate(self)
+    }
+
+    /// The curve as one polyline, projected onto the plane
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        std::slice::from_ref(self.points_2d())
+    }
+}
+
 /// Module for SVG export
 mod svg_export {
     use super::*;
-    use ::svg::node::element::path::Data;
-    use ::svg::node::element::Path;
-    use ::svg::Document;
+    use crate::common::svg_writer::SvgWriter;
 
     pub fn export_svg(
         filename: &str,
         points: &[Point2D],
         radius: f64,
+        config: &ExportConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        if points.is_empty() {
-            return Err("No points to export".into());
-        }
-
-        let mut data = Data::new().move_to((points[0].x, points[0].y));
+        svg_writer(points, radius, config.svg_precision)?
+            .with_origin(config.origin)
+            .save(filename)?;
+        Ok(())
+    }
 
-        for point in points.iter().skip(1) {
-            data = data.line_to((point.x, point.y));
+    /// One path through `points` on a square sheet 2.5 × `radius` each side
+    /// of the origin, rounded to `precision` decimals
+    pub fn svg_writer(
+        points: &[Point2D],
+        radius: f64,
+        precision: usize,
+    ) -> Result<SvgWriter, Box<dyn std::error::Error>> {
+        if polyline_is_degenerate(points) {
+            return Err("no drawable geometry".into());
         }
 
-        // Note: Not closing the path to avoid an unwanted line back to start
-        // data = data.close();
-
-        let path = Path::new()
-            .set("fill", "none")
-            .set("stroke", "black")
-            .set("stroke-width", 0.1)
-            .set("d", data);
-
         let size = radius * 2.5;
-        let document = Document::new()
-            .set("viewBox", (-size, -size, size * 2.0, size * 2.0))
-            .set("width", format!("{}mm", size * 2.0))
-            .set("height", format!("{}mm", size * 2.0))
-            .add(path);
-
-        ::svg::save(filename, &document)?;
-        Ok(())
+        let mut writer = SvgWriter::new((-size, -size, size, size), 0.0).with_precision(precision);
+        // Not closed, to avoid an unwanted line back to the start
+        writer.add_polyline(points, |path| {
+            path.set("fill", "none")
+                .set("stroke", "black")
+                .set("stroke-width", 0.1)
+        });
+
+        Ok(writer)
     }
 }
 
 /// Module for STL export
 mod stl {
     use super::*;
-    use stl_io::{Normal, Triangle, Vertex};
+    use crate::groove::{facet, flat_groove_triangles, groove_solid_triangles};
+    use stl_io::Triangle;
 
+    type Vec3 = [f64; 3];
+
+    /// Write the curve as a closed groove solid over a base slab
+    ///
+    /// The groove's cross-section comes from `config.groove_profile()`; a
+    /// curve that returns to its start is swept as a ring.
     pub fn export_stl(
         filename: &str,
         points: &[Point2D],
+        center: Point2D,
         config: &ExportConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        // Create a simple 3D extrusion from 2D points
-        let mut triangles = Vec::new();
-
-        // Create base surface at z=0
-        // Create groove surface at z=-depth
-        let depth = config.depth;
-        let num_points = points.len();
-
-        // For each line segment in the path, create a rectangular groove
-        for i in 0..num_points {
-            let p1 = points[i];
-            let p2 = points[(i + 1) % num_points];
-
-            // Create vertices for the groove
-            let v1_top = Vertex::new([p1.x as f32, p1.y as f32, 0.0]);
-            let v2_top = Vertex::new([p2.x as f32, p2.y as f32, 0.0]);
-            let v1_bottom = Vertex::new([p1.x as f32, p1.y as f32, -depth as f32]);
-            let v2_bottom = Vertex::new([p2.x as f32, p2.y as f32, -depth as f32]);
-
-            // Create triangles for the groove sides
-            let normal = Normal::new([0.0, 0.0, 1.0]);
-
-            // Top face (pointing up)
-            triangles.push(Triangle {
-                normal,
-                vertices: [v1_top, v2_top, v1_bottom],
-            });
-            triangles.push(Triangle {
-                normal,
-                vertices: [v2_top, v2_bottom, v1_bottom],
-            });
-        }
+        let closed = polyline_closes(points);
+        let mut triangles = flat_groove_triangles([(points, closed)], &config.groove_profile()?);
+
+        // Base slab under the grooves
+        triangles.extend(base_slab_triangles(
+            center,
+            points.iter().copied(),
+            0.0,
+            -config.depth,
+            config.base_thickness,
+        ));
+        config.origin.validate()?;
+        config.origin.apply_stl(&mut triangles);
 
         let mut file = std::fs::File::create(filename)?;
         stl_io::write_stl(&mut file, triangles.iter())?;
         Ok(())
     }
 
+    /// As [`export_stl`], with the groove following the curve's height
     pub fn export_stl_3d(
         filename: &str,
         points: &[Point3D],
+        center: Point2D,
         config: &ExportConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        // Similar to 2D but uses 3D points directly
-        let mut triangles = Vec::new();
-        let depth = config.depth;
-        let num_points = points.len();
-
-        for i in 0..num_points {
-            let p1 = points[i];
-            let p2 = points[(i + 1) % num_points];
-
-            let v1_top = Vertex::new([p1.x as f32, p1.y as f32, p1.z as f32]);
-            let v2_top = Vertex::new([p2.x as f32, p2.y as f32, p2.z as f32]);
-            let v1_bottom = Vertex::new([p1.x as f32, p1.y as f32, (p1.z - depth) as f32]);
-            let v2_bottom = Vertex::new([p2.x as f32, p2.y as f32, (p2.z - depth) as f32]);
-
-            let normal = Normal::new([0.0, 0.0, 1.0]);
-
-            triangles.push(Triangle {
-                normal,
-                vertices: [v1_top, v2_top, v1_bottom],
-            });
-            triangles.push(Triangle {
-                normal,
-                vertices: [v2_top, v2_bottom, v1_bottom],
-            });
+        let flat: Vec<Point2D> = points.iter().map(|p| Point2D::new(p.x, p.y)).collect();
+        let mut triangles = groove_solid_triangles(
+            points,
+            polyline_closes(&flat),
+            &config.groove_profile()?,
+            |_| Point3D::new(0.0, 0.0, 1.0),
+        );
+
+        // Base slab under the lowest groove
+        let lowest = points.iter().map(|p| p.z).fold(f64::INFINITY, f64::min);
+        triangles.extend(base_slab_triangles(
+            center,
+            points.iter().map(|p| Point2D::new(p.x, p.y)),
+            0.0,
+            lowest - config.depth,
+            config.base_thickness,
+        ));
+        config.origin.validate()?;
+        config.origin.apply_stl(&mut triangles);
+
+        let mut file = std::fs::File::create(filename)?;
+        stl_io::write_stl(&mut file, triangles.iter())?;
+        Ok(())
+    }
+
+    /// Spherical cap rising `height` over a base circle of radius `span`
+    /// about `center` at z = 0, faceted with `segments` sides around its axis
+    pub struct Dome {
+        pub center: Point2D,
+        pub span: f64,
+        pub height: f64,
+        pub segments: usize,
+    }
+
+    impl Dome {
+        /// Radius of the sphere the cap lies on, as in `generate()`
+        fn sphere_radius(&self) -> f64 {
+            (self.span * self.span + self.height * self.height) / (2.0 * self.height)
+        }
+
+        /// Center of that sphere
+        fn sphere_center(&self) -> Vec3 {
+            [
+                self.center.x,
+                self.center.y,
+                self.height - self.sphere_radius(),
+            ]
+        }
+
+        /// Outward normal of the sphere through `p`
+        fn normal_at(&self, p: Point3D) -> Point3D {
+            let [x, y, z] = self.sphere_center();
+            let r = self.sphere_radius();
+            Point3D::new((p.x - x) / r, (p.y - y) / r, (p.z - z) / r)
+        }
+
+        /// The cap as a closed solid with outward facets
+        ///
+        /// Rings of the cap are spaced about as far apart as the facets
+        /// around its rim, down to the base circle at z = 0. A positive
+        /// `thickness` hangs a cylindrical skirt from the rim to a flat
+        /// bottom at z = -`thickness`; otherwise the bottom closes the rim.
+        pub fn triangles(&self, thickness: f64) -> Vec<Triangle> {
+            let n = self.segments;
+            let sphere_radius = self.sphere_radius();
+            let [cx, cy, cz] = self.sphere_center();
+            // Polar angle of the rim, past 90° for a cap taller than wide
+            let rim_polar = self.span.atan2(sphere_radius - self.height);
+            let step = 2.0 * PI / n as f64;
+            // Arc from apex to rim over the width of a rim facet
+            let rings = ((sphere_radius * rim_polar) / (self.span * step))
+                .ceil()
+                .max(1.0) as usize;
+
+            let ring = |polar: f64, z: Option<f64>| -> Vec<Vec3> {
+                (0..n)
+                    .map(|i| {
+                        let azimuth = step * i as f64;
+                        let r = sphere_radius * polar.sin();
+                        [
+                            cx + r * azimuth.cos(),
+                            cy + r * azimuth.sin(),
+                            z.unwrap_or(cz + sphere_radius * polar.cos()),
+                        ]
+                    })
+                    .collect()
+            };
+            let apex = [self.center.x, self.center.y, self.height];
+            // The rim lands exactly on z = 0
+            let cap: Vec<Vec<Vec3>> = (1..=rings)
+                .map(|k| {
+                    let z = (k == rings).then_some(0.0);
+                    ring(rim_polar * k as f64 / rings as f64, z)
+                })
+                .collect();
+
+            let mut triangles = Vec::with_capacity(2 * n * (rings + 2));
+            for i in 0..n {
+                let next = (i + 1) % n;
+                triangles.push(facet(apex, cap[0][i], cap[0][next]));
+                for pair in cap.windows(2) {
+                    let (inner, outer) = (&pair[0], &pair[1]);
+                    triangles.push(facet(inner[i], outer[i], outer[next]));
+                    triangles.push(facet(inner[i], outer[next], inner[next]));
+                }
+            }
+
+            let rim = &cap[rings - 1];
+            // Without a skirt the bottom disc closes on the rim itself
+            let bottom_z = if thickness > 0.0 { -thickness } else { 0.0 };
+            let bottom: Vec<Vec3> = rim.iter().map(|&[x, y, _]| [x, y, bottom_z]).collect();
+            if thickness > 0.0 {
+                for i in 0..n {
+                    let next = (i + 1) % n;
+                    triangles.push(facet(rim[i], bottom[i], bottom[next]));
+                    triangles.push(facet(rim[i], bottom[next], rim[next]));
+                }
+            }
+            let middle = [self.center.x, self.center.y, bottom_z];
+            for i in 0..n {
+                triangles.push(facet(middle, bottom[(i + 1) % n], bottom[i]));
+            }
+            triangles
         }
+    }
+
+    /// As [`export_stl_3d`], with the curve cut into a solid `dome`
+    ///
+    /// The grooves hang from the curve along the sphere's normal, and the
+    /// cap stands on a skirt `config.base_thickness` deep.
+    pub fn export_stl_dome(
+        filename: &str,
+        points: &[Point3D],
+        dome: &Dome,
+        config: &ExportConfig,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let flat: Vec<Point2D> = points.iter().map(|p| Point2D::new(p.x, p.y)).collect();
+        let mut triangles = dome.triangles(config.base_thickness);
+        triangles.extend(groove_solid_triangles(
+            points,
+            polyline_closes(&flat),
+            &config.groove_profile()?,
+            |p| dome.normal_at(p),
+        ));
+        config.origin.validate()?;
+        config.origin.apply_stl(&mut triangles);
 
         let mut file = std::fs::File::create(filename)?;
         stl_io::write_stl(&mut file, triangles.iter())?;
@@ -752,8 +1493,9 @@ mod step {
     pub fn export_step(
         filename: &str,
         points: &[Point2D],
-        _config: &ExportConfig,
+        config: &ExportConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
+        config.origin.validate()?;
         // Basic STEP file generation
         // This is a simplified implementation - full STEP support would require a proper CAD library
         let mut content = String::new();
@@ -773,7 +1515,7 @@ mod step {
         content.push_str("DATA;\n");
 
         // Add points as a polyline
-        for (i, point) in points.iter().enumerate() {
+        for (i, point) in config.origin.apply_points(points).iter().enumerate() {
             content.push_str(&format!(
                 "#{}=CARTESIAN_POINT('',({}.,{}.,0.));\n",
                 i + 1,
@@ -792,9 +1534,12 @@ mod step {
     pub fn export_step_3d(
         filename: &str,
         points: &[Point3D],
-        _config: &ExportConfig,
+        config: &ExportConfig,
     ) -> Result<(), Box<dyn std::error::Error>> {
+        config.origin.validate()?;
         let mut content = String::new();
+        let flat: Vec<Point2D> = points.iter().map(|p| Point2D::new(p.x, p.y)).collect();
+        let flat = config.origin.apply_points(&flat);
 
         let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string();
 
@@ -809,12 +1554,12 @@ mod step {
         content.push_str("ENDSEC;\n");
         content.push_str("DATA;\n");
 
-        for (i, point) in points.iter().enumerate() {
+        for (i, (point, xy)) in points.iter().zip(&flat).enumerate() {
             content.push_str(&format!(
                 "#{}=CARTESIAN_POINT('',({}.,{}.,{}.));\n",
                 i + 1,
-                point.x,
-                point.y,
+                xy.x,
+                xy.y,
                 point.z
             ));
         }
@@ -830,6 +1575,7 @@ mod step {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::{validate_radius_in, OriginMode};
 
     #[test]
     fn test_validate_radius() {
@@ -838,6 +1584,10 @@ mod tests {
         assert!(validate_radius(44.0).is_ok());
         assert!(validate_radius(25.9).is_err());
         assert!(validate_radius(44.1).is_err());
+        assert!(validate_radius_in(15.0, 10.0, 20.0).is_ok());
+        assert!(validate_radius_in(20.5, 10.0, 20.0).is_err());
+        assert!(RadiusPolicy::Unrestricted.check(0.0).is_err());
+        assert!(RadiusPolicy::Unrestricted.check(f64::INFINITY).is_err());
     }
 
     #[test]
@@ -853,7 +1603,55 @@ mod tests {
     fn test_horizontal_spirograph_generate() {
         let mut spiro = HorizontalSpirograph::new(40.0, 0.75, 0.6, 50, 360).unwrap();
         let points = spiro.generate();
-        assert_eq!(points.len(), 50 * 360);
+        assert_eq!(points.len(), 50 * 360 + 1); // end point included
+    }
+
+    #[test]
+    fn test_epitrochoid_reaches_outside_fixed_circle() {
+        let mut spiro = HorizontalSpirograph::new(30.0, 0.5, 5.0, 1, 360)
+            .unwrap()
+            .with_mode(SpirographMode::Epitrochoid);
+        spiro.generate();
+        let max_radius = spiro
+            .points()
+            .iter()
+            .map(|p| p.x.hypot(p.y))
+            .fold(0.0, f64::max);
+        assert!((max_radius - (30.0 + 15.0 + 5.0)).abs() < 1e-6);
+        assert!(polyline_closes(spiro.points()));
+
+        let mut vertical = VerticalSpirograph::new(30.0, 0.5, 5.0, 1, 360, 0.0, 5.0)
+            .unwrap()
+            .with_mode(SpirographMode::Epitrochoid);
+        vertical.generate();
+        assert_eq!(vertical.points(), spiro.points());
+
+        // The dome spans the whole epitrochoid, so every point lies on it
+        let mut spherical = SphericalSpirograph::new(30.0, 0.5, 5.0, 1, 360, 8.0)
+            .unwrap()
+            .with_mode(SpirographMode::Epitrochoid);
+        spherical.generate();
+        assert!(spherical
+            .points_3d()
+            .iter()
+            .all(|p| p.is_finite() && p.z >= -1e-9 && p.z <= 8.0));
+    }
+
+    #[test]
+    fn test_closure_rotations() {
+        assert_eq!(closure_rotations(0.75), 3);
+        assert_eq!(closure_rotations(0.3), 3);
+        assert_eq!(closure_rotations(0.5), 1);
+        // Past the largest denominator, the best nearby fraction is used
+        assert_eq!(closure_rotations(1.0 / PI), 113);
+
+        let spiro = HorizontalSpirograph::new(40.0, 0.75, 8.0, 1, 360).unwrap();
+        assert_eq!(spiro.closure_rotations(), 3);
+        let mut closed = HorizontalSpirograph::new(40.0, 0.75, 8.0, 3, 360)
+            .unwrap()
+            .with_mode(SpirographMode::Epitrochoid);
+        closed.generate();
+        assert!(polyline_closes(closed.points()));
     }
 
     #[test]
@@ -868,6 +1666,244 @@ mod tests {
         assert!(spiro.is_ok());
     }
 
+    #[test]
+    fn test_rejects_zero_rotations_and_low_resolution() {
+        let message = |result: Result<(), SpirographError>| match result {
+            Err(SpirographError::InvalidParameter(msg)) => msg,
+            other => panic!("expected InvalidParameter, got {:?}", other),
+        };
+
+        for (rotations, resolution, field) in [
+            (0, 360, "rotations"),
+            (50, 0, "resolution"),
+            (50, 1, "resolution"),
+            (50, 7, "resolution"),
+        ] {
+            let h = HorizontalSpirograph::new(40.0, 0.75, 0.6, rotations, resolution).map(|_| ());
+            assert!(message(h).starts_with(field));
+            let v = VerticalSpirograph::new(35.0, 0.6, 0.5, rotations, resolution, 2.0, 5.0)
+                .map(|_| ());
+            assert!(message(v).starts_with(field));
+            let s =
+                SphericalSpirograph::new(38.0, 0.7, 0.4, rotations, resolution, 5.0).map(|_| ());
+            assert!(message(s).starts_with(field));
+        }
+
+        // Positioned constructors share the same validation
+        assert!(HorizontalSpirograph::new_at_polar(40.0, 0.75, 0.6, 0, 360, 0.0, 5.0).is_err());
+        assert!(
+            VerticalSpirograph::new_at_clock(35.0, 0.6, 0.5, 30, 4, 2.0, 5.0, 3, 0, 10.0).is_err()
+        );
+        assert!(
+            SphericalSpirograph::new_at_clock(38.0, 0.7, 0.4, 0, 360, 5.0, 3, 0, 10.0).is_err()
+        );
+        assert!(HorizontalSpirograph::new(40.0, 0.75, 0.6, 1, 8).is_ok());
+    }
+
+    #[test]
+    fn test_stl_base_slab_sets_z_extent() {
+        let z_range = |path: &std::path::Path| {
+            let mut file = std::fs::File::open(path).unwrap();
+            let mesh = stl_io::read_stl(&mut file).unwrap();
+            let _ = std::fs::remove_file(path);
+            let z = mesh.vertices.iter().map(|v| v[2] as f64);
+            let range = (
+                z.clone().fold(f64::INFINITY, f64::min),
+                z.fold(f64::NEG_INFINITY, f64::max),
+            );
+            (range, mesh.faces.len())
+        };
+        let path = std::env::temp_dir().join("test_spirograph_base_slab.stl");
+        let config = ExportConfig {
+            depth: 0.2,
+            base_thickness: 1.5,
+            ..ExportConfig::default()
+        };
+        let bare = ExportConfig {
+            base_thickness: 0.0,
+            ..config.clone()
+        };
+
+        let mut spiro = HorizontalSpirograph::new(40.0, 0.75, 0.6, 5, 360).unwrap();
+        spiro.generate();
+        spiro.to_stl(path.to_str().unwrap(), &config).unwrap();
+        let ((low, high), _) = z_range(&path);
+        assert!((high - low - 1.7).abs() < 1e-6);
+
+        // No slab: just the groove, a 90° V of three faces per segment,
+        // capped at both ends since five turns stop short of the start
+        spiro.to_stl(path.to_str().unwrap(), &bare).unwrap();
+        let ((low, high), faces) = z_range(&path);
+        assert!((high - low - 0.2).abs() < 1e-6);
+        assert_eq!(faces, 6 * (spiro.points.len() - 1) + 2);
+
+        // The dome's slab sits under its lowest groove
+        let mut sphere = SphericalSpirograph::new(38.0, 0.7, 0.4, 5, 360, 5.0).unwrap();
+        sphere.generate();
+        sphere.to_stl(path.to_str().unwrap(), &config).unwrap();
+        let ((low, high), _) = z_range(&path);
+        let dome = sphere.points_3d.iter().map(|p| p.z);
+        let dome_span =
+            dome.clone().fold(f64::NEG_INFINITY, f64::max) - dome.fold(f64::INFINITY, f64::min);
+        assert!((high - low - (dome_span + 1.7)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_dome_stl_cuts_grooves_into_a_solid_cap() {
+        use crate::common::tests::assert_closed_outward;
+        use crate::groove::tests::enclosed_volume;
+
+        let mut sphere = SphericalSpirograph::new(38.0, 0.7, 0.4, 5, 360, 5.0).unwrap();
+        sphere.generate();
+        let dome = stl::Dome {
+            center: Point2D::new(0.0, 0.0),
+            span: sphere.dome_span(),
+            height: sphere.dome_height,
+            segments: 64,
+        };
+        // The curve lies on the cap's sphere
+        let [cx, cy, cz] = [0.0, 0.0, dome.height - sphere.sphere_radius()];
+        assert!(sphere.points_3d.iter().all(|p| {
+            ((p.x - cx).hypot(p.y - cy).hypot(p.z - cz) - sphere.sphere_radius()).abs() < 1e-9
+        }));
+
+        // Closed, within 1% of the cap's volume, and as tall as the skirt says
+        let (h, r) = (dome.height, sphere.sphere_radius());
+        let cap_volume = PI * h * h * (3.0 * r - h) / 3.0;
+        for thickness in [0.0, 1.5] {
+            let cap = dome.triangles(thickness);
+            assert_closed_outward(&cap);
+            let skirt = PI * dome.span * dome.span * thickness;
+            let volume = enclosed_volume(&cap);
+            assert!((volume - cap_volume - skirt).abs() < 0.01 * (cap_volume + skirt));
+        }
+
+        let read = |config: &ExportConfig| {
+            let path = std::env::temp_dir().join("test_spirograph_dome.stl");
+            sphere.to_stl(path.to_str().unwrap(), config).unwrap();
+            let mut file = std::fs::File::open(&path).unwrap();
+            let mesh = stl_io::read_stl(&mut file).unwrap();
+            let _ = std::fs::remove_file(&path);
+            mesh
+        };
+        let ribbons = ExportConfig {
+            depth: 0.2,
+            base_thickness: 0.0,
+            ..ExportConfig::default()
+        };
+        let domed = ExportConfig {
+            surface: ExportSurface::dome(),
+            ..ribbons.clone()
+        };
+        let mesh = read(&domed);
+        let z = mesh.vertices.iter().map(|v| v[2] as f64);
+        let (low, high) = (
+            z.clone().fold(f64::INFINITY, f64::min),
+            z.fold(f64::NEG_INFINITY, f64::max),
+        );
+        assert!((high - sphere.dome_height).abs() < 1e-5);
+        assert!((-0.2 - 1e-5..=0.0).contains(&low));
+        assert!(mesh.faces.len() > read(&ribbons).faces.len() + 4 * 128);
+
+        let coarse = ExportConfig {
+            surface: ExportSurface::Dome { segments: 2 },
+            ..domed
+        };
+        let path = std::env::temp_dir().join("test_spirograph_dome_coarse.stl");
+        assert!(sphere.to_stl(path.to_str().unwrap(), &coarse).is_err());
+    }
+
+    #[test]
+    fn test_top_left_origin_svg_is_analytic_transform_of_center() {
+        // First path's points, the group transform and the viewBox of an SVG
+        type Parsed = (Vec<(f64, f64)>, Option<[f64; 6]>, Vec<f64>);
+        fn parse(path: &std::path::Path) -> Parsed {
+            let text = std::fs::read_to_string(path).unwrap();
+            let _ = std::fs::remove_file(path);
+            let attribute = |name: &str| {
+                let start = text.find(&format!(" {}=\"", name))? + name.len() + 3;
+                Some(text[start..start + text[start..].find('"')?].to_string())
+            };
+            let numbers = |s: &str| -> Vec<f64> {
+                s.split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == 'e'))
+                    .filter_map(|n| n.parse().ok())
+                    .collect()
+            };
+            let d = numbers(&attribute("d").unwrap());
+            let points = d.chunks(2).map(|xy| (xy[0], xy[1])).collect();
+            let matrix = attribute("transform").map(|t| numbers(&t).try_into().unwrap());
+            (points, matrix, numbers(&attribute("viewBox").unwrap()))
+        }
+
+        let mut spiro = HorizontalSpirograph::new(40.0, 0.75, 0.6, 1, 90).unwrap();
+        spiro.generate();
+        let path = std::env::temp_dir().join("test_spirograph_origin.svg");
+        spiro.to_svg(path.to_str().unwrap()).unwrap();
+        let (center_points, center_matrix, center_box) = parse(&path);
+        assert!(center_matrix.is_none());
+
+        let config = ExportConfig {
+            origin: OriginMode::TopLeftYDown,
+            ..ExportConfig::default()
+        };
+        spiro
+            .to_svg_with_config(path.to_str().unwrap(), &config)
+            .unwrap();
+        let (points, matrix, view_box) = parse(&path);
+        let [a, b, c, d, e, f] = matrix.unwrap();
+        let (vx, vy) = (center_box[0], center_box[1]);
+        assert_eq!(view_box, vec![0.0, 0.0, center_box[2], center_box[3]]);
+        assert!(!points.is_empty());
+        assert_eq!(points, center_points);
+
+        for &(x, y) in &center_points {
+            // Forward: the drawn point lands at its offset from the page corner
+            let (tx, ty) = (a * x + c * y + e, b * x + d * y + f);
+            assert!((tx - (x - vx)).abs() < 1e-3 && (ty - (y - vy)).abs() < 1e-3);
+            // Back: undoing the transform recovers the center-origin point
+            let det = a * d - b * c;
+            let (ux, uy) = (tx - e, ty - f);
+            let (bx, by) = ((d * ux - c * uy) / det, (a * uy - b * ux) / det);
+            assert!((bx - x).abs() < 1e-3 && (by - y).abs() < 1e-3);
+        }
+
+        let bad = ExportConfig {
+            origin: OriginMode::Custom {
+                offset: (f64::INFINITY, 0.0),
+                y_down: true,
+            },
+            ..ExportConfig::default()
+        };
+        assert!(matches!(
+            spiro.to_svg_with_config(path.to_str().unwrap(), &bad),
+            Err(SpirographError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_error_distinguishes_missing_generate() {
+        let file = std::env::temp_dir().join("test_spirograph_export_error.svg");
+        let file = file.to_str().unwrap();
+        let export_message = |result: Result<(), SpirographError>| match result {
+            Err(SpirographError::ExportError(msg)) => msg,
+            other => panic!("expected ExportError, got {:?}", other),
+        };
+
+        let mut spiro = HorizontalSpirograph::new(40.0, 0.75, 0.6, 5, 360).unwrap();
+        assert!(export_message(spiro.to_svg(file)).contains("never called"));
+        let mut sphere = SphericalSpirograph::new(38.0, 0.7, 0.4, 5, 360, 5.0).unwrap();
+        let config = ExportConfig::default();
+        assert!(export_message(sphere.to_stl(file, &config)).contains("never called"));
+
+        // Fields are public, so a later edit can still leave nothing to export
+        spiro.rotations = 0;
+        spiro.generate();
+        assert!(export_message(spiro.to_svg(file)).contains("produced no points"));
+        sphere.rotations = 0;
+        sphere.generate();
+        assert!(export_message(sphere.to_step(file, &config)).contains("produced no points"));
+    }
+
     #[test]
     fn test_point_2d() {
         let p = Point2D::new(1.0, 2.0);
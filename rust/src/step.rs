@@ -0,0 +1,288 @@
+use std::fmt::Write;
+
+use crate::common::{DialShape, Point2D, SpirographError, Unit};
+use crate::metadata::step_string;
+
+/// A named group of polylines, written as one part of a STEP assembly
+pub(crate) struct StepPart {
+    pub name: String,
+    /// Polylines with whether each is closed
+    pub lines: Vec<(Vec<Point2D>, bool)>,
+}
+
+/// Write a STEP (AP214) assembly of flat wireframe parts
+///
+/// The root product `name` holds one component per part, placed without a
+/// transform, so CAD tools list every layer by name. Each part's polylines
+/// are POLYLINE curves, closed ones ending on their first point, gathered
+/// in a GEOMETRIC_CURVE_SET of a wireframe shape representation. Parts
+/// without a polyline of at least two points are left out.
+///
+/// With `dial`, a `dial` component carries a PLANE through the dial center
+/// and its outline as a CIRCLE or ELLIPSE. Coordinates are written as
+/// given, in `unit`; `header` holds the FILE_DESCRIPTION and FILE_NAME
+/// lines.
+pub(crate) fn write_step_assembly(
+    filename: &str,
+    header: &str,
+    name: &str,
+    unit: Unit,
+    dial: Option<(Point2D, DialShape)>,
+    parts: &[StepPart],
+) -> Result<(), SpirographError> {
+    let mut data = StepData::default();
+
+    let application = data.add("APPLICATION_CONTEXT('automotive design')".to_string());
+    data.add(format!(
+        "APPLICATION_PROTOCOL_DEFINITION('international standard','automotive_design',2000,#{})",
+        application
+    ));
+    let product_context = data.add(format!("PRODUCT_CONTEXT('',#{},'mechanical')", application));
+    let definition_context = data.add(format!(
+        "PRODUCT_DEFINITION_CONTEXT('part definition',#{},'design')",
+        application
+    ));
+    let context = data.add_context(unit);
+
+    let (root, root_shape) = data.add_product(name, product_context, definition_context);
+    let root_axis = data.add_axis(Point2D::new(0.0, 0.0));
+    let root_rep = data.add(format!(
+        "SHAPE_REPRESENTATION({},(#{}),#{})",
+        step_string(name),
+        root_axis,
+        context
+    ));
+    data.add(format!(
+        "SHAPE_DEFINITION_REPRESENTATION(#{},#{})",
+        root_shape, root_rep
+    ));
+
+    let mut components = Vec::new();
+    if let Some((center, shape)) = dial {
+        let (product, shape_id) = data.add_product("dial", product_context, definition_context);
+        let axis = data.add_axis(Point2D::new(0.0, 0.0));
+        let dial_axis = data.add_axis(center);
+        let plane = data.add(format!("PLANE('dial',#{})", dial_axis));
+        let outline = match shape {
+            DialShape::Circle { radius } => data.add(format!(
+                "CIRCLE('dial',#{},{})",
+                dial_axis,
+                step_real(radius)
+            )),
+            DialShape::Ellipse { rx, ry } => data.add(format!(
+                "ELLIPSE('dial',#{},{},{})",
+                dial_axis,
+                step_real(rx),
+                step_real(ry)
+            )),
+        };
+        let curves = data.add(format!("GEOMETRIC_CURVE_SET('dial',(#{}))", outline));
+        let rep = data.add(format!(
+            "SHAPE_REPRESENTATION('dial',(#{},#{},#{}),#{})",
+            axis, plane, curves, context
+        ));
+        data.add(format!(
+            "SHAPE_DEFINITION_REPRESENTATION(#{},#{})",
+            shape_id, rep
+        ));
+        components.push(("dial".to_string(), product, axis, rep));
+    }
+
+    for part in parts {
+        let mut polylines = Vec::new();
+        for (points, closed) in part.lines.iter().filter(|(points, _)| points.len() >= 2) {
+            let mut ids: Vec<usize> = points
+                .iter()
+                .map(|&p| data.add(format!("CARTESIAN_POINT('',{})", step_point(p))))
+                .collect();
+            if *closed {
+                ids.push(ids[0]);
+            }
+            polylines.push(data.add(format!("POLYLINE('',({}))", step_refs(&ids))));
+        }
+        if polylines.is_empty() {
+            continue;
+        }
+        let (product, shape_id) = data.add_product(&part.name, product_context, definition_context);
+        let axis = data.add_axis(Point2D::new(0.0, 0.0));
+        let curves = data.add(format!(
+            "GEOMETRIC_CURVE_SET({},({}))",
+            step_string(&part.name),
+            step_refs(&polylines)
+        ));
+        let rep = data.add(format!(
+            "GEOMETRICALLY_BOUNDED_WIREFRAME_SHAPE_REPRESENTATION({},(#{},#{}),#{})",
+            step_string(&part.name),
+            axis,
+            curves,
+            context
+        ));
+        data.add(format!(
+            "SHAPE_DEFINITION_REPRESENTATION(#{},#{})",
+            shape_id, rep
+        ));
+        components.push((part.name.clone(), product, axis, rep));
+    }
+
+    // Place every component in the root without moving it
+    for (index, (component_name, product, axis, rep)) in components.into_iter().enumerate() {
+        let usage = data.add(format!(
+            "NEXT_ASSEMBLY_USAGE_OCCURRENCE('{}',{},'',#{},#{},$)",
+            index + 1,
+            step_string(&component_name),
+            root,
+            product
+        ));
+        let usage_shape = data.add(format!("PRODUCT_DEFINITION_SHAPE('','',#{})", usage));
+        let transform = data.add(format!(
+            "ITEM_DEFINED_TRANSFORMATION('','',#{},#{})",
+            axis, root_axis
+        ));
+        let relationship = data.add(format!(
+            "(REPRESENTATION_RELATIONSHIP('','',#{},#{})\
+             REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#{})\
+             SHAPE_REPRESENTATION_RELATIONSHIP())",
+            rep, root_rep, transform
+        ));
+        data.add(format!(
+            "CONTEXT_DEPENDENT_SHAPE_REPRESENTATION(#{},#{})",
+            relationship, usage_shape
+        ));
+    }
+
+    let mut content = String::new();
+    content.push_str("ISO-10303-21;\n");
+    content.push_str("HEADER;\n");
+    content.push_str(header);
+    content.push_str("FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));\n");
+    content.push_str("ENDSEC;\n");
+    content.push_str("DATA;\n");
+    content.push_str(&data.content);
+    content.push_str("ENDSEC;\n");
+    content.push_str("END-ISO-10303-21;\n");
+
+    std::fs::write(filename, content).map_err(|e| {
+        SpirographError::ExportError(format!("Failed to write STEP file '{}': {}", filename, e))
+    })
+}
+
+/// The DATA section, numbering entities as they are added
+#[derive(Default)]
+struct StepData {
+    content: String,
+    count: usize,
+}
+
+impl StepData {
+    /// Append `entity` and return its id
+    fn add(&mut self, entity: String) -> usize {
+        self.count += 1;
+        // Writing to a String cannot fail
+        let _ = writeln!(self.content, "#{}={};", self.count, entity);
+        self.count
+    }
+
+    /// A product with its definition, returning the definition and its
+    /// shape
+    fn add_product(
+        &mut self,
+        name: &str,
+        product_context: usize,
+        definition_context: usize,
+    ) -> (usize, usize) {
+        let name = step_string(name);
+        let product = self.add(format!(
+            "PRODUCT({},{},'',(#{}))",
+            name, name, product_context
+        ));
+        let formation = self.add(format!("PRODUCT_DEFINITION_FORMATION('','',#{})", product));
+        let definition = self.add(format!(
+            "PRODUCT_DEFINITION('design','',#{},#{})",
+            formation, definition_context
+        ));
+        let shape = self.add(format!("PRODUCT_DEFINITION_SHAPE('','',#{})", definition));
+        (definition, shape)
+    }
+
+    /// A placement at `origin` on z = 0 with the standard axes
+    fn add_axis(&mut self, origin: Point2D) -> usize {
+        let location = self.add(format!("CARTESIAN_POINT('',{})", step_point(origin)));
+        let z = self.add("DIRECTION('',(0.,0.,1.))".to_string());
+        let x = self.add("DIRECTION('',(1.,0.,0.))".to_string());
+        self.add(format!(
+            "AXIS2_PLACEMENT_3D('',#{},#{},#{})",
+            location, z, x
+        ))
+    }
+
+    /// The geometric context with `unit` for lengths
+    fn add_context(&mut self, unit: Unit) -> usize {
+        let millimeter =
+            self.add("(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.))".to_string());
+        let length = match unit {
+            Unit::Millimeter => millimeter,
+            Unit::Inch => {
+                let factor = self.add(format!(
+                    "LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE({}),#{})",
+                    step_real(Unit::MM_PER_INCH),
+                    millimeter
+                ));
+                let exponents = self.add("DIMENSIONAL_EXPONENTS(1.,0.,0.,0.,0.,0.,0.)".to_string());
+                self.add(format!(
+                    "(CONVERSION_BASED_UNIT('INCH',#{})LENGTH_UNIT()NAMED_UNIT(#{}))",
+                    factor, exponents
+                ))
+            }
+        };
+        let angle = self.add("(NAMED_UNIT(*)PLANE_ANGLE_UNIT()SI_UNIT($,.RADIAN.))".to_string());
+        let solid_angle =
+            self.add("(NAMED_UNIT(*)SI_UNIT($,.STERADIAN.)SOLID_ANGLE_UNIT())".to_string());
+        let uncertainty = self.add(format!(
+            "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E-07),#{},'distance_accuracy_value','')",
+            length
+        ));
+        self.add(format!(
+            "(GEOMETRIC_REPRESENTATION_CONTEXT(3)\
+             GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{}))\
+             GLOBAL_UNIT_ASSIGNED_CONTEXT((#{},#{},#{}))\
+             REPRESENTATION_CONTEXT('',''))",
+            uncertainty, length, angle, solid_angle
+        ))
+    }
+}
+
+/// A STEP real: always with a decimal point, never in exponent form
+fn step_real(value: f64) -> String {
+    let text = value.to_string();
+    if text.contains('.') {
+        text
+    } else {
+        text + "."
+    }
+}
+
+/// Coordinates of `p` on z = 0
+fn step_point(p: Point2D) -> String {
+    format!("({},{},0.)", step_real(p.x), step_real(p.y))
+}
+
+/// Entity references as a comma-separated list
+fn step_refs(ids: &[usize]) -> String {
+    ids.iter()
+        .map(|id| format!("#{}", id))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_real_keeps_a_decimal_point() {
+        assert_eq!(step_real(2.0), "2.");
+        assert_eq!(step_real(-0.5), "-0.5");
+        assert_eq!(step_real(1e-9), "0.000000001");
+        assert_eq!(step_point(Point2D::new(3.0, -1.25)), "(3.,-1.25,0.)");
+    }
+}
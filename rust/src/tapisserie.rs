@@ -0,0 +1,698 @@
+use std::f64::consts::{PI, SQRT_2};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cells::polygon_area;
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clock_to_cartesian, debug_assert_finite, dedupe_polylines, polar_to_cartesian,
+    polyline_is_degenerate, scale_polylines, segment_circle_crossings, simplified_layer,
+    simplify_polylines, ExportConfig, PatternLayer, Point2D, PointsPerRev, SpirographError,
+    StoredLayer, DEDUPE_EPSILON,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
+
+/// Configuration for the tapisserie (tapestry, or waffle) finish
+///
+/// A square grid of raised cells separated by straight channels, as on
+/// the Royal Oak dials. Cells are `cell_size` wide with `channel_width`
+/// between them, so the grid repeats every `cell_size + channel_width`;
+/// one cell sits on the layer's center and the grid is turned by
+/// `rotation`.
+///
+/// Each cell is drawn as its closed outline, a square or, with
+/// `truncate_corners`, the regular octagon cut from it. Cells crossing
+/// the circle of `radius` are clipped to it analytically: their outline
+/// follows the circle between the exact crossing points, sampled at
+/// `resolution` steps per full turn. The channel centerlines follow the
+/// cells as a second family of straight chords.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapisserieConfig {
+    /// Width of each raised cell in mm
+    pub cell_size: f64,
+    /// Width of the channels between cells in mm
+    pub channel_width: f64,
+    /// Radius of the circular clipping region in mm
+    pub radius: f64,
+    /// Rotation of the grid in radians, counter-clockwise from the axes
+    pub rotation: f64,
+    /// Steps per full turn where a cell outline follows the circle
+    pub resolution: PointsPerRev,
+    /// Cut the corners off every cell, leaving a regular octagon
+    pub truncate_corners: bool,
+}
+
+impl Default for TapisserieConfig {
+    fn default() -> Self {
+        TapisserieConfig {
+            cell_size: 1.0,
+            channel_width: 0.3,
+            radius: 15.0,
+            rotation: 0.0,
+            resolution: PointsPerRev(360),
+            truncate_corners: false,
+        }
+    }
+}
+
+impl TapisserieConfig {
+    /// Create a new tapisserie configuration
+    ///
+    /// # Arguments
+    /// * `cell_size` - Width of each raised cell in mm
+    /// * `channel_width` - Width of the channels between cells in mm
+    /// * `radius` - Radius of the circular clipping region in mm
+    pub fn new(cell_size: f64, channel_width: f64, radius: f64) -> Self {
+        TapisserieConfig {
+            cell_size,
+            channel_width,
+            radius,
+            ..Default::default()
+        }
+    }
+
+    /// Set the rotation of the grid in radians
+    pub fn with_rotation(mut self, rotation: f64) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set the resolution (steps per full turn along the circle)
+    pub fn with_resolution(mut self, resolution: impl Into<PointsPerRev>) -> Self {
+        self.resolution = resolution.into();
+        self
+    }
+
+    /// Draw the cells as octagons with their corners cut off
+    pub fn with_truncated_corners(mut self, truncate_corners: bool) -> Self {
+        self.truncate_corners = truncate_corners;
+        self
+    }
+
+    /// Check the cell and channel widths, the radius, the rotation and the
+    /// resolution
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if !(self.cell_size.is_finite() && self.cell_size > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "cell_size must be positive".to_string(),
+            ));
+        }
+        if !(self.channel_width.is_finite() && self.channel_width > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "channel_width must be positive".to_string(),
+            ));
+        }
+        if !(self.radius.is_finite() && self.radius > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "radius must be positive".to_string(),
+            ));
+        }
+        if !self.rotation.is_finite() {
+            return Err(SpirographError::InvalidParameter(
+                "rotation must be finite".to_string(),
+            ));
+        }
+        if self.resolution.get() < 3 {
+            return Err(SpirographError::InvalidParameter(
+                "resolution must be at least 3".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Distance between neighbouring cell centers in mm
+    pub fn pitch(&self) -> f64 {
+        self.cell_size + self.channel_width
+    }
+
+    /// Outline of the cell centered on the origin of the grid, counter-
+    /// clockwise without repeating its first point
+    fn cell_outline(&self) -> Vec<Point2D> {
+        let h = self.cell_size / 2.0;
+        if !self.truncate_corners {
+            return vec![
+                Point2D::new(h, -h),
+                Point2D::new(h, h),
+                Point2D::new(-h, h),
+                Point2D::new(-h, -h),
+            ];
+        }
+        // Cutting legs of s / (2 + √2) off each corner leaves all eight
+        // sides the same length
+        let c = self.cell_size / (2.0 + SQRT_2);
+        vec![
+            Point2D::new(h, -h + c),
+            Point2D::new(h, h - c),
+            Point2D::new(h - c, h),
+            Point2D::new(-h + c, h),
+            Point2D::new(-h, h - c),
+            Point2D::new(-h, -h + c),
+            Point2D::new(-h + c, -h),
+            Point2D::new(h - c, -h),
+        ]
+    }
+
+    /// Grid-frame point `p` turned by `rotation` and moved to `center`
+    fn place(&self, p: Point2D, center: Point2D) -> Point2D {
+        let (sin_r, cos_r) = self.rotation.sin_cos();
+        Point2D::new(
+            center.x + p.x * cos_r - p.y * sin_r,
+            center.y + p.x * sin_r + p.y * cos_r,
+        )
+    }
+
+    /// Grid indices reaching from the center to past the circle
+    fn index_range(&self) -> std::ops::RangeInclusive<i64> {
+        let n = (self.radius / self.pitch()).ceil() as i64 + 1;
+        -n..=n
+    }
+
+    /// Closed outline of every cell about `center` that overlaps the
+    /// circle, clipped to it
+    ///
+    /// Cells wholly inside keep their outline; cells crossing the circle
+    /// become the polygon of their part inside it. Slivers that only touch
+    /// the circle are left out.
+    pub(crate) fn cell_lines(&self, center: Point2D) -> Vec<Vec<Point2D>> {
+        let outline = self.cell_outline();
+        let pitch = self.pitch();
+        let reach = self.radius + self.cell_size / SQRT_2;
+        let mut cells = Vec::new();
+        for i in self.index_range() {
+            for j in self.index_range() {
+                let (cx, cy) = (i as f64 * pitch, j as f64 * pitch);
+                if cx.hypot(cy) >= reach {
+                    continue;
+                }
+                let cell: Vec<Point2D> = outline
+                    .iter()
+                    .map(|p| Point2D::new(p.x + cx, p.y + cy))
+                    .collect();
+                if let Some(clipped) = clip_convex_to_circle(&cell, self.radius, self.resolution) {
+                    cells.push(clipped.into_iter().map(|p| self.place(p, center)).collect());
+                }
+            }
+        }
+        cells
+    }
+
+    /// Centerline of every channel about `center`, as chords of the
+    /// circle: first those parallel to the grid's y axis, then those
+    /// parallel to its x axis
+    pub(crate) fn channel_lines(&self, center: Point2D) -> Vec<Vec<Point2D>> {
+        let pitch = self.pitch();
+        let r = self.radius;
+        let offsets: Vec<(f64, f64)> = self
+            .index_range()
+            .map(|k| (k as f64 + 0.5) * pitch)
+            .filter(|offset| offset.abs() < r)
+            .map(|offset| (offset, (r * r - offset * offset).sqrt()))
+            .collect();
+        let across = offsets
+            .iter()
+            .map(|&(offset, half)| [Point2D::new(offset, -half), Point2D::new(offset, half)]);
+        let along = offsets
+            .iter()
+            .map(|&(offset, half)| [Point2D::new(-half, offset), Point2D::new(half, offset)]);
+        across
+            .chain(along)
+            .map(|ends| ends.iter().map(|&p| self.place(p, center)).collect())
+            .collect()
+    }
+}
+
+/// The part of the convex, counter-clockwise `polygon` inside the circle
+/// of `radius` about the origin, closed, or `None` when they do not
+/// overlap
+///
+/// Crossing points are exact. Between leaving the circle and coming back
+/// in, the outline runs counter-clockwise along the circle at
+/// `resolution` steps per full turn.
+fn clip_convex_to_circle(
+    polygon: &[Point2D],
+    radius: f64,
+    resolution: PointsPerRev,
+) -> Option<Vec<Point2D>> {
+    let origin = Point2D::new(0.0, 0.0);
+    let inside = |p: Point2D| p.x.hypot(p.y) <= radius;
+    let lerp =
+        |a: Point2D, b: Point2D, t: f64| Point2D::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t);
+    let n = polygon.len();
+
+    // Outline points, each flagged when the circle leads on to the next
+    let mut corners: Vec<(Point2D, bool)> = Vec::new();
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        if inside(a) {
+            corners.push((a, false));
+        }
+        let ts = segment_circle_crossings(a, b, origin, radius);
+        match (inside(a), inside(b)) {
+            (true, false) => {
+                if let Some(&t) = ts.last() {
+                    corners.push((lerp(a, b, t), true));
+                }
+            }
+            (false, true) => {
+                if let Some(&t) = ts.first() {
+                    corners.push((lerp(a, b, t), false));
+                }
+            }
+            (false, false) if ts.len() == 2 => {
+                corners.push((lerp(a, b, ts[0]), false));
+                corners.push((lerp(a, b, ts[1]), true));
+            }
+            _ => {}
+        }
+    }
+
+    let steps_per_turn = resolution.get();
+    let arc = |from: Point2D, to: Point2D, out: &mut Vec<Point2D>| {
+        let start = from.y.atan2(from.x);
+        let sweep = (to.y.atan2(to.x) - start).rem_euclid(2.0 * PI);
+        let steps = ((sweep / (2.0 * PI) * steps_per_turn as f64).ceil() as usize).max(1);
+        out.extend((1..steps).map(|k| {
+            let angle = start + sweep * k as f64 / steps as f64;
+            Point2D::new(radius * angle.cos(), radius * angle.sin())
+        }));
+    };
+
+    if corners.is_empty() {
+        // Either the circle lies wholly inside the polygon or they miss
+        let holds_center = (0..n).all(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            (b.x - a.x) * -a.y - (b.y - a.y) * -a.x >= 0.0
+        });
+        return holds_center.then(|| {
+            (0..=steps_per_turn)
+                .map(|k| {
+                    let angle = 2.0 * PI * k as f64 / steps_per_turn as f64;
+                    Point2D::new(radius * angle.cos(), radius * angle.sin())
+                })
+                .collect()
+        });
+    }
+
+    let mut outline = Vec::with_capacity(corners.len() + 1);
+    for (k, &(p, leaves)) in corners.iter().enumerate() {
+        outline.push(p);
+        if leaves {
+            arc(p, corners[(k + 1) % corners.len()].0, &mut outline);
+        }
+    }
+    if polygon_area(&outline) <= 1e-12 {
+        return None;
+    }
+    outline.push(outline[0]);
+    Some(outline)
+}
+
+/// A tapisserie (tapestry, or waffle) pattern layer
+///
+/// Generates the closed outlines of the raised cells, clipped to the
+/// circle about the layer's center, followed by the channel centerlines;
+/// see `TapisserieConfig` for the layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapisserieLayer {
+    pub config: TapisserieConfig,
+    pub center_x: f64,
+    pub center_y: f64,
+    #[serde(skip)]
+    lines: Vec<Vec<Point2D>>,
+    #[serde(skip)]
+    num_cells: usize,
+}
+
+impl TapisserieLayer {
+    /// Create a new tapisserie layer centered at origin
+    pub fn new(config: TapisserieConfig) -> Result<Self, SpirographError> {
+        Self::new_with_center(config, 0.0, 0.0)
+    }
+
+    /// Create a new tapisserie layer with a custom center point
+    pub fn new_with_center(
+        config: TapisserieConfig,
+        center_x: f64,
+        center_y: f64,
+    ) -> Result<Self, SpirographError> {
+        config.validate()?;
+
+        Ok(TapisserieLayer {
+            config,
+            center_x,
+            center_y,
+            lines: Vec::new(),
+            num_cells: 0,
+        })
+    }
+
+    /// Create a tapisserie layer positioned at a given angle and distance
+    /// from origin
+    pub fn new_at_polar(
+        config: TapisserieConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (center_x, center_y) = polar_to_cartesian(angle, distance);
+        Self::new_with_center(config, center_x, center_y)
+    }
+
+    /// Create a tapisserie layer positioned at a clock position
+    ///
+    /// # Arguments
+    /// * `config` - Tapisserie configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn new_at_clock(
+        config: TapisserieConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (center_x, center_y) = clock_to_cartesian(hour, minute, distance);
+        Self::new_with_center(config, center_x, center_y)
+    }
+
+    /// Generate the cell outlines and channel centerlines
+    pub fn generate(&mut self) {
+        let center = Point2D::new(self.center_x, self.center_y);
+        self.lines = self.config.cell_lines(center);
+        self.num_cells = self.lines.len();
+        self.lines.extend(self.config.channel_lines(center));
+        dedupe_polylines(&mut self.lines, DEDUPE_EPSILON);
+        debug_assert_finite("TapisserieLayer", &self.lines);
+    }
+
+    /// Get the generated lines: the cell outlines, then the channel
+    /// centerlines
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.lines
+    }
+
+    /// The closed outlines of the raised cells
+    pub fn cell_lines(&self) -> &[Vec<Point2D>] {
+        &self.lines[..self.num_cells.min(self.lines.len())]
+    }
+
+    /// The channel centerlines, for engraving the channels on their own
+    pub fn channel_lines(&self) -> &[Vec<Point2D>] {
+        &self.lines[self.num_cells.min(self.lines.len())..]
+    }
+
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply every length (config, center and generated lines) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.cell_size *= factor;
+        self.config.channel_width *= factor;
+        self.config.radius *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.lines, factor);
+    }
+
+    /// Export the pattern to SVG format
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+
+        if self.lines.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.lines.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        for line in &layer.lines {
+            if polyline_is_degenerate(line) {
+                continue;
+            }
+
+            writer.add_polyline(line, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+            });
+        }
+
+        writer.save(filename)
+    }
+}
+
+impl PatternLayer for TapisserieLayer {
+    fn generate(&mut self) {
+        TapisserieLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for TapisserieLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+        self.num_cells = 0;
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        TapisserieLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for TapisserieLayer {
+    const KIND: LayerKind = LayerKind::Tapisserie;
+}
+
+impl Progressive for TapisserieLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
+        }
+
+        // The grid is the finish; only the arcs along the circle are coarser
+        let mut coarse = self.clone();
+        coarse.config.resolution =
+            PointsPerRev(scale_count(self.config.resolution.get(), fraction, 12));
+        coarse.generate();
+        self.lines = coarse.lines;
+        self.num_cells = coarse.num_cells;
+    }
+}
+
+impl PointCounted for TapisserieLayer {
+    fn point_count(&self) -> usize {
+        self.lines.iter().map(Vec::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tapisserie_invalid_params() {
+        assert!(TapisserieLayer::new(TapisserieConfig::default()).is_ok());
+        assert!(TapisserieLayer::new(TapisserieConfig::new(0.0, 0.3, 10.0)).is_err());
+        assert!(TapisserieLayer::new(TapisserieConfig::new(1.0, 0.0, 10.0)).is_err());
+        assert!(TapisserieLayer::new(TapisserieConfig::new(1.0, 0.3, -1.0)).is_err());
+        let coarse = TapisserieConfig::new(1.0, 0.3, 10.0).with_resolution(2);
+        assert!(TapisserieLayer::new(coarse).is_err());
+        let spun = TapisserieConfig::new(1.0, 0.3, 10.0).with_rotation(f64::NAN);
+        assert!(TapisserieLayer::new(spun).is_err());
+
+        // Edited after construction, the config is caught by try_generate
+        let mut layer = TapisserieLayer::new(TapisserieConfig::new(1.0, 0.3, 10.0)).unwrap();
+        layer.config.channel_width = -0.1;
+        assert!(layer.try_generate().is_err());
+        assert!(layer.lines().is_empty());
+    }
+
+    #[test]
+    fn test_inner_cells_are_whole_squares_and_octagons() {
+        for (truncate, sides) in [(false, 4), (true, 8)] {
+            let config = TapisserieConfig::new(1.0, 0.25, 6.0)
+                .with_rotation(0.3)
+                .with_truncated_corners(truncate);
+            let mut layer = TapisserieLayer::new_with_center(config, 1.0, -2.0).unwrap();
+            layer.generate();
+
+            // The cell on the center is whole, closed and counter-clockwise
+            let (sin_r, cos_r) = 0.3f64.sin_cos();
+            let middle = layer
+                .cell_lines()
+                .iter()
+                .find(|cell| {
+                    let n = (cell.len() - 1) as f64;
+                    let x = cell[1..].iter().map(|p| p.x).sum::<f64>() / n;
+                    let y = cell[1..].iter().map(|p| p.y).sum::<f64>() / n;
+                    (x - 1.0).hypot(y + 2.0) < 1e-9
+                })
+                .unwrap();
+            assert_eq!(middle.len(), sides + 1);
+            assert_eq!(middle[0], middle[sides]);
+            let area = polygon_area(&middle[..sides]);
+            let expected = if truncate {
+                let c = 1.0 / (2.0 + SQRT_2);
+                1.0 - 2.0 * c * c
+            } else {
+                1.0
+            };
+            assert!((area - expected).abs() < 1e-9);
+            // Its first side runs along the turned grid
+            let (dx, dy) = (middle[1].x - middle[0].x, middle[1].y - middle[0].y);
+            assert!((dx * cos_r + dy * sin_r).abs() < 1e-9);
+            // All sides are equal on the octagon
+            let lengths: Vec<f64> = middle
+                .windows(2)
+                .map(|w| (w[1].x - w[0].x).hypot(w[1].y - w[0].y))
+                .collect();
+            if truncate {
+                assert!(lengths.iter().all(|l| (l - lengths[0]).abs() < 1e-9));
+            }
+        }
+    }
+
+    #[test]
+    fn test_boundary_cells_are_clipped_not_dropped() {
+        let config = TapisserieConfig::new(1.0, 0.25, 5.1).with_resolution(720);
+        let mut layer = TapisserieLayer::new(config.clone()).unwrap();
+        layer.generate();
+
+        let mut clipped = 0;
+        let mut covered = 0.0;
+        for cell in layer.cell_lines() {
+            assert_eq!(cell[0], cell[cell.len() - 1]);
+            assert!(polygon_area(&cell[..cell.len() - 1]) > 0.0);
+            for p in cell {
+                assert!(p.x.hypot(p.y) <= 5.1 + 1e-9);
+            }
+            if cell.iter().any(|p| (p.x.hypot(p.y) - 5.1).abs() < 1e-9) {
+                clipped += 1;
+            }
+            covered += polygon_area(&cell[..cell.len() - 1]);
+        }
+        assert!(clipped > 0);
+
+        // Every grid cell overlapping the disc is there: together they
+        // cover the disc's share of raised area, short only by the arcs'
+        // chords
+        let pitch = config.pitch();
+        let mut expected = 0.0;
+        let samples = 200;
+        for i in -6i32..=6 {
+            for j in -6i32..=6 {
+                let mut inside = 0;
+                for a in 0..samples {
+                    for b in 0..samples {
+                        let x = i as f64 * pitch - 0.5 + (a as f64 + 0.5) / samples as f64;
+                        let y = j as f64 * pitch - 0.5 + (b as f64 + 0.5) / samples as f64;
+                        if x.hypot(y) <= 5.1 {
+                            inside += 1;
+                        }
+                    }
+                }
+                expected += inside as f64 / (samples * samples) as f64;
+            }
+        }
+        assert!((covered - expected).abs() < 0.01 * expected);
+    }
+
+    #[test]
+    fn test_channel_centerlines_are_chords_between_cells() {
+        let config = TapisserieConfig::new(1.0, 0.5, 4.0).with_rotation(PI / 4.0);
+        let mut layer = TapisserieLayer::new(config).unwrap();
+        layer.generate();
+
+        // Offsets ±0.75, ±2.25 and ±3.75 in each direction
+        let channels = layer.channel_lines();
+        assert_eq!(channels.len(), 12);
+        for line in channels {
+            assert_eq!(line.len(), 2);
+            for p in line {
+                assert!((p.x.hypot(p.y) - 4.0).abs() < 1e-9);
+            }
+        }
+        assert_eq!(
+            layer.lines().len(),
+            layer.cell_lines().len() + channels.len()
+        );
+    }
+
+    #[test]
+    fn test_disc_inside_one_cell_is_a_full_circle() {
+        let config = TapisserieConfig::new(10.0, 1.0, 2.0).with_resolution(36);
+        let mut layer = TapisserieLayer::new(config).unwrap();
+        layer.generate();
+        assert_eq!(layer.cell_lines().len(), 1);
+        assert_eq!(layer.cell_lines()[0].len(), 37);
+        assert!(layer.channel_lines().is_empty());
+    }
+
+    #[test]
+    fn test_tapisserie_composes_into_pattern() {
+        use crate::guilloche::GuillochePattern;
+
+        let mut pattern = GuillochePattern::new(30.0).unwrap();
+        pattern
+            .add_tapisserie_at_clock(TapisserieConfig::new(1.5, 0.4, 20.0), 12, 0, 0.0)
+            .unwrap();
+        pattern.generate();
+        assert_eq!(pattern.kind_count(LayerKind::Tapisserie), 1);
+        assert!(!pattern.tapisserie_lines()[0].is_empty());
+        assert_eq!(
+            LayerKind::from_name("tapisserie"),
+            Some(LayerKind::Tapisserie)
+        );
+    }
+}
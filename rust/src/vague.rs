@@ -0,0 +1,525 @@
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::svg_writer::SvgWriter;
+use crate::common::{
+    clip_polyline_to_circle, clock_to_cartesian, debug_assert_finite, polar_to_cartesian,
+    polyline_is_degenerate, scale_polylines, simplified_layer, simplify_polylines, ExportConfig,
+    PatternLayer, Point2D, SpirographError, StoredLayer,
+};
+use crate::guilloche::LayerType;
+use crate::post::{LayerKind, PostProcessors};
+use crate::progressive::{scale_count, PointCounted, Progressive};
+
+/// Configuration for the vague (wave, or flammes) finish
+///
+/// `num_lines` parallel waves sweep across the circle of `radius` in the
+/// direction `sweep_angle`, evenly spaced across its diameter. Each is a
+/// sinusoid of `wave_amplitude` and `wave_length` about its own straight
+/// track, so it swings in S-curves from one side of the track to the
+/// other.
+///
+/// Every `group_size` consecutive lines share a phase and so stay the
+/// same distance apart, and each group is shifted by `group_phase_step`
+/// radians from the one before. With an amplitude larger than the line
+/// spacing, neighbouring groups weave through each other in the braided
+/// flame interleave. The waves are sampled at `points_per_wave` points
+/// per wavelength and clipped exactly at the circle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VagueConfig {
+    /// Number of wave lines across the circle
+    pub num_lines: usize,
+    /// Radius of the circular clipping region in mm
+    pub radius: f64,
+    /// Peak deviation of each wave from its track in mm
+    pub wave_amplitude: f64,
+    /// Length of one full wave along the sweep direction in mm
+    pub wave_length: f64,
+    /// Direction the waves travel in radians, counter-clockwise from +x
+    pub sweep_angle: f64,
+    /// Consecutive lines sharing a phase
+    pub group_size: usize,
+    /// Phase shift between successive groups in radians
+    pub group_phase_step: f64,
+    /// Points per wavelength
+    pub points_per_wave: usize,
+}
+
+impl Default for VagueConfig {
+    fn default() -> Self {
+        VagueConfig {
+            num_lines: 40,
+            radius: 15.0,
+            wave_amplitude: 1.5,
+            wave_length: 10.0,
+            sweep_angle: 0.0,
+            group_size: 4,
+            group_phase_step: PI / 2.0,
+            points_per_wave: 64,
+        }
+    }
+}
+
+impl VagueConfig {
+    /// Create a new vague configuration
+    ///
+    /// # Arguments
+    /// * `num_lines` - Number of wave lines across the circle
+    /// * `radius` - Radius of the circular clipping region in mm
+    pub fn new(num_lines: usize, radius: f64) -> Self {
+        VagueConfig {
+            num_lines,
+            radius,
+            ..Default::default()
+        }
+    }
+
+    /// Set the amplitude and length of the waves in mm
+    pub fn with_wave(mut self, amplitude: f64, wave_length: f64) -> Self {
+        self.wave_amplitude = amplitude;
+        self.wave_length = wave_length;
+        self
+    }
+
+    /// Set the direction the waves travel in radians
+    pub fn with_sweep_angle(mut self, sweep_angle: f64) -> Self {
+        self.sweep_angle = sweep_angle;
+        self
+    }
+
+    /// Set the lines per phase group and the phase shift between groups
+    pub fn with_groups(mut self, group_size: usize, group_phase_step: f64) -> Self {
+        self.group_size = group_size;
+        self.group_phase_step = group_phase_step;
+        self
+    }
+
+    /// Set the points per wavelength
+    pub fn with_points_per_wave(mut self, points_per_wave: usize) -> Self {
+        self.points_per_wave = points_per_wave;
+        self
+    }
+
+    /// Check the line count, radius, wave shape, sweep, grouping and
+    /// sampling
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.num_lines == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "num_lines must be at least 1".to_string(),
+            ));
+        }
+        if !(self.radius.is_finite() && self.radius > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "radius must be positive".to_string(),
+            ));
+        }
+        if !(self.wave_amplitude.is_finite() && self.wave_amplitude >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "wave_amplitude must be non-negative".to_string(),
+            ));
+        }
+        if !(self.wave_length.is_finite() && self.wave_length > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "wave_length must be positive".to_string(),
+            ));
+        }
+        if !(self.sweep_angle.is_finite() && self.group_phase_step.is_finite()) {
+            return Err(SpirographError::InvalidParameter(
+                "sweep_angle and group_phase_step must be finite".to_string(),
+            ));
+        }
+        if self.group_size == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "group_size must be at least 1".to_string(),
+            ));
+        }
+        if self.points_per_wave < 4 {
+            return Err(SpirographError::InvalidParameter(
+                "points_per_wave must be at least 4".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Distance between neighbouring tracks in mm
+    pub fn spacing(&self) -> f64 {
+        2.0 * self.radius / self.num_lines as f64
+    }
+
+    /// Phase group of line `k`
+    pub fn group_of(&self, k: usize) -> usize {
+        k / self.group_size
+    }
+
+    /// Wave `k` about `center` across the whole circle, before clipping
+    ///
+    /// Its track is offset from the center across the sweep direction by
+    /// `(k + 0.5)` spacings less the radius; the wave runs from one side
+    /// of the circle to the other, both ends on or outside it.
+    pub(crate) fn wave_line(&self, k: usize, center: Point2D) -> Vec<Point2D> {
+        let r = self.radius;
+        let offset = -r + (k as f64 + 0.5) * self.spacing();
+        let phase = self.group_of(k) as f64 * self.group_phase_step;
+        let wavenumber = 2.0 * PI / self.wave_length;
+        let steps =
+            ((2.0 * r / self.wave_length * self.points_per_wave as f64).ceil() as usize).max(1);
+        let (sin_s, cos_s) = self.sweep_angle.sin_cos();
+        (0..=steps)
+            .map(|i| {
+                let u = -r + 2.0 * r * i as f64 / steps as f64;
+                let v = offset + self.wave_amplitude * (wavenumber * u + phase).sin();
+                Point2D::new(
+                    center.x + u * cos_s - v * sin_s,
+                    center.y + u * sin_s + v * cos_s,
+                )
+            })
+            .collect()
+    }
+}
+
+/// A vague (wave, or flammes) pattern layer
+///
+/// Generates the waves clipped to the circle about the layer's center,
+/// in line order; a wave that leaves the circle and comes back is split
+/// into pieces. See `VagueConfig` for the layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VagueLayer {
+    pub config: VagueConfig,
+    pub center_x: f64,
+    pub center_y: f64,
+    #[serde(skip)]
+    lines: Vec<Vec<Point2D>>,
+    #[serde(skip)]
+    line_groups: Vec<usize>,
+}
+
+impl VagueLayer {
+    /// Create a new vague layer centered at origin
+    pub fn new(config: VagueConfig) -> Result<Self, SpirographError> {
+        Self::new_with_center(config, 0.0, 0.0)
+    }
+
+    /// Create a new vague layer with a custom center point
+    pub fn new_with_center(
+        config: VagueConfig,
+        center_x: f64,
+        center_y: f64,
+    ) -> Result<Self, SpirographError> {
+        config.validate()?;
+
+        Ok(VagueLayer {
+            config,
+            center_x,
+            center_y,
+            lines: Vec::new(),
+            line_groups: Vec::new(),
+        })
+    }
+
+    /// Create a vague layer positioned at a given angle and distance from
+    /// origin
+    pub fn new_at_polar(
+        config: VagueConfig,
+        angle: f64,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (center_x, center_y) = polar_to_cartesian(angle, distance);
+        Self::new_with_center(config, center_x, center_y)
+    }
+
+    /// Create a vague layer positioned at a clock position
+    ///
+    /// # Arguments
+    /// * `config` - Vague configuration
+    /// * `hour` - Hour position (1-12, where 12 is at top)
+    /// * `minute` - Minute position (0-59)
+    /// * `distance` - Distance from center of watch face
+    pub fn new_at_clock(
+        config: VagueConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<Self, SpirographError> {
+        let (center_x, center_y) = clock_to_cartesian(hour, minute, distance);
+        Self::new_with_center(config, center_x, center_y)
+    }
+
+    /// Generate the waves, clipped to the circle
+    pub fn generate(&mut self) {
+        let center = Point2D::new(self.center_x, self.center_y);
+        self.lines.clear();
+        self.line_groups.clear();
+        for k in 0..self.config.num_lines {
+            let wave = self.config.wave_line(k, center);
+            for piece in clip_polyline_to_circle(&wave, center, self.config.radius, false) {
+                if polyline_is_degenerate(&piece) {
+                    continue;
+                }
+                self.lines.push(piece);
+                self.line_groups.push(self.config.group_of(k));
+            }
+        }
+        debug_assert_finite("VagueLayer", &self.lines);
+    }
+
+    /// Get the generated lines
+    pub fn lines(&self) -> &Vec<Vec<Point2D>> {
+        &self.lines
+    }
+
+    /// Phase group of each generated line, in the order of `lines()`
+    pub fn line_groups(&self) -> &[usize] {
+        &self.line_groups
+    }
+
+    /// Mutable access to the generated lines, for post-processing
+    pub(crate) fn lines_mut(&mut self) -> &mut Vec<Vec<Point2D>> {
+        &mut self.lines
+    }
+
+    /// Multiply every length (config, center and generated lines) by `factor`
+    pub(crate) fn scale_lengths(&mut self, factor: f64) {
+        self.config.radius *= factor;
+        self.config.wave_amplitude *= factor;
+        self.config.wave_length *= factor;
+        self.center_x *= factor;
+        self.center_y *= factor;
+        scale_polylines(&mut self.lines, factor);
+    }
+
+    /// Export the pattern to SVG format
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_config(filename, &ExportConfig::default())
+    }
+
+    /// Export to SVG with `config.origin` applied and lines simplified to
+    /// `config.simplify_tolerance`
+    ///
+    /// The other fields are ignored; a single layer is written in its own units.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+
+        if self.lines.is_empty() {
+            return Err(SpirographError::ExportError(
+                "Pattern not generated. Call generate() first.".to_string(),
+            ));
+        }
+
+        let layer = simplified_layer(self, config.simplify_tolerance);
+        let mut writer = SvgWriter::framing(layer.lines.iter().map(Vec::as_slice))?
+            .with_precision(config.svg_precision)
+            .with_origin(config.origin);
+
+        for line in &layer.lines {
+            if polyline_is_degenerate(line) {
+                continue;
+            }
+
+            writer.add_polyline(line, |path| {
+                path.set("fill", "none")
+                    .set("stroke", "black")
+                    .set("stroke-width", 0.05)
+            });
+        }
+
+        writer.save(filename)
+    }
+}
+
+impl PatternLayer for VagueLayer {
+    fn generate(&mut self) {
+        VagueLayer::generate(self)
+    }
+
+    fn validate(&self) -> Result<(), SpirographError> {
+        self.config.validate()
+    }
+
+    fn polylines(&self) -> &[Vec<Point2D>] {
+        self.lines()
+    }
+}
+
+impl StoredLayer for VagueLayer {
+    fn post_process(&mut self, post: &PostProcessors, kind: LayerKind, index: usize) {
+        post.apply(self.lines_mut(), kind, index);
+    }
+
+    fn clear(&mut self) {
+        self.lines_mut().clear();
+        self.line_groups.clear();
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        VagueLayer::scale_lengths(self, factor);
+    }
+
+    fn simplify(&mut self, tolerance: f64) {
+        simplify_polylines(self.lines_mut(), tolerance);
+    }
+
+    fn config_summary(&self) -> String {
+        format!("{:?}", self.config)
+    }
+
+    fn center(&self) -> Option<(f64, f64)> {
+        Some((self.center_x, self.center_y))
+    }
+
+    fn set_center(&mut self, x: f64, y: f64) {
+        (self.center_x, self.center_y) = (x, y);
+    }
+
+    fn point_counted_mut(&mut self) -> Option<&mut dyn PointCounted> {
+        Some(self)
+    }
+}
+
+impl LayerType for VagueLayer {
+    const KIND: LayerKind = LayerKind::Vague;
+}
+
+impl Progressive for VagueLayer {
+    fn generate_at_fraction(&mut self, fraction: f32) {
+        if fraction >= 1.0 {
+            self.generate();
+            return;
+        }
+
+        // Every line is kept; only the waves are sampled more coarsely
+        let mut coarse = self.clone();
+        coarse.config.points_per_wave = scale_count(self.config.points_per_wave, fraction, 8);
+        coarse.generate();
+        self.lines = coarse.lines;
+        self.line_groups = coarse.line_groups;
+    }
+}
+
+impl PointCounted for VagueLayer {
+    fn point_count(&self) -> usize {
+        self.lines.iter().map(Vec::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether segments `ab` and `cd` cross or touch
+    fn segments_meet(a: Point2D, b: Point2D, c: Point2D, d: Point2D) -> bool {
+        let cross = |o: Point2D, p: Point2D, q: Point2D| {
+            (p.x - o.x) * (q.y - o.y) - (p.y - o.y) * (q.x - o.x)
+        };
+        let (d1, d2) = (cross(c, d, a), cross(c, d, b));
+        let (d3, d4) = (cross(a, b, c), cross(a, b, d));
+        d1 * d2 <= 0.0 && d3 * d4 <= 0.0
+    }
+
+    fn lines_meet(a: &[Point2D], b: &[Point2D]) -> bool {
+        a.windows(2)
+            .any(|s| b.windows(2).any(|t| segments_meet(s[0], s[1], t[0], t[1])))
+    }
+
+    #[test]
+    fn test_vague_invalid_params() {
+        assert!(VagueLayer::new(VagueConfig::default()).is_ok());
+        assert!(VagueLayer::new(VagueConfig::new(0, 10.0)).is_err());
+        assert!(VagueLayer::new(VagueConfig::new(10, 0.0)).is_err());
+        assert!(VagueLayer::new(VagueConfig::new(10, 10.0).with_wave(-1.0, 5.0)).is_err());
+        assert!(VagueLayer::new(VagueConfig::new(10, 10.0).with_wave(1.0, 0.0)).is_err());
+        assert!(VagueLayer::new(VagueConfig::new(10, 10.0).with_groups(0, 1.0)).is_err());
+        assert!(VagueLayer::new(VagueConfig::new(10, 10.0).with_groups(2, f64::NAN)).is_err());
+        assert!(VagueLayer::new(VagueConfig::new(10, 10.0).with_points_per_wave(3)).is_err());
+
+        // Edited after construction, the config is caught by try_generate
+        let mut layer = VagueLayer::new(VagueConfig::new(10, 10.0)).unwrap();
+        layer.config.wave_length = f64::INFINITY;
+        assert!(layer.try_generate().is_err());
+        assert!(layer.lines().is_empty());
+    }
+
+    #[test]
+    fn test_waves_are_clipped_exactly_at_the_circle() {
+        let config = VagueConfig::new(16, 8.0)
+            .with_wave(2.0, 5.0)
+            .with_sweep_angle(0.4)
+            .with_groups(3, 2.0);
+        let mut layer = VagueLayer::new_with_center(config, 3.0, -1.0).unwrap();
+        layer.generate();
+
+        let center = Point2D::new(3.0, -1.0);
+        let reach = |p: &Point2D| (p.x - center.x).hypot(p.y - center.y);
+        // The outer waves leave the circle and come back, so there are
+        // more pieces than lines
+        assert!(layer.lines().len() > 16);
+        assert_eq!(layer.line_groups().len(), layer.lines().len());
+        for line in layer.lines() {
+            assert!(line.iter().all(|p| reach(p) <= 8.0 + 1e-9));
+            // Each piece starts and ends where its wave crosses the circle
+            assert!((reach(&line[0]) - 8.0).abs() < 1e-9);
+            assert!((reach(&line[line.len() - 1]) - 8.0).abs() < 1e-9);
+        }
+
+        // Inside the circle the pieces follow the unclipped wave
+        let wave = layer.config.wave_line(7, center);
+        let middle = wave[wave.len() / 2];
+        assert!(layer.lines().iter().flatten().any(|p| *p == middle));
+    }
+
+    #[test]
+    fn test_lines_in_a_group_never_cross_but_groups_interleave() {
+        // The amplitude is well over the spacing of 1 mm, so neighbouring
+        // groups half a wave apart weave through each other
+        let config = VagueConfig::new(12, 6.0)
+            .with_wave(1.5, 4.0)
+            .with_groups(3, PI)
+            .with_points_per_wave(24);
+        let mut layer = VagueLayer::new(config).unwrap();
+        layer.generate();
+
+        let lines = layer.lines();
+        let groups = layer.line_groups();
+        assert_eq!(*groups.iter().max().unwrap(), 3);
+        let mut interleaved = false;
+        for i in 0..lines.len() {
+            for j in i + 1..lines.len() {
+                let meet = lines_meet(&lines[i], &lines[j]);
+                if groups[i] == groups[j] {
+                    assert!(!meet, "lines {} and {} of group {} cross", i, j, groups[i]);
+                } else if groups[j] == groups[i] + 1 {
+                    interleaved |= meet;
+                }
+            }
+        }
+        assert!(interleaved);
+
+        // In phase, the groups are plain parallel waves
+        let mut parallel = VagueLayer::new(layer.config.clone().with_groups(3, 0.0)).unwrap();
+        parallel.generate();
+        let lines = parallel.lines();
+        for i in 0..lines.len() {
+            for j in i + 1..lines.len() {
+                assert!(!lines_meet(&lines[i], &lines[j]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_vague_composes_into_pattern() {
+        use crate::guilloche::GuillochePattern;
+
+        let mut pattern = GuillochePattern::new(30.0).unwrap();
+        pattern
+            .add_vague_at_clock(VagueConfig::new(30, 20.0), 12, 0, 0.0)
+            .unwrap();
+        pattern.generate();
+        assert_eq!(pattern.kind_count(LayerKind::Vague), 1);
+        assert!(!pattern.vague_lines()[0].is_empty());
+        assert_eq!(LayerKind::from_name("vague"), Some(LayerKind::Vague));
+    }
+}
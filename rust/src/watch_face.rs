@@ -1,17 +1,60 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cells::cut_polyline_outside_convex;
+use crate::chapter_ring::{ChapterRingConfig, ChapterRingLayer};
 use crate::clous_de_paris::{ClousDeParisConfig, ClousDeParisLayer};
-use crate::common::{ExportConfig, Point2D, SpirographError};
+use crate::common::svg_writer::{SvgWriter, DEFAULT_SVG_PRECISION};
+use crate::common::{
+    drawable_bounds, polyline_bounds, polyline_closes, polyline_is_degenerate, with_opacity,
+    DialShape, Envelope, ExportConfig, Point2D, ProjectSettings, RadiusPolicy, SpirographError,
+    Unit, DEFAULT_MAX_POINTS_PER_PATH,
+};
+use crate::cotes_de_geneve::{CotesDeGeneveConfig, CotesDeGeneveLayer};
 use crate::cube::{CubeConfig, CubeLayer};
 use crate::diamant::{DiamantConfig, DiamantLayer};
 use crate::draperie::{DraperieConfig, DraperieLayer};
+use crate::dxf::write_dxf_polylines;
+use crate::engrave_text::{EngravedTextConfig, EngravedTextLayer};
 use crate::flinque::{FlinqueConfig, FlinqueLayer};
-use crate::guilloche::GuillochePattern;
+use crate::grain_de_riz::{GrainDeRizConfig, GrainDeRizLayer};
+use crate::guilloche::{
+    clip_pieces, write_step_parts, GenerateOutcome, GuillochePattern, KindLines,
+    LayerValidationIssue, SPIROGRAPH_COLORS, SPIROGRAPH_STROKE_WIDTHS,
+};
+use crate::hpgl::{pens_by_layer, write_hpgl};
 use crate::huiteight::{HuitEightConfig, HuitEightLayer};
+use crate::index_markers::{IndexConfig, IndexMarkersLayer};
 use crate::limacon::{LimaconConfig, LimaconLayer};
+use crate::metadata::FileMetadata;
+use crate::overlay::OverlayOptions;
 use crate::paon::{PaonConfig, PaonLayer};
+use crate::phyllotaxis::{PhyllotaxisConfig, PhyllotaxisLayer};
+use crate::post::LayerKind;
+use crate::raster::{
+    rasterize_strokes, render_preview, svg_color_rgb, Bitmap1, Dither, PreviewStroke, RgbaImage,
+};
+use crate::soleil::{SoleilConfig, SoleilLayer};
 use crate::spirograph::{HorizontalSpirograph, SphericalSpirograph, VerticalSpirograph};
+use crate::step::StepPart;
+use crate::tapisserie::{TapisserieConfig, TapisserieLayer};
+use crate::vague::{VagueConfig, VagueLayer};
+
+pub mod edit;
+pub mod hands;
+
+use hands::{Hands, HandsSet, PlacedHand};
+
+/// Points on the polygon a circular hole is cut as in solid and DXF exports
+const HOLE_SEGMENTS: usize = 64;
+
+/// Segments in each rounded corner of a rectangular aperture
+const APERTURE_CORNER_SEGMENTS: usize = 8;
 
 /// Watch dial circle configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DialConfig {
     pub fill_color: String,
     pub stroke_color: String,
@@ -29,7 +72,7 @@ impl Default for DialConfig {
 }
 
 /// Outer bezel ring configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BezelConfig {
     pub radius_ratio: f64, // Multiplier of dial radius (e.g., 1.05 = 5% larger)
     pub stroke_color: String,
@@ -46,8 +89,57 @@ impl Default for BezelConfig {
     }
 }
 
+/// Stroke of one pattern layer in the SVG export
+///
+/// The width is in the design unit, like the dial and bezel strokes. The
+/// opacity multiplies any opacity envelope set for the layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayerStyle {
+    pub stroke_color: String,
+    pub stroke_width: f64,
+    pub opacity: f64,
+}
+
+impl LayerStyle {
+    /// Create a stroke style
+    pub fn new(stroke_color: &str, stroke_width: f64, opacity: f64) -> Self {
+        LayerStyle {
+            stroke_color: stroke_color.to_string(),
+            stroke_width,
+            opacity,
+        }
+    }
+
+    /// Check for a color, a positive width and an opacity between 0 and 1
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if self.stroke_color.trim().is_empty() {
+            return Err(SpirographError::InvalidParameter(
+                "layer stroke color must not be empty".to_string(),
+            ));
+        }
+        if !(self.stroke_width.is_finite() && self.stroke_width > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "layer stroke width must be positive and finite".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.opacity) {
+            return Err(SpirographError::InvalidParameter(
+                "layer opacity must be between 0 and 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for LayerStyle {
+    /// The stroke every layer without a style of its own is drawn with
+    fn default() -> Self {
+        LayerStyle::new("#1a1a1a", 0.03, 1.0)
+    }
+}
+
 /// Hole configuration (for center pinhole or other holes)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HoleConfig {
     pub center_x: f64,
     pub center_y: f64,
@@ -66,32 +158,752 @@ impl Default for HoleConfig {
     }
 }
 
+/// Outline of a complication aperture, centred on the aperture's center
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ApertureShape {
+    /// Rectangle such as a date window, with rounded corners
+    Rect {
+        width: f64,
+        height: f64,
+        corner_radius: f64,
+        rotation: f64, // Degrees, clockwise on screen about the center
+    },
+    /// Circle such as a subdial opening
+    Circle { radius: f64 },
+}
+
+/// Complication aperture: a date window or subdial opening
+///
+/// Pattern lines stop at the aperture edge in every export, the outline
+/// is drawn with its own stroke in SVG, and the aperture is cut through
+/// solid and DXF exports like a hole.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApertureConfig {
+    pub center_x: f64,
+    pub center_y: f64,
+    pub shape: ApertureShape,
+    pub stroke_color: String,
+    pub stroke_width: f64,
+}
+
+impl ApertureConfig {
+    /// Rectangular aperture at the origin with the default stroke
+    pub fn rect(width: f64, height: f64, corner_radius: f64, rotation: f64) -> Self {
+        Self::new(ApertureShape::Rect {
+            width,
+            height,
+            corner_radius,
+            rotation,
+        })
+    }
+
+    /// Circular aperture at the origin with the default stroke
+    pub fn circle(radius: f64) -> Self {
+        Self::new(ApertureShape::Circle { radius })
+    }
+
+    fn new(shape: ApertureShape) -> Self {
+        ApertureConfig {
+            center_x: 0.0,
+            center_y: 0.0,
+            shape,
+            stroke_color: "#2c2c2c".to_string(),
+            stroke_width: 0.2,
+        }
+    }
+
+    /// Validate the shape and stroke
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        if !(self.center_x.is_finite() && self.center_y.is_finite()) {
+            return Err(SpirographError::InvalidParameter(
+                "Aperture center must be finite".to_string(),
+            ));
+        }
+        if !(self.stroke_width.is_finite() && self.stroke_width >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "Aperture stroke width must be non-negative and finite".to_string(),
+            ));
+        }
+        match self.shape {
+            ApertureShape::Rect {
+                width,
+                height,
+                corner_radius,
+                rotation,
+            } => {
+                if !(positive(width) && positive(height)) {
+                    return Err(SpirographError::InvalidParameter(
+                        "Aperture width and height must be positive".to_string(),
+                    ));
+                }
+                if !(corner_radius.is_finite()
+                    && corner_radius >= 0.0
+                    && corner_radius <= width.min(height) / 2.0)
+                {
+                    return Err(SpirographError::InvalidParameter(format!(
+                        "Aperture corner radius must be between 0 and {}",
+                        width.min(height) / 2.0
+                    )));
+                }
+                if !rotation.is_finite() {
+                    return Err(SpirographError::InvalidParameter(
+                        "Aperture rotation must be finite".to_string(),
+                    ));
+                }
+            }
+            ApertureShape::Circle { radius } => {
+                if !positive(radius) {
+                    return Err(SpirographError::InvalidParameter(
+                        "Aperture radius must be positive".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The aperture as a convex polygon winding counter-clockwise (x-right,
+    /// y-up axes), not repeating its first point
+    ///
+    /// Circles are inscribed polygons as for holes, and rounded corners
+    /// are arcs of 8 segments.
+    pub fn outline(&self) -> Vec<Point2D> {
+        let center = Point2D::new(self.center_x, self.center_y);
+        match self.shape {
+            ApertureShape::Circle { radius } => circle_outline(center, radius, HOLE_SEGMENTS),
+            ApertureShape::Rect {
+                width,
+                height,
+                corner_radius,
+                rotation,
+            } => {
+                let (half_w, half_h) = (width / 2.0, height / 2.0);
+                let r = corner_radius.clamp(0.0, half_w.min(half_h));
+                let corners = [
+                    (half_w - r, half_h - r),
+                    (r - half_w, half_h - r),
+                    (r - half_w, r - half_h),
+                    (half_w - r, r - half_h),
+                ];
+                let mut local = Vec::new();
+                for (quadrant, (cx, cy)) in corners.into_iter().enumerate() {
+                    if r == 0.0 {
+                        local.push((cx, cy));
+                        continue;
+                    }
+                    for k in 0..=APERTURE_CORNER_SEGMENTS {
+                        let angle = std::f64::consts::FRAC_PI_2
+                            * (quadrant as f64 + k as f64 / APERTURE_CORNER_SEGMENTS as f64);
+                        local.push((cx + r * angle.cos(), cy + r * angle.sin()));
+                    }
+                }
+                let (sin, cos) = rotation.to_radians().sin_cos();
+                local
+                    .into_iter()
+                    .map(|(x, y)| {
+                        Point2D::new(center.x + x * cos - y * sin, center.y + x * sin + y * cos)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        self.center_x *= factor;
+        self.center_y *= factor;
+        self.stroke_width *= factor;
+        match &mut self.shape {
+            ApertureShape::Rect {
+                width,
+                height,
+                corner_radius,
+                ..
+            } => {
+                *width *= factor;
+                *height *= factor;
+                *corner_radius *= factor;
+            }
+            ApertureShape::Circle { radius } => *radius *= factor,
+        }
+    }
+}
+
+/// A small dial set into the main one, such as a small seconds or a
+/// chronograph counter
+///
+/// `face` is designed about its own origin. Its pattern layers are moved
+/// to the subdial center and clipped to `radius` in SVG and STL exports,
+/// and SVG draws a ring at the edge with the subdial's stroke; the face's
+/// own dial, bezel, holes and apertures are not used. With `mask`, the
+/// main pattern stops at the edge instead of showing through.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subdial {
+    pub center_x: f64,
+    pub center_y: f64,
+    pub radius: f64,
+    pub face: WatchFace,
+    pub mask: bool,
+    pub stroke_color: String,
+    pub stroke_width: f64,
+}
+
+impl Subdial {
+    /// Masking subdial at the origin with the default ring stroke
+    pub fn new(radius: f64, face: WatchFace) -> Self {
+        Subdial {
+            center_x: 0.0,
+            center_y: 0.0,
+            radius,
+            face,
+            mask: true,
+            stroke_color: "#2c2c2c".to_string(),
+            stroke_width: 0.2,
+        }
+    }
+
+    /// Validate the center, radius and ring stroke
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if !(self.center_x.is_finite() && self.center_y.is_finite()) {
+            return Err(SpirographError::InvalidParameter(
+                "Subdial center must be finite".to_string(),
+            ));
+        }
+        if !(self.radius.is_finite() && self.radius > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "Subdial radius must be positive".to_string(),
+            ));
+        }
+        if !(self.stroke_width.is_finite() && self.stroke_width >= 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "Subdial stroke width must be non-negative and finite".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn center(&self) -> Point2D {
+        Point2D::new(self.center_x, self.center_y)
+    }
+
+    /// Convex polygon covering the subdial circle, for cutting the main
+    /// pattern back to its edge
+    fn mask_outline(&self) -> Vec<Point2D> {
+        let circumradius = self.radius / (std::f64::consts::PI / HOLE_SEGMENTS as f64).cos();
+        circle_outline(self.center(), circumradius, HOLE_SEGMENTS)
+    }
+
+    /// The face's polylines for the solid exports, each flagged if closed,
+    /// clipped to `radius` less `inset` and moved to the center
+    fn export_polylines(&self, inset: f64) -> Vec<(Vec<Point2D>, bool)> {
+        let clip = DialShape::Circle {
+            radius: (self.radius - inset).max(0.0),
+        };
+        let center = self.center();
+        let mut polylines = self.face.guilloche.export_polylines(Some(clip));
+        for (points, _) in &mut polylines {
+            translate(points, center);
+        }
+        polylines
+    }
+
+    fn scale_lengths(&mut self, factor: f64) {
+        self.center_x *= factor;
+        self.center_y *= factor;
+        self.radius *= factor;
+        self.stroke_width *= factor;
+    }
+}
+
+/// Marker drawn at hobnail cell centers by `WatchFace::decorate_cells`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CellDecoration {
+    /// Filled circle of `radius` in the design unit, e.g. an applied gem
+    Circle { radius: f64, fill_color: String },
+    /// Dot of a fixed 0.15mm radius marking the position
+    Point { color: String },
+}
+
+/// Reference photo drawn under the pattern to line a design up with an
+/// existing dial
+///
+/// The href is written to the SVG as given; the image is never decoded.
+/// Width and offset are in millimeters whatever the design unit. The image
+/// is fitted into a square `width` wide centred on the dial, so a square
+/// photo of a round dial spans exactly `width`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceImage {
+    pub href: String,
+    pub width: f64,
+    pub opacity: f64,
+    pub rotation_deg: f64, // Clockwise on screen, about the dial center
+    pub offset: (f64, f64),
+}
+
+impl ReferenceImage {
+    /// SVG transform placing the image: rotate about the dial center, then
+    /// shift by the offset, both scaled by `scale` from millimeters
+    fn transform_matrix(&self, scale: f64) -> [f64; 6] {
+        let (sin, cos) = self.rotation_deg.to_radians().sin_cos();
+        [
+            cos,
+            sin,
+            -sin,
+            cos,
+            self.offset.0 * scale,
+            self.offset.1 * scale,
+        ]
+    }
+}
+
+/// One level of detail in a `WatchFace::export_lod_bundle` export
+#[derive(Debug, Clone, PartialEq)]
+pub struct LodSpec {
+    pub suffix: String,      // Appended to the bundle name: `{name}-{suffix}.svg`
+    pub point_budget: usize, // Most points the level should hold
+}
+
+impl LodSpec {
+    /// Create a level written with `suffix` and at most about `point_budget` points
+    pub fn new(suffix: &str, point_budget: usize) -> Self {
+        LodSpec {
+            suffix: suffix.to_string(),
+            point_budget,
+        }
+    }
+}
+
+/// A level written by `WatchFace::export_lod_bundle`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LodFile {
+    pub suffix: String,
+    pub path: PathBuf,
+    pub point_budget: usize,
+    pub point_count: usize,
+}
+
+/// Quote a string as a JSON string literal
+fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 /// WatchFace - A high-level wrapper around GuillochePattern for creating watch dials
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WatchFace {
     pub guilloche: GuillochePattern,
+    dial_shape: DialShape,
     dial_config: Option<DialConfig>,
     bezel_config: Option<BezelConfig>,
     holes: Vec<HoleConfig>,
+    apertures: Vec<ApertureConfig>,
+    settings: ProjectSettings,
+    max_points_per_path: usize,
+    svg_margin: f64,
+    fixed_viewbox: Option<(f64, f64, f64, f64)>,
+    reference: Option<ReferenceImage>,
+    metadata: Option<FileMetadata>,
+    opacity_envelopes: Vec<(LayerKind, usize, Envelope)>,
+    layer_styles: Vec<(LayerKind, usize, LayerStyle)>,
+    cell_decorations: Vec<(usize, usize, CellDecoration)>, // (layer, every_nth, decoration)
+    #[serde(default)]
+    subdials: Vec<Subdial>,
+    #[serde(default)]
+    hands: Option<Hands>,
+    #[serde(default)]
+    hands_in_exports: bool,
 }
 
 impl WatchFace {
     /// Create a new watch face with the specified radius
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{DraperieConfig, DraperieLayer, ExportConfig, WatchFace};
+    ///
+    /// let mut face = WatchFace::new(30.0).unwrap();
+    /// face.add_inner();
+    /// face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(24, 15.0)).unwrap());
+    /// face.generate();
+    ///
+    /// // The pattern stays on the dial
+    /// assert_eq!(face.lines().len(), 24);
+    /// # use turtles::doctest_support::{max_radius, temp_path};
+    /// assert!(max_radius(&face.lines(), (0.0, 0.0)) <= 30.0);
+    ///
+    /// let svg = face.to_svg_string().unwrap();
+    /// assert!(svg.contains("<path"));
+    ///
+    /// // STL is written to a file; a temporary one is removed when dropped
+    /// let stl = temp_path("dial.stl");
+    /// face.to_stl(stl.as_str(), &ExportConfig::default()).unwrap();
+    /// assert!(std::fs::metadata(stl.path()).unwrap().len() > 84);
+    /// ```
     pub fn new(radius: f64) -> Result<Self, SpirographError> {
-        let guilloche = GuillochePattern::new(radius)?;
+        Self::new_with_settings(radius, ProjectSettings::default())
+    }
+
+    /// Create a new watch face whose radius is checked by `policy` instead
+    /// of the 26-44mm wristwatch range
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{RadiusPolicy, WatchFace};
+    ///
+    /// // A pocket-watch dial
+    /// assert!(WatchFace::new(60.0).is_err());
+    /// let face = WatchFace::new_with_policy(60.0, RadiusPolicy::Unrestricted).unwrap();
+    /// assert_eq!(face.settings().radius_policy, RadiusPolicy::Unrestricted);
+    /// ```
+    pub fn new_with_policy(radius: f64, policy: RadiusPolicy) -> Result<Self, SpirographError> {
+        Self::new_with_settings(
+            radius,
+            ProjectSettings::default().with_radius_policy(policy),
+        )
+    }
+
+    /// Create a new watch face whose dimensions are in `settings.unit`
+    ///
+    /// The radius is validated against `settings.radius_policy`, by default
+    /// the usual 26-44mm range, after converting it to millimeters.
+    pub fn new_with_settings(
+        radius: f64,
+        settings: ProjectSettings,
+    ) -> Result<Self, SpirographError> {
+        Self::new_with_shape(DialShape::Circle { radius }, settings)
+    }
+
+    /// Create a new watch face with a round or oval dial
+    ///
+    /// Layers are clipped to the outline, and the dial, bezel and solid
+    /// exports follow it. The radius of the face is the larger semi-axis,
+    /// which is validated as in [`new_with_settings`](Self::new_with_settings).
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{DialShape, ProjectSettings, WatchFace};
+    ///
+    /// let shape = DialShape::Ellipse { rx: 32.0, ry: 26.0 };
+    /// let face = WatchFace::new_with_shape(shape, ProjectSettings::default()).unwrap();
+    /// assert_eq!(face.radius(), 32.0);
+    /// assert!(face.to_svg_string().unwrap().contains("<ellipse"));
+    /// ```
+    pub fn new_with_shape(
+        shape: DialShape,
+        settings: ProjectSettings,
+    ) -> Result<Self, SpirographError> {
+        shape.validate()?;
+        let radius = shape.max_semi_axis();
+        settings
+            .radius_policy
+            .check(radius * settings.unit.mm_per_unit())?;
         Ok(WatchFace {
-            guilloche,
+            guilloche: GuillochePattern::new_unchecked(radius),
+            dial_shape: shape,
             dial_config: None,
             bezel_config: None,
             holes: Vec::new(),
+            apertures: Vec::new(),
+            settings,
+            max_points_per_path: DEFAULT_MAX_POINTS_PER_PATH,
+            svg_margin: radius * 0.1,
+            fixed_viewbox: None,
+            reference: None,
+            metadata: None,
+            opacity_envelopes: Vec::new(),
+            layer_styles: Vec::new(),
+            cell_decorations: Vec::new(),
+            subdials: Vec::new(),
+            hands: None,
+            hands_in_exports: false,
         })
     }
 
+    /// The whole design as JSON: the dial shape and settings, dial,
+    /// bezel, holes and apertures, every layer with its configuration and
+    /// placement, and the per-layer styles and envelopes
+    ///
+    /// Generated lines and post-processing callbacks are not saved; a face
+    /// read back with [`from_json`](Self::from_json) generates the same
+    /// lines as this one.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{DraperieConfig, DraperieLayer, WatchFace};
+    ///
+    /// let mut face = WatchFace::new(30.0).unwrap();
+    /// face.add_inner();
+    /// face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(24, 15.0)).unwrap());
+    /// let json = face.to_json().unwrap();
+    ///
+    /// let mut copy = WatchFace::from_json(&json).unwrap();
+    /// face.generate();
+    /// copy.generate();
+    /// assert_eq!(copy.lines(), face.lines());
+    /// ```
+    pub fn to_json(&self) -> Result<String, SpirographError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| SpirographError::ExportError(format!("JSON export failed: {}", e)))
+    }
+
+    /// Read a design written by [`to_json`](Self::to_json)
+    ///
+    /// The dial shape, radius, apertures, styles and envelopes are checked
+    /// as when they are set in code. Layer parameters are checked by
+    /// [`validate`](Self::validate) and [`try_generate`](Self::try_generate),
+    /// as for layers whose configs were edited after they were added.
+    pub fn from_json(json: &str) -> Result<Self, SpirographError> {
+        let face: WatchFace = serde_json::from_str(json).map_err(|e| {
+            SpirographError::InvalidParameter(format!("invalid watch face JSON: {}", e))
+        })?;
+        face.dial_shape.validate()?;
+        face.settings
+            .radius_policy
+            .check(face.radius() * face.settings.unit.mm_per_unit())?;
+        for aperture in &face.apertures {
+            aperture.validate()?;
+        }
+        for subdial in &face.subdials {
+            face.check_subdial(subdial)?;
+        }
+        if let Some(hands) = &face.hands {
+            hands.validate(face.radius())?;
+        }
+        for (_, _, style) in &face.layer_styles {
+            style.validate()?;
+        }
+        for (_, _, envelope) in &face.opacity_envelopes {
+            envelope.validate()?;
+        }
+        Ok(face)
+    }
+
     /// Get the radius of the watch face
     pub fn radius(&self) -> f64 {
         self.guilloche.radius
     }
 
+    /// Get the outline of the dial
+    pub fn shape(&self) -> DialShape {
+        self.dial_shape
+    }
+
+    /// Get the project settings
+    pub fn settings(&self) -> &ProjectSettings {
+        &self.settings
+    }
+
+    /// Get the design unit
+    pub fn unit(&self) -> Unit {
+        self.settings.unit
+    }
+
+    /// Set the most points written to one SVG `<path>`
+    ///
+    /// Longer lines are split into consecutive paths grouped in a `<g>`.
+    pub fn set_max_points_per_path(&mut self, max_points: usize) -> Result<(), SpirographError> {
+        if max_points < 2 {
+            return Err(SpirographError::InvalidParameter(
+                "max_points_per_path must be at least 2".to_string(),
+            ));
+        }
+        self.max_points_per_path = max_points;
+        Ok(())
+    }
+
+    /// Write each layer's configuration and geometry as `data-` attributes
+    /// on its SVG group; see [`GuillochePattern::set_svg_layer_data`]
+    pub fn set_svg_layer_data(&mut self, enabled: bool) {
+        self.guilloche.set_svg_layer_data(enabled);
+    }
+
+    /// Set the space left around the content when sizing the SVG viewBox
+    ///
+    /// Defaults to a tenth of the dial radius.
+    pub fn set_svg_margin(&mut self, margin: f64) -> Result<(), SpirographError> {
+        if !margin.is_finite() || margin < 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "svg margin must be finite and non-negative".to_string(),
+            ));
+        }
+        self.svg_margin = margin;
+        Ok(())
+    }
+
+    /// Pin the SVG viewBox to `(min_x, min_y, width, height)` instead of
+    /// fitting it to the content
+    ///
+    /// Keeps frames the same size across an animation or a panel. `None`
+    /// restores automatic sizing. The dial clip is unaffected.
+    pub fn set_fixed_viewbox(
+        &mut self,
+        viewbox: Option<(f64, f64, f64, f64)>,
+    ) -> Result<(), SpirographError> {
+        if let Some((x, y, width, height)) = viewbox {
+            let finite = [x, y, width, height].iter().all(|v| v.is_finite());
+            if !(finite && width > 0.0 && height > 0.0) {
+                return Err(SpirographError::InvalidParameter(
+                    "fixed viewbox needs a finite origin and positive size".to_string(),
+                ));
+            }
+        }
+        self.fixed_viewbox = viewbox;
+        Ok(())
+    }
+
+    /// Set a reference image to overlay the design on
+    ///
+    /// Only exports that ask for it include the image (see
+    /// `to_svg_with_reference`), so production files never carry it.
+    pub fn set_reference_image(
+        &mut self,
+        href: &str,
+        width_mm: f64,
+        opacity: f64,
+        rotation_deg: f64,
+        offset: (f64, f64),
+    ) -> Result<(), SpirographError> {
+        if href.is_empty() {
+            return Err(SpirographError::InvalidParameter(
+                "reference image href must not be empty".to_string(),
+            ));
+        }
+        if !width_mm.is_finite() || width_mm <= 0.0 {
+            return Err(SpirographError::InvalidParameter(
+                "reference image width must be finite and positive".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&opacity) {
+            return Err(SpirographError::InvalidParameter(
+                "reference image opacity must be between 0 and 1".to_string(),
+            ));
+        }
+        if !(rotation_deg.is_finite() && offset.0.is_finite() && offset.1.is_finite()) {
+            return Err(SpirographError::InvalidParameter(
+                "reference image rotation and offset must be finite".to_string(),
+            ));
+        }
+        self.reference = Some(ReferenceImage {
+            href: href.to_string(),
+            width: width_mm,
+            opacity,
+            rotation_deg,
+            offset,
+        });
+        Ok(())
+    }
+
+    /// Get the reference image, if one is set
+    pub fn reference_image(&self) -> Option<&ReferenceImage> {
+        self.reference.as_ref()
+    }
+
+    /// Remove the reference image
+    pub fn clear_reference(&mut self) {
+        self.reference = None;
+    }
+
+    /// Set the traceability metadata written into SVG, STEP and DXF exports
+    pub fn set_metadata(&mut self, metadata: FileMetadata) {
+        self.metadata = Some(metadata);
+    }
+
+    /// Get the export metadata, if set
+    pub fn metadata(&self) -> Option<&FileMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Stop embedding metadata in exports
+    pub fn clear_metadata(&mut self) {
+        self.metadata = None;
+    }
+
+    /// Switch the design unit, rescaling every config and generated line
+    ///
+    /// Exports never need this: they convert on the way out. Use it only to
+    /// continue a design in another unit.
+    pub fn convert_units(&mut self, to: Unit) {
+        let factor = self.settings.unit.factor_to(to);
+        if factor != 1.0 {
+            self.guilloche.scale_lengths(factor);
+            self.dial_shape.scale_lengths(factor);
+            if let Some(dial) = self.dial_config.as_mut() {
+                dial.stroke_width *= factor;
+            }
+            if let Some(bezel) = self.bezel_config.as_mut() {
+                bezel.stroke_width *= factor;
+            }
+            for hole in &mut self.holes {
+                hole.center_x *= factor;
+                hole.center_y *= factor;
+                hole.radius *= factor;
+            }
+            for aperture in &mut self.apertures {
+                aperture.scale_lengths(factor);
+            }
+            for subdial in &mut self.subdials {
+                subdial.scale_lengths(factor);
+                subdial.face.convert_units(to);
+            }
+            if let Some(hands) = self.hands.as_mut() {
+                hands.scale_lengths(factor);
+            }
+            self.svg_margin *= factor;
+            for (_, _, envelope) in &mut self.opacity_envelopes {
+                envelope.scale_lengths(factor);
+            }
+            for (_, _, style) in &mut self.layer_styles {
+                style.stroke_width *= factor;
+            }
+            for (_, _, decoration) in &mut self.cell_decorations {
+                if let CellDecoration::Circle { radius, .. } = decoration {
+                    *radius *= factor;
+                }
+            }
+            if let Some((x, y, width, height)) = self.fixed_viewbox.as_mut() {
+                *x *= factor;
+                *y *= factor;
+                *width *= factor;
+                *height *= factor;
+            }
+        }
+        self.settings.unit = to;
+    }
+
+    /// This face with its geometry expressed in `unit`, cloning only if it differs
+    fn in_unit(&self, unit: Unit) -> Cow<'_, WatchFace> {
+        if unit == self.settings.unit {
+            Cow::Borrowed(self)
+        } else {
+            let mut converted = self.clone();
+            converted.convert_units(unit);
+            Cow::Owned(converted)
+        }
+    }
+
+    /// This face with its pattern lines simplified to `tolerance`, or
+    /// borrowed as-is when `tolerance` is zero
+    fn simplified(&self, tolerance: f64) -> Cow<'_, WatchFace> {
+        if tolerance > 0.0 {
+            let mut simplified = self.clone();
+            simplified.guilloche.simplify_lines(tolerance);
+            Cow::Owned(simplified)
+        } else {
+            Cow::Borrowed(self)
+        }
+    }
+
     /// Add the inner dial circle
     pub fn add_inner(&mut self) {
         self.add_inner_with_config(DialConfig::default());
@@ -133,6 +945,236 @@ impl WatchFace {
         });
     }
 
+    /// Add a complication aperture such as a date window or subdial opening
+    ///
+    /// Pattern lines are cut at the aperture edge in SVG, raster and HPGL
+    /// exports, its outline is drawn with the aperture's stroke, and solid
+    /// and DXF exports cut it through the dial. Apertures should not overlap
+    /// each other or the holes.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{ApertureConfig, DraperieConfig, DraperieLayer, WatchFace};
+    ///
+    /// let mut face = WatchFace::new(30.0).unwrap();
+    /// face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(24, 15.0)).unwrap());
+    /// face.add_aperture_at_clock(3, 0, 18.0, ApertureConfig::rect(6.0, 4.0, 0.5, 0.0))
+    ///     .unwrap();
+    /// face.generate();
+    /// assert!(face.to_svg_string().unwrap().contains("id=\"apertures\""));
+    /// ```
+    pub fn add_aperture(&mut self, config: ApertureConfig) -> Result<(), SpirographError> {
+        config.validate()?;
+        self.apertures.push(config);
+        Ok(())
+    }
+
+    /// Add a complication aperture centred at a clock position, replacing
+    /// the center of `aperture`
+    pub fn add_aperture_at_clock(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        aperture: ApertureConfig,
+    ) -> Result<(), SpirographError> {
+        let (x, y) = crate::common::clock_to_cartesian(hour, minute, distance);
+        self.add_aperture(ApertureConfig {
+            center_x: x,
+            center_y: y,
+            ..aperture
+        })
+    }
+
+    /// The complication apertures, in the order added
+    pub fn complication_apertures(&self) -> &[ApertureConfig] {
+        &self.apertures
+    }
+
+    /// Remove every complication aperture
+    pub fn clear_apertures(&mut self) {
+        self.apertures.clear();
+    }
+
+    /// Add a subdial, converting its face to this face's unit
+    ///
+    /// The subdial must lie on the dial. Generating this face generates
+    /// the subdial faces too.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{DraperieConfig, DraperieLayer, FlinqueConfig, FlinqueLayer, Subdial, WatchFace};
+    ///
+    /// let mut seconds = WatchFace::new(30.0).unwrap();
+    /// seconds.add_flinque_layer(FlinqueLayer::new(8.0, FlinqueConfig::default()).unwrap());
+    ///
+    /// let mut face = WatchFace::new(30.0).unwrap();
+    /// face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(24, 15.0)).unwrap());
+    /// face.add_subdial_at_clock(6, 0, 15.0, 7.0, seconds).unwrap();
+    /// face.generate();
+    /// assert!(face.to_svg_string().unwrap().contains("id=\"subdial-0\""));
+    /// ```
+    pub fn add_subdial(&mut self, mut subdial: Subdial) -> Result<(), SpirographError> {
+        subdial.face.convert_units(self.settings.unit);
+        self.check_subdial(&subdial)?;
+        self.subdials.push(subdial);
+        Ok(())
+    }
+
+    /// Add a masking subdial of `radius` centred at a clock position, with
+    /// `face` as its pattern
+    pub fn add_subdial_at_clock(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+        radius: f64,
+        face: WatchFace,
+    ) -> Result<(), SpirographError> {
+        let (x, y) = crate::common::clock_to_cartesian(hour, minute, distance);
+        self.add_subdial(Subdial {
+            center_x: x,
+            center_y: y,
+            ..Subdial::new(radius, face)
+        })
+    }
+
+    /// The subdials, in the order added
+    pub fn subdials(&self) -> &[Subdial] {
+        &self.subdials
+    }
+
+    /// Remove every subdial
+    pub fn clear_subdials(&mut self) {
+        self.subdials.clear();
+    }
+
+    /// The generated lines of subdial `index`, clipped to its circle and
+    /// moved to its center, spirographs first
+    pub fn subdial_lines(&self, index: usize) -> Option<Vec<Vec<Point2D>>> {
+        let subdial = self.subdials.get(index)?;
+        let clip = DialShape::Circle {
+            radius: subdial.radius,
+        };
+        let mut lines: Vec<Vec<Point2D>> = subdial
+            .face
+            .guilloche
+            .plot_layers(Some(clip))
+            .into_iter()
+            .flatten()
+            .collect();
+        for line in &mut lines {
+            translate(line, subdial.center());
+        }
+        Some(lines)
+    }
+
+    /// Check a subdial and that it lies on the dial, in this face's unit
+    fn check_subdial(&self, subdial: &Subdial) -> Result<(), SpirographError> {
+        subdial.validate()?;
+        let edge = circle_outline(subdial.center(), subdial.radius, HOLE_SEGMENTS);
+        if !edge.iter().all(|&p| self.dial_shape.contains(p)) {
+            return Err(SpirographError::InvalidParameter(
+                "Subdial must lie on the dial".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Outlines the main pattern is cut back to: the complication
+    /// apertures, then the masking subdials
+    fn pattern_cut_outlines(&self) -> Vec<Vec<Point2D>> {
+        let mut outlines = self.aperture_outlines();
+        outlines.extend(self.subdial_masks());
+        outlines
+    }
+
+    /// Outlines of the masking subdials
+    fn subdial_masks(&self) -> Vec<Vec<Point2D>> {
+        self.subdials
+            .iter()
+            .filter(|subdial| subdial.mask)
+            .map(Subdial::mask_outline)
+            .collect()
+    }
+
+    /// Draw hands set to `hour:minute`, and to `second` with the seconds
+    /// hand when given, replacing any hands already set
+    ///
+    /// Hands are filled over everything else in SVG and left out of the
+    /// solid exports unless [`set_hands_in_exports`](Self::set_hands_in_exports)
+    /// is on. Lengths are relative to the face radius, so hands stay on
+    /// the dial.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{HandsSet, WatchFace};
+    ///
+    /// let mut face = WatchFace::new(38.0).unwrap();
+    /// face.add_inner();
+    /// face.add_hands(10, 10, Some(30), HandsSet::default()).unwrap();
+    /// assert!(face.add_hands(10, 60, None, HandsSet::default()).is_err());
+    ///
+    /// assert_eq!(face.hand_outlines().len(), 3);
+    /// assert!(face.to_svg_string().unwrap().contains("id=\"hand-second\""));
+    /// ```
+    pub fn add_hands(
+        &mut self,
+        hour: u32,
+        minute: u32,
+        second: Option<u32>,
+        configs: HandsSet,
+    ) -> Result<(), SpirographError> {
+        let hands = Hands {
+            hour,
+            minute,
+            second,
+            configs,
+        };
+        hands.validate(self.radius())?;
+        self.hands = Some(hands);
+        Ok(())
+    }
+
+    /// Remove the hands
+    pub fn clear_hands(&mut self) {
+        self.hands = None;
+    }
+
+    /// Cut the hand outlines as grooves in STL and add them as a `hands`
+    /// part in STEP (off by default)
+    pub fn set_hands_in_exports(&mut self, enabled: bool) {
+        self.hands_in_exports = enabled;
+    }
+
+    /// The closed outlines of each hand, hour then minute then second
+    ///
+    /// A Breguet hand has two outlines, the second the hole in its ring.
+    pub fn hand_outlines(&self) -> Vec<Vec<Vec<Point2D>>> {
+        self.placed_hands()
+            .into_iter()
+            .map(|(_, _, outlines)| outlines)
+            .collect()
+    }
+
+    /// The hands to draw with their names, configs and outlines
+    fn placed_hands(&self) -> Vec<PlacedHand<'_>> {
+        // Hands are validated when set, and a unit change scales their
+        // widths with the radius
+        self.hands
+            .as_ref()
+            .and_then(|hands| hands.placed(self.radius()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Hand outlines for the solid exports, when they are included
+    fn exported_hand_outlines(&self) -> Vec<Vec<Point2D>> {
+        if !self.hands_in_exports {
+            return Vec::new();
+        }
+        self.hand_outlines().into_iter().flatten().collect()
+    }
+
     /// Add a horizontal spirograph layer
     pub fn add_horizontal_layer(&mut self, spiro: HorizontalSpirograph) {
         self.guilloche.add_horizontal_layer(spiro);
@@ -153,6 +1195,17 @@ impl WatchFace {
         self.guilloche.add_flinque_layer(flinque);
     }
 
+    /// Add a flinqué layer stroked with `style`
+    pub fn add_flinque_layer_styled(
+        &mut self,
+        flinque: FlinqueLayer,
+        style: LayerStyle,
+    ) -> Result<(), SpirographError> {
+        style.validate()?;
+        self.guilloche.add_flinque_layer(flinque);
+        self.style_last(LayerKind::Flinque, style)
+    }
+
     /// Add a flinqué layer at a clock position
     pub fn add_flinque_at_clock(
         &mut self,
@@ -171,7 +1224,18 @@ impl WatchFace {
         self.guilloche.add_diamant_layer(diamant);
     }
 
-    /// Add a diamant layer at a clock position
+    /// Add a diamant layer stroked with `style`
+    pub fn add_diamant_layer_styled(
+        &mut self,
+        diamant: DiamantLayer,
+        style: LayerStyle,
+    ) -> Result<(), SpirographError> {
+        style.validate()?;
+        self.guilloche.add_diamant_layer(diamant);
+        self.style_last(LayerKind::Diamant, style)
+    }
+
+    /// Add a diamant layer at a clock position
     pub fn add_diamant_at_clock(
         &mut self,
         config: DiamantConfig,
@@ -188,6 +1252,17 @@ impl WatchFace {
         self.guilloche.add_draperie_layer(draperie);
     }
 
+    /// Add a draperie layer stroked with `style`
+    pub fn add_draperie_layer_styled(
+        &mut self,
+        draperie: DraperieLayer,
+        style: LayerStyle,
+    ) -> Result<(), SpirographError> {
+        style.validate()?;
+        self.guilloche.add_draperie_layer(draperie);
+        self.style_last(LayerKind::Draperie, style)
+    }
+
     /// Add a draperie layer at a clock position
     pub fn add_draperie_at_clock(
         &mut self,
@@ -205,6 +1280,17 @@ impl WatchFace {
         self.guilloche.add_huiteight_layer(huiteight);
     }
 
+    /// Add a huit-eight layer stroked with `style`
+    pub fn add_huiteight_layer_styled(
+        &mut self,
+        huiteight: HuitEightLayer,
+        style: LayerStyle,
+    ) -> Result<(), SpirographError> {
+        style.validate()?;
+        self.guilloche.add_huiteight_layer(huiteight);
+        self.style_last(LayerKind::HuitEight, style)
+    }
+
     /// Add a huit-eight layer at a clock position
     pub fn add_huiteight_at_clock(
         &mut self,
@@ -222,6 +1308,17 @@ impl WatchFace {
         self.guilloche.add_limacon_layer(limacon);
     }
 
+    /// Add a limaçon layer stroked with `style`
+    pub fn add_limacon_layer_styled(
+        &mut self,
+        limacon: LimaconLayer,
+        style: LayerStyle,
+    ) -> Result<(), SpirographError> {
+        style.validate()?;
+        self.guilloche.add_limacon_layer(limacon);
+        self.style_last(LayerKind::Limacon, style)
+    }
+
     /// Add a limaçon layer at a clock position
     pub fn add_limacon_at_clock(
         &mut self,
@@ -239,6 +1336,17 @@ impl WatchFace {
         self.guilloche.add_paon_layer(paon);
     }
 
+    /// Add a paon layer stroked with `style`
+    pub fn add_paon_layer_styled(
+        &mut self,
+        paon: PaonLayer,
+        style: LayerStyle,
+    ) -> Result<(), SpirographError> {
+        style.validate()?;
+        self.guilloche.add_paon_layer(paon);
+        self.style_last(LayerKind::Paon, style)
+    }
+
     /// Add a paon layer at a clock position
     pub fn add_paon_at_clock(
         &mut self,
@@ -256,6 +1364,17 @@ impl WatchFace {
         self.guilloche.add_clous_de_paris_layer(cdp);
     }
 
+    /// Add a clous de Paris layer stroked with `style`
+    pub fn add_clous_de_paris_layer_styled(
+        &mut self,
+        cdp: ClousDeParisLayer,
+        style: LayerStyle,
+    ) -> Result<(), SpirographError> {
+        style.validate()?;
+        self.guilloche.add_clous_de_paris_layer(cdp);
+        self.style_last(LayerKind::ClousDeParis, style)
+    }
+
     /// Add a clous de Paris layer at a clock position
     pub fn add_clous_de_paris_at_clock(
         &mut self,
@@ -268,6 +1387,48 @@ impl WatchFace {
             .add_clous_de_paris_at_clock(config, hour, minute, distance)
     }
 
+    /// Draw `decoration` at every `every_nth` hobnail of a clous de Paris layer
+    ///
+    /// The layer is the `layer_index`-th clous de Paris layer. A cell is
+    /// decorated when its grid column plus row is a multiple of
+    /// `every_nth`, so 2 decorates every other hobnail in a checkerboard
+    /// and 1 decorates them all. Decorations are drawn in the SVG above
+    /// the pattern lines, at the centers from
+    /// [`ClousDeParisLayer::cell_centers`].
+    pub fn decorate_cells(
+        &mut self,
+        layer_index: usize,
+        every_nth: usize,
+        decoration: CellDecoration,
+    ) -> Result<(), SpirographError> {
+        if self.guilloche.clous_de_paris_layer(layer_index).is_none() {
+            return Err(SpirographError::InvalidParameter(format!(
+                "no clous de Paris layer at index {}",
+                layer_index
+            )));
+        }
+        if every_nth == 0 {
+            return Err(SpirographError::InvalidParameter(
+                "every_nth must be at least 1".to_string(),
+            ));
+        }
+        if let CellDecoration::Circle { radius, .. } = decoration {
+            if !(radius.is_finite() && radius > 0.0) {
+                return Err(SpirographError::InvalidParameter(
+                    "decoration radius must be positive and finite".to_string(),
+                ));
+            }
+        }
+        self.cell_decorations
+            .push((layer_index, every_nth, decoration));
+        Ok(())
+    }
+
+    /// Remove every cell decoration
+    pub fn clear_cell_decorations(&mut self) {
+        self.cell_decorations.clear();
+    }
+
     /// Add a cube (tumbling blocks) pattern layer
     pub fn add_cube_layer(&mut self, cube: CubeLayer) {
         self.guilloche.add_cube_layer(cube);
@@ -285,371 +1446,1378 @@ impl WatchFace {
             .add_cube_at_clock(config, hour, minute, distance)
     }
 
-    /// Generate all layers
-    pub fn generate(&mut self) {
-        self.guilloche.generate();
+    /// Add a phyllotaxis (sunflower) pattern layer
+    pub fn add_phyllotaxis_layer(&mut self, phyllotaxis: PhyllotaxisLayer) {
+        self.guilloche.add_phyllotaxis_layer(phyllotaxis);
     }
 
-    /// Get total layer count
-    pub fn layer_count(&self) -> usize {
-        self.guilloche.layer_count()
+    /// Add a phyllotaxis layer at a clock position
+    pub fn add_phyllotaxis_at_clock(
+        &mut self,
+        config: PhyllotaxisConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        self.guilloche
+            .add_phyllotaxis_at_clock(config, hour, minute, distance)
     }
 
-    /// Export to SVG
-    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
-        use ::svg::node::element::path::Data;
-        use ::svg::node::element::{Circle, Path};
-        use ::svg::Document;
-
-        let radius = self.guilloche.radius;
-        let size = radius * 2.5;
-        let mut document = Document::new()
-            .set("viewBox", (-size, -size, size * 2.0, size * 2.0))
-            .set("width", format!("{}mm", size * 2.0))
-            .set("height", format!("{}mm", size * 2.0));
-
-        // Add inner dial circle if configured
-        if let Some(ref dial) = self.dial_config {
-            let dial_circle = Circle::new()
-                .set("cx", 0)
-                .set("cy", 0)
-                .set("r", radius)
-                .set("fill", dial.fill_color.as_str())
-                .set("stroke", dial.stroke_color.as_str())
-                .set("stroke-width", dial.stroke_width);
-            document = document.add(dial_circle);
-        }
+    /// Add a grain-de-riz (rice grain) pattern layer
+    pub fn add_grain_de_riz_layer(&mut self, grain_de_riz: GrainDeRizLayer) {
+        self.guilloche.add_grain_de_riz_layer(grain_de_riz);
+    }
 
-        // Clip all pattern content to the dial circle
-        {
-            use ::svg::node::element::{ClipPath, Group};
+    /// Add a grain-de-riz layer at a clock position
+    pub fn add_grain_de_riz_at_clock(
+        &mut self,
+        config: GrainDeRizConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        self.guilloche
+            .add_grain_de_riz_at_clock(config, hour, minute, distance)
+    }
 
-            let clip_circle = Circle::new().set("cx", 0).set("cy", 0).set("r", radius);
-            let clip = ClipPath::new().set("id", "dial-clip").add(clip_circle);
-            document = document.add(clip);
-        }
+    /// Add a soleil (sunray) pattern layer
+    pub fn add_soleil_layer(&mut self, soleil: SoleilLayer) {
+        self.guilloche.add_soleil_layer(soleil);
+    }
 
-        // Guilloche line colors
-        let colors = [
-            "#1a1a1a", "#2d2d2d", "#3a3a3a", "#454545", "#505050", "#5a5a5a",
-        ];
-        let stroke_widths = [0.04, 0.035, 0.03, 0.03, 0.025, 0.025];
+    /// Add a soleil layer at a clock position
+    pub fn add_soleil_at_clock(
+        &mut self,
+        config: SoleilConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        self.guilloche
+            .add_soleil_at_clock(config, hour, minute, distance)
+    }
 
-        // All pattern content goes inside a clipped group
-        let mut pattern_group = {
-            use ::svg::node::element::Group;
-            Group::new().set("clip-path", "url(#dial-clip)")
-        };
+    /// Add a Côtes de Genève (Geneva stripes) pattern layer
+    pub fn add_cotes_de_geneve_layer(&mut self, cotes: CotesDeGeneveLayer) {
+        self.guilloche.add_cotes_de_geneve_layer(cotes);
+    }
 
-        // Render spirograph layers from guilloche
-        for (i, points) in self.get_spirograph_points().iter().enumerate() {
-            if points.is_empty() {
-                continue;
-            }
+    /// Add a Côtes de Genève layer at a clock position
+    pub fn add_cotes_de_geneve_at_clock(
+        &mut self,
+        config: CotesDeGeneveConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        self.guilloche
+            .add_cotes_de_geneve_at_clock(config, hour, minute, distance)
+    }
 
-            let mut data = Data::new().move_to((points[0].x, points[0].y));
-            for point in points.iter().skip(1) {
-                data = data.line_to((point.x, point.y));
-            }
-            data = data.close();
+    /// Add a tapisserie (tapestry, waffle) pattern layer
+    pub fn add_tapisserie_layer(&mut self, tapisserie: TapisserieLayer) {
+        self.guilloche.add_tapisserie_layer(tapisserie);
+    }
 
-            let color = colors[i % colors.len()];
-            let stroke_width = stroke_widths[i % stroke_widths.len()];
-            let path = Path::new()
-                .set("fill", "none")
-                .set("stroke", color)
-                .set("stroke-width", stroke_width)
-                .set("stroke-linecap", "round")
-                .set("stroke-linejoin", "round")
-                .set("d", data);
+    /// Add a tapisserie layer at a clock position
+    pub fn add_tapisserie_at_clock(
+        &mut self,
+        config: TapisserieConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        self.guilloche
+            .add_tapisserie_at_clock(config, hour, minute, distance)
+    }
 
-            pattern_group = pattern_group.add(path);
-        }
+    /// Add a vague (wave, flammes) pattern layer
+    pub fn add_vague_layer(&mut self, vague: VagueLayer) {
+        self.guilloche.add_vague_layer(vague);
+    }
 
-        // Render flinqué layers from guilloche
-        for wave_lines in self.get_flinque_lines() {
-            for wave_points in wave_lines {
-                if wave_points.is_empty() {
-                    continue;
-                }
+    /// Add a vague layer at a clock position
+    pub fn add_vague_at_clock(
+        &mut self,
+        config: VagueConfig,
+        hour: u32,
+        minute: u32,
+        distance: f64,
+    ) -> Result<(), SpirographError> {
+        self.guilloche
+            .add_vague_at_clock(config, hour, minute, distance)
+    }
 
-                let mut data = Data::new().move_to((wave_points[0].x, wave_points[0].y));
-                for point in wave_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
+    /// Add an engraved text layer
+    pub fn add_text_layer(&mut self, text: EngravedTextLayer) {
+        self.guilloche.add_text_layer(text);
+    }
 
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+    /// Add engraved numerals or text from a configuration
+    pub fn add_text(&mut self, config: EngravedTextConfig) -> Result<(), SpirographError> {
+        self.guilloche.add_text(config)
+    }
 
-                pattern_group = pattern_group.add(path);
-            }
-        }
+    /// Add a chapter ring layer
+    pub fn add_chapter_ring_layer(&mut self, ring: ChapterRingLayer) {
+        self.guilloche.add_chapter_ring_layer(ring);
+    }
 
-        // Render diamant layers from guilloche
-        for circle_lines in self.get_diamant_lines() {
-            for circle_points in circle_lines {
-                if circle_points.is_empty() {
-                    continue;
-                }
+    /// Add a railroad chapter ring from a configuration
+    ///
+    /// Its index cutouts are cut through the STL base slab and written to
+    /// the DXF profile along with the holes.
+    pub fn add_chapter_ring(&mut self, config: ChapterRingConfig) -> Result<(), SpirographError> {
+        self.guilloche.add_chapter_ring(config)
+    }
 
-                let mut data = Data::new().move_to((circle_points[0].x, circle_points[0].y));
-                for point in circle_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
+    /// Add a railroad minute track of `ticks` radial ticks between two
+    /// radii, with every `major_every`-th tick drawn as a bolder bar
+    ///
+    /// This is a chapter ring with the default tick motif, so it also draws
+    /// the two track circles.
+    pub fn add_minute_track(
+        &mut self,
+        inner_r: f64,
+        outer_r: f64,
+        ticks: usize,
+        major_every: usize,
+    ) -> Result<(), SpirographError> {
+        self.add_chapter_ring(ChapterRingConfig {
+            minutes: ticks,
+            major_every,
+            ..ChapterRingConfig::new(inner_r, outer_r)
+        })
+    }
 
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+    /// Add an index markers layer
+    pub fn add_index_markers_layer(&mut self, markers: IndexMarkersLayer) {
+        self.guilloche.add_index_markers_layer(markers);
+    }
 
-                pattern_group = pattern_group.add(path);
-            }
+    /// Add hour or minute index markers from a configuration
+    ///
+    /// The markers are geometry like any other layer, so they are engraved
+    /// in STL and STEP exports as well as drawn in SVG. The configuration's
+    /// style, if any, becomes the layer's style.
+    ///
+    /// # Example
+    /// ```
+    /// use turtles::{IndexConfig, IndexShape, WatchFace};
+    ///
+    /// let mut face = WatchFace::new(30.0).unwrap();
+    /// let dots = IndexConfig::new(IndexShape::Dot { radius: 0.6 }, 24.0, 26.0).excluding(3, 0);
+    /// face.add_index_markers(dots).unwrap();
+    /// face.generate();
+    /// assert_eq!(face.lines().len(), 11);
+    /// ```
+    pub fn add_index_markers(&mut self, config: IndexConfig) -> Result<(), SpirographError> {
+        let style = config.style.clone();
+        self.guilloche.add_index_markers(config)?;
+        match style {
+            Some(style) => self.style_last(LayerKind::IndexMarkers, style),
+            None => Ok(()),
         }
+    }
 
-        // Render draperie layers from guilloche
-        for ring_lines in self.get_draperie_lines() {
-            for ring_points in ring_lines {
-                if ring_points.is_empty() {
-                    continue;
-                }
+    /// Add fixed polylines in the face's current unit as a layer
+    pub fn add_imported_layer(&mut self, lines: Vec<Vec<Point2D>>) {
+        self.guilloche.add_imported_layer(lines);
+    }
 
-                let mut data = Data::new().move_to((ring_points[0].x, ring_points[0].y));
-                for point in ring_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
+    /// Add the straight-line paths of an SVG file (or SVG text) as a layer,
+    /// returning the warnings for the paths that were skipped
+    ///
+    /// Coordinates are taken to be in the face's current unit.
+    pub fn add_imported_svg(&mut self, path_or_str: &str) -> Result<Vec<String>, SpirographError> {
+        self.guilloche.add_imported_svg(path_or_str)
+    }
 
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+    /// Register a callback that edits each layer's lines after generation
+    ///
+    /// See [`GuillochePattern::add_post_process`]. Callbacks see lines in
+    /// the face's current unit.
+    pub fn add_post_process<F>(&mut self, f: F)
+    where
+        F: Fn(&mut Vec<Vec<Point2D>>, LayerKind, usize) + Send + Sync + 'static,
+    {
+        self.guilloche.add_post_process(f);
+    }
 
-                pattern_group = pattern_group.add(path);
-            }
-        }
+    /// Remove every post-processing callback
+    pub fn clear_post_processes(&mut self) {
+        self.guilloche.clear_post_processes();
+    }
 
-        // Render huiteight layers from guilloche
-        for curve_lines in self.get_huiteight_lines() {
-            for curve_points in curve_lines {
-                if curve_points.is_empty() {
-                    continue;
-                }
+    /// Fade one layer's SVG lines with `envelope`
+    ///
+    /// The layer is the `index`-th of its kind, counted as for
+    /// post-process callbacks. Each line's opacity is the envelope at the
+    /// line's mean angle and mean radius about the dial center. Setting an
+    /// envelope again for the same layer replaces it.
+    pub fn set_opacity_envelope(
+        &mut self,
+        kind: LayerKind,
+        index: usize,
+        envelope: Envelope,
+    ) -> Result<(), SpirographError> {
+        envelope.validate()?;
+        self.opacity_envelopes
+            .retain(|(k, i, _)| (*k, *i) != (kind, index));
+        self.opacity_envelopes.push((kind, index, envelope));
+        Ok(())
+    }
 
-                let mut data = Data::new().move_to((curve_points[0].x, curve_points[0].y));
-                for point in curve_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
+    /// Get the opacity envelope of the `index`-th layer of `kind`, if set
+    pub fn opacity_envelope(&self, kind: LayerKind, index: usize) -> Option<&Envelope> {
+        self.opacity_envelopes
+            .iter()
+            .find(|(k, i, _)| (*k, *i) == (kind, index))
+            .map(|(_, _, envelope)| envelope)
+    }
 
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+    /// Draw every layer fully opaque again
+    pub fn clear_opacity_envelopes(&mut self) {
+        self.opacity_envelopes.clear();
+    }
 
-                pattern_group = pattern_group.add(path);
-            }
+    /// Stroke one layer's SVG lines with `style`
+    ///
+    /// The layer is the `index`-th of its kind, counted as for opacity
+    /// envelopes. Setting a style again for the same layer replaces it;
+    /// spirograph layers keep their own palette.
+    pub fn set_layer_style(
+        &mut self,
+        kind: LayerKind,
+        index: usize,
+        style: LayerStyle,
+    ) -> Result<(), SpirographError> {
+        if kind == LayerKind::Spirograph {
+            return Err(SpirographError::InvalidParameter(
+                "spirograph layers are not styled per layer".to_string(),
+            ));
         }
+        style.validate()?;
+        self.layer_styles
+            .retain(|(k, i, _)| (*k, *i) != (kind, index));
+        self.layer_styles.push((kind, index, style));
+        Ok(())
+    }
 
-        // Render limaçon layers from guilloche
-        for curve_lines in self.get_limacon_lines() {
-            for curve_points in curve_lines {
-                if curve_points.is_empty() {
-                    continue;
-                }
+    /// Get the style of the `index`-th layer of `kind`, if set
+    pub fn layer_style(&self, kind: LayerKind, index: usize) -> Option<&LayerStyle> {
+        self.layer_styles
+            .iter()
+            .find(|(k, i, _)| (*k, *i) == (kind, index))
+            .map(|(_, _, style)| style)
+    }
 
-                let mut data = Data::new().move_to((curve_points[0].x, curve_points[0].y));
-                for point in curve_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
+    /// Draw every layer with the default stroke again
+    pub fn clear_layer_styles(&mut self) {
+        self.layer_styles.clear();
+    }
 
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+    /// Style the layer just added as the last of its kind
+    fn style_last(&mut self, kind: LayerKind, style: LayerStyle) -> Result<(), SpirographError> {
+        let index = self.guilloche.kind_count(kind) - 1;
+        self.set_layer_style(kind, index, style)
+    }
 
-                pattern_group = pattern_group.add(path);
-            }
+    /// Generate all layers
+    pub fn generate(&mut self) {
+        self.guilloche.generate();
+        for subdial in &mut self.subdials {
+            subdial.face.generate();
         }
+    }
 
-        // Render paon layers from guilloche
-        for line_set in self.get_paon_lines() {
-            for line_points in line_set {
-                if line_points.is_empty() {
-                    continue;
-                }
-
-                let mut data = Data::new().move_to((line_points[0].x, line_points[0].y));
-                for point in line_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
-
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+    /// Check every layer's parameters without generating anything
+    ///
+    /// Spirograph radii are checked in millimeters whatever the face's unit.
+    pub fn validate(&self) -> Vec<LayerValidationIssue> {
+        self.guilloche
+            .validate_scaled(self.settings.unit.mm_per_unit())
+    }
 
-                pattern_group = pattern_group.add(path);
-            }
+    /// Generate all layers, or fail listing every invalid layer
+    pub fn try_generate(&mut self) -> Result<(), SpirographError> {
+        self.guilloche
+            .try_generate_scaled(self.settings.unit.mm_per_unit())?;
+        for subdial in &mut self.subdials {
+            subdial.face.try_generate()?;
         }
+        Ok(())
+    }
 
-        // Render clous de Paris layers from guilloche
-        for line_set in self.get_clous_de_paris_lines() {
-            for line_points in line_set {
-                if line_points.is_empty() {
-                    continue;
-                }
+    /// Generate every valid layer, skipping and reporting the invalid ones
+    pub fn generate_lenient(&mut self) -> GenerateOutcome {
+        self.guilloche
+            .generate_lenient_scaled(self.settings.unit.mm_per_unit())
+    }
 
-                let mut data = Data::new().move_to((line_points[0].x, line_points[0].y));
-                for point in line_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
+    /// Generate only the layers edits have marked stale
+    ///
+    /// See [`edit`]. Returns how many layers were regenerated.
+    pub fn regenerate_stale(&mut self) -> usize {
+        self.guilloche.regenerate_stale()
+    }
 
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+    /// Every generated line across all layers, spirographs first
+    pub fn lines(&self) -> Vec<Vec<Point2D>> {
+        self.guilloche.all_lines()
+    }
 
-                pattern_group = pattern_group.add(path);
-            }
+    /// Generate all layers concurrently
+    ///
+    /// Layers are independent, so each is generated on its own scoped
+    /// thread. Results are stored in place: layer order and output are
+    /// identical to `generate()`, so exports are byte-for-byte the same.
+    pub fn generate_parallel(&mut self) {
+        self.guilloche.generate_parallel();
+        for subdial in &mut self.subdials {
+            subdial.face.generate_parallel();
         }
+    }
 
-        // Render cube layers from guilloche
-        for line_set in self.get_cube_lines() {
-            for line_points in line_set {
-                if line_points.is_empty() {
-                    continue;
-                }
-
-                let mut data = Data::new().move_to((line_points[0].x, line_points[0].y));
-                for point in line_points.iter().skip(1) {
-                    data = data.line_to((point.x, point.y));
-                }
+    /// Get total layer count
+    pub fn layer_count(&self) -> usize {
+        self.guilloche.layer_count()
+    }
 
-                let path = Path::new()
-                    .set("fill", "none")
-                    .set("stroke", "#1a1a1a")
-                    .set("stroke-width", 0.03)
-                    .set("stroke-linecap", "round")
-                    .set("stroke-linejoin", "round")
-                    .set("d", data);
+    /// Total points across all generated lines
+    pub fn point_count(&self) -> usize {
+        let spirographs: usize = self.get_spirograph_points().iter().map(Vec::len).sum();
+        let layers: usize = self
+            .layer_line_sets()
+            .iter()
+            .flatten()
+            .flat_map(|layer_lines| layer_lines.iter())
+            .map(Vec::len)
+            .sum();
+        spirographs + layers
+    }
 
-                pattern_group = pattern_group.add(path);
+    /// Export SVGs of the design at several levels of detail, plus a JSON index
+    ///
+    /// Each level is written to `{name}-{suffix}.svg` in `dir`, generated on
+    /// a copy of this face (which is never modified) and thinned to its
+    /// point budget: the budget is shared across layers in proportion to
+    /// their full point counts, with per-layer floors so no pattern
+    /// degenerates. A level whose budget covers the full design matches a
+    /// plain `generate()` and `to_svg()`. `{name}.json` lists each file with
+    /// its budget and actual point count.
+    pub fn export_lod_bundle(
+        &self,
+        dir: &Path,
+        name: &str,
+        levels: &[LodSpec],
+    ) -> Result<Vec<LodFile>, SpirographError> {
+        if name.is_empty() || levels.is_empty() {
+            return Err(SpirographError::InvalidParameter(
+                "LOD bundle needs a name and at least one level".to_string(),
+            ));
+        }
+        for (i, level) in levels.iter().enumerate() {
+            if level.suffix.is_empty() || level.point_budget == 0 {
+                return Err(SpirographError::InvalidParameter(
+                    "LOD levels need a suffix and a positive point budget".to_string(),
+                ));
+            }
+            if levels[..i].iter().any(|other| other.suffix == level.suffix) {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "duplicate LOD suffix '{}'",
+                    level.suffix
+                )));
             }
         }
 
-        document = document.add(pattern_group);
+        let mut full = self.clone();
+        full.generate();
 
-        // Add outer bezel ring if configured
-        if let Some(ref bezel) = self.bezel_config {
-            let bezel_circle = Circle::new()
-                .set("cx", 0)
-                .set("cy", 0)
-                .set("r", radius * bezel.radius_ratio)
-                .set("fill", "none")
-                .set("stroke", bezel.stroke_color.as_str())
-                .set("stroke-width", bezel.stroke_width);
-            document = document.add(bezel_circle);
+        let mut files = Vec::with_capacity(levels.len());
+        for level in levels {
+            let mut face = full.clone();
+            face.guilloche.reduce_to_point_budget(level.point_budget);
+            let path = dir.join(format!("{}-{}.svg", name, level.suffix));
+            let filename = path.to_str().ok_or_else(|| {
+                SpirographError::ExportError(format!("Path is not valid UTF-8: {:?}", path))
+            })?;
+            face.to_svg(filename)?;
+            files.push(LodFile {
+                suffix: level.suffix.clone(),
+                point_budget: level.point_budget,
+                point_count: face.point_count(),
+                path,
+            });
         }
 
-        // Add all holes
-        for hole in &self.holes {
-            let hole_circle = Circle::new()
-                .set("cx", hole.center_x)
-                .set("cy", hole.center_y)
-                .set("r", hole.radius)
-                .set("fill", hole.fill_color.as_str());
-            document = document.add(hole_circle);
-        }
+        let entries: Vec<String> = files
+            .iter()
+            .map(|file| {
+                let file_name = file.path.file_name().unwrap_or_default().to_string_lossy();
+                format!(
+                    "    {{\"suffix\": {}, \"file\": {}, \"point_budget\": {}, \"point_count\": {}}}",
+                    json_string(&file.suffix),
+                    json_string(&file_name),
+                    file.point_budget,
+                    file.point_count
+                )
+            })
+            .collect();
+        let index = format!(
+            "{{\n  \"name\": {},\n  \"levels\": [\n{}\n  ]\n}}\n",
+            json_string(name),
+            entries.join(",\n")
+        );
+        let index_path = dir.join(format!("{}.json", name));
+        std::fs::write(&index_path, index).map_err(|e| {
+            SpirographError::ExportError(format!(
+                "Failed to write LOD index {:?}: {}",
+                index_path, e
+            ))
+        })?;
 
-        ::svg::save(filename, &document)
-            .map_err(|e| SpirographError::ExportError(format!("SVG export failed: {}", e)))
+        Ok(files)
     }
 
-    /// Export to STL
-    pub fn to_stl(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
-        self.guilloche.export_combined_stl(filename, config)
+    /// Export to SVG in the design unit
+    ///
+    /// The dial, bezel and holes are in groups with ids `dial`, `bezel` and
+    /// `holes`. The pattern is in the clipped `pattern` group, with each
+    /// layer in its own `<g>` (id like `flinque-0`, class
+    /// `turtles-layer flinque`) and any cell decorations in
+    /// `cell-decorations`.
+    pub fn to_svg(&self, filename: &str) -> Result<(), SpirographError> {
+        self.to_svg_with_reference(filename, false)
     }
 
-    /// Export to STEP
-    pub fn to_step(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
-        self.guilloche.export_combined_step(filename, config)
+    /// Export to SVG in the design unit, drawing the reference image beneath
+    /// the pattern when `include_reference` is set
+    ///
+    /// Without a reference image set this is the same as `to_svg`.
+    pub fn to_svg_with_reference(
+        &self,
+        filename: &str,
+        include_reference: bool,
+    ) -> Result<(), SpirographError> {
+        self.svg_writer(None, include_reference, DEFAULT_SVG_PRECISION)
+            .save(filename)
     }
 
-    // Helper methods to access guilloche data for rendering
-    fn get_spirograph_points(&self) -> Vec<Vec<Point2D>> {
-        self.guilloche.spirograph_points()
+    /// The SVG export in the design unit as a string, without writing a file
+    pub fn to_svg_string(&self) -> Result<String, SpirographError> {
+        Ok(self.svg_document(None, false).to_string())
     }
 
-    fn get_flinque_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.guilloche.flinque_lines()
+    /// Export to SVG with coordinates and width/height in `unit`
+    pub fn to_svg_in(&self, filename: &str, unit: Unit) -> Result<(), SpirographError> {
+        self.in_unit(unit).to_svg(filename)
     }
 
-    fn get_diamant_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.guilloche.diamant_lines()
+    /// Export to SVG in `config.unit` with `config.origin` applied
+    ///
+    /// Pattern lines are simplified to `config.simplify_tolerance`, in
+    /// `config.unit`, before they are written. The other fields are for
+    /// solid exports and are ignored.
+    pub fn to_svg_with_config(
+        &self,
+        filename: &str,
+        config: &ExportConfig,
+    ) -> Result<(), SpirographError> {
+        config.origin.validate()?;
+        let face = self.in_unit(config.unit);
+        face.simplified(config.simplify_tolerance)
+            .svg_writer(None, false, config.svg_precision)
+            .with_origin(config.origin)
+            .save(filename)
     }
 
-    fn get_draperie_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.guilloche.draperie_lines()
+    /// Rasterize the pattern to a 1-bit bitmap for a laser engraver
+    ///
+    /// Uses ordered (Bayer) thresholding; see
+    /// `to_engraver_bitmap_with_dither`.
+    pub fn to_engraver_bitmap(
+        &self,
+        dpi: u32,
+        line_width_mm: f64,
+    ) -> Result<Bitmap1, SpirographError> {
+        self.to_engraver_bitmap_with_dither(dpi, line_width_mm, Dither::Ordered)
     }
 
-    fn get_huiteight_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.guilloche.huiteight_lines()
-    }
+    /// Rasterize the pattern to a 1-bit bitmap with the given thresholding
+    ///
+    /// Every pattern line is stroked `line_width_mm` wide with anti-aliased
+    /// coverage, then reduced to 1 bit by `dither`, which keeps thin grooves
+    /// continuous instead of beating against the pixel grid. Dial and bezel
+    /// outlines and holes are not drawn. The bitmap covers the dial square
+    /// (and any line reaching past it) plus half a stroke, in mm whatever
+    /// the design unit, with `origin_mm` at its top-left corner.
+    pub fn to_engraver_bitmap_with_dither(
+        &self,
+        dpi: u32,
+        line_width_mm: f64,
+        dither: Dither,
+    ) -> Result<Bitmap1, SpirographError> {
+        let face = self.in_unit(Unit::Millimeter);
+        let spirographs = face.get_spirograph_points();
+        let layer_sets = face.layer_line_sets();
+        let lines: Vec<&[Point2D]> = spirographs
+            .iter()
+            .chain(
+                layer_sets
+                    .iter()
+                    .flatten()
+                    .flat_map(|layer_lines| layer_lines.iter()),
+            )
+            .map(Vec::as_slice)
+            .collect();
 
-    fn get_limacon_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.guilloche.limacon_lines()
+        let (min_x, min_y, max_x, max_y) = drawable_bounds(lines.iter().copied())?;
+        let (reach_x, reach_y) = face.dial_shape.semi_axes();
+        let pad = line_width_mm.max(0.0) / 2.0;
+        let bounds = (
+            min_x.min(-reach_x) - pad,
+            min_y.min(-reach_y) - pad,
+            max_x.max(reach_x) + pad,
+            max_y.max(reach_y) + pad,
+        );
+        rasterize_strokes(lines, bounds, dpi, line_width_mm, dither)
     }
 
-    fn get_paon_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.guilloche.paon_lines()
+    /// Rasterize the SVG export to an RGBA preview
+    ///
+    /// Draws the dial and bezel outlines, every pattern line clipped to the
+    /// dial with its layer's color, stroke width and opacity, cell
+    /// decorations and holes, over the SVG's view fitted into `width_px ×
+    /// height_px` on a white background. The dial fill, reference image
+    /// and overlays are not drawn.
+    pub fn render_raster(
+        &self,
+        width_px: u32,
+        height_px: u32,
+    ) -> Result<RgbaImage, SpirographError> {
+        render_preview(self.preview_strokes(), self.view_box(), width_px, height_px)
     }
 
-    fn get_clous_de_paris_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.guilloche.clous_de_paris_lines()
+    /// Write the [`render_raster`](Self::render_raster) preview to a PNG file
+    pub fn to_png(
+        &self,
+        filename: &str,
+        width_px: u32,
+        height_px: u32,
+    ) -> Result<(), SpirographError> {
+        self.render_raster(width_px, height_px)?.write_png(filename)
     }
 
-    fn get_cube_lines(&self) -> Vec<&Vec<Vec<Point2D>>> {
-        self.guilloche.cube_lines()
-    }
-}
+    /// Every stroke of the SVG export, in drawing order
+    fn preview_strokes(&self) -> Vec<PreviewStroke<'static>> {
+        const OUTLINE_SEGMENTS: usize = 360;
+        let dot = |center: Point2D, radius: f64, color: &str| PreviewStroke {
+            points: Cow::Owned(vec![center]),
+            closed: false,
+            width: 2.0 * radius,
+            color: svg_color_rgb(color),
+            opacity: 1.0,
+        };
+        let mut strokes = Vec::new();
+        if let Some(ref dial) = self.dial_config {
+            strokes.push(PreviewStroke {
+                points: Cow::Owned(self.dial_shape.outline(OUTLINE_SEGMENTS)),
+                closed: true,
+                width: dial.stroke_width,
+                color: svg_color_rgb(&dial.stroke_color),
+                opacity: 1.0,
+            });
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let line_scale = Unit::Millimeter.factor_to(self.settings.unit);
+        let origin = Point2D::new(0.0, 0.0);
+        let clip = Some(self.dial_shape);
+        let aperture_outlines = self.aperture_outlines();
+        for (i, points) in self.get_spirograph_points().into_iter().enumerate() {
+            if polyline_is_degenerate(&points) {
+                continue;
+            }
+            let color = svg_color_rgb(SPIROGRAPH_COLORS[i % SPIROGRAPH_COLORS.len()]);
+            let width = SPIROGRAPH_STROKE_WIDTHS[i % SPIROGRAPH_STROKE_WIDTHS.len()] * line_scale;
+            let opacity = self
+                .opacity_envelope(LayerKind::Spirograph, i)
+                .map_or(1.0, |e| e.eval_polyline(&points, origin));
+            let closed = polyline_closes(&points);
+            for (piece, closed) in clip_pieces(clip, points, closed) {
+                let pieces = match cut_at_apertures(&piece, closed, &aperture_outlines) {
+                    Some(cut) => cut.into_iter().map(|piece| (piece, false)).collect(),
+                    None => vec![(piece, closed)],
+                };
+                for (piece, closed) in pieces {
+                    strokes.push(PreviewStroke {
+                        points: Cow::Owned(piece),
+                        closed,
+                        width,
+                        color,
+                        opacity,
+                    });
+                }
+            }
+        }
 
-    #[test]
-    fn test_watch_face_creation() {
-        let face = WatchFace::new(40.0);
-        assert!(face.is_ok());
+        let default_style = LayerStyle {
+            stroke_width: LayerStyle::default().stroke_width * line_scale,
+            ..LayerStyle::default()
+        };
+        for (kind, layer_lines) in self.layer_line_sets_by_kind() {
+            for (index, lines) in layer_lines.into_iter().enumerate() {
+                let envelope = self.opacity_envelope(kind, index);
+                let style = self.layer_style(kind, index).unwrap_or(&default_style);
+                let color = svg_color_rgb(&style.stroke_color);
+                for points in lines {
+                    if polyline_is_degenerate(points) {
+                        continue;
+                    }
+                    let opacity =
+                        envelope.map_or(1.0, |e| e.eval_polyline(points, origin)) * style.opacity;
+                    let pieces = self
+                        .dial_shape
+                        .clip_polyline(points, false)
+                        .into_iter()
+                        .flat_map(|piece| {
+                            cut_at_apertures(&piece, false, &aperture_outlines)
+                                .unwrap_or_else(|| vec![piece])
+                        });
+                    for piece in pieces {
+                        strokes.push(PreviewStroke {
+                            points: Cow::Owned(piece),
+                            closed: false,
+                            width: style.stroke_width,
+                            color,
+                            opacity,
+                        });
+                    }
+                }
+            }
+        }
 
-        let face_bad = WatchFace::new(50.0);
-        assert!(face_bad.is_err());
-    }
+        for (layer_index, every_nth, decoration) in &self.cell_decorations {
+            let Some(layer) = self.guilloche.clous_de_paris_layer(*layer_index) else {
+                continue;
+            };
+            let n = *every_nth as i64;
+            let (radius, fill) = match decoration {
+                CellDecoration::Circle { radius, fill_color } => (*radius, fill_color.as_str()),
+                CellDecoration::Point { color } => (0.15 * line_scale, color.as_str()),
+            };
+            for ((a, b), center) in layer.cell_grid().into_iter().zip(layer.cell_centers()) {
+                if (a + b).rem_euclid(n) == 0 && self.dial_shape.contains(center) {
+                    strokes.push(dot(center, radius, fill));
+                }
+            }
+        }
 
-    #[test]
-    fn test_add_inner_outer_hole() {
-        let mut face = WatchFace::new(38.0).unwrap();
+        if let Some(ref bezel) = self.bezel_config {
+            let mut outline = self.dial_shape;
+            outline.scale_lengths(bezel.radius_ratio);
+            strokes.push(PreviewStroke {
+                points: Cow::Owned(outline.outline(OUTLINE_SEGMENTS)),
+                closed: true,
+                width: bezel.stroke_width,
+                color: svg_color_rgb(&bezel.stroke_color),
+                opacity: 1.0,
+            });
+        }
+        for (aperture, outline) in self.apertures.iter().zip(aperture_outlines) {
+            strokes.push(PreviewStroke {
+                points: Cow::Owned(outline),
+                closed: true,
+                width: aperture.stroke_width,
+                color: svg_color_rgb(&aperture.stroke_color),
+                opacity: 1.0,
+            });
+        }
+        for hole in &self.holes {
+            let center = Point2D::new(hole.center_x, hole.center_y);
+            strokes.push(dot(center, hole.radius, &hole.fill_color));
+        }
+        strokes
+    }
+
+    /// Export to SVG with a design-review overlay drawn beneath the pattern
+    ///
+    /// Overlay labels are placed just outside the bezel (or dial) radius.
+    pub fn to_svg_with_overlay(
+        &self,
+        filename: &str,
+        overlay: &OverlayOptions,
+    ) -> Result<(), SpirographError> {
+        self.svg_writer(Some(overlay), false, DEFAULT_SVG_PRECISION)
+            .save(filename)
+    }
+
+    /// The SVG viewBox: the fixed one, or the content bounds plus the margin
+    fn view_box(&self) -> (f64, f64, f64, f64) {
+        self.fixed_viewbox.unwrap_or_else(|| {
+            let (min_x, min_y, max_x, max_y) = self.content_bounds();
+            let margin = self.svg_margin;
+            (
+                min_x - margin,
+                min_y - margin,
+                max_x - min_x + 2.0 * margin,
+                max_y - min_y + 2.0 * margin,
+            )
+        })
+    }
+
+    /// The SVG document at the default precision, as `to_svg` writes it
+    fn svg_document(
+        &self,
+        overlay: Option<&OverlayOptions>,
+        include_reference: bool,
+    ) -> ::svg::Document {
+        self.svg_writer(overlay, include_reference, DEFAULT_SVG_PRECISION)
+            .into_document()
+    }
+
+    /// Build the SVG document with coordinates rounded to `precision`
+    /// decimals, optionally with an overlay and the reference image beneath
+    /// the pattern
+    fn svg_writer(
+        &self,
+        overlay: Option<&OverlayOptions>,
+        include_reference: bool,
+        precision: usize,
+    ) -> SvgWriter {
+        use ::svg::node::element::{Group, Path};
+        use ::svg::Node;
+
+        let radius = self.dial_shape.max_semi_axis();
+        let (view_x, view_y, view_width, view_height) = self.view_box();
+        let mut writer = SvgWriter::with_view_box(
+            (view_x, view_y, view_width, view_height),
+            self.settings.unit.suffix(),
+        )
+        .with_precision(precision)
+        .with_max_points_per_path(self.max_points_per_path);
+        if let Some(metadata) = &self.metadata {
+            writer = writer.map_document(|document| metadata.apply_svg(document));
+        }
+
+        // Built-in stroke widths are in millimeters
+        let line_scale = Unit::Millimeter.factor_to(self.settings.unit);
+
+        // Add inner dial circle if configured
+        if let Some(ref dial) = self.dial_config {
+            let mut dial_outline = self.dial_shape.svg_element(1.0);
+            dial_outline.assign("fill", dial.fill_color.as_str());
+            dial_outline.assign("stroke", dial.stroke_color.as_str());
+            dial_outline.assign("stroke-width", dial.stroke_width);
+            writer.add(Group::new().set("id", "dial").add(dial_outline));
+        }
+
+        // Reference image sits above the dial fill so its opacity shows
+        // against it, beneath the overlay and the pattern
+        if let Some(reference) = self.reference.as_ref().filter(|_| include_reference) {
+            use ::svg::node::element::Image;
+
+            let width = reference.width * line_scale;
+            let matrix = reference.transform_matrix(line_scale);
+            let image = Image::new()
+                .set("href", reference.href.as_str())
+                .set("x", -width / 2.0)
+                .set("y", -width / 2.0)
+                .set("width", width)
+                .set("height", width)
+                .set("preserveAspectRatio", "xMidYMid meet")
+                .set("opacity", reference.opacity)
+                .set(
+                    "transform",
+                    format!(
+                        "matrix({} {} {} {} {} {})",
+                        matrix[0], matrix[1], matrix[2], matrix[3], matrix[4], matrix[5]
+                    ),
+                );
+            writer.add(image);
+        }
+
+        // Review overlay sits above the dial fill but beneath all pattern content
+        if let Some(overlay) = overlay {
+            let extent = match self.bezel_config {
+                Some(ref bezel) => radius * bezel.radius_ratio,
+                None => radius,
+            };
+            let bounds = (view_x, view_y, view_x + view_width, view_y + view_height);
+            writer.add(overlay.to_group(bounds, (0.0, 0.0), extent));
+        }
+
+        // Clip all pattern content to the dial outline, less the apertures
+        // and masking subdials
+        let aperture_outlines = self.aperture_outlines();
+        let cut_outlines = self.pattern_cut_outlines();
+        {
+            use ::svg::node::element::ClipPath;
+
+            let clip = ClipPath::new().set("id", "dial-clip");
+            let clip = if cut_outlines.is_empty() {
+                clip.add(self.dial_shape.svg_element(1.0))
+            } else {
+                let mut outlines = vec![self.dial_shape.outline(4 * HOLE_SEGMENTS)];
+                outlines.extend(cut_outlines.iter().cloned());
+                clip.add(
+                    Path::new()
+                        .set("d", writer.outlines_data(&outlines))
+                        .set("clip-rule", "evenodd"),
+                )
+            };
+            writer.add(clip);
+        }
+
+        // All pattern content goes inside a clipped group, one group per layer
+        let mut pattern_group = Group::new()
+            .set("id", "pattern")
+            .set("clip-path", "url(#dial-clip)");
+
+        for group in self.layer_groups(&writer, line_scale, &cut_outlines, None, "") {
+            pattern_group = pattern_group.add(group);
+        }
+
+        // Cell decorations sit above the grooves they decorate
+        let mut decorations = Group::new().set("id", "cell-decorations");
+        for (layer_index, every_nth, decoration) in &self.cell_decorations {
+            let Some(layer) = self.guilloche.clous_de_paris_layer(*layer_index) else {
+                continue;
+            };
+            let n = *every_nth as i64;
+            let (radius, fill) = match decoration {
+                CellDecoration::Circle { radius, fill_color } => (*radius, fill_color.as_str()),
+                CellDecoration::Point { color } => (0.15 * line_scale, color.as_str()),
+            };
+            for ((a, b), center) in layer.cell_grid().into_iter().zip(layer.cell_centers()) {
+                if (a + b).rem_euclid(n) != 0 {
+                    continue;
+                }
+                decorations = decorations
+                    .add(writer.circle(center, radius, |circle| circle.set("fill", fill)));
+            }
+        }
+        if !self.cell_decorations.is_empty() {
+            pattern_group = pattern_group.add(decorations);
+        }
+
+        writer.add(pattern_group);
+
+        // Subdials sit on the dial above the main pattern, each clipped to
+        // its own circle and edged with a ring
+        let mut subdials = Group::new().set("id", "subdials");
+        for (i, subdial) in self.subdials.iter().enumerate() {
+            let prefix = format!("subdial-{}-", i);
+            let placement = Some((subdial.center(), subdial.radius));
+            let mut group = Group::new().set("id", format!("subdial-{}", i));
+            for layer in subdial
+                .face
+                .layer_groups(&writer, line_scale, &[], placement, &prefix)
+            {
+                group = group.add(layer);
+            }
+            subdials = subdials.add(group.add(writer.circle(
+                subdial.center(),
+                subdial.radius,
+                |circle| {
+                    circle
+                        .set("fill", "none")
+                        .set("stroke", subdial.stroke_color.as_str())
+                        .set("stroke-width", subdial.stroke_width)
+                },
+            )));
+        }
+        if !self.subdials.is_empty() {
+            writer.add(subdials);
+        }
+
+        // Add outer bezel ring if configured
+        if let Some(ref bezel) = self.bezel_config {
+            let mut bezel_outline = self.dial_shape.svg_element(bezel.radius_ratio);
+            bezel_outline.assign("fill", "none");
+            bezel_outline.assign("stroke", bezel.stroke_color.as_str());
+            bezel_outline.assign("stroke-width", bezel.stroke_width);
+            writer.add(Group::new().set("id", "bezel").add(bezel_outline));
+        }
+
+        // Aperture outlines sit above the pattern they cut
+        let mut apertures = Group::new().set("id", "apertures");
+        for (aperture, outline) in self.apertures.iter().zip(&aperture_outlines) {
+            apertures = apertures.add(
+                Path::new()
+                    .set("d", writer.outlines_data(std::slice::from_ref(outline)))
+                    .set("fill", "none")
+                    .set("stroke", aperture.stroke_color.as_str())
+                    .set("stroke-width", aperture.stroke_width)
+                    .set("stroke-linejoin", "round"),
+            );
+        }
+        if !self.apertures.is_empty() {
+            writer.add(apertures);
+        }
+
+        // Add all holes
+        let mut holes = Group::new().set("id", "holes");
+        for hole in &self.holes {
+            let center = Point2D::new(hole.center_x, hole.center_y);
+            let hole_circle = writer.circle(center, hole.radius, |circle| {
+                circle.set("fill", hole.fill_color.as_str())
+            });
+            holes = holes.add(hole_circle);
+        }
+        if !self.holes.is_empty() {
+            writer.add(holes);
+        }
+
+        // Hands are filled over everything, as mounted on the finished dial
+        let mut hands = Group::new().set("id", "hands");
+        for (name, config, outlines) in self.placed_hands() {
+            hands = hands.add(
+                Path::new()
+                    .set("id", format!("hand-{}", name))
+                    .set("d", writer.outlines_data(&outlines))
+                    .set("fill", config.color.as_str())
+                    .set("fill-rule", "evenodd")
+                    .set("stroke", "none"),
+            );
+        }
+        if self.hands.is_some() {
+            writer.add(hands);
+        }
+
+        writer
+    }
+
+    /// The SVG group of every pattern layer, spirographs first, then the
+    /// other kinds in drawing order
+    ///
+    /// Lines are cut back to the convex `cuts`. A subdial `placement`
+    /// `(center, radius)` clips them to the circle of `radius` about the
+    /// origin and moves them to `center`. Group ids start with `id_prefix`.
+    fn layer_groups(
+        &self,
+        writer: &SvgWriter,
+        line_scale: f64,
+        cuts: &[Vec<Point2D>],
+        placement: Option<(Point2D, f64)>,
+        id_prefix: &str,
+    ) -> Vec<::svg::node::element::Group> {
+        use ::svg::node::element::Path;
+
+        let layer_group = |kind: LayerKind, index: usize| {
+            let group = self.guilloche.svg_layer_group(kind, index);
+            if id_prefix.is_empty() {
+                group
+            } else {
+                group.set("id", format!("{}{}-{}", id_prefix, kind.name(), index))
+            }
+        };
+        let mut groups = Vec::new();
+
+        // Opacity envelopes are evaluated about the dial center
+        let origin = Point2D::new(0.0, 0.0);
+
+        // Render spirograph layers from guilloche
+        for (i, points) in self.get_spirograph_points().iter().enumerate() {
+            let mut group = layer_group(LayerKind::Spirograph, i);
+            if polyline_is_degenerate(points) {
+                groups.push(group);
+                continue;
+            }
+
+            let color = SPIROGRAPH_COLORS[i % SPIROGRAPH_COLORS.len()];
+            let stroke_width =
+                SPIROGRAPH_STROKE_WIDTHS[i % SPIROGRAPH_STROKE_WIDTHS.len()] * line_scale;
+            let opacity = self
+                .opacity_envelope(LayerKind::Spirograph, i)
+                .map(|e| e.eval_polyline(points, origin));
+            let style = |path: Path| {
+                let path = path
+                    .set("fill", "none")
+                    .set("stroke", color)
+                    .set("stroke-width", stroke_width)
+                    .set("stroke-linecap", "round")
+                    .set("stroke-linejoin", "round");
+                with_opacity(path, opacity)
+            };
+            // Only close curves that return to their start; closing an open
+            // curve draws a chord across the dial
+            let closed = polyline_closes(points);
+            for (piece, closed) in placed_pieces(points, closed, cuts, placement) {
+                group = group.add(writer.polyline_node(&piece, closed, style));
+            }
+            groups.push(group);
+        }
+
+        // Layers without a style of their own share the default stroke
+        let default_style = LayerStyle {
+            stroke_width: LayerStyle::default().stroke_width * line_scale,
+            ..LayerStyle::default()
+        };
+        let layer_style = |path: Path, style: &LayerStyle| {
+            path.set("fill", "none")
+                .set("stroke", style.stroke_color.as_str())
+                .set("stroke-width", style.stroke_width)
+                .set("stroke-linecap", "round")
+                .set("stroke-linejoin", "round")
+        };
+
+        // Render every other layer kind from guilloche, in drawing order
+        for (kind, layer_lines) in self.layer_line_sets_by_kind() {
+            for (index, lines) in layer_lines.into_iter().enumerate() {
+                let envelope = self.opacity_envelope(kind, index);
+                let style = self.layer_style(kind, index).unwrap_or(&default_style);
+                let mut group = layer_group(kind, index);
+                for points in lines {
+                    if polyline_is_degenerate(points) {
+                        continue;
+                    }
+
+                    let opacity = match envelope.map(|e| e.eval_polyline(points, origin)) {
+                        Some(faded) => Some(faded * style.opacity),
+                        None => Some(style.opacity).filter(|&opacity| opacity < 1.0),
+                    };
+                    for (piece, _) in placed_pieces(points, false, cuts, placement) {
+                        group = group.add(writer.polyline_node(&piece, false, |path| {
+                            with_opacity(layer_style(path, style), opacity)
+                        }));
+                    }
+                }
+                groups.push(group);
+            }
+        }
+
+        groups
+    }
+
+    /// Export to STL
+    ///
+    /// Geometry is clipped to the dial outline, matching the SVG clip, and
+    /// scaled from the design unit to `config.unit`. The base slab follows
+    /// the outline, and holes and chapter ring cutouts are cut through it.
+    /// Subdial grooves stop half their width inside the subdial circle, and
+    /// masking subdials cut the main grooves back to their edge. Hand
+    /// outlines are cut only with
+    /// [`set_hands_in_exports`](Self::set_hands_in_exports).
+    pub fn to_stl(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
+        let face = self.in_unit(config.unit);
+        let half_width = config.groove_profile()?.width() / 2.0;
+        let mut extra_polylines: Vec<(Vec<Point2D>, bool)> = face
+            .subdials
+            .iter()
+            .flat_map(|subdial| subdial.export_polylines(half_width))
+            .collect();
+        extra_polylines.extend(
+            face.exported_hand_outlines()
+                .into_iter()
+                .map(|outline| (outline, true)),
+        );
+        face.guilloche.write_combined_stl(
+            filename,
+            config,
+            Some(face.dial_shape),
+            &face.opening_outlines(),
+            &face.subdial_masks(),
+            extra_polylines,
+        )
+    }
+
+    /// Export every layer to HPGL for a pen plotter, all with pen 1
+    ///
+    /// See [`to_hpgl_with_pens`](Self::to_hpgl_with_pens).
+    pub fn to_hpgl(&self, filename: &str, scale: f64) -> Result<(), SpirographError> {
+        self.to_hpgl_with_pens(filename, scale, &[])
+    }
+
+    /// Export every layer to HPGL, plotting layer `i` with `pens[i]`
+    ///
+    /// Geometry is converted to millimeters, multiplied by `scale` and
+    /// clipped to the dial outline and cut at the apertures, matching the
+    /// SVG clip. Layers are numbered and pens assigned as in
+    /// [`GuillochePattern::to_hpgl_with_pens`]; the dial, bezel, holes and
+    /// aperture outlines are not plotted.
+    pub fn to_hpgl_with_pens(
+        &self,
+        filename: &str,
+        scale: f64,
+        pens: &[u32],
+    ) -> Result<(), SpirographError> {
+        let face = self.in_unit(Unit::Millimeter);
+        let outlines = face.aperture_outlines();
+        let layers = face
+            .guilloche
+            .plot_layers(Some(face.dial_shape))
+            .into_iter()
+            .map(|lines| {
+                lines
+                    .into_iter()
+                    .flat_map(|line| {
+                        cut_at_apertures(&line, false, &outlines).unwrap_or_else(|| vec![line])
+                    })
+                    .collect()
+            })
+            .collect();
+        let (lines, line_pens) = pens_by_layer(layers, pens);
+        write_hpgl(filename, &lines, &line_pens, scale)
+    }
+
+    /// Export the dial's cutting profile to DXF in millimeters
+    ///
+    /// Writes the dial edge and every aperture (see `apertures`) as closed
+    /// polylines, with the metadata when set.
+    pub fn to_dxf_profile(&self, filename: &str) -> Result<(), SpirographError> {
+        let face = self.in_unit(Unit::Millimeter);
+        let mut outlines = vec![face.dial_shape.outline(4 * HOLE_SEGMENTS)];
+        outlines.extend(face.apertures());
+        write_dxf_polylines(filename, &outlines, true, self.metadata.as_ref())
+    }
+
+    /// Every opening through the dial: holes as polygons inscribed in their
+    /// circles, complication apertures, then chapter ring index cutouts
+    ///
+    /// Outlines wind counter-clockwise (x-right, y-up axes) and do not
+    /// repeat their first point.
+    pub fn apertures(&self) -> Vec<Vec<Point2D>> {
+        let mut apertures = self.opening_outlines();
+        apertures.extend(self.guilloche.apertures());
+        apertures
+    }
+
+    /// The holes, then the complication apertures, as polygons
+    fn opening_outlines(&self) -> Vec<Vec<Point2D>> {
+        let mut outlines = self.hole_outlines();
+        outlines.extend(self.aperture_outlines());
+        outlines
+    }
+
+    /// The complication apertures as convex polygons
+    fn aperture_outlines(&self) -> Vec<Vec<Point2D>> {
+        self.apertures.iter().map(ApertureConfig::outline).collect()
+    }
+
+    /// The holes as polygons inscribed in their circles
+    fn hole_outlines(&self) -> Vec<Vec<Point2D>> {
+        self.holes
+            .iter()
+            .map(|hole| {
+                circle_outline(
+                    Point2D::new(hole.center_x, hole.center_y),
+                    hole.radius,
+                    HOLE_SEGMENTS,
+                )
+            })
+            .collect()
+    }
+
+    /// Export to STEP
+    ///
+    /// An assembly named `watch_face` with the dial outline and a named
+    /// wireframe part per layer (see
+    /// [`GuillochePattern::export_combined_step`]). Geometry is clipped to
+    /// the dial outline, matching the SVG clip, and scaled from the design
+    /// unit to `config.unit`. With
+    /// [`set_hands_in_exports`](Self::set_hands_in_exports) the hand
+    /// outlines are a `hands` part.
+    pub fn to_step(&self, filename: &str, config: &ExportConfig) -> Result<(), SpirographError> {
+        let face = self.in_unit(config.unit);
+        let mut parts = face.guilloche.step_layer_parts(Some(face.dial_shape));
+        parts.push(StepPart {
+            name: "hands".to_string(),
+            lines: face
+                .exported_hand_outlines()
+                .into_iter()
+                .map(|outline| (outline, true))
+                .collect(),
+        });
+        write_step_parts(
+            filename,
+            config,
+            face.dial_shape,
+            parts,
+            self.metadata.as_ref(),
+            "watch_face",
+            config.unit,
+        )
+    }
+
+    /// Bounds of everything drawn: dial and bezel outlines, holes,
+    /// apertures and all generated lines
+    fn content_bounds(&self) -> (f64, f64, f64, f64) {
+        let (rx, ry) = self.dial_shape.semi_axes();
+        let (mut reach_x, mut reach_y) = (rx, ry);
+        if let Some(ref dial) = self.dial_config {
+            reach_x += dial.stroke_width / 2.0;
+            reach_y += dial.stroke_width / 2.0;
+        }
+        if let Some(ref bezel) = self.bezel_config {
+            let half_stroke = bezel.stroke_width / 2.0;
+            reach_x = reach_x.max(rx * bezel.radius_ratio + half_stroke);
+            reach_y = reach_y.max(ry * bezel.radius_ratio + half_stroke);
+        }
+        let mut bounds = (-reach_x, -reach_y, reach_x, reach_y);
+        let mut include = |(min_x, min_y, max_x, max_y): (f64, f64, f64, f64)| {
+            bounds.0 = bounds.0.min(min_x);
+            bounds.1 = bounds.1.min(min_y);
+            bounds.2 = bounds.2.max(max_x);
+            bounds.3 = bounds.3.max(max_y);
+        };
+
+        for hole in &self.holes {
+            include((
+                hole.center_x - hole.radius,
+                hole.center_y - hole.radius,
+                hole.center_x + hole.radius,
+                hole.center_y + hole.radius,
+            ));
+        }
+        for (aperture, outline) in self.apertures.iter().zip(self.aperture_outlines()) {
+            if let Some((min_x, min_y, max_x, max_y)) = polyline_bounds([outline.as_slice()]) {
+                let half_stroke = aperture.stroke_width / 2.0;
+                include((
+                    min_x - half_stroke,
+                    min_y - half_stroke,
+                    max_x + half_stroke,
+                    max_y + half_stroke,
+                ));
+            }
+        }
+
+        let spirographs = self.get_spirograph_points();
+        let layer_sets = self.layer_line_sets();
+        let lines = spirographs.iter().chain(
+            layer_sets
+                .iter()
+                .flatten()
+                .flat_map(|layer_lines| layer_lines.iter()),
+        );
+        let drawn = lines
+            .map(Vec::as_slice)
+            .filter(|line| !polyline_is_degenerate(line));
+        if let Some(line_bounds) = polyline_bounds(drawn) {
+            include(line_bounds);
+        }
+
+        bounds
+    }
+
+    /// Lines of every non-spirograph layer, grouped by kind
+    fn layer_line_sets(&self) -> Vec<KindLines<'_>> {
+        self.layer_line_sets_by_kind()
+            .into_iter()
+            .map(|(_, lines)| lines)
+            .collect()
+    }
+
+    /// Every layer kind but spirographs with its lines, in drawing order
+    fn layer_line_sets_by_kind(&self) -> Vec<(LayerKind, KindLines<'_>)> {
+        self.guilloche.line_sets_by_kind()
+    }
+
+    // Helper methods to access guilloche data for rendering
+    fn get_spirograph_points(&self) -> Vec<Vec<Point2D>> {
+        self.guilloche.spirograph_points()
+    }
+}
+
+/// Pieces of `points` outside every convex outline in `apertures`, or
+/// `None` when no aperture cuts the line
+///
+/// A closed line is cut as if its first point were repeated at the end;
+/// the pieces are open.
+fn cut_at_apertures(
+    points: &[Point2D],
+    closed: bool,
+    apertures: &[Vec<Point2D>],
+) -> Option<Vec<Vec<Point2D>>> {
+    let mut pieces: Option<Vec<Vec<Point2D>>> = None;
+    for aperture in apertures {
+        pieces = match pieces {
+            Some(pieces) => Some(
+                pieces
+                    .into_iter()
+                    .flat_map(|piece| {
+                        cut_polyline_outside_convex(&piece, aperture).unwrap_or_else(|| vec![piece])
+                    })
+                    .collect(),
+            ),
+            None if closed && !points.is_empty() => {
+                let mut open = points.to_vec();
+                open.push(points[0]);
+                cut_polyline_outside_convex(&open, aperture)
+            }
+            None => cut_polyline_outside_convex(points, aperture),
+        };
+    }
+    pieces
+}
+
+/// The pieces of `points` to draw, each flagged if closed: cut back to the
+/// convex `cuts`, or for a subdial `placement` `(center, radius)` clipped
+/// to the circle of `radius` about the origin and moved to `center`
+fn placed_pieces<'a>(
+    points: &'a [Point2D],
+    closed: bool,
+    cuts: &[Vec<Point2D>],
+    placement: Option<(Point2D, f64)>,
+) -> Vec<(Cow<'a, [Point2D]>, bool)> {
+    match placement {
+        Some((center, radius)) => {
+            let clip = Some(DialShape::Circle { radius });
+            clip_pieces(clip, points.to_vec(), closed)
+                .into_iter()
+                .map(|(mut piece, closed)| {
+                    translate(&mut piece, center);
+                    (Cow::Owned(piece), closed)
+                })
+                .collect()
+        }
+        None => match cut_at_apertures(points, closed, cuts) {
+            Some(pieces) => pieces
+                .into_iter()
+                .map(|piece| (Cow::Owned(piece), false))
+                .collect(),
+            None => vec![(Cow::Borrowed(points), closed)],
+        },
+    }
+}
+
+/// Move every point of `points` by `offset`
+fn translate(points: &mut [Point2D], offset: Point2D) {
+    for p in points {
+        p.x += offset.x;
+        p.y += offset.y;
+    }
+}
+
+/// Counter-clockwise polygon inscribed in a circle, not repeating its first point
+fn circle_outline(center: Point2D, radius: f64, segments: usize) -> Vec<Point2D> {
+    (0..segments)
+        .map(|i| {
+            let angle = 2.0 * std::f64::consts::PI * i as f64 / segments as f64;
+            Point2D::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_face_creation() {
+        let face = WatchFace::new(40.0);
+        assert!(face.is_ok());
+
+        let face_bad = WatchFace::new(50.0);
+        assert!(face_bad.is_err());
+    }
+
+    #[test]
+    fn test_add_inner_outer_hole() {
+        let mut face = WatchFace::new(38.0).unwrap();
         face.add_inner();
         face.add_outer();
         face.add_center_hole();
@@ -668,4 +2836,1482 @@ mod tests {
         // At 3 o'clock, x should be positive
         assert!(face.holes[0].center_x > 0.0);
     }
+
+    #[test]
+    fn test_svg_overlay_beneath_pattern() {
+        let mut face = WatchFace::new(30.0).unwrap();
+        face.add_inner();
+        face.add_outer();
+        face.add_diamant_layer(DiamantLayer::new(DiamantConfig::new(12, 15.0)).unwrap());
+        face.generate();
+        face.set_fixed_viewbox(Some((-75.0, -75.0, 150.0, 150.0)))
+            .unwrap();
+
+        let overlay = OverlayOptions::new()
+            .with_mm_grid(5.0)
+            .with_radial_rays(45.0);
+        let out = face.svg_document(Some(&overlay), false).to_string();
+
+        // viewBox spans ±75 mm, so a 5 mm pitch gives 31 lines each way
+        assert_eq!(out.matches("class=\"overlay-grid\"").count(), 62);
+        assert_eq!(out.matches("class=\"overlay-ray\"").count(), 8);
+
+        let overlay_pos = out.find("id=\"overlay\"").unwrap();
+        let pattern_pos = out.find("clip-path=\"url(#dial-clip)\"").unwrap();
+        assert!(overlay_pos < pattern_pos);
+
+        // Plain export has no overlay
+        assert!(!face
+            .svg_document(None, false)
+            .to_string()
+            .contains("id=\"overlay\""));
+    }
+
+    #[test]
+    fn test_svg_groups_layers_dial_bezel_and_holes() {
+        use crate::common::tests::svg_groups;
+
+        let mut face = WatchFace::new(30.0).unwrap();
+        face.add_inner();
+        face.add_outer();
+        face.add_flinque_layer(FlinqueLayer::new(20.0, FlinqueConfig::default()).unwrap());
+        face.add_clous_de_paris_layer(
+            ClousDeParisLayer::new(ClousDeParisConfig::default()).unwrap(),
+        );
+        face.add_chapter_ring(ChapterRingConfig::new(26.0, 28.0))
+            .unwrap();
+        face.decorate_cells(
+            0,
+            2,
+            CellDecoration::Point {
+                color: "#c0a060".to_string(),
+            },
+        )
+        .unwrap();
+        face.add_center_hole();
+        face.generate();
+
+        let plain = face.svg_document(None, false).to_string();
+        let named = |svg: &str| -> Vec<(usize, String)> {
+            svg_groups(svg)
+                .into_iter()
+                .filter_map(|(depth, g)| g.get("id").map(|id| (depth, id.clone())))
+                .collect()
+        };
+        let expected = [
+            (0, "dial"),
+            (0, "pattern"),
+            (1, "flinque-0"),
+            (1, "clous_de_paris-0"),
+            (1, "chapter_ring-0"),
+            (1, "cell-decorations"),
+            (0, "bezel"),
+            (0, "holes"),
+        ];
+        let expected: Vec<(usize, String)> = expected
+            .iter()
+            .map(|&(d, id)| (d, id.to_string()))
+            .collect();
+        assert_eq!(named(&plain), expected);
+        assert!(plain.contains("class=\"turtles-layer clous_de_paris\""));
+        assert!(!plain.contains("data-config"));
+
+        // Opting in adds data attributes and changes nothing drawn
+        face.set_svg_layer_data(true);
+        let annotated = face.svg_document(None, false).to_string();
+        assert_eq!(named(&annotated), expected);
+        let strip = |svg: &str| -> String {
+            let (mut out, mut rest) = (String::new(), svg);
+            while let Some(i) = rest.find(" data-") {
+                out.push_str(&rest[..i]);
+                let value = &rest[i + rest[i..].find('"').unwrap() + 1..];
+                rest = &value[value.find('"').unwrap() + 1..];
+            }
+            out + rest
+        };
+        assert!(strip(&annotated) == plain);
+        let ring = svg_groups(&annotated)
+            .into_iter()
+            .find(|(_, g)| g.get("id").map(String::as_str) == Some("chapter_ring-0"))
+            .unwrap()
+            .1;
+        assert!(ring["data-config"].starts_with("ChapterRingConfig { r_inner: 26.0"));
+        assert_eq!(ring["data-lines"], (2 + 60).to_string());
+    }
+
+    #[test]
+    fn test_generate_parallel_matches_sequential() {
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_inner();
+        face.add_diamant_layer(DiamantLayer::new(DiamantConfig::new(24, 19.0)).unwrap());
+        face.add_limacon_layer(LimaconLayer::new(LimaconConfig::new(24, 10.0, 8.0)).unwrap());
+        face.add_flinque_layer(FlinqueLayer::new(38.0, FlinqueConfig::default()).unwrap());
+        face.add_clous_de_paris_layer(
+            ClousDeParisLayer::new(ClousDeParisConfig::default()).unwrap(),
+        );
+
+        let mut sequential = face.clone();
+        sequential.generate();
+        let expected = sequential.svg_document(None, false).to_string();
+
+        let outputs: Vec<String> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    let mut f = face.clone();
+                    scope.spawn(move || {
+                        f.generate_parallel();
+                        f.svg_document(None, false).to_string()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        for out in outputs {
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn test_stl_clipped_to_dial() {
+        // Offset spirograph reaching ~10 mm past the 38 mm dial
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_horizontal_layer(
+            HorizontalSpirograph::new_with_center(38.0, 0.77, 30.0, 3, 360, 10.0, 0.0).unwrap(),
+        );
+        face.generate();
+        let unclipped = face.get_spirograph_points();
+        assert!(unclipped[0].iter().any(|p| p.x.hypot(p.y) > 40.0));
+
+        let path = std::env::temp_dir().join("test_watch_face_clip.stl");
+        face.to_stl(path.to_str().unwrap(), &ExportConfig::default())
+            .unwrap();
+
+        let mut file = std::fs::File::open(&path).unwrap();
+        let mesh = stl_io::read_stl(&mut file).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(!mesh.faces.is_empty());
+        let max_r = mesh
+            .vertices
+            .iter()
+            .map(|v| (v[0] as f64).hypot(v[1] as f64))
+            .fold(0.0, f64::max);
+        assert!(max_r <= 38.0 + 1e-4, "STL reaches radius {}", max_r);
+    }
+
+    #[test]
+    fn test_oval_dial_clips_and_draws_its_outline() {
+        let shape = DialShape::Ellipse { rx: 36.0, ry: 30.0 };
+        let mut face = WatchFace::new_with_shape(shape, ProjectSettings::default()).unwrap();
+        assert_eq!(face.radius(), 36.0);
+        assert_eq!(face.shape(), shape);
+        face.add_inner();
+        face.add_outer();
+        // Without layers the viewBox hugs the oval bezel
+        let vb = view_box(&face.to_svg_string().unwrap());
+        assert!((vb[2] - vb[3] - 2.0 * 6.0 * 1.05).abs() < 1e-9);
+
+        face.add_horizontal_layer(HorizontalSpirograph::new(36.0, 0.77, 30.0, 3, 360).unwrap());
+        face.generate();
+        assert!(face.get_spirograph_points()[0]
+            .iter()
+            .any(|p| !shape.contains(*p)));
+
+        // Dial, clip and bezel are all ellipses
+        let svg = face.to_svg_string().unwrap();
+        assert!(svg.contains(
+            "<clipPath id=\"dial-clip\">\n<ellipse cx=\"0\" cy=\"0\" rx=\"36\" ry=\"30\"/>"
+        ));
+        assert_eq!(svg.matches("<ellipse").count(), 3);
+        assert!(!svg.contains("<circle"));
+
+        // The larger semi-axis is the one validated
+        let settings = ProjectSettings::default();
+        let too_wide = DialShape::Ellipse { rx: 50.0, ry: 30.0 };
+        assert!(WatchFace::new_with_shape(too_wide, settings.clone()).is_err());
+        let flat = DialShape::Ellipse { rx: 30.0, ry: 0.0 };
+        assert!(WatchFace::new_with_shape(flat, settings).is_err());
+
+        // Solids stay inside the oval
+        let path = std::env::temp_dir().join("test_watch_face_oval.stl");
+        face.to_stl(path.to_str().unwrap(), &ExportConfig::default())
+            .unwrap();
+        let mut file = std::fs::File::open(&path).unwrap();
+        let mesh = stl_io::read_stl(&mut file).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(!mesh.faces.is_empty());
+        for v in &mesh.vertices {
+            let r = (v[0] as f64 / 36.0).hypot(v[1] as f64 / 30.0);
+            assert!(r <= 1.0 + 1e-5, "STL vertex outside the oval at {}", r);
+        }
+    }
+
+    #[test]
+    fn test_render_raster_clips_to_the_dial() {
+        let shape = DialShape::Ellipse { rx: 36.0, ry: 30.0 };
+        let mut face = WatchFace::new_with_shape(shape, ProjectSettings::default()).unwrap();
+        face.add_inner();
+        face.add_outer();
+        face.add_horizontal_layer(HorizontalSpirograph::new(36.0, 0.77, 30.0, 3, 360).unwrap());
+        face.generate();
+
+        let image = face.render_raster(256, 200).unwrap();
+        assert_eq!((image.width, image.height), (256, 200));
+        assert!(face.render_raster(256, 0).is_err());
+
+        // Every inked pixel lies within the bezel, though the spirograph
+        // reaches past it
+        let (view_x, view_y, view_width, view_height) = face.view_box();
+        let scale = (256.0 / view_width).min(200.0 / view_height);
+        let offset_x = (256.0 - view_width * scale) / 2.0;
+        let offset_y = (200.0 - view_height * scale) / 2.0;
+        // Bezel outline plus half its stroke and a couple of pixels of ramp
+        let pad = 0.4 + 2.0 / scale;
+        let (reach_x, reach_y) = (36.0 * 1.05 + pad, 30.0 * 1.05 + pad);
+        let mut dark = 0;
+        for y in 0..image.height {
+            for x in 0..image.width {
+                if image.pixel(x, y).unwrap()[0] >= 128 {
+                    continue;
+                }
+                dark += 1;
+                let px = (x as f64 + 0.5 - offset_x) / scale + view_x;
+                let py = (y as f64 + 0.5 - offset_y) / scale + view_y;
+                assert!((px / reach_x).hypot(py / reach_y) <= 1.0);
+            }
+        }
+        assert!(dark > 0);
+    }
+
+    fn unit_test_face() -> WatchFace {
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_inner();
+        face.add_center_hole();
+        face.add_diamant_layer(DiamantLayer::new(DiamantConfig::new(12, 19.0)).unwrap());
+        face.add_limacon_layer(LimaconLayer::new(LimaconConfig::new(12, 10.0, 8.0)).unwrap());
+        face.add_horizontal_layer(HorizontalSpirograph::new(30.0, 0.75, 12.0, 3, 120).unwrap());
+        face.generate();
+        face
+    }
+
+    #[test]
+    fn test_new_with_settings_validates_in_mm() {
+        let inch = ProjectSettings::new(Unit::Inch);
+        let face = WatchFace::new_with_settings(1.5, inch.clone()).unwrap();
+        assert_eq!(face.unit(), Unit::Inch);
+        assert_eq!(face.radius(), 1.5);
+        // 30 in is far outside the 26-44 mm range; 1.5 mm is too small
+        assert!(WatchFace::new_with_settings(30.0, inch).is_err());
+        assert!(WatchFace::new(1.5).is_err());
+        assert_eq!(WatchFace::new(38.0).unwrap().unit(), Unit::Millimeter);
+    }
+
+    #[test]
+    fn test_inch_export_scales_by_25_4() {
+        let face = unit_test_face();
+        let inch = face.in_unit(Unit::Inch);
+
+        // Design geometry is untouched; the exported copy is 25.4x smaller
+        assert_eq!(face.unit(), Unit::Millimeter);
+        assert_eq!(face.radius(), 38.0);
+        assert!((inch.radius() * 25.4 - 38.0).abs() < 1e-12);
+        let pairs = face
+            .guilloche
+            .diamant_lines()
+            .into_iter()
+            .chain(face.guilloche.limacon_lines())
+            .zip(
+                inch.guilloche
+                    .diamant_lines()
+                    .into_iter()
+                    .chain(inch.guilloche.limacon_lines()),
+            );
+        for (mm_lines, in_lines) in pairs {
+            for (a, b) in mm_lines.iter().flatten().zip(in_lines.iter().flatten()) {
+                assert!((b.x * 25.4 - a.x).abs() < 1e-12);
+                assert!((b.y * 25.4 - a.y).abs() < 1e-12);
+            }
+        }
+
+        let mm_svg = face.svg_document(None, false).to_string();
+        let in_svg = inch.svg_document(None, false).to_string();
+        let width = |svg: &str, suffix: &str| -> f64 {
+            let start = svg.find("width=\"").unwrap() + 7;
+            let end = start + svg[start..].find(suffix).unwrap();
+            svg[start..end].parse().unwrap()
+        };
+        // Both widths are rounded to 4 decimals, the inch one before scaling
+        assert!((width(&in_svg, "in\"") * 25.4 - width(&mm_svg, "mm\"")).abs() < 26.0 * 0.5e-4);
+        assert!(!in_svg.contains("mm\""));
+
+        // STL vertices differ by exactly the unit factor (to f32 precision)
+        let mm_path = std::env::temp_dir().join("test_watch_face_units_mm.stl");
+        let in_path = std::env::temp_dir().join("test_watch_face_units_in.stl");
+        let mm_config = ExportConfig::default();
+        let in_config = ExportConfig {
+            depth: mm_config.depth / 25.4,
+            base_thickness: mm_config.base_thickness / 25.4,
+            unit: Unit::Inch,
+            ..ExportConfig::default()
+        };
+        face.to_stl(mm_path.to_str().unwrap(), &mm_config).unwrap();
+        face.to_stl(in_path.to_str().unwrap(), &in_config).unwrap();
+        let read = |path: &std::path::Path| {
+            let mut file = std::fs::File::open(path).unwrap();
+            let mesh = stl_io::read_stl(&mut file).unwrap();
+            let _ = std::fs::remove_file(path);
+            mesh
+        };
+        let mm_mesh = read(&mm_path);
+        let in_mesh = read(&in_path);
+        assert_eq!(mm_mesh.vertices.len(), in_mesh.vertices.len());
+        for (a, b) in mm_mesh.vertices.iter().zip(in_mesh.vertices.iter()) {
+            for k in 0..3 {
+                let (a, b) = (a[k] as f64, b[k] as f64);
+                assert!((b * 25.4 - a).abs() <= 1e-5 * a.abs().max(1.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_convert_units_round_trip() {
+        let face = unit_test_face();
+        let mut converted = face.clone();
+        converted.convert_units(Unit::Inch);
+        assert_eq!(converted.unit(), Unit::Inch);
+        assert!((converted.holes[0].radius * 25.4 - face.holes[0].radius).abs() < 1e-12);
+
+        converted.convert_units(Unit::Millimeter);
+        assert!((converted.radius() - 38.0).abs() < 1e-12);
+        for (a, b) in face
+            .get_spirograph_points()
+            .iter()
+            .flatten()
+            .zip(converted.get_spirograph_points().iter().flatten())
+        {
+            assert!((a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9);
+        }
+
+        // Regenerating after conversion matches the converted geometry
+        converted.convert_units(Unit::Inch);
+        let mut regenerated = converted.clone();
+        regenerated.generate();
+        for (a, b) in converted
+            .guilloche
+            .limacon_lines()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .zip(
+                regenerated
+                    .guilloche
+                    .limacon_lines()
+                    .into_iter()
+                    .flatten()
+                    .flatten(),
+            )
+        {
+            assert!((a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9);
+        }
+    }
+
+    fn view_box(svg: &str) -> Vec<f64> {
+        let start = svg.find("viewBox=\"").unwrap() + 9;
+        let end = start + svg[start..].find('"').unwrap();
+        svg[start..end]
+            .split_whitespace()
+            .map(|v| v.parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_viewbox_fits_off_center_layer() {
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_inner();
+        face.add_outer();
+        face.add_flinque_at_clock(30.0, FlinqueConfig::default(), 3, 0, 80.0)
+            .unwrap();
+        face.generate();
+
+        let points: Vec<&Point2D> = face
+            .guilloche
+            .flinque_lines()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect();
+        // The layer reaches past the old fixed 2.5R box
+        assert!(points.iter().any(|p| p.x > 38.0 * 2.5));
+
+        let svg = face.svg_document(None, false).to_string();
+        let vb = view_box(&svg);
+        for p in points {
+            assert!(p.x >= vb[0] && p.x <= vb[0] + vb[2]);
+            assert!(p.y >= vb[1] && p.y <= vb[1] + vb[3]);
+        }
+        // The dial clip stays the dial circle
+        assert!(svg.contains("<clipPath id=\"dial-clip\">\n<circle cx=\"0\" cy=\"0\" r=\"38\"/>"));
+    }
+
+    #[test]
+    fn test_fixed_viewbox_matches_legacy_output() {
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_inner();
+        face.add_outer();
+        face.add_center_hole();
+        face.generate();
+
+        let size = 38.0 * 2.5;
+        let legacy = format!(
+            "viewBox=\"{} {} {} {}\"",
+            -size,
+            -size,
+            size * 2.0,
+            size * 2.0
+        );
+        assert!(!face.svg_document(None, false).to_string().contains(&legacy));
+
+        face.set_fixed_viewbox(Some((-size, -size, size * 2.0, size * 2.0)))
+            .unwrap();
+        let pinned = face.svg_document(None, false).to_string();
+        assert!(pinned.contains(&legacy));
+
+        // Written by the fixed-viewBox `to_svg` before layers were grouped
+        let golden = concat!(
+            "<svg height=\"190mm\" viewBox=\"-95 -95 190 190\" width=\"190mm\" ",
+            "xmlns=\"http://www.w3.org/2000/svg\">\n",
+            "<circle cx=\"0\" cy=\"0\" fill=\"#fafaf5\" r=\"38\" stroke=\"#2c2c2c\" ",
+            "stroke-width=\"0.3\"/>\n",
+            "<clipPath id=\"dial-clip\">\n",
+            "<circle cx=\"0\" cy=\"0\" r=\"38\"/>\n",
+            "</clipPath>\n",
+            "<g clip-path=\"url(#dial-clip)\"/>\n",
+            "<circle cx=\"0\" cy=\"0\" fill=\"none\" r=\"39.9\" stroke=\"#1a1a1a\" ",
+            "stroke-width=\"0.8\"/>\n",
+            "<circle cx=\"0\" cy=\"0\" fill=\"#1a1a1a\" r=\"0.8\"/>\n",
+            "</svg>",
+        );
+        let root = |svg: &str| svg.lines().next().unwrap().to_string();
+        let clip = |svg: &str| {
+            let start = svg.find("<clipPath").unwrap();
+            let end = svg.find("</clipPath>").unwrap();
+            svg[start..end].to_string()
+        };
+        assert_eq!(root(&pinned), root(golden));
+        assert_eq!(clip(&pinned), clip(golden));
+        // Apart from the groups naming each part, the documents match
+        let ungrouped = |svg: &str| {
+            svg.lines()
+                .filter(|line| !line.starts_with("<g") && !line.starts_with("</g"))
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ungrouped(&pinned), ungrouped(golden));
+
+        assert!(face.set_fixed_viewbox(Some((0.0, 0.0, 0.0, 1.0))).is_err());
+        assert!(face.set_svg_margin(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_engraved_text_in_svg_and_stl() {
+        use crate::engrave_text::{ArcDirection, TextPosition};
+
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_text(EngravedTextConfig::new(
+            "XII",
+            3.0,
+            TextPosition::arc_at_clock(12, 0, 32.0, ArcDirection::Clockwise),
+        ))
+        .unwrap();
+        face.generate();
+
+        let strokes: Vec<&Vec<Point2D>> =
+            face.guilloche.text_lines().into_iter().flatten().collect();
+        assert_eq!(strokes.len(), 4);
+        let svg = face.svg_document(None, false).to_string();
+        assert_eq!(svg.matches("<path").count(), strokes.len());
+
+        // A capped V-groove per stroke, three faces per segment (no base
+        // slab); the text sits inside the dial
+        let segments: usize = strokes.iter().map(|s| s.len() - 1).sum();
+        let triangles = 6 * segments + 2 * strokes.len();
+        let path = std::env::temp_dir().join("test_watch_face_text.stl");
+        let config = ExportConfig {
+            base_thickness: 0.0,
+            ..ExportConfig::default()
+        };
+        face.to_stl(path.to_str().unwrap(), &config).unwrap();
+        let size = std::fs::metadata(&path).unwrap().len();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(size, 84 + 50 * triangles as u64);
+    }
+
+    #[test]
+    fn test_chapter_ring_cutouts_pierce_stl_and_dxf() {
+        let mut config = ChapterRingConfig::new(30.0, 34.0);
+        for hour in [12, 3, 6, 9, 2] {
+            config = config.with_index_cutout(hour, 1.5, 3.0);
+        }
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_chapter_ring(config).unwrap();
+        face.add_center_hole();
+        face.generate();
+        assert_eq!(face.guilloche.chapter_ring_lines()[0].len(), 2 + 60 + 5);
+        let svg = face.svg_document(None, false).to_string();
+        assert_eq!(svg.matches("<path").count(), 2 + 60 + 5);
+
+        let apertures = face.apertures();
+        assert_eq!(apertures.len(), 6);
+        let opening: f64 = apertures
+            .iter()
+            .map(|a| crate::cells::polygon_area(a))
+            .sum();
+
+        let path = std::env::temp_dir().join("test_watch_face_chapter_ring.stl");
+        let config = ExportConfig {
+            depth: 0.1,
+            base_thickness: 1.0,
+            ..ExportConfig::default()
+        };
+        face.to_stl(path.to_str().unwrap(), &config).unwrap();
+        let mut file = std::fs::File::open(&path).unwrap();
+        let mesh = stl_io::read_stl(&mut file).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        // The slab's top face, at the groove floor, is the disc less every
+        // opening
+        let corner =
+            |i: usize| Point2D::new(mesh.vertices[i][0] as f64, mesh.vertices[i][1] as f64);
+        let top: f64 = mesh
+            .faces
+            .iter()
+            .filter(|f| {
+                f.normal[2] > 0.5 && f.vertices.iter().all(|&i| mesh.vertices[i][2] < -0.05)
+            })
+            .map(|f| crate::cells::polygon_area(&f.vertices.map(corner)))
+            .sum();
+        let disc = 64.0 * 38.0 * 38.0 * (2.0 * std::f64::consts::PI / 128.0).sin();
+        assert!(
+            (top - (disc - opening)).abs() < 1e-2,
+            "{} vs {}",
+            top,
+            disc - opening
+        );
+
+        // Grooves stop at the cutout edges
+        let rect = &apertures[1];
+        let center = Point2D::new(
+            rect.iter().map(|p| p.x).sum::<f64>() / 4.0,
+            rect.iter().map(|p| p.y).sum::<f64>() / 4.0,
+        );
+        assert!(mesh.vertices.iter().all(|v| {
+            let (dx, dy) = (v[0] as f64 - center.x, v[1] as f64 - center.y);
+            dx.abs() > 0.7 || dy.abs() > 0.7
+        }));
+
+        let path = std::env::temp_dir().join("test_watch_face_profile.dxf");
+        face.to_dxf_profile(path.to_str().unwrap()).unwrap();
+        let dxf = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(dxf.matches("LWPOLYLINE").count(), 1 + 6);
+        assert_eq!(dxf.matches("\n70\n1\n").count(), 7);
+    }
+
+    #[test]
+    fn test_aperture_cuts_pattern_svg_and_stl() {
+        let mut face = WatchFace::new(30.0).unwrap();
+        face.add_horizontal_layer(HorizontalSpirograph::new(28.0, 0.77, 24.0, 3, 360).unwrap());
+        face.add_flinque_layer(FlinqueLayer::new(28.0, FlinqueConfig::default()).unwrap());
+        face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(24, 25.0)).unwrap());
+        let window = ApertureConfig {
+            stroke_color: "#803020".to_string(),
+            ..ApertureConfig::rect(6.0, 4.0, 0.0, 0.0)
+        };
+        face.add_aperture_at_clock(3, 0, 18.0, window).unwrap();
+        face.add_aperture(ApertureConfig {
+            center_y: 12.0,
+            ..ApertureConfig::circle(5.0)
+        })
+        .unwrap();
+        face.generate();
+        assert!(face
+            .add_aperture(ApertureConfig::rect(2.0, 2.0, 1.5, 0.0))
+            .is_err());
+        assert!(face.add_aperture(ApertureConfig::circle(0.0)).is_err());
+        assert_eq!(face.complication_apertures().len(), 2);
+        assert_eq!(face.apertures().len(), 2);
+
+        // Rounded corners are convex arcs, turned clockwise on screen
+        let rounded = ApertureConfig::rect(6.0, 4.0, 0.5, 90.0).outline();
+        assert_eq!(rounded.len(), 4 * 9);
+        let (min_x, min_y, max_x, max_y) = polyline_bounds([rounded.as_slice()]).unwrap();
+        assert!((max_x - min_x - 4.0).abs() < 1e-9 && (max_y - min_y - 6.0).abs() < 1e-9);
+        assert!(crate::cells::polygon_area(&rounded) > 0.0);
+
+        // The window at 3 o'clock spans x 15..21 and y -2..2
+        let inside = |x: f64, y: f64| x > 15.0 + 1e-6 && x < 21.0 - 1e-6 && y.abs() < 2.0 - 1e-6;
+        let svg = face.svg_document(None, false).to_string();
+        let path_points: Vec<(f64, f64)> = svg
+            .split(" d=\"")
+            .skip(1)
+            .flat_map(|rest| {
+                let data = &rest[..rest.find('"').unwrap()];
+                let numbers: Vec<f64> = data
+                    .split(|c: char| c.is_ascii_alphabetic() || c == ',' || c == ' ')
+                    .filter_map(|n| n.parse().ok())
+                    .collect();
+                numbers
+                    .chunks(2)
+                    .map(|xy| (xy[0], xy[1]))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert!(path_points.len() > 1000);
+        assert!(path_points.iter().all(|&(x, y)| !inside(x, y)));
+        // Lines reach the window edge rather than stopping short of it
+        assert!(path_points
+            .iter()
+            .any(|&(x, y)| (x - 15.0).abs() < 1e-6 && y.abs() < 1.5));
+        assert!(svg.contains("clip-rule=\"evenodd\""));
+        let outlines = svg.split("id=\"apertures\"").nth(1).unwrap();
+        assert_eq!(outlines.matches("stroke=\"#803020\"").count(), 1);
+        assert_eq!(outlines.matches("<path").count(), 2);
+
+        // Without apertures the clip is the plain dial
+        let mut plain = face.clone();
+        plain.clear_apertures();
+        let plain_svg = plain.svg_document(None, false).to_string();
+        assert!(plain_svg.contains("<clipPath id=\"dial-clip\">\n<circle"));
+        assert!(!plain_svg.contains("id=\"apertures\""));
+
+        let path = std::env::temp_dir().join("test_watch_face_apertures.stl");
+        face.to_stl(path.to_str().unwrap(), &ExportConfig::default())
+            .unwrap();
+        let mut file = std::fs::File::open(&path).unwrap();
+        let mesh = stl_io::read_stl(&mut file).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(mesh
+            .vertices
+            .iter()
+            .all(|v| !inside(v[0] as f64, v[1] as f64) || (v[0] as f64 - 15.0).abs() < 1e-4));
+        assert!(mesh
+            .vertices
+            .iter()
+            .all(|v| (v[0] as f64).hypot(v[1] as f64 - 12.0) > 5.0 - 1e-3));
+    }
+
+    #[test]
+    fn test_subdial_clips_its_pattern_and_masks_the_main_one() {
+        // A ring and a diameter running past the subdial edge
+        let mut seconds = WatchFace::new_with_policy(7.0, RadiusPolicy::Unrestricted).unwrap();
+        let ring = circle_outline(Point2D::new(0.0, 0.0), 5.0, 90);
+        let diameter = (0..=36)
+            .map(|i| Point2D::new(i as f64 * 0.5 - 9.0, 0.0))
+            .collect();
+        seconds.add_imported_layer(vec![ring, diameter]);
+        seconds.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(12, 6.0)).unwrap());
+
+        let mut face = WatchFace::new(30.0).unwrap();
+        face.add_horizontal_layer(HorizontalSpirograph::new(28.0, 0.77, 24.0, 3, 360).unwrap());
+        face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(24, 18.0)).unwrap());
+        face.add_subdial_at_clock(6, 0, 15.0, 7.0, seconds.clone())
+            .unwrap();
+        assert!(face
+            .add_subdial_at_clock(3, 0, 25.0, 7.0, seconds.clone())
+            .is_err());
+        assert!(face.add_subdial(Subdial::new(0.0, seconds)).is_err());
+        face.generate();
+        assert_eq!(face.subdials().len(), 1);
+
+        // 6 o'clock is straight down the screen
+        let center = Point2D::new(0.0, 15.0);
+        let distance = |x: f64, y: f64| (x - center.x).hypot(y - center.y);
+        let lines = face.subdial_lines(0).unwrap();
+        assert!(!lines.is_empty());
+        assert!(lines
+            .iter()
+            .flatten()
+            .all(|p| distance(p.x, p.y) <= 7.0 + 1e-9));
+        assert!(face.subdial_lines(1).is_none());
+
+        let path_points = |svg: &str| -> Vec<(f64, f64)> {
+            svg.split(" d=\"")
+                .skip(1)
+                .flat_map(|rest| {
+                    let data = &rest[..rest.find('"').unwrap()];
+                    let numbers: Vec<f64> = data
+                        .split(|c: char| c.is_ascii_alphabetic() || c == ',' || c == ' ')
+                        .filter_map(|n| n.parse().ok())
+                        .collect();
+                    numbers
+                        .chunks(2)
+                        .map(|xy| (xy[0], xy[1]))
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        };
+        let svg = face.svg_document(None, false).to_string();
+        let pattern =
+            &svg[svg.find("id=\"pattern\"").unwrap()..svg.find("id=\"subdials\"").unwrap()];
+        let subdial = &svg[svg.find("id=\"subdials\"").unwrap()..];
+        let main_points = path_points(pattern);
+        let subdial_points = path_points(subdial);
+        assert!(main_points.len() > 1000 && subdial_points.len() > 100);
+        assert!(main_points
+            .iter()
+            .all(|&(x, y)| distance(x, y) >= 7.0 - 1e-4));
+        assert!(subdial_points
+            .iter()
+            .all(|&(x, y)| distance(x, y) <= 7.0 + 1e-4));
+        // The main pattern reaches the subdial edge, which is ringed
+        assert!(main_points
+            .iter()
+            .any(|&(x, y)| distance(x, y) < 7.0 * 1.01));
+        assert!(subdial.contains("id=\"subdial-0-draperie-0\""));
+        assert!(subdial.contains("<circle") && subdial.contains("r=\"7\""));
+
+        // Unmasked, the main pattern runs underneath
+        let mut unmasked = face.clone();
+        unmasked.subdials[0].mask = false;
+        let svg = unmasked.svg_document(None, false).to_string();
+        let pattern =
+            &svg[svg.find("id=\"pattern\"").unwrap()..svg.find("id=\"subdials\"").unwrap()];
+        assert!(path_points(pattern)
+            .iter()
+            .any(|&(x, y)| distance(x, y) < 6.0));
+
+        // The STL has the subdial's grooves and none of the main pattern's
+        // inside the subdial
+        let stl_vertices = |face: &WatchFace| {
+            let path = std::env::temp_dir().join("test_watch_face_subdial.stl");
+            face.to_stl(path.to_str().unwrap(), &ExportConfig::default())
+                .unwrap();
+            let mut file = std::fs::File::open(&path).unwrap();
+            let mesh = stl_io::read_stl(&mut file).unwrap();
+            let _ = std::fs::remove_file(&path);
+            mesh.vertices
+                .iter()
+                .filter(|v| v[2] < -1e-6)
+                .map(|v| (v[0] as f64, v[1] as f64))
+                .collect::<Vec<(f64, f64)>>()
+        };
+        // Between the subdial's ring and its edge only the diameter is cut
+        let in_gap = |&(x, y): &(f64, f64)| (5.5..6.5).contains(&distance(x, y));
+        let mut bare = face.clone();
+        bare.clear_subdials();
+        let without = stl_vertices(&bare);
+        assert!(without
+            .iter()
+            .any(|p| in_gap(p) && (p.1 - 15.0).abs() > 1.0));
+        let with_subdial = stl_vertices(&face);
+        assert!(with_subdial.iter().any(in_gap));
+        assert!(with_subdial
+            .iter()
+            .filter(|p| in_gap(p))
+            .all(|&(_, y)| (y - 15.0).abs() < 1.0));
+
+        // Subdials survive a JSON round trip
+        let copy = WatchFace::from_json(&face.to_json().unwrap()).unwrap();
+        assert_eq!(copy.subdials().len(), 1);
+        assert_eq!(copy.subdials()[0].radius, 7.0);
+    }
+
+    #[test]
+    fn test_hands_point_at_the_time_and_stay_out_of_exports() {
+        use super::hands::HandStyle;
+
+        let mut face = WatchFace::new(38.0).unwrap();
+        let mut configs = HandsSet::default();
+        configs.hour.style = HandStyle::Breguet;
+        face.add_hands(3, 15, Some(45), configs.clone()).unwrap();
+
+        let hands = face.hand_outlines();
+        assert_eq!(hands.len(), 3);
+        for outline in hands.iter().flatten() {
+            assert_eq!(outline.first(), outline.last());
+            assert!(outline.len() >= 5);
+        }
+        // The Breguet hour hand is its outline and the hole in its ring
+        assert_eq!(hands[0].len(), 2);
+
+        // Each hand's tip is its farthest point from the center
+        let tip = |outlines: &Vec<Vec<Point2D>>| {
+            outlines[0]
+                .iter()
+                .copied()
+                .max_by(|a, b| a.x.hypot(a.y).total_cmp(&b.x.hypot(b.y)))
+                .unwrap()
+        };
+        let minute = tip(&hands[1]);
+        assert!((minute.x - 0.75 * 38.0).abs() < 1e-9 && minute.y.abs() < 1e-9);
+        let second = tip(&hands[2]);
+        // The baton seconds hand ends in two corners half its width apart
+        assert!((second.x + 0.85 * 38.0).abs() < 1e-9 && (second.y.abs() - 0.125).abs() < 1e-9);
+        // A quarter past three puts the hour hand a quarter hour past +x
+        let hour = tip(&hands[0]);
+        assert!((hour.y.atan2(hour.x) - std::f64::consts::PI / 24.0).abs() < 1e-9);
+
+        assert!(face.add_hands(24, 0, None, configs.clone()).is_err());
+        assert!(face.add_hands(3, 0, Some(60), configs.clone()).is_err());
+        let mut wide = configs.clone();
+        wide.hour.width = 4.0;
+        assert!(face.add_hands(3, 0, None, wide).is_err());
+        assert_eq!(face.hand_outlines().len(), 3);
+
+        // Hands are drawn last, over the pattern
+        face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(12, 15.0)).unwrap());
+        face.generate();
+        let svg = face.to_svg_string().unwrap();
+        let pattern = svg.find("id=\"pattern\"").unwrap();
+        let hands_group = svg.find("id=\"hands\"").unwrap();
+        assert!(pattern < hands_group);
+        assert!(svg[hands_group..].contains("id=\"hand-minute\""));
+        assert!(svg[hands_group..].contains("fill=\"#b22222\""));
+
+        // STEP only has a hands part when asked for
+        let step = |face: &WatchFace| {
+            let path = std::env::temp_dir().join("test_watch_face_hands.stp");
+            face.to_step(path.to_str().unwrap(), &ExportConfig::default())
+                .unwrap();
+            let text = std::fs::read_to_string(&path).unwrap();
+            let _ = std::fs::remove_file(&path);
+            text
+        };
+        assert!(!step(&face).contains("'hands'"));
+        face.set_hands_in_exports(true);
+        assert!(step(&face).contains("'hands'"));
+
+        let copy = WatchFace::from_json(&face.to_json().unwrap()).unwrap();
+        assert_eq!(copy.hand_outlines(), face.hand_outlines());
+        face.clear_hands();
+        assert!(face.hand_outlines().is_empty());
+        assert!(!face.to_svg_string().unwrap().contains("id=\"hands\""));
+    }
+
+    #[test]
+    fn test_generate_lenient_skips_invalid_layer() {
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_diamant_layer(DiamantLayer::new(DiamantConfig::new(12, 15.0)).unwrap());
+        let mut draperie = DraperieLayer::new(DraperieConfig::new(8, 20.0)).unwrap();
+        draperie.config.num_rings = 0;
+        face.add_draperie_layer(draperie);
+        face.add_limacon_layer(LimaconLayer::new(LimaconConfig::new(12, 10.0, 8.0)).unwrap());
+
+        let issues = face.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, LayerKind::Draperie);
+        assert_eq!(issues[0].index, 0);
+        assert_eq!(issues[0].field, "num_rings");
+        assert!(face.guilloche.diamant_lines()[0].is_empty());
+
+        let err = face.clone().try_generate().unwrap_err();
+        assert!(err.to_string().contains("draperie layer 0: num_rings"));
+
+        let outcome = face.generate_lenient();
+        assert_eq!(outcome.generated, 2);
+        assert_eq!(outcome.skipped, issues);
+        assert!(!outcome.is_clean());
+        assert!(!face.guilloche.diamant_lines()[0].is_empty());
+        assert!(face.guilloche.draperie_lines()[0].is_empty());
+        assert!(!face.guilloche.limacon_lines()[0].is_empty());
+    }
+
+    #[test]
+    fn test_reference_image_in_svg() {
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_inner();
+        face.generate();
+        face.set_reference_image("dial.jpg", 76.2, 0.4, 30.0, (1.5, -2.0))
+            .unwrap();
+
+        let (sin, cos) = 30f64.to_radians().sin_cos();
+        let matrix = format!("matrix({} {} {} {} 1.5 -2)", cos, sin, -sin, cos);
+        let svg = face.svg_document(None, true).to_string();
+        let image_pos = svg.find("<image").unwrap();
+        assert!(svg.contains("href=\"dial.jpg\""));
+        assert!(svg.contains("width=\"76.2\""));
+        assert!(svg.contains("x=\"-38.1\""));
+        assert!(svg.contains(&format!("transform=\"{}\"", matrix)));
+        // Above the dial fill, beneath the pattern
+        assert!(svg.find("<circle").unwrap() < image_pos);
+        assert!(image_pos < svg.find("clip-path=\"url(#dial-clip)\"").unwrap());
+
+        // Production exports leave it out
+        assert!(!face
+            .svg_document(None, false)
+            .to_string()
+            .contains("<image"));
+
+        // Millimeter sizes follow the design unit
+        let inch = face.in_unit(Unit::Inch);
+        let inch_svg = inch.svg_document(None, true).to_string();
+        assert!(inch_svg.contains("width=\"3\""));
+
+        face.clear_reference();
+        assert!(face.reference_image().is_none());
+        assert!(!face.svg_document(None, true).to_string().contains("<image"));
+
+        assert!(face
+            .set_reference_image("", 10.0, 0.5, 0.0, (0.0, 0.0))
+            .is_err());
+        assert!(face
+            .set_reference_image("a.png", 0.0, 0.5, 0.0, (0.0, 0.0))
+            .is_err());
+        assert!(face
+            .set_reference_image("a.png", 10.0, 1.5, 0.0, (0.0, 0.0))
+            .is_err());
+    }
+
+    #[test]
+    fn test_engraver_bitmap_covers_dial_in_mm() {
+        let mut face = WatchFace::new(30.0).unwrap();
+        face.add_inner();
+        let mut empty = face.clone();
+        face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(40, 12.0)).unwrap());
+        face.generate();
+
+        let bitmap = face.to_engraver_bitmap(254, 0.1).unwrap();
+        // 60.1 mm at 10 px/mm
+        assert_eq!((bitmap.width, bitmap.height), (601, 601));
+        assert!((bitmap.origin_mm.0 + 30.05).abs() < 1e-12);
+        assert!(bitmap.count_set() > 0);
+        // Only the pattern is drawn: the dial edge row is blank
+        assert!((0..bitmap.width).all(|x| !bitmap.get(x, 0)));
+
+        // An inch design rasterizes in mm too; only rounding at stroke
+        // edges differs
+        let mut inches = face.clone();
+        inches.convert_units(Unit::Inch);
+        let converted = inches.to_engraver_bitmap(254, 0.1).unwrap();
+        assert_eq!((converted.width, converted.height), (601, 601));
+        let (a, b) = (converted.count_set() as f64, bitmap.count_set() as f64);
+        assert!((a - b).abs() / b < 1e-3, "{} vs {}", a, b);
+
+        empty.generate();
+        assert!(empty.to_engraver_bitmap(254, 0.1).is_err());
+        assert!(face.to_engraver_bitmap(0, 0.1).is_err());
+        assert!(face.to_engraver_bitmap(254, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_metadata_embedded_in_svg_and_step() {
+        let mut face = WatchFace::new(30.0).unwrap();
+        face.add_inner();
+        face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(12, 12.0)).unwrap());
+        face.generate();
+        face.set_metadata(
+            FileMetadata::new("D'42 é")
+                .with_params_hash("9f3c")
+                .with_license("CC-BY-4.0")
+                .with_extra("customer", "A \"B\" & C"),
+        );
+
+        let dir = std::env::temp_dir();
+        let svg_path = dir.join("test_watch_face_metadata.svg");
+        face.to_svg(svg_path.to_str().unwrap()).unwrap();
+        let svg = std::fs::read_to_string(&svg_path).unwrap();
+        let _ = std::fs::remove_file(&svg_path);
+        assert!(svg.contains("<metadata id=\"file-metadata\">"));
+        assert!(svg.contains("<dc:identifier>D'42 é</dc:identifier>"));
+        assert!(svg.contains("<dc:rights>CC-BY-4.0</dc:rights>"));
+        assert!(svg.contains("customer: A \"B\" &amp; C</dc:description>"));
+        assert!(svg.contains("data-params-hash=\"9f3c\""));
+        assert!(svg.contains("data-customer=\"A &apos;B&apos; &amp; C\""));
+
+        let step_path = dir.join("test_watch_face_metadata.stp");
+        face.to_step(step_path.to_str().unwrap(), &ExportConfig::default())
+            .unwrap();
+        let step = std::fs::read_to_string(&step_path).unwrap();
+        let _ = std::fs::remove_file(&step_path);
+        assert!(step.starts_with("ISO-10303-21;\nHEADER;\n"));
+        let header = &step[..step.find("ENDSEC;").unwrap()];
+        assert!(header.contains("'design_id: D''42 \\X2\\00E9\\X0\\'"));
+        assert!(header.contains("('D''42 \\X2\\00E9\\X0\\'),('')"));
+        assert!(header.contains("'params_hash: 9f3c'"));
+        // The header stays ASCII with balanced quotes, one entity per line
+        assert!(header.is_ascii());
+        for line in header.lines().skip(2) {
+            assert!(line.ends_with(");"), "{}", line);
+            assert_eq!(line.matches('\'').count() % 2, 0, "{}", line);
+        }
+
+        face.clear_metadata();
+        face.to_step(step_path.to_str().unwrap(), &ExportConfig::default())
+            .unwrap();
+        let step = std::fs::read_to_string(&step_path).unwrap();
+        let _ = std::fs::remove_file(&step_path);
+        assert!(step.contains("(''),(''),'','','');"));
+    }
+
+    #[test]
+    fn test_step_assembly_names_every_layer() {
+        let mut face = WatchFace::new(30.0).unwrap();
+        face.add_horizontal_layer(HorizontalSpirograph::new(28.0, 0.77, 20.0, 3, 360).unwrap());
+        face.add_flinque_layer(FlinqueLayer::new(20.0, FlinqueConfig::default()).unwrap());
+        face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(12, 12.0)).unwrap());
+        face.add_paon_layer(PaonLayer::new(PaonConfig::new(8, 12.0)).unwrap());
+        face.add_limacon_layer(LimaconLayer::new(LimaconConfig::new(24, 10.0, 8.0)).unwrap());
+        face.add_diamant_layer(DiamantLayer::new(DiamantConfig::new(12, 15.0)).unwrap());
+        face.add_huiteight_layer(HuitEightLayer::new(HuitEightConfig::new(6, 10.0)).unwrap());
+        face.add_clous_de_paris_layer(
+            ClousDeParisLayer::new(ClousDeParisConfig::default()).unwrap(),
+        );
+        face.generate();
+
+        let path = std::env::temp_dir().join("test_watch_face_assembly.stp");
+        face.to_step(path.to_str().unwrap(), &ExportConfig::default())
+            .unwrap();
+        let step = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let names = [
+            "spirograph-0",
+            "flinque-0",
+            "draperie-0",
+            "paon-0",
+            "limacon-0",
+            "diamant-0",
+            "huiteight-0",
+            "clous_de_paris-0",
+        ];
+        for name in names {
+            assert!(
+                step.contains(&format!("GEOMETRIC_CURVE_SET('{}',(", name)),
+                "{}",
+                name
+            );
+            assert!(
+                step.contains(&format!("PRODUCT('{}','{}'", name, name)),
+                "{}",
+                name
+            );
+        }
+        // The root, the dial and one product and placement per layer
+        let count = |entity: &str| step.matches(&format!("={}(", entity)).count();
+        assert_eq!(count("PRODUCT"), 2 + names.len());
+        assert_eq!(count("NEXT_ASSEMBLY_USAGE_OCCURRENCE"), 1 + names.len());
+        assert_eq!(
+            count("CONTEXT_DEPENDENT_SHAPE_REPRESENTATION"),
+            1 + names.len()
+        );
+        assert_eq!(count("GEOMETRIC_CURVE_SET"), 1 + names.len());
+        assert_eq!(count("CIRCLE"), 1);
+        assert!(step.contains("CIRCLE('dial',#"));
+        assert!(step.contains("PLANE('dial',#"));
+
+        // One polyline per clipped line, each point written once
+        let lines: usize = face
+            .guilloche
+            .kind_lines(LayerKind::Flinque)
+            .iter()
+            .flat_map(|lines| lines.iter())
+            .flat_map(|line| face.dial_shape.clip_polyline(line, false))
+            .filter(|piece| !polyline_is_degenerate(piece))
+            .count();
+        assert!(count("POLYLINE") > lines);
+        let ids: Vec<&str> = step
+            .lines()
+            .filter(|line| line.starts_with('#'))
+            .map(|line| &line[..line.find('=').unwrap()])
+            .collect();
+        assert_eq!(
+            ids.len(),
+            ids.iter().collect::<std::collections::HashSet<_>>().len()
+        );
+        assert_eq!(
+            ids.last().unwrap()[1..].parse::<usize>().unwrap(),
+            ids.len()
+        );
+    }
+
+    #[test]
+    fn test_opacity_envelope_fades_rings_in_svg() {
+        let mut face = WatchFace::new(30.0).unwrap();
+        face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(12, 12.0)).unwrap());
+        face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(12, 12.0)).unwrap());
+        face.generate();
+        face.set_opacity_envelope(LayerKind::Draperie, 0, Envelope::radial(0.0, 40.0, 40.0))
+            .unwrap();
+        assert!(face.opacity_envelope(LayerKind::Draperie, 1).is_none());
+        assert!(face
+            .set_opacity_envelope(LayerKind::Draperie, 1, Envelope::table(Vec::new()))
+            .is_err());
+
+        let opacities = |face: &WatchFace| -> Vec<f64> {
+            let svg = face.svg_document(None, false).to_string();
+            svg.match_indices(" opacity=\"")
+                .map(|(i, m)| {
+                    let rest = &svg[i + m.len()..];
+                    rest[..rest.find('"').unwrap()].parse().unwrap()
+                })
+                .collect()
+        };
+        // Only the first layer's rings carry an opacity, rising outward
+        let values = opacities(&face);
+        assert_eq!(values.len(), face.guilloche.draperie_lines()[0].len());
+        assert!(values.windows(2).all(|w| w[0] < w[1]), "{:?}", values);
+        assert!(values.iter().all(|&v| v > 0.0 && v < 1.0));
+
+        // Converting units scales the envelope's radii with the geometry
+        let mut inches = face.clone();
+        inches.convert_units(Unit::Inch);
+        for (a, b) in opacities(&inches).iter().zip(&values) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        face.clear_opacity_envelopes();
+        assert!(opacities(&face).is_empty());
+    }
+
+    #[test]
+    fn test_index_markers_and_minute_track_are_engraved() {
+        use crate::index_markers::IndexShape;
+
+        let track = || {
+            let mut face = WatchFace::new(30.0).unwrap();
+            face.add_minute_track(27.0, 28.5, 60, 5).unwrap();
+            face
+        };
+        let mut face = track();
+        let bars = IndexConfig::new(
+            IndexShape::Line {
+                length: 2.0,
+                width: 0.5,
+            },
+            22.0,
+            25.0,
+        )
+        .excluding(6, 0)
+        .with_style(LayerStyle::new("#b8860b", 0.08, 1.0));
+        face.add_index_markers(bars).unwrap();
+        face.generate();
+        assert_eq!(face.guilloche.chapter_ring_lines()[0].len(), 2 + 60);
+        assert_eq!(face.guilloche.index_markers_lines()[0].len(), 11);
+        let svg = face.svg_document(None, false).to_string();
+        assert_eq!(svg.matches("stroke=\"#b8860b\"").count(), 11);
+
+        // Every bar is cut into the solid alongside the track
+        let triangles = |face: &WatchFace, name: &str| {
+            let path = std::env::temp_dir().join(name);
+            face.to_stl(path.to_str().unwrap(), &ExportConfig::default())
+                .unwrap();
+            let mut file = std::fs::File::open(&path).unwrap();
+            let mesh = stl_io::read_stl(&mut file).unwrap();
+            let _ = std::fs::remove_file(&path);
+            mesh.faces.len()
+        };
+        let mut bare = track();
+        bare.generate();
+        assert!(
+            triangles(&face, "test_watch_face_indices.stl")
+                > triangles(&bare, "test_watch_face_track.stl")
+        );
+
+        // A bad style is rejected before the layer is added
+        let badly_styled = IndexConfig::default().with_style(LayerStyle::new("#000", 0.0, 1.0));
+        assert!(face.add_index_markers(badly_styled).is_err());
+        assert_eq!(face.guilloche.kind_count(LayerKind::IndexMarkers), 1);
+    }
+
+    #[test]
+    fn test_layer_styles_set_stroke_per_layer() {
+        use super::edit::{apply, undo, EditCommand};
+
+        let mut face = WatchFace::new(30.0).unwrap();
+        let draperie = || DraperieLayer::new(DraperieConfig::new(6, 12.0)).unwrap();
+        face.add_draperie_layer_styled(draperie(), LayerStyle::new("#8b0000", 0.1, 0.5))
+            .unwrap();
+        face.add_draperie_layer(draperie());
+        face.add_paon_layer(PaonLayer::new(PaonConfig::new(8, 12.0)).unwrap());
+        face.set_layer_style(LayerKind::Paon, 0, LayerStyle::new("navy", 0.05, 1.0))
+            .unwrap();
+        face.generate();
+
+        // (stroke, stroke-width, opacity) of every path, in drawing order
+        let strokes = |face: &WatchFace| -> Vec<(String, String, Option<String>)> {
+            let svg = face.svg_document(None, false).to_string();
+            let attr = |path: &str, name: &str| {
+                path.split(&format!(" {}=\"", name))
+                    .nth(1)
+                    .map(|rest| rest[..rest.find('"').unwrap()].to_string())
+            };
+            svg.split("<path")
+                .skip(1)
+                .map(|path| {
+                    (
+                        attr(path, "stroke").unwrap(),
+                        attr(path, "stroke-width").unwrap(),
+                        attr(path, "opacity"),
+                    )
+                })
+                .collect()
+        };
+        let expected = |styled: &str, width: &str, opacity: Option<&str>| {
+            (
+                styled.to_string(),
+                width.to_string(),
+                opacity.map(str::to_string),
+            )
+        };
+        let lines = strokes(&face);
+        assert_eq!(lines.len(), 6 + 6 + 8);
+        assert!(lines[..6]
+            .iter()
+            .all(|l| *l == expected("#8b0000", "0.1", Some("0.5"))));
+        assert!(lines[6..12]
+            .iter()
+            .all(|l| *l == expected("#1a1a1a", "0.03", None)));
+        assert!(lines[12..]
+            .iter()
+            .all(|l| *l == expected("navy", "0.05", None)));
+
+        // Styles survive a clone and scale with the design unit
+        assert_eq!(strokes(&face.clone()), lines);
+        let mut inches = face.clone();
+        inches.convert_units(Unit::Inch);
+        let width: f64 = strokes(&inches)[0].1.parse().unwrap();
+        assert!((width - 0.1 / 25.4).abs() < 1e-9);
+
+        // An opacity envelope is multiplied by the style's opacity
+        face.set_opacity_envelope(LayerKind::Draperie, 0, Envelope::constant(0.5))
+            .unwrap();
+        assert!(strokes(&face)[..6]
+            .iter()
+            .all(|l| l.2.as_deref() == Some("0.25")));
+        face.clear_opacity_envelopes();
+
+        // Removing a layer shifts the styles after it, and undo restores them
+        let token = apply(
+            &mut face,
+            &EditCommand::RemoveLayer {
+                kind: LayerKind::Draperie,
+                index: 0,
+            },
+        )
+        .unwrap();
+        assert!(face.layer_style(LayerKind::Draperie, 0).is_none());
+        undo(&mut face, token).unwrap();
+        assert_eq!(
+            face.layer_style(LayerKind::Draperie, 0)
+                .unwrap()
+                .stroke_color,
+            "#8b0000"
+        );
+
+        assert!(face
+            .set_layer_style(LayerKind::Draperie, 1, LayerStyle::new("", 0.1, 1.0))
+            .is_err());
+        assert!(face
+            .set_layer_style(LayerKind::Draperie, 1, LayerStyle::new("red", 0.0, 1.0))
+            .is_err());
+        assert!(face
+            .set_layer_style(LayerKind::Draperie, 1, LayerStyle::new("red", 0.1, 1.5))
+            .is_err());
+        assert!(face
+            .set_layer_style(LayerKind::Spirograph, 0, LayerStyle::default())
+            .is_err());
+        assert!(face
+            .add_paon_layer_styled(
+                PaonLayer::new(PaonConfig::new(8, 12.0)).unwrap(),
+                LayerStyle::new("red", -1.0, 1.0)
+            )
+            .is_err());
+        assert_eq!(face.guilloche.kind_count(LayerKind::Paon), 1);
+
+        face.clear_layer_styles();
+        assert!(strokes(&face)
+            .iter()
+            .all(|l| *l == expected("#1a1a1a", "0.03", None)));
+    }
+
+    #[test]
+    fn test_decorate_cells_marks_every_other_hobnail() {
+        use super::edit::{apply, undo, EditCommand};
+
+        let mut face = WatchFace::new(30.0).unwrap();
+        let cdp = ClousDeParisLayer::new(ClousDeParisConfig::new(2.0, 12.0)).unwrap();
+        let cells = cdp.cell_grid();
+        face.add_clous_de_paris_layer(cdp);
+        face.generate();
+
+        let gem = CellDecoration::Circle {
+            radius: 0.4,
+            fill_color: "#b9f2ff".to_string(),
+        };
+        assert!(face.decorate_cells(1, 2, gem.clone()).is_err());
+        assert!(face.decorate_cells(0, 0, gem.clone()).is_err());
+        let bad = CellDecoration::Circle {
+            radius: -1.0,
+            fill_color: "#b9f2ff".to_string(),
+        };
+        assert!(face.decorate_cells(0, 2, bad).is_err());
+        face.decorate_cells(0, 2, gem).unwrap();
+        face.decorate_cells(
+            0,
+            1,
+            CellDecoration::Point {
+                color: "#ff0000".to_string(),
+            },
+        )
+        .unwrap();
+
+        let count = |face: &WatchFace, color: &str| {
+            face.svg_document(None, false)
+                .to_string()
+                .matches(&format!("fill=\"{}\"", color))
+                .count()
+        };
+        let checkerboard = cells.iter().filter(|(a, b)| (a + b) % 2 == 0).count();
+        assert!(checkerboard > 0 && checkerboard < cells.len());
+        assert_eq!(count(&face, "#b9f2ff"), checkerboard);
+        assert_eq!(count(&face, "#ff0000"), cells.len());
+
+        // Removing the layer takes its decorations along; undo restores them
+        let token = apply(
+            &mut face,
+            &EditCommand::RemoveLayer {
+                kind: LayerKind::ClousDeParis,
+                index: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(count(&face, "#b9f2ff"), 0);
+        undo(&mut face, token).unwrap();
+        face.regenerate_stale();
+        assert_eq!(count(&face, "#b9f2ff"), checkerboard);
+
+        face.clear_cell_decorations();
+        assert_eq!(count(&face, "#ff0000"), 0);
+    }
+
+    #[test]
+    fn test_lod_bundle_budgets() {
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_inner();
+        face.add_horizontal_layer(HorizontalSpirograph::new(38.0, 0.75, 0.6, 40, 360).unwrap());
+        face.add_diamant_layer(DiamantLayer::new(DiamantConfig::new(24, 19.0)).unwrap());
+        face.add_draperie_layer(DraperieLayer::new(DraperieConfig::default()).unwrap());
+        face.add_paon_layer(PaonLayer::new(PaonConfig::default()).unwrap());
+        face.add_clous_de_paris_layer(
+            ClousDeParisLayer::new(ClousDeParisConfig::default()).unwrap(),
+        );
+
+        let dir = std::env::temp_dir().join("test_lod_bundle");
+        std::fs::create_dir_all(&dir).unwrap();
+        let levels = [
+            LodSpec::new("thumb", 5_000),
+            LodSpec::new("preview", 50_000),
+            LodSpec::new("full", usize::MAX),
+        ];
+        let files = face.export_lod_bundle(&dir, "dial", &levels).unwrap();
+        // The source face is untouched
+        assert_eq!(face.point_count(), 0);
+
+        for (file, level) in files.iter().zip(&levels[..2]) {
+            assert!(
+                file.point_count <= level.point_budget
+                    && file.point_count as f64 >= 0.9 * level.point_budget as f64,
+                "{}: {} points for a budget of {}",
+                file.suffix,
+                file.point_count,
+                level.point_budget
+            );
+        }
+
+        // The full level is a plain export
+        let mut plain = face.clone();
+        plain.generate();
+        assert_eq!(files[2].point_count, plain.point_count());
+        let plain_path = dir.join("plain.svg");
+        plain.to_svg(plain_path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            std::fs::read(&files[2].path).unwrap(),
+            std::fs::read(&plain_path).unwrap()
+        );
+
+        let index = std::fs::read_to_string(dir.join("dial.json")).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(index.contains("\"file\": \"dial-thumb.svg\""));
+        assert!(index.contains(&format!("\"point_count\": {}", files[1].point_count)));
+
+        assert!(face
+            .export_lod_bundle(
+                &dir,
+                "dial",
+                &[LodSpec::new("a", 10), LodSpec::new("a", 20)]
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_json_round_trip_multi_layer_face() {
+        use crate::engrave_text::{ArcDirection, TextPosition};
+
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_inner_with_config(DialConfig {
+            fill_color: "#e8e4d8".to_string(),
+            ..DialConfig::default()
+        });
+        face.add_outer();
+        face.add_center_hole();
+        face.add_hole_at_clock(6, 0, 20.0, 2.5);
+        face.add_horizontal_layer(HorizontalSpirograph::new(28.0, 0.77, 24.0, 3, 360).unwrap());
+        face.add_flinque_layer(FlinqueLayer::new(28.0, FlinqueConfig::default()).unwrap());
+        face.add_draperie_layer(DraperieLayer::new(DraperieConfig::new(12, 12.0)).unwrap());
+        face.add_soleil_layer(
+            SoleilLayer::new(SoleilConfig::new(60, 2.0, 30.0).with_curvature(0.02)).unwrap(),
+        );
+        face.add_text(EngravedTextConfig::new(
+            "XII",
+            3.0,
+            TextPosition::arc_at_clock(12, 0, 32.0, ArcDirection::Clockwise),
+        ))
+        .unwrap();
+        face.add_imported_layer(vec![vec![Point2D::new(0.0, 0.0), Point2D::new(5.0, 5.0)]]);
+        face.set_layer_style(LayerKind::Soleil, 0, LayerStyle::new("#b8860b", 0.05, 0.8))
+            .unwrap();
+        face.set_opacity_envelope(LayerKind::Draperie, 0, Envelope::radial(0.0, 40.0, 40.0))
+            .unwrap();
+
+        let json = face.to_json().unwrap();
+        let mut copy = WatchFace::from_json(&json).unwrap();
+        assert_eq!(copy.to_json().unwrap(), json);
+        assert_eq!(copy.dial_config.as_ref().unwrap().fill_color, "#e8e4d8");
+        assert_eq!(copy.holes.len(), 2);
+
+        face.generate();
+        copy.generate();
+        assert_eq!(copy.lines(), face.lines());
+        assert_eq!(
+            copy.svg_document(None, false).to_string(),
+            face.svg_document(None, false).to_string()
+        );
+    }
+
+    #[test]
+    fn test_from_json_rejects_bad_input() {
+        assert!(WatchFace::from_json("{").is_err());
+
+        let face = WatchFace::new(30.0).unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&face.to_json().unwrap()).unwrap();
+        value["guilloche"]["radius"] = serde_json::json!(-1.0);
+        assert!(WatchFace::from_json(&value.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_from_json_checks_radius_by_the_saved_policy() {
+        let face = WatchFace::new_with_policy(60.0, RadiusPolicy::Unrestricted).unwrap();
+        let json = face.to_json().unwrap();
+        let copy = WatchFace::from_json(&json).unwrap();
+        assert_eq!(copy.settings().radius_policy, RadiusPolicy::Unrestricted);
+
+        // Designs saved before the policy existed keep the wristwatch range
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["settings"]
+            .as_object_mut()
+            .unwrap()
+            .remove("radius_policy");
+        assert!(WatchFace::from_json(&value.to_string()).is_err());
+    }
 }
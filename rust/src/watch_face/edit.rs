@@ -0,0 +1,556 @@
+//! Undoable edits to a `WatchFace`
+//!
+//! Each `EditCommand` is applied with `apply`, which returns an
+//! `UndoToken` holding only the state the command replaced: a removed
+//! layer, a previous center, the old dial. `undo` puts that state back.
+//! `EditHistory` keeps the undo and redo stacks for an editor.
+//!
+//! Edits never regenerate geometry. Added and re-centered layers are
+//! marked stale instead, and `WatchFace::regenerate_stale` generates just
+//! those when the caller needs up-to-date lines.
+//!
+//! # Example
+//! ```
+//! use turtles::{DiamantConfig, DiamantLayer, EditCommand, EditHistory, LayerKind, LayerSpec, WatchFace};
+//!
+//! let mut face = WatchFace::new(38.0).unwrap();
+//! let mut history = EditHistory::default();
+//! let diamant = DiamantLayer::new(DiamantConfig::new(12, 15.0)).unwrap();
+//! history.apply(&mut face, EditCommand::AddLayer(LayerSpec::from(diamant))).unwrap();
+//! history
+//!     .apply(&mut face, EditCommand::SetLayerCenter { kind: LayerKind::Diamant, index: 0, x: 2.0, y: 0.0 })
+//!     .unwrap();
+//! face.regenerate_stale();
+//!
+//! assert!(history.undo(&mut face).unwrap());
+//! assert_eq!(face.guilloche.stale_layers(), &[(LayerKind::Diamant, 0)]);
+//! ```
+
+use std::collections::VecDeque;
+
+use super::{BezelConfig, CellDecoration, DialConfig, HoleConfig, LayerStyle, WatchFace};
+use crate::common::{Envelope, SpirographError, StoredLayer};
+use crate::guilloche::{IndexShift, LayerSpec};
+use crate::post::LayerKind;
+
+/// One mutation of a watch face
+///
+/// Layers are addressed like post-processing callbacks and opacity
+/// envelopes see them: by kind and position among layers of that kind.
+#[derive(Debug, Clone)]
+pub enum EditCommand {
+    /// Append a layer after the others of its kind
+    AddLayer(LayerSpec),
+    /// Remove a layer
+    RemoveLayer { kind: LayerKind, index: usize },
+    /// Move a layer to position `to`, shifting the layers in between
+    MoveLayer {
+        kind: LayerKind,
+        from: usize,
+        to: usize,
+    },
+    /// Move a layer's center; text and chapter rings have none
+    SetLayerCenter {
+        kind: LayerKind,
+        index: usize,
+        x: f64,
+        y: f64,
+    },
+    /// Show the dial circle with this styling, or hide it
+    SetDial(Option<DialConfig>),
+    /// Show the bezel ring with this styling, or hide it
+    SetBezel(Option<BezelConfig>),
+    /// Add a hole after the existing ones
+    AddHole(HoleConfig),
+}
+
+/// What `undo` needs to reverse one applied `EditCommand`
+#[derive(Debug, Clone)]
+pub struct UndoToken {
+    inverse: Inverse,
+}
+
+/// The minimal prior state behind an `UndoToken`
+#[derive(Debug, Clone)]
+enum Inverse {
+    Remove {
+        kind: LayerKind,
+        index: usize,
+    },
+    Reinsert {
+        index: usize,
+        layer: Box<dyn StoredLayer>,
+        was_stale: bool,
+        envelope: Option<Envelope>,
+        style: Option<LayerStyle>,
+        decorations: Vec<(usize, CellDecoration)>,
+    },
+    Move {
+        kind: LayerKind,
+        from: usize,
+        to: usize,
+    },
+    Recenter {
+        kind: LayerKind,
+        index: usize,
+        x: f64,
+        y: f64,
+    },
+    Dial(Option<DialConfig>),
+    Bezel(Option<BezelConfig>),
+    RemoveHole(usize),
+}
+
+/// Apply `command` to `face`, returning the token that reverses it
+///
+/// Nothing is regenerated; see the module docs.
+pub fn apply(face: &mut WatchFace, command: &EditCommand) -> Result<UndoToken, SpirographError> {
+    let inverse = match command {
+        EditCommand::AddLayer(spec) => {
+            let (kind, index) = face.guilloche.insert_layer(spec.0.clone(), usize::MAX);
+            face.guilloche.mark_stale(kind, index);
+            Inverse::Remove { kind, index }
+        }
+        EditCommand::RemoveLayer { kind, index } => {
+            let (layer, was_stale) = face.guilloche.remove_layer(*kind, *index)?;
+            let decorations = take_decorations(face, *kind, *index);
+            let style = take_style(face, *kind, *index);
+            let envelope = take_envelope(face, *kind, *index);
+            Inverse::Reinsert {
+                index: *index,
+                layer,
+                was_stale,
+                envelope,
+                style,
+                decorations,
+            }
+        }
+        EditCommand::MoveLayer { kind, from, to } => {
+            move_layer(face, *kind, *from, *to)?;
+            Inverse::Move {
+                kind: *kind,
+                from: *to,
+                to: *from,
+            }
+        }
+        EditCommand::SetLayerCenter { kind, index, x, y } => {
+            let (old_x, old_y) = face.guilloche.layer_center(*kind, *index)?.ok_or_else(|| {
+                SpirographError::InvalidParameter(format!("{} layers have no center", kind.name()))
+            })?;
+            face.guilloche.set_layer_center(*kind, *index, *x, *y)?;
+            Inverse::Recenter {
+                kind: *kind,
+                index: *index,
+                x: old_x,
+                y: old_y,
+            }
+        }
+        EditCommand::SetDial(config) => {
+            Inverse::Dial(std::mem::replace(&mut face.dial_config, config.clone()))
+        }
+        EditCommand::SetBezel(config) => {
+            Inverse::Bezel(std::mem::replace(&mut face.bezel_config, config.clone()))
+        }
+        EditCommand::AddHole(config) => {
+            face.holes.push(config.clone());
+            Inverse::RemoveHole(face.holes.len() - 1)
+        }
+    };
+    Ok(UndoToken { inverse })
+}
+
+/// Reverse the command `token` came from
+///
+/// Fails if the face has since changed so the state no longer fits, e.g.
+/// the layer to remove again is gone.
+pub fn undo(face: &mut WatchFace, token: UndoToken) -> Result<(), SpirographError> {
+    match token.inverse {
+        Inverse::Remove { kind, index } => {
+            face.guilloche.remove_layer(kind, index)?;
+            take_decorations(face, kind, index);
+            take_style(face, kind, index);
+            take_envelope(face, kind, index);
+        }
+        Inverse::Reinsert {
+            index,
+            layer,
+            was_stale,
+            envelope,
+            style,
+            decorations,
+        } => {
+            let (kind, index) = face.guilloche.insert_layer(layer, index);
+            shift_envelopes(face, kind, IndexShift::Inserted(index));
+            if let Some(envelope) = envelope {
+                face.opacity_envelopes.push((kind, index, envelope));
+            }
+            if let Some(style) = style {
+                face.layer_styles.push((kind, index, style));
+            }
+            for (every_nth, decoration) in decorations {
+                face.cell_decorations.push((index, every_nth, decoration));
+            }
+            if was_stale {
+                face.guilloche.mark_stale(kind, index);
+            }
+        }
+        Inverse::Move { kind, from, to } => move_layer(face, kind, from, to)?,
+        Inverse::Recenter { kind, index, x, y } => {
+            face.guilloche.set_layer_center(kind, index, x, y)?
+        }
+        Inverse::Dial(config) => face.dial_config = config,
+        Inverse::Bezel(config) => face.bezel_config = config,
+        Inverse::RemoveHole(index) => {
+            if index >= face.holes.len() {
+                return Err(SpirographError::InvalidParameter(format!(
+                    "no hole at index {}",
+                    index
+                )));
+            }
+            face.holes.remove(index);
+        }
+    }
+    Ok(())
+}
+
+/// Move a layer together with its opacity envelope, style and cell
+/// decorations
+fn move_layer(
+    face: &mut WatchFace,
+    kind: LayerKind,
+    from: usize,
+    to: usize,
+) -> Result<(), SpirographError> {
+    face.guilloche.move_layer(kind, from, to)?;
+    let decorations = take_decorations(face, kind, from);
+    let style = take_style(face, kind, from);
+    let envelope = take_envelope(face, kind, from);
+    shift_envelopes(face, kind, IndexShift::Inserted(to));
+    if let Some(envelope) = envelope {
+        face.opacity_envelopes.push((kind, to, envelope));
+    }
+    if let Some(style) = style {
+        face.layer_styles.push((kind, to, style));
+    }
+    for (every_nth, decoration) in decorations {
+        face.cell_decorations.push((to, every_nth, decoration));
+    }
+    Ok(())
+}
+
+/// Remove the cell decorations of a layer that is going away or moving
+///
+/// Does not renumber the other layers' decorations; `take_envelope`
+/// does that for both.
+fn take_decorations(
+    face: &mut WatchFace,
+    kind: LayerKind,
+    index: usize,
+) -> Vec<(usize, CellDecoration)> {
+    if kind != LayerKind::ClousDeParis {
+        return Vec::new();
+    }
+    let mut taken = Vec::new();
+    face.cell_decorations.retain(|(i, every_nth, decoration)| {
+        if *i == index {
+            taken.push((*every_nth, decoration.clone()));
+            false
+        } else {
+            true
+        }
+    });
+    taken
+}
+
+/// Remove the style of a layer that is going away or moving
+///
+/// Does not renumber the other layers' styles; `take_envelope` does that.
+fn take_style(face: &mut WatchFace, kind: LayerKind, index: usize) -> Option<LayerStyle> {
+    let position = face
+        .layer_styles
+        .iter()
+        .position(|(k, i, _)| (*k, *i) == (kind, index));
+    position.map(|p| face.layer_styles.remove(p).2)
+}
+
+/// Remove the opacity envelope of a layer that is going away, renumbering
+/// the envelopes (and styles) of the layers after it
+fn take_envelope(face: &mut WatchFace, kind: LayerKind, index: usize) -> Option<Envelope> {
+    let position = face
+        .opacity_envelopes
+        .iter()
+        .position(|(k, i, _)| (*k, *i) == (kind, index));
+    let envelope = position.map(|p| face.opacity_envelopes.remove(p).2);
+    shift_envelopes(face, kind, IndexShift::Removed(index));
+    envelope
+}
+
+/// Renumber the opacity envelopes, styles and (for clous de Paris) cell
+/// decorations of `kind` after an insertion or removal
+fn shift_envelopes(face: &mut WatchFace, kind: LayerKind, shift: IndexShift) {
+    shift_entries(&mut face.opacity_envelopes, kind, shift);
+    shift_entries(&mut face.layer_styles, kind, shift);
+    if kind == LayerKind::ClousDeParis {
+        face.cell_decorations
+            .retain_mut(|(i, _, _)| match shift.apply(*i) {
+                Some(new_index) => {
+                    *i = new_index;
+                    true
+                }
+                None => false,
+            });
+    }
+}
+
+/// Renumber per-layer entries of `kind`, dropping the removed layer's
+fn shift_entries<T>(entries: &mut Vec<(LayerKind, usize, T)>, kind: LayerKind, shift: IndexShift) {
+    entries.retain_mut(|(k, i, _)| {
+        if *k != kind {
+            return true;
+        }
+        match shift.apply(*i) {
+            Some(new_index) => {
+                *i = new_index;
+                true
+            }
+            None => false,
+        }
+    });
+}
+
+/// Undo and redo stacks for an editing session
+///
+/// Holds at most `depth` undo steps, dropping the oldest. Applying a new
+/// command clears the redo stack.
+#[derive(Debug, Clone)]
+pub struct EditHistory {
+    depth: usize,
+    undo_stack: VecDeque<(EditCommand, UndoToken)>,
+    redo_stack: Vec<EditCommand>,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl EditHistory {
+    /// History keeping at most `depth` undo steps
+    pub fn new(depth: usize) -> Self {
+        EditHistory {
+            depth,
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+
+    /// Most undo steps kept
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Change the most undo steps kept, dropping the oldest beyond it
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        while self.undo_stack.len() > depth {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    /// Apply `command` to `face` and record it
+    pub fn apply(
+        &mut self,
+        face: &mut WatchFace,
+        command: EditCommand,
+    ) -> Result<(), SpirographError> {
+        let token = apply(face, &command)?;
+        self.redo_stack.clear();
+        self.push_undo(command, token);
+        Ok(())
+    }
+
+    /// Undo the last applied command; false if there was none
+    ///
+    /// On error the step stays on the undo stack.
+    pub fn undo(&mut self, face: &mut WatchFace) -> Result<bool, SpirographError> {
+        let Some((command, token)) = self.undo_stack.pop_back() else {
+            return Ok(false);
+        };
+        if let Err(e) = undo(face, token.clone()) {
+            self.undo_stack.push_back((command, token));
+            return Err(e);
+        }
+        self.redo_stack.push(command);
+        Ok(true)
+    }
+
+    /// Apply the last undone command again; false if there was none
+    pub fn redo(&mut self, face: &mut WatchFace) -> Result<bool, SpirographError> {
+        let Some(command) = self.redo_stack.pop() else {
+            return Ok(false);
+        };
+        match apply(face, &command) {
+            Ok(token) => {
+                self.push_undo(command, token);
+                Ok(true)
+            }
+            Err(e) => {
+                self.redo_stack.push(command);
+                Err(e)
+            }
+        }
+    }
+
+    /// Number of steps that can be undone
+    pub fn undo_len(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    /// Number of steps that can be redone
+    pub fn redo_len(&self) -> usize {
+        self.redo_stack.len()
+    }
+
+    /// Forget every recorded step
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    fn push_undo(&mut self, command: EditCommand, token: UndoToken) {
+        self.undo_stack.push_back((command, token));
+        while self.undo_stack.len() > self.depth {
+            self.undo_stack.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diamant::{DiamantConfig, DiamantLayer};
+    use crate::limacon::{LimaconConfig, LimaconLayer};
+
+    fn face() -> WatchFace {
+        let mut face = WatchFace::new(38.0).unwrap();
+        face.add_diamant_layer(DiamantLayer::new(DiamantConfig::new(12, 15.0)).unwrap());
+        face.add_limacon_layer(LimaconLayer::new(LimaconConfig::new(12, 10.0, 8.0)).unwrap());
+        face.add_limacon_layer(
+            LimaconLayer::new_with_center(LimaconConfig::new(8, 6.0, 4.0), 3.0, -2.0).unwrap(),
+        );
+        face.generate();
+        face
+    }
+
+    /// Kind and center of each layer, in order
+    fn layer_list(face: &WatchFace) -> Vec<(LayerKind, Option<(f64, f64)>)> {
+        [LayerKind::Diamant, LayerKind::Limacon]
+            .into_iter()
+            .flat_map(|kind| {
+                (0..face.guilloche.kind_count(kind))
+                    .map(move |i| (kind, face.guilloche.layer_center(kind, i).unwrap()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_add_move_undo_undo_restores_face() {
+        let mut face = face();
+        let layers = layer_list(&face);
+        let lines = face.lines();
+
+        let added = LimaconLayer::new(LimaconConfig::new(6, 12.0, 3.0)).unwrap();
+        let add = apply(&mut face, &EditCommand::AddLayer(LayerSpec::from(added))).unwrap();
+        assert_eq!(face.guilloche.stale_layers(), &[(LayerKind::Limacon, 2)]);
+        let shift = apply(
+            &mut face,
+            &EditCommand::MoveLayer {
+                kind: LayerKind::Limacon,
+                from: 2,
+                to: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(face.guilloche.stale_layers(), &[(LayerKind::Limacon, 0)]);
+        assert_eq!(face.regenerate_stale(), 1);
+        assert_ne!(face.lines(), lines);
+
+        undo(&mut face, shift).unwrap();
+        undo(&mut face, add).unwrap();
+        assert_eq!(layer_list(&face), layers);
+        assert_eq!(face.lines(), lines);
+        assert!(face.guilloche.stale_layers().is_empty());
+    }
+
+    #[test]
+    fn test_recenter_regenerates_only_that_layer() {
+        let mut face = face();
+        let lines = face.lines();
+        let command = EditCommand::SetLayerCenter {
+            kind: LayerKind::Limacon,
+            index: 1,
+            x: 0.0,
+            y: 0.0,
+        };
+        let token = apply(&mut face, &command).unwrap();
+        assert_eq!(face.regenerate_stale(), 1);
+
+        let mut expected = face.clone();
+        expected.generate();
+        assert_eq!(face.lines(), expected.lines());
+
+        undo(&mut face, token).unwrap();
+        face.regenerate_stale();
+        assert_eq!(face.lines(), lines);
+
+        let ring = EditCommand::SetLayerCenter {
+            kind: LayerKind::Text,
+            index: 0,
+            x: 0.0,
+            y: 0.0,
+        };
+        assert!(apply(&mut face, &ring).is_err());
+    }
+
+    #[test]
+    fn test_history_redo_and_depth() {
+        let mut face = face();
+        let mut history = EditHistory::new(2);
+        for radius in [0.5, 0.6, 0.7] {
+            let hole = HoleConfig {
+                radius,
+                ..HoleConfig::default()
+            };
+            history
+                .apply(&mut face, EditCommand::AddHole(hole))
+                .unwrap();
+        }
+        assert_eq!(history.undo_len(), 2);
+        assert_eq!(face.holes.len(), 3);
+
+        assert!(history.undo(&mut face).unwrap());
+        assert!(history.undo(&mut face).unwrap());
+        assert!(!history.undo(&mut face).unwrap());
+        assert_eq!(face.holes.len(), 1);
+
+        assert!(history.redo(&mut face).unwrap());
+        assert_eq!(face.holes.len(), 2);
+        history
+            .apply(&mut face, EditCommand::SetDial(Some(DialConfig::default())))
+            .unwrap();
+        assert_eq!(history.redo_len(), 0);
+        assert!(history.undo(&mut face).unwrap());
+        assert!(face.dial_config.is_none());
+
+        let remove = EditCommand::RemoveLayer {
+            kind: LayerKind::Diamant,
+            index: 0,
+        };
+        let lines = face.lines();
+        history.apply(&mut face, remove).unwrap();
+        assert_eq!(face.guilloche.kind_count(LayerKind::Diamant), 0);
+        assert!(history.undo(&mut face).unwrap());
+        assert_eq!(face.lines(), lines);
+    }
+}
@@ -0,0 +1,250 @@
+//! Watch hands drawn over a finished dial for previews
+//!
+//! Each hand is built pointing along +x from the dial center and turned to
+//! its clock position with the same angles as [`clock_to_cartesian`]. The
+//! outlines are closed polylines, the last point repeating the first.
+
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{clock_to_cartesian, Point2D, SpirographError};
+
+/// Points on a full circle of a Breguet hand's ring
+const RING_SEGMENTS: usize = 48;
+
+/// How far a hand reaches back past the center, as a fraction of its length
+const TAIL_RATIO: f64 = 0.12;
+
+/// Outline of a watch hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HandStyle {
+    /// Straight bar of constant width
+    Baton,
+    /// Faceted lance, widest near the center and tapering to a point
+    Dauphine,
+    /// Bar with an open ring near the tip, then a short point
+    Breguet,
+}
+
+impl HandStyle {
+    /// The style named `name` ("baton", "dauphine" or "breguet")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "baton" => Some(HandStyle::Baton),
+            "dauphine" => Some(HandStyle::Dauphine),
+            "breguet" => Some(HandStyle::Breguet),
+            _ => None,
+        }
+    }
+}
+
+/// Shape, size and color of one hand
+///
+/// The length is `length_ratio` of the face radius; `width` is the widest
+/// part of the hand in the design unit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandConfig {
+    pub style: HandStyle,
+    pub length_ratio: f64,
+    pub width: f64,
+    pub color: String,
+}
+
+impl HandConfig {
+    pub fn new(style: HandStyle, length_ratio: f64, width: f64, color: &str) -> Self {
+        HandConfig {
+            style,
+            length_ratio,
+            width,
+            color: color.to_string(),
+        }
+    }
+
+    /// Validate the length ratio, width and color
+    pub fn validate(&self) -> Result<(), SpirographError> {
+        if !(self.length_ratio > 0.0 && self.length_ratio <= 1.0) {
+            return Err(SpirographError::InvalidParameter(
+                "hand length ratio must be in (0, 1]".to_string(),
+            ));
+        }
+        if !(self.width.is_finite() && self.width > 0.0) {
+            return Err(SpirographError::InvalidParameter(
+                "hand width must be positive and finite".to_string(),
+            ));
+        }
+        if self.color.trim().is_empty() {
+            return Err(SpirographError::InvalidParameter(
+                "hand color must not be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Closed outlines of the hand pointing along +x, `length` long
+    ///
+    /// A Breguet hand has two: the bar with its ring, then the hole in the ring.
+    fn outlines(&self, length: f64) -> Result<Vec<Vec<Point2D>>, SpirographError> {
+        let half = self.width / 2.0;
+        let tail = -TAIL_RATIO * length;
+        let outlines = match self.style {
+            HandStyle::Baton => vec![vec![
+                Point2D::new(tail, -half),
+                Point2D::new(length, -half),
+                Point2D::new(length, half),
+                Point2D::new(tail, half),
+            ]],
+            HandStyle::Dauphine => vec![vec![
+                Point2D::new(tail, 0.0),
+                Point2D::new(0.15 * length, -half),
+                Point2D::new(length, 0.0),
+                Point2D::new(0.15 * length, half),
+            ]],
+            HandStyle::Breguet => breguet_outlines(length, tail, self.width)?,
+        };
+        Ok(outlines
+            .into_iter()
+            .map(|mut outline| {
+                outline.push(outline[0]);
+                outline
+            })
+            .collect())
+    }
+}
+
+/// The bar of a Breguet hand joined to the outside of its ring, and the
+/// ring's hole, neither closed yet
+///
+/// The ring's outer radius is twice the bar width and its hole leaves a
+/// band of 0.6 widths; it is centred at 72% of the length so a point
+/// remains beyond it.
+fn breguet_outlines(
+    length: f64,
+    tail: f64,
+    width: f64,
+) -> Result<Vec<Vec<Point2D>>, SpirographError> {
+    let half = width / 2.0;
+    let ring = 2.0 * width;
+    let center = 0.72 * length;
+    if center + ring >= length {
+        return Err(SpirographError::InvalidParameter(
+            "Breguet hand is too wide for its length".to_string(),
+        ));
+    }
+
+    // Where the bar's edges meet the ring, at angles ±join from the +x axis
+    let join = (half / ring).asin();
+    let arc = |from: f64, to: f64| {
+        let steps = (((to - from) / (2.0 * PI)) * RING_SEGMENTS as f64).ceil() as usize;
+        (0..=steps).map(move |i| {
+            let angle = from + (to - from) * i as f64 / steps as f64;
+            Point2D::new(center + ring * angle.cos(), ring * angle.sin())
+        })
+    };
+
+    let mut outline = vec![Point2D::new(tail, -half)];
+    outline.extend(arc(-PI + join, -join));
+    outline.push(Point2D::new(length, 0.0));
+    outline.extend(arc(join, PI - join));
+    outline.push(Point2D::new(tail, half));
+
+    let hole = ring - 0.6 * width;
+    let hole_outline = (0..RING_SEGMENTS)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f64 / RING_SEGMENTS as f64;
+            Point2D::new(center + hole * angle.cos(), hole * angle.sin())
+        })
+        .collect();
+    Ok(vec![outline, hole_outline])
+}
+
+/// The hour, minute and second hands of a watch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandsSet {
+    pub hour: HandConfig,
+    pub minute: HandConfig,
+    /// Drawn only when a second is given to `WatchFace::add_hands`
+    pub second: HandConfig,
+}
+
+impl Default for HandsSet {
+    /// Black dauphine hour and minute hands and a thin red baton seconds hand
+    fn default() -> Self {
+        HandsSet {
+            hour: HandConfig::new(HandStyle::Dauphine, 0.5, 1.6, "#1a1a1a"),
+            minute: HandConfig::new(HandStyle::Dauphine, 0.75, 1.2, "#1a1a1a"),
+            second: HandConfig::new(HandStyle::Baton, 0.85, 0.25, "#b22222"),
+        }
+    }
+}
+
+/// A hand's name ("hour", "minute", "second") and config with its outlines
+pub(crate) type PlacedHand<'a> = (&'static str, &'a HandConfig, Vec<Vec<Point2D>>);
+
+/// Hands set to a time, as stored on a watch face
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Hands {
+    pub hour: u32,
+    pub minute: u32,
+    pub second: Option<u32>,
+    pub configs: HandsSet,
+}
+
+impl Hands {
+    /// Check the time and that every drawn hand can be built on a face of `radius`
+    pub fn validate(&self, radius: f64) -> Result<(), SpirographError> {
+        if self.hour >= 24 {
+            return Err(SpirographError::InvalidParameter(
+                "hour must be 0-23".to_string(),
+            ));
+        }
+        if self.minute >= 60 || self.second.is_some_and(|second| second >= 60) {
+            return Err(SpirographError::InvalidParameter(
+                "minute and second must be 0-59".to_string(),
+            ));
+        }
+        self.placed(radius).map(|_| ())
+    }
+
+    /// Each drawn hand with its closed outlines turned to the time, on a
+    /// face of `radius`
+    pub fn placed(&self, radius: f64) -> Result<Vec<PlacedHand<'_>>, SpirographError> {
+        // Minute m of the hour sits where the hour hand is at m/5 hours
+        let on_minute_track = |minute: u32| clock_to_cartesian(minute / 5, minute % 5 * 12, 1.0);
+        let mut hands = vec![
+            (
+                "hour",
+                &self.configs.hour,
+                clock_to_cartesian(self.hour, self.minute, 1.0),
+            ),
+            ("minute", &self.configs.minute, on_minute_track(self.minute)),
+        ];
+        if let Some(second) = self.second {
+            hands.push(("second", &self.configs.second, on_minute_track(second)));
+        }
+
+        hands
+            .into_iter()
+            .map(|(name, config, (dx, dy))| {
+                config.validate()?;
+                let outlines = config
+                    .outlines(config.length_ratio * radius)?
+                    .into_iter()
+                    .map(|outline| {
+                        outline
+                            .into_iter()
+                            .map(|p| Point2D::new(p.x * dx - p.y * dy, p.x * dy + p.y * dx))
+                            .collect()
+                    })
+                    .collect();
+                Ok((name, config, outlines))
+            })
+            .collect()
+    }
+
+    pub fn scale_lengths(&mut self, factor: f64) {
+        self.configs.hour.width *= factor;
+        self.configs.minute.width *= factor;
+        self.configs.second.width *= factor;
+    }
+}
@@ -1,38 +1,65 @@
 __version__ = "0.1.0"
 
+import json
+
 # Import all classes from the Rust extension
 from .turtles import (
+    ChapterRingLayer,
     ClousDeParisLayer,
+    CotesDeGeneveLayer,
     CubeLayer,
     CuttingBit,
     DiamantLayer,
     DraperieLayer,
+    EngravedTextLayer,
+    Envelope,
+    ExportConfig,
     FlinqueLayer,
+    GrainDeRizLayer,
     HuitEightLayer,
+    IndexMarkersLayer,
     LimaconLayer,
     PaonLayer,
+    PhyllotaxisLayer,
+    ProgressiveHandle,
     RoseEngineConfig,
     RoseEngineLathe,
     RoseEngineLatheRun,
     RosettePattern,
+    SoleilLayer,
+    TapisserieLayer,
+    VagueLayer,
     WatchFace as RustWatchFace,
 )
 
 __all__ = (
+    "ChapterRingLayer",
     "ClousDeParisLayer",
+    "CotesDeGeneveLayer",
     "CubeLayer",
     "CuttingBit",
     "DiamantLayer",
     "DraperieLayer",
+    "EngravedTextLayer",
+    "Envelope",
+    "ExportConfig",
     "FlinqueLayer",
+    "GrainDeRizLayer",
     "HuitEightLayer",
+    "IndexMarkersLayer",
     "LimaconLayer",
     "PaonLayer",
+    "PhyllotaxisLayer",
+    "ProgressiveHandle",
     "RoseEngineConfig",
     "RoseEngineLathe",
     "RoseEngineLatheRun",
     "RosettePattern",
+    "SoleilLayer",
+    "TapisserieLayer",
+    "VagueLayer",
     "WatchFace",
+    "random_design",
 )
 
 
@@ -43,19 +70,136 @@ class WatchFace:
     Python API for adding dial elements, textures, and exporting.
     """
 
-    def __init__(self, radius: float):
+    def __init__(self, radius: float, unit: str = "mm", radius_policy=None):
         """Create a new watch face generator.
 
         Args:
-            radius: The radius of the watch face in mm (must be 26-44mm).
+            radius: The radius of the watch face in ``unit`` (26-44mm unless
+                ``radius_policy`` allows otherwise).
+            unit: Design unit, "mm" or "in".
+            radius_policy: "watch" (the default 26-44mm), "unrestricted", or a
+                ``(min, max)`` range in mm.
+        """
+        self._watch_face = RustWatchFace(radius=radius, unit=unit, radius_policy=radius_policy)
+
+    @classmethod
+    def _wrap(cls, watch_face: RustWatchFace) -> "WatchFace":
+        face = cls.__new__(cls)
+        face._watch_face = watch_face
+        return face
+
+    @classmethod
+    def oval(cls, rx: float, ry: float, unit: str = "mm", radius_policy=None) -> "WatchFace":
+        """Create a watch face with an oval dial of semi-axes ``rx`` and ``ry``.
+
+        Layers are clipped to the oval, and the dial, bezel and solid exports
+        follow it. The larger semi-axis is the radius and is checked against
+        ``radius_policy`` as in the constructor.
+        """
+        return cls._wrap(RustWatchFace.oval(rx, ry, unit=unit, radius_policy=radius_policy))
+
+    @classmethod
+    def from_recipe(cls, recipe: dict) -> "WatchFace":
+        """Rebuild the face a ``random_design`` recipe describes (layers only; call ``generate()``)."""
+        return cls._wrap(RustWatchFace.from_recipe(json.dumps(recipe)))
+
+    @classmethod
+    def from_json(cls, json_str: str) -> "WatchFace":
+        """Read a face saved with ``to_json()`` (call ``generate()`` before exporting)."""
+        return cls._wrap(RustWatchFace.from_json(json_str))
+
+    def to_json(self) -> str:
+        """Save the whole design as JSON: dial, holes, layer configs, styles and envelopes.
+
+        Generated lines and post-process callbacks are not saved.
         """
-        self._watch_face = RustWatchFace(radius=radius)
+        return self._watch_face.to_json()
 
     @property
     def radius(self) -> float:
         """Get the radius of the watch face."""
         return self._watch_face.radius
 
+    @property
+    def semi_axes(self) -> tuple:
+        """Get the dial's semi-axes as (rx, ry); equal for a round dial."""
+        return self._watch_face.semi_axes
+
+    @property
+    def unit(self) -> str:
+        """Get the design unit ("mm" or "in")."""
+        return self._watch_face.unit
+
+    def convert_units(self, unit: str):
+        """Switch the design unit, rescaling all configs and generated lines."""
+        self._watch_face.convert_units(unit)
+
+    def set_max_points_per_path(self, max_points: int):
+        """Set the most points written to one SVG path; longer lines are split."""
+        self._watch_face.set_max_points_per_path(max_points)
+
+    def set_svg_layer_data(self, enabled: bool):
+        """Write each layer's configuration and geometry stats as data-*
+        attributes on its SVG group (ids like "flinque-0" are always set)."""
+        self._watch_face.set_svg_layer_data(enabled)
+
+    def set_svg_margin(self, margin: float):
+        """Set the space left around the content when sizing the SVG viewBox."""
+        self._watch_face.set_svg_margin(margin)
+
+    def set_fixed_viewbox(self, viewbox=None):
+        """Pin the SVG viewBox to (min_x, min_y, width, height); None fits the content."""
+        self._watch_face.set_fixed_viewbox(viewbox)
+
+    def set_reference_image(
+        self,
+        href: str,
+        width_mm: float,
+        opacity: float = 0.5,
+        rotation_deg: float = 0.0,
+        offset: tuple[float, float] = (0.0, 0.0),
+    ):
+        """Set a reference photo to line the design up with.
+
+        The href is written to the SVG as given. The image is fitted into a
+        square ``width_mm`` wide centred on the dial, rotated clockwise by
+        ``rotation_deg`` and shifted by ``offset`` (mm).
+        """
+        self._watch_face.set_reference_image(href, width_mm, opacity, rotation_deg, offset)
+
+    @property
+    def reference_image(self):
+        """The reference image as (href, width_mm, opacity, rotation_deg, offset), or None."""
+        return self._watch_face.reference_image
+
+    def clear_reference(self):
+        """Remove the reference image."""
+        self._watch_face.clear_reference()
+
+    def set_metadata(
+        self,
+        design_id: str,
+        params_hash: str = "",
+        license: str = "",
+        generator_version: str | None = None,
+        extra: dict[str, str] | None = None,
+    ):
+        """Set traceability metadata embedded in SVG, STEP and DXF exports.
+
+        ``generator_version`` defaults to this library's version. Empty
+        fields are left out of the exported files.
+        """
+        self._watch_face.set_metadata(design_id, params_hash, license, generator_version, extra)
+
+    @property
+    def metadata(self):
+        """The export metadata as a dict of its non-empty fields, or None."""
+        return self._watch_face.metadata
+
+    def clear_metadata(self):
+        """Stop embedding metadata in exports."""
+        self._watch_face.clear_metadata()
+
     def add_inner(self):
         """Add the inner dial circle."""
         self._watch_face.add_inner()
@@ -122,6 +266,207 @@ class WatchFace:
         """
         self._watch_face.add_hole_at_clock(hour, minute, radius, hole_radius)
 
+    def add_aperture(
+        self,
+        x: float,
+        y: float,
+        shape: str = "rect",
+        width: float = 6.0,
+        height: float = 4.0,
+        corner_radius: float = 0.0,
+        rotation: float = 0.0,
+        radius: float = 5.0,
+        stroke_color: str = "#2c2c2c",
+        stroke_width: float = 0.2,
+    ):
+        """Add a complication aperture, such as a date window or subdial opening.
+
+        Pattern lines stop at the aperture edge, its outline is drawn with its
+        own stroke, and STL and DXF exports cut it through the dial.
+
+        Args:
+            x: Center x coordinate.
+            y: Center y coordinate.
+            shape: "rect" or "circle".
+            width: Rectangle width.
+            height: Rectangle height.
+            corner_radius: Rectangle corner radius, at most half the smaller side.
+            rotation: Rectangle rotation in degrees, clockwise on screen.
+            radius: Circle radius.
+            stroke_color: Outline color.
+            stroke_width: Outline width.
+        """
+        self._watch_face.add_aperture(
+            x,
+            y,
+            shape=shape,
+            width=width,
+            height=height,
+            corner_radius=corner_radius,
+            rotation=rotation,
+            radius=radius,
+            stroke_color=stroke_color,
+            stroke_width=stroke_width,
+        )
+
+    def add_aperture_at_clock(
+        self,
+        hour: int,
+        minute: int,
+        distance: float,
+        shape: str = "rect",
+        width: float = 6.0,
+        height: float = 4.0,
+        corner_radius: float = 0.0,
+        rotation: float = 0.0,
+        radius: float = 5.0,
+        stroke_color: str = "#2c2c2c",
+        stroke_width: float = 0.2,
+    ):
+        """Add a complication aperture centred at a clock position.
+
+        Args:
+            hour: Hour position (1-12)
+            minute: Minute position (0-59)
+            distance: Distance of the aperture center from the dial center.
+            shape, width, height, corner_radius, rotation, radius, stroke_color,
+                stroke_width: As for `add_aperture`.
+        """
+        self._watch_face.add_aperture_at_clock(
+            hour,
+            minute,
+            distance,
+            shape=shape,
+            width=width,
+            height=height,
+            corner_radius=corner_radius,
+            rotation=rotation,
+            radius=radius,
+            stroke_color=stroke_color,
+            stroke_width=stroke_width,
+        )
+
+    def clear_apertures(self):
+        """Remove every complication aperture."""
+        self._watch_face.clear_apertures()
+
+    def add_subdial(
+        self,
+        x: float,
+        y: float,
+        radius: float,
+        face: "WatchFace",
+        mask: bool = True,
+        stroke_color: str = "#2c2c2c",
+        stroke_width: float = 0.2,
+    ):
+        """Nest another watch face as a subdial centred at (x, y).
+
+        The subdial's layers are drawn about its center and clipped to its
+        circle, which is ringed with its own stroke. Build ``face`` with
+        ``radius_policy="unrestricted"`` to give it a small radius. The
+        face is copied, so later changes to it do not reach the subdial.
+
+        Args:
+            x: Center x coordinate.
+            y: Center y coordinate.
+            radius: Subdial radius; the circle must lie on the dial.
+            face: The watch face drawn inside the subdial.
+            mask: Stop the main pattern at the subdial circle.
+            stroke_color: Ring color.
+            stroke_width: Ring width.
+        """
+        self._watch_face.add_subdial(
+            x,
+            y,
+            radius,
+            face._watch_face,
+            mask=mask,
+            stroke_color=stroke_color,
+            stroke_width=stroke_width,
+        )
+
+    def add_subdial_at_clock(
+        self,
+        hour: int,
+        minute: int,
+        distance: float,
+        radius: float,
+        face: "WatchFace",
+        mask: bool = True,
+        stroke_color: str = "#2c2c2c",
+        stroke_width: float = 0.2,
+    ):
+        """Nest another watch face as a subdial centred at a clock position.
+
+        Args:
+            hour: Hour position (1-12)
+            minute: Minute position (0-59)
+            distance: Distance of the subdial center from the dial center.
+            radius, face, mask, stroke_color, stroke_width: As for `add_subdial`.
+        """
+        self._watch_face.add_subdial_at_clock(
+            hour,
+            minute,
+            distance,
+            radius,
+            face._watch_face,
+            mask=mask,
+            stroke_color=stroke_color,
+            stroke_width=stroke_width,
+        )
+
+    @property
+    def subdial_count(self) -> int:
+        """Number of subdials."""
+        return self._watch_face.subdial_count
+
+    def clear_subdials(self):
+        """Remove every subdial."""
+        self._watch_face.clear_subdials()
+
+    def add_hands(
+        self,
+        hour: int,
+        minute: int,
+        second: int | None = None,
+        hour_hand: dict | None = None,
+        minute_hand: dict | None = None,
+        second_hand: dict | None = None,
+    ):
+        """Draw watch hands set to a time over the finished dial.
+
+        Args:
+            hour: Hour (0-23)
+            minute: Minute (0-59)
+            second: Second (0-59); the seconds hand is drawn only when given.
+            hour_hand, minute_hand, second_hand: Optional dicts overriding the
+                default hand's "style" ("baton", "dauphine" or "breguet"),
+                "length_ratio" of the face radius, "width" and "color".
+
+        Hands are left out of STL and STEP unless `set_hands_in_exports(True)`.
+        """
+        self._watch_face.add_hands(
+            hour,
+            minute,
+            second,
+            hour_hand=hour_hand,
+            minute_hand=minute_hand,
+            second_hand=second_hand,
+        )
+
+    def clear_hands(self):
+        """Remove the hands."""
+        self._watch_face.clear_hands()
+
+    def set_hands_in_exports(self, enabled: bool):
+        """Cut the hands into STL and add them as a STEP part."""
+        self._watch_face.set_hands_in_exports(enabled)
+
+    def get_hand_outlines(self) -> list[list[list[tuple[float, float]]]]:
+        """Closed outlines of each hand, hour then minute then second."""
+        return self._watch_face.get_hand_outlines()
+
     # Textures
     # TODO add_tapisserie
     # TODO add_sunburst
@@ -186,6 +531,7 @@ class WatchFace:
         wave_amplitude: float = 0.8,
         wave_frequency: float = 20.0,
         inner_radius_ratio: float = 0.05,
+        resolution: int | None = None,
     ):
         """Add a flinqué (engine-turned) pattern.
 
@@ -199,6 +545,7 @@ class WatchFace:
             wave_amplitude: Amplitude of the wave pattern.
             wave_frequency: Frequency of fine ripple texture.
             inner_radius_ratio: Inner radius as fraction of outer radius.
+            resolution: Points per ring. Defaults to 80 per petal.
         """
         if radius is None:
             radius = self.radius
@@ -212,6 +559,7 @@ class WatchFace:
             wave_amplitude=wave_amplitude,
             wave_frequency=wave_frequency,
             inner_radius_ratio=inner_radius_ratio,
+            resolution=resolution,
         )
 
     def add_flinque_layer(self, layer):
@@ -273,6 +621,7 @@ class WatchFace:
         minute: int = 0,
         distance: float = 0.0,
         resolution: int = 360,
+        loop_mode: str = "full",
     ):
         """Add a limaçon guilloché pattern.
 
@@ -287,6 +636,8 @@ class WatchFace:
             minute: Minute position for center (0-59).
             distance: Distance from center (0 = centered on watch face).
             resolution: Number of points per curve.
+            loop_mode: "full", "outer_only" to trim the inner loop when amplitude
+                exceeds base_radius, or "inner_only" to draw just the loop.
         """
         if base_radius is None:
             base_radius = self.radius
@@ -300,6 +651,7 @@ class WatchFace:
             minute=minute,
             distance=distance,
             resolution=resolution,
+            loop_mode=loop_mode,
         )
 
     def add_limacon_layer(self, layer):
@@ -436,6 +788,8 @@ class WatchFace:
         n_harmonics: int = 5,
         fan_angle: float = 3.0,
         vanishing_point: float = 0.2,
+        origin: str | float = "bottom",
+        asymmetry: float = 0.0,
     ):
         """Add a paon (peacock) guilloché pattern.
 
@@ -456,6 +810,10 @@ class WatchFace:
             n_harmonics: 0=pure sine (smooth arches), 1+=triangle-wave (sharper cusps).
             fan_angle: Total angular spread of the fan in radians (~1.4 = 80°).
             vanishing_point: VP distance below circle bottom (fraction of diameter).
+            origin: Side the fan radiates from: "bottom", "top", "left", "right",
+                or a turn from "bottom" in radians, clockwise on screen.
+            asymmetry: Skew of the line spacing, between -1 and 1; positive values
+                crowd the lines toward the right looking from the vanishing point.
         """
         if radius is None:
             radius = self.radius
@@ -472,6 +830,8 @@ class WatchFace:
             n_harmonics=n_harmonics,
             fan_angle=fan_angle,
             vanishing_point=vanishing_point,
+            origin=origin,
+            asymmetry=asymmetry,
         )
 
     def add_paon_layer(self, layer):
@@ -587,8 +947,539 @@ class WatchFace:
         """
         self._watch_face.add_cube_layer(layer)
 
+    def add_phyllotaxis(
+        self,
+        num_points: int = 800,
+        scale_c: float = 0.75,
+        radius: float | None = None,
+        style: str = "circle",
+        size: float = 0.25,
+        lobes: int = 6,
+        inner_exclusion_radius: float = 0.0,
+        edge: str = "clip",
+        hour: int = 12,
+        minute: int = 0,
+        distance: float = 0.0,
+        resolution: int = 48,
+    ):
+        """Add a phyllotaxis (sunflower spiral) pattern.
+
+        Cell n sits at r = scale_c * sqrt(n), theta = n * 137.507...°, the
+        golden-angle spiral of a sunflower head.
+
+        Args:
+            num_points: Number of cells on the spiral.
+            scale_c: Spiral scale in mm.
+            radius: Radius of the circular clipping region in mm. Defaults to watch face radius.
+            style: "circle", "tick" (radial tick) or "minilobe" (tiny rosette).
+            size: Circle radius, tick length or rosette radius in mm.
+            lobes: Lobes of each "minilobe" rosette.
+            inner_exclusion_radius: No cells are centered closer than this to the center.
+            edge: "clip" cells at the radius, or "drop" cells that cross it.
+            hour: Hour position for center (1-12, default 12 = centered).
+            minute: Minute position for center (0-59).
+            distance: Distance from center (0 = centered on watch face).
+            resolution: Points per circle or rosette loop.
+        """
+        if radius is None:
+            radius = self.radius
+        self._watch_face.add_phyllotaxis_at_clock(
+            hour=hour,
+            minute=minute,
+            distance=distance,
+            num_points=num_points,
+            scale_c=scale_c,
+            radius=radius,
+            style=style,
+            size=size,
+            lobes=lobes,
+            inner_exclusion_radius=inner_exclusion_radius,
+            edge=edge,
+            resolution=resolution,
+        )
+
+    def add_phyllotaxis_layer(self, layer):
+        """Add a pre-configured PhyllotaxisLayer to the watch face.
+
+        Args:
+            layer: A PhyllotaxisLayer instance.
+        """
+        self._watch_face.add_phyllotaxis_layer(layer)
+
+    def add_grain_de_riz(
+        self,
+        num_rows: int = 12,
+        grains_per_row: int = 48,
+        grain_length: float = 1.2,
+        grain_width: float = 0.5,
+        base_radius: float = 8.0,
+        radius_step: float = 1.0,
+        stagger: bool = True,
+        hour: int = 12,
+        minute: int = 0,
+        distance: float = 0.0,
+        resolution: int = 24,
+    ):
+        """Add a grain-de-riz (rice grain) pattern.
+
+        Small closed ovals are set end to end on concentric rows, each with
+        its long axis along the row.
+
+        Args:
+            num_rows: Number of concentric rows.
+            grains_per_row: Number of grains on every row.
+            grain_length: Length of each grain along its row in mm.
+            grain_width: Width of each grain across its row in mm.
+            base_radius: Radius of the innermost row in mm.
+            radius_step: Distance between neighbouring rows in mm.
+            stagger: Turn every other row by half a grain pitch.
+            hour: Hour position for center (1-12, default 12 = centered).
+            minute: Minute position for center (0-59).
+            distance: Distance from center (0 = centered on watch face).
+            resolution: Points around each grain.
+        """
+        self._watch_face.add_grain_de_riz_at_clock(
+            hour=hour,
+            minute=minute,
+            distance=distance,
+            num_rows=num_rows,
+            grains_per_row=grains_per_row,
+            grain_length=grain_length,
+            grain_width=grain_width,
+            base_radius=base_radius,
+            radius_step=radius_step,
+            resolution=resolution,
+            stagger=stagger,
+        )
+
+    def add_grain_de_riz_layer(self, layer):
+        """Add a pre-configured GrainDeRizLayer to the watch face.
+
+        Args:
+            layer: A GrainDeRizLayer instance.
+        """
+        self._watch_face.add_grain_de_riz_layer(layer)
+
+    def add_soleil(
+        self,
+        num_rays: int = 180,
+        inner_radius: float = 1.5,
+        outer_radius: float = 15.0,
+        curvature: float = 0.0,
+        taper: float = 0.0,
+        hour: int = 12,
+        minute: int = 0,
+        distance: float = 0.0,
+        resolution: int = 32,
+    ):
+        """Add a soleil (sunray) pattern.
+
+        Rays run out from the center and all stop exactly on the outer
+        radius. A curvature bends each ray along a circular arc; a taper
+        draws each ray as a thin closed wedge instead of a line.
+
+        Args:
+            num_rays: Number of rays around the dial.
+            inner_radius: Radius the rays start from in mm.
+            outer_radius: Radius the rays end on in mm.
+            curvature: Ray curvature in 1/mm, positive bends counter-clockwise
+                (0 = straight rays).
+            taper: Ray width at the outer radius in mm (0 = single lines).
+            hour: Hour position for center (1-12, default 12 = centered).
+            minute: Minute position for center (0-59).
+            distance: Distance from center (0 = centered on watch face).
+            resolution: Steps along each ray.
+        """
+        self._watch_face.add_soleil_at_clock(
+            hour=hour,
+            minute=minute,
+            distance=distance,
+            num_rays=num_rays,
+            inner_radius=inner_radius,
+            outer_radius=outer_radius,
+            curvature=curvature,
+            taper=taper,
+            resolution=resolution,
+        )
+
+    def add_soleil_layer(self, layer):
+        """Add a pre-configured SoleilLayer to the watch face.
+
+        Args:
+            layer: A SoleilLayer instance.
+        """
+        self._watch_face.add_soleil_layer(layer)
+
+    def add_cotes_de_geneve(
+        self,
+        stripe_width: float = 2.5,
+        num_stripes: int = None,
+        arc_sagitta: float = 0.5,
+        angle: float = 0.0,
+        radius: float = 15.0,
+        inner_radius: float = 0.0,
+        hour: int = 12,
+        minute: int = 0,
+        distance: float = 0.0,
+        resolution: int = 100,
+        lines_per_stripe: int = 6,
+    ):
+        """Add a Côtes de Genève (Geneva stripes) pattern.
+
+        Parallel stripes of gently arced lines fill the circle, or the
+        annulus outside inner_radius. Each stripe is edged by a boundary
+        line and holds a group of lines across its middle.
+
+        Args:
+            stripe_width: Width of each stripe in mm.
+            num_stripes: Number of stripes, overriding stripe_width
+                (None = from the width).
+            arc_sagitta: How far each line bows over the full diameter in mm
+                (0 = straight lines).
+            angle: Direction the stripes run in radians.
+            radius: Radius of the circular region in mm.
+            inner_radius: Radius of the bare center in mm (0 = full circle).
+            hour: Hour position for center (1-12, default 12 = centered).
+            minute: Minute position for center (0-59).
+            distance: Distance from center (0 = centered on watch face).
+            resolution: Steps along each line.
+            lines_per_stripe: Lines in each stripe between its boundaries.
+        """
+        self._watch_face.add_cotes_de_geneve_at_clock(
+            hour=hour,
+            minute=minute,
+            distance=distance,
+            stripe_width=stripe_width,
+            num_stripes=num_stripes,
+            arc_sagitta=arc_sagitta,
+            angle=angle,
+            radius=radius,
+            inner_radius=inner_radius,
+            resolution=resolution,
+            lines_per_stripe=lines_per_stripe,
+        )
+
+    def add_cotes_de_geneve_layer(self, layer):
+        """Add a pre-configured CotesDeGeneveLayer to the watch face.
+
+        Args:
+            layer: A CotesDeGeneveLayer instance.
+        """
+        self._watch_face.add_cotes_de_geneve_layer(layer)
+
+    def add_tapisserie(
+        self,
+        cell_size: float = 1.0,
+        channel_width: float = 0.3,
+        radius: float = 15.0,
+        rotation: float = 0.0,
+        truncate_corners: bool = False,
+        hour: int = 12,
+        minute: int = 0,
+        distance: float = 0.0,
+        resolution: int = 360,
+    ):
+        """Add a tapisserie (tapestry, or waffle) pattern.
+
+        A square grid of raised cells separated by straight channels fills
+        the circle, one cell on the center. Cells crossing the circle are
+        clipped to it, and the channel centerlines follow the cells.
+
+        Args:
+            cell_size: Width of each raised cell in mm.
+            channel_width: Width of the channels between cells in mm.
+            radius: Radius of the circular region in mm.
+            rotation: Rotation of the grid in radians.
+            truncate_corners: Cut the cell corners off, leaving octagons.
+            hour: Hour position for center (1-12, default 12 = centered).
+            minute: Minute position for center (0-59).
+            distance: Distance from center (0 = centered on watch face).
+            resolution: Steps per full turn where cells follow the circle.
+        """
+        self._watch_face.add_tapisserie_at_clock(
+            hour=hour,
+            minute=minute,
+            distance=distance,
+            cell_size=cell_size,
+            channel_width=channel_width,
+            radius=radius,
+            rotation=rotation,
+            resolution=resolution,
+            truncate_corners=truncate_corners,
+        )
+
+    def add_tapisserie_layer(self, layer):
+        """Add a pre-configured TapisserieLayer to the watch face.
+
+        Args:
+            layer: A TapisserieLayer instance.
+        """
+        self._watch_face.add_tapisserie_layer(layer)
+
+    def add_vague(
+        self,
+        num_lines: int = 40,
+        radius: float = 15.0,
+        wave_amplitude: float = 1.5,
+        wave_length: float = 10.0,
+        sweep_angle: float = 0.0,
+        group_size: int = 4,
+        group_phase_step: float = None,
+        hour: int = 12,
+        minute: int = 0,
+        distance: float = 0.0,
+        points_per_wave: int = 64,
+    ):
+        """Add a vague (wave, or flammes) pattern.
+
+        Parallel S-curves cross the circle and are clipped to it. Every
+        group_size neighbouring lines share a phase and never cross; each
+        group is shifted by group_phase_step against the one before.
+
+        Args:
+            num_lines: Number of wave lines across the circle.
+            radius: Radius of the circular region in mm.
+            wave_amplitude: Peak deviation of each wave from its track in mm.
+            wave_length: Length of one full wave in mm.
+            sweep_angle: Direction the waves travel in radians.
+            group_size: Consecutive lines sharing a phase.
+            group_phase_step: Phase shift between successive groups in radians
+                (default pi / 2).
+            hour: Hour position for center (1-12, default 12 = centered).
+            minute: Minute position for center (0-59).
+            distance: Distance from center (0 = centered on watch face).
+            points_per_wave: Points per wavelength.
+        """
+        import math
+
+        if group_phase_step is None:
+            group_phase_step = math.pi / 2.0
+        self._watch_face.add_vague_at_clock(
+            hour=hour,
+            minute=minute,
+            distance=distance,
+            num_lines=num_lines,
+            radius=radius,
+            wave_amplitude=wave_amplitude,
+            wave_length=wave_length,
+            sweep_angle=sweep_angle,
+            group_size=group_size,
+            group_phase_step=group_phase_step,
+            points_per_wave=points_per_wave,
+        )
+
+    def add_vague_layer(self, layer):
+        """Add a pre-configured VagueLayer to the watch face.
+
+        Args:
+            layer: A VagueLayer instance.
+        """
+        self._watch_face.add_vague_layer(layer)
+
+    def add_text(
+        self,
+        text: str,
+        height: float = 3.0,
+        radius: float | None = None,
+        hour: int = 12,
+        minute: int = 0,
+        direction: str = "clockwise",
+        origin: tuple[float, float] = (0.0, 0.0),
+        angle: float = 0.0,
+        spacing: float = 0.0,
+    ):
+        """Add engraved single-stroke text such as dial numerals.
+
+        Text follows an arc about the center when ``radius`` is given,
+        otherwise it runs straight from ``origin``.
+
+        Args:
+            text: Text to engrave (digits, letters, space and . , - + / : ').
+            height: Capital height in mm.
+            radius: Radius of the arc the baseline follows.
+            hour: Clock position the arc text is centered on (1-12).
+            minute: Minute of the clock position (0-59).
+            direction: "clockwise" (reads along the outside) or "counterclockwise".
+            origin: Baseline center of straight text.
+            angle: Direction of straight text in radians.
+            spacing: Extra gap between glyphs in mm.
+        """
+        self._watch_face.add_text(
+            text,
+            height=height,
+            radius=radius,
+            hour=hour,
+            minute=minute,
+            direction=direction,
+            origin=origin,
+            angle=angle,
+            spacing=spacing,
+        )
+
+    def add_text_layer(self, layer):
+        """Add a pre-configured EngravedTextLayer to the watch face.
+
+        Args:
+            layer: An EngravedTextLayer instance.
+        """
+        self._watch_face.add_text_layer(layer)
+
+    def add_chapter_ring(
+        self,
+        r_inner: float,
+        r_outer: float,
+        minutes: int = 60,
+        major_every: int = 5,
+        major_extra_width: float = 0.15,
+        index_cutouts: list[tuple[int, float, float]] | None = None,
+        resolution: int = 720,
+        motif: str = "ticks",
+        amplitude: float = 0.5,
+        frequency: int = 1,
+        diameter: float = 0.5,
+    ):
+        """Add a chapter ring: two track circles with a tick per minute, or a
+        wave, rope or dot motif repeated around the band between them.
+
+        Args:
+            r_inner: Radius of the inner track circle.
+            r_outer: Radius of the outer track circle.
+            minutes: Number of divisions.
+            major_every: Every this many divisions is a bolder major tick.
+            major_extra_width: Width of the major tick bars.
+            index_cutouts: (hour, width, height) rectangles for applied indices,
+                centered on the track; they are cut through STL and DXF exports.
+            resolution: Number of points per track circle.
+            motif: "ticks", "wave", "rope" or "dots"; motifs are repeated
+                `minutes` times and clamped so they never cross either circle.
+            amplitude: Wave crest distance from the mid-radius.
+            frequency: Waves per repeat.
+            diameter: Dot diameter.
+        """
+        self._watch_face.add_chapter_ring(
+            r_inner,
+            r_outer,
+            minutes=minutes,
+            major_every=major_every,
+            major_extra_width=major_extra_width,
+            index_cutouts=index_cutouts,
+            resolution=resolution,
+            motif=motif,
+            amplitude=amplitude,
+            frequency=frequency,
+            diameter=diameter,
+        )
+
+    def add_chapter_ring_layer(self, layer):
+        """Add a pre-configured ChapterRingLayer to the watch face.
+
+        Args:
+            layer: A ChapterRingLayer instance.
+        """
+        self._watch_face.add_chapter_ring_layer(layer)
+
+    def add_minute_track(
+        self,
+        inner_r: float,
+        outer_r: float,
+        ticks: int = 60,
+        major_every: int = 5,
+    ):
+        """Add a railroad minute track: two track circles with radial ticks
+        between them.
+
+        Args:
+            inner_r: Radius of the inner track circle.
+            outer_r: Radius of the outer track circle.
+            ticks: Number of ticks around the dial.
+            major_every: Every this many ticks is a bolder major tick.
+        """
+        self._watch_face.add_minute_track(inner_r, outer_r, ticks=ticks, major_every=major_every)
+
+    def add_index_markers(
+        self,
+        inner_radius: float,
+        outer_radius: float,
+        shape: str = "line",
+        count: int = 12,
+        length: float = 2.0,
+        width: float = 0.4,
+        radius: float = 0.5,
+        height: float = 2.0,
+        exclude: list[tuple[int, int]] | None = None,
+        resolution: int = 32,
+        stroke_color: str | None = None,
+        stroke_width: float = 0.03,
+        opacity: float = 1.0,
+    ):
+        """Add hour or minute index markers, engraved as outlines in every export.
+
+        Args:
+            inner_radius: Inner radius of the band holding the markers.
+            outer_radius: Outer radius of the band holding the markers.
+            shape: "line" (batons), "dot" or "roman" (single-stroke numerals,
+                12 markers only).
+            count: 12 for hours or 60 for minutes.
+            length: Baton length along the radius.
+            width: Baton width; 0 draws a single stroke.
+            radius: Dot radius.
+            height: Numeral height.
+            exclude: (hour, minute) clock positions to leave without a marker,
+                such as ones covered by a date window.
+            resolution: Number of points around each dot.
+            stroke_color: SVG stroke color for the markers; None keeps the
+                default stroke.
+            stroke_width: SVG stroke width, used with stroke_color.
+            opacity: SVG stroke opacity, used with stroke_color.
+        """
+        self._watch_face.add_index_markers(
+            inner_radius,
+            outer_radius,
+            shape=shape,
+            count=count,
+            length=length,
+            width=width,
+            radius=radius,
+            height=height,
+            exclude=exclude,
+            resolution=resolution,
+            stroke_color=stroke_color,
+            stroke_width=stroke_width,
+            opacity=opacity,
+        )
+
+    def add_index_markers_layer(self, layer):
+        """Add a pre-configured IndexMarkersLayer to the watch face.
+
+        Args:
+            layer: An IndexMarkersLayer instance.
+        """
+        self._watch_face.add_index_markers_layer(layer)
+
+    def add_imported_layer(self, lines: list[list[tuple[float, float]]]):
+        """Add fixed polylines as a layer, in the face's current unit.
+
+        Args:
+            lines: Polylines as lists of (x, y) points.
+        """
+        self._watch_face.add_imported_layer(lines)
+
+    def add_imported_svg(self, path_or_str: str) -> list[str]:
+        """Add the straight-line paths of an SVG as a layer.
+
+        Transforms on the paths and their groups are applied. Paths with
+        curves or arcs are skipped.
+
+        Args:
+            path_or_str: An SVG file name, or SVG text starting with "<".
+
+        Returns:
+            A warning for each skipped path.
+        """
+        return self._watch_face.add_imported_svg(path_or_str)
+
     def add(self, layer):
-        """Add a spirograph, flinque, diamant, draperie, huiteight, limacon, paon, clous_de_paris, or cube layer."""
+        """Add a spirograph, flinque, diamant, draperie, huiteight, limacon, paon, clous_de_paris, cube, phyllotaxis, grain_de_riz, soleil, cotes_de_geneve, tapisserie, vague, text, or chapter ring layer."""
         if isinstance(layer, FlinqueLayer):
             self._watch_face.add_flinque_layer(layer)
         elif isinstance(layer, DiamantLayer):
@@ -605,21 +1496,300 @@ class WatchFace:
             self._watch_face.add_clous_de_paris_layer(layer)
         elif isinstance(layer, CubeLayer):
             self._watch_face.add_cube_layer(layer)
+        elif isinstance(layer, PhyllotaxisLayer):
+            self._watch_face.add_phyllotaxis_layer(layer)
+        elif isinstance(layer, GrainDeRizLayer):
+            self._watch_face.add_grain_de_riz_layer(layer)
+        elif isinstance(layer, SoleilLayer):
+            self._watch_face.add_soleil_layer(layer)
+        elif isinstance(layer, CotesDeGeneveLayer):
+            self._watch_face.add_cotes_de_geneve_layer(layer)
+        elif isinstance(layer, TapisserieLayer):
+            self._watch_face.add_tapisserie_layer(layer)
+        elif isinstance(layer, VagueLayer):
+            self._watch_face.add_vague_layer(layer)
+        elif isinstance(layer, EngravedTextLayer):
+            self._watch_face.add_text_layer(layer)
+        elif isinstance(layer, ChapterRingLayer):
+            self._watch_face.add_chapter_ring_layer(layer)
+        elif isinstance(layer, IndexMarkersLayer):
+            self._watch_face.add_index_markers_layer(layer)
         else:
             self._watch_face.add_layer(layer)
 
+    def add_post_process(self, func):
+        """Register a callback that edits each layer's lines after generation.
+
+        Args:
+            func: Called as ``func(lines, kind, index)`` for every layer, where
+                ``lines`` is a list of lists of (x, y) tuples, ``kind`` the
+                layer kind (e.g. "draperie") and ``index`` the layer's index
+                among layers of that kind. Edit ``lines`` in place or return a
+                new list. Exceptions are raised from ``generate()``.
+        """
+        self._watch_face.add_post_process(func)
+
+    def clear_post_processes(self):
+        """Remove every post-process callback."""
+        self._watch_face.clear_post_processes()
+
+    def set_opacity_envelope(self, kind: str, index: int, envelope: Envelope):
+        """Fade one layer's SVG lines by ``envelope``.
+
+        ``kind`` and ``index`` name the layer as post-process callbacks see
+        it; each line's opacity is the envelope at its mean angle and radius.
+        """
+        self._watch_face.set_opacity_envelope(kind, index, envelope)
+
+    def clear_opacity_envelopes(self):
+        """Draw every layer fully opaque again."""
+        self._watch_face.clear_opacity_envelopes()
+
+    def set_layer_style(self, kind: str, index: int, stroke_color: str, stroke_width: float, opacity: float = 1.0):
+        """Stroke one layer's SVG lines with its own color, width and opacity.
+
+        ``kind`` and ``index`` name the layer as for ``set_opacity_envelope``;
+        ``stroke_width`` is in the design unit. Layers without a style keep
+        the default ``#1a1a1a`` stroke.
+        """
+        self._watch_face.set_layer_style(kind, index, stroke_color, stroke_width, opacity)
+
+    def layer_style(self, kind: str, index: int):
+        """Get a layer's ``(stroke_color, stroke_width, opacity)``, or None."""
+        return self._watch_face.layer_style(kind, index)
+
+    def clear_layer_styles(self):
+        """Draw every layer with the default stroke again."""
+        self._watch_face.clear_layer_styles()
+
+    def apply(self, op: str, **kwargs):
+        """Apply an undoable edit.
+
+        ``op`` is ``"add_layer"`` (``layer``), ``"remove_layer"`` (``kind``,
+        ``index``), ``"move_layer"`` (``kind``, ``index``, ``to``),
+        ``"set_layer_center"`` (``kind``, ``index``, ``x``, ``y``),
+        ``"set_dial"`` / ``"set_bezel"`` (``enabled`` plus optional styling)
+        or ``"add_hole"`` (``x``, ``y``, ``radius``). Nothing is regenerated;
+        call ``regenerate_stale()`` to redraw just the layers edits touched.
+        """
+        self._watch_face.apply(op, **kwargs)
+
+    def undo(self) -> bool:
+        """Undo the last edit; returns False if there was nothing to undo."""
+        return self._watch_face.undo()
+
+    def redo(self) -> bool:
+        """Redo the last undone edit; returns False if there was nothing to redo."""
+        return self._watch_face.redo()
+
+    def set_undo_depth(self, depth: int):
+        """Set how many edits can be undone (default 100)."""
+        self._watch_face.set_undo_depth(depth)
+
+    def regenerate_stale(self) -> int:
+        """Generate only the layers edits have added, moved or re-centered; returns how many."""
+        return self._watch_face.regenerate_stale()
+
+    def get_lines(self):
+        """Get every generated line across all layers as lists of (x, y) tuples."""
+        return self._watch_face.get_lines()
+
     def generate(self):
         """Generate all pattern layers."""
         self._watch_face.generate()
 
-    def to_svg(self, filename: str):
-        """Export the watch face to SVG."""
-        self._watch_face.to_svg(filename)
+    def validate(self):
+        """Check every layer without generating anything.
+
+        Returns one dict per invalid layer with ``kind``, ``index``,
+        ``field`` and ``message``; an empty list means ``generate()`` is safe.
+        """
+        return self._watch_face.validate()
+
+    def try_generate(self):
+        """Generate all pattern layers, raising ValueError listing every invalid layer."""
+        self._watch_face.try_generate()
 
-    def to_stl(self, filename: str, depth: float = 0.1, base_thickness: float = 2.0):
-        """Export the watch face to STL."""
-        self._watch_face.to_stl(filename, depth, base_thickness)
+    def generate_lenient(self):
+        """Generate every valid layer, skipping the invalid ones.
 
-    def to_step(self, filename: str, depth: float = 0.1):
-        """Export the watch face to STEP."""
-        self._watch_face.to_step(filename, depth)
+        Returns ``{"generated": count, "skipped": [issue dicts]}``, where the
+        issues are as returned by ``validate()``.
+        """
+        return self._watch_face.generate_lenient()
+
+    def generate_parallel(self):
+        """Generate all pattern layers concurrently.
+
+        Each layer is generated on its own thread; the result is identical to ``generate()``.
+        """
+        self._watch_face.generate_parallel()
+
+    def to_svg(
+        self,
+        filename: str,
+        unit: str | None = None,
+        include_reference: bool = False,
+        origin: str | None = None,
+        tolerance: float = 0.0,
+    ):
+        """Export the watch face to SVG, in the design unit unless ``unit`` is given.
+
+        The reference image, if set, is drawn beneath the pattern only when
+        ``include_reference`` is True. ``origin`` moves the coordinates to
+        "top_left" (y down) or "bottom_left" (y up) of the page; the default
+        "center" keeps the dial center at (0, 0). A positive ``tolerance``
+        simplifies the pattern lines to within that distance, in the export
+        unit, which shrinks dense patterns; 0 writes every point.
+        """
+        self._watch_face.to_svg(
+            filename, unit=unit, include_reference=include_reference, origin=origin, tolerance=tolerance
+        )
+
+    def to_svg_with_overlay(
+        self,
+        filename: str,
+        mm_grid: float | None = None,
+        radial_rays_every_deg: float | None = None,
+        concentric_circles_every_mm: float | None = None,
+        label_font_size: float | None = None,
+    ):
+        """Export the watch face to SVG with a design-review overlay.
+
+        The overlay is drawn beneath the pattern and never appears in STL/STEP exports.
+
+        Args:
+            mm_grid: Pitch of a light-gray square grid in mm.
+            radial_rays_every_deg: Angular spacing of labeled radial rays in degrees.
+            concentric_circles_every_mm: Radial spacing of labeled reference circles in mm.
+            label_font_size: Label font size in mm (default 2.0).
+        """
+        self._watch_face.to_svg_with_overlay(
+            filename,
+            mm_grid=mm_grid,
+            radial_rays_every_deg=radial_rays_every_deg,
+            concentric_circles_every_mm=concentric_circles_every_mm,
+            label_font_size=label_font_size,
+        )
+
+    def to_stl(
+        self,
+        filename: str,
+        depth: float | None = None,
+        base_thickness: float | None = None,
+        unit: str | None = None,
+        origin: str | None = None,
+        tool_radius: float | None = None,
+        config: ExportConfig | None = None,
+    ):
+        """Export the watch face to STL; depth, thickness and tool radius are in the export unit.
+
+        They may come from an ``ExportConfig`` as ``config`` instead, and
+        default to 0.1, 2.0 and 0.0. ``origin`` is as for ``to_svg``.
+        """
+        self._watch_face.to_stl(
+            filename,
+            depth,
+            base_thickness,
+            unit=unit,
+            origin=origin,
+            tool_radius=tool_radius,
+            config=config,
+        )
+
+    def to_dxf_profile(self, filename: str):
+        """Export the dial edge, holes and chapter ring cutouts to DXF as closed polylines in mm."""
+        self._watch_face.to_dxf_profile(filename)
+
+    def to_hpgl(self, filename: str, scale: float = 1.0, pens: list[int] | None = None):
+        """Export every layer to HPGL for a pen plotter.
+
+        Geometry is converted to mm, multiplied by ``scale`` and clipped to
+        the dial. Layer ``i`` (in SVG drawing order) is plotted with
+        ``pens[i]``; layers past the end of ``pens`` use its last pen, and
+        every layer uses pen 1 without any.
+        """
+        self._watch_face.to_hpgl(filename, scale, pens)
+
+    def get_apertures(self):
+        """Get every opening through the dial (holes, then chapter ring cutouts) as lists of (x, y) points."""
+        return self._watch_face.get_apertures()
+
+    def to_step(
+        self,
+        filename: str,
+        depth: float | None = None,
+        unit: str | None = None,
+        origin: str | None = None,
+        config: ExportConfig | None = None,
+    ):
+        """Export the watch face to STEP; depth (or an ``ExportConfig`` as ``config``) is in the export unit.
+
+        ``origin`` is as for ``to_svg``.
+        """
+        self._watch_face.to_step(filename, depth, unit=unit, origin=origin, config=config)
+
+    def to_png(self, filename: str, width_px: int = 1024, height_px: int = 1024):
+        """Rasterize the SVG export to an anti-aliased RGBA PNG preview.
+
+        The SVG view is fitted into ``width_px`` x ``height_px`` on a white
+        background, with each layer's color, stroke width and opacity.
+        """
+        self._watch_face.to_png(filename, width_px, height_px)
+
+    def render_bytes(self, width_px: int = 1024, height_px: int = 1024) -> bytes:
+        """Rasterize the SVG export as ``to_png`` does and return the PNG bytes.
+
+        Show it inline with ``PIL.Image.open(io.BytesIO(face.render_bytes()))``.
+        """
+        return self._watch_face.render_bytes(width_px, height_px)
+
+    def to_engraver_bitmap(self, filename: str, dpi: int, line_width_mm: float, dither: str = "ordered"):
+        """Rasterize the pattern to a 1-bit PNG (or PBM, by extension) for a laser engraver.
+
+        Args:
+            filename: Output path; ".pbm" writes PBM, anything else PNG
+            dpi: Resolution in dots per inch
+            line_width_mm: Stroke width of every groove in mm
+            dither: "ordered" (Bayer) or "error_diffusion" (Floyd-Steinberg)
+
+        Returns:
+            (width, height) in pixels
+        """
+        return self._watch_face.to_engraver_bitmap(filename, dpi, line_width_mm, dither)
+
+
+def random_design(
+    seed: int,
+    radius: float | None = 40.0,
+    density: str = "medium",
+    forbid: list[str] | None = None,
+    chapter_ring: bool = False,
+    min_groove_spacing: float | None = None,
+):
+    """Assemble a random watch face ("surprise me").
+
+    The same seed and arguments always give the same design.
+
+    Args:
+        seed: Seed for every random choice
+        radius: Dial radius in mm, or None to draw one from 30-42mm
+        density: "sparse", "medium" or "dense"
+        forbid: Layer kind names that must not appear, e.g. ["draperie"]
+        chapter_ring: Add a minute-track chapter ring inside the dial edge
+        min_groove_spacing: Re-roll designs whose groove pitch is below this (mm)
+
+    Returns:
+        (WatchFace, recipe) where recipe is a dict of every choice; edit it and
+        pass it to ``WatchFace.from_recipe`` to rebuild. Call ``generate()``
+        on the face before exporting.
+    """
+    watch_face, recipe = RustWatchFace.random_design(
+        seed,
+        radius=radius,
+        density=density,
+        forbid=forbid,
+        chapter_ring=chapter_ring,
+        min_groove_spacing=min_groove_spacing,
+    )
+    return WatchFace._wrap(watch_face), json.loads(recipe)
@@ -33,6 +33,29 @@ def test_horizontal_spirograph_invalid_radius():
         assert "26mm and 44mm" in str(e)
 
 
+def test_spirograph_invalid_sampling():
+    """Test that zero rotations and too-low resolution raise errors"""
+    for rotations, resolution, field in ((0, 360, "rotations"), (50, 0, "resolution"), (50, 1, "resolution")):
+        for make in (
+            lambda: HorizontalSpirograph(40.0, 0.75, 0.6, rotations, resolution),
+            lambda: VerticalSpirograph(35.0, 0.6, 0.5, rotations, resolution),
+            lambda: SphericalSpirograph(38.0, 0.7, 0.4, rotations, resolution),
+        ):
+            try:
+                make()
+                assert False, f"Should have raised ValueError for {field}"
+            except ValueError as e:
+                assert f"Invalid parameter: {field}" in str(e)
+
+    spiro = HorizontalSpirograph(40.0, 0.75, 0.6, 5, 360)
+    with tempfile.TemporaryDirectory() as tmpdir:
+        try:
+            spiro.to_svg(os.path.join(tmpdir, "never.svg"))
+            assert False, "Should have raised IOError before generate()"
+        except IOError as e:
+            assert "never called" in str(e)
+
+
 def test_vertical_spirograph():
     """Test VerticalSpirograph creation and generation"""
     spiro = VerticalSpirograph(
@@ -62,6 +85,66 @@ def test_spherical_spirograph():
     assert spiro is not None
 
 
+def test_spirograph_points():
+    """Test point accessors on the spirograph classes"""
+    for spiro in (
+        HorizontalSpirograph(40.0, 0.75, 0.6, 5, 360),
+        VerticalSpirograph(35.0, 0.6, 0.5, 5, 360),
+        SphericalSpirograph(38.0, 0.7, 0.4, 5, 360),
+    ):
+        assert spiro.get_points() == []
+        spiro.generate()
+        points = spiro.get_points()
+        assert len(points) == spiro.stats()["num_points"]
+        assert all(len(p) == 2 for p in points)
+
+    spiro = SphericalSpirograph(38.0, 0.7, 0.4, 5, 360, dome_height=5.0)
+    spiro.generate()
+    points_3d = spiro.get_points_3d()
+    assert len(points_3d) == len(spiro.get_points())
+    assert all(len(p) == 3 for p in points_3d)
+    assert max(z for _, _, z in points_3d) <= 5.0 + 1e-9
+
+
+def test_spherical_spirograph_dome_stl():
+    """Test exporting a spherical spirograph cut into a solid dome"""
+    spiro = SphericalSpirograph(38.0, 0.7, 0.4, 5, 360, dome_height=5.0)
+    spiro.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        ribbons = os.path.join(tmpdir, "ribbons.stl")
+        dome = os.path.join(tmpdir, "dome.stl")
+        spiro.to_stl(ribbons, depth=0.2, base_thickness=0.0)
+        spiro.to_stl(dome, depth=0.2, base_thickness=0.0, dome=True)
+        assert os.path.getsize(dome) > os.path.getsize(ribbons)
+
+        try:
+            spiro.to_stl(dome, dome=True, dome_segments=2)
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+
+def test_spirograph_epitrochoid_mode():
+    """Test epitrochoid spirographs and the closing rotation count"""
+    import math
+
+    spiro = HorizontalSpirograph(30.0, 0.5, 5.0, 1, 360, mode="epitrochoid")
+    assert spiro.mode == "epitrochoid"
+    spiro.generate()
+    assert abs(max(math.hypot(x, y) for x, y in spiro.get_points()) - 50.0) < 1e-6
+
+    assert HorizontalSpirograph(40.0, 0.75, 0.6, 1, 360).mode == "hypotrochoid"
+    assert HorizontalSpirograph(40.0, 0.75, 0.6, 1, 360).closure_rotations() == 3
+    assert VerticalSpirograph(35.0, 0.6, 0.5, 1, 360, mode="epitrochoid").closure_rotations() == 3
+    assert SphericalSpirograph(38.0, 0.5, 0.4, 1, 360, mode="epitrochoid").closure_rotations() == 1
+
+    try:
+        HorizontalSpirograph(40.0, 0.75, 0.6, 1, 360, mode="cycloid")
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
 def test_guilloche_pattern():
     """Test GuillochePattern with multiple layers"""
     pattern = GuillochePattern(radius=38.0)
@@ -76,6 +159,75 @@ def test_guilloche_pattern():
     assert pattern is not None
 
 
+def test_radius_policy():
+    """Test pocket-watch and jewelry radii under a radius policy"""
+    from turtles import WatchFace
+
+    for make in (lambda: GuillochePattern(60.0), lambda: WatchFace(60.0), lambda: GuillochePattern(60.0, radius_policy="watch")):
+        try:
+            make()
+            assert False, "Should have raised ValueError"
+        except ValueError as e:
+            assert "26mm and 44mm" in str(e)
+
+    pattern = GuillochePattern(60.0, radius_policy="unrestricted")
+    assert pattern.radius == 60.0
+    pattern.add_layer(HorizontalSpirograph(55.0, 0.75, 0.6, 3, 100, radius_policy="unrestricted"))
+    pattern.generate()
+
+    face = WatchFace(60.0, radius_policy="unrestricted")
+    assert WatchFace.from_json(face.to_json()) is not None
+    assert GuillochePattern(15.0, radius_policy=(10.0, 20.0)).radius == 15.0
+
+    for radius, policy in ((25.0, (10.0, 20.0)), (15.0, (20.0, 10.0)), (30.0, "pocket"), (30.0, 12)):
+        try:
+            GuillochePattern(radius, radius_policy=policy)
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+
+def test_guilloche_pattern_clip_to_radius():
+    """Test clipping a pattern's combined exports to a circle"""
+    import math
+    import struct
+
+    pattern = GuillochePattern(radius=30.0)
+    pattern.add_layer(HorizontalSpirograph(40.0, 0.75, 25.0, 3, 120))
+    pattern.generate()
+    assert pattern.clip_radius is None
+
+    try:
+        pattern.clip_to_radius(-1.0)
+        assert False, "Should have raised ValueError for a negative clip radius"
+    except ValueError:
+        pass
+
+    def widest_vertex(path):
+        with open(path, "rb") as f:
+            data = f.read()
+        (count,) = struct.unpack_from("<I", data, 80)
+        widest = 0.0
+        for i in range(count):
+            values = struct.unpack_from("<12f", data, 84 + 50 * i)
+            for j in range(3, 12, 3):
+                widest = max(widest, math.hypot(values[j], values[j + 1]))
+        return widest
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "clipped.stl")
+        pattern.to_stl(path)
+        assert widest_vertex(path) > 30.5
+
+        pattern.clip_to_radius(30.0)
+        assert pattern.clip_radius == 30.0
+        pattern.to_stl(path)
+        assert widest_vertex(path) <= 30.0 + 1e-4
+
+        pattern.clear_clip()
+        assert pattern.clip_radius is None
+
+
 def test_export_svg():
     """Test SVG export functionality"""
     with tempfile.TemporaryDirectory() as tmpdir:
@@ -101,6 +253,15 @@ def test_export_stl():
         assert os.path.exists(stl_path)
         assert os.path.getsize(stl_path) > 0
 
+        # Binary STL: 84-byte header plus 50 bytes per triangle. Without a
+        # base slab only the groove solid remains: a 90° V of three faces
+        # per step, 10 turns of 100 steps, capped at both ends.
+        bare_path = os.path.join(tmpdir, "bare.stl")
+        spiro.to_stl(bare_path, depth=0.1, base_thickness=0.0)
+        groove = os.path.getsize(bare_path)
+        assert groove == 84 + 50 * (6 * 1000 + 2)
+        assert os.path.getsize(stl_path) > groove
+
 
 def test_export_step():
     """Test STEP export functionality"""
@@ -146,6 +307,11 @@ def test_flinque_layer():
     )
     assert flinque is not None
 
+    # An explicit resolution: every closed ring holds resolution + 1 points
+    flinque = FlinqueLayer(radius=38.0, num_petals=6, num_waves=4, resolution=120)
+    flinque.generate()
+    assert all(len(ring) == 121 for ring in flinque.get_lines())
+
 
 def test_watch_top_level():
     wf = WatchFace(radius=38.0)
@@ -168,6 +334,543 @@ def test_watch_top_level():
         wf.to_svg(svg_path)
 
 
+def test_watch_face_reference_image():
+    """Test reference image embedding gated on the SVG export call"""
+    wf = WatchFace(radius=30.0)
+    wf.add_inner()
+    wf.add_diamant(num_circles=6)
+    wf.generate()
+    wf.set_reference_image("antique.jpg", 60.0, opacity=0.3, rotation_deg=0.0, offset=(2.0, -1.0))
+    assert wf.reference_image == ("antique.jpg", 60.0, 0.3, 0.0, (2.0, -1.0))
+
+    with tempfile.NamedTemporaryFile(mode="w", suffix=".svg", delete=False) as f:
+        svg_path = f.name
+
+    try:
+        wf.to_svg(svg_path, include_reference=True)
+        with open(svg_path) as f:
+            content = f.read()
+        assert 'href="antique.jpg"' in content
+        assert 'transform="matrix(1 0 -0 1 2 -1)"' in content
+
+        wf.to_svg(svg_path)
+        with open(svg_path) as f:
+            assert "<image" not in f.read()
+
+        wf.clear_reference()
+        assert wf.reference_image is None
+    finally:
+        if os.path.exists(svg_path):
+            os.unlink(svg_path)
+
+    try:
+        wf.set_reference_image("antique.jpg", 60.0, opacity=2.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
+def test_watch_face_post_process():
+    """Test Python post-process callbacks edit each layer after generation"""
+    wf = WatchFace(radius=30.0)
+    wf.add_draperie(num_rings=20)
+    seen = []
+
+    def keep_even(lines, kind, index):
+        seen.append((kind, index, len(lines)))
+        return lines[::2]
+
+    def nudge(lines, kind, index):
+        seen.append((kind, index, len(lines)))
+        for i, line in enumerate(lines):
+            lines[i] = [(x * 1.01, y * 1.01) for x, y in line]
+
+    wf.add_post_process(keep_even)
+    wf.add_post_process(nudge)
+    wf.generate()
+    assert seen == [("draperie", 0, 20), ("draperie", 0, 10)]
+
+    # Regenerating starts from fresh lines
+    wf.generate()
+    assert seen[2:] == seen[:2]
+
+    def fail(lines, kind, index):
+        raise ValueError("bad layer")
+
+    wf.clear_post_processes()
+    wf.add_post_process(fail)
+    try:
+        wf.generate()
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+    wf.clear_post_processes()
+    wf.generate()
+
+
+def test_watch_face_engraver_bitmap():
+    """Test 1-bit raster export for laser engravers"""
+    wf = WatchFace(radius=30.0)
+    wf.add_draperie(num_rings=40, base_radius=12.0)
+    wf.generate()
+
+    with tempfile.TemporaryDirectory() as tmp:
+        png = os.path.join(tmp, "dial.png")
+        assert wf.to_engraver_bitmap(png, 254, 0.1) == (601, 601)
+        with open(png, "rb") as f:
+            assert f.read(8) == b"\x89PNG\r\n\x1a\n"
+
+        pbm = os.path.join(tmp, "dial.pbm")
+        wf.to_engraver_bitmap(pbm, 254, 0.1, dither="error_diffusion")
+        with open(pbm, "rb") as f:
+            assert f.read(11) == b"P4\n601 601\n"
+
+        try:
+            wf.to_engraver_bitmap(png, 254, 0.1, dither="random")
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+
+def test_watch_face_metadata():
+    """Test traceability metadata is embedded in SVG and STEP exports"""
+    wf = WatchFace(radius=30.0)
+    wf.add_draperie(num_rings=12, base_radius=12.0)
+    wf.generate()
+    assert wf.metadata is None
+
+    wf.set_metadata("DIAL-0042", params_hash="9f3c", license="CC-BY-4.0", extra={"customer": "Nord"})
+    meta = wf.metadata
+    assert list(meta) == ["design_id", "params_hash", "generator_version", "license", "customer"]
+    assert meta["generator_version"].startswith("turtles ")
+
+    with tempfile.TemporaryDirectory() as tmp:
+        svg = os.path.join(tmp, "dial.svg")
+        wf.to_svg(svg)
+        with open(svg) as f:
+            content = f.read()
+        assert "<dc:identifier>DIAL-0042</dc:identifier>" in content
+        assert 'data-customer="Nord"' in content
+
+        step = os.path.join(tmp, "dial.stp")
+        wf.to_step(step)
+        with open(step) as f:
+            content = f.read()
+        assert "'design_id: DIAL-0042'" in content
+        assert "('DIAL-0042')" in content
+        assert "PRODUCT('watch_face','watch_face'" in content
+        assert "CIRCLE('dial'," in content
+
+    wf.clear_metadata()
+    assert wf.metadata is None
+
+
+def test_watch_face_svg_overlay():
+    """Test design-review overlay on watch face SVG export"""
+    import os
+    import tempfile
+
+    wf = WatchFace(radius=30.0)
+    wf.add_inner()
+    wf.add_outer()
+    wf.add_diamant(num_circles=12)
+    wf.generate()
+    wf.set_fixed_viewbox((-75.0, -75.0, 150.0, 150.0))
+
+    with tempfile.NamedTemporaryFile(mode="w", suffix=".svg", delete=False) as f:
+        svg_path = f.name
+
+    try:
+        wf.to_svg_with_overlay(svg_path, mm_grid=5.0, radial_rays_every_deg=30.0)
+        with open(svg_path) as f:
+            content = f.read()
+        assert 'id="overlay"' in content
+        # viewBox spans +/-75mm, so a 5mm pitch gives 31 lines in each direction
+        assert content.count('class="overlay-grid"') == 62
+        assert content.count('class="overlay-ray"') == 12
+        assert content.index('id="overlay"') < content.index("url(#dial-clip)")
+    finally:
+        if os.path.exists(svg_path):
+            os.unlink(svg_path)
+
+
+def test_watch_face_svg_layer_groups():
+    """Test every layer is a named group in the SVG, optionally with data attributes"""
+    import xml.etree.ElementTree as ET
+
+    ns = "{http://www.w3.org/2000/svg}"
+    wf = WatchFace(radius=30.0)
+    wf.add_inner()
+    wf.add_outer()
+    wf.add_diamant(num_circles=12)
+    wf.add_diamant(num_circles=24)
+    wf.add_center_hole()
+    wf.generate()
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "groups.svg")
+        wf.to_svg(path)
+        root = ET.parse(path).getroot()
+        assert [g.get("id") for g in root.findall(ns + "g")] == ["dial", "pattern", "bezel", "holes"]
+        layers = root.find(ns + "g[@id='pattern']").findall(ns + "g")
+        assert [g.get("id") for g in layers] == ["diamant-0", "diamant-1"]
+        assert all(g.get("class") == "turtles-layer diamant" for g in layers)
+        assert len(layers[0].findall(ns + "path")) == 12
+        assert layers[0].get("data-config") is None
+
+        wf.set_svg_layer_data(True)
+        wf.to_svg(path)
+        layer = ET.parse(path).getroot().find(ns + "g[@id='pattern']/" + ns + "g[@id='diamant-1']")
+        assert layer.get("data-config").startswith("DiamantConfig {")
+        assert layer.get("data-lines") == "24"
+
+
+def test_watch_face_apertures():
+    """Test complication apertures cut the pattern and are outlined"""
+    import re
+    import xml.etree.ElementTree as ET
+
+    ns = "{http://www.w3.org/2000/svg}"
+    wf = WatchFace(radius=30.0)
+    wf.add_inner()
+    wf.add_diamant(num_circles=24)
+    wf.add_aperture_at_clock(3, 0, 18.0, width=6.0, height=4.0, stroke_color="#803020")
+    wf.add_aperture(0.0, 12.0, shape="circle", radius=5.0)
+    wf.generate()
+    for bad in [{"shape": "hexagon"}, {"width": 2.0, "height": 2.0, "corner_radius": 1.5}]:
+        try:
+            wf.add_aperture(0.0, 0.0, **bad)
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "apertures.svg")
+        wf.to_svg(path)
+        root = ET.parse(path).getroot()
+        assert [g.get("id") for g in root.findall(ns + "g")] == ["dial", "pattern", "apertures"]
+        outlines = root.find(ns + "g[@id='apertures']").findall(ns + "path")
+        assert [p.get("stroke") for p in outlines] == ["#803020", "#2c2c2c"]
+        for layer_path in root.find(ns + "g[@id='pattern']").iter(ns + "path"):
+            numbers = [float(n) for n in re.findall(r"-?[\d.]+(?:e-?\d+)?", layer_path.get("d"))]
+            for x, y in zip(numbers[::2], numbers[1::2]):
+                assert not (15.0 + 1e-6 < x < 21.0 - 1e-6 and abs(y) < 2.0 - 1e-6)
+
+        wf.clear_apertures()
+        wf.to_svg(path)
+        root = ET.parse(path).getroot()
+        assert root.find(ns + "g[@id='apertures']") is None
+
+
+def test_watch_face_subdials():
+    """Test subdials draw a nested face clipped to their circle"""
+    import math
+    import re
+    import xml.etree.ElementTree as ET
+
+    ns = "{http://www.w3.org/2000/svg}"
+    seconds = WatchFace(radius=7.0, radius_policy="unrestricted")
+    seconds.add_diamant(num_circles=12)
+    wf = WatchFace(radius=30.0)
+    wf.add_diamant(num_circles=24)
+    wf.add_subdial_at_clock(6, 0, 15.0, 7.0, seconds, stroke_color="#803020")
+    for bad in [{"distance": 25.0}, {"radius": 0.0}]:
+        try:
+            wf.add_subdial_at_clock(3, 0, **{"distance": 15.0, "radius": 7.0, "face": seconds, **bad})
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+    assert wf.subdial_count == 1
+    wf.generate()
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "subdials.svg")
+        wf.to_svg(path)
+        root = ET.parse(path).getroot()
+        assert [g.get("id") for g in root.findall(ns + "g")] == ["pattern", "subdials"]
+        subdial = root.find(ns + "g[@id='subdials']/" + ns + "g[@id='subdial-0']")
+        assert subdial.find(ns + "circle").get("stroke") == "#803020"
+        assert subdial.find(ns + "g[@id='subdial-0-diamant-0']") is not None
+        for group, inside in [(subdial, True), (root.find(ns + "g[@id='pattern']"), False)]:
+            for layer_path in group.iter(ns + "path"):
+                numbers = [float(n) for n in re.findall(r"-?[\d.]+(?:e-?\d+)?", layer_path.get("d"))]
+                for x, y in zip(numbers[::2], numbers[1::2]):
+                    d = math.hypot(x, y - 15.0)
+                    assert d <= 7.0 + 1e-4 if inside else d >= 7.0 - 1e-4
+
+        wf.clear_subdials()
+        wf.to_svg(path)
+        assert ET.parse(path).getroot().find(ns + "g[@id='subdials']") is None
+
+
+def test_hpgl_export():
+    """Test HPGL export from a watch face, a pattern and a lathe run"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun
+
+    wf = WatchFace(radius=30.0)
+    wf.add_diamant(num_circles=12)
+    wf.add_diamant(num_circles=8)
+    wf.generate()
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=2.0)
+    run = RoseEngineLatheRun(config, CuttingBit.v_shaped(angle=30.0, width=0.5), num_passes=4)
+    run.generate()
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "face.hpgl")
+        wf.to_hpgl(path, pens=[1, 2])
+        with open(path) as f:
+            hpgl = f.read()
+        assert hpgl.startswith("IN;PA;SP1;PU")
+        assert hpgl.endswith("PU;SP0;\n")
+        assert hpgl.count("SP2;") == 1
+        # Circles crossing the dial edge are cut into arcs
+        assert hpgl.count("PD") >= 12 + 8
+        # Coordinates are whole plotter units
+        for command in hpgl.rstrip("\n").split(";"):
+            if command.startswith(("PU", "PD")) and len(command) > 2:
+                assert all(value.lstrip("-").isdigit() for value in command[2:].split(","))
+
+        path = os.path.join(tmpdir, "run.hpgl")
+        run.to_hpgl(path, scale=0.5, pens=[3])
+        with open(path) as f:
+            assert f.read().count("PD") == len(run.get_lines())
+
+        pattern = GuillochePattern(radius=38.0)
+        try:
+            pattern.to_hpgl(path, scale=0.0)
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+
+def test_watch_face_export_origin():
+    """Test moving the export origin to a page corner"""
+    import os
+    import re
+    import tempfile
+
+    wf = WatchFace(radius=30.0)
+    wf.add_inner()
+    wf.add_diamant(num_circles=12)
+    wf.generate()
+
+    with tempfile.TemporaryDirectory() as tmp:
+        centered = os.path.join(tmp, "centered.svg")
+        cornered = os.path.join(tmp, "cornered.svg")
+        wf.to_svg(centered)
+        wf.to_svg(cornered, origin="top_left")
+        with open(centered) as f:
+            view_box = [float(v) for v in re.search(r'viewBox="([^"]+)"', f.read()).group(1).split()]
+        with open(cornered) as f:
+            content = f.read()
+        assert re.search(r'viewBox="0 0 ', content)
+        assert f"matrix(1 0 0 1 {-view_box[0]:g} {-view_box[1]:g})" in content
+
+        wf.to_stl(os.path.join(tmp, "cornered.stl"), origin="bottom_left")
+        try:
+            wf.to_svg(cornered, origin="middle")
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+
+def test_svg_simplify_tolerance():
+    """Test simplifying SVG paths to a tolerance before export"""
+    import os
+    import tempfile
+
+    from turtles import DraperieLayer
+
+    wf = WatchFace(radius=30.0)
+    wf.add_draperie(num_rings=12, base_radius=15.0, resolution=720)
+    wf.generate()
+    layer = DraperieLayer(num_rings=12, base_radius=15.0, resolution=720)
+    layer.generate()
+
+    with tempfile.TemporaryDirectory() as tmp:
+        plain = os.path.join(tmp, "plain.svg")
+        exact = os.path.join(tmp, "exact.svg")
+        simplified = os.path.join(tmp, "simplified.svg")
+        for target in (wf, layer):
+            target.to_svg(plain)
+            target.to_svg(exact, tolerance=0.0)
+            target.to_svg(simplified, tolerance=0.01)
+            with open(plain) as a, open(exact) as b:
+                assert a.read() == b.read()
+            assert os.path.getsize(simplified) < os.path.getsize(plain)
+
+        try:
+            wf.to_svg(simplified, include_reference=True, tolerance=0.01)
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+        try:
+            layer.to_svg(simplified, streaming=True, tolerance=0.01)
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+
+def test_random_design():
+    """Test seeded random designs and rebuilding them from the recipe"""
+    import os
+    import tempfile
+
+    from turtles import random_design
+
+    face, recipe = random_design(7, radius=38.0, density="dense", chapter_ring=True)
+    again, same = random_design(7, radius=38.0, density="dense", chapter_ring=True)
+    assert recipe == same
+    assert recipe["radius"] == 38.0
+    assert recipe["density"] == "dense"
+    assert recipe["chapter_ring"] is not None
+    assert random_design(8, radius=38.0, density="dense", chapter_ring=True)[1] != recipe
+
+    _, limited = random_design(3, forbid=["draperie", "limacon", "huiteight", "clous_de_paris"])
+    assert limited["center"]["family"] == "flinque"
+    assert limited["band"]["family"] == "flinque"
+
+    rebuilt = WatchFace.from_recipe(recipe)
+    with tempfile.TemporaryDirectory() as tmp:
+        outputs = []
+        for wf in (face, rebuilt):
+            wf.generate()
+            path = os.path.join(tmp, f"design{len(outputs)}.svg")
+            wf.to_svg(path)
+            with open(path) as f:
+                outputs.append(f.read())
+        assert outputs[0] == outputs[1]
+
+    try:
+        random_design(1, density="extreme")
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
+def test_watch_face_generate_parallel():
+    """Test parallel generation produces the same SVG as sequential generation"""
+    import os
+    import tempfile
+
+    outputs = []
+    for parallel in (False, True):
+        wf = WatchFace(radius=38.0)
+        wf.add_inner()
+        wf.add_diamant(num_circles=24)
+        wf.add_limacon(num_curves=24)
+        wf.add_clous_de_paris()
+        if parallel:
+            wf.generate_parallel()
+        else:
+            wf.generate()
+
+        with tempfile.NamedTemporaryFile(mode="w", suffix=".svg", delete=False) as f:
+            svg_path = f.name
+        try:
+            wf.to_svg(svg_path)
+            with open(svg_path) as f:
+                outputs.append(f.read())
+        finally:
+            if os.path.exists(svg_path):
+                os.unlink(svg_path)
+
+    assert outputs[0] == outputs[1]
+
+
+def test_watch_face_units():
+    """Test mm/inch export units and design-unit conversion"""
+    import os
+    import re
+    import tempfile
+
+    wf = WatchFace(radius=38.0)
+    wf.add_inner()
+    wf.add_diamant(num_circles=12)
+    wf.generate()
+    assert wf.unit == "mm"
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        mm_path = os.path.join(tmpdir, "mm.svg")
+        in_path = os.path.join(tmpdir, "in.svg")
+        wf.to_svg(mm_path)
+        wf.to_svg(in_path, unit="in")
+        with open(mm_path) as f:
+            mm_svg = f.read()
+        with open(in_path) as f:
+            in_svg = f.read()
+
+    mm_width = float(re.search(r'width="([0-9.]+)mm"', mm_svg).group(1))
+    in_width = float(re.search(r'width="([0-9.]+)in"', in_svg).group(1))
+    # Widths are written to 4 decimals, the inch one before scaling
+    assert abs(in_width * 25.4 - mm_width) < 2e-3
+    mm_r = float(re.search(r'<circle[^>]* r="([0-9.]+)"', mm_svg).group(1))
+    in_r = float(re.search(r'<circle[^>]* r="([0-9.]+)"', in_svg).group(1))
+    assert abs(in_r * 25.4 - mm_r) < 1e-4
+
+    # Exports leave the design unit alone
+    assert wf.unit == "mm"
+    assert wf.radius == 38.0
+
+    wf.convert_units("in")
+    assert wf.unit == "in"
+    assert abs(wf.radius * 25.4 - 38.0) < 1e-9
+
+    inch_face = WatchFace(radius=1.5, unit="in")
+    assert inch_face.unit == "in"
+
+    try:
+        WatchFace(radius=38.0, unit="furlong")
+        assert False, "Should have raised ValueError for unknown unit"
+    except ValueError:
+        pass
+
+
+
+def test_watch_face_max_points_per_path():
+    """Test that long SVG lines are split into grouped paths"""
+    import os
+    import tempfile
+
+    wf = WatchFace(radius=38.0)
+    wf.add_diamant(num_circles=4)
+    wf.generate()
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        whole_path = os.path.join(tmpdir, "whole.svg")
+        split_path = os.path.join(tmpdir, "split.svg")
+        wf.to_svg(whole_path)
+        wf.set_max_points_per_path(50)
+        wf.to_svg(split_path)
+        with open(whole_path) as f:
+            whole_svg = f.read()
+        with open(split_path) as f:
+            split_svg = f.read()
+
+    assert split_svg.count("<path") > whole_svg.count("<path")
+    assert "<g" in split_svg
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        pinned_path = os.path.join(tmpdir, "pinned.svg")
+        wf.set_fixed_viewbox((-95.0, -95.0, 190.0, 190.0))
+        wf.to_svg(pinned_path)
+        with open(pinned_path) as f:
+            assert 'viewBox="-95 -95 190 190"' in f.read()
+    wf.set_fixed_viewbox(None)
+
+    try:
+        wf.set_fixed_viewbox((0.0, 0.0, -1.0, 1.0))
+        assert False, "Should have raised ValueError for negative size"
+    except ValueError:
+        pass
+
+    try:
+        wf.set_max_points_per_path(1)
+        assert False, "Should have raised ValueError for max_points < 2"
+    except ValueError:
+        pass
+
 def test_rose_engine_config():
     """Test RoseEngineConfig creation and presets"""
     from turtles import RoseEngineConfig
@@ -192,6 +895,45 @@ def test_rose_engine_config():
     assert config_dia.base_radius == 20.0
 
 
+def test_rose_engine_config_angles():
+    """Test phase and start/end angle control and the partial_arc preset"""
+    import math
+
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=1.0)
+    assert (config.phase, config.start_angle, config.end_angle) == (0.0, 0.0, 2 * math.pi)
+    config.set_phase(0.5)
+    config.set_end_angle(math.pi)
+    config.set_start_angle(math.pi / 2)
+    assert (config.phase, config.start_angle, config.end_angle) == (0.5, math.pi / 2, math.pi)
+    for bad in (lambda: config.set_end_angle(0.0), lambda: config.set_start_angle(4.0)):
+        try:
+            bad()
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+    config.set_angle_range(3 * math.pi, 4 * math.pi)
+    assert config.start_angle == 3 * math.pi
+
+    # The top half of the dial as previewed: y grows downward
+    arc = RoseEngineConfig.partial_arc(20.0, 1.0, 180.0, 360.0)
+    assert math.isclose(arc.start_angle, math.pi)
+    assert math.isclose(arc.end_angle, 2 * math.pi)
+    lathe = RoseEngineLathe(arc, CuttingBit.v_shaped(angle=30.0, width=0.5))
+    lathe.generate()
+    tool_path = lathe.get_lines_3d()[0]
+    assert len(tool_path) == arc.resolution + 1
+    assert all(y <= 1e-9 for _, y, _ in tool_path)
+    assert math.isclose(math.atan2(tool_path[0][1], tool_path[0][0]), math.pi)
+
+    try:
+        RoseEngineConfig.partial_arc(20.0, 1.0, 90.0, 45.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
 def test_rosette_pattern():
     """Test RosettePattern creation"""
     from turtles import RosettePattern
@@ -218,6 +960,50 @@ def test_rosette_pattern():
     diamant = RosettePattern.diamant(divisions=12)
     assert diamant is not None
 
+    # Zero counts and non-finite frequencies are rejected
+    from turtles import RoseEngineConfig
+
+    for make in (
+        lambda: RosettePattern.multi_lobe(lobes=0),
+        lambda: RosettePattern.epicycloid(petals=0),
+        lambda: RosettePattern.diamant(divisions=0),
+        lambda: RosettePattern.sinusoidal(frequency=float("nan")),
+        lambda: RoseEngineConfig.classic_multi_lobe(20.0, 0, 1.0),
+    ):
+        try:
+            make()
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+
+def test_rosette_custom_table_smoothing():
+    """Test resampling and corner-preserving smoothing of custom tables"""
+    import math
+
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe, RosettePattern
+
+    table = [math.sin(2 * math.pi * i / 90) + (0.05 if i % 2 else -0.05) for i in range(90)]
+    custom = RosettePattern.custom(table)
+    assert custom.table_len == 90
+    assert RosettePattern.circular().table_len is None
+
+    resampled = custom.resample_table(360)
+    assert resampled.table_len == 360
+
+    smoothed = resampled.smoothed(9, preserve_corners_deg=45.0)
+    assert smoothed.table_len == 360
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=1.0)
+    config.set_rosette(smoothed)
+    lathe = RoseEngineLathe(config, CuttingBit.v_shaped(angle=30.0, width=0.5))
+    lathe.generate()
+
+    try:
+        RosettePattern.custom([])
+        assert False, "Should have raised ValueError for empty table"
+    except ValueError:
+        pass
 
 def test_cutting_bit():
     """Test CuttingBit creation"""
@@ -247,103 +1033,1415 @@ def test_rose_engine_lathe():
 
     assert lathe is not None
 
-    # Generate the pattern
-    lathe.generate()
+    # Generate the pattern
+    lathe.generate()
+
+
+def test_export_config():
+    """Test ExportConfig defaults and a tool radius changing the STL"""
+    from turtles import CuttingBit, ExportConfig, RoseEngineConfig, RoseEngineLathe
+
+    config = ExportConfig()
+    assert (config.depth, config.base_thickness, config.tool_radius) == (0.1, 2.0, 0.0)
+    assert repr(ExportConfig(depth=0.2, tool_radius=0.3)) == "ExportConfig(depth=0.2, base_thickness=2, tool_radius=0.3)"
+
+    lathe = RoseEngineLathe(RoseEngineConfig(base_radius=20.0, amplitude=2.0), CuttingBit.v_shaped(angle=30.0, width=0.5))
+    lathe.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        plain = os.path.join(tmpdir, "plain.stl")
+        keywords = os.path.join(tmpdir, "keywords.stl")
+        offset = os.path.join(tmpdir, "offset.stl")
+        lathe.to_stl(plain, depth=0.2)
+        lathe.to_stl(keywords, depth=0.2, tool_radius=0.3)
+        lathe.to_stl(offset, config=ExportConfig(depth=0.2, tool_radius=0.3))
+        with open(plain, "rb") as a, open(keywords, "rb") as b, open(offset, "rb") as c:
+            plain_data, keyword_data, offset_data = a.read(), b.read(), c.read()
+        assert keyword_data[80:] == offset_data[80:]
+        assert plain_data[80:] != offset_data[80:]
+
+        try:
+            lathe.to_stl(offset, depth=0.2, config=ExportConfig())
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+
+def test_rose_engine_lathe_tool_path():
+    """Test tool path, cut edge and rendered line accessors"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=2.0)
+    lathe = RoseEngineLathe(config, CuttingBit.v_shaped(angle=30.0, width=0.5))
+    assert lathe.get_tool_path() == []
+    assert lathe.get_cut_edges() == ([], [])
+
+    lathe.generate()
+    path = lathe.get_tool_path()
+    assert len(path) > 2
+    assert all(len(p) == 2 for p in path)
+    left, right = lathe.get_cut_edges()
+    assert len(left) == len(right) == len(path)
+    rendered = lathe.get_rendered_lines()
+    assert len(rendered) > 0
+    assert all(len(p) == 2 for line in rendered for p in line)
+    assert lathe.get_kerf() == []
+
+
+def test_rose_engine_spiral():
+    """Test cutting several revolutions as one spiral with a radial feed"""
+    import math
+
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=0.0)
+    assert (config.revolutions, config.radial_feed_per_rev) == (1, 0.0)
+    config.set_revolutions(5)
+    config.set_radial_feed_per_rev(1.0)
+    assert (config.revolutions, config.radial_feed_per_rev) == (5, 1.0)
+    for bad in (lambda: config.set_revolutions(0), lambda: config.set_radial_feed_per_rev(math.nan)):
+        try:
+            bad()
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+    lathe = RoseEngineLathe(config, CuttingBit.v_shaped(angle=30.0, width=0.5))
+    lathe.generate()
+    path = lathe.get_tool_path()
+    assert len(path) == 5 * config.resolution + 1
+    radii = [math.hypot(x, y) for x, y in path]
+    assert math.isclose(radii[0], 20.0)
+    assert math.isclose(radii[-1], 15.0)
+    assert all(b < a for a, b in zip(radii, radii[1:]))
+
+
+def test_rose_engine_fit_arcs():
+    """Test fitting a circular tool path with arcs for G2/G3 output"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe, RosettePattern
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=0.0)
+    config.set_rosette(RosettePattern.circular())
+    lathe = RoseEngineLathe(config, CuttingBit.flat(width=0.5, depth=0.2))
+    lathe.generate()
+    assert lathe.arcs == []
+    try:
+        lathe.fit_arcs(-1.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+    lathe.fit_arcs(0.01)
+    assert 0 < len(lathe.arcs) <= 8
+    assert all(abs(radius - 20.0) <= 0.01 for _, _, radius, _, _ in lathe.arcs)
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "circle.nc")
+        lathe.to_gcode(path)
+        with open(path) as f:
+            gcode = f.read()
+        assert "G3 X" in gcode
+
+
+def test_cutting_bit_kerf_at_depth():
+    """Test that cut width follows the bit shape and depth modulation"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe
+
+    v_bit = CuttingBit.v_shaped(angle=30.0, width=1.0)
+    assert v_bit.kerf_at_depth(0.1) < 0.1
+    assert v_bit.kerf_at_depth(10.0) == 1.0
+    assert v_bit.kerf_at_depth(0.0) == 0.0
+    assert CuttingBit.flat(width=1.0, depth=0.5).kerf_at_depth(0.1) == 1.0
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=2.0)
+    config.with_depth_modulation(0.5, 4.0)
+    lathe = RoseEngineLathe(config, CuttingBit.v_shaped(angle=30.0, width=1.0))
+    lathe.generate()
+    kerf = lathe.get_kerf()
+    assert len(kerf) == len(lathe.get_tool_path())
+    assert max(kerf) > min(kerf)
+
+
+def test_rose_engine_svg_export():
+    """Test rose engine SVG export"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        config = RoseEngineConfig.huit_eight(base_radius=20.0, amplitude=2.0)
+        bit = CuttingBit.v_shaped(angle=30.0, width=0.5)
+        lathe = RoseEngineLathe(config, bit)
+        lathe.generate()
+
+        svg_path = os.path.join(tmpdir, "rose_pattern.svg")
+        lathe.to_svg(svg_path)
+
+        assert os.path.exists(svg_path)
+        assert os.path.getsize(svg_path) > 0
+
+
+def test_rose_engine_svg_shaded():
+    """Test SVG export shaded by the depth map"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe, RoseEngineLatheRun
+
+    def stroke_colors(path):
+        with open(path) as f:
+            svg = f.read()
+        return {rest.split('"', 1)[0] for rest in svg.split('stroke="')[1:]}
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        svg_path = os.path.join(tmpdir, "shaded.svg")
+
+        config = RoseEngineConfig.classic_multi_lobe(base_radius=20.0, lobes=12, amplitude=1.0)
+        config.with_depth_modulation(0.5, 6.0)
+        lathe = RoseEngineLathe(config, CuttingBit.v_shaped(angle=60.0, width=1.0))
+        lathe.generate()
+        lathe.to_svg_shaded(svg_path)
+        assert len(stroke_colors(svg_path)) > 2
+
+        run = RoseEngineLatheRun(config, CuttingBit.v_shaped(angle=60.0, width=1.0), num_passes=3)
+        run.generate()
+        run.to_svg_shaded(svg_path)
+        assert len(stroke_colors(svg_path)) > 2
+
+        flat = RoseEngineConfig.classic_multi_lobe(base_radius=20.0, lobes=12, amplitude=1.0)
+        run = RoseEngineLatheRun(flat, CuttingBit.v_shaped(angle=60.0, width=1.0), num_passes=3)
+        run.generate()
+        run.to_svg_shaded(svg_path)
+        assert len(stroke_colors(svg_path)) == 1
+
+
+def test_rose_engine_svg_styled():
+    """Test SVG export colored by pass index"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun
+
+    def stroke_colors(path):
+        with open(path) as f:
+            svg = f.read()
+        return {rest.split('"', 1)[0] for rest in svg.split('stroke="')[1:]}
+
+    config = RoseEngineConfig.classic_multi_lobe(base_radius=20.0, lobes=12, amplitude=1.0)
+    run = RoseEngineLatheRun(config, CuttingBit.v_shaped(angle=30.0, width=0.5), num_passes=3)
+    run.generate()
+    segments = run.get_segments_with_pass()
+    assert [pass_index for pass_index, _ in segments] == run.get_pass_indices()
+    assert segments[0][1] == run.get_lines()[0]
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        svg_path = os.path.join(tmpdir, "styled.svg")
+        run.to_svg_styled(svg_path, colors=["#ff0000", "#00ff00", "#0000ff"])
+        assert stroke_colors(svg_path) == {"#ff0000", "#00ff00", "#0000ff"}
+
+        run.to_svg_styled(svg_path, colors=["#336699"])
+        assert stroke_colors(svg_path) == {"#336699"}
+
+        for bad in ({"colors": []}, {"colors": ["#ff0000"], "by_depth": True}):
+            try:
+                run.to_svg_styled(svg_path, **bad)
+                assert False, "Should have raised ValueError"
+            except ValueError:
+                pass
+
+
+def test_rose_engine_patterns():
+    """Test all rose engine pattern presets"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe
+
+    bit = CuttingBit.v_shaped(angle=30.0, width=0.5)
+
+    # Test each pattern preset
+    patterns = [
+        RoseEngineConfig.huit_eight(base_radius=20.0, amplitude=2.0),
+        RoseEngineConfig.grain_de_riz(base_radius=20.0, grain_size=1.0, amplitude=1.5),
+        RoseEngineConfig.draperie(base_radius=20.0, wave_frequency=6.0, amplitude=2.0),
+        RoseEngineConfig.diamant(base_radius=20.0, divisions=12, amplitude=1.5),
+    ]
+
+    for config in patterns:
+        lathe = RoseEngineLathe(config, bit)
+        lathe.generate()
+        assert lathe is not None
+
+
+def test_rose_engine_lathe_run():
+    """Test multi-pass rose engine lathe run"""
+    import os
+    import tempfile
+
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun, RosettePattern
+
+    # Create a config
+    config = RoseEngineConfig(base_radius=20.0, amplitude=2.0)
+    config.set_rosette(RosettePattern.multi_lobe(12))
+
+    # Create cutting bit
+    bit = CuttingBit.v_shaped(angle=30.0, width=0.5)
+
+    # Test creation
+    run = RoseEngineLatheRun(config, bit, num_passes=8)
+    assert run is not None
+    assert run.num_passes == 8
+
+    # Test generation
+    run.generate()
+
+    # Test SVG export
+    with tempfile.NamedTemporaryFile(mode="w", suffix=".svg", delete=False) as f:
+        svg_path = f.name
+
+    try:
+        run.to_svg(svg_path)
+        assert os.path.exists(svg_path)
+        assert os.path.getsize(svg_path) > 0
+    finally:
+        if os.path.exists(svg_path):
+            os.unlink(svg_path)
+
+
+def test_rose_engine_lathe_run_amplitude_limit():
+    """Concentric rings that would cross the centre are reported or clamped"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun
+
+    config = RoseEngineConfig.classic_multi_lobe(4.0, 12, 3.0)
+    assert abs(config.max_safe_amplitude() - 4.0) < 1e-9
+    assert not config.clamp_amplitude
+    bit = CuttingBit.v_shaped(angle=30.0, width=0.5)
+
+    run = RoseEngineLatheRun(config, bit, num_passes=5, radius_step=1.0)
+    try:
+        run.try_generate()
+        assert False, "expected ValueError"
+    except ValueError as e:
+        assert "passes [0] of 5" in str(e)
+
+    config.set_clamp_amplitude(True)
+    run = RoseEngineLatheRun(config, bit, num_passes=5, radius_step=1.0)
+    run.try_generate()
+    assert len(run.get_lines()) > 0
+
+    # A ring stepped onto the centre cannot be cut at all
+    run = RoseEngineLatheRun(config, bit, num_passes=5, radius_step=2.0)
+    try:
+        run.try_generate()
+        assert False, "expected ValueError"
+    except ValueError as e:
+        assert "pass 0 of 5" in str(e)
+
+
+def test_rose_engine_lathe_run_patterns():
+    """Test multi-pass rose engine with different patterns"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun, RosettePattern
+
+    bit = CuttingBit.v_shaped(angle=30.0, width=0.1)
+
+    # Test different rosette patterns
+    patterns = [
+        RosettePattern.huit_eight(lobes=8),
+        RosettePattern.grain_de_riz(grain_size=1.0, rows=12),
+        RosettePattern.draperie(frequency=6.0, wave_exponent=1),
+        RosettePattern.diamant(divisions=12),
+    ]
+
+    for pattern in patterns:
+        config = RoseEngineConfig(base_radius=20.0, amplitude=2.0)
+        config.set_rosette(pattern)
+
+        run = RoseEngineLatheRun(config, bit, num_passes=12)
+        run.generate()
+        assert run.num_passes == 12
+
+
+def test_envelope():
+    """Test Envelope weights and their use as amplitude and opacity fades"""
+    import math
+    import re
+
+    from turtles import CuttingBit, Envelope, RoseEngineConfig, RoseEngineLatheRun, RosettePattern
+
+    angular = Envelope.angular_smoothstep(1.0, 2.0, feather=0.25)
+    radial = Envelope.radial_smoothstep(10.0, 20.0, feather=2.0)
+    assert angular.eval(1.0) == 0.0
+    assert angular.eval(1.25) == 1.0
+    assert radial.eval(0.0, radius=20.0) == 0.0
+    product = angular * radial
+    assert product.eval(1.1, 11.0) == angular.eval(1.1, 11.0) * radial.eval(1.1, 11.0)
+    assert Envelope.product(Envelope.constant(0.5), Envelope.constant(0.5)).eval(0.0) == 0.25
+    assert Envelope.table([0.0, 1.0]).eval(math.pi / 2) == 0.5
+    for bad in (lambda: Envelope.table([]), lambda: Envelope.radial_smoothstep(2.0, 1.0)):
+        try:
+            bad()
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+    # No displacement where the amplitude envelope is zero
+    config = RoseEngineConfig(base_radius=20.0, amplitude=1.5)
+    config.set_rosette(RosettePattern.multi_lobe(12))
+    config.set_amplitude_envelope(Envelope.angular_smoothstep(math.pi, 2.0 * math.pi, feather=0.3))
+    run = RoseEngineLatheRun(config, CuttingBit.v_shaped(angle=30.0, width=0.5), num_passes=1, segments_per_pass=1)
+    run.generate()
+    for x, y in run.get_lines()[0]:
+        if 0.1 < math.atan2(y, x) < math.pi - 0.1:
+            assert abs(math.hypot(x, y) - 20.0) < 1e-9
+
+    wf = WatchFace(radius=30.0)
+    wf.add_draperie(num_rings=12, base_radius=12.0)
+    wf.generate()
+    wf.set_opacity_envelope("draperie", 0, Envelope.radial_smoothstep(0.0, 40.0, feather=40.0))
+    try:
+        wf.set_opacity_envelope("sunburst", 0, Envelope.constant(1.0))
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "dial.svg")
+        wf.to_svg(path)
+        with open(path) as f:
+            opacities = [float(v) for v in re.findall(r' opacity="([^"]+)"', f.read())]
+        assert len(opacities) == 12
+        assert opacities == sorted(opacities) and opacities[0] < opacities[-1]
+
+        wf.clear_opacity_envelopes()
+        wf.to_svg(path)
+        with open(path) as f:
+            assert " opacity=" not in f.read()
+
+
+def test_watch_face_layer_styles():
+    """Test per-layer stroke styling in the watch face SVG"""
+    import re
+
+    wf = WatchFace(radius=30.0)
+    wf.add_draperie(num_rings=6, base_radius=12.0)
+    wf.add_paon(num_lines=8, radius=12.0)
+    wf.set_layer_style("draperie", 0, "#8b0000", 0.1, opacity=0.5)
+    assert wf.layer_style("draperie", 0) == ("#8b0000", 0.1, 0.5)
+    assert wf.layer_style("paon", 0) is None
+    try:
+        wf.set_layer_style("paon", 0, "red", 0.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+    wf.generate()
+
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "dial.svg")
+        wf.to_svg(path)
+        with open(path) as f:
+            strokes = re.findall(r'<path[^>]* stroke="([^"]+)"', f.read())
+        assert strokes == ["#8b0000"] * 6 + ["#1a1a1a"] * 8
+
+        wf.clear_layer_styles()
+        wf.to_svg(path)
+        with open(path) as f:
+            assert "#8b0000" not in f.read()
+
+
+def test_watch_face_oval_dial():
+    """Test an oval dial clips the pattern and draws ellipses"""
+    wf = WatchFace.oval(36.0, 30.0)
+    assert wf.radius == 36.0
+    assert wf.semi_axes == (36.0, 30.0)
+    assert WatchFace(radius=30.0).semi_axes == (30.0, 30.0)
+    try:
+        WatchFace.oval(50.0, 30.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+    wf.add_inner()
+    wf.add_outer()
+    wf.add_draperie(num_rings=6, base_radius=12.0)
+    wf.generate()
+
+    with tempfile.TemporaryDirectory() as tmp:
+        path = os.path.join(tmp, "dial.svg")
+        wf.to_svg(path)
+        with open(path) as f:
+            svg = f.read()
+        assert svg.count("<ellipse") == 3
+        assert '<ellipse cx="0" cy="0" rx="36" ry="30"/>' in svg
+
+        stl = os.path.join(tmp, "dial.stl")
+        wf.to_stl(stl)
+        assert os.path.getsize(stl) > 84
+
+
+def test_png_previews():
+    """Test RGBA PNG previews of a watch face, a pattern and a lathe run"""
+    import struct
+    import zlib
+
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun, RosettePattern
+
+    def decode(png):
+        assert png[:8] == b"\x89PNG\r\n\x1a\n"
+        width, height, depth, color_type = struct.unpack(">IIBB", png[16:26])
+        assert (depth, color_type) == (8, 6)
+        data, pos = b"", 8
+        while pos < len(png):
+            (length,) = struct.unpack(">I", png[pos : pos + 4])
+            if png[pos + 4 : pos + 8] == b"IDAT":
+                data += png[pos + 8 : pos + 8 + length]
+            pos += 12 + length
+        raw = zlib.decompress(data)
+        stride = 4 * width + 1
+        dark = sum(
+            1 for row in range(height) for x in range(width) if raw[row * stride + 1 + 4 * x] < 128
+        )
+        return width, height, dark
+
+    wf = WatchFace(radius=38.0)
+    wf.add_inner()
+    wf.add_draperie(num_rings=6, base_radius=12.0)
+    wf.generate()
+
+    pattern = GuillochePattern(radius=38.0)
+    pattern.add_layer(HorizontalSpirograph(38.0, 0.75, 20.0, 3, 360))
+    pattern.generate()
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=1.0)
+    config.set_rosette(RosettePattern.multi_lobe(12))
+    run = RoseEngineLatheRun(config, CuttingBit.v_shaped(angle=30.0, width=0.5), num_passes=3)
+    try:
+        run.render_bytes()
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+    run.generate()
+
+    with tempfile.TemporaryDirectory() as tmp:
+        for item in (wf, pattern, run):
+            png = item.render_bytes(160, 120)
+            width, height, dark = decode(png)
+            assert (width, height) == (160, 120)
+            assert dark > 0
+
+            path = os.path.join(tmp, "preview.png")
+            item.to_png(path, 64, 32)
+            with open(path, "rb") as f:
+                assert decode(f.read())[:2] == (64, 32)
+
+    try:
+        wf.render_bytes(0, 100)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
+def test_rose_engine_lathe_run_segment_gaps():
+    """Test the drawn fraction and per-pass stagger of lathe run segments"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun, RosettePattern
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=1.0)
+    config.set_rosette(RosettePattern.multi_lobe(12))
+    bit = CuttingBit.v_shaped(angle=30.0, width=0.5)
+
+    default = RoseEngineLatheRun(config, bit, num_passes=4, segments_per_pass=8)
+    default.generate()
+    short = RoseEngineLatheRun(
+        config, bit, num_passes=4, segments_per_pass=8, segment_draw_ratio=0.3, segment_phase_offset=0.5
+    )
+    short.generate()
+    assert len(short.get_lines()) == len(default.get_lines()) == 32
+    assert len(short.get_lines()[0]) < len(default.get_lines()[0])
+    try:
+        RoseEngineLatheRun(config, bit, num_passes=4, segment_draw_ratio=0.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
+def test_rose_engine_lathe_run_stl_and_step():
+    """Test solid exports of a multi-pass lathe run"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun, RosettePattern
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=1.0)
+    config.set_rosette(RosettePattern.multi_lobe(12))
+    bit = CuttingBit.v_shaped(angle=30.0, width=0.5)
+
+    with tempfile.TemporaryDirectory() as tmp:
+        stl = os.path.join(tmp, "run.stl")
+        sizes, groove_triangles = [], []
+        for segments in (8, 16):
+            run = RoseEngineLatheRun(config, bit, num_passes=4, segments_per_pass=segments)
+            try:
+                run.to_stl(stl)
+                assert False, "Should have raised IOError"
+            except IOError:
+                pass
+            run.generate()
+            run.to_stl(stl, depth=0.2, base_thickness=1.0)
+            assert len(run.get_lines()) == 4 * segments
+            sizes.append(os.path.getsize(stl))
+            # A V-groove of six triangles per segment, one per end cap
+            lines = run.get_lines()
+            groove_triangles.append(6 * sum(len(line) - 1 for line in lines) + 2 * len(lines))
+        # 50 bytes per triangle over the same base slab
+        assert sizes[0] - sizes[1] == 50 * (groove_triangles[0] - groove_triangles[1])
+
+        step = os.path.join(tmp, "run.stp")
+        run.to_step(step)
+        with open(step) as f:
+            content = f.read()
+        assert content.count("CARTESIAN_POINT") == sum(len(line) for line in run.get_lines())
+
+
+def test_rose_engine_lathe_run_machine_job():
+    """Test exporting a lathe run as a machine cutting sequence"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun, RosettePattern
+
+    config = RoseEngineConfig(base_radius=20.0, amplitude=1.5)
+    config.set_rosette(RosettePattern.multi_lobe(12))
+    bit = CuttingBit.v_shaped(angle=30.0, width=0.5)
+
+    run = RoseEngineLatheRun(config, bit, num_passes=12)
+    job = run.to_machine_job([(RosettePattern.multi_lobe(12), 3)])
+
+    passes = job["passes"]
+    assert len(passes) == 12
+    for i, p in enumerate(passes):
+        assert p["rosette_slot"] == 3
+        assert p["amplitude"] == 1.5
+        assert abs(p["phase"] - 30.0 * i) < 1e-9
+        assert p["direction"] == "ccw"
+
+    diamant = RoseEngineLatheRun.diamant(num_circles=12, circle_radius=10.0)
+    try:
+        diamant.to_machine_job([])
+        assert False, "Should have raised ValueError for diamant mode"
+    except ValueError as e:
+        assert "diamant" in str(e)
+
+
+def test_generate_progressive():
+    """Test coarse-to-fine generation ends identical to plain generate()"""
+    from turtles import CuttingBit, DraperieLayer, PaonLayer, RoseEngineConfig, RoseEngineLatheRun
+
+    def run():
+        config = RoseEngineConfig.classic_multi_lobe(20.0, 12, 1.0)
+        return RoseEngineLatheRun(config, CuttingBit.v_shaped(angle=30.0, width=0.5), num_passes=24, segments_per_pass=1)
+
+    for make in (DraperieLayer, PaonLayer, run):
+        progressive = make()
+        handle = progressive.generate_progressive([0.1, 0.3, 1.0])
+        assert len(handle.levels) == 3 and handle.levels[-1] == 1.0
+        assert handle.current_level() is None
+
+        counts = []
+        while handle.advance():
+            counts.append(sum(len(line) for line in progressive.get_lines()))
+        assert handle.is_complete
+        assert handle.current_level() == 2
+        assert counts == sorted(counts) and len(set(counts)) == 3
+
+        plain = make()
+        plain.generate()
+        assert progressive.get_lines() == plain.get_lines()
+
+
+def test_diff_svg():
+    """Test the differential preview on lathe runs and guilloche patterns"""
+    import os
+    import tempfile
+
+    from turtles import RoseEngineLatheRun
+
+    def draperie(phase_shift):
+        run = RoseEngineLatheRun.draperie(
+            num_rings=10,
+            base_radius=20.0,
+            radius_step=0.5,
+            phase_shift=phase_shift,
+            phase_oscillations=1.0,
+            resolution=720,
+            phase_exponent=1,
+            circular_phase=0.0,
+        )
+        run.generate()
+        return run
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "diff.svg")
+        summary = draperie(0.12).diff_svg(draperie(0.1), path, tolerance=0.02)
+        assert summary == {"unchanged": 0, "moved": 10, "added": 0, "removed": 0}
+        assert os.path.exists(path)
+
+        a = GuillochePattern(radius=38.0)
+        a.add_layer(HorizontalSpirograph(38.0, 0.75, 0.6, 10, 100))
+        a.generate()
+        summary = a.diff_svg(a, path)
+        assert summary["unchanged"] == 1
+        assert summary["moved"] == summary["added"] == summary["removed"] == 0
+
+        try:
+            a.diff_svg(a, path, tolerance=0.0)
+            assert False, "Should have raised ValueError for zero tolerance"
+        except ValueError:
+            pass
+
+def test_engraved_text():
+    """Test single-stroke engraved text layers and dial numerals"""
+    import math
+    import os
+    import tempfile
+
+    from turtles import EngravedTextLayer
+
+    numeral = EngravedTextLayer("XII", height=4.0, radius=32.0)
+    numeral.generate()
+    lines = numeral.get_lines()
+    assert len(lines) == 4
+    for line in lines:
+        for x, y in line:
+            assert 27.0 < math.hypot(x, y) < 37.0
+            assert y < 0.0
+
+    assert EngravedTextLayer.supports("a")
+    assert not EngravedTextLayer.supports("é")
+    for bad in ({"text": ""}, {"text": "é"}, {"text": "12", "height": 0.0}, {"text": "12", "direction": "up", "radius": 30.0}):
+        try:
+            EngravedTextLayer(**bad)
+            assert False, f"Should have raised ValueError for {bad}"
+        except ValueError:
+            pass
+
+    face = WatchFace(radius=38.0)
+    face.add_text("XII", height=4.0, radius=32.0)
+    face.add_text("GENEVE", height=2.0, origin=(0.0, 15.0))
+    face.add(numeral)
+    face.generate()
+
+    pattern = GuillochePattern(radius=38.0)
+    pattern.add_text("6", radius=32.0, hour=6, direction="counterclockwise")
+    pattern.generate()
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "text.svg")
+        face.to_svg(path)
+        assert os.path.getsize(path) > 0
+        numeral.to_svg(path)
+        assert os.path.exists(path)
+
+
+def test_chapter_ring():
+    """Test the railroad chapter ring and its cutouts in solid exports"""
+    import math
+    import os
+    import tempfile
+
+    from turtles import ChapterRingLayer
+
+    ring = ChapterRingLayer(30.0, 34.0, index_cutouts=[(12, 1.5, 3.0), (3, 1.5, 3.0)])
+    ring.generate()
+    ticks = ring.get_ticks()
+    assert len(ticks) == 60
+    assert [major for _, major in ticks] == [i % 5 == 0 for i in range(60)]
+    for points, _ in ticks:
+        for x, y in points:
+            r = math.hypot(x, y)
+            assert abs(r - 30.0) < 1e-9 or abs(r - 34.0) < 1e-9
+    cutouts = ring.get_cutouts()
+    assert len(cutouts) == 2
+    cx = sum(x for x, _ in cutouts[1]) / 4
+    cy = sum(y for _, y in cutouts[1]) / 4
+    assert abs(cx - 32.0) < 1e-9 and abs(cy) < 1e-9
+    assert len(ring.get_lines()) == 2 + 60 + 2
+
+    try:
+        ChapterRingLayer(34.0, 30.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+    face = WatchFace(radius=38.0)
+    face.add_chapter_ring(30.0, 34.0, index_cutouts=[(6, 1.5, 3.0)])
+    face.add(ring)
+    face.add_center_hole()
+    face.generate()
+    assert len(face.get_apertures()) == 1 + 1 + 2
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "ring.dxf")
+        face.to_dxf_profile(path)
+        with open(path) as f:
+            assert f.read().count("LWPOLYLINE") == 1 + 4
+        path = os.path.join(tmpdir, "ring.stl")
+        face.to_stl(path)
+        assert os.path.getsize(path) > 0
+        path = os.path.join(tmpdir, "ring.svg")
+        ring.to_svg(path)
+        assert os.path.getsize(path) > 0
+
+
+def test_index_markers():
+    """Test index markers, exclusions, styling and the minute track"""
+    import math
+
+    from turtles import IndexMarkersLayer
+
+    bars = IndexMarkersLayer(24.0, 27.0, length=2.0, width=0.5, exclude=[(3, 0), (6, 0)])
+    bars.generate()
+    markers = bars.get_markers()
+    assert [i for i, _ in markers] == [i for i in range(12) if i not in (3, 6)]
+    assert len(bars.get_lines()) == 10
+    for _, strokes in markers:
+        assert len(strokes) == 1 and strokes[0][0] == strokes[0][-1]
+
+    dots = IndexMarkersLayer(24.0, 26.0, shape="dot", count=60, radius=0.4)
+    dots.generate()
+    assert len(dots.get_lines()) == 60
+    for x, y in dots.get_lines()[0]:
+        assert 24.6 - 1e-9 <= math.hypot(x, y) <= 25.4 + 1e-9
+
+    roman = IndexMarkersLayer(22.0, 25.0, shape="roman", height=2.5)
+    roman.generate()
+    # XII, then I, II and III
+    assert [len(strokes) for _, strokes in roman.get_markers()[:4]] == [4, 1, 2, 3]
+
+    for bad in (
+        lambda: IndexMarkersLayer(24.0, 27.0, count=24),
+        lambda: IndexMarkersLayer(24.0, 27.0, shape="star"),
+        lambda: IndexMarkersLayer(24.0, 27.0, shape="roman", count=60),
+        lambda: IndexMarkersLayer(24.0, 27.0, exclude=[(3, 30)]),
+    ):
+        try:
+            bad()
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+    face = WatchFace(radius=30.0)
+    face.add_minute_track(27.0, 28.5)
+    face.add_index_markers(24.0, 27.0, exclude=[(3, 0)], stroke_color="#b8860b", stroke_width=0.08)
+    face.add(dots)
+    face.generate()
+    assert face.layer_style("index_markers", 0) == ("#b8860b", 0.08, 1.0)
+    assert face.layer_style("index_markers", 1) is None
+    assert len(face.get_lines()) == (2 + 60) + 11 + 60
+
+
+def test_chapter_ring_motifs():
+    """Test wave, rope and dot border motifs stay inside the chapter ring"""
+    import math
+
+    from turtles import ChapterRingLayer, FlinqueLayer
+
+    for motif, count in (("wave", 1), ("rope", 2), ("dots", 48)):
+        ring = ChapterRingLayer(30.0, 34.0, minutes=48, motif=motif, amplitude=5.0, frequency=2, diameter=1.0)
+        assert ring.motif == motif
+        ring.generate()
+        assert ring.get_ticks() == []
+        lines = ring.get_lines()[2:]
+        assert len(lines) == count
+        for line in lines:
+            assert line[0] == line[-1]
+            assert all(30.0 - 1e-9 <= math.hypot(x, y) <= 34.0 + 1e-9 for x, y in line)
+
+    try:
+        ChapterRingLayer(30.0, 34.0, motif="zigzag")
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+    # A rope border around a central flinqué
+    face = WatchFace(radius=38.0)
+    face.add_flinque_layer(FlinqueLayer(radius=28.0))
+    face.add_chapter_ring(30.0, 34.0, minutes=36, motif="rope")
+    face.generate()
+    assert max(math.hypot(x, y) for line in face.get_lines() for x, y in line) <= 34.0 + 1e-9
+
+
+def test_phyllotaxis_layer():
+    """Test the golden-angle spiral layer and its watch face integration"""
+    import math
+
+    from turtles import PhyllotaxisLayer
+
+    layer = PhyllotaxisLayer(num_points=1000, scale_c=1.0, radius=10.0, size=0.5, edge="drop")
+    layer.generate()
+    assert len(layer.get_drawn_cells()) == 91
+    x, y = layer.cell_center(5)
+    assert abs(math.hypot(x, y) - math.sqrt(5)) < 1e-9
+
+    ticks = PhyllotaxisLayer(num_points=600, scale_c=0.7, radius=12.0, style="tick", size=1.0)
+    ticks.generate()
+    assert all(len(line) == 2 for line in ticks.get_lines())
+    assert all(math.hypot(x, y) <= 12.0 + 1e-9 for line in ticks.get_lines() for x, y in line)
+
+    try:
+        PhyllotaxisLayer(style="star")
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+    face = WatchFace(radius=38.0)
+    face.add_phyllotaxis(num_points=400, scale_c=1.2, style="minilobe", size=0.5, inner_exclusion_radius=2.0)
+    face.add(PhyllotaxisLayer.at_clock(3, 0, 10.0, num_points=50, scale_c=0.5, radius=5.0))
+    face.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "phyllotaxis.svg")
+        face.to_svg(path)
+        assert os.path.getsize(path) > 0
+        path = os.path.join(tmpdir, "cells.svg")
+        layer.to_svg(path)
+        assert os.path.getsize(path) > 0
+
+
+def test_grain_de_riz_layer():
+    """Test the rice grain layer, its rose engine twin and watch face integration"""
+    from turtles import GrainDeRizLayer, RoseEngineLatheRun
+
+    layer = GrainDeRizLayer(num_rows=3, grains_per_row=20, resolution=16)
+    layer.generate()
+    lines = layer.get_lines()
+    assert len(lines) == 60
+    assert all(len(grain) == 17 and grain[0] == grain[-1] for grain in lines)
+
+    run = RoseEngineLatheRun.grain_de_riz(num_rows=3, grains_per_row=20, resolution=16)
+    run.generate()
+    assert run.get_lines() == lines
+
+    try:
+        GrainDeRizLayer(grain_width=0.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+    face = WatchFace(radius=38.0)
+    face.add_grain_de_riz(num_rows=4, grains_per_row=36, base_radius=12.0)
+    face.add(GrainDeRizLayer.at_clock(6, 0, 15.0, num_rows=2, grains_per_row=12, base_radius=3.0))
+    face.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "grain_de_riz.svg")
+        face.to_svg(path)
+        assert os.path.getsize(path) > 0
+
+
+def test_soleil_layer():
+    """Test straight, curved and tapered sunray layers on a watch face"""
+    import math
+
+    from turtles import SoleilLayer
+
+    layer = SoleilLayer(num_rays=24, inner_radius=2.0, outer_radius=12.0, curvature=0.05, resolution=16)
+    layer.generate()
+    lines = layer.get_lines()
+    assert len(lines) == 24
+    assert all(abs(math.hypot(*ray[-1]) - 12.0) < 1e-9 for ray in lines)
+
+    wedges = SoleilLayer(num_rays=12, outer_radius=10.0, taper=0.3)
+    wedges.generate()
+    assert all(wedge[0] == wedge[-1] for wedge in wedges.get_lines())
+
+    try:
+        SoleilLayer(inner_radius=10.0, outer_radius=10.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+    face = WatchFace(radius=38.0)
+    face.add_soleil(num_rays=120, outer_radius=30.0, curvature=-0.02)
+    face.add(SoleilLayer.at_clock(6, 0, 15.0, num_rays=36, outer_radius=5.0, taper=0.2))
+    face.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "soleil.svg")
+        face.to_svg(path)
+        assert os.path.getsize(path) > 0
+
+
+def test_cotes_de_geneve_layer():
+    """Test Geneva stripes over a circle and an annulus on a watch face"""
+    import math
+
+    from turtles import CotesDeGeneveLayer
+
+    layer = CotesDeGeneveLayer(stripe_width=2.0, arc_sagitta=0.8, radius=10.0, resolution=20)
+    layer.generate()
+    lines = layer.get_lines()
+    assert lines
+    assert all(math.hypot(x, y) <= 10.0 + 1e-9 for line in lines for x, y in line)
+    assert layer.stripe_layout() == (11, 2.0)
+
+    ring = CotesDeGeneveLayer(num_stripes=8, radius=10.0, inner_radius=4.0, lines_per_stripe=2)
+    ring.generate()
+    assert all(math.hypot(x, y) >= 4.0 - 1e-9 for line in ring.get_lines() for x, y in line)
+
+    try:
+        CotesDeGeneveLayer(radius=10.0, inner_radius=10.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+    face = WatchFace(radius=38.0)
+    face.add_cotes_de_geneve(stripe_width=3.0, radius=30.0, angle=math.pi / 4)
+    face.add(CotesDeGeneveLayer.at_clock(6, 0, 15.0, radius=5.0, stripe_width=1.0))
+    face.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "cotes.svg")
+        face.to_svg(path)
+        assert os.path.getsize(path) > 0
+
+
+def test_tapisserie_layer():
+    """Test waffle cells clipped to the circle and their channels on a watch face"""
+    import math
+
+    from turtles import TapisserieLayer
+
+    layer = TapisserieLayer(cell_size=1.0, channel_width=0.25, radius=5.1, truncate_corners=True)
+    layer.generate()
+    cells = layer.get_cell_lines()
+    channels = layer.get_channel_lines()
+    assert len(layer.get_lines()) == len(cells) + len(channels)
+    assert all(cell[0] == cell[-1] for cell in cells)
+    assert all(math.hypot(x, y) <= 5.1 + 1e-9 for line in layer.get_lines() for x, y in line)
+    # Boundary cells are clipped to the circle rather than dropped
+    assert any(abs(math.hypot(x, y) - 5.1) < 1e-9 for cell in cells for x, y in cell)
+    assert all(len(line) == 2 for line in channels)
+
+    try:
+        TapisserieLayer(channel_width=0.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+    face = WatchFace(radius=38.0)
+    face.add_tapisserie(cell_size=1.5, channel_width=0.4, radius=30.0, rotation=math.pi / 4)
+    face.add(TapisserieLayer.at_clock(6, 0, 15.0, radius=5.0, cell_size=0.5, channel_width=0.2))
+    face.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "tapisserie.svg")
+        face.to_svg(path)
+        assert os.path.getsize(path) > 0
+
+
+def test_vague_layer():
+    """Test wave lines clipped to the circle in phase groups on a watch face"""
+    import math
+
+    from turtles import VagueLayer
+
+    layer = VagueLayer(num_lines=12, radius=6.0, wave_amplitude=0.2, wave_length=4.0, group_size=3)
+    layer.generate()
+    lines = layer.get_lines()
+    groups = layer.get_line_groups()
+    assert len(lines) == 12
+    assert groups == [k // 3 for k in range(12)]
+    assert all(math.hypot(x, y) <= 6.0 + 1e-9 for line in lines for x, y in line)
+    # Every wave ends exactly on the circle
+    assert all(abs(math.hypot(*line[0]) - 6.0) < 1e-9 and abs(math.hypot(*line[-1]) - 6.0) < 1e-9 for line in lines)
+
+    try:
+        VagueLayer(wave_length=0.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+    face = WatchFace(radius=38.0)
+    face.add_vague(num_lines=60, radius=30.0, sweep_angle=math.pi / 6)
+    face.add(VagueLayer.at_clock(6, 0, 15.0, num_lines=10, radius=5.0, wave_length=3.0))
+    face.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "vague.svg")
+        face.to_svg(path)
+        assert os.path.getsize(path) > 0
+
+
+def test_imported_svg_layer():
+    """Test re-importing an exported clous de Paris SVG as a layer"""
+    from turtles import ClousDeParisLayer
+
+    layer = ClousDeParisLayer(spacing=1.5, radius=12.0)
+    layer.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "clous.svg")
+        layer.to_svg(path)
+
+        pattern = GuillochePattern(radius=38.0)
+        assert pattern.add_imported_svg(path) == []
+        face = WatchFace(radius=38.0)
+        assert face.add_imported_svg(path) == []
+
+    warnings = face.add_imported_svg('<svg><path d="M0 0 L1 0"/><path d="M0 0 C1 1 2 1 3 0"/></svg>')
+    assert len(warnings) == 1
+    face.add_imported_layer([[(0.0, 0.0), (5.0, 5.0)]])
+    face.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "face.svg")
+        face.to_svg(path)
+        assert os.path.getsize(path) > 0
+
+    try:
+        face.add_imported_svg(os.path.join("no", "such", "file.svg"))
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
+def test_layer_transforms():
+    """Test mirroring, rotating and replicating a layer's lines"""
+    import math
+
+    from turtles import DiamantLayer
+
+    layer = DiamantLayer.with_center(num_circles=12, circle_radius=5.0, center_x=12.0, center_y=0.0)
+    try:
+        layer.mirrored_x()
+        assert False, "Should have raised IOError"
+    except IOError:
+        pass
+    layer.generate()
+    lines = layer.get_lines()
+
+    for (x, y), (mx, my) in zip(lines[0], layer.mirrored_x()[0]):
+        assert mx == -x and my == y
+    for (x, y), (rx, ry) in zip(lines[0], layer.rotated(math.pi / 2)[0]):
+        assert abs(rx + y) < 1e-9 and abs(ry - x) < 1e-9
+
+    copies = layer.replicated_radial(3, 20.0)
+    assert len(copies) == 3
+    pattern = GuillochePattern(radius=38.0)
+    pattern.add_raw_lines(layer.mirrored_y())
+    for copy in copies:
+        pattern.add_raw_lines(copy)
+    pattern.generate()
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "copies.svg")
+        pattern.to_svg(path)
+        with open(path) as f:
+            assert f.read().count('id="imported-') == 4
+
+    try:
+        layer.replicated_radial(0, 20.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
+def test_validate_and_generate_lenient():
+    """Test validating layers and lenient generation"""
+    from turtles import DiamantLayer, LimaconLayer
+
+    face = WatchFace(radius=38.0)
+    face.add(DiamantLayer(num_circles=12, circle_radius=15.0))
+    face.add(LimaconLayer(num_curves=12, base_radius=10.0, amplitude=8.0))
+    assert face.validate() == []
+
+    outcome = face.generate_lenient()
+    assert outcome == {"generated": 2, "skipped": []}
+
+    face.try_generate()
+
+
+def test_edit_undo_redo():
+    """Test undoable edits restore the layer list and lines exactly"""
+    from turtles import DiamantLayer, LimaconLayer
+
+    face = WatchFace(radius=38.0)
+    face.add(DiamantLayer(num_circles=12, circle_radius=15.0))
+    face.add(LimaconLayer(num_curves=12, base_radius=10.0, amplitude=8.0))
+    face.generate()
+    lines = face.get_lines()
+
+    face.apply("add_layer", layer=LimaconLayer(num_curves=6, base_radius=12.0, amplitude=3.0))
+    face.apply("move_layer", kind="limacon", index=1, to=0)
+    assert face.regenerate_stale() == 1
+    edited = face.get_lines()
+    assert len(edited) > len(lines)
+
+    assert face.undo()
+    assert face.undo()
+    assert not face.undo()
+    assert face.get_lines() == lines
+
+    assert face.redo()
+    assert face.redo()
+    face.regenerate_stale()
+    assert face.get_lines() == edited
+
+    try:
+        face.apply("set_layer_center", kind="text", index=0, x=0.0, y=0.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
+def test_rose_engine_lathe_simulation():
+    """Test running-engine simulation frames and SVG frame export"""
+    import math
+    import os
+    import tempfile
+
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe
+
+    config = RoseEngineConfig.classic_multi_lobe(20.0, 12, 1.0)
+    lathe = RoseEngineLathe(config, CuttingBit.v_shaped(angle=30.0, width=0.5))
+    lathe.generate()
+
+    frames = lathe.simulate(12)
+    assert len(frames) == 13
+    for frame in frames:
+        x, y = frame["tool_contact_point"]
+        assert abs(math.atan2(y, x)) < 1e-12
+    assert frames[0]["cut_so_far_end_index"] == 1
+
+    with tempfile.TemporaryDirectory() as d:
+        lathe.simulation_to_svg_frames(d, 3)
+        assert sorted(os.listdir(d)) == ["frame_0000.svg", "frame_0001.svg", "frame_0002.svg", "frame_0003.svg"]
+
+def test_rose_engine_lathe_run_banded():
+    """Test a banded lathe run keeps each band's rings inside the band"""
+    import math
+
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun
+
+    bands = [
+        {"r_inner": 4.0, "r_outer": 9.0, "rings": 4, "config": RoseEngineConfig.classic_multi_lobe(0.0, 12, 1.5)},
+        {"r_inner": 9.0, "r_outer": 15.0, "rings": 6, "config": RoseEngineConfig.wave(0.0, 8.0, 2.0)},
+        {"r_inner": 15.0, "r_outer": 20.0, "rings": 3, "config": RoseEngineConfig.sunburst(0.0, 24, 0.2)},
+    ]
+    run = RoseEngineLatheRun.banded(bands, CuttingBit.v_shaped(angle=30.0, width=0.5))
+    run.generate()
+
+    lines = run.get_lines()
+    indices = run.get_band_indices()
+    assert len(lines) == 13
+    assert indices == [0] * 4 + [1] * 6 + [2] * 3
+    for line, b in zip(lines, indices):
+        for x, y in line:
+            r = math.hypot(x, y)
+            assert bands[b]["r_inner"] < r < bands[b]["r_outer"]
+
+    overlapping = [dict(bands[0]), dict(bands[1], r_inner=8.0)]
+    try:
+        RoseEngineLatheRun.banded(overlapping, CuttingBit.v_shaped(angle=30.0, width=0.5))
+        assert False, "Should have raised ValueError for overlapping bands"
+    except ValueError:
+        pass
+
+def test_rose_engine_lathe_run_pass_overrides():
+    """Test ramping the amplitude and swapping the rosette per pass"""
+    import math
+
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun, RosettePattern
+
+    config = RoseEngineConfig.classic_multi_lobe(20.0, 12, 1.0)
+    run = RoseEngineLatheRun(config, CuttingBit.v_shaped(angle=30.0, width=0.5), num_passes=4)
+    run.set_pass_overrides([{"amplitude": 0.2 * (i + 1)} for i in range(3)] + [{"rosette": RosettePattern.multi_lobe(6)}])
+    run.generate()
+
+    def deviation(pass_index):
+        radii = [
+            math.hypot(x, y)
+            for line, p in zip(run.get_lines(), run.get_pass_indices())
+            if p == pass_index
+            for x, y in line
+        ]
+        return max(radii) - min(radii)
+
+    assert deviation(0) < deviation(1) < deviation(2)
+    assert abs(deviation(3) - 2.0) < 0.05
+
+    for bad in ([{"amplitude": -1.0}], [{"depth": 1.0}], [{}] * 5):
+        try:
+            run.set_pass_overrides(bad)
+            assert False, f"Should have raised ValueError for {bad}"
+        except ValueError:
+            pass
+    run.clear_pass_overrides()
+
+
+def test_rose_engine_lathe_run_tag_passes():
+    """Test even/odd pass tagging and per-tag SVG export"""
+    import os
+    import re
+    import tempfile
+
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun
+
+    config = RoseEngineConfig.classic_multi_lobe(20.0, 12, 1.0)
+    run = RoseEngineLatheRun(config, CuttingBit.v_shaped(angle=30.0, width=0.5), num_passes=12)
+    run.generate()
+    run.tag_passes(lambda p: p % 2)
+
+    lines = run.get_lines()
+    assert len(run.lines_for_tag(0)) + len(run.lines_for_tag(1)) == len(lines)
+    assert run.get_line_tags() == [p % 2 for p in run.get_pass_indices()]
+
+    with tempfile.TemporaryDirectory() as d:
+        paths = run.to_svg_by_tag(d, "dial")
+        assert [os.path.basename(p) for p in paths] == ["dial_tag0.svg", "dial_tag1.svg"]
+        contents = [open(p).read() for p in paths]
+
+    assert sum(c.count("<path") for c in contents) == len(lines)
+    view_boxes = [re.search(r'viewBox="[^"]*"', c).group(0) for c in contents]
+    assert view_boxes[0] == view_boxes[1]
+
+
+def test_rose_engine_animation_frames():
+    """Test pass-by-pass frames and rosette phase sweeps"""
+    import math
+
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe, RoseEngineLatheRun
+
+    config = RoseEngineConfig.classic_multi_lobe(20.0, 12, 1.0)
+    run = RoseEngineLatheRun(config, CuttingBit.v_shaped(angle=30.0, width=0.5), num_passes=4)
+    assert run.get_frames(4) == []
+    run.generate()
 
+    frames = run.get_frames(4)
+    assert len(frames) == 4
+    for earlier, later in zip(frames, frames[1:]):
+        assert len(earlier) < len(later)
+        assert later[: len(earlier)] == earlier
+    assert frames[-1] == run.get_lines()
 
-def test_rose_engine_svg_export():
-    """Test rose engine SVG export"""
-    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe
+    with tempfile.TemporaryDirectory() as d:
+        paths = run.to_svg_frames(d, 4)
+        assert [os.path.basename(p) for p in paths] == [f"frame_{k:04}.svg" for k in range(4)]
 
-    with tempfile.TemporaryDirectory() as tmpdir:
-        config = RoseEngineConfig.huit_eight(base_radius=20.0, amplitude=2.0)
-        bit = CuttingBit.v_shaped(angle=30.0, width=0.5)
-        lathe = RoseEngineLathe(config, bit)
-        lathe.generate()
+    lathe = RoseEngineLathe(config, CuttingBit.v_shaped(angle=30.0, width=0.5))
+    sweep = lathe.frames_phase_sweep(6, 0.0, math.pi / 6.0)
+    assert len(sweep) == 6
+    assert sweep[0] != sweep[3]
 
-        svg_path = os.path.join(tmpdir, "rose_pattern.svg")
-        lathe.to_svg(svg_path)
+    try:
+        lathe.frames_phase_sweep(3, 0.0, float("inf"))
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
 
-        assert os.path.exists(svg_path)
-        assert os.path.getsize(svg_path) > 0
 
+def test_rose_engine_lathe_run_depth_passes():
+    """Test repeating passes at stepped depths for deep cuts"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun
 
-def test_rose_engine_patterns():
-    """Test all rose engine pattern presets"""
-    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe
+    config = RoseEngineConfig.classic_multi_lobe(20.0, 12, 1.0)
+    run = RoseEngineLatheRun(config, CuttingBit.flat(0.5, 0.4), num_passes=4)
+    run.generate()
 
-    bit = CuttingBit.v_shaped(angle=30.0, width=0.5)
+    deep = run.expand_depth_passes(0.15)
+    assert deep.depth_steps() == [0.15, 0.3, 0.4]
+    lines = run.get_lines()
+    per_pass = run.get_pass_indices().count(0)
+    assert len(deep.get_lines()) == 3 * len(lines)
+    assert deep.get_line_depths()[: 3 * per_pass] == [0.15] * per_pass + [0.3] * per_pass + [0.4] * per_pass
+    assert deep.get_lines()[per_pass:2 * per_pass] == lines[:per_pass]
+    assert abs(deep.cut_length() - 3 * run.cut_length()) < 1e-6
+
+    layered = run.expand_depth_passes(0.15, order="pass_first")
+    assert layered.get_lines()[: len(lines)] == lines
+    assert set(layered.get_line_depths()[: len(lines)]) == {0.15}
+
+    for bad in ({"max_depth_per_pass": 0.0}, {"max_depth_per_pass": 0.1, "order": "sideways"}):
+        try:
+            run.expand_depth_passes(**bad)
+            assert False, f"Should have raised ValueError for {bad}"
+        except ValueError:
+            pass
+
+
+def test_rose_engine_run_tool_wear():
+    """Test simulating bit wear and resharpening across passes"""
+    import math
+    from turtles import CuttingBit, RoseEngineConfig, RosettePattern, RoseEngineLatheRun
 
-    # Test each pattern preset
-    patterns = [
-        RoseEngineConfig.huit_eight(base_radius=20.0, amplitude=2.0),
-        RoseEngineConfig.grain_de_riz(base_radius=20.0, grain_size=1.0, amplitude=1.5),
-        RoseEngineConfig.draperie(base_radius=20.0, wave_frequency=6.0, amplitude=2.0),
-        RoseEngineConfig.diamant(base_radius=20.0, divisions=12, amplitude=1.5),
-    ]
+    run = RoseEngineLatheRun.diamant(10, 10.0, 360)
+    run.generate()
+    perimeter = 360 * 20.0 * math.sin(math.pi / 360)
 
-    for config in patterns:
-        lathe = RoseEngineLathe(config, bit)
-        lathe.generate()
-        assert lathe is not None
+    run.set_wear_model(0.01, resharpen_after_m=0.2)
+    wear = run.get_pass_wear()
+    assert len(wear) == 10
+    assert run.resharpen_passes() == [4, 8]
+    assert [w["resharpen_before"] for w in wear].count(True) == 2
+    assert abs(wear[9]["width"] - (wear[0]["width"] + 0.01 * perimeter / 1000)) < 1e-12
 
+    with tempfile.TemporaryDirectory() as tmpdir:
+        svg_path = os.path.join(tmpdir, "worn.svg")
+        run.to_svg_worn(svg_path)
+        assert os.path.exists(svg_path)
 
-def test_rose_engine_lathe_run():
-    """Test multi-pass rose engine lathe run"""
-    import os
-    import tempfile
+    run.clear_wear_model()
+    assert run.get_pass_wear() == []
 
-    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun, RosettePattern
+    try:
+        run.set_wear_model(0.01, resharpen_after_m=0.0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
 
-    # Create a config
-    config = RoseEngineConfig(base_radius=20.0, amplitude=2.0)
-    config.set_rosette(RosettePattern.multi_lobe(12))
+    config = RoseEngineConfig.classic_multi_lobe(20.0, 12, 1.0)
+    run = RoseEngineLatheRun(config, CuttingBit.flat(0.5, 0.4), num_passes=4)
+    run.generate()
+    run.set_wear_model(0.1, resharpen_after_m=1.5 * run.cut_length() / 4 / 1000)
+    job = run.to_machine_job([(RosettePattern.multi_lobe(12), 0)])
+    assert [p["resharpen_before"] for p in job["passes"]] == [False, False, True, False]
 
-    # Create cutting bit
-    bit = CuttingBit.v_shaped(angle=30.0, width=0.5)
 
-    # Test creation
-    run = RoseEngineLatheRun(config, bit, num_passes=8)
-    assert run is not None
-    assert run.num_passes == 8
+def test_rose_engine_dash_by_phase():
+    """Test cutting pointillé passes as dots locked to the rosette phase"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun
 
-    # Test generation
+    config = RoseEngineConfig.classic_multi_lobe(20.0, 12, 1.0)
+    config.set_dash_by_phase(24, 0.5)
+    run = RoseEngineLatheRun(config, CuttingBit.flat(0.5, 0.4), num_passes=2, segments_per_pass=1)
     run.generate()
+    assert len(run.get_lines()) == 48
+    assert run.get_pass_indices().count(1) == 24
 
-    # Test SVG export
-    with tempfile.NamedTemporaryFile(mode="w", suffix=".svg", delete=False) as f:
-        svg_path = f.name
+    config.clear_dash_by_phase()
+    run = RoseEngineLatheRun(config, CuttingBit.flat(0.5, 0.4), num_passes=2, segments_per_pass=1)
+    run.generate()
+    assert len(run.get_lines()) == 2
 
     try:
-        run.to_svg(svg_path)
-        assert os.path.exists(svg_path)
-        assert os.path.getsize(svg_path) > 0
-    finally:
-        if os.path.exists(svg_path):
-            os.unlink(svg_path)
+        config.set_dash_by_phase(0, 0.5)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
 
 
-def test_rose_engine_lathe_run_patterns():
-    """Test multi-pass rose engine with different patterns"""
-    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun, RosettePattern
+def test_rose_engine_cylinder_surface():
+    """Test wrapping a rose engine pattern around a cylindrical case band"""
+    import math
+    import os
+    import tempfile
 
-    bit = CuttingBit.v_shaped(angle=30.0, width=0.1)
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe, RosettePattern
 
-    # Test different rosette patterns
-    patterns = [
-        RosettePattern.huit_eight(lobes=8),
-        RosettePattern.grain_de_riz(grain_size=1.0, rows=12),
-        RosettePattern.draperie(frequency=6.0, wave_exponent=1),
-        RosettePattern.diamant(divisions=12),
-    ]
+    config = RoseEngineConfig(3.0, 0.5)
+    config.set_rosette(RosettePattern.multi_lobe(12))
+    config.set_resolution(720)
+    config.set_cylinder(20.0, 5.0)
+    lathe = RoseEngineLathe(config, CuttingBit.v_shaped(angle=60.0, width=0.2))
+    lathe.generate()
 
-    for pattern in patterns:
-        config = RoseEngineConfig(base_radius=20.0, amplitude=2.0)
-        config.set_rosette(pattern)
+    center_line = lathe.get_lines_3d()[0]
+    assert all(abs(math.hypot(x, y) - 20.0) < 1e-9 for x, y, _ in center_line)
+    z = [p[2] for p in center_line]
+    assert abs(min(z) - 2.5) < 1e-9 and abs(max(z) - 3.5) < 1e-9
 
-        run = RoseEngineLatheRun(config, bit, num_passes=12)
-        run.generate()
-        assert run.num_passes == 12
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "band.stl")
+        lathe.to_stl_3d(path, depth=0.05)
+        assert os.path.getsize(path) > 84
+
+        tall = RoseEngineConfig(20.0, 0.5)
+        tall.set_cone(18.0, 20.0, 5.0)
+        overflow = RoseEngineLathe(tall, CuttingBit.v_shaped(angle=60.0, width=0.2))
+        overflow.generate()
+        try:
+            overflow.to_stl_3d(path)
+            assert False, "Should have raised ValueError for a pattern taller than the cone"
+        except ValueError:
+            pass
+
+    bad = RoseEngineConfig(3.0, 0.5)
+    bad.set_cylinder(0.0, 5.0)
+    try:
+        RoseEngineLathe(bad, CuttingBit.v_shaped(angle=60.0, width=0.2))
+        assert False, "Should have raised ValueError for zero cylinder radius"
+    except ValueError:
+        pass
 
 
 def test_limacon_layer():
@@ -414,6 +2512,81 @@ def test_limacon_matches_rose_engine():
             assert dist < 1e-10, f"Point {i},{j} differs: limacon=({lim_pt[0]}, {lim_pt[1]}), rose=({rose_pt[0]}, {rose_pt[1]}), dist={dist}"
 
 
+def test_rose_engine_pierced_cells():
+    """Clous de Paris diamonds inset into cuttable contours and DXF export"""
+    from turtles import RoseEngineLatheRun
+
+    run = RoseEngineLatheRun.clous_de_paris(spacing=2.0, radius=9.0)
+    run.generate()
+    cells = run.pierceable_cells(0.3, min_area=1.0)
+    assert len(cells) > 0
+    assert run.pierceable_cells(2.0) == []
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "pierced.dxf")
+        run.to_dxf_pierced(path, 0.3, min_area=1.0)
+        with open(path) as f:
+            assert f.read().count("LWPOLYLINE") == len(cells)
+
+        run.set_metadata("CDP-7", generator_version="bench")
+        assert run.metadata == {"design_id": "CDP-7", "generator_version": "bench"}
+        run.to_dxf_pierced(path, 0.3, min_area=1.0)
+        with open(path) as f:
+            assert f.read().startswith("999\ndesign_id: CDP-7\n999\ngenerator_version: bench\n")
+
+
+def test_dxf_export_of_lathe_runs_and_layers():
+    """Test DXF export of segmented lathe runs and single layers"""
+    from turtles import ClousDeParisLayer, DraperieLayer, PaonLayer, RoseEngineLatheRun
+
+    run = RoseEngineLatheRun.clous_de_paris(spacing=2.0, radius=9.0)
+    paon = PaonLayer(num_lines=20, radius=15.0)
+    draperie = DraperieLayer(num_rings=12, base_radius=15.0, resolution=200)
+    clous = ClousDeParisLayer(spacing=2.0, radius=10.0)
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "pattern.dxf")
+        for pattern in (run, paon, draperie, clous):
+            try:
+                pattern.to_dxf(path)
+                assert False, "Should have raised IOError before generate()"
+            except IOError as e:
+                assert "generate()" in str(e)
+            pattern.generate()
+            pattern.to_dxf(path)
+            with open(path) as f:
+                dxf = f.read()
+            drawable = [line for line in pattern.get_lines() if len(line) >= 2]
+            assert dxf.count("\nLWPOLYLINE\n") == len(drawable)
+            assert dxf.endswith("0\nEOF\n")
+
+
+def test_gcode_export():
+    """Test G-code export from a lathe and a multi-pass run"""
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLathe, RoseEngineLatheRun
+
+    config = RoseEngineConfig.classic_multi_lobe(20.0, 12, 1.0)
+    lathe = RoseEngineLathe(config, CuttingBit.flat(width=0.5, depth=0.2))
+    run = RoseEngineLatheRun.clous_de_paris(spacing=2.0, radius=9.0)
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "dial.nc")
+        for pattern in (lathe, run):
+            pattern.generate()
+            pattern.to_gcode(path, feed_rate=200.0, safe_z=1.5, depth_per_pass=0.05)
+            with open(path) as f:
+                gcode = f.read()
+            assert gcode.startswith("G21\nG90\nG17\nG0 Z1.5\nM3 S12000\n")
+            assert gcode.endswith("G0 Z1.5\nM5\nM30\n")
+            assert " F200\n" in gcode
+
+        try:
+            lathe.to_gcode(path, depth_per_pass=0.0)
+            assert False, "Should have raised ValueError for a zero step"
+        except ValueError as e:
+            assert "depth_per_pass" in str(e)
+
+
 def test_draperie_pattern_displacement():
     """Test that the draperie pattern can be created and generates output"""
     from turtles import DraperieLayer
@@ -439,6 +2612,54 @@ def test_draperie_svg_export():
         assert os.path.getsize(svg_path) > 0, "SVG file should have content"
 
 
+def test_streaming_svg_matches_generated():
+    """Test streaming SVG export writes the generated file without generating"""
+    from turtles import DraperieLayer, HuitEightLayer, PaonLayer
+
+    layers = [
+        DraperieLayer(num_rings=30, base_radius=15.0, resolution=200),
+        PaonLayer(num_lines=20, radius=15.0),
+        HuitEightLayer(num_curves=12, scale=10.0, resolution=200),
+    ]
+    with tempfile.TemporaryDirectory() as tmpdir:
+        streamed = os.path.join(tmpdir, "streamed.svg")
+        generated = os.path.join(tmpdir, "generated.svg")
+        for layer in layers:
+            layer.to_svg(streamed, streaming=True)
+            assert len(layer.get_lines()) == 0
+            layer.generate()
+            layer.to_svg(generated)
+            with open(streamed) as a, open(generated) as b:
+                assert a.read() == b.read()
+
+
+def test_svg_filled_draws_shaded_bands():
+    """Test gradient-shaded band export for draperie and flinque"""
+    from turtles import DraperieLayer
+    from turtles.turtles import FlinqueLayer
+
+    draperie = DraperieLayer(num_rings=30, base_radius=15.0, resolution=200)
+    draperie.generate()
+    flinque = FlinqueLayer(radius=20.0, num_petals=6, num_waves=8)
+    flinque.generate()
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        svg_path = os.path.join(tmpdir, "filled.svg")
+        draperie.to_svg_filled(svg_path, 0.5)
+        with open(svg_path) as f:
+            assert f.read().count('class="band"') == 29
+
+        flinque.to_svg_filled(svg_path, 0.5)
+        with open(svg_path) as f:
+            assert f.read().count('class="band"') == len(flinque.get_lines()) - 1
+
+        try:
+            draperie.to_svg_filled(svg_path, float("nan"))
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+
 def test_draperie_multi_pass_creates_wavey_circles():
     """Test that DraperieLayer creates concentric non-overlapping rings"""
     import math
@@ -566,6 +2787,35 @@ def test_draperie_phase_exponent():
     assert differs, "Smooth and sharp draperie should produce different coordinates"
 
 
+def test_draperie_affects_geometry():
+    """Inert parameter edits are reported and leave the geometry hash unchanged"""
+    from turtles import DraperieLayer
+
+    # Dome mode (circular_phase > 0) ignores phase_exponent
+    layer = DraperieLayer(num_rings=20, base_radius=10.0, resolution=100, phase_exponent=3)
+    other = DraperieLayer(num_rings=20, base_radius=10.0, resolution=100, phase_exponent=1)
+    assert not layer.affects_geometry("phase_exponent")
+    assert layer.affects_geometry("radius_step")
+    assert layer.effective_amplitude > 0.0
+    layer.generate()
+    other.generate()
+    assert layer.geometry_hash() == other.geometry_hash()
+
+    sharp = DraperieLayer(num_rings=20, base_radius=10.0, resolution=100, circular_phase=0.0)
+    assert sharp.affects_geometry("phase_exponent")
+
+    # Phase-rotation runs (radius_step == 0) ignore the phase envelope
+    from turtles import CuttingBit, RoseEngineConfig, RoseEngineLatheRun
+
+    config = RoseEngineConfig(base_radius=10.0, amplitude=0.5)
+    run = RoseEngineLatheRun(config, CuttingBit.v_shaped(angle=30.0, width=0.5), num_passes=6)
+    assert not run.affects_geometry("phase_shift")
+    assert not run.affects_geometry("max_points_per_path")
+    assert run.affects_geometry("num_passes")
+    run.generate()
+    assert run.geometry_hash() != 0
+
+
 def test_draperie_wave_exponent():
     """Test that wave_exponent is configurable and produces different output"""
     from turtles import DraperieLayer
@@ -595,6 +2845,69 @@ def test_draperie_wave_exponent():
     assert differs, "Normal and soft-wave draperie should produce different coordinates"
 
 
+def test_draperie_non_integer_frequency():
+    """Test closing rings with a non-integer wave frequency"""
+    from turtles import DraperieLayer
+
+    layer = DraperieLayer(num_rings=12, base_radius=15.0, wave_frequency=11.5, resolution=120)
+    assert "11.5" in layer.normalization_report()
+    layer.generate()
+    assert all(len(ring) == 121 for ring in layer.get_lines())
+
+    layer.set_non_integer_mode("multi_revolution")
+    assert layer.normalization_report() is None
+    layer.generate()
+    for ring in layer.get_lines():
+        assert len(ring) == 241
+        assert ring[0] == ring[-1]
+
+    try:
+        layer.set_non_integer_mode("spiral")
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
+def test_layer_jitter_is_seeded():
+    """Test that jitter reproduces with its seed and changes with another"""
+    from turtles import CuttingBit, DraperieLayer, FlinqueLayer, PaonLayer, RoseEngineConfig, RoseEngineLathe
+
+    def lines(layer, seed):
+        layer.set_jitter(0.05, 0.01, seed)
+        layer.generate()
+        return layer.get_lines()
+
+    for layer in [
+        DraperieLayer(num_rings=12, base_radius=15.0, resolution=120),
+        FlinqueLayer(radius=20.0, num_petals=8, num_waves=10),
+        PaonLayer(num_lines=20, radius=15.0),
+    ]:
+        assert layer.jitter is None
+        first = lines(layer, 1)
+        assert layer.jitter == (0.05, 0.01, 1)
+        assert lines(layer, 1) == first
+        assert lines(layer, 2) != first
+        layer.clear_jitter()
+        layer.generate()
+        assert layer.get_lines() != first
+
+        try:
+            layer.set_jitter(-0.1)
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+    def path(seed):
+        config = RoseEngineConfig(base_radius=20.0, amplitude=1.0)
+        config.set_jitter(0.05, seed=seed)
+        lathe = RoseEngineLathe(config, CuttingBit.v_shaped(angle=30.0, width=0.5))
+        lathe.generate()
+        return lathe.get_tool_path()
+
+    assert path(5) == path(5)
+    assert path(5) != path(6)
+
+
 def test_draperie_matches_rose_engine():
     """Test that the mathematical DraperieLayer and rose engine RoseEngineLatheRun.draperie() produce identical output"""
     from turtles import DraperieLayer, RoseEngineLatheRun
@@ -663,6 +2976,24 @@ def test_paon_layer():
     assert len(lines) > 0, "PaonLayer should produce lines"
 
 
+def test_paon_invalid_physical_params():
+    """Test that paon inputs with hard physical constraints are rejected"""
+    from turtles import PaonLayer
+
+    for kwargs in (
+        {"vanishing_point": 0.0},
+        {"vanishing_point": -0.5},
+        {"fan_angle": -3.0},
+        {"phase_rate": -1.0},
+        {"wave_frequency": 0.0},
+    ):
+        try:
+            PaonLayer(**kwargs)
+            assert False, f"Should have raised ValueError for {kwargs}"
+        except ValueError:
+            pass
+
+
 def test_paon_svg_export():
     """Test creating a paon pattern and exporting to SVG"""
     from turtles import PaonLayer
@@ -789,6 +3120,99 @@ def test_paon_matches_rose_engine():
             assert dist < 1e-10, f"Line {i}, point {j}: math=({mp[0]:.6f},{mp[1]:.6f}), rose=({rp[0]:.6f},{rp[1]:.6f}), dist={dist}"
 
 
+def test_paon_origin_and_asymmetry():
+    """Test turning and skewing the paon fan, matched by the rose engine"""
+    import math
+
+    from turtles import PaonLayer, RoseEngineLatheRun
+
+    layer = PaonLayer(num_lines=40, radius=15.0, resolution=200)
+    layer.generate()
+    bottom = layer.get_lines()
+    layer.set_origin("left")
+    assert math.isclose(layer.origin_angle, math.pi / 2)
+    layer.generate()
+    for line, original in zip(layer.get_lines(), bottom):
+        for (x, y), (ox, oy) in zip(line, original):
+            assert abs(x + oy) < 1e-9 and abs(y - ox) < 1e-9
+
+    layer.set_origin(1.0)
+    layer.set_asymmetry(0.4)
+    assert layer.asymmetry == 0.4
+    for bad in (lambda: layer.set_origin("north"), lambda: layer.set_asymmetry(1.0)):
+        try:
+            bad()
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+    layer.generate()
+    rose_run = RoseEngineLatheRun.paon(num_lines=40, radius=15.0, resolution=200, origin=1.0, asymmetry=0.4)
+    rose_run.generate()
+    assert layer.get_lines() == rose_run.get_lines()
+
+    wf = WatchFace(radius=38.0)
+    wf.add_paon(num_lines=50, radius=15.0, resolution=200, origin="top", asymmetry=-0.2)
+    wf.generate()
+
+
+def test_watch_face_hands():
+    """Test drawing watch hands over the dial and keeping them out of exports"""
+    wf = WatchFace(radius=38.0)
+    wf.add_inner()
+    wf.add_hands(10, 15, 30, hour_hand={"style": "breguet"}, second_hand={"color": "#0000ff"})
+    hour, minute, second = wf.get_hand_outlines()
+    assert len(hour) == 2 and len(minute) == 1 and len(second) == 1
+    for outline in hour + minute + second:
+        assert outline[0] == outline[-1]
+    tip = max(minute[0], key=lambda p: p[0] ** 2 + p[1] ** 2)
+    assert abs(tip[0] - 0.75 * 38.0) < 1e-9 and abs(tip[1]) < 1e-9
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = os.path.join(tmpdir, "hands.svg")
+        wf.to_svg(path)
+        with open(path) as f:
+            svg = f.read()
+    assert 'id="hand-second"' in svg and "#0000ff" in svg
+
+    for bad in (
+        lambda: wf.add_hands(10, 60),
+        lambda: wf.add_hands(10, 0, hour_hand={"style": "cathedral"}),
+        lambda: wf.add_hands(10, 0, minute_hand={"length": 0.5}),
+    ):
+        try:
+            bad()
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+    wf.set_hands_in_exports(True)
+    wf.clear_hands()
+    assert wf.get_hand_outlines() == []
+
+
+def test_rose_engine_parallel_traces():
+    """Test repeating each lathe run line as parallel offset traces"""
+    import math
+
+    from turtles import RoseEngineLatheRun
+
+    run = RoseEngineLatheRun.diamant(num_circles=6, circle_radius=10.0, resolution=180)
+    run.generate()
+    single = run.get_lines()
+    run.with_parallel_traces(3, 0.2)
+    run.generate()
+    lines = run.get_lines()
+    assert len(lines) == 3 * len(single)
+    assert lines[1] == single[0]
+    for outer, inner in zip(lines[0], lines[2]):
+        assert math.isclose(math.dist(outer, inner), 0.4, abs_tol=1e-6)
+    for n, spacing in ((0, 0.2), (3, 0.0)):
+        try:
+            run.with_parallel_traces(n, spacing)
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+
 def test_diamant_matches_rose_engine():
     """Test that mathematical DiamantLayer and RoseEngineLatheRun.diamant() produce identical output"""
     from turtles import RoseEngineLatheRun
@@ -822,6 +3246,29 @@ def test_diamant_matches_rose_engine():
             assert dist < 1e-10, f"Circle {i}, point {j}: math=({mp[0]:.6f},{mp[1]:.6f}), rose=({rp[0]:.6f},{rp[1]:.6f}), dist={dist}"
 
 
+def test_diamant_rings():
+    """Test multi-ring diamant layers and their rose engine equivalent"""
+    from turtles import RoseEngineLatheRun
+    from turtles.turtles import DiamantLayer
+
+    rings = dict(num_rings=3, ring_spacing=4.0, scale_per_ring=0.75, stagger=True)
+    layer = DiamantLayer(num_circles=9, circle_radius=6.0, resolution=90, **rings)
+    assert (layer.num_rings, layer.ring_spacing, layer.scale_per_ring, layer.stagger) == (3, 4.0, 0.75, True)
+    layer.generate()
+    assert len(layer.get_lines()) == 27
+
+    rose_run = RoseEngineLatheRun.diamant(num_circles=9, circle_radius=6.0, resolution=90, **rings)
+    rose_run.generate()
+    assert rose_run.get_lines() == layer.get_lines()
+
+    assert DiamantLayer(num_circles=9, circle_radius=6.0).num_rings == 1
+    try:
+        DiamantLayer(num_circles=9, circle_radius=6.0, num_rings=0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
 def test_flinque_matches_rose_engine():
     """Test that mathematical FlinqueLayer and RoseEngineLatheRun.flinque() produce identical output"""
     from turtles import RoseEngineLatheRun
@@ -868,6 +3315,38 @@ def test_flinque_matches_rose_engine():
             assert dist < 1e-10, f"Ring {i}, point {j}: math=({mp[0]:.6f},{mp[1]:.6f}), rose=({rp[0]:.6f},{rp[1]:.6f}), dist={dist}"
 
 
+def test_flinque_herringbone_matches_rose_engine():
+    """Test that petal phase steps and alternating rings match between FlinqueLayer and the rose engine run"""
+    import math
+
+    from turtles import RoseEngineLatheRun
+    from turtles.turtles import FlinqueLayer
+
+    kwargs = dict(radius=10.0, num_petals=6, num_waves=10, wave_amplitude=0.5, wave_frequency=10.0)
+
+    plain = FlinqueLayer(**kwargs)
+    plain.generate()
+
+    math_layer = FlinqueLayer(**kwargs, petal_phase_step=math.pi / 6, alternate=True)
+    math_layer.generate()
+
+    rose_run = RoseEngineLatheRun.flinque(**kwargs, petal_phase_step=math.pi / 6, alternate=True)
+    rose_run.generate()
+
+    math_lines = math_layer.get_lines()
+    rose_lines = rose_run.get_lines()
+    assert len(math_lines) == len(rose_lines)
+    for ml, rl in zip(math_lines, rose_lines):
+        assert len(ml) == len(rl)
+        for mp, rp in zip(ml, rl):
+            assert abs(mp[0] - rp[0]) < 1e-10 and abs(mp[1] - rp[1]) < 1e-10
+
+    # Ring 0 has no phase offset and is not flipped; later rings differ
+    plain_lines = plain.get_lines()
+    assert plain_lines[0] == math_lines[0]
+    assert plain_lines[1] != math_lines[1]
+
+
 # ── Huit-Eight (Figure-Eight) tests ─────────────────────────────────────
 
 
@@ -1126,6 +3605,39 @@ def test_limacon_matches_rose_engine_via_limacon():
             assert dist < 1e-10, f"Point {i},{j} differs: dist={dist}"
 
 
+def test_limacon_loop_modes():
+    """Test trimming the limaçon inner loop and the cardioid preset"""
+    import math
+
+    from turtles import LimaconLayer, RoseEngineLatheRun
+
+    outer = LimaconLayer(num_curves=1, base_radius=6.0, amplitude=10.0, loop_mode="outer_only")
+    assert outer.loop_mode == "outer_only"
+    outer.generate()
+    inner = LimaconLayer(num_curves=1, base_radius=6.0, amplitude=10.0, loop_mode="inner_only")
+    inner.generate()
+    assert all(math.hypot(x, y) <= 4.0 + 1e-9 for line in inner.get_lines() for x, y in line)
+    assert outer.get_lines()[0][0] == (0.0, 0.0)
+
+    rose_run = RoseEngineLatheRun.limacon(num_curves=1, base_radius=6.0, amplitude=10.0, loop_mode="outer_only")
+    rose_run.generate()
+    assert len(rose_run.get_lines()) == len(outer.get_lines())
+
+    cardioid = LimaconLayer.cardioid(8, 12.0)
+    assert cardioid.base_radius == cardioid.amplitude == 12.0
+
+    for bad in ({"loop_mode": "middle"}, {"base_radius": 12.0, "amplitude": 12.0, "loop_mode": "inner_only"}):
+        try:
+            LimaconLayer(**{"num_curves": 4, "base_radius": 6.0, "amplitude": 10.0, **bad})
+            assert False, "Should have raised ValueError"
+        except ValueError:
+            pass
+
+    face = WatchFace(radius=38.0)
+    face.add_limacon(num_curves=12, base_radius=10.0, amplitude=16.0, loop_mode="outer_only")
+    face.generate()
+
+
 # ── Clous de Paris (Hobnail) tests ──────────────────────────────────────
 
 
@@ -1142,6 +3654,44 @@ def test_clous_de_paris_layer():
     assert len(lines) > 0, "ClousDeParisLayer should produce lines"
 
 
+def test_clous_de_paris_cell_centers():
+    """Test hobnail cell centers and their corner quads"""
+    import math
+
+    from turtles import ClousDeParisLayer
+
+    layer = ClousDeParisLayer(spacing=2.0, radius=2.9, angle=0.0)
+    centers = layer.cell_centers()
+    assert sorted(centers) == [(-1.0, -1.0), (-1.0, 1.0), (1.0, -1.0), (1.0, 1.0)]
+    quads = layer.cell_polygons()
+    assert len(quads) == 4 and all(len(quad) == 4 for quad in quads)
+
+    # Partial cells at the edge are left out
+    layer = ClousDeParisLayer(spacing=1.0, radius=10.0)
+    for quad in layer.cell_polygons():
+        assert all(math.hypot(x, y) <= 10.0 + 1e-9 for x, y in quad)
+
+
+def test_clous_de_paris_cells():
+    """Test closed hobnail cells clipped to the circle"""
+    import math
+
+    from turtles import ClousDeParisLayer
+
+    layer = ClousDeParisLayer(spacing=2.0, radius=10.0, emit_cells=True)
+    assert layer.emit_cells
+    cells = layer.cells()
+    whole = len(layer.cell_centers())
+    assert len(cells) > whole
+    assert all(len(cell) == 5 for cell in cells[:whole])
+    for cell in cells:
+        assert cell[0] == cell[-1]
+        assert all(math.hypot(x, y) <= 10.0 + 1e-6 for x, y in cell)
+
+    layer.generate()
+    assert len(layer.get_lines()) == len(cells)
+
+
 def test_clous_de_paris_svg_export():
     """Test creating a clous de Paris pattern and exporting to SVG"""
     from turtles import ClousDeParisLayer
@@ -1180,6 +3730,33 @@ def test_clous_de_paris_layer_at_clock():
     assert len(lines) > 0
 
 
+def test_huiteight_and_clous_de_paris_layers_on_watch_face():
+    """Test composing pre-built huit-eight and clous de Paris layers onto a dial"""
+    import math
+
+    from turtles import ClousDeParisLayer, HuitEightLayer
+
+    huit = HuitEightLayer.at_polar(12, 8.0, math.pi / 2, 12.0, resolution=200)
+    assert abs(huit.center_x) < 1e-9 and abs(huit.center_y - 12.0) < 1e-9
+    clous = ClousDeParisLayer.at_polar(0.0, 10.0, spacing=2.0, radius=8.0)
+    assert abs(clous.center_x - 10.0) < 1e-9 and abs(clous.center_y) < 1e-9
+    for layer in (huit, clous):
+        layer.generate()
+        assert all(isinstance(p, tuple) and len(p) == 2 for line in layer.get_lines() for p in line)
+
+    wf = WatchFace(radius=30.0)
+    wf.add_huiteight_layer(huit)
+    wf.add_clous_de_paris_layer(clous)
+    wf.generate()
+    assert len(wf.get_lines()) == len(huit.get_lines()) + len(clous.get_lines())
+
+    with tempfile.TemporaryDirectory() as tmp:
+        for layer, name in ((huit, "huit.svg"), (clous, "clous.svg")):
+            path = os.path.join(tmp, name)
+            layer.to_svg(path)
+            assert os.path.getsize(path) > 0
+
+
 def test_clous_de_paris_lines_within_circle():
     """Test that all clous de Paris points are within the radius circle"""
     import math
@@ -1600,3 +4177,58 @@ def test_cube_invalid_params():
         assert False, "Should have raised ValueError for negative radius"
     except ValueError:
         pass
+
+
+def test_layer_stats():
+    """Test stats() on layers and spirographs before and after generating"""
+    import math
+
+    from turtles import DiamantLayer, LimaconLayer
+
+    layer = DiamantLayer(num_circles=12, circle_radius=10.0, resolution=36)
+    assert layer.stats() is None
+    layer.generate()
+    stats = layer.stats()
+    assert stats["num_polylines"] == 12
+    assert stats["num_points"] == 12 * 37
+    perimeter = 36 * 2 * 10.0 * math.sin(math.pi / 36)
+    assert abs(stats["total_path_length"] - 12 * perimeter) < 1e-9
+    xs = [x for line in layer.get_lines() for x, _ in line]
+    ys = [y for line in layer.get_lines() for _, y in line]
+    assert stats["min"] == (min(xs), min(ys))
+    assert stats["max"] == (max(xs), max(ys))
+
+    limacon = LimaconLayer(num_curves=8, base_radius=10.0, amplitude=3.0, resolution=180)
+    limacon.generate()
+    assert limacon.stats()["num_polylines"] == len(limacon.get_lines())
+
+    spiro = HorizontalSpirograph(38.0, 0.75, 20.0, 3, 360)
+    assert spiro.stats() is None
+    spiro.generate()
+    stats = spiro.stats()
+    assert stats["num_polylines"] == 1
+    assert stats["total_path_length"] > 0.0
+    assert stats["max"][0] - stats["min"][0] <= 2 * 38.0
+
+
+def test_watch_face_json_round_trip():
+    """Test saving a multi-layer face as JSON and reading it back"""
+    face = WatchFace(radius=38.0)
+    face.add_inner()
+    face.add_outer()
+    face.add_center_hole()
+    face.add_draperie(num_rings=12, resolution=360)
+    face.add_soleil(num_rays=60, outer_radius=30.0, curvature=0.02)
+    json_str = face.to_json()
+
+    copy = WatchFace.from_json(json_str)
+    assert copy.to_json() == json_str
+    face.generate()
+    copy.generate()
+    assert copy.get_lines() == face.get_lines()
+
+    try:
+        WatchFace.from_json("{")
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass